@@ -0,0 +1,77 @@
+//! measures staging a large change like a vendored dependency update:
+//! once a file at a time, each writing the index like we used to, and
+//! all at once with a single index write.
+//!
+//! run with `cargo bench -p asyncgit`
+
+use asyncgit::sync::{self, RepoPath};
+use git2::Repository;
+use std::{
+	fs,
+	path::Path,
+	time::{Duration, Instant},
+};
+use tempfile::TempDir;
+
+const FILES: usize = 5_000;
+
+fn setup() -> (TempDir, RepoPath, Vec<String>) {
+	let td = TempDir::new().expect("temp dir");
+	let repo = Repository::init(td.path()).expect("repo init");
+
+	let mut config = repo.config().expect("config");
+	config.set_str("user.name", "name").expect("user.name");
+	config.set_str("user.email", "email").expect("user.email");
+
+	let paths = (0..FILES)
+		.map(|i| format!("vendor/dep{}/src/file{i}.rs", i % 50))
+		.collect::<Vec<_>>();
+
+	for path in &paths {
+		let path = td.path().join(path);
+		fs::create_dir_all(path.parent().expect("parent"))
+			.expect("create dir");
+		fs::write(&path, format!("// {}\n", path.display()))
+			.expect("write file");
+	}
+
+	let repo_path = td.path().to_str().expect("utf8 path").into();
+
+	(td, repo_path, paths)
+}
+
+fn unstage(repo_path: &RepoPath, paths: &[String]) {
+	sync::stage_all_paths(repo_path, paths, false, |_, _| true)
+		.expect("unstage");
+}
+
+fn one_by_one(repo_path: &RepoPath, paths: &[String]) -> Duration {
+	let start = Instant::now();
+	for path in paths {
+		sync::stage_add_file(repo_path, Path::new(path))
+			.expect("stage file");
+	}
+	start.elapsed()
+}
+
+fn all_at_once(repo_path: &RepoPath, paths: &[String]) -> Duration {
+	let start = Instant::now();
+	sync::stage_all_paths(repo_path, paths, true, |_, _| true)
+		.expect("stage paths");
+	start.elapsed()
+}
+
+fn main() {
+	let (_td, repo_path, paths) = setup();
+
+	// batched goes first so the blobs it writes do not make it look
+	// faster than it is
+	let batched = all_at_once(&repo_path, &paths);
+	unstage(&repo_path, &paths);
+	let previous = one_by_one(&repo_path, &paths);
+
+	println!(
+		"stage {FILES} files: {previous:?} one by one, {batched:?} at once ({:.1}x)",
+		previous.as_secs_f64() / batched.as_secs_f64().max(f64::EPSILON)
+	);
+}
@@ -0,0 +1,71 @@
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{compare_branch_with_head, BranchComparison, RepoPath},
+	AsyncGitNotification,
+};
+use std::sync::{Arc, Mutex};
+
+enum JobState {
+	Request { branch_ref: String, repo: RepoPath },
+	Response(Result<BranchComparison>),
+}
+
+/// finds the merge base of `HEAD` and a branch and walks both sides
+#[derive(Clone, Default)]
+pub struct AsyncBranchCompareJob {
+	state: Arc<Mutex<Option<JobState>>>,
+}
+
+///
+impl AsyncBranchCompareJob {
+	///
+	pub fn new(repo: RepoPath, branch_ref: String) -> Self {
+		Self {
+			state: Arc::new(Mutex::new(Some(JobState::Request {
+				branch_ref,
+				repo,
+			}))),
+		}
+	}
+
+	///
+	pub fn result(&self) -> Option<Result<BranchComparison>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request { .. } => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncBranchCompareJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request { branch_ref, repo } => {
+					JobState::Response(compare_branch_with_head(
+						&repo,
+						&branch_ref,
+					))
+				}
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::BranchCompare)
+	}
+}
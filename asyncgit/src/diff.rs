@@ -2,8 +2,10 @@ use crate::{
 	error::Result,
 	hash,
 	sync::{
-		self, commit_files::OldNew, diff::DiffOptions, CommitId,
-		RepoPath,
+		self,
+		commit_files::{OldNew, TreeSource},
+		diff::DiffOptions,
+		CommitId, RepoPath,
 	},
 	AsyncGitNotification, FileDiff,
 };
@@ -25,8 +27,12 @@ pub enum DiffType {
 	Commit(CommitId),
 	/// diff against staged file
 	Stage,
+	/// diff of everything staged (`path` is ignored)
+	StageAll,
 	/// diff against file in workdir
 	WorkDir,
+	/// diff two trees, commits or the workdir
+	TreeToTree(OldNew<TreeSource>),
 }
 
 ///
@@ -76,7 +82,22 @@ impl AsyncDiff {
 	pub fn last(&self) -> Result<Option<(DiffParams, FileDiff)>> {
 		let last = self.last.lock()?;
 
-		Ok(last.clone().map(|res| (res.params, res.result)))
+		Ok(last
+			.clone()
+			.filter(|res| !self.is_outdated(&res.result))
+			.map(|res| (res.params, res.result)))
+	}
+
+	/// a diff missing objects is outdated once they got fetched
+	fn is_outdated(&self, diff: &FileDiff) -> bool {
+		!diff.missing_objects.is_empty()
+			&& sync::missing_objects(
+				&self.repo,
+				&diff.missing_objects,
+			)
+			.is_ok_and(|missing| {
+				missing.len() < diff.missing_objects.len()
+			})
 	}
 
 	///
@@ -105,7 +126,12 @@ impl AsyncDiff {
 		{
 			let mut current = self.current.lock()?;
 
-			if current.0 == hash {
+			if current.0 == hash
+				&& !current
+					.1
+					.as_ref()
+					.is_some_and(|diff| self.is_outdated(diff))
+			{
 				return Ok(current.1.clone());
 			}
 
@@ -168,6 +194,10 @@ impl AsyncDiff {
 				true,
 				Some(params.options),
 			)?,
+			DiffType::StageAll => sync::diff::get_diff_stage_all(
+				repo_path,
+				Some(params.options),
+			)?,
 			DiffType::WorkDir => sync::diff::get_diff(
 				repo_path,
 				&params.path,
@@ -186,6 +216,14 @@ impl AsyncDiff {
 				params.path.clone(),
 				Some(params.options),
 			)?,
+			DiffType::TreeToTree(trees) => {
+				sync::diff::get_diff_trees(
+					repo_path,
+					trees,
+					params.path.clone(),
+					Some(params.options),
+				)?
+			}
 		};
 
 		let mut notify = false;
@@ -19,6 +19,10 @@ pub enum Error {
 	#[error("git: conflict during rebase")]
 	RebaseConflict,
 
+	///
+	#[error("the rebase is done, but the uncommitted changes it stashed conflict with it: they are kept in the stash list")]
+	AutostashConflict,
+
 	///
 	#[error("git: remote url not found")]
 	UnknownRemote,
@@ -35,13 +39,29 @@ pub enum Error {
 	#[error("git: uncommitted changes")]
 	UncommittedChanges,
 
+	///
+	#[error("git: the repository uses {0} which gitui can\u{2019}t write without losing information, use git to change the index")]
+	UnsupportedIndex(String),
+
 	///
 	#[error("git: can\u{2019}t run blame on a binary file")]
 	NoBlameOnBinaryFile,
 
 	///
-	#[error("binary file")]
-	BinaryFile,
+	#[error("git: can\u{2019}t run blame on a file that is not part of the revision")]
+	NoBlameOnUntrackedFile,
+
+	///
+	#[error("git: the history of this shallow clone ends before that commit, deepen it in the log to get there")]
+	ShallowHistory,
+
+	///
+	#[error("credentials prompt cancelled")]
+	CredentialsCancelled,
+
+	///
+	#[error("credentials prompt timed out")]
+	CredentialsTimeout,
 
 	///
 	#[error("io error:{0}")]
@@ -79,6 +99,10 @@ pub enum Error {
 	#[error("not on a branch")]
 	NoBranch,
 
+	///
+	#[error("regex error: {0}")]
+	Regex(#[from] regex::Error),
+
 	///
 	#[error("rayon error: {0}")]
 	ThreadPool(#[from] rayon_core::ThreadPoolBuildError),
@@ -172,6 +196,44 @@ pub enum Error {
 	///
 	#[error("reword error: config commit.gpgsign=true detected.\ngpg signing is not supported for rewording commits with staged changes\ntry unstaging or stashing your changes")]
 	SignRewordLastCommitStaged,
+
+	///
+	#[error("reword error: the commit is or has a merge commit on top of it, rewording it would flatten the merge")]
+	RewordMergeCommit,
+
+	///
+	#[error("undo commit error: there is no commit before the initial commit to go back to")]
+	UndoInitialCommit,
+
+	///
+	#[error("undo commit error: `HEAD` is a merge commit, undoing it would stage all changes of the merged branch")]
+	UndoMergeCommit,
+
+	///
+	#[error("there are merge commits on top of the commit, the rebase would flatten them")]
+	RebaseMergeCommit,
+
+	///
+	#[error(
+		"git: gitui can\u{2019}t run `{0}` steps, continue with git"
+	)]
+	SequencerStep(String),
+
+	/// the remote ref is not where its remote-tracking ref says
+	#[error("push to '{0}' rejected: stale info, the remote ref moved since it was last fetched, fetch first")]
+	PushStaleInfo(String),
+
+	///
+	#[error("push to '{0}' needs a force push without lease: there is no remote-tracking ref to check the remote ref against")]
+	PushNoLease(String),
+
+	///
+	#[error("push authentication failed: {0}")]
+	PushAuth(String),
+
+	/// the remote has a tag of that name pointing somewhere else
+	#[error("tag '{0}' exists on the remote with another target, only a force push replaces it")]
+	PushTagExists(String),
 }
 
 ///
@@ -3,16 +3,23 @@
 use crate::{
 	asyncjob::{AsyncJob, RunParams},
 	error::Result,
-	sync::remotes::fetch_all,
+	remote_progress::RemoteProgress,
+	sync::remotes::{
+		fetch_all, push::ProgressNotification, CredPrompt,
+	},
 	sync::{cred::BasicAuthCredential, RepoPath},
-	AsyncGitNotification, ProgressPercent,
+	AsyncGitNotification,
+};
+use crossbeam_channel::unbounded;
+use std::{
+	sync::{Arc, Mutex},
+	thread,
+	time::Instant,
 };
-
-use std::sync::{Arc, Mutex};
 
 enum JobState {
 	Request(Option<BasicAuthCredential>),
-	Response(Result<()>),
+	Response(Result<Vec<String>>),
 }
 
 ///
@@ -20,48 +27,118 @@ enum JobState {
 pub struct AsyncFetchJob {
 	state: Arc<Mutex<Option<JobState>>>,
 	repo: RepoPath,
+	cred_prompt: Option<CredPrompt>,
 }
 
 ///
 impl AsyncFetchJob {
-	///
+	/// `cred_prompt` is asked for new credentials whenever a remote
+	/// rejects them
 	pub fn new(
 		repo: RepoPath,
 		basic_credential: Option<BasicAuthCredential>,
+		cred_prompt: Option<CredPrompt>,
 	) -> Self {
 		Self {
 			repo,
 			state: Arc::new(Mutex::new(Some(JobState::Request(
 				basic_credential,
 			)))),
+			cred_prompt,
+		}
+	}
+
+	/// the remote-tracking branches that were pruned
+	pub fn result(&self) -> Option<Result<Vec<String>>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request(_) => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+
+	/// fetches all remotes while a second thread publishes the
+	/// transfer progress (throttled like push and pull do)
+	fn fetch(
+		&self,
+		basic_credentials: Option<&BasicAuthCredential>,
+		params: &RunParams<
+			AsyncGitNotification,
+			Option<RemoteProgress>,
+		>,
+	) -> Result<Vec<String>> {
+		params.set_progress(None)?;
+		if let Some(cred_prompt) = &self.cred_prompt {
+			cred_prompt.reset()?;
 		}
+
+		let (progress_sender, receiver) = unbounded();
+
+		thread::scope(|scope| {
+			scope.spawn(move || {
+				RemoteProgress::forward_progress(
+					&receiver,
+					Instant::now,
+					|update: &ProgressNotification| {
+						if let Err(e) = params
+							.set_progress(Some(update.clone().into()))
+						{
+							log::error!(
+								"fetch progress error: {}",
+								e
+							);
+						}
+					},
+					|| {
+						if let Err(e) =
+							params.send(AsyncGitNotification::Fetch)
+						{
+							log::error!("fetch notify error: {}", e);
+						}
+					},
+				);
+			});
+
+			let result = fetch_all(
+				&self.repo,
+				&basic_credentials.cloned(),
+				&Some(progress_sender.clone()),
+				self.cred_prompt.as_ref(),
+			);
+
+			progress_sender.send(ProgressNotification::Done)?;
+
+			result
+		})
 	}
 }
 
 impl AsyncJob for AsyncFetchJob {
 	type Notification = AsyncGitNotification;
-	type Progress = ProgressPercent;
+	type Progress = Option<RemoteProgress>;
 
 	fn run(
 		&mut self,
-		_params: RunParams<Self::Notification, Self::Progress>,
+		params: RunParams<Self::Notification, Self::Progress>,
 	) -> Result<Self::Notification> {
 		if let Ok(mut state) = self.state.lock() {
-			*state = state.take().map(|state| match state {
-				JobState::Request(basic_credentials) => {
-					//TODO: support progress
-					let result = fetch_all(
-						&self.repo,
-						&basic_credentials,
-						&None,
-					);
-
-					JobState::Response(result)
-				}
-				JobState::Response(result) => {
-					JobState::Response(result)
-				}
-			});
+			*state =
+				state.take().map(|state| match state {
+					JobState::Request(basic_credentials) => {
+						JobState::Response(self.fetch(
+							basic_credentials.as_ref(),
+							&params,
+						))
+					}
+					JobState::Response(result) => {
+						JobState::Response(result)
+					}
+				});
 		}
 
 		Ok(AsyncGitNotification::Fetch)
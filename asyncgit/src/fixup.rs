@@ -0,0 +1,67 @@
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{fixup_targets, FixupTargets, RepoPath},
+	AsyncGitNotification,
+};
+use std::sync::{Arc, Mutex};
+
+enum JobState {
+	Request(RepoPath),
+	Response(Result<FixupTargets>),
+}
+
+/// finds the commits the staged hunks belong to
+#[derive(Clone)]
+pub struct AsyncFixupJob {
+	state: Arc<Mutex<Option<JobState>>>,
+}
+
+///
+impl AsyncFixupJob {
+	///
+	pub fn new(repo: RepoPath) -> Self {
+		Self {
+			state: Arc::new(Mutex::new(Some(JobState::Request(
+				repo,
+			)))),
+		}
+	}
+
+	///
+	pub fn result(&self) -> Option<Result<FixupTargets>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request(_) => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncFixupJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request(repo) => {
+					JobState::Response(fixup_targets(&repo))
+				}
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::Fixup)
+	}
+}
@@ -0,0 +1,226 @@
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{grep_tree, CommitId, GrepHit, GrepQuery, RepoPath},
+	AsyncGitNotification, ProgressPercent,
+};
+use std::{
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc, Mutex,
+	},
+	time::{Duration, Instant},
+};
+
+///
+pub struct GrepResult {
+	/// search stopped after reaching the maximum amount of hits
+	pub truncated: bool,
+	///
+	pub duration: Duration,
+}
+
+enum JobState {
+	Request {
+		repo_path: RepoPath,
+		commit: CommitId,
+		query: GrepQuery,
+	},
+	Response(Result<GrepResult>),
+}
+
+/// searches file contents, hits become available via `hits` while
+/// the search is still running
+#[derive(Clone)]
+pub struct AsyncGrepJob {
+	state: Arc<Mutex<Option<JobState>>>,
+	hits: Arc<Mutex<Vec<GrepHit>>>,
+	max_hits: usize,
+	cancellation_flag: Arc<AtomicBool>,
+}
+
+///
+impl AsyncGrepJob {
+	///
+	pub fn new(
+		repo_path: RepoPath,
+		commit: CommitId,
+		query: GrepQuery,
+		max_hits: usize,
+	) -> Self {
+		Self {
+			state: Arc::new(Mutex::new(Some(JobState::Request {
+				repo_path,
+				commit,
+				query,
+			}))),
+			hits: Arc::default(),
+			max_hits,
+			cancellation_flag: Arc::default(),
+		}
+	}
+
+	/// hits found so far, skipping the first `skip`
+	pub fn hits(&self, skip: usize) -> Vec<GrepHit> {
+		self.hits
+			.lock()
+			.map(|hits| hits.iter().skip(skip).cloned().collect())
+			.unwrap_or_default()
+	}
+
+	/// stops a running search after the current file
+	pub fn cancel(&self) {
+		self.cancellation_flag.store(true, Ordering::Relaxed);
+	}
+
+	/// `None` until the search is finished
+	pub fn result(&self) -> Option<Result<GrepResult>> {
+		if let Ok(mut state) = self.state.lock() {
+			if matches!(*state, Some(JobState::Response(_))) {
+				if let Some(JobState::Response(result)) = state.take()
+				{
+					return Some(result);
+				}
+			}
+		}
+
+		None
+	}
+
+	fn run_request(
+		&self,
+		repo_path: &RepoPath,
+		commit: CommitId,
+		query: &GrepQuery,
+		params: &RunParams<AsyncGitNotification, ProgressPercent>,
+	) -> Result<GrepResult> {
+		let start = Instant::now();
+		let mut truncated = false;
+
+		grep_tree(repo_path, commit, query, |done, total, hits| {
+			if self.cancellation_flag.load(Ordering::Relaxed) {
+				return false;
+			}
+
+			if !hits.is_empty() {
+				if let Ok(mut all) = self.hits.lock() {
+					let room =
+						self.max_hits.saturating_sub(all.len());
+					truncated = hits.len() > room;
+					all.extend(hits.into_iter().take(room));
+				}
+			}
+
+			Self::update_progress(
+				params,
+				ProgressPercent::new(done, total),
+			);
+
+			!truncated
+		})?;
+
+		Ok(GrepResult {
+			truncated,
+			duration: start.elapsed(),
+		})
+	}
+
+	fn update_progress(
+		params: &RunParams<AsyncGitNotification, ProgressPercent>,
+		new_progress: ProgressPercent,
+	) {
+		match params.set_progress(new_progress) {
+			Err(e) => log::error!("progress error: {e}"),
+			Ok(result) if result => {
+				if let Err(e) =
+					params.send(AsyncGitNotification::Grep)
+				{
+					log::error!("send error: {e}");
+				}
+			}
+			_ => (),
+		}
+	}
+}
+
+impl AsyncJob for AsyncGrepJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ProgressPercent;
+
+	fn run(
+		&mut self,
+		params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		// the state is not locked while searching so hits can be read
+		let request = self.state.lock().ok().and_then(|mut state| {
+			if matches!(*state, Some(JobState::Request { .. })) {
+				state.take()
+			} else {
+				None
+			}
+		});
+
+		if let Some(JobState::Request {
+			repo_path,
+			commit,
+			query,
+		}) = request
+		{
+			let result =
+				self.run_request(&repo_path, commit, &query, &params);
+
+			if let Ok(mut state) = self.state.lock() {
+				*state = Some(JobState::Response(result));
+			}
+		}
+
+		Ok(AsyncGitNotification::Grep)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		asyncjob::AsyncSingleJob,
+		sync::tests::{repo_init, write_commit_file},
+	};
+	use crossbeam_channel::unbounded;
+	use std::time::Duration;
+
+	#[test]
+	fn test_max_hits() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: RepoPath =
+			root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a", "x\nx\nx", "a");
+		let id = write_commit_file(&repo, "b", "x", "b");
+
+		let (tx, rx) = unbounded();
+		let job = AsyncSingleJob::new(tx);
+		let grep = AsyncGrepJob::new(
+			repo_path,
+			id,
+			GrepQuery {
+				pattern: String::from("x"),
+				regex: false,
+			},
+			2,
+		);
+
+		job.spawn(grep.clone());
+
+		let result = loop {
+			rx.recv_timeout(Duration::from_secs(5)).unwrap();
+			if let Some(result) = grep.result() {
+				break result.unwrap();
+			}
+		};
+
+		assert!(result.truncated);
+		assert_eq!(grep.hits(0).len(), 2);
+		assert_eq!(grep.hits(1).len(), 1);
+	}
+}
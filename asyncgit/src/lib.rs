@@ -42,6 +42,7 @@ It wraps libraries like git2 and gix.
 
 pub mod asyncjob;
 mod blame;
+mod branch_compare;
 mod branches;
 pub mod cached;
 mod commit_files;
@@ -49,36 +50,53 @@ mod diff;
 mod error;
 mod fetch_job;
 mod filter_commits;
+mod fixup;
+mod grep;
+mod local_history;
+mod patch_hashes;
+pub mod process;
 mod progress;
 mod pull;
 mod push;
 mod push_tags;
+mod reflog;
 pub mod remote_progress;
 pub mod remote_tags;
 mod revlog;
+mod stage_paths;
 mod status;
+mod submodule_update;
 pub mod sync;
 mod tags;
 mod treefiles;
 
 pub use crate::{
 	blame::{AsyncBlame, BlameParams},
+	branch_compare::AsyncBranchCompareJob,
 	branches::AsyncBranchesJob,
 	commit_files::{AsyncCommitFiles, CommitFilesParams},
 	diff::{AsyncDiff, DiffParams, DiffType},
 	error::{Error, Result},
 	fetch_job::AsyncFetchJob,
 	filter_commits::{AsyncCommitFilterJob, CommitFilterResult},
+	fixup::AsyncFixupJob,
+	grep::{AsyncGrepJob, GrepResult},
+	local_history::AsyncLocalHistoryJob,
+	patch_hashes::{AsyncPatchHashesJob, PatchHashesResult},
+	process::{AsyncProcessJob, ProcessResult},
 	progress::ProgressPercent,
 	pull::{AsyncPull, FetchRequest},
 	push::{AsyncPush, PushRequest},
 	push_tags::{AsyncPushTags, PushTagsRequest},
+	reflog::AsyncReflogJob,
 	remote_progress::{RemoteProgress, RemoteProgressState},
 	revlog::{AsyncLog, FetchStatus},
+	stage_paths::{AsyncStagePathsJob, StagePathsProgress},
 	status::{AsyncStatus, StatusParams},
+	submodule_update::AsyncSubmoduleUpdateJob,
 	sync::{
 		diff::{DiffLine, DiffLineType, FileDiff},
-		remotes::push::PushType,
+		remotes::push::{PushForce, PushType},
 		status::{StatusItem, StatusItemType},
 	},
 	tags::AsyncTags,
@@ -125,6 +143,24 @@ pub enum AsyncGitNotification {
 	TreeFiles,
 	///
 	CommitFilter,
+	///
+	Grep,
+	///
+	PatchHashes,
+	///
+	BranchCompare,
+	///
+	Process,
+	///
+	Fixup,
+	///
+	LocalHistory,
+	///
+	StagePaths,
+	///
+	Reflog,
+	///
+	SubmoduleUpdate,
 }
 
 /// helper function to calculate the hash of an arbitrary type that implements the `Hash` trait
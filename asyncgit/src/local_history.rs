@@ -0,0 +1,87 @@
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{
+		prune_local_history, snapshot_changed_files,
+		LocalHistoryLimits, RepoPath,
+	},
+	AsyncGitNotification,
+};
+use std::sync::{Arc, Mutex};
+
+enum JobState {
+	Request {
+		repo: RepoPath,
+		limits: LocalHistoryLimits,
+		snapshot: bool,
+	},
+	Response(Result<usize>),
+}
+
+/// snapshots the changed files in the background or only prunes
+/// expired snapshots
+#[derive(Clone)]
+pub struct AsyncLocalHistoryJob {
+	state: Arc<Mutex<Option<JobState>>>,
+}
+
+///
+impl AsyncLocalHistoryJob {
+	///
+	pub fn new(
+		repo: RepoPath,
+		limits: LocalHistoryLimits,
+		snapshot: bool,
+	) -> Self {
+		Self {
+			state: Arc::new(Mutex::new(Some(JobState::Request {
+				repo,
+				limits,
+				snapshot,
+			}))),
+		}
+	}
+
+	/// number of snapshots added
+	pub fn result(&self) -> Option<Result<usize>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request { .. } => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncLocalHistoryJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request {
+					repo,
+					limits,
+					snapshot,
+				} => JobState::Response(if snapshot {
+					snapshot_changed_files(&repo, limits)
+				} else {
+					prune_local_history(&repo, limits).map(|()| 0)
+				}),
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::LocalHistory)
+	}
+}
@@ -0,0 +1,91 @@
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{
+		commit_files::{get_compare_commits_patch_hashes, OldNew},
+		CommitId, RepoPath,
+	},
+	AsyncGitNotification,
+};
+use std::{
+	collections::HashMap,
+	sync::{Arc, Mutex},
+};
+
+///
+pub struct PatchHashesResult {
+	///
+	pub ids: OldNew<CommitId>,
+	/// hash of every changed file's patch
+	pub result: Result<HashMap<String, u64>>,
+}
+
+enum JobState {
+	Request {
+		ids: OldNew<CommitId>,
+		repo: RepoPath,
+	},
+	Response(PatchHashesResult),
+}
+
+/// hashes the patch of every file changed between two commits
+#[derive(Clone, Default)]
+pub struct AsyncPatchHashesJob {
+	state: Arc<Mutex<Option<JobState>>>,
+}
+
+///
+impl AsyncPatchHashesJob {
+	///
+	pub fn new(repo: RepoPath, ids: OldNew<CommitId>) -> Self {
+		Self {
+			state: Arc::new(Mutex::new(Some(JobState::Request {
+				repo,
+				ids,
+			}))),
+		}
+	}
+
+	///
+	pub fn result(&self) -> Option<PatchHashesResult> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request { .. } => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncPatchHashesJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request { ids, repo } => {
+					let result =
+						get_compare_commits_patch_hashes(&repo, ids);
+
+					JobState::Response(PatchHashesResult {
+						ids,
+						result,
+					})
+				}
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::PatchHashes)
+	}
+}
@@ -0,0 +1,434 @@
+//! streams the output of an external process (hooks, commands) while
+//! it is running
+
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::{Error, Result},
+	AsyncGitNotification,
+};
+use std::{
+	collections::VecDeque,
+	io::{ErrorKind, Read},
+	process::{Child, Command},
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc, Mutex,
+	},
+	thread::{self, JoinHandle},
+	time::{Duration, Instant},
+};
+
+/// oldest lines are dropped once the scrollback grows beyond this
+const MAX_LINES: usize = 10_000;
+/// how often the process is checked for new output or being done
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// processes are not run in a pty, so this asks tools that only
+/// color their output in a terminal to do it anyway
+pub fn force_colors(command: &mut Command) {
+	command.env("CLICOLOR_FORCE", "1").env("FORCE_COLOR", "1");
+}
+
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputStream {
+	///
+	Stdout,
+	///
+	Stderr,
+}
+
+/// scrollback of a process, lines still contain ansi escapes
+#[derive(Debug, Default)]
+pub struct ProcessOutput {
+	lines: VecDeque<String>,
+	/// lines dropped from the front because of `MAX_LINES`
+	dropped: usize,
+	/// bytes after the last newline of stdout and stderr
+	partial: [Vec<u8>; 2],
+}
+
+impl ProcessOutput {
+	///
+	pub fn push(&mut self, stream: OutputStream, bytes: &[u8]) {
+		let partial = &mut self.partial[stream as usize];
+		partial.extend_from_slice(bytes);
+
+		let mut lines = Vec::new();
+		while let Some(end) = partial.iter().position(|b| *b == b'\n')
+		{
+			lines.push(line_text(&partial[..end]));
+			partial.drain(..=end);
+		}
+
+		// a progress bar redrawing itself with `\r` only needs to
+		// keep its latest state
+		if let Some(start) = partial
+			.iter()
+			.rev()
+			.skip(1)
+			.position(|b| *b == b'\r')
+			.map(|pos| partial.len() - 1 - pos)
+		{
+			partial.drain(..start);
+		}
+
+		for line in lines {
+			self.lines.push_back(line);
+			if self.lines.len() > MAX_LINES {
+				self.lines.pop_front();
+				self.dropped += 1;
+			}
+		}
+	}
+
+	/// number of complete lines ever received
+	pub fn len(&self) -> usize {
+		self.dropped + self.lines.len()
+	}
+
+	///
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// complete lines starting at `index` (counting dropped lines)
+	pub fn lines_since(&self, index: usize) -> Vec<String> {
+		self.lines
+			.iter()
+			.skip(index.saturating_sub(self.dropped))
+			.cloned()
+			.collect()
+	}
+
+	/// lines not terminated yet (stdout first)
+	pub fn partial(&self) -> Vec<String> {
+		self.partial
+			.iter()
+			.filter(|partial| !partial.is_empty())
+			.map(|partial| line_text(partial))
+			.collect()
+	}
+}
+
+/// what follows the last `\r` (the line was redrawn)
+fn line_text(line: &[u8]) -> String {
+	let line = line.strip_suffix(b"\r").unwrap_or(line);
+	let start = line
+		.iter()
+		.rposition(|b| *b == b'\r')
+		.map_or(0, |pos| pos + 1);
+
+	String::from_utf8_lossy(&line[start..]).into_owned()
+}
+
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProcessResult {
+	/// `None` if the process was ended by a signal
+	pub code: Option<i32>,
+	///
+	pub killed: bool,
+	///
+	pub duration: Duration,
+}
+
+impl ProcessResult {
+	///
+	pub const fn success(&self) -> bool {
+		!self.killed && matches!(self.code, Some(0))
+	}
+}
+
+/// waits for an already spawned process collecting its output,
+/// which can be read via `output` while it is still running
+#[derive(Clone)]
+pub struct AsyncProcessJob {
+	child: Arc<Mutex<Option<Child>>>,
+	output: Arc<Mutex<ProcessOutput>>,
+	result: Arc<Mutex<Option<Result<ProcessResult>>>>,
+	kill_flag: Arc<AtomicBool>,
+	changed: Arc<AtomicBool>,
+}
+
+impl AsyncProcessJob {
+	/// `child` should have piped stdout and stderr
+	pub fn new(child: Child) -> Self {
+		Self {
+			child: Arc::new(Mutex::new(Some(child))),
+			output: Arc::default(),
+			result: Arc::default(),
+			kill_flag: Arc::default(),
+			changed: Arc::default(),
+		}
+	}
+
+	/// runs `f` on the output received so far
+	pub fn output<T>(
+		&self,
+		f: impl FnOnce(&ProcessOutput) -> T,
+	) -> T {
+		match self.output.lock() {
+			Ok(output) => f(&output),
+			Err(poisoned) => f(&poisoned.into_inner()),
+		}
+	}
+
+	/// kills the process, also stops waiting for output of processes
+	/// it started
+	pub fn kill(&self) {
+		self.kill_flag.store(true, Ordering::Relaxed);
+	}
+
+	/// `None` until the process is done
+	pub fn result(&self) -> Option<Result<ProcessResult>> {
+		self.result.lock().ok()?.take()
+	}
+
+	fn run_process(
+		&self,
+		params: &RunParams<AsyncGitNotification, ()>,
+	) -> Result<ProcessResult> {
+		let mut child =
+			self.child.lock()?.take().ok_or_else(|| {
+				Error::Generic(String::from("process already ran"))
+			})?;
+		let start = Instant::now();
+
+		let mut readers = Vec::new();
+		if let Some(pipe) = child.stdout.take() {
+			readers
+				.push(self.read_output(OutputStream::Stdout, pipe));
+		}
+		if let Some(pipe) = child.stderr.take() {
+			readers
+				.push(self.read_output(OutputStream::Stderr, pipe));
+		}
+
+		let mut killed = false;
+		let status = loop {
+			if !killed && self.kill_flag.load(Ordering::Relaxed) {
+				// fails if it exited already, which `try_wait` sees
+				child.kill().ok();
+				killed = true;
+			}
+
+			if let Some(status) = child.try_wait()? {
+				break status;
+			}
+
+			self.notify_changed(params);
+			thread::sleep(POLL_INTERVAL);
+		};
+
+		// processes started by it can keep the pipes open
+		while !readers.iter().all(JoinHandle::is_finished)
+			&& !self.kill_flag.load(Ordering::Relaxed)
+		{
+			self.notify_changed(params);
+			thread::sleep(POLL_INTERVAL);
+		}
+
+		Ok(ProcessResult {
+			code: status.code(),
+			killed,
+			duration: start.elapsed(),
+		})
+	}
+
+	fn read_output(
+		&self,
+		stream: OutputStream,
+		mut pipe: impl Read + Send + 'static,
+	) -> JoinHandle<()> {
+		let output = Arc::clone(&self.output);
+		let changed = Arc::clone(&self.changed);
+
+		thread::spawn(move || {
+			let mut buffer = [0; 4096];
+
+			loop {
+				match pipe.read(&mut buffer) {
+					Ok(0) => break,
+					Ok(read) => {
+						if let Ok(mut output) = output.lock() {
+							output.push(stream, &buffer[..read]);
+						}
+						changed.store(true, Ordering::Relaxed);
+					}
+					Err(e) if e.kind() == ErrorKind::Interrupted => {}
+					Err(e) => {
+						log::error!("process output error: {e}");
+						break;
+					}
+				}
+			}
+		})
+	}
+
+	fn notify_changed(
+		&self,
+		params: &RunParams<AsyncGitNotification, ()>,
+	) {
+		if self.changed.swap(false, Ordering::Relaxed) {
+			if let Err(e) = params.send(AsyncGitNotification::Process)
+			{
+				log::error!("send error: {e}");
+			}
+		}
+	}
+}
+
+impl AsyncJob for AsyncProcessJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		let result = self.run_process(&params);
+
+		*self.result.lock()? = Some(result);
+
+		Ok(AsyncGitNotification::Process)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::asyncjob::AsyncSingleJob;
+	use crossbeam_channel::unbounded;
+	use std::process::Stdio;
+
+	#[test]
+	fn test_output_lines() {
+		let mut output = ProcessOutput::default();
+
+		output.push(OutputStream::Stdout, b"first\r\nsec");
+		output.push(OutputStream::Stderr, b"error");
+		output.push(OutputStream::Stdout, b"ond\n");
+
+		assert_eq!(output.len(), 2);
+		assert_eq!(output.lines_since(0), vec!["first", "second"]);
+		assert_eq!(output.lines_since(1), vec!["second"]);
+		assert_eq!(output.partial(), vec!["error"]);
+
+		// utf8 split across reads
+		let bytes = "\u{00e4}\n".as_bytes();
+		output.push(OutputStream::Stdout, &bytes[..1]);
+		output.push(OutputStream::Stdout, &bytes[1..]);
+		assert_eq!(output.lines_since(2), vec!["\u{00e4}"]);
+	}
+
+	#[test]
+	fn test_output_carriage_return() {
+		let mut output = ProcessOutput::default();
+
+		output.push(OutputStream::Stderr, b" 10%\r 50%\r");
+		assert_eq!(output.partial(), vec![" 50%"]);
+
+		output.push(OutputStream::Stderr, b"100%\r\ndone\n");
+		assert_eq!(output.lines_since(0), vec!["100%", "done"]);
+		assert!(output.partial().is_empty());
+	}
+
+	#[test]
+	fn test_output_long_line() {
+		let mut output = ProcessOutput::default();
+
+		let chunk = "\x1b[31mx\x1b[0m".repeat(1000);
+		for _ in 0..100 {
+			output.push(OutputStream::Stdout, chunk.as_bytes());
+		}
+		output.push(OutputStream::Stdout, b"\n");
+
+		let lines = output.lines_since(0);
+		assert_eq!(lines.len(), 1);
+		assert_eq!(lines[0].len(), chunk.len() * 100);
+	}
+
+	#[test]
+	fn test_output_scrollback_limit() {
+		let mut output = ProcessOutput::default();
+
+		for i in 0..MAX_LINES + 5 {
+			output.push(
+				OutputStream::Stdout,
+				format!("{i}\n").as_bytes(),
+			);
+		}
+
+		assert_eq!(output.len(), MAX_LINES + 5);
+		assert_eq!(output.lines_since(0).len(), MAX_LINES);
+		assert_eq!(output.lines_since(0)[0], "5");
+		assert_eq!(
+			output.lines_since(MAX_LINES + 3),
+			vec![
+				(MAX_LINES + 3).to_string(),
+				(MAX_LINES + 4).to_string()
+			]
+		);
+	}
+
+	fn spawn_sh(script: &str) -> Child {
+		Command::new("sh")
+			.arg("-c")
+			.arg(script)
+			.stdout(Stdio::piped())
+			.stderr(Stdio::piped())
+			.spawn()
+			.unwrap()
+	}
+
+	fn wait_for(process: &AsyncProcessJob) -> ProcessResult {
+		loop {
+			if let Some(result) = process.result() {
+				return result.unwrap();
+			}
+			thread::sleep(Duration::from_millis(10));
+		}
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn test_process_job() {
+		let (sender, _receiver) = unbounded();
+		let job = AsyncSingleJob::new(sender);
+
+		let process = AsyncProcessJob::new(spawn_sh(
+			"echo out; echo err >&2; exit 3",
+		));
+		assert!(job.spawn(process.clone()));
+
+		let result = wait_for(&process);
+
+		assert_eq!(result.code, Some(3));
+		assert!(!result.killed);
+		assert!(!result.success());
+
+		let mut lines =
+			process.output(|output| output.lines_since(0));
+		lines.sort();
+		assert_eq!(lines, vec!["err", "out"]);
+		assert!(process.result().is_none());
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn test_process_job_kill() {
+		let (sender, _receiver) = unbounded();
+		let job = AsyncSingleJob::new(sender);
+
+		let process = AsyncProcessJob::new(spawn_sh("sleep 60"));
+		assert!(job.spawn(process.clone()));
+		process.kill();
+
+		let result = wait_for(&process);
+
+		assert!(result.killed);
+		assert!(!result.success());
+		assert!(result.duration < Duration::from_secs(60));
+	}
+}
@@ -1,7 +1,10 @@
 //!
 
 use easy_cast::{Conv, ConvFloat};
-use std::cmp;
+use std::{
+	cmp,
+	time::{Duration, Instant},
+};
 
 ///
 #[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
@@ -28,6 +31,33 @@ impl ProgressPercent {
 	}
 }
 
+/// average throughput of a transfer since its first update
+#[derive(Clone, Copy, Default, Debug)]
+pub struct TransferRate {
+	start: Option<Instant>,
+}
+
+impl TransferRate {
+	/// bytes per second given `bytes` transferred until now
+	pub fn update(&mut self, bytes: usize) -> usize {
+		let now = Instant::now();
+		let start = *self.start.get_or_insert(now);
+
+		Self::bytes_per_sec(bytes, now.duration_since(start))
+	}
+
+	fn bytes_per_sec(bytes: usize, elapsed: Duration) -> usize {
+		let secs = elapsed.as_secs_f64();
+
+		if secs > 0.0 {
+			usize::try_conv_nearest(f64::conv(bytes) / secs)
+				.unwrap_or_default()
+		} else {
+			0
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -51,4 +81,19 @@ mod tests {
 
 		assert_eq!(prog.progress, 20);
 	}
+
+	#[test]
+	fn test_transfer_rate() {
+		assert_eq!(
+			TransferRate::bytes_per_sec(
+				1000,
+				Duration::from_millis(500)
+			),
+			2000
+		);
+		assert_eq!(
+			TransferRate::bytes_per_sec(1000, Duration::ZERO),
+			0
+		);
+	}
 }
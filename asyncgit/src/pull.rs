@@ -2,7 +2,7 @@ use crate::{
 	error::{Error, Result},
 	sync::{
 		cred::BasicAuthCredential,
-		remotes::{fetch, push::ProgressNotification},
+		remotes::{fetch, push::ProgressNotification, CredPrompt},
 		RepoPath,
 	},
 	AsyncGitNotification, RemoteProgress,
@@ -34,6 +34,7 @@ pub struct AsyncPull {
 	last_result: Arc<Mutex<Option<(usize, String)>>>,
 	progress: Arc<Mutex<Option<ProgressNotification>>>,
 	sender: Sender<AsyncGitNotification>,
+	cred_prompt: CredPrompt,
 	repo: RepoPath,
 }
 
@@ -49,6 +50,10 @@ impl AsyncPull {
 			last_result: Arc::new(Mutex::new(None)),
 			progress: Arc::new(Mutex::new(None)),
 			sender: sender.clone(),
+			cred_prompt: CredPrompt::new(Some((
+				sender.clone(),
+				AsyncGitNotification::Pull,
+			))),
 		}
 	}
 
@@ -70,6 +75,11 @@ impl AsyncPull {
 		Ok(res.as_ref().map(|progress| progress.clone().into()))
 	}
 
+	/// answers credential requests of the running fetch
+	pub const fn cred_prompt(&self) -> &CredPrompt {
+		&self.cred_prompt
+	}
+
 	///
 	pub fn request(&self, params: FetchRequest) -> Result<()> {
 		log::trace!("request");
@@ -80,12 +90,14 @@ impl AsyncPull {
 
 		self.set_request(&params)?;
 		RemoteProgress::set_progress(&self.progress, None)?;
+		self.cred_prompt.reset()?;
 
 		let arc_state = Arc::clone(&self.state);
 		let arc_res = Arc::clone(&self.last_result);
 		let arc_progress = Arc::clone(&self.progress);
 		let sender = self.sender.clone();
 		let repo = self.repo.clone();
+		let cred_prompt = self.cred_prompt.clone();
 
 		thread::spawn(move || {
 			let (progress_sender, receiver) = unbounded();
@@ -102,6 +114,7 @@ impl AsyncPull {
 				&params.branch,
 				params.basic_credential,
 				Some(progress_sender.clone()),
+				Some(&cred_prompt),
 			);
 
 			progress_sender
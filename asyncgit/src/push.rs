@@ -3,7 +3,8 @@ use crate::{
 	sync::{
 		cred::BasicAuthCredential,
 		remotes::push::push_raw,
-		remotes::push::{ProgressNotification, PushType},
+		remotes::push::{ProgressNotification, PushForce, PushType},
+		remotes::CredPrompt,
 		RepoPath,
 	},
 	AsyncGitNotification, RemoteProgress,
@@ -24,7 +25,7 @@ pub struct PushRequest {
 	///
 	pub push_type: PushType,
 	///
-	pub force: bool,
+	pub force: PushForce,
 	///
 	pub delete: bool,
 	///
@@ -39,8 +40,11 @@ struct PushState {}
 pub struct AsyncPush {
 	state: Arc<Mutex<Option<PushState>>>,
 	last_result: Arc<Mutex<Option<String>>>,
+	/// see [`Self::last_rejected_tag`]
+	rejected_tag: Arc<Mutex<Option<String>>>,
 	progress: Arc<Mutex<Option<ProgressNotification>>>,
 	sender: Sender<AsyncGitNotification>,
+	cred_prompt: CredPrompt,
 	repo: RepoPath,
 }
 
@@ -54,8 +58,13 @@ impl AsyncPush {
 			repo,
 			state: Arc::new(Mutex::new(None)),
 			last_result: Arc::new(Mutex::new(None)),
+			rejected_tag: Arc::new(Mutex::new(None)),
 			progress: Arc::new(Mutex::new(None)),
 			sender: sender.clone(),
+			cred_prompt: CredPrompt::new(Some((
+				sender.clone(),
+				AsyncGitNotification::Push,
+			))),
 		}
 	}
 
@@ -71,12 +80,24 @@ impl AsyncPush {
 		Ok(res.clone())
 	}
 
+	/// tag the last push failed to update on the remote without
+	/// force (see [`Error::PushTagExists`])
+	pub fn last_rejected_tag(&self) -> Result<Option<String>> {
+		let res = self.rejected_tag.lock()?;
+		Ok(res.clone())
+	}
+
 	///
 	pub fn progress(&self) -> Result<Option<RemoteProgress>> {
 		let res = self.progress.lock()?;
 		Ok(res.as_ref().map(|progress| progress.clone().into()))
 	}
 
+	/// answers credential requests of the running push
+	pub const fn cred_prompt(&self) -> &CredPrompt {
+		&self.cred_prompt
+	}
+
 	///
 	pub fn request(&self, params: PushRequest) -> Result<()> {
 		log::trace!("request");
@@ -87,12 +108,15 @@ impl AsyncPush {
 
 		self.set_request(&params)?;
 		RemoteProgress::set_progress(&self.progress, None)?;
+		self.cred_prompt.reset()?;
 
 		let arc_state = Arc::clone(&self.state);
 		let arc_res = Arc::clone(&self.last_result);
+		let arc_rejected_tag = Arc::clone(&self.rejected_tag);
 		let arc_progress = Arc::clone(&self.progress);
 		let sender = self.sender.clone();
 		let repo = self.repo.clone();
+		let cred_prompt = self.cred_prompt.clone();
 
 		thread::spawn(move || {
 			let (progress_sender, receiver) = unbounded();
@@ -104,16 +128,24 @@ impl AsyncPush {
 				arc_progress,
 			);
 
-			let res = push_raw(
-				&repo,
-				params.remote.as_str(),
-				params.branch.as_str(),
-				params.push_type,
-				params.force,
-				params.delete,
-				params.basic_credential.clone(),
-				Some(progress_sender.clone()),
-			);
+			// switching to another url restarts the whole push
+			let res = loop {
+				let res = push_raw(
+					&repo,
+					params.remote.as_str(),
+					params.branch.as_str(),
+					params.push_type,
+					params.force,
+					params.delete,
+					params.basic_credential.clone(),
+					Some(progress_sender.clone()),
+					Some(&cred_prompt),
+				);
+
+				if res.is_ok() || !cred_prompt.take_restart() {
+					break res;
+				}
+			};
 
 			progress_sender
 				.send(ProgressNotification::Done)
@@ -121,7 +153,8 @@ impl AsyncPush {
 
 			handle.join().expect("joining thread failed");
 
-			Self::set_result(&arc_res, res).expect("result error");
+			Self::set_result(&arc_res, &arc_rejected_tag, res)
+				.expect("result error");
 
 			Self::clear_request(&arc_state).expect("clear error");
 
@@ -157,10 +190,16 @@ impl AsyncPush {
 
 	fn set_result(
 		arc_result: &Arc<Mutex<Option<String>>>,
+		arc_rejected_tag: &Arc<Mutex<Option<String>>>,
 		res: Result<()>,
 	) -> Result<()> {
 		let mut last_res = arc_result.lock()?;
 
+		*arc_rejected_tag.lock()? = match &res {
+			Err(Error::PushTagExists(tag)) => Some(tag.clone()),
+			_ => None,
+		};
+
 		*last_res = match res {
 			Ok(()) => None,
 			Err(e) => {
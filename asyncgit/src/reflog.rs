@@ -0,0 +1,67 @@
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{get_reflog, ReflogEntry, RepoPath},
+	AsyncGitNotification,
+};
+use std::sync::{Arc, Mutex};
+
+enum JobState {
+	Request(RepoPath),
+	Response(Result<Vec<ReflogEntry>>),
+}
+
+/// reads the reflog of `HEAD`, it can be long in old repos
+#[derive(Clone, Default)]
+pub struct AsyncReflogJob {
+	state: Arc<Mutex<Option<JobState>>>,
+}
+
+///
+impl AsyncReflogJob {
+	///
+	pub fn new(repo: RepoPath) -> Self {
+		Self {
+			state: Arc::new(Mutex::new(Some(JobState::Request(
+				repo,
+			)))),
+		}
+	}
+
+	///
+	pub fn result(&self) -> Option<Result<Vec<ReflogEntry>>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request(_) => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncReflogJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request(repo) => {
+					JobState::Response(get_reflog(&repo))
+				}
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::Reflog)
+	}
+}
@@ -6,15 +6,19 @@ use crate::{
 	sync::remotes::push::{AsyncProgress, ProgressNotification},
 	AsyncGitNotification,
 };
-use crossbeam_channel::{Receiver, Sender};
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
 use git2::PackBuilderStage;
 use std::{
 	sync::{Arc, Mutex},
 	thread::{self, JoinHandle},
+	time::{Duration, Instant},
 };
 
+/// minimum time between two progress notifications
+const NOTIFY_INTERVAL: Duration = Duration::from_millis(100);
+
 /// used for push/pull
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum RemoteProgressState {
 	///
 	PackingAddingObject,
@@ -29,12 +33,20 @@ pub enum RemoteProgressState {
 }
 
 ///
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct RemoteProgress {
 	///
 	pub state: RemoteProgressState,
 	///
 	pub progress: ProgressPercent,
+	/// objects (or packed/pushed items) done so far
+	pub current: usize,
+	///
+	pub total: usize,
+	/// bytes transferred so far
+	pub bytes: usize,
+	/// average transfer rate, `0` if unknown
+	pub bytes_per_sec: usize,
 }
 
 impl RemoteProgress {
@@ -47,6 +59,24 @@ impl RemoteProgress {
 		Self {
 			state,
 			progress: ProgressPercent::new(current, total),
+			current,
+			total,
+			bytes: 0,
+			bytes_per_sec: 0,
+		}
+	}
+
+	///
+	#[must_use]
+	pub const fn with_bytes(
+		self,
+		bytes: usize,
+		bytes_per_sec: usize,
+	) -> Self {
+		Self {
+			bytes,
+			bytes_per_sec,
+			..self
 		}
 	}
 
@@ -67,6 +97,9 @@ impl RemoteProgress {
 	}
 
 	/// spawn thread to listen to progress notifications coming in from blocking remote git method (fetch/push)
+	///
+	/// notifications to the ui are throttled (see [`NOTIFY_INTERVAL`]),
+	/// updates held back are sent once the interval passed.
 	pub(crate) fn spawn_receiver_thread<
 		T: 'static + AsyncProgress,
 	>(
@@ -75,18 +108,59 @@ impl RemoteProgress {
 		receiver: Receiver<T>,
 		progress: Arc<Mutex<Option<T>>>,
 	) -> JoinHandle<()> {
-		thread::spawn(move || loop {
-			let incoming = receiver.recv();
-			match incoming {
-				Ok(update) => {
+		thread::spawn(move || {
+			Self::forward_progress(
+				&receiver,
+				Instant::now,
+				|update| {
 					Self::set_progress(
 						&progress,
 						Some(update.clone()),
 					)
 					.expect("set progress failed");
+				},
+				|| {
 					sender
 						.send(notification_type)
 						.expect("Notification error");
+				},
+			);
+		})
+	}
+
+	/// passes every update from `receiver` to `on_update` and calls
+	/// `notify` for the ones worth a notification (see
+	/// [`ProgressThrottle`]) until the progress is done or all senders
+	/// are gone
+	pub(crate) fn forward_progress<T: AsyncProgress>(
+		receiver: &Receiver<T>,
+		now: impl Fn() -> Instant,
+		mut on_update: impl FnMut(&T),
+		mut notify: impl FnMut(),
+	) {
+		let mut throttle = ProgressThrottle::default();
+
+		loop {
+			let incoming = throttle.pending_deadline().map_or_else(
+				|| {
+					receiver
+						.recv()
+						.map_err(|_| RecvTimeoutError::Disconnected)
+				},
+				|deadline| receiver.recv_deadline(deadline),
+			);
+
+			match incoming {
+				Ok(update) => {
+					on_update(&update);
+
+					if throttle.update(
+						now(),
+						update.progress(),
+						update.is_done(),
+					) {
+						notify();
+					}
 
 					thread::yield_now();
 
@@ -94,6 +168,10 @@ impl RemoteProgress {
 						break;
 					}
 				}
+				Err(RecvTimeoutError::Timeout) => {
+					throttle.flush(now());
+					notify();
+				}
 				Err(e) => {
 					log::error!(
 						"remote progress receiver error: {}",
@@ -102,7 +180,60 @@ impl RemoteProgress {
 					break;
 				}
 			}
-		})
+		}
+	}
+}
+
+/// decides which progress updates are worth a notification: at most
+/// one per [`NOTIFY_INTERVAL`] but always the ones finishing a stage
+#[derive(Default)]
+struct ProgressThrottle {
+	last_notify: Option<Instant>,
+	last_progress: Option<ProgressPercent>,
+	/// update that was held back
+	pending: Option<ProgressPercent>,
+}
+
+impl ProgressThrottle {
+	/// returns whether to notify about this update
+	fn update(
+		&mut self,
+		now: Instant,
+		progress: ProgressPercent,
+		done: bool,
+	) -> bool {
+		let finished = progress == ProgressPercent::full()
+			&& self.last_progress != Some(progress);
+		let due = self.last_notify.map_or(true, |last| {
+			now.duration_since(last) >= NOTIFY_INTERVAL
+		});
+
+		if done || finished || due {
+			self.notified(now, progress);
+			true
+		} else {
+			self.pending = Some(progress);
+			false
+		}
+	}
+
+	/// when to notify about a held back update
+	fn pending_deadline(&self) -> Option<Instant> {
+		self.pending?;
+		self.last_notify.map(|last| last + NOTIFY_INTERVAL)
+	}
+
+	/// the held back update was notified
+	fn flush(&mut self, now: Instant) {
+		if let Some(progress) = self.pending {
+			self.notified(now, progress);
+		}
+	}
+
+	fn notified(&mut self, now: Instant, progress: ProgressPercent) {
+		self.last_notify = Some(now);
+		self.last_progress = Some(progress);
+		self.pending = None;
 	}
 }
 
@@ -128,22 +259,148 @@ impl From<ProgressNotification> for RemoteProgress {
 			ProgressNotification::PushTransfer {
 				current,
 				total,
-				..
+				bytes,
+				bytes_per_sec,
 			} => Self::new(
 				RemoteProgressState::Pushing,
 				current,
 				total,
-			),
+			)
+			.with_bytes(bytes, bytes_per_sec),
 			ProgressNotification::Transfer {
 				objects,
 				total_objects,
-				..
+				bytes,
+				bytes_per_sec,
 			} => Self::new(
 				RemoteProgressState::Transfer,
 				objects,
 				total_objects,
-			),
+			)
+			.with_bytes(bytes, bytes_per_sec),
 			_ => Self::new(RemoteProgressState::Done, 1, 1),
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crossbeam_channel::unbounded;
+
+	#[test]
+	fn test_throttle_burst() {
+		let mut throttle = ProgressThrottle::default();
+		let start = Instant::now();
+		let count = 10_000;
+
+		// 10k updates within 100ms
+		let notified = (1..=count)
+			.filter(|i| {
+				throttle.update(
+					start + Duration::from_micros(10 * *i as u64),
+					ProgressPercent::new(*i, count),
+					false,
+				)
+			})
+			.count();
+
+		// first, one after the interval and the final 100%
+		assert!(notified <= 3, "{notified} notifications");
+		assert_eq!(
+			throttle.last_progress,
+			Some(ProgressPercent::full())
+		);
+	}
+
+	#[test]
+	fn test_throttle_pending() {
+		let mut throttle = ProgressThrottle::default();
+		let start = Instant::now();
+
+		assert!(throttle.update(
+			start,
+			ProgressPercent::new(1, 10),
+			false
+		));
+		assert!(!throttle.update(
+			start + Duration::from_millis(1),
+			ProgressPercent::new(2, 10),
+			false
+		));
+		assert_eq!(
+			throttle.pending_deadline(),
+			Some(start + NOTIFY_INTERVAL)
+		);
+
+		throttle.flush(start + NOTIFY_INTERVAL);
+
+		assert_eq!(throttle.pending_deadline(), None);
+		assert_eq!(
+			throttle.last_progress,
+			Some(ProgressPercent::new(2, 10))
+		);
+	}
+
+	#[test]
+	fn test_forward_progress_burst() {
+		let (progress_sender, progress_receiver) = unbounded();
+
+		let count = 10_000;
+		for objects in 1..=count {
+			progress_sender
+				.send(ProgressNotification::Transfer {
+					objects,
+					total_objects: count,
+					bytes: objects,
+					bytes_per_sec: 0,
+				})
+				.unwrap();
+		}
+		progress_sender.send(ProgressNotification::Done).unwrap();
+
+		// the whole burst arrives at the same instant
+		let start = Instant::now();
+		let mut last = None;
+		let mut notified = 0;
+
+		RemoteProgress::forward_progress(
+			&progress_receiver,
+			|| start,
+			|update| last = Some(update.clone()),
+			|| notified += 1,
+		);
+
+		// first update, the final 100% and done
+		assert_eq!(notified, 3);
+		assert_eq!(last, Some(ProgressNotification::Done));
+		assert!(progress_receiver.is_empty());
+	}
+
+	#[test]
+	fn test_receiver_thread() {
+		let (progress_sender, progress_receiver) = unbounded();
+		let (sender, receiver) = unbounded();
+		let progress = Arc::new(Mutex::new(None));
+
+		progress_sender.send(ProgressNotification::Done).unwrap();
+
+		RemoteProgress::spawn_receiver_thread(
+			AsyncGitNotification::Fetch,
+			sender,
+			progress_receiver,
+			Arc::clone(&progress),
+		)
+		.join()
+		.unwrap();
+
+		assert_eq!(
+			receiver.try_iter().collect::<Vec<_>>(),
+			vec![AsyncGitNotification::Fetch]
+		);
+		assert_eq!(
+			*progress.lock().unwrap(),
+			Some(ProgressNotification::Done)
+		);
+	}
+}
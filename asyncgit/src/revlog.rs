@@ -1,14 +1,15 @@
 use crate::{
 	error::Result,
 	sync::{
-		gix_repo, repo, CommitId, LogWalker, LogWalkerWithoutFilter,
-		RepoPath, SharedCommitFilterFn,
+		get_commit_replacements, gix_repo, repo, CommitId, LogWalker,
+		LogWalkerWithoutFilter, RepoPath, SharedCommitFilterFn,
 	},
 	AsyncGitNotification, Error,
 };
 use crossbeam_channel::Sender;
 use scopetime::scope_time;
 use std::{
+	collections::HashMap,
 	sync::{
 		atomic::{AtomicBool, Ordering},
 		Arc, Mutex,
@@ -44,12 +45,16 @@ pub struct AsyncLog {
 	background: Arc<AtomicBool>,
 	filter: Option<SharedCommitFilterFn>,
 	partial_extract: AtomicBool,
+	/// walk replacements in place of the commits replace refs replace
+	replace_refs: AtomicBool,
 	repo: RepoPath,
 }
 
 static LIMIT_COUNT: usize = 3000;
 static SLEEP_FOREGROUND: Duration = Duration::from_millis(2);
 static SLEEP_BACKGROUND: Duration = Duration::from_millis(1000);
+/// the log is drawn again on every notification
+static NOTIFY_INTERVAL: Duration = Duration::from_millis(100);
 
 impl AsyncLog {
 	///
@@ -70,9 +75,33 @@ impl AsyncLog {
 			background: Arc::new(AtomicBool::new(false)),
 			filter,
 			partial_extract: AtomicBool::new(false),
+			replace_refs: AtomicBool::new(true),
 		}
 	}
 
+	/// the next fetch walks the history with or without replace refs
+	pub fn set_replace_refs(&self, enabled: bool) -> Result<()> {
+		if self.replace_refs.swap(enabled, Ordering::Relaxed)
+			!= enabled
+		{
+			self.invalidate()?;
+		}
+
+		Ok(())
+	}
+
+	/// the next fetch walks again even if `HEAD` did not move
+	pub fn invalidate(&self) -> Result<()> {
+		*self.current_head.lock()? = None;
+
+		Ok(())
+	}
+
+	///
+	pub fn replace_refs(&self) -> bool {
+		self.replace_refs.load(Ordering::Relaxed)
+	}
+
 	///
 	pub fn count(&self) -> Result<usize> {
 		Ok(self.current.lock()?.commits.len())
@@ -167,6 +196,7 @@ impl AsyncLog {
 		let arc_background = Arc::clone(&self.background);
 		let filter = self.filter.clone();
 		let repo_path = self.repo.clone();
+		let replace_refs = self.replace_refs();
 
 		if let Ok(head) = repo(&self.repo)?.head() {
 			*self.current_head.lock()? =
@@ -182,6 +212,7 @@ impl AsyncLog {
 				&arc_background,
 				&sender,
 				filter,
+				replace_refs,
 			)
 			.expect("failed to fetch");
 
@@ -199,26 +230,32 @@ impl AsyncLog {
 		arc_background: &Arc<AtomicBool>,
 		sender: &Sender<AsyncGitNotification>,
 		filter: Option<SharedCommitFilterFn>,
+		replace_refs: bool,
 	) -> Result<()> {
-		filter.map_or_else(
-			|| {
-				Self::fetch_helper_without_filter(
-					repo_path,
-					arc_current,
-					arc_background,
-					sender,
-				)
-			},
-			|filter| {
-				Self::fetch_helper_with_filter(
-					repo_path,
-					arc_current,
-					arc_background,
-					sender,
-					filter,
-				)
-			},
-		)
+		let replacements = if replace_refs {
+			get_commit_replacements(&repo(repo_path)?)?
+		} else {
+			HashMap::new()
+		};
+
+		// the gix walker does not follow replacements
+		if filter.is_none() && replacements.is_empty() {
+			Self::fetch_helper_without_filter(
+				repo_path,
+				arc_current,
+				arc_background,
+				sender,
+			)
+		} else {
+			Self::fetch_helper_with_filter(
+				repo_path,
+				arc_current,
+				arc_background,
+				sender,
+				filter,
+				replacements,
+			)
+		}
 	}
 
 	fn fetch_helper_with_filter(
@@ -226,16 +263,19 @@ impl AsyncLog {
 		arc_current: &Arc<Mutex<AsyncLogResult>>,
 		arc_background: &Arc<AtomicBool>,
 		sender: &Sender<AsyncGitNotification>,
-		filter: SharedCommitFilterFn,
+		filter: Option<SharedCommitFilterFn>,
+		replacements: HashMap<CommitId, CommitId>,
 	) -> Result<()> {
 		let start_time = Instant::now();
+		let mut notified = None;
 
 		let mut entries = vec![CommitId::default(); LIMIT_COUNT];
 		entries.resize(0, CommitId::default());
 
 		let r = repo(repo_path)?;
-		let mut walker =
-			LogWalker::new(&r, LIMIT_COUNT)?.filter(Some(filter));
+		let mut walker = LogWalker::new(&r, LIMIT_COUNT)?
+			.filter(filter)
+			.replacements(replacements);
 
 		loop {
 			entries.clear();
@@ -248,7 +288,7 @@ impl AsyncLog {
 			if read == 0 {
 				break;
 			}
-			Self::notify(sender);
+			Self::notify_throttled(sender, &mut notified);
 
 			let sleep_duration =
 				if arc_background.load(Ordering::Relaxed) {
@@ -272,6 +312,7 @@ impl AsyncLog {
 		sender: &Sender<AsyncGitNotification>,
 	) -> Result<()> {
 		let start_time = Instant::now();
+		let mut notified = None;
 
 		let mut entries = vec![CommitId::default(); LIMIT_COUNT];
 		entries.resize(0, CommitId::default());
@@ -291,7 +332,7 @@ impl AsyncLog {
 			if read == 0 {
 				break;
 			}
-			Self::notify(sender);
+			Self::notify_throttled(sender, &mut notified);
 
 			let sleep_duration =
 				if arc_background.load(Ordering::Relaxed) {
@@ -320,6 +361,20 @@ impl AsyncLog {
 			.send(AsyncGitNotification::Log)
 			.expect("error sending");
 	}
+
+	/// notifies about the commits walked so far at most every
+	/// [`NOTIFY_INTERVAL`], the end of the walk is always notified
+	fn notify_throttled(
+		sender: &Sender<AsyncGitNotification>,
+		notified: &mut Option<Instant>,
+	) {
+		if notified.map_or(true, |last: Instant| {
+			last.elapsed() >= NOTIFY_INTERVAL
+		}) {
+			Self::notify(sender);
+			*notified = Some(Instant::now());
+		}
+	}
 }
 
 #[cfg(test)]
@@ -402,4 +457,21 @@ mod tests {
 
 		assert_eq!(result.unwrap(), ());
 	}
+
+	#[test]
+	fn test_notify_throttled() {
+		let (tx_git, rx_git) = unbounded();
+		let mut notified = None;
+
+		for _ in 0..10 {
+			AsyncLog::notify_throttled(&tx_git, &mut notified);
+		}
+		assert_eq!(rx_git.len(), 1);
+
+		notified = notified.map(|last| {
+			last.checked_sub(super::NOTIFY_INTERVAL).unwrap()
+		});
+		AsyncLog::notify_throttled(&tx_git, &mut notified);
+		assert_eq!(rx_git.len(), 2);
+	}
 }
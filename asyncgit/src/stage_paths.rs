@@ -0,0 +1,134 @@
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{stage_all_paths, RepoPath, StagePathsResult},
+	AsyncGitNotification,
+};
+use std::sync::{
+	atomic::{AtomicBool, Ordering},
+	Arc, Mutex,
+};
+
+/// progress is only published every this many paths
+const PROGRESS_STEP: usize = 100;
+
+/// paths (un)staged so far
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct StagePathsProgress {
+	///
+	pub done: usize,
+	///
+	pub total: usize,
+}
+
+enum JobState {
+	Request {
+		repo_path: RepoPath,
+		paths: Vec<String>,
+		stage: bool,
+	},
+	Response(Result<StagePathsResult>),
+}
+
+/// (un)stages a lot of paths with a single index write
+#[derive(Clone)]
+pub struct AsyncStagePathsJob {
+	state: Arc<Mutex<Option<JobState>>>,
+	cancellation_flag: Arc<AtomicBool>,
+}
+
+///
+impl AsyncStagePathsJob {
+	///
+	pub fn new(
+		repo_path: RepoPath,
+		paths: Vec<String>,
+		stage: bool,
+	) -> Self {
+		Self {
+			state: Arc::new(Mutex::new(Some(JobState::Request {
+				repo_path,
+				paths,
+				stage,
+			}))),
+			cancellation_flag: Arc::default(),
+		}
+	}
+
+	/// stops before the index is written, leaving it untouched
+	pub fn cancel(&self) {
+		self.cancellation_flag.store(true, Ordering::Relaxed);
+	}
+
+	///
+	pub fn result(&self) -> Option<Result<StagePathsResult>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request { .. } => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+
+	fn update_progress(
+		params: &RunParams<AsyncGitNotification, StagePathsProgress>,
+		new_progress: StagePathsProgress,
+	) {
+		match params.set_progress(new_progress) {
+			Err(e) => log::error!("progress error: {e}"),
+			Ok(result) if result => {
+				if let Err(e) =
+					params.send(AsyncGitNotification::StagePaths)
+				{
+					log::error!("send error: {e}");
+				}
+			}
+			_ => (),
+		}
+	}
+}
+
+impl AsyncJob for AsyncStagePathsJob {
+	type Notification = AsyncGitNotification;
+	type Progress = StagePathsProgress;
+
+	fn run(
+		&mut self,
+		params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request {
+					repo_path,
+					paths,
+					stage,
+				} => JobState::Response(stage_all_paths(
+					&repo_path,
+					&paths,
+					stage,
+					|done, total| {
+						if done % PROGRESS_STEP == 0 {
+							Self::update_progress(
+								&params,
+								StagePathsProgress { done, total },
+							);
+						}
+
+						!self
+							.cancellation_flag
+							.load(Ordering::Relaxed)
+					},
+				)),
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::StagePaths)
+	}
+}
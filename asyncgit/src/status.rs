@@ -29,11 +29,12 @@ pub struct Status {
 }
 
 ///
-#[derive(Default, Hash, Copy, Clone, PartialEq, Eq)]
+#[derive(Default, Hash, Clone, PartialEq, Eq)]
 pub struct StatusParams {
 	tick: u128,
 	status_type: StatusType,
 	config: Option<ShowUntrackedFilesConfig>,
+	scope: Option<String>,
 }
 
 impl StatusParams {
@@ -46,8 +47,30 @@ impl StatusParams {
 			tick: current_tick(),
 			status_type,
 			config,
+			scope: None,
 		}
 	}
+
+	/// like `new` but keyed by `key` instead of the current time,
+	/// so repeated requests with the same key reuse the cached result
+	pub fn new_keyed(
+		status_type: StatusType,
+		config: Option<ShowUntrackedFilesConfig>,
+		key: u64,
+	) -> Self {
+		Self {
+			tick: u128::from(key),
+			status_type,
+			config,
+			scope: None,
+		}
+	}
+
+	/// limits the status to files inside folder `scope`
+	#[must_use]
+	pub fn scoped(self, scope: Option<String>) -> Self {
+		Self { scope, ..self }
+	}
 }
 
 struct Request<R, A>(R, Option<A>);
@@ -122,6 +145,7 @@ impl AsyncStatus {
 		let arc_pending = Arc::clone(&self.pending);
 		let status_type = params.status_type;
 		let config = params.config;
+		let scope = params.scope.clone();
 		let repo = self.repo.clone();
 
 		self.pending.fetch_add(1, Ordering::Relaxed);
@@ -131,6 +155,7 @@ impl AsyncStatus {
 				&repo,
 				status_type,
 				config,
+				scope.as_deref(),
 				hash_request,
 				&arc_current,
 				&arc_last,
@@ -152,11 +177,12 @@ impl AsyncStatus {
 		repo: &RepoPath,
 		status_type: StatusType,
 		config: Option<ShowUntrackedFilesConfig>,
+		scope: Option<&str>,
 		hash_request: u64,
 		arc_current: &Arc<Mutex<Request<u64, Status>>>,
 		arc_last: &Arc<Mutex<Status>>,
 	) -> Result<()> {
-		let res = Self::get_status(repo, status_type, config)?;
+		let res = Self::get_status(repo, status_type, config, scope)?;
 		log::trace!(
 			"status fetched: {} (type: {:?})",
 			hash_request,
@@ -182,12 +208,14 @@ impl AsyncStatus {
 		repo: &RepoPath,
 		status_type: StatusType,
 		config: Option<ShowUntrackedFilesConfig>,
+		scope: Option<&str>,
 	) -> Result<Status> {
 		Ok(Status {
-			items: sync::status::get_status(
+			items: sync::status::get_status_scoped(
 				repo,
 				status_type,
 				config,
+				scope,
 			)?,
 		})
 	}
@@ -0,0 +1,132 @@
+//!
+
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	remote_progress::RemoteProgress,
+	sync::{
+		remotes::push::ProgressNotification, update_submodule,
+		RepoPath,
+	},
+	AsyncGitNotification,
+};
+use crossbeam_channel::unbounded;
+use std::{
+	sync::{Arc, Mutex},
+	thread,
+	time::Instant,
+};
+
+enum JobState {
+	Request(String),
+	Response(Result<()>),
+}
+
+/// clones or fetches a submodule and checks out the commit the
+/// parent records for it
+#[derive(Clone)]
+pub struct AsyncSubmoduleUpdateJob {
+	state: Arc<Mutex<Option<JobState>>>,
+	repo: RepoPath,
+}
+
+impl AsyncSubmoduleUpdateJob {
+	///
+	pub fn new(repo: RepoPath, name: String) -> Self {
+		Self {
+			repo,
+			state: Arc::new(Mutex::new(Some(JobState::Request(
+				name,
+			)))),
+		}
+	}
+
+	///
+	pub fn result(&self) -> Option<Result<()>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request(_) => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+
+	fn update(
+		&self,
+		name: &str,
+		params: &RunParams<
+			AsyncGitNotification,
+			Option<RemoteProgress>,
+		>,
+	) -> Result<()> {
+		params.set_progress(None)?;
+
+		let (progress_sender, receiver) = unbounded();
+
+		thread::scope(|scope| {
+			scope.spawn(move || {
+				RemoteProgress::forward_progress(
+					&receiver,
+					Instant::now,
+					|update: &ProgressNotification| {
+						if let Err(e) = params
+							.set_progress(Some(update.clone().into()))
+						{
+							log::error!(
+								"submodule update progress error: {}",
+								e
+							);
+						}
+					},
+					|| {
+						if let Err(e) = params.send(
+							AsyncGitNotification::SubmoduleUpdate,
+						) {
+							log::error!(
+								"submodule update notify error: {}",
+								e
+							);
+						}
+					},
+				);
+			});
+
+			let result = update_submodule(
+				&self.repo,
+				name,
+				Some(progress_sender.clone()),
+			);
+
+			progress_sender.send(ProgressNotification::Done)?;
+
+			result
+		})
+	}
+}
+
+impl AsyncJob for AsyncSubmoduleUpdateJob {
+	type Notification = AsyncGitNotification;
+	type Progress = Option<RemoteProgress>;
+
+	fn run(
+		&mut self,
+		params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request(name) => {
+					JobState::Response(self.update(&name, &params))
+				}
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::SubmoduleUpdate)
+	}
+}
@@ -0,0 +1,328 @@
+//! binary content is summarized instead of shown as text, for images
+//! that includes their dimensions read from the header
+
+use super::commit_files::OldNew;
+use crate::error::Result;
+use git2::{Diff, DiffFile, Repository};
+use std::{fs::File, io::Read, path::Path};
+
+/// all of the supported headers (and the jpeg segments before the
+/// frame in most files) fit into this
+const IMAGE_HEADER_SIZE: u64 = 64 * 1024;
+
+///
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum ImageFormat {
+	///
+	Png,
+	///
+	Gif,
+	///
+	Jpeg,
+	///
+	Bmp,
+	///
+	WebP,
+}
+
+impl ImageFormat {
+	///
+	pub const fn name(self) -> &'static str {
+		match self {
+			Self::Png => "PNG",
+			Self::Gif => "GIF",
+			Self::Jpeg => "JPEG",
+			Self::Bmp => "BMP",
+			Self::WebP => "WebP",
+		}
+	}
+}
+
+/// dimensions of an image in pixels
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ImageSize {
+	///
+	pub format: ImageFormat,
+	///
+	pub width: u32,
+	///
+	pub height: u32,
+}
+
+fn u16_be(bytes: &[u8], at: usize) -> Option<u32> {
+	let b = bytes.get(at..at + 2)?;
+	Some(u32::from(u16::from_be_bytes([b[0], b[1]])))
+}
+
+fn u16_le(bytes: &[u8], at: usize) -> Option<u32> {
+	let b = bytes.get(at..at + 2)?;
+	Some(u32::from(u16::from_le_bytes([b[0], b[1]])))
+}
+
+fn u24_le(bytes: &[u8], at: usize) -> Option<u32> {
+	let b = bytes.get(at..at + 3)?;
+	Some(u32::from_le_bytes([b[0], b[1], b[2], 0]))
+}
+
+fn u32_be(bytes: &[u8], at: usize) -> Option<u32> {
+	let b = bytes.get(at..at + 4)?;
+	Some(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn u32_le(bytes: &[u8], at: usize) -> Option<u32> {
+	let b = bytes.get(at..at + 4)?;
+	Some(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// `None` unless `content` starts like one of the `ImageFormat`s
+pub fn image_size(content: &[u8]) -> Option<ImageSize> {
+	let (format, width, height) = if content
+		.starts_with(b"\x89PNG\r\n\x1a\n")
+	{
+		(ImageFormat::Png, u32_be(content, 16)?, u32_be(content, 20)?)
+	} else if content.starts_with(b"GIF87a")
+		|| content.starts_with(b"GIF89a")
+	{
+		(ImageFormat::Gif, u16_le(content, 6)?, u16_le(content, 8)?)
+	} else if content.starts_with(b"BM") {
+		bmp_size(content)?
+	} else if content.starts_with(b"RIFF")
+		&& content.get(8..12) == Some(b"WEBP")
+	{
+		webp_size(content)?
+	} else if content.starts_with(&[0xff, 0xd8]) {
+		jpeg_size(content)?
+	} else {
+		return None;
+	};
+
+	Some(ImageSize {
+		format,
+		width,
+		height,
+	})
+}
+
+fn bmp_size(content: &[u8]) -> Option<(ImageFormat, u32, u32)> {
+	// the old os/2 header has 16 bit dimensions
+	if u32_le(content, 14)? == 12 {
+		return Some((
+			ImageFormat::Bmp,
+			u16_le(content, 18)?,
+			u16_le(content, 20)?,
+		));
+	}
+
+	// a negative height stores the rows top-down
+	let height = content.get(22..26)?;
+	let height = i32::from_le_bytes([
+		height[0], height[1], height[2], height[3],
+	])
+	.unsigned_abs();
+
+	Some((ImageFormat::Bmp, u32_le(content, 18)?, height))
+}
+
+fn webp_size(content: &[u8]) -> Option<(ImageFormat, u32, u32)> {
+	let (width, height) = match content.get(12..16)? {
+		b"VP8 " => (
+			u16_le(content, 26)? & 0x3fff,
+			u16_le(content, 28)? & 0x3fff,
+		),
+		b"VP8L" => {
+			let bits = u32_le(content, 21)?;
+			((bits & 0x3fff) + 1, ((bits >> 14) & 0x3fff) + 1)
+		}
+		b"VP8X" => {
+			(u24_le(content, 24)? + 1, u24_le(content, 27)? + 1)
+		}
+		_ => return None,
+	};
+
+	Some((ImageFormat::WebP, width, height))
+}
+
+/// walks the segments up to the start of the frame
+fn jpeg_size(content: &[u8]) -> Option<(ImageFormat, u32, u32)> {
+	let mut at = 2;
+
+	loop {
+		if *content.get(at)? != 0xff {
+			return None;
+		}
+
+		let marker = *content.get(at + 1)?;
+		match marker {
+			// fill bytes
+			0xff => at += 1,
+			// no length
+			0x01 | 0xd0..=0xd7 => at += 2,
+			// start of frame, but not huffman tables and the like
+			0xc0..=0xcf if !matches!(marker, 0xc4 | 0xc8 | 0xcc) => {
+				return Some((
+					ImageFormat::Jpeg,
+					u16_be(content, at + 7)?,
+					u16_be(content, at + 5)?,
+				));
+			}
+			_ => {
+				at += 2 + usize::try_from(u16_be(content, at + 2)?)
+					.ok()?;
+			}
+		}
+	}
+}
+
+/// old and new image size of a diff of a single file, only the sides
+/// in `odb` are read from the object database, the others from the
+/// working tree
+pub fn diff_image_sizes(
+	repo: &Repository,
+	diff: &Diff,
+	work_dir: &Path,
+	odb: OldNew<bool>,
+) -> Result<(Option<ImageSize>, Option<ImageSize>)> {
+	let Some(delta) =
+		diff.get_delta(0).filter(|_| diff.deltas().len() == 1)
+	else {
+		return Ok((None, None));
+	};
+
+	Ok((
+		file_image_size(repo, work_dir, odb.old, &delta.old_file())?,
+		file_image_size(repo, work_dir, odb.new, &delta.new_file())?,
+	))
+}
+
+fn file_image_size(
+	repo: &Repository,
+	work_dir: &Path,
+	in_odb: bool,
+	file: &DiffFile,
+) -> Result<Option<ImageSize>> {
+	if in_odb {
+		if file.id().is_zero() {
+			return Ok(None);
+		}
+
+		return Ok(image_size(repo.find_blob(file.id())?.content()));
+	}
+
+	let Some(path) = file.path() else {
+		return Ok(None);
+	};
+	let Ok(file) = File::open(work_dir.join(path)) else {
+		return Ok(None);
+	};
+
+	let mut header = Vec::new();
+	file.take(IMAGE_HEADER_SIZE).read_to_end(&mut header)?;
+
+	Ok(image_size(&header))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		commit,
+		diff::get_diff_commit,
+		tests::{repo_init, write_commit_file},
+		utils::{repo_write_file, stage_add_file},
+		RepoPath,
+	};
+
+	fn png(width: u32, height: u32) -> Vec<u8> {
+		let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+		png.extend(width.to_be_bytes());
+		png.extend(height.to_be_bytes());
+		png.extend([8, 6, 0, 0, 0]);
+		png
+	}
+
+	const fn size(
+		format: ImageFormat,
+		width: u32,
+		height: u32,
+	) -> ImageSize {
+		ImageSize {
+			format,
+			width,
+			height,
+		}
+	}
+
+	#[test]
+	fn test_image_sizes() {
+		assert_eq!(
+			image_size(&png(640, 480)),
+			Some(size(ImageFormat::Png, 640, 480))
+		);
+
+		let gif = b"GIF89a\x40\x01\xf0\x00\x80\x00\x00";
+		assert_eq!(
+			image_size(gif),
+			Some(size(ImageFormat::Gif, 320, 240))
+		);
+
+		let mut bmp = b"BM".to_vec();
+		bmp.extend([0; 12]);
+		bmp.extend(40_u32.to_le_bytes());
+		bmp.extend(100_u32.to_le_bytes());
+		bmp.extend((-50_i32).to_le_bytes());
+		assert_eq!(
+			image_size(&bmp),
+			Some(size(ImageFormat::Bmp, 100, 50))
+		);
+
+		let mut webp =
+			b"RIFF\0\0\0\0WEBPVP8X\x0a\0\0\0\0\0\0\0".to_vec();
+		webp.extend([0x1f, 0x03, 0x00, 0xff, 0x00, 0x00]);
+		assert_eq!(
+			image_size(&webp),
+			Some(size(ImageFormat::WebP, 800, 256))
+		);
+
+		// app0 segment, then the baseline frame
+		let mut jpeg = vec![0xff, 0xd8, 0xff, 0xe0, 0x00, 0x04, 0, 0];
+		jpeg.extend([0xff, 0xc0, 0x00, 0x11, 0x08]);
+		jpeg.extend(600_u16.to_be_bytes());
+		jpeg.extend(1024_u16.to_be_bytes());
+		assert_eq!(
+			image_size(&jpeg),
+			Some(size(ImageFormat::Jpeg, 1024, 600))
+		);
+
+		assert_eq!(image_size(b"plain text"), None);
+		assert_eq!(image_size(&png(1, 1)[..20]), None);
+		assert_eq!(image_size(&[0xff, 0xd8, 0xff, 0xe0, 0xff]), None);
+	}
+
+	#[test]
+	fn test_diff_of_images() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		repo_write_file(&repo, "a.png", png(10, 20)).unwrap();
+		stage_add_file(repo_path, Path::new("a.png")).unwrap();
+		let id = commit(repo_path, "c1").unwrap();
+
+		let diff =
+			get_diff_commit(repo_path, id, "a.png".into(), None)
+				.unwrap();
+		assert!(diff.hunks.is_empty());
+		assert_eq!(diff.images.0, None);
+		assert_eq!(
+			diff.images.1,
+			Some(size(ImageFormat::Png, 10, 20))
+		);
+
+		let id = write_commit_file(&repo, "b.txt", "text", "c2");
+		let diff =
+			get_diff_commit(repo_path, id, "b.txt".into(), None)
+				.unwrap();
+		assert_eq!(diff.images, (None, None));
+	}
+}
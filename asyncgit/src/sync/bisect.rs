@@ -0,0 +1,523 @@
+//! `git bisect`, kept in the same files and refs git uses so a
+//! session can be carried on from the command line and back
+
+use super::{repository::repo, CommitId, RepoPath};
+use crate::error::Result;
+use git2::{
+	build::CheckoutBuilder, BranchType, Oid, Repository, Sort,
+};
+use scopetime::scope_time;
+use std::{
+	collections::{HashMap, HashSet},
+	fs::{self, OpenOptions},
+	io::Write,
+};
+
+const BISECT_START: &str = "BISECT_START";
+const BISECT_LOG: &str = "BISECT_LOG";
+const BISECT_NAMES: &str = "BISECT_NAMES";
+const BISECT_TERMS: &str = "BISECT_TERMS";
+const BISECT_EXPECTED_REV: &str = "BISECT_EXPECTED_REV";
+
+/// everything git might leave in the git dir while bisecting
+const BISECT_FILES: [&str; 8] = [
+	BISECT_START,
+	BISECT_LOG,
+	BISECT_NAMES,
+	BISECT_TERMS,
+	BISECT_EXPECTED_REV,
+	"BISECT_ANCESTORS_OK",
+	"BISECT_HEAD",
+	"BISECT_RUN",
+];
+
+/// above this many candidates their weights are only estimated
+const EXACT_WEIGHTS_LIMIT: usize = 4096;
+
+/// the verdict on a tested commit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BisectMark {
+	///
+	Good,
+	///
+	Bad,
+	/// cannot be tested
+	Skip,
+}
+
+/// where a bisect session stands
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BisectState {
+	/// `None` until a commit is marked bad
+	pub bad: Option<CommitId>,
+	///
+	pub good: Vec<CommitId>,
+	///
+	pub skipped: Vec<CommitId>,
+	/// commits that might still be the first bad one besides `bad`,
+	/// not counting the skipped ones
+	pub left: usize,
+	/// roughly how many more commits need testing
+	pub steps: usize,
+	/// the commit checked out for testing
+	pub next: Option<CommitId>,
+	/// the first bad commit once it is found
+	pub culprit: Option<CommitId>,
+}
+
+/// `git bisect start --term-new/--term-old` renames bad and good
+struct Terms {
+	bad: String,
+	good: String,
+}
+
+impl Terms {
+	fn read(repo: &Repository) -> Self {
+		let terms =
+			fs::read_to_string(repo.path().join(BISECT_TERMS))
+				.unwrap_or_default();
+		let mut lines = terms.lines().map(str::trim);
+
+		match (lines.next(), lines.next()) {
+			(Some(bad), Some(good))
+				if !bad.is_empty() && !good.is_empty() =>
+			{
+				Self {
+					bad: bad.to_string(),
+					good: good.to_string(),
+				}
+			}
+			_ => Self {
+				bad: String::from("bad"),
+				good: String::from("good"),
+			},
+		}
+	}
+}
+
+/// `None` unless a bisect session is running
+pub fn bisect_state(
+	repo_path: &RepoPath,
+) -> Result<Option<BisectState>> {
+	scope_time!("bisect_state");
+
+	let repo = repo(repo_path)?;
+
+	if !is_bisecting(&repo) {
+		return Ok(None);
+	}
+
+	Ok(Some(read_state(&repo)?))
+}
+
+/// marks `id` like `git bisect good|bad|skip <id>` does
+///
+/// starts a session first if there is none. once a good and a bad
+/// commit are known the next one to test is checked out, in the end
+/// the first bad commit
+pub fn bisect_mark(
+	repo_path: &RepoPath,
+	id: CommitId,
+	mark: BisectMark,
+) -> Result<BisectState> {
+	scope_time!("bisect_mark");
+
+	let repo = repo(repo_path)?;
+
+	if !is_bisecting(&repo) {
+		start(&repo)?;
+	}
+
+	let terms = Terms::read(&repo);
+	let (term, refname) = match mark {
+		BisectMark::Bad => {
+			(terms.bad.as_str(), format!("refs/bisect/{}", terms.bad))
+		}
+		BisectMark::Good => (
+			terms.good.as_str(),
+			format!("refs/bisect/{}-{id}", terms.good),
+		),
+		BisectMark::Skip => {
+			("skip", format!("refs/bisect/skip-{id}"))
+		}
+	};
+
+	let summary = commit_summary(&repo, id)?;
+	repo.reference(
+		&refname,
+		id.into(),
+		true,
+		&format!("bisect {term}"),
+	)?;
+	append_log(
+		&repo,
+		&format!(
+			"# {term}: [{id}] {summary}\ngit bisect {term} {id}\n"
+		),
+	)?;
+
+	let state = read_state(&repo)?;
+
+	if let Some(culprit) = state.culprit {
+		let summary = commit_summary(&repo, culprit)?;
+		append_log(
+			&repo,
+			&format!("# first bad commit: [{culprit}] {summary}\n"),
+		)?;
+		let commit = repo.find_commit(culprit.into())?;
+		checkout_detached(&repo, &commit)?;
+	} else if let Some(next) = state.next {
+		let commit = repo.find_commit(next.into())?;
+		checkout_detached(&repo, &commit)?;
+		fs::write(
+			repo.path().join(BISECT_EXPECTED_REV),
+			format!("{next}\n"),
+		)?;
+	}
+
+	Ok(state)
+}
+
+/// ends the session like `git bisect reset`, checking out what was
+/// checked out when it started
+pub fn bisect_reset(repo_path: &RepoPath) -> Result<()> {
+	scope_time!("bisect_reset");
+
+	let repo = repo(repo_path)?;
+	let git_dir = repo.path().to_path_buf();
+
+	if let Ok(start) = fs::read_to_string(git_dir.join(BISECT_START))
+	{
+		let start = start.trim();
+
+		if let Ok(branch) = repo.find_branch(start, BranchType::Local)
+		{
+			let commit = branch.get().peel_to_commit()?;
+			checkout_detached(&repo, &commit)?;
+			if let Some(name) = branch.get().name() {
+				repo.set_head(name)?;
+			}
+		} else {
+			let commit =
+				repo.revparse_single(start)?.peel_to_commit()?;
+			checkout_detached(&repo, &commit)?;
+		}
+	}
+
+	let refs: Vec<String> = repo
+		.references_glob("refs/bisect/*")?
+		.flatten()
+		.filter_map(|reference| reference.name().map(String::from))
+		.collect();
+	for name in refs {
+		repo.find_reference(&name)?.delete()?;
+	}
+
+	for file in BISECT_FILES {
+		let path = git_dir.join(file);
+		if path.exists() {
+			fs::remove_file(path)?;
+		}
+	}
+
+	Ok(())
+}
+
+fn is_bisecting(repo: &Repository) -> bool {
+	repo.path().join(BISECT_START).exists()
+}
+
+/// writes the files `git bisect start` does, remembering the branch
+/// to go back to on reset
+fn start(repo: &Repository) -> Result<()> {
+	let head = repo.head()?;
+	let start = if head.is_branch() {
+		head.shorthand().unwrap_or_default().to_string()
+	} else {
+		head.peel_to_commit()?.id().to_string()
+	};
+
+	let git_dir = repo.path();
+	fs::write(git_dir.join(BISECT_START), format!("{start}\n"))?;
+	fs::write(git_dir.join(BISECT_TERMS), "bad\ngood\n")?;
+	fs::write(git_dir.join(BISECT_NAMES), "\n")?;
+	fs::write(git_dir.join(BISECT_LOG), "git bisect start\n")?;
+
+	Ok(())
+}
+
+fn append_log(repo: &Repository, text: &str) -> Result<()> {
+	let mut file = OpenOptions::new()
+		.create(true)
+		.append(true)
+		.open(repo.path().join(BISECT_LOG))?;
+	file.write_all(text.as_bytes())?;
+
+	Ok(())
+}
+
+fn commit_summary(repo: &Repository, id: CommitId) -> Result<String> {
+	Ok(repo
+		.find_commit(id.into())?
+		.summary()
+		.unwrap_or_default()
+		.to_string())
+}
+
+/// moves `HEAD` to `commit`, local changes in the way fail the
+/// checkout instead of getting lost
+fn checkout_detached(
+	repo: &Repository,
+	commit: &git2::Commit,
+) -> Result<()> {
+	repo.checkout_tree(
+		commit.as_object(),
+		Some(CheckoutBuilder::new().safe()),
+	)?;
+	repo.set_head_detached(commit.id())?;
+
+	Ok(())
+}
+
+fn read_state(repo: &Repository) -> Result<BisectState> {
+	let terms = Terms::read(repo);
+	let good_prefix = format!("{}-", terms.good);
+
+	let mut state = BisectState::default();
+
+	for reference in repo.references_glob("refs/bisect/*")?.flatten()
+	{
+		let (Some(name), Some(id)) = (
+			reference
+				.name()
+				.and_then(|name| name.strip_prefix("refs/bisect/")),
+			reference.target(),
+		) else {
+			continue;
+		};
+
+		if name == terms.bad {
+			state.bad = Some(id.into());
+		} else if name.starts_with(&good_prefix) {
+			state.good.push(id.into());
+		} else if name.starts_with("skip-") {
+			state.skipped.push(id.into());
+		}
+	}
+
+	if let (Some(bad), false) = (state.bad, state.good.is_empty()) {
+		let candidates = candidates(repo, bad, &state.good)?;
+		let weights = weights(repo, &candidates)?;
+		let all = candidates.len();
+
+		let bad: Oid = bad.into();
+		let skipped: HashSet<Oid> =
+			state.skipped.iter().map(|id| (*id).into()).collect();
+
+		let testable: Vec<(Oid, usize)> = candidates
+			.iter()
+			.zip(weights)
+			.filter(|(id, _)| **id != bad && !skipped.contains(id))
+			.map(|(id, weight)| (*id, weight))
+			.collect();
+
+		state.left = testable.len();
+		state.steps =
+			(usize::BITS - state.left.leading_zeros()) as usize;
+
+		// testing it rules out about half either way
+		state.next = testable
+			.iter()
+			.max_by_key(|(_, weight)| (*weight).min(all - weight))
+			.map(|(id, _)| (*id).into());
+
+		if all == 1 {
+			state.culprit = Some(bad.into());
+		}
+	}
+
+	Ok(state)
+}
+
+/// the commits that might be the first bad one, parents before their
+/// children
+fn candidates(
+	repo: &Repository,
+	bad: CommitId,
+	good: &[CommitId],
+) -> Result<Vec<Oid>> {
+	let mut walk = repo.revwalk()?;
+	walk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+	walk.push(bad.into())?;
+	for id in good {
+		walk.hide((*id).into())?;
+	}
+
+	Ok(walk.collect::<std::result::Result<_, _>>()?)
+}
+
+/// for each candidate how many candidates it reaches, itself
+/// included: the ones left if it turns out bad
+fn weights(
+	repo: &Repository,
+	candidates: &[Oid],
+) -> Result<Vec<usize>> {
+	if candidates.len() > EXACT_WEIGHTS_LIMIT {
+		// as if the history was linear
+		return Ok((1..=candidates.len()).collect());
+	}
+
+	let index: HashMap<Oid, usize> = candidates
+		.iter()
+		.enumerate()
+		.map(|(idx, id)| (*id, idx))
+		.collect();
+	let words = candidates.len().div_ceil(64);
+
+	let mut reaches: Vec<Vec<u64>> =
+		Vec::with_capacity(candidates.len());
+	for (idx, id) in candidates.iter().enumerate() {
+		let mut bits = vec![0_u64; words];
+		bits[idx / 64] |= 1 << (idx % 64);
+
+		for parent in repo.find_commit(*id)?.parent_ids() {
+			if let Some(parent) = index.get(&parent) {
+				for (word, parent_word) in
+					bits.iter_mut().zip(&reaches[*parent])
+				{
+					*word |= parent_word;
+				}
+			}
+		}
+
+		reaches.push(bits);
+	}
+
+	Ok(reaches
+		.iter()
+		.map(|bits| {
+			bits.iter().map(|word| word.count_ones() as usize).sum()
+		})
+		.collect())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::{repo_init, write_commit_file};
+
+	#[test]
+	fn test_bisect_finds_first_bad() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let commits: Vec<CommitId> = (0..8)
+			.map(|idx| {
+				write_commit_file(
+					&repo,
+					"a",
+					&idx.to_string(),
+					&format!("c{idx}"),
+				)
+			})
+			.collect();
+		let first_bad = 5;
+		let branch =
+			repo.head().unwrap().shorthand().unwrap().to_string();
+
+		assert_eq!(bisect_state(repo_path).unwrap(), None);
+
+		let state =
+			bisect_mark(repo_path, commits[7], BisectMark::Bad)
+				.unwrap();
+		assert_eq!(state.bad, Some(commits[7]));
+		assert_eq!(state.next, None);
+		assert!(repo.head().unwrap().is_branch());
+
+		let mut state =
+			bisect_mark(repo_path, commits[0], BisectMark::Good)
+				.unwrap();
+		assert_eq!(state.left, 6);
+		assert_eq!(state.steps, 3);
+
+		let mut tested = 0;
+		while let Some(next) = state.next {
+			assert_eq!(
+				CommitId::from(
+					repo.head().unwrap().target().unwrap()
+				),
+				next
+			);
+			assert!(repo.head_detached().unwrap());
+
+			let idx =
+				commits.iter().position(|id| *id == next).unwrap();
+			let mark = if idx >= first_bad {
+				BisectMark::Bad
+			} else {
+				BisectMark::Good
+			};
+			state = bisect_mark(repo_path, next, mark).unwrap();
+			tested += 1;
+		}
+
+		assert!(tested <= 3);
+		assert_eq!(state.culprit, Some(commits[first_bad]));
+		assert_eq!(
+			CommitId::from(repo.head().unwrap().target().unwrap()),
+			commits[first_bad]
+		);
+		assert_eq!(bisect_state(repo_path).unwrap(), Some(state));
+
+		let log =
+			fs::read_to_string(repo.path().join(BISECT_LOG)).unwrap();
+		assert!(log.starts_with("git bisect start\n"));
+		assert!(
+			log.contains(&format!("git bisect bad {}", commits[7]))
+		);
+		assert!(log.contains(&format!(
+			"# first bad commit: [{}] c{first_bad}",
+			commits[first_bad]
+		)));
+
+		bisect_reset(repo_path).unwrap();
+
+		assert_eq!(bisect_state(repo_path).unwrap(), None);
+		assert_eq!(repo.head().unwrap().shorthand().unwrap(), branch);
+		assert_eq!(
+			CommitId::from(repo.head().unwrap().target().unwrap()),
+			commits[7]
+		);
+		assert_eq!(
+			repo.references_glob("refs/bisect/*").unwrap().count(),
+			0
+		);
+		assert!(!repo.path().join(BISECT_LOG).exists());
+	}
+
+	#[test]
+	fn test_bisect_only_skipped_left() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a", "1", "c1");
+		let c2 = write_commit_file(&repo, "a", "2", "c2");
+		let c3 = write_commit_file(&repo, "a", "3", "c3");
+
+		bisect_mark(repo_path, c3, BisectMark::Bad).unwrap();
+		let state =
+			bisect_mark(repo_path, c1, BisectMark::Good).unwrap();
+		assert_eq!(state.next, Some(c2));
+
+		let state =
+			bisect_mark(repo_path, c2, BisectMark::Skip).unwrap();
+		assert_eq!(state.skipped, vec![c2]);
+		assert_eq!(state.left, 0);
+		assert_eq!(state.next, None);
+		assert_eq!(state.culprit, None);
+	}
+}
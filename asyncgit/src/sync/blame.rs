@@ -1,11 +1,14 @@
 //! Sync git API for fetching a file blame
 
-use super::{utils, CommitId, RepoPath};
+use super::{
+	shallow::{shallow_commits_repo, shallow_error},
+	utils, CommitId, RepoPath,
+};
 use crate::{
 	error::{Error, Result},
 	sync::{get_commits_info, repository::repo},
 };
-use git2::BlameOptions;
+use git2::{BlameOptions, Blob, Repository};
 use scopetime::scope_time;
 use std::collections::{HashMap, HashSet};
 use std::io::{BufRead, BufReader};
@@ -38,6 +41,9 @@ pub struct FileBlame {
 	pub path: String,
 	///
 	pub lines: Vec<(Option<BlameHunk>, String)>,
+	/// some lines are put on a commit the history of a shallow clone
+	/// ends at, they may be older
+	pub truncated: bool,
 }
 
 /// fixup `\` windows path separators to git compatible `/`
@@ -53,14 +59,13 @@ fn fixup_windows_path(path: &str) -> String {
 	}
 }
 
-///
-pub fn blame_file(
+/// checks whether `file_path` can be blamed in `commit_id` (or
+/// `HEAD`), fails if it is binary or not part of that revision
+pub fn blame_file_check(
 	repo_path: &RepoPath,
 	file_path: &str,
 	commit_id: Option<CommitId>,
-) -> Result<FileBlame> {
-	scope_time!("blame_file");
-
+) -> Result<()> {
 	let repo = repo(repo_path)?;
 
 	let commit_id = if let Some(commit_id) = commit_id {
@@ -69,21 +74,61 @@ pub fn blame_file(
 		utils::get_head_repo(&repo)?
 	};
 
+	blame_blob(&repo, file_path, commit_id).map(|_| ())
+}
+
+fn blame_blob<'a>(
+	repo: &'a Repository,
+	file_path: &str,
+	commit_id: CommitId,
+) -> Result<Blob<'a>> {
+	if repo.is_shallow()
+		&& repo.find_commit(commit_id.into()).is_err()
+	{
+		return Err(Error::ShallowHistory);
+	}
+
 	let spec =
 		format!("{}:{}", commit_id, fixup_windows_path(file_path));
 
-	let object = repo.revparse_single(&spec)?;
-	let blob = repo.find_blob(object.id())?;
+	let object = repo
+		.revparse_single(&spec)
+		.map_err(|_| Error::NoBlameOnUntrackedFile)?;
+	let blob = repo
+		.find_blob(object.id())
+		.map_err(|_| Error::NoBlameOnUntrackedFile)?;
 
 	if blob.is_binary() {
 		return Err(Error::NoBlameOnBinaryFile);
 	}
 
+	Ok(blob)
+}
+
+///
+pub fn blame_file(
+	repo_path: &RepoPath,
+	file_path: &str,
+	commit_id: Option<CommitId>,
+) -> Result<FileBlame> {
+	scope_time!("blame_file");
+
+	let repo = repo(repo_path)?;
+
+	let commit_id = if let Some(commit_id) = commit_id {
+		commit_id
+	} else {
+		utils::get_head_repo(&repo)?
+	};
+
+	let blob = blame_blob(&repo, file_path, commit_id)?;
+
 	let mut opts = BlameOptions::new();
 	opts.newest_commit(commit_id.into());
 
-	let blame =
-		repo.blame_file(Path::new(file_path), Some(&mut opts))?;
+	let blame = repo
+		.blame_file(Path::new(file_path), Some(&mut opts))
+		.map_err(|e| shallow_error(&repo, e))?;
 
 	let reader = BufReader::new(blob.content());
 
@@ -91,6 +136,9 @@ pub fn blame_file(
 		.iter()
 		.map(|hunk| CommitId::new(hunk.final_commit_id()))
 		.collect();
+	let truncated = shallow_commits_repo(&repo)?
+		.iter()
+		.any(|id| unique_commit_ids.contains(id));
 	let mut commit_ids = Vec::with_capacity(unique_commit_ids.len());
 	commit_ids.extend(unique_commit_ids);
 
@@ -141,6 +189,7 @@ pub fn blame_file(
 		commit_id,
 		path: file_path.into(),
 		lines,
+		truncated,
 	};
 
 	Ok(file_blame)
@@ -239,6 +288,34 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_blame_file_check() -> Result<()> {
+		let (_td, repo) = repo_init_empty()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(root.join("foo"))?.write_all(b"line 1\n")?;
+		File::create(root.join("bin"))?.write_all(b"\0\x01\x02")?;
+		stage_add_file(repo_path, Path::new("foo"))?;
+		stage_add_file(repo_path, Path::new("bin"))?;
+		commit(repo_path, "first commit")?;
+
+		File::create(root.join("untracked"))?.write_all(b"new\n")?;
+
+		assert!(blame_file_check(repo_path, "foo", None).is_ok());
+		assert!(matches!(
+			blame_file_check(repo_path, "bin", None),
+			Err(Error::NoBlameOnBinaryFile)
+		));
+		assert!(matches!(
+			blame_file_check(repo_path, "untracked", None),
+			Err(Error::NoBlameOnUntrackedFile)
+		));
+
+		Ok(())
+	}
+
 	#[test]
 	fn test_blame_windows_path_dividers() {
 		let file_path = Path::new("bar\\foo");
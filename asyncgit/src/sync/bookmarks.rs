@@ -0,0 +1,259 @@
+//! named commits kept per repo inside the git dir (not as refs, so
+//! they are never pushed and do not keep commits alive)
+
+use super::{
+	commit_files::get_commit_diff, repository::repo,
+	utils::gitui_dir, CommitId, RepoPath,
+};
+use crate::error::{Error, Result};
+use git2::{Oid, Repository};
+use scopetime::scope_time;
+use std::fs;
+
+const BOOKMARKS_FILE: &str = "bookmarks";
+/// how many commits of `HEAD` are searched for a matching patch
+const PATCH_ID_SEARCH_LIMIT: usize = 10_000;
+
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bookmark {
+	///
+	pub name: String,
+	///
+	pub id: CommitId,
+}
+
+impl Bookmark {
+	fn to_line(&self) -> String {
+		format!("{}\t{}\n", self.id, self.name)
+	}
+
+	fn from_line(line: &str) -> Option<Self> {
+		let (id, name) = line.split_once('\t')?;
+
+		Some(Self {
+			name: name.to_string(),
+			id: CommitId::from_str_unchecked(id).ok()?,
+		})
+	}
+}
+
+/// all bookmarks sorted by name
+pub fn get_bookmarks(repo_path: &RepoPath) -> Result<Vec<Bookmark>> {
+	let path = gitui_dir(repo_path)?.join(BOOKMARKS_FILE);
+
+	let Ok(content) = fs::read_to_string(path) else {
+		return Ok(Vec::new());
+	};
+
+	let mut bookmarks: Vec<Bookmark> =
+		content.lines().filter_map(Bookmark::from_line).collect();
+	bookmarks.sort_by(|a, b| a.name.cmp(&b.name));
+
+	Ok(bookmarks)
+}
+
+fn write_bookmarks(
+	repo_path: &RepoPath,
+	bookmarks: &[Bookmark],
+) -> Result<()> {
+	let dir = gitui_dir(repo_path)?;
+	fs::create_dir_all(&dir)?;
+
+	let content: String =
+		bookmarks.iter().map(Bookmark::to_line).collect();
+	fs::write(dir.join(BOOKMARKS_FILE), content)?;
+
+	Ok(())
+}
+
+/// bookmarks `id` as `name`, moving an existing bookmark of that name
+pub fn bookmark_commit(
+	repo_path: &RepoPath,
+	name: &str,
+	id: CommitId,
+) -> Result<()> {
+	scope_time!("bookmark_commit");
+
+	let name = name.replace(['\t', '\n', '\r'], " ");
+	let name = name.trim();
+
+	if name.is_empty() {
+		return Err(Error::Generic(String::from(
+			"bookmark name must not be empty",
+		)));
+	}
+
+	let mut bookmarks = get_bookmarks(repo_path)?;
+	bookmarks.retain(|bookmark| bookmark.name != name);
+	bookmarks.push(Bookmark {
+		name: name.to_string(),
+		id,
+	});
+
+	write_bookmarks(repo_path, &bookmarks)
+}
+
+/// removes the bookmark called `name`, missing ones are ignored
+pub fn delete_bookmark(
+	repo_path: &RepoPath,
+	name: &str,
+) -> Result<()> {
+	scope_time!("delete_bookmark");
+
+	let mut bookmarks = get_bookmarks(repo_path)?;
+	bookmarks.retain(|bookmark| bookmark.name != name);
+
+	write_bookmarks(repo_path, &bookmarks)
+}
+
+/// `true` if `id` is `HEAD` or in the history of any local or remote
+/// branch, bookmarks of rewritten or gc'ed commits are not
+pub fn bookmark_reachable(
+	repo_path: &RepoPath,
+	id: CommitId,
+) -> Result<bool> {
+	scope_time!("bookmark_reachable");
+
+	let repo = repo(repo_path)?;
+	let id: Oid = id.into();
+
+	if repo.find_commit(id).is_err() {
+		return Ok(false);
+	}
+
+	let mut tips = Vec::new();
+	if let Ok(head) = repo.head() {
+		tips.extend(head.target());
+	}
+	for branch in repo.branches(None)? {
+		let (branch, _) = branch?;
+		tips.extend(branch.get().target());
+	}
+
+	for tip in tips {
+		if tip == id || repo.graph_descendant_of(tip, id)? {
+			return Ok(true);
+		}
+	}
+
+	Ok(false)
+}
+
+/// finds the commit in the history of `HEAD` that has the same
+/// changes (patch-id) as `id`, which is what a rebase or amend leaves
+/// behind for a bookmarked commit
+pub fn find_commit_by_patch_id(
+	repo_path: &RepoPath,
+	id: CommitId,
+) -> Result<Option<CommitId>> {
+	scope_time!("find_commit_by_patch_id");
+
+	let repo = repo(repo_path)?;
+
+	let Ok(wanted) = patch_id(&repo, id) else {
+		return Ok(None);
+	};
+
+	let mut walk = repo.revwalk()?;
+	walk.push_head()?;
+
+	for oid in walk.take(PATCH_ID_SEARCH_LIMIT) {
+		let candidate = CommitId::new(oid?);
+
+		if candidate != id && patch_id(&repo, candidate)? == wanted {
+			return Ok(Some(candidate));
+		}
+	}
+
+	Ok(None)
+}
+
+fn patch_id(repo: &Repository, id: CommitId) -> Result<Oid> {
+	Ok(get_commit_diff(repo, id, None, None, None)?.patchid(None)?)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		reset_repo,
+		tests::{repo_init, write_commit_file},
+		ResetType,
+	};
+
+	#[test]
+	fn test_bookmarks_roundtrip() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		assert!(get_bookmarks(repo_path)?.is_empty());
+
+		let first = write_commit_file(&repo, "foo", "a", "first");
+		let second = write_commit_file(&repo, "foo", "b", "second");
+
+		bookmark_commit(repo_path, "release", first)?;
+		bookmark_commit(repo_path, "\tbug\n", second)?;
+		assert!(bookmark_commit(repo_path, " ", second).is_err());
+
+		assert_eq!(
+			get_bookmarks(repo_path)?,
+			vec![
+				Bookmark {
+					name: String::from("bug"),
+					id: second,
+				},
+				Bookmark {
+					name: String::from("release"),
+					id: first,
+				},
+			]
+		);
+
+		bookmark_commit(repo_path, "release", second)?;
+		delete_bookmark(repo_path, "bug")?;
+
+		assert_eq!(
+			get_bookmarks(repo_path)?,
+			vec![Bookmark {
+				name: String::from("release"),
+				id: second,
+			}]
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_bookmark_rewritten_commit() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let first = write_commit_file(&repo, "foo", "a", "first");
+		let second = write_commit_file(&repo, "foo", "b", "second");
+
+		assert!(bookmark_reachable(repo_path, first)?);
+		assert!(bookmark_reachable(repo_path, second)?);
+
+		reset_repo(repo_path, first, ResetType::Hard)?;
+		let rewritten =
+			write_commit_file(&repo, "foo", "b", "second (reworded)");
+
+		assert!(!bookmark_reachable(repo_path, second)?);
+		assert!(!bookmark_reachable(repo_path, CommitId::default())?);
+		assert_eq!(
+			find_commit_by_patch_id(repo_path, second)?,
+			Some(rewritten)
+		);
+		assert_eq!(
+			find_commit_by_patch_id(repo_path, CommitId::default())?,
+			None
+		);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,137 @@
+//! commits `HEAD` and another branch do not share
+
+use crate::{
+	error::Result,
+	sync::{repository::repo, CommitId, RepoPath},
+};
+use git2::{ErrorCode, Oid, Repository, Sort};
+use scopetime::scope_time;
+
+/// commits on either side of `HEAD` and another branch
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BranchComparison {
+	/// the branch `HEAD` was compared with
+	pub branch_ref: String,
+	/// what `HEAD` pointed to
+	pub head: CommitId,
+	/// where both sides forked, `None` for unrelated histories
+	pub merge_base: Option<CommitId>,
+	/// commits of `HEAD` the branch lacks, newest first
+	pub ahead: Vec<CommitId>,
+	/// commits of the branch `HEAD` lacks, newest first
+	pub behind: Vec<CommitId>,
+}
+
+/// walks both sides of `branch_ref...HEAD`
+pub fn compare_branch_with_head(
+	repo_path: &RepoPath,
+	branch_ref: &str,
+) -> Result<BranchComparison> {
+	scope_time!("compare_branch_with_head");
+
+	let repo = repo(repo_path)?;
+
+	let head = repo.head()?.peel_to_commit()?.id();
+	let branch =
+		repo.find_reference(branch_ref)?.peel_to_commit()?.id();
+
+	let merge_base = match repo.merge_base(head, branch) {
+		Ok(id) => Some(id.into()),
+		Err(e) if e.code() == ErrorCode::NotFound => None,
+		Err(e) => return Err(e.into()),
+	};
+
+	Ok(BranchComparison {
+		branch_ref: branch_ref.to_string(),
+		head: head.into(),
+		merge_base,
+		ahead: commits_not_in(&repo, head, branch)?,
+		behind: commits_not_in(&repo, branch, head)?,
+	})
+}
+
+/// `hide..tip`
+fn commits_not_in(
+	repo: &Repository,
+	tip: Oid,
+	hide: Oid,
+) -> Result<Vec<CommitId>> {
+	let mut walk = repo.revwalk()?;
+	walk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+	walk.push(tip)?;
+	walk.hide(hide)?;
+
+	walk.map(|id| Ok(id?.into())).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		checkout_branch, create_branch,
+		tests::{repo_init, write_commit_file},
+	};
+
+	#[test]
+	fn test_ahead_and_behind() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let base = write_commit_file(&repo, "a", "1", "base");
+		create_branch(repo_path, "other").unwrap();
+		let theirs = write_commit_file(&repo, "b", "1", "theirs");
+
+		checkout_branch(repo_path, "master").unwrap();
+		let ours1 = write_commit_file(&repo, "c", "1", "ours1");
+		let ours2 = write_commit_file(&repo, "c", "2", "ours2");
+
+		let res =
+			compare_branch_with_head(repo_path, "refs/heads/other")
+				.unwrap();
+		assert_eq!(res.head, ours2);
+		assert_eq!(res.merge_base, Some(base));
+		assert_eq!(res.ahead, vec![ours2, ours1]);
+		assert_eq!(res.behind, vec![theirs]);
+
+		let res =
+			compare_branch_with_head(repo_path, "refs/heads/master")
+				.unwrap();
+		assert_eq!(res.merge_base, Some(ours2));
+		assert!(res.ahead.is_empty() && res.behind.is_empty());
+	}
+
+	#[test]
+	fn test_unrelated_histories() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let ours = write_commit_file(&repo, "a", "1", "ours");
+
+		let sig = repo.signature().unwrap();
+		let tree = repo
+			.find_tree(repo.index().unwrap().write_tree().unwrap())
+			.unwrap();
+		let orphan = repo
+			.commit(
+				Some("refs/heads/orphan"),
+				&sig,
+				&sig,
+				"orphan",
+				&tree,
+				&[],
+			)
+			.unwrap();
+
+		let res =
+			compare_branch_with_head(repo_path, "refs/heads/orphan")
+				.unwrap();
+		assert_eq!(res.merge_base, None);
+		assert_eq!(res.behind, vec![orphan.into()]);
+		assert_eq!(res.ahead.len(), 2);
+		assert_eq!(res.ahead[0], ours);
+	}
+}
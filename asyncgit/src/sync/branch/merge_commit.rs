@@ -164,7 +164,8 @@ mod test {
 
 		//lets fetch from origin
 		let bytes =
-			fetch(&clone2_dir.into(), "master", None, None).unwrap();
+			fetch(&clone2_dir.into(), "master", None, None, None)
+				.unwrap();
 		assert!(bytes > 0);
 
 		//we should be one commit behind
@@ -253,6 +254,7 @@ mod test {
 			"master",
 			None,
 			None,
+			None,
 		)
 		.unwrap();
 		assert!(bytes > 0);
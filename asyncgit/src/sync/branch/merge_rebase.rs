@@ -3,11 +3,15 @@
 use crate::{
 	error::{Error, Result},
 	sync::{
-		rebase::conflict_free_rebase, repository::repo, CommitId,
-		RepoPath,
+		rebase::{
+			autostash_apply, autostash_keep, autostash_save,
+			conflict_free_rebase, rebase,
+		},
+		repository::repo,
+		CommitId, RebaseState, RepoPath,
 	},
 };
-use git2::BranchType;
+use git2::{build::CheckoutBuilder, BranchType, Repository};
 use scopetime::scope_time;
 
 /// tries merging current branch with its upstream using rebase
@@ -33,6 +37,73 @@ pub fn merge_upstream_rebase(
 	conflict_free_rebase(&repo, &annotated_upstream)
 }
 
+/// rebases the current branch onto its upstream like `git pull
+/// --rebase`, a conflict leaves the rebase pending
+///
+/// uncommitted changes are stashed meanwhile if `rebase.autoStash`
+/// is set, otherwise they make it fail
+pub fn pull_rebase(
+	repo_path: &RepoPath,
+	branch_name: &str,
+) -> Result<RebaseState> {
+	scope_time!("pull_rebase");
+
+	let mut repo = repo(repo_path)?;
+	if super::get_branch_name_repo(&repo)? != branch_name {
+		return Err(Error::Generic(String::from(
+			"can only rebase in head branch",
+		)));
+	}
+
+	let autostash = autostash_save(&mut repo)?;
+
+	let state = match rebase_onto_upstream(&repo, branch_name) {
+		Ok(RebaseState::Conflicted) => {
+			autostash_keep(&repo, autostash)?;
+			return Ok(RebaseState::Conflicted);
+		}
+		res => res,
+	};
+
+	// put the changes back even if the rebase failed to start
+	autostash_apply(&mut repo, autostash)?;
+
+	state
+}
+
+fn rebase_onto_upstream(
+	repo: &Repository,
+	branch_name: &str,
+) -> Result<RebaseState> {
+	let branch = repo.find_branch(branch_name, BranchType::Local)?;
+	let upstream = branch.upstream()?.get().peel_to_commit()?;
+	let head = repo.head()?.peel_to_commit()?;
+
+	if repo.graph_descendant_of(head.id(), upstream.id())?
+		|| head.id() == upstream.id()
+	{
+		return Ok(RebaseState::Finished);
+	}
+
+	// nothing to rebase, only move the branch
+	if repo.graph_descendant_of(upstream.id(), head.id())? {
+		repo.checkout_tree(
+			upstream.as_object(),
+			Some(CheckoutBuilder::new().safe()),
+		)?;
+		repo.head()?.set_target(
+			upstream.id(),
+			&format!("pull: fast-forward to {}", upstream.id()),
+		)?;
+
+		return Ok(RebaseState::Finished);
+	}
+
+	let annotated = repo.find_annotated_commit(upstream.id())?;
+
+	rebase(repo, &annotated)
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
@@ -46,6 +117,7 @@ mod test {
 		RepoState,
 	};
 	use git2::{Repository, Time};
+	use tempfile::TempDir;
 
 	fn get_commit_msgs(r: &Repository) -> Vec<String> {
 		let commits = get_commit_ids(r, 10);
@@ -138,7 +210,8 @@ mod test {
 
 		//lets fetch from origin
 		let bytes =
-			fetch(&clone1_dir.into(), "master", None, None).unwrap();
+			fetch(&clone1_dir.into(), "master", None, None, None)
+				.unwrap();
 		assert!(bytes > 0);
 
 		//we should be one commit behind
@@ -249,7 +322,8 @@ mod test {
 
 		//lets fetch from origin
 
-		fetch(&clone1_dir.into(), "master", None, None).unwrap();
+		fetch(&clone1_dir.into(), "master", None, None, None)
+			.unwrap();
 
 		merge_upstream_rebase(&clone1_dir.into(), "master").unwrap();
 
@@ -329,7 +403,8 @@ mod test {
 			write_commit_file(&clone1, "test2.txt", "foo", "commit3");
 
 		let bytes =
-			fetch(&clone1_dir.into(), "master", None, None).unwrap();
+			fetch(&clone1_dir.into(), "master", None, None, None)
+				.unwrap();
 		assert!(bytes > 0);
 
 		assert_eq!(
@@ -353,4 +428,173 @@ mod test {
 			vec![String::from("commit3"), String::from("commit1")]
 		);
 	}
+
+	/// a clone one commit behind its upstream, the upstream changed
+	/// `test.txt` if `conflicting`
+	fn setup_diverged(
+		conflicting: bool,
+	) -> (TempDir, TempDir, TempDir, Repository) {
+		let (r1_dir, _repo) = repo_init_bare().unwrap();
+		let (clone1_dir, clone1) =
+			repo_clone(r1_dir.path().to_str().unwrap()).unwrap();
+		let clone1_path = clone1_dir.path().to_str().unwrap();
+
+		write_commit_file(&clone1, "test.txt", "base", "commit1");
+		push_branch(
+			&clone1_path.into(),
+			"origin",
+			"master",
+			false,
+			false,
+			None,
+			None,
+		)
+		.unwrap();
+
+		let (clone2_dir, clone2) =
+			repo_clone(r1_dir.path().to_str().unwrap()).unwrap();
+		let file = if conflicting { "test.txt" } else { "other.txt" };
+		write_commit_file(&clone2, file, "theirs", "commit2");
+		push_branch(
+			&clone2_dir.path().to_str().unwrap().into(),
+			"origin",
+			"master",
+			false,
+			false,
+			None,
+			None,
+		)
+		.unwrap();
+
+		fetch(&clone1_path.into(), "master", None, None, None)
+			.unwrap();
+
+		(r1_dir, clone1_dir, clone2_dir, clone1)
+	}
+
+	#[test]
+	fn test_pull_rebase_fast_forward() {
+		let (_r1, clone1_dir, _clone2, clone1) =
+			setup_diverged(false);
+		let path: &RepoPath =
+			&clone1_dir.path().to_str().unwrap().into();
+
+		assert_eq!(
+			pull_rebase(path, "master").unwrap(),
+			RebaseState::Finished
+		);
+		assert_eq!(
+			get_commit_msgs(&clone1),
+			vec![String::from("commit2"), String::from("commit1")]
+		);
+		assert!(clone1_dir.path().join("other.txt").exists());
+		assert!(!clone1.head_detached().unwrap());
+
+		assert!(!super::super::config_is_pull_rebase(path).unwrap());
+		clone1
+			.config()
+			.unwrap()
+			.set_str("pull.rebase", "Interactive")
+			.unwrap();
+		assert!(super::super::config_is_pull_rebase(path).unwrap());
+	}
+
+	#[test]
+	fn test_pull_rebase_autostash() {
+		let (_r1, clone1_dir, _clone2, clone1) =
+			setup_diverged(false);
+		let path: &RepoPath =
+			&clone1_dir.path().to_str().unwrap().into();
+
+		write_commit_file(&clone1, "mine.txt", "a", "commit3");
+		std::fs::write(clone1_dir.path().join("mine.txt"), "b")
+			.unwrap();
+
+		assert!(pull_rebase(path, "master").is_err());
+
+		clone1
+			.config()
+			.unwrap()
+			.set_bool("rebase.autoStash", true)
+			.unwrap();
+		assert_eq!(
+			pull_rebase(path, "master").unwrap(),
+			RebaseState::Finished
+		);
+		assert_eq!(
+			get_commit_msgs(&clone1),
+			vec![
+				String::from("commit3"),
+				String::from("commit2"),
+				String::from("commit1")
+			]
+		);
+		assert_eq!(
+			std::fs::read_to_string(
+				clone1_dir.path().join("mine.txt")
+			)
+			.unwrap(),
+			"b"
+		);
+		assert!(clone1.find_reference("refs/stash").is_err());
+	}
+
+	#[test]
+	fn test_pull_rebase_conflict() {
+		let (_r1, clone1_dir, _clone2, clone1) = setup_diverged(true);
+		let path: &RepoPath =
+			&clone1_dir.path().to_str().unwrap().into();
+
+		write_commit_file(&clone1, "test.txt", "ours", "commit3");
+		write_commit_file(&clone1, "mine.txt", "a", "commit4");
+		std::fs::write(clone1_dir.path().join("mine.txt"), "b")
+			.unwrap();
+		clone1
+			.config()
+			.unwrap()
+			.set_bool("rebase.autoStash", true)
+			.unwrap();
+
+		assert_eq!(
+			pull_rebase(path, "master").unwrap(),
+			RebaseState::Conflicted
+		);
+		assert_eq!(
+			crate::sync::repo_state(path).unwrap(),
+			RepoState::Rebase
+		);
+
+		std::fs::write(clone1_dir.path().join("test.txt"), "merged")
+			.unwrap();
+		crate::sync::stage_add_file(
+			path,
+			std::path::Path::new("test.txt"),
+		)
+		.unwrap();
+
+		assert_eq!(
+			crate::sync::continue_pending_rebase(path).unwrap(),
+			RebaseState::Finished
+		);
+		assert_eq!(
+			crate::sync::repo_state(path).unwrap(),
+			RepoState::Clean
+		);
+		assert_eq!(
+			get_commit_msgs(&clone1),
+			vec![
+				String::from("commit4"),
+				String::from("commit3"),
+				String::from("commit2"),
+				String::from("commit1")
+			]
+		);
+		assert_eq!(
+			std::fs::read_to_string(
+				clone1_dir.path().join("mine.txt")
+			)
+			.unwrap(),
+			"b"
+		);
+	}
 }
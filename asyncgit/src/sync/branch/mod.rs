@@ -1,5 +1,6 @@
 //! branch functions
 
+pub mod compare;
 pub mod merge_commit;
 pub mod merge_ff;
 pub mod merge_rebase;
@@ -9,8 +10,13 @@ use super::{utils::bytes2string, RepoPath};
 use crate::{
 	error::{Error, Result},
 	sync::{
-		remotes::get_default_remote_for_push_in_repo,
-		repository::repo, utils::get_head_repo, CommitId,
+		oplog::{oplog_record, OpKind},
+		remotes::{
+			get_branch_push_remote_in_repo, push::push_tracking_ref,
+		},
+		repository::repo,
+		utils::get_head_repo,
+		CommitId,
 	},
 };
 use git2::{Branch, BranchType, Repository};
@@ -53,6 +59,9 @@ pub struct LocalBranch {
 	pub has_upstream: bool,
 	///
 	pub upstream: Option<UpstreamBranch>,
+	/// an upstream is configured but its remote-tracking branch does
+	/// not exist (anymore), like after it was pruned
+	pub upstream_gone: bool,
 	///
 	pub remote: Option<String>,
 }
@@ -171,6 +180,10 @@ pub fn get_branches_info(
 				BranchDetails::Local(LocalBranch {
 					is_head: branch.is_head(),
 					has_upstream: upstream_branch.is_some(),
+					upstream_gone: upstream_branch.is_none()
+						&& repo
+							.branch_upstream_name(&reference)
+							.is_ok(),
 					upstream: upstream_branch,
 					remote,
 				})
@@ -211,6 +224,7 @@ pub struct BranchCompare {
 ///
 pub(crate) fn branch_set_upstream_after_push(
 	repo: &Repository,
+	remote: &str,
 	branch_name: &str,
 ) -> Result<()> {
 	scope_time!("branch_set_upstream");
@@ -219,7 +233,6 @@ pub(crate) fn branch_set_upstream_after_push(
 		repo.find_branch(branch_name, BranchType::Local)?;
 
 	if branch.upstream().is_err() {
-		let remote = get_default_remote_for_push_in_repo(repo)?;
 		let upstream_name = format!("{remote}/{branch_name}");
 		branch.set_upstream(Some(upstream_name.as_str()))?;
 	}
@@ -243,6 +256,26 @@ pub fn get_branch_remote(
 	}
 }
 
+/// remote and name of the branch on it for a remote tracking
+/// branch `reference` (`refs/remotes/{remote}/{branch}`), both may
+/// contain slashes
+pub fn get_remote_branch_parts(
+	repo_path: &RepoPath,
+	reference: &str,
+) -> Result<(String, String)> {
+	let repo = repo(repo_path)?;
+	let remote = bytes2string(&repo.branch_remote_name(reference)?)?;
+	let branch = reference
+		.strip_prefix(&format!("refs/remotes/{remote}/"))
+		.ok_or_else(|| {
+			Error::Generic(format!(
+				"not a remote tracking branch: {reference}"
+			))
+		})?;
+
+	Ok((remote, branch.to_string()))
+}
+
 /// Retrieve the upstream merge of a local `branch`,
 /// configured in "branch.*.merge"
 ///
@@ -262,15 +295,24 @@ pub fn get_branch_upstream_merge(
 	}
 }
 
-/// returns whether the pull merge strategy is set to rebase
+/// returns whether the pull merge strategy is set to rebase, the
+/// `interactive` and `merges` kinds of rebase count as rebase too
 pub fn config_is_pull_rebase(repo_path: &RepoPath) -> Result<bool> {
 	let repo = repo(repo_path)?;
 	let config = repo.config()?;
 
 	if let Ok(rebase) = config.get_entry("pull.rebase") {
 		let value =
-			rebase.value().map(String::from).unwrap_or_default();
-		return Ok(value == "true");
+			rebase.value().map(str::to_lowercase).unwrap_or_default();
+		return Ok(matches!(
+			value.as_str(),
+			"true"
+				| "yes" | "on"
+				| "1" | "interactive"
+				| "i" | "merges"
+				| "m" | "preserve"
+				| "p"
+		));
 	}
 
 	Ok(false)
@@ -301,6 +343,94 @@ pub fn branch_compare_upstream(
 	Ok(BranchCompare { ahead, behind })
 }
 
+/// where a branch fetches from and pushes to, in triangular
+/// workflows (`remote.pushDefault`, `branch.<name>.pushRemote`)
+/// these are different remotes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchRemotes {
+	/// remote pushes go to
+	pub push_remote: String,
+	/// remote-tracking branch pushes update, like `origin/feature`
+	pub push: Option<String>,
+	/// remote-tracking upstream, like `upstream/main`
+	pub upstream: Option<String>,
+}
+
+impl BranchRemotes {
+	/// pushes do not go to the upstream
+	pub fn is_triangular(&self) -> bool {
+		self.upstream.is_some()
+			&& self.push.is_some()
+			&& self.push != self.upstream
+	}
+}
+
+///
+pub fn get_branch_remotes(
+	repo_path: &RepoPath,
+	branch: &str,
+) -> Result<BranchRemotes> {
+	scope_time!("get_branch_remotes");
+
+	let repo = repo(repo_path)?;
+
+	let push_remote = get_branch_push_remote_in_repo(&repo, branch)?;
+	let push =
+		push_tracking_ref(&repo, repo_path, &push_remote, branch)?
+			.map(|name| remote_tracking_shorthand(&name));
+
+	let upstream = repo
+		.find_branch(branch, BranchType::Local)?
+		.upstream()
+		.ok()
+		.and_then(|upstream| upstream.get().name().map(String::from))
+		.map(|name| remote_tracking_shorthand(&name));
+
+	Ok(BranchRemotes {
+		push_remote,
+		push,
+		upstream,
+	})
+}
+
+fn remote_tracking_shorthand(name: &str) -> String {
+	name.strip_prefix("refs/remotes/")
+		.unwrap_or(name)
+		.to_string()
+}
+
+/// compares `branch` to the remote-tracking branch its pushes
+/// update (see [`BranchRemotes::push`]), `None` until it was pushed
+pub fn branch_compare_push(
+	repo_path: &RepoPath,
+	branch: &str,
+) -> Result<Option<BranchCompare>> {
+	scope_time!("branch_compare_push");
+
+	let repo = repo(repo_path)?;
+
+	let remote = get_branch_push_remote_in_repo(&repo, branch)?;
+	let Some(tracking) =
+		push_tracking_ref(&repo, repo_path, &remote, branch)?
+	else {
+		return Ok(None);
+	};
+	let Ok(tracking_commit) = repo.refname_to_id(&tracking) else {
+		return Ok(None);
+	};
+
+	let branch_commit = repo
+		.find_branch(branch, BranchType::Local)?
+		.into_reference()
+		.peel_to_commit()?
+		.id();
+
+	let (ahead, behind) =
+		repo.graph_ahead_behind(branch_commit, tracking_commit)?;
+
+	Ok(Some(BranchCompare { ahead, behind }))
+}
+
 /// Switch branch to given `branch_name`.
 ///
 /// Method will fail if there are conflicting changes between current and target branch. However,
@@ -418,6 +548,26 @@ pub fn delete_branch(
 	scope_time!("delete_branch");
 
 	let repo = repo(repo_path)?;
+	let old_id =
+		repo.refname_to_id(branch_ref).ok().map(CommitId::new);
+
+	let result = delete_branch_repo(&repo, branch_ref);
+	oplog_record(
+		repo_path,
+		OpKind::DeleteBranch,
+		branch_ref,
+		old_id,
+		None,
+		&result,
+	);
+
+	result
+}
+
+fn delete_branch_repo(
+	repo: &Repository,
+	branch_ref: &str,
+) -> Result<()> {
 	let branch_as_ref = repo.find_reference(branch_ref)?;
 	let mut branch = git2::Branch::wrap(branch_as_ref);
 	if branch.is_head() {
@@ -447,6 +597,25 @@ pub fn create_branch(
 	Ok(branch_ref_name)
 }
 
+/// creates a branch pointing to `commit` without touching `HEAD`,
+/// other branches may point to the same commit already
+pub fn create_branch_at(
+	repo_path: &RepoPath,
+	name: &str,
+	commit: CommitId,
+) -> Result<String> {
+	scope_time!("create_branch_at");
+
+	let repo = repo(repo_path)?;
+
+	let commit = repo.find_commit(commit.into())?;
+	let branch = repo.branch(name, &commit, false)?;
+	let branch_ref_name =
+		bytes2string(branch.into_reference().name_bytes())?;
+
+	Ok(branch_ref_name)
+}
+
 #[cfg(test)]
 mod tests_branch_name {
 	use super::*;
@@ -482,7 +651,10 @@ mod tests_branch_name {
 #[cfg(test)]
 mod tests_create_branch {
 	use super::*;
-	use crate::sync::tests::repo_init;
+	use crate::sync::{
+		get_head,
+		tests::{repo_init, write_commit_file},
+	};
 
 	#[test]
 	fn test_smoke() {
@@ -498,12 +670,45 @@ mod tests_create_branch {
 			"branch1"
 		);
 	}
+
+	#[test]
+	fn test_create_at_commit() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let first = write_commit_file(&repo, "a", "1", "c1");
+		write_commit_file(&repo, "a", "2", "c2");
+
+		assert_eq!(
+			create_branch_at(repo_path, "old", first).unwrap(),
+			"refs/heads/old"
+		);
+		// `HEAD` stays where it is
+		assert_eq!(
+			get_branch_name(repo_path).unwrap().as_str(),
+			"master"
+		);
+
+		// already the tip of `old`
+		create_branch_at(repo_path, "older", first).unwrap();
+		assert!(create_branch_at(repo_path, "old", first).is_err());
+
+		checkout_branch(repo_path, "older").unwrap();
+		assert_eq!(get_head(repo_path).unwrap(), first);
+	}
 }
 
 #[cfg(test)]
 mod tests_branch_compare {
 	use super::*;
-	use crate::sync::tests::repo_init;
+	use crate::sync::{
+		remotes::push::push_branch,
+		tests::{
+			repo_clone, repo_init, repo_init_bare, write_commit_file,
+		},
+	};
 
 	#[test]
 	fn test_smoke() {
@@ -518,6 +723,68 @@ mod tests_branch_compare {
 
 		assert!(res.is_err());
 	}
+
+	#[test]
+	fn test_triangular() {
+		let (upstream_dir, _upstream) = repo_init_bare().unwrap();
+		let (fork_dir, _fork) = repo_init_bare().unwrap();
+		let (clone_dir, clone) =
+			repo_clone(upstream_dir.path().to_str().unwrap())
+				.unwrap();
+		let repo_path: &RepoPath =
+			&clone_dir.path().to_str().unwrap().into();
+
+		write_commit_file(&clone, "a", "1", "commit1");
+		push_branch(
+			repo_path, "origin", "master", false, false, None, None,
+		)
+		.unwrap();
+
+		let remotes =
+			get_branch_remotes(repo_path, "master").unwrap();
+		assert_eq!(remotes.push.as_deref(), Some("origin/master"));
+		assert!(!remotes.is_triangular());
+
+		clone
+			.remote("fork", fork_dir.path().to_str().unwrap())
+			.unwrap();
+		clone
+			.config()
+			.unwrap()
+			.set_str("remote.pushDefault", "fork")
+			.unwrap();
+
+		let remotes =
+			get_branch_remotes(repo_path, "master").unwrap();
+		assert_eq!(remotes.push_remote, "fork");
+		assert_eq!(remotes.push.as_deref(), Some("fork/master"));
+		assert_eq!(
+			remotes.upstream.as_deref(),
+			Some("origin/master")
+		);
+		assert!(remotes.is_triangular());
+		assert!(branch_compare_push(repo_path, "master")
+			.unwrap()
+			.is_none());
+
+		push_branch(
+			repo_path, "fork", "master", false, false, None, None,
+		)
+		.unwrap();
+		write_commit_file(&clone, "a", "2", "commit2");
+
+		let push = branch_compare_push(repo_path, "master")
+			.unwrap()
+			.unwrap();
+		assert_eq!((push.ahead, push.behind), (1, 0));
+		assert_eq!(
+			get_branch_remotes(repo_path, "master")
+				.unwrap()
+				.upstream
+				.as_deref(),
+			Some("origin/master")
+		);
+	}
 }
 
 #[cfg(test)]
@@ -1070,6 +1337,54 @@ mod test_remote_branches {
 		);
 	}
 
+	#[test]
+	fn test_delete_remote_branch() {
+		let (r1_dir, _repo) = repo_init_bare().unwrap();
+
+		let (clone1_dir, clone1) =
+			repo_clone(r1_dir.path().to_str().unwrap()).unwrap();
+		let clone1_dir: &RepoPath =
+			&clone1_dir.path().to_str().unwrap().into();
+
+		write_commit_file(&clone1, "test.txt", "test", "commit1");
+		push_branch(
+			clone1_dir, "origin", "master", false, false, None, None,
+		)
+		.unwrap();
+		create_branch(clone1_dir, "bar/foo").unwrap();
+		push_branch(
+			clone1_dir, "origin", "bar/foo", false, false, None, None,
+		)
+		.unwrap();
+
+		let (remote, branch) = get_remote_branch_parts(
+			clone1_dir,
+			"refs/remotes/origin/bar/foo",
+		)
+		.unwrap();
+		assert_eq!(
+			(remote.as_str(), branch.as_str()),
+			("origin", "bar/foo")
+		);
+		assert!(get_remote_branch_parts(
+			clone1_dir,
+			"refs/heads/bar/foo"
+		)
+		.is_err());
+
+		push_branch(
+			clone1_dir, &remote, &branch, false, true, None, None,
+		)
+		.unwrap();
+
+		let branches: Vec<_> = get_branches_info(clone1_dir, false)
+			.unwrap()
+			.into_iter()
+			.map(|b| b.name)
+			.collect();
+		assert_eq!(branches, vec![String::from("origin/master")]);
+	}
+
 	#[test]
 	fn test_has_tracking() {
 		let (r1_dir, _repo) = repo_init_bare().unwrap();
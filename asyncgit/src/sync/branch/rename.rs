@@ -1,12 +1,15 @@
 //! renaming of branches
 
 use crate::{
-	error::Result,
+	error::{Error, Result},
 	sync::{repository::repo, RepoPath},
 };
+use git2::BranchType;
 use scopetime::scope_time;
 
-/// Rename the branch reference
+/// Rename the branch reference, its upstream configuration moves
+/// along and `HEAD` follows if it is checked out. Errors instead of
+/// overwriting a branch that already has `new_name`.
 pub fn rename_branch(
 	repo_path: &RepoPath,
 	branch_ref: &str,
@@ -15,9 +18,21 @@ pub fn rename_branch(
 	scope_time!("rename_branch");
 
 	let repo = repo(repo_path)?;
+
+	if !git2::Branch::name_is_valid(new_name)? {
+		return Err(Error::Generic(format!(
+			"invalid branch name: {new_name}"
+		)));
+	}
+	if repo.find_branch(new_name, BranchType::Local).is_ok() {
+		return Err(Error::Generic(format!(
+			"a branch named '{new_name}' already exists"
+		)));
+	}
+
 	let branch_as_ref = repo.find_reference(branch_ref)?;
 	let mut branch = git2::Branch::wrap(branch_as_ref);
-	branch.rename(new_name, true)?;
+	branch.rename(new_name, false)?;
 
 	Ok(())
 }
@@ -26,7 +41,7 @@ pub fn rename_branch(
 mod test {
 	use super::super::{checkout_branch, create_branch, RepoPath};
 	use super::rename_branch;
-	use crate::sync::tests::repo_init;
+	use crate::sync::{get_branch_remote, tests::repo_init};
 
 	#[test]
 	fn test_rename_branch() {
@@ -68,4 +83,56 @@ mod test {
 			"AnotherName"
 		);
 	}
+
+	#[test]
+	fn test_rename_checked_out_branch_keeps_upstream() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let mut config = repo.config().unwrap();
+		config.set_str("branch.master.remote", "origin").unwrap();
+		config
+			.set_str("branch.master.merge", "refs/heads/master")
+			.unwrap();
+
+		rename_branch(repo_path, "refs/heads/master", "main")
+			.unwrap();
+
+		assert_eq!(
+			repo.head().unwrap().name().unwrap(),
+			"refs/heads/main"
+		);
+		assert_eq!(
+			get_branch_remote(repo_path, "main").unwrap().as_deref(),
+			Some("origin")
+		);
+	}
+
+	#[test]
+	fn test_rename_branch_to_existing_name() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		create_branch(repo_path, "branch1").unwrap();
+
+		assert!(rename_branch(
+			repo_path,
+			"refs/heads/branch1",
+			"master"
+		)
+		.is_err());
+		assert!(rename_branch(
+			repo_path,
+			"refs/heads/branch1",
+			"a..b"
+		)
+		.is_err());
+		assert!(repo
+			.find_branch("branch1", git2::BranchType::Local)
+			.is_ok());
+	}
 }
@@ -3,7 +3,12 @@ use super::{CommitId, RepoPath};
 use crate::sync::sign::{SignBuilder, SignError};
 use crate::{
 	error::{Error, Result},
-	sync::{repository::repo, utils::get_head_repo},
+	sync::{
+		merge::remove_squash_msg,
+		oplog::{oplog_record, OpKind},
+		repository::repo,
+		utils::{get_head_repo, undo_last_commit_repo},
+	},
 };
 use git2::{
 	message_prettify, ErrorCode, ObjectType, Repository, Signature,
@@ -18,6 +23,24 @@ pub fn amend(
 ) -> Result<CommitId> {
 	scope_time!("amend");
 
+	let result = amend_commit(repo_path, id, msg);
+	oplog_record(
+		repo_path,
+		OpKind::Commit,
+		"HEAD (amend)",
+		Some(id),
+		result.as_ref().ok().copied(),
+		&result,
+	);
+
+	result
+}
+
+fn amend_commit(
+	repo_path: &RepoPath,
+	id: CommitId,
+	msg: &str,
+) -> Result<CommitId> {
 	let repo = repo(repo_path)?;
 	let config = repo.config()?;
 
@@ -29,12 +52,10 @@ pub fn amend(
 
 	if config.get_bool("commit.gpgsign").unwrap_or(false) {
 		// HACK: we undo the last commit and create a new one
-		use crate::sync::utils::undo_last_commit;
-
 		let head = get_head_repo(&repo)?;
 		if head == commit.id().into() {
-			undo_last_commit(repo_path)?;
-			return self::commit(repo_path, msg);
+			undo_last_commit_repo(&repo)?;
+			return create_commit(repo_path, msg);
 		}
 
 		return Err(Error::SignAmendNonLastCommit);
@@ -82,6 +103,27 @@ pub(crate) fn signature_allow_undefined_name(
 pub fn commit(repo_path: &RepoPath, msg: &str) -> Result<CommitId> {
 	scope_time!("commit");
 
+	let old_id = repo(repo_path)
+		.ok()
+		.and_then(|repo| get_head_repo(&repo).ok());
+
+	let result = create_commit(repo_path, msg);
+	oplog_record(
+		repo_path,
+		OpKind::Commit,
+		"HEAD",
+		old_id,
+		result.as_ref().ok().copied(),
+		&result,
+	);
+
+	result
+}
+
+fn create_commit(
+	repo_path: &RepoPath,
+	msg: &str,
+) -> Result<CommitId> {
 	let repo = repo(repo_path)?;
 	let config = repo.config()?;
 	let signature = signature_allow_undefined_name(&repo)?;
@@ -150,6 +192,8 @@ pub fn commit(repo_path: &RepoPath, msg: &str) -> Result<CommitId> {
 		)?
 	};
 
+	remove_squash_msg(&repo)?;
+
 	Ok(commit_id.into())
 }
 
@@ -172,8 +216,17 @@ pub fn tag_commit(
 		repo.find_object(object_id, Some(ObjectType::Commit))?;
 
 	let c = if let Some(message) = message {
+		let config = repo.config()?;
 		let signature = signature_allow_undefined_name(&repo)?;
-		repo.tag(tag, &target, &signature, message, false)?.into()
+
+		if config.get_bool("tag.gpgsign").unwrap_or(false) {
+			tag_signed(
+				&repo, &config, tag, object_id, &signature, message,
+			)?
+			.into()
+		} else {
+			repo.tag(tag, &target, &signature, message, false)?.into()
+		}
 	} else {
 		repo.tag_lightweight(tag, &target, false)?.into()
 	};
@@ -181,19 +234,83 @@ pub fn tag_commit(
 	Ok(c)
 }
 
+/// the tag object libgit2 writes for an annotated tag of a commit
+fn tag_buffer(
+	signature: &Signature,
+	tag: &str,
+	target: git2::Oid,
+	message: &str,
+) -> String {
+	let when = signature.when();
+	let offset = when.offset_minutes();
+
+	format!(
+		"object {target}\ntype commit\ntag {tag}\ntagger {} <{}> {} {}{:02}{:02}\n\n{message}",
+		String::from_utf8_lossy(signature.name_bytes()),
+		String::from_utf8_lossy(signature.email_bytes()),
+		when.seconds(),
+		when.sign(),
+		offset.abs() / 60,
+		offset.abs() % 60,
+	)
+}
+
+/// like `git tag -s`: the signature of the tag object is appended to
+/// its message
+fn tag_signed(
+	repo: &Repository,
+	config: &git2::Config,
+	tag: &str,
+	target: git2::Oid,
+	signature: &Signature,
+	message: &str,
+) -> Result<git2::Oid> {
+	let ref_name = format!("refs/tags/{tag}");
+	if !git2::Reference::is_valid_name(&ref_name) {
+		return Err(Error::Generic(format!(
+			"'{tag}' is not a valid tag name"
+		)));
+	}
+	if repo.find_reference(&ref_name).is_ok() {
+		return Err(Error::Generic(format!(
+			"tag '{tag}' already exists"
+		)));
+	}
+
+	let mut buffer = tag_buffer(signature, tag, target, message);
+	if !buffer.ends_with('\n') {
+		buffer.push('\n');
+	}
+
+	let signer = SignBuilder::from_gitconfig(repo, config)?;
+	let (tag_signature, _) = signer.sign(buffer.as_bytes())?;
+	buffer.push_str(&tag_signature);
+
+	let id = repo.odb()?.write(ObjectType::Tag, buffer.as_bytes())?;
+	repo.reference(&ref_name, id, false, &format!("tag: {tag}"))?;
+
+	Ok(id)
+}
+
 /// Loads the comment prefix from config & uses it to prettify commit messages
 pub fn commit_message_prettify(
 	repo_path: &RepoPath,
 	message: String,
 ) -> Result<String> {
-	let comment_char = repo(repo_path)?
+	let comment_char = commit_comment_char(repo_path)? as u8;
+
+	Ok(message_prettify(message, Some(comment_char))?)
+}
+
+/// lines starting with it are removed from commit messages
+/// (`core.commentChar`, `#` by default)
+pub fn commit_comment_char(repo_path: &RepoPath) -> Result<char> {
+	Ok(repo(repo_path)?
 		.config()?
 		.get_string("core.commentChar")
 		.ok()
 		.and_then(|char_string| char_string.chars().next())
-		.unwrap_or('#') as u8;
-
-	Ok(message_prettify(message, Some(comment_char))?)
+		.unwrap_or('#'))
 }
 
 #[cfg(test)]
@@ -208,8 +325,11 @@ mod tests {
 		utils::get_head,
 		LogWalker,
 	};
-	use commit::{amend, commit_message_prettify, tag_commit};
-	use git2::Repository;
+	use commit::{
+		amend, commit_comment_char, commit_message_prettify,
+		tag_buffer, tag_commit,
+	};
+	use git2::{ObjectType, Repository, Signature, Time};
 	use std::{fs::File, io::Write, path::Path};
 
 	fn count_commits(repo: &Repository, max: usize) -> usize {
@@ -374,6 +494,33 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_tag_buffer() -> Result<()> {
+		let (_td, repo) = repo_init().unwrap();
+		let head = repo.head()?.peel_to_commit()?;
+
+		let signature = Signature::new(
+			"name",
+			"name@example.com",
+			&Time::new(1_700_000_000, -90),
+		)?;
+		let id = repo.tag_annotation_create(
+			"v1",
+			head.as_object(),
+			&signature,
+			"message\n",
+		)?;
+
+		let buffer =
+			tag_buffer(&signature, "v1", head.id(), "message\n");
+		assert_eq!(
+			repo.odb()?.write(ObjectType::Tag, buffer.as_bytes())?,
+			id
+		);
+
+		Ok(())
+	}
+
 	/// Beware: this test has to be run with a `$HOME/.gitconfig` that has
 	/// `user.email` not set. Otherwise, git falls back to the value of
 	/// `user.email` in `$HOME/.gitconfig` and this test fails.
@@ -483,7 +630,9 @@ mod tests {
 		let repo_path: &RepoPath =
 			&root.as_os_str().to_str().unwrap().into();
 
+		assert_eq!(commit_comment_char(repo_path)?, '#');
 		repo.config()?.set_str("core.commentChar", ";")?;
+		assert_eq!(commit_comment_char(repo_path)?, ';');
 
 		let message = commit_message_prettify(
 			repo_path,
@@ -1,14 +1,19 @@
 //! Functions for getting infos about files in commits
 
-use super::{diff::DiffOptions, CommitId, RepoPath};
+use super::{
+	diff::DiffOptions,
+	file_mode::{symlink_target, FileMode},
+	lfs::set_lfs_tracked,
+	CommitId, RepoPath,
+};
 use crate::{
-	error::Result,
+	error::{Error, Result},
 	sync::{get_stashes, repository::repo},
 	StatusItem, StatusItemType,
 };
-use git2::{Diff, Repository};
+use git2::{Diff, Patch, Repository};
 use scopetime::scope_time;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 /// struct containing a new and an old version
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
@@ -19,6 +24,15 @@ pub struct OldNew<T> {
 	pub new: T,
 }
 
+/// one side of a diff between two trees
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum TreeSource {
+	/// tree of a commit (or stash)
+	Commit(CommitId),
+	/// tracked files in the workdir
+	WorkDir,
+}
+
 /// Sort two commits.
 pub fn sort_commits(
 	repo: &Repository,
@@ -67,10 +81,33 @@ pub fn get_commit_files(
 		)?
 	};
 
-	let res = diff
+	diff_files(&repo, &diff)
+}
+
+/// get all files that differ between two trees
+pub fn get_trees_files(
+	repo_path: &RepoPath,
+	trees: OldNew<TreeSource>,
+) -> Result<Vec<StatusItem>> {
+	scope_time!("get_trees_files");
+
+	let repo = repo(repo_path)?;
+	let diff = get_trees_diff(&repo, trees, None, None)?;
+
+	diff_files(&repo, &diff)
+}
+
+fn diff_files(
+	repo: &Repository,
+	diff: &Diff,
+) -> Result<Vec<StatusItem>> {
+	let mut files: Vec<StatusItem> = diff
 		.deltas()
 		.map(|delta| {
 			let status = StatusItemType::from(delta.status());
+			let mode = FileMode::from_raw(u32::from(
+				delta.new_file().mode(),
+			));
 
 			StatusItem {
 				path: delta
@@ -79,11 +116,27 @@ pub fn get_commit_files(
 					.map(|p| p.to_str().unwrap_or("").to_string())
 					.unwrap_or_default(),
 				status,
+				conflict: None,
+				auto_resolved: false,
+				submodule: None,
+				lfs: false,
+				mode,
+				symlink_target: if mode == Some(FileMode::Symlink) {
+					symlink_target(
+						repo,
+						repo.workdir(),
+						&delta.new_file(),
+					)
+				} else {
+					None
+				},
 			}
 		})
-		.collect::<Vec<_>>();
+		.collect();
 
-	Ok(res)
+	set_lfs_tracked(repo, &mut files)?;
+
+	Ok(files)
 }
 
 /// get diff of two arbitrary commits
@@ -124,6 +177,85 @@ pub fn get_compare_commits_diff(
 	Ok(diff)
 }
 
+/// get diff of two trees, the workdir can be one of them
+pub fn get_trees_diff(
+	repo: &Repository,
+	trees: OldNew<TreeSource>,
+	pathspec: Option<String>,
+	options: Option<DiffOptions>,
+) -> Result<Diff<'_>> {
+	let mut opts = git2::DiffOptions::new();
+	if let Some(options) = options {
+		opts.context_lines(options.context);
+		opts.ignore_whitespace(options.ignore_whitespace);
+		opts.interhunk_lines(options.interhunk_lines);
+	}
+	if let Some(p) = pathspec {
+		opts.pathspec(p);
+	}
+
+	let tree = |id: CommitId| repo.find_commit(id.into())?.tree();
+
+	let diff = match (trees.old, trees.new) {
+		(TreeSource::Commit(old), TreeSource::Commit(new)) => repo
+			.diff_tree_to_tree(
+				Some(&tree(old)?),
+				Some(&tree(new)?),
+				Some(&mut opts),
+			)?,
+		(TreeSource::Commit(old), TreeSource::WorkDir) => repo
+			.diff_tree_to_workdir(
+				Some(&tree(old)?),
+				Some(&mut opts),
+			)?,
+		(TreeSource::WorkDir, TreeSource::Commit(new)) => {
+			opts.reverse(true);
+			repo.diff_tree_to_workdir(
+				Some(&tree(new)?),
+				Some(&mut opts),
+			)?
+		}
+		(TreeSource::WorkDir, TreeSource::WorkDir) => {
+			return Err(Error::Generic(String::from(
+				"can not diff the workdir against itself",
+			)));
+		}
+	};
+
+	Ok(diff)
+}
+
+/// hash of the patch of every file changed between two commits,
+/// a file's hash changes only if the changes to that file do
+pub fn get_compare_commits_patch_hashes(
+	repo_path: &RepoPath,
+	ids: OldNew<CommitId>,
+) -> Result<HashMap<String, u64>> {
+	scope_time!("get_compare_commits_patch_hashes");
+
+	let repo = repo(repo_path)?;
+	let diff = get_compare_commits_diff(&repo, ids, None, None)?;
+
+	let mut res = HashMap::with_capacity(diff.deltas().len());
+
+	for (idx, delta) in diff.deltas().enumerate() {
+		let path = delta
+			.new_file()
+			.path()
+			.or_else(|| delta.old_file().path())
+			.and_then(|path| path.to_str());
+
+		if let (Some(path), Some(mut patch)) =
+			(path, Patch::from_diff(&diff, idx)?)
+		{
+			let buf = patch.to_buf()?;
+			res.insert(path.to_string(), crate::hash(&&*buf));
+		}
+	}
+
+	Ok(res)
+}
+
 /// get diff of a commit to its first parent
 pub(crate) fn get_commit_diff<'a>(
 	repo: &'a Repository,
@@ -181,18 +313,97 @@ pub(crate) fn get_commit_diff<'a>(
 
 #[cfg(test)]
 mod tests {
-	use super::get_commit_files;
+	use super::{
+		get_commit_files, get_compare_commits_patch_hashes,
+		get_trees_files, OldNew, TreeSource,
+	};
 	use crate::{
 		error::Result,
 		sync::{
-			commit, stage_add_file, stash_save,
-			tests::{get_statuses, repo_init},
+			commit,
+			diff::{get_diff_trees, DiffLineType},
+			get_head, stage_add_file, stash_save,
+			tests::{get_statuses, repo_init, write_commit_file},
 			RepoPath,
 		},
 		StatusItemType,
 	};
 	use std::{fs::File, io::Write, path::Path};
 
+	#[test]
+	fn test_trees_files() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		write_commit_file(&repo, "b.txt", "b", "c2");
+		let head = get_head(repo_path)?;
+
+		File::create(root.join("a.txt"))?.write_all(b"a1")?;
+		let stash1 = stash_save(repo_path, None, false, false)?;
+
+		File::create(root.join("a.txt"))?.write_all(b"a2")?;
+		File::create(root.join("b.txt"))?.write_all(b"b2")?;
+		let stash2 = stash_save(repo_path, None, false, false)?;
+
+		let files = |old, new| {
+			get_trees_files(repo_path, OldNew { old, new })
+				.unwrap()
+				.into_iter()
+				.map(|item| item.path)
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(
+			files(
+				TreeSource::Commit(head),
+				TreeSource::Commit(stash1)
+			),
+			vec!["a.txt"]
+		);
+		assert_eq!(
+			files(
+				TreeSource::Commit(stash1),
+				TreeSource::Commit(stash2)
+			),
+			vec!["a.txt", "b.txt"]
+		);
+
+		File::create(root.join("b.txt"))?.write_all(b"b2")?;
+		assert_eq!(
+			files(TreeSource::WorkDir, TreeSource::Commit(stash1)),
+			vec!["a.txt", "b.txt"]
+		);
+		assert_eq!(
+			files(TreeSource::WorkDir, TreeSource::Commit(stash2)),
+			vec!["a.txt"]
+		);
+
+		// the stash is the new side even against the workdir
+		let diff = get_diff_trees(
+			repo_path,
+			OldNew {
+				old: TreeSource::WorkDir,
+				new: TreeSource::Commit(stash2),
+			},
+			String::from("a.txt"),
+			None,
+		)?;
+		let added = diff
+			.hunks
+			.iter()
+			.flat_map(|hunk| &hunk.lines)
+			.filter(|line| line.line_type == DiffLineType::Add)
+			.map(|line| line.content.trim().to_string())
+			.collect::<Vec<_>>();
+		assert!(added.contains(&String::from("a2")));
+		assert!(!added.contains(&String::from("a")));
+
+		Ok(())
+	}
+
 	#[test]
 	fn test_smoke() -> Result<()> {
 		let file_path = Path::new("file1.txt");
@@ -266,4 +477,40 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_patch_hashes() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let base = write_commit_file(&repo, "a.txt", "a", "c1");
+		write_commit_file(&repo, "a.txt", "b", "c2");
+		let first = write_commit_file(&repo, "b.txt", "b", "c3");
+		let hashes = get_compare_commits_patch_hashes(
+			repo_path,
+			OldNew {
+				old: base,
+				new: first,
+			},
+		)?;
+
+		assert_eq!(hashes.len(), 2);
+
+		// only touches `b.txt`
+		let second = write_commit_file(&repo, "b.txt", "c", "c4");
+		let changed = get_compare_commits_patch_hashes(
+			repo_path,
+			OldNew {
+				old: base,
+				new: second,
+			},
+		)?;
+
+		assert_eq!(changed["a.txt"], hashes["a.txt"]);
+		assert_ne!(changed["b.txt"], hashes["b.txt"]);
+
+		Ok(())
+	}
 }
@@ -5,8 +5,12 @@ use super::{
 use crate::error::Result;
 use bitflags::bitflags;
 use fuzzy_matcher::FuzzyMatcher;
-use git2::{Diff, Repository};
-use std::sync::Arc;
+use git2::{Delta, Diff, DiffFindOptions, Repository};
+use std::{
+	collections::HashMap,
+	path::Path,
+	sync::{Arc, Mutex},
+};
 
 ///
 pub type SharedCommitFilterFn = Arc<
@@ -34,6 +38,126 @@ pub fn diff_contains_file(file_path: String) -> SharedCommitFilterFn {
 	))
 }
 
+/// follows a file back through renames while the log is walked
+/// (like `git log --follow`) and remembers the path it had in the
+/// commits that touched it
+#[derive(Clone)]
+pub struct FileFollow {
+	file_path: String,
+	paths: Arc<Mutex<HashMap<CommitId, String>>>,
+}
+
+impl FileFollow {
+	///
+	pub fn new(file_path: String) -> Self {
+		Self {
+			file_path,
+			paths: Arc::new(Mutex::new(HashMap::new())),
+		}
+	}
+
+	/// filter for `LogWalker`/`AsyncLog` matching every commit that
+	/// touched the file under any of its names
+	pub fn filter(&self) -> SharedCommitFilterFn {
+		let follow = self.clone();
+		Arc::new(Box::new(
+			move |repo: &Repository,
+			      commit_id: &CommitId|
+			      -> Result<bool> {
+				follow.visit(repo, *commit_id)
+			},
+		))
+	}
+
+	/// path of the file in `id`, only meaningful for commits the
+	/// filter already matched
+	pub fn path_at(&self, id: CommitId) -> Result<String> {
+		Ok(self
+			.paths
+			.lock()?
+			.get(&id)
+			.cloned()
+			.unwrap_or_else(|| self.file_path.clone()))
+	}
+
+	fn visit(&self, repo: &Repository, id: CommitId) -> Result<bool> {
+		let path = self
+			.paths
+			.lock()?
+			.remove(&id)
+			.unwrap_or_else(|| self.file_path.clone());
+
+		let diff = get_commit_diff(
+			repo,
+			id,
+			Some(path.clone()),
+			None,
+			None,
+		)?;
+		let touched = diff.deltas().len() > 0;
+		let added =
+			diff.deltas().any(|delta| delta.status() == Delta::Added);
+
+		let parent_path = if added {
+			rename_source(repo, id, &path)?
+		} else {
+			None
+		};
+
+		let commit = repo.find_commit(id.into())?;
+		let mut paths = self.paths.lock()?;
+
+		for (index, parent) in commit.parent_ids().enumerate() {
+			let parent_path = if index == 0 {
+				parent_path.as_ref().unwrap_or(&path)
+			} else {
+				&path
+			};
+
+			if parent_path != &self.file_path {
+				paths
+					.entry(parent.into())
+					.or_insert_with(|| parent_path.clone());
+			}
+		}
+
+		if touched && path != self.file_path {
+			paths.insert(id, path);
+		}
+
+		Ok(touched)
+	}
+}
+
+/// old path of `path` if `id` renamed it (compared to its first parent)
+fn rename_source(
+	repo: &Repository,
+	id: CommitId,
+	path: &str,
+) -> Result<Option<String>> {
+	let mut diff = get_commit_diff(repo, id, None, None, None)?;
+
+	let mut opts = DiffFindOptions::new();
+	opts.renames(true);
+	diff.find_similar(Some(&mut opts))?;
+
+	let source = diff
+		.deltas()
+		.find(|delta| {
+			delta.status() == Delta::Renamed
+				&& delta.new_file().path() == Some(Path::new(path))
+		})
+		.and_then(|delta| {
+			delta
+				.old_file()
+				.path()
+				.and_then(Path::to_str)
+				.map(String::from)
+		});
+
+	Ok(source)
+}
+
 bitflags! {
 	///
 	#[derive(Debug, Clone, Copy)]
@@ -222,3 +346,138 @@ pub fn filter_commit_by_search(
 		},
 	))
 }
+
+/// what `filter_commit_by_criteria` matches, empty ones match every
+/// commit
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct LogFilterCriteria {
+	/// in the summary or body
+	pub message: String,
+	/// in the name or email
+	pub author: String,
+	/// file or folder the commit touched
+	pub path: String,
+}
+
+impl LogFilterCriteria {
+	///
+	pub fn is_empty(&self) -> bool {
+		self.message.is_empty()
+			&& self.author.is_empty()
+			&& self.path.is_empty()
+	}
+}
+
+/// commits matching all criteria that are set, text is matched
+/// ignoring case
+pub fn filter_commit_by_criteria(
+	criteria: LogFilterCriteria,
+) -> SharedCommitFilterFn {
+	let message = criteria.message.to_lowercase();
+	let author = criteria.author.to_lowercase();
+	let path = (!criteria.path.is_empty())
+		.then(|| diff_contains_file(criteria.path));
+
+	Arc::new(Box::new(
+		move |repo: &Repository,
+		      commit_id: &CommitId|
+		      -> Result<bool> {
+			let commit = repo.find_commit((*commit_id).into())?;
+
+			if !message.is_empty()
+				&& !String::from_utf8_lossy(commit.message_bytes())
+					.to_lowercase()
+					.contains(&message)
+			{
+				return Ok(false);
+			}
+
+			if !author.is_empty() {
+				let mailmap = repo.mailmap()?;
+				let signature =
+					get_author_of_commit(&commit, &mailmap);
+
+				if ![signature.name(), signature.email()].iter().any(
+					|text| {
+						text.is_some_and(|text| {
+							text.to_lowercase().contains(&author)
+						})
+					},
+				) {
+					return Ok(false);
+				}
+			}
+
+			path.as_ref()
+				.map_or(Ok(true), |path| path(repo, commit_id))
+		},
+	))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		tests::{repo_init_empty, write_commit_file},
+		LogWalker,
+	};
+
+	// `LogWalker::read` is no `io::Read`
+	#[allow(clippy::read_zero_byte_vec)]
+	fn filtered(
+		repo: &Repository,
+		criteria: LogFilterCriteria,
+	) -> Vec<CommitId> {
+		let mut items = Vec::new();
+		LogWalker::new(repo, 100)
+			.unwrap()
+			.filter(Some(filter_commit_by_criteria(criteria)))
+			.read(&mut items)
+			.unwrap();
+		items
+	}
+
+	#[test]
+	fn test_filter_by_criteria() {
+		let (_td, repo) = repo_init_empty().unwrap();
+		std::fs::create_dir(repo.workdir().unwrap().join("src"))
+			.unwrap();
+
+		let c1 = write_commit_file(&repo, "src/a", "1", "Fix parser");
+		let c2 =
+			write_commit_file(&repo, "b", "1", "fix docs\n\nbody");
+		let c3 = write_commit_file(&repo, "src/c", "1", "feature");
+
+		let criteria = |message: &str, author: &str, path: &str| {
+			LogFilterCriteria {
+				message: message.into(),
+				author: author.into(),
+				path: path.into(),
+			}
+		};
+
+		assert_eq!(
+			filtered(&repo, criteria("", "", "")),
+			vec![c3, c2, c1]
+		);
+		assert_eq!(
+			filtered(&repo, criteria("FIX", "", "")),
+			vec![c2, c1]
+		);
+		assert_eq!(
+			filtered(&repo, criteria("body", "", "")),
+			vec![c2]
+		);
+		assert_eq!(
+			filtered(&repo, criteria("fix", "", "src")),
+			vec![c1]
+		);
+		assert_eq!(
+			filtered(&repo, criteria("", "EMAIL", "src")),
+			vec![c3, c1]
+		);
+		assert!(
+			filtered(&repo, criteria("", "someone", "")).is_empty()
+		);
+	}
+}
@@ -1,26 +1,68 @@
-use super::{CommitId, RepoPath};
+use super::{CommitId, RebaseState, RepoPath};
 use crate::{
-	error::Result,
+	error::{Error, Result},
 	sync::{repository::repo, utils::read_file},
 };
+use git2::{RepositoryState, RevertOptions, StatusOptions};
 use scopetime::scope_time;
 
 const GIT_REVERT_HEAD_FILE: &str = "REVERT_HEAD";
 
-///
+/// merges can only be reverted against one of their parents
+pub fn is_merge_commit(
+	repo_path: &RepoPath,
+	commit: CommitId,
+) -> Result<bool> {
+	let repo = repo(repo_path)?;
+
+	let commit = repo.find_commit(commit.into())?;
+
+	Ok(commit.parent_count() > 1)
+}
+
+/// undoes `commit` in the index and working tree, leaving the
+/// `Revert "..."` message for `commit_revert`. merges need the
+/// `mainline` parent (starting at 1) to revert against.
 pub fn revert_commit(
 	repo_path: &RepoPath,
 	commit: CommitId,
-) -> Result<()> {
+	mainline: Option<u32>,
+) -> Result<RebaseState> {
 	scope_time!("revert");
 
 	let repo = repo(repo_path)?;
 
+	if repo.state() != RepositoryState::Clean {
+		return Err(Error::Generic(String::from(
+			"revert error: finish the pending operation first",
+		)));
+	}
+
+	let mut options = StatusOptions::new();
+	options.include_untracked(false).include_ignored(false);
+	if !repo.statuses(Some(&mut options))?.is_empty() {
+		return Err(Error::UncommittedChanges);
+	}
+
 	let commit = repo.find_commit(commit.into())?;
 
-	repo.revert(&commit, None)?;
+	let mut options = RevertOptions::new();
+	if commit.parent_count() > 1 {
+		let mainline = mainline.ok_or_else(|| {
+			Error::Generic(String::from(
+				"revert error: a merge is reverted against one of its parents",
+			))
+		})?;
+		options.mainline(mainline);
+	}
+
+	repo.revert(&commit, Some(&mut options))?;
 
-	Ok(())
+	Ok(if repo.index()?.has_conflicts() {
+		RebaseState::Conflicted
+	} else {
+		RebaseState::Finished
+	})
 }
 
 ///
@@ -49,3 +91,74 @@ pub fn commit_revert(
 
 	Ok(id)
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		repo_state,
+		tests::{debug_cmd_print, repo_init, write_commit_file},
+		utils::repo_write_file,
+		RepoState,
+	};
+	use std::fs;
+
+	#[test]
+	fn test_revert_commit() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "f", "1", "c1");
+		let c2 = write_commit_file(&repo, "f", "2", "c2");
+
+		repo_write_file(&repo, "f", "3").unwrap();
+		assert!(matches!(
+			revert_commit(repo_path, c2, None),
+			Err(Error::UncommittedChanges)
+		));
+		repo_write_file(&repo, "f", "2").unwrap();
+
+		assert_eq!(
+			revert_commit(repo_path, c2, None).unwrap(),
+			RebaseState::Finished
+		);
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Revert);
+		assert_eq!(fs::read_to_string(root.join("f")).unwrap(), "1");
+		assert!(crate::sync::merge_msg(repo_path)
+			.unwrap()
+			.starts_with("Revert \"c2\""));
+
+		commit_revert(repo_path, "Revert c2").unwrap();
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+	}
+
+	#[test]
+	fn test_revert_merge_needs_mainline() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "f", "1", "c1");
+		debug_cmd_print(repo_path, "git checkout -q -b feature");
+		write_commit_file(&repo, "g", "1", "c2");
+		debug_cmd_print(repo_path, "git checkout -q master");
+		write_commit_file(&repo, "h", "1", "c3");
+		debug_cmd_print(repo_path, "git merge -q --no-edit feature");
+
+		let merge: CommitId =
+			repo.head().unwrap().target().unwrap().into();
+		assert!(is_merge_commit(repo_path, merge).unwrap());
+		assert!(revert_commit(repo_path, merge, None).is_err());
+
+		assert_eq!(
+			revert_commit(repo_path, merge, Some(1)).unwrap(),
+			RebaseState::Finished
+		);
+		// what the merge brought in from `feature` is gone
+		assert!(!root.join("g").exists());
+		assert!(root.join("h").exists());
+	}
+}
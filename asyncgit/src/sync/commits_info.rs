@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{collections::HashSet, fmt::Display};
 
 use super::RepoPath;
 use crate::{
@@ -8,7 +8,7 @@ use crate::{
 		repository::{gix_repo, repo},
 	},
 };
-use git2::{Commit, Error, Oid};
+use git2::{Commit, Error, Oid, Repository};
 use scopetime::scope_time;
 use unicode_truncate::UnicodeTruncateStr;
 
@@ -113,6 +113,22 @@ pub struct CommitInfo {
 	pub author: String,
 	///
 	pub id: CommitId,
+	/// has a signature, it is not verified
+	pub signed: bool,
+	/// has a note in the default notes ref
+	pub has_note: bool,
+}
+
+/// commits annotated in the default notes ref
+fn noted_commits(repo: &Repository) -> HashSet<Oid> {
+	repo.notes(None).map_or_else(
+		|_| HashSet::new(),
+		|notes| {
+			notes
+				.filter_map(|note| note.ok().map(|(_, id)| id))
+				.collect()
+		},
+	)
 }
 
 ///
@@ -125,6 +141,7 @@ pub fn get_commits_info(
 
 	let repo = repo(repo_path)?;
 	let mailmap = repo.mailmap()?;
+	let noted = noted_commits(&repo);
 
 	let commits = ids
 		.iter()
@@ -146,6 +163,8 @@ pub fn get_commits_info(
 				author,
 				time: c.time().seconds(),
 				id: CommitId(c.id()),
+				signed: c.header_field_bytes("gpgsig").is_ok(),
+				has_note: noted.contains(&c.id()),
 			}
 		})
 		.collect::<Vec<_>>();
@@ -180,9 +199,22 @@ pub fn get_commit_info(
 		author: author.to_string(),
 		time: commit_ref.time().seconds,
 		id: commit.id().detach().into(),
+		signed: commit_ref.extra_headers().pgp_signature().is_some(),
+		has_note: commit_has_note(repo_path, *commit_id)?,
 	})
 }
 
+fn commit_has_note(
+	repo_path: &RepoPath,
+	commit_id: CommitId,
+) -> Result<bool> {
+	let repo = repo(repo_path)?;
+
+	let note = repo.find_note(None, commit_id.into());
+
+	Ok(note.is_ok())
+}
+
 /// if `message_limit` is set the message will be
 /// limited to the first line and truncated to fit
 pub fn get_message(
@@ -221,7 +253,7 @@ pub fn gix_get_message(
 
 #[cfg(test)]
 mod tests {
-	use super::get_commits_info;
+	use super::{get_commit_info, get_commits_info};
 	use crate::{
 		error::Result,
 		sync::{
@@ -262,6 +294,50 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_signed_and_noted() {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = commit(repo_path, "commit1").unwrap();
+
+		let sig = repo.signature().unwrap();
+		let head = repo.find_commit(c1.into()).unwrap();
+		let buffer = repo
+			.commit_create_buffer(
+				&sig,
+				&sig,
+				"commit2",
+				&head.tree().unwrap(),
+				&[&head],
+			)
+			.unwrap();
+		let c2: CommitId = repo
+			.commit_signed(
+				buffer.as_str().unwrap(),
+				"-----BEGIN PGP SIGNATURE-----",
+				None,
+			)
+			.unwrap()
+			.into();
+		repo.note(&sig, &sig, None, c1.into(), "note", false)
+			.unwrap();
+
+		let res = get_commits_info(repo_path, &[c2, c1], 50).unwrap();
+
+		assert!(res[0].signed);
+		assert!(!res[0].has_note);
+		assert!(!res[1].signed);
+		assert!(res[1].has_note);
+
+		let info = get_commit_info(repo_path, &c2).unwrap();
+		assert!(info.signed);
+		assert!(!info.has_note);
+		assert!(get_commit_info(repo_path, &c1).unwrap().has_note);
+	}
+
 	#[test]
 	fn test_log_first_msg_line() -> Result<()> {
 		let file_path = Path::new("foo");
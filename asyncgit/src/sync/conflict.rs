@@ -0,0 +1,1147 @@
+//! sync git api for inspecting and resolving conflicts
+
+use super::{
+	index_file::{check_index_writable, strip_index_extension},
+	repository::repo,
+	utils::work_dir,
+	CommitId, RepoPath,
+};
+use crate::error::{Error, Result};
+use git2::{
+	Index, IndexConflict, IndexEntry, MergeFileOptions, Oid,
+	Repository,
+};
+use scopetime::scope_time;
+use std::{fs, path::Path};
+
+/// bits of `IndexEntry::flags` holding the stage of an entry
+const INDEX_ENTRY_STAGE_MASK: u16 = 0x3000;
+
+/// kind of conflict derived from the stages (ancestor, ours, theirs)
+/// that are present in the index for a path
+#[derive(Copy, Clone, Hash, PartialEq, Eq, Debug)]
+pub enum ConflictType {
+	/// modified on both sides
+	BothModified,
+	/// added on both sides with different content
+	BothAdded,
+	/// deleted by us, modified by them
+	DeletedByUs,
+	/// modified by us, deleted by them
+	DeletedByThem,
+	/// deleted on both sides
+	BothDeleted,
+	/// only exists on our side
+	AddedByUs,
+	/// only exists on their side
+	AddedByThem,
+	/// renamed to different names on both sides
+	BothRenamed,
+}
+
+impl ConflictType {
+	const fn from_stages(
+		ancestor: bool,
+		ours: bool,
+		theirs: bool,
+	) -> Option<Self> {
+		match (ancestor, ours, theirs) {
+			(true, true, true) => Some(Self::BothModified),
+			(false, true, true) => Some(Self::BothAdded),
+			(true, false, true) => Some(Self::DeletedByUs),
+			(true, true, false) => Some(Self::DeletedByThem),
+			(true, false, false) => Some(Self::BothDeleted),
+			(false, true, false) => Some(Self::AddedByUs),
+			(false, false, true) => Some(Self::AddedByThem),
+			(false, false, false) => None,
+		}
+	}
+
+	/// resolutions that can be applied to this kind of conflict
+	pub const fn resolutions(self) -> &'static [ConflictResolution] {
+		use ConflictResolution::{
+			KeepDeleted, KeepModified, MergeBoth, TakeOurs,
+			TakeTheirs,
+		};
+
+		match self {
+			Self::BothModified | Self::BothRenamed => {
+				&[TakeOurs, TakeTheirs]
+			}
+			Self::BothAdded => &[TakeOurs, TakeTheirs, MergeBoth],
+			Self::DeletedByUs | Self::DeletedByThem => {
+				&[KeepDeleted, KeepModified]
+			}
+			Self::AddedByUs => &[TakeOurs],
+			Self::AddedByThem => &[TakeTheirs],
+			Self::BothDeleted => &[KeepDeleted],
+		}
+	}
+
+	/// the resolution keeping what `side` did to the path (like
+	/// `git checkout --ours/--theirs` followed by `git add`), `None`
+	/// if the path does not exist on that side
+	pub const fn side_resolution(
+		self,
+		side: ConflictSide,
+	) -> Option<ConflictResolution> {
+		use ConflictResolution::{
+			KeepDeleted, KeepModified, TakeOurs, TakeTheirs,
+		};
+
+		match (side, self) {
+			(
+				ConflictSide::Ours,
+				Self::BothModified
+				| Self::BothAdded
+				| Self::BothRenamed
+				| Self::AddedByUs,
+			) => Some(TakeOurs),
+			(
+				ConflictSide::Theirs,
+				Self::BothModified
+				| Self::BothAdded
+				| Self::BothRenamed
+				| Self::AddedByThem,
+			) => Some(TakeTheirs),
+			(ConflictSide::Ours, Self::DeletedByThem)
+			| (ConflictSide::Theirs, Self::DeletedByUs) => Some(KeepModified),
+			(ConflictSide::Ours, Self::DeletedByUs)
+			| (ConflictSide::Theirs, Self::DeletedByThem)
+			| (_, Self::BothDeleted) => Some(KeepDeleted),
+			(ConflictSide::Ours, Self::AddedByThem)
+			| (ConflictSide::Theirs, Self::AddedByUs) => None,
+		}
+	}
+}
+
+/// how to resolve a conflict
+#[derive(Copy, Clone, Hash, PartialEq, Eq, Debug)]
+pub enum ConflictResolution {
+	/// use our version of the file
+	TakeOurs,
+	/// use their version of the file
+	TakeTheirs,
+	/// write both versions separated by conflict markers into the
+	/// workdir, the path stays conflicted until it is staged
+	MergeBoth,
+	/// remove the file from index and workdir
+	KeepDeleted,
+	/// keep the version of the side that did not delete the file
+	KeepModified,
+}
+
+/// one side of a conflict
+#[derive(Copy, Clone, Hash, PartialEq, Eq, Debug)]
+pub enum ConflictSide {
+	/// `HEAD`, the branch merged into
+	Ours,
+	/// the branch or commit merged, cherry-picked or reverted
+	Theirs,
+}
+
+/// names of the two sides of the conflicts
+#[derive(Clone, Hash, PartialEq, Eq, Debug)]
+pub struct ConflictSides {
+	/// the checked out branch, the short id of `HEAD` if detached
+	pub ours: String,
+	/// the merged branch from `MERGE_MSG`, the short id of the
+	/// commit otherwise
+	pub theirs: String,
+}
+
+/// both sides renamed the same file to different names
+#[derive(Clone, Hash, PartialEq, Eq, Debug)]
+pub struct RenameConflict {
+	/// name before the rename
+	pub ancestor: String,
+	/// name on our side
+	pub ours: String,
+	/// name on their side
+	pub theirs: String,
+}
+
+/// a conflicted path in the index
+#[derive(Clone, Hash, PartialEq, Eq, Debug)]
+pub struct Conflict {
+	///
+	pub path: String,
+	///
+	pub kind: ConflictType,
+	/// all three paths involved if `kind` is `BothRenamed`
+	pub rename: Option<RenameConflict>,
+}
+
+struct ConflictEntry {
+	path: String,
+	kind: ConflictType,
+	stages: IndexConflict,
+}
+
+/// all conflicts in the index
+pub fn get_conflicts(repo_path: &RepoPath) -> Result<Vec<Conflict>> {
+	scope_time!("get_conflicts");
+
+	let repo = repo(repo_path)?;
+
+	get_conflicts_repo(&repo)
+}
+
+/// conflict of a single path, see [`get_conflicts`]
+pub fn get_conflict(
+	repo_path: &RepoPath,
+	path: &str,
+) -> Result<Option<Conflict>> {
+	Ok(get_conflicts(repo_path)?
+		.into_iter()
+		.find(|conflict| conflict.path == path))
+}
+
+pub fn get_conflicts_repo(
+	repo: &Repository,
+) -> Result<Vec<Conflict>> {
+	let index = repo.index()?;
+
+	let mut entries = Vec::new();
+	for stages in index.conflicts()? {
+		let stages = stages?;
+
+		let Some(kind) = ConflictType::from_stages(
+			stages.ancestor.is_some(),
+			stages.our.is_some(),
+			stages.their.is_some(),
+		) else {
+			continue;
+		};
+
+		let path = stages
+			.ancestor
+			.as_ref()
+			.or(stages.our.as_ref())
+			.or(stages.their.as_ref())
+			.map(|entry| String::from_utf8(entry.path.clone()))
+			.transpose()?
+			.unwrap_or_default();
+
+		entries.push(ConflictEntry { path, kind, stages });
+	}
+
+	let renames = find_renames(&entries);
+
+	Ok(entries
+		.into_iter()
+		.map(|entry| {
+			let rename = renames
+				.iter()
+				.find(|rename| {
+					rename.ancestor == entry.path
+						|| rename.ours == entry.path
+						|| rename.theirs == entry.path
+				})
+				.cloned();
+
+			Conflict {
+				kind: if rename.is_some() {
+					ConflictType::BothRenamed
+				} else {
+					entry.kind
+				},
+				path: entry.path,
+				rename,
+			}
+		})
+		.collect())
+}
+
+/// the index has no record of renames, a rename on both sides leaves
+/// the original name with only an ancestor stage and the new names
+/// with only our or their stage. we pair them up by content and fall
+/// back to pairing if there is only one candidate of each.
+fn find_renames(entries: &[ConflictEntry]) -> Vec<RenameConflict> {
+	let of_kind = |kind: ConflictType| {
+		entries
+			.iter()
+			.filter(|entry| entry.kind == kind)
+			.collect::<Vec<_>>()
+	};
+
+	let deleted = of_kind(ConflictType::BothDeleted);
+	let mut ours = of_kind(ConflictType::AddedByUs);
+	let mut theirs = of_kind(ConflictType::AddedByThem);
+	let single = deleted.len() == 1;
+
+	let mut res = Vec::new();
+
+	for entry in deleted {
+		let Some(id) = entry.stages.ancestor.as_ref().map(|e| e.id)
+		else {
+			continue;
+		};
+
+		let our_idx = rename_target(&ours, id, single, |stages| {
+			stages.our.as_ref()
+		});
+		let their_idx =
+			rename_target(&theirs, id, single, |stages| {
+				stages.their.as_ref()
+			});
+
+		if let (Some(our_idx), Some(their_idx)) = (our_idx, their_idx)
+		{
+			res.push(RenameConflict {
+				ancestor: entry.path.clone(),
+				ours: ours.remove(our_idx).path.clone(),
+				theirs: theirs.remove(their_idx).path.clone(),
+			});
+		}
+	}
+
+	res
+}
+
+fn rename_target(
+	candidates: &[&ConflictEntry],
+	id: Oid,
+	single: bool,
+	stage: impl Fn(&IndexConflict) -> Option<&IndexEntry>,
+) -> Option<usize> {
+	candidates
+		.iter()
+		.position(|entry| {
+			stage(&entry.stages).is_some_and(|e| e.id == id)
+		})
+		.or_else(|| (single && candidates.len() == 1).then_some(0))
+}
+
+/// applies `resolution` to `conflict` and updates index and workdir
+/// accordingly.
+///
+/// fails if the resolution does not apply to this kind of conflict
+/// (see [`ConflictType::resolutions`]).
+pub fn resolve_conflict(
+	repo_path: &RepoPath,
+	conflict: &Conflict,
+	resolution: ConflictResolution,
+) -> Result<()> {
+	scope_time!("resolve_conflict");
+
+	if !conflict.kind.resolutions().contains(&resolution) {
+		return Err(Error::Generic(format!(
+			"{resolution:?} does not resolve a {:?} conflict",
+			conflict.kind
+		)));
+	}
+
+	let repo = repo(repo_path)?;
+	check_index_writable(&repo)?;
+	let mut index = repo.index()?;
+
+	let stages = |index: &Index, path: &str| {
+		index.conflict_get(Path::new(path))
+	};
+
+	match (resolution, &conflict.rename) {
+		(
+			ConflictResolution::TakeOurs
+			| ConflictResolution::TakeTheirs,
+			Some(rename),
+		) => {
+			let (keep, entry, drop) =
+				if resolution == ConflictResolution::TakeOurs {
+					let entry = stages(&index, &rename.ours)?.our;
+					(&rename.ours, entry, &rename.theirs)
+				} else {
+					let entry = stages(&index, &rename.theirs)?.their;
+					(&rename.theirs, entry, &rename.ours)
+				};
+
+			remove_path(&repo, &mut index, &rename.ancestor)?;
+			remove_path(&repo, &mut index, drop)?;
+			take_entry(&repo, &mut index, keep, entry)?;
+		}
+		(ConflictResolution::TakeOurs, None) => {
+			let entry = stages(&index, &conflict.path)?.our;
+			take_entry(&repo, &mut index, &conflict.path, entry)?;
+		}
+		(ConflictResolution::TakeTheirs, None) => {
+			let entry = stages(&index, &conflict.path)?.their;
+			take_entry(&repo, &mut index, &conflict.path, entry)?;
+		}
+		(ConflictResolution::KeepModified, _) => {
+			let stages = stages(&index, &conflict.path)?;
+			let entry = stages.our.or(stages.their);
+			take_entry(&repo, &mut index, &conflict.path, entry)?;
+		}
+		(ConflictResolution::KeepDeleted, _) => {
+			remove_path(&repo, &mut index, &conflict.path)?;
+		}
+		(ConflictResolution::MergeBoth, _) => {
+			merge_both(&repo, &stages(&index, &conflict.path)?)?;
+		}
+	}
+
+	index.write()?;
+	drop_rename_records(&mut index)?;
+
+	Ok(())
+}
+
+/// resolves all conflicts of paths in `folder` (all of them if it is
+/// empty) by taking `side` (see [`ConflictType::side_resolution`]).
+///
+/// returns the conflicts left because the path does not exist on
+/// that side.
+pub fn resolve_conflicts_with_side(
+	repo_path: &RepoPath,
+	folder: &str,
+	side: ConflictSide,
+) -> Result<Vec<Conflict>> {
+	scope_time!("resolve_conflicts_with_side");
+
+	let folder = folder.trim_end_matches('/');
+	let in_folder = |path: &str| {
+		folder.is_empty()
+			|| path
+				.strip_prefix(folder)
+				.is_some_and(|rest| rest.starts_with('/'))
+	};
+
+	let mut left: Vec<Conflict> = Vec::new();
+
+	// resolving a rename resolves all paths involved, so the
+	// conflicts are read again after each one
+	loop {
+		let next = get_conflicts(repo_path)?.into_iter().find(|c| {
+			in_folder(&c.path)
+				&& !left.iter().any(|left| left.path == c.path)
+		});
+		let Some(conflict) = next else {
+			break;
+		};
+
+		match conflict.kind.side_resolution(side) {
+			Some(resolution) => {
+				resolve_conflict(repo_path, &conflict, resolution)?;
+			}
+			None => left.push(conflict),
+		}
+	}
+
+	Ok(left)
+}
+
+/// names to tell the sides of the pending conflicts apart
+pub fn conflict_sides(repo_path: &RepoPath) -> Result<ConflictSides> {
+	scope_time!("conflict_sides");
+
+	let repo = repo(repo_path)?;
+
+	let ours = match repo.head() {
+		Ok(head) if head.is_branch() => {
+			head.shorthand().unwrap_or("HEAD").to_string()
+		}
+		Ok(head) => head.target().map_or_else(
+			|| String::from("HEAD"),
+			|id| CommitId::from(id).get_short_string(),
+		),
+		Err(_) => String::from("HEAD"),
+	};
+
+	let merged = repo
+		.message()
+		.ok()
+		.and_then(|msg| merged_name(&msg).map(String::from));
+
+	let theirs = merged
+		.or_else(|| {
+			["MERGE_HEAD", "CHERRY_PICK_HEAD", "REVERT_HEAD"]
+				.iter()
+				.find_map(|name| repo.refname_to_id(name).ok())
+				.map(|id| CommitId::from(id).get_short_string())
+		})
+		.unwrap_or_else(|| String::from("theirs"));
+
+	Ok(ConflictSides { ours, theirs })
+}
+
+/// `other` from a `MERGE_MSG` like "Merge branch 'other' into main"
+fn merged_name(msg: &str) -> Option<&str> {
+	let subject = msg.lines().next()?.strip_prefix("Merge ")?;
+	let (_, rest) = subject.split_once('\'')?;
+	let (name, _) = rest.split_once('\'')?;
+
+	(!name.is_empty()).then_some(name)
+}
+
+/// libgit2 records renames of conflicts in the `NAME` extension of
+/// the index and fails any checkout once a path referenced there is
+/// resolved (or outside of the checkout pathspec). we do not rely on
+/// it (see [`find_renames`]) and libgit2 offers no way to remove
+/// just these records, so we strip the extension from the written
+/// index file and keep all others (like resolve undo and tree cache).
+fn drop_rename_records(index: &mut Index) -> Result<()> {
+	let Some(path) = index.path().map(Path::to_path_buf) else {
+		return Ok(());
+	};
+
+	let data = fs::read(&path)?;
+	let Some(stripped) =
+		strip_index_extension(&data, NAME_EXTENSION)?
+	else {
+		return Ok(());
+	};
+
+	let lock = path.with_extension("lock");
+	fs::write(&lock, stripped)?;
+	fs::rename(&lock, &path)?;
+
+	index.read(true)?;
+
+	Ok(())
+}
+
+const NAME_EXTENSION: [u8; 4] = *b"NAME";
+
+/// replaces all stages of `path` by `entry` and writes its content
+/// into the workdir
+fn take_entry(
+	repo: &Repository,
+	index: &mut Index,
+	path: &str,
+	entry: Option<IndexEntry>,
+) -> Result<()> {
+	let mut entry = entry.ok_or_else(|| {
+		Error::Generic(format!("no version of '{path}' to keep"))
+	})?;
+
+	entry.flags &= !INDEX_ENTRY_STAGE_MASK;
+
+	index.remove_path(Path::new(path))?;
+	index.add(&entry)?;
+
+	// `checkout_index` refuses to work on an index that still
+	// contains rename conflicts, so we write the file ourselves
+	let file = work_dir(repo)?.join(path);
+	if let Some(parent) = file.parent() {
+		fs::create_dir_all(parent)?;
+	}
+
+	// writing through a symlink would change its target instead
+	if file.symlink_metadata().is_ok() {
+		fs::remove_file(&file)?;
+	}
+
+	let blob = repo.find_blob(entry.id)?;
+
+	#[cfg(unix)]
+	if entry.mode == u32::from(git2::FileMode::Link) {
+		use std::os::unix::ffi::OsStrExt;
+
+		std::os::unix::fs::symlink(
+			std::ffi::OsStr::from_bytes(blob.content()),
+			&file,
+		)?;
+
+		return Ok(());
+	}
+
+	fs::write(&file, blob.content())?;
+
+	#[cfg(unix)]
+	{
+		use std::os::unix::fs::PermissionsExt;
+
+		if entry.mode == u32::from(git2::FileMode::BlobExecutable) {
+			fs::set_permissions(
+				&file,
+				fs::Permissions::from_mode(0o755),
+			)?;
+		}
+	}
+
+	Ok(())
+}
+
+fn remove_path(
+	repo: &Repository,
+	index: &mut Index,
+	path: &str,
+) -> Result<()> {
+	index.remove_path(Path::new(path))?;
+
+	let file = work_dir(repo)?.join(path);
+	if file.symlink_metadata().is_ok() {
+		fs::remove_file(file)?;
+	}
+
+	Ok(())
+}
+
+/// `both added` has no ancestor, so this diffs both sides against an
+/// empty file which puts both versions next to each other
+fn merge_both(
+	repo: &Repository,
+	stages: &IndexConflict,
+) -> Result<()> {
+	let (Some(ours), Some(theirs)) = (&stages.our, &stages.their)
+	else {
+		return Err(Error::Generic(String::from(
+			"merge needs both versions",
+		)));
+	};
+
+	let ancestor = IndexEntry {
+		id: repo.blob(&[])?,
+		file_size: 0,
+		path: ours.path.clone(),
+		..*ours
+	};
+
+	let mut opts = MergeFileOptions::new();
+	opts.our_label("ours").their_label("theirs");
+
+	let merged = repo.merge_file_from_index(
+		&ancestor,
+		ours,
+		theirs,
+		Some(&mut opts),
+	)?;
+
+	let path = String::from_utf8(ours.path.clone())?;
+	fs::write(work_dir(repo)?.join(path), merged.content())?;
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		checkout_branch, commit, create_branch,
+		index_file::read_extensions,
+		merge_branch, reset_workdir,
+		status::{get_status, StatusType},
+		tests::{repo_init, write_commit_file},
+		utils::repo_write_file,
+	};
+	use git2::BranchType;
+	use pretty_assertions::assert_eq;
+	use tempfile::TempDir;
+
+	/// writes (`Some`) or deletes (`None`) files and commits that
+	fn commit_changes(
+		repo: &Repository,
+		changes: &[(&str, Option<&str>)],
+		msg: &str,
+	) {
+		let mut index = repo.index().unwrap();
+		index.read(true).unwrap();
+
+		for (file, content) in changes {
+			if let Some(content) = content {
+				repo_write_file(repo, file, content).unwrap();
+				index.add_path(Path::new(file)).unwrap();
+			} else {
+				fs::remove_file(repo.workdir().unwrap().join(file))
+					.unwrap();
+				index.remove_path(Path::new(file)).unwrap();
+			}
+		}
+
+		index.write().unwrap();
+		commit(
+			&repo.workdir().unwrap().to_str().unwrap().into(),
+			msg,
+		)
+		.unwrap();
+	}
+
+	const RENAMED: &str = "some longer\ncontent\nto\nrename\n";
+
+	/// merges a branch into master that conflicts in every way
+	/// covered here:
+	/// * `a.txt`: modified by us, deleted by them
+	/// * `b.txt`: deleted by us, modified by them
+	/// * `c.txt`: added on both sides
+	/// * `r.txt`: renamed to `r2.txt` by us and `r1.txt` by them
+	fn conflicted_repo() -> (TempDir, Repository, RepoPath) {
+		let (td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: RepoPath =
+			root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a\n", "base");
+		commit_changes(
+			&repo,
+			&[("b.txt", Some("b\n")), ("r.txt", Some(RENAMED))],
+			"base2",
+		);
+
+		create_branch(&repo_path, "other").unwrap();
+		commit_changes(
+			&repo,
+			&[
+				("a.txt", None),
+				("b.txt", Some("b theirs\n")),
+				("c.txt", Some("c theirs\n")),
+				("r.txt", None),
+				("r1.txt", Some(RENAMED)),
+			],
+			"theirs",
+		);
+
+		checkout_branch(&repo_path, "master").unwrap();
+		commit_changes(
+			&repo,
+			&[
+				("a.txt", Some("a ours\n")),
+				("b.txt", None),
+				("c.txt", Some("c ours\n")),
+				("r.txt", None),
+				("r2.txt", Some(RENAMED)),
+			],
+			"ours",
+		);
+
+		merge_branch(&repo_path, "other", BranchType::Local).unwrap();
+
+		(td, repo, repo_path)
+	}
+
+	fn conflict(repo_path: &RepoPath, path: &str) -> Conflict {
+		get_conflict(repo_path, path).unwrap().unwrap()
+	}
+
+	fn index_entry(
+		repo_path: &RepoPath,
+		path: &str,
+	) -> Option<String> {
+		let repo = repo(repo_path).unwrap();
+		let index = repo.index().unwrap();
+		let entry = index.get_path(Path::new(path), 0)?;
+		let blob = repo.find_blob(entry.id).unwrap();
+
+		Some(String::from_utf8(blob.content().to_vec()).unwrap())
+	}
+
+	fn workdir_file(
+		repo_path: &RepoPath,
+		path: &str,
+	) -> Option<String> {
+		fs::read_to_string(repo_path.gitpath().join(path)).ok()
+	}
+
+	#[test]
+	fn test_conflict_types() {
+		let (_td, _repo, repo_path) = conflicted_repo();
+
+		let conflicts = get_conflicts(&repo_path).unwrap();
+
+		let kinds = conflicts
+			.iter()
+			.map(|c| (c.path.as_str(), c.kind))
+			.collect::<Vec<_>>();
+
+		assert_eq!(
+			kinds,
+			vec![
+				("a.txt", ConflictType::DeletedByThem),
+				("b.txt", ConflictType::DeletedByUs),
+				("c.txt", ConflictType::BothAdded),
+				("r.txt", ConflictType::BothRenamed),
+				("r1.txt", ConflictType::BothRenamed),
+				("r2.txt", ConflictType::BothRenamed),
+			]
+		);
+
+		assert_eq!(
+			conflicts[3].rename,
+			Some(RenameConflict {
+				ancestor: String::from("r.txt"),
+				ours: String::from("r2.txt"),
+				theirs: String::from("r1.txt"),
+			})
+		);
+		assert_eq!(conflicts[3].rename, conflicts[4].rename);
+		assert_eq!(conflicts[3].rename, conflicts[5].rename);
+
+		let status =
+			get_status(&repo_path, StatusType::WorkingDir, None)
+				.unwrap();
+		assert!(status.iter().any(|item| item.path == "c.txt"
+			&& item.conflict == Some(ConflictType::BothAdded)));
+	}
+
+	#[test]
+	fn test_delete_modify_keep_deleted() {
+		let (_td, _repo, repo_path) = conflicted_repo();
+
+		for path in ["a.txt", "b.txt"] {
+			resolve_conflict(
+				&repo_path,
+				&conflict(&repo_path, path),
+				ConflictResolution::KeepDeleted,
+			)
+			.unwrap();
+
+			assert_eq!(get_conflict(&repo_path, path).unwrap(), None);
+			assert_eq!(index_entry(&repo_path, path), None);
+			assert_eq!(workdir_file(&repo_path, path), None);
+		}
+	}
+
+	#[test]
+	fn test_delete_modify_keep_modified() {
+		let (_td, _repo, repo_path) = conflicted_repo();
+
+		for (path, content) in
+			[("a.txt", "a ours\n"), ("b.txt", "b theirs\n")]
+		{
+			resolve_conflict(
+				&repo_path,
+				&conflict(&repo_path, path),
+				ConflictResolution::KeepModified,
+			)
+			.unwrap();
+
+			assert_eq!(get_conflict(&repo_path, path).unwrap(), None);
+			assert_eq!(
+				index_entry(&repo_path, path).as_deref(),
+				Some(content)
+			);
+			assert_eq!(
+				workdir_file(&repo_path, path).as_deref(),
+				Some(content)
+			);
+		}
+	}
+
+	#[test]
+	fn test_both_added_take_ours() {
+		let (_td, _repo, repo_path) = conflicted_repo();
+
+		resolve_conflict(
+			&repo_path,
+			&conflict(&repo_path, "c.txt"),
+			ConflictResolution::TakeOurs,
+		)
+		.unwrap();
+
+		assert_eq!(get_conflict(&repo_path, "c.txt").unwrap(), None);
+		assert_eq!(
+			index_entry(&repo_path, "c.txt").as_deref(),
+			Some("c ours\n")
+		);
+
+		// the other conflicts are untouched
+		assert_eq!(get_conflicts(&repo_path).unwrap().len(), 5);
+		assert_eq!(
+			conflict(&repo_path, "r.txt").kind,
+			ConflictType::BothRenamed
+		);
+
+		// index must still be usable for checkouts
+		reset_workdir(&repo_path, "c.txt").unwrap();
+		assert_eq!(
+			workdir_file(&repo_path, "c.txt").as_deref(),
+			Some("c ours\n")
+		);
+	}
+
+	#[test]
+	fn test_both_added_take_theirs() {
+		let (_td, _repo, repo_path) = conflicted_repo();
+
+		resolve_conflict(
+			&repo_path,
+			&conflict(&repo_path, "c.txt"),
+			ConflictResolution::TakeTheirs,
+		)
+		.unwrap();
+
+		assert_eq!(get_conflict(&repo_path, "c.txt").unwrap(), None);
+		assert_eq!(
+			index_entry(&repo_path, "c.txt").as_deref(),
+			Some("c theirs\n")
+		);
+		assert_eq!(
+			workdir_file(&repo_path, "c.txt").as_deref(),
+			Some("c theirs\n")
+		);
+	}
+
+	#[test]
+	fn test_both_added_merge_both() {
+		let (_td, _repo, repo_path) = conflicted_repo();
+
+		// simulate the user having edited the file already
+		fs::write(repo_path.gitpath().join("c.txt"), "garbage")
+			.unwrap();
+
+		resolve_conflict(
+			&repo_path,
+			&conflict(&repo_path, "c.txt"),
+			ConflictResolution::MergeBoth,
+		)
+		.unwrap();
+
+		assert_eq!(
+			workdir_file(&repo_path, "c.txt").as_deref(),
+			Some(
+				"<<<<<<< ours\nc ours\n=======\nc theirs\n>>>>>>> theirs\n"
+			)
+		);
+
+		// still needs to be staged by the user
+		assert_eq!(
+			conflict(&repo_path, "c.txt").kind,
+			ConflictType::BothAdded
+		);
+		assert_eq!(index_entry(&repo_path, "c.txt"), None);
+	}
+
+	#[test]
+	fn test_both_renamed_take_theirs() {
+		let (_td, _repo, repo_path) = conflicted_repo();
+
+		resolve_conflict(
+			&repo_path,
+			&conflict(&repo_path, "r2.txt"),
+			ConflictResolution::TakeTheirs,
+		)
+		.unwrap();
+
+		for path in ["r.txt", "r1.txt", "r2.txt"] {
+			assert_eq!(get_conflict(&repo_path, path).unwrap(), None);
+		}
+
+		assert_eq!(index_entry(&repo_path, "r.txt"), None);
+		assert_eq!(index_entry(&repo_path, "r2.txt"), None);
+		assert_eq!(workdir_file(&repo_path, "r2.txt"), None);
+		assert_eq!(
+			index_entry(&repo_path, "r1.txt").as_deref(),
+			Some(RENAMED)
+		);
+		assert_eq!(
+			workdir_file(&repo_path, "r1.txt").as_deref(),
+			Some(RENAMED)
+		);
+
+		// index must still be usable for checkouts
+		reset_workdir(&repo_path, "*").unwrap();
+	}
+
+	/// signatures of all extensions in the index file
+	fn index_extensions(repo_path: &RepoPath) -> Vec<Vec<u8>> {
+		let data =
+			fs::read(repo(repo_path).unwrap().path().join("index"))
+				.unwrap();
+
+		read_extensions(&data)
+			.unwrap()
+			.into_iter()
+			.map(|(signature, _)| signature.to_vec())
+			.collect()
+	}
+
+	#[test]
+	fn test_keeps_index_extensions() {
+		let (_td, _repo, repo_path) = conflicted_repo();
+
+		assert!(index_extensions(&repo_path)
+			.contains(&NAME_EXTENSION.to_vec()));
+
+		resolve_conflict(
+			&repo_path,
+			&conflict(&repo_path, "c.txt"),
+			ConflictResolution::TakeOurs,
+		)
+		.unwrap();
+
+		// resolve undo of `c.txt` stays, rename records are gone
+		let extensions = index_extensions(&repo_path);
+		assert!(extensions.contains(&b"REUC".to_vec()));
+		assert!(!extensions.contains(&NAME_EXTENSION.to_vec()));
+
+		let repo = repo(&repo_path).unwrap();
+		let index = repo.index().unwrap();
+		assert!(index.get_path(Path::new("c.txt"), 0).is_some());
+		assert!(index.has_conflicts());
+	}
+
+	/// `l` is added as symlink to `a.txt` by us and as file by them
+	#[cfg(unix)]
+	fn symlink_conflict_repo() -> (TempDir, RepoPath) {
+		let (td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: RepoPath =
+			root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a\n", "base");
+		create_branch(&repo_path, "other").unwrap();
+		commit_changes(&repo, &[("l", Some("theirs\n"))], "theirs");
+
+		checkout_branch(&repo_path, "master").unwrap();
+		std::os::unix::fs::symlink("a.txt", root.join("l")).unwrap();
+		let mut index = repo.index().unwrap();
+		index.add_path(Path::new("l")).unwrap();
+		index.write().unwrap();
+		commit(&repo_path, "ours").unwrap();
+
+		merge_branch(&repo_path, "other", BranchType::Local).unwrap();
+
+		(td, repo_path)
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn test_take_symlink() {
+		let (_td, repo_path) = symlink_conflict_repo();
+
+		resolve_conflict(
+			&repo_path,
+			&conflict(&repo_path, "l"),
+			ConflictResolution::TakeOurs,
+		)
+		.unwrap();
+
+		let link = repo_path.gitpath().join("l");
+		assert!(link.is_symlink());
+		assert_eq!(fs::read_link(link).unwrap(), Path::new("a.txt"));
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn test_replace_symlink() {
+		let (_td, repo_path) = symlink_conflict_repo();
+
+		resolve_conflict(
+			&repo_path,
+			&conflict(&repo_path, "l"),
+			ConflictResolution::TakeTheirs,
+		)
+		.unwrap();
+
+		assert!(!repo_path.gitpath().join("l").is_symlink());
+		assert_eq!(
+			workdir_file(&repo_path, "l").as_deref(),
+			Some("theirs\n")
+		);
+
+		// the former link target is untouched
+		assert_eq!(
+			workdir_file(&repo_path, "a.txt").as_deref(),
+			Some("a\n")
+		);
+	}
+
+	#[test]
+	fn test_added_by_one_side_resolutions() {
+		assert_eq!(
+			ConflictType::AddedByUs.resolutions(),
+			&[ConflictResolution::TakeOurs]
+		);
+		assert_eq!(
+			ConflictType::AddedByThem.resolutions(),
+			&[ConflictResolution::TakeTheirs]
+		);
+	}
+
+	#[test]
+	fn test_resolve_with_side() {
+		let (_td, _repo, repo_path) = conflicted_repo();
+
+		let left = resolve_conflicts_with_side(
+			&repo_path,
+			"sub",
+			ConflictSide::Theirs,
+		)
+		.unwrap();
+		assert!(left.is_empty());
+		assert_eq!(get_conflicts(&repo_path).unwrap().len(), 6);
+
+		let left = resolve_conflicts_with_side(
+			&repo_path,
+			"",
+			ConflictSide::Theirs,
+		)
+		.unwrap();
+		assert!(left.is_empty());
+		assert!(get_conflicts(&repo_path).unwrap().is_empty());
+
+		for (path, content) in [
+			("a.txt", None),
+			("b.txt", Some("b theirs\n")),
+			("c.txt", Some("c theirs\n")),
+			("r.txt", None),
+			("r1.txt", Some(RENAMED)),
+			("r2.txt", None),
+		] {
+			assert_eq!(
+				index_entry(&repo_path, path).as_deref(),
+				content
+			);
+			assert_eq!(
+				workdir_file(&repo_path, path).as_deref(),
+				content
+			);
+		}
+	}
+
+	#[test]
+	fn test_side_resolution() {
+		assert_eq!(
+			ConflictType::DeletedByThem
+				.side_resolution(ConflictSide::Ours),
+			Some(ConflictResolution::KeepModified)
+		);
+		assert_eq!(
+			ConflictType::DeletedByThem
+				.side_resolution(ConflictSide::Theirs),
+			Some(ConflictResolution::KeepDeleted)
+		);
+		assert_eq!(
+			ConflictType::AddedByThem
+				.side_resolution(ConflictSide::Ours),
+			None
+		);
+	}
+
+	#[test]
+	fn test_conflict_sides() {
+		let (_td, _repo, repo_path) = conflicted_repo();
+
+		assert_eq!(
+			conflict_sides(&repo_path).unwrap(),
+			ConflictSides {
+				ours: String::from("master"),
+				theirs: String::from("other"),
+			}
+		);
+	}
+
+	#[test]
+	fn test_merged_name() {
+		assert_eq!(
+			merged_name("Merge branch 'feat/a' into main\n"),
+			Some("feat/a")
+		);
+		assert_eq!(
+			merged_name("Merge remote-tracking branch 'origin/main'"),
+			Some("origin/main")
+		);
+		assert_eq!(merged_name("fix: 'quoted'"), None);
+	}
+
+	#[test]
+	fn test_invalid_resolution() {
+		let (_td, _repo, repo_path) = conflicted_repo();
+
+		assert!(resolve_conflict(
+			&repo_path,
+			&conflict(&repo_path, "a.txt"),
+			ConflictResolution::MergeBoth,
+		)
+		.is_err());
+	}
+}
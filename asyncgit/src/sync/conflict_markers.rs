@@ -0,0 +1,374 @@
+//! conflict blocks git writes into conflicted files of the working
+//! tree, with or without the base section of `merge.conflictStyle
+//! diff3` (or `zdiff3`)
+
+use super::{repository::repo, utils::work_dir, RepoPath};
+use crate::error::{Error, Result};
+use scopetime::scope_time;
+use std::{fs, ops::Range};
+
+/// length of the markers git writes unless `conflict-marker-size`
+/// is set, longer runs (like the ones of a recursive merge) are
+/// content
+const MARKER_SIZE: usize = 7;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Marker {
+	Ours,
+	Base,
+	Separator,
+	Theirs,
+}
+
+impl Marker {
+	/// `line` has to start with exactly [`MARKER_SIZE`] marker chars
+	/// followed by the end of the line or, except for the separator,
+	/// a space and a label
+	fn parse(line: &str) -> Option<(Self, &str)> {
+		let line = line.trim_end_matches(['\n', '\r']);
+		let first = line.chars().next()?;
+		let marker = match first {
+			'<' => Self::Ours,
+			'|' => Self::Base,
+			'=' => Self::Separator,
+			'>' => Self::Theirs,
+			_ => return None,
+		};
+
+		let rest = line.get(MARKER_SIZE..)?;
+		if !line[..MARKER_SIZE].chars().all(|c| c == first) {
+			return None;
+		}
+
+		if rest.is_empty() {
+			Some((marker, rest))
+		} else if marker != Self::Separator {
+			rest.strip_prefix(' ').map(|label| (marker, label))
+		} else {
+			None
+		}
+	}
+}
+
+/// how to replace a conflict block
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum BlockResolution {
+	///
+	Ours,
+	///
+	Theirs,
+	/// our lines followed by theirs
+	OursFirst,
+	/// their lines followed by ours
+	TheirsFirst,
+}
+
+/// a conflict in a file, all ranges are lines of
+/// [`ConflictText::lines`]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ConflictBlock {
+	/// from the `<<<<<<<` up to and including the `>>>>>>>` line
+	pub range: Range<usize>,
+	///
+	pub ours: Range<usize>,
+	/// only present in the diff3 style
+	pub base: Option<Range<usize>>,
+	///
+	pub theirs: Range<usize>,
+	/// label of the `<<<<<<<` line
+	pub ours_label: String,
+	/// label of the `>>>>>>>` line
+	pub theirs_label: String,
+}
+
+/// text of a conflicted file split into lines (keeping their line
+/// endings) and the conflict blocks found in it
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct ConflictText {
+	lines: Vec<String>,
+	blocks: Vec<ConflictBlock>,
+}
+
+impl ConflictText {
+	///
+	pub fn parse(text: &str) -> Self {
+		let lines: Vec<String> =
+			text.split_inclusive('\n').map(String::from).collect();
+		let blocks = find_blocks(&lines);
+
+		Self { lines, blocks }
+	}
+
+	/// lines including their line endings
+	pub fn lines(&self) -> &[String] {
+		&self.lines
+	}
+
+	///
+	pub fn blocks(&self) -> &[ConflictBlock] {
+		&self.blocks
+	}
+
+	///
+	pub fn text(&self) -> String {
+		self.lines.concat()
+	}
+
+	/// replaces the block at index `block` of [`Self::blocks`] by
+	/// the side(s) picked in `resolution`
+	pub fn resolve(
+		&mut self,
+		block: usize,
+		resolution: BlockResolution,
+	) {
+		let Some(block) = self.blocks.get(block) else {
+			return;
+		};
+
+		let ours = self.lines[block.ours.clone()].iter();
+		let theirs = self.lines[block.theirs.clone()].iter();
+		let replacement: Vec<String> = match resolution {
+			BlockResolution::Ours => ours.cloned().collect(),
+			BlockResolution::Theirs => theirs.cloned().collect(),
+			BlockResolution::OursFirst => {
+				ours.chain(theirs).cloned().collect()
+			}
+			BlockResolution::TheirsFirst => {
+				theirs.chain(ours).cloned().collect()
+			}
+		};
+
+		self.lines.splice(block.range.clone(), replacement);
+		self.blocks = find_blocks(&self.lines);
+	}
+}
+
+/// conflict markers nested inside a block (like the ones copied
+/// from another conflicted file) are part of its sides, unterminated
+/// blocks are not conflicts at all
+fn find_blocks(lines: &[String]) -> Vec<ConflictBlock> {
+	struct Open {
+		start: usize,
+		ours_label: String,
+		base: Option<usize>,
+		separator: Option<usize>,
+		depth: usize,
+	}
+
+	let mut blocks = Vec::new();
+	let mut open: Option<Open> = None;
+
+	for (idx, line) in lines.iter().enumerate() {
+		let Some((marker, label)) = Marker::parse(line) else {
+			continue;
+		};
+
+		match (&mut open, marker) {
+			(None, Marker::Ours) => {
+				open = Some(Open {
+					start: idx,
+					ours_label: label.to_string(),
+					base: None,
+					separator: None,
+					depth: 0,
+				});
+			}
+			(Some(block), Marker::Ours) => block.depth += 1,
+			(Some(block), Marker::Theirs) if block.depth > 0 => {
+				block.depth -= 1;
+			}
+			(Some(block), Marker::Base)
+				if block.depth == 0
+					&& block.base.is_none()
+					&& block.separator.is_none() =>
+			{
+				block.base = Some(idx);
+			}
+			(Some(block), Marker::Separator)
+				if block.depth == 0 && block.separator.is_none() =>
+			{
+				block.separator = Some(idx);
+			}
+			(Some(block), Marker::Theirs) => {
+				// a `>>>>>>>` before the `=======` is content
+				let Some(separator) = block.separator else {
+					continue;
+				};
+
+				let ours_end = block.base.unwrap_or(separator);
+				blocks.push(ConflictBlock {
+					range: block.start..idx + 1,
+					ours: block.start + 1..ours_end,
+					base: block.base.map(|base| base + 1..separator),
+					theirs: separator + 1..idx,
+					ours_label: std::mem::take(&mut block.ours_label),
+					theirs_label: label.to_string(),
+				});
+				open = None;
+			}
+			(None, _)
+			| (Some(_), Marker::Base | Marker::Separator) => (),
+		}
+	}
+
+	blocks
+}
+
+/// content of the conflicted file `path` in the working tree
+pub fn read_conflicted_file(
+	repo_path: &RepoPath,
+	path: &str,
+) -> Result<ConflictText> {
+	scope_time!("read_conflicted_file");
+
+	let repo = repo(repo_path)?;
+	let content = fs::read(work_dir(&repo)?.join(path))?;
+	let text = String::from_utf8(content).map_err(|_| {
+		Error::Generic(format!("'{path}' is not valid UTF-8"))
+	})?;
+
+	Ok(ConflictText::parse(&text))
+}
+
+/// writes the merge result into the working tree, the file stays
+/// conflicted in the index until it is staged
+pub fn write_merged_file(
+	repo_path: &RepoPath,
+	path: &str,
+	content: &str,
+) -> Result<()> {
+	scope_time!("write_merged_file");
+
+	let repo = repo(repo_path)?;
+	fs::write(work_dir(&repo)?.join(path), content)?;
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn side(text: &ConflictText, range: &Range<usize>) -> String {
+		text.lines()[range.clone()].concat()
+	}
+
+	#[test]
+	fn test_merge_style() {
+		let text = ConflictText::parse(
+			"a\n<<<<<<< HEAD\nours\n=======\ntheirs 1\ntheirs 2\n>>>>>>> feature\nb\n",
+		);
+
+		assert_eq!(text.blocks().len(), 1);
+		let block = &text.blocks()[0];
+		assert_eq!(block.range, 1..7);
+		assert_eq!(side(&text, &block.ours), "ours\n");
+		assert_eq!(block.base, None);
+		assert_eq!(
+			side(&text, &block.theirs),
+			"theirs 1\ntheirs 2\n"
+		);
+		assert_eq!(block.ours_label, "HEAD");
+		assert_eq!(block.theirs_label, "feature");
+	}
+
+	#[test]
+	fn test_diff3_style() {
+		let text = ConflictText::parse(
+			"<<<<<<< ours\n=======\n>>>>>>> theirs\nx\n<<<<<<< HEAD\no\n||||||| base\nb\n=======\nt\n>>>>>>> other\n",
+		);
+
+		let blocks = text.blocks();
+		assert_eq!(blocks.len(), 2);
+		assert!(blocks[0].ours.is_empty());
+		assert!(blocks[0].theirs.is_empty());
+		assert_eq!(side(&text, &blocks[1].ours), "o\n");
+		assert_eq!(
+			blocks[1].base.as_ref().map(|base| side(&text, base)),
+			Some(String::from("b\n"))
+		);
+		assert_eq!(side(&text, &blocks[1].theirs), "t\n");
+	}
+
+	#[test]
+	fn test_nested_markers() {
+		// inner markers of a recursive merge are longer, the ones of
+		// a conflicted file committed by accident are not
+		let text = ConflictText::parse(concat!(
+			"<<<<<<< HEAD\n",
+			"<<<<<<< old\n",
+			"x\n",
+			"=======\n",
+			"y\n",
+			">>>>>>> old\n",
+			"<<<<<<<<< Temporary merge branch 1\n",
+			"=========\n",
+			">>>>>>>>> Temporary merge branch 2\n",
+			"=======\n",
+			"theirs\n",
+			">>>>>>> feature\n",
+			"=======\n",
+			">>>>>>>\n",
+			"<<<<<<<< not a marker\n",
+			"<<<<<<<no label separator\n",
+		));
+
+		assert_eq!(text.blocks().len(), 1);
+		let block = &text.blocks()[0];
+		assert_eq!(block.range, 0..12);
+		assert_eq!(block.ours, 1..9);
+		assert_eq!(side(&text, &block.theirs), "theirs\n");
+	}
+
+	#[test]
+	fn test_unterminated_block() {
+		let text =
+			ConflictText::parse("<<<<<<< HEAD\na\n=======\nb\n");
+		assert!(text.blocks().is_empty());
+
+		let text = ConflictText::parse(
+			"<<<<<<< HEAD\na\n>>>>>>> x\nb\n=======\nc\n>>>>>>> y",
+		);
+		assert_eq!(text.blocks().len(), 1);
+		let block = &text.blocks()[0];
+		assert_eq!(side(&text, &block.ours), "a\n>>>>>>> x\nb\n");
+		assert_eq!(block.theirs_label, "y");
+	}
+
+	#[test]
+	fn test_crlf() {
+		let mut text = ConflictText::parse(
+			"a\r\n<<<<<<< HEAD\r\nours\r\n||||||| base\r\n=======\r\ntheirs\r\n>>>>>>> feature\r\nb\r\n",
+		);
+
+		assert_eq!(text.blocks().len(), 1);
+		let block = &text.blocks()[0];
+		assert_eq!(block.ours_label, "HEAD");
+		assert_eq!(block.theirs_label, "feature");
+		assert_eq!(
+			block.base.clone().map(|base| base.len()),
+			Some(0)
+		);
+
+		text.resolve(0, BlockResolution::TheirsFirst);
+		assert!(text.blocks().is_empty());
+		assert_eq!(text.text(), "a\r\ntheirs\r\nours\r\nb\r\n");
+	}
+
+	#[test]
+	fn test_resolve_blocks() {
+		let conflicted = "<<<<<<< a\n1\n=======\n2\n>>>>>>> b\n-\n<<<<<<< a\n3\n=======\n4\n>>>>>>> b\n";
+
+		let mut text = ConflictText::parse(conflicted);
+		text.resolve(1, BlockResolution::OursFirst);
+		assert_eq!(text.blocks().len(), 1);
+		text.resolve(0, BlockResolution::Theirs);
+		assert_eq!(text.text(), "2\n-\n3\n4\n");
+
+		let mut text = ConflictText::parse(conflicted);
+		text.resolve(0, BlockResolution::Ours);
+		assert_eq!(text.blocks()[0].range, 2..7);
+		text.resolve(5, BlockResolution::Ours);
+		assert_eq!(text.blocks().len(), 1);
+	}
+}
@@ -1,8 +1,17 @@
 //! sync git api for fetching a diff
 
 use super::{
+	binary::{diff_image_sizes, ImageSize},
 	commit_files::{
-		get_commit_diff, get_compare_commits_diff, OldNew,
+		get_commit_diff, get_compare_commits_diff, get_trees_diff,
+		OldNew, TreeSource,
+	},
+	file_mode::{diff_mode_change, ModeChange},
+	lfs::{lfs_diff, LfsDiff},
+	partial_clone::missing_blobs,
+	status::StatusType,
+	submodules::{
+		submodule_status_at, SubmoduleChanges, SubmoduleDiff,
 	},
 	utils::{get_head_repo, work_dir},
 	CommitId, RepoPath,
@@ -15,7 +24,9 @@ use crate::{
 };
 use easy_cast::Conv;
 use git2::{
-	Delta, Diff, DiffDelta, DiffFormat, DiffHunk, Patch, Repository,
+	build::TreeUpdateBuilder, AttrCheckFlags, AttrValue, Delta, Diff,
+	DiffDelta, DiffFile, DiffFormat, DiffHunk, FileMode, Oid, Patch,
+	Repository,
 };
 use scopetime::scope_time;
 use serde::{Deserialize, Serialize};
@@ -130,8 +141,38 @@ pub struct FileDiff {
 	pub sizes: (u64, u64),
 	/// size delta in bytes
 	pub size_delta: i64,
+	/// set if text or binary was not decided by the content
+	pub forced: Option<ForcedDiffType>,
+	/// blobs not available locally (e.g. not fetched yet by a
+	/// partial clone), the diff is empty then
+	pub missing_objects: Vec<String>,
+	/// set for a submodule, the diff is empty then
+	pub submodule: Option<SubmoduleDiff>,
+	/// set if either side is a git lfs pointer, the diff is empty
+	/// then and `sizes` are the ones of the content
+	pub lfs: Option<LfsDiff>,
+	/// old and new dimensions of images, only read for binary diffs
+	pub images: (Option<ImageSize>, Option<ImageSize>),
+	/// set if the mode or the target of a symlink changed, a pure
+	/// mode change has no hunks
+	pub mode_change: Option<ModeChange>,
+}
+
+/// why a file is diffed as text or binary without looking at its
+/// content (which treats null bytes as binary)
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum ForcedDiffType {
+	/// `diff` or `text` attribute
+	Text,
+	/// `-diff`, `binary` or `-text` attribute
+	Binary,
+	/// larger than `core.bigFileThreshold`
+	BigFile,
 }
 
+/// git's default for `core.bigFileThreshold`
+const BIG_FILE_THRESHOLD: i64 = 512 * 1024 * 1024;
+
 /// see <https://libgit2.org/libgit2/#HEAD/type/git_diff_options>
 #[derive(
 	Debug, Hash, Clone, Copy, PartialEq, Eq, Serialize, Deserialize,
@@ -172,6 +213,7 @@ pub(crate) fn get_diff_raw<'a>(
 	}
 	opt.pathspec(p);
 	opt.reverse(reverse);
+	force_diff_type(&mut opt, forced_diff_type(repo, p, stage)?);
 
 	let diff = if stage {
 		// diff against head
@@ -211,9 +253,136 @@ pub fn get_diff(
 
 	let repo = repo(repo_path)?;
 	let work_dir = work_dir(&repo)?;
+	let forced = forced_diff_type(&repo, p, stage)?;
 	let diff = get_diff_raw(&repo, p, stage, false, options)?;
 
-	raw_diff_to_file_diff(&diff, work_dir)
+	// only the index and `HEAD` are in the object database
+	let odb = OldNew {
+		old: true,
+		new: stage,
+	};
+
+	let mut res =
+		checked_file_diff(&repo, &diff, work_dir, forced, odb)?;
+	if let Some(submodule) = &mut res.submodule {
+		let status_type = if stage {
+			StatusType::Stage
+		} else {
+			StatusType::WorkingDir
+		};
+
+		submodule.changes = submodule_status_at(&repo, Path::new(p))
+			.map(|status| SubmoduleChanges::new(status, status_type))
+			.unwrap_or_default();
+	}
+
+	Ok(res)
+}
+
+/// errors if the diff of `p` is not the one hashing to `diff_hash`
+/// anymore, e.g. because the file changed since it was loaded
+pub fn diff_unchanged(
+	repo_path: &RepoPath,
+	p: &str,
+	stage: bool,
+	diff_hash: u64,
+	options: Option<DiffOptions>,
+) -> Result<()> {
+	scope_time!("diff_unchanged");
+
+	if hash(&get_diff(repo_path, p, stage, options)?) == diff_hash {
+		Ok(())
+	} else {
+		Err(Error::Generic(format!(
+			"'{p}' changed since its diff was loaded, nothing was done"
+		)))
+	}
+}
+
+/// consults the attributes of `path` like git does (`diff` first,
+/// then `binary` and `text`) and then `core.bigFileThreshold`,
+/// `None` leaves the decision to the content
+fn forced_diff_type(
+	repo: &Repository,
+	path: &str,
+	stage: bool,
+) -> Result<Option<ForcedDiffType>> {
+	if path.is_empty() {
+		return Ok(None);
+	}
+
+	let forced = attr_diff_type(repo, path, stage)?;
+	if forced.is_some() {
+		return Ok(forced);
+	}
+
+	let size = if stage {
+		repo.index()?
+			.get_path(Path::new(path), 0)
+			.map(|entry| u64::from(entry.file_size))
+	} else {
+		fs::metadata(work_dir(repo)?.join(path))
+			.ok()
+			.map(|meta| meta.len())
+	};
+
+	let threshold = repo
+		.config()?
+		.get_i64("core.bigFileThreshold")
+		.unwrap_or(BIG_FILE_THRESHOLD);
+
+	Ok(size
+		.filter(|size| {
+			i64::try_from(*size).unwrap_or(i64::MAX) > threshold
+		})
+		.map(|_| ForcedDiffType::BigFile))
+}
+
+/// the `diff`, `binary` and `text` attributes of `path`, `None` if
+/// they don't decide it
+pub(crate) fn attr_diff_type(
+	repo: &Repository,
+	path: &str,
+	stage: bool,
+) -> Result<Option<ForcedDiffType>> {
+	let flags = if stage {
+		AttrCheckFlags::INDEX_THEN_FILE
+	} else {
+		AttrCheckFlags::FILE_THEN_INDEX
+	};
+	let attr = |name| -> Result<AttrValue> {
+		Ok(AttrValue::from_string(repo.get_attr(
+			Path::new(path),
+			name,
+			flags,
+		)?))
+	};
+
+	let forced = match (attr("diff")?, attr("binary")?, attr("text")?)
+	{
+		(AttrValue::True, ..) => Some(ForcedDiffType::Text),
+		(AttrValue::False, ..) | (_, AttrValue::True, _) => {
+			Some(ForcedDiffType::Binary)
+		}
+		(_, _, AttrValue::True) => Some(ForcedDiffType::Text),
+		(_, _, AttrValue::False) => Some(ForcedDiffType::Binary),
+		_ => None,
+	};
+
+	Ok(forced)
+}
+
+fn force_diff_type(
+	opt: &mut git2::DiffOptions,
+	forced: Option<ForcedDiffType>,
+) {
+	match forced {
+		Some(ForcedDiffType::Text) => opt.force_text(true),
+		Some(ForcedDiffType::Binary | ForcedDiffType::BigFile) => {
+			opt.force_binary(true)
+		}
+		None => opt,
+	};
 }
 
 /// returns diff of a specific file inside a commit
@@ -236,7 +405,43 @@ pub fn get_diff_commit(
 		Some(&get_stashes(repo_path)?.into_iter().collect()),
 	)?;
 
-	raw_diff_to_file_diff(&diff, work_dir)
+	checked_file_diff(&repo, &diff, work_dir, None, ODB_BOTH)
+}
+
+/// diff of the blob `id` as the old content of `p` to the file in
+/// the workdir
+pub(crate) fn get_diff_blob(
+	repo_path: &RepoPath,
+	p: &str,
+	id: Oid,
+	options: Option<DiffOptions>,
+) -> Result<FileDiff> {
+	scope_time!("get_diff_blob");
+
+	let repo = repo(repo_path)?;
+	let work_dir = work_dir(&repo)?;
+	let forced = forced_diff_type(&repo, p, false)?;
+
+	let empty = repo.find_tree(repo.treebuilder(None)?.write()?)?;
+	let tree = TreeUpdateBuilder::new()
+		.upsert(p, id, FileMode::Blob)
+		.create_updated(&repo, &empty)?;
+
+	let mut opt = git2::DiffOptions::new();
+	if let Some(options) = options {
+		opt.context_lines(options.context);
+		opt.ignore_whitespace(options.ignore_whitespace);
+		opt.interhunk_lines(options.interhunk_lines);
+	}
+	opt.pathspec(p);
+	force_diff_type(&mut opt, forced);
+
+	let diff = repo.diff_tree_to_workdir(
+		Some(&repo.find_tree(tree)?),
+		Some(&mut opt),
+	)?;
+
+	raw_diff_to_file_diff(&diff, work_dir, forced)
 }
 
 /// get file changes of a diff between two commits
@@ -253,7 +458,208 @@ pub fn get_diff_commits(
 	let diff =
 		get_compare_commits_diff(&repo, ids, Some(p), options)?;
 
-	raw_diff_to_file_diff(&diff, work_dir)
+	checked_file_diff(&repo, &diff, work_dir, None, ODB_BOTH)
+}
+
+/// diff of file `p` between two trees
+pub fn get_diff_trees(
+	repo_path: &RepoPath,
+	trees: OldNew<TreeSource>,
+	p: String,
+	options: Option<DiffOptions>,
+) -> Result<FileDiff> {
+	scope_time!("get_diff_trees");
+
+	let repo = repo(repo_path)?;
+	let work_dir = work_dir(&repo)?;
+	let diff = get_trees_diff(&repo, trees, Some(p), options)?;
+	let odb = OldNew {
+		old: trees.old != TreeSource::WorkDir,
+		new: trees.new != TreeSource::WorkDir,
+	};
+
+	checked_file_diff(&repo, &diff, work_dir, None, odb)
+}
+
+/// returns the diff of everything in `stage`, every file is
+/// introduced by a header line carrying its path
+pub fn get_diff_stage_all(
+	repo_path: &RepoPath,
+	options: Option<DiffOptions>,
+) -> Result<FileDiff> {
+	scope_time!("get_diff_stage_all");
+
+	let repo = repo(repo_path)?;
+	let diff = get_diff_raw(&repo, "", true, false, options)?;
+
+	let missing_objects = missing_blobs(&repo, &diff, ODB_BOTH)?;
+	if !missing_objects.is_empty() {
+		return Ok(FileDiff {
+			missing_objects,
+			..FileDiff::default()
+		});
+	}
+
+	let mut res = FileDiff::default();
+
+	for idx in 0..diff.deltas().len() {
+		let Some(delta) = diff.get_delta(idx) else {
+			continue;
+		};
+
+		let file_path = delta
+			.new_file()
+			.path()
+			.or_else(|| delta.old_file().path())
+			.map(|p| p.to_string_lossy().to_string())
+			.unwrap_or_default();
+
+		res.sizes.0 += delta.old_file().size();
+		res.sizes.1 += delta.new_file().size();
+
+		let file_header = DiffLine {
+			content: file_path.into(),
+			line_type: DiffLineType::Header,
+			position: DiffLinePosition::default(),
+		};
+
+		let patch = Patch::from_diff(&diff, idx)?
+			.filter(|patch| patch.num_hunks() > 0);
+
+		let Some(patch) = patch else {
+			// binary files or mode changes have no textual hunks
+			res.lines += 1;
+			res.hunks.push(Hunk {
+				header_hash: hash(&file_header),
+				lines: vec![file_header],
+			});
+			continue;
+		};
+
+		for hunk_idx in 0..patch.num_hunks() {
+			let (hunk, line_count) = patch.hunk(hunk_idx)?;
+
+			let mut hunk_lines = Vec::with_capacity(line_count + 2);
+			if hunk_idx == 0 {
+				hunk_lines.push(file_header.clone());
+			}
+			hunk_lines.push(DiffLine {
+				content: String::from_utf8_lossy(hunk.header())
+					.trim_matches(is_newline)
+					.into(),
+				line_type: DiffLineType::Header,
+				position: DiffLinePosition::default(),
+			});
+
+			for line_idx in 0..line_count {
+				let line = patch.line_in_hunk(hunk_idx, line_idx)?;
+				hunk_lines.push(DiffLine {
+					position: DiffLinePosition::from(&line),
+					content: String::from_utf8_lossy(line.content())
+						.trim_matches(is_newline)
+						.into(),
+					line_type: line.origin_value().into(),
+				});
+			}
+
+			res.lines += hunk_lines.len();
+			res.hunks.push(Hunk {
+				header_hash: hash(&(
+					&file_header.content,
+					HunkHeader::from(hunk),
+				)),
+				lines: hunk_lines,
+			});
+		}
+	}
+
+	res.size_delta = (i64::conv(res.sizes.1))
+		.saturating_sub(i64::conv(res.sizes.0));
+
+	Ok(res)
+}
+
+/// both sides of a diff between commits are read from the object
+/// database
+const ODB_BOTH: OldNew<bool> = OldNew {
+	old: true,
+	new: true,
+};
+
+/// `raw_diff_to_file_diff` unless blobs of the `odb` sides are not
+/// available, those are listed instead of failing on the first one
+fn checked_file_diff(
+	repo: &Repository,
+	diff: &Diff,
+	work_dir: &Path,
+	forced: Option<ForcedDiffType>,
+	odb: OldNew<bool>,
+) -> Result<FileDiff> {
+	if let Some(submodule) = submodule_diff(diff) {
+		return Ok(FileDiff {
+			submodule: Some(submodule),
+			..FileDiff::default()
+		});
+	}
+
+	let missing_objects = missing_blobs(repo, diff, odb)?;
+	if !missing_objects.is_empty() {
+		return Ok(FileDiff {
+			missing_objects,
+			..FileDiff::default()
+		});
+	}
+
+	if let Some(lfs) = lfs_diff(repo, diff, work_dir, odb)? {
+		let sizes = (lfs.old.size(), lfs.new.size());
+
+		return Ok(FileDiff {
+			untracked: diff.get_delta(0).is_some_and(|delta| {
+				delta.status() == Delta::Untracked
+			}),
+			sizes,
+			size_delta: (i64::conv(sizes.1))
+				.saturating_sub(i64::conv(sizes.0)),
+			lfs: Some(lfs),
+			..FileDiff::default()
+		});
+	}
+
+	let mut res = raw_diff_to_file_diff(diff, work_dir, forced)?;
+	if res.hunks.is_empty() {
+		res.images = diff_image_sizes(repo, diff, work_dir, odb)?;
+	}
+	res.mode_change = diff_mode_change(repo, diff, work_dir);
+
+	Ok(res)
+}
+
+/// the commits of a diff of a single submodule, the text git would
+/// show (`Subproject commit ...`) tells nothing more
+fn submodule_diff(diff: &Diff) -> Option<SubmoduleDiff> {
+	if diff.deltas().len() != 1 {
+		return None;
+	}
+
+	let delta = diff.get_delta(0)?;
+	let is_submodule =
+		|file: &DiffFile| file.mode() == FileMode::Commit;
+	if !is_submodule(&delta.old_file())
+		&& !is_submodule(&delta.new_file())
+	{
+		return None;
+	}
+
+	let id = |file: &DiffFile| {
+		(is_submodule(file) && !file.id().is_zero())
+			.then(|| file.id().into())
+	};
+
+	Some(SubmoduleDiff {
+		old: id(&delta.old_file()),
+		new: id(&delta.new_file()),
+		changes: SubmoduleChanges::default(),
+	})
 }
 
 ///
@@ -262,6 +668,7 @@ pub fn get_diff_commits(
 fn raw_diff_to_file_diff(
 	diff: &Diff,
 	work_dir: &Path,
+	forced: Option<ForcedDiffType>,
 ) -> Result<FileDiff> {
 	let res = Rc::new(RefCell::new(FileDiff::default()));
 	{
@@ -336,12 +743,15 @@ fn raw_diff_to_file_diff(
 					if let Some(newfile_content) =
 						new_file_content(&newfile_path)
 					{
+						let mut opt = git2::DiffOptions::new();
+						force_diff_type(&mut opt, forced);
+
 						let mut patch = Patch::from_buffers(
 							&[],
 							None,
 							newfile_content.as_slice(),
 							Some(&newfile_path),
-							None,
+							Some(&mut opt),
 						)?;
 
 						patch.print(
@@ -390,6 +800,7 @@ fn raw_diff_to_file_diff(
 		if new_file_diff {
 			res.borrow_mut().untracked = true;
 		}
+		res.borrow_mut().forced = forced;
 	}
 	let res = Rc::try_unwrap(res)
 		.map_err(|_| Error::Generic("rc unwrap error".to_owned()))?;
@@ -420,7 +831,10 @@ fn new_file_content(path: &Path) -> Option<Vec<u8>> {
 
 #[cfg(test)]
 mod tests {
-	use super::{get_diff, get_diff_commit};
+	use super::{
+		get_diff, get_diff_commit, get_diff_stage_all, DiffLineType,
+		ForcedDiffType,
+	};
 	use crate::{
 		error::Result,
 		sync::{
@@ -616,6 +1030,98 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_gitattributes_force_text() -> Result<()> {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		fs::write(root.join(".gitattributes"), "*.dat text\n")?;
+		fs::write(root.join("a.dat"), b"a\x00b\n")?;
+		stage_add_file(repo_path, Path::new("a.dat"))?;
+		commit(repo_path, "")?;
+
+		fs::write(root.join("a.dat"), b"a\x00c\n")?;
+		fs::write(root.join("new.dat"), b"\x00new\n")?;
+		fs::write(root.join("new.bin"), b"\x00new\n")?;
+
+		let diff = get_diff(repo_path, "a.dat", false, None)?;
+		assert_eq!(diff.forced, Some(ForcedDiffType::Text));
+		assert_eq!(diff.hunks.len(), 1);
+
+		let diff = get_diff(repo_path, "new.dat", false, None)?;
+		assert_eq!(diff.forced, Some(ForcedDiffType::Text));
+		assert_eq!(diff.hunks.len(), 1);
+
+		let diff = get_diff(repo_path, "new.bin", false, None)?;
+		assert_eq!(diff.forced, None);
+		assert!(diff.hunks.is_empty());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_gitattributes_force_binary() -> Result<()> {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		fs::write(
+			root.join(".gitattributes"),
+			"a.txt binary\nb.txt -text\nc.txt -text diff\n",
+		)?;
+		for file in ["a.txt", "b.txt", "c.txt"] {
+			fs::write(root.join(file), "text\n")?;
+			stage_add_file(repo_path, Path::new(file))?;
+		}
+
+		let diff = get_diff(repo_path, "a.txt", true, None)?;
+		assert_eq!(diff.forced, Some(ForcedDiffType::Binary));
+		assert!(diff.hunks.is_empty());
+
+		let diff = get_diff(repo_path, "b.txt", true, None)?;
+		assert_eq!(diff.forced, Some(ForcedDiffType::Binary));
+		assert!(diff.hunks.is_empty());
+
+		// `diff` takes precedence
+		let diff = get_diff(repo_path, "c.txt", true, None)?;
+		assert_eq!(diff.forced, Some(ForcedDiffType::Text));
+		assert_eq!(diff.hunks.len(), 1);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_big_file_threshold() -> Result<()> {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		repo.config()?.set_str("core.bigFileThreshold", "8")?;
+
+		fs::write(root.join("small"), "small\n")?;
+		fs::write(root.join("big"), "larger than 8\n")?;
+		fs::write(root.join("big.txt"), "larger than 8\n")?;
+		fs::write(root.join(".gitattributes"), "*.txt text\n")?;
+
+		let diff = get_diff(repo_path, "small", false, None)?;
+		assert_eq!(diff.forced, None);
+		assert_eq!(diff.hunks.len(), 1);
+
+		let diff = get_diff(repo_path, "big", false, None)?;
+		assert_eq!(diff.forced, Some(ForcedDiffType::BigFile));
+		assert!(diff.hunks.is_empty());
+
+		let diff = get_diff(repo_path, "big.txt", false, None)?;
+		assert_eq!(diff.forced, Some(ForcedDiffType::Text));
+		assert_eq!(diff.hunks.len(), 1);
+
+		Ok(())
+	}
+
 	#[test]
 	fn test_binary_diff_delta_size_untracked() -> Result<()> {
 		let file_path = Path::new("bar");
@@ -671,4 +1177,43 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_diff_stage_all() -> Result<()> {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(root.join("a.txt"))?.write_all(b"a\n")?;
+		File::create(root.join("b.txt"))?.write_all(b"b\n")?;
+		File::create(root.join("c.txt"))?.write_all(b"c\n")?;
+
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+		stage_add_file(repo_path, Path::new("b.txt")).unwrap();
+
+		let diff = get_diff_stage_all(repo_path, None).unwrap();
+
+		assert_eq!(diff.hunks.len(), 2);
+		assert_eq!(diff.lines, 6);
+
+		let file_headers: Vec<_> = diff
+			.hunks
+			.iter()
+			.map(|h| &h.lines[0])
+			.inspect(|l| {
+				assert_eq!(l.line_type, DiffLineType::Header);
+			})
+			.map(|l| &*l.content)
+			.collect();
+		assert_eq!(file_headers, vec!["a.txt", "b.txt"]);
+
+		assert_eq!(&*diff.hunks[0].lines[2].content, "a");
+		assert_eq!(
+			diff.hunks[0].lines[2].line_type,
+			DiffLineType::Add
+		);
+
+		Ok(())
+	}
 }
@@ -0,0 +1,298 @@
+//! git records a mode with every file: regular or executable, a
+//! symlink (its blob holds the target it points to) or a submodule.
+//! the working tree is read like git does, the executable bit only
+//! counts with `core.fileMode`
+
+use super::{commit_files::OldNew, status::StatusType};
+use crate::{error::Result, StatusItem};
+use git2::{Diff, DiffFile, Repository};
+use std::{fs, path::Path};
+
+/// the mode of a file in a tree or the index
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum FileMode {
+	/// `100644`
+	Regular,
+	/// `100755`
+	Executable,
+	/// `120000`
+	Symlink,
+	/// `160000`
+	Submodule,
+}
+
+impl FileMode {
+	/// `None` for trees and modes git does not record for files
+	pub const fn from_raw(mode: u32) -> Option<Self> {
+		match mode {
+			// written by very old versions of git
+			0o100_644 | 0o100_664 => Some(Self::Regular),
+			0o100_755 => Some(Self::Executable),
+			0o120_000 => Some(Self::Symlink),
+			0o160_000 => Some(Self::Submodule),
+			_ => None,
+		}
+	}
+
+	fn of_file(file: &DiffFile) -> Option<Self> {
+		Self::from_raw(u32::from(file.mode()))
+	}
+}
+
+/// what changed about a file apart from its content
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum ModeChange {
+	/// e.g. made executable or a file replaced by a symlink
+	Mode(OldNew<FileMode>),
+	/// a symlink pointing somewhere else
+	SymlinkTarget(OldNew<String>),
+}
+
+/// the mode change of a diff of a single file, `None` unless the file
+/// exists on both sides
+pub fn diff_mode_change(
+	repo: &Repository,
+	diff: &Diff,
+	work_dir: &Path,
+) -> Option<ModeChange> {
+	let delta =
+		diff.get_delta(0).filter(|_| diff.deltas().len() == 1)?;
+	let (old_file, new_file) = (delta.old_file(), delta.new_file());
+
+	let old = FileMode::of_file(&old_file)?;
+	let new = FileMode::of_file(&new_file)?;
+
+	if old != new {
+		return Some(ModeChange::Mode(OldNew { old, new }));
+	}
+
+	if old != FileMode::Symlink {
+		return None;
+	}
+
+	let old = symlink_target(repo, Some(work_dir), &old_file)?;
+	let new = symlink_target(repo, Some(work_dir), &new_file)?;
+
+	(old != new)
+		.then_some(ModeChange::SymlinkTarget(OldNew { old, new }))
+}
+
+/// where the symlink `file` points to, read from its blob if that is
+/// in the object database and from the working tree otherwise
+pub fn symlink_target(
+	repo: &Repository,
+	work_dir: Option<&Path>,
+	file: &DiffFile,
+) -> Option<String> {
+	if !file.id().is_zero() {
+		if let Ok(blob) = repo.find_blob(file.id()) {
+			return Some(blob_target(blob.content()));
+		}
+	}
+
+	work_dir_target(&work_dir?.join(file.path()?))
+}
+
+fn blob_target(content: &[u8]) -> String {
+	String::from_utf8_lossy(content).to_string()
+}
+
+fn work_dir_target(path: &Path) -> Option<String> {
+	fs::read_link(path)
+		.ok()
+		.map(|target| target.to_string_lossy().to_string())
+}
+
+/// sets `StatusItem::mode` and `StatusItem::symlink_target` to the
+/// ones of the index for `Stage`, of the working tree otherwise
+pub fn set_file_modes(
+	repo: &Repository,
+	status_type: StatusType,
+	items: &mut [StatusItem],
+) -> Result<()> {
+	let index = repo.index()?;
+	let work_dir = repo.workdir();
+	let exec_bit =
+		repo.config()?.get_bool("core.fileMode").unwrap_or(true);
+
+	for item in items.iter_mut() {
+		let path = Path::new(&item.path);
+		let entry = index.get_path(path, 0);
+		let index_mode = entry
+			.as_ref()
+			.and_then(|entry| FileMode::from_raw(entry.mode));
+
+		match (status_type, work_dir) {
+			(StatusType::Stage, _) | (_, None) => {
+				item.mode = index_mode;
+				item.symlink_target = match entry {
+					Some(entry)
+						if index_mode == Some(FileMode::Symlink) =>
+					{
+						Some(blob_target(
+							repo.find_blob(entry.id)?.content(),
+						))
+					}
+					_ => None,
+				};
+			}
+			(_, Some(work_dir)) => {
+				let path = work_dir.join(path);
+				item.mode =
+					work_dir_mode(&path, index_mode, exec_bit);
+				item.symlink_target =
+					if item.mode == Some(FileMode::Symlink) {
+						work_dir_target(&path)
+					} else {
+						None
+					};
+			}
+		}
+	}
+
+	Ok(())
+}
+
+/// the mode git sees for the file at `path`, without `exec_bit` the
+/// one of the index tells whether it is executable
+fn work_dir_mode(
+	path: &Path,
+	index_mode: Option<FileMode>,
+	exec_bit: bool,
+) -> Option<FileMode> {
+	let meta = fs::symlink_metadata(path).ok()?;
+
+	if meta.file_type().is_symlink() {
+		return Some(FileMode::Symlink);
+	}
+	if meta.is_dir() {
+		return index_mode
+			.filter(|mode| *mode == FileMode::Submodule);
+	}
+
+	let executable = if exec_bit {
+		is_executable(&meta)
+	} else {
+		index_mode == Some(FileMode::Executable)
+	};
+
+	Some(if executable {
+		FileMode::Executable
+	} else {
+		FileMode::Regular
+	})
+}
+
+#[cfg(unix)]
+fn is_executable(meta: &fs::Metadata) -> bool {
+	use std::os::unix::fs::PermissionsExt;
+
+	meta.permissions().mode() & 0o100 != 0
+}
+
+#[cfg(not(unix))]
+const fn is_executable(_meta: &fs::Metadata) -> bool {
+	false
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		diff::get_diff,
+		get_commit_files,
+		status::get_status,
+		tests::{repo_init, write_commit_file},
+		utils::stage_add_file,
+		RepoPath,
+	};
+	use std::os::unix::fs::{symlink, PermissionsExt};
+
+	fn status_item(
+		repo_path: &RepoPath,
+		status_type: StatusType,
+		path: &str,
+	) -> StatusItem {
+		get_status(repo_path, status_type, None)
+			.unwrap()
+			.into_iter()
+			.find(|item| item.path == path)
+			.unwrap()
+	}
+
+	#[test]
+	fn test_stage_mode_change() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "run.sh", "echo\n", "c1");
+		fs::set_permissions(
+			root.join("run.sh"),
+			fs::Permissions::from_mode(0o755),
+		)
+		.unwrap();
+
+		let item =
+			status_item(repo_path, StatusType::WorkingDir, "run.sh");
+		assert_eq!(item.mode, Some(FileMode::Executable));
+
+		let modes = Some(ModeChange::Mode(OldNew {
+			old: FileMode::Regular,
+			new: FileMode::Executable,
+		}));
+
+		let diff =
+			get_diff(repo_path, "run.sh", false, None).unwrap();
+		assert!(diff.hunks.is_empty());
+		assert_eq!(diff.mode_change, modes);
+
+		stage_add_file(repo_path, Path::new("run.sh")).unwrap();
+
+		assert!(get_status(repo_path, StatusType::WorkingDir, None)
+			.unwrap()
+			.is_empty());
+		let item =
+			status_item(repo_path, StatusType::Stage, "run.sh");
+		assert_eq!(item.mode, Some(FileMode::Executable));
+
+		let diff = get_diff(repo_path, "run.sh", true, None).unwrap();
+		assert!(diff.hunks.is_empty());
+		assert_eq!(diff.mode_change, modes);
+	}
+
+	#[test]
+	fn test_symlink_target() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		symlink("a.txt", root.join("link")).unwrap();
+		stage_add_file(repo_path, Path::new("link")).unwrap();
+		let id = crate::sync::commit(repo_path, "c2").unwrap();
+
+		let files = get_commit_files(repo_path, id, None).unwrap();
+		assert_eq!(files[0].mode, Some(FileMode::Symlink));
+		assert_eq!(files[0].symlink_target.as_deref(), Some("a.txt"));
+
+		fs::remove_file(root.join("link")).unwrap();
+		symlink("b.txt", root.join("link")).unwrap();
+
+		let item =
+			status_item(repo_path, StatusType::WorkingDir, "link");
+		assert_eq!(item.mode, Some(FileMode::Symlink));
+		assert_eq!(item.symlink_target.as_deref(), Some("b.txt"));
+
+		let diff = get_diff(repo_path, "link", false, None).unwrap();
+		assert_eq!(
+			diff.mode_change,
+			Some(ModeChange::SymlinkTarget(OldNew {
+				old: String::from("a.txt"),
+				new: String::from("b.txt"),
+			}))
+		);
+	}
+}
@@ -0,0 +1,214 @@
+//! running formatters or linters that fix files in place over the
+//! staged files
+
+use super::{
+	config::get_config_string_repo,
+	repository::repo,
+	status::{get_status, StatusType},
+	utils::work_dir,
+	RepoPath,
+};
+use crate::{
+	error::Result, process::force_colors, StatusItem, StatusItemType,
+};
+use scopetime::scope_time;
+use std::{
+	collections::HashSet,
+	process::{Child, Command, Stdio},
+};
+
+/// git config key of the fixer command, like `cargo fmt --`
+pub const FIX_STAGED_CONFIG: &str = "gitui.fixStaged";
+
+/// replaced by the paths of a batch, otherwise they are appended
+pub const FIX_STAGED_PLACEHOLDER: &str = "{}";
+
+/// most paths passed to a single invocation of the fixer
+pub const FIX_STAGED_BATCH_SIZE: usize = 64;
+
+/// staged files split by whether the fixer can run on them
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FixStagedFiles {
+	/// the working tree matches the index
+	pub fixable: Vec<String>,
+	/// have unstaged changes on top, restaging them after fixing
+	/// would stage those too
+	pub partially_staged: Vec<String>,
+}
+
+///
+pub fn fix_staged_command(
+	repo_path: &RepoPath,
+) -> Result<Option<String>> {
+	let repo = repo(repo_path)?;
+
+	Ok(get_config_string_repo(&repo, FIX_STAGED_CONFIG)?
+		.filter(|command| !command.trim().is_empty()))
+}
+
+/// staged files still in the working tree
+pub fn fix_staged_files(
+	repo_path: &RepoPath,
+) -> Result<FixStagedFiles> {
+	scope_time!("fix_staged_files");
+
+	let unstaged: HashSet<String> =
+		get_status(repo_path, StatusType::WorkingDir, None)?
+			.into_iter()
+			.map(|item| item.path)
+			.collect();
+
+	let mut res = FixStagedFiles::default();
+
+	for item in get_status(repo_path, StatusType::Stage, None)? {
+		if matches!(
+			item.status,
+			StatusItemType::Deleted | StatusItemType::Conflicted
+		) {
+			continue;
+		}
+
+		if unstaged.contains(&item.path) {
+			res.partially_staged.push(item.path);
+		} else {
+			res.fixable.push(item.path);
+		}
+	}
+
+	Ok(res)
+}
+
+/// starts `command` on `paths` in the working tree
+pub fn spawn_fixer(
+	repo_path: &RepoPath,
+	command: &str,
+	paths: &[String],
+) -> Result<Child> {
+	scope_time!("spawn_fixer");
+
+	let repo = repo(repo_path)?;
+
+	// the paths are passed as arguments to not quote them
+	let script = if command.contains(FIX_STAGED_PLACEHOLDER) {
+		command.replace(FIX_STAGED_PLACEHOLDER, "\"$@\"")
+	} else {
+		format!("{command} \"$@\"")
+	};
+
+	let mut cmd = Command::new(gix::path::env::shell());
+	cmd.arg("-c")
+		.arg(script)
+		.arg("gitui-fix-staged")
+		.args(paths)
+		.current_dir(work_dir(&repo)?)
+		.stdin(Stdio::null())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::piped());
+	force_colors(&mut cmd);
+
+	Ok(cmd.spawn()?)
+}
+
+/// which of `paths` the fixer changed, these differ from the index
+/// again
+pub fn fixed_files(
+	repo_path: &RepoPath,
+	paths: &[String],
+) -> Result<Vec<StatusItem>> {
+	let paths: HashSet<&String> = paths.iter().collect();
+
+	Ok(get_status(repo_path, StatusType::WorkingDir, None)?
+		.into_iter()
+		.filter(|item| paths.contains(&item.path))
+		.collect())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		stage_add_file,
+		tests::{repo_init, write_commit_file},
+	};
+	use std::{fs, path::Path};
+
+	#[test]
+	fn test_fix_staged_files() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		write_commit_file(&repo, "b.txt", "b", "c2");
+		write_commit_file(&repo, "gone.txt", "c", "c3");
+
+		fs::remove_file(root.join("gone.txt")).unwrap();
+		let mut index = repo.index().unwrap();
+		index.remove_path(Path::new("gone.txt")).unwrap();
+		index.write().unwrap();
+
+		fs::write(root.join("a.txt"), "a1").unwrap();
+		fs::write(root.join("b.txt"), "b1").unwrap();
+		fs::write(root.join("new.txt"), "n").unwrap();
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+		stage_add_file(repo_path, Path::new("b.txt")).unwrap();
+		stage_add_file(repo_path, Path::new("new.txt")).unwrap();
+
+		fs::write(root.join("b.txt"), "b2").unwrap();
+
+		assert_eq!(
+			fix_staged_files(repo_path).unwrap(),
+			FixStagedFiles {
+				fixable: vec![
+					String::from("a.txt"),
+					String::from("new.txt")
+				],
+				partially_staged: vec![String::from("b.txt")],
+			}
+		);
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn test_spawn_fixer() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a b.txt", "a", "c1");
+		write_commit_file(&repo, "c.txt", "c", "c2");
+
+		let paths =
+			vec![String::from("a b.txt"), String::from("c.txt")];
+
+		let status = spawn_fixer(
+			repo_path,
+			"for f in {}; do echo fixed > \"$f\"; done",
+			&paths[..1],
+		)
+		.unwrap()
+		.wait()
+		.unwrap();
+		assert!(status.success());
+
+		let fixed: Vec<String> = fixed_files(repo_path, &paths)
+			.unwrap()
+			.into_iter()
+			.map(|item| item.path)
+			.collect();
+		assert_eq!(fixed, vec![String::from("a b.txt")]);
+		assert_eq!(
+			fs::read_to_string(root.join("a b.txt")).unwrap(),
+			"fixed\n"
+		);
+
+		// without placeholder the paths are appended
+		let status = spawn_fixer(repo_path, "test -f", &paths[1..])
+			.unwrap()
+			.wait()
+			.unwrap();
+		assert!(status.success());
+	}
+}
@@ -0,0 +1,527 @@
+//! finds the commit staged changes are a fixup of (like `git absorb`)
+
+use super::{
+	commit,
+	rebase::{autosquash, rebase_onto_parent, RebaseState},
+	utils::{get_head_repo, reachable_from, remote_tips},
+	CommitId, RepoPath,
+};
+use crate::{
+	error::{Error, Result},
+	sync::repository::repo,
+};
+use git2::{
+	BlameOptions, Delta, DiffOptions, Oid, Repository,
+	RepositoryState, ResetType,
+};
+use scopetime::scope_time;
+use std::{
+	collections::{BTreeSet, HashMap},
+	path::PathBuf,
+};
+
+/// commit that last touched lines of staged hunks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixupCandidate {
+	///
+	pub id: CommitId,
+	///
+	pub summary: String,
+	/// reachable from a remote branch, rewriting it needs a force push
+	pub pushed: bool,
+	/// number of staged hunks touching lines of this commit
+	pub hunks: usize,
+}
+
+///
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FixupTargets {
+	/// most hunks first
+	pub candidates: Vec<FixupCandidate>,
+	/// staged hunks
+	pub hunks: usize,
+	/// hunks of new files, no commit touched their lines
+	pub unattributed: usize,
+}
+
+impl FixupTargets {
+	/// the single unpushed commit all staged hunks belong to
+	pub fn unambiguous(&self) -> Option<&FixupCandidate> {
+		match self.candidates.as_slice() {
+			[candidate]
+				if !candidate.pushed
+					&& candidate.hunks == self.hunks
+					&& self.unattributed == 0 =>
+			{
+				Some(candidate)
+			}
+			_ => None,
+		}
+	}
+}
+
+/// how a commit is folded into the commit it names when
+/// autosquashing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixupKind {
+	/// keeps the message of the target
+	Fixup,
+	/// adds its message to the one of the target
+	Squash,
+}
+
+impl FixupKind {
+	const ALL: [Self; 2] = [Self::Fixup, Self::Squash];
+
+	const fn prefix(self) -> &'static str {
+		match self {
+			Self::Fixup => "fixup! ",
+			Self::Squash => "squash! ",
+		}
+	}
+
+	/// kind and target subject of a `fixup!`/`squash!` message,
+	/// nested ones (`fixup! fixup! <subject>`) name the same target
+	pub fn parse(message: &str) -> Option<(Self, &str)> {
+		let subject = message.lines().next()?;
+		let kind = Self::ALL
+			.into_iter()
+			.find(|kind| subject.starts_with(kind.prefix()))?;
+
+		let mut target = subject;
+		while let Some(rest) = Self::ALL
+			.into_iter()
+			.find_map(|kind| target.strip_prefix(kind.prefix()))
+		{
+			target = rest;
+		}
+
+		Some((kind, target))
+	}
+}
+
+/// lines of `HEAD` a staged hunk is about (1-based)
+struct StagedHunk {
+	path: PathBuf,
+	lines: Option<(usize, usize)>,
+}
+
+/// blames the lines the staged hunks change (or the context around
+/// added lines) to find the commits they belong to
+pub fn fixup_targets(repo_path: &RepoPath) -> Result<FixupTargets> {
+	scope_time!("fixup_targets");
+
+	let repo = repo(repo_path)?;
+	let head = get_head_repo(&repo)?;
+
+	let hunks = staged_hunks(&repo, head)?;
+
+	let mut files: HashMap<&PathBuf, Vec<&StagedHunk>> =
+		HashMap::new();
+	let mut unattributed = 0;
+	for hunk in &hunks {
+		if hunk.lines.is_some() {
+			files.entry(&hunk.path).or_default().push(hunk);
+		} else {
+			unattributed += 1;
+		}
+	}
+
+	let mut hunks_per_commit: HashMap<Oid, usize> = HashMap::new();
+	for (path, hunks) in files {
+		let lines = hunks.iter().filter_map(|hunk| hunk.lines);
+		let (Some(min), Some(max)) = (
+			lines.clone().map(|(start, _)| start).min(),
+			lines.map(|(_, end)| end).max(),
+		) else {
+			continue;
+		};
+
+		let mut opts = BlameOptions::new();
+		opts.newest_commit(head.into()).min_line(min).max_line(max);
+		let blame = repo.blame_file(path, Some(&mut opts))?;
+
+		for hunk in hunks {
+			let Some((start, end)) = hunk.lines else {
+				continue;
+			};
+
+			let commits: BTreeSet<Oid> = (start..=end)
+				.filter_map(|line| blame.get_line(line))
+				.map(|blame| blame.final_commit_id())
+				.collect();
+
+			if commits.is_empty() {
+				unattributed += 1;
+			}
+			for id in commits {
+				*hunks_per_commit.entry(id).or_default() += 1;
+			}
+		}
+	}
+
+	let remotes = remote_tips(&repo)?;
+
+	let mut candidates = hunks_per_commit
+		.into_iter()
+		.map(|(id, hunks)| {
+			let commit = repo.find_commit(id)?;
+			let pushed = reachable_from(&repo, &remotes, id)?;
+
+			Ok(FixupCandidate {
+				id: id.into(),
+				summary: commit
+					.message()
+					.and_then(|msg| msg.lines().next())
+					.unwrap_or_default()
+					.to_string(),
+				pushed,
+				hunks,
+			})
+		})
+		.collect::<Result<Vec<_>>>()?;
+
+	candidates.sort_by(|a, b| {
+		b.hunks.cmp(&a.hunks).then_with(|| a.pushed.cmp(&b.pushed))
+	});
+
+	Ok(FixupTargets {
+		candidates,
+		hunks: hunks.len(),
+		unattributed,
+	})
+}
+
+/// commits the staged changes as `fixup! <subject of target>` (or
+/// `squash! ...`)
+pub fn commit_fixup(
+	repo_path: &RepoPath,
+	target: CommitId,
+	kind: FixupKind,
+) -> Result<CommitId> {
+	scope_time!("commit_fixup");
+
+	let subject = {
+		let repo = repo(repo_path)?;
+		let head = repo.find_commit(get_head_repo(&repo)?.into())?;
+		if repo
+			.diff_tree_to_index(Some(&head.tree()?), None, None)?
+			.deltas()
+			.len() == 0
+		{
+			return Err(Error::Generic(String::from(
+				"fixup error: there are no staged changes",
+			)));
+		}
+
+		let commit = repo.find_commit(target.into())?;
+		commit
+			.message()
+			.and_then(|msg| msg.lines().next())
+			.unwrap_or_default()
+			.to_string()
+	};
+
+	commit(repo_path, &format!("{}{subject}", kind.prefix()))
+}
+
+/// what amending an older commit of the current branch takes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AmendPlan {
+	///
+	pub target: CommitId,
+	/// subject of `target`
+	pub subject: String,
+	/// staged files, without any the rebase stops at `target` to
+	/// edit it instead (see [`edit_commit`](super::edit_commit))
+	pub staged: usize,
+	/// commits on top of `target` the rebase picks again
+	pub rebased: usize,
+	/// reachable from a remote branch, rewriting it needs a force push
+	pub pushed: bool,
+}
+
+/// fails if `target` can't be rebased or there are unstaged changes
+/// the rebase would have to carry along
+pub fn amend_plan(
+	repo_path: &RepoPath,
+	target: CommitId,
+) -> Result<AmendPlan> {
+	scope_time!("amend_plan");
+
+	let repo = repo(repo_path)?;
+
+	if repo.state() != RepositoryState::Clean {
+		return Err(Error::Generic(String::from(
+			"amend error: finish the pending operation first",
+		)));
+	}
+
+	rebase_onto_parent(&repo, target.into())?;
+
+	let mut opts = DiffOptions::new();
+	opts.include_untracked(false);
+	if repo
+		.diff_index_to_workdir(None, Some(&mut opts))?
+		.deltas()
+		.len() > 0
+	{
+		return Err(Error::UncommittedChanges);
+	}
+
+	let head = repo.find_commit(get_head_repo(&repo)?.into())?;
+	let staged = repo
+		.diff_tree_to_index(Some(&head.tree()?), None, None)?
+		.deltas()
+		.len();
+
+	let mut walk = repo.revwalk()?;
+	walk.push(head.id())?;
+	walk.hide(target.into())?;
+	let rebased = walk.count();
+
+	let subject = repo
+		.find_commit(target.into())?
+		.summary()
+		.unwrap_or_default()
+		.to_string();
+
+	Ok(AmendPlan {
+		target,
+		subject,
+		staged,
+		rebased,
+		pushed: reachable_from(
+			&repo,
+			&remote_tips(&repo)?,
+			target.into(),
+		)?,
+	})
+}
+
+/// commits the staged changes as a fixup of `target` and folds it in
+/// right away, a conflict leaves the rebase pending like
+/// [`autosquash`] does
+pub fn amend_older_commit(
+	repo_path: &RepoPath,
+	target: CommitId,
+) -> Result<RebaseState> {
+	scope_time!("amend_older_commit");
+
+	let plan = amend_plan(repo_path, target)?;
+	if plan.staged == 0 {
+		return Err(Error::Generic(String::from(
+			"amend error: there are no staged changes",
+		)));
+	}
+
+	let head = get_head_repo(&repo(repo_path)?)?;
+	commit_fixup(repo_path, target, FixupKind::Fixup)?;
+
+	autosquash(repo_path, target).or_else(|e| {
+		// stage the changes again instead of leaving the fixup behind
+		let repo = repo(repo_path)?;
+		repo.reset(
+			&repo.find_object(head.into(), None)?,
+			ResetType::Soft,
+			None,
+		)?;
+		Err(e)
+	})
+}
+
+fn staged_hunks(
+	repo: &Repository,
+	head: CommitId,
+) -> Result<Vec<StagedHunk>> {
+	let tree = repo.find_commit(head.into())?.tree()?;
+
+	let mut opts = DiffOptions::new();
+	opts.context_lines(0).interhunk_lines(0);
+
+	let diff = repo.diff_tree_to_index(
+		Some(&tree),
+		Some(&repo.index()?),
+		Some(&mut opts),
+	)?;
+
+	let mut hunks = Vec::new();
+	diff.foreach(
+		&mut |_, _| true,
+		None,
+		Some(&mut |delta, hunk| {
+			let Some(path) = delta.old_file().path() else {
+				return true;
+			};
+
+			let (start, len) = (
+				hunk.old_start() as usize,
+				hunk.old_lines() as usize,
+			);
+
+			let lines = match delta.status() {
+				Delta::Added => None,
+				// only adds lines after `start`: the lines around
+				// it give the context
+				_ if len == 0 => Some((start.max(1), start + 1)),
+				_ => Some((start, start + len - 1)),
+			};
+
+			hunks.push(StagedHunk {
+				path: path.to_path_buf(),
+				lines,
+			});
+
+			true
+		}),
+		None,
+	)?;
+
+	Ok(hunks)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		stage_add_file,
+		tests::{repo_init, write_commit_file},
+		utils::repo_write_file,
+	};
+	use std::path::Path;
+
+	#[test]
+	fn test_unambiguous_target() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a", "a\nb\nc\n", "add a");
+		let target =
+			write_commit_file(&repo, "b", "1\n2\n3\n", "add b");
+		write_commit_file(&repo, "a", "a\nb\nc\nd\n", "extend a");
+
+		repo_write_file(&repo, "b", "1\ntwo\n3\n").unwrap();
+		stage_add_file(repo_path, Path::new("b")).unwrap();
+
+		let targets = fixup_targets(repo_path).unwrap();
+
+		assert_eq!(targets.hunks, 1);
+		let candidate = targets.unambiguous().unwrap();
+		assert_eq!(candidate.id, target);
+		assert_eq!(candidate.summary, "add b");
+		assert!(!candidate.pushed);
+
+		let id =
+			commit_fixup(repo_path, candidate.id, FixupKind::Fixup)
+				.unwrap();
+		let commit = repo.find_commit(id.into()).unwrap();
+		assert_eq!(commit.message(), Some("fixup! add b"));
+	}
+
+	#[test]
+	fn test_ambiguous_targets() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let first = write_commit_file(&repo, "a", "a\nb\n", "first");
+		let second =
+			write_commit_file(&repo, "a", "a\nb\nc\nd\n", "second");
+
+		// pushed commits are not picked even if there is only one
+		repo.reference(
+			"refs/remotes/origin/master",
+			first.into(),
+			true,
+			"",
+		)
+		.unwrap();
+
+		repo_write_file(&repo, "a", "x\nb\nc\ny\n").unwrap();
+		stage_add_file(repo_path, Path::new("a")).unwrap();
+
+		let targets = fixup_targets(repo_path).unwrap();
+
+		assert_eq!(targets.unambiguous(), None);
+		assert_eq!(targets.hunks, 2);
+		assert_eq!(
+			targets
+				.candidates
+				.iter()
+				.map(|c| (c.id, c.pushed, c.hunks))
+				.collect::<Vec<_>>(),
+			vec![(second, false, 1), (first, true, 1)]
+		);
+	}
+
+	#[test]
+	fn test_added_lines_and_new_files() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let target = write_commit_file(&repo, "a", "a\nb\n", "add a");
+
+		// added lines are attributed to their neighbours
+		repo_write_file(&repo, "a", "a\nnew\nb\nend\n").unwrap();
+		stage_add_file(repo_path, Path::new("a")).unwrap();
+
+		let targets = fixup_targets(repo_path).unwrap();
+		assert_eq!(targets.hunks, 2);
+		assert_eq!(targets.unambiguous().map(|c| c.id), Some(target));
+
+		repo_write_file(&repo, "new", "new\n").unwrap();
+		stage_add_file(repo_path, Path::new("new")).unwrap();
+
+		let targets = fixup_targets(repo_path).unwrap();
+		assert_eq!(targets.unattributed, 1);
+		assert_eq!(targets.unambiguous(), None);
+		assert_eq!(targets.candidates.len(), 1);
+	}
+
+	#[test]
+	fn test_amend_older_commit() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a", "1", "add a");
+		let target = write_commit_file(&repo, "b", "1", "add b");
+		let head = write_commit_file(&repo, "c", "1", "add c");
+
+		// nothing staged: the rebase would stop to edit it
+		let plan = amend_plan(repo_path, target).unwrap();
+		assert_eq!((plan.staged, plan.rebased), (0, 1));
+		assert_eq!(plan.subject, "add b");
+		assert!(!plan.pushed);
+		assert!(amend_older_commit(repo_path, target).is_err());
+		assert_eq!(get_head_repo(&repo).unwrap(), head);
+
+		// unstaged changes would have to be carried along
+		repo_write_file(&repo, "b", "2").unwrap();
+		assert!(amend_plan(repo_path, target).is_err());
+
+		stage_add_file(repo_path, Path::new("b")).unwrap();
+		assert_eq!(amend_plan(repo_path, target).unwrap().staged, 1);
+		assert_eq!(
+			amend_older_commit(repo_path, target).unwrap(),
+			RebaseState::Finished
+		);
+
+		let head = repo.head().unwrap().peel_to_commit().unwrap();
+		assert_eq!(head.summary(), Some("add c"));
+		let amended = head.parent(0).unwrap();
+		assert_eq!(amended.summary(), Some("add b"));
+		assert!(amended.tree().unwrap().get_name("b").is_some());
+		assert_eq!(
+			std::fs::read_to_string(root.join("b")).unwrap(),
+			"2"
+		);
+		assert!(repo.statuses(None).unwrap().is_empty());
+	}
+}
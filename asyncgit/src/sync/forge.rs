@@ -0,0 +1,410 @@
+//! pull requests on the forge hosting a remote (GitHub or GitLab),
+//! either through its web page or its CLI (`gh` or `glab`)
+
+use super::{
+	branch::get_branch_name_repo,
+	commit_details::CommitMessage,
+	remotes::get_default_remote_for_push_in_repo,
+	repository::repo,
+	utils::{bytes2string, work_dir},
+	RepoPath,
+};
+use crate::error::{Error, Result};
+use git2::Repository;
+use scopetime::scope_time;
+use std::{
+	fmt::Write,
+	process::{Child, Command, Stdio},
+};
+
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Forge {
+	///
+	GitHub,
+	///
+	GitLab,
+}
+
+impl Forge {
+	/// the CLI creating pull requests
+	pub const fn cli(self) -> &'static str {
+		match self {
+			Self::GitHub => "gh",
+			Self::GitLab => "glab",
+		}
+	}
+}
+
+/// a remote hosted on a forge
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForgeRemote {
+	///
+	pub forge: Forge,
+	///
+	pub host: String,
+	/// page of the repository, `https://{host}/{path}`
+	pub web_url: String,
+}
+
+impl ForgeRemote {
+	/// recognizes https, ssh and scp like (`git@host:path`) urls,
+	/// `None` for hosts that are neither GitHub nor GitLab
+	pub fn from_url(url: &str) -> Option<Self> {
+		let (authority, path) = match url.split_once("://") {
+			Some((_, rest)) => rest.split_once('/')?,
+			None => url.split_once(':')?,
+		};
+		// drop the user and the port
+		let host = authority.rsplit('@').next()?;
+		let host = host.split(':').next()?.to_lowercase();
+
+		let forge = if host.contains("github") {
+			Forge::GitHub
+		} else if host.contains("gitlab") {
+			Forge::GitLab
+		} else {
+			return None;
+		};
+
+		let path = path.trim_matches('/');
+		let path = path.strip_suffix(".git").unwrap_or(path);
+
+		Some(Self {
+			forge,
+			web_url: format!("https://{host}/{path}"),
+			host,
+		})
+	}
+
+	/// page comparing `branch` to `base` to open a pull request
+	pub fn new_pull_request_url(
+		&self,
+		base: &str,
+		branch: &str,
+	) -> String {
+		match self.forge {
+			Forge::GitHub => format!(
+				"{}/compare/{}...{}?expand=1",
+				self.web_url,
+				url_encode(base),
+				url_encode(branch)
+			),
+			Forge::GitLab => format!(
+				"{}/-/merge_requests/new?merge_request%5Bsource_branch%5D={}&merge_request%5Btarget_branch%5D={}",
+				self.web_url,
+				url_encode(branch),
+				url_encode(base)
+			),
+		}
+	}
+}
+
+/// keeps `/` so branch names stay readable
+fn url_encode(text: &str) -> String {
+	text.bytes().fold(String::new(), |mut res, b| {
+		if b.is_ascii_alphanumeric() || b"-._~/".contains(&b) {
+			res.push(char::from(b));
+		} else {
+			let _ = write!(res, "%{b:02X}");
+		}
+		res
+	})
+}
+
+/// the current branch and where a pull request for it goes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullRequestTarget {
+	/// remote the branch is pushed to
+	pub remote: String,
+	///
+	pub forge: ForgeRemote,
+	/// name of the branch on the remote
+	pub branch: String,
+	/// default branch of the remote (its `HEAD`)
+	pub base: String,
+}
+
+impl PullRequestTarget {
+	/// see [`ForgeRemote::new_pull_request_url`]
+	pub fn new_pull_request_url(&self) -> String {
+		self.forge.new_pull_request_url(&self.base, &self.branch)
+	}
+}
+
+/// errors if the push remote is not on a forge or its default
+/// branch is unknown
+pub fn pull_request_target(
+	repo_path: &RepoPath,
+) -> Result<PullRequestTarget> {
+	scope_time!("pull_request_target");
+
+	let repo = repo(repo_path)?;
+
+	let local = get_branch_name_repo(&repo)?;
+	let remote = get_default_remote_for_push_in_repo(&repo)?;
+
+	let url = repo
+		.find_remote(&remote)?
+		.url()
+		.map(String::from)
+		.ok_or(Error::UnknownRemote)?;
+	let forge = ForgeRemote::from_url(&url).ok_or_else(|| {
+		Error::Generic(format!(
+			"remote `{remote}` is neither on GitHub nor on GitLab: {url}"
+		))
+	})?;
+
+	let branch = repo
+		.branch_upstream_merge(&format!("refs/heads/{local}"))
+		.ok()
+		.and_then(|merge| bytes2string(&merge).ok())
+		.and_then(|merge| {
+			merge.strip_prefix("refs/heads/").map(String::from)
+		})
+		.unwrap_or(local);
+
+	let base = remote_default_branch(&repo, &remote)
+		.ok_or_else(|| {
+			Error::Generic(format!(
+				"default branch of `{remote}` unknown, run `git remote set-head {remote} --auto`"
+			))
+		})?;
+
+	Ok(PullRequestTarget {
+		remote,
+		forge,
+		branch,
+		base,
+	})
+}
+
+/// what `refs/remotes/{remote}/HEAD` points to, falls back to a
+/// `main` or `master` branch of the remote
+fn remote_default_branch(
+	repo: &Repository,
+	remote: &str,
+) -> Option<String> {
+	let prefix = format!("refs/remotes/{remote}/");
+
+	if let Ok(head) = repo.find_reference(&format!("{prefix}HEAD")) {
+		if let Some(target) = head.symbolic_target() {
+			if let Some(branch) = target.strip_prefix(&prefix) {
+				return Some(branch.to_string());
+			}
+		}
+	}
+
+	["main", "master"]
+		.into_iter()
+		.find(|name| {
+			repo.find_reference(&format!("{prefix}{name}")).is_ok()
+		})
+		.map(String::from)
+}
+
+/// title and body for the commits of the branch not in the base
+/// yet: the message of a single commit or the branch name and a
+/// list of their summaries
+pub fn pull_request_message(
+	repo_path: &RepoPath,
+	target: &PullRequestTarget,
+) -> Result<(String, String)> {
+	scope_time!("pull_request_message");
+
+	let repo = repo(repo_path)?;
+
+	let mut walk = repo.revwalk()?;
+	walk.push_head()?;
+	walk.hide_ref(&format!(
+		"refs/remotes/{}/{}",
+		target.remote, target.base
+	))?;
+	walk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+
+	let mut messages = Vec::new();
+	for id in walk {
+		let commit = repo.find_commit(id?)?;
+		messages.push(CommitMessage::from(&String::from_utf8_lossy(
+			commit.message_bytes(),
+		)));
+	}
+
+	match messages.as_slice() {
+		[] => Err(Error::Generic(format!(
+			"no commits on top of `{}/{}`",
+			target.remote, target.base
+		))),
+		[message] => Ok((
+			message.subject.clone(),
+			message
+				.body
+				.clone()
+				.unwrap_or_default()
+				.trim()
+				.to_string(),
+		)),
+		messages => Ok((
+			target.branch.clone(),
+			messages
+				.iter()
+				.map(|message| format!("- {}", message.subject))
+				.collect::<Vec<_>>()
+				.join("\n"),
+		)),
+	}
+}
+
+/// the CLI of the forge if it is installed and logged in to the
+/// host of the remote
+pub fn pull_request_cli(
+	target: &PullRequestTarget,
+) -> Option<&'static str> {
+	scope_time!("pull_request_cli");
+
+	let cli = target.forge.forge.cli();
+	let args: &[&str] = match target.forge.forge {
+		Forge::GitHub => &["auth", "token", "--hostname"],
+		Forge::GitLab => &["auth", "status", "--hostname"],
+	};
+
+	Command::new(cli)
+		.args(args)
+		.arg(&target.forge.host)
+		.stdin(Stdio::null())
+		.stdout(Stdio::null())
+		.stderr(Stdio::null())
+		.status()
+		.is_ok_and(|status| status.success())
+		.then_some(cli)
+}
+
+/// runs the CLI of the forge, it prints the url of the pull request
+/// it created, stdout and stderr are piped
+pub fn spawn_create_pull_request(
+	repo_path: &RepoPath,
+	target: &PullRequestTarget,
+	title: &str,
+	body: &str,
+) -> Result<Child> {
+	scope_time!("spawn_create_pull_request");
+
+	let repo = repo(repo_path)?;
+
+	let mut command = Command::new(target.forge.forge.cli());
+	command
+		.current_dir(work_dir(&repo)?)
+		.stdin(Stdio::null())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::piped());
+
+	match target.forge.forge {
+		Forge::GitHub => command.args([
+			"pr",
+			"create",
+			"--repo",
+			&target.forge.web_url,
+			"--head",
+			&target.branch,
+			"--base",
+			&target.base,
+			"--title",
+			title,
+			"--body",
+			body,
+		]),
+		Forge::GitLab => command.args([
+			"mr",
+			"create",
+			"--yes",
+			"--repo",
+			&target.forge.web_url,
+			"--source-branch",
+			&target.branch,
+			"--target-branch",
+			&target.base,
+			"--title",
+			title,
+			"--description",
+			body,
+		]),
+	};
+
+	Ok(command.spawn()?)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::{repo_init, write_commit_file};
+
+	fn forge(url: &str) -> Option<(Forge, String)> {
+		ForgeRemote::from_url(url)
+			.map(|remote| (remote.forge, remote.web_url))
+	}
+
+	#[test]
+	fn test_forge_remote() {
+		let github = Some((
+			Forge::GitHub,
+			String::from("https://github.com/o/r"),
+		));
+
+		assert_eq!(forge("git@github.com:o/r.git"), github);
+		assert_eq!(forge("https://github.com/o/r"), github);
+		assert_eq!(forge("https://user@github.com/o/r.git/"), github);
+		assert_eq!(forge("ssh://git@github.com:22/o/r.git"), github);
+		assert_eq!(
+			forge("git@gitlab.example.com:group/sub/r.git"),
+			Some((
+				Forge::GitLab,
+				String::from(
+					"https://gitlab.example.com/group/sub/r"
+				)
+			))
+		);
+		assert_eq!(forge("https://example.com/o/r.git"), None);
+		assert_eq!(forge("/some/path"), None);
+
+		let remote =
+			ForgeRemote::from_url("git@github.com:o/r.git").unwrap();
+		assert_eq!(
+			remote.new_pull_request_url("main", "feature/a#1"),
+			"https://github.com/o/r/compare/main...feature/a%231?expand=1"
+		);
+	}
+
+	#[test]
+	fn test_pull_request_message() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		repo.remote("origin", "git@github.com:o/r.git").unwrap();
+		let base = repo.head().unwrap().target().unwrap();
+		repo.reference("refs/remotes/origin/main", base, false, "")
+			.unwrap();
+
+		let target = pull_request_target(repo_path).unwrap();
+		assert_eq!(target.remote, "origin");
+		assert_eq!(target.branch, "master");
+		assert_eq!(target.base, "main");
+		assert!(pull_request_message(repo_path, &target).is_err());
+
+		write_commit_file(&repo, "a", "1", "add a\n\nbecause");
+		assert_eq!(
+			pull_request_message(repo_path, &target).unwrap(),
+			(String::from("add a"), String::from("because"))
+		);
+
+		write_commit_file(&repo, "b", "1", "add b");
+		assert_eq!(
+			pull_request_message(repo_path, &target).unwrap(),
+			(
+				String::from("master"),
+				String::from("- add a\n- add b")
+			)
+		);
+	}
+}
@@ -0,0 +1,223 @@
+//! search the content of all files of a commit
+
+use super::{repository::repo, tree_files, CommitId, RepoPath};
+use crate::error::Result;
+use regex::Regex;
+use scopetime::scope_time;
+use std::{
+	borrow::Cow,
+	path::{Path, PathBuf},
+};
+use unicode_truncate::UnicodeTruncateStr;
+
+/// snippets of very long lines get cut off at this width
+const SNIPPET_MAX_WIDTH: usize = 200;
+
+/// what to search for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrepQuery {
+	///
+	pub pattern: String,
+	/// treat `pattern` as regular expression instead of a literal
+	pub regex: bool,
+}
+
+impl GrepQuery {
+	fn matcher(&self) -> Result<Regex> {
+		let pattern = if self.regex {
+			Cow::from(self.pattern.as_str())
+		} else {
+			Cow::from(regex::escape(&self.pattern))
+		};
+
+		Ok(Regex::new(&pattern)?)
+	}
+}
+
+/// a single line matching a `GrepQuery`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrepHit {
+	/// path of the file as in `TreeFile::path`
+	pub path: PathBuf,
+	/// line number, starting at 1
+	pub line: usize,
+	/// the matching line with surrounding whitespace trimmed
+	pub snippet: String,
+}
+
+/// searches all text files of `commit` for lines matching `query`
+///
+/// binary files are skipped. after each file `on_file` is called with the amount of files
+/// searched so far, the total amount of files and the hits found in
+/// that file. the search stops early once `on_file` returns `false`.
+pub fn grep_tree(
+	repo_path: &RepoPath,
+	commit: CommitId,
+	query: &GrepQuery,
+	mut on_file: impl FnMut(usize, usize, Vec<GrepHit>) -> bool,
+) -> Result<()> {
+	scope_time!("grep_tree");
+
+	let matcher = query.matcher()?;
+
+	let repo = repo(repo_path)?;
+	let files = tree_files(repo_path, commit)?;
+	let total = files.len();
+
+	for (idx, file) in files.iter().enumerate() {
+		let blob = repo.find_blob(file.id)?;
+
+		let hits = if blob.is_binary() {
+			Vec::new()
+		} else {
+			grep_content(&matcher, &file.path, blob.content())
+		};
+
+		if !on_file(idx + 1, total, hits) {
+			break;
+		}
+	}
+
+	Ok(())
+}
+
+fn grep_content(
+	matcher: &Regex,
+	path: &Path,
+	content: &[u8],
+) -> Vec<GrepHit> {
+	String::from_utf8_lossy(content)
+		.lines()
+		.enumerate()
+		.filter(|(_, line)| matcher.is_match(line))
+		.map(|(idx, line)| GrepHit {
+			path: path.to_path_buf(),
+			line: idx + 1,
+			snippet: line
+				.trim()
+				.unicode_truncate(SNIPPET_MAX_WIDTH)
+				.0
+				.to_string(),
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		commit, stage_add_file,
+		tests::{repo_init, write_commit_file},
+		utils::repo_write_file,
+	};
+	use pretty_assertions::assert_eq;
+
+	fn grep(
+		repo_path: &RepoPath,
+		commit: CommitId,
+		pattern: &str,
+		regex: bool,
+	) -> Result<Vec<(String, usize, String)>> {
+		let mut hits = Vec::new();
+
+		grep_tree(
+			repo_path,
+			commit,
+			&GrepQuery {
+				pattern: pattern.to_string(),
+				regex,
+			},
+			|_, _, file_hits| {
+				hits.extend(file_hits.into_iter().map(|hit| {
+					(
+						hit.path.to_string_lossy().to_string(),
+						hit.line,
+						hit.snippet,
+					)
+				}));
+				true
+			},
+		)?;
+
+		Ok(hits)
+	}
+
+	#[test]
+	fn test_grep_literal_and_regex() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(
+			&repo,
+			"a.txt",
+			"foo\n  bar.*\nfoobar",
+			"a",
+		);
+		let id = write_commit_file(&repo, "b.txt", "no match", "b");
+
+		assert_eq!(
+			grep(repo_path, id, "bar.*", false).unwrap(),
+			vec![(String::from("./a.txt"), 2, String::from("bar.*"))]
+		);
+
+		assert_eq!(
+			grep(repo_path, id, "^foo", true).unwrap(),
+			vec![
+				(String::from("./a.txt"), 1, String::from("foo")),
+				(String::from("./a.txt"), 3, String::from("foobar")),
+			]
+		);
+
+		assert!(grep(repo_path, id, "(", true).is_err());
+	}
+
+	#[test]
+	fn test_grep_skips_binary() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		repo_write_file(&repo, "bin", "needle\0").unwrap();
+		repo_write_file(&repo, "text", "needle").unwrap();
+		stage_add_file(repo_path, Path::new("bin")).unwrap();
+		stage_add_file(repo_path, Path::new("text")).unwrap();
+		let id = commit(repo_path, "files").unwrap();
+
+		assert_eq!(
+			grep(repo_path, id, "needle", false).unwrap(),
+			vec![(String::from("./text"), 1, String::from("needle"))]
+		);
+	}
+
+	#[test]
+	fn test_grep_stops_early() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a", "x", "a");
+		let id = write_commit_file(&repo, "b", "x", "b");
+
+		let mut searched = 0;
+		grep_tree(
+			repo_path,
+			id,
+			&GrepQuery {
+				pattern: String::from("x"),
+				regex: false,
+			},
+			|done, total, _| {
+				searched = done;
+				assert_eq!(total, 2);
+				false
+			},
+		)
+		.unwrap();
+
+		assert_eq!(searched, 1);
+	}
+}
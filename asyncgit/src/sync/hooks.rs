@@ -1,7 +1,8 @@
 use super::{repository::repo, RepoPath};
-use crate::error::Result;
+use crate::{error::Result, process::force_colors};
 pub use git2_hooks::PrepareCommitMsgSource;
 use scopetime::scope_time;
+use std::process::Child;
 
 ///
 #[derive(Debug, PartialEq, Eq)]
@@ -47,6 +48,52 @@ pub fn hooks_pre_commit(repo_path: &RepoPath) -> Result<HookResult> {
 	Ok(git2_hooks::hooks_pre_commit(&repo, None)?.into())
 }
 
+/// starts the pre-commit hook so its output can be streamed (see
+/// `AsyncProcessJob`), `None` if there is no such hook
+pub fn spawn_pre_commit_hook(
+	repo_path: &RepoPath,
+) -> Result<Option<Child>> {
+	scope_time!("spawn_pre_commit_hook");
+
+	let repo = repo(repo_path)?;
+
+	Ok(git2_hooks::spawn_hook(
+		&repo,
+		None,
+		git2_hooks::HOOK_PRE_COMMIT,
+		&[],
+		force_colors,
+	)?)
+}
+
+/// starts the commit-msg hook checking `msg` so its output can be
+/// streamed, `None` if there is no such hook. the hook may alter the
+/// msg, see [`hook_commit_msg`]
+pub fn spawn_commit_msg_hook(
+	repo_path: &RepoPath,
+	msg: &str,
+) -> Result<Option<Child>> {
+	scope_time!("spawn_commit_msg_hook");
+
+	let repo = repo(repo_path)?;
+
+	Ok(git2_hooks::spawn_hooks_commit_msg(
+		&repo,
+		None,
+		msg,
+		force_colors,
+	)?)
+}
+
+/// the msg the last commit-msg or prepare-commit-msg hook started by
+/// [`spawn_commit_msg_hook`] or [`spawn_prepare_commit_msg_hook`]
+/// left behind
+pub fn hook_commit_msg(repo_path: &RepoPath) -> Result<String> {
+	let repo = repo(repo_path)?;
+
+	Ok(git2_hooks::read_hook_msg(&repo)?)
+}
+
 /// see `git2_hooks::hooks_post_commit`
 pub fn hooks_post_commit(repo_path: &RepoPath) -> Result<HookResult> {
 	scope_time!("hooks_post_commit");
@@ -56,6 +103,24 @@ pub fn hooks_post_commit(repo_path: &RepoPath) -> Result<HookResult> {
 	Ok(git2_hooks::hooks_post_commit(&repo, None)?.into())
 }
 
+/// starts the post-commit hook so its output can be streamed,
+/// `None` if there is no such hook
+pub fn spawn_post_commit_hook(
+	repo_path: &RepoPath,
+) -> Result<Option<Child>> {
+	scope_time!("spawn_post_commit_hook");
+
+	let repo = repo(repo_path)?;
+
+	Ok(git2_hooks::spawn_hook(
+		&repo,
+		None,
+		git2_hooks::HOOK_POST_COMMIT,
+		&[],
+		force_colors,
+	)?)
+}
+
 /// see `git2_hooks::hooks_prepare_commit_msg`
 pub fn hooks_prepare_commit_msg(
 	repo_path: &RepoPath,
@@ -72,6 +137,27 @@ pub fn hooks_prepare_commit_msg(
 	.into())
 }
 
+/// starts the prepare-commit-msg hook with `msg` so its output can
+/// be streamed, `None` if there is no such hook. the hook may alter
+/// the msg, see [`hook_commit_msg`]
+pub fn spawn_prepare_commit_msg_hook(
+	repo_path: &RepoPath,
+	source: PrepareCommitMsgSource,
+	msg: &str,
+) -> Result<Option<Child>> {
+	scope_time!("spawn_prepare_commit_msg_hook");
+
+	let repo = repo(repo_path)?;
+
+	Ok(git2_hooks::spawn_hooks_prepare_commit_msg(
+		&repo,
+		None,
+		source,
+		msg,
+		force_colors,
+	)?)
+}
+
 #[cfg(test)]
 mod tests {
 	use std::{ffi::OsString, io::Write as _, path::Path};
@@ -178,6 +264,101 @@ mod tests {
 		}
 	}
 
+	#[test]
+	#[cfg(unix)]
+	fn test_spawn_pre_commit_hook() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.workdir().unwrap();
+		let repo_path: &RepoPath = &root.to_path_buf().into();
+
+		assert!(spawn_pre_commit_hook(repo_path).unwrap().is_none());
+
+		let hook = b"#!/bin/sh
+	echo \"color: $FORCE_COLOR\"
+	exit 0
+		";
+		git2_hooks::create_hook(
+			&repo,
+			git2_hooks::HOOK_PRE_COMMIT,
+			hook,
+		);
+
+		let output = spawn_pre_commit_hook(repo_path)
+			.unwrap()
+			.unwrap()
+			.wait_with_output()
+			.unwrap();
+
+		assert!(output.status.success());
+		assert_eq!(
+			String::from_utf8_lossy(&output.stdout).trim_end(),
+			"color: 1"
+		);
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn test_spawn_commit_msg_hooks() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.workdir().unwrap();
+		let repo_path: &RepoPath = &root.to_path_buf().into();
+
+		assert!(spawn_commit_msg_hook(repo_path, "msg")
+			.unwrap()
+			.is_none());
+		assert!(spawn_post_commit_hook(repo_path).unwrap().is_none());
+
+		let hook = b"#!/bin/sh
+	echo \"$(cat \"$1\") $2\" > \"$1\"
+	exit 0
+		";
+		git2_hooks::create_hook(
+			&repo,
+			git2_hooks::HOOK_PREPARE_COMMIT_MSG,
+			hook,
+		);
+
+		let status = spawn_prepare_commit_msg_hook(
+			repo_path,
+			PrepareCommitMsgSource::Template,
+			"msg",
+		)
+		.unwrap()
+		.unwrap()
+		.wait()
+		.unwrap();
+
+		assert!(status.success());
+		assert_eq!(
+			hook_commit_msg(repo_path).unwrap(),
+			"msg template\n"
+		);
+
+		let hook = b"#!/bin/sh
+	echo 'color:' $FORCE_COLOR
+	exit 1
+		";
+		git2_hooks::create_hook(
+			&repo,
+			git2_hooks::HOOK_COMMIT_MSG,
+			hook,
+		);
+
+		let output = spawn_commit_msg_hook(repo_path, "msg")
+			.unwrap()
+			.unwrap()
+			.wait_with_output()
+			.unwrap();
+
+		assert_eq!(output.status.code(), Some(1));
+		assert_eq!(
+			String::from_utf8_lossy(&output.stdout).trim_end(),
+			"color: 1"
+		);
+		// left as it was
+		assert_eq!(hook_commit_msg(repo_path).unwrap(), "msg");
+	}
+
 	#[test]
 	fn test_hooks_commit_msg_reject_in_subfolder() {
 		let (_td, repo) = repo_init().unwrap();
@@ -5,7 +5,10 @@ use super::{
 use crate::{
 	error::{Error, Result},
 	hash,
-	sync::repository::repo,
+	sync::{
+		index_file::check_index_writable, repository::repo,
+		stage_cleanup::StageSnapshot,
+	},
 };
 use git2::{ApplyLocation, ApplyOptions, Diff};
 use scopetime::scope_time;
@@ -20,8 +23,10 @@ pub fn stage_hunk(
 	scope_time!("stage_hunk");
 
 	let repo = repo(repo_path)?;
+	check_index_writable(&repo)?;
 
 	let diff = get_diff_raw(&repo, file_path, false, false, options)?;
+	let snapshot = StageSnapshot::take(&repo, &repo.index()?)?;
 
 	let mut opt = ApplyOptions::new();
 	opt.hunk_callback(|hunk| {
@@ -33,6 +38,13 @@ pub fn stage_hunk(
 
 	repo.apply(&diff, ApplyLocation::Index, Some(&mut opt))?;
 
+	if let Some(snapshot) = snapshot {
+		let mut index = repo.index()?;
+		index.read(true)?;
+		snapshot.clean(&repo, &mut index)?;
+		index.write()?;
+	}
+
 	Ok(())
 }
 
@@ -59,7 +71,9 @@ pub fn reset_hunk(
 			res
 		});
 
-		let diff = get_diff_raw(&repo, file_path, false, true, None)?;
+		// same options, otherwise the index could be another hunk
+		let diff =
+			get_diff_raw(&repo, file_path, false, true, options)?;
 
 		repo.apply(&diff, ApplyLocation::WorkDir, Some(&mut opt))?;
 
@@ -105,6 +119,7 @@ pub fn unstage_hunk(
 	scope_time!("revert_hunk");
 
 	let repo = repo(repo_path)?;
+	check_index_writable(&repo)?;
 
 	let diff = get_diff_raw(&repo, file_path, true, false, options)?;
 	let diff_count_positive = diff.deltas().len();
@@ -153,7 +168,11 @@ mod tests {
 	use super::*;
 	use crate::{
 		error::Result,
-		sync::{diff::get_diff, tests::repo_init_empty},
+		sync::{
+			diff::{diff_unchanged, get_diff},
+			tests::{repo_init, repo_init_empty, write_commit_file},
+			utils::repo_write_file,
+		},
 	};
 	use std::{
 		fs::{self, File},
@@ -191,4 +210,71 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn reset_hunk_keeps_line_endings() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let lines = (1..=6).map(|i| format!("{i}\r\n"));
+		let original =
+			format!("{}end", lines.clone().collect::<String>());
+		write_commit_file(&repo, "a.txt", &original, "c1");
+
+		let changed = format!(
+			"first\r\n{}changed end",
+			lines.skip(1).collect::<String>()
+		);
+		repo_write_file(&repo, "a.txt", &changed)?;
+
+		// the hunks only exist with less context
+		let options = Some(DiffOptions {
+			context: 1,
+			..DiffOptions::default()
+		});
+		let diff = get_diff(repo_path, "a.txt", false, options)?;
+		assert_eq!(diff.hunks.len(), 2);
+
+		reset_hunk(
+			repo_path,
+			"a.txt",
+			diff.hunks[1].header_hash,
+			options,
+		)?;
+
+		assert_eq!(
+			fs::read_to_string(root.join("a.txt"))?,
+			format!("first\r\n{}", &original[3..])
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn diff_changed_on_disk() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a\n", "c1");
+		repo_write_file(&repo, "a.txt", "b\n")?;
+
+		let diff_hash =
+			hash(&get_diff(repo_path, "a.txt", false, None)?);
+		assert!(diff_unchanged(
+			repo_path, "a.txt", false, diff_hash, None
+		)
+		.is_ok());
+
+		repo_write_file(&repo, "a.txt", "c\n")?;
+		assert!(diff_unchanged(
+			repo_path, "a.txt", false, diff_hash, None
+		)
+		.is_err());
+
+		Ok(())
+	}
 }
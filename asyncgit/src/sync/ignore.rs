@@ -12,6 +12,48 @@ use std::{
 
 static GITIGNORE: &str = ".gitignore";
 
+/// how to ignore a path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IgnoreKind {
+	/// exactly this path
+	Path,
+	/// every file with the same extension
+	Extension,
+	/// the folder containing the file (or the folder itself)
+	Directory,
+}
+
+impl IgnoreKind {
+	///
+	pub const ALL: [Self; 3] =
+		[Self::Path, Self::Extension, Self::Directory];
+}
+
+/// the pattern to append to ignore the repo relative `path` the
+/// `kind` way, `None` if that does not apply (e.g. no extension)
+pub fn ignore_pattern(
+	path: &str,
+	is_dir: bool,
+	kind: IgnoreKind,
+) -> Option<String> {
+	let path = path.trim_end_matches('/');
+
+	match kind {
+		IgnoreKind::Path => Some(path.to_string()),
+		IgnoreKind::Extension if !is_dir => Path::new(path)
+			.extension()
+			.and_then(|ext| ext.to_str())
+			.map(|ext| format!("*.{ext}")),
+		IgnoreKind::Extension => None,
+		IgnoreKind::Directory if is_dir => Some(format!("{path}/")),
+		IgnoreKind::Directory => Path::new(path)
+			.parent()
+			.and_then(Path::to_str)
+			.filter(|parent| !parent.is_empty())
+			.map(|parent| format!("{parent}/")),
+	}
+}
+
 /// add file or path to root ignore file
 pub fn add_to_ignore(
 	repo_path: &RepoPath,
@@ -140,6 +182,63 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_ignore_pattern() {
+		let pattern = |path, is_dir, kind| {
+			ignore_pattern(path, is_dir, kind).unwrap_or_default()
+		};
+
+		assert_eq!(
+			pattern("a/b.txt", false, IgnoreKind::Path),
+			"a/b.txt"
+		);
+		assert_eq!(
+			pattern("a/b.txt", false, IgnoreKind::Extension),
+			"*.txt"
+		);
+		assert_eq!(
+			pattern("a/b.txt", false, IgnoreKind::Directory),
+			"a/"
+		);
+
+		assert_eq!(pattern("a/b/", true, IgnoreKind::Path), "a/b");
+		assert_eq!(
+			pattern("a/b", true, IgnoreKind::Directory),
+			"a/b/"
+		);
+
+		assert_eq!(
+			ignore_pattern("b", false, IgnoreKind::Directory),
+			None
+		);
+		assert_eq!(
+			ignore_pattern("b", false, IgnoreKind::Extension),
+			None
+		);
+		assert_eq!(
+			ignore_pattern("a.d", true, IgnoreKind::Extension),
+			None
+		);
+	}
+
+	#[test]
+	fn test_ignore_from_subdir() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let sub = root.join("sub");
+		std::fs::create_dir(&sub)?;
+		let repo_path: &RepoPath =
+			&sub.as_os_str().to_str().unwrap().into();
+
+		add_to_ignore(repo_path, "sub/foo.txt")?;
+
+		assert!(!sub.join(".gitignore").exists());
+		let mut lines = read_lines(root.join(".gitignore")).unwrap();
+		assert_eq!(&lines.next().unwrap().unwrap(), "sub/foo.txt");
+
+		Ok(())
+	}
+
 	#[test]
 	fn test_ignore_ignore() {
 		let ignore_file_path = Path::new(".gitignore");
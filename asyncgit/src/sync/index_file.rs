@@ -0,0 +1,344 @@
+//! reads the raw index file for what libgit2 does not expose or would
+//! not write back (see `gitformat-index`)
+
+use super::{repository::repo, RepoPath};
+use crate::error::{Error, Result};
+use git2::Repository;
+use scopetime::scope_time;
+use std::{fs, io, ops::Range, path::Path};
+
+const INDEX_HASH_LEN: usize = 20;
+const INDEX_ENTRY_PATH_OFFSET: usize = 62;
+const INDEX_ENTRY_EXTENDED: u16 = 0x4000;
+/// newest index version libgit2 can write
+const MAX_WRITABLE_VERSION: u32 = 4;
+const SPARSE_EXTENSION: &str = "sdir";
+const SPLIT_EXTENSION: &str = "link";
+
+/// version and extensions of the index file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexInfo {
+	///
+	pub version: u32,
+	/// signatures in file order
+	pub extensions: Vec<String>,
+}
+
+impl IndexInfo {
+	/// directories outside of the sparse checkout are single entries
+	pub fn is_sparse(&self) -> bool {
+		self.has_extension(SPARSE_EXTENSION)
+	}
+
+	///
+	pub fn has_extension(&self, signature: &str) -> bool {
+		self.extensions.iter().any(|ext| ext == signature)
+	}
+
+	/// why writing the index with libgit2 would lose information:
+	/// it drops extensions it does not know, which is only safe for
+	/// the optional ones (caches git rebuilds)
+	fn unwritable_reason(&self) -> Option<String> {
+		if self.is_sparse() {
+			return Some(String::from("a sparse index"));
+		}
+
+		if self.has_extension(SPLIT_EXTENSION) {
+			return Some(String::from("a split index"));
+		}
+
+		if self.version > MAX_WRITABLE_VERSION {
+			return Some(format!("index version {}", self.version));
+		}
+
+		self.extensions
+			.iter()
+			.find(|ext| {
+				!ext.starts_with(|c: char| c.is_ascii_uppercase())
+			})
+			.map(|ext| format!("the index extension `{ext}`"))
+	}
+}
+
+/// `None` if there is no index file (yet)
+pub fn index_info(repo_path: &RepoPath) -> Result<Option<IndexInfo>> {
+	scope_time!("index_info");
+
+	let repo = repo(repo_path)?;
+
+	index_info_at(&repo.path().join("index"))
+}
+
+fn index_info_at(path: &Path) -> Result<Option<IndexInfo>> {
+	let data = match fs::read(path) {
+		Ok(data) => data,
+		Err(e) if e.kind() == io::ErrorKind::NotFound => {
+			return Ok(None)
+		}
+		Err(e) => return Err(e.into()),
+	};
+
+	Ok(Some(IndexInfo {
+		version: be_u32(&data, 4)?,
+		extensions: read_extensions(&data)?
+			.into_iter()
+			.map(|(signature, _)| {
+				String::from_utf8_lossy(&signature).into_owned()
+			})
+			.collect(),
+	}))
+}
+
+/// fails instead of letting libgit2 rewrite an index it can not
+/// represent (like a sparse index) into a lesser one
+pub fn check_index_writable(repo: &Repository) -> Result<()> {
+	let Some(info) = index_info_at(&repo.path().join("index"))?
+	else {
+		return Ok(());
+	};
+
+	info.unwritable_reason()
+		.map_or(Ok(()), |reason| Err(Error::UnsupportedIndex(reason)))
+}
+
+fn invalid_index() -> Error {
+	Error::Generic(String::from("unsupported index file format"))
+}
+
+fn be_u32(data: &[u8], offset: usize) -> Result<u32> {
+	data.get(offset..offset + 4)
+		.and_then(|bytes| bytes.try_into().ok())
+		.map(u32::from_be_bytes)
+		.ok_or_else(invalid_index)
+}
+
+/// offset of the first extension in an index file, right behind the
+/// entries
+fn index_extensions_offset(data: &[u8]) -> Result<usize> {
+	if data.get(..4) != Some(b"DIRC") {
+		return Err(invalid_index());
+	}
+
+	let version = be_u32(data, 4)?;
+	let count = be_u32(data, 8)?;
+	let mut offset = 12;
+
+	for _ in 0..count {
+		let flags = data
+			.get(offset + 60..offset + INDEX_ENTRY_PATH_OFFSET)
+			.map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+			.ok_or_else(invalid_index)?;
+
+		let mut path = offset + INDEX_ENTRY_PATH_OFFSET;
+		if version >= 3 && flags & INDEX_ENTRY_EXTENDED != 0 {
+			path += 2;
+		}
+
+		if version >= 4 {
+			// varint of how much to strip from the previous path
+			while data.get(path).ok_or_else(invalid_index)? & 0x80
+				!= 0
+			{
+				path += 1;
+			}
+			path += 1;
+		}
+
+		let path_len = data
+			.get(path..)
+			.and_then(|rest| rest.iter().position(|b| *b == 0))
+			.ok_or_else(invalid_index)?;
+
+		offset = if version >= 4 {
+			path + path_len + 1
+		} else {
+			// entries are padded with 1-8 nul bytes to a multiple of 8
+			offset + ((path - offset + path_len + 8) & !7)
+		};
+	}
+
+	Ok(offset)
+}
+
+/// signature and byte range (header included) of each extension
+pub fn read_extensions(
+	data: &[u8],
+) -> Result<Vec<([u8; 4], Range<usize>)>> {
+	let end = data
+		.len()
+		.checked_sub(INDEX_HASH_LEN)
+		.ok_or_else(invalid_index)?;
+	let mut offset = index_extensions_offset(data)?;
+	let mut res = Vec::new();
+
+	while offset < end {
+		let size = usize::try_from(be_u32(data, offset + 4)?)
+			.map_err(|_| invalid_index())?;
+		let next = offset + 8 + size;
+		if next > end {
+			return Err(invalid_index());
+		}
+
+		let mut signature = [0; 4];
+		signature.copy_from_slice(&data[offset..offset + 4]);
+		res.push((signature, offset..next));
+
+		offset = next;
+	}
+
+	Ok(res)
+}
+
+/// returns the index file without `signature` extension and with an
+/// updated checksum or `None` if there is no such extension
+pub fn strip_index_extension(
+	data: &[u8],
+	signature: [u8; 4],
+) -> Result<Option<Vec<u8>>> {
+	let extensions = read_extensions(data)?;
+	if !extensions.iter().any(|(ext, _)| *ext == signature) {
+		return Ok(None);
+	}
+
+	let mut res = data[..index_extensions_offset(data)?].to_vec();
+	for (_, range) in
+		extensions.into_iter().filter(|(ext, _)| *ext != signature)
+	{
+		res.extend_from_slice(&data[range]);
+	}
+
+	let mut hasher = gix::hash::hasher(gix::hash::Kind::Sha1);
+	hasher.update(&res);
+	let hash = hasher
+		.try_finalize()
+		.map_err(|e| Error::Generic(e.to_string()))?;
+	res.extend_from_slice(hash.as_bytes());
+
+	Ok(Some(res))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		reset_stage, stage_add_file, stage_hunk,
+		tests::{repo_init, write_commit_file},
+	};
+	use git2::Repository;
+	use std::{fs::File, io::Write};
+
+	/// appends an empty extension like git writes it for sparse and
+	/// split indexes
+	fn add_extension(repo: &Repository, signature: [u8; 4]) {
+		let path = repo.path().join("index");
+		let data = fs::read(&path).unwrap();
+		let end = data.len() - INDEX_HASH_LEN;
+
+		let mut res = data[..end].to_vec();
+		res.extend_from_slice(&signature);
+		res.extend_from_slice(&0_u32.to_be_bytes());
+
+		let mut hasher = gix::hash::hasher(gix::hash::Kind::Sha1);
+		hasher.update(&res);
+		res.extend_from_slice(
+			hasher.try_finalize().unwrap().as_bytes(),
+		);
+
+		fs::write(path, res).unwrap();
+	}
+
+	#[test]
+	fn test_keeps_index_version() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+
+		let mut index = repo.index().unwrap();
+		index.set_version(4).unwrap();
+		index.write().unwrap();
+
+		File::create(root.join("a.txt"))
+			.unwrap()
+			.write_all(b"b")
+			.unwrap();
+		File::create(root.join("b.txt"))
+			.unwrap()
+			.write_all(b"b")
+			.unwrap();
+
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+		stage_add_file(repo_path, Path::new("b.txt")).unwrap();
+		reset_stage(repo_path, "a.txt").unwrap();
+
+		let info = index_info(repo_path).unwrap().unwrap();
+		assert_eq!(info.version, 4);
+		assert!(!info.is_sparse());
+	}
+
+	#[test]
+	fn test_refuses_sparse_index() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		add_extension(&repo, *b"sdir");
+
+		let info = index_info(repo_path).unwrap().unwrap();
+		assert!(info.is_sparse());
+		assert_eq!(info.extensions, vec![String::from("sdir")]);
+
+		File::create(root.join("a.txt"))
+			.unwrap()
+			.write_all(b"b")
+			.unwrap();
+
+		let before = fs::read(repo.path().join("index")).unwrap();
+
+		assert!(matches!(
+			stage_add_file(repo_path, Path::new("a.txt")),
+			Err(Error::UnsupportedIndex(_))
+		));
+		assert!(matches!(
+			stage_hunk(repo_path, "a.txt", 0, None),
+			Err(Error::UnsupportedIndex(_))
+		));
+
+		assert_eq!(
+			fs::read(repo.path().join("index")).unwrap(),
+			before
+		);
+	}
+
+	#[test]
+	fn test_optional_extensions_are_writable() {
+		let (_td, repo) = repo_init().unwrap();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		add_extension(&repo, *b"UNTR");
+
+		assert!(check_index_writable(&repo).is_ok());
+
+		add_extension(&repo, *b"link");
+
+		assert!(matches!(
+			check_index_writable(&repo),
+			Err(Error::UnsupportedIndex(reason)) if reason == "a split index"
+		));
+	}
+
+	#[test]
+	fn test_no_index() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		// nothing staged yet
+		assert_eq!(index_info(repo_path).unwrap(), None);
+	}
+}
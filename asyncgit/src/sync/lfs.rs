@@ -0,0 +1,391 @@
+//! git lfs keeps large files out of the repository, what gets
+//! committed is a small pointer to the content stored elsewhere.
+//! libgit2 does not run the `lfs` filter that swaps the two, so we
+//! recognize the pointers ourselves and run `git lfs` for the content
+
+use super::{
+	commit_files::OldNew, repository::repo, utils::work_dir, RepoPath,
+};
+use crate::{error::Result, StatusItem};
+use easy_cast::Conv;
+use git2::{AttrCheckFlags, Diff, DiffFile, Repository};
+use scopetime::scope_time;
+use std::{
+	fs,
+	io::Write,
+	path::Path,
+	process::{Child, Command, Stdio},
+};
+
+/// pointers are tiny, larger content is never one
+const MAX_POINTER_SIZE: u64 = 1024;
+
+/// the spec of the first line, `hawser` is its old name
+const SPEC_VERSIONS: [&str; 2] = [
+	"https://git-lfs.github.com/spec/v1",
+	"https://hawser.github.com/spec/v1",
+];
+
+/// what a pointer file tells about the content it stands for
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct LfsPointer {
+	/// sha256 of the content (hex, without the `sha256:` prefix)
+	pub oid: String,
+	/// size of the content in bytes
+	pub size: u64,
+}
+
+impl LfsPointer {
+	/// the pointer file as `git lfs` writes it
+	pub fn text(&self) -> String {
+		format!(
+			"version {}\noid sha256:{}\nsize {}\n",
+			SPEC_VERSIONS[0], self.oid, self.size
+		)
+	}
+}
+
+/// one side of a file diff involving lfs
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum LfsSide {
+	/// the file does not exist on this side
+	Absent,
+	/// a pointer to content kept by lfs
+	Pointer(LfsPointer),
+	/// plain content, e.g. the checked out (smudged) working copy
+	Content(u64),
+}
+
+impl LfsSide {
+	/// size of the content in bytes
+	pub const fn size(&self) -> u64 {
+		match self {
+			Self::Absent => 0,
+			Self::Pointer(pointer) => pointer.size,
+			Self::Content(size) => *size,
+		}
+	}
+
+	///
+	pub const fn pointer(&self) -> Option<&LfsPointer> {
+		match self {
+			Self::Pointer(pointer) => Some(pointer),
+			_ => None,
+		}
+	}
+}
+
+/// a file diff with an lfs pointer on at least one side, a text
+/// diff of the pointers would only show their hashes
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct LfsDiff {
+	///
+	pub old: LfsSide,
+	///
+	pub new: LfsSide,
+}
+
+impl LfsDiff {
+	/// the pointer to show the content of, the new one if both are
+	pub const fn pointer(&self) -> Option<&LfsPointer> {
+		match self.new.pointer() {
+			Some(pointer) => Some(pointer),
+			None => self.old.pointer(),
+		}
+	}
+}
+
+/// `None` unless `content` is an lfs pointer file
+pub fn parse_lfs_pointer(content: &[u8]) -> Option<LfsPointer> {
+	if u64::conv(content.len()) > MAX_POINTER_SIZE {
+		return None;
+	}
+
+	let text = std::str::from_utf8(content).ok()?;
+	let mut lines = text.lines();
+
+	let version = lines.next()?.strip_prefix("version ")?;
+	if !SPEC_VERSIONS.contains(&version) {
+		return None;
+	}
+
+	let mut oid = None;
+	let mut size = None;
+	for line in lines {
+		let (key, value) = line.split_once(' ')?;
+
+		match key {
+			"oid" => {
+				oid = value
+					.strip_prefix("sha256:")
+					.filter(|oid| {
+						oid.len() == 64
+							&& oid
+								.bytes()
+								.all(|b| b.is_ascii_hexdigit())
+					})
+					.map(String::from);
+			}
+			"size" => size = value.parse().ok(),
+			_ => (),
+		}
+	}
+
+	Some(LfsPointer {
+		oid: oid?,
+		size: size?,
+	})
+}
+
+/// the lfs pointers of a diff of a single file, `None` if neither
+/// side is one. only the sides in `odb` are read from the object
+/// database, the others from the working tree
+pub fn lfs_diff(
+	repo: &Repository,
+	diff: &Diff,
+	work_dir: &Path,
+	odb: OldNew<bool>,
+) -> Result<Option<LfsDiff>> {
+	if diff.deltas().len() != 1 {
+		return Ok(None);
+	}
+
+	let Some(delta) = diff.get_delta(0) else {
+		return Ok(None);
+	};
+
+	let old = lfs_side(repo, work_dir, odb.old, &delta.old_file())?;
+	let new = lfs_side(repo, work_dir, odb.new, &delta.new_file())?;
+
+	if old.pointer().is_none() && new.pointer().is_none() {
+		return Ok(None);
+	}
+
+	Ok(Some(LfsDiff { old, new }))
+}
+
+fn lfs_side(
+	repo: &Repository,
+	work_dir: &Path,
+	in_odb: bool,
+	file: &DiffFile,
+) -> Result<LfsSide> {
+	if in_odb {
+		if file.id().is_zero() {
+			return Ok(LfsSide::Absent);
+		}
+
+		// the header tells the size without reading large blobs
+		let (size, _) = repo.odb()?.read_header(file.id())?;
+		let size = u64::conv(size);
+		let pointer = if size <= MAX_POINTER_SIZE {
+			parse_lfs_pointer(repo.find_blob(file.id())?.content())
+		} else {
+			None
+		};
+
+		return Ok(
+			pointer.map_or(LfsSide::Content(size), LfsSide::Pointer)
+		);
+	}
+
+	let Some(path) = file.path() else {
+		return Ok(LfsSide::Absent);
+	};
+	let path = work_dir.join(path);
+
+	let Ok(meta) = fs::symlink_metadata(&path) else {
+		return Ok(LfsSide::Absent);
+	};
+	if !meta.is_file() {
+		return Ok(LfsSide::Content(meta.len()));
+	}
+
+	let pointer = if meta.len() <= MAX_POINTER_SIZE {
+		parse_lfs_pointer(&fs::read(&path)?)
+	} else {
+		None
+	};
+
+	Ok(
+		pointer
+			.map_or(LfsSide::Content(meta.len()), LfsSide::Pointer),
+	)
+}
+
+/// sets `StatusItem::lfs` for the files the `filter=lfs` attribute
+/// (`git lfs track`) applies to
+pub fn set_lfs_tracked(
+	repo: &Repository,
+	items: &mut [StatusItem],
+) -> Result<()> {
+	for item in items.iter_mut() {
+		item.lfs = repo.get_attr(
+			Path::new(&item.path),
+			"filter",
+			AttrCheckFlags::FILE_THEN_INDEX,
+		)? == Some("lfs");
+	}
+
+	Ok(())
+}
+
+/// whether `git lfs` is installed to get the content of pointers
+pub fn lfs_installed() -> bool {
+	Command::new("git")
+		.args(["lfs", "version"])
+		.stdin(Stdio::null())
+		.stdout(Stdio::null())
+		.stderr(Stdio::null())
+		.status()
+		.is_ok_and(|status| status.success())
+}
+
+/// writes the content `pointer` of `path` stands for to stdout,
+/// downloading it if it is not in the local lfs storage yet. stdout
+/// and stderr are piped
+pub fn spawn_lfs_smudge(
+	repo_path: &RepoPath,
+	path: &str,
+	pointer: &LfsPointer,
+) -> Result<Child> {
+	scope_time!("spawn_lfs_smudge");
+
+	let repo = repo(repo_path)?;
+
+	let mut child = Command::new("git")
+		.current_dir(work_dir(&repo).unwrap_or_else(|_| repo.path()))
+		.arg("--git-dir")
+		.arg(repo.path())
+		.args(["lfs", "smudge", "--", path])
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::piped())
+		.spawn()?;
+
+	// a pointer fits into the pipe buffer, closing it ends the input
+	if let Some(mut stdin) = child.stdin.take() {
+		stdin.write_all(pointer.text().as_bytes())?;
+	}
+
+	Ok(child)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		diff::{get_diff, get_diff_commit},
+		status::{get_status, StatusType},
+		tests::{repo_init, write_commit_file},
+		utils::repo_write_file,
+	};
+
+	const OID: &str =
+		"4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393";
+
+	fn pointer(size: u64) -> String {
+		LfsPointer {
+			oid: OID.to_string(),
+			size,
+		}
+		.text()
+	}
+
+	#[test]
+	fn test_parse_pointer() {
+		assert_eq!(
+			parse_lfs_pointer(pointer(12345).as_bytes()),
+			Some(LfsPointer {
+				oid: OID.to_string(),
+				size: 12345,
+			})
+		);
+
+		// extensions are allowed between the version and the oid
+		let extended = format!(
+			"version {}\next-0-foo sha256:{OID}\noid sha256:{OID}\nsize 1\n",
+			SPEC_VERSIONS[0]
+		);
+		assert!(parse_lfs_pointer(extended.as_bytes()).is_some());
+
+		assert_eq!(parse_lfs_pointer(b"hello\n"), None);
+		assert_eq!(
+			parse_lfs_pointer(
+				pointer(1).replace("sha256:", "md5:").as_bytes()
+			),
+			None
+		);
+		assert_eq!(
+			parse_lfs_pointer(
+				pointer(1).replace("size 1", "size x").as_bytes()
+			),
+			None
+		);
+	}
+
+	#[test]
+	fn test_diff_of_pointers() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "big.bin", &pointer(1000), "c1");
+		let id =
+			write_commit_file(&repo, "big.bin", &pointer(3000), "c2");
+
+		let diff =
+			get_diff_commit(repo_path, id, "big.bin".into(), None)
+				.unwrap();
+		assert!(diff.hunks.is_empty());
+		assert_eq!(diff.sizes, (1000, 3000));
+		assert_eq!(diff.size_delta, 2000);
+		let lfs = diff.lfs.unwrap();
+		assert_eq!(lfs.old.size(), 1000);
+		assert_eq!(lfs.pointer().unwrap().size, 3000);
+
+		// the smudged working copy is plain content
+		repo_write_file(&repo, "big.bin", "content").unwrap();
+		let diff =
+			get_diff(repo_path, "big.bin", false, None).unwrap();
+		let lfs = diff.lfs.unwrap();
+		assert_eq!(lfs.new, LfsSide::Content(7));
+		assert_eq!(lfs.pointer().unwrap().size, 3000);
+
+		write_commit_file(&repo, "plain.txt", "a\n", "c3");
+		repo_write_file(&repo, "plain.txt", "b\n").unwrap();
+		let diff =
+			get_diff(repo_path, "plain.txt", false, None).unwrap();
+		assert!(diff.lfs.is_none());
+		assert_eq!(diff.hunks.len(), 1);
+	}
+
+	#[test]
+	fn test_lfs_tracked_status() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		repo_write_file(
+			&repo,
+			".gitattributes",
+			"*.bin filter=lfs diff=lfs merge=lfs -text\n",
+		)
+		.unwrap();
+		repo_write_file(&repo, "big.bin", pointer(1)).unwrap();
+		repo_write_file(&repo, "small.txt", "a\n").unwrap();
+
+		let status =
+			get_status(repo_path, StatusType::WorkingDir, None)
+				.unwrap();
+		let lfs = |path: &str| {
+			status.iter().find(|item| item.path == path).unwrap().lfs
+		};
+
+		assert!(lfs("big.bin"));
+		assert!(!lfs("small.txt"));
+		assert!(!lfs(".gitattributes"));
+	}
+}
@@ -0,0 +1,468 @@
+//! opt-in local history: snapshots of changed files in the workdir,
+//! kept as blobs under a ref that no branch, tag or log ever shows
+
+use super::{
+	diff::{get_diff_blob, DiffOptions},
+	repository::repo,
+	utils::work_dir,
+	RepoPath,
+};
+use crate::{error::Result, FileDiff};
+use git2::{FileMode, Oid, Repository, StatusOptions, StatusShow};
+use scopetime::scope_time;
+use std::{
+	collections::HashMap,
+	fs,
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+/// points to a tree with the index and every snapshot blob, `git gc`
+/// keeps them but log walks (even `git log --all`) skip trees
+const LOCAL_HISTORY_REF: &str = "refs/gitui/local-history";
+/// name of the blob listing the snapshots, one per line
+const INDEX_FILE: &str = "index";
+/// larger files are not snapshotted
+const MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+/// how many snapshots are kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalHistoryLimits {
+	/// per file, older ones are dropped first
+	pub max_per_file: usize,
+	/// in seconds
+	pub max_age: i64,
+}
+
+impl Default for LocalHistoryLimits {
+	fn default() -> Self {
+		Self {
+			max_per_file: 50,
+			max_age: 14 * 24 * 60 * 60,
+		}
+	}
+}
+
+/// content of a file at some point in time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+	/// unix time in seconds
+	pub time: i64,
+	/// relative to the workdir
+	pub path: String,
+	id: Oid,
+}
+
+impl Snapshot {
+	fn to_line(&self) -> String {
+		format!("{} {} {}\n", self.time, self.id, self.path)
+	}
+
+	fn from_line(line: &str) -> Option<Self> {
+		let mut fields = line.splitn(3, ' ');
+
+		Some(Self {
+			time: fields.next()?.parse().ok()?,
+			id: Oid::from_str(fields.next()?).ok()?,
+			path: fields.next()?.to_string(),
+		})
+	}
+}
+
+fn now() -> i64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.ok()
+		.and_then(|time| i64::try_from(time.as_secs()).ok())
+		.unwrap_or_default()
+}
+
+/// all snapshots, oldest first
+fn read_snapshots(repo: &Repository) -> Result<Vec<Snapshot>> {
+	let Ok(reference) = repo.find_reference(LOCAL_HISTORY_REF) else {
+		return Ok(Vec::new());
+	};
+
+	let tree = reference.peel_to_tree()?;
+	let Some(index) = tree.get_name(INDEX_FILE) else {
+		return Ok(Vec::new());
+	};
+	let index = repo.find_blob(index.id())?;
+
+	Ok(String::from_utf8_lossy(index.content())
+		.lines()
+		.filter_map(Snapshot::from_line)
+		.collect())
+}
+
+fn write_snapshots(
+	repo: &Repository,
+	snapshots: &[Snapshot],
+) -> Result<()> {
+	if snapshots.is_empty() {
+		if let Ok(mut reference) =
+			repo.find_reference(LOCAL_HISTORY_REF)
+		{
+			reference.delete()?;
+		}
+
+		return Ok(());
+	}
+
+	let index: String =
+		snapshots.iter().map(Snapshot::to_line).collect();
+
+	let mut tree = repo.treebuilder(None)?;
+	tree.insert(
+		INDEX_FILE,
+		repo.blob(index.as_bytes())?,
+		FileMode::Blob.into(),
+	)?;
+	for snapshot in snapshots {
+		tree.insert(
+			snapshot.id.to_string(),
+			snapshot.id,
+			FileMode::Blob.into(),
+		)?;
+	}
+
+	repo.reference(
+		LOCAL_HISTORY_REF,
+		tree.write()?,
+		true,
+		"gitui: local history",
+	)?;
+
+	Ok(())
+}
+
+/// drops expired snapshots and the oldest ones of files with too
+/// many, returns whether any got dropped
+fn prune(
+	snapshots: &mut Vec<Snapshot>,
+	limits: LocalHistoryLimits,
+	now: i64,
+) -> bool {
+	let count = snapshots.len();
+	let mut per_file: HashMap<String, usize> = HashMap::new();
+
+	let mut keep: Vec<bool> = snapshots
+		.iter()
+		.rev()
+		.map(|snapshot| {
+			let kept =
+				per_file.entry(snapshot.path.clone()).or_default();
+			*kept += 1;
+
+			*kept <= limits.max_per_file
+				&& now.saturating_sub(snapshot.time) <= limits.max_age
+		})
+		.collect();
+	keep.reverse();
+
+	let mut keep = keep.into_iter();
+	snapshots.retain(|_| keep.next().unwrap_or_default());
+
+	snapshots.len() != count
+}
+
+/// adds a snapshot of `path` unless the last one has the same content
+fn add_snapshot(
+	repo: &Repository,
+	snapshots: &mut Vec<Snapshot>,
+	path: &str,
+	time: i64,
+) -> Result<bool> {
+	let file = work_dir(repo)?.join(path);
+	let Ok(meta) = fs::symlink_metadata(&file) else {
+		return Ok(false);
+	};
+
+	if !meta.is_file() || meta.len() > MAX_FILE_SIZE {
+		return Ok(false);
+	}
+
+	let id = repo.blob_path(&file)?;
+	let unchanged = snapshots
+		.iter()
+		.rev()
+		.find(|snapshot| snapshot.path == path)
+		.is_some_and(|snapshot| snapshot.id == id);
+
+	if !unchanged {
+		snapshots.push(Snapshot {
+			time,
+			path: path.to_string(),
+			id,
+		});
+	}
+
+	Ok(!unchanged)
+}
+
+fn snapshot_changed_files_at(
+	repo_path: &RepoPath,
+	limits: LocalHistoryLimits,
+	time: i64,
+) -> Result<usize> {
+	let repo = repo(repo_path)?;
+	let mut snapshots = read_snapshots(&repo)?;
+
+	let statuses = repo.statuses(Some(
+		StatusOptions::new()
+			.show(StatusShow::Workdir)
+			.include_untracked(false)
+			.include_ignored(false)
+			.exclude_submodules(true),
+	))?;
+
+	let mut added = 0;
+	for entry in statuses.iter() {
+		if entry.status().is_wt_deleted() {
+			continue;
+		}
+
+		if let Some(path) = entry.path() {
+			if add_snapshot(&repo, &mut snapshots, path, time)? {
+				added += 1;
+			}
+		}
+	}
+
+	let pruned = prune(&mut snapshots, limits, time);
+
+	if added > 0 || pruned {
+		write_snapshots(&repo, &snapshots)?;
+	}
+
+	Ok(added)
+}
+
+/// snapshots every changed tracked file whose content differs from
+/// its last snapshot, returns how many were added
+pub fn snapshot_changed_files(
+	repo_path: &RepoPath,
+	limits: LocalHistoryLimits,
+) -> Result<usize> {
+	scope_time!("snapshot_changed_files");
+
+	snapshot_changed_files_at(repo_path, limits, now())
+}
+
+/// drops snapshots beyond `limits`
+pub fn prune_local_history(
+	repo_path: &RepoPath,
+	limits: LocalHistoryLimits,
+) -> Result<()> {
+	scope_time!("prune_local_history");
+
+	let repo = repo(repo_path)?;
+	let mut snapshots = read_snapshots(&repo)?;
+
+	if prune(&mut snapshots, limits, now()) {
+		write_snapshots(&repo, &snapshots)?;
+	}
+
+	Ok(())
+}
+
+/// snapshots of `path`, most recent first
+pub fn get_local_history(
+	repo_path: &RepoPath,
+	path: &str,
+) -> Result<Vec<Snapshot>> {
+	let repo = repo(repo_path)?;
+
+	let mut snapshots = read_snapshots(&repo)?;
+	snapshots.retain(|snapshot| snapshot.path == path);
+	snapshots.reverse();
+
+	Ok(snapshots)
+}
+
+/// what changed in the workdir since `snapshot`
+pub fn get_diff_snapshot(
+	repo_path: &RepoPath,
+	snapshot: &Snapshot,
+	options: Option<DiffOptions>,
+) -> Result<FileDiff> {
+	get_diff_blob(repo_path, &snapshot.path, snapshot.id, options)
+}
+
+/// writes the content of `snapshot` to its file, the content it
+/// replaces becomes a snapshot itself
+pub fn restore_snapshot(
+	repo_path: &RepoPath,
+	snapshot: &Snapshot,
+) -> Result<()> {
+	scope_time!("restore_snapshot");
+
+	let repo = repo(repo_path)?;
+	let mut snapshots = read_snapshots(&repo)?;
+
+	if add_snapshot(&repo, &mut snapshots, &snapshot.path, now())? {
+		write_snapshots(&repo, &snapshots)?;
+	}
+
+	let file = work_dir(&repo)?.join(&snapshot.path);
+	if let Some(parent) = file.parent() {
+		fs::create_dir_all(parent)?;
+	}
+	fs::write(file, repo.find_blob(snapshot.id)?.content())?;
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		get_branches_info, get_tags,
+		tests::{repo_init, write_commit_file},
+		utils::repo_write_file,
+		LogWalker,
+	};
+	use pretty_assertions::assert_eq;
+
+	const LIMITS: LocalHistoryLimits = LocalHistoryLimits {
+		max_per_file: 2,
+		max_age: 100,
+	};
+
+	fn contents(
+		repo: &Repository,
+		snapshots: &[Snapshot],
+	) -> Vec<String> {
+		snapshots
+			.iter()
+			.map(|snapshot| {
+				String::from_utf8_lossy(
+					repo.find_blob(snapshot.id).unwrap().content(),
+				)
+				.to_string()
+			})
+			.collect()
+	}
+
+	#[test]
+	fn test_snapshots() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let head = write_commit_file(&repo, "foo.txt", "a", "first");
+		repo_write_file(&repo, "untracked.txt", "u")?;
+
+		assert_eq!(
+			snapshot_changed_files_at(repo_path, LIMITS, 10)?,
+			0
+		);
+
+		repo_write_file(&repo, "foo.txt", "b")?;
+		assert_eq!(
+			snapshot_changed_files_at(repo_path, LIMITS, 20)?,
+			1
+		);
+		// same content again
+		assert_eq!(
+			snapshot_changed_files_at(repo_path, LIMITS, 30)?,
+			0
+		);
+
+		repo_write_file(&repo, "foo.txt", "c")?;
+		assert_eq!(
+			snapshot_changed_files_at(repo_path, LIMITS, 40)?,
+			1
+		);
+
+		let history = get_local_history(repo_path, "foo.txt")?;
+		assert_eq!(contents(&repo, &history), vec!["c", "b"]);
+		assert_eq!(history[0].time, 40);
+		assert!(
+			get_local_history(repo_path, "untracked.txt")?.is_empty()
+		);
+
+		restore_snapshot(repo_path, &history[1])?;
+		assert_eq!(fs::read_to_string(root.join("foo.txt"))?, "b");
+
+		// the branch, tag and log views never see snapshots
+		let mut commits = Vec::new();
+		LogWalker::new(&repo, 10)?.read(&mut commits)?;
+		assert_eq!(commits.len(), 2);
+		assert_eq!(commits[0], head);
+		assert_eq!(get_branches_info(repo_path, true)?.len(), 1);
+		assert!(get_tags(repo_path)?.is_empty());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_snapshot_diff() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "foo.txt", "a\n", "first");
+		repo_write_file(&repo, "foo.txt", "b\n")?;
+		snapshot_changed_files_at(repo_path, LIMITS, 10)?;
+		repo_write_file(&repo, "foo.txt", "c\n")?;
+
+		let snapshot =
+			get_local_history(repo_path, "foo.txt")?.pop().unwrap();
+		let diff = get_diff_snapshot(repo_path, &snapshot, None)?;
+
+		let lines: Vec<&str> = diff.hunks[0]
+			.lines
+			.iter()
+			.map(|line| line.content.as_ref())
+			.collect();
+		assert_eq!(&lines[1..], &["b", "c"]);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_prune() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+
+		let snapshot = |time, path: &str| Snapshot {
+			time,
+			path: path.to_string(),
+			id: Oid::zero(),
+		};
+
+		let mut snapshots = vec![
+			snapshot(0, "a"),
+			snapshot(50, "a"),
+			snapshot(60, "b"),
+			snapshot(70, "a"),
+			snapshot(80, "a"),
+		];
+
+		assert!(prune(&mut snapshots, LIMITS, 150));
+		assert_eq!(
+			snapshots,
+			vec![
+				snapshot(60, "b"),
+				snapshot(70, "a"),
+				snapshot(80, "a")
+			]
+		);
+		assert!(!prune(&mut snapshots, LIMITS, 150));
+
+		// everything expired removes the ref
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+		write_commit_file(&repo, "foo.txt", "a", "first");
+		repo_write_file(&repo, "foo.txt", "b")?;
+		snapshot_changed_files_at(repo_path, LIMITS, 0)?;
+		assert!(repo.find_reference(LOCAL_HISTORY_REF).is_ok());
+
+		prune_local_history(repo_path, LIMITS)?;
+		assert!(repo.find_reference(LOCAL_HISTORY_REF).is_err());
+
+		Ok(())
+	}
+}
@@ -4,7 +4,7 @@ use git2::{Commit, Oid, Repository};
 use gix::revision::Walk;
 use std::{
 	cmp::Ordering,
-	collections::{BinaryHeap, HashSet},
+	collections::{BinaryHeap, HashMap, HashSet},
 };
 
 struct TimeOrderedCommit<'a>(Commit<'a>);
@@ -36,6 +36,7 @@ pub struct LogWalker<'a> {
 	limit: usize,
 	repo: &'a Repository,
 	filter: Option<SharedCommitFilterFn>,
+	replacements: HashMap<CommitId, CommitId>,
 }
 
 impl<'a> LogWalker<'a> {
@@ -52,6 +53,7 @@ impl<'a> LogWalker<'a> {
 			visited: HashSet::with_capacity(1000),
 			repo,
 			filter: None,
+			replacements: HashMap::new(),
 		})
 	}
 
@@ -69,16 +71,36 @@ impl<'a> LogWalker<'a> {
 		Self { filter, ..self }
 	}
 
+	/// walks the replacement in place of each replaced commit (see
+	/// `get_commit_replacements`), none by default
+	#[must_use]
+	pub fn replacements(
+		self,
+		replacements: HashMap<CommitId, CommitId>,
+	) -> Self {
+		Self {
+			replacements,
+			..self
+		}
+	}
+
 	///
 	pub fn read(&mut self, out: &mut Vec<CommitId>) -> Result<usize> {
 		let mut count = 0_usize;
 
-		while let Some(c) = self.commits.pop() {
-			for p in c.0.parents() {
+		while let Some(TimeOrderedCommit(c)) = self.commits.pop() {
+			let c = match self.replacements.get(&c.id().into()) {
+				Some(replacement) => {
+					self.repo.find_commit((*replacement).into())?
+				}
+				None => c,
+			};
+
+			for p in c.parents() {
 				self.visit(p);
 			}
 
-			let id: CommitId = c.0.id().into();
+			let id: CommitId = c.id().into();
 			let commit_should_be_included =
 				if let Some(ref filter) = self.filter {
 					filter(self.repo, &id)?
@@ -124,11 +146,13 @@ pub struct LogWalkerWithoutFilter<'a> {
 }
 
 impl<'a> LogWalkerWithoutFilter<'a> {
-	///
+	/// walks history as recorded, `LogWalker` follows replace refs
 	pub fn new(
 		repo: &'a mut gix::Repository,
 		limit: usize,
 	) -> Result<Self> {
+		repo.objects.ignore_replacements = true;
+
 		// This seems to be an object cache size that yields optimal performance. There’s no specific
 		// reason this is 2^14, so benchmarking might reveal that there’s better values.
 		repo.object_cache_size_if_unset(2_usize.pow(14));
@@ -188,7 +212,8 @@ mod tests {
 		tests::repo_init_empty,
 	};
 	use crate::sync::{
-		diff_contains_file, filter_commit_by_search, LogFilterSearch,
+		diff_contains_file, filter_commit_by_search,
+		stage_addremoved, FileFollow, LogFilterSearch,
 		LogFilterSearchOptions, RepoPath,
 	};
 	use pretty_assertions::assert_eq;
@@ -337,6 +362,54 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_logwalker_follow_renames() -> Result<()> {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: RepoPath =
+			root.as_os_str().to_str().unwrap().into();
+
+		let content = "line 1\nline 2\nline 3\nline 4\n";
+		let first_commit_id =
+			write_commit_file(&repo, "foo", content, "commit1");
+		write_commit_file(&repo, "other", "a", "commit2");
+
+		std::fs::rename(root.join("foo"), root.join("bar"))?;
+		stage_addremoved(&repo_path, Path::new("foo")).unwrap();
+		stage_add_file(&repo_path, Path::new("bar")).unwrap();
+		let rename_commit_id = commit(&repo_path, "rename").unwrap();
+
+		let last_commit_id = write_commit_file(
+			&repo,
+			"bar",
+			&format!("{content}line 5\n"),
+			"commit4",
+		);
+
+		let follow = FileFollow::new("bar".into());
+		let mut items = Vec::new();
+		let mut walker =
+			LogWalker::new(&repo, 100)?.filter(Some(follow.filter()));
+		walker.read(&mut items).unwrap();
+
+		assert_eq!(
+			items,
+			vec![last_commit_id, rename_commit_id, first_commit_id]
+		);
+		assert_eq!(follow.path_at(last_commit_id)?, "bar");
+		assert_eq!(follow.path_at(rename_commit_id)?, "bar");
+		assert_eq!(follow.path_at(first_commit_id)?, "foo");
+
+		items.clear();
+		let mut walker = LogWalker::new(&repo, 100)?
+			.filter(Some(diff_contains_file("bar".into())));
+		walker.read(&mut items).unwrap();
+
+		assert_eq!(items, vec![last_commit_id, rename_commit_id]);
+
+		Ok(())
+	}
+
 	#[test]
 	fn test_logwalker_with_filter_search() {
 		let (_td, repo) = repo_init_empty().unwrap();
@@ -2,15 +2,28 @@ use crate::{
 	error::{Error, Result},
 	sync::{
 		branch::merge_commit::commit_merge_with_head,
+		commit_revert,
 		rebase::{
-			abort_rebase, continue_rebase, get_rebase_progress,
+			abort_rebase, autostash_apply, continue_rebase,
+			get_rebase_progress, read_autostash,
 		},
 		repository::repo,
-		reset_stage, reset_workdir, CommitId,
+		rerere::rerere_clear,
+		reset_stage, reset_workdir,
+		sequencer::{
+			abort_cherry_pick, abort_todo_rebase,
+			continue_cherry_pick, continue_todo_rebase,
+			is_todo_rebase, todo_rebase_progress,
+		},
+		CommitId,
 	},
 };
-use git2::{BranchType, Commit, MergeOptions, Repository};
+use git2::{
+	BranchType, Commit, MergeOptions, Oid, Repository,
+	RepositoryState,
+};
 use scopetime::scope_time;
+use std::{fmt::Write, fs};
 
 use super::{
 	rebase::{RebaseProgress, RebaseState},
@@ -36,6 +49,7 @@ pub fn mergehead_ids(repo_path: &RepoPath) -> Result<Vec<CommitId>> {
 /// * reset all staged changes,
 /// * revert all changes in workdir
 /// * cleanup repo merge state
+/// * forget the conflicts for rerere
 pub fn abort_pending_state(repo_path: &RepoPath) -> Result<()> {
 	scope_time!("abort_pending_state");
 
@@ -45,6 +59,7 @@ pub fn abort_pending_state(repo_path: &RepoPath) -> Result<()> {
 	reset_workdir(repo_path, "*")?;
 
 	repo.cleanup_state()?;
+	rerere_clear(&repo)?;
 
 	Ok(())
 }
@@ -72,27 +87,251 @@ pub fn rebase_progress(
 
 	let repo = repo(repo_path)?;
 
-	get_rebase_progress(&repo)
+	if is_todo_rebase(&repo) {
+		todo_rebase_progress(&repo)
+	} else {
+		get_rebase_progress(&repo)
+	}
 }
 
-///
+/// the changes the rebase stashed are applied again once it is done
 pub fn continue_pending_rebase(
 	repo_path: &RepoPath,
 ) -> Result<RebaseState> {
 	scope_time!("continue_pending_rebase");
 
-	let repo = repo(repo_path)?;
+	let mut repo = repo(repo_path)?;
+	let autostash = read_autostash(&repo);
 
-	continue_rebase(&repo)
+	let state = if is_todo_rebase(&repo) {
+		continue_todo_rebase(&repo)
+	} else {
+		continue_rebase(&repo)
+	}?;
+
+	if state == RebaseState::Finished {
+		autostash_apply(&mut repo, autostash)?;
+	}
+
+	Ok(state)
 }
 
-///
+/// the changes the rebase stashed are applied again
 pub fn abort_pending_rebase(repo_path: &RepoPath) -> Result<()> {
 	scope_time!("abort_pending_rebase");
 
+	let mut repo = repo(repo_path)?;
+	let autostash = read_autostash(&repo);
+
+	if is_todo_rebase(&repo) {
+		abort_todo_rebase(&repo)?;
+	} else {
+		abort_rebase(&repo)?;
+		rerere_clear(&repo)?;
+	}
+
+	autostash_apply(&mut repo, autostash)
+}
+
+/// goes on with the merge, revert, cherry-pick or rebase that stopped
+/// on conflicts
+///
+/// once they are resolved and staged this commits with the prepared
+/// `MERGE_MSG` and applies the steps left, `Conflicted` if conflicts
+/// are left or the next step has some
+pub fn continue_pending_state(
+	repo_path: &RepoPath,
+) -> Result<RebaseState> {
+	scope_time!("continue_pending_state");
+
+	let repo = repo(repo_path)?;
+
+	match repo.state() {
+		RepositoryState::RebaseMerge
+		| RepositoryState::RebaseInteractive
+		| RepositoryState::Rebase => {
+			return continue_pending_rebase(repo_path)
+		}
+		RepositoryState::CherryPick
+		| RepositoryState::CherryPickSequence => {
+			return continue_cherry_pick(&repo)
+		}
+		RepositoryState::Merge | RepositoryState::Revert => (),
+		state => {
+			return Err(Error::Generic(format!(
+				"can\u{2019}t continue {state:?}"
+			)))
+		}
+	}
+
+	if repo.index()?.has_conflicts() {
+		return Ok(RebaseState::Conflicted);
+	}
+
+	let msg = git2::message_prettify(repo.message()?, Some(b'#'))?;
+
+	if repo.state() == RepositoryState::Merge {
+		merge_commit(repo_path, &msg, &mergehead_ids(repo_path)?)?;
+	} else {
+		commit_revert(repo_path, &msg)?;
+	}
+
+	Ok(RebaseState::Finished)
+}
+
+/// drops the pending cherry-pick, a `git cherry-pick` sequence goes
+/// back to where it started
+pub fn abort_pending_cherry_pick(repo_path: &RepoPath) -> Result<()> {
+	scope_time!("abort_pending_cherry_pick");
+
+	let repo = repo(repo_path)?;
+
+	abort_cherry_pick(&repo)
+}
+
+/// message of a squash merge for the next commit, `git merge
+/// --squash` writes it there too
+const SQUASH_MSG: &str = "SQUASH_MSG";
+
+/// how [`merge_branch_with_mode`] merges
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MergeMode {
+	/// only moves the branch if possible, merges otherwise
+	FastForward,
+	/// always ends in a merge commit (`--no-ff`)
+	NoFastForward,
+	/// stages the changes for a regular commit (`--squash`)
+	Squash,
+}
+
+/// what [`merge_branch_with_mode`] did
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MergeOutcome {
+	/// the branch is merged already
+	UpToDate,
+	/// `HEAD` was moved to the branch
+	FastForwarded,
+	/// index and workdir are merged, the merge commit with
+	/// `MERGE_MSG` is left to the user
+	Merged,
+	/// stopped on conflicts
+	Conflicted,
+	/// the changes are staged, the commit with `SQUASH_MSG` is left
+	/// to the user
+	Squashed,
+}
+
+/// merges `branch` into `HEAD` like `git merge` does with the
+/// options of `mode`
+pub fn merge_branch_with_mode(
+	repo_path: &RepoPath,
+	branch: &str,
+	branch_type: BranchType,
+	mode: MergeMode,
+) -> Result<MergeOutcome> {
+	scope_time!("merge_branch_with_mode");
+
+	let repo = repo(repo_path)?;
+	let reference =
+		repo.find_branch(branch, branch_type)?.into_reference();
+	let annotated = repo.reference_to_annotated_commit(&reference)?;
+
+	let (analysis, _) = repo.merge_analysis(&[&annotated])?;
+
+	if analysis.is_up_to_date() {
+		return Ok(MergeOutcome::UpToDate);
+	}
+
+	//TODO: support merge on unborn
+	if analysis.is_unborn() {
+		return Err(Error::Generic("head is unborn".into()));
+	}
+
+	if mode == MergeMode::FastForward && analysis.is_fast_forward() {
+		let commit = repo.find_commit(annotated.id())?;
+		repo.checkout_tree(commit.as_object(), None)?;
+		repo.head()?.set_target(
+			annotated.id(),
+			&format!("merge {branch}: Fast-forward"),
+		)?;
+
+		return Ok(MergeOutcome::FastForwarded);
+	}
+
+	repo.merge(&[&annotated], None, None)?;
+
+	let conflicted = repo.index()?.has_conflicts();
+
+	if mode == MergeMode::Squash {
+		let msg = squash_message(&repo, annotated.id())?;
+
+		// a squash leaves no merge in progress
+		repo.cleanup_state()?;
+		fs::write(repo.path().join(SQUASH_MSG), msg)?;
+
+		return Ok(if conflicted {
+			MergeOutcome::Conflicted
+		} else {
+			MergeOutcome::Squashed
+		});
+	}
+
+	Ok(if conflicted {
+		MergeOutcome::Conflicted
+	} else {
+		MergeOutcome::Merged
+	})
+}
+
+/// lists the commits squashed like `git merge --squash` does
+fn squash_message(repo: &Repository, merged: Oid) -> Result<String> {
+	let mut walk = repo.revwalk()?;
+	walk.push(merged)?;
+	walk.hide_head()?;
+
+	let mut msg = String::from("Squashed commit of the following:\n");
+
+	for id in walk {
+		let commit = repo.find_commit(id?)?;
+		let author = commit.author();
+
+		let _ = write!(
+			msg,
+			"\ncommit {}\nAuthor: {} <{}>\n\n",
+			commit.id(),
+			author.name().unwrap_or_default(),
+			author.email().unwrap_or_default(),
+		);
+
+		for line in commit.message().unwrap_or_default().lines() {
+			let _ = writeln!(msg, "    {line}");
+		}
+	}
+
+	Ok(msg)
+}
+
+/// the message prepared by a squash merge, if there was one since
+/// the last commit
+pub fn squash_msg(repo_path: &RepoPath) -> Result<Option<String>> {
 	let repo = repo(repo_path)?;
+	let path = repo.path().join(SQUASH_MSG);
 
-	abort_rebase(&repo)
+	Ok(path
+		.exists()
+		.then(|| fs::read_to_string(path))
+		.transpose()?)
+}
+
+/// a commit uses up the prepared squash message
+pub fn remove_squash_msg(repo: &Repository) -> Result<()> {
+	let path = repo.path().join(SQUASH_MSG);
+
+	if path.exists() {
+		fs::remove_file(path)?;
+	}
+
+	Ok(())
 }
 
 ///
@@ -155,9 +394,10 @@ pub fn merge_commit(
 mod tests {
 	use super::*;
 	use crate::sync::{
-		create_branch,
+		checkout_branch, commit, create_branch, repo_state,
 		tests::{repo_init, write_commit_file},
-		RepoPath,
+		utils::get_head_repo,
+		RepoPath, RepoState,
 	};
 	use pretty_assertions::assert_eq;
 
@@ -185,4 +425,101 @@ mod tests {
 
 		assert_eq!(mergeheads[0], c1);
 	}
+
+	/// `foo` is one commit ahead of `master`
+	fn repo_with_branch_ahead(
+	) -> (tempfile::TempDir, Repository, RepoPath, CommitId) {
+		let (td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: RepoPath =
+			root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "test.txt", "test", "commit1");
+		create_branch(&repo_path, "foo").unwrap();
+		let ahead =
+			write_commit_file(&repo, "test.txt", "test2", "commit2");
+		checkout_branch(&repo_path, "master").unwrap();
+
+		(td, repo, repo_path, ahead)
+	}
+
+	#[test]
+	fn test_merge_fast_forward() {
+		let (_td, repo, repo_path, ahead) = repo_with_branch_ahead();
+
+		assert_eq!(
+			merge_branch_with_mode(
+				&repo_path,
+				"foo",
+				BranchType::Local,
+				MergeMode::FastForward
+			)
+			.unwrap(),
+			MergeOutcome::FastForwarded
+		);
+		assert_eq!(get_head_repo(&repo).unwrap(), ahead);
+		assert_eq!(repo_state(&repo_path).unwrap(), RepoState::Clean);
+
+		assert_eq!(
+			merge_branch_with_mode(
+				&repo_path,
+				"foo",
+				BranchType::Local,
+				MergeMode::NoFastForward
+			)
+			.unwrap(),
+			MergeOutcome::UpToDate
+		);
+	}
+
+	#[test]
+	fn test_merge_no_fast_forward() {
+		let (_td, repo, repo_path, ahead) = repo_with_branch_ahead();
+		let head = get_head_repo(&repo).unwrap();
+
+		assert_eq!(
+			merge_branch_with_mode(
+				&repo_path,
+				"foo",
+				BranchType::Local,
+				MergeMode::NoFastForward
+			)
+			.unwrap(),
+			MergeOutcome::Merged
+		);
+		assert_eq!(get_head_repo(&repo).unwrap(), head);
+		assert_eq!(repo_state(&repo_path).unwrap(), RepoState::Merge);
+		assert_eq!(mergehead_ids(&repo_path).unwrap(), vec![ahead]);
+		assert!(merge_msg(&repo_path).unwrap().starts_with("Merge"));
+	}
+
+	#[test]
+	fn test_merge_squash() {
+		let (_td, repo, repo_path, _) = repo_with_branch_ahead();
+		let head = get_head_repo(&repo).unwrap();
+
+		assert_eq!(
+			merge_branch_with_mode(
+				&repo_path,
+				"foo",
+				BranchType::Local,
+				MergeMode::Squash
+			)
+			.unwrap(),
+			MergeOutcome::Squashed
+		);
+		assert_eq!(get_head_repo(&repo).unwrap(), head);
+		assert_eq!(repo_state(&repo_path).unwrap(), RepoState::Clean);
+
+		let msg = squash_msg(&repo_path).unwrap().unwrap();
+		assert!(msg.starts_with("Squashed commit of the following:"));
+		assert!(msg.contains("    commit2"));
+
+		let id = commit(&repo_path, &msg).unwrap();
+		assert_eq!(
+			repo.find_commit(id.into()).unwrap().parent_count(),
+			1
+		);
+		assert_eq!(squash_msg(&repo_path).unwrap(), None);
+	}
 }
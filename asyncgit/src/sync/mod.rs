@@ -3,7 +3,10 @@
 //TODO: remove once we have this activated on the toplevel
 #![deny(clippy::expect_used)]
 
+mod binary;
+mod bisect;
 pub mod blame;
+mod bookmarks;
 pub mod branch;
 pub mod commit;
 mod commit_details;
@@ -12,20 +15,40 @@ mod commit_filter;
 mod commit_revert;
 mod commits_info;
 mod config;
+mod conflict;
+mod conflict_markers;
 pub mod cred;
 pub mod diff;
+mod file_mode;
+mod fix_staged;
+mod fixup;
+mod forge;
+mod grep;
 mod hooks;
 mod hunks;
 mod ignore;
+mod index_file;
+mod lfs;
+mod local_history;
 mod logwalker;
 mod merge;
+mod oplog;
+mod park;
+mod partial_clone;
 mod patches;
 mod rebase;
+mod reflog;
 pub mod remotes;
+mod replace_refs;
 mod repository;
+mod rerere;
 mod reset;
 mod reword;
+mod sequencer;
+mod shallow;
 pub mod sign;
+mod stage_cleanup;
+mod stage_paths;
 mod staging;
 mod stash;
 mod state;
@@ -34,28 +57,46 @@ mod submodules;
 mod tags;
 mod tree;
 pub mod utils;
+mod worktrees;
 
-pub use blame::{blame_file, BlameHunk, FileBlame};
+pub use binary::{ImageFormat, ImageSize};
+pub use bisect::{
+	bisect_mark, bisect_reset, bisect_state, BisectMark, BisectState,
+};
+pub use blame::{blame_file, blame_file_check, BlameHunk, FileBlame};
+pub use bookmarks::{
+	bookmark_commit, bookmark_reachable, delete_bookmark,
+	find_commit_by_patch_id, get_bookmarks, Bookmark,
+};
 pub use branch::{
-	branch_compare_upstream, checkout_branch, checkout_commit,
-	config_is_pull_rebase, create_branch, delete_branch,
-	get_branch_remote, get_branch_upstream_merge, get_branches_info,
+	branch_compare_push, branch_compare_upstream, checkout_branch,
+	checkout_commit,
+	compare::{compare_branch_with_head, BranchComparison},
+	config_is_pull_rebase, create_branch, create_branch_at,
+	delete_branch, get_branch_remote, get_branch_remotes,
+	get_branch_upstream_merge, get_branches_info,
+	get_remote_branch_parts,
 	merge_commit::merge_upstream_commit,
 	merge_ff::branch_merge_upstream_fastforward,
-	merge_rebase::merge_upstream_rebase, rename::rename_branch,
+	merge_rebase::{merge_upstream_rebase, pull_rebase},
+	rename::rename_branch,
 	validate_branch_name, BranchCompare, BranchDetails, BranchInfo,
+	BranchRemotes,
 };
 pub use commit::{amend, commit, tag_commit};
 pub use commit_details::{
 	get_commit_details, CommitDetails, CommitMessage, CommitSignature,
 };
-pub use commit_files::get_commit_files;
+pub use commit_files::{get_commit_files, get_trees_files};
 pub use commit_filter::{
-	diff_contains_file, filter_commit_by_search, LogFilterSearch,
-	LogFilterSearchOptions, SearchFields, SearchOptions,
-	SharedCommitFilterFn,
+	diff_contains_file, filter_commit_by_criteria,
+	filter_commit_by_search, FileFollow, LogFilterCriteria,
+	LogFilterSearch, LogFilterSearchOptions, SearchFields,
+	SearchOptions, SharedCommitFilterFn,
+};
+pub use commit_revert::{
+	commit_revert, is_merge_commit, revert_commit, revert_head,
 };
-pub use commit_revert::{commit_revert, revert_commit, revert_head};
 pub use commits_info::{
 	get_commit_info, get_commits_info, CommitId, CommitInfo,
 };
@@ -63,49 +104,134 @@ pub use config::{
 	get_config_string, untracked_files_config,
 	ShowUntrackedFilesConfig,
 };
+pub use conflict::{
+	conflict_sides, get_conflict, get_conflicts, resolve_conflict,
+	resolve_conflicts_with_side, Conflict, ConflictResolution,
+	ConflictSide, ConflictSides, ConflictType, RenameConflict,
+};
+pub use conflict_markers::{
+	read_conflicted_file, write_merged_file, BlockResolution,
+	ConflictBlock, ConflictText,
+};
 pub use diff::get_diff_commit;
+pub use file_mode::{FileMode, ModeChange};
+pub use fix_staged::{
+	fix_staged_command, fix_staged_files, fixed_files, spawn_fixer,
+	FixStagedFiles, FIX_STAGED_BATCH_SIZE, FIX_STAGED_CONFIG,
+	FIX_STAGED_PLACEHOLDER,
+};
+pub use fixup::{
+	amend_older_commit, amend_plan, commit_fixup, fixup_targets,
+	AmendPlan, FixupCandidate, FixupKind, FixupTargets,
+};
+pub use forge::{
+	pull_request_cli, pull_request_message, pull_request_target,
+	spawn_create_pull_request, Forge, ForgeRemote, PullRequestTarget,
+};
 pub use git2::BranchType;
+pub use grep::{grep_tree, GrepHit, GrepQuery};
 pub use hooks::{
-	hooks_commit_msg, hooks_post_commit, hooks_pre_commit,
-	hooks_prepare_commit_msg, HookResult, PrepareCommitMsgSource,
+	hook_commit_msg, hooks_commit_msg, hooks_post_commit,
+	hooks_pre_commit, hooks_prepare_commit_msg, spawn_commit_msg_hook,
+	spawn_post_commit_hook, spawn_pre_commit_hook,
+	spawn_prepare_commit_msg_hook, HookResult, PrepareCommitMsgSource,
 };
 pub use hunks::{reset_hunk, stage_hunk, unstage_hunk};
-pub use ignore::add_to_ignore;
+pub use ignore::{add_to_ignore, ignore_pattern, IgnoreKind};
+pub use index_file::{index_info, IndexInfo};
+pub use lfs::{
+	lfs_installed, parse_lfs_pointer, spawn_lfs_smudge, LfsDiff,
+	LfsPointer, LfsSide,
+};
+pub use local_history::{
+	get_diff_snapshot, get_local_history, prune_local_history,
+	restore_snapshot, snapshot_changed_files, LocalHistoryLimits,
+	Snapshot,
+};
 pub use logwalker::{LogWalker, LogWalkerWithoutFilter};
 pub use merge::{
-	abort_pending_rebase, abort_pending_state,
-	continue_pending_rebase, merge_branch, merge_commit, merge_msg,
-	mergehead_ids, rebase_progress,
+	abort_pending_cherry_pick, abort_pending_rebase,
+	abort_pending_state, continue_pending_rebase,
+	continue_pending_state, merge_branch, merge_branch_with_mode,
+	merge_commit, merge_msg, mergehead_ids, rebase_progress,
+	squash_msg, MergeMode, MergeOutcome,
 };
-pub use rebase::rebase_branch;
+pub use oplog::{get_oplog, OpKind, OpLogEntry};
+pub use park::{
+	get_parked, park_and_switch, return_to_parked, ParkReturn,
+	ParkedChanges,
+};
+pub use partial_clone::{
+	missing_objects, promisor_remote, spawn_fetch_objects,
+};
+pub use rebase::{autosquash, rebase_branch, RebaseState};
+pub use reflog::{get_reflog, ReflogEntry};
 pub use remotes::{
-	add_remote, delete_remote, get_default_remote,
+	add_remote, delete_remote, get_branch_push_remote,
+	get_branch_push_remote_config, get_default_remote,
 	get_default_remote_for_fetch, get_default_remote_for_push,
-	get_remote_url, get_remotes, push::AsyncProgress, rename_remote,
-	tags::PushTagsProgress, update_remote_url, validate_remote_name,
+	get_remote_defaults, get_remote_push_url, get_remote_url,
+	get_remotes, last_fetch_time,
+	push::{push_lease, AsyncProgress},
+	rename_remote, set_branch_push_remote, set_default_remote,
+	tags::PushTagsProgress,
+	update_remote_url, validate_remote_name, validate_remote_url,
+	RemoteDefaults,
+};
+pub use replace_refs::{
+	delete_replace_ref, get_commit_replacements, get_replace_refs,
+	replace_refs_enabled, ReplaceRef,
 };
 pub(crate) use repository::{gix_repo, repo};
 pub use repository::{RepoPath, RepoPathRef};
-pub use reset::{reset_repo, reset_stage, reset_workdir};
-pub use reword::reword;
+pub use rerere::{
+	rerere, rerere_enabled, rerere_forget, rerere_has_resolution,
+	rerere_record, RerereResult,
+};
+pub use reset::{
+	reset_repo, reset_stage, reset_stage_paths, reset_workdir,
+};
+pub use reword::{reword, reword_check};
+pub use sequencer::{
+	cherry_pick, cherry_pick_head, edit_commit, CherryPickOutcome,
+};
+pub use shallow::{shallow_commits, spawn_deepen_history, Deepen};
+pub use stage_cleanup::{
+	stage_cleanup_config, stage_cleanup_preview, StageCleanup,
+	StageCleanupPreview, STAGE_FINAL_NEWLINE_CONFIG,
+	STAGE_STRIP_WHITESPACE_CONFIG,
+};
+pub use stage_paths::{stage_all_paths, StagePathsResult};
 pub use staging::{discard_lines, stage_lines};
 pub use stash::{
-	get_stashes, stash_apply, stash_drop, stash_pop, stash_save,
+	get_stashes, stash_apply, stash_apply_file, stash_branch,
+	stash_drop, stash_pop, stash_save, stash_save_paths,
+	StashFileApply,
 };
 pub use state::{repo_state, RepoState};
 pub use status::is_workdir_clean;
 pub use submodules::{
-	get_submodules, submodule_parent_info, update_submodule,
-	SubmoduleInfo, SubmoduleParentInfo, SubmoduleStatus,
+	get_submodules, init_submodule, submodule_parent_info,
+	update_submodule, SubmoduleChanges, SubmoduleDiff, SubmoduleInfo,
+	SubmoduleParentInfo, SubmoduleStatus,
 };
 pub use tags::{
 	delete_tag, get_tags, get_tags_with_metadata, CommitTags, Tag,
-	TagWithMetadata, Tags,
+	TagDetails, TagWithMetadata, Tags,
+};
+pub use tree::{
+	tree_file_content, tree_file_write, tree_files,
+	tree_files_with_progress, TreeFile, TreeFileContent,
 };
-pub use tree::{tree_file_content, tree_files, TreeFile};
 pub use utils::{
-	get_head, get_head_tuple, repo_dir, repo_open_error,
-	stage_add_all, stage_add_file, stage_addremoved, Head,
+	get_head, get_head_tuple, is_commit_pushed, repo_dir,
+	repo_open_error, repo_watch_paths, stage_add_all, stage_add_file,
+	stage_add_folder, stage_addremoved, undo_last_commit_check, Head,
+	RepoWatchPaths,
+};
+pub use worktrees::{
+	create_worktree, get_worktrees, is_valid_worktree_path,
+	prune_worktrees, worktree_of_branch, WorktreeInfo,
 };
 
 pub use git2::ResetType;
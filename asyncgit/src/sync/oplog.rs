@@ -0,0 +1,338 @@
+//! append-only log of the mutating operations gitui ran on a repo
+
+use super::{utils::gitui_dir, CommitId, RepoPath};
+use crate::error::Result;
+use std::{
+	fs::{self, File, OpenOptions},
+	io::{BufRead, BufReader, Write},
+	path::Path,
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+const OPLOG_FILE: &str = "oplog";
+const OPLOG_FILE_ROTATED: &str = "oplog.1";
+/// once the log grows past this it is rotated (one old file is kept)
+const OPLOG_MAX_SIZE: u64 = 256 * 1024;
+
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpKind {
+	///
+	Commit,
+	///
+	Reset,
+	///
+	DeleteBranch,
+	///
+	ForcePush,
+	///
+	StashDrop,
+}
+
+impl OpKind {
+	///
+	pub const ALL: [Self; 5] = [
+		Self::Commit,
+		Self::Reset,
+		Self::DeleteBranch,
+		Self::ForcePush,
+		Self::StashDrop,
+	];
+
+	///
+	pub const fn name(self) -> &'static str {
+		match self {
+			Self::Commit => "commit",
+			Self::Reset => "reset",
+			Self::DeleteBranch => "delete-branch",
+			Self::ForcePush => "force-push",
+			Self::StashDrop => "stash-drop",
+		}
+	}
+
+	fn from_name(name: &str) -> Option<Self> {
+		Self::ALL.into_iter().find(|kind| kind.name() == name)
+	}
+}
+
+/// one recorded operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpLogEntry {
+	/// unix time in seconds
+	pub time: i64,
+	///
+	pub kind: OpKind,
+	/// affected ref or short description (e.g. `HEAD (hard)`)
+	pub target: String,
+	/// what `target` pointed to before
+	pub old_id: Option<CommitId>,
+	/// what `target` points to after
+	pub new_id: Option<CommitId>,
+	/// `None` if the operation succeeded
+	pub error: Option<String>,
+}
+
+impl OpLogEntry {
+	fn to_line(&self) -> String {
+		let id = |id: Option<CommitId>| {
+			id.map_or_else(|| String::from("-"), |id| id.to_string())
+		};
+
+		format!(
+			"{}\t{}\t{}\t{}\t{}\t{}\n",
+			self.time,
+			self.kind.name(),
+			sanitize(&self.target),
+			id(self.old_id),
+			id(self.new_id),
+			self.error.as_ref().map_or_else(
+				|| String::from("ok"),
+				|error| format!("error: {}", sanitize(error))
+			),
+		)
+	}
+
+	fn from_line(line: &str) -> Option<Self> {
+		let id = |id: &str| -> Option<Option<CommitId>> {
+			if id == "-" {
+				Some(None)
+			} else {
+				CommitId::from_str_unchecked(id).ok().map(Some)
+			}
+		};
+
+		let mut fields = line.splitn(6, '\t');
+
+		let time = fields.next()?.parse().ok()?;
+		let kind = OpKind::from_name(fields.next()?)?;
+		let target = fields.next()?.to_string();
+		let old_id = id(fields.next()?)?;
+		let new_id = id(fields.next()?)?;
+		let outcome = fields.next()?;
+		let error = if outcome == "ok" {
+			None
+		} else {
+			Some(
+				outcome
+					.strip_prefix("error: ")
+					.unwrap_or(outcome)
+					.to_string(),
+			)
+		};
+
+		Some(Self {
+			time,
+			kind,
+			target,
+			old_id,
+			new_id,
+			error,
+		})
+	}
+}
+
+fn sanitize(text: &str) -> String {
+	text.replace(['\t', '\n', '\r'], " ")
+}
+
+/// appends an entry for `kind` with the outcome of `result`, a
+/// failure to write the log is logged but never fails the operation
+pub fn oplog_record<T>(
+	repo_path: &RepoPath,
+	kind: OpKind,
+	target: &str,
+	old_id: Option<CommitId>,
+	new_id: Option<CommitId>,
+	result: &Result<T>,
+) {
+	let time = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.ok()
+		.and_then(|time| i64::try_from(time.as_secs()).ok())
+		.unwrap_or_default();
+
+	let entry = OpLogEntry {
+		time,
+		kind,
+		target: target.to_string(),
+		old_id,
+		new_id: result.as_ref().ok().and(new_id),
+		error: result.as_ref().err().map(ToString::to_string),
+	};
+
+	if let Err(e) = oplog_append(repo_path, &entry) {
+		log::error!("oplog append error: {e}");
+	}
+}
+
+fn oplog_append(
+	repo_path: &RepoPath,
+	entry: &OpLogEntry,
+) -> Result<()> {
+	let dir = gitui_dir(repo_path)?;
+	fs::create_dir_all(&dir)?;
+
+	let path = dir.join(OPLOG_FILE);
+
+	if fs::metadata(&path)
+		.is_ok_and(|meta| meta.len() >= OPLOG_MAX_SIZE)
+	{
+		fs::rename(&path, dir.join(OPLOG_FILE_ROTATED))?;
+	}
+
+	let mut file =
+		OpenOptions::new().create(true).append(true).open(path)?;
+	file.write_all(entry.to_line().as_bytes())?;
+
+	Ok(())
+}
+
+fn read_entries(
+	path: &Path,
+	out: &mut Vec<OpLogEntry>,
+) -> Result<()> {
+	let Ok(file) = File::open(path) else {
+		return Ok(());
+	};
+
+	for line in BufReader::new(file).lines() {
+		if let Some(entry) = OpLogEntry::from_line(&line?) {
+			out.push(entry);
+		}
+	}
+
+	Ok(())
+}
+
+/// all recorded operations, most recent first
+pub fn get_oplog(repo_path: &RepoPath) -> Result<Vec<OpLogEntry>> {
+	let dir = gitui_dir(repo_path)?;
+
+	let mut entries = Vec::new();
+	read_entries(&dir.join(OPLOG_FILE_ROTATED), &mut entries)?;
+	read_entries(&dir.join(OPLOG_FILE), &mut entries)?;
+	entries.reverse();
+
+	Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		error::Error,
+		sync::{
+			commit, delete_branch, reset_repo, stash_drop,
+			stash_save,
+			tests::{repo_init, write_commit_file},
+			utils::repo_write_file,
+			ResetType,
+		},
+	};
+
+	#[test]
+	fn test_oplog_records_operations() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let first = write_commit_file(&repo, "foo", "a", "first");
+		let second = write_commit_file(&repo, "foo", "b", "second");
+
+		reset_repo(repo_path, first, ResetType::Hard)?;
+
+		repo_write_file(&repo, "foo", "c")?;
+		let stash = stash_save(repo_path, None, false, false)?;
+		stash_drop(repo_path, stash)?;
+
+		assert!(
+			delete_branch(repo_path, "refs/heads/missing").is_err()
+		);
+
+		let log = get_oplog(repo_path)?;
+		let kinds: Vec<_> = log.iter().map(|e| e.kind).collect();
+
+		assert_eq!(
+			kinds,
+			vec![
+				OpKind::DeleteBranch,
+				OpKind::StashDrop,
+				OpKind::Reset,
+				OpKind::Commit,
+				OpKind::Commit,
+			]
+		);
+		assert!(log[0].error.is_some());
+		assert_eq!(log[1].old_id, Some(stash));
+		assert_eq!(log[2].old_id, Some(second));
+		assert_eq!(log[2].new_id, Some(first));
+		assert_eq!(log[3].new_id, Some(second));
+		assert_eq!(log[4].new_id, Some(first));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_oplog_failed_commit() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let result: Result<()> =
+			Err(Error::Generic(String::from("a\tb\nc")));
+		oplog_record(
+			repo_path,
+			OpKind::Commit,
+			"HEAD",
+			None,
+			Some(CommitId::default()),
+			&result,
+		);
+		commit(repo_path, "empty")?;
+
+		let log = get_oplog(repo_path)?;
+
+		assert_eq!(log.len(), 2);
+		assert_eq!(log[1].error.as_deref(), Some("`a b c`"));
+		assert_eq!(log[1].new_id, None);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_oplog_rotation() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let dir = gitui_dir(repo_path)?;
+		fs::create_dir_all(&dir)?;
+		let old = OpLogEntry {
+			time: 1,
+			kind: OpKind::Reset,
+			target: String::from("HEAD"),
+			old_id: None,
+			new_id: None,
+			error: None,
+		};
+		let line = old.to_line();
+		let count =
+			usize::try_from(OPLOG_MAX_SIZE).unwrap() / line.len() + 1;
+		fs::write(dir.join(OPLOG_FILE), line.repeat(count))?;
+
+		commit(repo_path, "after rotation")?;
+
+		assert!(dir.join(OPLOG_FILE_ROTATED).exists());
+
+		let log = get_oplog(repo_path)?;
+
+		assert_eq!(log.len(), count + 1);
+		assert_eq!(log[0].kind, OpKind::Commit);
+		assert_eq!(log[1], old);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,292 @@
+//! uncommitted changes parked in a stash to switch to another branch
+//! and brought back when returning, the pairing is kept per repo
+//! inside the git dir so it survives restarts
+
+use super::{
+	branch::{checkout_branch, get_branch_name_repo},
+	repository::repo,
+	stash::{
+		get_stashes, stash_apply, stash_drop, stash_pop, stash_save,
+	},
+	utils::gitui_dir,
+	CommitId, RepoPath,
+};
+use crate::error::{Error, Result};
+use scopetime::scope_time;
+use std::fs;
+
+const PARKED_FILE: &str = "parked";
+
+/// changes stashed on `branch` to switch to `target`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParkedChanges {
+	///
+	pub stash: CommitId,
+	/// the branch to return to
+	pub branch: String,
+	///
+	pub target: String,
+}
+
+impl ParkedChanges {
+	fn to_line(&self) -> String {
+		format!("{}\t{}\t{}\n", self.stash, self.branch, self.target)
+	}
+
+	fn from_line(line: &str) -> Option<Self> {
+		let mut parts = line.split('\t');
+		let stash =
+			CommitId::from_str_unchecked(parts.next()?).ok()?;
+
+		Some(Self {
+			stash,
+			branch: parts.next()?.to_string(),
+			target: parts.next()?.to_string(),
+		})
+	}
+}
+
+/// what returning to a parked branch did with its changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParkReturn {
+	/// the stash was popped
+	Restored,
+	/// applying the stash conflicts, the conflicts are in the index
+	/// and the stash is kept
+	Conflicted,
+	/// the stash was dropped meanwhile, only the branch was switched
+	StashGone,
+}
+
+/// parked changes, the latest last
+pub fn get_parked(
+	repo_path: &RepoPath,
+) -> Result<Vec<ParkedChanges>> {
+	let path = gitui_dir(repo_path)?.join(PARKED_FILE);
+
+	let Ok(content) = fs::read_to_string(path) else {
+		return Ok(Vec::new());
+	};
+
+	Ok(content
+		.lines()
+		.filter_map(ParkedChanges::from_line)
+		.collect())
+}
+
+fn write_parked(
+	repo_path: &RepoPath,
+	parked: &[ParkedChanges],
+) -> Result<()> {
+	let dir = gitui_dir(repo_path)?;
+	fs::create_dir_all(&dir)?;
+
+	let content: String =
+		parked.iter().map(ParkedChanges::to_line).collect();
+	fs::write(dir.join(PARKED_FILE), content)?;
+
+	Ok(())
+}
+
+/// stashes the uncommitted changes (untracked files too) and checks
+/// out `target`, returns `None` if there was nothing to park
+pub fn park_and_switch(
+	repo_path: &RepoPath,
+	target: &str,
+) -> Result<Option<ParkedChanges>> {
+	scope_time!("park_and_switch");
+
+	let branch = get_branch_name_repo(&repo(repo_path)?)?;
+	if branch == target {
+		return Err(Error::Generic(format!(
+			"'{target}' is checked out already"
+		)));
+	}
+
+	if super::status::is_workdir_clean(repo_path, None)? {
+		checkout_branch(repo_path, target)?;
+		return Ok(None);
+	}
+
+	let stash = stash_save(
+		repo_path,
+		Some(&format!("parked to switch to {target}")),
+		true,
+		false,
+	)?;
+
+	if let Err(e) = checkout_branch(repo_path, target) {
+		stash_pop(repo_path, stash)?;
+		return Err(e);
+	}
+
+	let parked = ParkedChanges {
+		stash,
+		branch,
+		target: target.to_string(),
+	};
+
+	let mut all = get_parked(repo_path)?;
+	all.push(parked.clone());
+	write_parked(repo_path, &all)?;
+
+	Ok(Some(parked))
+}
+
+/// checks out the branch of the latest parked changes and pops their
+/// stash, the pairing is forgotten once the branch is checked out
+pub fn return_to_parked(
+	repo_path: &RepoPath,
+) -> Result<(ParkedChanges, ParkReturn)> {
+	scope_time!("return_to_parked");
+
+	let mut all = get_parked(repo_path)?;
+	let parked = all.pop().ok_or_else(|| {
+		Error::Generic(String::from("no changes are parked"))
+	})?;
+
+	if let Err(e) = checkout_branch(repo_path, &parked.branch) {
+		if repo(repo_path)?
+			.find_branch(&parked.branch, git2::BranchType::Local)
+			.is_err()
+		{
+			write_parked(repo_path, &all)?;
+			return Err(Error::Generic(format!(
+				"branch '{}' is gone, the parked changes stay in the stash list",
+				parked.branch
+			)));
+		}
+
+		return Err(e);
+	}
+
+	write_parked(repo_path, &all)?;
+
+	if !get_stashes(repo_path)?.contains(&parked.stash) {
+		return Ok((parked, ParkReturn::StashGone));
+	}
+
+	stash_apply(repo_path, parked.stash, true).map_err(|e| {
+		Error::Generic(format!(
+			"applying the parked changes failed, they stay in the stash list: {e}"
+		))
+	})?;
+
+	// like `git stash pop` a conflicting stash is not dropped
+	let result = if repo(repo_path)?.index()?.has_conflicts() {
+		ParkReturn::Conflicted
+	} else {
+		stash_drop(repo_path, parked.stash)?;
+		ParkReturn::Restored
+	};
+
+	Ok((parked, result))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::{repo_init, write_commit_file};
+	use git2::Repository;
+	use std::path::Path;
+
+	fn setup() -> (tempfile::TempDir, Repository, RepoPath) {
+		let (td, repo) = repo_init().unwrap();
+		let repo_path: RepoPath = td.path().to_str().unwrap().into();
+
+		write_commit_file(&repo, "file", "base", "base");
+		{
+			let head = repo.head().unwrap().peel_to_commit().unwrap();
+			repo.branch("hotfix", &head, false).unwrap();
+		}
+
+		(td, repo, repo_path)
+	}
+
+	fn read(root: &Path, file: &str) -> String {
+		fs::read_to_string(root.join(file)).unwrap()
+	}
+
+	#[test]
+	fn test_park_and_return() {
+		let (td, repo, repo_path) = setup();
+		let root = td.path();
+
+		fs::write(root.join("file"), "wip").unwrap();
+		fs::write(root.join("new"), "untracked").unwrap();
+
+		let parked =
+			park_and_switch(&repo_path, "hotfix").unwrap().unwrap();
+		assert_eq!(parked.branch, "master");
+		assert_eq!(read(root, "file"), "base");
+		assert!(!root.join("new").exists());
+		assert_eq!(
+			get_branch_name_repo(&repo).unwrap(),
+			String::from("hotfix")
+		);
+		assert_eq!(get_parked(&repo_path).unwrap(), vec![parked]);
+
+		let (returned, result) =
+			return_to_parked(&repo_path).unwrap();
+		assert_eq!(returned.target, "hotfix");
+		assert_eq!(result, ParkReturn::Restored);
+		assert_eq!(read(root, "file"), "wip");
+		assert_eq!(read(root, "new"), "untracked");
+		assert!(get_stashes(&repo_path).unwrap().is_empty());
+		assert!(get_parked(&repo_path).unwrap().is_empty());
+		assert!(return_to_parked(&repo_path).is_err());
+	}
+
+	#[test]
+	fn test_return_without_stash() {
+		let (td, repo, repo_path) = setup();
+
+		fs::write(td.path().join("file"), "wip").unwrap();
+		let parked =
+			park_and_switch(&repo_path, "hotfix").unwrap().unwrap();
+		stash_drop(&repo_path, parked.stash).unwrap();
+
+		assert_eq!(
+			return_to_parked(&repo_path).unwrap().1,
+			ParkReturn::StashGone
+		);
+		assert_eq!(
+			get_branch_name_repo(&repo).unwrap(),
+			String::from("master")
+		);
+		assert!(get_parked(&repo_path).unwrap().is_empty());
+	}
+
+	#[test]
+	fn test_return_conflicted() {
+		let (td, repo, repo_path) = setup();
+		let root = td.path();
+
+		fs::write(root.join("file"), "wip").unwrap();
+		park_and_switch(&repo_path, "hotfix").unwrap().unwrap();
+
+		// the branch moved on meanwhile
+		repo.set_head("refs/heads/master").unwrap();
+		repo.checkout_head(Some(
+			git2::build::CheckoutBuilder::new().force(),
+		))
+		.unwrap();
+		write_commit_file(&repo, "file", "other", "other");
+		repo.set_head("refs/heads/hotfix").unwrap();
+		repo.checkout_head(Some(
+			git2::build::CheckoutBuilder::new().force(),
+		))
+		.unwrap();
+
+		assert_eq!(
+			return_to_parked(&repo_path).unwrap().1,
+			ParkReturn::Conflicted
+		);
+		assert!(super::repo(&repo_path)
+			.unwrap()
+			.index()
+			.unwrap()
+			.has_conflicts());
+		assert_eq!(get_stashes(&repo_path).unwrap().len(), 1);
+	}
+}
@@ -0,0 +1,254 @@
+//! partial clones (`git clone --filter=blob:none`) leave objects out
+//! and fetch them from their promisor remote once they are needed,
+//! libgit2 can't do that on its own so we run `git fetch` for them
+
+use super::{
+	commit_files::OldNew, repository::repo, utils::work_dir, RepoPath,
+};
+use crate::error::{Error, Result};
+use git2::{Config, Diff, FileMode, Oid, Repository};
+use scopetime::scope_time;
+use std::process::{Child, Command, Stdio};
+
+/// the remote a partial clone fetches missing objects from, `None`
+/// if the repo has all of its objects
+pub fn promisor_remote(
+	repo_path: &RepoPath,
+) -> Result<Option<String>> {
+	let repo = repo(repo_path)?;
+
+	promisor_remote_repo(&repo)
+}
+
+fn promisor_remote_repo(repo: &Repository) -> Result<Option<String>> {
+	let config = repo.config()?;
+
+	if let Ok(remote) = config.get_string("extensions.partialclone") {
+		return Ok(Some(remote));
+	}
+
+	let mut entries =
+		config.entries(Some(r"remote\..*\.promisor"))?;
+	while let Some(entry) = entries.next() {
+		let entry = entry?;
+
+		let promisor = entry.value().is_some_and(|value| {
+			Config::parse_bool(value).unwrap_or(false)
+		});
+		let remote = entry.name().and_then(|name| {
+			name.strip_prefix("remote.")?.strip_suffix(".promisor")
+		});
+
+		if let (true, Some(remote)) = (promisor, remote) {
+			return Ok(Some(remote.to_string()));
+		}
+	}
+
+	Ok(None)
+}
+
+/// which of `ids` are neither in the object database nor in one of
+/// its `objects/info/alternates`
+pub fn missing_objects(
+	repo_path: &RepoPath,
+	ids: &[String],
+) -> Result<Vec<String>> {
+	let repo = repo(repo_path)?;
+	let odb = repo.odb()?;
+
+	let mut res = Vec::new();
+	for id in ids {
+		if !odb.exists(Oid::from_str(id)?) {
+			res.push(id.clone());
+		}
+	}
+
+	Ok(res)
+}
+
+/// blobs of `diff` that are not available locally, only the sides
+/// in `odb` are looked up (the working tree is read from disk)
+pub fn missing_blobs(
+	repo: &Repository,
+	diff: &Diff,
+	odb: OldNew<bool>,
+) -> Result<Vec<String>> {
+	let objects = repo.odb()?;
+
+	let mut res = Vec::new();
+	for delta in diff.deltas() {
+		for (in_odb, file) in
+			[(odb.old, delta.old_file()), (odb.new, delta.new_file())]
+		{
+			// submodules point to commits of another repo
+			let id = file.id();
+			if in_odb
+				&& !id.is_zero()
+				&& file.mode() != FileMode::Commit
+				&& !objects.exists(id)
+			{
+				let id = id.to_string();
+				if !res.contains(&id) {
+					res.push(id);
+				}
+			}
+		}
+	}
+
+	Ok(res)
+}
+
+/// fetches `ids` from the promisor remote the way git does when it
+/// needs them itself, stdout and stderr are piped
+pub fn spawn_fetch_objects(
+	repo_path: &RepoPath,
+	ids: &[String],
+) -> Result<Child> {
+	scope_time!("spawn_fetch_objects");
+
+	let repo = repo(repo_path)?;
+	let remote = promisor_remote_repo(&repo)?.ok_or_else(|| {
+		Error::Generic(String::from(
+			"not a partial clone, there is no remote to fetch missing objects from",
+		))
+	})?;
+	let filter = repo
+		.config()?
+		.get_string(&format!("remote.{remote}.partialclonefilter"))
+		.unwrap_or_else(|_| String::from("blob:none"));
+
+	let child = Command::new("git")
+		.current_dir(work_dir(&repo).unwrap_or_else(|_| repo.path()))
+		.arg("--git-dir")
+		.arg(repo.path())
+		.args([
+			"-c",
+			"fetch.negotiationAlgorithm=noop",
+			"fetch",
+			&remote,
+			"--no-tags",
+			"--no-write-fetch-head",
+			"--recurse-submodules=no",
+		])
+		.arg(format!("--filter={filter}"))
+		.args(ids)
+		.stdin(Stdio::null())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::piped())
+		.spawn()?;
+
+	Ok(child)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		diff::get_diff_commit,
+		tests::{repo_init, write_commit_file},
+	};
+	use std::{fs, path::Path};
+	use tempfile::TempDir;
+
+	fn git(dir: &Path, args: &[&str]) {
+		let status = Command::new("git")
+			.current_dir(dir)
+			.args(args)
+			.stdout(Stdio::null())
+			.stderr(Stdio::null())
+			.status()
+			.unwrap();
+		assert!(status.success(), "git {args:?}");
+	}
+
+	fn repo_path(path: &Path) -> RepoPath {
+		path.as_os_str().to_str().unwrap().into()
+	}
+
+	#[test]
+	fn test_fetch_missing_objects() {
+		let (_td, origin) = repo_init().unwrap();
+		let origin_root = origin.path().parent().unwrap();
+
+		write_commit_file(&origin, "f", "a\n", "c1");
+		let id = write_commit_file(&origin, "f", "b\n", "c2");
+
+		let mut config = origin.config().unwrap();
+		config.set_bool("uploadpack.allowFilter", true).unwrap();
+		config
+			.set_bool("uploadpack.allowAnySHA1InWant", true)
+			.unwrap();
+
+		let td = TempDir::new().unwrap();
+		let url = format!("file://{}", origin_root.display());
+		git(
+			td.path(),
+			&[
+				"clone",
+				"-q",
+				"--no-checkout",
+				"--filter=blob:none",
+				&url,
+				"clone",
+			],
+		);
+		let repo_path = &repo_path(&td.path().join("clone"));
+
+		assert_eq!(
+			promisor_remote(repo_path).unwrap().as_deref(),
+			Some("origin")
+		);
+
+		let diff =
+			get_diff_commit(repo_path, id, String::from("f"), None)
+				.unwrap();
+		assert_eq!(diff.missing_objects.len(), 2);
+		assert!(diff.hunks.is_empty());
+		assert_eq!(
+			missing_objects(repo_path, &diff.missing_objects)
+				.unwrap(),
+			diff.missing_objects
+		);
+
+		let output =
+			spawn_fetch_objects(repo_path, &diff.missing_objects)
+				.unwrap()
+				.wait_with_output()
+				.unwrap();
+		assert!(output.status.success());
+
+		assert!(missing_objects(repo_path, &diff.missing_objects)
+			.unwrap()
+			.is_empty());
+		let diff =
+			get_diff_commit(repo_path, id, String::from("f"), None)
+				.unwrap();
+		assert!(diff.missing_objects.is_empty());
+		assert_eq!(diff.hunks.len(), 1);
+	}
+
+	#[test]
+	fn test_complete_repo_and_alternates() {
+		let (_td, origin) = repo_init().unwrap();
+		let id = write_commit_file(&origin, "f", "a\n", "c1");
+
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path = &repo_path(root);
+
+		assert_eq!(promisor_remote(repo_path).unwrap(), None);
+
+		// borrow the objects of `origin` like `git clone --shared`
+		fs::write(
+			repo.path().join("objects/info/alternates"),
+			origin.path().join("objects").to_str().unwrap(),
+		)
+		.unwrap();
+
+		let diff =
+			get_diff_commit(repo_path, id, String::from("f"), None)
+				.unwrap();
+		assert!(diff.missing_objects.is_empty());
+		assert_eq!(diff.hunks.len(), 1);
+	}
+}
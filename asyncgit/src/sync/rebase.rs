@@ -1,12 +1,25 @@
-use git2::{BranchType, Repository};
+use git2::{
+	BranchType, Oid, Rebase, Repository, Signature, StashFlags,
+	StatusOptions,
+};
 use scopetime::scope_time;
+use std::fs;
 
 use crate::{
 	error::{Error, Result},
 	sync::repository::repo,
 };
 
-use super::{CommitId, RepoPath};
+use super::{utils::get_head_repo, CommitId, FixupKind, RepoPath};
+
+const REBASE_MERGE_DIR: &str = "rebase-merge";
+/// names the stash commit of the uncommitted changes a rebase put
+/// aside, `git` applies it again when the rebase ends too
+const AUTOSTASH_FILE: &str = "autostash";
+
+/// marks a rebase started by `autosquash`, continuing it folds the
+/// `fixup!`/`squash!` commits into their targets too
+const AUTOSQUASH_MARKER: &str = "gitui-autosquash";
 
 /// rebase current HEAD on `branch`
 pub fn rebase_branch(
@@ -86,24 +99,7 @@ pub fn rebase(
 	let signature =
 		crate::sync::commit::signature_allow_undefined_name(repo)?;
 
-	while let Some(op) = rebase.next() {
-		let _op = op?;
-		// dbg!(op.id());
-
-		if repo.index()?.has_conflicts() {
-			return Ok(RebaseState::Conflicted);
-		}
-
-		rebase.commit(None, &signature, None)?;
-	}
-
-	if repo.index()?.has_conflicts() {
-		return Ok(RebaseState::Conflicted);
-	}
-
-	rebase.finish(Some(&signature))?;
-
-	Ok(RebaseState::Finished)
+	run_rebase(repo, &mut rebase, &signature, false)
 }
 
 /// continue pending rebase
@@ -113,6 +109,7 @@ pub fn continue_rebase(
 	let mut rebase = repo.open_rebase(None)?;
 	let signature =
 		crate::sync::commit::signature_allow_undefined_name(repo)?;
+	let autosquash = is_autosquash(repo);
 
 	if repo.index()?.has_conflicts() {
 		return Ok(RebaseState::Conflicted);
@@ -120,9 +117,19 @@ pub fn continue_rebase(
 
 	// try commit current rebase step
 	if !repo.index()?.is_empty() {
-		rebase.commit(None, &signature, None)?;
+		commit_step(repo, &mut rebase, &signature, autosquash)?;
 	}
 
+	run_rebase(repo, &mut rebase, &signature, autosquash)
+}
+
+/// applies the remaining steps until one conflicts
+fn run_rebase(
+	repo: &Repository,
+	rebase: &mut Rebase,
+	signature: &Signature,
+	autosquash: bool,
+) -> Result<RebaseState> {
 	while let Some(op) = rebase.next() {
 		let _op = op?;
 		// dbg!(op.id());
@@ -131,18 +138,241 @@ pub fn continue_rebase(
 			return Ok(RebaseState::Conflicted);
 		}
 
-		rebase.commit(None, &signature, None)?;
+		commit_step(repo, rebase, signature, autosquash)?;
 	}
 
 	if repo.index()?.has_conflicts() {
 		return Ok(RebaseState::Conflicted);
 	}
 
-	rebase.finish(Some(&signature))?;
+	rebase.finish(Some(signature))?;
 
 	Ok(RebaseState::Finished)
 }
 
+/// folds every `fixup!`/`squash!` commit into the commit it names
+/// (like `git rebase --autosquash`)
+///
+/// rebases `target` and the commits on top of it onto its parent, a
+/// conflict leaves the rebase pending to be continued or aborted
+pub fn autosquash(
+	repo_path: &RepoPath,
+	target: CommitId,
+) -> Result<RebaseState> {
+	scope_time!("autosquash");
+
+	let repo = repo(repo_path)?;
+	let onto = autosquash_check_repo(&repo, target.get_oid())?;
+
+	let onto = repo.find_annotated_commit(onto)?;
+	let mut rebase = repo.rebase(None, Some(&onto), None, None)?;
+	let steps: Vec<Oid> = (0..rebase.len())
+		.filter_map(|idx| rebase.nth(idx).map(|op| op.id()))
+		.collect();
+
+	let todo = match autosquash_todo(&repo, &steps) {
+		Ok(todo) => todo,
+		Err(e) => {
+			rebase.abort()?;
+			return Err(e);
+		}
+	};
+	drop(rebase);
+
+	// libgit2 picks in history order, hand it the reordered steps
+	let dir = repo.path().join(REBASE_MERGE_DIR);
+	for (idx, id) in todo.iter().enumerate() {
+		fs::write(
+			dir.join(format!("cmt.{}", idx + 1)),
+			format!("{id}\n"),
+		)?;
+	}
+	fs::write(dir.join(AUTOSQUASH_MARKER), "")?;
+
+	let mut rebase = repo.open_rebase(None)?;
+	let signature =
+		crate::sync::commit::signature_allow_undefined_name(&repo)?;
+
+	run_rebase(&repo, &mut rebase, &signature, true).or_else(|e| {
+		rebase.abort()?;
+		Err(e)
+	})
+}
+
+/// the commit to rebase onto, fails if there are uncommitted changes
+/// or [`rebase_onto_parent`] does
+fn autosquash_check_repo(
+	repo: &Repository,
+	target: Oid,
+) -> Result<Oid> {
+	let mut options = StatusOptions::new();
+	options.include_untracked(false).include_ignored(false);
+	if !repo.statuses(Some(&mut options))?.is_empty() {
+		return Err(Error::UncommittedChanges);
+	}
+
+	rebase_onto_parent(repo, target)
+}
+
+/// the parent of `target` to rebase it and the commits on top of it
+/// onto, fails if `target` is not part of the current branch or
+/// there are merge commits on top of it the rebase would drop
+pub fn rebase_onto_parent(
+	repo: &Repository,
+	target: Oid,
+) -> Result<Oid> {
+	let head = get_head_repo(repo)?.get_oid();
+	if head != target && !repo.graph_descendant_of(head, target)? {
+		return Err(Error::Generic(String::from(
+			"the commit is not part of the current branch",
+		)));
+	}
+
+	let onto =
+		repo.find_commit(target)?.parent_id(0).map_err(|_| {
+			Error::Generic(String::from(
+				"the initial commit has no parent to rebase onto",
+			))
+		})?;
+
+	let mut walk = repo.revwalk()?;
+	walk.push(head)?;
+	walk.hide(onto)?;
+	for id in walk {
+		if repo.find_commit(id?)?.parent_count() > 1 {
+			return Err(Error::RebaseMergeCommit);
+		}
+	}
+
+	Ok(onto)
+}
+
+/// `steps` with each `fixup!`/`squash!` commit moved behind the first
+/// commit whose subject it names
+fn autosquash_todo(
+	repo: &Repository,
+	steps: &[Oid],
+) -> Result<Vec<Oid>> {
+	let mut groups: Vec<(String, Vec<Oid>)> = Vec::new();
+
+	for id in steps {
+		let commit = repo.find_commit(*id)?;
+
+		if let Some((_, subject)) =
+			commit.message().and_then(FixupKind::parse)
+		{
+			if let Some((_, group)) = groups
+				.iter_mut()
+				.find(|(target, _)| target == subject)
+			{
+				group.push(*id);
+				continue;
+			}
+		}
+
+		groups.push((
+			commit.summary().unwrap_or_default().to_string(),
+			vec![*id],
+		));
+	}
+
+	if groups.iter().all(|(_, group)| group.len() == 1) {
+		return Err(Error::Generic(String::from(
+			"autosquash error: no fixup! or squash! commits of the commit or the ones on top of it",
+		)));
+	}
+
+	Ok(groups.into_iter().flat_map(|(_, group)| group).collect())
+}
+
+fn is_autosquash(repo: &Repository) -> bool {
+	repo.path()
+		.join(REBASE_MERGE_DIR)
+		.join(AUTOSQUASH_MARKER)
+		.exists()
+}
+
+/// commits the current step, in an autosquash rebase a
+/// `fixup!`/`squash!` of `HEAD` is folded into it instead
+fn commit_step(
+	repo: &Repository,
+	rebase: &mut Rebase,
+	signature: &Signature,
+	autosquash: bool,
+) -> Result<()> {
+	if autosquash {
+		// the first step has the rebased onto commit as `HEAD`
+		let op = rebase
+			.operation_current()
+			.filter(|idx| *idx > 0)
+			.and_then(|idx| rebase.nth(idx))
+			.map(|op| op.id());
+
+		if let Some(op) = op {
+			if squash_into_head(repo, op, signature)? {
+				return Ok(());
+			}
+		}
+	}
+
+	rebase.commit(None, signature, None)?;
+
+	Ok(())
+}
+
+/// replaces `HEAD` by a commit of the index if `op` is a fixup of it
+fn squash_into_head(
+	repo: &Repository,
+	op: Oid,
+	signature: &Signature,
+) -> Result<bool> {
+	let commit = repo.find_commit(op)?;
+	let Some((kind, subject)) =
+		commit.message().and_then(FixupKind::parse)
+	else {
+		return Ok(false);
+	};
+
+	let head = repo.head()?.peel_to_commit()?;
+	if head.summary() != Some(subject) {
+		return Ok(false);
+	}
+
+	let head_message = head.message().unwrap_or_default();
+	let message = match kind {
+		FixupKind::Fixup => head_message.to_string(),
+		FixupKind::Squash => {
+			// the `squash!` subject only says where it belongs
+			let body = commit
+				.message()
+				.unwrap_or_default()
+				.split_once('\n')
+				.map(|(_, body)| body.trim())
+				.unwrap_or_default();
+
+			if body.is_empty() {
+				head_message.to_string()
+			} else {
+				format!("{}\n\n{body}\n", head_message.trim_end())
+			}
+		}
+	};
+
+	let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+	let parents = head.parents().collect::<Vec<_>>();
+	let id = repo.commit(
+		None,
+		&head.author(),
+		signature,
+		&message,
+		&tree,
+		&parents.iter().collect::<Vec<_>>(),
+	)?;
+	repo.set_head_detached(id)?;
+
+	Ok(true)
+}
+
 ///
 #[derive(PartialEq, Eq, Debug)]
 pub struct RebaseProgress {
@@ -183,6 +413,79 @@ pub fn abort_rebase(repo: &git2::Repository) -> Result<()> {
 	Ok(())
 }
 
+/// stashes the uncommitted changes to tracked files if
+/// `rebase.autoStash` is set and fails otherwise, the stash commit
+/// is not in the stash list while the rebase runs (like in `git`)
+pub fn autostash_save(repo: &mut Repository) -> Result<Option<Oid>> {
+	let mut options = StatusOptions::new();
+	options.include_untracked(false).include_ignored(false);
+	if repo.statuses(Some(&mut options))?.is_empty() {
+		return Ok(None);
+	}
+
+	if !repo.config()?.get_bool("rebase.autoStash").unwrap_or(false) {
+		return Err(Error::Generic(String::from(
+			"there are uncommitted changes: commit or stash them first, or set rebase.autoStash",
+		)));
+	}
+
+	let signature =
+		crate::sync::commit::signature_allow_undefined_name(repo)?
+			.to_owned();
+	let id = repo.stash_save(
+		&signature,
+		"autostash",
+		Some(StashFlags::DEFAULT),
+	)?;
+	repo.stash_drop(0)?;
+
+	Ok(Some(id))
+}
+
+/// remembers `autostash` in the rebase that stopped
+pub fn autostash_keep(
+	repo: &Repository,
+	autostash: Option<Oid>,
+) -> Result<()> {
+	if let Some(id) = autostash {
+		fs::write(
+			repo.path().join(REBASE_MERGE_DIR).join(AUTOSTASH_FILE),
+			format!("{id}\n"),
+		)?;
+	}
+
+	Ok(())
+}
+
+/// the autostash of the pending rebase, read it before the rebase
+/// ends and removes its state
+pub fn read_autostash(repo: &Repository) -> Option<Oid> {
+	fs::read_to_string(
+		repo.path().join(REBASE_MERGE_DIR).join(AUTOSTASH_FILE),
+	)
+	.ok()
+	.and_then(|id| Oid::from_str(id.trim()).ok())
+}
+
+/// applies the autostash once the rebase ended, if that conflicts
+/// it is kept in the stash list instead
+pub fn autostash_apply(
+	repo: &mut Repository,
+	autostash: Option<Oid>,
+) -> Result<()> {
+	let Some(id) = autostash else {
+		return Ok(());
+	};
+
+	repo.reference_ensure_log("refs/stash")?;
+	repo.reference("refs/stash", id, true, "autostash")?;
+
+	repo.stash_pop(0, None).map_err(|e| {
+		log::error!("applying autostash failed: {e}");
+		Error::AutostashConflict
+	})
+}
+
 #[cfg(test)]
 mod test_conflict_free_rebase {
 	use crate::sync::{
@@ -341,3 +644,131 @@ mod test_rebase {
 		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
 	}
 }
+
+#[cfg(test)]
+mod test_autosquash {
+	use super::*;
+	use crate::sync::{
+		commit_fixup, continue_pending_rebase, get_head, repo_state,
+		stage_add_file,
+		tests::{repo_init, write_commit_file},
+		utils::repo_write_file,
+		RepoState,
+	};
+	use std::path::Path;
+
+	/// subjects from `HEAD` down to (excluding) `until`
+	fn subjects(repo: &Repository, until: CommitId) -> Vec<String> {
+		let mut walk = repo.revwalk().unwrap();
+		walk.push_head().unwrap();
+		walk.hide(until.into()).unwrap();
+
+		walk.map(|id| {
+			let commit = repo.find_commit(id.unwrap()).unwrap();
+			commit.summary().unwrap().to_string()
+		})
+		.collect()
+	}
+
+	#[test]
+	fn test_autosquash() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let base = write_commit_file(&repo, "a", "a", "add a");
+		let target = write_commit_file(&repo, "b", "1", "add b");
+		write_commit_file(&repo, "c", "1", "add c");
+
+		repo_write_file(&repo, "b", "2").unwrap();
+		stage_add_file(repo_path, Path::new("b")).unwrap();
+		commit_fixup(repo_path, target, FixupKind::Fixup).unwrap();
+
+		write_commit_file(&repo, "c", "2", "squash! add c\n\nmore c");
+
+		assert_eq!(
+			autosquash(repo_path, target).unwrap(),
+			RebaseState::Finished
+		);
+
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+		assert_eq!(subjects(&repo, base), vec!["add c", "add b"]);
+
+		let head = repo
+			.find_commit(get_head(repo_path).unwrap().into())
+			.unwrap();
+		assert_eq!(head.message(), Some("add c\n\nmore c\n"));
+
+		let tree = head.tree().unwrap();
+		let content = |path: &str| {
+			let blob = tree
+				.get_path(Path::new(path))
+				.unwrap()
+				.to_object(&repo)
+				.unwrap()
+				.peel_to_blob()
+				.unwrap();
+			String::from_utf8(blob.content().to_vec()).unwrap()
+		};
+		assert_eq!(content("b"), "2");
+		assert_eq!(content("c"), "2");
+	}
+
+	#[test]
+	fn test_autosquash_conflict() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let base = write_commit_file(&repo, "a", "1", "add a");
+		let target = write_commit_file(&repo, "a", "2", "set a");
+		write_commit_file(&repo, "a", "3", "change a");
+		// moved before `change a` it does not apply cleanly
+		write_commit_file(&repo, "a", "4", "fixup! set a");
+
+		assert_eq!(
+			autosquash(repo_path, target).unwrap(),
+			RebaseState::Conflicted
+		);
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Rebase);
+
+		repo_write_file(&repo, "a", "4").unwrap();
+		stage_add_file(repo_path, Path::new("a")).unwrap();
+
+		// the fixup is folded in, `change a` conflicts next
+		assert_eq!(
+			continue_pending_rebase(repo_path).unwrap(),
+			RebaseState::Conflicted
+		);
+
+		repo_write_file(&repo, "a", "3").unwrap();
+		stage_add_file(repo_path, Path::new("a")).unwrap();
+
+		assert_eq!(
+			continue_pending_rebase(repo_path).unwrap(),
+			RebaseState::Finished
+		);
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+		assert_eq!(subjects(&repo, base), vec!["change a", "set a"]);
+	}
+
+	#[test]
+	fn test_autosquash_nothing_to_squash() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a", "1", "add a");
+		let target = write_commit_file(&repo, "b", "1", "add b");
+		let head = write_commit_file(&repo, "c", "1", "add c");
+
+		assert!(autosquash(repo_path, target).is_err());
+
+		// the rebase is aborted again
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+		assert_eq!(get_head(repo_path).unwrap(), head);
+	}
+}
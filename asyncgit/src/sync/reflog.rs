@@ -0,0 +1,78 @@
+//! entries of the reflog of `HEAD`, the way back after a rebase or
+//! reset went wrong
+
+use super::{repository::repo, CommitId, RepoPath};
+use crate::error::Result;
+use scopetime::scope_time;
+
+/// one move of `HEAD`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReflogEntry {
+	/// like `HEAD@{2}`
+	pub selector: String,
+	/// like `rebase (finish): refs/heads/main onto 1a2b3c`
+	pub message: String,
+	/// what `HEAD` pointed to after the move
+	pub id: CommitId,
+	/// what `HEAD` pointed to before, zero for the first entry
+	pub old_id: CommitId,
+	/// unix time in seconds
+	pub time: i64,
+}
+
+/// the reflog of `HEAD`, the latest entry first
+pub fn get_reflog(repo_path: &RepoPath) -> Result<Vec<ReflogEntry>> {
+	scope_time!("get_reflog");
+
+	let repo = repo(repo_path)?;
+	let reflog = repo.reflog("HEAD")?;
+
+	Ok(reflog
+		.iter()
+		.enumerate()
+		.map(|(idx, entry)| ReflogEntry {
+			selector: format!("HEAD@{{{idx}}}"),
+			message: entry
+				.message()
+				.map(String::from)
+				.unwrap_or_default(),
+			id: entry.id_new().into(),
+			old_id: entry.id_old().into(),
+			time: entry.committer().when().seconds(),
+		})
+		.collect())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		checkout_commit, reset_repo,
+		tests::{repo_init, write_commit_file},
+		ResetType,
+	};
+
+	#[test]
+	fn test_reflog() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let first = write_commit_file(&repo, "a", "1", "first");
+		let second = write_commit_file(&repo, "a", "2", "second");
+		reset_repo(repo_path, first, ResetType::Hard).unwrap();
+		checkout_commit(repo_path, second).unwrap();
+
+		let reflog = get_reflog(repo_path).unwrap();
+
+		assert_eq!(reflog[0].selector, "HEAD@{0}");
+		assert_eq!(reflog[0].id, second);
+		assert_eq!(reflog[0].old_id, first);
+		assert!(reflog[0].message.starts_with("checkout:"));
+		assert_eq!(reflog[1].id, first);
+		assert_eq!(reflog[1].old_id, second);
+		assert_eq!(reflog[2].id, second);
+		assert!(reflog[2].message.contains("second"));
+	}
+}
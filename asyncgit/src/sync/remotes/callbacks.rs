@@ -1,16 +1,45 @@
-use super::push::ProgressNotification;
-use crate::{error::Result, sync::cred::BasicAuthCredential};
+use super::{
+	push::ProgressNotification, CredAnswer, CredPrompt,
+	CredRejection, CredRequest,
+};
+use crate::{
+	error::{Error, Result},
+	progress::TransferRate,
+	sync::{
+		cred::{credential_helper_update, BasicAuthCredential},
+		RepoPath,
+	},
+};
 use crossbeam_channel::Sender;
-use git2::{Cred, Error as GitError, RemoteCallbacks};
-use std::sync::{
-	atomic::{AtomicBool, Ordering},
-	Arc, Mutex,
+use git2::{Cred, Error as GitError, Oid, RemoteCallbacks};
+use std::{
+	path::PathBuf,
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc, Mutex,
+	},
 };
 
+/// how often the prompt asks again after the remote rejected the
+/// credentials it got
+pub const MAX_CRED_ATTEMPTS: usize = 3;
+
 ///
 #[derive(Default, Clone)]
 pub struct CallbackStats {
 	pub push_rejected_msg: Option<(String, String)>,
+	/// the remote ref the lease did not hold for
+	pub push_stale_ref: Option<String>,
+	/// last line the remote printed (`remote: ..` in git)
+	pub remote_msg: Option<String>,
+}
+
+/// user names and passwords handed to libgit2, so the credential
+/// helpers learn which of them work
+#[derive(Default)]
+struct OfferedCredentials {
+	last: Option<(String, BasicAuthCredential)>,
+	rejected: Vec<(String, BasicAuthCredential)>,
 }
 
 ///
@@ -19,7 +48,15 @@ pub struct Callbacks {
 	sender: Option<Sender<ProgressNotification>>,
 	basic_credential: Option<BasicAuthCredential>,
 	stats: Arc<Mutex<CallbackStats>>,
-	first_call_to_credentials: Arc<AtomicBool>,
+	transfer_rate: Arc<Mutex<TransferRate>>,
+	credential_calls: Arc<AtomicUsize>,
+	prompt: Option<CredPrompt>,
+	prompt_attempts: Arc<AtomicUsize>,
+	/// cancelled or timed out, libgit2 only reports that the
+	/// credentials callback failed
+	prompt_error: Arc<Mutex<Option<Error>>>,
+	offered: Arc<Mutex<OfferedCredentials>>,
+	lease: Option<(String, Oid)>,
 }
 
 impl Callbacks {
@@ -34,18 +71,80 @@ impl Callbacks {
 			sender,
 			basic_credential,
 			stats,
-			first_call_to_credentials: Arc::new(AtomicBool::new(
-				true,
-			)),
+			transfer_rate: Arc::default(),
+			credential_calls: Arc::default(),
+			prompt: None,
+			prompt_attempts: Arc::default(),
+			prompt_error: Arc::default(),
+			offered: Arc::default(),
+			lease: None,
 		}
 	}
 
+	/// ask `prompt` instead of failing when the first credentials
+	/// were rejected (up to `MAX_CRED_ATTEMPTS` times)
+	#[must_use]
+	pub fn with_prompt(mut self, prompt: Option<CredPrompt>) -> Self {
+		self.prompt = prompt;
+		self
+	}
+
+	/// refuse to push unless the remote ref (`refs/heads/..`) is at
+	/// the given commit still (`--force-with-lease`)
+	#[must_use]
+	pub fn with_lease(
+		mut self,
+		lease: Option<(String, Oid)>,
+	) -> Self {
+		self.lease = lease;
+		self
+	}
+
 	///
 	pub fn get_stats(&self) -> Result<CallbackStats> {
 		let stats = self.stats.lock()?;
 		Ok(stats.clone())
 	}
 
+	/// the error to report for a failed operation, a cancelled
+	/// prompt rather than the callback error libgit2 made of it
+	pub fn operation_error(&self, e: GitError) -> Error {
+		self.take_prompt_error().unwrap_or_else(|| e.into())
+	}
+
+	///
+	pub fn take_prompt_error(&self) -> Option<Error> {
+		self.prompt_error.lock().ok()?.take()
+	}
+
+	/// like git lets the configured `credential.helper`s store the
+	/// user name and password that worked (if the operation
+	/// `succeeded`) and erase the ones the remote rejected
+	pub fn update_credential_helpers(
+		&self,
+		repo_path: &RepoPath,
+		succeeded: bool,
+	) {
+		let Ok(mut offered) = self.offered.lock() else {
+			return;
+		};
+
+		let approved = offered.last.take().filter(|_| succeeded);
+		let updates = offered
+			.rejected
+			.drain(..)
+			.map(|(url, cred)| (url, cred, false))
+			.chain(approved.map(|(url, cred)| (url, cred, true)));
+
+		for (url, cred, approve) in updates {
+			if let Err(e) = credential_helper_update(
+				repo_path, &url, &cred, approve,
+			) {
+				log::error!("credential helper error: {e}");
+			}
+		}
+	}
+
 	///
 	pub fn callbacks<'a>(&self) -> RemoteCallbacks<'a> {
 		let mut callbacks = RemoteCallbacks::new();
@@ -91,17 +190,52 @@ impl Callbacks {
 			},
 		);
 
+		if self.lease.is_some() {
+			let this = self.clone();
+			callbacks.push_negotiation(move |updates| {
+				this.push_negotiation(updates)
+			});
+		}
+
+		let this = self.clone();
 		callbacks.sideband_progress(move |data| {
-			log::debug!(
-				"sideband transfer: '{}'",
-				String::from_utf8_lossy(data).trim()
-			);
+			this.sideband_progress(data);
 			true
 		});
 
 		callbacks
 	}
 
+	/// the remote sends its tips before anything is pushed
+	fn push_negotiation(
+		&self,
+		updates: &[git2::PushUpdate],
+	) -> std::result::Result<(), GitError> {
+		let Some((reference, expected)) = &self.lease else {
+			return Ok(());
+		};
+
+		for update in updates {
+			log::debug!(
+				"push_negotiation: '{:?}' {} (lease {})",
+				update.dst_refname(),
+				update.src(),
+				expected
+			);
+
+			if update.dst_refname() == Some(reference.as_str())
+				&& update.src() != *expected
+			{
+				if let Ok(mut stats) = self.stats.lock() {
+					stats.push_stale_ref = Some(reference.clone());
+				}
+				return Err(GitError::from_str("stale info"));
+			}
+		}
+
+		Ok(())
+	}
+
 	fn push_update_reference(
 		&self,
 		reference: &str,
@@ -119,6 +253,22 @@ impl Callbacks {
 		}
 	}
 
+	fn sideband_progress(&self, data: &[u8]) {
+		let text = String::from_utf8_lossy(data);
+		log::debug!("sideband transfer: '{}'", text.trim());
+
+		// progress lines are overwritten using `\r`
+		let line = text
+			.split(['\r', '\n'])
+			.map(str::trim)
+			.rfind(|line| !line.is_empty());
+
+		if let (Some(line), Ok(mut stats)) = (line, self.stats.lock())
+		{
+			stats.remote_msg = Some(line.to_string());
+		}
+	}
+
 	fn pack_progress(
 		&self,
 		stage: git2::PackBuilderStage,
@@ -135,6 +285,13 @@ impl Callbacks {
 		});
 	}
 
+	fn bytes_per_sec(&self, bytes: usize) -> usize {
+		self.transfer_rate
+			.lock()
+			.map(|mut rate| rate.update(bytes))
+			.unwrap_or_default()
+	}
+
 	fn transfer_progress(&self, p: &git2::Progress) {
 		log::debug!(
 			"transfer: {}/{}",
@@ -145,6 +302,8 @@ impl Callbacks {
 			sender.send(ProgressNotification::Transfer {
 				objects: p.received_objects(),
 				total_objects: p.total_objects(),
+				bytes: p.received_bytes(),
+				bytes_per_sec: self.bytes_per_sec(p.received_bytes()),
 			})
 		});
 	}
@@ -172,11 +331,12 @@ impl Callbacks {
 				current,
 				total,
 				bytes,
+				bytes_per_sec: self.bytes_per_sec(bytes),
 			})
 		});
 	}
 
-	// If credentials are bad, we don't ask the user to re-fill their creds. We push an error and they will be able to restart their action (for example a push) and retype their creds.
+	// If credentials are bad and there is no prompt, we don't ask the user to re-fill their creds. We push an error and they will be able to restart their action (for example a push) and retype their creds.
 	// This behavior is explained in a issue on git2-rs project : https://github.com/rust-lang/git2-rs/issues/347
 	// An implementation reference is done in cargo : https://github.com/rust-lang/cargo/blob/9fb208dddb12a3081230a5fd8f470e01df8faa25/src/cargo/sources/git/utils.rs#L588
 	// There is also a guide about libgit2 authentication : https://libgit2.org/docs/guides/authentication/
@@ -193,14 +353,60 @@ impl Callbacks {
 			allowed_types
 		);
 
-		// This boolean is used to avoid multiple calls to credentials callback.
-		if self.first_call_to_credentials.load(Ordering::Relaxed) {
-			self.first_call_to_credentials
-				.store(false, Ordering::Relaxed);
+		// libgit2 only calls again once the credentials it got were
+		// rejected
+		let calls =
+			self.credential_calls.fetch_add(1, Ordering::Relaxed);
+		if calls > 0 {
+			self.reject_offered();
 		} else {
+			match self.configured_credentials(
+				url,
+				username_from_url,
+				allowed_types,
+			) {
+				Err(_) if self.prompt.is_some() => (),
+				res => return res,
+			}
+		}
+
+		let Some(prompt) = &self.prompt else {
 			return Err(GitError::from_str("Bad credentials."));
+		};
+
+		let attempt =
+			self.prompt_attempts.fetch_add(1, Ordering::Relaxed) + 1;
+		if attempt > MAX_CRED_ATTEMPTS {
+			return Err(GitError::from_str(&format!(
+				"credentials rejected {MAX_CRED_ATTEMPTS} times{}",
+				self.remote_msg()
+					.map(|msg| format!(": {msg}"))
+					.unwrap_or_default()
+			)));
 		}
 
+		let rejection = (calls > 0).then(|| CredRejection {
+			attempt,
+			max_attempts: MAX_CRED_ATTEMPTS,
+			message: self.remote_msg(),
+		});
+
+		self.prompt_credentials(
+			prompt,
+			url,
+			username_from_url,
+			allowed_types,
+			rejection,
+		)
+	}
+
+	/// the ssh agent or the credentials the job was started with
+	fn configured_credentials(
+		&self,
+		url: &str,
+		username_from_url: Option<&str>,
+		allowed_types: git2::CredentialType,
+	) -> std::result::Result<Cred, GitError> {
 		match &self.basic_credential {
 			_ if allowed_types.is_ssh_key() => username_from_url
 				.map_or_else(
@@ -211,10 +417,13 @@ impl Callbacks {
 					},
 					Cred::ssh_key_from_agent,
 				),
-			Some(BasicAuthCredential {
-				username: Some(user),
-				password: Some(pwd),
-			}) if allowed_types.is_user_pass_plaintext() => {
+			Some(
+				cred @ BasicAuthCredential {
+					username: Some(user),
+					password: Some(pwd),
+				},
+			) if allowed_types.is_user_pass_plaintext() => {
+				self.offer(url, cred.clone());
 				Cred::userpass_plaintext(user, pwd)
 			}
 			Some(BasicAuthCredential {
@@ -225,4 +434,202 @@ impl Callbacks {
 			_ => Err(GitError::from_str("Couldn't find credentials")),
 		}
 	}
+
+	/// asks for one more attempt, the user either answers or gives
+	/// up which fails the job
+	fn prompt_credentials(
+		&self,
+		prompt: &CredPrompt,
+		url: &str,
+		username_from_url: Option<&str>,
+		allowed_types: git2::CredentialType,
+		rejection: Option<CredRejection>,
+	) -> std::result::Result<Cred, GitError> {
+		let request = if allowed_types.is_ssh_key() {
+			let username = username_from_url.ok_or_else(|| {
+				GitError::from_str(
+					" Couldn't extract username from url.",
+				)
+			})?;
+			let key = default_ssh_key().ok_or_else(|| {
+				GitError::from_str("Bad credentials.")
+			})?;
+
+			CredRequest::SshPassphrase {
+				username: username.to_string(),
+				key,
+			}
+		} else if allowed_types.is_user_pass_plaintext() {
+			CredRequest::UserPass {
+				url: url.to_string(),
+				username: username_from_url.map(ToString::to_string),
+			}
+		} else {
+			return Err(GitError::from_str("Bad credentials."));
+		};
+
+		let answer =
+			prompt.ask(request.clone(), rejection).map_err(|e| {
+				let git_error = GitError::from_str(&e.to_string());
+				if let Ok(mut prompt_error) = self.prompt_error.lock()
+				{
+					*prompt_error = Some(e);
+				}
+				git_error
+			})?;
+
+		match (answer, request) {
+			(
+				CredAnswer::Passphrase(passphrase),
+				CredRequest::SshPassphrase { username, key },
+			) => Cred::ssh_key(
+				&username,
+				None,
+				&key,
+				Some(&passphrase),
+			),
+			(
+				CredAnswer::UserPass(cred),
+				CredRequest::UserPass { .. },
+			) if cred.username.is_some() => {
+				let res = Cred::userpass_plaintext(
+					cred.username.as_deref().unwrap_or_default(),
+					cred.password.as_deref().unwrap_or_default(),
+				);
+				self.offer(url, cred);
+				res
+			}
+			(CredAnswer::SwitchUrl(url), _) => Err(
+				GitError::from_str(&format!("switching to {url}")),
+			),
+			_ => Err(GitError::from_str("Couldn't find credentials")),
+		}
+	}
+
+	fn remote_msg(&self) -> Option<String> {
+		self.stats.lock().ok()?.remote_msg.clone()
+	}
+
+	fn offer(&self, url: &str, cred: BasicAuthCredential) {
+		if let Ok(mut offered) = self.offered.lock() {
+			offered.last = Some((url.to_string(), cred));
+		}
+	}
+
+	fn reject_offered(&self) {
+		if let Ok(mut offered) = self.offered.lock() {
+			if let Some(last) = offered.last.take() {
+				offered.rejected.push(last);
+			}
+		}
+	}
+}
+
+/// first private key in `~/.ssh` with one of the default names
+fn default_ssh_key() -> Option<PathBuf> {
+	let dir = dirs::home_dir()?.join(".ssh");
+
+	["id_ed25519", "id_ecdsa", "id_rsa"]
+		.into_iter()
+		.map(|name| dir.join(name))
+		.find(|path| path.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use git2::CredentialType;
+	use std::thread;
+
+	const URL: &str = "https://example.com/repo.git";
+
+	fn userpass(password: &str) -> BasicAuthCredential {
+		BasicAuthCredential::new(
+			Some(String::from("user")),
+			Some(password.to_string()),
+		)
+	}
+
+	fn ask(callbacks: &Callbacks) -> bool {
+		callbacks
+			.credentials(
+				URL,
+				None,
+				CredentialType::USER_PASS_PLAINTEXT,
+			)
+			.is_ok()
+	}
+
+	#[test]
+	fn test_prompt_attempts() {
+		let prompt = CredPrompt::new(None);
+		let callbacks = Callbacks::new(None, Some(userpass("stale")))
+			.with_prompt(Some(prompt.clone()));
+
+		let ui = thread::spawn(move || {
+			let mut rejections = Vec::new();
+			while rejections.len() < MAX_CRED_ATTEMPTS {
+				if prompt.request().is_some() {
+					rejections.push(prompt.rejection());
+					prompt
+						.answer(CredAnswer::UserPass(userpass(
+							"wrong",
+						)))
+						.unwrap();
+				}
+				thread::yield_now();
+			}
+			rejections
+		});
+
+		// the configured credentials first, then the prompt
+		assert!(ask(&callbacks));
+		for _ in 0..MAX_CRED_ATTEMPTS {
+			assert!(ask(&callbacks));
+		}
+		assert!(!ask(&callbacks));
+
+		let rejections = ui.join().unwrap();
+		assert_eq!(
+			rejections[0],
+			Some(CredRejection {
+				attempt: 1,
+				max_attempts: MAX_CRED_ATTEMPTS,
+				message: None,
+			})
+		);
+		assert_eq!(
+			rejections[MAX_CRED_ATTEMPTS - 1]
+				.as_ref()
+				.map(|rejection| rejection.attempt),
+			Some(MAX_CRED_ATTEMPTS)
+		);
+
+		let offered = callbacks.offered.lock().unwrap();
+		assert_eq!(offered.rejected.len(), MAX_CRED_ATTEMPTS + 1);
+		assert_eq!(offered.rejected[0].1, userpass("stale"));
+	}
+
+	#[test]
+	fn test_prompt_cancelled() {
+		let prompt = CredPrompt::new(None);
+		let callbacks = Callbacks::new(None, None)
+			.with_prompt(Some(prompt.clone()));
+
+		let ui = thread::spawn(move || {
+			while !prompt.cancel().unwrap() {
+				thread::yield_now();
+			}
+		});
+
+		// without configured credentials it asks right away
+		assert!(!ask(&callbacks));
+		ui.join().unwrap();
+
+		assert!(matches!(
+			callbacks.operation_error(GitError::from_str("failed")),
+			Error::CredentialsCancelled
+		));
+		assert!(callbacks.take_prompt_error().is_none());
+	}
 }
@@ -1,6 +1,7 @@
 //!
 
 mod callbacks;
+mod prompt;
 pub(crate) mod push;
 pub(crate) mod tags;
 
@@ -10,16 +11,19 @@ use crate::{
 		cred::BasicAuthCredential,
 		remotes::push::ProgressNotification, repository::repo, utils,
 	},
-	ProgressPercent,
 };
 use crossbeam_channel::Sender;
 use git2::{
 	BranchType, FetchOptions, ProxyOptions, Remote, Repository,
 };
 use scopetime::scope_time;
+use std::{collections::BTreeSet, fs, time::SystemTime};
 use utils::bytes2string;
 
 pub use callbacks::Callbacks;
+pub use prompt::{
+	CredAnswer, CredPrompt, CredRejection, CredRequest,
+};
 pub use tags::tags_missing_remote;
 
 use super::RepoPath;
@@ -27,6 +31,13 @@ use super::RepoPath;
 /// origin
 pub const DEFAULT_REMOTE_NAME: &str = "origin";
 
+/// remote picked when the config does not name one for the current
+/// branch, overrides the [`DEFAULT_REMOTE_NAME`] fallback
+pub const DEFAULT_REMOTE_CONFIG: &str = "gitui.defaultRemote";
+
+/// remote pushed to when the current branch does not name one
+pub const PUSH_DEFAULT_CONFIG: &str = "remote.pushDefault";
+
 ///
 pub fn proxy_auto<'a>() -> ProxyOptions<'a> {
 	let mut proxy = ProxyOptions::new();
@@ -40,12 +51,16 @@ pub fn add_remote(
 	name: &str,
 	url: &str,
 ) -> Result<()> {
+	if !validate_remote_url(url) {
+		return Err(Error::Generic(format!("invalid url: '{url}'")));
+	}
+
 	let repo = repo(repo_path)?;
 	repo.remote(name, url)?;
 	Ok(())
 }
 
-///
+/// the defaults naming the remote are renamed with it
 pub fn rename_remote(
 	repo_path: &RepoPath,
 	name: &str,
@@ -53,6 +68,14 @@ pub fn rename_remote(
 ) -> Result<()> {
 	let repo = repo(repo_path)?;
 	repo.remote_rename(name, new_name)?;
+
+	let mut config = repo.config()?;
+	for key in [DEFAULT_REMOTE_CONFIG, PUSH_DEFAULT_CONFIG] {
+		if config_names_remote(&repo, key, name) {
+			config.set_str(key, new_name)?;
+		}
+	}
+
 	Ok(())
 }
 
@@ -62,26 +85,151 @@ pub fn update_remote_url(
 	name: &str,
 	new_url: &str,
 ) -> Result<()> {
+	if !validate_remote_url(new_url) {
+		return Err(Error::Generic(format!(
+			"invalid url: '{new_url}'"
+		)));
+	}
+
 	let repo = repo(repo_path)?;
 	repo.remote_set_url(name, new_url)?;
 	Ok(())
 }
 
-///
+/// the defaults naming the remote are removed with it
 pub fn delete_remote(
 	repo_path: &RepoPath,
 	remote_name: &str,
 ) -> Result<()> {
 	let repo = repo(repo_path)?;
 	repo.remote_delete(remote_name)?;
+
+	let mut config = repo.config()?;
+	for key in [DEFAULT_REMOTE_CONFIG, PUSH_DEFAULT_CONFIG] {
+		if config_names_remote(&repo, key, remote_name) {
+			config.remove(key)?;
+		}
+	}
+
 	Ok(())
 }
 
+fn config_names_remote(
+	repo: &Repository,
+	key: &str,
+	remote: &str,
+) -> bool {
+	repo.config()
+		.and_then(|config| config.get_string(key))
+		.is_ok_and(|value| value == remote)
+}
+
 ///
 pub fn validate_remote_name(name: &str) -> bool {
 	Remote::is_valid_name(name)
 }
 
+/// rejects obviously malformed urls: empty ones, `scheme://` or scp
+/// like (`host:path`) urls with whitespace or without a host or path.
+/// anything else is taken as a local path
+pub fn validate_remote_url(url: &str) -> bool {
+	if url.trim().is_empty() || url.trim() != url {
+		return false;
+	}
+
+	if let Some((scheme, rest)) = url.split_once("://") {
+		return !scheme.is_empty()
+			&& scheme.chars().all(|c| {
+				c.is_ascii_alphanumeric() || "+-.".contains(c)
+			}) && !rest.is_empty()
+			&& !rest.contains(char::is_whitespace);
+	}
+
+	let drive_letter = |host: &str| {
+		host.len() == 1
+			&& host.chars().all(|c| c.is_ascii_alphabetic())
+	};
+
+	// `host:path` unless the colon comes after a slash or is the one
+	// of a drive letter
+	match url.split_once(':') {
+		Some((host, path))
+			if !host.contains('/') && !drive_letter(host) =>
+		{
+			let host = host.rsplit('@').next().unwrap_or(host);
+
+			!host.is_empty()
+				&& !path.is_empty()
+				&& !url.contains(char::is_whitespace)
+		}
+		_ => true,
+	}
+}
+
+/// the url pushed to if it differs from the fetch url
+pub fn get_remote_push_url(
+	repo_path: &RepoPath,
+	remote_name: &str,
+) -> Result<Option<String>> {
+	let repo = repo(repo_path)?;
+	let remote = repo.find_remote(remote_name)?;
+
+	Ok(remote.pushurl().map(String::from))
+}
+
+/// remotes fetch and push go to unless the current branch is
+/// configured otherwise
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RemoteDefaults {
+	/// `gitui.defaultRemote`, pushes fall back to it as well
+	pub fetch: Option<String>,
+	/// `remote.pushDefault`
+	pub push: Option<String>,
+}
+
+///
+pub fn get_remote_defaults(
+	repo_path: &RepoPath,
+) -> Result<RemoteDefaults> {
+	let repo = repo(repo_path)?;
+	let config = repo.config()?;
+
+	Ok(RemoteDefaults {
+		fetch: config.get_string(DEFAULT_REMOTE_CONFIG).ok(),
+		push: config.get_string(PUSH_DEFAULT_CONFIG).ok(),
+	})
+}
+
+/// `push` sets `remote.pushDefault`, otherwise the remote every
+/// repo wide fallback (fetch, pull, tags, ..) goes to. `None`
+/// removes the setting
+pub fn set_default_remote(
+	repo_path: &RepoPath,
+	remote: Option<&str>,
+	push: bool,
+) -> Result<()> {
+	let repo = repo(repo_path)?;
+	let mut config = repo.config()?;
+	let key = if push {
+		PUSH_DEFAULT_CONFIG
+	} else {
+		DEFAULT_REMOTE_CONFIG
+	};
+
+	match remote {
+		Some(remote) => {
+			repo.find_remote(remote)?;
+			config.set_str(key, remote)?;
+		}
+		None if config.get_entry(key).is_ok() => {
+			config.remove(key)?;
+		}
+		None => (),
+	}
+
+	Ok(())
+}
+
 ///
 pub fn get_remotes(repo_path: &RepoPath) -> Result<Vec<String>> {
 	scope_time!("get_remotes");
@@ -204,32 +352,82 @@ pub(crate) fn get_default_remote_for_push_in_repo(
 ) -> Result<String> {
 	scope_time!("get_default_remote_for_push_in_repo");
 
-	let config = repo.config()?;
-
-	let branch = get_current_branch(repo)?;
+	match get_current_branch(repo)? {
+		Some(branch) => get_branch_push_remote_in_repo(
+			repo,
+			&bytes2string(branch.name_bytes()?)?,
+		),
+		None => get_default_remote_in_repo(repo),
+	}
+}
 
-	if let Some(branch) = branch {
-		let remote_name = bytes2string(branch.name_bytes()?)?;
+/// remote pushes of `branch` go to, see
+/// [`get_default_remote_for_push`] for the order of the settings
+pub fn get_branch_push_remote(
+	repo_path: &RepoPath,
+	branch: &str,
+) -> Result<String> {
+	let repo = repo(repo_path)?;
+	get_branch_push_remote_in_repo(&repo, branch)
+}
 
-		let entry_name =
-			format!("branch.{}.pushRemote", &remote_name);
+pub(crate) fn get_branch_push_remote_in_repo(
+	repo: &Repository,
+	branch: &str,
+) -> Result<String> {
+	let config = repo.config()?;
 
+	for entry_name in [
+		push_remote_config(branch),
+		PUSH_DEFAULT_CONFIG.to_string(),
+		format!("branch.{branch}.remote"),
+	] {
 		if let Ok(entry) = config.get_entry(&entry_name) {
 			return bytes2string(entry.value_bytes());
 		}
+	}
 
-		if let Ok(entry) = config.get_entry("remote.pushDefault") {
-			return bytes2string(entry.value_bytes());
-		}
+	get_default_remote_in_repo(repo)
+}
 
-		let entry_name = format!("branch.{}.remote", &remote_name);
+fn push_remote_config(branch: &str) -> String {
+	format!("branch.{branch}.pushRemote")
+}
 
-		if let Ok(entry) = config.get_entry(&entry_name) {
-			return bytes2string(entry.value_bytes());
+/// `branch.<name>.pushRemote`, the remote `branch` pushes to
+/// regardless of its upstream and of `remote.pushDefault`
+pub fn get_branch_push_remote_config(
+	repo_path: &RepoPath,
+	branch: &str,
+) -> Result<Option<String>> {
+	let repo = repo(repo_path)?;
+	let config = repo.config()?;
+
+	Ok(config.get_string(&push_remote_config(branch)).ok())
+}
+
+/// sets `branch.<name>.pushRemote`, `None` removes it
+pub fn set_branch_push_remote(
+	repo_path: &RepoPath,
+	branch: &str,
+	remote: Option<&str>,
+) -> Result<()> {
+	let repo = repo(repo_path)?;
+	let mut config = repo.config()?;
+	let key = push_remote_config(branch);
+
+	match remote {
+		Some(remote) => {
+			repo.find_remote(remote)?;
+			config.set_str(&key, remote)?;
+		}
+		None if config.get_entry(&key).is_ok() => {
+			config.remove(&key)?;
 		}
+		None => (),
 	}
 
-	get_default_remote_in_repo(repo)
+	Ok(())
 }
 
 /// see `get_default_remote`
@@ -240,6 +438,15 @@ pub(crate) fn get_default_remote_in_repo(
 
 	let remotes = repo.remotes()?;
 
+	// the one picked in gitui if it still exists
+	if let Ok(remote) =
+		repo.config()?.get_string(DEFAULT_REMOTE_CONFIG)
+	{
+		if remotes.iter().flatten().any(|r| r == remote) {
+			return Ok(remote);
+		}
+	}
+
 	// if `origin` exists return that
 	let found_origin = remotes
 		.iter()
@@ -266,40 +473,85 @@ pub(crate) fn get_default_remote_in_repo(
 	Err(Error::NoDefaultRemoteFound)
 }
 
-///
+/// remote-tracking branches of `remote` (like `origin/main`)
+fn tracking_branches(
+	repo: &Repository,
+	remote: &str,
+) -> Result<BTreeSet<String>> {
+	let mut branches = BTreeSet::new();
+	for reference in
+		repo.references_glob(&format!("refs/remotes/{remote}/*"))?
+	{
+		if let Some(name) = reference?.shorthand() {
+			branches.insert(name.to_string());
+		}
+	}
+
+	Ok(branches)
+}
+
+/// `remote.<name>.prune` or else `fetch.prune`, like in `git fetch`
+fn prune_configured(repo: &Repository, remote: &str) -> bool {
+	repo.config().is_ok_and(|config| {
+		config
+			.get_bool(&format!("remote.{remote}.prune"))
+			.or_else(|_| config.get_bool("fetch.prune"))
+			.unwrap_or_default()
+	})
+}
+
+/// returns the remote-tracking branches that were pruned
 fn fetch_from_remote(
 	repo_path: &RepoPath,
 	remote: &str,
 	basic_credential: Option<BasicAuthCredential>,
 	progress_sender: Option<Sender<ProgressNotification>>,
-) -> Result<()> {
+	cred_prompt: Option<&CredPrompt>,
+) -> Result<Vec<String>> {
 	let repo = repo(repo_path)?;
 
+	let before = tracking_branches(&repo, remote)?;
 	let mut remote = repo.find_remote(remote)?;
 
 	let mut options = FetchOptions::new();
-	let callbacks = Callbacks::new(progress_sender, basic_credential);
+	let callbacks = Callbacks::new(progress_sender, basic_credential)
+		.with_prompt(cred_prompt.cloned());
 	options.prune(git2::FetchPrune::On);
 	options.proxy_options(proxy_auto());
 	options.download_tags(git2::AutotagOption::All);
 	options.remote_callbacks(callbacks.callbacks());
-	remote.fetch(&[] as &[&str], Some(&mut options), None)?;
-	// fetch tags (also removing remotely deleted ones)
-	remote.fetch(
-		&["refs/tags/*:refs/tags/*"],
-		Some(&mut options),
-		None,
-	)?;
-
-	Ok(())
+	let res = remote
+		.fetch(&[] as &[&str], Some(&mut options), None)
+		.and_then(|()| {
+			// fetch tags (also removing remotely deleted ones)
+			remote.fetch(
+				&["refs/tags/*:refs/tags/*"],
+				Some(&mut options),
+				None,
+			)
+		});
+
+	callbacks.update_credential_helpers(repo_path, res.is_ok());
+	res.map_err(|e| callbacks.operation_error(e))?;
+
+	let after =
+		tracking_branches(&repo, remote.name().unwrap_or_default())?;
+
+	Ok(before.difference(&after).cloned().collect())
 }
 
-/// updates/prunes all branches from all remotes
+/// updates/prunes all branches from all remotes, returns the pruned
+/// remote-tracking branches
+///
+/// transfer progress of each remote is sent to `progress_sender`,
+/// `cred_prompt` is asked for new credentials whenever a remote
+/// rejects them
 pub fn fetch_all(
 	repo_path: &RepoPath,
 	basic_credential: &Option<BasicAuthCredential>,
-	progress_sender: &Option<Sender<ProgressPercent>>,
-) -> Result<()> {
+	progress_sender: &Option<Sender<ProgressNotification>>,
+	cred_prompt: Option<&CredPrompt>,
+) -> Result<Vec<String>> {
 	scope_time!("fetch_all");
 
 	let repo = repo(repo_path)?;
@@ -309,31 +561,47 @@ pub fn fetch_all(
 		.flatten()
 		.map(String::from)
 		.collect::<Vec<_>>();
-	let remotes_count = remotes.len();
 
-	for (idx, remote) in remotes.into_iter().enumerate() {
-		fetch_from_remote(
+	let mut pruned = Vec::new();
+	for remote in remotes {
+		pruned.extend(fetch_from_remote(
 			repo_path,
 			&remote,
 			basic_credential.clone(),
-			None,
-		)?;
-
-		if let Some(sender) = progress_sender {
-			let progress = ProgressPercent::new(idx, remotes_count);
-			sender.send(progress)?;
-		}
+			progress_sender.clone(),
+			cred_prompt,
+		)?);
 	}
 
-	Ok(())
+	Ok(pruned)
+}
+
+/// time of the last fetch from any remote
+///
+/// taken from the modification time of `FETCH_HEAD`, `None` if the
+/// repo was never fetched into. every worktree has its own
+/// `FETCH_HEAD` in its git dir
+pub fn last_fetch_time(
+	repo_path: &RepoPath,
+) -> Result<Option<SystemTime>> {
+	let repo = repo(repo_path)?;
+
+	Ok(fs::metadata(repo.path().join("FETCH_HEAD"))
+		.and_then(|meta| meta.modified())
+		.ok())
 }
 
 /// fetches from upstream/remote for local `branch`
+///
+/// if pruning is configured for the remote (`remote.<name>.prune` or
+/// `fetch.prune`) all of its branches are fetched to prune the ones
+/// deleted on the remote
 pub(crate) fn fetch(
 	repo_path: &RepoPath,
 	branch: &str,
 	basic_credential: Option<BasicAuthCredential>,
 	progress_sender: Option<Sender<ProgressNotification>>,
+	cred_prompt: Option<&CredPrompt>,
 ) -> Result<usize> {
 	scope_time!("fetch");
 
@@ -348,11 +616,20 @@ pub(crate) fn fetch(
 
 	let mut options = FetchOptions::new();
 	options.download_tags(git2::AutotagOption::All);
-	let callbacks = Callbacks::new(progress_sender, basic_credential);
+	let callbacks = Callbacks::new(progress_sender, basic_credential)
+		.with_prompt(cred_prompt.cloned());
 	options.remote_callbacks(callbacks.callbacks());
 	options.proxy_options(proxy_auto());
 
-	remote.fetch(&[branch], Some(&mut options), None)?;
+	let res = if prune_configured(&repo, &remote_name) {
+		options.prune(git2::FetchPrune::On);
+		remote.fetch(&[] as &[&str], Some(&mut options), None)
+	} else {
+		remote.fetch(&[branch], Some(&mut options), None)
+	};
+
+	callbacks.update_credential_helpers(repo_path, res.is_ok());
+	res.map_err(|e| callbacks.operation_error(e))?;
 
 	Ok(remote.stats().received_bytes())
 }
@@ -380,7 +657,98 @@ mod tests {
 
 		assert_eq!(remotes, vec![String::from("origin")]);
 
-		fetch(repo_path, "master", None, None).unwrap();
+		fetch(repo_path, "master", None, None, None).unwrap();
+	}
+
+	#[test]
+	fn test_fetch_prune() {
+		let (remote_dir, remote) = repo_init().unwrap();
+		let head = remote.head().unwrap().peel_to_commit().unwrap();
+		remote.branch("a", &head, false).unwrap();
+		remote.branch("b", &head, false).unwrap();
+
+		let (repo_dir, repo) =
+			repo_clone(remote_dir.path().to_str().unwrap()).unwrap();
+		let repo_path: &RepoPath =
+			&repo_dir.path().to_str().unwrap().into();
+		repo.branch("a", &head_commit(&repo), false)
+			.unwrap()
+			.set_upstream(Some("origin/a"))
+			.unwrap();
+
+		let delete = |branch| {
+			remote
+				.find_branch(branch, BranchType::Local)
+				.unwrap()
+				.delete()
+				.unwrap();
+		};
+
+		// pull only prunes if configured
+		delete("b");
+		fetch(repo_path, "master", None, None, None).unwrap();
+		assert_eq!(
+			tracking_branches(&repo, "origin").unwrap().len(),
+			4
+		);
+
+		let mut config = repo.config().unwrap();
+		config.set_bool("fetch.prune", true).unwrap();
+		config.set_bool("remote.origin.prune", false).unwrap();
+		fetch(repo_path, "master", None, None, None).unwrap();
+		assert_eq!(
+			tracking_branches(&repo, "origin").unwrap().len(),
+			4
+		);
+
+		config.set_bool("remote.origin.prune", true).unwrap();
+		fetch(repo_path, "master", None, None, None).unwrap();
+		assert!(!tracking_branches(&repo, "origin")
+			.unwrap()
+			.contains("origin/b"));
+
+		delete("a");
+		assert_eq!(
+			fetch_all(repo_path, &None, &None, None).unwrap(),
+			vec![String::from("origin/a")]
+		);
+		assert!(fetch_all(repo_path, &None, &None, None)
+			.unwrap()
+			.is_empty());
+
+		let branches =
+			crate::sync::get_branches_info(repo_path, true).unwrap();
+		let gone = branches
+			.iter()
+			.filter(|branch| {
+				branch
+					.local_details()
+					.is_some_and(|details| details.upstream_gone)
+			})
+			.map(|branch| branch.name.as_str())
+			.collect::<Vec<_>>();
+		assert_eq!(gone, vec!["a"]);
+	}
+
+	fn head_commit(repo: &Repository) -> git2::Commit<'_> {
+		repo.head().unwrap().peel_to_commit().unwrap()
+	}
+
+	#[test]
+	fn test_last_fetch_time() {
+		let (remote_dir, _remote) = repo_init().unwrap();
+		let remote_path = remote_dir.path().to_str().unwrap();
+		let repo_path: &RepoPath = &remote_path.into();
+
+		assert_eq!(last_fetch_time(repo_path).unwrap(), None);
+
+		let (repo_dir, _repo) = repo_clone(remote_path).unwrap();
+		let repo_path: &RepoPath =
+			&repo_dir.path().to_str().unwrap().into();
+
+		fetch(repo_path, "master", None, None, None).unwrap();
+
+		assert!(last_fetch_time(repo_path).unwrap().is_some());
 	}
 
 	#[test]
@@ -580,4 +948,88 @@ mod tests {
 			matches!(default_push_remote, Ok(remote_name) if remote_name == "branchpushremote")
 		);
 	}
+
+	#[test]
+	fn test_validate_remote_url() {
+		for url in [
+			"https://github.com/o/r.git",
+			"ssh://git@host:22/r",
+			"file:///tmp/r",
+			"git@github.com:o/r.git",
+			"host:r",
+			"/tmp/some repo",
+			"../r",
+			"C:\\repos\\r",
+		] {
+			assert!(validate_remote_url(url), "{url}");
+		}
+
+		for url in [
+			"",
+			" ",
+			" https://host/r",
+			"https://",
+			"://host/r",
+			"ht tp://host/r",
+			"https://host/a b",
+			"git@:r",
+			"host:",
+			"git@host:a b",
+		] {
+			assert!(!validate_remote_url(url), "{url}");
+		}
+	}
+
+	#[test]
+	fn test_set_default_remote() {
+		let (remote_dir, _remote) = repo_init().unwrap();
+		let remote_path = remote_dir.path().to_str().unwrap();
+		let (repo_dir, repo) = repo_clone(remote_path).unwrap();
+		let repo_path: &RepoPath =
+			&repo_dir.path().as_os_str().to_str().unwrap().into();
+
+		add_remote(repo_path, "upstream", remote_path).unwrap();
+		add_remote(repo_path, "fork", remote_path).unwrap();
+		assert!(add_remote(repo_path, "fork", remote_path).is_err());
+		assert!(add_remote(repo_path, "bad", "https://").is_err());
+
+		set_default_remote(repo_path, Some("upstream"), false)
+			.unwrap();
+		set_default_remote(repo_path, Some("fork"), true).unwrap();
+		assert!(
+			set_default_remote(repo_path, Some("x"), true).is_err()
+		);
+
+		assert_eq!(
+			get_default_remote_in_repo(&repo).unwrap(),
+			"upstream"
+		);
+		// the branch's own remote still wins
+		assert_eq!(
+			get_default_remote_for_fetch(repo_path).unwrap(),
+			"origin"
+		);
+		assert_eq!(
+			get_default_remote_for_push(repo_path).unwrap(),
+			"fork"
+		);
+
+		rename_remote(repo_path, "fork", "mine").unwrap();
+		delete_remote(repo_path, "upstream").unwrap();
+
+		assert_eq!(
+			get_remote_defaults(repo_path).unwrap(),
+			RemoteDefaults {
+				fetch: None,
+				push: Some(String::from("mine")),
+			}
+		);
+
+		set_default_remote(repo_path, None, true).unwrap();
+		set_default_remote(repo_path, None, true).unwrap();
+		assert_eq!(
+			get_remote_defaults(repo_path).unwrap(),
+			RemoteDefaults::default()
+		);
+	}
 }
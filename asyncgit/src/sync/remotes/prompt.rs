@@ -0,0 +1,327 @@
+//! lets a running remote job ask the ui for credentials
+
+use crate::{
+	error::{Error, Result},
+	sync::cred::BasicAuthCredential,
+	AsyncGitNotification,
+};
+use crossbeam_channel::Sender;
+use std::{
+	path::PathBuf,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc, Condvar, Mutex,
+	},
+	time::Duration,
+};
+
+/// a prompt nobody answers gives up after this long so the worker
+/// thread never hangs forever
+const PROMPT_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// what the remote callback needs to continue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CredRequest {
+	/// passphrase to unlock the ssh key at `key`
+	SshPassphrase {
+		///
+		username: String,
+		///
+		key: PathBuf,
+	},
+	///
+	UserPass {
+		///
+		url: String,
+		///
+		username: Option<String>,
+	},
+}
+
+/// why the credentials given before were not good enough
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CredRejection {
+	/// the prompt is this attempt out of `max_attempts`
+	pub attempt: usize,
+	///
+	pub max_attempts: usize,
+	/// what the remote said about it, if anything
+	pub message: Option<String>,
+}
+
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CredAnswer {
+	///
+	Passphrase(String),
+	///
+	UserPass(BasicAuthCredential),
+	/// restart the job against this url instead
+	SwitchUrl(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PromptState {
+	Idle,
+	Pending(CredRequest, Option<CredRejection>),
+	Answered(CredAnswer),
+	Cancelled,
+}
+
+/// shared between the thread of a remote job (asking) and the ui
+/// (answering), cloning it keeps talking to the same prompt
+#[derive(Clone)]
+pub struct CredPrompt {
+	state: Arc<(Mutex<PromptState>, Condvar)>,
+	url: Arc<Mutex<Option<String>>>,
+	restart: Arc<AtomicBool>,
+	notify:
+		Option<(Sender<AsyncGitNotification>, AsyncGitNotification)>,
+	timeout: Duration,
+}
+
+impl CredPrompt {
+	/// `notify` is sent whenever a new request is pending
+	pub fn new(
+		notify: Option<(
+			Sender<AsyncGitNotification>,
+			AsyncGitNotification,
+		)>,
+	) -> Self {
+		Self {
+			state: Arc::new((
+				Mutex::new(PromptState::Idle),
+				Condvar::new(),
+			)),
+			url: Arc::default(),
+			restart: Arc::default(),
+			notify,
+			timeout: PROMPT_TIMEOUT,
+		}
+	}
+
+	///
+	#[must_use]
+	pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+		self.timeout = timeout;
+		self
+	}
+
+	/// forget everything about a previous job
+	pub fn reset(&self) -> Result<()> {
+		*self.state.0.lock()? = PromptState::Idle;
+		*self.url.lock()? = None;
+		self.restart.store(false, Ordering::Relaxed);
+
+		Ok(())
+	}
+
+	/// the request waiting for an answer, if any
+	pub fn request(&self) -> Option<CredRequest> {
+		let state = self.state.0.lock().ok()?;
+
+		if let PromptState::Pending(request, _) = &*state {
+			Some(request.clone())
+		} else {
+			None
+		}
+	}
+
+	/// why the pending request asks again, `None` for the first one
+	pub fn rejection(&self) -> Option<CredRejection> {
+		let state = self.state.0.lock().ok()?;
+
+		if let PromptState::Pending(_, rejection) = &*state {
+			rejection.clone()
+		} else {
+			None
+		}
+	}
+
+	/// returns `false` if nothing was waiting for an answer (anymore)
+	pub fn answer(&self, answer: CredAnswer) -> Result<bool> {
+		self.resolve(PromptState::Answered(answer))
+	}
+
+	/// gives up on the pending request, which fails the job
+	pub fn cancel(&self) -> Result<bool> {
+		self.resolve(PromptState::Cancelled)
+	}
+
+	fn resolve(&self, outcome: PromptState) -> Result<bool> {
+		let (state, answered) = &*self.state;
+		let mut state = state.lock()?;
+
+		if !matches!(&*state, PromptState::Pending(..)) {
+			return Ok(false);
+		}
+
+		*state = outcome;
+		answered.notify_all();
+
+		Ok(true)
+	}
+
+	/// url the job has to use instead of the configured one
+	pub fn url(&self) -> Option<String> {
+		self.url.lock().ok()?.clone()
+	}
+
+	/// `true` once if the job failed only to restart on a new url
+	pub fn take_restart(&self) -> bool {
+		self.restart.swap(false, Ordering::Relaxed)
+	}
+
+	/// blocks until the ui answered `request`, it was cancelled or
+	/// the timeout passed
+	pub(crate) fn ask(
+		&self,
+		request: CredRequest,
+		rejection: Option<CredRejection>,
+	) -> Result<CredAnswer> {
+		let (state, answered) = &*self.state;
+		let mut state = state.lock()?;
+
+		*state = PromptState::Pending(request, rejection);
+
+		if let Some((sender, notification)) = &self.notify {
+			sender.send(*notification)?;
+		}
+
+		let (mut state, _) = answered.wait_timeout_while(
+			state,
+			self.timeout,
+			|state| matches!(state, PromptState::Pending(..)),
+		)?;
+
+		match std::mem::replace(&mut *state, PromptState::Idle) {
+			PromptState::Answered(CredAnswer::SwitchUrl(url)) => {
+				*self.url.lock()? = Some(url.clone());
+				self.restart.store(true, Ordering::Relaxed);
+				Ok(CredAnswer::SwitchUrl(url))
+			}
+			PromptState::Answered(answer) => Ok(answer),
+			PromptState::Cancelled => {
+				Err(Error::CredentialsCancelled)
+			}
+			PromptState::Pending(..) | PromptState::Idle => {
+				Err(Error::CredentialsTimeout)
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::thread;
+
+	fn request() -> CredRequest {
+		CredRequest::UserPass {
+			url: String::from("https://example.com"),
+			username: None,
+		}
+	}
+
+	fn wait_for_request(prompt: &CredPrompt) -> CredRequest {
+		loop {
+			if let Some(request) = prompt.request() {
+				return request;
+			}
+			thread::yield_now();
+		}
+	}
+
+	#[test]
+	fn test_answer() {
+		let prompt = CredPrompt::new(None);
+		let worker = prompt.clone();
+		let handle =
+			thread::spawn(move || worker.ask(request(), None));
+
+		assert_eq!(wait_for_request(&prompt), request());
+		assert_eq!(prompt.rejection(), None);
+
+		let answer = CredAnswer::UserPass(BasicAuthCredential::new(
+			Some(String::from("user")),
+			Some(String::from("pwd")),
+		));
+		assert!(prompt.answer(answer.clone()).unwrap());
+
+		assert_eq!(handle.join().unwrap().unwrap(), answer);
+		assert_eq!(prompt.request(), None);
+		assert!(!prompt.cancel().unwrap());
+	}
+
+	#[test]
+	fn test_cancel() {
+		let prompt = CredPrompt::new(None);
+		let worker = prompt.clone();
+		let handle =
+			thread::spawn(move || worker.ask(request(), None));
+
+		wait_for_request(&prompt);
+		assert!(prompt.cancel().unwrap());
+
+		assert!(matches!(
+			handle.join().unwrap(),
+			Err(Error::CredentialsCancelled)
+		));
+	}
+
+	#[test]
+	fn test_timeout() {
+		let prompt =
+			CredPrompt::new(None).with_timeout(Duration::ZERO);
+
+		assert!(matches!(
+			prompt.ask(request(), None),
+			Err(Error::CredentialsTimeout)
+		));
+		assert_eq!(prompt.request(), None);
+		assert!(!prompt
+			.answer(CredAnswer::Passphrase(String::new()))
+			.unwrap());
+	}
+
+	#[test]
+	fn test_switch_url() {
+		let prompt = CredPrompt::new(None);
+		let worker = prompt.clone();
+		let handle =
+			thread::spawn(move || worker.ask(request(), None));
+
+		wait_for_request(&prompt);
+		let url = String::from("https://example.com/repo");
+		prompt.answer(CredAnswer::SwitchUrl(url.clone())).unwrap();
+		handle.join().unwrap().unwrap();
+
+		assert_eq!(prompt.url(), Some(url));
+		assert!(prompt.take_restart());
+		assert!(!prompt.take_restart());
+
+		prompt.reset().unwrap();
+		assert_eq!(prompt.url(), None);
+	}
+
+	#[test]
+	fn test_rejection() {
+		let prompt = CredPrompt::new(None);
+		let worker = prompt.clone();
+		let rejection = CredRejection {
+			attempt: 2,
+			max_attempts: 3,
+			message: Some(String::from("invalid password")),
+		};
+		let asked = rejection.clone();
+		let handle =
+			thread::spawn(move || worker.ask(request(), Some(asked)));
+
+		wait_for_request(&prompt);
+		assert_eq!(prompt.rejection(), Some(rejection));
+
+		prompt.cancel().unwrap();
+		assert!(handle.join().unwrap().is_err());
+		assert_eq!(prompt.rejection(), None);
+	}
+}
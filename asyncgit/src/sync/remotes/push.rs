@@ -8,14 +8,21 @@ use crate::{
 			PushDefaultStrategyConfig,
 		},
 		cred::BasicAuthCredential,
-		get_branch_upstream_merge,
-		remotes::{proxy_auto, Callbacks},
+		get_branch_remote, get_branch_upstream_merge,
+		oplog::{oplog_record, OpKind},
+		remotes::{
+			proxy_auto, tags::remote_tag_differs, Callbacks,
+			CredPrompt,
+		},
 		repository::repo,
 		CommitId, RepoPath,
 	},
 };
 use crossbeam_channel::Sender;
-use git2::{PackBuilderStage, PushOptions};
+use git2::{
+	Direction, ErrorClass, ErrorCode, Oid, PackBuilderStage,
+	PushOptions, Repository,
+};
 use scopetime::scope_time;
 use std::fmt::Write as _;
 
@@ -45,6 +52,10 @@ pub enum ProgressNotification {
 		objects: usize,
 		///
 		total_objects: usize,
+		///
+		bytes: usize,
+		///
+		bytes_per_sec: usize,
 	},
 	///
 	PushTransfer {
@@ -54,6 +65,8 @@ pub enum ProgressNotification {
 		total: usize,
 		///
 		bytes: usize,
+		///
+		bytes_per_sec: usize,
 	},
 	///
 	Packing {
@@ -112,6 +125,35 @@ impl Default for PushType {
 	}
 }
 
+impl PushType {
+	const fn ref_dir(self) -> &'static str {
+		match self {
+			Self::Branch => "heads",
+			Self::Tag => "tags",
+		}
+	}
+}
+
+/// what a push may do to commits only the remote ref has
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum PushForce {
+	/// fast-forwards only
+	#[default]
+	FastForward,
+	/// replaces the remote ref only if it is where its
+	/// remote-tracking ref says (`--force-with-lease`)
+	Lease,
+	/// replaces the remote ref whatever it points to (`--force`)
+	Force,
+}
+
+impl PushForce {
+	///
+	pub fn is_forced(self) -> bool {
+		self != Self::FastForward
+	}
+}
+
 #[cfg(test)]
 pub fn push_branch(
 	repo_path: &RepoPath,
@@ -127,13 +169,113 @@ pub fn push_branch(
 		remote,
 		branch,
 		PushType::Branch,
-		force,
+		if force {
+			PushForce::Force
+		} else {
+			PushForce::FastForward
+		},
 		delete,
 		basic_credential,
 		progress_sender,
+		None,
 	)
 }
 
+/// the commit a force push with lease of `branch` to `remote`
+/// expects the remote ref at, `None` without a remote-tracking ref
+/// of it (like for tags)
+pub fn push_lease(
+	repo_path: &RepoPath,
+	remote: &str,
+	branch: &str,
+	ref_type: PushType,
+) -> Result<Option<CommitId>> {
+	scope_time!("push_lease");
+
+	let repo = repo(repo_path)?;
+	let dst = remote_ref(
+		&repo, repo_path, remote, branch, ref_type, false,
+	)?;
+
+	Ok(lease_target(&repo, remote, &dst)?.map(CommitId::new))
+}
+
+/// the remote-tracking ref pushing `branch` to `remote` updates,
+/// whether it exists or not
+pub fn push_tracking_ref(
+	repo: &Repository,
+	repo_path: &RepoPath,
+	remote: &str,
+	branch: &str,
+) -> Result<Option<String>> {
+	let dst = remote_ref(
+		repo,
+		repo_path,
+		remote,
+		branch,
+		PushType::Branch,
+		false,
+	)?;
+
+	tracking_ref(repo, remote, &dst)
+}
+
+/// the ref `branch` updates on `remote`, like git the upstream is
+/// only pushed to if it is on that remote
+fn remote_ref(
+	repo: &Repository,
+	repo_path: &RepoPath,
+	remote: &str,
+	branch: &str,
+	ref_type: PushType,
+	delete: bool,
+) -> Result<String> {
+	if !delete
+		&& ref_type == PushType::Branch
+		&& push_default_strategy_config_repo(repo)?
+			== PushDefaultStrategyConfig::Upstream
+		&& get_branch_remote(repo_path, branch)?.as_deref()
+			== Some(remote)
+	{
+		if let Ok(Some(branch_upstream_merge)) =
+			get_branch_upstream_merge(repo_path, branch)
+		{
+			return Ok(branch_upstream_merge);
+		}
+	}
+
+	Ok(format!("refs/{}/{branch}", ref_type.ref_dir()))
+}
+
+/// the remote-tracking ref the fetch refspecs of `remote` map `dst`
+/// to
+fn tracking_ref(
+	repo: &Repository,
+	remote: &str,
+	dst: &str,
+) -> Result<Option<String>> {
+	let remote = repo.find_remote(remote)?;
+
+	Ok(remote
+		.refspecs()
+		.filter(|spec| spec.direction() == Direction::Fetch)
+		.find(|spec| spec.src_matches(dst))
+		.and_then(|spec| spec.transform(dst).ok())
+		.and_then(|name| name.as_str().map(String::from)))
+}
+
+/// tip of the remote-tracking ref of `dst` (see [`tracking_ref`])
+fn lease_target(
+	repo: &Repository,
+	remote: &str,
+	dst: &str,
+) -> Result<Option<Oid>> {
+	Ok(tracking_ref(repo, remote, dst)?
+		.and_then(|name| repo.refname_to_id(&name).ok()))
+}
+
+/// force pushes are recorded in the oplog, `cred_prompt` is asked
+/// for new credentials whenever the remote rejects them
 //TODO: clenaup
 #[allow(clippy::too_many_arguments)]
 pub fn push_raw(
@@ -141,70 +283,200 @@ pub fn push_raw(
 	remote: &str,
 	branch: &str,
 	ref_type: PushType,
-	force: bool,
+	force: PushForce,
 	delete: bool,
 	basic_credential: Option<BasicAuthCredential>,
 	progress_sender: Option<Sender<ProgressNotification>>,
+	cred_prompt: Option<&CredPrompt>,
 ) -> Result<()> {
 	scope_time!("push");
 
+	if !force.is_forced() {
+		return push_refs(
+			repo_path,
+			remote,
+			branch,
+			ref_type,
+			force,
+			delete,
+			basic_credential,
+			progress_sender,
+			cred_prompt,
+		);
+	}
+
+	let git_ref_type = ref_type.ref_dir();
+	let (old_id, new_id) =
+		repo(repo_path).map_or((None, None), |repo| {
+			let id = |name: &str| {
+				repo.refname_to_id(name).ok().map(CommitId::new)
+			};
+			let old_id = if ref_type == PushType::Branch {
+				id(&format!("refs/remotes/{remote}/{branch}"))
+			} else {
+				None
+			};
+			let new_id = if delete {
+				None
+			} else {
+				id(&format!("refs/{git_ref_type}/{branch}"))
+			};
+			(old_id, new_id)
+		});
+
+	let result = push_refs(
+		repo_path,
+		remote,
+		branch,
+		ref_type,
+		force,
+		delete,
+		basic_credential,
+		progress_sender,
+		cred_prompt,
+	);
+
+	oplog_record(
+		repo_path,
+		OpKind::ForcePush,
+		&format!("{remote}/{branch}"),
+		old_id,
+		new_id,
+		&result,
+	);
+
+	result
+}
+
+#[allow(clippy::too_many_arguments)]
+fn push_refs(
+	repo_path: &RepoPath,
+	remote: &str,
+	branch: &str,
+	ref_type: PushType,
+	force: PushForce,
+	delete: bool,
+	basic_credential: Option<BasicAuthCredential>,
+	progress_sender: Option<Sender<ProgressNotification>>,
+	cred_prompt: Option<&CredPrompt>,
+) -> Result<()> {
 	let repo = repo(repo_path)?;
-	let mut remote = repo.find_remote(remote)?;
+	let remote_name = remote;
+	let mut remote = match cred_prompt.and_then(CredPrompt::url) {
+		Some(url) => repo.remote_anonymous(&url)?,
+		None => repo.find_remote(remote_name)?,
+	};
 
-	let push_default_strategy =
-		push_default_strategy_config_repo(&repo)?;
+	let local = format!("refs/{}/{branch}", ref_type.ref_dir());
+	let dst = remote_ref(
+		&repo,
+		repo_path,
+		remote_name,
+		branch,
+		ref_type,
+		delete,
+	)?;
+
+	let lease = if force == PushForce::Lease {
+		let expected = lease_target(&repo, remote_name, &dst)?
+			.ok_or_else(|| Error::PushNoLease(dst.clone()))?;
+		Some((dst.clone(), expected))
+	} else {
+		None
+	};
 
 	let mut options = PushOptions::new();
 	options.proxy_options(proxy_auto());
 
-	let callbacks = Callbacks::new(progress_sender, basic_credential);
+	// like git a tag is only moved on the remote by force
+	let tag_update =
+		ref_type == PushType::Tag && !delete && !force.is_forced();
+
+	let callbacks =
+		Callbacks::new(progress_sender, basic_credential.clone())
+			.with_prompt(cred_prompt.cloned())
+			.with_lease(lease);
 	options.remote_callbacks(callbacks.callbacks());
 	options.packbuilder_parallelism(0);
 
-	let branch_modifier = match (force, delete) {
+	let branch_modifier = match (force.is_forced(), delete) {
 		(true, true) => "+:",
 		(false, true) => ":",
 		(true, false) => "+",
 		(false, false) => "",
 	};
-	let git_ref_type = match ref_type {
-		PushType::Branch => "heads",
-		PushType::Tag => "tags",
-	};
 
-	let mut push_ref =
-		format!("{branch_modifier}refs/{git_ref_type}/{branch}");
+	let mut push_ref = format!("{branch_modifier}{local}");
+	if dst != local {
+		let _ = write!(push_ref, ":{dst}");
+	}
 
-	if !delete
-		&& ref_type == PushType::Branch
-		&& push_default_strategy
-			== PushDefaultStrategyConfig::Upstream
-	{
-		if let Ok(Some(branch_upstream_merge)) =
-			get_branch_upstream_merge(repo_path, branch)
+	log::debug!("push to: {push_ref}");
+	let res = remote.push(&[push_ref], Some(&mut options));
+	callbacks.update_credential_helpers(repo_path, res.is_ok());
+
+	if let Err(e) = res {
+		if let Some(prompt_error) = callbacks.take_prompt_error() {
+			return Err(prompt_error);
+		}
+
+		// libgit2 fails differently for lightweight and annotated tags
+		if tag_update
+			&& remote_tag_differs(
+				repo_path,
+				remote_name,
+				branch,
+				basic_credential,
+			)
+			.unwrap_or_default()
 		{
-			let _ = write!(push_ref, ":{branch_upstream_merge}");
+			return Err(Error::PushTagExists(branch.to_string()));
 		}
-	}
 
-	log::debug!("push to: {push_ref}");
-	remote.push(&[push_ref], Some(&mut options))?;
+		return Err(push_error(
+			e,
+			callbacks.get_stats()?.push_stale_ref,
+		));
+	}
 
 	if let Some((reference, msg)) =
 		callbacks.get_stats()?.push_rejected_msg
 	{
+		if tag_update && msg.contains("already exists") {
+			return Err(Error::PushTagExists(branch.to_string()));
+		}
+
 		return Err(Error::Generic(format!(
 			"push to '{reference}' rejected: {msg}"
 		)));
 	}
 
-	if !delete {
-		branch_set_upstream_after_push(&repo, branch)?;
+	if !delete && ref_type == PushType::Branch {
+		branch_set_upstream_after_push(&repo, remote_name, branch)?;
 	}
 
 	Ok(())
 }
 
+/// tells a lease that did not hold and rejected credentials apart
+/// from the other errors
+fn push_error(e: git2::Error, stale_ref: Option<String>) -> Error {
+	if let Some(reference) = stale_ref {
+		return Error::PushStaleInfo(reference);
+	}
+
+	let msg = e.message().to_lowercase();
+	if e.code() == ErrorCode::Auth
+		|| (matches!(e.class(), ErrorClass::Http | ErrorClass::Ssh)
+			&& msg.contains("auth"))
+		|| msg.contains("credentials")
+	{
+		return Error::PushAuth(e.message().to_string());
+	}
+
+	e.into()
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -524,4 +796,227 @@ mod tests {
 			.map(|(i, _)| i.name().unwrap().unwrap().to_string())
 			.any(|i| &i == "test_branch"));
 	}
+
+	#[test]
+	fn test_push_sets_upstream_to_pushed_remote() {
+		let (tmp_repo_dir, repo) = repo_init().unwrap();
+		let (tmp_origin_dir, _) = repo_init_bare().unwrap();
+		let (tmp_other_dir, _) = repo_init_bare().unwrap();
+		let repo_path: &RepoPath =
+			&tmp_repo_dir.path().to_str().unwrap().into();
+
+		repo.remote(
+			"origin",
+			tmp_origin_dir.path().to_str().unwrap(),
+		)
+		.unwrap();
+		repo.remote("other", tmp_other_dir.path().to_str().unwrap())
+			.unwrap();
+
+		sync::create_branch(repo_path, "feature").unwrap();
+
+		push_raw(
+			repo_path,
+			"other",
+			"feature",
+			PushType::Branch,
+			PushForce::FastForward,
+			false,
+			None,
+			None,
+			None,
+		)
+		.unwrap();
+
+		assert_eq!(
+			sync::get_branch_remote(repo_path, "feature").unwrap(),
+			Some(String::from("other"))
+		);
+	}
+
+	#[test]
+	fn test_push_tag_does_not_touch_branches() {
+		let (tmp_repo_dir, repo) = repo_init().unwrap();
+		let (tmp_upstream_dir, upstream) = repo_init_bare().unwrap();
+		let repo_path: &RepoPath =
+			&tmp_repo_dir.path().to_str().unwrap().into();
+
+		repo.remote(
+			"origin",
+			tmp_upstream_dir.path().to_str().unwrap(),
+		)
+		.unwrap();
+
+		let head = sync::get_head(repo_path).unwrap();
+		sync::tag_commit(repo_path, &head, "v1", None).unwrap();
+
+		push_raw(
+			repo_path,
+			"origin",
+			"v1",
+			PushType::Tag,
+			PushForce::FastForward,
+			false,
+			None,
+			None,
+			None,
+		)
+		.unwrap();
+
+		assert!(upstream.find_reference("refs/tags/v1").is_ok());
+
+		// tags have no remote-tracking refs to lease against
+		assert_eq!(
+			push_lease(repo_path, "origin", "v1", PushType::Tag)
+				.unwrap(),
+			None
+		);
+		assert!(matches!(
+			push_raw(
+				repo_path,
+				"origin",
+				"v1",
+				PushType::Tag,
+				PushForce::Lease,
+				false,
+				None,
+				None,
+				None,
+			),
+			Err(Error::PushNoLease(_))
+		));
+	}
+
+	#[test]
+	fn test_push_moved_tag() {
+		let (tmp_repo_dir, repo) = repo_init().unwrap();
+		let (tmp_upstream_dir, upstream) = repo_init_bare().unwrap();
+		let repo_path: &RepoPath =
+			&tmp_repo_dir.path().to_str().unwrap().into();
+
+		repo.remote(
+			"origin",
+			tmp_upstream_dir.path().to_str().unwrap(),
+		)
+		.unwrap();
+
+		let first = sync::get_head(repo_path).unwrap();
+		let second = write_commit_file(&repo, "a", "1", "commit2");
+		sync::tag_commit(repo_path, &second, "v1", None).unwrap();
+
+		let push_tag = |force| {
+			push_raw(
+				repo_path,
+				"origin",
+				"v1",
+				PushType::Tag,
+				force,
+				false,
+				None,
+				None,
+				None,
+			)
+		};
+		push_tag(PushForce::FastForward).unwrap();
+
+		sync::delete_tag(repo_path, "v1").unwrap();
+		sync::tag_commit(repo_path, &first, "v1", Some("moved"))
+			.unwrap();
+
+		assert!(matches!(
+			push_tag(PushForce::FastForward),
+			Err(Error::PushTagExists(tag)) if tag == "v1"
+		));
+		let remote_tag = || {
+			upstream
+				.find_reference("refs/tags/v1")
+				.unwrap()
+				.peel_to_commit()
+				.unwrap()
+				.id()
+		};
+		assert_eq!(remote_tag(), second.get_oid());
+
+		push_tag(PushForce::Force).unwrap();
+		assert_eq!(remote_tag(), first.get_oid());
+	}
+
+	#[test]
+	fn test_force_push_with_lease() {
+		let (tmp_upstream_dir, _) = repo_init_bare().unwrap();
+		let upstream = tmp_upstream_dir.path().to_str().unwrap();
+		let (tmp_repo_dir, repo) = repo_clone(upstream).unwrap();
+		let repo_path: &RepoPath =
+			&tmp_repo_dir.path().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a", "1", "c1");
+		push_branch(
+			repo_path, "origin", "master", false, false, None, None,
+		)
+		.unwrap();
+
+		let (tmp_other_dir, other) = repo_clone(upstream).unwrap();
+		let other_path: &RepoPath =
+			&tmp_other_dir.path().to_str().unwrap().into();
+		let teammate = write_commit_file(&other, "b", "1", "c2");
+		push_branch(
+			other_path, "origin", "master", false, false, None, None,
+		)
+		.unwrap();
+
+		// rewrite the branch without knowing about `c2`
+		sync::amend(
+			repo_path,
+			sync::get_head(repo_path).unwrap(),
+			"c1'",
+		)
+		.unwrap();
+		let lease = push_lease(
+			repo_path,
+			"origin",
+			"master",
+			PushType::Branch,
+		)
+		.unwrap();
+		assert!(lease.is_some());
+		assert_ne!(lease, Some(teammate));
+
+		let push = |force| {
+			push_raw(
+				repo_path,
+				"origin",
+				"master",
+				PushType::Branch,
+				force,
+				false,
+				None,
+				None,
+				None,
+			)
+		};
+
+		assert!(matches!(
+			push(PushForce::Lease),
+			Err(Error::PushStaleInfo(reference)) if reference == "refs/heads/master"
+		));
+		assert_eq!(
+			Repository::open_bare(upstream)
+				.unwrap()
+				.refname_to_id("refs/heads/master")
+				.unwrap(),
+			teammate.into()
+		);
+
+		// once fetched the lease expects `c2`
+		super::super::fetch(repo_path, "master", None, None, None)
+			.unwrap();
+		push(PushForce::Lease).unwrap();
+		assert_eq!(
+			Repository::open_bare(upstream)
+				.unwrap()
+				.refname_to_id("refs/heads/master")
+				.unwrap(),
+			repo.head().unwrap().target().unwrap()
+		);
+	}
 }
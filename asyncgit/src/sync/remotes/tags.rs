@@ -76,6 +76,34 @@ fn remote_tag_refs(
 	Ok(remote_tags)
 }
 
+/// whether `remote` has the tag `tag` pointing somewhere else than
+/// the local one
+pub fn remote_tag_differs(
+	repo_path: &RepoPath,
+	remote: &str,
+	tag: &str,
+	basic_credential: Option<BasicAuthCredential>,
+) -> Result<bool> {
+	scope_time!("remote_tag_differs");
+
+	let repo = repo(repo_path)?;
+	let name = format!("refs/tags/{tag}");
+	let local = repo.refname_to_id(&name)?;
+
+	let mut remote = repo.find_remote(remote)?;
+	let callbacks = Callbacks::new(None, basic_credential);
+	let conn = remote.connect_auth(
+		Direction::Fetch,
+		Some(callbacks.callbacks()),
+		Some(proxy_auto()),
+	)?;
+
+	Ok(conn
+		.list()?
+		.iter()
+		.any(|head| head.name() == name && head.oid() != local))
+}
+
 /// lists the remotes tags missing
 pub fn tags_missing_remote(
 	repo_path: &RepoPath,
@@ -161,7 +189,7 @@ mod tests {
 			self, delete_tag,
 			remotes::{
 				fetch, fetch_all,
-				push::{push_branch, push_raw},
+				push::{push_branch, push_raw, PushForce},
 			},
 			tests::{repo_clone, repo_init_bare},
 		},
@@ -210,7 +238,8 @@ mod tests {
 		assert_eq!(sync::get_tags(clone2_dir).unwrap().len(), 0);
 
 		//lets fetch from origin
-		let bytes = fetch(clone2_dir, "master", None, None).unwrap();
+		let bytes =
+			fetch(clone2_dir, "master", None, None, None).unwrap();
 		assert!(bytes > 0);
 
 		sync::merge_upstream_commit(clone2_dir, "master").unwrap();
@@ -325,7 +354,7 @@ mod tests {
 
 		// clone 2 - pull
 
-		fetch(clone2_dir, "master", None, None).unwrap();
+		fetch(clone2_dir, "master", None, None, None).unwrap();
 
 		let tags2 = sync::get_tags(clone2_dir).unwrap();
 
@@ -365,7 +394,7 @@ mod tests {
 
 		// clone 2 - pull
 
-		fetch_all(clone2_dir, &None, &None).unwrap();
+		fetch_all(clone2_dir, &None, &None, None).unwrap();
 
 		let tags2 = sync::get_tags(clone2_dir).unwrap();
 
@@ -399,7 +428,7 @@ mod tests {
 
 		// clone 2 - pull
 
-		fetch_all(clone2_dir, &None, &None).unwrap();
+		fetch_all(clone2_dir, &None, &None, None).unwrap();
 		assert_eq!(sync::get_tags(clone2_dir).unwrap().len(), 1);
 
 		// delete on clone 1
@@ -411,10 +440,11 @@ mod tests {
 			"origin",
 			"tag1",
 			PushType::Tag,
-			false,
+			PushForce::FastForward,
 			true,
 			None,
 			None,
+			None,
 		)
 		.unwrap();
 
@@ -422,7 +452,7 @@ mod tests {
 
 		// clone 2
 
-		fetch_all(clone2_dir, &None, &None).unwrap();
+		fetch_all(clone2_dir, &None, &None, None).unwrap();
 		assert_eq!(sync::get_tags(clone2_dir).unwrap().len(), 0);
 	}
 }
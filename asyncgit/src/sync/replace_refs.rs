@@ -0,0 +1,223 @@
+//! `git replace` refs: objects git reads in place of others, like
+//! commits grafted onto history that was imported separately
+
+use super::{repository::repo, CommitId, RepoPath};
+use crate::error::Result;
+use git2::{ObjectType, Oid, Repository};
+use scopetime::scope_time;
+use std::{collections::HashMap, env};
+
+const REPLACE_REF_BASE: &str = "refs/replace/";
+
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplaceRef {
+	/// the replaced object, usually a commit
+	pub original: CommitId,
+	///
+	pub replacement: CommitId,
+	/// a commit with the tree of the original and other parents
+	/// (`git replace --graft`)
+	pub graft: bool,
+}
+
+/// where replace refs live, `GIT_REPLACE_REF_BASE` can move them
+fn replace_ref_base() -> String {
+	env::var("GIT_REPLACE_REF_BASE")
+		.ok()
+		.filter(|base| !base.is_empty())
+		.map_or_else(
+			|| String::from(REPLACE_REF_BASE),
+			|base| format!("{}/", base.trim_end_matches('/')),
+		)
+}
+
+/// whether git reads objects through replace refs: on unless
+/// `GIT_NO_REPLACE_OBJECTS` is set or `core.useReplaceRefs` is false
+pub fn replace_refs_enabled(repo_path: &RepoPath) -> Result<bool> {
+	let repo = repo(repo_path)?;
+
+	replace_refs_enabled_repo(&repo)
+}
+
+fn replace_refs_enabled_repo(repo: &Repository) -> Result<bool> {
+	if env::var_os("GIT_NO_REPLACE_OBJECTS").is_some() {
+		return Ok(false);
+	}
+
+	Ok(repo
+		.config()?
+		.get_bool("core.useReplaceRefs")
+		.unwrap_or(true))
+}
+
+/// all replace refs sorted by the replaced object
+pub fn get_replace_refs(
+	repo_path: &RepoPath,
+) -> Result<Vec<ReplaceRef>> {
+	scope_time!("get_replace_refs");
+
+	let repo = repo(repo_path)?;
+
+	replace_refs_repo(&repo)
+}
+
+fn replace_refs_repo(repo: &Repository) -> Result<Vec<ReplaceRef>> {
+	let base = replace_ref_base();
+	let mut res = Vec::new();
+
+	for reference in repo.references_glob(&format!("{base}*"))? {
+		let reference = reference?;
+		let (Some(original), Some(replacement)) = (
+			reference
+				.name()
+				.and_then(|name| name.strip_prefix(base.as_str()))
+				.and_then(|hex| Oid::from_str(hex).ok()),
+			reference.target(),
+		) else {
+			continue;
+		};
+
+		let graft = match (
+			repo.find_commit(original),
+			repo.find_commit(replacement),
+		) {
+			(Ok(original), Ok(replacement)) => {
+				original.tree_id() == replacement.tree_id()
+			}
+			_ => false,
+		};
+
+		res.push(ReplaceRef {
+			original: original.into(),
+			replacement: replacement.into(),
+			graft,
+		});
+	}
+
+	res.sort_by_key(|replace| replace.original);
+
+	Ok(res)
+}
+
+/// commits to walk in place of others when history is viewed through
+/// replace refs, empty if git would not use them
+pub fn get_commit_replacements(
+	repo: &Repository,
+) -> Result<HashMap<CommitId, CommitId>> {
+	if !replace_refs_enabled_repo(repo)? {
+		return Ok(HashMap::new());
+	}
+
+	let is_commit = |id: CommitId| {
+		repo.find_object(id.into(), None).is_ok_and(|object| {
+			object.kind() == Some(ObjectType::Commit)
+		})
+	};
+
+	Ok(replace_refs_repo(repo)?
+		.into_iter()
+		.filter(|replace| {
+			is_commit(replace.original)
+				&& is_commit(replace.replacement)
+		})
+		.map(|replace| (replace.original, replace.replacement))
+		.collect())
+}
+
+/// stops replacing `original`, the replacement object is kept
+pub fn delete_replace_ref(
+	repo_path: &RepoPath,
+	original: CommitId,
+) -> Result<()> {
+	scope_time!("delete_replace_ref");
+
+	let repo = repo(repo_path)?;
+	repo.find_reference(&format!(
+		"{}{original}",
+		replace_ref_base()
+	))?
+	.delete()?;
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		gix_repo,
+		tests::{repo_init, write_commit_file},
+		LogWalker, LogWalkerWithoutFilter,
+	};
+
+	#[test]
+	fn test_graft() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let old = write_commit_file(&repo, "a", "1", "old history");
+		let first = write_commit_file(&repo, "a", "2", "first");
+		let head = write_commit_file(&repo, "a", "3", "second");
+
+		assert!(get_replace_refs(repo_path).unwrap().is_empty());
+
+		// like `git replace --graft <first>`: cut off old history
+		let commit = repo.find_commit(first.into()).unwrap();
+		let graft = repo
+			.commit(
+				None,
+				&commit.author(),
+				&commit.committer(),
+				commit.message().unwrap(),
+				&commit.tree().unwrap(),
+				&[],
+			)
+			.unwrap();
+		repo.reference(
+			&format!("refs/replace/{first}"),
+			graft,
+			false,
+			"",
+		)
+		.unwrap();
+
+		assert_eq!(
+			get_replace_refs(repo_path).unwrap(),
+			vec![ReplaceRef {
+				original: first,
+				replacement: graft.into(),
+				graft: true,
+			}]
+		);
+
+		let walk = |replacements| {
+			let mut items = vec![];
+			LogWalker::new(&repo, 100)
+				.unwrap()
+				.replacements(replacements)
+				.read(&mut items)
+				.unwrap();
+			items
+		};
+
+		let replacements = get_commit_replacements(&repo).unwrap();
+		assert_eq!(walk(replacements), vec![head, graft.into()]);
+		assert_eq!(walk(HashMap::new()).len(), 4);
+		assert!(walk(HashMap::new()).contains(&old));
+
+		// gix must not follow the replace ref on its own
+		let mut repo = gix_repo(repo_path).unwrap();
+		let mut items = vec![];
+		LogWalkerWithoutFilter::new(&mut repo, 100)
+			.unwrap()
+			.read(&mut items)
+			.unwrap();
+		assert_eq!(items.len(), 4);
+
+		delete_replace_ref(repo_path, first).unwrap();
+		assert!(get_replace_refs(repo_path).unwrap().is_empty());
+	}
+}
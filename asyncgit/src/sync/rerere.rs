@@ -0,0 +1,695 @@
+//! reuse of recorded conflict resolutions, compatible with the
+//! `rr-cache` of `git rerere`
+
+use super::{repository::repo, utils::work_dir, RepoPath};
+use crate::error::{Error, Result};
+use git2::{
+	Index, IndexConflict, IndexEntry, MergeFileOptions, Repository,
+};
+use scopetime::scope_time;
+use std::{
+	collections::HashSet,
+	fs,
+	path::{Path, PathBuf},
+};
+
+const MARKER_SIZE: usize = 7;
+const MERGE_RR: &str = "MERGE_RR";
+const PREIMAGE: &str = "preimage";
+const POSTIMAGE: &str = "postimage";
+
+/// what [`rerere`] did
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RerereResult {
+	/// conflicts the working dir file got resolved of with a
+	/// recorded resolution, the paths stay conflicted until staged
+	pub resolved: Vec<String>,
+	/// resolutions that got recorded because they were staged
+	pub recorded: Vec<String>,
+}
+
+/// conflicted path and its conflict id (`<hash>[.<variant>]`)
+type MergeRr = Vec<(String, String)>;
+
+/// `rerere.enabled`, like git it defaults to whether there is a
+/// `rr-cache` already
+pub fn rerere_enabled(repo_path: &RepoPath) -> Result<bool> {
+	let repo = repo(repo_path)?;
+
+	Ok(rerere_enabled_repo(&repo))
+}
+
+/// what `git rerere` does: remembers the conflicts that are new, resolves
+/// those a resolution is recorded for and records the resolutions of
+/// conflicts that got staged since
+pub fn rerere(repo_path: &RepoPath) -> Result<RerereResult> {
+	scope_time!("rerere");
+
+	let repo = repo(repo_path)?;
+	let mut res = RerereResult::default();
+
+	if !rerere_enabled_repo(&repo) {
+		return Ok(res);
+	}
+
+	let work_dir = work_dir(&repo)?;
+	let index = repo.index()?;
+	let conflicts = text_conflicts(&index)?;
+	let mut merge_rr = read_merge_rr(&repo);
+
+	for (path, stages) in &conflicts {
+		let file = work_dir.join(path);
+		let Ok(content) = fs::read(&file) else {
+			continue;
+		};
+
+		let id = if let Some(id) = merge_rr_id(&merge_rr, path) {
+			id.to_string()
+		} else {
+			let Some((id, preimage)) = normalize(&content) else {
+				continue;
+			};
+
+			write_image(&repo, &id, PREIMAGE, &preimage)?;
+			merge_rr.push((id.clone(), path.clone()));
+			id
+		};
+
+		if let Some(resolved) =
+			apply_resolution(&repo, stages, &id, &content)?
+		{
+			fs::write(&file, resolved)?;
+			res.resolved.push(path.clone());
+		}
+	}
+
+	let mut remaining = MergeRr::new();
+	for (id, path) in merge_rr {
+		if conflicts.iter().any(|(conflict, _)| *conflict == path) {
+			remaining.push((id, path));
+			continue;
+		}
+
+		let postimage = image_path(&repo, &id, POSTIMAGE);
+		if postimage.exists() {
+			continue;
+		}
+
+		if let Ok(content) = fs::read(work_dir.join(&path)) {
+			if normalize(&content).is_none() {
+				write_image(&repo, &id, POSTIMAGE, &content)?;
+				res.recorded.push(path);
+			}
+		}
+	}
+
+	write_merge_rr(&repo, &remaining)?;
+
+	Ok(res)
+}
+
+/// records the conflict free working dir file of the conflicted `path`
+/// as resolution of its conflict
+pub fn rerere_record(repo_path: &RepoPath, path: &str) -> Result<()> {
+	scope_time!("rerere_record");
+
+	let repo = repo(repo_path)?;
+
+	let content = fs::read(work_dir(&repo)?.join(path))?;
+	if normalize(&content).is_some() {
+		return Err(Error::Generic(format!(
+			"'{path}' still has conflict markers"
+		)));
+	}
+
+	let id = remembered_conflict(&repo, path)?;
+
+	write_image(&repo, &id, POSTIMAGE, &content)
+}
+
+/// whether a resolution is recorded for the conflict of `path`
+pub fn rerere_has_resolution(
+	repo_path: &RepoPath,
+	path: &str,
+) -> Result<bool> {
+	scope_time!("rerere_has_resolution");
+
+	let repo = repo(repo_path)?;
+
+	let id =
+		if let Some(id) = merge_rr_id(&read_merge_rr(&repo), path) {
+			id.to_string()
+		} else {
+			match index_conflict(&repo, path)? {
+				Some((id, _)) => id,
+				None => return Ok(false),
+			}
+		};
+
+	Ok(image_path(&repo, &id, POSTIMAGE).exists())
+}
+
+/// drops the recorded resolution of the conflict of `path` and puts
+/// the conflict back into the working dir file to resolve it again
+pub fn rerere_forget(repo_path: &RepoPath, path: &str) -> Result<()> {
+	scope_time!("rerere_forget");
+
+	let repo = repo(repo_path)?;
+
+	let stages = repo.index()?.conflict_get(Path::new(path))?;
+	let Some(image) = conflict_image(&repo, &stages)? else {
+		return Err(no_text_conflict(path));
+	};
+
+	let id = remembered_conflict(&repo, path)?;
+
+	let postimage = image_path(&repo, &id, POSTIMAGE);
+	if postimage.exists() {
+		fs::remove_file(postimage)?;
+	}
+
+	fs::write(work_dir(&repo)?.join(path), image)?;
+
+	Ok(())
+}
+
+/// conflicted paths whose working dir file got resolved with a
+/// recorded resolution
+pub fn rerere_resolved_paths(
+	repo: &Repository,
+) -> Result<HashSet<String>> {
+	let merge_rr = read_merge_rr(repo);
+	if merge_rr.is_empty() {
+		return Ok(HashSet::new());
+	}
+
+	let work_dir = work_dir(repo)?;
+
+	Ok(merge_rr
+		.into_iter()
+		.filter(|(id, path)| {
+			image_path(repo, id, POSTIMAGE).exists()
+				&& fs::read(work_dir.join(path)).is_ok_and(
+					|content| normalize(&content).is_none(),
+				)
+		})
+		.map(|(_, path)| path)
+		.collect())
+}
+
+/// forgets the current conflicts when the operation is aborted, so
+/// that their state is not recorded as resolution
+pub fn rerere_clear(repo: &Repository) -> Result<()> {
+	for (id, _) in read_merge_rr(repo) {
+		if image_path(repo, &id, POSTIMAGE).exists() {
+			continue;
+		}
+
+		let preimage = image_path(repo, &id, PREIMAGE);
+		if preimage.exists() {
+			fs::remove_file(&preimage)?;
+		}
+		if let Some(dir) = preimage.parent() {
+			// only removed if empty
+			fs::remove_dir(dir).ok();
+		}
+	}
+
+	write_merge_rr(repo, &[])
+}
+
+fn rerere_enabled_repo(repo: &Repository) -> bool {
+	repo.config()
+		.and_then(|config| config.get_bool("rerere.enabled"))
+		.unwrap_or_else(|_| rr_cache(repo).is_dir())
+}
+
+fn rr_cache(repo: &Repository) -> PathBuf {
+	repo.commondir().join("rr-cache")
+}
+
+/// `image` of the conflict with `id` (`<hash>[.<variant>]`)
+fn image_path(repo: &Repository, id: &str, image: &str) -> PathBuf {
+	let (hash, image) = id.split_once('.').map_or_else(
+		|| (id, image.to_string()),
+		|(hash, variant)| (hash, format!("{image}.{variant}")),
+	);
+
+	rr_cache(repo).join(hash).join(image)
+}
+
+fn write_image(
+	repo: &Repository,
+	id: &str,
+	image: &str,
+	content: &[u8],
+) -> Result<()> {
+	let path = image_path(repo, id, image);
+
+	if let Some(dir) = path.parent() {
+		fs::create_dir_all(dir)?;
+	}
+	fs::write(path, content)?;
+
+	Ok(())
+}
+
+fn read_merge_rr(repo: &Repository) -> MergeRr {
+	let Ok(content) = fs::read(repo.path().join(MERGE_RR)) else {
+		return MergeRr::new();
+	};
+
+	content
+		.split(|byte| *byte == 0)
+		.filter_map(|entry| {
+			let entry = std::str::from_utf8(entry).ok()?;
+			let (id, path) = entry.split_once('\t')?;
+			Some((id.to_string(), path.to_string()))
+		})
+		.collect()
+}
+
+fn write_merge_rr(
+	repo: &Repository,
+	merge_rr: &[(String, String)],
+) -> Result<()> {
+	let path = repo.path().join(MERGE_RR);
+
+	if merge_rr.is_empty() {
+		if path.exists() {
+			fs::remove_file(path)?;
+		}
+		return Ok(());
+	}
+
+	let mut content = Vec::new();
+	for (id, conflict) in merge_rr {
+		content.extend_from_slice(id.as_bytes());
+		content.push(b'\t');
+		content.extend_from_slice(conflict.as_bytes());
+		content.push(0);
+	}
+	fs::write(path, content)?;
+
+	Ok(())
+}
+
+fn merge_rr_id<'a>(
+	merge_rr: &'a MergeRr,
+	path: &str,
+) -> Option<&'a str> {
+	merge_rr
+		.iter()
+		.find(|(_, conflict)| conflict == path)
+		.map(|(id, _)| id.as_str())
+}
+
+/// id of the conflict of `path`, remembers it if it is not yet
+fn remembered_conflict(
+	repo: &Repository,
+	path: &str,
+) -> Result<String> {
+	let mut merge_rr = read_merge_rr(repo);
+
+	if let Some(id) = merge_rr_id(&merge_rr, path) {
+		return Ok(id.to_string());
+	}
+
+	let Some((id, preimage)) = index_conflict(repo, path)? else {
+		return Err(no_text_conflict(path));
+	};
+
+	write_image(repo, &id, PREIMAGE, &preimage)?;
+	merge_rr.push((id.clone(), path.to_string()));
+	write_merge_rr(repo, &merge_rr)?;
+
+	Ok(id)
+}
+
+/// id and preimage of the conflict of `path` recreated from the index
+fn index_conflict(
+	repo: &Repository,
+	path: &str,
+) -> Result<Option<(String, Vec<u8>)>> {
+	let Ok(stages) = repo.index()?.conflict_get(Path::new(path))
+	else {
+		return Ok(None);
+	};
+
+	Ok(conflict_image(repo, &stages)?
+		.and_then(|image| normalize(&image)))
+}
+
+fn no_text_conflict(path: &str) -> Error {
+	Error::Generic(format!("'{path}' has no conflicting content"))
+}
+
+/// conflicted paths that have both sides
+fn text_conflicts(
+	index: &Index,
+) -> Result<Vec<(String, IndexConflict)>> {
+	let mut res = Vec::new();
+
+	for stages in index.conflicts()? {
+		let stages = stages?;
+
+		if let (Some(ours), Some(_)) = (&stages.our, &stages.their) {
+			let path = String::from_utf8(ours.path.clone())?;
+			res.push((path, stages));
+		}
+	}
+
+	Ok(res)
+}
+
+/// the file with conflict markers merging the stages produces
+fn conflict_image(
+	repo: &Repository,
+	stages: &IndexConflict,
+) -> Result<Option<Vec<u8>>> {
+	let (Some(ours), Some(theirs)) = (&stages.our, &stages.their)
+	else {
+		return Ok(None);
+	};
+
+	let empty;
+	let ancestor = if let Some(ancestor) = &stages.ancestor {
+		ancestor
+	} else {
+		empty = blob_entry(repo, ours, &[])?;
+		&empty
+	};
+
+	let mut opts = MergeFileOptions::new();
+	opts.our_label("ours").their_label("theirs");
+
+	let merged = repo.merge_file_from_index(
+		ancestor,
+		ours,
+		theirs,
+		Some(&mut opts),
+	)?;
+
+	Ok(Some(merged.content().to_vec()))
+}
+
+/// the recorded resolution merged into the conflicts of `content`,
+/// `None` if there is none or it does not apply
+fn apply_resolution(
+	repo: &Repository,
+	stages: &IndexConflict,
+	id: &str,
+	content: &[u8],
+) -> Result<Option<Vec<u8>>> {
+	let (Ok(preimage), Ok(postimage), Some(ours)) = (
+		fs::read(image_path(repo, id, PREIMAGE)),
+		fs::read(image_path(repo, id, POSTIMAGE)),
+		&stages.our,
+	) else {
+		return Ok(None);
+	};
+
+	let Some((_, current)) = normalize(content) else {
+		return Ok(None);
+	};
+
+	if current == preimage {
+		return Ok(Some(postimage));
+	}
+
+	// like git: the changes from preimage to postimage applied to
+	// the current conflicts which differ outside of the conflicts
+	let merged = repo.merge_file_from_index(
+		&blob_entry(repo, ours, &preimage)?,
+		&blob_entry(repo, ours, &current)?,
+		&blob_entry(repo, ours, &postimage)?,
+		None,
+	)?;
+
+	Ok(merged.is_automergeable().then(|| merged.content().to_vec()))
+}
+
+fn blob_entry(
+	repo: &Repository,
+	template: &IndexEntry,
+	content: &[u8],
+) -> Result<IndexEntry> {
+	Ok(IndexEntry {
+		id: repo.blob(content)?,
+		file_size: u32::try_from(content.len()).unwrap_or(u32::MAX),
+		path: template.path.clone(),
+		..*template
+	})
+}
+
+/// the conflicts of `content` like git stores them in a preimage:
+/// without labels and the common ancestor part and with the sides
+/// sorted, so that it does not matter which side is ours. also
+/// returns the id of the conflicts. `None` if there are none (or the
+/// markers are broken).
+fn normalize(content: &[u8]) -> Option<(String, Vec<u8>)> {
+	let mut lines = content.split_inclusive(|byte| *byte == b'\n');
+	let mut hasher = gix::hash::hasher(gix::hash::Kind::Sha1);
+	let mut out = Vec::with_capacity(content.len());
+	let mut conflicts = false;
+
+	while let Some(line) = lines.next() {
+		if is_marker(line, b'<') {
+			let (one, two) = conflict_sides(&mut lines)?;
+
+			hasher.update(&one);
+			hasher.update(&[0]);
+			hasher.update(&two);
+			hasher.update(&[0]);
+
+			put_conflict(&mut out, &one, &two);
+			conflicts = true;
+		} else {
+			out.extend_from_slice(line);
+		}
+	}
+
+	if !conflicts {
+		return None;
+	}
+
+	let id = hasher.try_finalize().ok()?;
+	Some((id.to_string(), out))
+}
+
+/// both sides of the conflict whose start marker was just read, the
+/// smaller one first
+fn conflict_sides<'a>(
+	lines: &mut impl Iterator<Item = &'a [u8]>,
+) -> Option<(Vec<u8>, Vec<u8>)> {
+	#[derive(PartialEq, Eq)]
+	enum Part {
+		One,
+		Ancestor,
+		Two,
+	}
+
+	let mut part = Part::One;
+	let mut one = Vec::new();
+	let mut two = Vec::new();
+
+	while let Some(line) = lines.next() {
+		if is_marker(line, b'<') {
+			let (nested_one, nested_two) = conflict_sides(lines)?;
+			let side = if part == Part::One {
+				&mut one
+			} else {
+				&mut two
+			};
+			put_conflict(side, &nested_one, &nested_two);
+		} else if is_marker(line, b'|') {
+			if part != Part::One {
+				return None;
+			}
+			part = Part::Ancestor;
+		} else if is_marker(line, b'=') {
+			if part == Part::Two {
+				return None;
+			}
+			part = Part::Two;
+		} else if is_marker(line, b'>') {
+			if part != Part::Two {
+				return None;
+			}
+			if one > two {
+				std::mem::swap(&mut one, &mut two);
+			}
+			return Some((one, two));
+		} else {
+			match part {
+				Part::One => one.extend_from_slice(line),
+				Part::Ancestor => (),
+				Part::Two => two.extend_from_slice(line),
+			}
+		}
+	}
+
+	None
+}
+
+fn put_conflict(out: &mut Vec<u8>, one: &[u8], two: &[u8]) {
+	let marker = |c: u8| {
+		let mut line = vec![c; MARKER_SIZE];
+		line.push(b'\n');
+		line
+	};
+
+	out.extend(marker(b'<'));
+	out.extend_from_slice(one);
+	out.extend(marker(b'='));
+	out.extend_from_slice(two);
+	out.extend(marker(b'>'));
+}
+
+/// `<<<<<<<` and `>>>>>>>` are followed by a label, the others may be
+fn is_marker(line: &[u8], c: u8) -> bool {
+	let needs_label = c == b'<' || c == b'>';
+
+	line.len() > MARKER_SIZE
+		&& line[..MARKER_SIZE].iter().all(|byte| *byte == c)
+		&& if needs_label {
+			line[MARKER_SIZE] == b' '
+		} else {
+			line[MARKER_SIZE].is_ascii_whitespace()
+		}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		checkout_branch, create_branch, merge_branch, stage_add_file,
+		tests::{repo_init, write_commit_file},
+	};
+	use git2::BranchType;
+	use pretty_assertions::assert_eq;
+	use tempfile::TempDir;
+
+	/// merging `other` into `master` conflicts in `a.txt`
+	fn conflicted_repo(
+		ours: &str,
+		theirs: &str,
+	) -> (TempDir, Repository, RepoPath) {
+		let (td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: RepoPath =
+			root.as_os_str().to_str().unwrap().into();
+
+		repo.config()
+			.unwrap()
+			.set_bool("rerere.enabled", true)
+			.unwrap();
+
+		write_commit_file(
+			&repo,
+			"a.txt",
+			"start\nbase\nend\n",
+			"base",
+		);
+		create_branch(&repo_path, "other").unwrap();
+		write_commit_file(&repo, "a.txt", theirs, "theirs");
+		checkout_branch(&repo_path, "master").unwrap();
+		write_commit_file(&repo, "a.txt", ours, "ours");
+
+		merge_branch(&repo_path, "other", BranchType::Local).unwrap();
+
+		(td, repo, repo_path)
+	}
+
+	fn read(repo: &Repository, path: &str) -> String {
+		fs::read_to_string(repo.workdir().unwrap().join(path))
+			.unwrap()
+	}
+
+	#[test]
+	fn test_normalize() {
+		let content = b"a\n<<<<<<< ours\ny\n||||||| base\nb\n=======\nx\n>>>>>>> theirs\nz";
+
+		let (id, preimage) = normalize(content).unwrap();
+
+		assert_eq!(
+			String::from_utf8(preimage).unwrap(),
+			"a\n<<<<<<<\nx\n=======\ny\n>>>>>>>\nz"
+		);
+		// sha1 of "x\n\0y\n\0" like git computes it
+		assert_eq!(id, "1d34bb1dd367248866f56bde41ecc561d2155cf0");
+
+		assert_eq!(normalize(b"a\n<<<<<<< ours\nb\n"), None);
+		assert_eq!(normalize(b"a\n=======\nb\n"), None);
+	}
+
+	#[test]
+	fn test_reuse_resolution() {
+		let (_td, repo, repo_path) = conflicted_repo(
+			"start\nours\nend\n",
+			"start\ntheirs\nend\n",
+		);
+
+		let res = rerere(&repo_path).unwrap();
+		assert_eq!(res, RerereResult::default());
+		assert!(!rerere_has_resolution(&repo_path, "a.txt").unwrap());
+
+		fs::write(
+			repo.workdir().unwrap().join("a.txt"),
+			"start\nresolved\nend\n",
+		)
+		.unwrap();
+		stage_add_file(&repo_path, Path::new("a.txt")).unwrap();
+
+		let res = rerere(&repo_path).unwrap();
+		assert_eq!(res.recorded, vec![String::from("a.txt")]);
+		assert!(!repo.path().join(MERGE_RR).exists());
+
+		// the same conflict again
+		crate::sync::abort_pending_state(&repo_path).unwrap();
+		merge_branch(&repo_path, "other", BranchType::Local).unwrap();
+
+		let res = rerere(&repo_path).unwrap();
+		assert_eq!(res.resolved, vec![String::from("a.txt")]);
+		assert_eq!(read(&repo, "a.txt"), "start\nresolved\nend\n");
+		assert_eq!(
+			rerere_resolved_paths(&repo).unwrap(),
+			HashSet::from([String::from("a.txt")])
+		);
+
+		rerere_forget(&repo_path, "a.txt").unwrap();
+
+		assert!(read(&repo, "a.txt").contains("<<<<<<< ours"));
+		assert!(rerere_resolved_paths(&repo).unwrap().is_empty());
+		assert!(!rerere_has_resolution(&repo_path, "a.txt").unwrap());
+	}
+
+	#[test]
+	fn test_record_and_clear() {
+		let (_td, repo, repo_path) = conflicted_repo(
+			"start\nours\nend\n",
+			"start\ntheirs\nend\n",
+		);
+
+		let err = rerere_record(&repo_path, "a.txt");
+		assert!(err.is_err());
+
+		fs::write(
+			repo.workdir().unwrap().join("a.txt"),
+			"start\nboth\nend\n",
+		)
+		.unwrap();
+
+		rerere_record(&repo_path, "a.txt").unwrap();
+		assert!(rerere_has_resolution(&repo_path, "a.txt").unwrap());
+
+		// aborting does not record the reset file
+		crate::sync::abort_pending_state(&repo_path).unwrap();
+		assert!(!repo.path().join(MERGE_RR).exists());
+
+		merge_branch(&repo_path, "other", BranchType::Local).unwrap();
+		let res = rerere(&repo_path).unwrap();
+		assert_eq!(res.resolved, vec![String::from("a.txt")]);
+		assert_eq!(read(&repo, "a.txt"), "start\nboth\nend\n");
+	}
+}
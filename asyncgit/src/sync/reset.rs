@@ -1,5 +1,12 @@
 use super::{utils::get_head_repo, CommitId, RepoPath};
-use crate::{error::Result, sync::repository::repo};
+use crate::{
+	error::Result,
+	sync::{
+		index_file::check_index_writable,
+		oplog::{oplog_record, OpKind},
+		repository::repo,
+	},
+};
 use git2::{build::CheckoutBuilder, ObjectType, ResetType};
 use scopetime::scope_time;
 
@@ -7,15 +14,31 @@ use scopetime::scope_time;
 pub fn reset_stage(repo_path: &RepoPath, path: &str) -> Result<()> {
 	scope_time!("reset_stage");
 
+	reset_stage_paths(repo_path, &[path])
+}
+
+/// unstages all `paths` with a single index write, an empty list
+/// does nothing (instead of unstaging everything)
+pub fn reset_stage_paths(
+	repo_path: &RepoPath,
+	paths: &[&str],
+) -> Result<()> {
+	scope_time!("reset_stage_paths");
+
+	if paths.is_empty() {
+		return Ok(());
+	}
+
 	let repo = repo(repo_path)?;
+	check_index_writable(&repo)?;
 
 	if let Ok(id) = get_head_repo(&repo) {
 		let obj =
 			repo.find_object(id.into(), Some(ObjectType::Commit))?;
 
-		repo.reset_default(Some(&obj), [path])?;
+		repo.reset_default(Some(&obj), paths)?;
 	} else {
-		repo.reset_default(None, [path])?;
+		repo.reset_default(None, paths)?;
 	}
 
 	Ok(())
@@ -47,17 +70,33 @@ pub fn reset_repo(
 	scope_time!("reset_repo");
 
 	let repo = repo(repo_path)?;
+	let old_id = get_head_repo(&repo).ok();
 
-	let c = repo.find_commit(commit.into())?;
+	let result = repo
+		.find_commit(commit.into())
+		.and_then(|c| repo.reset(c.as_object(), kind, None))
+		.map_err(Into::into);
 
-	repo.reset(c.as_object(), kind, None)?;
-
-	Ok(())
+	let kind = match kind {
+		ResetType::Soft => "soft",
+		ResetType::Mixed => "mixed",
+		ResetType::Hard => "hard",
+	};
+	oplog_record(
+		repo_path,
+		OpKind::Reset,
+		&format!("HEAD ({kind})"),
+		old_id,
+		Some(commit),
+		&result,
+	);
+
+	result
 }
 
 #[cfg(test)]
 mod tests {
-	use super::{reset_stage, reset_workdir};
+	use super::{reset_stage, reset_stage_paths, reset_workdir};
 	use crate::error::Result;
 	use crate::sync::{
 		commit,
@@ -100,6 +139,34 @@ mod tests {
 0   newb
 1   end";
 
+	#[test]
+	fn test_reset_stage_paths() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		fs::create_dir_all(root.join("a")).unwrap();
+		for file in ["a/1.txt", "a/2.txt", "a/3.txt"] {
+			fs::write(root.join(file), "content").unwrap();
+			stage_add_file(repo_path, Path::new(file)).unwrap();
+		}
+
+		reset_stage_paths(repo_path, &["a/1.txt", "a/3.txt"])
+			.unwrap();
+
+		let staged = get_status(repo_path, StatusType::Stage, None)
+			.unwrap()
+			.into_iter()
+			.map(|item| item.path)
+			.collect::<Vec<_>>();
+		assert_eq!(staged, vec![String::from("a/2.txt")]);
+
+		reset_stage_paths(repo_path, &[]).unwrap();
+
+		assert_eq!(get_statuses(repo_path), (2, 1));
+	}
+
 	#[test]
 	fn test_reset_only_unstaged() {
 		let (_td, repo) = repo_init().unwrap();
@@ -1,4 +1,4 @@
-use git2::{Oid, RebaseOptions, Repository};
+use git2::{Oid, RebaseOptions, Repository, StatusOptions};
 
 use super::{
 	commit::signature_allow_undefined_name,
@@ -8,6 +8,47 @@ use super::{
 };
 use crate::error::{Error, Result};
 
+/// fails if `commit` can not be reworded safely: the workdir has
+/// uncommitted changes, the commit is not on the current branch or it
+/// is (or is followed by) a merge commit the rebase would flatten
+pub fn reword_check(
+	repo_path: &RepoPath,
+	commit: CommitId,
+) -> Result<()> {
+	let repo = repo(repo_path)?;
+
+	reword_check_repo(&repo, commit.get_oid())
+}
+
+fn reword_check_repo(repo: &Repository, commit: Oid) -> Result<()> {
+	let mut options = StatusOptions::new();
+	options.include_untracked(false).include_ignored(false);
+	if !repo.statuses(Some(&mut options))?.is_empty() {
+		return Err(Error::UncommittedChanges);
+	}
+
+	let head = get_head_repo(repo)?.get_oid();
+	if head != commit && !repo.graph_descendant_of(head, commit)? {
+		return Err(Error::Generic(String::from(
+			"reword error: the commit is not part of the current branch",
+		)));
+	}
+
+	let mut walk = repo.revwalk()?;
+	walk.push(head)?;
+	for parent in repo.find_commit(commit)?.parent_ids() {
+		walk.hide(parent)?;
+	}
+
+	for id in walk {
+		if repo.find_commit(id?)?.parent_count() > 1 {
+			return Err(Error::RewordMergeCommit);
+		}
+	}
+
+	Ok(())
+}
+
 /// This is the same as reword, but will abort and fix the repo if something goes wrong
 pub fn reword(
 	repo_path: &RepoPath,
@@ -17,6 +58,8 @@ pub fn reword(
 	let repo = repo(repo_path)?;
 	let config = repo.config()?;
 
+	reword_check_repo(&repo, commit.get_oid())?;
+
 	if config.get_bool("commit.gpgsign").unwrap_or(false) {
 		// HACK: we undo the last commit and create a new one
 		use crate::sync::utils::undo_last_commit;
@@ -153,9 +196,98 @@ mod tests {
 	use crate::sync::{
 		get_commit_info,
 		tests::{repo_init_empty, write_commit_file},
+		utils::repo_write_file,
 	};
 	use pretty_assertions::assert_eq;
 
+	#[test]
+	fn test_reword_older_commit() {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "foo", "a", "commit1");
+		let oid2 = write_commit_file(&repo, "foo", "ab", "commit2");
+		write_commit_file(&repo, "bar", "a", "commit3");
+
+		let reworded = reword(repo_path, oid2, "reworded").unwrap();
+
+		let head = repo.head().unwrap().peel_to_commit().unwrap();
+		assert_eq!(head.message(), Some("commit3"));
+		assert_eq!(head.parent_id(0).unwrap(), reworded.get_oid());
+		assert_eq!(
+			get_commit_info(repo_path, &reworded).unwrap().message,
+			"reworded"
+		);
+	}
+
+	#[test]
+	fn test_reword_refuses_dirty_workdir() {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "foo", "a", "commit1");
+		let oid2 = write_commit_file(&repo, "foo", "ab", "commit2");
+
+		// untracked files do not matter
+		repo_write_file(&repo, "new", "a").unwrap();
+		assert!(reword_check(repo_path, oid2).is_ok());
+
+		repo_write_file(&repo, "foo", "abc").unwrap();
+		assert!(matches!(
+			reword(repo_path, oid2, "reworded"),
+			Err(Error::UncommittedChanges)
+		));
+		assert_eq!(
+			repo.head().unwrap().peel_to_commit().unwrap().id(),
+			oid2.get_oid()
+		);
+	}
+
+	#[test]
+	fn test_reword_refuses_merges() {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "foo", "a", "commit1");
+		let oid2 = write_commit_file(&repo, "foo", "ab", "commit2");
+
+		// a side commit merged into `commit2` by hand
+		let sig = repo.signature().unwrap();
+		let first = repo.find_commit(oid2.get_oid()).unwrap();
+		let tree = first.tree().unwrap();
+		let side = repo
+			.commit(None, &sig, &sig, "side", &tree, &[&first])
+			.unwrap();
+		let second = repo.find_commit(side).unwrap();
+		let merge = repo
+			.commit(
+				Some("HEAD"),
+				&sig,
+				&sig,
+				"merge",
+				&tree,
+				&[&first, &second],
+			)
+			.unwrap();
+		let head = write_commit_file(&repo, "baz", "a", "commit4");
+
+		assert!(matches!(
+			reword_check(repo_path, oid2),
+			Err(Error::RewordMergeCommit)
+		));
+		assert!(matches!(
+			reword_check(repo_path, merge.into()),
+			Err(Error::RewordMergeCommit)
+		));
+		assert!(reword_check(repo_path, head).is_ok());
+	}
+
 	#[test]
 	fn test_reword() {
 		let (_td, repo) = repo_init_empty().unwrap();
@@ -0,0 +1,1193 @@
+//! continuing and aborting what `git` stopped on conflicts: rebases
+//! it runs from a `git-rebase-todo` and cherry-pick sequences, in
+//! the on disk format `git` reads so either can go on with them
+
+use super::{
+	commit::signature_allow_undefined_name,
+	rebase::{rebase_onto_parent, RebaseProgress, RebaseState},
+	repository::repo,
+	rerere::rerere_clear,
+	CommitId, RepoPath,
+};
+use crate::error::{Error, Result};
+use git2::{
+	build::CheckoutBuilder, CherrypickOptions, Commit, Oid,
+	Repository, RepositoryState, ResetType, Signature, Sort,
+	StatusOptions,
+};
+use scopetime::scope_time;
+use std::{
+	fs,
+	io::{self, ErrorKind},
+	path::Path,
+};
+
+const REBASE_MERGE_DIR: &str = "rebase-merge";
+const REBASE_APPLY_DIR: &str = "rebase-apply";
+const SEQUENCER_DIR: &str = "sequencer";
+const TODO_FILE: &str = "git-rebase-todo";
+const CHERRY_PICK_HEAD_FILE: &str = "CHERRY_PICK_HEAD";
+const REBASE_HEAD_FILE: &str = "REBASE_HEAD";
+const MERGE_MSG_FILE: &str = "MERGE_MSG";
+
+/// one line of a todo list, like `pick 1a2b3c4 subject`
+struct Step {
+	line: String,
+	command: String,
+	commit: Option<String>,
+}
+
+impl Step {
+	fn parse(line: &str) -> Option<Self> {
+		let trimmed = line.trim();
+		if trimmed.is_empty() || trimmed.starts_with('#') {
+			return None;
+		}
+
+		let mut parts = trimmed.split_whitespace();
+		let command = parts.next()?.to_string();
+		let commit = parts.next().map(String::from);
+
+		Some(Self {
+			line: trimmed.to_string(),
+			command,
+			commit,
+		})
+	}
+
+	fn commit<'a>(&self, repo: &'a Repository) -> Result<Commit<'a>> {
+		let id = self.commit.as_deref().ok_or_else(|| {
+			Error::Generic(format!("no commit in: {}", self.line))
+		})?;
+
+		Ok(repo.revparse_single(id)?.peel_to_commit()?)
+	}
+}
+
+fn read_steps(path: &Path) -> Result<Vec<Step>> {
+	Ok(read_optional(path)?
+		.unwrap_or_default()
+		.lines()
+		.filter_map(Step::parse)
+		.collect())
+}
+
+fn write_steps(path: &Path, steps: &[Step]) -> Result<()> {
+	let lines: Vec<&str> =
+		steps.iter().map(|step| step.line.as_str()).collect();
+
+	fs::write(path, format!("{}\n", lines.join("\n")))?;
+
+	Ok(())
+}
+
+fn read_optional(path: &Path) -> Result<Option<String>> {
+	match fs::read_to_string(path) {
+		Ok(content) => Ok(Some(content)),
+		Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+		Err(e) => Err(e.into()),
+	}
+}
+
+fn remove_optional(path: &Path) -> io::Result<()> {
+	let res = if path.is_dir() {
+		fs::remove_dir_all(path)
+	} else {
+		fs::remove_file(path)
+	};
+
+	match res {
+		Err(e) if e.kind() != ErrorKind::NotFound => Err(e),
+		_ => Ok(()),
+	}
+}
+
+fn read_id(path: &Path) -> Result<Option<Oid>> {
+	read_optional(path)?
+		.map(|content| Ok(Oid::from_str(content.trim())?))
+		.transpose()
+}
+
+/// removes comments and surplus whitespace like `git commit` does
+fn cleanup_message(msg: &str) -> Result<String> {
+	Ok(git2::message_prettify(msg, Some(b'#'))?)
+}
+
+fn index_matches_head(repo: &Repository) -> Result<bool> {
+	let tree = repo.index()?.write_tree()?;
+
+	Ok(repo.head()?.peel_to_tree()?.id() == tree)
+}
+
+/// applies `commit` on `HEAD` to the index and working tree, `false`
+/// if it conflicts (`CHERRY_PICK_HEAD` and `MERGE_MSG` are left)
+fn pick(repo: &Repository, commit: &Commit) -> Result<bool> {
+	let mut options = CherrypickOptions::new();
+	if commit.parent_count() > 1 {
+		options.mainline(1);
+	}
+
+	repo.cherrypick(commit, Some(&mut options))?;
+
+	Ok(!repo.index()?.has_conflicts())
+}
+
+/// commits the index on `HEAD` keeping the author of `original`,
+/// nothing if it does not change anything (like `git` drops commits
+/// that became empty)
+fn commit_pick(
+	repo: &Repository,
+	original: &Commit,
+	msg: &str,
+	committer: &Signature,
+) -> Result<()> {
+	if index_matches_head(repo)? {
+		return Ok(());
+	}
+
+	let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+	let head = repo.head()?.peel_to_commit()?;
+
+	repo.commit(
+		Some("HEAD"),
+		&original.author(),
+		committer,
+		msg,
+		&tree,
+		&[&head],
+	)?;
+
+	Ok(())
+}
+
+/// replaces `HEAD` by a commit with the index as its tree, for
+/// `fixup` and `squash` steps
+fn fold_into_head(
+	repo: &Repository,
+	msg: &str,
+	committer: &Signature,
+) -> Result<()> {
+	let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+	let head = repo.head()?.peel_to_commit()?;
+	let parents = head.parents().collect::<Vec<_>>();
+
+	let id = repo.commit(
+		None,
+		&head.author(),
+		committer,
+		msg,
+		&tree,
+		&parents.iter().collect::<Vec<_>>(),
+	)?;
+	repo.set_head_detached(id)?;
+
+	Ok(())
+}
+
+fn squash_message(
+	repo: &Repository,
+	commit: &Commit,
+) -> Result<String> {
+	let head = repo.head()?.peel_to_commit()?;
+
+	Ok(format!(
+		"{}\n\n{}",
+		head.message().unwrap_or_default().trim_end(),
+		commit.message().unwrap_or_default()
+	))
+}
+
+/// `true` if `git` runs this rebase, gitui's own keep no todo list
+pub fn is_todo_rebase(repo: &Repository) -> bool {
+	repo.path().join(REBASE_MERGE_DIR).join(TODO_FILE).exists()
+		|| repo.path().join(REBASE_APPLY_DIR).exists()
+}
+
+/// progress of a rebase `git` runs
+pub fn todo_rebase_progress(
+	repo: &Repository,
+) -> Result<RebaseProgress> {
+	let (dir, current, steps) =
+		if repo.path().join(REBASE_APPLY_DIR).exists() {
+			(REBASE_APPLY_DIR, "next", "last")
+		} else {
+			(REBASE_MERGE_DIR, "msgnum", "end")
+		};
+	let dir = repo.path().join(dir);
+
+	let number = |file: &str| -> usize {
+		read_optional(&dir.join(file))
+			.ok()
+			.flatten()
+			.and_then(|content| content.trim().parse().ok())
+			.unwrap_or_default()
+	};
+
+	Ok(RebaseProgress {
+		steps: number(steps),
+		current: number(current).saturating_sub(1),
+		current_commit: read_id(&repo.path().join(REBASE_HEAD_FILE))?
+			.map(CommitId::from),
+	})
+}
+
+/// stops at `step` for conflicts like `git` does, so `git rebase
+/// --continue` can pick it up too
+fn stop_rebase(
+	repo: &Repository,
+	dir: &Path,
+	commit: &Commit,
+	msg: &str,
+) -> Result<()> {
+	fs::write(
+		repo.path().join(REBASE_HEAD_FILE),
+		commit.id().to_string(),
+	)?;
+	fs::write(dir.join("stopped-sha"), commit.id().to_string())?;
+	fs::write(dir.join("message"), msg)?;
+
+	let author = commit.author();
+	let quote = |value: &str| value.replace('\'', "'\\''");
+	let offset = author.when().offset_minutes();
+	fs::write(
+		dir.join("author-script"),
+		format!(
+			"GIT_AUTHOR_NAME='{}'\nGIT_AUTHOR_EMAIL='{}'\nGIT_AUTHOR_DATE='@{} {}{:02}{:02}'\n",
+			quote(author.name().unwrap_or_default()),
+			quote(author.email().unwrap_or_default()),
+			author.when().seconds(),
+			if offset < 0 { '-' } else { '+' },
+			offset.abs() / 60,
+			offset.abs() % 60,
+		),
+	)?;
+
+	// `git` would take this for a cherry-pick of its own
+	remove_optional(&repo.path().join(CHERRY_PICK_HEAD_FILE))?;
+
+	Ok(())
+}
+
+/// commits what the step `git` stopped at left in the index
+fn commit_stopped_step(
+	repo: &Repository,
+	dir: &Path,
+	committer: &Signature,
+) -> Result<()> {
+	let Some(stopped) = read_id(&repo.path().join(REBASE_HEAD_FILE))?
+	else {
+		return Ok(());
+	};
+	let commit = repo.find_commit(stopped)?;
+
+	// `edit` steps stop with the commit made already
+	let command = if let Some(amend) = read_id(&dir.join("amend"))? {
+		// it was undone to split it up, folding the rest into the
+		// commit before it would lose it
+		if repo.head()?.target() != Some(amend)
+			&& !index_matches_head(repo)?
+		{
+			return Err(Error::Generic(String::from(
+				"rebase error: commit the staged changes first",
+			)));
+		}
+
+		String::from("fixup")
+	} else {
+		read_steps(&dir.join("done"))?
+			.pop()
+			.map(|step| step.command)
+			.unwrap_or_default()
+	};
+
+	// `git` asks for the new message once the step is resolved
+	if matches!(command.as_str(), "reword" | "r") {
+		return Err(Error::SequencerStep(command));
+	}
+
+	match command.as_str() {
+		"fixup" | "f" => {
+			if !index_matches_head(repo)? {
+				let msg = repo.head()?.peel_to_commit()?;
+				fold_into_head(
+					repo,
+					msg.message().unwrap_or_default(),
+					committer,
+				)?;
+			}
+		}
+		"squash" | "s" => {
+			let msg =
+				cleanup_message(&squash_message(repo, &commit)?)?;
+			fold_into_head(repo, &msg, committer)?;
+		}
+		_ => {
+			let msg = read_optional(&dir.join("message"))?
+				.unwrap_or_else(|| {
+					commit.message().unwrap_or_default().to_string()
+				});
+			commit_pick(
+				repo,
+				&commit,
+				&cleanup_message(&msg)?,
+				committer,
+			)?;
+		}
+	}
+
+	for file in ["stopped-sha", "message", "author-script", "amend"] {
+		remove_optional(&dir.join(file))?;
+	}
+	for file in
+		[REBASE_HEAD_FILE, MERGE_MSG_FILE, CHERRY_PICK_HEAD_FILE]
+	{
+		remove_optional(&repo.path().join(file))?;
+	}
+
+	Ok(())
+}
+
+/// commits the resolved step and applies the remaining ones of a
+/// rebase `git` runs, until one conflicts or the branch is rebased
+pub fn continue_todo_rebase(
+	repo: &Repository,
+) -> Result<RebaseState> {
+	if repo.path().join(REBASE_APPLY_DIR).exists() {
+		return Err(Error::SequencerStep(String::from(
+			"git rebase --apply",
+		)));
+	}
+
+	if repo.index()?.has_conflicts() {
+		return Ok(RebaseState::Conflicted);
+	}
+
+	let dir = repo.path().join(REBASE_MERGE_DIR);
+	let committer = signature_allow_undefined_name(repo)?;
+
+	commit_stopped_step(repo, &dir, &committer)?;
+
+	let mut todo = read_steps(&dir.join(TODO_FILE))?;
+
+	while !todo.is_empty() {
+		let step = todo.remove(0);
+
+		let fold = match step.command.as_str() {
+			"pick" | "p" => false,
+			"fixup" | "f" | "squash" | "s" => true,
+			"drop" | "d" | "noop" => {
+				record_done(&dir, &step, &todo)?;
+				continue;
+			}
+			_ => return Err(Error::SequencerStep(step.command)),
+		};
+
+		let commit = step.commit(repo)?;
+		record_done(&dir, &step, &todo)?;
+
+		let msg = if matches!(step.command.as_str(), "squash" | "s") {
+			squash_message(repo, &commit)?
+		} else {
+			commit.message().unwrap_or_default().to_string()
+		};
+
+		if !pick(repo, &commit)? {
+			stop_rebase(repo, &dir, &commit, &msg)?;
+			return Ok(RebaseState::Conflicted);
+		}
+
+		if fold {
+			let msg =
+				if matches!(step.command.as_str(), "fixup" | "f") {
+					repo.head()?
+						.peel_to_commit()?
+						.message()
+						.unwrap_or_default()
+						.to_string()
+				} else {
+					cleanup_message(&msg)?
+				};
+			fold_into_head(repo, &msg, &committer)?;
+		} else {
+			commit_pick(repo, &commit, &msg, &committer)?;
+		}
+
+		for file in [MERGE_MSG_FILE, CHERRY_PICK_HEAD_FILE] {
+			remove_optional(&repo.path().join(file))?;
+		}
+	}
+
+	finish_todo_rebase(repo, &dir)?;
+
+	Ok(RebaseState::Finished)
+}
+
+fn record_done(dir: &Path, step: &Step, todo: &[Step]) -> Result<()> {
+	let mut done = read_steps(&dir.join("done"))?;
+	done.push(Step {
+		line: step.line.clone(),
+		command: step.command.clone(),
+		commit: step.commit.clone(),
+	});
+	write_steps(&dir.join("done"), &done)?;
+	write_steps(&dir.join(TODO_FILE), todo)?;
+
+	let msgnum = read_optional(&dir.join("msgnum"))?
+		.and_then(|content| content.trim().parse::<usize>().ok())
+		.unwrap_or_default();
+	fs::write(dir.join("msgnum"), format!("{}\n", msgnum + 1))?;
+
+	Ok(())
+}
+
+/// points the rebased branch to the result and checks it out again
+fn finish_todo_rebase(repo: &Repository, dir: &Path) -> Result<()> {
+	let head = repo.head()?.peel_to_commit()?.id();
+
+	if let Some(branch) = read_optional(&dir.join("head-name"))?
+		.map(|name| name.trim().to_string())
+		.filter(|name| name.starts_with("refs/"))
+	{
+		repo.reference(&branch, head, true, "rebase (finish)")?;
+		repo.set_head(&branch)?;
+	}
+
+	remove_optional(dir)?;
+	remove_optional(&repo.path().join(REBASE_HEAD_FILE))?;
+
+	Ok(())
+}
+
+/// starts a rebase `git` can continue too that stops at `target`
+/// with it checked out like an `edit` step, to amend it or to split
+/// it up by undoing it and committing its changes in parts
+pub fn edit_commit(
+	repo_path: &RepoPath,
+	target: CommitId,
+) -> Result<()> {
+	scope_time!("edit_commit");
+
+	let repo = repo(repo_path)?;
+
+	if repo.state() != RepositoryState::Clean {
+		return Err(Error::Generic(String::from(
+			"edit commit error: finish the pending operation first",
+		)));
+	}
+
+	let mut options = StatusOptions::new();
+	options.include_untracked(false).include_ignored(false);
+	if !repo.statuses(Some(&mut options))?.is_empty() {
+		return Err(Error::UncommittedChanges);
+	}
+
+	let onto = rebase_onto_parent(&repo, target.into())?;
+	let head = repo.head()?;
+	let orig_head = head.peel_to_commit()?.id();
+	let head_name = if head.is_branch() {
+		head.name().unwrap_or("detached HEAD").to_string()
+	} else {
+		String::from("detached HEAD")
+	};
+
+	let mut walk = repo.revwalk()?;
+	walk.push(orig_head)?;
+	walk.hide(target.into())?;
+	walk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+	let todo = walk
+		.map(|id| step_line(&repo, "pick", id?))
+		.collect::<Result<Vec<_>>>()?;
+
+	let commit = repo.find_commit(target.into())?;
+	repo.checkout_tree(
+		commit.as_object(),
+		Some(CheckoutBuilder::new().safe()),
+	)?;
+	repo.set_head_detached(commit.id())?;
+
+	let dir = repo.path().join(REBASE_MERGE_DIR);
+	fs::create_dir_all(&dir)?;
+	for (file, content) in [
+		("head-name", head_name),
+		("onto", onto.to_string()),
+		("orig-head", orig_head.to_string()),
+		("interactive", String::new()),
+		("msgnum", String::from("1")),
+		("end", (todo.len() + 1).to_string()),
+		("amend", commit.id().to_string()),
+	] {
+		fs::write(dir.join(file), format!("{content}\n"))?;
+	}
+	fs::write(
+		dir.join("done"),
+		format!("{}\n", step_line(&repo, "edit", commit.id())?),
+	)?;
+	fs::write(dir.join(TODO_FILE), format!("{}\n", todo.join("\n")))?;
+
+	stop_rebase(
+		&repo,
+		&dir,
+		&commit,
+		commit.message().unwrap_or_default(),
+	)
+}
+
+/// like `pick <id> subject`
+fn step_line(
+	repo: &Repository,
+	command: &str,
+	id: Oid,
+) -> Result<String> {
+	let commit = repo.find_commit(id)?;
+
+	Ok(format!(
+		"{command} {id} {}",
+		commit.summary().unwrap_or_default()
+	))
+}
+
+/// goes back to where a rebase `git` runs started
+pub fn abort_todo_rebase(repo: &Repository) -> Result<()> {
+	let dir = [REBASE_MERGE_DIR, REBASE_APPLY_DIR]
+		.into_iter()
+		.map(|dir| repo.path().join(dir))
+		.find(|dir| dir.exists())
+		.ok_or_else(|| Error::Generic("no rebase pending".into()))?;
+
+	let orig_head =
+		read_id(&dir.join("orig-head"))?.ok_or_else(|| {
+			Error::Generic("rebase lacks orig-head".into())
+		})?;
+	let head_name = read_optional(&dir.join("head-name"))?
+		.map(|name| name.trim().to_string())
+		.filter(|name| name.starts_with("refs/"));
+
+	repo.reset(
+		&repo.find_object(orig_head, None)?,
+		ResetType::Hard,
+		None,
+	)?;
+
+	if let Some(branch) = head_name {
+		repo.reference(&branch, orig_head, true, "rebase (abort)")?;
+		repo.set_head(&branch)?;
+	}
+
+	remove_optional(&dir)?;
+	remove_optional(&repo.path().join(REBASE_HEAD_FILE))?;
+	repo.cleanup_state()?;
+	rerere_clear(repo)?;
+
+	Ok(())
+}
+
+/// commits the resolved cherry-pick with its original author and
+/// picks the rest of a `git cherry-pick` sequence
+pub fn continue_cherry_pick(
+	repo: &Repository,
+) -> Result<RebaseState> {
+	if repo.index()?.has_conflicts() {
+		return Ok(RebaseState::Conflicted);
+	}
+
+	let committer = signature_allow_undefined_name(repo)?;
+	let todo_path = repo.path().join(SEQUENCER_DIR).join("todo");
+	let mut todo = read_steps(&todo_path)?;
+
+	if let Some(picked) =
+		read_id(&repo.path().join(CHERRY_PICK_HEAD_FILE))?
+	{
+		let commit = repo.find_commit(picked)?;
+		let msg = read_optional(&repo.path().join(MERGE_MSG_FILE))?
+			.unwrap_or_else(|| {
+				commit.message().unwrap_or_default().to_string()
+			});
+		commit_pick(
+			repo,
+			&commit,
+			&cleanup_message(&msg)?,
+			&committer,
+		)?;
+
+		// the sequence starts with the pick that stopped
+		if todo
+			.first()
+			.and_then(|step| step.commit(repo).ok())
+			.is_some_and(|first| first.id() == picked)
+		{
+			todo.remove(0);
+		}
+	}
+
+	for file in [CHERRY_PICK_HEAD_FILE, MERGE_MSG_FILE] {
+		remove_optional(&repo.path().join(file))?;
+	}
+
+	while !todo.is_empty() {
+		if !matches!(todo[0].command.as_str(), "pick" | "p") {
+			write_steps(&todo_path, &todo)?;
+			return Err(Error::SequencerStep(todo.remove(0).command));
+		}
+
+		let commit = todo[0].commit(repo)?;
+
+		if !pick(repo, &commit)? {
+			write_steps(&todo_path, &todo)?;
+			return Ok(RebaseState::Conflicted);
+		}
+
+		commit_pick(
+			repo,
+			&commit,
+			commit.message().unwrap_or_default(),
+			&committer,
+		)?;
+		for file in [CHERRY_PICK_HEAD_FILE, MERGE_MSG_FILE] {
+			remove_optional(&repo.path().join(file))?;
+		}
+
+		todo.remove(0);
+	}
+
+	remove_optional(&repo.path().join(SEQUENCER_DIR))?;
+
+	Ok(RebaseState::Finished)
+}
+
+/// what [`cherry_pick`] did
+#[derive(Debug, PartialEq, Eq)]
+pub struct CherryPickOutcome {
+	/// `Conflicted` if it stopped, the status tab continues it
+	pub state: RebaseState,
+	/// picks skipped because their changes are already in `HEAD`
+	pub empty: Vec<CommitId>,
+}
+
+/// picks `commits` in the given order onto `HEAD`, stops on the first
+/// conflict leaving the rest in a `git cherry-pick` sequence
+pub fn cherry_pick(
+	repo_path: &RepoPath,
+	commits: &[CommitId],
+) -> Result<CherryPickOutcome> {
+	scope_time!("cherry_pick");
+
+	let repo = repo(repo_path)?;
+
+	if repo.state() != RepositoryState::Clean {
+		return Err(Error::Generic(String::from(
+			"cherry-pick error: finish the pending operation first",
+		)));
+	}
+
+	let mut options = StatusOptions::new();
+	options.include_untracked(false).include_ignored(false);
+	if !repo.statuses(Some(&mut options))?.is_empty() {
+		return Err(Error::UncommittedChanges);
+	}
+
+	let committer = signature_allow_undefined_name(&repo)?;
+	let start = repo.head()?.peel_to_commit()?.id();
+	let mut empty = Vec::new();
+
+	for (index, id) in commits.iter().enumerate() {
+		let commit = repo.find_commit((*id).into())?;
+
+		if !pick(&repo, &commit)? {
+			if commits.len() > 1 {
+				write_sequence(&repo, start, &commits[index..])?;
+			}
+
+			return Ok(CherryPickOutcome {
+				state: RebaseState::Conflicted,
+				empty,
+			});
+		}
+
+		if index_matches_head(&repo)? {
+			empty.push(*id);
+		} else {
+			commit_pick(
+				&repo,
+				&commit,
+				commit.message().unwrap_or_default(),
+				&committer,
+			)?;
+		}
+
+		for file in [CHERRY_PICK_HEAD_FILE, MERGE_MSG_FILE] {
+			remove_optional(&repo.path().join(file))?;
+		}
+	}
+
+	Ok(CherryPickOutcome {
+		state: RebaseState::Finished,
+		empty,
+	})
+}
+
+/// the `sequencer` dir `git cherry-pick --continue` and `--abort`
+/// read, `todo` starts with the pick that stopped
+fn write_sequence(
+	repo: &Repository,
+	start: Oid,
+	todo: &[CommitId],
+) -> Result<()> {
+	let dir = repo.path().join(SEQUENCER_DIR);
+	fs::create_dir_all(&dir)?;
+
+	let steps = todo
+		.iter()
+		.map(|id| {
+			let commit = repo.find_commit((*id).into())?;
+
+			Ok(Step {
+				line: format!(
+					"pick {id} {}",
+					commit.summary().unwrap_or_default()
+				),
+				command: String::from("pick"),
+				commit: Some(id.to_string()),
+			})
+		})
+		.collect::<Result<Vec<_>>>()?;
+
+	write_steps(&dir.join("todo"), &steps)?;
+	fs::write(dir.join("head"), format!("{start}\n"))?;
+	fs::write(
+		dir.join("abort-safety"),
+		format!("{}\n", repo.head()?.peel_to_commit()?.id()),
+	)?;
+
+	Ok(())
+}
+
+/// the commit the pending cherry-pick applies
+pub fn cherry_pick_head(repo_path: &RepoPath) -> Result<CommitId> {
+	scope_time!("cherry_pick_head");
+
+	let repo = repo(repo_path)?;
+
+	read_id(&repo.path().join(CHERRY_PICK_HEAD_FILE))?
+		.map(CommitId::from)
+		.ok_or_else(|| {
+			Error::Generic("no cherry-pick pending".into())
+		})
+}
+
+/// drops the cherry-pick and goes back to where a sequence started
+pub fn abort_cherry_pick(repo: &Repository) -> Result<()> {
+	let start =
+		match read_id(&repo.path().join(SEQUENCER_DIR).join("head"))?
+		{
+			Some(start) => start,
+			None => repo.head()?.peel_to_commit()?.id(),
+		};
+
+	repo.reset(
+		&repo.find_object(start, None)?,
+		ResetType::Hard,
+		None,
+	)?;
+
+	repo.cleanup_state()?;
+	remove_optional(&repo.path().join(SEQUENCER_DIR))?;
+	rerere_clear(repo)?;
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::error::Error;
+	use crate::sync::{
+		abort_pending_cherry_pick, abort_pending_rebase, cherry_pick,
+		continue_pending_state, edit_commit, rebase_progress,
+		repo_state, stage_add_file,
+		tests::{debug_cmd_print, repo_init, write_commit_file},
+		utils::repo_write_file,
+		CommitId, RebaseState, RepoPath, RepoState,
+	};
+	use git2::Repository;
+	use std::path::Path;
+
+	fn subjects(repo: &Repository) -> Vec<String> {
+		let mut walk = repo.revwalk().unwrap();
+		walk.push_head().unwrap();
+		walk.map(|id| {
+			repo.find_commit(id.unwrap())
+				.unwrap()
+				.summary()
+				.unwrap()
+				.to_string()
+		})
+		.collect()
+	}
+
+	fn resolve(
+		repo: &Repository,
+		repo_path: &RepoPath,
+		content: &str,
+	) {
+		repo_write_file(repo, "f", content).unwrap();
+		stage_add_file(repo_path, Path::new("f")).unwrap();
+	}
+
+	/// `feature` has `c2` (conflicting with `c4` on `master`) and `c3`
+	fn setup(repo: &Repository, repo_path: &RepoPath) -> CommitId {
+		write_commit_file(repo, "f", "1", "c1");
+		debug_cmd_print(repo_path, "git checkout -q -b feature");
+		write_commit_file(repo, "f", "2", "c2");
+		write_commit_file(repo, "g", "x", "c3");
+		debug_cmd_print(repo_path, "git checkout -q master");
+		write_commit_file(repo, "f", "3", "c4");
+		debug_cmd_print(repo_path, "git checkout -q feature");
+
+		repo.head().unwrap().target().unwrap().into()
+	}
+
+	#[test]
+	fn test_continue_git_rebase() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		setup(&repo, repo_path);
+
+		debug_cmd_print(repo_path, "git rebase master");
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Rebase);
+		assert_eq!(rebase_progress(repo_path).unwrap().steps, 2);
+
+		assert_eq!(
+			continue_pending_state(repo_path).unwrap(),
+			RebaseState::Conflicted
+		);
+
+		resolve(&repo, repo_path, "2");
+
+		assert_eq!(
+			continue_pending_state(repo_path).unwrap(),
+			RebaseState::Finished
+		);
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+		assert_eq!(
+			repo.head().unwrap().name().unwrap(),
+			"refs/heads/feature"
+		);
+		assert_eq!(
+			subjects(&repo),
+			["c3", "c2", "c4", "c1", "initial"]
+		);
+	}
+
+	#[test]
+	fn test_continue_git_rebase_fixup() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		setup(&repo, repo_path);
+		write_commit_file(&repo, "f", "4", "c5");
+
+		// stops at c2, gitui picks c3 and folds c5 into it
+		debug_cmd_print(
+			repo_path,
+			"GIT_SEQUENCE_EDITOR='sed -i 3s/^pick/fixup/' git rebase -i master",
+		);
+		resolve(&repo, repo_path, "2");
+
+		assert_eq!(
+			continue_pending_state(repo_path).unwrap(),
+			RebaseState::Finished
+		);
+		assert_eq!(
+			subjects(&repo),
+			["c3", "c2", "c4", "c1", "initial"]
+		);
+		assert_eq!(
+			std::fs::read_to_string(root.join("f")).unwrap(),
+			"4"
+		);
+	}
+
+	#[test]
+	fn test_continue_git_rebase_reword() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		setup(&repo, repo_path);
+		write_commit_file(&repo, "g", "y", "c5");
+
+		// stops at c2, gitui picks c3 and leaves the reword of c5
+		// to git
+		debug_cmd_print(
+			repo_path,
+			"GIT_SEQUENCE_EDITOR='sed -i 3s/^pick/reword/' git rebase -i master",
+		);
+		resolve(&repo, repo_path, "2");
+
+		assert!(matches!(
+			continue_pending_state(repo_path),
+			Err(Error::SequencerStep(step)) if step == "reword"
+		));
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Rebase);
+		assert_eq!(
+			subjects(&repo),
+			["c3", "c2", "c4", "c1", "initial"]
+		);
+
+		debug_cmd_print(
+			repo_path,
+			"GIT_EDITOR='sed -i 1s/c5/c6/' git rebase --continue",
+		);
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+		assert_eq!(
+			subjects(&repo),
+			["c6", "c3", "c2", "c4", "c1", "initial"]
+		);
+	}
+
+	#[test]
+	fn test_continue_git_rebase_stopped_reword() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		setup(&repo, repo_path);
+
+		// the reword of c2 stops at its conflict
+		debug_cmd_print(
+			repo_path,
+			"GIT_SEQUENCE_EDITOR='sed -i 1s/^pick/reword/' git rebase -i master",
+		);
+		resolve(&repo, repo_path, "2");
+
+		assert!(matches!(
+			continue_pending_state(repo_path),
+			Err(Error::SequencerStep(step)) if step == "reword"
+		));
+		// nothing was committed
+		assert_eq!(subjects(&repo), ["c4", "c1", "initial"]);
+	}
+
+	#[test]
+	fn test_edit_commit() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "f", "1", "c1");
+		let target = write_commit_file(&repo, "g", "x", "c2");
+		write_commit_file(&repo, "f", "2", "c3");
+
+		edit_commit(repo_path, target).unwrap();
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Rebase);
+		assert_eq!(
+			CommitId::from(repo.head().unwrap().target().unwrap()),
+			target
+		);
+		assert_eq!(rebase_progress(repo_path).unwrap().steps, 2);
+
+		repo_write_file(&repo, "g", "y").unwrap();
+		stage_add_file(repo_path, Path::new("g")).unwrap();
+
+		assert_eq!(
+			continue_pending_state(repo_path).unwrap(),
+			RebaseState::Finished
+		);
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+		assert_eq!(
+			repo.head().unwrap().name().unwrap(),
+			"refs/heads/master"
+		);
+		assert_eq!(subjects(&repo), ["c3", "c2", "c1", "initial"]);
+		assert_eq!(
+			std::fs::read_to_string(root.join("g")).unwrap(),
+			"y"
+		);
+	}
+
+	#[test]
+	fn test_edit_commit_split() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "f", "1", "c1");
+		repo_write_file(&repo, "g", "x").unwrap();
+		stage_add_file(repo_path, Path::new("g")).unwrap();
+		let target = write_commit_file(&repo, "h", "x", "c2");
+		write_commit_file(&repo, "f", "2", "c3");
+
+		edit_commit(repo_path, target).unwrap();
+		debug_cmd_print(repo_path, "git reset -q --soft HEAD~");
+
+		// the undone commit is not folded into the one before it
+		assert!(continue_pending_state(repo_path).is_err());
+
+		debug_cmd_print(repo_path, "git commit -q -m c2a -- g");
+		debug_cmd_print(repo_path, "git commit -q -m c2b");
+
+		// `git` can continue it as well
+		debug_cmd_print(
+			repo_path,
+			"GIT_EDITOR=true git rebase --continue",
+		);
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+		assert_eq!(
+			subjects(&repo),
+			["c3", "c2b", "c2a", "c1", "initial"]
+		);
+	}
+
+	#[test]
+	fn test_abort_git_rebase() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let head = setup(&repo, repo_path);
+
+		debug_cmd_print(repo_path, "git rebase master");
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Rebase);
+
+		abort_pending_rebase(repo_path).unwrap();
+
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+		assert_eq!(
+			repo.head().unwrap().name().unwrap(),
+			"refs/heads/feature"
+		);
+		assert_eq!(
+			CommitId::from(repo.head().unwrap().target().unwrap()),
+			head
+		);
+		assert!(repo.statuses(None).unwrap().is_empty());
+	}
+
+	#[test]
+	fn test_cherry_pick_sequence() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		setup(&repo, repo_path);
+		debug_cmd_print(repo_path, "git checkout -q master");
+
+		debug_cmd_print(
+			repo_path,
+			"git cherry-pick feature~1 feature",
+		);
+		assert_eq!(
+			repo_state(repo_path).unwrap(),
+			RepoState::CherryPick
+		);
+
+		resolve(&repo, repo_path, "2");
+
+		assert_eq!(
+			continue_pending_state(repo_path).unwrap(),
+			RebaseState::Finished
+		);
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+		assert_eq!(
+			subjects(&repo),
+			["c3", "c2", "c4", "c1", "initial"]
+		);
+		assert!(!repo.path().join("sequencer").exists());
+	}
+
+	#[test]
+	fn test_abort_cherry_pick_sequence() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		setup(&repo, repo_path);
+		debug_cmd_print(repo_path, "git checkout -q master");
+		let head = repo.head().unwrap().target().unwrap();
+
+		// c3 applies, c2 conflicts
+		debug_cmd_print(
+			repo_path,
+			"git cherry-pick feature feature~1",
+		);
+		assert_eq!(subjects(&repo)[0], "c3");
+
+		abort_pending_cherry_pick(repo_path).unwrap();
+
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+		assert_eq!(repo.head().unwrap().target().unwrap(), head);
+	}
+
+	#[test]
+	fn test_cherry_pick_commits() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c3 = setup(&repo, repo_path);
+		let c2: CommitId =
+			repo.revparse_single("feature~1").unwrap().id().into();
+		debug_cmd_print(repo_path, "git checkout -q master");
+
+		let res = cherry_pick(repo_path, &[c3, c2]).unwrap();
+		assert_eq!(res.state, RebaseState::Conflicted);
+		assert_eq!(subjects(&repo)[0], "c3");
+		assert_eq!(
+			repo_state(repo_path).unwrap(),
+			RepoState::CherryPick
+		);
+
+		resolve(&repo, repo_path, "2");
+
+		assert_eq!(
+			continue_pending_state(repo_path).unwrap(),
+			RebaseState::Finished
+		);
+		assert_eq!(
+			subjects(&repo),
+			["c2", "c3", "c4", "c1", "initial"]
+		);
+
+		// c3 is already on master
+		let res = cherry_pick(repo_path, &[c3]).unwrap();
+		assert_eq!(res.state, RebaseState::Finished);
+		assert_eq!(res.empty, vec![c3]);
+		assert_eq!(subjects(&repo)[0], "c2");
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+	}
+
+	#[test]
+	fn test_abort_cherry_pick_commits() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c3 = setup(&repo, repo_path);
+		let c2: CommitId =
+			repo.revparse_single("feature~1").unwrap().id().into();
+		debug_cmd_print(repo_path, "git checkout -q master");
+		let head = repo.head().unwrap().target().unwrap();
+
+		let res = cherry_pick(repo_path, &[c3, c2]).unwrap();
+		assert_eq!(res.state, RebaseState::Conflicted);
+
+		// git goes on with the sequence just as well
+		debug_cmd_print(repo_path, "git cherry-pick --abort");
+
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+		assert_eq!(repo.head().unwrap().target().unwrap(), head);
+	}
+}
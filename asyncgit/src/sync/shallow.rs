@@ -0,0 +1,207 @@
+//! shallow clones (`git clone --depth`) end their history at the
+//! commits listed in `shallow`, their parents were never fetched.
+//! libgit2 and gix walk them like root commits. libgit2 can only
+//! fetch to a depth counted from the remote tips, so we run
+//! `git fetch` to deepen the history from where it ends
+
+use super::{repository::repo, utils::work_dir, CommitId, RepoPath};
+use crate::error::{Error, Result};
+use git2::{ErrorCode, Oid, Repository};
+use scopetime::scope_time;
+use std::{
+	fs,
+	io::ErrorKind,
+	process::{Child, Command, Stdio},
+};
+
+/// how much of the missing history to fetch
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Deepen {
+	/// this many commits more from where the history ends
+	By(u32),
+	/// all of it, the clone is complete afterwards
+	Unshallow,
+}
+
+/// the commits the history of a shallow clone ends at, empty if the
+/// repo has all of it
+pub fn shallow_commits(
+	repo_path: &RepoPath,
+) -> Result<Vec<CommitId>> {
+	let repo = repo(repo_path)?;
+
+	shallow_commits_repo(&repo)
+}
+
+pub fn shallow_commits_repo(
+	repo: &Repository,
+) -> Result<Vec<CommitId>> {
+	if !repo.is_shallow() {
+		return Ok(Vec::new());
+	}
+
+	// worktrees share the one of the main repo
+	let content =
+		match fs::read_to_string(repo.commondir().join("shallow")) {
+			Ok(content) => content,
+			Err(e) if e.kind() == ErrorKind::NotFound => {
+				return Ok(Vec::new())
+			}
+			Err(e) => return Err(e.into()),
+		};
+
+	content
+		.lines()
+		.filter(|line| !line.trim().is_empty())
+		.map(|line| Ok(CommitId::new(Oid::from_str(line.trim())?)))
+		.collect()
+}
+
+/// libgit2 reports the commits a shallow clone left out as not
+/// found, this tells why they are missing
+pub fn shallow_error(repo: &Repository, e: git2::Error) -> Error {
+	if e.code() == ErrorCode::NotFound && repo.is_shallow() {
+		Error::ShallowHistory
+	} else {
+		Error::Git(e)
+	}
+}
+
+/// fetches the history of a shallow clone `deepen` further from
+/// where it ends, stdout and stderr (with the progress) are piped
+pub fn spawn_deepen_history(
+	repo_path: &RepoPath,
+	deepen: Deepen,
+) -> Result<Child> {
+	scope_time!("spawn_deepen_history");
+
+	let repo = repo(repo_path)?;
+
+	let arg = match deepen {
+		Deepen::By(0) => {
+			return Err(Error::Generic(String::from(
+				"deepen the history by at least one commit",
+			)));
+		}
+		Deepen::By(count) => format!("--deepen={count}"),
+		Deepen::Unshallow => String::from("--unshallow"),
+	};
+
+	let child = Command::new("git")
+		.current_dir(work_dir(&repo).unwrap_or_else(|_| repo.path()))
+		.arg("--git-dir")
+		.arg(repo.path())
+		.args(["fetch", "--progress", "--recurse-submodules=no"])
+		.arg(arg)
+		.stdin(Stdio::null())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::piped())
+		.spawn()?;
+
+	Ok(child)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		blame::{blame_file, blame_file_check},
+		tests::{repo_init, write_commit_file},
+		LogWalkerWithoutFilter,
+	};
+	use std::path::Path;
+	use tempfile::TempDir;
+
+	fn git(dir: &Path, args: &[&str]) {
+		let status = Command::new("git")
+			.current_dir(dir)
+			.args(args)
+			.stdout(Stdio::null())
+			.stderr(Stdio::null())
+			.status()
+			.unwrap();
+		assert!(status.success(), "git {args:?}");
+	}
+
+	fn deepen(repo_path: &RepoPath, deepen: Deepen) {
+		let output = spawn_deepen_history(repo_path, deepen)
+			.unwrap()
+			.wait_with_output()
+			.unwrap();
+		assert!(output.status.success());
+	}
+
+	fn walk(repo_path: &RepoPath) -> usize {
+		let mut repo = crate::sync::gix_repo(repo_path).unwrap();
+		let mut walker =
+			LogWalkerWithoutFilter::new(&mut repo, 100).unwrap();
+		let mut items = Vec::new();
+		walker.read(&mut items).unwrap();
+		items.len()
+	}
+
+	#[test]
+	fn test_deepen_shallow_clone() {
+		let (_td, origin) = repo_init().unwrap();
+		let origin_root = origin.path().parent().unwrap();
+
+		let c1 = write_commit_file(&origin, "f", "a\n", "c1");
+		let c2 = write_commit_file(&origin, "f", "a\nb\n", "c2");
+		let c3 = write_commit_file(&origin, "f", "a\nb\nc\n", "c3");
+
+		let td = TempDir::new().unwrap();
+		let url = format!("file://{}", origin_root.display());
+		git(td.path(), &["clone", "-q", "--depth=1", &url, "clone"]);
+		let repo_path: &RepoPath =
+			&td.path().join("clone").to_str().unwrap().into();
+
+		assert_eq!(shallow_commits(repo_path).unwrap(), vec![c3]);
+		assert_eq!(walk(repo_path), 1);
+
+		// all lines are put on the commit the history ends at
+		let blame = blame_file(repo_path, "f", None).unwrap();
+		assert!(blame.truncated);
+		assert!(matches!(
+			blame_file_check(repo_path, "f", Some(c2)),
+			Err(Error::ShallowHistory)
+		));
+
+		deepen(repo_path, Deepen::By(1));
+
+		assert_eq!(shallow_commits(repo_path).unwrap(), vec![c2]);
+		assert_eq!(walk(repo_path), 2);
+		assert!(blame_file_check(repo_path, "f", Some(c2)).is_ok());
+
+		deepen(repo_path, Deepen::Unshallow);
+
+		assert!(shallow_commits(repo_path).unwrap().is_empty());
+		// the initial commit of `repo_init` included
+		assert_eq!(walk(repo_path), 4);
+		let blame = blame_file(repo_path, "f", None).unwrap();
+		assert!(!blame.truncated);
+		assert_eq!(
+			blame.lines[0].0.as_ref().map(|hunk| hunk.commit_id),
+			Some(c1)
+		);
+	}
+
+	#[test]
+	fn test_complete_repo() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		assert!(shallow_commits(repo_path).unwrap().is_empty());
+
+		let not_found = git2::Error::new(
+			ErrorCode::NotFound,
+			git2::ErrorClass::Odb,
+			"not found",
+		);
+		assert!(matches!(
+			shallow_error(&repo, not_found),
+			Error::Git(_)
+		));
+	}
+}
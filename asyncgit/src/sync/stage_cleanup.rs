@@ -0,0 +1,422 @@
+//! cleaning up what gets staged: stripping trailing whitespace off
+//! added lines and adding a missing newline at the end of the file,
+//! in the index only so the working tree stays as it is
+
+use super::{repository::repo, utils::work_dir, RepoPath};
+use crate::error::Result;
+use easy_cast::Conv;
+use git2::{Index, Oid, Patch, Repository};
+use scopetime::scope_time;
+use std::{
+	collections::{HashMap, HashSet},
+	fs,
+	io::ErrorKind,
+	path::Path,
+};
+
+/// git config key, strip trailing whitespace off staged lines
+pub const STAGE_STRIP_WHITESPACE_CONFIG: &str =
+	"gitui.stageStripTrailingWhitespace";
+
+/// git config key, end staged files with a newline
+pub const STAGE_FINAL_NEWLINE_CONFIG: &str =
+	"gitui.stageFinalNewline";
+
+const NEWLINE: u8 = b'\n';
+
+/// the configured cleanups, set globally or per repo
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct StageCleanup {
+	///
+	pub strip_trailing_whitespace: bool,
+	///
+	pub final_newline: bool,
+}
+
+impl StageCleanup {
+	const ALL: Self = Self {
+		strip_trailing_whitespace: true,
+		final_newline: true,
+	};
+
+	///
+	pub const fn is_enabled(self) -> bool {
+		self.strip_trailing_whitespace || self.final_newline
+	}
+}
+
+/// what staging a whole file would clean up
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct StageCleanupPreview {
+	/// bytes of whitespace at the end of added lines
+	pub trailing_whitespace: usize,
+	/// the added last line has no newline
+	pub missing_final_newline: bool,
+	/// which of it staging cleans up
+	pub cleanup: StageCleanup,
+}
+
+///
+pub fn stage_cleanup_config(
+	repo_path: &RepoPath,
+) -> Result<StageCleanup> {
+	let repo = repo(repo_path)?;
+
+	stage_cleanup_config_repo(&repo)
+}
+
+fn stage_cleanup_config_repo(
+	repo: &Repository,
+) -> Result<StageCleanup> {
+	let config = repo.config()?;
+	let get = |key| config.get_bool(key).unwrap_or(false);
+
+	Ok(StageCleanup {
+		strip_trailing_whitespace: get(STAGE_STRIP_WHITESPACE_CONFIG),
+		final_newline: get(STAGE_FINAL_NEWLINE_CONFIG),
+	})
+}
+
+/// whitespace issues on the lines staging `file_path` would add,
+/// `None` if there are none
+pub fn stage_cleanup_preview(
+	repo_path: &RepoPath,
+	file_path: &str,
+) -> Result<Option<StageCleanupPreview>> {
+	scope_time!("stage_cleanup_preview");
+
+	let repo = repo(repo_path)?;
+
+	let new = match fs::read(work_dir(&repo)?.join(file_path)) {
+		Ok(new) => new,
+		Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+		Err(e) => return Err(e.into()),
+	};
+
+	let old = match repo.index()?.get_path(Path::new(file_path), 0) {
+		Some(entry) => repo.find_blob(entry.id)?.content().to_vec(),
+		None => Vec::new(),
+	};
+
+	let Some((_, found)) =
+		clean_content(&old, &new, StageCleanup::ALL)?
+	else {
+		return Ok(None);
+	};
+
+	Ok(Some(StageCleanupPreview {
+		cleanup: stage_cleanup_config_repo(&repo)?,
+		..found
+	}))
+}
+
+/// lines of `content` including their line endings
+fn split_lines(content: &[u8]) -> Vec<&[u8]> {
+	content.split_inclusive(|c| *c == NEWLINE).collect()
+}
+
+/// indices of the lines in `new` that are not in `old`
+fn added_lines(old: &[u8], new: &[u8]) -> Result<HashSet<usize>> {
+	let mut options = git2::DiffOptions::new();
+	options.context_lines(0);
+
+	let patch = Patch::from_buffers(
+		old,
+		None,
+		new,
+		None,
+		Some(&mut options),
+	)?;
+
+	let mut res = HashSet::new();
+	for hunk in 0..patch.num_hunks() {
+		for line in 0..patch.num_lines_in_hunk(hunk)? {
+			let line = patch.line_in_hunk(hunk, line)?;
+			if line.origin_value() == git2::DiffLineType::Addition {
+				if let Some(lineno) = line.new_lineno() {
+					res.insert(usize::conv(lineno).saturating_sub(1));
+				}
+			}
+		}
+	}
+
+	Ok(res)
+}
+
+/// `new` with `cleanup` applied to the lines `old` does not have,
+/// `None` if there is nothing to clean up or `new` is binary
+fn clean_content(
+	old: &[u8],
+	new: &[u8],
+	cleanup: StageCleanup,
+) -> Result<Option<(Vec<u8>, StageCleanupPreview)>> {
+	if new.is_empty() || new.contains(&0) {
+		return Ok(None);
+	}
+
+	let added = added_lines(old, new)?;
+	let lines = split_lines(new);
+	let crlf = lines.iter().any(|line| line.ends_with(b"\r\n"));
+
+	let mut found = StageCleanupPreview {
+		cleanup,
+		..StageCleanupPreview::default()
+	};
+	let mut content = Vec::with_capacity(new.len());
+
+	for (index, line) in lines.iter().enumerate() {
+		if !added.contains(&index) {
+			content.extend_from_slice(line);
+			continue;
+		}
+
+		let eol = if line.ends_with(b"\r\n") {
+			2
+		} else {
+			usize::from(line.ends_with(&[NEWLINE]))
+		};
+		let (text, eol) = line.split_at(line.len() - eol);
+		let trimmed_len = text
+			.iter()
+			.rposition(|c| !matches!(c, b' ' | b'\t'))
+			.map_or(0, |last| last + 1);
+
+		found.trailing_whitespace += text.len() - trimmed_len;
+
+		if cleanup.strip_trailing_whitespace {
+			content.extend_from_slice(&text[..trimmed_len]);
+		} else {
+			content.extend_from_slice(text);
+		}
+		content.extend_from_slice(eol);
+
+		if eol.is_empty() {
+			found.missing_final_newline = true;
+
+			if cleanup.final_newline {
+				content.extend_from_slice(if crlf {
+					b"\r\n"
+				} else {
+					b"\n"
+				});
+			}
+		}
+	}
+
+	if found.trailing_whitespace == 0 && !found.missing_final_newline
+	{
+		return Ok(None);
+	}
+
+	Ok(Some((content, found)))
+}
+
+/// the index before staging, to clean up what staging changed
+pub struct StageSnapshot {
+	cleanup: StageCleanup,
+	entries: HashMap<Vec<u8>, Oid>,
+}
+
+impl StageSnapshot {
+	/// `None` if no cleanup is configured
+	pub fn take(
+		repo: &Repository,
+		index: &Index,
+	) -> Result<Option<Self>> {
+		let cleanup = stage_cleanup_config_repo(repo)?;
+		if !cleanup.is_enabled() {
+			return Ok(None);
+		}
+
+		Ok(Some(Self {
+			cleanup,
+			entries: index
+				.iter()
+				.map(|entry| (entry.path, entry.id))
+				.collect(),
+		}))
+	}
+
+	/// cleans up the regular files staged since the snapshot, the
+	/// caller writes `index`
+	pub fn clean(
+		&self,
+		repo: &Repository,
+		index: &mut Index,
+	) -> Result<()> {
+		scope_time!("stage_cleanup");
+
+		const MODE_MASK: u32 = 0o170_000;
+		const MODE_FILE: u32 = 0o100_000;
+		const STAGE_MASK: u16 = 0x3000;
+
+		let staged: Vec<_> = index
+			.iter()
+			.filter(|entry| {
+				entry.flags & STAGE_MASK == 0
+					&& entry.mode & MODE_MASK == MODE_FILE
+					&& self.entries.get(&entry.path)
+						!= Some(&entry.id)
+			})
+			.collect();
+
+		for mut entry in staged {
+			let old = match self.entries.get(&entry.path) {
+				Some(id) => repo.find_blob(*id)?.content().to_vec(),
+				None => Vec::new(),
+			};
+			let new = repo.find_blob(entry.id)?;
+
+			if let Some((content, _)) =
+				clean_content(&old, new.content(), self.cleanup)?
+			{
+				entry.id = repo.blob(&content)?;
+				entry.file_size = u32::try_conv(content.len())?;
+				index.add(&entry)?;
+			}
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		diff::{get_diff, DiffLinePosition},
+		stage_add_file, stage_hunk, stage_lines,
+		tests::{repo_init, write_commit_file},
+		utils::repo_write_file,
+	};
+
+	fn staged(repo: &Repository, path: &str) -> String {
+		let mut index = repo.index().unwrap();
+		index.read(true).unwrap();
+		let entry = index.get_path(Path::new(path), 0).unwrap();
+
+		String::from_utf8(
+			repo.find_blob(entry.id).unwrap().content().to_vec(),
+		)
+		.unwrap()
+	}
+
+	fn enable(repo: &Repository) {
+		let mut config = repo.config().unwrap();
+		config
+			.set_bool(STAGE_STRIP_WHITESPACE_CONFIG, true)
+			.unwrap();
+		config.set_bool(STAGE_FINAL_NEWLINE_CONFIG, true).unwrap();
+	}
+
+	#[test]
+	fn test_clean_content() {
+		let cleanup = StageCleanup::ALL;
+
+		let (content, found) =
+			clean_content(b"a  \n", b"a  \nb \t\nc", cleanup)
+				.unwrap()
+				.unwrap();
+		// the context line keeps its whitespace
+		assert_eq!(content, b"a  \nb\nc\n");
+		assert_eq!(found.trailing_whitespace, 2);
+		assert!(found.missing_final_newline);
+
+		let (content, _) =
+			clean_content(b"", b"a \r\nb", cleanup).unwrap().unwrap();
+		assert_eq!(content, b"a\r\nb\r\n");
+
+		assert!(clean_content(b"a \n", b"a \nb\n", cleanup)
+			.unwrap()
+			.is_none());
+		assert!(clean_content(b"", b"a \0", cleanup)
+			.unwrap()
+			.is_none());
+	}
+
+	#[test]
+	fn test_stage_file_cleaned() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		repo_write_file(&repo, "f", "a  \nb").unwrap();
+
+		assert_eq!(
+			stage_cleanup_preview(repo_path, "f").unwrap(),
+			Some(StageCleanupPreview {
+				trailing_whitespace: 2,
+				missing_final_newline: true,
+				cleanup: StageCleanup::default(),
+			})
+		);
+
+		enable(&repo);
+		stage_add_file(repo_path, Path::new("f")).unwrap();
+
+		assert_eq!(staged(&repo, "f"), "a\nb\n");
+		// the working tree is left alone
+		assert_eq!(
+			fs::read_to_string(root.join("f")).unwrap(),
+			"a  \nb"
+		);
+		// what is left unstaged is what staging strips
+		assert_eq!(
+			stage_cleanup_preview(repo_path, "f").unwrap(),
+			Some(StageCleanupPreview {
+				trailing_whitespace: 2,
+				missing_final_newline: true,
+				cleanup: StageCleanup::ALL,
+			})
+		);
+	}
+
+	#[test]
+	fn test_stage_hunk_and_lines_cleaned() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(
+			&repo,
+			"f",
+			"a \n1\n2\n3\n4\n5\n6\n7\nz\n",
+			"c1",
+		);
+		enable(&repo);
+
+		repo_write_file(
+			&repo,
+			"f",
+			"a \nb \n1\n2\n3\n4\n5\n6\n7\nz\ny \n",
+		)
+		.unwrap();
+
+		let diff = get_diff(repo_path, "f", false, None).unwrap();
+		assert_eq!(diff.hunks.len(), 2);
+
+		stage_hunk(repo_path, "f", diff.hunks[0].header_hash, None)
+			.unwrap();
+		assert_eq!(
+			staged(&repo, "f"),
+			"a \nb\n1\n2\n3\n4\n5\n6\n7\nz\n"
+		);
+
+		stage_lines(
+			repo_path,
+			"f",
+			false,
+			&[DiffLinePosition {
+				old_lineno: None,
+				new_lineno: Some(11),
+			}],
+		)
+		.unwrap();
+		assert_eq!(
+			staged(&repo, "f"),
+			"a \nb\n1\n2\n3\n4\n5\n6\n7\nz\ny\n"
+		);
+	}
+}
@@ -0,0 +1,234 @@
+//! (un)staging many paths at once: all of them go into the index in
+//! memory and it is written a single time at the end
+
+use super::{
+	index_file::check_index_writable, repository::repo,
+	stage_cleanup::StageSnapshot, utils::work_dir, RepoPath,
+};
+use crate::error::Result;
+use git2::{IndexAddOption, IndexEntry, IndexTime, Repository, Tree};
+use scopetime::scope_time;
+use std::{collections::HashSet, path::Path};
+
+///
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StagePathsResult {
+	/// paths processed before finishing or being cancelled
+	pub done: usize,
+	/// conflicted paths left alone so the conflict stays in the index
+	pub skipped: Vec<String>,
+	/// `progress` stopped it, the index was not written
+	pub cancelled: bool,
+}
+
+/// stages (or unstages if `!stage`) all `paths` with one index
+/// write, `progress(done, total)` is called for every path and
+/// returning `false` cancels without touching the index.
+///
+/// staging adds what is in the working tree and removes what is
+/// gone from it, unstaging goes back to `HEAD`.
+pub fn stage_all_paths(
+	repo_path: &RepoPath,
+	paths: &[String],
+	stage: bool,
+	mut progress: impl FnMut(usize, usize) -> bool,
+) -> Result<StagePathsResult> {
+	scope_time!("stage_all_paths");
+
+	let repo = repo(repo_path)?;
+	check_index_writable(&repo)?;
+
+	let mut index = repo.index()?;
+	index.read(true)?;
+
+	let conflicted = conflicted_paths(&index)?;
+	let snapshot = if stage {
+		StageSnapshot::take(&repo, &index)?
+	} else {
+		None
+	};
+	let head = head_tree(&repo)?;
+	let work_dir = work_dir(&repo)?.to_path_buf();
+
+	let mut res = StagePathsResult::default();
+
+	for path in paths {
+		if !progress(res.done, paths.len()) {
+			res.cancelled = true;
+			return Ok(res);
+		}
+
+		res.done += 1;
+
+		if conflicted.contains(path.as_bytes()) {
+			res.skipped.push(path.clone());
+			continue;
+		}
+
+		let file = Path::new(path);
+
+		if stage {
+			let full = work_dir.join(file);
+			if full.is_dir() && !full.join(".git").exists() {
+				// untracked folder listed as a whole
+				index.add_all(
+					[path],
+					IndexAddOption::DEFAULT,
+					None,
+				)?;
+			} else if full.symlink_metadata().is_ok() {
+				index.add_path(file)?;
+			} else {
+				index.remove_path(file)?;
+			}
+		} else if let Some(entry) =
+			head.as_ref().and_then(|tree| tree.get_path(file).ok())
+		{
+			index.add(&head_entry(path, &entry)?)?;
+		} else {
+			index.remove_path(file)?;
+		}
+	}
+
+	if !progress(res.done, paths.len()) {
+		res.cancelled = true;
+		return Ok(res);
+	}
+
+	if let Some(snapshot) = snapshot {
+		snapshot.clean(&repo, &mut index)?;
+	}
+	index.write()?;
+
+	Ok(res)
+}
+
+fn conflicted_paths(index: &git2::Index) -> Result<HashSet<Vec<u8>>> {
+	let mut res = HashSet::new();
+
+	for conflict in index.conflicts()? {
+		let conflict = conflict?;
+		if let Some(entry) =
+			conflict.our.or(conflict.their).or(conflict.ancestor)
+		{
+			res.insert(entry.path);
+		}
+	}
+
+	Ok(res)
+}
+
+/// `None` on an unborn branch, unstaging removes everything then
+fn head_tree(repo: &Repository) -> Result<Option<Tree<'_>>> {
+	match repo.head() {
+		Ok(head) => Ok(Some(head.peel_to_tree()?)),
+		Err(e) if e.code() == git2::ErrorCode::UnbornBranch => {
+			Ok(None)
+		}
+		Err(e) => Err(e.into()),
+	}
+}
+
+/// index entry of the `HEAD` version, without stat data so `git`
+/// compares the content with the working tree again
+fn head_entry(
+	path: &str,
+	entry: &git2::TreeEntry,
+) -> Result<IndexEntry> {
+	let path = path.as_bytes().to_vec();
+
+	Ok(IndexEntry {
+		ctime: IndexTime::new(0, 0),
+		mtime: IndexTime::new(0, 0),
+		dev: 0,
+		ino: 0,
+		mode: u32::try_from(entry.filemode())?,
+		uid: 0,
+		gid: 0,
+		file_size: 0,
+		id: entry.id(),
+		flags: u16::try_from(path.len().min(0xfff))?,
+		flags_extended: 0,
+		path,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		status::{get_status, StatusType},
+		tests::{repo_init, write_commit_file},
+		utils::repo_write_file,
+	};
+	use std::fs;
+
+	fn status(repo_path: &RepoPath, kind: StatusType) -> Vec<String> {
+		get_status(repo_path, kind, None)
+			.unwrap()
+			.into_iter()
+			.map(|item| item.path)
+			.collect()
+	}
+
+	#[test]
+	fn test_stage_and_unstage_paths() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a", "1", "c1");
+		write_commit_file(&repo, "b", "1", "c2");
+
+		repo_write_file(&repo, "a", "2").unwrap();
+		fs::remove_file(root.join("b")).unwrap();
+		repo_write_file(&repo, "c", "new").unwrap();
+		fs::create_dir(root.join("d")).unwrap();
+		repo_write_file(&repo, "d/e", "new").unwrap();
+
+		let paths = vec![
+			String::from("a"),
+			String::from("b"),
+			String::from("c"),
+			String::from("d"),
+		];
+
+		let res =
+			stage_all_paths(repo_path, &paths, true, |_, _| true)
+				.unwrap();
+		assert_eq!(res.done, 4);
+		assert!(!res.cancelled);
+		let staged = status(repo_path, StatusType::Stage);
+		assert_eq!(staged, ["a", "b", "c", "d/e"]);
+		assert!(status(repo_path, StatusType::WorkingDir).is_empty());
+
+		stage_all_paths(repo_path, &staged, false, |_, _| true)
+			.unwrap();
+		assert!(status(repo_path, StatusType::Stage).is_empty());
+		assert_eq!(status(repo_path, StatusType::WorkingDir), paths);
+	}
+
+	#[test]
+	fn test_cancel_leaves_index() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		repo_write_file(&repo, "a", "1").unwrap();
+		repo_write_file(&repo, "b", "1").unwrap();
+
+		let paths = vec![String::from("a"), String::from("b")];
+
+		let res =
+			stage_all_paths(repo_path, &paths, true, |done, _| {
+				done < 1
+			})
+			.unwrap();
+
+		assert!(res.cancelled);
+		assert_eq!(res.done, 1);
+		assert!(status(repo_path, StatusType::Stage).is_empty());
+	}
+}
@@ -1,4 +1,4 @@
-use super::{apply_selection, load_file};
+use super::{apply_selection, load_file, split_lines};
 use crate::{
 	error::Result,
 	sync::{
@@ -32,12 +32,12 @@ pub fn discard_lines(
 		let hunks = patch_get_hunklines(&patch)?;
 
 		let working_content = load_file(&repo, file_path)?;
-		let old_lines = working_content.lines().collect::<Vec<_>>();
+		let old_lines = split_lines(&working_content);
 
 		apply_selection(lines, &hunks, &old_lines, false, true)?
 	};
 
-	repo_write_file(&repo, file_path, new_content.as_str())?;
+	repo_write_file(&repo, file_path, new_content)?;
 
 	Ok(())
 }
@@ -95,7 +95,7 @@ mod test {
 
 		let result_file = load_file(&repo, "test.txt").unwrap();
 
-		assert_eq!(result_file.as_str(), FILE_3);
+		assert_eq!(result_file, FILE_3.as_bytes());
 	}
 
 	#[test]
@@ -134,7 +134,7 @@ end
 
 		let result_file = load_file(&repo, "test.txt").unwrap();
 
-		assert_eq!(result_file.as_str(), FILE_3);
+		assert_eq!(result_file, FILE_3.as_bytes());
 	}
 
 	#[test]
@@ -179,7 +179,7 @@ end
 
 		let result_file = load_file(&repo, "test.txt").unwrap();
 
-		assert_eq!(result_file.as_str(), FILE_3);
+		assert_eq!(result_file, FILE_3.as_bytes());
 	}
 
 	#[test]
@@ -226,7 +226,7 @@ end
 
 		let result_file = load_file(&repo, "test.txt").unwrap();
 
-		assert_eq!(result_file.as_str(), FILE_3);
+		assert_eq!(result_file, FILE_3.as_bytes());
 	}
 
 	#[test]
@@ -269,7 +269,7 @@ end
 
 		let result_file = load_file(&repo, "test.txt").unwrap();
 
-		assert_eq!(result_file.as_str(), FILE_3);
+		assert_eq!(result_file, FILE_3.as_bytes());
 	}
 
 	//this test shows that we require at least a diff context around add/removes of 1
@@ -308,7 +308,7 @@ end
 
 		let result_file = load_file(&repo, "test.txt").unwrap();
 
-		assert_eq!(result_file.as_str(), FILE_3);
+		assert_eq!(result_file, FILE_3.as_bytes());
 	}
 
 	#[test]
@@ -341,6 +341,30 @@ end
 
 		let result_file = load_file(&repo, "test.txt").unwrap();
 
-		assert_eq!(result_file.as_str(), FILE_3);
+		assert_eq!(result_file, FILE_3.as_bytes());
+	}
+
+	#[test]
+	fn test_discard_keeps_line_endings() {
+		let (path, repo) = repo_init().unwrap();
+		let path: &RepoPath = &path.path().to_str().unwrap().into();
+
+		write_commit_file(&repo, "test.txt", "a\r\nb\r\nc", "c1");
+		repo_write_file(&repo, "test.txt", "a\r\nx\r\nb\r\nc\r\nd")
+			.unwrap();
+
+		discard_lines(
+			path,
+			"test.txt",
+			&[DiffLinePosition {
+				old_lineno: None,
+				new_lineno: Some(2),
+			}],
+		)
+		.unwrap();
+
+		let result_file = load_file(&repo, "test.txt").unwrap();
+
+		assert_eq!(result_file, b"a\r\nb\r\nc\r\nd");
 	}
 }
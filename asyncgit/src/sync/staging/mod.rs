@@ -9,74 +9,77 @@ use super::{
 };
 use crate::error::Result;
 use git2::{DiffLine, DiffLineType, Repository};
-use std::{collections::HashSet, fs::File, io::Read};
+use std::{collections::HashSet, fs};
 
-const NEWLINE: char = '\n';
+const NEWLINE: u8 = b'\n';
 
+/// builds the content line by line, lines keep their line endings so
+/// that untouched lines stay the same byte for byte
 #[derive(Default)]
 struct NewFromOldContent {
-	lines: Vec<String>,
+	content: Vec<u8>,
 	old_index: usize,
 }
 
 impl NewFromOldContent {
-	fn add_from_hunk(&mut self, line: &DiffLine) -> Result<()> {
-		let line = String::from_utf8(line.content().into())?;
-
-		let line = if line.ends_with(NEWLINE) {
-			line[0..line.len() - 1].to_string()
-		} else {
-			line
-		};
+	fn push(&mut self, line: &[u8]) {
+		// only the last line can lack a newline, it needs one if
+		// more lines follow
+		if self.content.last().is_some_and(|c| *c != NEWLINE) {
+			self.content.push(NEWLINE);
+		}
 
-		self.lines.push(line);
+		self.content.extend_from_slice(line);
+	}
 
-		Ok(())
+	fn add_from_hunk(&mut self, line: &DiffLine) {
+		self.push(line.content());
 	}
 
 	fn skip_old_line(&mut self) {
 		self.old_index += 1;
 	}
 
-	fn add_old_line(&mut self, old_lines: &[&str]) {
-		self.lines.push(old_lines[self.old_index].to_string());
+	fn add_old_line(&mut self, old_lines: &[&[u8]]) {
+		if let Some(line) = old_lines.get(self.old_index) {
+			self.push(line);
+		}
 		self.old_index += 1;
 	}
 
 	fn catchup_to_hunkstart(
 		&mut self,
 		hunk_start: usize,
-		old_lines: &[&str],
+		old_lines: &[&[u8]],
 	) {
 		while hunk_start > self.old_index + 1 {
 			self.add_old_line(old_lines);
 		}
 	}
 
-	fn finish(mut self, old_lines: &[&str]) -> String {
+	fn finish(mut self, old_lines: &[&[u8]]) -> Vec<u8> {
 		for line in old_lines.iter().skip(self.old_index) {
-			self.lines.push((*line).to_string());
-		}
-		let lines = self.lines.join("\n");
-		if lines.ends_with(NEWLINE) {
-			lines
-		} else {
-			let mut lines = lines;
-			lines.push(NEWLINE);
-			lines
+			self.push(line);
 		}
+
+		self.content
 	}
 }
 
+/// lines of `content` including their line endings
+fn split_lines(content: &[u8]) -> Vec<&[u8]> {
+	content.split_inclusive(|c| *c == NEWLINE).collect()
+}
+
 // this is the heart of the per line discard,stage,unstage. heavily inspired by the great work in
 // nodegit: https://github.com/nodegit/nodegit
 pub fn apply_selection(
 	lines: &[DiffLinePosition],
 	hunks: &[HunkLines],
-	old_lines: &[&str],
+	old_lines: &[&[u8]],
 	is_staged: bool,
 	reverse: bool,
-) -> Result<String> {
+) -> Result<Vec<u8>> {
 	let mut new_content = NewFromOldContent::default();
 	let lines = lines.iter().collect::<HashSet<_>>();
 
@@ -128,19 +131,21 @@ pub fn apply_selection(
 						.trim()
 				);
 
-				if hunk_line.origin_value()
-					== DiffLineType::DeleteEOFNL
-					|| hunk_line.origin_value()
-						== DiffLineType::AddEOFNL
-				{
-					break;
+				// the line before lacks the newline already
+				if matches!(
+					hunk_line.origin_value(),
+					DiffLineType::ContextEOFNL
+						| DiffLineType::AddEOFNL
+						| DiffLineType::DeleteEOFNL
+				) {
+					continue;
 				}
 
 				if (is_staged && !selected_line)
 					|| (!is_staged && selected_line)
 				{
 					if hunk_line.origin_value() == added {
-						new_content.add_from_hunk(hunk_line)?;
+						new_content.add_from_hunk(hunk_line);
 						if is_staged {
 							new_content.skip_old_line();
 						}
@@ -153,7 +158,7 @@ pub fn apply_selection(
 					}
 				} else {
 					if hunk_line.origin_value() != added {
-						new_content.add_from_hunk(hunk_line)?;
+						new_content.add_from_hunk(hunk_line);
 					}
 
 					if (is_staged
@@ -174,11 +179,6 @@ pub fn apply_selection(
 pub fn load_file(
 	repo: &Repository,
 	file_path: &str,
-) -> Result<String> {
-	let repo_path = work_dir(repo)?;
-	let mut file = File::open(repo_path.join(file_path).as_path())?;
-	let mut res = String::new();
-	file.read_to_string(&mut res)?;
-
-	Ok(res)
+) -> Result<Vec<u8>> {
+	Ok(fs::read(work_dir(repo)?.join(file_path))?)
 }
@@ -1,16 +1,22 @@
-use super::apply_selection;
+use super::{apply_selection, load_file, split_lines};
 use crate::{
 	error::{Error, Result},
 	sync::{
-		diff::DiffLinePosition, patches::get_file_diff_patch,
-		patches::patch_get_hunklines, repository::repo, RepoPath,
+		diff::DiffLinePosition,
+		index_file::check_index_writable,
+		patches::{get_file_diff_patch, patch_get_hunklines},
+		repository::repo,
+		stage_cleanup::StageSnapshot,
+		RepoPath,
 	},
 };
 use easy_cast::Conv;
+use git2::Patch;
 use scopetime::scope_time;
 use std::path::Path;
 
-///
+/// (un)stages the selected lines of the staged (`is_stage`) or
+/// unstaged diff, works for untracked files too
 pub fn stage_lines(
 	repo_path: &RepoPath,
 	file_path: &str,
@@ -24,35 +30,58 @@ pub fn stage_lines(
 	}
 
 	let repo = repo(repo_path)?;
+	check_index_writable(&repo)?;
 	// log::debug!("stage_lines: {:?}", lines);
 
 	let mut index = repo.index()?;
 	index.read(true)?;
-	let mut idx =
-		index.get_path(Path::new(file_path), 0).ok_or_else(|| {
-			Error::Generic(String::from(
-				"only non new files supported",
-			))
-		})?;
-	let blob = repo.find_blob(idx.id)?;
-	let indexed_content = String::from_utf8(blob.content().into())?;
+	let snapshot = if is_stage {
+		None
+	} else {
+		StageSnapshot::take(&repo, &index)?
+	};
+
+	let new_content = if let Some(idx) =
+		index.get_path(Path::new(file_path), 0)
+	{
+		let blob = repo.find_blob(idx.id)?;
+		let old_lines = split_lines(blob.content());
 
-	let new_content = {
 		let patch =
 			get_file_diff_patch(&repo, file_path, is_stage, false)?;
 		let hunks = patch_get_hunklines(&patch)?;
 
-		let old_lines = indexed_content.lines().collect::<Vec<_>>();
-
 		apply_selection(lines, &hunks, &old_lines, is_stage, false)?
+	} else if is_stage {
+		return Err(Error::Generic(String::from(
+			"file is not staged",
+		)));
+	} else {
+		// untracked: the selected lines of the file are added
+		let content = load_file(&repo, file_path)?;
+		let patch =
+			Patch::from_buffers(&[], None, &content, None, None)?;
+		let hunks = patch_get_hunklines(&patch)?;
+
+		let new_content =
+			apply_selection(lines, &hunks, &[], false, false)?;
+
+		index.add_path(Path::new(file_path))?;
+		new_content
 	};
 
-	let blob_id = repo.blob(new_content.as_bytes())?;
+	let mut idx =
+		index.get_path(Path::new(file_path), 0).ok_or_else(|| {
+			Error::Generic(String::from("file not in index"))
+		})?;
 
-	idx.id = blob_id;
+	idx.id = repo.blob(&new_content)?;
 	idx.file_size = u32::try_conv(new_content.len())?;
 	index.add(&idx)?;
 
+	if let Some(snapshot) = snapshot {
+		snapshot.clean(&repo, &mut index)?;
+	}
 	index.write()?;
 	index.read(true)?;
 
@@ -67,6 +96,146 @@ mod test {
 		tests::{get_statuses, repo_init, write_commit_file},
 		utils::{repo_write_file, stage_add_file},
 	};
+	use git2::Repository;
+	use pretty_assertions::assert_eq;
+
+	fn index_content(repo: &Repository, path: &str) -> String {
+		let mut index = repo.index().unwrap();
+		index.read(true).unwrap();
+		let entry = index.get_path(Path::new(path), 0).unwrap();
+		let blob = repo.find_blob(entry.id).unwrap();
+
+		String::from_utf8(blob.content().to_vec()).unwrap()
+	}
+
+	fn added(lines: &[u32]) -> Vec<DiffLinePosition> {
+		lines
+			.iter()
+			.map(|line| DiffLinePosition {
+				old_lineno: None,
+				new_lineno: Some(*line),
+			})
+			.collect()
+	}
+
+	fn removed(lines: &[u32]) -> Vec<DiffLinePosition> {
+		lines
+			.iter()
+			.map(|line| DiffLinePosition {
+				old_lineno: Some(*line),
+				new_lineno: None,
+			})
+			.collect()
+	}
+
+	/// `old` committed and `new` in the working dir
+	fn changed_repo(
+		old: &str,
+		new: &str,
+	) -> (tempfile::TempDir, Repository, RepoPath) {
+		let (td, repo) = repo_init().unwrap();
+		let path: RepoPath = td.path().to_str().unwrap().into();
+
+		write_commit_file(&repo, "test.txt", old, "c1");
+		repo_write_file(&repo, "test.txt", new).unwrap();
+
+		(td, repo, path)
+	}
+
+	#[test]
+	fn test_stage_part_of_replaced_block() {
+		let old = "a\nold 1\nold 2\nc\n";
+		let new = "a\nnew 1\nnew 2\nc\n";
+
+		// only the additions: the removed lines stay
+		let (_td, repo, path) = changed_repo(old, new);
+		stage_lines(&path, "test.txt", false, &added(&[2, 3]))
+			.unwrap();
+		assert_eq!(
+			index_content(&repo, "test.txt"),
+			"a\nold 1\nold 2\nnew 1\nnew 2\nc\n"
+		);
+
+		// only the removals
+		let (_td, repo, path) = changed_repo(old, new);
+		stage_lines(&path, "test.txt", false, &removed(&[2, 3]))
+			.unwrap();
+		assert_eq!(index_content(&repo, "test.txt"), "a\nc\n");
+
+		// one of each
+		let (_td, repo, path) = changed_repo(old, new);
+		let mut lines = removed(&[3]);
+		lines.extend(added(&[2]));
+		stage_lines(&path, "test.txt", false, &lines).unwrap();
+		assert_eq!(
+			index_content(&repo, "test.txt"),
+			"a\nold 1\nnew 1\nc\n"
+		);
+	}
+
+	#[test]
+	fn test_unstage_part_of_replaced_block() {
+		let (_td, repo, path) = changed_repo(
+			"a\nold 1\nold 2\nc\n",
+			"a\nnew 1\nnew 2\nc\n",
+		);
+		stage_add_file(&path, Path::new("test.txt")).unwrap();
+
+		// puts back the removed line, keeps the added ones
+		stage_lines(&path, "test.txt", true, &removed(&[2])).unwrap();
+		assert_eq!(
+			index_content(&repo, "test.txt"),
+			"a\nold 1\nnew 1\nnew 2\nc\n"
+		);
+
+		stage_lines(&path, "test.txt", true, &added(&[4])).unwrap();
+		assert_eq!(
+			index_content(&repo, "test.txt"),
+			"a\nold 1\nnew 1\nc\n"
+		);
+	}
+
+	#[test]
+	fn test_stage_keeps_line_endings() {
+		let (_td, repo, path) = changed_repo(
+			"a\r\nb\r\nc\r\n",
+			"a\r\nx\r\nb\r\ny\r\nc\r\n",
+		);
+
+		stage_lines(&path, "test.txt", false, &added(&[2])).unwrap();
+
+		assert_eq!(
+			index_content(&repo, "test.txt"),
+			"a\r\nx\r\nb\r\nc\r\n"
+		);
+	}
+
+	#[test]
+	fn test_stage_missing_newline_at_end() {
+		let (_td, repo, path) = changed_repo("a\nb", "a\nb\nc\n");
+
+		// a line lacking the newline gets it when more follow
+		stage_lines(&path, "test.txt", false, &added(&[3])).unwrap();
+		assert_eq!(index_content(&repo, "test.txt"), "a\nb\nc\n");
+
+		// and keeps lacking it at the end
+		let (_td, repo, path) = changed_repo("a\nb", "x\na\nb\nc\n");
+		stage_lines(&path, "test.txt", false, &added(&[1])).unwrap();
+		assert_eq!(index_content(&repo, "test.txt"), "x\na\nb");
+	}
+
+	#[test]
+	fn test_stage_lines_of_untracked() {
+		let (td, repo) = repo_init().unwrap();
+		let path: &RepoPath = &td.path().to_str().unwrap().into();
+
+		repo_write_file(&repo, "new.txt", "1\n2\n3\n").unwrap();
+
+		stage_lines(path, "new.txt", false, &added(&[1, 3])).unwrap();
+
+		assert_eq!(index_content(&repo, "new.txt"), "1\n3\n");
+		assert_eq!(get_statuses(path), (1, 1));
+	}
 
 	#[test]
 	fn test_stage() {
@@ -138,8 +307,13 @@ c = 4";
 
 		let diff = get_diff(path, "test.txt", true, None).unwrap();
 
-		assert_eq!(diff.lines, 5);
-		assert_eq!(&*diff.hunks[0].lines[0].content, "@@ -1,2 +1 @@");
+		// both lines removed leaves an empty file
+		assert_eq!(index_content(&repo, "test.txt"), "");
+		assert_eq!(diff.lines, 4);
+		assert_eq!(
+			&*diff.hunks[0].lines[0].content,
+			"@@ -1,2 +0,0 @@"
+		);
 	}
 
 	#[test]
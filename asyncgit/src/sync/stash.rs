@@ -1,13 +1,23 @@
 use super::{CommitId, RepoPath};
 use crate::{
 	error::{Error, Result},
-	sync::repository::repo,
+	sync::{
+		branch::{
+			checkout_branch, create_branch_at, get_branch_name_repo,
+		},
+		commit::signature_allow_undefined_name,
+		oplog::{oplog_record, OpKind},
+		repository::repo,
+		utils::work_dir,
+	},
 };
 use git2::{
-	build::CheckoutBuilder, Oid, Repository, StashApplyOptions,
-	StashFlags,
+	build::CheckoutBuilder, Commit, Index, IndexEntry, IndexTime,
+	MergeFileOptions, ObjectType, Oid, Repository, StashApplyOptions,
+	StashFlags, Status, StatusOptions, Tree, TreeEntry,
 };
 use scopetime::scope_time;
+use std::path::Path;
 
 ///
 pub fn get_stashes(repo_path: &RepoPath) -> Result<Vec<CommitId>> {
@@ -32,11 +42,19 @@ pub fn stash_drop(
 
 	let mut repo = repo(repo_path)?;
 
-	let index = get_stash_index(&mut repo, stash_id.into())?;
+	let result = get_stash_index(&mut repo, stash_id.into())
+		.and_then(|index| repo.stash_drop(index).map_err(Into::into));
 
-	repo.stash_drop(index)?;
+	oplog_record(
+		repo_path,
+		OpKind::StashDrop,
+		"refs/stash",
+		Some(stash_id),
+		None,
+		&result,
+	);
 
-	Ok(())
+	result
 }
 
 ///
@@ -124,11 +142,416 @@ pub fn stash_save(
 	Ok(CommitId::new(id))
 }
 
+/// like `git stash push --include-untracked -- <paths>`: only the
+/// changes of `paths` (files or folders) are stashed, all others
+/// stay in the working dir and the index
+pub fn stash_save_paths(
+	repo_path: &RepoPath,
+	message: Option<&str>,
+	paths: &[String],
+) -> Result<CommitId> {
+	scope_time!("stash_save_paths");
+
+	let repo = repo(repo_path)?;
+
+	let (tracked, untracked) = changed_files(&repo, paths)?;
+	if tracked.is_empty() && untracked.is_empty() {
+		return Err(Error::Generic(String::from(
+			"no changes to stash",
+		)));
+	}
+
+	let head = repo.head()?.peel_to_commit()?;
+	let head_tree = head.tree()?;
+	let mut head_index = Index::new()?;
+	head_index.read_tree(&head_tree)?;
+
+	let mut index = repo.index()?;
+	index.read(true)?;
+
+	let trees = stash_trees(
+		&repo, &mut index, &head_tree, &tracked, &untracked,
+	);
+	// the stash trees were built in the index, nothing of it is kept
+	index.read(true)?;
+	let trees = trees?;
+
+	let id = commit_stash(&repo, &head, &trees, message)?;
+
+	// back to `HEAD` for exactly the stashed paths
+	for path in &tracked {
+		match head_index.get_path(Path::new(path), 0) {
+			Some(entry) => index.add(&entry)?,
+			None => index.remove_path(Path::new(path))?,
+		}
+	}
+	index.write()?;
+
+	let mut checkout = CheckoutBuilder::new();
+	checkout
+		.force()
+		.remove_untracked(true)
+		.disable_pathspec_match(true);
+	for path in tracked.iter().chain(&untracked) {
+		checkout.path(path);
+	}
+	repo.checkout_head(Some(&mut checkout))?;
+
+	Ok(id)
+}
+
+/// changed files in `paths` split into tracked and untracked ones,
+/// folders are expanded
+fn changed_files(
+	repo: &Repository,
+	paths: &[String],
+) -> Result<(Vec<String>, Vec<String>)> {
+	let mut tracked = Vec::new();
+	let mut untracked = Vec::new();
+
+	if paths.is_empty() {
+		return Ok((tracked, untracked));
+	}
+
+	let mut options = StatusOptions::new();
+	options
+		.include_untracked(true)
+		.recurse_untracked_dirs(true)
+		.disable_pathspec_match(true);
+	for path in paths {
+		options.pathspec(path);
+	}
+
+	for entry in repo.statuses(Some(&mut options))?.iter() {
+		let Some(path) = entry.path().map(String::from) else {
+			continue;
+		};
+
+		if entry.status().is_conflicted() {
+			return Err(Error::Generic(format!(
+				"'{path}' is conflicted and cannot be stashed"
+			)));
+		} else if entry.status() == Status::WT_NEW {
+			untracked.push(path);
+		} else {
+			tracked.push(path);
+		}
+	}
+
+	Ok((tracked, untracked))
+}
+
+/// trees of the index, working dir and untracked parts of a stash
+struct StashTrees {
+	index: Oid,
+	work_dir: Oid,
+	untracked: Option<Oid>,
+}
+
+/// `index` is used for the working dir versions of the files so
+/// they go through the filters of the repo like `git add` does, it
+/// has to be read again afterwards
+fn stash_trees(
+	repo: &Repository,
+	index: &mut Index,
+	head_tree: &Tree,
+	tracked: &[String],
+	untracked: &[String],
+) -> Result<StashTrees> {
+	let mut staged = Index::new()?;
+	staged.read_tree(head_tree)?;
+	for path in tracked {
+		match index.get_path(Path::new(path), 0) {
+			Some(entry) => staged.add(&entry)?,
+			None => staged.remove_path(Path::new(path))?,
+		}
+	}
+	let index_tree = repo.find_tree(staged.write_tree_to(repo)?)?;
+
+	let work_dir = work_dir(repo)?;
+	index.read_tree(&index_tree)?;
+	for path in tracked {
+		if work_dir.join(path).symlink_metadata().is_ok() {
+			index.add_path(Path::new(path))?;
+		} else {
+			index.remove_path(Path::new(path))?;
+		}
+	}
+	let work_dir_tree = index.write_tree()?;
+
+	let untracked_tree = if untracked.is_empty() {
+		None
+	} else {
+		index.clear()?;
+		for path in untracked {
+			index.add_path(Path::new(path))?;
+		}
+		Some(index.write_tree()?)
+	};
+
+	Ok(StashTrees {
+		index: index_tree.id(),
+		work_dir: work_dir_tree,
+		untracked: untracked_tree,
+	})
+}
+
+/// writes the commits `git stash` would and puts them on top of the
+/// stash list
+fn commit_stash(
+	repo: &Repository,
+	head: &Commit,
+	trees: &StashTrees,
+	message: Option<&str>,
+) -> Result<CommitId> {
+	let sig = signature_allow_undefined_name(repo)?;
+
+	let branch = if repo.head_detached()? {
+		String::from("(no branch)")
+	} else {
+		get_branch_name_repo(repo)?
+	};
+	let on = format!(
+		"{branch}: {} {}",
+		CommitId::new(head.id()).get_short_string(),
+		head.summary().unwrap_or_default()
+	);
+
+	let index = repo.find_commit(repo.commit(
+		None,
+		&sig,
+		&sig,
+		&format!("index on {on}"),
+		&repo.find_tree(trees.index)?,
+		&[head],
+	)?)?;
+
+	let untracked = trees
+		.untracked
+		.map(|tree| {
+			repo.find_tree(tree).and_then(|tree| {
+				repo.commit(
+					None,
+					&sig,
+					&sig,
+					&format!("untracked files on {on}"),
+					&tree,
+					&[],
+				)
+				.and_then(|id| repo.find_commit(id))
+			})
+		})
+		.transpose()?;
+
+	let message = message.map_or_else(
+		|| format!("WIP on {on}"),
+		|message| format!("On {branch}: {message}"),
+	);
+
+	let mut parents = vec![head, &index];
+	if let Some(untracked) = &untracked {
+		parents.push(untracked);
+	}
+
+	let id = repo.commit(
+		None,
+		&sig,
+		&sig,
+		&message,
+		&repo.find_tree(trees.work_dir)?,
+		&parents,
+	)?;
+
+	repo.reference_ensure_log("refs/stash")?;
+	repo.reference("refs/stash", id, true, &message)?;
+
+	Ok(CommitId::new(id))
+}
+
+/// like `git stash branch`
+///
+/// creates `name` at the commit `stash_id` was made on, checks it
+/// out and pops the stash there, staged changes go back into the
+/// index. refuses to start with a dirty working tree, a failing
+/// apply keeps the stash.
+pub fn stash_branch(
+	repo_path: &RepoPath,
+	name: &str,
+	stash_id: CommitId,
+) -> Result<()> {
+	scope_time!("stash_branch");
+
+	let mut repo = repo(repo_path)?;
+
+	let mut options = StatusOptions::new();
+	options.include_untracked(true);
+	if !repo.statuses(Some(&mut options))?.is_empty() {
+		return Err(Error::Generic(String::from(
+			"the working tree has changes, commit or stash them first",
+		)));
+	}
+
+	let index = get_stash_index(&mut repo, stash_id.into())?;
+	let base = repo.find_commit(stash_id.into())?.parent_id(0)?;
+
+	create_branch_at(repo_path, name, base.into())?;
+	checkout_branch(repo_path, name)?;
+
+	// the index cached before the checkout is outdated
+	let mut repo = super::repository::repo(repo_path)?;
+	let mut opt = StashApplyOptions::default();
+	opt.reinstantiate_index();
+	repo.stash_apply(index, Some(&mut opt)).map_err(|e| {
+		Error::Generic(format!(
+			"'{name}' is checked out, but applying the stash failed, it stays in the stash list:\n{e}"
+		))
+	})?;
+
+	if repo.index()?.has_conflicts() {
+		return Err(Error::Generic(format!(
+			"'{name}' is checked out, but the stash conflicts, it stays in the stash list"
+		)));
+	}
+
+	repo.stash_drop(index)?;
+
+	Ok(())
+}
+
+/// what [`stash_apply_file`] did with the working tree file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StashFileApply {
+	/// the file was the one stashed on (or gone) and got replaced
+	Applied,
+	/// the file changed since, the stashed changes merged cleanly
+	Merged,
+	/// merging conflicts, the markers are in the file now
+	Conflicted,
+}
+
+/// brings back the stashed version of `path` only, the stash itself
+/// is kept. a file changed since it was stashed gets the stashed
+/// changes merged into it instead of being overwritten.
+pub fn stash_apply_file(
+	repo_path: &RepoPath,
+	stash_id: CommitId,
+	path: &str,
+) -> Result<StashFileApply> {
+	scope_time!("stash_apply_file");
+
+	let repo = repo(repo_path)?;
+	let stash = repo.find_commit(stash_id.into())?;
+	let file = Path::new(path);
+	let full = work_dir(&repo)?.join(file);
+
+	let base = stash.parent(0)?.tree()?.get_path(file).ok();
+	let current = if full.is_file() {
+		Some(Oid::hash_file(ObjectType::Blob, &full)?)
+	} else {
+		None
+	};
+	let unchanged = current.is_none()
+		|| current == base.as_ref().map(TreeEntry::id);
+
+	let Some(tree) = stashed_tree(&stash, file)? else {
+		// the file was deleted in the stash
+		if base.is_none() {
+			return Err(Error::Generic(format!(
+				"'{path}' is not in the stash"
+			)));
+		} else if !unchanged {
+			return Err(Error::Generic(format!(
+				"'{path}' changed since it was stashed as deleted"
+			)));
+		}
+
+		if current.is_some() {
+			std::fs::remove_file(&full)?;
+		}
+		return Ok(StashFileApply::Applied);
+	};
+	let stashed = tree.get_path(file)?;
+
+	if unchanged || current == Some(stashed.id()) {
+		let mut checkout = CheckoutBuilder::new();
+		checkout
+			.force()
+			.update_index(false)
+			.disable_pathspec_match(true)
+			.path(path);
+		repo.checkout_tree(tree.as_object(), Some(&mut checkout))?;
+
+		return Ok(StashFileApply::Applied);
+	}
+
+	let mode = u32::try_from(stashed.filemode())?;
+	let ancestor = match &base {
+		Some(base) => base.id(),
+		None => repo.blob(&[])?,
+	};
+
+	let mut opts = MergeFileOptions::new();
+	opts.our_label("working tree").their_label("stash");
+
+	let merged = repo.merge_file_from_index(
+		&blob_entry(path, mode, ancestor)?,
+		&blob_entry(path, mode, repo.blob_path(&full)?)?,
+		&blob_entry(path, mode, stashed.id())?,
+		Some(&mut opts),
+	)?;
+	std::fs::write(&full, merged.content())?;
+
+	Ok(if merged.is_automergeable() {
+		StashFileApply::Merged
+	} else {
+		StashFileApply::Conflicted
+	})
+}
+
+/// the tree of `stash` holding `file`, stashed untracked files are
+/// in the tree of its third parent
+fn stashed_tree<'a>(
+	stash: &Commit<'a>,
+	file: &Path,
+) -> Result<Option<Tree<'a>>> {
+	let tree = stash.tree()?;
+	if tree.get_path(file).is_ok() {
+		return Ok(Some(tree));
+	}
+
+	Ok(stash
+		.parent(2)
+		.ok()
+		.map(|untracked| untracked.tree())
+		.transpose()?
+		.filter(|tree| tree.get_path(file).is_ok()))
+}
+
+fn blob_entry(path: &str, mode: u32, id: Oid) -> Result<IndexEntry> {
+	let path = path.as_bytes().to_vec();
+
+	Ok(IndexEntry {
+		ctime: IndexTime::new(0, 0),
+		mtime: IndexTime::new(0, 0),
+		dev: 0,
+		ino: 0,
+		mode,
+		uid: 0,
+		gid: 0,
+		file_size: 0,
+		id,
+		flags: u16::try_from(path.len().min(0xfff))?,
+		flags_extended: 0,
+		path,
+	})
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use crate::sync::{
-		commit, get_commit_files, get_commits_info, stage_add_file,
+		commit, get_commit_details, get_commit_files,
+		get_commits_info, stage_add_file,
 		tests::{
 			debug_cmd_print, get_statuses, repo_init,
 			write_commit_file,
@@ -192,6 +615,184 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_stash_paths() -> Result<()> {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		std::fs::create_dir(root.join("sub"))?;
+		write_commit_file(&repo, "a", "a", "commit a");
+		write_commit_file(&repo, "sub/b", "b", "commit b");
+		repo_write_file(&repo, "a", "a changed").unwrap();
+		repo_write_file(&repo, "sub/b", "b changed").unwrap();
+		repo_write_file(&repo, "sub/c", "untracked").unwrap();
+		repo_write_file(&repo, "d", "untracked").unwrap();
+
+		let id = stash_save_paths(
+			repo_path,
+			Some("part"),
+			&[String::from("sub")],
+		)?;
+
+		assert_eq!(get_stashes(repo_path)?, vec![id]);
+		assert_eq!(
+			get_commits_info(repo_path, &[id], 100)?[0].message,
+			"On master: part"
+		);
+		let mut files: Vec<_> =
+			get_commit_files(repo_path, id, None)?
+				.into_iter()
+				.map(|file| file.path)
+				.collect();
+		files.sort();
+		assert_eq!(files, vec!["sub/b", "sub/c"]);
+
+		assert_eq!(repo_read_file(&repo, "a").unwrap(), "a changed");
+		assert_eq!(repo_read_file(&repo, "sub/b").unwrap(), "b");
+		assert!(!root.join("sub/c").exists());
+		assert!(root.join("d").exists());
+
+		stash_pop(repo_path, id)?;
+		assert_eq!(
+			repo_read_file(&repo, "sub/b").unwrap(),
+			"b changed"
+		);
+		assert!(root.join("sub/c").exists());
+		assert!(get_stashes(repo_path)?.is_empty());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_stash_paths_literal() -> Result<()> {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		repo_write_file(&repo, "a.txt", "a").unwrap();
+		repo_write_file(&repo, "[ab].txt", "glob").unwrap();
+
+		let id = stash_save_paths(
+			repo_path,
+			None,
+			&[String::from("[ab].txt")],
+		)?;
+
+		let files: Vec<_> = get_commit_files(repo_path, id, None)?
+			.into_iter()
+			.map(|file| file.path)
+			.collect();
+		assert_eq!(files, vec!["[ab].txt"]);
+		assert!(root.join("a.txt").exists());
+		assert!(!root.join("[ab].txt").exists());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_stash_paths_without_user_name() -> Result<()> {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		repo.config()?.remove("user.name")?;
+		repo_write_file(&repo, "a", "a").unwrap();
+
+		let id =
+			stash_save_paths(repo_path, None, &[String::from("a")])?;
+
+		let details = get_commit_details(repo_path, id)?;
+		assert_eq!(details.author.name, "unknown");
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_stash_apply_file() -> Result<()> {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a", "1\n2\n3\n", "commit a");
+		write_commit_file(&repo, "b", "b", "commit b");
+		repo_write_file(&repo, "a", "1\n2\nstashed\n").unwrap();
+		repo_write_file(&repo, "b", "b changed").unwrap();
+		repo_write_file(&repo, "u", "untracked").unwrap();
+
+		let id = stash_save(repo_path, None, true, false)?;
+
+		assert_eq!(
+			stash_apply_file(repo_path, id, "u")?,
+			StashFileApply::Applied
+		);
+		assert_eq!(repo_read_file(&repo, "u").unwrap(), "untracked");
+		assert_eq!(repo_read_file(&repo, "b").unwrap(), "b");
+
+		repo_write_file(&repo, "a", "changed\n2\n3\n").unwrap();
+		assert_eq!(
+			stash_apply_file(repo_path, id, "a")?,
+			StashFileApply::Merged
+		);
+		assert_eq!(
+			repo_read_file(&repo, "a").unwrap(),
+			"changed\n2\nstashed\n"
+		);
+
+		repo_write_file(&repo, "a", "1\n2\nother\n").unwrap();
+		assert_eq!(
+			stash_apply_file(repo_path, id, "a")?,
+			StashFileApply::Conflicted
+		);
+		assert!(repo_read_file(&repo, "a")
+			.unwrap()
+			.contains("<<<<<<< working tree"));
+
+		// the index is left alone and the stash is kept
+		assert_eq!(get_statuses(repo_path), (2, 0));
+		assert_eq!(get_stashes(repo_path)?, vec![id]);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_stash_branch() -> Result<()> {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a", "a", "commit a");
+		let base = repo.head()?.peel_to_commit()?.id();
+		repo_write_file(&repo, "a", "stashed").unwrap();
+		stage_add_file(repo_path, Path::new("a")).unwrap();
+		let id = stash_save(repo_path, None, true, false)?;
+
+		write_commit_file(&repo, "a", "moved on", "commit b");
+
+		repo_write_file(&repo, "dirty", "x").unwrap();
+		assert!(stash_branch(repo_path, "from-stash", id).is_err());
+		std::fs::remove_file(root.join("dirty"))?;
+		assert!(repo
+			.find_branch("from-stash", git2::BranchType::Local)
+			.is_err());
+
+		stash_branch(repo_path, "from-stash", id)?;
+
+		assert_eq!(repo.head()?.shorthand(), Some("from-stash"));
+		assert_eq!(repo.head()?.peel_to_commit()?.id(), base);
+		assert_eq!(repo_read_file(&repo, "a").unwrap(), "stashed");
+		// staged like before stashing
+		assert_eq!(get_statuses(repo_path), (0, 1));
+		assert!(get_stashes(repo_path)?.is_empty());
+
+		Ok(())
+	}
+
 	#[test]
 	fn test_stash_nothing_untracked() -> Result<()> {
 		let (_td, repo) = repo_init().unwrap();
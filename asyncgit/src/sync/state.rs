@@ -4,7 +4,7 @@ use git2::RepositoryState;
 use scopetime::scope_time;
 
 ///
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RepoState {
 	///
 	Clean,
@@ -15,16 +15,25 @@ pub enum RepoState {
 	///
 	Revert,
 	///
+	CherryPick,
+	///
 	Other,
 }
 
 impl From<RepositoryState> for RepoState {
 	fn from(state: RepositoryState) -> Self {
 		match state {
-			RepositoryState::Clean => Self::Clean,
+			// nothing is pending in between the bisect steps
+			RepositoryState::Clean | RepositoryState::Bisect => {
+				Self::Clean
+			}
 			RepositoryState::Merge => Self::Merge,
 			RepositoryState::Revert => Self::Revert,
-			RepositoryState::RebaseMerge => Self::Rebase,
+			RepositoryState::RebaseMerge
+			| RepositoryState::RebaseInteractive
+			| RepositoryState::Rebase => Self::Rebase,
+			RepositoryState::CherryPick
+			| RepositoryState::CherryPickSequence => Self::CherryPick,
 			_ => {
 				log::warn!("state not supported yet: {:?}", state);
 				Self::Other
@@ -4,12 +4,27 @@ use crate::{
 	error::Result,
 	sync::{
 		config::untracked_files_config_repo,
+		conflict::{get_conflicts_repo, ConflictType},
+		file_mode::{set_file_modes, FileMode},
+		lfs::set_lfs_tracked,
 		repository::{gix_repo, repo},
+		rerere::rerere_resolved_paths,
+		submodules::SubmoduleChanges,
+		utils::work_dir,
 	},
 };
-use git2::{Delta, Status, StatusOptions, StatusShow};
+use git2::{
+	Delta, Status, StatusOptions, StatusShow, Submodule,
+	SubmoduleIgnore,
+};
 use scopetime::scope_time;
-use std::path::Path;
+use std::{
+	collections::BTreeSet,
+	fs::{self, File},
+	io::{Read, Seek, SeekFrom},
+	ops::Bound,
+	path::Path,
+};
 
 use super::{RepoPath, ShowUntrackedFilesConfig};
 
@@ -101,6 +116,19 @@ pub struct StatusItem {
 	pub path: String,
 	///
 	pub status: StatusItemType,
+	/// kind of conflict if `status` is `Conflicted`
+	pub conflict: Option<ConflictType>,
+	/// conflicted but the working dir file got resolved with a
+	/// recorded resolution (rerere), needs review before staging
+	pub auto_resolved: bool,
+	/// set if the item is a submodule
+	pub submodule: Option<SubmoduleChanges>,
+	/// tracked by git lfs, the repo holds a pointer to its content
+	pub lfs: bool,
+	/// mode of the file, `None` if it does not exist (anymore)
+	pub mode: Option<FileMode>,
+	/// where the file points to if it is a symlink
+	pub symlink_target: Option<String>,
 }
 
 ///
@@ -162,6 +190,43 @@ pub fn is_workdir_clean(
 	Ok(statuses.is_empty())
 }
 
+/// fingerprint of everything a `StatusType::Stage` pass depends on:
+/// the index file and the commit `HEAD` points to.
+///
+/// it changes whenever the index content changes or `HEAD` moves and
+/// is cheap to compute, so it can be used to cache stage results.
+pub fn index_fingerprint(repo_path: &RepoPath) -> Result<u64> {
+	let repo = repo(repo_path)?;
+
+	let head = repo.head().ok().and_then(|head| head.target());
+	let index_path = repo.path().join("index");
+	let index = fs::metadata(&index_path)
+		.ok()
+		.map(|meta| (meta.len(), meta.modified().ok()));
+
+	Ok(crate::hash(&(head, index, index_checksum(&index_path))))
+}
+
+/// trailing checksum git writes at the end of the index file
+///
+/// covers writes of the same size within one mtime tick. it is all
+/// zeros with `index.skipHash` set, that is why the fingerprint keeps
+/// size and mtime as well.
+fn index_checksum(path: &Path) -> Option<Vec<u8>> {
+	// long enough for sha1 and sha256 repositories
+	const CHECKSUM_LEN: u64 = 32;
+
+	let mut file = File::open(path).ok()?;
+	let len = file.metadata().ok()?.len();
+	file.seek(SeekFrom::Start(len.saturating_sub(CHECKSUM_LEN)))
+		.ok()?;
+
+	let mut checksum = Vec::new();
+	file.read_to_end(&mut checksum).ok()?;
+
+	Some(checksum)
+}
+
 impl From<ShowUntrackedFilesConfig> for gix::status::UntrackedFiles {
 	fn from(value: ShowUntrackedFilesConfig) -> Self {
 		match value {
@@ -177,10 +242,157 @@ pub fn get_status(
 	repo_path: &RepoPath,
 	status_type: StatusType,
 	show_untracked: Option<ShowUntrackedFilesConfig>,
+) -> Result<Vec<StatusItem>> {
+	get_status_scoped(repo_path, status_type, show_untracked, None)
+}
+
+/// pathspec limiting a status to everything below folder `scope`
+fn scope_pathspec(scope: Option<&str>) -> Vec<gix::bstr::BString> {
+	scope
+		.map(|scope| scope.trim_end_matches('/'))
+		.filter(|scope| !scope.is_empty())
+		.map(|scope| format!(":(literal){scope}/").into())
+		.into_iter()
+		.collect()
+}
+
+/// like [`get_status`] but only looks at files inside folder `scope`
+/// (relative to the repo root), the whole repo if `None`
+pub fn get_status_scoped(
+	repo_path: &RepoPath,
+	status_type: StatusType,
+	show_untracked: Option<ShowUntrackedFilesConfig>,
+	scope: Option<&str>,
 ) -> Result<Vec<StatusItem>> {
 	scope_time!("get_status");
 
+	status_matching(
+		repo_path,
+		status_type,
+		show_untracked,
+		scope_pathspec(scope),
+	)
+}
+
+/// rechecks the working dir status of `paths` only and merges it into
+/// `items`, the result of an earlier [`StatusType::WorkingDir`] pass
+///
+/// brings a cached working dir pass up to date after the index changed
+/// for a few files without scanning the whole working dir. entries of
+/// collapsed untracked folders containing one of `paths` are rechecked
+/// as well.
+pub fn refresh_workdir_status(
+	repo_path: &RepoPath,
+	show_untracked: Option<ShowUntrackedFilesConfig>,
+	mut items: Vec<StatusItem>,
+	paths: &BTreeSet<String>,
+) -> Result<Vec<StatusItem>> {
+	scope_time!("refresh_workdir_status");
+
+	if paths.is_empty() {
+		return Ok(items);
+	}
+
+	// an entry for a folder covers everything inside, those paths
+	// sort right after its own
+	let is_stale = |item: &StatusItem| {
+		paths
+			.range::<str, _>((
+				Bound::Included(item.path.as_str()),
+				Bound::Unbounded,
+			))
+			.take_while(|path| path.starts_with(item.path.as_str()))
+			.any(|path| Path::new(path).starts_with(&item.path))
+	};
+
+	let patterns = items
+		.iter()
+		.filter(|item| is_stale(item))
+		.map(|item| item.path.as_str())
+		.chain(paths.iter().map(String::as_str))
+		.map(|path| format!(":(literal){path}").into())
+		.collect();
+
+	let fresh = status_matching(
+		repo_path,
+		StatusType::WorkingDir,
+		show_untracked,
+		patterns,
+	)?;
+
+	items.retain(|item| !is_stale(item));
+	items.extend(fresh);
+	items.sort_by(|a, b| {
+		Path::new(a.path.as_str()).cmp(Path::new(b.path.as_str()))
+	});
+	items.dedup_by(|a, b| a.path == b.path);
+
+	Ok(items)
+}
+
+/// replaces collapsed untracked folders in `items` by the untracked
+/// files inside of them
+pub fn expand_untracked_folders(
+	repo_path: &RepoPath,
+	items: Vec<StatusItem>,
+) -> Result<Vec<StatusItem>> {
+	scope_time!("expand_untracked_folders");
+
+	let workdir = work_dir(&repo(repo_path)?)?.to_path_buf();
+	let (folders, mut items): (Vec<_>, Vec<_>) =
+		items.into_iter().partition(|item| {
+			item.status == StatusItemType::New
+				&& fs::symlink_metadata(workdir.join(&item.path))
+					.is_ok_and(|meta| meta.is_dir())
+		});
+
+	if folders.is_empty() {
+		return Ok(items);
+	}
+
+	let patterns = folders
+		.iter()
+		.map(|item| {
+			format!(":(literal){}/", item.path.trim_end_matches('/'))
+				.into()
+		})
+		.collect();
+
+	let files = status_matching(
+		repo_path,
+		StatusType::WorkingDir,
+		Some(ShowUntrackedFilesConfig::All),
+		patterns,
+	)?
+	.into_iter()
+	.filter(|item| item.status == StatusItemType::New)
+	.collect::<Vec<_>>();
+
+	// nested repositories are not expanded and stay as they are
+	items.extend(folders.into_iter().filter(|folder| {
+		!files.iter().any(|file| {
+			Path::new(&file.path).starts_with(&folder.path)
+		})
+	}));
+	items.extend(files);
+	items.sort_by(|a, b| {
+		Path::new(a.path.as_str()).cmp(Path::new(b.path.as_str()))
+	});
+	items.dedup_by(|a, b| a.path == b.path);
+
+	Ok(items)
+}
+
+fn status_matching(
+	repo_path: &RepoPath,
+	status_type: StatusType,
+	show_untracked: Option<ShowUntrackedFilesConfig>,
+	patterns: Vec<gix::bstr::BString>,
+) -> Result<Vec<StatusItem>> {
 	let repo: gix::Repository = gix_repo(repo_path)?;
+	let has_submodules = repo
+		.workdir()
+		.is_some_and(|wd| wd.join(".gitmodules").exists());
 
 	let mut status = repo.status(gix::progress::Discard)?;
 
@@ -188,88 +400,458 @@ pub fn get_status(
 		status = status.untracked_files(config.into());
 	}
 
+	let mut res = match status_type {
+		StatusType::WorkingDir => workdir_items(status, patterns)?,
+		StatusType::Stage => stage_items(&repo, &patterns)?,
+		StatusType::Both => all_items(status, patterns)?,
+	};
+
+	res.sort_by(|a, b| {
+		Path::new(a.path.as_str()).cmp(Path::new(b.path.as_str()))
+	});
+
+	set_conflict_types(repo_path, &mut res)?;
+	if has_submodules {
+		set_submodule_changes(repo_path, status_type, &mut res)?;
+	}
+	if !res.is_empty() {
+		// `repo` is the gix one here
+		let repo = super::repository::repo(repo_path)?;
+		set_lfs_tracked(&repo, &mut res)?;
+		set_file_modes(&repo, status_type, &mut res)?;
+	}
+
+	Ok(res)
+}
+
+const fn status_item(
+	path: String,
+	status: StatusItemType,
+) -> StatusItem {
+	StatusItem {
+		path,
+		status,
+		conflict: None,
+		auto_resolved: false,
+		submodule: None,
+		lfs: false,
+		mode: None,
+		symlink_target: None,
+	}
+}
+
+fn workdir_items(
+	status: gix::status::Platform<'_, gix::progress::Discard>,
+	patterns: Vec<gix::bstr::BString>,
+) -> Result<Vec<StatusItem>> {
 	let mut res = Vec::new();
 
-	match status_type {
-		StatusType::WorkingDir => {
-			let iter = status.into_index_worktree_iter(Vec::new())?;
+	for item in status.into_index_worktree_iter(patterns)? {
+		let item = item?;
+
+		if let Some(status) = item.summary().map(Into::into) {
+			res.push(status_item(
+				item.rela_path().to_string(),
+				status,
+			));
+		}
+	}
 
-			for item in iter {
-				let item = item?;
+	Ok(res)
+}
 
-				let status = item.summary().map(Into::into);
+fn stage_items(
+	repo: &gix::Repository,
+	patterns: &[gix::bstr::BString],
+) -> Result<Vec<StatusItem>> {
+	let mut res = Vec::new();
+
+	let tree_id: gix::ObjectId = repo.head_tree_id_or_empty()?.into();
+	let worktree_index =
+		gix::worktree::IndexPersistedOrInMemory::Persisted(
+			repo.index_or_empty()?,
+		);
 
-				if let Some(status) = status {
-					let path = item.rela_path().to_string();
+	let mut pathspec = repo.pathspec(
+		false, /* empty patterns match prefix */
+		patterns,
+		true, /* inherit ignore case */
+		&gix::index::State::new(repo.object_hash()),
+		gix::worktree::stack::state::attributes::Source::WorktreeThenIdMapping
+	)?;
+
+	let cb =
+		|change_ref: gix::diff::index::ChangeRef<'_, '_>,
+		 _: &gix::index::State,
+		 _: &gix::index::State|
+		 -> Result<gix::diff::index::Action> {
+			let path = change_ref.fields().0.to_string();
+			res.push(status_item(path, change_ref.into()));
+
+			Ok(gix::diff::index::Action::Continue)
+		};
+
+	repo.tree_index_status(
+		&tree_id,
+		&worktree_index,
+		Some(&mut pathspec),
+		gix::status::tree_index::TrackRenames::default(),
+		cb,
+	)?;
 
-					res.push(StatusItem { path, status });
-				}
+	Ok(res)
+}
+
+fn all_items(
+	status: gix::status::Platform<'_, gix::progress::Discard>,
+	patterns: Vec<gix::bstr::BString>,
+) -> Result<Vec<StatusItem>> {
+	let mut res = Vec::new();
+
+	for item in status.into_iter(patterns)? {
+		let item = item?;
+
+		let path = item.location().to_string();
+
+		let status = match item {
+			gix::status::Item::IndexWorktree(item) => {
+				item.summary().map(Into::into)
+			}
+			gix::status::Item::TreeIndex(change_ref) => {
+				Some(change_ref.into())
 			}
+		};
+
+		if let Some(status) = status {
+			res.push(status_item(path, status));
 		}
-		StatusType::Stage => {
-			let tree_id: gix::ObjectId =
-				repo.head_tree_id_or_empty()?.into();
-			let worktree_index =
-				gix::worktree::IndexPersistedOrInMemory::Persisted(
-					repo.index_or_empty()?,
-				);
-
-			let mut pathspec = repo.pathspec(
-				false, /* empty patterns match prefix */
-				None::<&str>,
-				true, /* inherit ignore case */
-				&gix::index::State::new(repo.object_hash()),
-				gix::worktree::stack::state::attributes::Source::WorktreeThenIdMapping
-			)?;
-
-			let cb =
-				|change_ref: gix::diff::index::ChangeRef<'_, '_>,
-				 _: &gix::index::State,
-				 _: &gix::index::State|
-				 -> Result<gix::diff::index::Action> {
-					let path = change_ref.fields().0.to_string();
-					let status = change_ref.into();
-
-					res.push(StatusItem { path, status });
-
-					Ok(gix::diff::index::Action::Continue)
-				};
-
-			repo.tree_index_status(
-				&tree_id,
-				&worktree_index,
-				Some(&mut pathspec),
-				gix::status::tree_index::TrackRenames::default(),
-				cb,
-			)?;
+	}
+
+	Ok(res)
+}
+
+fn set_submodule_changes(
+	repo_path: &RepoPath,
+	status_type: StatusType,
+	items: &mut [StatusItem],
+) -> Result<()> {
+	let repo = repo(repo_path)?;
+	let submodules = repo.submodules()?;
+
+	for item in items.iter_mut() {
+		let Some(name) = submodules
+			.iter()
+			.find(|s| s.path() == Path::new(&item.path))
+			.and_then(Submodule::name)
+		else {
+			continue;
+		};
+
+		item.submodule = repo
+			.submodule_status(name, SubmoduleIgnore::None)
+			.ok()
+			.map(|status| SubmoduleChanges::new(status, status_type));
+	}
+
+	Ok(())
+}
+
+fn set_conflict_types(
+	repo_path: &RepoPath,
+	items: &mut [StatusItem],
+) -> Result<()> {
+	if !items
+		.iter()
+		.any(|item| item.status == StatusItemType::Conflicted)
+	{
+		return Ok(());
+	}
+
+	let repo = repo(repo_path)?;
+	let conflicts = get_conflicts_repo(&repo)?;
+	let auto_resolved = rerere_resolved_paths(&repo)?;
+
+	for item in items
+		.iter_mut()
+		.filter(|item| item.status == StatusItemType::Conflicted)
+	{
+		item.conflict = conflicts
+			.iter()
+			.find(|conflict| conflict.path == item.path)
+			.map(|conflict| conflict.kind);
+		item.auto_resolved = auto_resolved.contains(&item.path);
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		commit, stage_add_file,
+		tests::{repo_init, write_commit_file},
+		utils::repo_write_file,
+	};
+
+	#[test]
+	fn test_status_scoped() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		std::fs::create_dir_all(root.join("a/b")).unwrap();
+		std::fs::create_dir_all(root.join("a/bc")).unwrap();
+
+		for file in ["a/b/x", "a/bc/y", "c"] {
+			write_commit_file(&repo, file, "a", "init");
+			repo_write_file(&repo, file, "b").unwrap();
 		}
-		StatusType::Both => {
-			let iter = status.into_iter(Vec::new())?;
-
-			for item in iter {
-				let item = item?;
-
-				let path = item.location().to_string();
-
-				let status = match item {
-					gix::status::Item::IndexWorktree(item) => {
-						item.summary().map(Into::into)
-					}
-					gix::status::Item::TreeIndex(change_ref) => {
-						Some(change_ref.into())
-					}
-				};
-
-				if let Some(status) = status {
-					res.push(StatusItem { path, status });
-				}
-			}
+		repo_write_file(&repo, "a/b/new", "b").unwrap();
+		stage_add_file(repo_path, Path::new("a/bc/y")).unwrap();
+		stage_add_file(repo_path, Path::new("a/b/x")).unwrap();
+		repo_write_file(&repo, "a/b/x", "c").unwrap();
+
+		let paths = |status_type, scope| {
+			get_status_scoped(repo_path, status_type, None, scope)
+				.unwrap()
+				.into_iter()
+				.map(|item| item.path)
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(
+			paths(StatusType::WorkingDir, Some("a/b")),
+			vec!["a/b/new", "a/b/x"]
+		);
+		assert_eq!(
+			paths(StatusType::WorkingDir, Some("a/b/")),
+			vec!["a/b/new", "a/b/x"]
+		);
+		assert_eq!(
+			paths(StatusType::Stage, Some("a/b")),
+			vec!["a/b/x"]
+		);
+		assert_eq!(paths(StatusType::Both, Some("a")).len(), 4);
+		assert_eq!(
+			paths(StatusType::WorkingDir, None),
+			vec!["a/b/new", "a/b/x", "c"]
+		);
+		assert!(paths(StatusType::Stage, Some("c/d")).is_empty());
+	}
+
+	#[test]
+	fn test_expand_untracked_folders() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		std::fs::create_dir_all(root.join("a/b/c")).unwrap();
+		write_commit_file(&repo, "a/x", "a", "init");
+		repo_write_file(&repo, "a/x", "b").unwrap();
+		for file in ["a/b/1", "a/b/2", "a/b/c/3"] {
+			repo_write_file(&repo, file, "new").unwrap();
 		}
+
+		let items = get_status_scoped(
+			repo_path,
+			StatusType::WorkingDir,
+			Some(ShowUntrackedFilesConfig::Normal),
+			Some("a"),
+		)
+		.unwrap();
+		assert_eq!(items.len(), 2);
+		assert!(items.iter().any(|item| item.path == "a/b"));
+
+		let expanded = expand_untracked_folders(repo_path, items)
+			.unwrap()
+			.into_iter()
+			.map(|item| (item.path, item.status))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			expanded,
+			vec![
+				(String::from("a/b/1"), StatusItemType::New),
+				(String::from("a/b/2"), StatusItemType::New),
+				(String::from("a/b/c/3"), StatusItemType::New),
+				(String::from("a/x"), StatusItemType::Modified),
+			]
+		);
 	}
 
-	res.sort_by(|a, b| {
-		Path::new(a.path.as_str()).cmp(Path::new(b.path.as_str()))
-	});
+	#[test]
+	fn test_index_fingerprint() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
 
-	Ok(res)
+		let initial = index_fingerprint(repo_path).unwrap();
+
+		// worktree only changes do not touch the fingerprint
+		repo_write_file(&repo, "foo.txt", "a").unwrap();
+		assert_eq!(index_fingerprint(repo_path).unwrap(), initial);
+
+		stage_add_file(repo_path, Path::new("foo.txt")).unwrap();
+		let staged = index_fingerprint(repo_path).unwrap();
+		assert_ne!(staged, initial);
+
+		commit(repo_path, "msg").unwrap();
+		assert_ne!(index_fingerprint(repo_path).unwrap(), staged);
+	}
+
+	#[test]
+	fn test_index_fingerprint_same_size_writes() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		repo_write_file(&repo, "foo.txt", "a").unwrap();
+		stage_add_file(repo_path, Path::new("foo.txt")).unwrap();
+		let index = repo.path().join("index");
+		let (len, mtime) = {
+			let meta = fs::metadata(&index).unwrap();
+			(meta.len(), meta.modified().unwrap())
+		};
+		let first = index_fingerprint(repo_path).unwrap();
+
+		repo_write_file(&repo, "foo.txt", "b").unwrap();
+		stage_add_file(repo_path, Path::new("foo.txt")).unwrap();
+		// pretend both writes happened within the same mtime tick
+		File::options()
+			.write(true)
+			.open(&index)
+			.unwrap()
+			.set_modified(mtime)
+			.unwrap();
+
+		assert_eq!(fs::metadata(&index).unwrap().len(), len);
+		assert_ne!(index_fingerprint(repo_path).unwrap(), first);
+	}
+
+	#[test]
+	fn test_refresh_workdir_status() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a", "a", "init");
+		write_commit_file(&repo, "b", "b", "init");
+		repo_write_file(&repo, "a", "changed").unwrap();
+		repo_write_file(&repo, "b", "changed").unwrap();
+		fs::create_dir_all(root.join("dir")).unwrap();
+		repo_write_file(&repo, "dir/new", "new").unwrap();
+
+		let paths = |items: &[StatusItem]| {
+			items
+				.iter()
+				.map(|item| item.path.clone())
+				.collect::<Vec<_>>()
+		};
+
+		let cached =
+			get_status(repo_path, StatusType::WorkingDir, None)
+				.unwrap();
+		assert_eq!(paths(&cached), vec!["a", "b", "dir"]);
+
+		stage_add_file(repo_path, Path::new("a")).unwrap();
+		stage_add_file(repo_path, Path::new("dir/new")).unwrap();
+		// not refreshed below, so it has to stay as cached
+		repo_write_file(&repo, "c", "untracked").unwrap();
+
+		let refreshed = refresh_workdir_status(
+			repo_path,
+			None,
+			cached,
+			&BTreeSet::from([
+				String::from("a"),
+				String::from("dir/new"),
+			]),
+		)
+		.unwrap();
+
+		assert_eq!(paths(&refreshed), vec!["b"]);
+
+		repo_write_file(&repo, "a", "changed again").unwrap();
+
+		let refreshed = refresh_workdir_status(
+			repo_path,
+			None,
+			refreshed,
+			&BTreeSet::from([String::from("a")]),
+		)
+		.unwrap();
+
+		assert_eq!(paths(&refreshed), vec!["a", "b"]);
+	}
+
+	#[test]
+	fn test_index_fingerprint_head_moves() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "foo.txt", "a", "c1");
+		let before = index_fingerprint(repo_path).unwrap();
+
+		let head = repo.head().unwrap().peel_to_commit().unwrap();
+		let parent = head.parent(0).unwrap();
+		repo.reset(parent.as_object(), git2::ResetType::Soft, None)
+			.unwrap();
+
+		assert_ne!(index_fingerprint(repo_path).unwrap(), before);
+	}
+
+	#[test]
+	fn test_linked_worktrees() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let main_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a", "1", "init");
+
+		let wt_td = tempfile::TempDir::new().unwrap();
+		let wt_root = wt_td.path().join("wt");
+		repo.worktree("wt", &wt_root, None).unwrap();
+		let wt_path: &RepoPath = &wt_root.to_str().unwrap().into();
+
+		let paths = |repo_path, status_type| {
+			get_status(repo_path, status_type, None)
+				.unwrap()
+				.into_iter()
+				.map(|item| item.path)
+				.collect::<Vec<_>>()
+		};
+
+		assert!(is_workdir_clean(main_path, None).unwrap());
+		assert!(is_workdir_clean(wt_path, None).unwrap());
+
+		std::fs::write(wt_root.join("a"), "2").unwrap();
+
+		assert!(is_workdir_clean(main_path, None).unwrap());
+		assert!(!is_workdir_clean(wt_path, None).unwrap());
+
+		std::fs::write(root.join("b"), "1").unwrap();
+
+		assert_eq!(paths(main_path, StatusType::WorkingDir), ["b"]);
+		assert_eq!(paths(wt_path, StatusType::WorkingDir), ["a"]);
+
+		let main_index = index_fingerprint(main_path).unwrap();
+		stage_add_file(wt_path, Path::new("a")).unwrap();
+
+		assert_eq!(paths(wt_path, StatusType::Stage), ["a"]);
+		assert!(paths(main_path, StatusType::Stage).is_empty());
+		assert_eq!(index_fingerprint(main_path).unwrap(), main_index);
+
+		stage_add_file(main_path, Path::new("b")).unwrap();
+
+		assert_eq!(paths(main_path, StatusType::Stage), ["b"]);
+		assert_eq!(paths(wt_path, StatusType::Stage), ["a"]);
+	}
 }
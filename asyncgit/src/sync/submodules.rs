@@ -1,16 +1,91 @@
 use std::path::{Path, PathBuf};
 
+use crossbeam_channel::Sender;
 use git2::{
-	Repository, RepositoryOpenFlags, Submodule,
-	SubmoduleUpdateOptions,
+	FetchOptions, Repository, RepositoryOpenFlags, Submodule,
+	SubmoduleIgnore, SubmoduleUpdateOptions,
 };
 use scopetime::scope_time;
 
-use super::{repo, CommitId, RepoPath};
+use super::{
+	remotes::{proxy_auto, push::ProgressNotification, Callbacks},
+	repo,
+	status::StatusType,
+	CommitId, RepoPath,
+};
 use crate::{error::Result, sync::utils::work_dir, Error};
 
 pub use git2::SubmoduleStatus;
 
+/// what changed in a submodule, worded like `git status` does
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Copy, Clone, Default, Hash, PartialEq, Eq, Debug)]
+pub struct SubmoduleChanges {
+	/// its `HEAD` is not the commit recorded in the parent
+	pub new_commits: bool,
+	/// tracked files of the submodule are changed
+	pub modified_content: bool,
+	///
+	pub untracked_content: bool,
+	/// not cloned into the working tree yet
+	pub uninitialized: bool,
+}
+
+impl SubmoduleChanges {
+	/// the changes of `status` that show in the status of
+	/// `status_type`
+	pub fn new(
+		status: SubmoduleStatus,
+		status_type: StatusType,
+	) -> Self {
+		let (stage, workdir) = match status_type {
+			StatusType::Stage => (true, false),
+			StatusType::WorkingDir => (false, true),
+			StatusType::Both => (true, true),
+		};
+
+		Self {
+			new_commits: (stage
+				&& status.intersects(
+					SubmoduleStatus::INDEX_ADDED
+						| SubmoduleStatus::INDEX_MODIFIED,
+				)) || (workdir && status.is_wd_modified()),
+			modified_content: workdir
+				&& status.intersects(
+					SubmoduleStatus::WD_INDEX_MODIFIED
+						| SubmoduleStatus::WD_WD_MODIFIED,
+				),
+			untracked_content: workdir && status.is_wd_untracked(),
+			uninitialized: workdir && status.is_wd_uninitialized(),
+		}
+	}
+}
+
+/// a submodule changing the commit it points to, which is all a diff
+/// of it has to say
+#[derive(Copy, Clone, Default, Hash, PartialEq, Eq, Debug)]
+pub struct SubmoduleDiff {
+	/// `None` if the submodule is new
+	pub old: Option<CommitId>,
+	/// `None` if the submodule is removed
+	pub new: Option<CommitId>,
+	/// only known for the diff of the working dir or the stage
+	pub changes: SubmoduleChanges,
+}
+
+/// the submodule at `path` (relative to the working dir), `None` if
+/// there is none
+pub fn submodule_status_at(
+	repo: &Repository,
+	path: &Path,
+) -> Option<SubmoduleStatus> {
+	let submodules = repo.submodules().ok()?;
+	let submodule = submodules.iter().find(|s| s.path() == path)?;
+
+	repo.submodule_status(submodule.name()?, SubmoduleIgnore::None)
+		.ok()
+}
+
 ///
 #[derive(Debug)]
 pub struct SubmoduleInfo {
@@ -87,10 +162,26 @@ pub fn get_submodules(
 	Ok(res)
 }
 
-///
+/// copies the url of the submodule `name` into the config, like
+/// `git submodule init`
+pub fn init_submodule(
+	repo_path: &RepoPath,
+	name: &str,
+) -> Result<()> {
+	scope_time!("init_submodule");
+
+	let repo = repo(repo_path)?;
+	repo.find_submodule(name)?.init(false)?;
+
+	Ok(())
+}
+
+/// clones (initializing it if needed) or fetches the submodule `name`
+/// and checks out the commit the parent records for it
 pub fn update_submodule(
 	repo_path: &RepoPath,
 	name: &str,
+	progress_sender: Option<Sender<ProgressNotification>>,
 ) -> Result<()> {
 	scope_time!("update_submodule");
 
@@ -98,8 +189,14 @@ pub fn update_submodule(
 
 	let mut submodule = repo.find_submodule(name)?;
 
+	let callbacks = Callbacks::new(progress_sender, None);
+	let mut fetch = FetchOptions::new();
+	fetch.remote_callbacks(callbacks.callbacks());
+	fetch.proxy_options(proxy_auto());
+
 	let mut options = SubmoduleUpdateOptions::new();
 	options.allow_fetch(true);
+	options.fetch(fetch);
 
 	submodule.update(true, Some(&mut options))?;
 
@@ -155,13 +252,39 @@ pub fn submodule_parent_info(
 
 #[cfg(test)]
 mod tests {
-	use super::get_submodules;
+	use super::*;
 	use crate::sync::{
-		submodules::submodule_parent_info, tests::repo_init, RepoPath,
+		commit,
+		diff::get_diff,
+		status::get_status,
+		tests::{repo_clone, repo_init, write_commit_file},
 	};
-	use git2::Repository;
 	use pretty_assertions::assert_eq;
-	use std::path::Path;
+	use std::fs;
+
+	/// a repo with the local repo `sub` (one commit) as submodule
+	fn repo_with_submodule(
+	) -> (tempfile::TempDir, tempfile::TempDir, RepoPath) {
+		let (sub_td, sub) = repo_init().unwrap();
+		write_commit_file(&sub, "file", "a", "a");
+
+		let (td, r) = repo_init().unwrap();
+		let repo_path: RepoPath = td.path().to_str().unwrap().into();
+		{
+			let mut s = r
+				.submodule(
+					sub_td.path().to_str().unwrap(),
+					Path::new("sub"),
+					false,
+				)
+				.unwrap();
+			s.clone(None).unwrap();
+			s.add_finalize().unwrap();
+		}
+		commit(&repo_path, "add sub").unwrap();
+
+		(sub_td, td, repo_path)
+	}
 
 	#[test]
 	fn test_smoke() {
@@ -198,4 +321,77 @@ mod tests {
 
 		assert_eq!(&info.submodule_info.name, "foo/bar");
 	}
+
+	#[test]
+	fn test_submodule_changes() {
+		let (_sub_td, td, repo_path) = repo_with_submodule();
+		let status = || {
+			get_status(&repo_path, StatusType::WorkingDir, None)
+				.unwrap()
+		};
+		assert!(status().is_empty());
+
+		let sub = Repository::open(td.path().join("sub")).unwrap();
+		let mut config = sub.config().unwrap();
+		config.set_str("user.name", "name").unwrap();
+		config.set_str("user.email", "email").unwrap();
+		let recorded = sub.head().unwrap().target().unwrap();
+		write_commit_file(&sub, "file", "b", "b");
+		fs::write(td.path().join("sub/file"), "c").unwrap();
+		let head = sub.head().unwrap().target().unwrap();
+
+		let items = status();
+		assert_eq!(items.len(), 1);
+		assert_eq!(items[0].path, "sub");
+		assert_eq!(
+			items[0].submodule,
+			Some(SubmoduleChanges {
+				new_commits: true,
+				modified_content: true,
+				..SubmoduleChanges::default()
+			})
+		);
+
+		let diff = get_diff(&repo_path, "sub", false, None).unwrap();
+		assert!(diff.hunks.is_empty());
+		assert_eq!(
+			diff.submodule,
+			Some(SubmoduleDiff {
+				old: Some(recorded.into()),
+				new: Some(head.into()),
+				changes: items[0].submodule.unwrap(),
+			})
+		);
+	}
+
+	#[test]
+	fn test_init_and_update() {
+		let (_sub_td, td, _) = repo_with_submodule();
+		let (clone_td, _clone) =
+			repo_clone(td.path().to_str().unwrap()).unwrap();
+		let repo_path: RepoPath =
+			clone_td.path().to_str().unwrap().into();
+
+		let subs = get_submodules(&repo_path).unwrap();
+		assert_eq!(subs.len(), 1);
+		assert!(subs[0].status.is_wd_uninitialized());
+
+		init_submodule(&repo_path, "sub").unwrap();
+		assert!(repo(&repo_path)
+			.unwrap()
+			.config()
+			.unwrap()
+			.get_string("submodule.sub.url")
+			.is_ok());
+
+		update_submodule(&repo_path, "sub", None).unwrap();
+		assert_eq!(
+			fs::read_to_string(clone_td.path().join("sub/file"))
+				.unwrap(),
+			"a"
+		);
+		assert!(get_status(&repo_path, StatusType::WorkingDir, None)
+			.unwrap()
+			.is_empty());
+	}
 }
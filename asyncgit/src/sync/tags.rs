@@ -3,11 +3,16 @@ use crate::{
 	error::Result,
 	sync::{repository::repo, utils::bytes2string},
 };
+use git2::Repository;
 use scopetime::scope_time;
-use std::{
-	collections::{BTreeMap, HashMap, HashSet},
-	ops::Not,
-};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// armor lines the signature appended by `git tag -s` starts with
+const SIGNATURE_HEADERS: [&str; 3] = [
+	"-----BEGIN PGP SIGNATURE-----",
+	"-----BEGIN PGP MESSAGE-----",
+	"-----BEGIN SSH SIGNATURE-----",
+];
 
 ///
 #[derive(Clone, Hash, PartialEq, Eq, Debug)]
@@ -47,6 +52,58 @@ pub struct TagWithMetadata {
 	pub commit_id: CommitId,
 	///
 	pub annotation: Option<String>,
+	/// `None` for lightweight tags
+	pub details: Option<TagDetails>,
+}
+
+/// the tag object of an annotated tag
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TagDetails {
+	/// `name <email>`
+	pub tagger: String,
+	///
+	pub time: i64,
+	///
+	pub signed: bool,
+}
+
+/// splits the message of a tag object into the message itself and
+/// the signature appended to it
+fn split_tag_signature(message: &str) -> (&str, Option<&str>) {
+	let start = SIGNATURE_HEADERS
+		.iter()
+		.filter_map(|header| {
+			if message.starts_with(header) {
+				Some(0)
+			} else {
+				message
+					.find(&format!("\n{header}"))
+					.map(|idx| idx + 1)
+			}
+		})
+		.min();
+
+	start.map_or((message, None), |start| {
+		(&message[..start], Some(&message[start..]))
+	})
+}
+
+fn tag_details(repo: &Repository, name: &str) -> Option<TagDetails> {
+	let id = repo.refname_to_id(&format!("refs/tags/{name}")).ok()?;
+	let tag = repo.find_tag(id).ok()?;
+	let tagger = tag.tagger();
+
+	Some(TagDetails {
+		tagger: tagger
+			.as_ref()
+			.map(ToString::to_string)
+			.unwrap_or_default(),
+		time: tagger.map_or(0, |tagger| tagger.when().seconds()),
+		signed: tag
+			.message_bytes()
+			.and_then(|msg| bytes2string(msg).ok())
+			.is_some_and(|msg| split_tag_signature(&msg).1.is_some()),
+	})
 }
 
 static MAX_MESSAGE_WIDTH: usize = 100;
@@ -95,12 +152,9 @@ pub fn get_tags(repo_path: &RepoPath) -> Result<Tags> {
 				.ok()
 				.as_ref()
 				.and_then(git2::Tag::message_bytes)
-				.and_then(|msg| {
-					msg.is_empty()
-						.not()
-						.then(|| bytes2string(msg).ok())
-						.flatten()
-				});
+				.and_then(|msg| bytes2string(msg).ok())
+				.map(|msg| split_tag_signature(&msg).0.to_string())
+				.filter(|msg| !msg.is_empty());
 
 			if let Some(commit) = commit {
 				adder(commit, Tag { name, annotation });
@@ -120,6 +174,7 @@ pub fn get_tags_with_metadata(
 ) -> Result<Vec<TagWithMetadata>> {
 	scope_time!("get_tags_with_metadata");
 
+	let repo = repo(repo_path)?;
 	let tags_grouped_by_commit_id = get_tags(repo_path)?;
 
 	let tags_with_commit_id: Vec<(&str, Option<&str>, &CommitId)> =
@@ -164,6 +219,7 @@ pub fn get_tags_with_metadata(
 					message: commit_info.message.clone(),
 					commit_id: *commit_id,
 					annotation: annotation.map(String::from),
+					details: tag_details(&repo, tag),
 				}
 			})
 		})
@@ -238,6 +294,10 @@ mod tests {
 		assert_eq!(tags[1].name, "b");
 		assert_eq!(tags[1].message, "initial");
 		assert_eq!(tags[0].commit_id, tags[1].commit_id);
+		assert_eq!(
+			tags[0].details.as_ref().map(|details| &details.tagger),
+			Some(&sig.to_string())
+		);
 
 		delete_tag(repo_path, "a").unwrap();
 
@@ -251,4 +311,44 @@ mod tests {
 
 		assert_eq!(tags.len(), 0);
 	}
+
+	#[test]
+	fn test_signed_tag() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let head_id = repo.head().unwrap().target().unwrap();
+		let target = repo
+			.find_object(head_id, Some(ObjectType::Commit))
+			.unwrap();
+		let sig = repo.signature().unwrap();
+
+		repo.tag(
+			"signed",
+			&target,
+			&sig,
+			"release\n-----BEGIN SSH SIGNATURE-----\nabc\n-----END SSH SIGNATURE-----\n",
+			false,
+		)
+		.unwrap();
+		repo.tag_lightweight("light", &target, false).unwrap();
+
+		assert_eq!(
+			get_tags(repo_path).unwrap()[&CommitId::new(head_id)][1]
+				.annotation,
+			Some(String::from("release\n"))
+		);
+
+		let tags = get_tags_with_metadata(repo_path).unwrap();
+		let details = |name: &str| {
+			tags.iter()
+				.find(|tag| tag.name == name)
+				.and_then(|tag| tag.details.clone())
+		};
+
+		assert_eq!(details("light"), None);
+		assert!(details("signed").unwrap().signed);
+	}
 }
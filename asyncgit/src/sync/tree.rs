@@ -1,12 +1,14 @@
-use super::{CommitId, RepoPath};
-use crate::{
-	error::{Error, Result},
-	sync::repository::repo,
+use super::{
+	binary::{image_size, ImageSize},
+	diff::{attr_diff_type, ForcedDiffType},
+	CommitId, RepoPath,
 };
+use crate::{error::Result, sync::repository::repo};
 use git2::{Oid, Repository, Tree};
 use scopetime::scope_time;
 use std::{
 	cmp::Ordering,
+	fs,
 	path::{Path, PathBuf},
 };
 
@@ -17,8 +19,10 @@ pub struct TreeFile {
 	pub path: PathBuf,
 	/// unix filemode
 	pub filemode: i32,
+	/// where the file points to if it is a symlink
+	pub symlink_target: Option<String>,
 	// internal object id
-	id: Oid,
+	pub(super) id: Oid,
 }
 
 /// guarantees sorting the result
@@ -26,6 +30,19 @@ pub fn tree_files(
 	repo_path: &RepoPath,
 	commit: CommitId,
 ) -> Result<Vec<TreeFile>> {
+	tree_files_with_progress(repo_path, commit, usize::MAX, |_| true)
+		.map(Option::unwrap_or_default)
+}
+
+/// like `tree_files`, `progress` is told how many files were read
+/// after every `batch` files, returning `false` stops reading and
+/// results in `None`
+pub fn tree_files_with_progress(
+	repo_path: &RepoPath,
+	commit: CommitId,
+	batch: usize,
+	mut progress: impl FnMut(usize) -> bool,
+) -> Result<Option<Vec<TreeFile>>> {
 	scope_time!("tree_files");
 
 	let repo = repo(repo_path)?;
@@ -35,11 +52,20 @@ pub fn tree_files(
 
 	let mut files: Vec<TreeFile> = Vec::new();
 
-	tree_recurse(&repo, &PathBuf::from("./"), &tree, &mut files)?;
+	if !tree_recurse(
+		&repo,
+		&PathBuf::from("./"),
+		&tree,
+		&mut files,
+		batch,
+		&mut progress,
+	)? {
+		return Ok(None);
+	}
 
 	sort_file_list(&mut files);
 
-	Ok(files)
+	Ok(Some(files))
 }
 
 fn sort_file_list(files: &mut [TreeFile]) {
@@ -71,33 +97,96 @@ fn path_cmp(a: &Path, b: &Path) -> Ordering {
 	}
 }
 
-/// will only work on utf8 content
+/// content of a file in a tree, binary content is not turned into
+/// text
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum TreeFileContent {
+	///
+	Text(String),
+	/// null bytes in it or a `binary` attribute
+	Binary {
+		///
+		bytes: Vec<u8>,
+		///
+		image: Option<ImageSize>,
+	},
+}
+
+/// text is only valid utf8 content, the rest gets replaced
 pub fn tree_file_content(
 	repo_path: &RepoPath,
 	file: &TreeFile,
-) -> Result<String> {
+) -> Result<TreeFileContent> {
 	scope_time!("tree_file_content");
 
 	let repo = repo(repo_path)?;
 
 	let blob = repo.find_blob(file.id)?;
 
-	if blob.is_binary() {
-		return Err(Error::BinaryFile);
+	let path = file.path.strip_prefix("./").unwrap_or(&file.path);
+	let forced = match path.to_str() {
+		Some(path) => attr_diff_type(&repo, path, true)?,
+		None => None,
+	};
+	let binary = match forced {
+		Some(ForcedDiffType::Text) => false,
+		Some(ForcedDiffType::Binary | ForcedDiffType::BigFile) => {
+			true
+		}
+		None => blob.is_binary(),
+	};
+
+	if binary {
+		return Ok(TreeFileContent::Binary {
+			bytes: blob.content().to_vec(),
+			image: image_size(blob.content()),
+		});
 	}
 
 	let content = String::from_utf8_lossy(blob.content()).to_string();
 
-	Ok(content)
+	Ok(TreeFileContent::Text(content))
+}
+
+/// writes the content of `file` to `dest` and marks it read-only,
+/// replaces a previous copy
+pub fn tree_file_write(
+	repo_path: &RepoPath,
+	file: &TreeFile,
+	dest: &Path,
+) -> Result<()> {
+	scope_time!("tree_file_write");
+
+	let repo = repo(repo_path)?;
+
+	let blob = repo.find_blob(file.id)?;
+
+	if let Ok(meta) = fs::metadata(dest) {
+		let mut permissions = meta.permissions();
+		#[allow(clippy::permissions_set_readonly_false)]
+		permissions.set_readonly(false);
+		fs::set_permissions(dest, permissions)?;
+	}
+
+	fs::write(dest, blob.content())?;
+
+	let mut permissions = fs::metadata(dest)?.permissions();
+	permissions.set_readonly(true);
+	fs::set_permissions(dest, permissions)?;
+
+	Ok(())
 }
 
 ///
+/// `false` once `progress` asked to stop
 fn tree_recurse(
 	repo: &Repository,
 	path: &Path,
 	tree: &Tree,
 	out: &mut Vec<TreeFile>,
-) -> Result<()> {
+	batch: usize,
+	progress: &mut impl FnMut(usize) -> bool,
+) -> Result<bool> {
 	out.reserve(tree.len());
 
 	for e in tree {
@@ -107,17 +196,40 @@ fn tree_recurse(
 			Some(git2::ObjectType::Blob) => {
 				let id = e.id();
 				let filemode = e.filemode();
-				out.push(TreeFile { path, filemode, id });
+				let symlink_target =
+					if filemode == i32::from(git2::FileMode::Link) {
+						let blob = repo.find_blob(id)?;
+						Some(
+							String::from_utf8_lossy(blob.content())
+								.to_string(),
+						)
+					} else {
+						None
+					};
+				out.push(TreeFile {
+					path,
+					filemode,
+					symlink_target,
+					id,
+				});
+
+				if out.len() % batch == 0 && !progress(out.len()) {
+					return Ok(false);
+				}
 			}
 			Some(git2::ObjectType::Tree) => {
 				let obj = e.to_object(repo)?;
 				let tree = obj.peel_to_tree()?;
-				tree_recurse(repo, &path, &tree, out)?;
+				if !tree_recurse(
+					repo, &path, &tree, out, batch, progress,
+				)? {
+					return Ok(false);
+				}
 			}
 			Some(_) | None => (),
 		}
 	}
-	Ok(())
+	Ok(true)
 }
 
 #[cfg(test)]
@@ -146,7 +258,16 @@ mod tests {
 
 		let content =
 			tree_file_content(repo_path, &files[0]).unwrap();
-		assert_eq!(&content, "content");
+		assert_eq!(content, TreeFileContent::Text("content".into()));
+
+		let dest = root.join("copy.txt");
+		tree_file_write(repo_path, &files[0], &dest).unwrap();
+		tree_file_write(repo_path, &files[0], &dest).unwrap();
+		assert_eq!(
+			std::fs::read_to_string(&dest).unwrap(),
+			"content"
+		);
+		assert!(dest.metadata().unwrap().permissions().readonly());
 
 		let files_c2 = tree_files(repo_path, c2).unwrap();
 
@@ -154,6 +275,80 @@ mod tests {
 		assert_ne!(files_c2[0], files[0]);
 	}
 
+	#[test]
+	fn test_binary_content() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(
+			&repo,
+			".gitattributes",
+			"*.dat binary\n*.txt diff\n",
+			"c1",
+		);
+		write_commit_file(&repo, "a.bin", "a\0b", "c2");
+		write_commit_file(&repo, "b.dat", "text", "c3");
+		let c4 = write_commit_file(&repo, "c.txt", "a\0b", "c4");
+
+		let files = tree_files(repo_path, c4).unwrap();
+		let content = |name: &str| {
+			let file = files
+				.iter()
+				.find(|f| f.path == Path::new("./").join(name))
+				.unwrap();
+			tree_file_content(repo_path, file).unwrap()
+		};
+
+		assert_eq!(
+			content("a.bin"),
+			TreeFileContent::Binary {
+				bytes: b"a\0b".to_vec(),
+				image: None,
+			}
+		);
+		assert!(matches!(
+			content("b.dat"),
+			TreeFileContent::Binary { .. }
+		));
+		assert_eq!(
+			content("c.txt"),
+			TreeFileContent::Text("a\0b".into())
+		);
+	}
+
+	#[test]
+	fn test_progress() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		std::fs::create_dir(root.join("sub")).unwrap();
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		write_commit_file(&repo, "sub/b.txt", "b", "c2");
+		let c3 = write_commit_file(&repo, "sub/c.txt", "c", "c3");
+
+		let mut reported = Vec::new();
+		let files = tree_files_with_progress(repo_path, c3, 2, |n| {
+			reported.push(n);
+			true
+		})
+		.unwrap()
+		.unwrap();
+
+		assert_eq!(files.len(), 3);
+		assert_eq!(reported, vec![2]);
+		assert_eq!(files, tree_files(repo_path, c3).unwrap());
+
+		let stopped =
+			tree_files_with_progress(repo_path, c3, 1, |_| false)
+				.unwrap();
+
+		assert_eq!(stopped, None);
+	}
+
 	#[test]
 	fn test_sorting() {
 		let mut list = ["file", "folder/file", "folder/afile"]
@@ -161,6 +356,7 @@ mod tests {
 			.map(|f| TreeFile {
 				path: PathBuf::from(f),
 				filemode: 0,
+				symlink_target: None,
 				id: Oid::zero(),
 			})
 			.collect::<Vec<_>>();
@@ -186,6 +382,7 @@ mod tests {
 			.map(|f| TreeFile {
 				path: PathBuf::from(f),
 				filemode: 0,
+				symlink_target: None,
 				id: Oid::zero(),
 			})
 			.collect::<Vec<_>>();
@@ -210,6 +407,7 @@ mod tests {
 			.map(|f| TreeFile {
 				path: PathBuf::from(f),
 				filemode: 0,
+				symlink_target: None,
 				id: Oid::zero(),
 			})
 			.collect::<Vec<_>>();
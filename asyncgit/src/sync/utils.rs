@@ -5,11 +5,17 @@ use super::{
 };
 use crate::{
 	error::{Error, Result},
-	sync::config::untracked_files_config_repo,
+	sync::{
+		config::untracked_files_config_repo,
+		index_file::check_index_writable,
+		oplog::{oplog_record, OpKind},
+		stage_cleanup::StageSnapshot,
+	},
 };
-use git2::{IndexAddOption, Repository, RepositoryOpenFlags};
+use git2::{IndexAddOption, Oid, Repository, RepositoryOpenFlags};
 use scopetime::scope_time;
 use std::{
+	collections::{BTreeSet, HashSet},
 	fs::File,
 	io::Write,
 	path::{Path, PathBuf},
@@ -39,6 +45,12 @@ pub(crate) fn work_dir(repo: &Repository) -> Result<&Path> {
 	repo.workdir().ok_or(Error::NoWorkDir)
 }
 
+/// folder inside the .git folder where gitui keeps its own per repo
+/// state (oplog, bookmarks)
+pub(crate) fn gitui_dir(repo_path: &RepoPath) -> Result<PathBuf> {
+	Ok(repo(repo_path)?.path().join("gitui"))
+}
+
 /// path to .git folder
 pub fn repo_dir(repo_path: &RepoPath) -> Result<PathBuf> {
 	let repo = repo(repo_path)?;
@@ -54,6 +66,33 @@ pub fn repo_work_dir(repo_path: &RepoPath) -> Result<String> {
 	)
 }
 
+/// directories a file watcher has to cover to see every change
+///
+/// for a linked worktree its git dir (`HEAD`, `index`) and the common
+/// dir (refs shared by all worktrees) are not inside the working tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoWatchPaths {
+	/// root of the (linked) worktree
+	pub work_dir: PathBuf,
+	/// git dir of the worktree
+	pub git_dir: PathBuf,
+	/// git dir of the main worktree
+	pub common_dir: PathBuf,
+}
+
+///
+pub fn repo_watch_paths(
+	repo_path: &RepoPath,
+) -> Result<RepoWatchPaths> {
+	let repo = repo(repo_path)?;
+
+	Ok(RepoWatchPaths {
+		work_dir: work_dir(&repo)?.to_path_buf(),
+		git_dir: repo.path().to_path_buf(),
+		common_dir: repo.commondir().to_path_buf(),
+	})
+}
+
 ///
 pub fn get_head(repo_path: &RepoPath) -> Result<CommitId> {
 	let repo = repo(repo_path)?;
@@ -86,6 +125,47 @@ pub fn get_head_repo(repo: &Repository) -> Result<CommitId> {
 	head.map_or(Err(Error::NoHead), |head_id| Ok(head_id.into()))
 }
 
+/// tips of all remote tracking branches
+pub fn remote_tips(repo: &Repository) -> Result<Vec<Oid>> {
+	let mut tips = Vec::new();
+
+	for reference in repo.references_glob("refs/remotes/*")? {
+		if let Some(id) = reference?.resolve()?.target() {
+			tips.push(id);
+		}
+	}
+
+	Ok(tips)
+}
+
+/// whether `id` is one of `tips` or an ancestor of one
+pub fn reachable_from(
+	repo: &Repository,
+	tips: &[Oid],
+	id: Oid,
+) -> Result<bool> {
+	for tip in tips {
+		if *tip == id || repo.graph_descendant_of(*tip, id)? {
+			return Ok(true);
+		}
+	}
+
+	Ok(false)
+}
+
+/// reachable from a remote tracking branch, rewriting it needs a
+/// force push
+pub fn is_commit_pushed(
+	repo_path: &RepoPath,
+	id: CommitId,
+) -> Result<bool> {
+	scope_time!("is_commit_pushed");
+
+	let repo = repo(repo_path)?;
+
+	reachable_from(&repo, &remote_tips(&repo)?, id.into())
+}
+
 /// add a file diff from workingdir to stage (will not add removed files see `stage_addremoved`)
 pub fn stage_add_file(
 	repo_path: &RepoPath,
@@ -94,10 +174,15 @@ pub fn stage_add_file(
 	scope_time!("stage_add_file");
 
 	let repo = repo(repo_path)?;
+	check_index_writable(&repo)?;
 
 	let mut index = repo.index()?;
+	let snapshot = StageSnapshot::take(&repo, &index)?;
 
 	index.add_path(path)?;
+	if let Some(snapshot) = snapshot {
+		snapshot.clean(&repo, &mut index)?;
+	}
 	index.write()?;
 
 	Ok(())
@@ -112,8 +197,10 @@ pub fn stage_add_all(
 	scope_time!("stage_add_all");
 
 	let repo = repo(repo_path)?;
+	check_index_writable(&repo)?;
 
 	let mut index = repo.index()?;
+	let snapshot = StageSnapshot::take(&repo, &index)?;
 
 	let stage_untracked = if let Some(config) = stage_untracked {
 		config
@@ -131,24 +218,132 @@ pub fn stage_add_all(
 		index.update_all(vec![pattern], None)?;
 	}
 
+	if let Some(snapshot) = snapshot {
+		snapshot.clean(&repo, &mut index)?;
+	}
 	index.write()?;
 
 	Ok(())
 }
 
+/// stages everything inside `folder` with a single index write.
+///
+/// this includes additions, modifications and deletions. conflicted
+/// paths are left alone so their conflict stays in the index, they
+/// are returned so they can be reported.
+pub fn stage_add_folder(
+	repo_path: &RepoPath,
+	folder: &str,
+	stage_untracked: Option<ShowUntrackedFilesConfig>,
+) -> Result<Vec<String>> {
+	scope_time!("stage_add_folder");
+
+	let repo = repo(repo_path)?;
+	check_index_writable(&repo)?;
+
+	let mut index = repo.index()?;
+	let snapshot = StageSnapshot::take(&repo, &index)?;
+
+	let stage_untracked = if let Some(config) = stage_untracked {
+		config
+	} else {
+		untracked_files_config_repo(&repo)?
+	};
+
+	let mut conflicted = HashSet::new();
+	for conflict in index.conflicts()? {
+		let conflict = conflict?;
+		if let Some(entry) =
+			conflict.our.or(conflict.their).or(conflict.ancestor)
+		{
+			conflicted
+				.insert(PathBuf::from(bytes2string(&entry.path)?));
+		}
+	}
+
+	let mut skipped = BTreeSet::new();
+	let mut skip_conflicted = |path: &Path, _: &[u8]| -> i32 {
+		if conflicted.contains(path) {
+			skipped.insert(path.to_string_lossy().into_owned());
+			1
+		} else {
+			0
+		}
+	};
+
+	if stage_untracked.include_untracked() {
+		index.add_all(
+			vec![folder],
+			IndexAddOption::DEFAULT,
+			Some(&mut skip_conflicted),
+		)?;
+	}
+	// picks up deletions
+	index.update_all(vec![folder], Some(&mut skip_conflicted))?;
+
+	if let Some(snapshot) = snapshot {
+		snapshot.clean(&repo, &mut index)?;
+	}
+	index.write()?;
+
+	Ok(skipped.into_iter().collect())
+}
+
+/// fails if the last commit can not be undone, returns it otherwise
+pub fn undo_last_commit_check(
+	repo_path: &RepoPath,
+) -> Result<CommitId> {
+	let repo = repo(repo_path)?;
+
+	undo_last_commit_check_repo(&repo)
+}
+
+fn undo_last_commit_check_repo(
+	repo: &Repository,
+) -> Result<CommitId> {
+	let head = get_head_repo(repo)?;
+
+	match repo.find_commit(head.into())?.parent_count() {
+		0 => Err(Error::UndoInitialCommit),
+		1 => Ok(head),
+		_ => Err(Error::UndoMergeCommit),
+	}
+}
+
 /// Undo last commit in repo
 pub fn undo_last_commit(repo_path: &RepoPath) -> Result<()> {
 	let repo = repo(repo_path)?;
+	let old_id = get_head_repo(&repo).ok();
+
+	undo_last_commit_check_repo(&repo)?;
+
+	let result = undo_last_commit_repo(&repo);
+	oplog_record(
+		repo_path,
+		OpKind::Reset,
+		"HEAD (soft)",
+		old_id,
+		result.as_ref().ok().copied(),
+		&result,
+	);
+
+	result.map(|_| ())
+}
+
+/// soft reset to `HEAD~`, returns the new head
+pub(crate) fn undo_last_commit_repo(
+	repo: &Repository,
+) -> Result<CommitId> {
 	let previous_commit = repo.revparse_single("HEAD~")?;
 
 	Repository::reset(
-		&repo,
+		repo,
 		&previous_commit,
 		git2::ResetType::Soft,
 		None,
 	)?;
 
-	Ok(())
+	Ok(previous_commit.id().into())
 }
 
 /// stage a removed file
@@ -159,6 +354,7 @@ pub fn stage_addremoved(
 	scope_time!("stage_addremoved");
 
 	let repo = repo(repo_path)?;
+	check_index_writable(&repo)?;
 
 	let mut index = repo.index()?;
 
@@ -176,14 +372,14 @@ pub(crate) fn bytes2string(bytes: &[u8]) -> Result<String> {
 pub(crate) fn repo_write_file(
 	repo: &Repository,
 	file: &str,
-	content: &str,
+	content: impl AsRef<[u8]>,
 ) -> Result<()> {
 	let dir = work_dir(repo)?.join(file);
 	let file_path = dir.to_str().ok_or_else(|| {
 		Error::Generic(String::from("invalid file path"))
 	})?;
 	let mut file = File::create(file_path)?;
-	file.write_all(content.as_bytes())?;
+	file.write_all(content.as_ref())?;
 	Ok(())
 }
 
@@ -221,14 +417,16 @@ pub(crate) fn repo_read_file(
 mod tests {
 	use super::*;
 	use crate::sync::{
-		commit,
+		checkout_branch, commit, create_branch,
 		diff::get_diff,
+		merge_branch,
 		status::{get_status, StatusType},
 		tests::{
 			debug_cmd_print, get_statuses, repo_init,
 			repo_init_empty, write_commit_file,
 		},
 	};
+	use git2::BranchType;
 	use std::{
 		env,
 		fs::{self, remove_file, File},
@@ -340,6 +538,52 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_staging_folder_skips_conflicts() -> Result<()> {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		fs::create_dir_all(root.join("a"))?;
+		write_commit_file(&repo, "a/conflict.txt", "base", "c1");
+		write_commit_file(&repo, "a/deleted.txt", "base", "c2");
+		write_commit_file(&repo, "a/modified.txt", "base", "c3");
+
+		create_branch(repo_path, "other")?;
+		write_commit_file(&repo, "a/conflict.txt", "theirs", "c4");
+		checkout_branch(repo_path, "master")?;
+		write_commit_file(&repo, "a/conflict.txt", "ours", "c5");
+		merge_branch(repo_path, "other", BranchType::Local)?;
+		assert!(super::repo(repo_path)?.index()?.has_conflicts());
+
+		remove_file(root.join("a/deleted.txt"))?;
+		fs::write(root.join("a/modified.txt"), "changed")?;
+		fs::write(root.join("a/new.txt"), "new")?;
+		fs::write(root.join("outside.txt"), "new")?;
+
+		let skipped = stage_add_folder(repo_path, "a", None)?;
+
+		assert_eq!(skipped, vec![String::from("a/conflict.txt")]);
+
+		let staged = get_status(repo_path, StatusType::Stage, None)?
+			.into_iter()
+			.map(|item| item.path)
+			.collect::<Vec<_>>();
+		assert!(staged.contains(&String::from("a/deleted.txt")));
+		assert!(staged.contains(&String::from("a/modified.txt")));
+		assert!(staged.contains(&String::from("a/new.txt")));
+		assert!(!staged.contains(&String::from("outside.txt")));
+
+		// the conflict is still there
+		assert!(super::repo(repo_path)?
+			.index()?
+			.conflict_get(Path::new("a/conflict.txt"))
+			.is_ok());
+
+		Ok(())
+	}
+
 	#[test]
 	fn test_undo_commit_empty_repo() {
 		let (_td, repo) = repo_init().unwrap();
@@ -351,6 +595,61 @@ mod tests {
 		assert!(undo_last_commit(repo_path).is_err());
 	}
 
+	#[test]
+	fn test_undo_initial_commit() {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "test.txt", "content1", "c1");
+		assert!(matches!(
+			undo_last_commit(repo_path),
+			Err(Error::UndoInitialCommit)
+		));
+	}
+
+	#[test]
+	fn test_undo_merge_commit() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+		let c2 = write_commit_file(&repo, "b.txt", "b", "c2");
+
+		let sig = repo.signature().unwrap();
+		let tree =
+			repo.find_commit(c2.into()).unwrap().tree().unwrap();
+		let merge = repo
+			.commit(
+				Some("HEAD"),
+				&sig,
+				&sig,
+				"merge",
+				&tree,
+				&[
+					&repo.find_commit(c2.into()).unwrap(),
+					&repo.find_commit(c1.into()).unwrap(),
+				],
+			)
+			.unwrap();
+
+		assert!(matches!(
+			undo_last_commit_check(repo_path),
+			Err(Error::UndoMergeCommit)
+		));
+		assert!(undo_last_commit(repo_path).is_err());
+		assert_eq!(get_head_repo(&repo).unwrap(), merge.into());
+
+		// a child of the merge is fine
+		let c3 = write_commit_file(&repo, "c.txt", "c", "c3");
+		assert_eq!(undo_last_commit_check(repo_path).unwrap(), c3);
+		assert!(undo_last_commit(repo_path).is_ok());
+		assert_eq!(get_head_repo(&repo).unwrap(), merge.into());
+	}
+
 	#[test]
 	fn test_undo_commit() {
 		let (_td, repo) = repo_init().unwrap();
@@ -499,4 +798,30 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_watch_paths_linked_worktree() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let wt_td = tempfile::TempDir::new()?;
+		let wt_root = wt_td.path().join("wt");
+		repo.worktree("wt", &wt_root, None)?;
+		let wt_path: &RepoPath = &wt_root.to_str().unwrap().into();
+
+		let paths = repo_watch_paths(repo_path)?;
+		assert_eq!(paths.work_dir, root);
+		assert_eq!(paths.git_dir, repo.path());
+		assert_eq!(paths.common_dir, repo.path());
+
+		let paths = repo_watch_paths(wt_path)?;
+		assert_eq!(paths.work_dir, wt_root);
+		assert_eq!(paths.git_dir, repo.path().join("worktrees/wt"));
+		assert_eq!(paths.common_dir, repo.path());
+		assert!(paths.git_dir.join("index").exists());
+
+		Ok(())
+	}
 }
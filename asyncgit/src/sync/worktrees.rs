@@ -0,0 +1,328 @@
+//! the working trees of a repository (`git worktree`)
+
+use super::{repository::repo, utils::work_dir, CommitId, RepoPath};
+use crate::error::{Error, Result};
+use git2::{
+	BranchType, Repository, WorktreeAddOptions, WorktreeLockStatus,
+};
+use scopetime::scope_time;
+use std::path::{Path, PathBuf};
+
+/// one working tree, the main one or a linked one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorktreeInfo {
+	/// `None` for the main working tree
+	pub name: Option<String>,
+	///
+	pub path: PathBuf,
+	/// checked out branch, `None` if `HEAD` is detached
+	pub branch: Option<String>,
+	/// `None` if the branch has no commit yet
+	pub head: Option<CommitId>,
+	/// the reason given, empty if there is none
+	pub locked: Option<String>,
+	/// its directory is gone, only the bookkeeping is left
+	pub prunable: bool,
+	/// the working tree `repo_path` is
+	pub current: bool,
+}
+
+/// the main working tree first, then the linked ones by name
+pub fn get_worktrees(
+	repo_path: &RepoPath,
+) -> Result<Vec<WorktreeInfo>> {
+	scope_time!("get_worktrees");
+
+	let repo = repo(repo_path)?;
+	let current = work_dir(&repo)?.canonicalize()?;
+	let is_current =
+		|path: &Path| path.canonicalize().is_ok_and(|p| p == current);
+
+	let mut res = Vec::new();
+
+	let main = Repository::open(repo.commondir())?;
+	if let Some(path) = main.workdir() {
+		let (branch, head) = worktree_head(&main);
+		res.push(WorktreeInfo {
+			name: None,
+			path: path.to_path_buf(),
+			branch,
+			head,
+			locked: None,
+			prunable: false,
+			current: is_current(path),
+		});
+	}
+
+	let mut names: Vec<String> = main
+		.worktrees()?
+		.iter()
+		.flatten()
+		.map(String::from)
+		.collect();
+	names.sort();
+
+	for name in names {
+		let worktree = main.find_worktree(&name)?;
+		let (branch, head) =
+			Repository::open_from_worktree(&worktree)
+				.map(|repo| worktree_head(&repo))
+				.unwrap_or_default();
+
+		res.push(WorktreeInfo {
+			path: worktree.path().to_path_buf(),
+			branch,
+			head,
+			locked: match worktree.is_locked()? {
+				WorktreeLockStatus::Unlocked => None,
+				WorktreeLockStatus::Locked(reason) => {
+					Some(reason.unwrap_or_default())
+				}
+			},
+			prunable: worktree.is_prunable(None)?,
+			current: is_current(worktree.path()),
+			name: Some(name),
+		});
+	}
+
+	Ok(res)
+}
+
+/// the working tree `branch` is checked out in, if any
+pub fn worktree_of_branch(
+	repo_path: &RepoPath,
+	branch: &str,
+) -> Result<Option<PathBuf>> {
+	Ok(get_worktrees(repo_path)?
+		.into_iter()
+		.find(|worktree| worktree.branch.as_deref() == Some(branch))
+		.map(|worktree| worktree.path))
+}
+
+/// a working tree is created in a new directory only
+pub fn is_valid_worktree_path(path: &Path) -> bool {
+	path.file_name().is_some() && !path.exists()
+}
+
+/// adds a working tree at `path` with the local branch `branch`
+/// checked out, `force` allows a branch checked out in another
+/// working tree already (like `git worktree add --force`)
+pub fn create_worktree(
+	repo_path: &RepoPath,
+	path: &Path,
+	branch: &str,
+	force: bool,
+) -> Result<()> {
+	scope_time!("create_worktree");
+
+	if !is_valid_worktree_path(path) {
+		return Err(Error::Generic(format!(
+			"'{}' exists already",
+			path.display()
+		)));
+	}
+
+	let checked_out = worktree_of_branch(repo_path, branch)?;
+	if let (Some(other), false) = (&checked_out, force) {
+		return Err(Error::Generic(format!(
+			"branch '{branch}' is checked out at '{}' already",
+			other.display()
+		)));
+	}
+
+	let repo = repo(repo_path)?;
+	let reference = repo
+		.find_branch(branch, BranchType::Local)?
+		.into_reference();
+	let name = new_worktree_name(&repo, path);
+
+	if checked_out.is_none() {
+		let mut options = WorktreeAddOptions::new();
+		options.reference(Some(&reference));
+		repo.worktree(&name, path, Some(&options))?;
+
+		return Ok(());
+	}
+
+	// libgit2 refuses a branch checked out elsewhere, so the working
+	// tree starts out on a temporary branch at the same commit and
+	// its `HEAD` is pointed at `branch` afterwards
+	let commit = reference.peel_to_commit()?;
+	let mut temp = repo.branch(
+		&format!("gitui-worktree-{name}"),
+		&commit,
+		true,
+	)?;
+
+	let res = {
+		let mut options = WorktreeAddOptions::new();
+		options.reference(Some(temp.get()));
+
+		repo.worktree(&name, path, Some(&options)).and_then(
+			|worktree| {
+				// `set_head` refuses a branch checked out elsewhere
+				// as well, the symbolic ref is written directly
+				Repository::open_from_worktree(&worktree)?
+					.reference_symbolic(
+						"HEAD",
+						reference.name().unwrap_or_default(),
+						true,
+						"gitui: worktree add --force",
+					)
+					.map(|_| ())
+			},
+		)
+	};
+
+	// the temporary branch can only go once no working tree has it
+	// checked out anymore
+	if res.is_err() {
+		remove_new_worktree(&repo, &name, path);
+	}
+	let deleted = temp.delete();
+
+	res?;
+	Ok(deleted?)
+}
+
+/// undoes a half created working tree: its directory and its
+/// bookkeeping
+fn remove_new_worktree(repo: &Repository, name: &str, path: &Path) {
+	if path.exists() {
+		if let Err(e) = std::fs::remove_dir_all(path) {
+			log::error!("remove worktree dir error: {e}");
+		}
+	}
+
+	if let Ok(worktree) = repo.find_worktree(name) {
+		if let Err(e) = worktree.prune(None) {
+			log::error!("prune worktree error: {e}");
+		}
+	}
+}
+
+/// removes the bookkeeping of working trees whose directory is gone,
+/// returns how many
+pub fn prune_worktrees(repo_path: &RepoPath) -> Result<usize> {
+	scope_time!("prune_worktrees");
+
+	let repo = repo(repo_path)?;
+
+	let mut pruned = 0;
+	for name in repo.worktrees()?.iter().flatten() {
+		let worktree = repo.find_worktree(name)?;
+		if worktree.is_prunable(None)? {
+			worktree.prune(None)?;
+			pruned += 1;
+		}
+	}
+
+	Ok(pruned)
+}
+
+fn worktree_head(
+	repo: &Repository,
+) -> (Option<String>, Option<CommitId>) {
+	let branch = repo.find_reference("HEAD").ok().and_then(|head| {
+		head.symbolic_target()
+			.and_then(|target| target.strip_prefix("refs/heads/"))
+			.map(String::from)
+	});
+	let head = repo
+		.head()
+		.ok()
+		.and_then(|head| head.target())
+		.map(Into::into);
+
+	(branch, head)
+}
+
+/// the name of the directory, numbered like git does if a working
+/// tree of that name exists already
+fn new_worktree_name(repo: &Repository, path: &Path) -> String {
+	let base = path
+		.file_name()
+		.map(|name| name.to_string_lossy().to_string())
+		.unwrap_or_default();
+	let taken = |name: &str| {
+		repo.commondir().join("worktrees").join(name).exists()
+	};
+
+	let mut name = base.clone();
+	let mut idx = 1;
+	while taken(&name) {
+		name = format!("{base}{idx}");
+		idx += 1;
+	}
+
+	name
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		create_branch,
+		tests::{repo_init, write_commit_file},
+	};
+	use std::fs;
+
+	#[test]
+	fn test_create_and_prune() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+		write_commit_file(&repo, "a", "1", "first");
+		let main_branch =
+			repo.head().unwrap().shorthand().unwrap().to_string();
+
+		create_branch(repo_path, "feature").unwrap();
+		repo.set_head(&format!("refs/heads/{main_branch}")).unwrap();
+
+		let wt_td = tempfile::TempDir::new().unwrap();
+		let wt_root = wt_td.path().join("wt");
+		assert!(is_valid_worktree_path(&wt_root));
+
+		create_worktree(repo_path, &wt_root, "feature", false)
+			.unwrap();
+		assert!(!is_valid_worktree_path(&wt_root));
+		assert_eq!(
+			fs::read_to_string(wt_root.join("a")).unwrap(),
+			"1"
+		);
+
+		let worktrees = get_worktrees(repo_path).unwrap();
+		assert_eq!(worktrees.len(), 2);
+		assert!(worktrees[0].current);
+		assert_eq!(
+			worktrees[0].branch.as_deref(),
+			Some(main_branch.as_str())
+		);
+		assert_eq!(worktrees[1].name.as_deref(), Some("wt"));
+		assert_eq!(worktrees[1].branch.as_deref(), Some("feature"));
+		assert!(!worktrees[1].current);
+		assert!(!worktrees[1].prunable);
+
+		let other = wt_td.path().join("other");
+		assert!(create_worktree(repo_path, &other, "feature", false)
+			.is_err());
+		create_worktree(repo_path, &other, "feature", true).unwrap();
+		let worktrees = get_worktrees(repo_path).unwrap();
+		assert_eq!(worktrees[1].name.as_deref(), Some("other"));
+		assert_eq!(worktrees[1].branch.as_deref(), Some("feature"));
+		assert!(repo
+			.find_branch("gitui-worktree-other", BranchType::Local)
+			.is_err());
+
+		fs::remove_dir_all(&wt_root).unwrap();
+		let worktrees = get_worktrees(repo_path).unwrap();
+		assert!(!worktrees[1].prunable);
+		assert!(worktrees[2].prunable);
+
+		assert_eq!(prune_worktrees(repo_path).unwrap(), 1);
+		let worktrees = get_worktrees(repo_path).unwrap();
+		assert_eq!(worktrees.len(), 2);
+		assert_eq!(worktrees[1].name.as_deref(), Some("other"));
+	}
+}
@@ -1,17 +1,23 @@
 use crate::{
 	asyncjob::{AsyncJob, RunParams},
 	error::Result,
-	sync::{tree_files, CommitId, RepoPath, TreeFile},
+	sync::{tree_files_with_progress, CommitId, RepoPath, TreeFile},
 	AsyncGitNotification,
 };
-use std::sync::{Arc, Mutex};
+use std::sync::{
+	atomic::{AtomicBool, Ordering},
+	Arc, Mutex,
+};
+
+/// progress is only published every this many files
+const TREE_FILES_BATCH: usize = 1000;
 
 ///
 pub struct FileTreeResult {
 	///
 	pub commit: CommitId,
-	///
-	pub result: Result<Vec<TreeFile>>,
+	/// `None` if the job was cancelled
+	pub result: Result<Option<Vec<TreeFile>>>,
 }
 
 enum JobState {
@@ -19,10 +25,12 @@ enum JobState {
 	Response(FileTreeResult),
 }
 
-///
+/// reads the files of a commit, the number read so far is its
+/// progress
 #[derive(Clone, Default)]
 pub struct AsyncTreeFilesJob {
 	state: Arc<Mutex<Option<JobState>>>,
+	cancellation_flag: Arc<AtomicBool>,
 }
 
 ///
@@ -34,46 +42,92 @@ impl AsyncTreeFilesJob {
 				repo,
 				commit,
 			}))),
+			cancellation_flag: Arc::default(),
 		}
 	}
 
+	/// stops reading after the current batch
+	pub fn cancel(&self) {
+		self.cancellation_flag.store(true, Ordering::Relaxed);
+	}
+
 	///
 	pub fn result(&self) -> Option<FileTreeResult> {
 		if let Ok(mut state) = self.state.lock() {
-			if let Some(state) = state.take() {
-				return match state {
-					JobState::Request { .. } => None,
-					JobState::Response(result) => Some(result),
-				};
+			if matches!(*state, Some(JobState::Response(_))) {
+				if let Some(JobState::Response(result)) = state.take()
+				{
+					return Some(result);
+				}
 			}
 		}
 
 		None
 	}
+
+	fn take_request(&self) -> Option<(CommitId, RepoPath)> {
+		let mut state = self.state.lock().ok()?;
+
+		match state.take() {
+			Some(JobState::Request { commit, repo }) => {
+				Some((commit, repo))
+			}
+			response => {
+				*state = response;
+				None
+			}
+		}
+	}
+
+	fn update_progress(
+		params: &RunParams<AsyncGitNotification, usize>,
+		new_progress: usize,
+	) {
+		match params.set_progress(new_progress) {
+			Err(e) => log::error!("progress error: {e}"),
+			Ok(result) if result => {
+				if let Err(e) =
+					params.send(AsyncGitNotification::TreeFiles)
+				{
+					log::error!("send error: {e}");
+				}
+			}
+			_ => (),
+		}
+	}
 }
 
 impl AsyncJob for AsyncTreeFilesJob {
 	type Notification = AsyncGitNotification;
-	type Progress = ();
+	type Progress = usize;
 
 	fn run(
 		&mut self,
-		_params: RunParams<Self::Notification, Self::Progress>,
+		params: RunParams<Self::Notification, Self::Progress>,
 	) -> Result<Self::Notification> {
-		if let Ok(mut state) = self.state.lock() {
-			*state = state.take().map(|state| match state {
-				JobState::Request { commit, repo } => {
-					let files = tree_files(&repo, commit);
-
-					JobState::Response(FileTreeResult {
-						commit,
-						result: files,
-					})
-				}
-				JobState::Response(result) => {
-					JobState::Response(result)
-				}
-			});
+		Self::update_progress(&params, 0);
+
+		// not locked while reading so `result` never waits for it
+		let request = self.take_request();
+
+		if let Some((commit, repo)) = request {
+			let result = tree_files_with_progress(
+				&repo,
+				commit,
+				TREE_FILES_BATCH,
+				|read| {
+					Self::update_progress(&params, read);
+
+					!self.cancellation_flag.load(Ordering::Relaxed)
+				},
+			);
+
+			if let Ok(mut state) = self.state.lock() {
+				*state = Some(JobState::Response(FileTreeResult {
+					commit,
+					result,
+				}));
+			}
 		}
 
 		Ok(AsyncGitNotification::TreeFiles)
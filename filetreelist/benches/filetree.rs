@@ -0,0 +1,248 @@
+//! measures building a `FileTree` from large path lists, restoring a
+//! selection in it and iterating its rows like a render would.
+//!
+//! run with `cargo bench -p filetreelist`
+
+use filetreelist::{FileTree, FileTreeItem};
+use std::{
+	collections::{BTreeSet, HashMap},
+	hint::black_box,
+	path::{Path, PathBuf},
+	time::{Duration, Instant},
+};
+
+const PATHS: usize = 200_000;
+const RUNS: u32 = 10;
+
+fn monorepo_paths() -> Vec<PathBuf> {
+	(0..PATHS)
+		.map(|i| {
+			PathBuf::from(format!(
+				"packages/pkg{}/src/mod{}/file{i}.rs",
+				i % 500,
+				(i / 500) % 40,
+			))
+		})
+		.collect()
+}
+
+/// the construction `FileTreeItems::new` used before it grouped
+/// entries by folder: every ancestor of every path is looked up
+/// again, folder names are allocated for the collapsed check and
+/// folding removes items from the middle of the list.
+/// it builds no path index, so it still does less than the real one
+fn previous_items(list: &[&Path]) -> Vec<FileTreeItem> {
+	let collapsed = BTreeSet::<&String>::new();
+	let mut items = Vec::with_capacity(list.len());
+	let mut paths_added: HashMap<&Path, usize> =
+		HashMap::with_capacity(list.len());
+
+	for e in list {
+		let mut ancestors = e.ancestors().skip(1).collect::<Vec<_>>();
+		ancestors.reverse();
+
+		for c in &ancestors {
+			if c.parent().is_some() && !paths_added.contains_key(c) {
+				paths_added.insert(c, 0);
+
+				if let Some(parent) = c.parent() {
+					if !parent.as_os_str().is_empty() {
+						*paths_added.entry(parent).or_insert(0) += 1;
+					}
+				}
+
+				let path_string =
+					c.to_str().unwrap_or_default().to_string();
+				let is_collapsed = collapsed.contains(&path_string);
+				items.push(
+					FileTreeItem::new_path(c, is_collapsed)
+						.expect("valid path"),
+				);
+			}
+		}
+
+		if let Some(parent) = e.parent() {
+			*paths_added.entry(parent).or_insert(0) += 1;
+		}
+
+		items.push(FileTreeItem::new_file(e).expect("valid path"));
+	}
+
+	previous_fold(&mut items, &paths_added);
+
+	items
+}
+
+fn previous_fold(
+	items: &mut Vec<FileTreeItem>,
+	paths: &HashMap<&Path, usize>,
+) {
+	let mut i = 0;
+
+	while i < items.len() {
+		let single_child = items[i].kind().is_path()
+			&& paths.get(items[i].info().full_path()) == Some(&1);
+
+		if single_child
+			&& items
+				.get(i + 1)
+				.is_some_and(|next| next.kind().is_path())
+		{
+			let next = items.remove(i + 1);
+			items[i].fold(next);
+
+			let prefix = items[i].info().full_path_str().to_owned();
+			for elem in items.iter_mut().skip(i + 1) {
+				if !elem.info().full_path_str().starts_with(&prefix) {
+					break;
+				}
+				elem.info_mut().unindent();
+			}
+			continue;
+		}
+
+		i += 1;
+	}
+}
+
+fn measure(build: &dyn Fn() -> bool) -> Duration {
+	let mut total = Duration::ZERO;
+
+	for _ in 0..RUNS {
+		let start = Instant::now();
+		let built = build();
+		total += start.elapsed();
+		assert!(black_box(built));
+	}
+
+	total / RUNS
+}
+
+/// java like layout where most folders only contain one folder, so
+/// nearly every folder gets merged into its parent. kept smaller
+/// since merging used to be quadratic and took seconds per run
+fn nested_paths() -> Vec<PathBuf> {
+	let mut paths = (0..PATHS / 4)
+		.map(|i| {
+			PathBuf::from(format!(
+				"vendor/lib{0}/src/main/java/org/lib{0}/File{i}.java",
+				i % 2000,
+			))
+		})
+		.collect::<Vec<_>>();
+	paths.sort();
+	paths
+}
+
+fn bench(name: &str, list: &[&Path]) {
+	let collapsed = BTreeSet::new();
+	let current = measure(&|| {
+		FileTree::new(black_box(list), &collapsed).is_ok()
+	});
+
+	println!("{name}: {} paths, {current:?} per run", list.len());
+}
+
+/// only meaningful on sorted input, the previous construction did
+/// not sort and misbuilt the tree for interleaved folders
+fn bench_previous(name: &str, list: &[&Path]) {
+	let collapsed = BTreeSet::new();
+	let current = measure(&|| {
+		FileTree::new(black_box(list), &collapsed).is_ok()
+	});
+	let previous =
+		measure(&|| !previous_items(black_box(list)).is_empty());
+
+	println!(
+		"{name} before: {} paths, {previous:?} per run, now {current:?} ({:.1}x)",
+		list.len(),
+		previous.as_secs_f64() / current.as_secs_f64().max(f64::EPSILON)
+	);
+}
+
+/// restores the selection of every file like a status refresh would
+fn bench_select(list: &[&Path]) {
+	let mut tree =
+		FileTree::new(list, &BTreeSet::new()).expect("valid paths");
+	let mut total = Duration::ZERO;
+
+	for path in list.iter().step_by(1000) {
+		tree.select_file(path);
+
+		let start = Instant::now();
+		for _ in 0..RUNS {
+			black_box(tree.select_file(black_box(path)));
+		}
+		total += start.elapsed();
+	}
+
+	println!(
+		"select_file: {} paths, {:?} per lookup",
+		list.len() / 1000,
+		total / RUNS / u32::try_from(list.len() / 1000).unwrap_or(1)
+	);
+}
+
+/// names of all rows, once borrowed and once allocated per row the
+/// way rendering worked before names were stored as part of the path
+fn bench_iterate(list: &[&Path]) {
+	const ITEMS: usize = 100_000;
+
+	let tree = FileTree::new(
+		&list[..ITEMS.min(list.len())],
+		&BTreeSet::new(),
+	)
+	.expect("valid paths");
+	let rows = tree.iterate(0, usize::MAX).count();
+
+	let measure = |row: &dyn Fn(&FileTreeItem) -> usize| {
+		let start = Instant::now();
+		for _ in 0..RUNS {
+			for (item, _) in tree.iterate(0, usize::MAX) {
+				black_box(row(black_box(item)));
+			}
+		}
+		start.elapsed() / RUNS
+	};
+
+	let borrowed = measure(&|item| {
+		item.info().name().len() + item.info().full_path_str().len()
+	});
+	let allocated = measure(&|item| {
+		item.info().path().to_string_lossy().into_owned().len()
+			+ item
+				.info()
+				.full_path()
+				.to_string_lossy()
+				.into_owned()
+				.len()
+	});
+
+	println!(
+		"iterate: {rows} rows, {borrowed:?} per run borrowed, {allocated:?} allocating ({:.1}x)",
+		allocated.as_secs_f64() / borrowed.as_secs_f64().max(f64::EPSILON)
+	);
+}
+
+fn main() {
+	let mut paths = monorepo_paths();
+
+	// generation order interleaves folders, so this has to sort first
+	let unsorted =
+		paths.iter().map(PathBuf::as_path).collect::<Vec<_>>();
+	bench("unsorted", &unsorted);
+
+	paths.sort();
+	let sorted =
+		paths.iter().map(PathBuf::as_path).collect::<Vec<_>>();
+	bench("sorted", &sorted);
+	bench_previous("sorted", &sorted);
+
+	let nested = nested_paths();
+	let nested =
+		nested.iter().map(PathBuf::as_path).collect::<Vec<_>>();
+	bench_previous("nested", &nested);
+
+	bench_select(&sorted);
+	bench_iterate(&sorted);
+}
@@ -6,6 +6,9 @@ pub enum Error {
 	#[error("InvalidPath: `{0}`")]
 	InvalidPath(PathBuf),
 
+	#[error("PathConflict: `{0}` is used as file and folder")]
+	PathConflict(PathBuf),
+
 	#[error("TryFromInt error:{0}")]
 	IntConversion(#[from] TryFromIntError),
 }
@@ -58,6 +58,25 @@ impl FileTree {
 		Ok(new_self)
 	}
 
+	/// tree with one labeled item per root, see
+	/// [`TreeItemInfo::root`] to tell the roots of items apart.
+	/// navigation treats every root like a top level folder that
+	/// page movements do not skip over
+	pub fn new_multi(
+		roots: &[(String, &[&Path])],
+		collapsed: &BTreeSet<&String>,
+	) -> Result<Self> {
+		let mut new_self = Self {
+			items: FileTreeItems::new_multi(roots, collapsed)?,
+			selection: if roots.is_empty() { None } else { Some(0) },
+			visual_selection: None,
+			window_height: None.into(),
+		};
+		new_self.visual_selection = new_self.calc_visual_selection();
+
+		Ok(new_self)
+	}
+
 	///
 	pub const fn is_empty(&self) -> bool {
 		self.items.file_count() == 0
@@ -70,9 +89,17 @@ impl FileTree {
 
 	///
 	pub fn collapse_but_root(&mut self) {
-		if !self.is_empty() {
-			self.items.collapse(0, true);
-			self.items.expand(0, false);
+		if self.items.roots().is_empty() {
+			if !self.is_empty() {
+				self.items.collapse(0, true);
+				self.items.expand(0, false);
+			}
+		} else {
+			for root in self.items.roots().to_vec() {
+				self.items.collapse(root, true);
+				self.items.expand(root, false);
+			}
+			self.visual_selection = self.calc_visual_selection();
 		}
 	}
 
@@ -96,6 +123,36 @@ impl FileTree {
 		self.visual_selection.as_ref()
 	}
 
+	/// returns the scroll offset (first visible visual index) needed to
+	/// keep the selection inside a window of `window_height` lines.
+	///
+	/// the viewport only moves if the selection would leave it when
+	/// starting from `current_offset`, so consecutive calls scroll stable.
+	pub fn calc_scroll(&self, current_offset: usize) -> usize {
+		let height = self.window_height.get().unwrap_or(0);
+
+		let Some(selection) = self.visual_selection else {
+			return 0;
+		};
+
+		if height == 0 || selection.count <= height {
+			return 0;
+		}
+
+		let max_offset = selection.count - height;
+		let offset = current_offset.min(max_offset);
+
+		let offset = if selection.index < offset {
+			selection.index
+		} else if selection.index >= offset + height {
+			selection.index + 1 - height
+		} else {
+			offset
+		};
+
+		offset.min(max_offset)
+	}
+
 	///
 	pub fn selected_file(&self) -> Option<&TreeItemInfo> {
 		self.selection.and_then(|index| {
@@ -108,6 +165,63 @@ impl FileTree {
 		})
 	}
 
+	/// info of the selected item, file or folder
+	pub fn selected_item(&self) -> Option<&TreeItemInfo> {
+		self.selection
+			.map(|index| self.items.tree_items[index].info())
+	}
+
+	/// full paths of the collapsed folders (labels for collapsed
+	/// roots), folders merged into one item by the deepest path.
+	/// folders inside of collapsed ones keep their own state
+	pub fn collapsed_paths(&self) -> BTreeSet<String> {
+		self.items
+			.tree_items
+			.iter()
+			.filter(|item| item.kind().is_path_collapsed())
+			.map(|item| {
+				let info = item.info();
+				if info.is_root() {
+					info.name().to_string()
+				} else {
+					info.full_path_str().to_string()
+				}
+			})
+			.collect()
+	}
+
+	/// collapses exactly the folders in `collapsed` (see
+	/// [`Self::collapsed_paths`]), a selection hidden by that moves
+	/// up to its visible parent
+	pub fn set_collapsed(&mut self, collapsed: &BTreeSet<String>) {
+		self.items.set_collapsed(collapsed);
+
+		if let Some(mut selection) = self.selection {
+			while !self.is_visible_index(selection) {
+				match self.items.find_parent(selection) {
+					Some(parent) => selection = parent,
+					None => break,
+				}
+			}
+			self.selection = Some(selection);
+		}
+		self.visual_selection = self.calc_visual_selection();
+	}
+
+	/// `None` if no folder has this path, in trees with multiple roots
+	/// the folder in the first root having it
+	pub fn is_collapsed(&self, path: &Path) -> Option<bool> {
+		self.items
+			.tree_items
+			.iter()
+			.find(|item| {
+				item.kind().is_path()
+					&& !item.info().is_root()
+					&& item.info().folder_paths().any(|p| p == path)
+			})
+			.map(|item| item.kind().is_path_collapsed())
+	}
+
 	///
 	pub fn collapse_recursive(&mut self) {
 		if let Some(selection) = self.selection {
@@ -129,17 +243,7 @@ impl FileTree {
 	) -> Option<usize> {
 		let page_size = self.window_height.get().unwrap_or(0);
 
-		if direction == Direction::Up {
-			self.get_new_selection(
-				(0..=current_index).rev(),
-				page_size,
-			)
-		} else {
-			self.get_new_selection(
-				current_index..(self.items.len()),
-				page_size,
-			)
-		}
+		self.selection_page(current_index, direction, page_size)
 	}
 
 	fn selection_half_page_updown(
@@ -149,16 +253,38 @@ impl FileTree {
 	) -> Option<usize> {
 		let page_size = self.window_height.get().unwrap_or(0) / 2;
 
+		self.selection_page(current_index, direction, page_size)
+	}
+
+	/// moves up to `page_size` visible items but stops on the item
+	/// of a root that is passed on the way
+	fn selection_page(
+		&self,
+		current_index: usize,
+		direction: Direction,
+		page_size: usize,
+	) -> Option<usize> {
+		let roots = self.items.roots();
+
 		if direction == Direction::Up {
+			let start = roots
+				.iter()
+				.rev()
+				.find(|root| **root < current_index)
+				.copied()
+				.unwrap_or_default();
+
 			self.get_new_selection(
-				(0..=current_index).rev(),
+				(start..=current_index).rev(),
 				page_size,
 			)
 		} else {
-			self.get_new_selection(
-				current_index..(self.items.len()),
-				page_size,
-			)
+			let end = roots
+				.iter()
+				.find(|root| **root > current_index)
+				.map_or(self.items.len(), |root| root + 1);
+
+			self.get_new_selection(current_index..end, page_size)
 		}
 	}
 
@@ -197,7 +323,9 @@ impl FileTree {
 			let changed_index =
 				new_index.is_some_and(|i| i != selection);
 
-			if changed_index {
+			// collapsing/expanding keeps the index but changes the
+			// visual count
+			if new_index.is_some() {
 				self.selection = new_index;
 				self.visual_selection = self.calc_visual_selection();
 			}
@@ -206,13 +334,84 @@ impl FileTree {
 		})
 	}
 
+	/// selects `path`, in trees with multiple roots the first root
+	/// containing it
 	pub fn select_file(&mut self, path: &Path) -> bool {
-		let new_selection = self
+		let new_selection = if self.items.roots().is_empty() {
+			self.items.find_path(path)
+		} else {
+			(0..self.items.roots().len())
+				.find_map(|root| self.items.find_path_in(root, path))
+		};
+
+		self.select_index(new_selection)
+	}
+
+	/// sets the unix mode (and the target of a symlink) of the file
+	/// at `path`, `false` if there is no such file
+	pub fn set_file_mode(
+		&mut self,
+		path: &Path,
+		mode: u32,
+		link_target: Option<String>,
+	) -> bool {
+		let Some(item) = self
 			.items
-			.tree_items
-			.iter()
-			.position(|item| item.info().full_path() == path);
+			.find_path(path)
+			.and_then(|index| self.items.tree_items.get_mut(index))
+			.filter(|item| !item.kind().is_path())
+		else {
+			return false;
+		};
 
+		item.info_mut().set_mode(mode, link_target);
+
+		true
+	}
+
+	/// selects `path` inside of `root` (an empty path selects the
+	/// item of the root itself)
+	pub fn select_file_in(
+		&mut self,
+		root: usize,
+		path: &Path,
+	) -> bool {
+		let new_selection = self.items.find_path_in(root, path);
+
+		self.select_index(new_selection)
+	}
+
+	/// selects the item at the absolute `index`, see
+	/// [`Self::visual_index_to_absolute`]
+	pub fn select(&mut self, index: usize) -> bool {
+		if !self.is_visible_index(index) {
+			return false;
+		}
+
+		self.select_index(Some(index))
+	}
+
+	/// collapses or expands the selected folder just like
+	/// [`MoveSelection::Left`]/[`MoveSelection::Right`] would
+	pub fn toggle_selected_folder(&mut self) -> bool {
+		let Some(selection) = self.selection else {
+			return false;
+		};
+
+		let kind = self.items.tree_items[selection].kind();
+
+		if !kind.is_path() {
+			return false;
+		}
+
+		self.move_selection(if kind.is_path_collapsed() {
+			MoveSelection::Right
+		} else {
+			MoveSelection::Left
+		})
+	}
+
+	fn select_index(&mut self, new_selection: Option<usize>) -> bool {
 		if new_selection == self.selection {
 			return false;
 		}
@@ -225,7 +424,9 @@ impl FileTree {
 		true
 	}
 
-	fn visual_index_to_absolute(
+	/// absolute index of the item drawn in line `visual_index`
+	/// (counting visible items only)
+	pub fn visual_index_to_absolute(
 		&self,
 		visual_index: usize,
 	) -> Option<usize> {
@@ -298,17 +499,9 @@ impl FileTree {
 	}
 
 	fn select_parent(&self, current_index: usize) -> Option<usize> {
-		let current_indent =
-			self.items.tree_items[current_index].info().indent();
-
-		let range = (0..=current_index).rev();
-
-		range.filter(|index| self.is_visible_index(*index)).find(
-			|index| {
-				self.items.tree_items[*index].info().indent()
-					< current_indent
-			},
-		)
+		self.items
+			.find_parent(current_index)
+			.filter(|index| self.is_visible_index(*index))
 	}
 
 	fn selection_left(
@@ -355,7 +548,7 @@ impl FileTree {
 
 #[cfg(test)]
 mod test {
-	use crate::{FileTree, MoveSelection};
+	use crate::{FileTree, MoveSelection, TreeItemInfo};
 	use pretty_assertions::assert_eq;
 	use std::{collections::BTreeSet, path::Path};
 
@@ -400,6 +593,71 @@ mod test {
 		assert_eq!(tree.selection, Some(3));
 	}
 
+	#[test]
+	fn test_select_file() {
+		let items = vec![
+			Path::new("a/b/c"), //
+			Path::new("a/d"),   //
+		];
+
+		//0 a/
+		//1   b/
+		//2     c
+		//3   d
+
+		let mut tree =
+			FileTree::new(&items, &BTreeSet::new()).unwrap();
+
+		tree.collapse_recursive();
+
+		assert!(tree.select_file(Path::new("a/b/c")));
+		assert_eq!(tree.selection, Some(2));
+		assert!(tree.items.tree_items[2].info().is_visible());
+
+		assert!(!tree.select_file(Path::new("a/b/c")));
+
+		assert!(tree.select_file(Path::new("a/d")));
+		assert_eq!(tree.selection, Some(3));
+
+		assert!(tree.select_file(Path::new("a/b")));
+		assert!(tree.selected_file().is_none());
+		assert_eq!(
+			tree.selected_item().map(TreeItemInfo::full_path),
+			Some(Path::new("a/b"))
+		);
+
+		assert!(tree.select_file(Path::new("x")));
+		assert_eq!(tree.selection, None);
+	}
+
+	#[test]
+	fn test_set_file_mode() {
+		let items = vec![
+			Path::new("a/b"), //
+			Path::new("a/c"), //
+		];
+
+		let mut tree =
+			FileTree::new(&items, &BTreeSet::new()).unwrap();
+
+		assert!(tree.set_file_mode(
+			Path::new("a/b"),
+			0o120_000,
+			Some(String::from("c"))
+		));
+		assert!(!tree.set_file_mode(Path::new("a"), 0o100_755, None));
+		assert!(!tree.set_file_mode(Path::new("x"), 0o100_755, None));
+
+		let info = tree.items.tree_items[1].info();
+		assert_eq!(info.mode(), 0o120_000);
+		assert_eq!(info.link_target(), Some("c"));
+		assert_eq!(tree.items.tree_items[0].info().mode(), 0);
+		assert_eq!(
+			tree.items.tree_items[2].info().link_target(),
+			None
+		);
+	}
+
 	#[test]
 	fn test_selection_left_collapse() {
 		let items = vec![
@@ -532,6 +790,42 @@ mod test {
 		assert_eq!(s.index, 2);
 	}
 
+	#[test]
+	fn test_select_visual() {
+		let items = vec![
+			Path::new("a/b/c"),  //
+			Path::new("a/b/c2"), //
+			Path::new("a/d"),    //
+		];
+
+		//0 a/
+		//1   b/
+		//2     c
+		//3     c2
+		//4   d
+
+		let mut tree =
+			FileTree::new(&items, &BTreeSet::new()).unwrap();
+
+		tree.selection = Some(1);
+		assert!(tree.toggle_selected_folder());
+
+		// b/ collapsed: a/, b/, d
+		assert_eq!(tree.visual_index_to_absolute(2), Some(4));
+		assert_eq!(tree.visual_index_to_absolute(3), None);
+		assert_eq!(tree.visual_selection().unwrap().count, 3);
+
+		assert!(tree.select(4));
+		assert_eq!(tree.visual_selection().unwrap().index, 2);
+		assert!(!tree.select(2));
+		assert!(!tree.toggle_selected_folder());
+
+		assert!(tree.select(1));
+		assert!(tree.toggle_selected_folder());
+		assert_eq!(tree.visual_index_to_absolute(2), Some(2));
+		assert_eq!(tree.visual_selection().unwrap().count, 5);
+	}
+
 	#[test]
 	fn test_selection_page_updown() {
 		let items = vec![
@@ -563,4 +857,321 @@ mod test {
 		assert!(tree.move_selection(MoveSelection::PageUp));
 		assert_eq!(tree.selection, Some(0));
 	}
+
+	fn scroll_tree(visual_count: usize, height: usize) -> FileTree {
+		let paths = (0..visual_count)
+			.map(|i| format!("file{i:03}"))
+			.collect::<Vec<_>>();
+		let items = paths.iter().map(Path::new).collect::<Vec<_>>();
+
+		let tree = FileTree::new(&items, &BTreeSet::new()).unwrap();
+		tree.window_height.set(Some(height));
+		tree
+	}
+
+	#[test]
+	fn test_calc_scroll_top() {
+		let mut tree = scroll_tree(10, 3);
+
+		assert_eq!(tree.calc_scroll(0), 0);
+
+		assert!(tree.move_selection(MoveSelection::Down));
+		assert!(tree.move_selection(MoveSelection::Down));
+		assert_eq!(tree.calc_scroll(0), 0);
+
+		assert!(tree.move_selection(MoveSelection::Top));
+		assert_eq!(tree.calc_scroll(5), 0);
+	}
+
+	#[test]
+	fn test_calc_scroll_middle() {
+		let mut tree = scroll_tree(10, 3);
+
+		tree.selection = Some(3);
+		tree.visual_selection = tree.calc_visual_selection();
+		assert_eq!(tree.calc_scroll(0), 1);
+
+		// selection still inside viewport: keep offset stable
+		tree.selection = Some(4);
+		tree.visual_selection = tree.calc_visual_selection();
+		assert_eq!(tree.calc_scroll(3), 3);
+		assert_eq!(tree.calc_scroll(2), 2);
+
+		// selection above viewport
+		assert_eq!(tree.calc_scroll(5), 4);
+	}
+
+	#[test]
+	fn test_calc_scroll_bottom() {
+		let mut tree = scroll_tree(10, 3);
+
+		assert!(tree.move_selection(MoveSelection::End));
+		assert_eq!(tree.calc_scroll(0), 7);
+
+		// offset beyond the end gets clamped
+		assert_eq!(tree.calc_scroll(100), 7);
+	}
+
+	#[test]
+	fn test_calc_scroll_page_jump() {
+		let mut tree = scroll_tree(10, 3);
+
+		assert!(tree.move_selection(MoveSelection::PageDown));
+		assert_eq!(tree.selection, Some(2));
+		let offset = tree.calc_scroll(0);
+		assert_eq!(offset, 0);
+
+		assert!(tree.move_selection(MoveSelection::PageDown));
+		assert_eq!(tree.selection, Some(4));
+		let offset = tree.calc_scroll(offset);
+		assert_eq!(offset, 2);
+
+		assert!(tree.move_selection(MoveSelection::PageUp));
+		assert_eq!(tree.selection, Some(2));
+		assert_eq!(tree.calc_scroll(offset), 2);
+	}
+
+	#[test]
+	fn test_calc_scroll_window_bigger_than_list() {
+		let mut tree = scroll_tree(3, 10);
+
+		assert!(tree.move_selection(MoveSelection::End));
+		assert_eq!(tree.calc_scroll(2), 0);
+
+		tree.window_height.set(None);
+		assert_eq!(tree.calc_scroll(2), 0);
+	}
+
+	fn multi_tree(collapsed: &[&str]) -> FileTree {
+		let repo = [Path::new("a/b.txt"), Path::new("c.txt")];
+		let submodule = [Path::new("a/b.txt")];
+		let collapsed = collapsed
+			.iter()
+			.map(|c| (*c).to_string())
+			.collect::<Vec<_>>();
+
+		//0 <repo>
+		//1   a/
+		//2     b.txt
+		//3   c.txt
+		//4 <sub>
+		//5   a/
+		//6     b.txt
+		FileTree::new_multi(
+			&[
+				(String::from("<repo>"), &repo[..]),
+				(String::from("<sub>"), &submodule[..]),
+			],
+			&collapsed.iter().collect(),
+		)
+		.unwrap()
+	}
+
+	#[test]
+	fn test_multi_root_items() {
+		let tree = multi_tree(&[]);
+		let item = |idx: usize| tree.items.tree_items[idx].info();
+
+		assert!(item(0).is_root());
+		assert_eq!(item(0).name(), "<repo>");
+		assert_eq!(item(0).root(), Some(0));
+		assert_eq!(item(4).name(), "<sub>");
+		assert_eq!(item(4).indent(), 0);
+
+		assert_eq!(item(6).full_path(), Path::new("a/b.txt"));
+		assert_eq!(item(6).root(), Some(1));
+		assert_eq!(item(6).indent(), 2);
+		assert!(!item(5).is_root());
+	}
+
+	#[test]
+	fn test_multi_root_selection_across_roots() {
+		let mut tree = multi_tree(&[]);
+
+		tree.selection = Some(3);
+		assert!(tree.move_selection(MoveSelection::Down));
+		assert_eq!(tree.selection, Some(4));
+		assert!(tree.move_selection(MoveSelection::Up));
+		assert_eq!(tree.selection, Some(3));
+
+		assert!(tree.select_file_in(1, Path::new("a/b.txt")));
+		assert_eq!(tree.selection, Some(6));
+		// parents never leave the root
+		assert!(tree.move_selection(MoveSelection::Left));
+		assert_eq!(tree.selection, Some(5));
+		assert!(tree.move_selection(MoveSelection::Left));
+		assert_eq!(tree.selection, Some(5));
+		assert!(tree.move_selection(MoveSelection::Left));
+		assert_eq!(tree.selection, Some(4));
+
+		assert!(tree.select_file(Path::new("a/b.txt")));
+		assert_eq!(tree.selection, Some(2));
+		let selected = tree.selected_file().unwrap();
+		assert_eq!(selected.root(), Some(0));
+		assert_eq!(selected.full_path(), Path::new("a/b.txt"));
+	}
+
+	#[test]
+	fn test_multi_root_page_stops_at_root() {
+		let mut tree = multi_tree(&[]);
+		tree.window_height.set(Some(10));
+
+		assert!(tree.move_selection(MoveSelection::PageDown));
+		assert_eq!(tree.selection, Some(4));
+		assert!(tree.move_selection(MoveSelection::PageDown));
+		assert_eq!(tree.selection, Some(6));
+
+		assert!(tree.move_selection(MoveSelection::PageUp));
+		assert_eq!(tree.selection, Some(4));
+		assert!(tree.move_selection(MoveSelection::PageUp));
+		assert_eq!(tree.selection, Some(0));
+	}
+
+	#[test]
+	fn test_multi_root_collapse() {
+		let visible = |tree: &FileTree| {
+			tree.items
+				.tree_items
+				.iter()
+				.enumerate()
+				.filter(|(_, item)| item.info().is_visible())
+				.map(|(idx, _)| idx)
+				.collect::<Vec<_>>()
+		};
+
+		let mut tree = multi_tree(&["<repo>"]);
+		assert_eq!(visible(&tree), vec![0, 4, 5, 6]);
+
+		// stays on the collapsed root
+		assert!(tree.move_selection(MoveSelection::Down));
+		assert_eq!(tree.selection, Some(4));
+
+		assert!(tree.move_selection(MoveSelection::Left));
+		assert_eq!(visible(&tree), vec![0, 4]);
+		assert!(!tree.move_selection(MoveSelection::Down));
+
+		assert!(tree.move_selection(MoveSelection::Right));
+		assert_eq!(visible(&tree), vec![0, 4, 5, 6]);
+
+		// paths are collapsed in every root
+		let tree = multi_tree(&["a"]);
+		assert_eq!(visible(&tree), vec![0, 1, 3, 4, 5]);
+
+		let mut tree = multi_tree(&[]);
+		tree.collapse_but_root();
+		assert_eq!(visible(&tree), vec![0, 1, 3, 4, 5]);
+	}
+
+	#[test]
+	fn test_collapsed_paths_round_trip() {
+		let items = vec![
+			Path::new("a/b/c"), //
+			Path::new("a/d"),   //
+			Path::new("e/f/g"), //
+			Path::new("h"),     //
+		];
+
+		//0 a/
+		//1   b/
+		//2     c
+		//3   d
+		//4 e/f/
+		//5   g
+		//6 h
+
+		let mut tree =
+			FileTree::new(&items, &BTreeSet::new()).unwrap();
+		assert!(tree.collapsed_paths().is_empty());
+
+		tree.select_file(Path::new("a/b"));
+		tree.collapse_recursive();
+		tree.select_file(Path::new("e/f"));
+		assert!(tree.move_selection(MoveSelection::Left));
+
+		let collapsed = tree.collapsed_paths();
+		assert_eq!(
+			collapsed.iter().map(String::as_str).collect::<Vec<_>>(),
+			vec!["a/b", "e/f"]
+		);
+		assert_eq!(tree.is_collapsed(Path::new("a")), Some(false));
+		assert_eq!(tree.is_collapsed(Path::new("a/b")), Some(true));
+		// merged folders are known by all of their paths
+		assert_eq!(tree.is_collapsed(Path::new("e")), Some(true));
+		assert_eq!(tree.is_collapsed(Path::new("h")), None);
+
+		// `e` is no longer merged with `e/f`
+		let superset = vec![
+			Path::new("a/b/c"),  //
+			Path::new("a/b/c2"), //
+			Path::new("a/d"),    //
+			Path::new("e/f/g"),  //
+			Path::new("e/i"),    //
+			Path::new("h"),      //
+		];
+
+		let mut tree =
+			FileTree::new(&superset, &collapsed.iter().collect())
+				.unwrap();
+
+		assert_eq!(tree.collapsed_paths(), collapsed);
+		assert_eq!(tree.is_collapsed(Path::new("e")), Some(false));
+		assert_eq!(tree.is_collapsed(Path::new("e/f")), Some(true));
+		assert!(!tree.items.tree_items[2].info().is_visible());
+		assert!(!tree.items.tree_items[7].info().is_visible());
+
+		assert!(tree.selected_item().unwrap().is_visible());
+		assert!(tree.move_selection(MoveSelection::Down));
+		assert_eq!(
+			tree.selected_item().unwrap().full_path(),
+			Path::new("a/b")
+		);
+		assert!(tree.move_selection(MoveSelection::Down));
+		assert_eq!(
+			tree.selected_item().unwrap().full_path(),
+			Path::new("a/d")
+		);
+	}
+
+	#[test]
+	fn test_set_collapsed() {
+		let items = vec![
+			Path::new("a/b/c"), //
+			Path::new("a/d"),   //
+		];
+
+		//0 a/
+		//1   b/
+		//2     c
+		//3   d
+
+		let mut tree =
+			FileTree::new(&items, &BTreeSet::new()).unwrap();
+		assert!(tree.select_file(Path::new("a/b/c")));
+
+		let collapsed = BTreeSet::from([String::from("a")]);
+		tree.set_collapsed(&collapsed);
+
+		assert_eq!(tree.collapsed_paths(), collapsed);
+		// the hidden selection moved to the collapsed folder
+		assert_eq!(tree.selection, Some(0));
+		assert_eq!(tree.visual_selection().unwrap().count, 1);
+
+		tree.set_collapsed(&BTreeSet::new());
+		assert!(tree.collapsed_paths().is_empty());
+		assert_eq!(tree.visual_selection().unwrap().count, 4);
+
+		let mut tree = multi_tree(&[]);
+		tree.set_collapsed(&BTreeSet::from([
+			String::from("<sub>"),
+			String::from("a"),
+		]));
+		assert_eq!(
+			tree.collapsed_paths(),
+			BTreeSet::from([
+				String::from("<sub>"),
+				String::from("a")
+			])
+		);
+		assert_eq!(tree.visual_selection().unwrap().count, 4);
+	}
 }
@@ -5,19 +5,43 @@ use crate::{
 };
 use crate::{error::Result, treeitems_iter::TreeItemsIterator};
 use std::{
-	collections::{BTreeSet, HashMap},
+	cmp::Ordering,
+	collections::{BTreeSet, HashMap, HashSet},
 	path::{Path, PathBuf},
 };
 
+/// tree items and the child count of every folder in there
+type CreatedItems<'a> = (Vec<FileTreeItem>, HashMap<&'a Path, usize>);
+
+/// outcome of building the items in the order they were given
+enum Grouped<'a> {
+	Built(CreatedItems<'a>),
+	/// entries of some folder are not adjacent, needs sorting
+	Unsorted,
+}
+
 ///
 #[derive(Default)]
 pub struct FileTreeItems {
 	pub tree_items: Vec<FileTreeItem>,
 	files: usize,
+	/// absolute index of every item by its full path
+	path_index: HashMap<PathBuf, usize>,
+	/// absolute index of every root item, empty unless built by
+	/// [`Self::new_multi`]
+	roots: Vec<usize>,
 }
 
 impl FileTreeItems {
+	/// builds the tree from a list of file paths.
 	///
+	/// construction is linear if all entries of a folder are next
+	/// to each other (like in any sorted list), otherwise the list is
+	/// sorted first.
+	///
+	/// exact duplicates are only added once, paths only differing in
+	/// case are separate entries. fails with [`Error::PathConflict`]
+	/// if a path is used as a file and as a folder at the same time.
 	pub fn new(
 		list: &[&Path],
 		collapsed: &BTreeSet<&String>,
@@ -26,24 +50,219 @@ impl FileTreeItems {
 
 		Self::fold_paths(&mut items, &paths);
 
-		Ok(Self {
+		let files =
+			items.iter().filter(|i| !i.kind().is_path()).count();
+		let path_index = Self::index_paths(&items);
+
+		let mut res = Self {
 			tree_items: items,
-			files: list.len(),
-		})
+			files,
+			path_index,
+			roots: Vec::new(),
+		};
+		if !collapsed.is_empty() {
+			res.update_visibility(None, 0, true);
+		}
+
+		Ok(res)
+	}
+
+	/// builds a tree with one item per root (showing its label)
+	/// containing the tree of that root's paths.
+	///
+	/// the items of a root never mix with another root's even if
+	/// their paths are the same. `collapsed` is matched against the
+	/// paths inside of every root and against the labels. fails like
+	/// [`Self::new`], with the path inside of its root.
+	pub fn new_multi(
+		roots: &[(String, &[&Path])],
+		collapsed: &BTreeSet<&String>,
+	) -> Result<Self> {
+		let mut tree_items = Vec::new();
+		let mut root_indices = Vec::with_capacity(roots.len());
+
+		for (idx, (label, list)) in roots.iter().enumerate() {
+			// items of a root are kept apart by a leading component
+			let root = PathBuf::from(idx.to_string());
+			let list =
+				list.iter().map(|p| root.join(p)).collect::<Vec<_>>();
+			let list =
+				list.iter().map(PathBuf::as_path).collect::<Vec<_>>();
+
+			let mut root_collapsed = collapsed
+				.iter()
+				.map(|p| root.join(p).to_string_lossy().into_owned())
+				.collect::<Vec<_>>();
+			if collapsed.contains(label) {
+				root_collapsed.push(idx.to_string());
+			}
+			let root_collapsed =
+				root_collapsed.iter().collect::<BTreeSet<_>>();
+
+			let (mut items, paths) = Self::create_items(
+				&list,
+				&root_collapsed,
+			)
+			.map_err(|e| match e {
+				Error::PathConflict(path) => Error::PathConflict(
+					path.strip_prefix(&root)
+						.map(Path::to_path_buf)
+						.unwrap_or(path),
+				),
+				e => e,
+			})?;
+
+			if items.is_empty() {
+				items.push(FileTreeItem::new_path(
+					&root,
+					collapsed.contains(label),
+				)?);
+			}
+			items[0].info_mut().set_label(label);
+
+			Self::fold_paths(&mut items, &paths);
+
+			for item in &mut items {
+				item.info_mut().set_root(idx);
+			}
+
+			root_indices.push(tree_items.len());
+			tree_items.extend(items);
+		}
+
+		let files =
+			tree_items.iter().filter(|i| !i.kind().is_path()).count();
+		let path_index = Self::index_paths(&tree_items);
+
+		let mut res = Self {
+			tree_items,
+			files,
+			path_index,
+			roots: root_indices,
+		};
+		res.update_visibility(None, 0, true);
+
+		Ok(res)
+	}
+
+	/// absolute index of every root item, empty for trees built from
+	/// a single list
+	pub fn roots(&self) -> &[usize] {
+		&self.roots
+	}
+
+	/// like [`Self::find_path`] with `path` inside of `root`
+	pub fn find_path_in(
+		&self,
+		root: usize,
+		path: &Path,
+	) -> Option<usize> {
+		if self.roots.is_empty() {
+			return self.find_path(path);
+		}
+
+		let root = PathBuf::from(root.to_string());
+		if path.as_os_str().is_empty() {
+			self.find_path(&root)
+		} else {
+			self.find_path(&root.join(path))
+		}
+	}
+
+	/// has to be rebuilt whenever items are added, removed or moved,
+	/// collapsing and expanding does not affect it
+	fn index_paths(
+		items: &[FileTreeItem],
+	) -> HashMap<PathBuf, usize> {
+		let mut index = HashMap::with_capacity(items.len());
+
+		for (idx, item) in items.iter().enumerate() {
+			index
+				.entry(item.info().tree_path().to_path_buf())
+				.or_insert(idx);
+		}
+
+		index
+	}
+
+	/// absolute index of the item with exactly this full path
+	pub fn find_path(&self, path: &Path) -> Option<usize> {
+		self.path_index.get(path).copied()
+	}
+
+	/// absolute index of the folder containing the item at `index`
+	pub fn find_parent(&self, index: usize) -> Option<usize> {
+		// merged folders are only indexed by their deepest path
+		self.tree_items
+			.get(index)?
+			.info()
+			.tree_path()
+			.ancestors()
+			.skip(1)
+			.find_map(|p| self.find_path(p))
 	}
 
 	fn create_items<'a>(
-		list: &'a [&Path],
+		list: &[&'a Path],
+		collapsed: &BTreeSet<&String>,
+	) -> Result<CreatedItems<'a>> {
+		match Self::create_items_grouped(list, collapsed)? {
+			Grouped::Built(res) => Ok(res),
+			Grouped::Unsorted => {
+				// sorted input is always grouped, so this recursion
+				// ends after one level
+				let mut sorted = list.to_vec();
+				sorted.sort_unstable();
+
+				Self::create_items(&sorted, collapsed)
+			}
+		}
+	}
+
+	fn create_items_grouped<'a>(
+		list: &[&'a Path],
 		collapsed: &BTreeSet<&String>,
-	) -> Result<(Vec<FileTreeItem>, HashMap<&'a Path, usize>)> {
+	) -> Result<Grouped<'a>> {
 		// scopetime::scope_time!("create_items");
 
 		let mut items = Vec::with_capacity(list.len());
 		let mut paths_added: HashMap<&Path, usize> =
 			HashMap::with_capacity(list.len());
+		let mut files: Option<HashSet<&Path>> = None;
+		let mut conflicts = Vec::new();
+		let mut last_parent: Option<&Path> = None;
+
+		for (idx, e) in list.iter().enumerate() {
+			if Self::is_duplicate(
+				list,
+				idx,
+				&mut files,
+				&mut conflicts,
+			) {
+				continue;
+			}
+
+			let parent = e.parent();
+
+			if parent.is_some() && parent == last_parent {
+				// shares all folders with the previous entry
+				if let Some(count) =
+					parent.and_then(|p| paths_added.get_mut(p))
+				{
+					*count += 1;
+				}
+			} else {
+				if let Some(existing) =
+					Self::deepest_added_ancestor(e, &paths_added)
+				{
+					// folders that were left already must not reappear
+					if !last_parent.is_some_and(|last| {
+						last.starts_with(existing)
+					}) {
+						return Ok(Grouped::Unsorted);
+					}
+				}
 
-		for e in list {
-			{
 				Self::push_dirs(
 					e,
 					&mut items,
@@ -52,10 +271,74 @@ impl FileTreeItems {
 				)?;
 			}
 
+			last_parent = parent;
 			items.push(FileTreeItem::new_file(e)?);
 		}
 
-		Ok((items, paths_added))
+		conflicts.extend(
+			files
+				.iter()
+				.flatten()
+				.filter(|f| paths_added.contains_key(*f)),
+		);
+		if let Some(conflict) = conflicts.iter().min() {
+			return Err(Error::PathConflict(conflict.to_path_buf()));
+		}
+
+		Ok(Grouped::Built((items, paths_added)))
+	}
+
+	/// as long as the input is sorted a duplicate is always next to
+	/// the original and a file is directly followed by anything below
+	/// it, so only the previous entry has to be looked at. the set of
+	/// `files` is built once an entry is out of order and then used
+	/// for the remaining entries (their conflicts are found at the end)
+	fn is_duplicate<'a>(
+		list: &[&'a Path],
+		idx: usize,
+		files: &mut Option<HashSet<&'a Path>>,
+		conflicts: &mut Vec<&'a Path>,
+	) -> bool {
+		let e = list[idx];
+
+		if let Some(files) = files {
+			return !files.insert(e);
+		}
+
+		let Some(previous) =
+			idx.checked_sub(1).map(|prev| list[prev])
+		else {
+			return false;
+		};
+
+		match previous.cmp(e) {
+			Ordering::Equal => true,
+			Ordering::Less => {
+				if e.starts_with(previous) {
+					conflicts.push(previous);
+				}
+				false
+			}
+			Ordering::Greater => {
+				let mut seen = HashSet::with_capacity(list.len());
+				seen.extend(list[..idx].iter().copied());
+				let duplicate = !seen.insert(e);
+				*files = Some(seen);
+
+				duplicate
+			}
+		}
+	}
+
+	fn deepest_added_ancestor<'a>(
+		item_path: &'a Path,
+		paths_added: &HashMap<&'a Path, usize>,
+	) -> Option<&'a Path> {
+		item_path
+			.ancestors()
+			.skip(1)
+			.take_while(|p| !p.as_os_str().is_empty())
+			.find(|p| paths_added.contains_key(p))
 	}
 
 	/// how many individual items (files/paths) are in the list
@@ -85,12 +368,18 @@ impl FileTreeItems {
 		paths_added: &mut HashMap<&'a Path, usize>,
 		collapsed: &BTreeSet<&String>,
 	) -> Result<()> {
-		let mut ancestors =
-			item_path.ancestors().skip(1).collect::<Vec<_>>();
+		// if a folder was added before, so were all of its parents
+		let mut ancestors = item_path
+			.ancestors()
+			.skip(1)
+			.take_while(|c| {
+				c.parent().is_some() && !paths_added.contains_key(c)
+			})
+			.collect::<Vec<_>>();
 		ancestors.reverse();
 
 		for c in &ancestors {
-			if c.parent().is_some() && !paths_added.contains_key(c) {
+			{
 				// add node and set count to have no children
 				paths_added.insert(c, 0);
 
@@ -126,13 +415,44 @@ impl FileTreeItems {
 			.to_string())
 	}
 
+	/// collapses exactly the folders in `collapsed` and expands all
+	/// others. like in [`Self::new_multi`] the paths are matched inside
+	/// of every root and roots by their label, merged folders by any
+	/// of their paths
+	pub fn set_collapsed(&mut self, collapsed: &BTreeSet<String>) {
+		for item in &mut self.tree_items {
+			if !item.kind().is_path() {
+				continue;
+			}
+
+			let info = item.info();
+			let is_collapsed = if info.is_root() {
+				collapsed.contains(info.name())
+			} else {
+				info.folder_paths().any(|path| {
+					path.to_str()
+						.is_some_and(|p| collapsed.contains(p))
+				})
+			};
+
+			if is_collapsed {
+				item.collapse_path();
+			} else {
+				item.expand_path();
+			}
+		}
+
+		self.update_visibility(None, 0, true);
+	}
+
 	pub fn collapse(&mut self, index: usize, recursive: bool) {
 		if self.tree_items[index].kind().is_path() {
 			self.tree_items[index].collapse_path();
 
-			let path = PathBuf::from(
-				self.tree_items[index].info().full_path_str(),
-			);
+			let path = self.tree_items[index]
+				.info()
+				.tree_path()
+				.to_path_buf();
 
 			for i in index + 1..self.tree_items.len() {
 				let item = &mut self.tree_items[i];
@@ -141,8 +461,7 @@ impl FileTreeItems {
 					item.collapse_path();
 				}
 
-				let item_path =
-					Path::new(item.info().full_path_str());
+				let item_path = item.info().tree_path();
 
 				if item_path.starts_with(&path) {
 					item.hide();
@@ -157,15 +476,18 @@ impl FileTreeItems {
 		if self.tree_items[index].kind().is_path() {
 			self.tree_items[index].expand_path();
 
-			let full_path = PathBuf::from(
-				self.tree_items[index].info().full_path_str(),
-			);
+			let full_path = self.tree_items[index]
+				.info()
+				.tree_path()
+				.to_path_buf();
 
 			if recursive {
 				for i in index + 1..self.tree_items.len() {
 					let item = &mut self.tree_items[i];
 
-					if !Path::new(item.info().full_path_str())
+					if !item
+						.info()
+						.tree_path()
 						.starts_with(&full_path)
 					{
 						break;
@@ -188,84 +510,48 @@ impl FileTreeItems {
 	}
 
 	/// makes sure `index` is visible.
-	/// this expands all parents and shows all siblings
+	/// this expands all parents and shows all siblings, returns how
+	/// many items became visible.
+	///
+	/// parents are looked up by path, so only the folders on the way
+	/// up to the first visible parent are visited.
 	pub fn show_element(&mut self, index: usize) -> Option<usize> {
-		Some(
-			self.show_element_upward(index)?
-				+ self.show_element_downward(index)?,
-		)
-	}
-
-	fn show_element_upward(&mut self, index: usize) -> Option<usize> {
 		let mut shown = 0_usize;
-
-		let item = self.tree_items.get(index)?;
-		let mut current_folder: (PathBuf, u8) = (
-			item.info().full_path().parent()?.to_path_buf(),
-			item.info().indent(),
-		);
-
-		let item_count = self.tree_items.len();
-		for item in self
-			.tree_items
-			.iter_mut()
-			.rev()
-			.skip(item_count - index - 1)
-		{
-			if item.info().indent() == current_folder.1 {
-				item.show();
-				shown += 1;
-			} else if item.info().indent() == current_folder.1 - 1 {
-				// this must be our parent
-
-				item.expand_path();
-
-				if item.info().is_visible() {
-					// early out if parent already visible
-					break;
+		let mut current = index;
+
+		loop {
+			let indent =
+				self.tree_items.get(current)?.info().indent();
+
+			let Some(parent) = self.find_parent(current) else {
+				// top level items are always visible
+				break;
+			};
+
+			let end = self.tree_items[current + 1..]
+				.iter()
+				.position(|item| item.info().indent() < indent)
+				.map_or(self.tree_items.len(), |pos| {
+					current + 1 + pos
+				});
+
+			for item in &mut self.tree_items[parent + 1..end] {
+				if item.info().indent() == indent
+					&& !item.info().is_visible()
+				{
+					item.show();
+					shown += 1;
 				}
-
-				item.show();
-				shown += 1;
-
-				current_folder = (
-					item.info().full_path().parent()?.to_path_buf(),
-					item.info().indent(),
-				);
 			}
-		}
-
-		Some(shown)
-	}
-
-	fn show_element_downward(
-		&mut self,
-		index: usize,
-	) -> Option<usize> {
-		let mut shown = 0_usize;
 
-		let item = self.tree_items.get(index)?;
-		let mut current_folder: (PathBuf, u8) = (
-			item.info().full_path().parent()?.to_path_buf(),
-			item.info().indent(),
-		);
+			let parent_item = &mut self.tree_items[parent];
+			parent_item.expand_path();
 
-		for item in self.tree_items.iter_mut().skip(index + 1) {
-			if item.info().indent() == current_folder.1 {
-				item.show();
-				shown += 1;
+			if parent_item.info().is_visible() {
+				break;
 			}
-			if item.info().indent() == current_folder.1 - 1 {
-				// this must be our parent
 
-				item.show();
-				shown += 1;
-
-				current_folder = (
-					item.info().full_path().parent()?.to_path_buf(),
-					item.info().indent(),
-				);
-			}
+			current = parent;
 		}
 
 		Some(shown)
@@ -282,9 +568,7 @@ impl FileTreeItems {
 
 		for i in start_idx..self.tree_items.len() {
 			if let Some(ref collapsed_path) = inner_collapsed {
-				let p = Path::new(
-					self.tree_items[i].info().full_path_str(),
-				);
+				let p = self.tree_items[i].info().tree_path();
 				if p.starts_with(collapsed_path) {
 					if set_defaults {
 						self.tree_items[i]
@@ -298,8 +582,7 @@ impl FileTreeItems {
 			}
 
 			let item_kind = self.tree_items[i].kind().clone();
-			let item_path =
-				Path::new(self.tree_items[i].info().full_path_str());
+			let item_path = self.tree_items[i].info().tree_path();
 
 			if matches!(item_kind, FileTreeItemKind::Path(PathCollapsed(collapsed)) if collapsed)
 			{
@@ -323,60 +606,57 @@ impl FileTreeItems {
 		}
 	}
 
+	/// merges folders having nothing but a single folder inside
+	/// into one item (`a/b/c`), every merged away folder unindents
+	/// everything below it
 	fn fold_paths(
 		items: &mut Vec<FileTreeItem>,
 		paths: &HashMap<&Path, usize>,
 	) {
-		let mut i = 0;
-
-		while i < items.len() {
-			let item = &items[i];
-			if item.kind().is_path() {
-				let children = paths
-					.get(&Path::new(item.info().full_path_str()));
-
-				if let Some(children) = children {
-					if *children == 1 {
-						if i + 1 >= items.len() {
-							return;
-						}
-
-						if items
-							.get(i + 1)
-							.is_some_and(|item| item.kind().is_path())
-						{
-							let next_item = items.remove(i + 1);
-							let item_mut = &mut items[i];
-							item_mut.fold(next_item);
-
-							let prefix = item_mut
-								.info()
-								.full_path_str()
-								.to_owned();
-
-							Self::unindent(items, &prefix, i + 1);
-							continue;
-						}
-					}
+		let single_child = |item: &FileTreeItem| {
+			item.kind().is_path()
+				&& !item.info().is_root()
+				&& paths.get(&item.info().tree_path()) == Some(&1)
+		};
+
+		let mut res = Vec::with_capacity(items.len());
+		// number of merged away folders above each indent level
+		let mut folded: Vec<u8> = Vec::new();
+		let mut rest = std::mem::take(items).into_iter().peekable();
+
+		while let Some(mut item) = rest.next() {
+			let indent = usize::from(item.info().indent());
+			folded.truncate(indent);
+			let unindent = folded.last().copied().unwrap_or_default();
+
+			let mut removed = 0_u8;
+			while single_child(&item)
+				&& rest
+					.peek()
+					.is_some_and(|next| next.kind().is_path())
+			{
+				if let Some(next) = rest.next() {
+					item.fold(next);
+					removed = removed.saturating_add(1);
 				}
 			}
 
-			i += 1;
-		}
-	}
+			for _ in 0..unindent {
+				item.info_mut().unindent();
+			}
 
-	fn unindent(
-		items: &mut [FileTreeItem],
-		prefix: &str,
-		start: usize,
-	) {
-		for elem in items.iter_mut().skip(start) {
-			if elem.info().full_path_str().starts_with(prefix) {
-				elem.info_mut().unindent();
-			} else {
-				return;
+			if item.kind().is_path() {
+				// merged away folders unindent everything below them
+				folded.resize(
+					indent + 1 + usize::from(removed),
+					unindent.saturating_add(removed),
+				);
 			}
+
+			res.push(item);
 		}
+
+		*items = res;
 	}
 }
 
@@ -521,6 +801,43 @@ mod tests {
 		assert_eq!(res.next(), Some((0, "a.txt")));
 	}
 
+	#[test]
+	fn test_names() {
+		let items = vec![
+			Path::new("a/b/c.txt"), //
+			Path::new("a/d/"),      //
+			Path::new("e.txt"),     //
+			Path::new("f/"),        //
+		];
+
+		//0 a/
+		//1   b/c.txt (folded)
+		//2   d/
+		//3 e.txt
+		//4 f/
+
+		let tree =
+			FileTreeItems::new(&items, &BTreeSet::new()).unwrap();
+
+		let res = tree
+			.tree_items
+			.iter()
+			.map(|i| (i.info().indent(), i.info().name()))
+			.collect::<Vec<_>>();
+
+		assert_eq!(
+			res,
+			vec![
+				(0, "a"),
+				(1, "b"),
+				(2, "c.txt"),
+				(1, "d"),
+				(0, "e.txt"),
+				(0, "f"),
+			]
+		);
+	}
+
 	#[test]
 	fn test_folder_dup() {
 		let items = vec![
@@ -550,6 +867,62 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn test_find_path() {
+		let items = vec![
+			Path::new("a/b/c"), //
+			Path::new("a/d"),   //
+			Path::new("e/f/g"), //
+			Path::new("h"),     //
+		];
+
+		let res =
+			FileTreeItems::new(&items, &BTreeSet::new()).unwrap();
+
+		for (idx, item) in res.tree_items.iter().enumerate() {
+			assert_eq!(
+				res.find_path(item.info().full_path()),
+				Some(idx)
+			);
+		}
+
+		// folded into `e/f`
+		assert_eq!(res.find_path(Path::new("e")), None);
+		assert_eq!(res.find_path(Path::new("x")), None);
+	}
+
+	#[test]
+	fn test_find_path_after_collapse_expand() {
+		let items = vec![
+			Path::new("a/b/c"), //
+			Path::new("a/b/d"), //
+			Path::new("a/e"),   //
+			Path::new("f"),     //
+		];
+
+		let mut res =
+			FileTreeItems::new(&items, &BTreeSet::new()).unwrap();
+
+		let before = res
+			.tree_items
+			.iter()
+			.map(|item| res.find_path(item.info().full_path()))
+			.collect::<Vec<_>>();
+
+		res.collapse(0, true);
+		res.expand(0, false);
+		res.collapse(1, false);
+
+		for (idx, item) in res.tree_items.iter().enumerate() {
+			assert_eq!(
+				res.find_path(item.info().full_path()),
+				Some(idx)
+			);
+			assert_eq!(before[idx], Some(idx));
+		}
+		assert_eq!(res.find_path(Path::new("a/b/d")), Some(3));
+	}
+
 	#[test]
 	fn test_collapse() {
 		let items = vec![
@@ -915,6 +1288,181 @@ mod tests {
 			]
 		);
 	}
+
+	#[test]
+	fn test_show_element_merged_parent() {
+		let items = vec![
+			Path::new("a/b/c/d"), //
+			Path::new("a/b/c/e"), //
+			Path::new("a/f"),     //
+			Path::new("x"),       //
+		];
+
+		//0 a/
+		//1   b/c/
+		//2     d
+		//3     e
+		//4   f
+		//5 x
+
+		let mut tree =
+			FileTreeItems::new(&items, &BTreeSet::new()).unwrap();
+
+		tree.collapse(0, true);
+
+		assert_eq!(tree.show_element(3), Some(4));
+		assert_eq!(
+			get_visible(&tree),
+			vec![true, true, true, true, true, true]
+		);
+
+		// already visible
+		assert_eq!(tree.show_element(3), Some(0));
+		assert_eq!(tree.show_element(5), Some(0));
+	}
+
+	#[test]
+	fn test_unsorted_input() {
+		let sorted = vec![
+			Path::new("a/x"), //
+			Path::new("a/y"), //
+			Path::new("b"),   //
+		];
+		let unsorted = vec![
+			Path::new("a/x"), //
+			Path::new("b"),   //
+			Path::new("a/y"), //
+		];
+
+		let to_info = |items: &[&Path]| {
+			FileTreeItems::new(items, &BTreeSet::new())
+				.unwrap()
+				.tree_items
+				.iter()
+				.map(|i| {
+					(
+						i.info().indent(),
+						i.info().full_path_str().to_string(),
+					)
+				})
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(to_info(&unsorted), to_info(&sorted));
+		assert_eq!(
+			to_info(&unsorted),
+			vec![
+				(0, String::from("a")),
+				(1, String::from("a/x")),
+				(1, String::from("a/y")),
+				(0, String::from("b")),
+			]
+		);
+	}
+
+	#[test]
+	fn test_exact_duplicates() {
+		let items = vec![
+			Path::new("a/file.txt"), //
+			Path::new("b.txt"),      //
+			Path::new("a/file.txt"), //
+			Path::new("b.txt"),      //
+		];
+
+		let tree =
+			FileTreeItems::new(&items, &BTreeSet::new()).unwrap();
+
+		assert_eq!(tree.file_count(), 2);
+
+		let res = tree
+			.tree_items
+			.iter()
+			.map(|i| i.info().full_path_str().to_string())
+			.collect::<Vec<_>>();
+
+		assert_eq!(
+			res,
+			vec![
+				String::from("a"),
+				String::from("a/file.txt"),
+				String::from("b.txt"),
+			]
+		);
+	}
+
+	#[test]
+	fn test_sorted_duplicates() {
+		let items = vec![
+			Path::new("a/x"), //
+			Path::new("a/x"), //
+			Path::new("a/y"), //
+			Path::new("a/y"), //
+		];
+
+		let tree =
+			FileTreeItems::new(&items, &BTreeSet::new()).unwrap();
+
+		assert_eq!(tree.file_count(), 2);
+		assert_eq!(tree.len(), 3);
+	}
+
+	#[test]
+	fn test_case_differing_paths() {
+		let items = vec![
+			Path::new("README.md"), //
+			Path::new("Readme.md"), //
+		];
+
+		let tree =
+			FileTreeItems::new(&items, &BTreeSet::new()).unwrap();
+
+		assert_eq!(tree.file_count(), 2);
+		assert_eq!(tree.len(), 2);
+		assert_eq!(tree.tree_items[0].info().path(), items[0]);
+		assert_eq!(tree.tree_items[1].info().path(), items[1]);
+	}
+
+	#[test]
+	fn test_file_and_folder_conflict() {
+		let items = vec![
+			Path::new("a/b"),   //
+			Path::new("a/b/c"), //
+		];
+
+		let res = FileTreeItems::new(&items, &BTreeSet::new());
+
+		assert!(matches!(
+			res,
+			Err(Error::PathConflict(path)) if path == Path::new("a/b")
+		));
+
+		// sorted with deeper nesting
+		let items = vec![
+			Path::new("a/b"),     //
+			Path::new("a/b/c/d"), //
+		];
+
+		let res = FileTreeItems::new(&items, &BTreeSet::new());
+
+		assert!(matches!(
+			res,
+			Err(Error::PathConflict(path)) if path == Path::new("a/b")
+		));
+
+		// same in the other order
+		let items = vec![
+			Path::new("a/b/c"), //
+			Path::new("a/b"),   //
+			Path::new("a/d"),   //
+		];
+
+		let res = FileTreeItems::new(&items, &BTreeSet::new());
+
+		assert!(matches!(
+			res,
+			Err(Error::PathConflict(path)) if path == Path::new("a/b")
+		));
+	}
 }
 
 #[cfg(test)]
@@ -922,6 +1470,58 @@ mod test_merging {
 	use super::*;
 	use pretty_assertions::assert_eq;
 
+	#[test]
+	fn test_multi_root() {
+		let repo = [Path::new("a/b/c.txt")];
+		let empty: [&Path; 0] = [];
+
+		let res = FileTreeItems::new_multi(
+			&[
+				(String::from("<repo>"), &repo[..]),
+				(String::from("<clean>"), &empty[..]),
+			],
+			&BTreeSet::new(),
+		)
+		.unwrap();
+
+		let names = res
+			.tree_items
+			.iter()
+			.map(|i| (i.info().indent(), i.info().path_str()))
+			.collect::<Vec<_>>();
+
+		// the root is not merged with its only folder
+		assert_eq!(
+			names,
+			vec![
+				(0, "<repo>"),
+				(1, "a/b"),
+				(2, "c.txt"),
+				(0, "<clean>")
+			]
+		);
+		assert_eq!(res.roots(), &[0, 3]);
+		assert_eq!(res.file_count(), 1);
+		assert_eq!(res.find_path_in(0, Path::new("a/b")), Some(1));
+		assert_eq!(res.find_path_in(1, Path::new("")), Some(3));
+		assert_eq!(res.find_path_in(1, Path::new("a/b")), None);
+
+		// conflicts are reported inside of their root
+		let conflicting = [Path::new("a/b/c.txt"), Path::new("a/b")];
+		let res = FileTreeItems::new_multi(
+			&[
+				(String::from("<clean>"), &empty[..]),
+				(String::from("<repo>"), &conflicting[..]),
+			],
+			&BTreeSet::new(),
+		);
+
+		assert!(matches!(
+			res,
+			Err(Error::PathConflict(path)) if path == Path::new("a/b")
+		));
+	}
+
 	#[test]
 	fn test_merge_simple() {
 		let list = vec![Path::new("a/b/c")];
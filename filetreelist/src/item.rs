@@ -8,11 +8,22 @@ pub struct TreeItemInfo {
 	indent: u8,
 	/// currently visible depending on the folder collapse states
 	visible: bool,
-	/// contains this paths last component and folded up paths added to it
-	/// if this is `None` nothing was folding into here
-	folded: Option<PathBuf>,
-	/// the full path
+	/// if children paths got folded up into this one: the amount of
+	/// leading components of `full_path` not part of the displayed name.
+	/// `None` shows only the last component
+	folded: Option<usize>,
+	/// the full path, in trees with multiple roots it starts with a
+	/// component identifying the root
 	full_path: PathBuf,
+	/// index of the root this item belongs to in trees with multiple
+	/// roots
+	root: Option<usize>,
+	/// shown instead of the path for the item of a root
+	label: Option<String>,
+	/// unix mode of a file as git records it, 0 if not known
+	mode: u32,
+	/// where a file that is a symlink points to
+	link_target: Option<String>,
 }
 
 impl TreeItemInfo {
@@ -23,6 +34,10 @@ impl TreeItemInfo {
 			visible: true,
 			folded: None,
 			full_path,
+			root: None,
+			label: None,
+			mode: 0,
+			link_target: None,
 		}
 	}
 
@@ -31,37 +46,113 @@ impl TreeItemInfo {
 		self.visible
 	}
 
-	///
-	//TODO: remove
+	/// empty if the path is not valid utf-8
 	pub fn full_path_str(&self) -> &str {
-		self.full_path.to_str().unwrap_or_default()
+		self.full_path().to_str().unwrap_or_default()
 	}
 
-	///
+	/// path inside of its root, empty for the item of a root itself
 	pub fn full_path(&self) -> &Path {
+		if self.root.is_none() {
+			return self.full_path.as_path();
+		}
+
+		let mut components = self.full_path.components();
+		components.next();
+		components.as_path()
+	}
+
+	/// includes the component identifying the root, unique among all
+	/// items of the tree
+	pub(crate) fn tree_path(&self) -> &Path {
 		self.full_path.as_path()
 	}
 
-	/// like `path` but as `&str`
-	pub fn path_str(&self) -> &str {
-		self.path().as_os_str().to_str().unwrap_or_default()
+	/// the folders this item stands for, the deepest first. more than
+	/// one if children paths got folded up into it
+	pub(crate) fn folder_paths(&self) -> impl Iterator<Item = &Path> {
+		let merged = self.folded.map_or(0, |skip| {
+			self.full_path
+				.components()
+				.count()
+				.saturating_sub(skip + 1)
+		});
+
+		self.full_path().ancestors().take(merged + 1)
+	}
+
+	/// index of the root this item belongs to, `None` in trees built
+	/// from a single list
+	pub const fn root(&self) -> Option<usize> {
+		self.root
+	}
+
+	/// `true` for the item standing for a whole root
+	pub const fn is_root(&self) -> bool {
+		self.label.is_some()
+	}
+
+	pub(crate) fn set_root(&mut self, root: usize) {
+		self.root = Some(root);
+	}
+
+	pub(crate) fn set_label(&mut self, label: &str) {
+		self.label = Some(label.to_string());
+	}
+
+	/// unix mode of a file, 0 if not known (and for folders)
+	pub const fn mode(&self) -> u32 {
+		self.mode
+	}
+
+	/// where the file points to if it is a symlink
+	pub fn link_target(&self) -> Option<&str> {
+		self.link_target.as_deref()
+	}
+
+	pub(crate) fn set_mode(
+		&mut self,
+		mode: u32,
+		link_target: Option<String>,
+	) {
+		self.mode = mode;
+		self.link_target = link_target;
 	}
 
 	/// returns the last component of `full_path`
 	/// or the last components plus folded up children paths
+	/// (the label for the item of a root)
+	///
+	/// empty if the name is not valid utf-8
+	pub fn name(&self) -> &str {
+		self.path().to_str().unwrap_or_default()
+	}
+
+	/// like `path` but as `&str`
+	pub fn path_str(&self) -> &str {
+		self.name()
+	}
+
+	/// like `name` but as `&Path`
 	pub fn path(&self) -> &Path {
-		self.folded.as_ref().map_or_else(
-			|| {
-				Path::new(
-					self.full_path
-						.components()
-						.next_back()
-						.and_then(|c| c.as_os_str().to_str())
-						.unwrap_or_default(),
-				)
-			},
-			PathBuf::as_path,
-		)
+		if let Some(label) = &self.label {
+			return Path::new(label);
+		}
+
+		let mut components = self.full_path.components();
+
+		match self.folded {
+			Some(skip) => {
+				for _ in 0..skip {
+					components.next();
+				}
+				components.as_path()
+			}
+			None => components.next_back().map_or_else(
+				|| Path::new(""),
+				|c| Path::new(c.as_os_str()),
+			),
+		}
 	}
 
 	///
@@ -112,14 +203,11 @@ pub struct FileTreeItem {
 
 impl FileTreeItem {
 	pub fn new_file(path: &Path) -> Result<Self> {
-		let item_path = PathBuf::from(path);
-
-		let indent = u8::try_from(
-			item_path.ancestors().count().saturating_sub(2),
-		)?;
+		let indent =
+			u8::try_from(path.ancestors().count().saturating_sub(2))?;
 
 		Ok(Self {
-			info: TreeItemInfo::new(indent, item_path),
+			info: TreeItemInfo::new(indent, path.to_path_buf()),
 			kind: FileTreeItemKind::File,
 		})
 	}
@@ -129,21 +217,26 @@ impl FileTreeItem {
 			u8::try_from(path.ancestors().count().saturating_sub(2))?;
 
 		Ok(Self {
-			info: TreeItemInfo::new(indent, path.to_owned()),
+			info: TreeItemInfo::new(indent, path.to_path_buf()),
 			kind: FileTreeItemKind::Path(PathCollapsed(collapsed)),
 		})
 	}
 
-	///
+	/// folds `next` (the only child of this path) into this item.
+	/// `next.full_path` starts with ours, so our name keeps starting at
+	/// the same component and extends to the end of the new full path.
+	/// the item stays collapsed if either of both was
 	pub fn fold(&mut self, next: Self) {
-		if let Some(folded) = self.info.folded.as_mut() {
-			*folded = folded.join(next.info.path());
-		} else {
-			self.info.folded =
-				Some(self.info.path().join(next.info.path()));
-		}
+		let skip = self.info.folded.unwrap_or_else(|| {
+			self.info.full_path.components().count().saturating_sub(1)
+		});
 
+		self.info.folded = Some(skip);
 		self.info.full_path = next.info.full_path;
+
+		if next.kind.is_path_collapsed() {
+			self.collapse_path();
+		}
 	}
 
 	///
@@ -227,10 +320,88 @@ mod tests {
 		a.fold(b);
 
 		assert_eq!(a.info.full_path_str(), "a/b");
+		assert_eq!(a.info.name(), "a/b");
+		assert_eq!(a.info.path(), Path::new("a/b"));
+
+		let c = FileTreeItem::new_path(Path::new("a/b/c"), false)
+			.unwrap();
+		a.fold(c);
+
+		assert_eq!(a.info.full_path_str(), "a/b/c");
+		assert_eq!(a.info.name(), "a/b/c");
+	}
+
+	#[test]
+	fn test_name_nested() {
+		let mut a =
+			FileTreeItem::new_path(Path::new("x/a"), false).unwrap();
+
+		assert_eq!(a.info.name(), "a");
+
+		let b = FileTreeItem::new_path(Path::new("x/a/b"), false)
+			.unwrap();
+		a.fold(b);
+
+		assert_eq!(a.info.full_path_str(), "x/a/b");
+		assert_eq!(a.info.name(), "a/b");
+	}
+
+	#[test]
+	fn test_name_root_file() {
+		let a =
+			FileTreeItem::new_file(Path::new("file.txt")).unwrap();
+
+		assert_eq!(a.info.name(), "file.txt");
+		assert_eq!(a.info.full_path_str(), "file.txt");
+		assert_eq!(a.info.indent(), 0);
+	}
+
+	#[test]
+	fn test_name_trailing_slash() {
+		let a = FileTreeItem::new_file(Path::new("a/dir/")).unwrap();
+
+		assert_eq!(a.info.name(), "dir");
+		assert_eq!(a.info.full_path_str(), "a/dir/");
+
+		let b = FileTreeItem::new_file(Path::new("dir/")).unwrap();
+
+		assert_eq!(b.info.name(), "dir");
+		assert_eq!(b.info.indent(), 0);
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn test_non_utf8_full_path() {
+		use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+		let path = Path::new(OsStr::from_bytes(b"a/f\xffile"));
+		let mut a =
+			FileTreeItem::new_path(Path::new("a"), false).unwrap();
+		a.fold(FileTreeItem::new_file(path).unwrap());
+
+		assert_eq!(a.info.full_path(), path);
+		assert_eq!(a.info.path(), path);
+
+		let b = FileTreeItem::new_file(path).unwrap();
 		assert_eq!(
-			&a.info.folded.as_ref().unwrap(),
-			&Path::new("a/b")
+			b.info.path(),
+			Path::new(OsStr::from_bytes(b"f\xffile"))
 		);
-		assert_eq!(a.info.path(), Path::new("a/b"));
+	}
+
+	#[cfg(windows)]
+	#[test]
+	fn test_backslash_separator() {
+		let mut a =
+			FileTreeItem::new_path(Path::new("x\\a"), false).unwrap();
+
+		assert_eq!(a.info.name(), "a");
+
+		a.fold(
+			FileTreeItem::new_path(Path::new("x\\a\\b"), false)
+				.unwrap(),
+		);
+
+		assert_eq!(a.info.path(), Path::new("a\\b"));
 	}
 }
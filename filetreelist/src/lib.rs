@@ -30,6 +30,7 @@ mod tree_iter;
 mod treeitems_iter;
 
 pub use crate::{
+	error::Error,
 	filetree::FileTree,
 	filetree::MoveSelection,
 	item::{FileTreeItem, TreeItemInfo},
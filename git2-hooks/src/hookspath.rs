@@ -5,7 +5,7 @@ use crate::{error::Result, HookResult, HooksError};
 use std::{
 	ffi::{OsStr, OsString},
 	path::{Path, PathBuf},
-	process::Command,
+	process::{Child, Command, Stdio},
 	str::FromStr,
 };
 
@@ -148,15 +148,74 @@ impl HookPaths {
 		let hook = self.hook.clone();
 		log::trace!("run hook '{:?}' in '{:?}'", hook, self.pwd);
 
-		let run_command = |command: &mut Command| {
+		let output = self.run_command(args, Command::output)?;
+
+		if output.status.success() {
+			Ok(HookResult::Ok { hook })
+		} else {
+			let stderr =
+				String::from_utf8_lossy(&output.stderr).to_string();
+			let stdout =
+				String::from_utf8_lossy(&output.stdout).to_string();
+
+			Ok(HookResult::RunNotSuccessful {
+				code: output.status.code(),
+				stdout,
+				stderr,
+				hook,
+			})
+		}
+	}
+
+	/// like `run_hook` but returns right after starting the hook,
+	/// stdout and stderr are piped for the caller to read.
+	/// `prepare` can adjust the command (e.g. its env) beforehand
+	pub fn spawn_hook<I, S>(
+		&self,
+		args: I,
+		prepare: impl Fn(&mut Command),
+	) -> Result<Child>
+	where
+		I: IntoIterator<Item = S> + Copy,
+		S: AsRef<OsStr>,
+	{
+		log::trace!(
+			"spawn hook '{:?}' in '{:?}'",
+			self.hook,
+			self.pwd
+		);
+
+		self.run_command(args, |command| {
+			prepare(command);
 			command
+				.stdin(Stdio::null())
+				.stdout(Stdio::piped())
+				.stderr(Stdio::piped())
+				.spawn()
+		})
+	}
+
+	/// runs the hook directly (or in a shell where needed) using
+	/// `run` to start the prepared command
+	fn run_command<I, S, T>(
+		&self,
+		args: I,
+		run: impl Fn(&mut Command) -> std::io::Result<T>,
+	) -> Result<T>
+	where
+		I: IntoIterator<Item = S> + Copy,
+		S: AsRef<OsStr>,
+	{
+		let hook = &self.hook;
+
+		let run_command = |command: &mut Command| {
+			run(command
 				.args(args)
 				.current_dir(&self.pwd)
-				.with_no_window()
-				.output()
+				.with_no_window())
 		};
 
-		let output = if cfg!(windows) {
+		let result = if cfg!(windows) {
 			// execute hook in shell
 			let command = {
 				// SEE: https://pubs.opengroup.org/onlinepubs/9699919799/utilities/V3_chap02.html#tag_18_02_02
@@ -193,34 +252,18 @@ impl HookPaths {
 
 				os_str
 			};
-			run_command(
-				sh_command().arg("-c").arg(command).arg(&hook),
-			)
+			run_command(sh_command().arg("-c").arg(command).arg(hook))
 		} else {
 			// execute hook directly
-			match run_command(&mut Command::new(&hook)) {
+			match run_command(&mut Command::new(hook)) {
 				Err(err) if err.raw_os_error() == Some(ENOEXEC) => {
-					run_command(sh_command().arg(&hook))
+					run_command(sh_command().arg(hook))
 				}
 				result => result,
 			}
 		}?;
 
-		if output.status.success() {
-			Ok(HookResult::Ok { hook })
-		} else {
-			let stderr =
-				String::from_utf8_lossy(&output.stderr).to_string();
-			let stdout =
-				String::from_utf8_lossy(&output.stdout).to_string();
-
-			Ok(HookResult::RunNotSuccessful {
-				code: output.status.code(),
-				stdout,
-				stderr,
-				hook,
-			})
-		}
+		Ok(result)
 	}
 }
 
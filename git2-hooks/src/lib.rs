@@ -28,9 +28,11 @@ mod error;
 mod hookspath;
 
 use std::{
+	ffi::OsString,
 	fs::File,
 	io::{Read, Write},
 	path::{Path, PathBuf},
+	process::{Child, Command},
 };
 
 pub use error::HooksError;
@@ -129,18 +131,53 @@ pub fn hooks_commit_msg(
 		return Ok(HookResult::NoHookFound);
 	}
 
-	let temp_file = hook.git.join(HOOK_COMMIT_MSG_TEMP_FILE);
-	File::create(&temp_file)?.write_all(msg.as_bytes())?;
+	let temp_file = write_msg_file(&hook, msg)?;
 
 	let res = hook.run_hook_os_str([&temp_file])?;
 
 	// load possibly altered msg
-	msg.clear();
-	File::open(temp_file)?.read_to_string(msg)?;
+	*msg = read_hook_msg(repo)?;
 
 	Ok(res)
 }
 
+/// like [`hooks_commit_msg`] but returns right after starting the
+/// hook, see [`spawn_hook`]. [`read_hook_msg`] reads the possibly
+/// altered msg once it is done
+pub fn spawn_hooks_commit_msg(
+	repo: &Repository,
+	other_paths: Option<&[&str]>,
+	msg: &str,
+	prepare: impl Fn(&mut Command),
+) -> Result<Option<Child>> {
+	let hook = HookPaths::new(repo, other_paths, HOOK_COMMIT_MSG)?;
+
+	if !hook.found() {
+		return Ok(None);
+	}
+
+	let temp_file = write_msg_file(&hook, msg)?;
+
+	hook.spawn_hook([&temp_file], prepare).map(Some)
+}
+
+/// the msg a `commit-msg` or `prepare-commit-msg` hook left in the
+/// temp file it was given
+pub fn read_hook_msg(repo: &Repository) -> Result<String> {
+	let mut msg = String::new();
+	File::open(repo.path().join(HOOK_COMMIT_MSG_TEMP_FILE))?
+		.read_to_string(&mut msg)?;
+
+	Ok(msg)
+}
+
+fn write_msg_file(hook: &HookPaths, msg: &str) -> Result<PathBuf> {
+	let temp_file = hook.git.join(HOOK_COMMIT_MSG_TEMP_FILE);
+	File::create(&temp_file)?.write_all(msg.as_bytes())?;
+
+	Ok(temp_file)
+}
+
 /// this hook is documented here <https://git-scm.com/docs/githooks#_pre_commit>
 pub fn hooks_pre_commit(
 	repo: &Repository,
@@ -169,6 +206,25 @@ pub fn hooks_post_commit(
 	hook.run_hook(&[])
 }
 
+/// starts `hook` without waiting for it so its output can be read
+/// while it runs, `prepare` can adjust the command beforehand.
+/// returns `None` if there is no such hook
+pub fn spawn_hook(
+	repo: &Repository,
+	other_paths: Option<&[&str]>,
+	hook: &str,
+	args: &[&str],
+	prepare: impl Fn(&mut Command),
+) -> Result<Option<Child>> {
+	let hook = HookPaths::new(repo, other_paths, hook)?;
+
+	if !hook.found() {
+		return Ok(None);
+	}
+
+	hook.spawn_hook(args, prepare).map(Some)
+}
+
 pub enum PrepareCommitMsgSource {
 	Message,
 	Template,
@@ -192,40 +248,62 @@ pub fn hooks_prepare_commit_msg(
 		return Ok(HookResult::NoHookFound);
 	}
 
-	let temp_file = hook.git.join(HOOK_COMMIT_MSG_TEMP_FILE);
-	File::create(&temp_file)?.write_all(msg.as_bytes())?;
+	let temp_file = write_msg_file(&hook, msg)?;
+	let args = prepare_commit_msg_args(&temp_file, &source);
+
+	let res = hook.run_hook_os_str(&args)?;
 
-	let temp_file_path = temp_file.as_os_str().to_string_lossy();
+	// load possibly altered msg
+	*msg = read_hook_msg(repo)?;
+
+	Ok(res)
+}
 
-	let vec = vec![
-		temp_file_path.as_ref(),
-		match source {
+/// like [`hooks_prepare_commit_msg`] but returns right after
+/// starting the hook, see [`spawn_hook`]. [`read_hook_msg`] reads
+/// the possibly altered msg once it is done
+#[allow(clippy::needless_pass_by_value)]
+pub fn spawn_hooks_prepare_commit_msg(
+	repo: &Repository,
+	other_paths: Option<&[&str]>,
+	source: PrepareCommitMsgSource,
+	msg: &str,
+	prepare: impl Fn(&mut Command),
+) -> Result<Option<Child>> {
+	let hook =
+		HookPaths::new(repo, other_paths, HOOK_PREPARE_COMMIT_MSG)?;
+
+	if !hook.found() {
+		return Ok(None);
+	}
+
+	let temp_file = write_msg_file(&hook, msg)?;
+	let args = prepare_commit_msg_args(&temp_file, &source);
+
+	hook.spawn_hook(&args, prepare).map(Some)
+}
+
+fn prepare_commit_msg_args(
+	temp_file: &Path,
+	source: &PrepareCommitMsgSource,
+) -> Vec<OsString> {
+	// the path is passed as is, it need not be valid UTF-8
+	let mut args = vec![
+		temp_file.as_os_str().to_os_string(),
+		OsString::from(match source {
 			PrepareCommitMsgSource::Message => "message",
 			PrepareCommitMsgSource::Template => "template",
 			PrepareCommitMsgSource::Merge => "merge",
 			PrepareCommitMsgSource::Squash => "squash",
 			PrepareCommitMsgSource::Commit(_) => "commit",
-		},
+		}),
 	];
-	let mut args = vec;
 
-	let id = if let PrepareCommitMsgSource::Commit(id) = &source {
-		Some(id.to_string())
-	} else {
-		None
-	};
-
-	if let Some(id) = &id {
-		args.push(id);
+	if let PrepareCommitMsgSource::Commit(id) = source {
+		args.push(id.to_string().into());
 	}
 
-	let res = hook.run_hook(args.as_slice())?;
-
-	// load possibly altered msg
-	msg.clear();
-	File::open(temp_file)?.read_to_string(msg)?;
-
-	Ok(res)
+	args
 }
 
 #[cfg(test)]
@@ -349,6 +427,40 @@ exit 0
 		assert_eq!(res, HookResult::NoHookFound);
 	}
 
+	#[test]
+	fn test_spawn_hook() {
+		let (_td, repo) = repo_init();
+
+		assert!(spawn_hook(
+			&repo,
+			None,
+			HOOK_PRE_COMMIT,
+			&[],
+			|_| ()
+		)
+		.unwrap()
+		.is_none());
+
+		let hook = b"echo \"$@ $GITUI_TEST\"\nexit 3";
+
+		create_hook(&repo, HOOK_PRE_COMMIT, hook);
+
+		let child =
+			spawn_hook(&repo, None, HOOK_PRE_COMMIT, &["arg"], |c| {
+				c.env("GITUI_TEST", "env");
+			})
+			.unwrap()
+			.unwrap();
+
+		let output = child.wait_with_output().unwrap();
+
+		assert_eq!(output.status.code(), Some(3));
+		assert_eq!(
+			String::from_utf8_lossy(&output.stdout).trim_ascii_end(),
+			"arg env"
+		);
+	}
+
 	#[test]
 	fn test_other_path() {
 		let (td, repo) = repo_init();
@@ -575,6 +687,39 @@ exit 0
 		assert_eq!(msg, String::from("msg\n"));
 	}
 
+	#[test]
+	fn test_spawn_hooks_commit_msg() {
+		let (_td, repo) = repo_init();
+
+		assert!(spawn_hooks_commit_msg(&repo, None, "test", |_| ())
+			.unwrap()
+			.is_none());
+
+		let hook = b"#!/bin/sh
+echo \"$(cat \"$1\") $GITUI_TEST\" > \"$1\"
+echo 'checked'
+exit 0
+        ";
+
+		create_hook(&repo, HOOK_COMMIT_MSG, hook);
+
+		let output =
+			spawn_hooks_commit_msg(&repo, None, "test", |c| {
+				c.env("GITUI_TEST", "env");
+			})
+			.unwrap()
+			.unwrap()
+			.wait_with_output()
+			.unwrap();
+
+		assert!(output.status.success());
+		assert_eq!(
+			String::from_utf8_lossy(&output.stdout),
+			"checked\n"
+		);
+		assert_eq!(read_hook_msg(&repo).unwrap(), "test env\n");
+	}
+
 	#[test]
 	fn test_hook_pwd_in_bare_without_workdir() {
 		let (_td, repo) = repo_init_bare();
@@ -657,4 +802,34 @@ exit 2
 			)
 		);
 	}
+
+	#[test]
+	fn test_spawn_hooks_prep_commit_msg() {
+		let (_td, repo) = repo_init();
+
+		let hook = b"#!/bin/sh
+echo \"$(cat \"$1\"):$2,$3\" > \"$1\"
+exit 2
+        ";
+
+		create_hook(&repo, HOOK_PREPARE_COMMIT_MSG, hook);
+
+		let status = spawn_hooks_prepare_commit_msg(
+			&repo,
+			None,
+			PrepareCommitMsgSource::Commit(git2::Oid::zero()),
+			"test",
+			|_| (),
+		)
+		.unwrap()
+		.unwrap()
+		.wait()
+		.unwrap();
+
+		assert_eq!(status.code(), Some(2));
+		assert_eq!(
+			read_hook_msg(&repo).unwrap(),
+			"test:commit,0000000000000000000000000000000000000000\n"
+		);
+	}
 }
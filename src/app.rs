@@ -1,29 +1,38 @@
 use crate::{
 	accessors,
+	args::PrintOnExit,
 	cmdbar::CommandBar,
 	components::{
 		command_pump, event_pump, CommandInfo, Component,
 		DrawableComponent, FuzzyFinderTarget,
 	},
 	input::{Input, InputEvent, InputState},
-	keys::{key_match, KeyConfig, SharedKeyConfig},
+	keys::{
+		key_match, KeyConfig, SharedKeyConfig, VimContext, VimLayer,
+	},
 	options::{Options, SharedOptions},
 	popup_stack::PopupStack,
 	popups::{
-		AppOption, BlameFilePopup, BranchListPopup, CommitPopup,
-		CompareCommitsPopup, ConfirmPopup, CreateBranchPopup,
-		CreateRemotePopup, ExternalEditorPopup, FetchPopup,
-		FileRevlogPopup, FuzzyFindPopup, HelpPopup,
-		InspectCommitPopup, LogSearchPopupPopup, MsgPopup,
-		OptionsPopup, PullPopup, PushPopup, PushTagsPopup,
-		RemoteListPopup, RenameBranchPopup, RenameRemotePopup,
-		ResetPopup, RevisionFilesPopup, StashMsgPopup,
-		SubmodulesListPopup, TagCommitPopup, TagListPopup,
-		UpdateRemoteUrlPopup,
+		AppOption, BlameFilePopup, BookmarkCommitPopup,
+		BookmarksPopup, BranchListPopup, CommitPopup,
+		CompareBranchesPopup, CompareCommitsPopup, CompareStashPopup,
+		ConfirmPopup, ConflictEditorPopup, CreateBranchPopup,
+		CreatePullRequestPopup, CreateRemotePopup,
+		CreateWorktreePopup, DeepenHistoryPopup, ExternalEditorPopup,
+		FetchPopup, FileGrepPopup, FileRevlogPopup, FixStagedPopup,
+		FixupPopup, FuzzyFindPopup, HelpPopup, IgnorePopup,
+		InspectCommitPopup, KeyConflictsPopup, LocalHistoryPopup,
+		LogFilterPopup, LogSearchPopupPopup, MergeBranchPopup,
+		MsgPopup, OpLogPopup, OptionsPopup, ProcessPopup, PullPopup,
+		PushPopup, PushTagsPopup, ReflogPopup, RemoteListPopup,
+		RenameBranchPopup, RenameRemotePopup, ReplaceRefsPopup,
+		ResetPopup, ResolveConflictPopup, RevisionFilesPopup,
+		StashMsgPopup, SubmodulesListPopup, TagCommitPopup,
+		TagListPopup, UpdateRemoteUrlPopup, WorktreesPopup,
 	},
 	queue::{
-		Action, AppTabs, InternalEvent, NeedsUpdate, Queue,
-		StackablePopupOpen,
+		Action, AppTabs, InternalEvent, NeedsUpdate, ProcessItem,
+		Queue, StackablePopupOpen,
 	},
 	setup_popups,
 	strings::{self, ellipsis_trim_start, order},
@@ -32,14 +41,16 @@ use crate::{
 	ui::style::{SharedTheme, Theme},
 	AsyncAppNotification, AsyncNotification,
 };
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Result};
 use asyncgit::{
 	sync::{
 		self,
+		diff::DiffOptions,
 		utils::{repo_work_dir, undo_last_commit},
-		RepoPath, RepoPathRef,
+		AmendPlan, CommitId, Deepen, RebaseState, RepoPath,
+		RepoPathRef, RepoState,
 	},
-	AsyncGitNotification, PushType,
+	AsyncGitNotification, PushForce, PushType,
 };
 use crossbeam_channel::Sender;
 use crossterm::event::{Event, KeyEvent};
@@ -48,56 +59,87 @@ use ratatui::{
 		Alignment, Constraint, Direction, Layout, Margin, Rect,
 	},
 	text::{Line, Span},
-	widgets::{Block, Borders, Paragraph, Tabs},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph, Tabs},
 	Frame,
 };
 use std::{
 	cell::{Cell, RefCell},
 	path::{Path, PathBuf},
 	rc::Rc,
+	time::{Duration, Instant},
 };
 use unicode_width::UnicodeWidthStr;
 
+/// how long a toast stays up
+const TOAST_DURATION: Duration = Duration::from_secs(2);
+
 #[derive(Clone)]
 pub enum QuitState {
 	None,
 	Close,
 	OpenSubmodule(RepoPath),
+	/// `--print-on-exit` value to print once the terminal is restored
+	Print(String),
 }
 
 /// the main app type
 pub struct App {
 	repo: RepoPathRef,
 	do_quit: QuitState,
+	toast: Option<(String, Instant)>,
+	/// only used with the vim mode option
+	vim: VimLayer,
 	help_popup: HelpPopup,
+	key_conflicts_popup: KeyConflictsPopup,
 	msg_popup: MsgPopup,
 	confirm_popup: ConfirmPopup,
 	commit_popup: CommitPopup,
+	process_popup: ProcessPopup,
 	blame_file_popup: BlameFilePopup,
 	file_revlog_popup: FileRevlogPopup,
 	stashmsg_popup: StashMsgPopup,
 	inspect_commit_popup: InspectCommitPopup,
 	compare_commits_popup: CompareCommitsPopup,
+	compare_branches_popup: CompareBranchesPopup,
 	external_editor_popup: ExternalEditorPopup,
 	revision_files_popup: RevisionFilesPopup,
 	fuzzy_find_popup: FuzzyFindPopup,
+	file_grep_popup: FileGrepPopup,
 	log_search_popup: LogSearchPopupPopup,
+	log_filter_popup: LogFilterPopup,
 	push_popup: PushPopup,
 	push_tags_popup: PushTagsPopup,
 	pull_popup: PullPopup,
 	fetch_popup: FetchPopup,
 	tag_commit_popup: TagCommitPopup,
+	bookmark_commit_popup: BookmarkCommitPopup,
 	create_branch_popup: CreateBranchPopup,
+	create_worktree_popup: CreateWorktreePopup,
+	create_pull_request_popup: CreatePullRequestPopup,
 	create_remote_popup: CreateRemotePopup,
 	rename_remote_popup: RenameRemotePopup,
+	deepen_history_popup: DeepenHistoryPopup,
 	update_remote_url_popup: UpdateRemoteUrlPopup,
 	remotes_popup: RemoteListPopup,
 	rename_branch_popup: RenameBranchPopup,
 	select_branch_popup: BranchListPopup,
 	options_popup: OptionsPopup,
 	submodule_popup: SubmodulesListPopup,
+	worktrees_popup: WorktreesPopup,
 	tags_popup: TagListPopup,
+	oplog_popup: OpLogPopup,
+	reflog_popup: ReflogPopup,
+	local_history_popup: LocalHistoryPopup,
+	compare_stash_popup: CompareStashPopup,
+	fix_staged_popup: FixStagedPopup,
+	conflict_editor_popup: ConflictEditorPopup,
+	bookmarks_popup: BookmarksPopup,
+	replace_refs_popup: ReplaceRefsPopup,
 	reset_popup: ResetPopup,
+	merge_branch_popup: MergeBranchPopup,
+	resolve_conflict_popup: ResolveConflictPopup,
+	ignore_popup: IgnorePopup,
+	fixup_popup: FixupPopup,
 	cmdbar: RefCell<CommandBar>,
 	tab: usize,
 	revlog: Revlog,
@@ -112,12 +154,44 @@ pub struct App {
 	popup_stack: PopupStack,
 	options: SharedOptions,
 	repo_path_text: String,
+	print_on_exit: Option<PrintOnExit>,
+	sequencer_state: SequencerState,
 
 	// "Flags"
 	requires_redraw: Cell<bool>,
 	file_to_open: Option<String>,
 }
 
+/// the merge, rebase, .. in progress as far as
+/// [`App::update_repo_state`] cares
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct SequencerState {
+	state: RepoState,
+	/// a rebase stopping again is a change, too
+	rebase_step: Option<usize>,
+	conflicted: bool,
+}
+
+impl SequencerState {
+	fn new(repo: &RepoPath) -> Self {
+		let state =
+			sync::repo_state(repo).unwrap_or(RepoState::Clean);
+		let rebase_step = (state == RepoState::Rebase)
+			.then(|| sync::rebase_progress(repo).ok())
+			.flatten()
+			.map(|progress| progress.current);
+		let conflicted = state != RepoState::Clean
+			&& sync::get_conflicts(repo)
+				.is_ok_and(|conflicts| !conflicts.is_empty());
+
+		Self {
+			state,
+			rebase_step,
+			conflicted,
+		}
+	}
+}
+
 pub struct Environment {
 	pub queue: Queue,
 	pub theme: SharedTheme,
@@ -156,11 +230,13 @@ impl App {
 		input: Input,
 		theme: Theme,
 		key_config: KeyConfig,
+		print_on_exit: Option<PrintOnExit>,
 	) -> Result<Self> {
 		log::trace!("open repo at: {:?}", &repo);
 
 		let repo_path_text =
 			repo_work_dir(&repo.borrow()).unwrap_or_default();
+		let sequencer_state = SequencerState::new(&repo.borrow());
 
 		let env = Environment {
 			queue: Queue::new(),
@@ -178,6 +254,7 @@ impl App {
 			input,
 			confirm_popup: ConfirmPopup::new(&env),
 			commit_popup: CommitPopup::new(&env),
+			process_popup: ProcessPopup::new(&env),
 			blame_file_popup: BlameFilePopup::new(
 				&env,
 				&strings::blame_title(&env.key_config),
@@ -187,31 +264,56 @@ impl App {
 			stashmsg_popup: StashMsgPopup::new(&env),
 			inspect_commit_popup: InspectCommitPopup::new(&env),
 			compare_commits_popup: CompareCommitsPopup::new(&env),
+			compare_branches_popup: CompareBranchesPopup::new(&env),
 			external_editor_popup: ExternalEditorPopup::new(&env),
 			push_popup: PushPopup::new(&env),
 			push_tags_popup: PushTagsPopup::new(&env),
 			reset_popup: ResetPopup::new(&env),
+			merge_branch_popup: MergeBranchPopup::new(&env),
+			resolve_conflict_popup: ResolveConflictPopup::new(&env),
+			ignore_popup: IgnorePopup::new(&env),
+			fixup_popup: FixupPopup::new(&env),
 			pull_popup: PullPopup::new(&env),
 			fetch_popup: FetchPopup::new(&env),
 			tag_commit_popup: TagCommitPopup::new(&env),
+			bookmark_commit_popup: BookmarkCommitPopup::new(&env),
 			create_branch_popup: CreateBranchPopup::new(&env),
+			create_worktree_popup: CreateWorktreePopup::new(&env),
+			create_pull_request_popup: CreatePullRequestPopup::new(
+				&env,
+			),
 			create_remote_popup: CreateRemotePopup::new(&env),
 			rename_remote_popup: RenameRemotePopup::new(&env),
+			deepen_history_popup: DeepenHistoryPopup::new(&env),
 			update_remote_url_popup: UpdateRemoteUrlPopup::new(&env),
 			remotes_popup: RemoteListPopup::new(&env),
 			rename_branch_popup: RenameBranchPopup::new(&env),
 			select_branch_popup: BranchListPopup::new(&env),
 			tags_popup: TagListPopup::new(&env),
+			oplog_popup: OpLogPopup::new(&env),
+			reflog_popup: ReflogPopup::new(&env),
+			local_history_popup: LocalHistoryPopup::new(&env),
+			compare_stash_popup: CompareStashPopup::new(&env),
+			fix_staged_popup: FixStagedPopup::new(&env),
+			conflict_editor_popup: ConflictEditorPopup::new(&env),
+			bookmarks_popup: BookmarksPopup::new(&env),
+			replace_refs_popup: ReplaceRefsPopup::new(&env),
 			options_popup: OptionsPopup::new(&env),
 			submodule_popup: SubmodulesListPopup::new(&env),
+			worktrees_popup: WorktreesPopup::new(&env),
 			log_search_popup: LogSearchPopupPopup::new(&env),
+			log_filter_popup: LogFilterPopup::new(&env),
 			fuzzy_find_popup: FuzzyFindPopup::new(&env),
+			file_grep_popup: FileGrepPopup::new(&env),
 			do_quit: QuitState::None,
+			toast: None,
+			vim: VimLayer::new(),
 			cmdbar: RefCell::new(CommandBar::new(
 				env.theme.clone(),
 				env.key_config.clone(),
 			)),
 			help_popup: HelpPopup::new(&env),
+			key_conflicts_popup: KeyConflictsPopup::new(&env),
 			msg_popup: MsgPopup::new(&env),
 			revlog: Revlog::new(&env),
 			status_tab: Status::new(&env),
@@ -227,10 +329,17 @@ impl App {
 			file_to_open: None,
 			repo: env.repo,
 			repo_path_text,
+			print_on_exit,
+			sequencer_state,
 			popup_stack: PopupStack::default(),
 		};
 
 		app.set_tab(tab)?;
+		app.local_history_popup.prune();
+
+		if app.key_conflicts_popup.has_issues() {
+			app.key_conflicts_popup.show()?;
+		}
 
 		Ok(app)
 	}
@@ -263,8 +372,13 @@ impl App {
 			self.revision_files_popup.is_visible()
 				|| self.inspect_commit_popup.is_visible()
 				|| self.compare_commits_popup.is_visible()
+				|| self.compare_branches_popup.is_visible()
 				|| self.blame_file_popup.is_visible()
-				|| self.file_revlog_popup.is_visible();
+				|| self.file_revlog_popup.is_visible()
+				|| self.local_history_popup.is_visible()
+				|| self.compare_stash_popup.is_visible()
+				|| self.fix_staged_popup.is_visible()
+				|| self.conflict_editor_popup.is_visible();
 
 		if !fullscreen_popup_open {
 			//TODO: macro because of generic draw call
@@ -279,10 +393,25 @@ impl App {
 		}
 
 		self.draw_popups(f)?;
+		self.draw_toast(f, chunks_main[1]);
 
 		Ok(())
 	}
 
+	/// drops the toast once it was up long enough, `true` if the
+	/// screen needs to be drawn again
+	pub fn expire_toast(&mut self) -> bool {
+		let expired =
+			self.toast.as_ref().is_some_and(|(_, shown)| {
+				shown.elapsed() >= TOAST_DURATION
+			});
+		if expired {
+			self.toast = None;
+		}
+
+		expired
+	}
+
 	///
 	pub fn event(&mut self, ev: InputEvent) -> Result<()> {
 		log::trace!("event: {:?}", ev);
@@ -294,75 +423,32 @@ impl App {
 
 			let mut flags = NeedsUpdate::empty();
 
-			if event_pump(&ev, self.components_mut().as_mut_slice())?
-				.is_consumed()
-			{
+			let events = self.vim_translate(&ev);
+			if events.is_empty() {
+				// the mode indicator changed
 				flags.insert(NeedsUpdate::COMMANDS);
-			} else if let Event::Key(k) = &ev {
-				let new_flags = if key_match(
-					k,
-					self.key_config.keys.tab_toggle,
-				) {
-					self.toggle_tabs(false)?;
-					NeedsUpdate::COMMANDS
-				} else if key_match(
-					k,
-					self.key_config.keys.tab_toggle_reverse,
-				) {
-					self.toggle_tabs(true)?;
-					NeedsUpdate::COMMANDS
-				} else if key_match(
-					k,
-					self.key_config.keys.tab_status,
-				) || key_match(
-					k,
-					self.key_config.keys.tab_log,
-				) || key_match(
-					k,
-					self.key_config.keys.tab_files,
-				) || key_match(
-					k,
-					self.key_config.keys.tab_stashing,
-				) || key_match(
-					k,
-					self.key_config.keys.tab_stashes,
-				) {
-					self.switch_tab(k)?;
-					NeedsUpdate::COMMANDS
-				} else if key_match(
-					k,
-					self.key_config.keys.cmd_bar_toggle,
-				) {
-					self.cmdbar.borrow_mut().toggle_more();
-					NeedsUpdate::empty()
-				} else if key_match(
-					k,
-					self.key_config.keys.open_options,
-				) {
-					self.options_popup.show()?;
-					NeedsUpdate::ALL
-				} else {
-					NeedsUpdate::empty()
-				};
-
-				flags.insert(new_flags);
+			}
+			for ev in events {
+				if !self.dispatch_event(&ev, &mut flags)? {
+					break;
+				}
 			}
 
 			self.process_queue(flags)?;
 		} else if let InputEvent::State(polling_state) = ev {
 			self.external_editor_popup.hide();
 			if matches!(polling_state, InputState::Paused) {
-				let result =
-					if let Some(path) = self.file_to_open.take() {
-						ExternalEditorPopup::open_file_in_editor(
-							&self.repo.borrow(),
-							Path::new(&path),
-						)
-					} else {
-						let changes =
-							self.status_tab.get_files_changes()?;
-						self.commit_popup.show_editor(changes)
-					};
+				let edited_file = self.file_to_open.take();
+				let result = if let Some(path) = &edited_file {
+					ExternalEditorPopup::open_file_in_editor(
+						&self.repo.borrow(),
+						Path::new(path),
+					)
+				} else {
+					let changes =
+						self.status_tab.get_files_changes()?;
+					self.commit_popup.show_editor(changes)
+				};
 
 				if let Err(e) = result {
 					let msg =
@@ -371,6 +457,11 @@ impl App {
 					self.msg_popup.show_error(msg.as_str())?;
 				}
 
+				// the file may have changed
+				if edited_file.is_some() {
+					self.update()?;
+				}
+
 				self.requires_redraw.set(true);
 				self.input.set_polling(true);
 			}
@@ -379,18 +470,219 @@ impl App {
 		Ok(())
 	}
 
+	/// hands `ev` to the tabs and popups, keys none of them took go
+	/// to `global_key_event`. `true` if a tab or popup took it
+	fn dispatch_event(
+		&mut self,
+		ev: &Event,
+		flags: &mut NeedsUpdate,
+	) -> Result<bool> {
+		let consumed = if self.diff_search_input_active() {
+			self.status_tab.event(ev)?
+		} else {
+			event_pump(ev, self.components_mut().as_mut_slice())?
+		};
+
+		if consumed.is_consumed() {
+			flags.insert(NeedsUpdate::COMMANDS);
+		} else if let Event::Key(k) = ev {
+			flags.insert(self.global_key_event(k)?);
+		}
+
+		Ok(consumed.is_consumed())
+	}
+
+	/// popups and inputs get the keys as they are typed (insert
+	/// mode), the tabs the ones the vim layer translates them to
+	fn vim_translate(&mut self, ev: &Event) -> Vec<Event> {
+		let Event::Key(key) = ev else {
+			return vec![ev.clone()];
+		};
+
+		if !self.options.borrow().vim_mode() {
+			return vec![ev.clone()];
+		}
+
+		if self.vim_insert_mode() {
+			self.vim.reset();
+			return vec![ev.clone()];
+		}
+
+		let keys = &self.key_config.keys;
+		let context = VimContext {
+			filter: match self.tab {
+				0 | 3 => Some(keys.diff_search),
+				1 => Some(keys.log_filter),
+				2 => Some(keys.file_find),
+				_ => None,
+			},
+			marking: match self.tab {
+				0 if self.status_tab.workdir_focused() => {
+					Some(keys.log_mark_commit)
+				}
+				1 | 4 => Some(keys.log_mark_commit),
+				_ => None,
+			},
+		};
+
+		self.vim
+			.translate(key, keys, context)
+			.into_iter()
+			.map(Event::Key)
+			.collect()
+	}
+
+	fn vim_insert_mode(&self) -> bool {
+		self.any_popup_visible() || self.diff_search_input_active()
+	}
+
+	/// keys no tab or popup took
+	fn global_key_event(
+		&mut self,
+		k: &KeyEvent,
+	) -> Result<NeedsUpdate> {
+		let flags = if key_match(k, self.key_config.keys.tab_toggle) {
+			self.toggle_tabs(false)?;
+			NeedsUpdate::COMMANDS
+		} else if key_match(
+			k,
+			self.key_config.keys.tab_toggle_reverse,
+		) {
+			self.toggle_tabs(true)?;
+			NeedsUpdate::COMMANDS
+		} else if key_match(k, self.key_config.keys.tab_status)
+			|| key_match(k, self.key_config.keys.tab_log)
+			|| key_match(k, self.key_config.keys.tab_files)
+			|| key_match(k, self.key_config.keys.tab_stashing)
+			|| key_match(k, self.key_config.keys.tab_stashes)
+		{
+			self.switch_tab(k)?;
+			NeedsUpdate::COMMANDS
+		} else if key_match(k, self.key_config.keys.cmd_bar_toggle) {
+			self.cmdbar.borrow_mut().toggle_more();
+			NeedsUpdate::empty()
+		} else if key_match(k, self.key_config.keys.open_options) {
+			self.options_popup.show()?;
+			NeedsUpdate::ALL
+		} else if key_match(k, self.key_config.keys.open_oplog) {
+			self.oplog_popup.open()?;
+			NeedsUpdate::ALL
+		} else if key_match(k, self.key_config.keys.open_reflog) {
+			self.reflog_popup.open()?;
+			NeedsUpdate::ALL
+		} else if key_match(k, self.key_config.keys.refresh) {
+			self.refresh_focused()?;
+			NeedsUpdate::COMMANDS
+		} else if key_match(k, self.key_config.keys.refresh_all) {
+			self.refresh_all()?;
+			NeedsUpdate::COMMANDS
+		} else {
+			NeedsUpdate::empty()
+		};
+
+		Ok(flags)
+	}
+
 	//TODO: do we need this?
 	/// forward ticking to components that require it
 	pub fn update(&mut self) -> Result<()> {
 		log::trace!("update");
 
-		self.commit_popup.update();
 		self.status_tab.update()?;
+		self.local_history_popup.update();
+		self.compare_stash_popup.update()?;
+		self.update_but_status()
+	}
+
+	/// only reads the data behind the focused view again
+	fn refresh_focused(&mut self) -> Result<()> {
+		let what = match self.tab {
+			0 => self.status_tab.refresh()?,
+			1 => {
+				self.revlog.refresh()?;
+				"log"
+			}
+			2 => {
+				self.files_tab.refresh()?;
+				"files"
+			}
+			3 => {
+				self.stashing_tab.update()?;
+				"status"
+			}
+			4 => {
+				self.stashlist_tab.update()?;
+				"stashes"
+			}
+			_ => bail!("unknown tab"),
+		};
+
+		self.queue
+			.push(InternalEvent::ShowToast(strings::refreshed(what)));
+
+		Ok(())
+	}
+
+	/// `update` after dropping what the log caches
+	fn refresh_all(&mut self) -> Result<()> {
+		self.revlog.invalidate()?;
+		self.update()?;
+
+		self.queue.push(InternalEvent::ShowToast(
+			strings::refreshed("everything"),
+		));
+
+		Ok(())
+	}
+
+	/// like `update` but keeps the cached working dir status
+	pub fn update_git_dir(&mut self) -> Result<()> {
+		log::trace!("update git dir");
+
+		self.status_tab.update_git_dir()?;
+		self.update_but_status()
+	}
+
+	/// a merge, rebase, .. started or moved on, also from outside of
+	/// gitui: once it stops on a conflict the status tab is focused
+	/// with the first conflicted file selected
+	pub fn update_repo_state(&mut self) -> Result<()> {
+		log::trace!("update repo state");
+
+		let state = SequencerState::new(&self.repo.borrow());
+		if state != self.sequencer_state {
+			self.sequencer_state = state;
+
+			if state.conflicted
+				&& self.options.borrow().focus_conflicts()
+			{
+				self.status_tab.focus_first_conflict();
+				self.set_tab(0)?;
+			}
+		}
+
+		self.update()
+	}
+
+	/// the commit popup shows the status tabs cached staged diff
+	fn update_commit_staged_diff(&mut self) -> Result<()> {
+		if self.commit_popup.wants_staged_diff() {
+			let diff = self.status_tab.staged_diff()?;
+			self.commit_popup.set_staged_diff(diff);
+		}
+
+		Ok(())
+	}
+
+	fn update_but_status(&mut self) -> Result<()> {
+		self.commit_popup.update();
+		self.revlog.update_bisect()?;
 		self.revlog.update()?;
 		self.files_tab.update()?;
 		self.stashing_tab.update()?;
 		self.stashlist_tab.update()?;
 		self.reset_popup.update()?;
+		self.reflog_popup.update();
 
 		self.update_commands();
 
@@ -411,11 +703,23 @@ impl App {
 			self.file_revlog_popup.update_git(ev)?;
 			self.inspect_commit_popup.update_git(ev)?;
 			self.compare_commits_popup.update_git(ev)?;
+			self.compare_branches_popup.update_git(ev);
 			self.push_popup.update_git(ev)?;
 			self.push_tags_popup.update_git(ev)?;
 			self.pull_popup.update_git(ev);
 			self.fetch_popup.update_git(ev);
 			self.select_branch_popup.update_git(ev)?;
+			if ev == AsyncGitNotification::Diff {
+				self.update_commit_staged_diff()?;
+			}
+			self.file_grep_popup.update_git(ev);
+			self.process_popup.update_git(ev);
+			self.fixup_popup.update_git(ev);
+			self.local_history_popup.update_git(ev)?;
+			self.compare_stash_popup.update_git(ev)?;
+			self.fix_staged_popup.update_git(ev)?;
+			self.reflog_popup.update_git(ev);
+			self.submodule_popup.update_git(ev)?;
 		}
 
 		self.files_tab.update_async(ev)?;
@@ -451,6 +755,9 @@ impl App {
 			|| self.file_revlog_popup.any_work_pending()
 			|| self.inspect_commit_popup.any_work_pending()
 			|| self.compare_commits_popup.any_work_pending()
+			|| self.compare_branches_popup.any_work_pending()
+			|| self.compare_stash_popup.any_work_pending()
+			|| self.fix_staged_popup.any_work_pending()
 			|| self.input.is_state_changing()
 			|| self.push_popup.any_work_pending()
 			|| self.push_tags_popup.any_work_pending()
@@ -458,6 +765,11 @@ impl App {
 			|| self.fetch_popup.any_work_pending()
 			|| self.revision_files_popup.any_work_pending()
 			|| self.tags_popup.any_work_pending()
+			|| self.file_grep_popup.any_work_pending()
+			|| self.process_popup.any_work_pending()
+			|| self.fixup_popup.any_work_pending()
+			|| self.reflog_popup.any_work_pending()
+			|| self.submodule_popup.any_work_pending()
 	}
 
 	///
@@ -477,25 +789,38 @@ impl App {
 		self,
 		[
 			log_search_popup,
+			log_filter_popup,
 			fuzzy_find_popup,
+			file_grep_popup,
 			msg_popup,
 			confirm_popup,
+			process_popup,
 			commit_popup,
 			blame_file_popup,
 			file_revlog_popup,
 			stashmsg_popup,
 			inspect_commit_popup,
 			compare_commits_popup,
+			compare_branches_popup,
 			external_editor_popup,
 			push_popup,
 			push_tags_popup,
 			pull_popup,
 			fetch_popup,
 			tag_commit_popup,
+			bookmark_commit_popup,
 			reset_popup,
+			merge_branch_popup,
+			resolve_conflict_popup,
+			ignore_popup,
+			fixup_popup,
+			worktrees_popup,
+			create_worktree_popup,
 			create_branch_popup,
+			create_pull_request_popup,
 			create_remote_popup,
 			rename_remote_popup,
+			deepen_history_popup,
 			update_remote_url_popup,
 			remotes_popup,
 			rename_branch_popup,
@@ -503,7 +828,16 @@ impl App {
 			revision_files_popup,
 			submodule_popup,
 			tags_popup,
+			oplog_popup,
+			reflog_popup,
+			local_history_popup,
+			compare_stash_popup,
+			fix_staged_popup,
+			conflict_editor_popup,
+			bookmarks_popup,
+			replace_refs_popup,
 			options_popup,
+			key_conflicts_popup,
 			help_popup,
 			revlog,
 			status_tab,
@@ -521,35 +855,58 @@ impl App {
 			help_popup,
 			inspect_commit_popup,
 			compare_commits_popup,
+			compare_branches_popup,
 			blame_file_popup,
 			file_revlog_popup,
+			local_history_popup,
+			compare_stash_popup,
+			fix_staged_popup,
+			conflict_editor_popup,
 			external_editor_popup,
 			tag_commit_popup,
+			bookmark_commit_popup,
 			select_branch_popup,
 			remotes_popup,
 			create_remote_popup,
 			rename_remote_popup,
+			deepen_history_popup,
 			update_remote_url_popup,
 			submodule_popup,
 			tags_popup,
+			oplog_popup,
+			reflog_popup,
+			bookmarks_popup,
+			replace_refs_popup,
 			reset_popup,
+			merge_branch_popup,
+			resolve_conflict_popup,
+			ignore_popup,
+			fixup_popup,
 			create_branch_popup,
+			create_worktree_popup,
+			worktrees_popup,
+			create_pull_request_popup,
 			rename_branch_popup,
 			revision_files_popup,
 			fuzzy_find_popup,
+			file_grep_popup,
 			log_search_popup,
+			log_filter_popup,
 			push_popup,
 			push_tags_popup,
 			pull_popup,
 			fetch_popup,
+			process_popup,
 			options_popup,
+			key_conflicts_popup,
 			confirm_popup,
 			msg_popup
 		]
 	);
 
 	fn check_quit(&mut self, ev: &Event) -> bool {
-		if self.any_popup_visible() {
+		if self.any_popup_visible() || self.diff_search_input_active()
+		{
 			return false;
 		}
 		if let Event::Key(e) = ev {
@@ -557,10 +914,81 @@ impl App {
 				self.do_quit = QuitState::Close;
 				return true;
 			}
+			if let Some(what) = self.print_on_exit {
+				if key_match(e, self.key_config.keys.quit_print) {
+					match self.print_on_exit_value(what) {
+						Ok(Some(value)) => {
+							self.do_quit = QuitState::Print(value);
+						}
+						Ok(None) => {
+							self.queue.push(InternalEvent::ShowErrorMsg(
+								strings::print_on_exit_nothing_selected(
+									what,
+								),
+							));
+						}
+						Err(e) => {
+							self.queue.push(
+								InternalEvent::ShowErrorMsg(
+									e.to_string(),
+								),
+							);
+						}
+					}
+					return true;
+				}
+			}
 		}
 		false
 	}
 
+	/// `None` if the current tab has nothing of `what` selected
+	fn print_on_exit_value(
+		&self,
+		what: PrintOnExit,
+	) -> Result<Option<String>> {
+		let repo = self.repo.borrow();
+
+		Ok(match what {
+			PrintOnExit::RepoRoot => Some(repo_work_dir(&repo)?),
+			PrintOnExit::File => {
+				let path = if self.status_tab.is_visible() {
+					self.status_tab
+						.selected_path()
+						.map(|(path, _)| path)
+				} else if self.files_tab.is_visible() {
+					self.files_tab.selected_file_path()
+				} else {
+					None
+				};
+
+				path.map(|path| {
+					Path::new(&repo_work_dir(&repo)?)
+						.join(path)
+						.to_str()
+						.map(String::from)
+						.ok_or_else(|| {
+							anyhow!("path is not valid utf8")
+						})
+				})
+				.transpose()?
+			}
+			PrintOnExit::Commit => if self.revlog.is_visible() {
+				self.revlog.selected_commit()
+			} else {
+				sync::get_head(&repo).ok()
+			}
+			.map(|id| id.to_string()),
+		})
+	}
+
+	/// the input is part of a tab, other components must not see
+	/// the keys typed into it
+	fn diff_search_input_active(&self) -> bool {
+		!self.any_popup_visible()
+			&& self.status_tab.diff_search_input_active()
+	}
+
 	fn check_hard_exit(&mut self, ev: &Event) -> bool {
 		if let Event::Key(e) = ev {
 			if key_match(e, self.key_config.keys.exit) {
@@ -620,6 +1048,8 @@ impl App {
 
 		self.tab = tab;
 		self.options.borrow_mut().set_current_tab(tab);
+		// a visual selection does not carry over to another view
+		self.vim.reset();
 
 		Ok(())
 	}
@@ -649,6 +1079,8 @@ impl App {
 
 		if flags.contains(NeedsUpdate::ALL) {
 			self.update()?;
+		} else if flags.contains(NeedsUpdate::INDEX) {
+			self.status_tab.update_index()?;
 		}
 		//TODO: make this a queue event?
 		//NOTE: set when any tree component changed selection
@@ -664,6 +1096,10 @@ impl App {
 		if flags.contains(NeedsUpdate::BRANCHES) {
 			self.select_branch_popup.update_branches()?;
 		}
+		if flags.intersects(NeedsUpdate::BRANCHES | NeedsUpdate::TAGS)
+		{
+			self.revlog.update_refs()?;
+		}
 		if flags.contains(NeedsUpdate::REMOTES) {
 			self.remotes_popup.update_remotes()?;
 		}
@@ -691,6 +1127,10 @@ impl App {
 			StackablePopupOpen::CompareCommits(param) => {
 				self.compare_commits_popup.open(param)?;
 			}
+			StackablePopupOpen::CompareBranches(param) => {
+				self.compare_branches_popup.open(param)?;
+			}
+			StackablePopupOpen::Reflog => self.reflog_popup.open()?,
 		}
 
 		Ok(())
@@ -712,6 +1152,162 @@ impl App {
 		Ok(flags)
 	}
 
+	/// opens the message of `id` for rewording, asks first if the
+	/// commit was pushed already
+	fn reword_commit(&mut self, id: CommitId) -> Result<()> {
+		sync::reword_check(&self.repo.borrow(), id)?;
+
+		if sync::is_commit_pushed(&self.repo.borrow(), id)? {
+			self.queue.push(InternalEvent::ConfirmAction(
+				Action::RewordPushedCommit(id),
+			));
+		} else {
+			self.commit_popup.open(Some(id))?;
+			self.update_commit_staged_diff()?;
+		}
+
+		Ok(())
+	}
+
+	/// the revert is committed from the commit popup, a conflict is
+	/// resolved in the status tab first
+	fn revert_commit(
+		&self,
+		id: CommitId,
+		mainline: Option<u32>,
+	) -> Result<()> {
+		let state =
+			sync::revert_commit(&self.repo.borrow(), id, mainline)?;
+
+		self.queue.push(InternalEvent::TabSwitchStatus);
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+
+		if state == RebaseState::Conflicted {
+			self.queue.push(InternalEvent::ShowInfoMsg(
+				strings::revert_conflicted(),
+			));
+		} else {
+			self.queue.push(InternalEvent::OpenCommit);
+		}
+
+		Ok(())
+	}
+
+	/// the forge's CLI creates it if it is set up, otherwise the page
+	/// to create it is opened in the browser
+	fn open_pull_request(&mut self) -> Result<()> {
+		let target = sync::pull_request_target(&self.repo.borrow())?;
+
+		if sync::pull_request_cli(&target).is_some() {
+			let (title, body) = sync::pull_request_message(
+				&self.repo.borrow(),
+				&target,
+			)?;
+			self.create_pull_request_popup
+				.open(target, title, body)?;
+		} else {
+			let url = target.new_pull_request_url();
+			crate::browser::open_url(&url)?;
+			self.queue.push(InternalEvent::ShowInfoMsg(
+				strings::pull_request_page_opened(&url),
+			));
+		}
+
+		Ok(())
+	}
+
+	/// the fetch output is shown while it runs, the diffs are loaded
+	/// again once it is done
+	fn fetch_missing_objects(&self, ids: &[String]) -> Result<()> {
+		let child =
+			sync::spawn_fetch_objects(&self.repo.borrow(), ids)?;
+
+		self.queue.push(InternalEvent::RunProcess(Box::new(
+			ProcessItem {
+				title: strings::title_fetch_objects(ids.len()),
+				child,
+				on_success: Some(InternalEvent::Update(
+					NeedsUpdate::DIFF,
+				)),
+			},
+		)));
+
+		Ok(())
+	}
+
+	/// the log walks the history again once it sees the clone got
+	/// deeper
+	fn deepen_history(&self, deepen: Deepen) -> Result<()> {
+		let child =
+			sync::spawn_deepen_history(&self.repo.borrow(), deepen)?;
+
+		self.queue.push(InternalEvent::RunProcess(Box::new(
+			ProcessItem {
+				title: strings::title_deepen_history(deepen),
+				child,
+				on_success: Some(InternalEvent::Update(
+					NeedsUpdate::ALL,
+				)),
+			},
+		)));
+
+		Ok(())
+	}
+
+	/// a conflict leaves the rebase to finish in the status tab
+	fn autosquash(&self, target: CommitId) -> Result<()> {
+		if sync::autosquash(&self.repo.borrow(), target)?
+			== RebaseState::Conflicted
+		{
+			self.queue.push(InternalEvent::ShowInfoMsg(
+				strings::autosquash_conflicted(),
+			));
+			self.queue.push(InternalEvent::TabSwitchStatus);
+		}
+
+		Ok(())
+	}
+
+	/// a conflict or the edit stop leaves the rebase to finish in the
+	/// status tab
+	fn amend_commit(&self, plan: &AmendPlan) -> Result<()> {
+		let repo = self.repo.borrow();
+
+		if plan.staged > 0 {
+			if sync::amend_older_commit(&repo, plan.target)?
+				== RebaseState::Conflicted
+			{
+				self.queue.push(InternalEvent::ShowInfoMsg(
+					strings::autosquash_conflicted(),
+				));
+				self.queue.push(InternalEvent::TabSwitchStatus);
+			}
+		} else {
+			sync::edit_commit(&repo, plan.target)?;
+			self.queue.push(InternalEvent::ShowInfoMsg(
+				strings::edit_commit_stopped(&plan.subject),
+			));
+			self.queue.push(InternalEvent::TabSwitchStatus);
+		}
+
+		Ok(())
+	}
+
+	/// the message of the undone commit is kept for the next commit
+	fn undo_last_commit(&mut self) -> Result<()> {
+		let repo = self.repo.borrow().clone();
+		let head = sync::get_head(&repo)?;
+		let msg = sync::get_commit_details(&repo, head)?
+			.message
+			.unwrap_or_default()
+			.combine();
+
+		undo_last_commit(&repo)?;
+		self.commit_popup.prefill_message(msg);
+
+		Ok(())
+	}
+
 	#[allow(clippy::too_many_lines)]
 	fn process_internal_event(
 		&mut self,
@@ -736,18 +1332,90 @@ impl App {
 				flags
 					.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS);
 			}
+			InternalEvent::ShowToast(msg) => {
+				self.toast = Some((msg, Instant::now()));
+			}
 			InternalEvent::Update(u) => flags.insert(u),
-			InternalEvent::OpenCommit => self.commit_popup.show()?,
+			InternalEvent::OpenCommit => {
+				self.commit_popup.show()?;
+				self.update_commit_staged_diff()?;
+			}
 			InternalEvent::RewordCommit(id) => {
-				self.commit_popup.open(Some(id))?;
+				try_or_popup!(
+					self,
+					"reword failed:",
+					self.reword_commit(id)
+				);
+			}
+			InternalEvent::RevertCommit(id, mainline) => {
+				try_or_popup!(
+					self,
+					"revert failed:",
+					self.revert_commit(id, mainline)
+				);
+			}
+			InternalEvent::RunProcess(item) => {
+				self.process_popup.open(*item)?;
+			}
+			InternalEvent::OpenPullRequest => {
+				try_or_popup!(
+					self,
+					"pull request error:",
+					self.open_pull_request()
+				);
+			}
+			InternalEvent::FetchMissingObjects(ids) => {
+				try_or_popup!(
+					self,
+					"fetching missing objects failed:",
+					self.fetch_missing_objects(&ids)
+				);
+			}
+			InternalEvent::OpenDeepenHistoryPopup => {
+				self.deepen_history_popup.open()?;
+			}
+			InternalEvent::DeepenHistory(deepen) => {
+				try_or_popup!(
+					self,
+					"deepening the history failed:",
+					self.deepen_history(deepen)
+				);
+			}
+			InternalEvent::PreCommitHookPassed => {
+				self.commit_popup.pre_commit_hook_passed()?;
+			}
+			InternalEvent::CommitMsgHookPassed => {
+				self.commit_popup.commit_msg_hook_passed()?;
+			}
+			InternalEvent::PrepareCommitMsgHookPassed => {
+				self.commit_popup.prepare_commit_msg_hook_passed()?;
 			}
 			InternalEvent::PopupStashing(opts) => {
 				self.stashmsg_popup.options(opts);
 				self.stashmsg_popup.show()?;
 			}
+			InternalEvent::PopupStashPaths(paths) => {
+				self.stashmsg_popup.open_paths(paths)?;
+			}
+			InternalEvent::BranchCommit(id) => {
+				self.create_branch_popup.open_at(Some(id))?;
+			}
+			InternalEvent::BranchFromStash(id) => {
+				self.create_branch_popup.open_stash(id)?;
+			}
 			InternalEvent::TagCommit(id) => {
 				self.tag_commit_popup.open(id)?;
 			}
+			InternalEvent::BookmarkCommit(id) => {
+				self.bookmark_commit_popup.open(id)?;
+			}
+			InternalEvent::ViewReplaceRefs => {
+				self.replace_refs_popup.open()?;
+				flags.insert(NeedsUpdate::COMMANDS);
+			}
+			InternalEvent::ViewBookmarks => {
+				self.bookmarks_popup.open()?;
+			}
 			InternalEvent::CreateRemote => {
 				self.create_remote_popup.open()?;
 			}
@@ -774,6 +1442,20 @@ impl App {
 			InternalEvent::ViewSubmodules => {
 				self.submodule_popup.open()?;
 			}
+			InternalEvent::ViewWorktrees => {
+				try_or_popup!(
+					self,
+					"worktrees error:",
+					self.worktrees_popup.open()
+				);
+			}
+			InternalEvent::CreateWorktree(branch) => {
+				try_or_popup!(
+					self,
+					"create worktree error:",
+					self.create_worktree_popup.open(branch)
+				);
+			}
 			InternalEvent::Tags => {
 				self.tags_popup.open()?;
 			}
@@ -789,9 +1471,23 @@ impl App {
 					));
 				} else {
 					self.tags_popup.hide();
+					self.bookmarks_popup.hide();
 					flags.insert(NeedsUpdate::ALL);
 				}
 			}
+			InternalEvent::OpenLocalHistory(path) => {
+				self.local_history_popup.open(path)?;
+				flags
+					.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS);
+			}
+			InternalEvent::OpenCompareStash(stash, base) => {
+				try_or_popup!(
+					self,
+					"compare stash error:",
+					self.compare_stash_popup.open(stash, base)
+				);
+				flags.insert(NeedsUpdate::COMMANDS);
+			}
 			InternalEvent::OpenExternalEditor(path) => {
 				self.input.set_polling(false);
 				self.external_editor_popup.show()?;
@@ -803,6 +1499,10 @@ impl App {
 					.push(branch, push_type, force, delete)?;
 				flags.insert(NeedsUpdate::ALL);
 			}
+			InternalEvent::PushAfterCreate(created) => {
+				self.push_popup.push_after_create(created);
+				flags.insert(NeedsUpdate::ALL);
+			}
 			InternalEvent::Pull(branch) => {
 				if let Err(error) = self.pull_popup.fetch(branch) {
 					self.queue.push(InternalEvent::ShowErrorMsg(
@@ -811,6 +1511,16 @@ impl App {
 				}
 				flags.insert(NeedsUpdate::ALL);
 			}
+			InternalEvent::PullRebase(branch) => {
+				if let Err(error) =
+					self.pull_popup.fetch_rebase(branch)
+				{
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						error.to_string(),
+					));
+				}
+				flags.insert(NeedsUpdate::ALL);
+			}
 			InternalEvent::FetchRemotes => {
 				if let Err(error) = self.fetch_popup.fetch() {
 					self.queue.push(InternalEvent::ShowErrorMsg(
@@ -831,6 +1541,28 @@ impl App {
 				flags
 					.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS);
 			}
+			InternalEvent::OpenFileGrep(commit) => {
+				self.file_grep_popup.open(commit)?;
+				flags
+					.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS);
+			}
+			InternalEvent::FileGrepChanged(path, line) => {
+				if self.revision_files_popup.is_visible() {
+					self.revision_files_popup
+						.file_grep_update(&path, line);
+				} else {
+					self.files_tab.file_grep_update(&path, line);
+				}
+
+				flags
+					.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS);
+			}
+			InternalEvent::OpenLogFilterPopup(criteria) => {
+				self.log_filter_popup.open(criteria)?;
+			}
+			InternalEvent::LogFilter(criteria) => {
+				self.revlog.filter(criteria)?;
+			}
 			InternalEvent::OpenLogSearchPopup => {
 				self.log_search_popup.open()?;
 				flags
@@ -838,14 +1570,25 @@ impl App {
 			}
 			InternalEvent::OptionSwitched(o) => {
 				match o {
-					AppOption::StatusShowUntracked => {
+					AppOption::StatusShowUntracked
+					| AppOption::StatusSubmoduleSections => {
 						self.status_tab.update()?;
 					}
+					AppOption::TreeInitialCollapse => {
+						self.status_tab.apply_initial_collapse();
+					}
 					AppOption::DiffContextLines
 					| AppOption::DiffIgnoreWhitespaces
 					| AppOption::DiffInterhunkLines => {
 						self.status_tab.update_diff()?;
 					}
+					AppOption::StatusFocusConflicts
+					| AppOption::DiffWordChanges
+					| AppOption::CommitVerbose
+					| AppOption::CommitBehindNotice
+					| AppOption::CommitHardWrap
+					| AppOption::LocalHistory => {}
+					AppOption::VimMode => self.vim.reset(),
 				}
 
 				flags.insert(NeedsUpdate::ALL);
@@ -872,6 +1615,19 @@ impl App {
 				flags
 					.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS);
 			}
+			InternalEvent::FuzzyFinderCancelled(target) => {
+				if matches!(target, FuzzyFinderTarget::Files) {
+					if self.revision_files_popup.is_visible() {
+						self.revision_files_popup
+							.file_finder_cancel();
+					} else {
+						self.files_tab.file_finder_cancel();
+					}
+				}
+
+				flags
+					.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS);
+			}
 			InternalEvent::OpenPopup(popup) => {
 				self.open_popup(popup)?;
 				flags
@@ -902,6 +1658,41 @@ impl App {
 			InternalEvent::OpenResetPopup(id) => {
 				self.reset_popup.open(id)?;
 			}
+			InternalEvent::OpenResetPopupReflog(id, selector) => {
+				self.reset_popup.open_reflog(id, selector)?;
+			}
+			InternalEvent::OpenMergeBranchPopup(
+				branch,
+				branch_type,
+			) => {
+				self.merge_branch_popup.open(branch, branch_type)?;
+			}
+			InternalEvent::OpenResolveConflictPopup(path) => {
+				self.resolve_conflict_popup.open(&path)?;
+			}
+			InternalEvent::OpenConflictEditor(path) => {
+				try_or_popup!(
+					self,
+					"conflict editor error:",
+					self.conflict_editor_popup.open(&path)
+				);
+			}
+			InternalEvent::OpenIgnorePopup(item) => {
+				self.ignore_popup.open(item)?;
+			}
+			InternalEvent::OpenFixupPopup => {
+				self.fixup_popup.open()?;
+			}
+			InternalEvent::OpenFixStaged => {
+				try_or_popup!(
+					self,
+					"fix staged error:",
+					self.fix_staged_popup.open()
+				);
+			}
+			InternalEvent::OpenKeyConflictsPopup => {
+				self.key_conflicts_popup.show()?;
+			}
 			InternalEvent::CommitSearch(options) => {
 				self.revlog.search(options);
 			}
@@ -910,6 +1701,7 @@ impl App {
 		Ok(flags)
 	}
 
+	#[allow(clippy::too_many_lines)]
 	fn process_confirmed_action(
 		&mut self,
 		action: Action,
@@ -919,6 +1711,9 @@ impl App {
 			Action::Reset(r) => {
 				self.status_tab.reset(&r);
 			}
+			Action::ResetFolder(r) => {
+				self.status_tab.reset_folder(&r);
+			}
 			Action::StashDrop(_) | Action::StashPop(_) => {
 				if let Err(e) = self
 					.stashlist_tab
@@ -929,20 +1724,19 @@ impl App {
 					));
 				}
 			}
-			Action::ResetHunk(path, hash) => {
-				sync::reset_hunk(
-					&self.repo.borrow(),
+			Action::ResetHunk(path, hash, diff_hash) => {
+				self.discard_changes(
 					&path,
-					hash,
-					Some(self.options.borrow().diff_options()),
-				)?;
+					diff_hash,
+					|repo, options| {
+						sync::reset_hunk(repo, &path, hash, options)
+					},
+				);
 			}
-			Action::ResetLines(path, lines) => {
-				sync::discard_lines(
-					&self.repo.borrow(),
-					&path,
-					&lines,
-				)?;
+			Action::ResetLines(path, lines, diff_hash) => {
+				self.discard_changes(&path, diff_hash, |repo, _| {
+					sync::discard_lines(repo, &path, &lines)
+				});
 			}
 			Action::DeleteLocalBranch(branch_ref) => {
 				if let Err(e) = sync::delete_branch(
@@ -964,14 +1758,37 @@ impl App {
 			}
 			Action::DeleteTag(tag_name) => {
 				self.delete_tag(tag_name)?;
+				flags.insert(NeedsUpdate::TAGS);
 			}
-			Action::DeleteRemoteTag(tag_name, _remote) => {
-				self.queue.push(InternalEvent::Push(
+			Action::DeleteRemoteTag(tag_name, remote) => {
+				self.push_popup.push_on_remote(
+					remote,
 					tag_name,
 					PushType::Tag,
-					false,
+					PushForce::FastForward,
 					true,
-				));
+				)?;
+			}
+			Action::PushTag(tag_name, remote) => {
+				self.push_popup.push_on_remote(
+					remote,
+					tag_name,
+					PushType::Tag,
+					PushForce::FastForward,
+					false,
+				)?;
+			}
+			Action::PushAllTags => {
+				self.push_tags_popup.push_tags()?;
+			}
+			Action::ForcePushTag(tag_name, remote) => {
+				self.push_popup.push_on_remote(
+					remote,
+					tag_name,
+					PushType::Tag,
+					PushForce::Force,
+					false,
+				)?;
 			}
 			Action::ForcePush(branch, force) => {
 				self.queue.push(InternalEvent::Push(
@@ -990,11 +1807,65 @@ impl App {
 			Action::AbortRebase => {
 				self.status_tab.abort_rebase();
 			}
-			Action::UndoCommit => {
+			Action::AbortCherryPick => {
+				self.status_tab.abort_cherry_pick();
+			}
+			Action::UndoCommit { .. } => {
 				try_or_popup!(
 					self,
 					"undo commit failed:",
-					undo_last_commit(&self.repo.borrow())
+					self.undo_last_commit()
+				);
+			}
+			Action::CommitOutsideScope { .. } => {
+				self.queue.push(InternalEvent::OpenCommit);
+			}
+			Action::RevertMerge(id) => {
+				self.queue
+					.push(InternalEvent::RevertCommit(id, Some(1)));
+			}
+			Action::RewordPushedCommit(id) => {
+				self.commit_popup.open(Some(id))?;
+				self.update_commit_staged_diff()?;
+			}
+			Action::ResetReflog { id, kind, .. } => {
+				try_or_popup!(
+					self,
+					"reset:",
+					sync::reset_repo(&self.repo.borrow(), id, kind)
+				);
+				self.queue
+					.push(InternalEvent::Update(NeedsUpdate::ALL));
+			}
+			Action::DeleteReplaceRef(original) => {
+				sync::delete_replace_ref(
+					&self.repo.borrow(),
+					original,
+				)?;
+				self.replace_refs_popup.update_entries()?;
+			}
+			Action::Autosquash { target, .. } => {
+				try_or_popup!(
+					self,
+					"autosquash failed:",
+					self.autosquash(target)
+				);
+			}
+			Action::AmendCommit(plan) => {
+				try_or_popup!(
+					self,
+					"amend failed:",
+					self.amend_commit(&plan)
+				);
+			}
+			Action::MarkResolved { path, .. } => {
+				try_or_popup!(
+					self,
+					"mark resolved failed:",
+					sync::stage_add_file(
+						&self.repo.borrow(),
+						Path::new(&path),
+					)
 				);
 			}
 		}
@@ -1027,28 +1898,43 @@ impl App {
 		&mut self,
 		branch_ref: &str,
 	) -> Result<()> {
-		self.queue.push(
-			//TODO: check if this is correct based on the fix in `c6abbaf`
-			branch_ref.rsplit('/').next().map_or_else(
-				|| {
-					InternalEvent::ShowErrorMsg(format!(
-						    "Failed to find the branch name in {branch_ref}"
-					    ))
-				},
-				|name| {
-					InternalEvent::Push(
-						name.to_string(),
-						PushType::Branch,
-						false,
-						true,
-					)
-				},
-			),
-		);
-
-		self.select_branch_popup.update_branches()?;
+		let (remote, branch) = sync::get_remote_branch_parts(
+			&self.repo.borrow(),
+			branch_ref,
+		)?;
+
+		// the branch list refreshes once the push is done
+		self.push_popup.push_on_remote(
+			remote,
+			branch,
+			PushType::Branch,
+			PushForce::FastForward,
+			true,
+		)
+	}
 
-		Ok(())
+	/// discards changes picked in the diff of `path`, unless the
+	/// file changed since that diff was loaded
+	fn discard_changes(
+		&self,
+		path: &str,
+		diff_hash: u64,
+		discard: impl FnOnce(
+			&RepoPath,
+			Option<DiffOptions>,
+		) -> asyncgit::Result<()>,
+	) {
+		let options = Some(self.options.borrow().diff_options());
+		let repo = self.repo.borrow();
+
+		if let Err(e) = sync::diff::diff_unchanged(
+			&repo, path, false, diff_hash, options,
+		)
+		.and_then(|()| discard(&repo, options))
+		{
+			self.queue
+				.push(InternalEvent::ShowErrorMsg(e.to_string()));
+		}
 	}
 
 	fn delete_remote(&self, remote_name: &str) {
@@ -1069,6 +1955,37 @@ impl App {
 		}
 	}
 
+	/// only listed in the help popup (and only with the vim mode
+	/// option), the quick bar keeps showing the configured keys
+	fn add_vim_commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) {
+		if !self.options.borrow().vim_mode() {
+			return;
+		}
+
+		let available = force_all || !self.vim_insert_mode();
+
+		out.extend(
+			[
+				strings::commands::vim_move(),
+				strings::commands::vim_top_bottom(),
+				strings::commands::vim_count(),
+				strings::commands::vim_tabs(),
+				strings::commands::vim_filter(),
+				strings::commands::vim_commands(),
+				strings::commands::vim_visual(),
+				strings::commands::vim_escape(),
+			]
+			.into_iter()
+			.map(|text| {
+				CommandInfo::new(text, true, available).hidden()
+			}),
+		);
+	}
+
 	fn commands(&self, force_all: bool) -> Vec<CommandInfo> {
 		let mut res = Vec::new();
 
@@ -1083,6 +2000,17 @@ impl App {
 				|| force_all,
 		));
 
+		res.push(CommandInfo::new(
+			strings::commands::grep_files(&self.key_config),
+			!self.file_grep_popup.is_visible(),
+			(!self.any_popup_visible()
+				&& self.files_tab.is_visible())
+				|| self.revision_files_popup.is_visible()
+				|| force_all,
+		));
+
+		self.add_vim_commands(&mut res, force_all);
+
 		res.push(
 			CommandInfo::new(
 				strings::commands::toggle_tabs(&self.key_config),
@@ -1109,6 +2037,39 @@ impl App {
 			)
 			.order(order::NAV),
 		);
+		res.push(
+			CommandInfo::new(
+				strings::commands::oplog_popup(&self.key_config),
+				true,
+				!self.any_popup_visible(),
+			)
+			.order(order::NAV),
+		);
+		res.push(
+			CommandInfo::new(
+				strings::commands::reflog_popup(&self.key_config),
+				true,
+				!self.any_popup_visible(),
+			)
+			.order(order::NAV),
+		);
+
+		res.push(
+			CommandInfo::new(
+				strings::commands::refresh(&self.key_config),
+				true,
+				!self.any_popup_visible(),
+			)
+			.order(order::NAV),
+		);
+		res.push(
+			CommandInfo::new(
+				strings::commands::refresh_all(&self.key_config),
+				true,
+				!self.any_popup_visible(),
+			)
+			.order(order::NAV),
+		);
 
 		res.push(
 			CommandInfo::new(
@@ -1119,9 +2080,56 @@ impl App {
 			.order(100),
 		);
 
+		if let Some(what) = self.print_on_exit {
+			res.push(
+				CommandInfo::new(
+					strings::commands::quit_print(
+						&self.key_config,
+						what,
+					),
+					true,
+					!self.any_popup_visible(),
+				)
+				.order(100),
+			);
+		}
+
 		res
 	}
 
+	/// bottom right of the main area, on top of everything
+	fn draw_toast(&self, f: &mut Frame, r: Rect) {
+		let Some((msg, _)) = &self.toast else {
+			return;
+		};
+
+		let width = u16::try_from(msg.width())
+			.unwrap_or(u16::MAX)
+			.saturating_add(4)
+			.min(r.width);
+		let height = 3.min(r.height);
+		let area = Rect::new(
+			r.right().saturating_sub(width),
+			r.bottom().saturating_sub(height),
+			width,
+			height,
+		);
+
+		f.render_widget(Clear, area);
+		f.render_widget(
+			Paragraph::new(msg.as_str())
+				.alignment(Alignment::Center)
+				.style(self.theme.text(true, false))
+				.block(
+					Block::default()
+						.borders(Borders::ALL)
+						.border_type(BorderType::Rounded)
+						.border_style(self.theme.block(false)),
+				),
+			area,
+		);
+	}
+
 	//TODO: make this dynamic
 	fn draw_top_bar(&self, f: &mut Frame, r: Rect) {
 		const DIVIDER_PAD_SPACES: usize = 2;
@@ -1180,15 +2188,41 @@ impl App {
 			table_area,
 		);
 
+		let mode = self.options.borrow().vim_mode().then(|| {
+			let mode = if self.vim_insert_mode() {
+				None
+			} else {
+				Some(self.vim.mode())
+			};
+			strings::vim_mode_indicator(mode, &self.vim.pending())
+		});
+		let bisect = self.revlog.bisect().map(strings::bisect_banner);
+		let prefix_width =
+			mode.as_ref().map_or(0, |mode| mode.width())
+				+ bisect.as_ref().map_or(0, |bisect| bisect.width());
+
+		let mut spans: Vec<Span> = bisect
+			.map(|bisect| {
+				Span::styled(bisect, self.theme.text_danger())
+			})
+			.into_iter()
+			.chain(mode.map(|mode| {
+				Span::styled(mode, self.theme.title(true))
+			}))
+			.collect();
+		spans.push(Span::styled(
+			ellipsis_trim_start(
+				&self.repo_path_text,
+				(text_area.width as usize)
+					.saturating_sub(prefix_width),
+				self.theme.glyphs().ellipsis,
+			),
+			self.theme.title(false),
+		));
+
 		f.render_widget(
-			Paragraph::new(Line::from(vec![Span::styled(
-				ellipsis_trim_start(
-					&self.repo_path_text,
-					text_area.width as usize,
-				),
-				self.theme.title(false),
-			)]))
-			.alignment(Alignment::Right),
+			Paragraph::new(Line::from(spans))
+				.alignment(Alignment::Right),
 			text_area,
 		);
 	}
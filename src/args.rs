@@ -1,9 +1,9 @@
-use crate::bug_report;
+use crate::{bug_report, ui::GlyphSet};
 use anyhow::{anyhow, Result};
 use asyncgit::sync::RepoPath;
 use clap::{
 	builder::ArgPredicate, crate_authors, crate_description,
-	crate_name, Arg, Command as ClapApp,
+	crate_name, Arg, ArgMatches, Command as ClapApp,
 };
 use simplelog::{Config, LevelFilter, WriteLogger};
 use std::{
@@ -16,6 +16,69 @@ pub struct CliArgs {
 	pub theme: PathBuf,
 	pub repo_path: RepoPath,
 	pub notify_watcher: bool,
+	pub mouse: bool,
+	pub glyphs: GlyphSet,
+	pub print_on_exit: Option<PrintOnExit>,
+}
+
+/// what `--print-on-exit` prints to stdout when quitting with
+/// `quit_print`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrintOnExit {
+	/// absolute path of the file selected in the status or files tab
+	File,
+	///
+	RepoRoot,
+	/// selected in the log, `HEAD` in the other tabs
+	Commit,
+}
+
+impl PrintOnExit {
+	const ALL: [Self; 3] = [Self::File, Self::RepoRoot, Self::Commit];
+
+	const fn name(self) -> &'static str {
+		match self {
+			Self::File => "file",
+			Self::RepoRoot => "repo-root",
+			Self::Commit => "commit",
+		}
+	}
+
+	fn parse(name: &str) -> Option<Self> {
+		Self::ALL.into_iter().find(|what| what.name() == name)
+	}
+
+	/// shell snippet using the printed value
+	fn example(self) -> String {
+		let print = format!("gitui --print-on-exit {}", self.name());
+		match self {
+			Self::File => {
+				format!("\"${{EDITOR:-vi}}\" \"$({print})\"")
+			}
+			Self::RepoRoot => format!("cd \"$({print})\""),
+			Self::Commit => format!("git show \"$({print})\""),
+		}
+	}
+}
+
+fn print_on_exit_arg(
+	arg_matches: &ArgMatches,
+) -> Option<PrintOnExit> {
+	arg_matches
+		.get_one::<String>("print_on_exit")
+		.and_then(|what| PrintOnExit::parse(what))
+}
+
+fn print_on_exit_help() -> String {
+	let examples = PrintOnExit::ALL
+		.into_iter()
+		.map(|what| format!("    {}", what.example()))
+		.collect::<Vec<_>>()
+		.join("\n");
+
+	format!(
+		"Print on exit: the ui is drawn to stderr and quitting with `Q` (the `quit_print` key) prints to stdout, e.g.\n{examples}"
+	)
 }
 
 pub fn process_cmdline() -> Result<CliArgs> {
@@ -55,10 +118,26 @@ pub fn process_cmdline() -> Result<CliArgs> {
 	let notify_watcher: bool =
 		*arg_matches.get_one("watcher").unwrap_or(&false);
 
+	let mouse = arg_matches.get_flag("mouse");
+
+	let print_on_exit = print_on_exit_arg(&arg_matches);
+
+	let glyphs = match arg_matches
+		.get_one::<String>("glyphs")
+		.map(String::as_str)
+	{
+		Some("unicode") => GlyphSet::Unicode,
+		Some("ascii") => GlyphSet::Ascii,
+		_ => GlyphSet::detect(),
+	};
+
 	Ok(CliArgs {
 		theme,
 		repo_path,
 		notify_watcher,
+		mouse,
+		glyphs,
+		print_on_exit,
 	})
 }
 
@@ -105,6 +184,30 @@ fn app() -> ClapApp {
 				.long("watcher")
 				.action(clap::ArgAction::SetTrue),
 		)
+		.arg(
+			Arg::new("mouse")
+				.help("Capture the mouse to scroll and click in the file trees. Selecting text with the mouse then needs the terminal's modifier (often shift).")
+				.long("mouse")
+				.action(clap::ArgAction::SetTrue),
+		)
+		.arg(
+			Arg::new("glyphs")
+				.help("Set of glyphs used for trees, markers, scrollbars, spinners and key hints (`auto` falls back to ascii if the locale is not UTF-8)")
+				.long("glyphs")
+				.value_name("GLYPHS")
+				.value_parser(["auto", "unicode", "ascii"])
+				.default_value("auto")
+				.num_args(1),
+		)
+		.arg(
+			Arg::new("print_on_exit")
+				.help("Draw to stderr and print the selected file, the repo root or the selected commit to stdout when quitting with the print key")
+				.long("print-on-exit")
+				.value_name("WHAT")
+				.value_parser(PrintOnExit::ALL.map(PrintOnExit::name))
+				.num_args(1),
+		)
+		.after_help(print_on_exit_help())
 		.arg(
 			Arg::new("bugreport")
 				.help("Generate a bug report")
@@ -138,7 +241,7 @@ fn setup_logging(path_override: Option<PathBuf>) -> Result<()> {
 		path
 	};
 
-	println!("Logging enabled. Log written to: {}", path.display());
+	eprintln!("Logging enabled. Log written to: {}", path.display());
 
 	WriteLogger::init(
 		LevelFilter::Trace,
@@ -174,3 +277,23 @@ pub fn get_app_config_path() -> Result<PathBuf> {
 fn verify_app() {
 	app().debug_assert();
 }
+
+#[test]
+fn test_print_on_exit_arg() {
+	let parse = |args: &[&str]| {
+		app()
+			.try_get_matches_from(args)
+			.map(|matches| print_on_exit_arg(&matches))
+	};
+
+	assert_eq!(parse(&["gitui"]).unwrap(), None);
+	for what in PrintOnExit::ALL {
+		assert_eq!(
+			parse(&["gitui", "--print-on-exit", what.name()])
+				.unwrap(),
+			Some(what)
+		);
+	}
+	assert!(parse(&["gitui", "--print-on-exit", "branch"]).is_err());
+	assert!(parse(&["gitui", "--print-on-exit"]).is_err());
+}
@@ -0,0 +1,30 @@
+use anyhow::{anyhow, Result};
+use std::process::{Command, Stdio};
+
+#[cfg(all(target_family = "unix", not(target_os = "macos")))]
+const OPENER: (&str, &[&str]) = ("xdg-open", &[]);
+
+#[cfg(target_os = "macos")]
+const OPENER: (&str, &[&str]) = ("open", &[]);
+
+// the empty title keeps `start` from taking the url for one
+#[cfg(windows)]
+const OPENER: (&str, &[&str]) = ("cmd", &["/C", "start", ""]);
+
+/// opens `url` in the default browser without waiting for it
+pub fn open_url(url: &str) -> Result<()> {
+	let (command, args) = OPENER;
+
+	let mut child = Command::new(command)
+		.args(args)
+		.arg(url)
+		.stdin(Stdio::null())
+		.stdout(Stdio::null())
+		.stderr(Stdio::null())
+		.spawn()
+		.map_err(|e| anyhow!("`{command}`: {e}"))?;
+
+	std::thread::spawn(move || child.wait());
+
+	Ok(())
+}
@@ -89,7 +89,7 @@ fn copy_string_wayland(text: &str) -> Result<()> {
 		return Ok(());
 	}
 
-	copy_string_osc52(text, &mut std::io::stdout())
+	copy_string_osc52(text, &mut crate::tty::output())
 }
 
 #[cfg(all(target_family = "unix", not(target_os = "macos")))]
@@ -111,7 +111,7 @@ fn copy_string_x(text: &str) -> Result<()> {
 		return Ok(());
 	}
 
-	copy_string_osc52(text, &mut std::io::stdout())
+	copy_string_osc52(text, &mut crate::tty::output())
 }
 
 #[cfg(all(target_family = "unix", not(target_os = "macos")))]
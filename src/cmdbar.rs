@@ -202,3 +202,56 @@ impl CommandBar {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		app::Environment,
+		components::{ChangesComponent, Component, DiffComponent},
+		keys::KeyConfig,
+		ui::{style::Theme, GlyphSet},
+	};
+	use ratatui::{backend::TestBackend, Terminal};
+	use std::rc::Rc;
+
+	fn render_cmds(glyphs: GlyphSet) -> String {
+		let mut env = Environment::test_env();
+		env.theme = Rc::new(Theme::default().with_glyphs(glyphs));
+		env.key_config = Rc::new(KeyConfig::with_glyphs(glyphs));
+
+		let mut cmds = Vec::new();
+		ChangesComponent::new(&env, "changes", true, true)
+			.commands(&mut cmds, true);
+		DiffComponent::new(&env, false).commands(&mut cmds, true);
+
+		let mut bar =
+			CommandBar::new(env.theme.clone(), env.key_config);
+		bar.set_cmds(cmds);
+		bar.refresh_width(80);
+		bar.toggle_more();
+
+		let area = Rect::new(0, 0, 80, bar.height());
+		let mut terminal =
+			Terminal::new(TestBackend::new(area.width, area.height))
+				.expect("Unable to set up terminal");
+		terminal.draw(|f| bar.draw(f, area)).expect("Draw failed");
+
+		let buffer = terminal.backend().buffer();
+		area.rows()
+			.flat_map(Rect::columns)
+			.map(|pos| buffer[pos].symbol())
+			.collect()
+	}
+
+	#[test]
+	fn test_ascii_key_hints() {
+		let unicode = render_cmds(GlyphSet::Unicode);
+		assert!(unicode.contains("[\u{2191}"), "{unicode}");
+
+		let ascii = render_cmds(GlyphSet::Ascii);
+		assert!(ascii.is_ascii(), "{ascii}");
+		assert!(ascii.contains("Nav [UpDown"), "{ascii}");
+		assert!(ascii.contains("Blame [S-B]"), "{ascii}");
+	}
+}
@@ -7,24 +7,45 @@ use crate::{
 	app::Environment,
 	components::{CommandInfo, Component, EventState},
 	keys::{key_match, SharedKeyConfig},
-	options::SharedOptions,
-	queue::{Action, InternalEvent, NeedsUpdate, Queue, ResetItem},
+	options::{InitialCollapse, SharedOptions},
+	queue::{
+		Action, IgnoreItem, InternalEvent, NeedsUpdate, Queue,
+		ResetFolderItem, ResetItem,
+	},
 	strings, try_or_popup,
 };
 use anyhow::Result;
 use asyncgit::{
-	sync::{self, RepoPathRef},
-	StatusItem, StatusItemType,
+	asyncjob::AsyncSingleJob,
+	sync::{self, RepoPath, RepoPathRef, StagePathsResult},
+	AsyncGitNotification, AsyncStagePathsJob, StatusItem,
+	StatusItemType,
 };
 use crossterm::event::Event;
 use ratatui::{layout::Rect, Frame};
 use std::path::Path;
 
+/// changes of a dirty submodule shown in a section of their own
+pub struct SubmoduleSection {
+	/// path of the submodule in the repo
+	pub path: String,
+	///
+	pub repo: RepoPath,
+	///
+	pub items: Vec<StatusItem>,
+}
+
 ///
 pub struct ChangesComponent {
 	repo: RepoPathRef,
 	files: StatusTreeComponent,
+	/// the submodule sections, in the order of their roots
+	submodules: Vec<SubmoduleSection>,
+	title: String,
 	is_working_dir: bool,
+	async_stage: AsyncSingleJob<AsyncStagePathsJob>,
+	/// the running job, `true` if it (un)stages everything
+	stage_job: Option<(AsyncStagePathsJob, bool)>,
 	queue: Queue,
 	key_config: SharedKeyConfig,
 	options: SharedOptions,
@@ -40,7 +61,11 @@ impl ChangesComponent {
 	) -> Self {
 		Self {
 			files: StatusTreeComponent::new(env, title, focus),
+			title: title.to_string(),
 			is_working_dir,
+			async_stage: AsyncSingleJob::new(env.sender_git.clone()),
+			stage_job: None,
+			submodules: Vec::new(),
 			queue: env.queue.clone(),
 			key_config: env.key_config.clone(),
 			options: env.options.clone(),
@@ -48,10 +73,193 @@ impl ChangesComponent {
 		}
 	}
 
-	///
-	pub fn set_items(&mut self, list: &[StatusItem]) -> Result<()> {
+	/// `submodules` get sections next to the one of the repo, without
+	/// any it is a plain tree
+	pub fn set_sections(
+		&mut self,
+		list: &[StatusItem],
+		submodules: Vec<SubmoduleSection>,
+	) -> Result<()> {
 		self.files.show()?;
-		self.files.update(list)?;
+
+		let options = self.options.borrow();
+		let policy = options.tree_initial_collapse();
+		let collapse_new = |folder: &str| {
+			policy.collapses(folder, options.tree_collapsed())
+		};
+
+		if submodules.is_empty() {
+			self.submodules.clear();
+			self.files.update_collapsing(list, collapse_new)?;
+		} else {
+			let roots = std::iter::once((
+				strings::STATUS_SECTION_REPO.to_string(),
+				list.to_vec(),
+			))
+			.chain(submodules.iter().map(|section| {
+				(
+					strings::status_section_submodule(&section.path),
+					section.items.clone(),
+				)
+			}))
+			.collect::<Vec<_>>();
+
+			self.submodules = submodules;
+			self.files.update_roots(&roots, collapse_new)?;
+		}
+
+		Ok(())
+	}
+
+	/// the submodule whose section the selection is in
+	pub fn selection_submodule(&self) -> Option<&SubmoduleSection> {
+		let root = self.selection()?.info.root?;
+		self.submodules.get(root.checked_sub(1)?)
+	}
+
+	/// the repo the selection belongs to
+	fn selection_repo(&self) -> RepoPath {
+		self.selection_submodule().map_or_else(
+			|| self.repo.borrow().clone(),
+			|section| section.repo.clone(),
+		)
+	}
+
+	/// collapses all folders like `tree_initial_collapse` does for
+	/// new ones, with `Remember` the current state is remembered
+	pub fn apply_initial_collapse(&mut self) {
+		match self.options.borrow().tree_initial_collapse() {
+			InitialCollapse::Expanded => {
+				self.files.expand_to_depth(usize::MAX);
+			}
+			InitialCollapse::Collapsed => {
+				self.files.expand_to_depth(0);
+			}
+			InitialCollapse::TopLevelOnly => {
+				self.files.expand_to_depth(1);
+			}
+			InitialCollapse::Remember => {}
+		}
+
+		self.remember_collapsed();
+	}
+
+	fn remember_collapsed(&self) {
+		if self.options.borrow().tree_initial_collapse()
+			== InitialCollapse::Remember
+		{
+			let (folders, collapsed) = self.files.collapse_state();
+			self.options
+				.borrow_mut()
+				.remember_tree_collapsed(&folders, &collapsed);
+		}
+	}
+
+	///
+	pub fn set_title(&mut self, title: String) {
+		self.title = title;
+		self.update_title();
+	}
+
+	/// shows how far (un)staging many paths got
+	fn update_title(&mut self) {
+		let progress = self
+			.stage_job
+			.as_ref()
+			.and_then(|_| self.async_stage.progress());
+
+		self.files.set_title(progress.map_or_else(
+			|| self.title.clone(),
+			|progress| {
+				strings::title_stage_paths_progress(
+					&self.title,
+					self.is_working_dir,
+					progress,
+					&self.key_config,
+				)
+			},
+		));
+	}
+
+	///
+	pub const fn any_work_pending(&self) -> bool {
+		self.stage_job.is_some()
+	}
+
+	///
+	pub fn update_git(&mut self, ev: AsyncGitNotification) {
+		if ev != AsyncGitNotification::StagePaths {
+			return;
+		}
+
+		if let Some((job, all)) = &self.stage_job {
+			if let Some(result) = job.result() {
+				let all = *all;
+				self.stage_job = None;
+				try_or_popup!(
+					self,
+					"staging error:",
+					self.stage_paths_done(result, all)
+				);
+			}
+		}
+
+		self.update_title();
+	}
+
+	/// (un)stages `paths` of `repo` in the background, one index write
+	/// for all
+	fn spawn_stage_paths(
+		&mut self,
+		repo: RepoPath,
+		paths: Vec<String>,
+		all: bool,
+	) {
+		if paths.is_empty() || self.stage_job.is_some() {
+			return;
+		}
+
+		let job =
+			AsyncStagePathsJob::new(repo, paths, self.is_working_dir);
+		self.async_stage.spawn(job.clone());
+		self.stage_job = Some((job, all));
+		self.update_title();
+	}
+
+	fn stage_paths_done(
+		&self,
+		result: asyncgit::Result<StagePathsResult>,
+		all: bool,
+	) -> Result<()> {
+		self.queue.push(InternalEvent::Update(NeedsUpdate::INDEX));
+
+		let result = result?;
+		if result.cancelled {
+			return Ok(());
+		}
+
+		self.report_skipped(
+			if self.is_working_dir {
+				"stage"
+			} else {
+				"unstage"
+			},
+			&result.skipped,
+		);
+
+		//TODO: this might be slow in big repos,
+		// in theory we should be able to ask the tree structure
+		// if we are currently on a leaf or a lonely branch that
+		// would mean that after staging the workdir becomes empty
+		if all
+			|| (self.is_working_dir
+				&& sync::is_workdir_clean(
+					&self.repo.borrow(),
+					self.options.borrow().status_show_untracked(),
+				)?) {
+			self.queue.push(InternalEvent::StatusLastFileMoved);
+		}
+
 		Ok(())
 	}
 
@@ -60,6 +268,13 @@ impl ChangesComponent {
 		self.files.selection()
 	}
 
+	/// `false` if nothing is conflicted
+	pub fn select_first_conflict(&mut self) -> bool {
+		self.files.select_first_file(|item| {
+			item.status == StatusItemType::Conflicted
+		})
+	}
+
 	///
 	pub fn focus_select(&mut self, focus: bool) {
 		self.files.focus(focus);
@@ -76,39 +291,27 @@ impl ChangesComponent {
 		self.files.is_file_selected()
 	}
 
-	fn index_add_remove(&self) -> Result<bool> {
+	fn index_add_remove(&mut self) -> Result<bool> {
 		if let Some(tree_item) = self.selection() {
+			let repo = self.selection_repo();
 			if self.is_working_dir {
 				if let FileTreeItemKind::File(i) = tree_item.kind {
 					let path = Path::new(i.path.as_str());
 					match i.status {
 						StatusItemType::Deleted => {
-							sync::stage_addremoved(
-								&self.repo.borrow(),
-								path,
-							)?;
+							sync::stage_addremoved(&repo, path)?;
 						}
-						_ => sync::stage_add_file(
-							&self.repo.borrow(),
-							path,
-						)?,
+						_ => sync::stage_add_file(&repo, path)?,
 					}
 				} else {
-					let config =
-						self.options.borrow().status_show_untracked();
-
-					//TODO: check if we can handle the one file case with it as well
-					sync::stage_add_all(
-						&self.repo.borrow(),
-						tree_item.info.full_path.as_str(),
-						config,
-					)?;
+					self.spawn_stage_paths(
+						repo,
+						self.selected_paths(),
+						false,
+					);
+					return Ok(true);
 				}
 
-				//TODO: this might be slow in big repos,
-				// in theory we should be able to ask the tree structure
-				// if we are currently on a leaf or a lonely branch that
-				// would mean that after staging the workdir becomes empty
 				if sync::is_workdir_clean(
 					&self.repo.borrow(),
 					self.options.borrow().status_show_untracked(),
@@ -116,10 +319,15 @@ impl ChangesComponent {
 					self.queue
 						.push(InternalEvent::StatusLastFileMoved);
 				}
-			} else {
+			} else if let FileTreeItemKind::File(i) = tree_item.kind {
 				// this is a staged entry, so lets unstage it
-				let path = tree_item.info.full_path.as_str();
-				sync::reset_stage(&self.repo.borrow(), path)?;
+				sync::reset_stage(&repo, &i.path)?;
+			} else {
+				self.spawn_stage_paths(
+					repo,
+					self.selected_paths(),
+					false,
+				);
 			}
 
 			return Ok(true);
@@ -128,31 +336,122 @@ impl ChangesComponent {
 		Ok(false)
 	}
 
-	fn index_add_all(&self) -> Result<()> {
-		let config = self.options.borrow().status_show_untracked();
+	fn report_skipped(&self, action: &str, skipped: &[String]) {
+		if !skipped.is_empty() {
+			self.queue.push(InternalEvent::ShowInfoMsg(
+				strings::folder_conflicts_skipped(action, skipped),
+			));
+		}
+	}
 
-		sync::stage_add_all(&self.repo.borrow(), "*", config)?;
+	fn selected_paths(&self) -> Vec<String> {
+		self.files
+			.selection_files()
+			.into_iter()
+			.map(|file| file.path)
+			.collect()
+	}
 
-		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+	/// everything of the section the selection is in
+	fn stage_all(&mut self) {
+		let paths = self
+			.files
+			.selection_root_files()
+			.unwrap_or_else(|| self.files.all_files())
+			.into_iter()
+			.map(|file| file.path)
+			.collect();
+
+		self.spawn_stage_paths(self.selection_repo(), paths, true);
+	}
 
-		Ok(())
+	fn dispatch_reset_workdir(&self) -> bool {
+		if let Some(tree_item) = self.selection() {
+			let submodule = self
+				.selection_submodule()
+				.map(|section| section.repo.clone());
+			let action =
+				if let FileTreeItemKind::Path(_) = tree_item.kind {
+					// collapsed untracked folders count as one entry
+					let files =
+						match sync::status::expand_untracked_folders(
+							&self.selection_repo(),
+							self.files.selection_files(),
+						) {
+							Ok(files) => files,
+							Err(e) => {
+								self.queue.push(
+									InternalEvent::ShowErrorMsg(
+										format!("reset error:\n{e}"),
+									),
+								);
+								return true;
+							}
+						};
+					let (untracked, modified): (Vec<_>, Vec<_>) =
+						files.into_iter().partition(|file| {
+							file.status == StatusItemType::New
+						});
+
+					Action::ResetFolder(ResetFolderItem {
+						path: tree_item.info.full_path,
+						modified: modified
+							.into_iter()
+							.map(|file| file.path)
+							.collect(),
+						untracked: untracked
+							.into_iter()
+							.map(|file| file.path)
+							.collect(),
+						submodule,
+					})
+				} else {
+					Action::Reset(ResetItem {
+						path: tree_item.info.path_in_root().into(),
+						submodule,
+					})
+				};
+
+			self.queue.push(InternalEvent::ConfirmAction(action));
+
+			return true;
+		}
+		false
 	}
 
-	fn stage_remove_all(&self) -> Result<()> {
-		sync::reset_stage(&self.repo.borrow(), "*")?;
+	/// files of the selection belong to the repo, not a submodule
+	fn selection_in_repo(&self) -> bool {
+		self.selection_submodule().is_none()
+	}
 
-		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+	/// a conflicted file or a folder with conflicts in it
+	fn selected_conflict(&self) -> Option<String> {
+		let tree_item = self.selection()?;
+		if !self.selection_in_repo() {
+			return None;
+		}
 
-		Ok(())
+		match tree_item.kind {
+			FileTreeItemKind::File(i) if i.conflict.is_some() => {
+				Some(i.path)
+			}
+			FileTreeItemKind::Path(_)
+				if self
+					.files
+					.selection_files()
+					.iter()
+					.any(|file| file.conflict.is_some()) =>
+			{
+				Some(tree_item.info.path_in_root().into())
+			}
+			_ => None,
+		}
 	}
 
-	fn dispatch_reset_workdir(&self) -> bool {
-		if let Some(tree_item) = self.selection() {
-			self.queue.push(InternalEvent::ConfirmAction(
-				Action::Reset(ResetItem {
-					path: tree_item.info.full_path,
-				}),
-			));
+	fn dispatch_resolve_conflict(&self) -> bool {
+		if let Some(path) = self.selected_conflict() {
+			self.queue
+				.push(InternalEvent::OpenResolveConflictPopup(path));
 
 			return true;
 		}
@@ -161,22 +460,29 @@ impl ChangesComponent {
 
 	fn add_to_ignore(&self) -> bool {
 		if let Some(tree_item) = self.selection() {
-			if let Err(e) = sync::add_to_ignore(
-				&self.repo.borrow(),
-				&tree_item.info.full_path,
-			) {
-				self.queue.push(InternalEvent::ShowErrorMsg(
-					format!(
-						"ignore error:\n{}\nfile:\n{:?}",
-						e, tree_item.info.full_path
-					),
-				));
-			} else {
-				self.queue
-					.push(InternalEvent::Update(NeedsUpdate::ALL));
-
-				return true;
+			if !self.selection_in_repo() || tree_item.info.is_root() {
+				return false;
 			}
+
+			let path = String::from(tree_item.info.path_in_root());
+			let is_dir =
+				matches!(tree_item.kind, FileTreeItemKind::Path(_))
+					|| path.ends_with('/');
+			let tracked = self
+				.files
+				.selection_files()
+				.iter()
+				.any(|file| file.status != StatusItemType::New);
+
+			self.queue.push(InternalEvent::OpenIgnorePopup(
+				IgnoreItem {
+					path,
+					is_dir,
+					tracked,
+				},
+			));
+
+			return true;
 		}
 
 		false
@@ -201,6 +507,12 @@ impl Component for ChangesComponent {
 
 		let some_selection = self.selection().is_some();
 
+		out.push(CommandInfo::new(
+			strings::commands::cancel_stage_paths(&self.key_config),
+			true,
+			self.stage_job.is_some() && self.focused(),
+		));
+
 		if self.is_working_dir {
 			out.push(CommandInfo::new(
 				strings::commands::stage_all(&self.key_config),
@@ -220,7 +532,34 @@ impl Component for ChangesComponent {
 			out.push(CommandInfo::new(
 				strings::commands::ignore_item(&self.key_config),
 				true,
-				some_selection && self.focused(),
+				some_selection
+					&& self.selection_in_repo()
+					&& self.focused(),
+			));
+			out.push(CommandInfo::new(
+				strings::commands::resolve_conflict(&self.key_config),
+				true,
+				self.selected_conflict().is_some() && self.focused(),
+			));
+			out.push(CommandInfo::new(
+				strings::commands::mark_file(
+					&self.key_config,
+					self.files.selection_marked(),
+				),
+				true,
+				some_selection
+					&& self.selection_in_repo()
+					&& self.focused(),
+			));
+			out.push(CommandInfo::new(
+				strings::commands::stash_selected(
+					&self.key_config,
+					self.files.marked_count(),
+				),
+				true,
+				some_selection
+					&& self.selection_in_repo()
+					&& self.focused(),
 			));
 		} else {
 			out.push(CommandInfo::new(
@@ -233,18 +572,51 @@ impl Component for ChangesComponent {
 				true,
 				some_selection && self.focused(),
 			));
+			out.push(CommandInfo::new(
+				strings::commands::fixup_commit(&self.key_config),
+				true,
+				some_selection && self.focused(),
+			));
+			out.push(CommandInfo::new(
+				strings::commands::fix_staged(&self.key_config),
+				true,
+				some_selection && self.focused(),
+			));
 		}
 
 		CommandBlocking::PassingOn
 	}
 
+	#[allow(clippy::too_many_lines)]
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.files.event(ev)?.is_consumed() {
+			self.remember_collapsed();
 			return Ok(EventState::Consumed);
 		}
 
 		if self.focused() {
 			if let Event::Key(e) = ev {
+				if let Some((job, _)) = &self.stage_job {
+					if key_match(e, self.key_config.keys.exit_popup) {
+						job.cancel();
+						return Ok(EventState::Consumed);
+					}
+
+					// the index is written once the job is done
+					if key_match(
+						e,
+						self.key_config.keys.stage_unstage_item,
+					) || key_match(
+						e,
+						self.key_config.keys.status_stage_all,
+					) || key_match(
+						e,
+						self.key_config.keys.status_reset_item,
+					) {
+						return Ok(EventState::Consumed);
+					}
+				}
+
 				return if key_match(
 					e,
 					self.key_config.keys.stage_unstage_item,
@@ -256,7 +628,7 @@ impl Component for ChangesComponent {
 					);
 
 					self.queue.push(InternalEvent::Update(
-						NeedsUpdate::ALL,
+						NeedsUpdate::INDEX,
 					));
 					Ok(EventState::Consumed)
 				} else if key_match(
@@ -264,17 +636,7 @@ impl Component for ChangesComponent {
 					self.key_config.keys.status_stage_all,
 				) && !self.is_empty()
 				{
-					if self.is_working_dir {
-						try_or_popup!(
-							self,
-							"staging all error:",
-							self.index_add_all()
-						);
-					} else {
-						self.stage_remove_all()?;
-					}
-					self.queue
-						.push(InternalEvent::StatusLastFileMoved);
+					self.stage_all();
 					Ok(EventState::Consumed)
 				} else if key_match(
 					e,
@@ -289,6 +651,52 @@ impl Component for ChangesComponent {
 					&& !self.is_empty()
 				{
 					Ok(self.add_to_ignore().into())
+				} else if key_match(
+					e,
+					self.key_config.keys.status_resolve_conflict,
+				) && self.is_working_dir
+				{
+					Ok(self.dispatch_resolve_conflict().into())
+				} else if key_match(
+					e,
+					self.key_config.keys.status_stash_selected,
+				) && self.is_working_dir
+					&& !self.is_empty()
+					&& self.selection_in_repo()
+				{
+					self.queue.push(InternalEvent::PopupStashPaths(
+						self.files
+							.marked_or_selection_files()
+							.into_iter()
+							.map(|file| file.path)
+							.collect(),
+					));
+					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.log_mark_commit,
+				) && self.is_working_dir
+					&& !self.is_empty()
+					&& self.selection_in_repo()
+				{
+					self.files.toggle_mark();
+					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.status_fixup,
+				) && !self.is_working_dir
+					&& !self.is_empty()
+				{
+					self.queue.push(InternalEvent::OpenFixupPopup);
+					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.status_fix_staged,
+				) && !self.is_working_dir
+					&& !self.is_empty()
+				{
+					self.queue.push(InternalEvent::OpenFixStaged);
+					Ok(EventState::Consumed)
 				} else {
 					Ok(EventState::NotConsumed)
 				};
@@ -319,3 +727,98 @@ impl Component for ChangesComponent {
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use asyncgit::sync::status::{get_status, StatusType};
+	use std::{fs, process::Command};
+	use tempfile::TempDir;
+
+	fn git(path: &Path, args: &[&str]) {
+		let output = Command::new("git")
+			.args(["-c", "user.name=name", "-c", "user.email=email"])
+			.args(["-c", "protocol.file.allow=always"])
+			.args(args)
+			.current_dir(path)
+			.output()
+			.unwrap();
+		assert!(output.status.success());
+	}
+
+	fn commit_file(path: &Path, file: &str) {
+		fs::create_dir_all(path).unwrap();
+		git(path, &["init", "-q"]);
+		fs::write(path.join(file), file).unwrap();
+		git(path, &["add", file]);
+		git(path, &["commit", "-q", "-m", file]);
+	}
+
+	#[test]
+	fn test_submodule_section_routing() {
+		let td = TempDir::new().unwrap();
+		let main = td.path().join("main");
+		commit_file(&td.path().join("sub"), "a");
+		commit_file(&main, "b");
+		git(&main, &["submodule", "add", "-q", "../sub", "sub"]);
+		git(&main, &["commit", "-q", "-m", "sub"]);
+		fs::write(main.join("sub/a"), "changed").unwrap();
+
+		let env = Environment::test_env();
+		*env.repo.borrow_mut() = RepoPath::Path(main.clone());
+		let sub = RepoPath::Path(main.join("sub"));
+
+		let mut changes = ChangesComponent::new(&env, "", true, true);
+		changes
+			.set_sections(
+				&[],
+				vec![SubmoduleSection {
+					path: String::from("sub"),
+					repo: sub.clone(),
+					items: get_status(
+						&sub,
+						StatusType::WorkingDir,
+						None,
+					)
+					.unwrap(),
+				}],
+			)
+			.unwrap();
+
+		//0 <repo>/
+		//1 <submodule sub>/
+		//2   a
+
+		assert_eq!(
+			changes.selection().map(|item| item.info.full_path),
+			Some(String::from("<submodule sub>/a"))
+		);
+		assert!(changes.selection_submodule().is_some());
+
+		assert!(changes.dispatch_reset_workdir());
+		assert!(matches!(
+			env.queue.pop(),
+			Some(InternalEvent::ConfirmAction(Action::Reset(ResetItem {
+				path,
+				submodule: Some(_),
+			}))) if path == "a"
+		));
+
+		assert!(changes.index_add_remove().unwrap());
+		assert_eq!(
+			get_status(&sub, StatusType::Stage, None)
+				.unwrap()
+				.into_iter()
+				.map(|item| item.path)
+				.collect::<Vec<_>>(),
+			vec![String::from("a")]
+		);
+		assert!(get_status(
+			&RepoPath::Path(main),
+			StatusType::Stage,
+			None
+		)
+		.unwrap()
+		.is_empty());
+	}
+}
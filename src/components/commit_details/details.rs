@@ -3,19 +3,25 @@ use crate::{
 	components::{
 		commit_details::style::style_detail,
 		dialog_paragraph,
-		utils::{scroll_vertical::VerticalScroll, time_to_string},
+		utils::{
+			copy_commits, scroll_vertical::VerticalScroll,
+			time_to_string, CommitCopy,
+		},
 		CommandBlocking, CommandInfo, Component, DrawableComponent,
 		EventState, ScrollType,
 	},
 	keys::{key_match, SharedKeyConfig},
+	options::SharedOptions,
+	queue::Queue,
 	strings::{self, order},
+	try_or_popup,
 	ui::style::SharedTheme,
 };
 use anyhow::Result;
 use asyncgit::sync::{
 	self, CommitDetails, CommitId, CommitMessage, RepoPathRef, Tag,
 };
-use crossterm::event::Event;
+use crossterm::event::{Event, KeyEvent};
 use ratatui::{
 	layout::{Constraint, Direction, Layout, Rect},
 	style::{Modifier, Style},
@@ -37,6 +43,8 @@ pub struct DetailsComponent {
 	scroll: VerticalScroll,
 	scroll_to_bottom_next_draw: Cell<bool>,
 	key_config: SharedKeyConfig,
+	options: SharedOptions,
+	queue: Queue,
 }
 
 type WrappedCommitMessage<'a> =
@@ -55,6 +63,8 @@ impl DetailsComponent {
 			current_width: Cell::new(0),
 			scroll: VerticalScroll::new(),
 			key_config: env.key_config.clone(),
+			options: env.options.clone(),
+			queue: env.queue.clone(),
 		}
 	}
 
@@ -76,6 +86,36 @@ impl DetailsComponent {
 		}
 	}
 
+	fn copy_key(&self, key: &KeyEvent) -> Option<CommitCopy> {
+		if key_match(key, self.key_config.keys.copy) {
+			Some(CommitCopy::Hash)
+		} else if key_match(key, self.key_config.keys.copy_short_hash)
+		{
+			Some(CommitCopy::ShortHash)
+		} else if key_match(
+			key,
+			self.key_config.keys.copy_commit_summary,
+		) {
+			Some(CommitCopy::Summary)
+		} else {
+			None
+		}
+	}
+
+	fn copy(&self, what: CommitCopy) -> Result<()> {
+		if let Some(data) = &self.data {
+			copy_commits(
+				&self.queue,
+				&self.repo.borrow(),
+				&[CommitId::from_str_unchecked(&data.hash)?],
+				what,
+				&self.options.borrow().copy_commit_format(),
+			)?;
+		}
+
+		Ok(())
+	}
+
 	fn wrap_commit_details(
 		message: &CommitMessage,
 		width: usize,
@@ -255,9 +295,6 @@ impl DetailsComponent {
 
 impl DrawableComponent for DetailsComponent {
 	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
-		const CANSCROLL_STRING: &str = "[\u{2026}]";
-		const EMPTY_STRING: &str = "";
-
 		let chunks = Layout::default()
 			.direction(Direction::Vertical)
 			.constraints(
@@ -311,9 +348,9 @@ impl DrawableComponent for DetailsComponent {
 						&self.key_config,
 					),
 					if !self.focused && can_scroll {
-						CANSCROLL_STRING
+						format!("[{}]", self.theme.glyphs().ellipsis)
 					} else {
-						EMPTY_STRING
+						String::new()
 					}
 				),
 				Text::from(self.get_wrapped_text_message(
@@ -355,12 +392,37 @@ impl Component for DetailsComponent {
 			.order(order::NAV),
 		);
 
+		out.push(CommandInfo::new(
+			strings::commands::copy_hash(&self.key_config),
+			self.data.is_some(),
+			self.focused || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::copy_short_hash(&self.key_config),
+			self.data.is_some(),
+			self.focused || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::copy_commit_summary(&self.key_config),
+			self.data.is_some(),
+			self.focused || force_all,
+		));
+
 		CommandBlocking::PassingOn
 	}
 
 	fn event(&mut self, event: &Event) -> Result<EventState> {
 		if self.focused {
 			if let Event::Key(e) = event {
+				if let Some(what) = self.copy_key(e) {
+					try_or_popup!(
+						self,
+						strings::POPUP_FAIL_COPY,
+						self.copy(what)
+					);
+					return Ok(EventState::Consumed);
+				}
+
 				return Ok(
 					if key_match(e, self.key_config.keys.move_up) {
 						self.move_scroll_top(ScrollType::Up).into()
@@ -24,6 +24,7 @@ use ratatui::{
 	layout::{Constraint, Direction, Layout, Rect},
 	Frame,
 };
+use std::collections::HashSet;
 
 pub struct CommitDetailsComponent {
 	commit: Option<CommitFilesParams>,
@@ -56,12 +57,31 @@ impl CommitDetailsComponent {
 
 	fn get_files_title(&self) -> String {
 		let files_count = self.file_tree.file_count();
-
-		format!(
+		let title = format!(
 			"{} {}",
 			strings::commit::details_files_title(&self.key_config),
 			files_count
-		)
+		);
+
+		if let Some((viewed, total)) = self.file_tree.viewed_count() {
+			format!(
+				"{title} - {}",
+				strings::commit::details_files_viewed(viewed, total)
+			)
+		} else {
+			title
+		}
+	}
+
+	/// see [`StatusTreeComponent::set_viewed`]
+	pub fn set_viewed(&mut self, viewed: Option<HashSet<String>>) {
+		self.file_tree.set_viewed(viewed);
+		self.file_tree.set_title(self.get_files_title());
+	}
+
+	///
+	pub fn select_next_unviewed(&mut self) -> bool {
+		self.file_tree.select_next_unviewed()
 	}
 
 	///
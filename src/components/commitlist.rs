@@ -2,11 +2,13 @@ use super::utils::logitems::{ItemBatch, LogEntry};
 use crate::{
 	app::Environment,
 	components::{
-		utils::string_width_align, CommandBlocking, CommandInfo,
-		Component, DrawableComponent, EventState, ScrollType,
+		utils::{copy_commits, string_width_align, CommitCopy},
+		CommandBlocking, CommandInfo, Component, DrawableComponent,
+		EventState, ScrollType,
 	},
 	keys::{key_match, SharedKeyConfig},
-	queue::{InternalEvent, Queue},
+	options::{LogColumn, LogColumnKind, SharedOptions},
+	queue::Queue,
 	strings::{self, symbol},
 	try_or_popup,
 	ui::style::{SharedTheme, Theme},
@@ -14,8 +16,8 @@ use crate::{
 };
 use anyhow::Result;
 use asyncgit::sync::{
-	self, checkout_commit, BranchDetails, BranchInfo, CommitId,
-	RepoPathRef, Tags,
+	self, checkout_commit, Bookmark, BranchDetails, BranchInfo,
+	CommitId, RepoPathRef, Tags,
 };
 use chrono::{DateTime, Local};
 use crossterm::event::Event;
@@ -45,19 +47,32 @@ pub struct CommitList {
 	items: ItemBatch,
 	highlights: Option<Rc<IndexSet<CommitId>>>,
 	commits: IndexSet<CommitId>,
+	/// commits of a reload, they replace `commits` once they include
+	/// the selected one (see [`Self::reload`])
+	reloaded: Option<IndexSet<CommitId>>,
 	/// The marked commits.
 	/// `self.marked[].0` holds the commit index into `self.items.items` - used for ordering the list.
 	/// `self.marked[].1` is the commit id of the marked commit.
 	marked: Vec<(usize, CommitId)>,
 	scroll_state: (Instant, f32),
 	tags: Option<Tags>,
+	bookmarks: BTreeMap<CommitId, Vec<String>>,
+	/// notes shown next to the tags, like the branch a stash was
+	/// parked from
+	labels: BTreeMap<CommitId, String>,
 	local_branches: BTreeMap<CommitId, Vec<BranchInfo>>,
 	remote_branches: BTreeMap<CommitId, Vec<BranchInfo>>,
 	current_size: Cell<Option<(u16, u16)>>,
 	scroll_top: Cell<usize>,
+	/// the history ends early (shallow clone), marked after the last
+	/// commit
+	truncated: bool,
+	/// lists next to each other show which one keys go to
+	focused: bool,
 	theme: SharedTheme,
 	queue: Queue,
 	key_config: SharedKeyConfig,
+	options: SharedOptions,
 }
 
 impl CommitList {
@@ -70,16 +85,22 @@ impl CommitList {
 			selection: 0,
 			highlighted_selection: None,
 			commits: IndexSet::new(),
+			reloaded: None,
 			highlights: None,
 			scroll_state: (Instant::now(), 0_f32),
 			tags: None,
+			bookmarks: BTreeMap::default(),
+			labels: BTreeMap::default(),
 			local_branches: BTreeMap::default(),
 			remote_branches: BTreeMap::default(),
 			current_size: Cell::new(None),
 			scroll_top: Cell::new(0),
+			truncated: false,
+			focused: true,
 			theme: env.theme.clone(),
 			queue: env.queue.clone(),
 			key_config: env.key_config.clone(),
+			options: env.options.clone(),
 			title: title.into(),
 		}
 	}
@@ -89,10 +110,92 @@ impl CommitList {
 		self.tags.as_ref()
 	}
 
+	///
+	pub fn focus(&mut self, focus: bool) {
+		self.focused = focus;
+	}
+
+	///
+	pub fn set_title(&mut self, title: &str) {
+		self.title = title.into();
+	}
+
+	/// marks the end of the list as the end of a shallow clone, set
+	/// once all of the commits are loaded
+	pub fn set_truncated(&mut self, truncated: bool) {
+		self.truncated = truncated;
+	}
+
 	///
 	pub fn clear(&mut self) {
 		self.items.clear();
 		self.commits.clear();
+		self.reloaded = None;
+	}
+
+	/// the commits passed to [`Self::refresh_extend_data`] from now
+	/// on make up a new list, it is shown once it includes the
+	/// selected commit so neither the selection nor the view jump
+	/// while it loads
+	pub fn reload(&mut self) {
+		self.reloaded = Some(IndexSet::new());
+	}
+
+	/// shows the reloaded list even if the selected commit is not
+	/// part of it, call this once all of it is loaded
+	pub fn finish_reload(&mut self) {
+		if self.reloaded.is_some() {
+			self.apply_reload();
+		}
+	}
+
+	/// selects the same commit and keeps the topmost one in view
+	/// where they are in the reloaded list
+	fn apply_reload(&mut self) {
+		let Some(commits) = self.reloaded.take() else {
+			return;
+		};
+
+		let selected =
+			self.commits.get_index(self.selection).copied();
+		let top = self
+			.commits
+			.get_index(
+				self.items.index_offset() + self.scroll_top.get(),
+			)
+			.copied();
+
+		self.commits = commits;
+		self.items.clear();
+		self.selection = selected
+			.and_then(|id| self.commits.get_index_of(&id))
+			.unwrap_or(self.selection)
+			.min(self.selection_max());
+		self.fetch_commits(false);
+
+		let offset = self.items.index_offset();
+		self.scroll_top.set(
+			top.and_then(|id| self.commits.get_index_of(&id))
+				.filter(|top| *top <= self.selection)
+				.map_or(0, |top| top.saturating_sub(offset)),
+		);
+
+		let marked = std::mem::take(&mut self.marked);
+		self.marked = marked
+			.into_iter()
+			.filter_map(|(_, id)| {
+				let idx = self.commits.get_index_of(&id)?;
+				Some((idx.saturating_sub(offset), id))
+			})
+			.collect();
+		self.marked.sort_unstable_by_key(|marked| marked.0);
+
+		self.set_highlighted_selection_index();
+	}
+
+	/// commits in the list, loaded or not
+	pub fn count(&self) -> usize {
+		self.commits.len()
 	}
 
 	///
@@ -130,36 +233,26 @@ impl CommitList {
 		commits
 	}
 
-	/// Build string of marked or selected (if none are marked) commit ids
-	fn concat_selected_commit_ids(&self) -> Option<String> {
-		match self.marked.as_slice() {
-			[] => self
-				.items
-				.iter()
-				.nth(
-					self.selection
-						.saturating_sub(self.items.index_offset()),
-				)
-				.map(|e| e.id.to_string()),
-			marked => Some(
-				marked
-					.iter()
-					.map(|(_idx, commit)| commit.to_string())
-					.join(" "),
-			),
+	/// the marked commits in log order, the selected one if none
+	/// are marked
+	fn selected_or_marked_ids(&self) -> Vec<CommitId> {
+		if self.marked.is_empty() {
+			self.selected_entry().map(|e| e.id).into_iter().collect()
+		} else {
+			self.marked_commits()
 		}
 	}
 
-	/// Copy currently marked or selected (if none are marked) commit ids
-	/// to clipboard
-	pub fn copy_commit_hash(&self) -> Result<()> {
-		if let Some(yank) = self.concat_selected_commit_ids() {
-			crate::clipboard::copy_string(&yank)?;
-			self.queue.push(InternalEvent::ShowInfoMsg(
-				strings::copy_success(&yank),
-			));
-		}
-		Ok(())
+	/// copies `what` of the marked commits one per line, of the
+	/// selected one if none are marked
+	pub fn copy_commits(&self, what: CommitCopy) -> Result<()> {
+		copy_commits(
+			&self.queue,
+			&self.repo.borrow(),
+			&self.selected_or_marked_ids(),
+			what,
+			&self.options.borrow().copy_commit_format(),
+		)
 	}
 
 	///
@@ -175,6 +268,23 @@ impl CommitList {
 		}
 	}
 
+	///
+	pub fn set_bookmarks(&mut self, bookmarks: Vec<Bookmark>) {
+		self.bookmarks.clear();
+
+		for bookmark in bookmarks {
+			self.bookmarks
+				.entry(bookmark.id)
+				.or_default()
+				.push(bookmark.name);
+		}
+	}
+
+	///
+	pub fn set_labels(&mut self, labels: BTreeMap<CommitId, String>) {
+		self.labels = labels;
+	}
+
 	///
 	pub fn set_local_branches(
 		&mut self,
@@ -207,6 +317,7 @@ impl CommitList {
 
 	///
 	pub fn set_commits(&mut self, commits: IndexSet<CommitId>) {
+		self.reloaded = None;
 		if commits != self.commits {
 			self.items.clear();
 			self.commits = commits;
@@ -216,6 +327,19 @@ impl CommitList {
 
 	///
 	pub fn refresh_extend_data(&mut self, commits: Vec<CommitId>) {
+		if let Some(reloaded) = &mut self.reloaded {
+			reloaded.extend(commits);
+
+			if self
+				.commits
+				.get_index(self.selection)
+				.map_or(true, |id| reloaded.contains(id))
+			{
+				self.apply_reload();
+			}
+			return;
+		}
+
 		let new_commits = !commits.is_empty();
 		self.commits.extend(commits);
 
@@ -271,11 +395,12 @@ impl CommitList {
 	}
 
 	fn set_highlighted_selection_index(&mut self) {
+		let selected = self.commits.get_index(self.selection);
 		self.highlighted_selection =
 			self.highlights.as_ref().and_then(|highlights| {
-				highlights.iter().position(|entry| {
-					entry == &self.commits[self.selection]
-				})
+				highlights
+					.iter()
+					.position(|entry| Some(entry) == selected)
 			});
 	}
 
@@ -322,7 +447,9 @@ impl CommitList {
 			//TODO: support this?
 			// ScrollType::Home => 0,
 			// ScrollType::End => self.selection_max(),
-			ScrollType::HalfPageUp | ScrollType::HalfPageDown => return Ok(false),
+			ScrollType::HalfPageUp | ScrollType::HalfPageDown => {
+				return Ok(false)
+			}
 			_ => return Ok(false),
 		};
 
@@ -446,7 +573,7 @@ impl CommitList {
 		}
 	}
 
-	#[allow(clippy::too_many_arguments)]
+	#[allow(clippy::too_many_arguments, clippy::too_many_lines)]
 	fn get_entry_to_add<'a>(
 		&self,
 		e: &'a LogEntry,
@@ -458,6 +585,7 @@ impl CommitList {
 		width: usize,
 		now: DateTime<Local>,
 		marked: Option<bool>,
+		columns: &[LogColumn],
 	) -> Line<'a> {
 		let mut txt: Vec<Span> = Vec::with_capacity(
 			ELEMENTS_PER_LINE + if marked.is_some() { 2 } else { 0 },
@@ -480,7 +608,7 @@ impl CommitList {
 		if let Some(marked) = marked {
 			txt.push(Span::styled(
 				Cow::from(if marked {
-					symbol::CHECKMARK
+					theme.glyphs().checkmark
 				} else {
 					symbol::EMPTY_SPACE
 				}),
@@ -520,55 +648,135 @@ impl CommitList {
 			theme.commit_unhighlighted()
 		};
 
-		// commit hash
-		txt.push(Span::styled(Cow::from(&*e.hash_short), style_hash));
-
-		txt.push(splitter.clone());
-
-		// commit timestamp
-		txt.push(Span::styled(
-			Cow::from(e.time_to_string(now)),
-			style_time,
-		));
-
-		txt.push(splitter.clone());
+		let flag = |set: bool, glyph: &'static str| {
+			Span::styled(
+				Cow::from(if set {
+					glyph
+				} else {
+					symbol::EMPTY_SPACE
+				}),
+				style_hash,
+			)
+		};
 
-		let author_width =
-			(width.saturating_sub(19) / 3).clamp(3, 20);
-		let author = string_width_align(&e.author, author_width);
+		let mut refs = [
+			tags.map(|tags| Span::styled(tags, style_tags)),
+			local_branches.map(|local_branches| {
+				Span::styled(local_branches, style_branches)
+			}),
+			remote_branches.map(|remote_branches| {
+				Span::styled(remote_branches, style_branches)
+			}),
+		]
+		.into_iter()
+		.flatten();
+
+		// the message is filled in once the width left for it is known
+		let mut cells: Vec<Option<Vec<Span>>> = columns
+			.iter()
+			.map(|column| {
+				let spans = match column.kind {
+					LogColumnKind::Hash => vec![Span::styled(
+						Cow::from(&*e.hash_short),
+						style_hash,
+					)],
+					LogColumnKind::Age => vec![Span::styled(
+						Cow::from(e.time_to_string(now)),
+						style_time,
+					)],
+					LogColumnKind::Author => {
+						let author_width =
+							column.width.unwrap_or_else(|| {
+								(width.saturating_sub(19) / 3)
+									.clamp(3, 20)
+							});
+						vec![Span::styled(
+							string_width_align(
+								&e.author,
+								author_width,
+							),
+							style_author,
+						)]
+					}
+					LogColumnKind::Refs => {
+						let mut spans = Vec::new();
+						for span in refs.by_ref() {
+							if !spans.is_empty() {
+								spans.push(splitter.clone());
+							}
+							spans.push(span);
+						}
+						spans
+					}
+					LogColumnKind::Signature => {
+						vec![flag(e.signed, theme.glyphs().signed)]
+					}
+					LogColumnKind::Note => {
+						vec![flag(e.has_note, theme.glyphs().note)]
+					}
+					LogColumnKind::Message => return None,
+				};
 
-		// commit author
-		txt.push(Span::styled(author, style_author));
+				Some(match column.width {
+					Some(width) => {
+						fit_spans(spans, width, splitter.style)
+					}
+					None => spans,
+				})
+			})
+			.collect();
 
-		txt.push(splitter.clone());
+		// an empty column sized to its content takes no space at all
+		let shown = |cell: &Option<Vec<Span>>, column: &LogColumn| {
+			cell.as_ref().map_or(true, |spans| {
+				column.width.is_some() || !spans.is_empty()
+			})
+		};
 
-		// commit tags
-		if let Some(tags) = tags {
-			txt.push(splitter.clone());
-			txt.push(Span::styled(tags, style_tags));
+		let used: usize = cells
+			.iter()
+			.zip(columns)
+			.filter(|(cell, column)| shown(cell, column))
+			.map(|(cell, _)| {
+				splitter.width()
+					+ cell
+						.iter()
+						.flatten()
+						.map(Span::width)
+						.sum::<usize>()
+			})
+			.sum();
+
+		for (cell, column) in cells.iter_mut().zip(columns) {
+			if cell.is_none() {
+				let message_width =
+					column.width.unwrap_or_else(|| {
+						(width + splitter.width())
+							.saturating_sub(used + txt_width(&txt))
+					});
+				*cell = Some(fit_spans(
+					vec![Span::styled(
+						format!("{:message_width$}", &e.msg),
+						style_msg,
+					)],
+					message_width,
+					splitter.style,
+				));
+			}
 		}
 
-		if let Some(local_branches) = local_branches {
-			txt.push(splitter.clone());
-			txt.push(Span::styled(local_branches, style_branches));
-		}
-		if let Some(remote_branches) = remote_branches {
-			txt.push(splitter.clone());
-			txt.push(Span::styled(remote_branches, style_branches));
+		let mut first = true;
+		for (cell, column) in cells.into_iter().zip(columns) {
+			if !shown(&cell, column) {
+				continue;
+			}
+			if !first {
+				txt.push(splitter.clone());
+			}
+			first = false;
+			txt.extend(cell.into_iter().flatten());
 		}
 
-		txt.push(splitter);
-
-		let message_width = width.saturating_sub(
-			txt.iter().map(|span| span.content.len()).sum(),
-		);
-
-		// commit msg
-		txt.push(Span::styled(
-			format!("{:message_width$}", &e.msg),
-			style_msg,
-		));
-
 		Line::from(txt)
 	}
 
@@ -580,6 +788,13 @@ impl CommitList {
 		let now = Local::now();
 
 		let any_marked = !self.marked.is_empty();
+		let columns: Vec<LogColumn> = self
+			.options
+			.borrow()
+			.log_columns()
+			.into_iter()
+			.filter(|column| column.visible(width))
+			.collect();
 
 		for (idx, e) in self
 			.items
@@ -588,14 +803,7 @@ impl CommitList {
 			.take(height)
 			.enumerate()
 		{
-			let tags =
-				self.tags.as_ref().and_then(|t| t.get(&e.id)).map(
-					|tags| {
-						tags.iter()
-							.map(|t| format!("<{}>", t.name))
-							.join(" ")
-					},
-				);
+			let tags = self.tags_string(e);
 
 			let local_branches =
 				self.local_branches.get(&e.id).map(|local_branch| {
@@ -623,12 +831,53 @@ impl CommitList {
 				width,
 				now,
 				marked,
+				&columns,
 			));
 		}
 
+		if txt.len() < height && self.shows_truncated_marker() {
+			txt.push(Line::from(Span::styled(
+				strings::LOG_TRUNCATED_SHALLOW,
+				self.theme.text(false, false),
+			)));
+		}
+
 		txt
 	}
 
+	/// the marker follows the last commit once that one is loaded
+	fn shows_truncated_marker(&self) -> bool {
+		self.truncated
+			&& !self.commits.is_empty()
+			&& self.items.index_offset() + self.items.iter().len()
+				== self.commits.len()
+	}
+
+	/// tags, bookmarks and labels share the same decoration
+	fn tags_string(&self, e: &LogEntry) -> Option<String> {
+		let tags = self
+			.tags
+			.as_ref()
+			.and_then(|t| t.get(&e.id))
+			.into_iter()
+			.flatten()
+			.map(|t| format!("<{}>", t.name));
+
+		let bookmarks =
+			self.bookmarks.get(&e.id).into_iter().flatten().map(
+				|name| {
+					format!("{}{name}", self.theme.glyphs().bookmark)
+				},
+			);
+
+		let label =
+			self.labels.get(&e.id).map(|label| format!("[{label}]"));
+
+		let decoration = tags.chain(bookmarks).chain(label).join(" ");
+
+		(!decoration.is_empty()).then_some(decoration)
+	}
+
 	fn remote_branches_string(&self, e: &LogEntry) -> Option<String> {
 		self.remote_branches.get(&e.id).and_then(|remote_branches| {
 			let filtered_branches: Vec<_> = remote_branches
@@ -778,6 +1027,44 @@ impl CommitList {
 	}
 }
 
+fn txt_width(txt: &[Span]) -> usize {
+	txt.iter().map(Span::width).sum()
+}
+
+/// pads or cuts `spans` to exactly `width` columns
+fn fit_spans(
+	spans: Vec<Span<'_>>,
+	width: usize,
+	pad_style: Style,
+) -> Vec<Span<'_>> {
+	let mut res = Vec::with_capacity(spans.len() + 1);
+	let mut left = width;
+
+	for span in spans {
+		if left == 0 {
+			break;
+		}
+
+		let span_width = span.width();
+		if span_width <= left {
+			left -= span_width;
+			res.push(span);
+		} else {
+			res.push(Span::styled(
+				string_width_align(&span.content, left),
+				span.style,
+			));
+			left = 0;
+		}
+	}
+
+	if left > 0 {
+		res.push(Span::styled(" ".repeat(left), pad_style));
+	}
+
+	res
+}
+
 impl DrawableComponent for CommitList {
 	fn draw(&self, f: &mut Frame, area: Rect) -> Result<()> {
 		let current_size = (
@@ -789,9 +1076,18 @@ impl DrawableComponent for CommitList {
 		let height_in_lines = current_size.1 as usize;
 		let selection = self.relative_selection();
 
+		// the last commit keeps the marker below it in view
+		let scroll_height = if self.selection == self.selection_max()
+			&& self.shows_truncated_marker()
+		{
+			height_in_lines.saturating_sub(1).max(1)
+		} else {
+			height_in_lines
+		};
+
 		self.scroll_top.set(calc_scroll_top(
 			self.scroll_top.get(),
-			height_in_lines,
+			scroll_height,
 			selection,
 		));
 
@@ -814,9 +1110,9 @@ impl DrawableComponent for CommitList {
 					.borders(Borders::ALL)
 					.title(Span::styled(
 						title.as_str(),
-						self.theme.title(true),
+						self.theme.title(self.focused),
 					))
-					.border_style(self.theme.block(true)),
+					.border_style(self.theme.block(self.focused)),
 			)
 			.alignment(Alignment::Left),
 			area,
@@ -907,6 +1203,8 @@ mod tests {
 	use asyncgit::sync::CommitInfo;
 
 	use super::*;
+	use crate::options::Options;
+	use std::cell::RefCell;
 
 	impl Default for CommitList {
 		fn default() -> Self {
@@ -916,10 +1214,15 @@ mod tests {
 				highlighted_selection: Option::None,
 				highlights: Option::None,
 				tags: Option::None,
+				bookmarks: BTreeMap::default(),
+				labels: BTreeMap::default(),
 				items: ItemBatch::default(),
 				commits: IndexSet::default(),
+				reloaded: None,
 				marked: Vec::default(),
 				scroll_top: Cell::default(),
+				truncated: false,
+				focused: true,
 				local_branches: BTreeMap::default(),
 				remote_branches: BTreeMap::default(),
 				theme: SharedTheme::default(),
@@ -930,6 +1233,7 @@ mod tests {
 					std::path::PathBuf::default(),
 				)),
 				queue: Queue::default(),
+				options: Rc::new(RefCell::new(Options::test_env())),
 			}
 		}
 	}
@@ -964,6 +1268,8 @@ mod tests {
 			time: 0,
 			author: String::default(),
 			id: CommitId::default(),
+			signed: false,
+			has_note: false,
 		};
 		// This just creates a sequence of fake ordered ids
 		// 0000000000000000000000000000000000000000
@@ -990,6 +1296,110 @@ mod tests {
 		}
 	}
 
+	fn column(
+		kind: LogColumnKind,
+		width: Option<usize>,
+	) -> LogColumn {
+		LogColumn {
+			kind,
+			width,
+			hide_below: 0,
+		}
+	}
+
+	#[test]
+	fn test_log_columns() {
+		let mut cl = CommitList::default();
+		cl.items.set_items(
+			0,
+			vec![CommitInfo {
+				message: String::from("msg"),
+				time: 0,
+				author: String::from("author"),
+				id: CommitId::default(),
+				signed: true,
+				has_note: false,
+			}],
+			None,
+		);
+		let entry = cl.items.iter().next().unwrap();
+		let theme = Theme::default();
+
+		let text = |columns: &[LogColumn], refs: Option<&str>| {
+			cl.get_entry_to_add(
+				entry,
+				false,
+				None,
+				refs.map(String::from),
+				None,
+				&theme,
+				30,
+				Local::now(),
+				None,
+				columns,
+			)
+			.spans
+			.iter()
+			.map(|span| span.content.as_ref())
+			.collect::<String>()
+		};
+
+		let columns = [
+			column(LogColumnKind::Hash, None),
+			column(LogColumnKind::Refs, None),
+			column(LogColumnKind::Note, Some(3)),
+			column(LogColumnKind::Message, None),
+			column(LogColumnKind::Signature, None),
+		];
+		assert_eq!(
+			text(&columns, Some("{main}")),
+			format!(
+				"0000000 {{main}}     msg       {}",
+				theme.glyphs().signed
+			)
+		);
+		// no refs, no column for them
+		assert_eq!(
+			text(&columns, None),
+			format!(
+				"0000000     msg              {}",
+				theme.glyphs().signed
+			)
+		);
+
+		let columns = [
+			column(LogColumnKind::Message, Some(5)),
+			column(LogColumnKind::Author, Some(4)),
+		];
+		assert_eq!(text(&columns, None), "msg   au..");
+	}
+
+	#[test]
+	fn test_log_columns_hidden() {
+		let visible = |width| {
+			LogColumn::defaults()
+				.into_iter()
+				.filter(|column| column.visible(width))
+				.map(|column| column.kind)
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(
+			visible(55),
+			[
+				LogColumnKind::Hash,
+				LogColumnKind::Age,
+				LogColumnKind::Refs,
+				LogColumnKind::Message
+			]
+		);
+		assert_eq!(
+			visible(10),
+			[LogColumnKind::Hash, LogColumnKind::Message]
+		);
+		assert_eq!(LogColumn::defaults().len(), visible(100).len());
+	}
+
 	/// Build a value for cl.marked based on indices into cl.items
 	fn build_marked_from_indices(
 		cl: &CommitList,
@@ -1006,10 +1416,9 @@ mod tests {
 
 	#[test]
 	fn test_copy_commit_list_empty() {
-		assert_eq!(
-			CommitList::default().concat_selected_commit_ids(),
-			None
-		);
+		assert!(CommitList::default()
+			.selected_or_marked_ids()
+			.is_empty());
 	}
 
 	#[test]
@@ -1021,12 +1430,7 @@ mod tests {
 		// ids from build_commit_list_with_some_commits() are
 		// offset by two, so we expect commit id 2 for
 		// selection = 4
-		assert_eq!(
-			cl.concat_selected_commit_ids(),
-			Some(String::from(
-				"0000000000000000000000000000000000000002"
-			))
-		);
+		assert_eq!(cl.selected_or_marked_ids(), vec![fake_id(2)]);
 	}
 
 	#[test]
@@ -1036,12 +1440,7 @@ mod tests {
 			marked: build_marked_from_indices(&cl, &[3]),
 			..cl
 		};
-		assert_eq!(
-			cl.concat_selected_commit_ids(),
-			Some(String::from(
-				"0000000000000000000000000000000000000001",
-			))
-		);
+		assert_eq!(cl.selected_or_marked_ids(), vec![fake_id(1)]);
 	}
 
 	#[test]
@@ -1052,13 +1451,8 @@ mod tests {
 			..cl
 		};
 		assert_eq!(
-			cl.concat_selected_commit_ids(),
-			Some(String::from(concat!(
-				"0000000000000000000000000000000000000002 ",
-				"0000000000000000000000000000000000000003 ",
-				"0000000000000000000000000000000000000004 ",
-				"0000000000000000000000000000000000000005"
-			)))
+			cl.selected_or_marked_ids(),
+			vec![fake_id(2), fake_id(3), fake_id(4), fake_id(5)]
 		);
 	}
 
@@ -1070,11 +1464,105 @@ mod tests {
 			..cl
 		};
 		assert_eq!(
-			cl.concat_selected_commit_ids(),
-			Some(String::from(concat!(
-				"0000000000000000000000000000000000000002 ",
-				"0000000000000000000000000000000000000005"
-			)))
+			cl.selected_or_marked_ids(),
+			vec![fake_id(2), fake_id(5)]
 		);
 	}
+
+	fn fake_id(idx: usize) -> CommitId {
+		CommitId::from_str_unchecked(&format!("{idx:040}")).unwrap()
+	}
+
+	fn selected_id(list: &CommitList) -> Option<CommitId> {
+		list.commits.get_index(list.selection).copied()
+	}
+
+	#[test]
+	fn test_truncated_marker() {
+		let mut list = CommitList {
+			commits: (0..5).map(fake_id).collect(),
+			truncated: true,
+			..Default::default()
+		};
+		let infos =
+			|range: std::ops::Range<usize>| -> Vec<CommitInfo> {
+				range
+					.map(|idx| CommitInfo {
+						message: String::from("msg"),
+						time: 0,
+						author: String::from("author"),
+						id: fake_id(idx),
+						signed: false,
+						has_note: false,
+					})
+					.collect()
+			};
+		let last_line = |list: &CommitList| {
+			list.get_text(10, 40)
+				.last()
+				.map(|line| {
+					line.spans
+						.iter()
+						.map(|span| span.content.as_ref())
+						.collect::<String>()
+				})
+				.unwrap_or_default()
+		};
+
+		// the end of the list is not loaded
+		list.items.set_items(0, infos(0..3), None);
+		assert_ne!(last_line(&list), strings::LOG_TRUNCATED_SHALLOW);
+
+		list.items.set_items(0, infos(0..5), None);
+		assert_eq!(list.get_text(10, 40).len(), 6);
+		assert_eq!(last_line(&list), strings::LOG_TRUNCATED_SHALLOW);
+
+		// no room below the last commit
+		assert_eq!(list.get_text(5, 40).len(), 5);
+
+		list.set_truncated(false);
+		assert_eq!(list.get_text(10, 40).len(), 5);
+	}
+
+	#[test]
+	fn test_reload_keeps_selection() {
+		let mut list = CommitList::default();
+		for chunk in (0..30).collect_vec().chunks(7) {
+			list.refresh_extend_data(
+				chunk.iter().copied().map(fake_id).collect(),
+			);
+		}
+		list.selection = 20;
+		let mut expected = selected_id(&list);
+
+		// two new commits on top, the old ones stream in after them
+		// while the selection moves
+		list.reload();
+		let reloaded =
+			[100, 101].into_iter().chain(0..30).collect_vec();
+		for (idx, chunk) in reloaded.chunks(4).enumerate() {
+			list.refresh_extend_data(
+				chunk.iter().copied().map(fake_id).collect(),
+			);
+			assert_eq!(selected_id(&list), expected);
+
+			if idx % 2 == 0 {
+				list.move_selection(ScrollType::Down).unwrap();
+				expected = selected_id(&list);
+			}
+		}
+		list.finish_reload();
+
+		assert_eq!(selected_id(&list), expected);
+		assert_eq!(list.count(), 32);
+		assert!(list.reloaded.is_none());
+
+		// the selected commit is gone, the index is kept
+		list.reload();
+		list.refresh_extend_data((0..10).map(fake_id).collect());
+		assert_eq!(list.count(), 32);
+		list.finish_reload();
+		assert_eq!(list.count(), 10);
+		assert_eq!(list.selection, 9);
+	}
 }
@@ -57,6 +57,15 @@ impl CredComponent {
 	pub const fn get_cred(&self) -> &BasicAuthCredential {
 		&self.cred
 	}
+
+	/// screen area of the input shown (last time we got drawn)
+	pub fn get_area(&self) -> Rect {
+		if self.input_username.is_visible() {
+			self.input_username.get_area()
+		} else {
+			self.input_password.get_area()
+		}
+	}
 }
 
 impl DrawableComponent for CredComponent {
@@ -0,0 +1,393 @@
+//! answers the credential requests of a running push, fetch or pull.
+//! the job waits for an answer while the inputs are shown, cancelling
+//! them only offers the options of what to do instead
+
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		CredComponent, DrawableComponent, EventState, InputType,
+		TextInputComponent,
+	},
+	keys::{key_match, SharedKeyConfig},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	cred::BasicAuthCredential,
+	remotes::{CredAnswer, CredPrompt, CredRejection, CredRequest},
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::Rect,
+	text::{Line, Span},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+
+#[derive(Clone, PartialEq, Eq)]
+enum PromptOption {
+	Retry,
+	SwitchUrl(String),
+	Abort,
+}
+
+/// what to do after cancelling a credential prompt of a running job
+struct PromptOptions {
+	options: Vec<PromptOption>,
+	selection: usize,
+}
+
+impl PromptOptions {
+	fn new(switch_url: Option<String>) -> Self {
+		let mut options = vec![PromptOption::Retry];
+		options.extend(switch_url.map(PromptOption::SwitchUrl));
+		options.push(PromptOption::Abort);
+
+		Self {
+			options,
+			selection: 0,
+		}
+	}
+
+	fn move_selection(&mut self, up: bool) {
+		self.selection = if up {
+			self.selection.saturating_sub(1)
+		} else {
+			(self.selection + 1).min(self.options.len() - 1)
+		};
+	}
+
+	fn selected(&self) -> PromptOption {
+		self.options[self.selection].clone()
+	}
+}
+
+///
+pub struct CredPromptComponent {
+	prompt: CredPrompt,
+	/// named in the abort option
+	job: &'static str,
+	/// offered after cancelling, e.g. the https url of an ssh remote
+	switch_url: Option<String>,
+	rejection: Option<CredRejection>,
+	input_cred: CredComponent,
+	input_passphrase: TextInputComponent,
+	/// the running job keeps waiting while these are shown
+	options: Option<PromptOptions>,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl CredPromptComponent {
+	///
+	pub fn new(
+		env: &Environment,
+		prompt: CredPrompt,
+		job: &'static str,
+	) -> Self {
+		Self {
+			prompt,
+			job,
+			switch_url: None,
+			rejection: None,
+			input_cred: CredComponent::new(env),
+			input_passphrase: TextInputComponent::new(
+				env,
+				&strings::passphrase_popup_title(&env.key_config),
+				"",
+				false,
+			)
+			.with_input_type(InputType::Password),
+			options: None,
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	/// for the job to ask
+	pub const fn prompt(&self) -> &CredPrompt {
+		&self.prompt
+	}
+
+	/// offer switching to `url` once a prompt is cancelled, unless
+	/// the job uses it already
+	pub fn set_switch_url(&mut self, url: Option<String>) {
+		self.switch_url = url;
+	}
+
+	/// asks for whatever the running job is waiting on
+	pub fn update(&mut self) -> Result<()> {
+		match self.prompt.request() {
+			Some(request) if !self.is_visible() => {
+				self.rejection = self.prompt.rejection();
+				self.open(&request)
+			}
+			Some(_) => Ok(()),
+			// answered or timed out meanwhile
+			None => {
+				self.hide();
+				Ok(())
+			}
+		}
+	}
+
+	fn open(&mut self, request: &CredRequest) -> Result<()> {
+		self.options = None;
+
+		match request {
+			CredRequest::SshPassphrase { key, .. } => {
+				self.input_passphrase.set_default_msg(
+					strings::passphrase_popup_msg(key),
+				);
+				self.input_passphrase.show()
+			}
+			CredRequest::UserPass { username, .. } => {
+				self.input_cred.set_cred(BasicAuthCredential::new(
+					username.clone(),
+					None,
+				));
+				self.input_cred.show()
+			}
+		}
+	}
+
+	fn show_options(&mut self) {
+		let current_url = self.prompt.url();
+		let switch_url = self
+			.switch_url
+			.clone()
+			.filter(|url| Some(url) != current_url.as_ref());
+
+		self.options = Some(PromptOptions::new(switch_url));
+	}
+
+	fn answer(&mut self, answer: CredAnswer) -> Result<()> {
+		self.hide();
+		self.prompt.answer(answer)?;
+
+		Ok(())
+	}
+
+	fn select(&mut self, option: PromptOption) -> Result<()> {
+		match option {
+			PromptOption::Retry => {
+				self.options = None;
+				self.update()?;
+			}
+			PromptOption::SwitchUrl(url) => {
+				self.answer(CredAnswer::SwitchUrl(url))?;
+			}
+			PromptOption::Abort => {
+				self.hide();
+				self.prompt.cancel()?;
+			}
+		}
+
+		Ok(())
+	}
+
+	fn draw_options(&self, f: &mut Frame, options: &PromptOptions) {
+		let lines: Vec<Line> = options
+			.options
+			.iter()
+			.enumerate()
+			.map(|(index, option)| {
+				let text = match option {
+					PromptOption::Retry => {
+						strings::cred_prompt_retry()
+					}
+					PromptOption::SwitchUrl(url) => {
+						strings::cred_prompt_switch_url(url)
+					}
+					PromptOption::Abort => {
+						strings::cred_prompt_abort(self.job)
+					}
+				};
+				Line::from(Span::styled(
+					text,
+					self.theme.text(true, index == options.selection),
+				))
+			})
+			.collect();
+
+		let width = lines
+			.iter()
+			.map(Line::width)
+			.max()
+			.unwrap_or_default()
+			.max(strings::cred_prompt_cancelled_title().len())
+			.saturating_add(4);
+		let area = ui::centered_rect_absolute(
+			u16::try_from(width).unwrap_or(u16::MAX),
+			u16::try_from(lines.len() + 2).unwrap_or(u16::MAX),
+			f.area(),
+		);
+
+		f.render_widget(Clear, area);
+		f.render_widget(
+			Paragraph::new(lines).block(
+				Block::default()
+					.title(Span::styled(
+						strings::cred_prompt_cancelled_title(),
+						self.theme.title(true),
+					))
+					.borders(Borders::ALL)
+					.border_type(BorderType::Thick)
+					.border_style(self.theme.block(true)),
+			),
+			area,
+		);
+	}
+
+	/// right above the input the answer goes into
+	fn draw_rejection(
+		&self,
+		f: &mut Frame,
+		rejection: &CredRejection,
+		input_area: Rect,
+	) {
+		let area = Rect {
+			y: input_area.y.saturating_sub(3),
+			height: 3.min(input_area.y),
+			..input_area
+		};
+
+		f.render_widget(Clear, area);
+		f.render_widget(
+			Paragraph::new(Span::styled(
+				strings::cred_prompt_rejected(rejection),
+				self.theme.text_danger(),
+			))
+			.block(
+				Block::default()
+					.borders(Borders::ALL)
+					.border_type(BorderType::Thick)
+					.border_style(self.theme.block(true)),
+			),
+			area,
+		);
+	}
+}
+
+impl DrawableComponent for CredPromptComponent {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		self.input_cred.draw(f, rect)?;
+		self.input_passphrase.draw(f, rect)?;
+
+		if let Some(options) = &self.options {
+			self.draw_options(f, options);
+		} else if let Some(rejection) = &self.rejection {
+			let input_area = if self.input_cred.is_visible() {
+				Some(self.input_cred.get_area())
+			} else if self.input_passphrase.is_visible() {
+				Some(self.input_passphrase.get_area())
+			} else {
+				None
+			};
+
+			if let Some(input_area) = input_area {
+				self.draw_rejection(f, rejection, input_area);
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for CredPromptComponent {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.input_cred.is_visible() {
+			return self.input_cred.commands(out, force_all);
+		}
+		if self.input_passphrase.is_visible() {
+			out.push(CommandInfo::new(
+				strings::commands::validate_msg(&self.key_config),
+				true,
+				true,
+			));
+			return self.input_passphrase.commands(out, force_all);
+		}
+		if self.options.is_some() {
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::confirm_action(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if !self.is_visible() {
+			return Ok(EventState::NotConsumed);
+		}
+		let Event::Key(e) = ev else {
+			return Ok(EventState::Consumed);
+		};
+
+		if let Some(options) = &mut self.options {
+			if key_match(e, self.key_config.keys.move_up) {
+				options.move_selection(true);
+			} else if key_match(e, self.key_config.keys.move_down) {
+				options.move_selection(false);
+			} else if key_match(e, self.key_config.keys.enter) {
+				let option = options.selected();
+				self.select(option)?;
+			} else if key_match(e, self.key_config.keys.exit_popup) {
+				self.select(PromptOption::Abort)?;
+			}
+		} else if self.input_passphrase.is_visible() {
+			if key_match(e, self.key_config.keys.exit_popup) {
+				self.input_passphrase.hide();
+				self.input_passphrase.clear();
+				self.show_options();
+			} else if key_match(e, self.key_config.keys.enter) {
+				let passphrase =
+					self.input_passphrase.get_text().to_string();
+				self.answer(CredAnswer::Passphrase(passphrase))?;
+			} else {
+				self.input_passphrase.event(ev)?;
+			}
+		} else if self.input_cred.is_visible() {
+			self.input_cred.event(ev)?;
+
+			if self.input_cred.get_cred().is_complete() {
+				let cred = self.input_cred.get_cred().clone();
+				self.answer(CredAnswer::UserPass(cred))?;
+			} else if !self.input_cred.is_visible() {
+				self.show_options();
+			}
+		}
+
+		Ok(EventState::Consumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.input_cred.is_visible()
+			|| self.input_passphrase.is_visible()
+			|| self.options.is_some()
+	}
+
+	fn hide(&mut self) {
+		self.input_cred.hide();
+		self.input_passphrase.hide();
+		self.input_passphrase.clear();
+		self.options = None;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.update()
+	}
+}
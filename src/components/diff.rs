@@ -1,14 +1,21 @@
 use super::{
+	utils::diff_search::{highlight_segments, DiffSearch, Highlight},
 	utils::scroll_horizontal::HorizontalScroll,
-	utils::scroll_vertical::VerticalScroll, CommandBlocking,
-	Direction, DrawableComponent, HorizontalScrollType, ScrollType,
+	utils::scroll_vertical::VerticalScroll,
+	utils::split_diff::{split_rows, SplitRow},
+	utils::word_diff::word_diff,
+	CommandBlocking, Direction, DrawableComponent,
+	HorizontalScrollType, InputType, ScrollType, TextInputComponent,
 };
 use crate::{
 	app::Environment,
 	components::{CommandInfo, Component, EventState},
 	keys::{key_match, SharedKeyConfig},
 	options::SharedOptions,
-	queue::{Action, InternalEvent, NeedsUpdate, Queue, ResetItem},
+	queue::{
+		Action, InternalEvent, NeedsUpdate, ProcessItem, Queue,
+		ResetItem,
+	},
 	string_utils::tabs_to_spaces,
 	string_utils::trim_offset,
 	strings, try_or_popup,
@@ -17,19 +24,28 @@ use crate::{
 use anyhow::Result;
 use asyncgit::{
 	hash,
-	sync::{self, diff::DiffLinePosition, RepoPathRef},
+	sync::{
+		self, diff::DiffLinePosition, LfsDiff, LfsPointer,
+		ModeChange, RepoPathRef, StageCleanupPreview, SubmoduleDiff,
+	},
 	DiffLine, DiffLineType, FileDiff,
 };
 use bytesize::ByteSize;
 use crossterm::event::Event;
 use ratatui::{
 	layout::Rect,
+	style::Style,
 	symbols,
 	text::{Line, Span},
 	widgets::{Block, Borders, Paragraph},
 	Frame,
 };
-use std::{borrow::Cow, cell::Cell, cmp, path::Path};
+use std::{
+	borrow::Cow, cell::Cell, cmp, collections::HashMap, ops::Range,
+	path::Path,
+};
+use unicode_truncate::UnicodeTruncateStr;
+use unicode_width::UnicodeWidthStr;
 
 #[derive(Default)]
 struct Current {
@@ -102,10 +118,23 @@ impl Selection {
 }
 
 ///
+#[allow(clippy::struct_excessive_bools)]
 pub struct DiffComponent {
 	repo: RepoPathRef,
 	diff: Option<FileDiff>,
+	/// changed words of paired removed and added lines (by index)
+	word_changes: HashMap<usize, Vec<Range<usize>>>,
+	/// side-by-side instead of unified, the selection is a row then
+	split: bool,
+	split_rows: Vec<SplitRow>,
+	/// split row of each line
+	line_rows: Vec<usize>,
+	lineno_width: usize,
 	longest_line: usize,
+	search: DiffSearch,
+	search_input: TextInputComponent,
+	/// line selected when the search was opened
+	search_origin: usize,
 	pending: bool,
 	selection: Selection,
 	selected_hunk: Option<usize>,
@@ -118,12 +147,27 @@ pub struct DiffComponent {
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
 	is_immutable: bool,
+	/// whitespace issues of the unstaged changes
+	stage_cleanup: Option<StageCleanupPreview>,
+	/// where the missing objects of the diff get fetched from
+	promisor_remote: Option<String>,
+	/// `git lfs` is there to show the content of a pointer
+	lfs_installed: bool,
 	options: SharedOptions,
 }
 
 impl DiffComponent {
 	///
 	pub fn new(env: &Environment, is_immutable: bool) -> Self {
+		let mut search_input = TextInputComponent::new(
+			env,
+			"",
+			&strings::diff_search_placeholder(&env.key_config),
+			false,
+		)
+		.with_input_type(InputType::Singleline);
+		search_input.embed();
+
 		Self {
 			focused: false,
 			queue: env.queue.clone(),
@@ -131,7 +175,15 @@ impl DiffComponent {
 			pending: false,
 			selected_hunk: None,
 			diff: None,
+			word_changes: HashMap::new(),
+			split: false,
+			split_rows: Vec::new(),
+			line_rows: Vec::new(),
+			lineno_width: 0,
 			longest_line: 0,
+			search: DiffSearch::default(),
+			search_input,
+			search_origin: 0,
 			current_size: Cell::new((0, 0)),
 			selection: Selection::Single(0),
 			vertical_scroll: VerticalScroll::new(),
@@ -139,13 +191,20 @@ impl DiffComponent {
 			theme: env.theme.clone(),
 			key_config: env.key_config.clone(),
 			is_immutable,
+			stage_cleanup: None,
+			promisor_remote: None,
+			lfs_installed: false,
 			repo: env.repo.clone(),
 			options: env.options.clone(),
 		}
 	}
 	///
 	fn can_scroll(&self) -> bool {
-		self.diff.as_ref().is_some_and(|diff| diff.lines > 1)
+		self.lines_count() > 1
+	}
+	/// hunks and lines can not be (un)staged or reset
+	pub fn set_immutable(&mut self, immutable: bool) {
+		self.is_immutable = immutable;
 	}
 	///
 	pub fn current(&self) -> (String, bool) {
@@ -155,12 +214,17 @@ impl DiffComponent {
 	pub fn clear(&mut self, pending: bool) {
 		self.current = Current::default();
 		self.diff = None;
+		self.stage_cleanup = None;
+		self.word_changes.clear();
+		self.split_rows.clear();
+		self.line_rows.clear();
 		self.longest_line = 0;
 		self.vertical_scroll.reset();
 		self.horizontal_scroll.reset();
 		self.selection = Selection::Single(0);
 		self.selected_hunk = None;
 		self.pending = pending;
+		self.clear_search();
 	}
 	///
 	pub fn update(
@@ -176,12 +240,56 @@ impl DiffComponent {
 		if self.current.hash != hash {
 			let reset_selection = self.current.path != path;
 
+			self.stage_cleanup = if self.is_immutable || is_stage {
+				None
+			} else {
+				sync::stage_cleanup_preview(
+					&self.repo.borrow(),
+					&path,
+				)
+				.unwrap_or_default()
+			};
+
 			self.current = Current {
 				path,
 				is_stage,
 				hash,
 			};
 
+			self.promisor_remote = None;
+			if !diff.missing_objects.is_empty() {
+				self.promisor_remote =
+					sync::promisor_remote(&self.repo.borrow())
+						.unwrap_or_default();
+
+				if self.promisor_remote.is_some() {
+					self.queue.push(
+						InternalEvent::FetchMissingObjects(
+							diff.missing_objects.clone(),
+						),
+					);
+				}
+			}
+
+			self.lfs_installed =
+				diff.lfs.is_some() && sync::lfs_installed();
+
+			let (split_rows, line_rows) = split_rows(&diff);
+			self.word_changes =
+				Self::word_changes(&diff, &split_rows);
+			self.split_rows = split_rows;
+			self.line_rows = line_rows;
+			self.lineno_width = diff
+				.hunks
+				.iter()
+				.flat_map(|hunk| hunk.lines.iter())
+				.filter_map(|line| {
+					line.position
+						.old_lineno
+						.max(line.position.new_lineno)
+				})
+				.max()
+				.map_or(0, |lineno| lineno.to_string().len());
 			self.diff = Some(diff);
 
 			self.longest_line = self
@@ -214,12 +322,129 @@ impl DiffComponent {
 				};
 				self.update_selection(old_selection);
 			}
+
+			if reset_selection {
+				self.clear_search();
+			} else {
+				self.run_search(
+					self.search.current().map_or(0, |m| m.line),
+				);
+			}
 		}
 	}
 
-	fn move_selection(&mut self, move_type: ScrollType) {
-		if let Some(diff) = &self.diff {
-			let max = diff.lines.saturating_sub(1);
+	/// the search input takes all keys while it is open
+	pub fn search_input_active(&self) -> bool {
+		self.search_input.is_visible()
+	}
+
+	fn search_active(&self) -> bool {
+		!self.search_input.get_text().is_empty()
+	}
+
+	fn open_search(&mut self) -> Result<()> {
+		self.search.clear();
+		self.search_input.clear();
+		self.search_origin =
+			self.row_to_line(self.selection.get_start());
+		self.search_input.show()
+	}
+
+	fn clear_search(&mut self) {
+		self.search.clear();
+		self.search_input.clear();
+		self.search_input.hide();
+	}
+
+	/// looks for the search text in all hunks, the current match is
+	/// the first one from `origin` on
+	fn run_search(&mut self, origin: usize) {
+		let lines: Vec<String> = self
+			.diff
+			.iter()
+			.flat_map(|diff| diff.hunks.iter())
+			.flat_map(|hunk| hunk.lines.iter())
+			.map(|line| {
+				tabs_to_spaces(line.content.as_ref().to_string())
+			})
+			.collect();
+
+		self.search.update(
+			lines.iter().map(String::as_str),
+			self.search_input.get_text(),
+			origin,
+		);
+	}
+
+	/// selects the line of the current match and scrolls sideways if
+	/// the match is out of view
+	fn jump_to_match(&mut self) {
+		let Some(found) = self.search.current().cloned() else {
+			return;
+		};
+
+		self.update_selection(self.line_to_row(found.line));
+
+		let content = self
+			.diff
+			.iter()
+			.flat_map(|diff| diff.hunks.iter())
+			.flat_map(|hunk| hunk.lines.iter())
+			.nth(found.line)
+			.map(|line| {
+				tabs_to_spaces(line.content.as_ref().to_string())
+			})
+			.unwrap_or_default();
+		let column = |index: usize| {
+			content.get(..index).map_or(0, UnicodeWidthStr::width)
+		};
+		let (start, end) =
+			(column(found.range.start), column(found.range.end));
+
+		let width = self.content_width();
+		let right = self.horizontal_scroll.get_right();
+		if start < right || end > right + width {
+			self.horizontal_scroll
+				.set_right(start.saturating_sub(width / 2));
+		}
+	}
+
+	/// keys of the open search input, the search follows the input
+	fn search_input_event(
+		&mut self,
+		ev: &Event,
+	) -> Result<EventState> {
+		if let Event::Key(e) = ev {
+			if key_match(e, self.key_config.keys.enter) {
+				self.search_input.hide();
+				return Ok(EventState::Consumed);
+			}
+
+			if key_match(e, self.key_config.keys.diff_search_case) {
+				self.search.case_sensitive =
+					!self.search.case_sensitive;
+				self.run_search(self.search_origin);
+				self.jump_to_match();
+				return Ok(EventState::Consumed);
+			}
+		}
+
+		if self.search_input.event(ev)?.is_consumed() {
+			if self.search_input.is_visible() {
+				self.run_search(self.search_origin);
+				self.jump_to_match();
+			} else {
+				// closed with `exit_popup`
+				self.clear_search();
+			}
+		}
+
+		Ok(EventState::Consumed)
+	}
+
+	pub fn move_selection(&mut self, move_type: ScrollType) {
+		if self.diff.is_some() {
+			let max = self.lines_count().saturating_sub(1);
 
 			let new_start = match move_type {
 				ScrollType::Down => {
@@ -262,21 +487,83 @@ impl DiffComponent {
 
 	fn update_selection(&mut self, new_start: usize) {
 		if let Some(diff) = &self.diff {
-			let max = diff.lines.saturating_sub(1);
+			let max = self.lines_count().saturating_sub(1);
 			let new_start = cmp::min(max, new_start);
 			self.selection = Selection::Single(new_start);
-			self.selected_hunk =
-				Self::find_selected_hunk(diff, new_start);
+			self.selected_hunk = if self.split {
+				self.split_rows.get(new_start).map(|row| row.hunk)
+			} else {
+				Self::find_selected_hunk(diff, new_start)
+			};
 		}
 	}
 
+	/// lines or rows in split mode
 	fn lines_count(&self) -> usize {
-		self.diff.as_ref().map_or(0, |diff| diff.lines)
+		if self.split {
+			self.split_rows.len()
+		} else {
+			self.diff.as_ref().map_or(0, |diff| diff.lines)
+		}
+	}
+
+	/// row of `line` in the current mode
+	fn line_to_row(&self, line: usize) -> usize {
+		if self.split {
+			self.line_rows.get(line).copied().unwrap_or_default()
+		} else {
+			line
+		}
+	}
+
+	/// first line of `row` in the current mode
+	fn row_to_line(&self, row: usize) -> usize {
+		if self.split {
+			self.split_rows
+				.get(row)
+				.and_then(|row| row.old.or(row.new))
+				.unwrap_or_default()
+		} else {
+			row
+		}
+	}
+
+	fn line_selected(&self, line: usize) -> bool {
+		self.selection.contains(self.line_to_row(line))
+	}
+
+	fn toggle_split(&mut self) {
+		let line = self.row_to_line(self.selection.get_start());
+
+		self.split = !self.split;
+		self.horizontal_scroll.reset();
+		self.update_selection(self.line_to_row(line));
+	}
+
+	/// content width of each side in split mode
+	fn split_widths(&self) -> (usize, usize) {
+		// hunk marker and separator
+		let width =
+			usize::from(self.current_size.get().0).saturating_sub(2);
+		let left = width / 2;
+		let lineno = self.lineno_width + 1;
+
+		(
+			left.saturating_sub(lineno),
+			(width - left).saturating_sub(lineno),
+		)
+	}
+
+	fn content_width(&self) -> usize {
+		if self.split {
+			self.split_widths().1
+		} else {
+			self.current_size.get().0.into()
+		}
 	}
 
 	fn max_scroll_right(&self) -> usize {
-		self.longest_line
-			.saturating_sub(self.current_size.get().0.into())
+		self.longest_line.saturating_sub(self.content_width())
 	}
 
 	fn modify_selection(&mut self, direction: Direction) {
@@ -293,7 +580,7 @@ impl DiffComponent {
 					.flat_map(|hunk| hunk.lines.iter())
 					.enumerate()
 					.filter_map(|(i, line)| {
-						if self.selection.contains(i) {
+						if self.line_selected(i) {
 							Some(line.content.trim_matches(|c| {
 								c == '\n' || c == '\r'
 							}))
@@ -313,6 +600,49 @@ impl DiffComponent {
 		}
 	}
 
+	/// compares the removed and added lines that are next to each
+	/// other in split mode
+	fn word_changes(
+		diff: &FileDiff,
+		rows: &[SplitRow],
+	) -> HashMap<usize, Vec<Range<usize>>> {
+		let lines: Vec<&DiffLine> = diff
+			.hunks
+			.iter()
+			.flat_map(|hunk| hunk.lines.iter())
+			.collect();
+		let mut changes = HashMap::new();
+
+		for row in rows {
+			let (Some(old_index), Some(new_index)) =
+				(row.old, row.new)
+			else {
+				continue;
+			};
+			let (old, new) = (lines[old_index], lines[new_index]);
+
+			if old.line_type != DiffLineType::Delete
+				|| new.line_type != DiffLineType::Add
+			{
+				continue;
+			}
+
+			let old_content =
+				tabs_to_spaces(old.content.as_ref().to_string());
+			let new_content =
+				tabs_to_spaces(new.content.as_ref().to_string());
+
+			if let Some(word_changes) =
+				word_diff(&old_content, &new_content)
+			{
+				changes.insert(old_index, word_changes.old);
+				changes.insert(new_index, word_changes.new);
+			}
+		}
+
+		changes
+	}
+
 	fn find_selected_hunk(
 		diff: &FileDiff,
 		line_selected: usize,
@@ -338,8 +668,23 @@ impl DiffComponent {
 
 	fn get_text(&self, width: u16, height: u16) -> Vec<Line> {
 		if let Some(diff) = &self.diff {
-			return if diff.hunks.is_empty() {
+			let word_changes =
+				self.options.borrow().diff_word_changes();
+
+			return if !diff.missing_objects.is_empty() {
+				self.get_text_missing(diff)
+			} else if let Some(submodule) = &diff.submodule {
+				self.get_text_submodule(submodule)
+			} else if let Some(lfs) = &diff.lfs {
+				self.get_text_lfs(diff, lfs)
+			} else if let (true, Some(change)) =
+				(diff.hunks.is_empty(), &diff.mode_change)
+			{
+				self.get_text_mode_change(diff, change)
+			} else if diff.hunks.is_empty() {
 				self.get_text_binary(diff)
+			} else if self.split {
+				self.get_text_split(diff, width, height)
 			} else {
 				let mut res: Vec<Line> = Vec::new();
 
@@ -381,6 +726,13 @@ impl DiffComponent {
 									&self.theme,
 									self.horizontal_scroll
 										.get_right(),
+									self.word_changes
+										.get(&line_cursor)
+										.filter(|_| word_changes)
+										.map_or(&[], Vec::as_slice),
+									&self
+										.search
+										.line_matches(line_cursor),
 								));
 								lines_added += 1;
 							}
@@ -399,12 +751,124 @@ impl DiffComponent {
 		vec![]
 	}
 
+	fn lfs_pointer(&self) -> Option<&LfsPointer> {
+		self.diff
+			.as_ref()
+			.and_then(|diff| diff.lfs.as_ref())
+			.and_then(LfsDiff::pointer)
+	}
+
+	fn can_show_lfs_content(&self) -> bool {
+		self.lfs_installed && self.lfs_pointer().is_some()
+	}
+
+	/// `git lfs` downloads the content first if it does not have it
+	fn show_lfs_content(&self) -> Result<()> {
+		let Some(pointer) = self.lfs_pointer() else {
+			return Ok(());
+		};
+
+		let child = sync::spawn_lfs_smudge(
+			&self.repo.borrow(),
+			&self.current.path,
+			pointer,
+		)?;
+
+		self.queue.push(InternalEvent::RunProcess(Box::new(
+			ProcessItem {
+				title: strings::title_lfs_content(&self.current.path),
+				child,
+				on_success: None,
+			},
+		)));
+
+		Ok(())
+	}
+
+	fn get_text_missing(&self, diff: &FileDiff) -> Vec<Line<'_>> {
+		let msg = self.promisor_remote.as_ref().map_or_else(
+			|| String::from(strings::DIFF_OBJECTS_MISSING),
+			|remote| strings::diff_objects_not_fetched(remote),
+		);
+
+		std::iter::once(Line::from(Span::styled(
+			msg,
+			self.theme.text(true, false),
+		)))
+		.chain(diff.missing_objects.iter().map(|id| {
+			Line::from(Span::styled(
+				format!("  {id}"),
+				self.theme.text(false, false),
+			))
+		}))
+		.collect()
+	}
+
+	fn get_text_submodule(
+		&self,
+		submodule: &SubmoduleDiff,
+	) -> Vec<Line<'_>> {
+		strings::submodule_diff(submodule)
+			.into_iter()
+			.enumerate()
+			.map(|(idx, line)| {
+				Line::from(Span::styled(
+					line,
+					self.theme.text(idx == 0, false),
+				))
+			})
+			.collect()
+	}
+
+	fn get_text_lfs(
+		&self,
+		diff: &FileDiff,
+		lfs: &LfsDiff,
+	) -> Vec<Line<'_>> {
+		strings::lfs_diff(lfs)
+			.into_iter()
+			.enumerate()
+			.map(|(idx, line)| {
+				Line::from(Span::styled(
+					line,
+					self.theme.text(idx == 0, false),
+				))
+			})
+			.chain(self.get_text_binary(diff))
+			.collect()
+	}
+
+	fn get_text_mode_change(
+		&self,
+		diff: &FileDiff,
+		change: &ModeChange,
+	) -> Vec<Line<'_>> {
+		std::iter::once(Line::from(Span::styled(
+			strings::mode_change(change),
+			self.theme.text(true, false),
+		)))
+		.chain(self.get_text_binary(diff))
+		.collect()
+	}
+
+	/// a mode change without any change of the content has no hunk
+	/// to (un)stage, the whole file is
+	fn is_pure_mode_change(&self) -> bool {
+		self.diff.as_ref().is_some_and(|diff| {
+			diff.hunks.is_empty()
+				&& matches!(
+					diff.mode_change,
+					Some(ModeChange::Mode(_))
+				)
+		})
+	}
+
 	fn get_text_binary(&self, diff: &FileDiff) -> Vec<Line> {
 		let is_positive = diff.size_delta >= 0;
 		let delta_byte_size =
 			ByteSize::b(diff.size_delta.unsigned_abs());
 		let sign = if is_positive { "+" } else { "-" };
-		vec![Line::from(vec![
+		let mut lines = vec![Line::from(vec![
 			Span::raw(Cow::from("size: ")),
 			Span::styled(
 				Cow::from(format!("{}", ByteSize::b(diff.sizes.0))),
@@ -428,9 +892,19 @@ impl DiffComponent {
 				),
 			),
 			Span::raw(Cow::from(")")),
-		])]
+		])];
+
+		if let Some(images) = strings::diff_images(diff.images) {
+			lines.push(Line::from(Span::styled(
+				images,
+				self.theme.text(false, false),
+			)));
+		}
+
+		lines
 	}
 
+	#[allow(clippy::too_many_arguments)]
 	fn get_line_to_add<'a>(
 		width: u16,
 		line: &'a DiffLine,
@@ -439,6 +913,8 @@ impl DiffComponent {
 		end_of_hunk: bool,
 		theme: &SharedTheme,
 		scrolled_right: usize,
+		word_changes: &[Range<usize>],
+		matches: &[(Range<usize>, bool)],
 	) -> Line<'a> {
 		let style = theme.diff_hunk_marker(selected_hunk);
 
@@ -446,15 +922,15 @@ impl DiffComponent {
 			matches!(line.line_type, DiffLineType::None);
 
 		let left_side_of_line = if end_of_hunk {
-			Span::styled(Cow::from(symbols::line::BOTTOM_LEFT), style)
+			Span::styled(Cow::from(theme.glyphs().hunk_end), style)
 		} else {
 			match line.line_type {
 				DiffLineType::Header => Span::styled(
-					Cow::from(symbols::line::TOP_LEFT),
+					Cow::from(theme.glyphs().hunk_start),
 					style,
 				),
 				_ => Span::styled(
-					Cow::from(symbols::line::VERTICAL),
+					Cow::from(theme.glyphs().hunk_line),
 					style,
 				),
 			}
@@ -466,23 +942,239 @@ impl DiffComponent {
 			} else {
 				tabs_to_spaces(line.content.as_ref().to_string())
 			};
-		let content = trim_offset(&content, scrolled_right);
+		let offset = content.len()
+			- trim_offset(&content, scrolled_right).len();
+
+		let style = theme.diff_line(line.line_type, selected);
+
+		let mut segments = highlight_segments(
+			offset..content.len(),
+			word_changes,
+			matches,
+		);
+		// the unchanged end of the line is filled up
+		let rest = if segments.last().is_some_and(|(_, highlight)| {
+			*highlight == Highlight::None
+		}) {
+			segments.pop().map(|(range, _)| range)
+		} else {
+			None
+		}
+		.unwrap_or(content.len()..content.len());
+
+		let mut spans = vec![left_side_of_line];
+		spans.extend(segments.into_iter().map(
+			|(range, highlight)| {
+				Span::styled(
+					Cow::from(content[range].to_string()),
+					Self::highlight_style(
+						theme,
+						line.line_type,
+						selected,
+						highlight,
+					),
+				)
+			},
+		));
 
 		let filled = if selected {
 			// selected line
-			format!("{content:w$}\n", w = width as usize)
+			let used = content[offset..rest.start].chars().count();
+			format!(
+				"{:w$}\n",
+				&content[rest],
+				w = (width as usize).saturating_sub(used)
+			)
 		} else {
 			// weird eof missing eol line
-			format!("{content}\n")
+			format!("{}\n", &content[rest])
 		};
 
-		Line::from(vec![
-			left_side_of_line,
-			Span::styled(
-				Cow::from(filled),
-				theme.diff_line(line.line_type, selected),
+		spans.push(Span::styled(Cow::from(filled), style));
+
+		Line::from(spans)
+	}
+
+	fn get_text_split<'a>(
+		&'a self,
+		diff: &'a FileDiff,
+		width: u16,
+		height: u16,
+	) -> Vec<Line<'a>> {
+		let word_changes = self.options.borrow().diff_word_changes();
+		let hunk_starts: Vec<usize> = diff
+			.hunks
+			.iter()
+			.scan(0, |start, hunk| {
+				let hunk_start = *start;
+				*start += hunk.lines.len();
+				Some(hunk_start)
+			})
+			.collect();
+		let line = |hunk: usize, index: usize| {
+			(
+				index,
+				&diff.hunks[hunk].lines[index - hunk_starts[hunk]],
+			)
+		};
+		let (old_width, new_width) = self.split_widths();
+
+		self.split_rows
+			.iter()
+			.enumerate()
+			.skip(self.vertical_scroll.get_top())
+			.take(height.into())
+			.map(|(index, row)| {
+				let selected =
+					self.focused() && self.selection.contains(index);
+				let selected_hunk = self.focused()
+					&& self.selected_hunk == Some(row.hunk);
+				let end_of_hunk = self
+					.split_rows
+					.get(index + 1)
+					.map_or(true, |next| next.hunk != row.hunk);
+
+				let old = row.old.map(|index| line(row.hunk, index));
+				let new = row.new.map(|index| line(row.hunk, index));
+
+				if let Some((index, header)) =
+					old.filter(|(_, line)| {
+						line.line_type == DiffLineType::Header
+					}) {
+					return Self::get_line_to_add(
+						width,
+						header,
+						selected,
+						selected_hunk,
+						end_of_hunk,
+						&self.theme,
+						self.horizontal_scroll.get_right(),
+						&[],
+						&self.search.line_matches(index),
+					);
+				}
+
+				let glyphs = self.theme.glyphs();
+				let mut spans = vec![Span::styled(
+					Cow::from(if end_of_hunk {
+						glyphs.hunk_end
+					} else {
+						glyphs.hunk_line
+					}),
+					self.theme.diff_hunk_marker(selected_hunk),
+				)];
+				spans.extend(self.split_side(
+					old,
+					true,
+					old_width,
+					selected,
+					word_changes,
+				));
+				spans.push(Span::styled(
+					Cow::from(symbols::line::VERTICAL),
+					self.theme.block(false),
+				));
+				spans.extend(self.split_side(
+					new,
+					false,
+					new_width,
+					selected,
+					word_changes,
+				));
+
+				Line::from(spans)
+			})
+			.collect()
+	}
+
+	/// line number and content of one side of a split row, empty if
+	/// there is no line on this side
+	fn split_side(
+		&self,
+		line: Option<(usize, &DiffLine)>,
+		old: bool,
+		width: usize,
+		selected: bool,
+		word_changes: bool,
+	) -> Vec<Span<'static>> {
+		let Some((index, line)) = line else {
+			return vec![Span::styled(
+				" ".repeat(self.lineno_width + 1 + width),
+				self.theme.diff_line(DiffLineType::None, selected),
+			)];
+		};
+
+		let lineno = if old {
+			line.position.old_lineno
+		} else {
+			line.position.new_lineno
+		};
+		let style = self.theme.diff_line(line.line_type, selected);
+
+		let mut spans = vec![Span::styled(
+			format!(
+				"{:>w$} ",
+				lineno.map_or_else(String::new, |n| n.to_string()),
+				w = self.lineno_width
 			),
-		])
+			self.theme.text(false, selected),
+		)];
+
+		let content =
+			tabs_to_spaces(line.content.as_ref().to_string());
+		let start = content.len()
+			- trim_offset(
+				&content,
+				self.horizontal_scroll.get_right(),
+			)
+			.len();
+		let (visible, used) =
+			content[start..].unicode_truncate(width);
+		let changes = self
+			.word_changes
+			.get(&index)
+			.filter(|_| word_changes)
+			.map_or(&[][..], Vec::as_slice);
+
+		spans.extend(
+			highlight_segments(
+				start..start + visible.len(),
+				changes,
+				&self.search.line_matches(index),
+			)
+			.into_iter()
+			.map(|(range, highlight)| {
+				Span::styled(
+					content[range].to_string(),
+					Self::highlight_style(
+						&self.theme,
+						line.line_type,
+						selected,
+						highlight,
+					),
+				)
+			}),
+		);
+		spans.push(Span::styled(
+			" ".repeat(width.saturating_sub(used)),
+			style,
+		));
+
+		spans
+	}
+
+	fn highlight_style(
+		theme: &SharedTheme,
+		line_type: DiffLineType,
+		selected: bool,
+		highlight: Highlight,
+	) -> Style {
+		match highlight {
+			Highlight::None => theme.diff_line(line_type, selected),
+			Highlight::Word => theme.diff_word(line_type, selected),
+			Highlight::Match => theme.diff_search_match(false),
+			Highlight::CurrentMatch => theme.diff_search_match(true),
+		}
 	}
 
 	const fn hunk_visible(
@@ -561,6 +1253,7 @@ impl DiffComponent {
 					Action::ResetHunk(
 						self.current.path.clone(),
 						hash,
+						self.current.hash,
 					),
 				));
 			}
@@ -572,29 +1265,27 @@ impl DiffComponent {
 			Action::ResetLines(
 				self.current.path.clone(),
 				self.selected_lines(),
+				self.current.hash,
 			),
 		));
 	}
 
 	fn stage_lines(&self) {
-		if let Some(diff) = &self.diff {
-			//TODO: support untracked files as well
-			if !diff.untracked {
-				let selected_lines = self.selected_lines();
-
-				try_or_popup!(
-					self,
-					"(un)stage lines:",
-					sync::stage_lines(
-						&self.repo.borrow(),
-						&self.current.path,
-						self.is_stage(),
-						&selected_lines,
-					)
-				);
+		if self.diff.is_some() {
+			let selected_lines = self.selected_lines();
 
-				self.queue_update();
-			}
+			try_or_popup!(
+				self,
+				"(un)stage lines:",
+				sync::stage_lines(
+					&self.repo.borrow(),
+					&self.current.path,
+					self.is_stage(),
+					&selected_lines,
+				)
+			);
+
+			self.queue_update();
 		}
 	}
 
@@ -610,9 +1301,7 @@ impl DiffComponent {
 						let is_add_or_delete = line.line_type
 							== DiffLineType::Add
 							|| line.line_type == DiffLineType::Delete;
-						if self.selection.contains(i)
-							&& is_add_or_delete
-						{
+						if self.line_selected(i) && is_add_or_delete {
 							Some(line.position)
 						} else {
 							None
@@ -627,12 +1316,24 @@ impl DiffComponent {
 		self.queue.push(InternalEvent::ConfirmAction(Action::Reset(
 			ResetItem {
 				path: self.current.path.clone(),
+				submodule: None,
 			},
 		)));
 	}
 
 	fn stage_unstage_hunk(&self) -> Result<()> {
-		if self.current.is_stage {
+		if self.is_pure_mode_change() {
+			let repo = self.repo.borrow();
+			if self.current.is_stage {
+				sync::reset_stage(&repo, &self.current.path)?;
+			} else {
+				sync::stage_add_file(
+					&repo,
+					Path::new(&self.current.path),
+				)?;
+			}
+			self.queue_update();
+		} else if self.current.is_stage {
 			self.unstage_hunk()?;
 		} else {
 			self.stage_hunk()?;
@@ -674,13 +1375,18 @@ impl DiffComponent {
 				.iter()
 				.take(hunk_index)
 				.fold(0, |sum, hunk| sum + hunk.lines.len());
-			let hunk = &diff.hunks[hunk_index];
-			self.selection = Selection::Single(line_index);
+			let last_line = line_index
+				+ diff.hunks[hunk_index]
+					.lines
+					.len()
+					.saturating_sub(1);
+			let row = self.line_to_row(line_index);
+			self.selection = Selection::Single(row);
 			self.selected_hunk = Some(hunk_index);
 			self.vertical_scroll.move_area_to_visible(
 				self.current_size.get().1 as usize,
-				line_index,
-				line_index.saturating_add(hunk.lines.len()),
+				row,
+				self.line_to_row(last_line).saturating_add(1),
 			);
 		}
 	}
@@ -692,12 +1398,13 @@ impl DiffComponent {
 
 impl DrawableComponent for DiffComponent {
 	fn draw(&self, f: &mut Frame, r: Rect) -> Result<()> {
+		// the search input takes the last line
+		let input_height = u16::from(self.search_input.is_visible());
 		self.current_size.set((
 			r.width.saturating_sub(2),
-			r.height.saturating_sub(2),
+			r.height.saturating_sub(2 + input_height),
 		));
 
-		let current_width = self.current_size.get().0;
 		let current_height = self.current_size.get().1;
 
 		self.vertical_scroll.update(
@@ -708,13 +1415,40 @@ impl DrawableComponent for DiffComponent {
 
 		self.horizontal_scroll.update_no_selection(
 			self.longest_line,
-			current_width.into(),
+			self.content_width(),
 		);
 
 		let title = format!(
-			"{}{}",
+			"{}{}{}{}{}{}{}",
 			strings::title_diff(&self.key_config),
-			self.current.path
+			self.current.path,
+			self.diff
+				.as_ref()
+				.and_then(|diff| diff.lfs.as_ref())
+				.map(strings::diff_lfs_title)
+				.unwrap_or_default(),
+			self.diff
+				.as_ref()
+				.and_then(|diff| diff.mode_change.as_ref())
+				.map(strings::diff_mode_change_title)
+				.unwrap_or_default(),
+			self.diff
+				.as_ref()
+				.and_then(|diff| diff.forced)
+				.map_or("", strings::diff_forced_type),
+			self.stage_cleanup
+				.as_ref()
+				.map(strings::diff_stage_cleanup)
+				.unwrap_or_default(),
+			if self.search_active() {
+				strings::diff_search_matches(
+					self.search.current_index(),
+					self.search.count(),
+					self.search.case_sensitive,
+				)
+			} else {
+				String::new()
+			}
 		);
 
 		let txt = if self.pending {
@@ -747,6 +1481,18 @@ impl DrawableComponent for DiffComponent {
 			}
 		}
 
+		if self.search_input.is_visible() {
+			self.search_input.draw(
+				f,
+				Rect::new(
+					r.x + 1,
+					(r.y + r.height).saturating_sub(2),
+					r.width.saturating_sub(2),
+					1,
+				),
+			)?;
+		}
+
 		Ok(())
 	}
 }
@@ -821,18 +1567,58 @@ impl Component for DiffComponent {
 			));
 		}
 
+		out.push(CommandInfo::new(
+			strings::commands::diff_toggle_split(
+				&self.key_config,
+				self.split,
+			),
+			true,
+			self.focused(),
+		));
+
 		out.push(CommandInfo::new(
 			strings::commands::copy(&self.key_config),
 			true,
 			self.focused(),
 		));
 
+		out.push(CommandInfo::new(
+			strings::commands::diff_lfs_content(&self.key_config),
+			true,
+			self.focused() && self.can_show_lfs_content(),
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::diff_search(&self.key_config),
+			self.diff.is_some(),
+			self.focused(),
+		));
+		out.push(CommandInfo::new(
+			strings::commands::diff_search_next_prev(
+				&self.key_config,
+			),
+			self.search.count() > 0,
+			self.focused(),
+		));
+		out.push(CommandInfo::new(
+			strings::commands::diff_search_case(
+				&self.key_config,
+				self.search.case_sensitive,
+			),
+			true,
+			self.focused() && self.search_input.is_visible(),
+		));
+
 		CommandBlocking::PassingOn
 	}
 
 	#[allow(clippy::cognitive_complexity, clippy::too_many_lines)]
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.focused() {
+			if self.search_input.is_visible() {
+				return self.search_input_event(ev);
+			}
+
 			if let Event::Key(e) = ev {
 				return if key_match(e, self.key_config.keys.move_down)
 				{
@@ -864,12 +1650,16 @@ impl Component for DiffComponent {
 				{
 					self.move_selection(ScrollType::PageDown);
 					Ok(EventState::Consumed)
-				} else if key_match(e, self.key_config.keys.scroll_up_half_page)
-				{
+				} else if key_match(
+					e,
+					self.key_config.keys.scroll_up_half_page,
+				) {
 					self.move_selection(ScrollType::HalfPageUp);
 					Ok(EventState::Consumed)
-				} else if key_match(e, self.key_config.keys.scroll_down_half_page)
-				{
+				} else if key_match(
+					e,
+					self.key_config.keys.scroll_down_half_page,
+				) {
 					self.move_selection(ScrollType::HalfPageDown);
 					Ok(EventState::Consumed)
 				} else if key_match(
@@ -884,12 +1674,48 @@ impl Component for DiffComponent {
 					self.horizontal_scroll
 						.move_right(HorizontalScrollType::Left);
 					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.diff_search,
+				) && self.diff.is_some()
+				{
+					self.open_search()?;
+					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.diff_search_next,
+				) && self.search.count() > 0
+				{
+					self.search.select_next(true);
+					self.jump_to_match();
+					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.diff_search_prev,
+				) && self.search.count() > 0
+				{
+					self.search.select_next(false);
+					self.jump_to_match();
+					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.exit_popup,
+				) && self.search_active()
+				{
+					self.clear_search();
+					Ok(EventState::Consumed)
 				} else if key_match(
 					e,
 					self.key_config.keys.diff_hunk_next,
 				) {
 					self.diff_hunk_move_up_down(1);
 					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.diff_toggle_split,
+				) {
+					self.toggle_split();
+					Ok(EventState::Consumed)
 				} else if key_match(
 					e,
 					self.key_config.keys.diff_hunk_prev,
@@ -945,6 +1771,17 @@ impl Component for DiffComponent {
 				} else if key_match(e, self.key_config.keys.copy) {
 					self.copy_selection();
 					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.diff_lfs_content,
+				) && self.can_show_lfs_content()
+				{
+					try_or_popup!(
+						self,
+						"lfs content error:",
+						self.show_lfs_content()
+					);
+					Ok(EventState::Consumed)
 				} else {
 					Ok(EventState::NotConsumed)
 				};
@@ -959,6 +1796,9 @@ impl Component for DiffComponent {
 	}
 	fn focus(&mut self, focus: bool) {
 		self.focused = focus;
+		if !focus {
+			self.clear_search();
+		}
 	}
 }
 
@@ -989,7 +1829,9 @@ mod tests {
 					false,
 					false,
 					&default_theme,
-					0
+					0,
+					&[],
+					&[]
 				)
 				.spans
 				.last()
@@ -1020,7 +1862,15 @@ mod tests {
 
 			assert_eq!(
 				DiffComponent::get_line_to_add(
-					4, &diff_line, false, false, false, &theme, 0
+					4,
+					&diff_line,
+					false,
+					false,
+					false,
+					&theme,
+					0,
+					&[],
+					&[]
 				)
 				.spans
 				.last()
@@ -1032,4 +1882,96 @@ mod tests {
 			);
 		}
 	}
+
+	#[test]
+	fn test_word_changes() {
+		let diff_line = DiffLine {
+			content: "let foo = bar;".into(),
+			line_type: DiffLineType::Delete,
+			position: DiffLinePosition::default(),
+		};
+		let theme = Rc::new(Theme::default());
+		let style = theme.diff_line(diff_line.line_type, false);
+		let word = theme.diff_word(diff_line.line_type, false);
+
+		let spans = |scrolled_right| {
+			DiffComponent::get_line_to_add(
+				4,
+				&diff_line,
+				false,
+				false,
+				false,
+				&theme,
+				scrolled_right,
+				&[4..7, 10..13],
+				&[],
+			)
+			.spans
+			.into_iter()
+			.skip(1)
+			.map(|span| (span.content.to_string(), span.style))
+			.collect::<Vec<_>>()
+		};
+
+		assert_eq!(
+			spans(0),
+			vec![
+				(String::from("let "), style),
+				(String::from("foo"), word),
+				(String::from(" = "), style),
+				(String::from("bar"), word),
+				(String::from(";\n"), style),
+			]
+		);
+		assert_eq!(
+			spans(5),
+			vec![
+				(String::from("oo"), word),
+				(String::from(" = "), style),
+				(String::from("bar"), word),
+				(String::from(";\n"), style),
+			]
+		);
+	}
+	#[test]
+	fn test_search_matches() {
+		let diff_line = DiffLine {
+			content: "let foo = bar;".into(),
+			line_type: DiffLineType::Add,
+			position: DiffLinePosition::default(),
+		};
+		let theme = Rc::new(Theme::default());
+		let style = theme.diff_line(diff_line.line_type, false);
+		let word = theme.diff_word(diff_line.line_type, false);
+
+		let spans = DiffComponent::get_line_to_add(
+			4,
+			&diff_line,
+			false,
+			false,
+			false,
+			&theme,
+			0,
+			&[0..3, 4..7],
+			&[(5..7, false), (10..13, true)],
+		)
+		.spans
+		.into_iter()
+		.skip(1)
+		.map(|span| (span.content.to_string(), span.style))
+		.collect::<Vec<_>>();
+
+		assert_eq!(
+			spans,
+			vec![
+				(String::from("let"), word),
+				(String::from(" "), style),
+				(String::from("f"), word),
+				(String::from("oo"), theme.diff_search_match(false)),
+				(String::from(" = "), style),
+				(String::from("bar"), theme.diff_search_match(true)),
+				(String::from(";\n"), style),
+			]
+		);
+	}
 }
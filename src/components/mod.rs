@@ -40,7 +40,9 @@ mod command;
 mod commit_details;
 mod commitlist;
 mod cred;
+mod cred_prompt;
 mod diff;
+mod process_output;
 mod revision_files;
 mod status_tree;
 mod syntax_text;
@@ -48,19 +50,22 @@ mod textinput;
 mod utils;
 
 pub use self::status_tree::StatusTreeComponent;
-pub use changes::ChangesComponent;
+pub use changes::{ChangesComponent, SubmoduleSection};
 pub use command::{CommandInfo, CommandText};
 pub use commit_details::CommitDetailsComponent;
 pub use commitlist::CommitList;
 pub use cred::CredComponent;
+pub use cred_prompt::CredPromptComponent;
 pub use diff::DiffComponent;
+pub use process_output::ProcessOutputComponent;
 pub use revision_files::RevisionFilesComponent;
 pub use syntax_text::SyntaxTextComponent;
 pub use textinput::{InputType, TextInputComponent};
 pub use utils::{
-	filetree::FileTreeItemKind, logitems::ItemBatch,
-	scroll_vertical::VerticalScroll, string_width_align,
-	time_to_string,
+	commit_msg::LineLimits, filetree::FileTreeItemKind,
+	logitems::ItemBatch, scroll_vertical::VerticalScroll,
+	statustree::folder_depth, string_width_align, time_to_string,
+	CommitCopy,
 };
 
 use crate::ui::style::Theme;
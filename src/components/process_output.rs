@@ -0,0 +1,196 @@
+use super::{utils::scroll_vertical::VerticalScroll, ScrollType};
+use crate::{
+	keys::{key_match, SharedKeyConfig},
+	ui::{style::SharedTheme, AnsiParser},
+};
+use asyncgit::AsyncProcessJob;
+use crossterm::event::Event;
+use ratatui::{
+	layout::Rect,
+	text::{Line, Text},
+	widgets::Paragraph,
+	Frame,
+};
+use std::{cell::Cell, collections::VecDeque};
+
+/// scrollback of the parsed lines, matches what a job keeps
+const MAX_LINES: usize = 10_000;
+
+/// colorized output of an external process, sticks to the bottom
+/// while new output arrives unless scrolled up
+pub struct ProcessOutputComponent {
+	lines: VecDeque<Line<'static>>,
+	/// lines not terminated yet
+	partial: Vec<Line<'static>>,
+	/// complete lines taken from the job so far
+	received: usize,
+	parser: AnsiParser,
+	scroll: VerticalScroll,
+	follow: Cell<bool>,
+	current_height: Cell<usize>,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl ProcessOutputComponent {
+	///
+	pub fn new(
+		theme: SharedTheme,
+		key_config: SharedKeyConfig,
+	) -> Self {
+		Self {
+			lines: VecDeque::new(),
+			partial: Vec::new(),
+			received: 0,
+			parser: AnsiParser::default(),
+			scroll: VerticalScroll::new(),
+			follow: Cell::new(true),
+			current_height: Cell::new(0),
+			theme,
+			key_config,
+		}
+	}
+
+	///
+	pub fn clear(&mut self) {
+		self.lines.clear();
+		self.partial.clear();
+		self.received = 0;
+		self.parser = AnsiParser::default();
+		self.scroll.reset();
+		self.follow.set(true);
+	}
+
+	/// the output without its styling
+	pub fn text(&self) -> String {
+		self.lines
+			.iter()
+			.chain(&self.partial)
+			.map(|line| {
+				line.spans
+					.iter()
+					.map(|span| span.content.as_ref())
+					.collect::<String>()
+			})
+			.collect::<Vec<_>>()
+			.join("\n")
+	}
+
+	/// keeps the output so far below which the next job's output
+	/// is appended
+	pub fn start_job(&mut self) {
+		self.lines.extend(self.partial.drain(..));
+		self.received = 0;
+	}
+
+	/// takes the output `job` received since the last update
+	pub fn update(&mut self, job: &AsyncProcessJob) {
+		let (lines, len, partial) = job.output(|output| {
+			(
+				output.lines_since(self.received),
+				output.len(),
+				output.partial(),
+			)
+		});
+		self.received = len;
+
+		let base = self.theme.text(true, false);
+
+		for line in lines {
+			self.lines.push_back(self.parser.parse_line(&line, base));
+			if self.lines.len() > MAX_LINES {
+				self.lines.pop_front();
+			}
+		}
+
+		// the style of unfinished lines must not leak into the next
+		// update, they are parsed again once complete
+		let mut parser = self.parser.clone();
+		self.partial = partial
+			.iter()
+			.map(|line| parser.parse_line(line, base))
+			.collect();
+	}
+
+	///
+	pub fn draw(&self, f: &mut Frame, area: Rect) {
+		let height = usize::from(area.height);
+		let line_count = self.lines.len() + self.partial.len();
+		let max_top = line_count.saturating_sub(height);
+
+		let top = if self.follow.get() {
+			max_top
+		} else {
+			self.scroll.get_top().min(max_top)
+		};
+		self.scroll.set_top(top, line_count, height);
+		self.current_height.set(height);
+
+		let lines: Vec<Line> = self
+			.lines
+			.iter()
+			.chain(self.partial.iter())
+			.skip(top)
+			.take(height)
+			.cloned()
+			.collect();
+
+		f.render_widget(Paragraph::new(Text::from(lines)), area);
+
+		self.scroll.draw(f, area, &self.theme);
+	}
+
+	/// scrolls the output, returns `true` if the event was used
+	pub fn event(&self, ev: &Event) -> bool {
+		let Event::Key(key) = ev else {
+			return false;
+		};
+
+		let keys = &self.key_config.keys;
+
+		let scroll = if key_match(key, keys.move_up) {
+			ScrollType::Up
+		} else if key_match(key, keys.move_down) {
+			ScrollType::Down
+		} else if key_match(key, keys.page_up) {
+			ScrollType::PageUp
+		} else if key_match(key, keys.page_down) {
+			ScrollType::PageDown
+		} else if key_match(key, keys.home)
+			|| key_match(key, keys.shift_up)
+		{
+			ScrollType::Home
+		} else if key_match(key, keys.end)
+			|| key_match(key, keys.shift_down)
+		{
+			ScrollType::End
+		} else {
+			return false;
+		};
+
+		self.scroll_to(scroll);
+
+		true
+	}
+
+	fn scroll_to(&self, scroll: ScrollType) {
+		let line_count = self.lines.len() + self.partial.len();
+		let height = self.current_height.get();
+		let max_top = line_count.saturating_sub(height);
+		let top = self.scroll.get_top();
+		let page = height.saturating_sub(1).max(1);
+
+		let top = match scroll {
+			ScrollType::Up => top.saturating_sub(1),
+			ScrollType::Down => top.saturating_add(1),
+			ScrollType::PageUp => top.saturating_sub(page),
+			ScrollType::PageDown => top.saturating_add(page),
+			ScrollType::Home => 0,
+			_ => max_top,
+		}
+		.min(max_top);
+
+		self.scroll.set_top(top, line_count, height);
+		self.follow.set(top == max_top);
+	}
+}
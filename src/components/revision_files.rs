@@ -1,36 +1,45 @@
 use super::{
-	utils::scroll_vertical::VerticalScroll, CommandBlocking,
-	CommandInfo, Component, DrawableComponent, EventState,
-	FuzzyFinderTarget, SyntaxTextComponent,
+	utils::{
+		copy_paths, list_mouse, scroll_vertical::VerticalScroll,
+		ListMouse,
+	},
+	CommandBlocking, CommandInfo, Component, DrawableComponent,
+	EventState, FuzzyFinderTarget, SyntaxTextComponent,
 };
 use crate::{
 	app::Environment,
 	keys::{key_match, SharedKeyConfig},
 	popups::{BlameFileOpen, FileRevOpen},
-	queue::{InternalEvent, Queue, StackablePopupOpen},
+	queue::{AppTabs, InternalEvent, Queue, StackablePopupOpen},
 	strings::{self, order, symbol},
 	try_or_popup,
 	ui::{self, common_nav, style::SharedTheme},
-	AsyncNotification,
+	AsyncAppNotification, AsyncNotification,
 };
 use anyhow::Result;
 use asyncgit::{
-	asyncjob::AsyncSingleJob,
+	asyncjob::{AsyncJob, AsyncSingleJob, RunParams},
 	sync::{
-		get_commit_info, CommitId, CommitInfo, RepoPathRef, TreeFile,
+		get_commit_info, get_head, tree_file_write, CommitId,
+		CommitInfo, FileMode, LogFilterCriteria, RepoPathRef,
+		TreeFile,
 	},
 	AsyncGitNotification, AsyncTreeFilesJob,
 };
-use crossterm::event::Event;
-use filetreelist::{FileTree, FileTreeItem};
+use crossterm::event::{Event, MouseEvent};
+use filetreelist::{FileTree, FileTreeItem, MoveSelection};
 use ratatui::{
 	layout::{Constraint, Direction, Layout, Rect},
 	text::Span,
 	widgets::{Block, Borders},
 	Frame,
 };
-use std::{borrow::Cow, fmt::Write};
-use std::{collections::BTreeSet, path::Path};
+use std::{borrow::Cow, cell::Cell, fmt::Write};
+use std::{
+	collections::BTreeSet,
+	path::{Path, PathBuf},
+	sync::{Arc, Mutex},
+};
 use unicode_truncate::UnicodeTruncateStr;
 use unicode_width::UnicodeWidthStr;
 
@@ -46,13 +55,18 @@ pub struct RevisionFilesComponent {
 	//TODO: store TreeFiles in `tree`
 	files: Option<Vec<TreeFile>>,
 	async_treefiles: AsyncSingleJob<AsyncTreeFilesJob>,
+	treefiles_job: Option<AsyncTreeFilesJob>,
+	/// builds the tree off the ui thread, it is swapped in once done
+	async_tree: AsyncSingleJob<AsyncFileTreeJob>,
 	current_file: SyntaxTextComponent,
 	tree: FileTree,
 	scroll: VerticalScroll,
+	tree_area: Cell<Rect>,
 	visible: bool,
 	revision: Option<CommitInfo>,
 	focus: Focus,
 	key_config: SharedKeyConfig,
+	finder_origin: Option<PathBuf>,
 }
 
 impl RevisionFilesComponent {
@@ -62,15 +76,19 @@ impl RevisionFilesComponent {
 			queue: env.queue.clone(),
 			tree: FileTree::default(),
 			scroll: VerticalScroll::new(),
+			tree_area: Cell::new(Rect::default()),
 			current_file: SyntaxTextComponent::new(env),
 			theme: env.theme.clone(),
 			files: None,
 			async_treefiles: AsyncSingleJob::new(
 				env.sender_git.clone(),
 			),
+			treefiles_job: None,
+			async_tree: AsyncSingleJob::new(env.sender_app.clone()),
 			revision: None,
 			focus: Focus::Tree,
 			key_config: env.key_config.clone(),
+			finder_origin: None,
 			repo: env.repo.clone(),
 			visible: false,
 		}
@@ -95,6 +113,13 @@ impl RevisionFilesComponent {
 		Ok(())
 	}
 
+	/// requests the files of the revision again
+	pub fn refresh(&mut self) {
+		if let Some(revision) = &self.revision {
+			self.request_files(revision.id);
+		}
+	}
+
 	///
 	pub const fn revision(&self) -> Option<&CommitInfo> {
 		self.revision.as_ref()
@@ -104,41 +129,71 @@ impl RevisionFilesComponent {
 	pub fn update(&mut self, ev: AsyncNotification) -> Result<()> {
 		self.current_file.update(ev);
 
-		if matches!(
-			ev,
-			AsyncNotification::Git(AsyncGitNotification::TreeFiles)
-		) {
-			self.refresh_files()?;
+		match ev {
+			AsyncNotification::Git(
+				AsyncGitNotification::TreeFiles,
+			) => {
+				self.build_tree();
+			}
+			AsyncNotification::App(
+				AsyncAppNotification::FileTree,
+			) => {
+				self.refresh_files()?;
+			}
+			_ => (),
 		}
 
 		Ok(())
 	}
 
+	fn build_tree(&mut self) {
+		let Some(result) = self
+			.treefiles_job
+			.as_ref()
+			.and_then(AsyncTreeFilesJob::result)
+		else {
+			return;
+		};
+
+		self.treefiles_job = None;
+
+		match result.result {
+			Ok(Some(files)) => {
+				self.async_tree.spawn(AsyncFileTreeJob::new(
+					result.commit,
+					files,
+				));
+			}
+			Ok(None) => (),
+			Err(e) => log::error!("tree files error: {e}"),
+		}
+	}
+
 	fn refresh_files(&mut self) -> Result<(), anyhow::Error> {
-		if let Some(last) = self.async_treefiles.take_last() {
-			if let Some(result) = last.result() {
-				if self
-					.revision
-					.as_ref()
-					.is_some_and(|commit| commit.id == result.commit)
-				{
-					if let Ok(last) = result.result {
-						let filenames: Vec<&Path> = last
-							.iter()
-							.map(|f| f.path.as_path())
-							.collect();
-						self.tree = FileTree::new(
-							&filenames,
-							&BTreeSet::new(),
-						)?;
-						self.tree.collapse_but_root();
-
-						self.files = Some(last);
-					}
-				} else if let Some(rev) = &self.revision {
-					self.request_files(rev.id);
-				}
+		let Some((commit, files, tree)) =
+			self.async_tree.take_last().and_then(|job| job.result())
+		else {
+			return Ok(());
+		};
+
+		if self
+			.revision
+			.as_ref()
+			.is_some_and(|revision| revision.id == commit)
+		{
+			let selected = self
+				.tree
+				.selected_item()
+				.map(|item| item.full_path().to_path_buf());
+
+			self.tree = tree?;
+
+			// a new revision keeps what was selected
+			if let Some(selected) = selected {
+				self.tree.select_file(&selected);
 			}
+
+			self.files = Some(files);
 		}
 
 		Ok(())
@@ -148,6 +203,7 @@ impl RevisionFilesComponent {
 	pub fn any_work_pending(&self) -> bool {
 		self.current_file.any_work_pending()
 			|| self.async_treefiles.is_pending()
+			|| self.async_tree.is_pending()
 	}
 
 	fn tree_item_to_span<'a>(
@@ -156,8 +212,16 @@ impl RevisionFilesComponent {
 		width: usize,
 		selected: bool,
 	) -> Span<'a> {
-		let path = item.info().path_str();
-		let indent = item.info().indent();
+		let info = item.info();
+		let path = format!(
+			"{}{}",
+			info.path_str(),
+			strings::file_mode_suffix(
+				FileMode::from_raw(info.mode()),
+				info.link_target()
+			)
+		);
+		let indent = info.indent();
 
 		let indent_str = if indent == 0 {
 			String::new()
@@ -168,16 +232,16 @@ impl RevisionFilesComponent {
 		let is_path = item.kind().is_path();
 		let path_arrow = if is_path {
 			if item.kind().is_path_collapsed() {
-				symbol::FOLDER_ICON_COLLAPSED
+				theme.glyphs().folder_collapsed
 			} else {
-				symbol::FOLDER_ICON_EXPANDED
+				theme.glyphs().folder_expanded
 			}
 		} else {
 			symbol::EMPTY_STR
 		};
 
-		let available_width =
-			width.saturating_sub(indent_str.len() + path_arrow.len());
+		let available_width = width
+			.saturating_sub(indent_str.len() + path_arrow.width());
 
 		let path = format!(
 			"{indent_str}{path_arrow}{path:available_width$}"
@@ -212,8 +276,31 @@ impl RevisionFilesComponent {
 		})
 	}
 
-	fn open_finder(&self) {
+	/// the log tab filtered to the commits touching the selected
+	/// file or folder
+	fn goto_log(&self) -> bool {
+		self.selected_item_path()
+			.filter(|path| *path != ".")
+			.is_some_and(|path| {
+				self.queue
+					.push(InternalEvent::TabSwitch(AppTabs::Log));
+				self.queue.push(InternalEvent::LogFilter(
+					LogFilterCriteria {
+						path: path.to_string(),
+						..LogFilterCriteria::default()
+					},
+				));
+
+				true
+			})
+	}
+
+	fn open_finder(&mut self) {
 		if let Some(files) = self.files.clone() {
+			self.finder_origin = self
+				.tree
+				.selected_item()
+				.map(|item| item.full_path().to_path_buf());
 			self.queue.push(InternalEvent::OpenFuzzyFinder(
 				files
 					.iter()
@@ -236,13 +323,26 @@ impl RevisionFilesComponent {
 		}
 	}
 
+	/// selects `file` and scrolls its content to `line`
+	pub fn find_line(&mut self, file: &Path, line: usize) {
+		self.find_file(file);
+		self.current_file.scroll_to_line(line);
+	}
+
+	/// restores the selection from before the finder was opened
+	pub fn find_file_cancel(&mut self) {
+		if let Some(origin) = self.finder_origin.take() {
+			self.find_file(&origin);
+		}
+	}
+
 	fn selected_file_path_with_prefix(&self) -> Option<String> {
 		self.tree
 			.selected_file()
 			.map(|file| file.full_path_str().to_string())
 	}
 
-	fn selected_file_path(&self) -> Option<String> {
+	pub fn selected_file_path(&self) -> Option<String> {
 		self.tree.selected_file().map(|file| {
 			file.full_path_str()
 				.strip_prefix("./")
@@ -251,6 +351,91 @@ impl RevisionFilesComponent {
 		})
 	}
 
+	/// repo relative path of the selected file or folder
+	fn selected_item_path(&self) -> Option<&str> {
+		self.tree.selected_item().map(|item| {
+			item.full_path_str().strip_prefix("./").unwrap_or(".")
+		})
+	}
+
+	fn copy_selected_path(&self, absolute: bool) {
+		if let Some(path) = self.selected_item_path() {
+			try_or_popup!(
+				self,
+				strings::POPUP_FAIL_COPY,
+				copy_paths(
+					&self.queue,
+					&self.repo.borrow(),
+					&[path],
+					absolute,
+				)
+			);
+		}
+	}
+
+	fn selected_tree_file(&self) -> Option<&TreeFile> {
+		let file = self.selected_file_path_with_prefix()?;
+		let path = Path::new(&file);
+
+		self.files.as_ref()?.iter().find(|f| f.path == path)
+	}
+
+	/// opens the work dir file if we browse `HEAD`, otherwise a
+	/// read-only copy of the file in the browsed revision
+	fn edit_file(&self) -> bool {
+		let Some(file) = self.selected_file_path_with_prefix() else {
+			return false;
+		};
+
+		let revision = self.revision.as_ref().map(|r| r.id);
+		let head = get_head(&self.repo.borrow()).ok();
+
+		match (revision, self.selected_tree_file()) {
+			(Some(revision), Some(item))
+				if Some(revision) != head =>
+			{
+				let name = item
+					.path
+					.file_name()
+					.map(|name| name.to_string_lossy().to_string())
+					.unwrap_or_default();
+				let dest = std::env::temp_dir().join(format!(
+					"gitui-{}-{name}",
+					revision.get_short_string()
+				));
+
+				if let Err(e) =
+					tree_file_write(&self.repo.borrow(), item, &dest)
+				{
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!("{}\n{e}", strings::POPUP_FAIL_EDIT),
+					));
+					return true;
+				}
+
+				self.queue.push(InternalEvent::ShowInfoMsg(
+					strings::edit_revision_copy(
+						file.trim_start_matches("./"),
+						&revision.get_short_string(),
+					),
+				));
+				self.queue.push(InternalEvent::OpenExternalEditor(
+					Some(dest.to_string_lossy().to_string()),
+				));
+			}
+			_ => {
+				//Note: switch to status tab so its clear we are
+				// not altering a file inside a revision here
+				self.queue.push(InternalEvent::TabSwitchStatus);
+				self.queue.push(InternalEvent::OpenExternalEditor(
+					Some(file),
+				));
+			}
+		}
+
+		true
+	}
+
 	fn selection_changed(&mut self) {
 		//TODO: retrieve TreeFile from tree datastructure
 		if let Some(file) = self.selected_file_path_with_prefix() {
@@ -276,14 +461,15 @@ impl RevisionFilesComponent {
 		let tree_width = usize::from(area.width);
 
 		self.tree.window_height.set(Some(tree_height));
+		self.tree_area.set(area);
 
 		self.tree.visual_selection().map_or_else(
 			|| {
 				self.scroll.reset();
 			},
 			|selection| {
-				self.scroll.update(
-					selection.index,
+				self.scroll.set_top(
+					self.tree.calc_scroll(self.scroll.get_top()),
 					selection.count,
 					tree_height,
 				);
@@ -321,9 +507,7 @@ impl RevisionFilesComponent {
 				area,
 				block,
 				vec![Span::styled(
-					Cow::from(strings::loading_text(
-						&self.key_config,
-					)),
+					Cow::from(self.loading_text()),
 					self.theme.text(false, false),
 				)]
 				.into_iter(),
@@ -337,6 +521,49 @@ impl RevisionFilesComponent {
 		Ok(())
 	}
 
+	/// wheel moves the selection, a click selects the line under the
+	/// cursor and a click on the selected folder toggles it
+	fn tree_mouse(&mut self, mouse: MouseEvent) -> bool {
+		let Some(action) = list_mouse(self.tree_area.get(), mouse)
+		else {
+			return false;
+		};
+
+		if !matches!(self.focus, Focus::Tree) {
+			self.focus = Focus::Tree;
+			self.current_file.focus(false);
+			self.focus(false);
+		}
+
+		let changed = match action {
+			ListMouse::ScrollUp => {
+				self.tree.move_selection(MoveSelection::Up)
+			}
+			ListMouse::ScrollDown => {
+				self.tree.move_selection(MoveSelection::Down)
+			}
+			ListMouse::Click(line) => {
+				match self.tree.visual_index_to_absolute(
+					self.scroll.get_top() + line,
+				) {
+					Some(index)
+						if self.tree.selection() == Some(index) =>
+					{
+						self.tree.toggle_selected_folder()
+					}
+					Some(index) => self.tree.select(index),
+					None => false,
+				}
+			}
+		};
+
+		if changed {
+			self.selection_changed();
+		}
+
+		true
+	}
+
 	fn title_within(&self, tree_width: usize) -> Result<String> {
 		let mut title = String::from("Files at");
 		let message = self.revision.as_ref().and_then(|c| {
@@ -347,7 +574,7 @@ impl RevisionFilesComponent {
 		});
 
 		if let Some(message) = message {
-			const ELLIPSIS: char = '\u{2026}'; // …
+			let ellipsis = self.theme.glyphs().ellipsis;
 
 			let available = tree_width
 				.saturating_sub(title.width())
@@ -362,21 +589,41 @@ impl RevisionFilesComponent {
 					title,
 					" [{}{}]",
 					message.unicode_truncate(available - 1).0,
-					ELLIPSIS
+					ellipsis
 				)?;
 			} else {
-				title.push(ELLIPSIS);
+				title.push(ellipsis);
 			}
 		}
 
 		Ok(title)
 	}
 
-	fn request_files(&self, commit: CommitId) {
-		self.async_treefiles.spawn(AsyncTreeFilesJob::new(
+	/// how many files are read already while they are
+	fn loading_text(&self) -> String {
+		match self.async_treefiles.progress() {
+			Some(read)
+				if read > 0 && self.treefiles_job.is_some() =>
+			{
+				strings::loading_files_text(read)
+			}
+			_ => strings::loading_text(&self.key_config),
+		}
+	}
+
+	/// the files of the revision requested before are not needed
+	/// anymore
+	fn request_files(&mut self, commit: CommitId) {
+		if let Some(job) = self.treefiles_job.take() {
+			job.cancel();
+		}
+
+		let job = AsyncTreeFilesJob::new(
 			self.repo.borrow().clone(),
 			commit,
-		));
+		);
+		self.async_treefiles.spawn(job.clone());
+		self.treefiles_job = Some(job);
 	}
 }
 
@@ -438,10 +685,31 @@ impl Component for RevisionFilesComponent {
 				)
 				.order(order::RARE_ACTION),
 			);
+			out.push(
+				CommandInfo::new(
+					strings::commands::files_goto_log(
+						&self.key_config,
+					),
+					self.selected_item_path()
+						.is_some_and(|path| path != "."),
+					true,
+				)
+				.order(order::RARE_ACTION),
+			);
 			out.push(
 				CommandInfo::new(
 					strings::commands::copy_path(&self.key_config),
-					self.tree.selected_file().is_some(),
+					self.tree.selected_item().is_some(),
+					true,
+				)
+				.order(order::RARE_ACTION),
+			);
+			out.push(
+				CommandInfo::new(
+					strings::commands::copy_absolute_path(
+						&self.key_config,
+					),
+					self.tree.selected_item().is_some(),
 					true,
 				)
 				.order(order::RARE_ACTION),
@@ -462,6 +730,10 @@ impl Component for RevisionFilesComponent {
 			return Ok(EventState::NotConsumed);
 		}
 
+		if let Event::Mouse(mouse) = event {
+			return Ok(self.tree_mouse(*mouse).into());
+		}
+
 		if let Event::Key(key) = event {
 			let is_tree_focused = matches!(self.focus, Focus::Tree);
 			if is_tree_focused
@@ -482,6 +754,14 @@ impl Component for RevisionFilesComponent {
 					self.hide();
 					return Ok(EventState::Consumed);
 				}
+			} else if key_match(
+				key,
+				self.key_config.keys.files_goto_log,
+			) {
+				if is_tree_focused && self.goto_log() {
+					self.hide();
+					return Ok(EventState::Consumed);
+				}
 			} else if key_match(key, self.key_config.keys.move_right)
 			{
 				if is_tree_focused {
@@ -502,26 +782,25 @@ impl Component for RevisionFilesComponent {
 					self.open_finder();
 					return Ok(EventState::Consumed);
 				}
+			} else if key_match(key, self.key_config.keys.file_grep) {
+				if let Some(revision) = &self.revision {
+					self.queue.push(InternalEvent::OpenFileGrep(
+						revision.id,
+					));
+					return Ok(EventState::Consumed);
+				}
 			} else if key_match(key, self.key_config.keys.edit_file) {
-				if let Some(file) =
-					self.selected_file_path_with_prefix()
-				{
-					//Note: switch to status tab so its clear we are
-					// not altering a file inside a revision here
-					self.queue.push(InternalEvent::TabSwitchStatus);
-					self.queue.push(
-						InternalEvent::OpenExternalEditor(Some(file)),
-					);
+				if self.edit_file() {
 					return Ok(EventState::Consumed);
 				}
 			} else if key_match(key, self.key_config.keys.copy) {
-				if let Some(file) = self.selected_file_path() {
-					try_or_popup!(
-						self,
-						strings::POPUP_FAIL_COPY,
-						crate::clipboard::copy_string(&file)
-					);
-				}
+				self.copy_selected_path(false);
+				return Ok(EventState::Consumed);
+			} else if key_match(
+				key,
+				self.key_config.keys.copy_absolute_path,
+			) {
+				self.copy_selected_path(true);
 				return Ok(EventState::Consumed);
 			} else if !is_tree_focused {
 				return self.current_file.event(event);
@@ -546,6 +825,100 @@ impl Component for RevisionFilesComponent {
 	}
 }
 
+enum TreeJobState {
+	Request {
+		commit: CommitId,
+		files: Vec<TreeFile>,
+	},
+	Response {
+		commit: CommitId,
+		files: Vec<TreeFile>,
+		tree: Result<FileTree>,
+	},
+}
+
+/// builds the `FileTree` of the files of a revision, large revisions
+/// take a while
+#[derive(Clone, Default)]
+struct AsyncFileTreeJob {
+	state: Arc<Mutex<Option<TreeJobState>>>,
+}
+
+impl AsyncFileTreeJob {
+	fn new(commit: CommitId, files: Vec<TreeFile>) -> Self {
+		Self {
+			state: Arc::new(Mutex::new(Some(
+				TreeJobState::Request { commit, files },
+			))),
+		}
+	}
+
+	fn result(
+		&self,
+	) -> Option<(CommitId, Vec<TreeFile>, Result<FileTree>)> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(TreeJobState::Response {
+				commit,
+				files,
+				tree,
+			}) = state.take()
+			{
+				return Some((commit, files, tree));
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncFileTreeJob {
+	type Notification = AsyncAppNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> asyncgit::Result<Self::Notification> {
+		let mut state_mutex = self.state.lock()?;
+
+		if let Some(state) = state_mutex.take() {
+			*state_mutex = Some(match state {
+				TreeJobState::Request { commit, files } => {
+					let filenames: Vec<&Path> = files
+						.iter()
+						.map(|f| f.path.as_path())
+						.collect();
+
+					let tree =
+						FileTree::new(&filenames, &BTreeSet::new())
+							.map(|mut tree| {
+								tree.collapse_but_root();
+								for file in &files {
+									tree.set_file_mode(
+										&file.path,
+										u32::try_from(file.filemode)
+											.unwrap_or_default(),
+										file.symlink_target.clone(),
+									);
+								}
+								tree
+							})
+							.map_err(Into::into);
+
+					TreeJobState::Response {
+						commit,
+						files,
+						tree,
+					}
+				}
+				response @ TreeJobState::Response { .. } => response,
+			});
+		}
+
+		Ok(AsyncAppNotification::FileTree)
+	}
+}
+
 //TODO: reuse for other tree usages
 fn tree_nav_cmds(
 	tree: &FileTree,
@@ -581,3 +954,140 @@ fn tree_nav(
 		false
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use asyncgit::sync::{tree_files, RepoPath};
+	use std::{fs, process::Command};
+	use tempfile::TempDir;
+
+	fn git(path: &Path, args: &[&str]) -> String {
+		let output = Command::new("git")
+			.args(["-c", "user.name=name", "-c", "user.email=email"])
+			.args(args)
+			.current_dir(path)
+			.output()
+			.unwrap();
+		assert!(output.status.success());
+
+		String::from_utf8(output.stdout).unwrap().trim().to_string()
+	}
+
+	/// `a.txt` changes in the second commit
+	fn repo() -> TempDir {
+		let td = TempDir::new().unwrap();
+		fs::create_dir(td.path().join("sub")).unwrap();
+		fs::write(td.path().join("sub/b.txt"), "b\n").unwrap();
+		fs::write(td.path().join("a.txt"), "old\n").unwrap();
+		git(td.path(), &["init", "-q"]);
+		git(td.path(), &["add", "."]);
+		git(td.path(), &["commit", "-q", "-m", "1"]);
+		fs::write(td.path().join("a.txt"), "new\n").unwrap();
+		git(td.path(), &["commit", "-q", "-am", "2"]);
+
+		td
+	}
+
+	fn browse(
+		env: &Environment,
+		commit: CommitId,
+	) -> RevisionFilesComponent {
+		let mut component = RevisionFilesComponent::new(env);
+		let files = tree_files(&env.repo.borrow(), commit).unwrap();
+		let paths: Vec<&Path> =
+			files.iter().map(|f| f.path.as_path()).collect();
+
+		component.tree =
+			FileTree::new(&paths, &BTreeSet::new()).unwrap();
+		component.files = Some(files);
+		component.revision = Some(
+			get_commit_info(&env.repo.borrow(), &commit).unwrap(),
+		);
+
+		component
+	}
+
+	#[test]
+	fn test_edit_file_of_revision() {
+		let td = repo();
+		let env = Environment::test_env();
+		*env.repo.borrow_mut() = RepoPath::Path(td.path().into());
+		let first = CommitId::from_str_unchecked(&git(
+			td.path(),
+			&["rev-parse", "HEAD~1"],
+		))
+		.unwrap();
+
+		let component = browse(&env, first);
+		assert!(!component.edit_file());
+
+		let mut component = browse(&env, first);
+		assert!(component.tree.select_file(Path::new("./a.txt")));
+		assert!(component.edit_file());
+
+		assert!(matches!(
+			env.queue.pop(),
+			Some(InternalEvent::ShowInfoMsg(_))
+		));
+		let copy = match env.queue.pop() {
+			Some(InternalEvent::OpenExternalEditor(Some(copy))) => {
+				Some(copy)
+			}
+			_ => None,
+		}
+		.unwrap();
+		assert_eq!(fs::read_to_string(&copy).unwrap(), "old\n");
+		assert!(fs::metadata(&copy)
+			.unwrap()
+			.permissions()
+			.readonly());
+		fs::remove_file(copy).unwrap();
+
+		// the work dir file itself at `HEAD`
+		let head = get_head(&env.repo.borrow()).unwrap();
+		let mut component = browse(&env, head);
+		assert!(component.tree.select_file(Path::new("./a.txt")));
+		assert!(component.edit_file());
+
+		assert!(matches!(
+			env.queue.pop(),
+			Some(InternalEvent::TabSwitchStatus)
+		));
+		assert!(matches!(
+			env.queue.pop(),
+			Some(InternalEvent::OpenExternalEditor(Some(file)))
+				if file == "./a.txt"
+		));
+	}
+
+	#[test]
+	fn test_goto_log() {
+		let td = repo();
+		let env = Environment::test_env();
+		*env.repo.borrow_mut() = RepoPath::Path(td.path().into());
+		let head = get_head(&env.repo.borrow()).unwrap();
+		let mut component = browse(&env, head);
+
+		// the root is the whole log already
+		assert!(!component.goto_log());
+		assert!(env.queue.pop().is_none());
+
+		for (selected, path) in
+			[("./sub", "sub"), ("./sub/b.txt", "sub/b.txt")]
+		{
+			assert!(component.tree.select_file(Path::new(selected)));
+			assert!(component.goto_log());
+
+			assert!(matches!(
+				env.queue.pop(),
+				Some(InternalEvent::TabSwitch(AppTabs::Log))
+			));
+			assert!(matches!(
+				env.queue.pop(),
+				Some(InternalEvent::LogFilter(filter))
+					if filter.path == path
+			));
+		}
+	}
+}
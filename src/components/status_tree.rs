@@ -1,7 +1,10 @@
 use super::{
 	utils::{
+		copy_paths,
 		filetree::{FileTreeItem, FileTreeItemKind},
+		list_mouse,
 		statustree::{MoveSelection, StatusTree},
+		ListMouse,
 	},
 	CommandBlocking, DrawableComponent,
 };
@@ -12,13 +15,23 @@ use crate::{
 	popups::{BlameFileOpen, FileRevOpen},
 	queue::{InternalEvent, NeedsUpdate, Queue, StackablePopupOpen},
 	strings::{self, order},
+	try_or_popup,
 	ui::{self, style::SharedTheme},
 };
 use anyhow::Result;
-use asyncgit::{hash, sync::CommitId, StatusItem, StatusItemType};
-use crossterm::event::Event;
+use asyncgit::{
+	hash,
+	sync::{CommitId, RepoPathRef},
+	StatusItem, StatusItemType,
+};
+use crossterm::event::{Event, KeyEvent, MouseEvent};
 use ratatui::{layout::Rect, text::Span, Frame};
-use std::{borrow::Cow, cell::Cell, path::Path};
+use std::{
+	borrow::Cow,
+	cell::Cell,
+	collections::{BTreeSet, HashSet},
+	path::Path,
+};
 
 //TODO: use new `filetreelist` crate
 
@@ -27,6 +40,7 @@ use std::{borrow::Cow, cell::Cell, path::Path};
 pub struct StatusTreeComponent {
 	title: String,
 	tree: StatusTree,
+	repo: RepoPathRef,
 	pending: bool,
 	current_hash: u64,
 	focused: bool,
@@ -35,8 +49,13 @@ pub struct StatusTreeComponent {
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
 	scroll_top: Cell<usize>,
+	area: Cell<Rect>,
 	visible: bool,
 	revision: Option<CommitId>,
+	/// files marked as viewed, `None` if not reviewing
+	viewed: Option<HashSet<String>>,
+	/// files marked to act on several at once
+	marked: BTreeSet<String>,
 }
 
 impl StatusTreeComponent {
@@ -45,6 +64,7 @@ impl StatusTreeComponent {
 		Self {
 			title: title.to_string(),
 			tree: StatusTree::default(),
+			repo: env.repo.clone(),
 			current_hash: 0,
 			focused: focus,
 			show_selection: focus,
@@ -52,9 +72,12 @@ impl StatusTreeComponent {
 			theme: env.theme.clone(),
 			key_config: env.key_config.clone(),
 			scroll_top: Cell::new(0),
+			area: Cell::new(Rect::default()),
 			pending: true,
 			visible: false,
 			revision: None,
+			viewed: None,
+			marked: BTreeSet::new(),
 		}
 	}
 
@@ -64,22 +87,149 @@ impl StatusTreeComponent {
 
 	///
 	pub fn update(&mut self, list: &[StatusItem]) -> Result<()> {
+		self.update_collapsing(list, |_| false)
+	}
+
+	/// see [`StatusTree::update_collapsing`]
+	pub fn update_collapsing(
+		&mut self,
+		list: &[StatusItem],
+		collapse_new: impl Fn(&str) -> bool,
+	) -> Result<()> {
 		self.pending = false;
 
 		let new_hash = hash(list);
 		if self.current_hash != new_hash {
-			self.tree.update(list)?;
+			self.tree.update_collapsing(list, collapse_new)?;
 			self.current_hash = new_hash;
+			self.collapse_viewed();
+			self.marked.retain(|path| {
+				list.iter().any(|item| item.path == *path)
+			});
 		}
 
 		Ok(())
 	}
 
+	/// see [`StatusTree::update_roots`], the first root stands for the
+	/// repo, files below the others belong to other repos and are left
+	/// out of the actions on repo files
+	pub fn update_roots(
+		&mut self,
+		roots: &[(String, Vec<StatusItem>)],
+		collapse_new: impl Fn(&str) -> bool,
+	) -> Result<()> {
+		self.pending = false;
+
+		let new_hash = hash(roots);
+		if self.current_hash != new_hash {
+			self.tree.update_roots(roots, collapse_new)?;
+			self.current_hash = new_hash;
+			self.collapse_viewed();
+			let repo_files =
+				roots.first().map(|(_, list)| list.as_slice());
+			self.marked.retain(|path| {
+				repo_files
+					.unwrap_or_default()
+					.iter()
+					.any(|item| item.path == *path)
+			});
+		}
+
+		Ok(())
+	}
+
+	/// enables review mode: `viewed` files are checked off and
+	/// folders with nothing left to view get collapsed
+	pub fn set_viewed(&mut self, viewed: Option<HashSet<String>>) {
+		self.viewed = viewed;
+		self.collapse_viewed();
+	}
+
+	fn is_viewed(&self, path: &str) -> bool {
+		self.viewed
+			.as_ref()
+			.is_some_and(|viewed| viewed.contains(path))
+	}
+
+	fn collapse_viewed(&mut self) {
+		if let Some(viewed) = &self.viewed {
+			self.tree
+				.collapse_folders(|item| viewed.contains(&item.path));
+		}
+	}
+
+	/// `(viewed, total)` files while reviewing
+	pub fn viewed_count(&self) -> Option<(usize, usize)> {
+		self.viewed.as_ref().map(|viewed| {
+			let viewed = self
+				.tree
+				.tree
+				.items()
+				.iter()
+				.filter(|item| match &item.kind {
+					FileTreeItemKind::File(file) => {
+						viewed.contains(&file.path)
+					}
+					FileTreeItemKind::Path(_) => false,
+				})
+				.count();
+
+			(viewed, self.file_count())
+		})
+	}
+
+	/// moves the selection to the next file not viewed yet
+	pub fn select_next_unviewed(&mut self) -> bool {
+		let Some(viewed) = &self.viewed else {
+			return false;
+		};
+
+		let changed = self
+			.tree
+			.select_next_file(|item| viewed.contains(&item.path));
+
+		if changed {
+			self.queue.push(InternalEvent::Update(NeedsUpdate::DIFF));
+		}
+
+		changed
+	}
+
+	/// see [`StatusTree::select_first_file`], `false` if there is
+	/// no such file
+	pub fn select_first_file(
+		&mut self,
+		matches: impl Fn(&StatusItem) -> bool,
+	) -> bool {
+		if self.tree.select_first_file(&matches) {
+			self.queue.push(InternalEvent::Update(NeedsUpdate::DIFF));
+		}
+
+		self.selection_file().is_some_and(|file| matches(&file))
+	}
+
 	///
 	pub fn selection(&self) -> Option<FileTreeItem> {
 		self.tree.selected_item()
 	}
 
+	/// the selected file if it belongs to the repo
+	fn selection_repo_file(&self) -> Option<StatusItem> {
+		self.tree
+			.selected_item()
+			.filter(|item| item.info.in_repo())
+			.and_then(|item| match item.kind {
+				FileTreeItemKind::File(file) => Some(file),
+				FileTreeItemKind::Path(_) => None,
+			})
+	}
+
+	/// see [`StatusTree::selected_root_files`]
+	pub fn selection_root_files(&self) -> Option<Vec<StatusItem>> {
+		self.tree.selected_root_files()
+	}
+
 	///
 	pub fn selection_file(&self) -> Option<StatusItem> {
 		self.tree.selected_item().and_then(|f| {
@@ -91,6 +241,67 @@ impl StatusTreeComponent {
 		})
 	}
 
+	/// every file inside the selected folder (or the selected file)
+	pub fn selection_files(&self) -> Vec<StatusItem> {
+		self.tree.selected_files()
+	}
+
+	///
+	pub fn all_files(&self) -> Vec<StatusItem> {
+		self.tree.all_files()
+	}
+
+	/// marks the files of the selection, unmarks them if all of
+	/// them are marked already
+	pub fn toggle_mark(&mut self) {
+		let files = self.selection_files();
+		if files.iter().all(|file| self.marked.contains(&file.path)) {
+			for file in files {
+				self.marked.remove(&file.path);
+			}
+		} else {
+			self.marked
+				.extend(files.into_iter().map(|file| file.path));
+		}
+	}
+
+	/// whether all files of the selection are marked
+	pub fn selection_marked(&self) -> bool {
+		let files = self.selection_files();
+		!files.is_empty()
+			&& files
+				.iter()
+				.all(|file| self.marked.contains(&file.path))
+	}
+
+	///
+	pub fn marked_count(&self) -> usize {
+		self.marked.len()
+	}
+
+	/// the marked files, the ones of the selection if none are
+	/// marked
+	pub fn marked_or_selection_files(&self) -> Vec<StatusItem> {
+		if self.marked.is_empty() {
+			self.selection_files()
+		} else {
+			self.tree
+				.tree
+				.items()
+				.iter()
+				.filter(|item| item.info.in_repo())
+				.filter_map(|item| match &item.kind {
+					FileTreeItemKind::File(file)
+						if self.marked.contains(&file.path) =>
+					{
+						Some(file.clone())
+					}
+					_ => None,
+				})
+				.collect()
+		}
+	}
+
 	///
 	pub fn show_selection(&mut self, show: bool) {
 		self.show_selection = show;
@@ -111,6 +322,31 @@ impl StatusTreeComponent {
 		self.title = title;
 	}
 
+	/// all folders of the repo and the collapsed ones among them,
+	/// with their paths within their root
+	pub fn collapse_state(
+		&self,
+	) -> (BTreeSet<String>, BTreeSet<String>) {
+		let folders = || {
+			self.tree
+				.folders()
+				.filter(|(f, _)| f.in_repo() && !f.is_root())
+		};
+
+		(
+			folders().map(|(f, _)| f.path_in_root().into()).collect(),
+			folders()
+				.filter(|(_, collapsed)| *collapsed)
+				.map(|(f, _)| f.path_in_root().into())
+				.collect(),
+		)
+	}
+
+	/// see [`StatusTree::expand_to_depth`]
+	pub fn expand_to_depth(&mut self, depth: usize) {
+		self.tree.expand_to_depth(depth);
+	}
+
 	///
 	pub fn clear(&mut self) -> Result<()> {
 		self.current_hash = 0;
@@ -149,26 +385,26 @@ impl StatusTreeComponent {
 		}
 	}
 
+	#[allow(clippy::too_many_lines)]
 	fn item_to_text<'b>(
-		string: &str,
-		indent: usize,
-		visible: bool,
-		file_item_kind: &FileTreeItemKind,
+		draw_text_info: &TextDrawInfo,
+		viewed: bool,
+		marked: bool,
 		width: u16,
 		selected: bool,
 		theme: &'b SharedTheme,
-	) -> Option<Span<'b>> {
+	) -> Span<'b> {
+		let string = draw_text_info.name.as_str();
+		let indent = draw_text_info.indent as usize;
+		let conflicted = draw_text_info.conflicted;
+
 		let indent_str = if indent == 0 {
 			String::new()
 		} else {
 			format!("{:w$}", " ", w = indent * 2)
 		};
 
-		if !visible {
-			return None;
-		}
-
-		match file_item_kind {
+		match draw_text_info.item_kind {
 			FileTreeItemKind::File(status_item) => {
 				let status_char =
 					Self::item_status_char(status_item.status);
@@ -176,6 +412,60 @@ impl StatusTreeComponent {
 					.file_name()
 					.and_then(std::ffi::OsStr::to_str)
 					.expect("invalid path.");
+				let file = format!(
+					"{file}{}",
+					strings::file_mode_suffix(
+						status_item.mode,
+						status_item.symlink_target.as_deref()
+					)
+				);
+				let file = status_item.conflict.map_or_else(
+					|| Cow::from(file.as_str()),
+					|kind| {
+						Cow::from(format!(
+							"{file} ({}){}",
+							strings::conflict_type(kind),
+							if status_item.auto_resolved {
+								format!(
+									" {}",
+									strings::RERERE_AUTO_RESOLVED
+								)
+							} else {
+								String::new()
+							}
+						))
+					},
+				);
+				let submodule = status_item
+					.submodule
+					.map(|changes| {
+						format!(
+							" ({})",
+							strings::submodule_changes(changes)
+						)
+					})
+					.unwrap_or_default();
+				let file = if submodule.is_empty() {
+					file
+				} else {
+					Cow::from(format!("{file}{submodule}"))
+				};
+				let file = if status_item.lfs {
+					Cow::from(format!(
+						"{file} ({})",
+						strings::LFS_FILE
+					))
+				} else {
+					file
+				};
+				let file = if viewed || marked {
+					Cow::from(format!(
+						"{} {file}",
+						theme.glyphs().checkmark
+					))
+				} else {
+					file
+				};
 
 				let txt = if selected {
 					format!(
@@ -189,32 +479,52 @@ impl StatusTreeComponent {
 					format!("{status_char} {indent_str}{file}")
 				};
 
-				Some(Span::styled(
-					Cow::from(txt),
-					theme.item(status_item.status, selected),
-				))
+				let style = if viewed {
+					theme.text(false, selected)
+				} else {
+					theme.item(status_item.status, selected)
+				};
+
+				Span::styled(Cow::from(txt), style)
 			}
 
 			FileTreeItemKind::Path(path_collapsed) => {
-				let collapse_char =
-					if path_collapsed.0 { '▸' } else { '▾' };
+				let collapse_char = if path_collapsed.0 {
+					theme.glyphs().folder_collapsed
+				} else {
+					theme.glyphs().folder_expanded
+				};
+
+				// folders with a conflict somewhere below carry its
+				// marker
+				let status_char = if conflicted {
+					Self::item_status_char(StatusItemType::Conflicted)
+				} else {
+					' '
+				};
 
 				let txt = if selected {
 					format!(
-						"  {}{}{:w$}",
+						"{} {}{}{:w$}",
+						status_char,
 						indent_str,
 						collapse_char,
 						string,
 						w = width as usize
 					)
 				} else {
-					format!("  {indent_str}{collapse_char}{string}",)
+					format!(
+						"{status_char} {indent_str}{collapse_char}{string}",
+					)
 				};
 
-				Some(Span::styled(
-					Cow::from(txt),
-					theme.text(true, selected),
-				))
+				let style = if conflicted {
+					theme.item(StatusItemType::Conflicted, selected)
+				} else {
+					theme.text(true, selected)
+				};
+
+				Span::styled(Cow::from(txt), style)
 			}
 		}
 	}
@@ -247,12 +557,14 @@ impl StatusTreeComponent {
 				name: item.info.path.clone(),
 				indent: item.info.indent,
 				visible: item.info.visible,
+				conflicted: item.info.conflicted,
 				item_kind: &item.kind,
 			});
 
 			let mut idx_temp = index;
 
 			while idx_temp < tree_items.len().saturating_sub(2)
+				&& !tree_items[idx_temp].info.is_root()
 				&& tree_items[idx_temp].info.indent
 					< tree_items[idx_temp + 1].info.indent
 			{
@@ -296,16 +608,78 @@ impl StatusTreeComponent {
 		)
 	}
 
-	// Copy the real path of selected file to clickboard
-	fn copy_file_path(&self) {
-		if let Some(item) = self.selection() {
-			if crate::clipboard::copy_string(&item.info.full_path)
-				.is_err()
-			{
-				self.queue.push(InternalEvent::ShowErrorMsg(
-					strings::POPUP_FAIL_COPY.to_string(),
-				));
+	/// wheel moves the selection, a click selects the line under the
+	/// cursor and a click on the selected folder toggles it
+	fn mouse_event(&mut self, mouse: MouseEvent) -> bool {
+		let Some(action) = list_mouse(self.area.get(), mouse) else {
+			return false;
+		};
+
+		let changed = match action {
+			ListMouse::ScrollUp => {
+				self.tree.move_selection(MoveSelection::Up)
+			}
+			ListMouse::ScrollDown => {
+				self.tree.move_selection(MoveSelection::Down)
+			}
+			ListMouse::Click(line) => {
+				match self.tree.visual_index_to_absolute(
+					self.scroll_top.get() + line,
+				) {
+					Some(index)
+						if self.tree.selection == Some(index) =>
+					{
+						self.tree.toggle_selected_folder()
+					}
+					Some(index) => self.tree.select(index),
+					None => false,
+				}
 			}
+		};
+
+		if changed {
+			self.queue.push(InternalEvent::Update(NeedsUpdate::DIFF));
+		}
+
+		true
+	}
+
+	fn copy_event(&self, e: &KeyEvent) -> bool {
+		let absolute = if key_match(e, self.key_config.keys.copy) {
+			false
+		} else if key_match(
+			e,
+			self.key_config.keys.copy_absolute_path,
+		) {
+			true
+		} else {
+			return false;
+		};
+
+		self.copy_selected_path(absolute);
+
+		true
+	}
+
+	fn selection_copyable(&self) -> Option<FileTreeItem> {
+		self.selection().filter(|item| {
+			item.info.in_repo() && !item.info.is_root()
+		})
+	}
+
+	// Copy the path of the selected file or folder to clipboard
+	fn copy_selected_path(&self, absolute: bool) {
+		if let Some(item) = self.selection_copyable() {
+			try_or_popup!(
+				self,
+				strings::POPUP_FAIL_COPY,
+				copy_paths(
+					&self.queue,
+					&self.repo.borrow(),
+					&[item.info.path_in_root()],
+					absolute,
+				)
+			);
 		}
 	}
 }
@@ -315,6 +689,7 @@ struct TextDrawInfo<'a> {
 	name: String,
 	indent: u8,
 	visible: bool,
+	conflicted: bool,
 	item_kind: &'a FileTreeItemKind,
 }
 
@@ -324,6 +699,8 @@ impl DrawableComponent for StatusTreeComponent {
 			return Ok(());
 		}
 
+		self.area.set(r);
+
 		if self.pending {
 			let items = vec![Span::styled(
 				Cow::from(strings::loading_text(&self.key_config)),
@@ -362,12 +739,22 @@ impl DrawableComponent for StatusTreeComponent {
 			let items = vec_draw_text_info
 				.iter()
 				.enumerate()
-				.filter_map(|(index, draw_text_info)| {
+				.filter(|(_, draw_text_info)| draw_text_info.visible)
+				.map(|(index, draw_text_info)| {
+					let (viewed, marked) = match draw_text_info
+						.item_kind
+					{
+						FileTreeItemKind::File(file) => (
+							self.is_viewed(&file.path),
+							self.marked.contains(&file.path),
+						),
+						FileTreeItemKind::Path(_) => (false, false),
+					};
+
 					Self::item_to_text(
-						&draw_text_info.name,
-						draw_text_info.indent as usize,
-						draw_text_info.visible,
-						draw_text_info.item_kind,
+						draw_text_info,
+						viewed,
+						marked,
 						r.width,
 						self.show_selection && select == index,
 						&self.theme,
@@ -407,7 +794,7 @@ impl Component for StatusTreeComponent {
 		out.push(
 			CommandInfo::new(
 				strings::commands::blame_file(&self.key_config),
-				self.selection_file().is_some(),
+				self.selection_repo_file().is_some(),
 				self.focused || force_all,
 			)
 			.order(order::RARE_ACTION),
@@ -418,16 +805,29 @@ impl Component for StatusTreeComponent {
 				strings::commands::open_file_history(
 					&self.key_config,
 				),
-				self.selection_file().is_some(),
+				self.selection_repo_file().is_some(),
 				self.focused || force_all,
 			)
 			.order(order::RARE_ACTION),
 		);
 
+		out.push(
+			CommandInfo::new(
+				strings::commands::open_local_history(
+					&self.key_config,
+				),
+				self.revision.is_none()
+					&& self.selection_repo_file().is_some(),
+				(self.focused && self.revision.is_none())
+					|| force_all,
+			)
+			.order(order::RARE_ACTION),
+		);
+
 		out.push(
 			CommandInfo::new(
 				strings::commands::edit_item(&self.key_config),
-				self.selection_file().is_some(),
+				self.selection_repo_file().is_some(),
 				self.focused || force_all,
 			)
 			.order(order::RARE_ACTION),
@@ -436,7 +836,18 @@ impl Component for StatusTreeComponent {
 		out.push(
 			CommandInfo::new(
 				strings::commands::copy_path(&self.key_config),
-				self.selection_file().is_some(),
+				self.selection_copyable().is_some(),
+				self.focused || force_all,
+			)
+			.order(order::RARE_ACTION),
+		);
+
+		out.push(
+			CommandInfo::new(
+				strings::commands::copy_absolute_path(
+					&self.key_config,
+				),
+				self.selection_copyable().is_some(),
 				self.focused || force_all,
 			)
 			.order(order::RARE_ACTION),
@@ -445,11 +856,22 @@ impl Component for StatusTreeComponent {
 		CommandBlocking::PassingOn
 	}
 
+	#[allow(clippy::too_many_lines)]
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.focused {
+			if let Event::Mouse(mouse) = ev {
+				return Ok(self.mouse_event(*mouse).into());
+			}
+
 			if let Event::Key(e) = ev {
+				if self.copy_event(e) {
+					return Ok(EventState::Consumed);
+				}
+
 				return if key_match(e, self.key_config.keys.blame) {
-					if let Some(status_item) = self.selection_file() {
+					if let Some(status_item) =
+						self.selection_repo_file()
+					{
 						self.hide();
 						self.queue.push(InternalEvent::OpenPopup(
 							StackablePopupOpen::BlameFile(
@@ -466,7 +888,9 @@ impl Component for StatusTreeComponent {
 					e,
 					self.key_config.keys.file_history,
 				) {
-					if let Some(status_item) = self.selection_file() {
+					if let Some(status_item) =
+						self.selection_repo_file()
+					{
 						self.hide();
 						self.queue.push(InternalEvent::OpenPopup(
 							StackablePopupOpen::FileRevlog(
@@ -475,9 +899,26 @@ impl Component for StatusTreeComponent {
 						));
 					}
 					Ok(EventState::Consumed)
+				} else if self.revision.is_none()
+					&& key_match(
+						e,
+						self.key_config.keys.open_local_history,
+					) {
+					if let Some(status_item) =
+						self.selection_repo_file()
+					{
+						self.queue.push(
+							InternalEvent::OpenLocalHistory(
+								status_item.path,
+							),
+						);
+					}
+					Ok(EventState::Consumed)
 				} else if key_match(e, self.key_config.keys.edit_file)
 				{
-					if let Some(status_item) = self.selection_file() {
+					if let Some(status_item) =
+						self.selection_repo_file()
+					{
 						self.queue.push(
 							InternalEvent::OpenExternalEditor(Some(
 								status_item.path,
@@ -485,9 +926,6 @@ impl Component for StatusTreeComponent {
 						);
 					}
 					Ok(EventState::Consumed)
-				} else if key_match(e, self.key_config.keys.copy) {
-					self.copy_file_path();
-					Ok(EventState::Consumed)
 				} else if key_match(e, self.key_config.keys.move_down)
 				{
 					Ok(self
@@ -514,13 +952,17 @@ impl Component for StatusTreeComponent {
 					Ok(self
 						.move_selection(MoveSelection::PageDown)
 						.into())
-				} else if key_match(e, self.key_config.keys.scroll_up_half_page)
-				{
+				} else if key_match(
+					e,
+					self.key_config.keys.scroll_up_half_page,
+				) {
 					Ok(self
 						.move_selection(MoveSelection::HalfPageUp)
 						.into())
-				} else if key_match(e, self.key_config.keys.scroll_down_half_page)
-				{
+				} else if key_match(
+					e,
+					self.key_config.keys.scroll_down_half_page,
+				) {
 					Ok(self
 						.move_selection(MoveSelection::HalfPageDown)
 						.into())
@@ -570,6 +1012,8 @@ impl Component for StatusTreeComponent {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::ui::{style::Theme, GlyphSet};
+	use std::rc::Rc;
 
 	fn string_vec_to_status(items: &[&str]) -> Vec<StatusItem> {
 		items
@@ -577,6 +1021,12 @@ mod tests {
 			.map(|a| StatusItem {
 				path: String::from(*a),
 				status: StatusItemType::Modified,
+				conflict: None,
+				auto_resolved: false,
+				submodule: None,
+				lfs: false,
+				mode: None,
+				symlink_target: None,
 			})
 			.collect::<Vec<_>>()
 	}
@@ -622,6 +1072,52 @@ mod tests {
 		assert_eq!(ftc.scroll_top.get(), 0); // should still be at top
 	}
 
+	#[test]
+	fn test_mark_files() {
+		let items = string_vec_to_status(&[
+			"a/b/b1", //
+			"a/b/b2", //
+			"a/c/c1", //
+		]);
+		let paths = |files: Vec<StatusItem>| -> Vec<String> {
+			files.into_iter().map(|file| file.path).collect()
+		};
+
+		let mut ftc = StatusTreeComponent::new(
+			&Environment::test_env(),
+			"title",
+			true,
+		);
+		ftc.update(&items).unwrap();
+		ftc.tree.selection = Some(0);
+
+		ftc.move_selection(MoveSelection::Down); // b/
+		assert!(!ftc.selection_marked());
+		ftc.toggle_mark();
+		assert!(ftc.selection_marked());
+		assert_eq!(
+			paths(ftc.marked_or_selection_files()),
+			vec!["a/b/b1", "a/b/b2"]
+		);
+
+		ftc.move_selection(MoveSelection::Down); // b1
+		ftc.toggle_mark();
+		ftc.move_selection(MoveSelection::Down); // b2
+		ftc.move_selection(MoveSelection::Down); // c/
+		assert_eq!(
+			paths(ftc.marked_or_selection_files()),
+			vec!["a/b/b2"]
+		);
+
+		// marks of files that are gone are dropped
+		ftc.update(&items[..1]).unwrap();
+		assert_eq!(ftc.marked_count(), 0);
+		assert_eq!(
+			paths(ftc.marked_or_selection_files()),
+			paths(ftc.selection_files())
+		);
+	}
+
 	#[test]
 	fn test_correct_foldup_and_not_visible_scroll_position() {
 		let items = string_vec_to_status(&[
@@ -660,4 +1156,60 @@ mod tests {
 
 		assert_eq!(ftc.scroll_top.get(), 0); // should still be at top
 	}
+
+	fn render_tree(glyphs: GlyphSet) -> String {
+		let items = string_vec_to_status(&[
+			"a/b/b1", //
+			"a/b/b2", //
+			"a/c/c1", //
+		]);
+
+		let mut env = Environment::test_env();
+		env.theme = Rc::new(Theme::default().with_glyphs(glyphs));
+
+		let mut ftc = StatusTreeComponent::new(&env, "title", true);
+		ftc.show().expect("Showing FileTreeComponent failed");
+		ftc.update(&items)
+			.expect("Updating FileTreeComponent failed");
+		ftc.set_viewed(Some(HashSet::from([String::from("a/b/b1")])));
+		ftc.tree.selection = Some(0);
+		for _ in 0..4 {
+			ftc.move_selection(MoveSelection::Down); // Move to c/
+		}
+		ftc.move_selection(MoveSelection::Left); // Fold c/
+
+		let area = Rect::new(0, 0, 20, 7);
+		let mut terminal = ratatui::Terminal::new(
+			ratatui::backend::TestBackend::new(20, 7),
+		)
+		.expect("Unable to set up terminal");
+		terminal
+			.draw(|f| ftc.draw(f, area).expect("Draw failed"))
+			.expect("Draw failed");
+
+		// skip the block borders which are not part of the glyph set
+		let inner = area.inner(ratatui::layout::Margin {
+			horizontal: 1,
+			vertical: 1,
+		});
+		let buffer = terminal.backend().buffer();
+		inner
+			.rows()
+			.flat_map(Rect::columns)
+			.map(|pos| buffer[pos].symbol())
+			.collect()
+	}
+
+	#[test]
+	fn test_glyph_sets() {
+		let unicode = render_tree(GlyphSet::Unicode);
+		assert!(unicode.contains('\u{25b8}'), "{unicode}");
+		assert!(unicode.contains('\u{2713}'), "{unicode}");
+
+		let ascii = render_tree(GlyphSet::Ascii);
+		assert!(ascii.is_ascii(), "{ascii}");
+		assert!(ascii.contains("-b"), "{ascii}");
+		assert!(ascii.contains("* b1"), "{ascii}");
+		assert!(ascii.contains("+c"), "{ascii}");
+	}
 }
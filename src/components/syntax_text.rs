@@ -4,8 +4,8 @@ use super::{
 };
 use crate::{
 	app::Environment,
-	keys::SharedKeyConfig,
-	string_utils::tabs_to_spaces,
+	keys::{key_match, SharedKeyConfig},
+	string_utils::{hex_dump_line, tabs_to_spaces, HEX_ROW_BYTES},
 	strings,
 	ui::{
 		self, common_nav, style::SharedTheme, AsyncSyntaxJob,
@@ -16,7 +16,7 @@ use crate::{
 use anyhow::Result;
 use asyncgit::{
 	asyncjob::AsyncSingleJob,
-	sync::{self, RepoPathRef, TreeFile},
+	sync::{self, RepoPathRef, TreeFile, TreeFileContent},
 	ProgressPercent,
 };
 use crossterm::event::Event;
@@ -24,19 +24,29 @@ use filetreelist::MoveSelection;
 use itertools::Either;
 use ratatui::{
 	layout::Rect,
-	text::Text,
-	widgets::{Block, Borders, Wrap},
+	text::{Line, Text},
+	widgets::{Block, Borders, Paragraph, Wrap},
 	Frame,
 };
 use std::{cell::Cell, path::Path};
 
+/// binary content is summarized, the hex dump is shown on request
+struct BinaryFile {
+	bytes: Vec<u8>,
+	hex: bool,
+}
+
 pub struct SyntaxTextComponent {
 	repo: RepoPathRef,
 	current_file: Option<(String, Either<ui::SyntaxText, String>)>,
+	binary: Option<BinaryFile>,
+	/// first row of the hex dump shown and how many fit
+	hex_scroll: Cell<(usize, usize)>,
 	async_highlighting: AsyncSingleJob<AsyncSyntaxJob>,
 	syntax_progress: Option<ProgressPercent>,
 	key_config: SharedKeyConfig,
 	paragraph_state: Cell<ParagraphState>,
+	pending_scroll: Cell<Option<u16>>,
 	focused: bool,
 	theme: SharedTheme,
 }
@@ -50,7 +60,10 @@ impl SyntaxTextComponent {
 			),
 			syntax_progress: None,
 			current_file: None,
+			binary: None,
+			hex_scroll: Cell::new((0, 0)),
 			paragraph_state: Cell::new(ParagraphState::default()),
+			pending_scroll: Cell::new(None),
 			focused: false,
 			key_config: env.key_config.clone(),
 			theme: env.theme.clone(),
@@ -97,6 +110,7 @@ impl SyntaxTextComponent {
 	///
 	pub fn clear(&mut self) {
 		self.current_file = None;
+		self.binary = None;
 	}
 
 	///
@@ -107,19 +121,36 @@ impl SyntaxTextComponent {
 			.is_some_and(|(current_file, _)| current_file == &path);
 
 		if !already_loaded {
+			self.binary = None;
+
 			//TODO: fetch file content async as well
 			match sync::tree_file_content(&self.repo.borrow(), item) {
-				Ok(content) => {
+				Ok(TreeFileContent::Binary { bytes, image }) => {
+					self.current_file = Some((
+						path,
+						Either::Right(strings::binary_file_summary(
+							bytes.len(),
+							image,
+							&self.key_config,
+						)),
+					));
+					self.binary =
+						Some(BinaryFile { bytes, hex: false });
+					self.hex_scroll.set((0, 0));
+				}
+				Ok(TreeFileContent::Text(content)) => {
 					let content = tabs_to_spaces(content);
-					self.syntax_progress =
-						Some(ProgressPercent::empty());
-					self.async_highlighting.spawn(
-						AsyncSyntaxJob::new(
-							content.clone(),
-							path.clone(),
-							self.theme.get_syntax(),
-						),
-					);
+					if let Some(syntax) = self.theme.get_syntax() {
+						self.syntax_progress =
+							Some(ProgressPercent::empty());
+						self.async_highlighting.spawn(
+							AsyncSyntaxJob::new(
+								content.clone(),
+								path.clone(),
+								syntax,
+							),
+						);
+					}
 
 					self.current_file =
 						Some((path, Either::Right(content)));
@@ -136,7 +167,109 @@ impl SyntaxTextComponent {
 		}
 	}
 
+	/// scrolls `line` (starting at 1) to the top once the content has
+	/// been drawn next
+	pub fn scroll_to_line(&self, line: usize) {
+		self.pending_scroll.set(Some(
+			u16::try_from(line.saturating_sub(1)).unwrap_or(u16::MAX),
+		));
+	}
+
+	fn hex_bytes(&self) -> Option<&[u8]> {
+		self.binary
+			.as_ref()
+			.filter(|binary| binary.hex)
+			.map(|binary| binary.bytes.as_slice())
+	}
+
+	fn toggle_hex(&mut self) {
+		if let Some(binary) = &mut self.binary {
+			binary.hex = !binary.hex;
+		}
+	}
+
+	/// only the rows that fit get formatted, so the size of the
+	/// content does not matter
+	fn draw_hex(&self, f: &mut Frame, area: Rect, bytes: &[u8]) {
+		let height = usize::from(area.height.saturating_sub(2));
+		let rows = bytes.len().div_ceil(HEX_ROW_BYTES);
+		let max_top = rows.saturating_sub(height);
+		let top = self.hex_scroll.get().0.min(max_top);
+		self.hex_scroll.set((top, height));
+
+		let start = top * HEX_ROW_BYTES;
+		let lines: Vec<Line> = bytes
+			.get(start..)
+			.unwrap_or_default()
+			.chunks(HEX_ROW_BYTES)
+			.take(height)
+			.enumerate()
+			.map(|(row, chunk)| {
+				Line::from(hex_dump_line(
+					start + row * HEX_ROW_BYTES,
+					chunk,
+				))
+			})
+			.collect();
+
+		let title = format!(
+			"{} (hex)",
+			self.current_file
+				.as_ref()
+				.map(|(name, _)| name.as_str())
+				.unwrap_or_default(),
+		);
+
+		f.render_widget(
+			Paragraph::new(lines).block(
+				Block::default()
+					.title(title)
+					.borders(Borders::ALL)
+					.border_style(self.theme.title(self.focused())),
+			),
+			area,
+		);
+
+		if self.focused() {
+			ui::draw_scrollbar(
+				f,
+				area,
+				&self.theme,
+				max_top,
+				top,
+				ui::Orientation::Vertical,
+			);
+		}
+	}
+
+	fn scroll_hex(&self, bytes: &[u8], nav: MoveSelection) -> bool {
+		let (top, height) = self.hex_scroll.get();
+		let max_top = bytes
+			.len()
+			.div_ceil(HEX_ROW_BYTES)
+			.saturating_sub(height);
+
+		let new_top = match nav {
+			MoveSelection::Down => top.saturating_add(1),
+			MoveSelection::Up => top.saturating_sub(1),
+			MoveSelection::Top => 0,
+			MoveSelection::End => max_top,
+			MoveSelection::PageUp => top.saturating_sub(height),
+			MoveSelection::PageDown => top.saturating_add(height),
+			_ => top,
+		}
+		.min(max_top);
+
+		self.hex_scroll.set((new_top, height));
+
+		new_top != top
+	}
+
 	fn scroll(&self, nav: MoveSelection) -> bool {
+		if let Some(bytes) = self.hex_bytes() {
+			return self.scroll_hex(bytes, nav);
+		}
+
 		let state = self.paragraph_state.get();
 
 		let new_scroll_pos = match nav {
@@ -185,6 +318,11 @@ impl SyntaxTextComponent {
 
 impl DrawableComponent for SyntaxTextComponent {
 	fn draw(&self, f: &mut Frame, area: Rect) -> Result<()> {
+		if let Some(bytes) = self.hex_bytes() {
+			self.draw_hex(f, area, bytes);
+			return Ok(());
+		}
+
 		let text = self.current_file.as_ref().map_or_else(
 			|| Text::from(""),
 			|(_, content)| match content {
@@ -219,7 +357,11 @@ impl DrawableComponent for SyntaxTextComponent {
 
 		self.paragraph_state.set(state);
 
-		self.set_scroll(state.scroll().y);
+		self.set_scroll(
+			self.pending_scroll
+				.take()
+				.unwrap_or_else(|| state.scroll().y),
+		);
 
 		if self.focused() {
 			ui::draw_scrollbar(
@@ -253,6 +395,14 @@ impl Component for SyntaxTextComponent {
 				)
 				.order(strings::order::NAV),
 			);
+			out.push(CommandInfo::new(
+				strings::commands::toggle_hex_view(
+					&self.key_config,
+					self.hex_bytes().is_some(),
+				),
+				true,
+				self.binary.is_some() || force_all,
+			));
 		}
 		CommandBlocking::PassingOn
 	}
@@ -262,6 +412,13 @@ impl Component for SyntaxTextComponent {
 		event: &crossterm::event::Event,
 	) -> Result<EventState> {
 		if let Event::Key(key) = event {
+			if key_match(key, self.key_config.keys.toggle_hex_view)
+				&& self.binary.is_some()
+			{
+				self.toggle_hex();
+				return Ok(EventState::Consumed);
+			}
+
 			if let Some(nav) = common_nav(key, &self.key_config) {
 				return Ok(if self.scroll(nav) {
 					EventState::Consumed
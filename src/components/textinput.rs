@@ -3,6 +3,9 @@ use crate::keys::key_match;
 use crate::ui::Size;
 use crate::{
 	components::{
+		utils::commit_msg::{
+			continuation_indent, wrap_point, LineKind, LineLimits,
+		},
 		visibility_blocking, CommandBlocking, CommandInfo, Component,
 		DrawableComponent, EventState,
 	},
@@ -14,13 +17,16 @@ use anyhow::Result;
 use crossterm::event::Event;
 use ratatui::widgets::{Block, Borders};
 use ratatui::{
-	layout::{Alignment, Rect},
+	layout::{Alignment, Margin, Position, Rect},
+	style::{Modifier, Style},
+	text::{Line, Span},
 	widgets::{Clear, Paragraph},
 	Frame,
 };
 use std::cell::Cell;
 use std::cell::OnceCell;
 use tui_textarea::{CursorMove, Input, Key, Scrolling, TextArea};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 ///
 #[derive(PartialEq, Eq)]
@@ -39,6 +45,12 @@ enum SelectionState {
 
 type TextAreaComponent = TextArea<'static>;
 
+/// grows with every char or line added
+fn text_len(ta: &TextAreaComponent) -> usize {
+	ta.lines().iter().map(String::len).sum::<usize>()
+		+ ta.lines().len()
+}
+
 ///
 pub struct TextInputComponent {
 	title: String,
@@ -53,6 +65,25 @@ pub struct TextInputComponent {
 	embed: bool,
 	textarea: Option<TextAreaComponent>,
 	select_state: SelectionState,
+	line_limits: Option<LineLimits>,
+	/// top row and column the textarea is scrolled to, it keeps them
+	/// to itself so they are followed here the same way
+	scroll_top: Cell<(u16, u16)>,
+}
+
+/// how the textarea scrolls to keep the cursor in view
+const fn next_scroll_top(
+	prev_top: u16,
+	cursor: u16,
+	len: u16,
+) -> u16 {
+	if cursor < prev_top {
+		cursor
+	} else if prev_top.saturating_add(len) <= cursor {
+		(cursor - len).saturating_add(1)
+	} else {
+		prev_top
+	}
 }
 
 impl TextInputComponent {
@@ -76,6 +107,8 @@ impl TextInputComponent {
 			embed: false,
 			textarea: None,
 			select_state: SelectionState::NotSelecting,
+			line_limits: None,
+			scroll_top: Cell::new((0, 0)),
 		}
 	}
 
@@ -147,6 +180,9 @@ impl TextInputComponent {
 			.map(ToString::to_string)
 			.collect();
 
+		let title = self.title_line(&lines, 0);
+		self.scroll_top.set((0, 0));
+
 		self.textarea = Some({
 			let mut text_area = TextArea::new(lines);
 			if self.input_type == InputType::Password {
@@ -165,22 +201,145 @@ impl TextInputComponent {
 			);
 
 			if !self.embed {
-				text_area.set_block(
-					Block::default()
-						.borders(Borders::ALL)
-						.border_style(
-							ratatui::style::Style::default()
-								.add_modifier(
-									ratatui::style::Modifier::BOLD,
-								),
-						)
-						.title(self.title.clone()),
-				);
+				text_area.set_block(Self::block(title));
 			}
 			text_area
 		});
 	}
 
+	fn block(title: Line<'static>) -> Block<'static> {
+		Block::default()
+			.borders(Borders::ALL)
+			.border_style(
+				Style::default().add_modifier(Modifier::BOLD),
+			)
+			.title(title)
+	}
+
+	/// the title, with the length of the line at `row` against its
+	/// limit if it has one
+	fn title_line(
+		&self,
+		lines: &[String],
+		row: usize,
+	) -> Line<'static> {
+		let mut title = vec![Span::raw(self.title.clone())];
+
+		let limit = self
+			.line_limits
+			.and_then(|limits| {
+				limits.of_lines(lines).get(row).copied()
+			})
+			.flatten();
+
+		if let (Some(line), Some(limit)) = (lines.get(row), limit) {
+			let len = line.chars().count();
+			title.push(Span::styled(
+				strings::commit_line_length(len, limit),
+				if len > limit {
+					self.theme.text_danger()
+				} else {
+					Style::default()
+				},
+			));
+		}
+
+		Line::from(title)
+	}
+
+	fn update_title(&mut self) {
+		if self.embed {
+			return;
+		}
+
+		let block = self.textarea.as_ref().map(|ta| {
+			Self::block(self.title_line(ta.lines(), ta.cursor().0))
+		});
+
+		if let (Some(ta), Some(block)) = (&mut self.textarea, block) {
+			ta.set_block(block);
+		}
+	}
+
+	/// guides for the line lengths of a commit message, and breaking
+	/// its body lines while typing if `LineLimits::hard_wrap`
+	pub fn set_line_limits(&mut self, limits: Option<LineLimits>) {
+		self.line_limits = limits;
+		self.update_title();
+	}
+
+	/// joins and wraps the paragraphs of the body, see
+	/// `LineLimits::reflow`
+	pub fn reflow(&mut self) {
+		let Some(limits) = self.line_limits else {
+			return;
+		};
+
+		if let Some(ta) = &mut self.textarea {
+			let lines = limits.reflow(ta.lines());
+
+			if lines != ta.lines() {
+				// one edit to undo
+				ta.select_all();
+				ta.insert_str(lines.join("\n"));
+				self.msg.take();
+			}
+		}
+
+		self.update_title();
+	}
+
+	/// breaks the body lines from `first_row` to the cursor that are
+	/// longer than their limit, the cursor stays at its text
+	fn hard_wrap(
+		ta: &mut TextAreaComponent,
+		limits: LineLimits,
+		first_row: usize,
+	) {
+		let yank = ta.yank_text();
+		let (mut cursor_row, mut cursor_col) = ta.cursor();
+		let mut row = first_row.min(cursor_row);
+
+		while row <= cursor_row {
+			let lines = ta.lines();
+			let range = (limits.kinds(lines).get(row)
+				== Some(&LineKind::Body))
+			.then(|| wrap_point(&lines[row], limits.body))
+			.flatten();
+
+			if let Some(range) = range {
+				let indent = continuation_indent(&lines[row]);
+
+				ta.move_cursor(CursorMove::Jump(
+					u16::try_from(row).unwrap_or(u16::MAX),
+					u16::try_from(range.start).unwrap_or(u16::MAX),
+				));
+				ta.delete_str(range.len());
+				ta.insert_newline();
+				ta.insert_str(&indent);
+
+				if cursor_row > row {
+					cursor_row += 1;
+				} else if cursor_col >= range.end {
+					cursor_row += 1;
+					cursor_col = cursor_col - range.end
+						+ indent.chars().count();
+				} else {
+					cursor_col = cursor_col.min(range.start);
+				}
+			}
+
+			row += 1;
+		}
+
+		ta.move_cursor(CursorMove::Jump(
+			u16::try_from(cursor_row).unwrap_or(u16::MAX),
+			u16::try_from(cursor_col).unwrap_or(u16::MAX),
+		));
+		// breaking lines is no cut
+		ta.set_yank_text(yank);
+	}
+
 	/// Set the `msg`.
 	pub fn set_text(&mut self, msg: String) {
 		self.msg = msg.into();
@@ -192,6 +351,7 @@ impl TextInputComponent {
 	/// Set the `title`.
 	pub fn set_title(&mut self, t: String) {
 		self.title = t;
+		self.update_title();
 	}
 
 	///
@@ -586,6 +746,13 @@ impl TextInputComponent {
 				ta.paste();
 				true
 			}
+			_ => false,
+		}
+	}
+
+	/// whether `input` scrolls a page down or up
+	const fn page_scroll(input: &Input) -> Option<bool> {
+		match input {
 			Input {
 				key: Key::Char('v'),
 				ctrl: true,
@@ -594,10 +761,7 @@ impl TextInputComponent {
 			}
 			| Input {
 				key: Key::PageDown, ..
-			} => {
-				ta.scroll(Scrolling::PageDown);
-				true
-			}
+			} => Some(true),
 			Input {
 				key: Key::Char('v'),
 				ctrl: false,
@@ -606,11 +770,85 @@ impl TextInputComponent {
 			}
 			| Input {
 				key: Key::PageUp, ..
-			} => {
-				ta.scroll(Scrolling::PageUp);
-				true
+			} => Some(false),
+			_ => None,
+		}
+	}
+
+	/// where the text goes in `area`
+	const fn text_area(&self, area: Rect) -> Rect {
+		if self.embed {
+			area
+		} else {
+			area.inner(Margin::new(1, 1))
+		}
+	}
+
+	/// follows the scrolling the textarea does while being drawn
+	fn follow_scroll(&self, ta: &TextAreaComponent, text: Rect) {
+		let (row, col) = ta.cursor();
+		let (top_row, top_col) = self.scroll_top.get();
+
+		self.scroll_top.set((
+			next_scroll_top(
+				top_row,
+				u16::try_from(row).unwrap_or(u16::MAX),
+				text.height,
+			),
+			next_scroll_top(
+				top_col,
+				u16::try_from(col).unwrap_or(u16::MAX),
+				text.width,
+			),
+		));
+	}
+
+	/// colors the part of the lines beyond their limit
+	fn draw_line_guides(
+		&self,
+		f: &mut Frame,
+		ta: &TextAreaComponent,
+		limits: LineLimits,
+		text: Rect,
+	) {
+		let (top_row, top_col) = self.scroll_top.get();
+		let lines = ta.lines();
+		let rows = lines
+			.iter()
+			.zip(limits.of_lines(lines))
+			.skip(top_row.into());
+
+		for (y, (line, limit)) in
+			(text.top()..text.bottom()).zip(rows)
+		{
+			let Some(limit) = limit else {
+				continue;
+			};
+
+			let start: usize = line
+				.chars()
+				.take(limit)
+				.map(|c| c.width().unwrap_or_default())
+				.sum();
+			let columns = start.max(top_col.into())..line.width();
+
+			for column in columns {
+				let Ok(x) =
+					u16::try_from(column - usize::from(top_col))
+				else {
+					break;
+				};
+				if x >= text.width {
+					break;
+				}
+
+				if let Some(cell) = f
+					.buffer_mut()
+					.cell_mut(Position::new(text.x + x, y))
+				{
+					cell.set_style(self.theme.text_danger());
+				}
 			}
-			_ => false,
 		}
 	}
 }
@@ -641,8 +879,15 @@ impl DrawableComponent for TextInputComponent {
 
 			f.render_widget(Clear, area);
 
+			let text = self.text_area(area);
+			self.follow_scroll(ta, text);
+
 			f.render_widget(ta, area);
 
+			if let Some(limits) = self.line_limits {
+				self.draw_line_guides(f, ta, limits, text);
+			}
+
 			if self.show_char_count {
 				self.draw_char_count(f, area);
 			}
@@ -676,7 +921,11 @@ impl Component for TextInputComponent {
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		let input = Input::from(ev.clone());
 		self.should_select(&input);
+		let page = self.text_area(self.current_area.get()).height;
 		if let Some(ta) = &mut self.textarea {
+			let row = ta.cursor().0;
+			let len = text_len(ta);
+
 			let modified = if let Event::Key(e) = ev {
 				if key_match(e, self.key_config.keys.exit_popup) {
 					self.hide();
@@ -688,6 +937,22 @@ impl Component for TextInputComponent {
 				{
 					ta.insert_newline();
 					true
+				} else if let Some(down) = Self::page_scroll(&input) {
+					let (top_row, top_col) = self.scroll_top.get();
+					let (top_row, scrolling) = if down {
+						(
+							top_row.saturating_add(page),
+							Scrolling::PageDown,
+						)
+					} else {
+						(
+							top_row.saturating_sub(page),
+							Scrolling::PageUp,
+						)
+					};
+					self.scroll_top.set((top_row, top_col));
+					ta.scroll(scrolling);
+					true
 				} else {
 					Self::process_inputs(ta, &input)
 				}
@@ -703,7 +968,20 @@ impl Component for TextInputComponent {
 			}
 
 			if modified {
+				// only what got added is wrapped, joining lines
+				// stays possible
+				if let Some(limits) =
+					self.line_limits.filter(|limits| limits.hard_wrap)
+				{
+					if text_len(ta) > len && !ta.is_selecting() {
+						Self::hard_wrap(ta, limits, row);
+					}
+				}
+
 				self.msg.take();
+				if self.line_limits.is_some() {
+					self.update_title();
+				}
 				return Ok(EventState::Consumed);
 			}
 		}
@@ -733,6 +1011,7 @@ impl Component for TextInputComponent {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 	#[test]
 	fn test_smoke() {
@@ -752,6 +1031,43 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn test_hard_wrap() {
+		let env = Environment::test_env();
+		let mut comp = TextInputComponent::new(&env, "", "", false);
+		comp.set_line_limits(Some(LineLimits {
+			subject: 5,
+			body: 10,
+			hard_wrap: true,
+			comment_char: '#',
+		}));
+		comp.show_inner_textarea();
+		comp.set_text(String::from("subject line\n\naaaa bbbb"));
+
+		let mut type_at = |row: u16, text: &str| {
+			if let Some(ta) = &mut comp.textarea {
+				ta.move_cursor(CursorMove::Jump(row, u16::MAX));
+			}
+			for c in text.chars() {
+				comp.event(&Event::Key(KeyEvent::new(
+					KeyCode::Char(c),
+					KeyModifiers::empty(),
+				)))
+				.unwrap();
+			}
+		};
+
+		type_at(2, " cc");
+		// the subject is never wrapped
+		type_at(0, " too");
+
+		assert_eq!(
+			comp.get_text(),
+			"subject line too\n\naaaa bbbb\ncc"
+		);
+		assert_eq!(comp.textarea.as_ref().unwrap().cursor(), (0, 16));
+	}
+
 	#[test]
 	fn text_cursor_initial_position() {
 		let env = Environment::test_env();
@@ -0,0 +1,328 @@
+//! git takes the first paragraph of a commit message as its subject
+//! and everything after the blank line following it as the body,
+//! both are usually kept to a line length of their own
+
+use std::ops::Range;
+
+/// line lengths of a commit message, a length of 0 has no limit
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct LineLimits {
+	pub subject: usize,
+	pub body: usize,
+	/// break body lines getting longer than `body` while typing
+	pub hard_wrap: bool,
+	/// lines starting with it are dropped from the message
+	pub comment_char: char,
+}
+
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LineKind {
+	Subject,
+	Body,
+	Blank,
+	Comment,
+}
+
+impl LineLimits {
+	/// what each of `lines` is part of, the subject ends at the first
+	/// blank line after it. comments are skipped like git does
+	pub fn kinds(&self, lines: &[String]) -> Vec<LineKind> {
+		let mut subject = false;
+		let mut body = false;
+
+		lines
+			.iter()
+			.map(|line| {
+				if line.starts_with(self.comment_char) {
+					LineKind::Comment
+				} else if line.trim().is_empty() {
+					body |= subject;
+					LineKind::Blank
+				} else if body {
+					LineKind::Body
+				} else {
+					subject = true;
+					LineKind::Subject
+				}
+			})
+			.collect()
+	}
+
+	/// the length `lines` should be kept to, `None` for the ones
+	/// without a limit
+	pub fn of_lines(&self, lines: &[String]) -> Vec<Option<usize>> {
+		self.kinds(lines)
+			.into_iter()
+			.map(|kind| {
+				match kind {
+					LineKind::Subject => Some(self.subject),
+					LineKind::Body => Some(self.body),
+					LineKind::Blank | LineKind::Comment => None,
+				}
+				.filter(|limit| *limit > 0)
+			})
+			.collect()
+	}
+
+	/// the body paragraphs joined and wrapped at `body`, the subject,
+	/// lists (each item on its own) and trailers like
+	/// `Signed-off-by:` are kept as they are
+	pub fn reflow(&self, lines: &[String]) -> Vec<String> {
+		if self.body == 0 {
+			return lines.to_vec();
+		}
+
+		let kinds = self.kinds(lines);
+		let mut res = Vec::with_capacity(lines.len());
+		let mut paragraph: Vec<&str> = Vec::new();
+
+		for (line, kind) in lines.iter().zip(kinds) {
+			let continues = kind == LineKind::Body
+				&& paragraph.first().is_some_and(|first| {
+					!is_list_item(line)
+						&& leading_space(line)
+							== continuation_indent(first)
+				});
+
+			if !continues {
+				self.flush_paragraph(&mut paragraph, &mut res);
+			}
+
+			if kind == LineKind::Body {
+				paragraph.push(line);
+			} else {
+				res.push(line.clone());
+			}
+		}
+		self.flush_paragraph(&mut paragraph, &mut res);
+
+		res
+	}
+
+	fn flush_paragraph(
+		&self,
+		paragraph: &mut Vec<&str>,
+		res: &mut Vec<String>,
+	) {
+		if paragraph.is_empty() {
+			return;
+		}
+
+		if paragraph.iter().all(|line| is_trailer(line)) {
+			res.extend(
+				paragraph.iter().map(|line| (*line).to_string()),
+			);
+		} else {
+			let joined = paragraph
+				.iter()
+				.enumerate()
+				.map(|(idx, line)| {
+					if idx == 0 {
+						line.trim_end()
+					} else {
+						line.trim()
+					}
+				})
+				.collect::<Vec<_>>()
+				.join(" ");
+
+			res.extend(wrap_line(&joined, self.body));
+		}
+
+		paragraph.clear();
+	}
+}
+
+/// where to break `line` to keep it within `width` chars: the char
+/// range of the spaces to replace with the line break. a word longer
+/// than `width` is broken after, `None` if it fits
+pub fn wrap_point(line: &str, width: usize) -> Option<Range<usize>> {
+	let chars: Vec<char> = line.chars().collect();
+	if width == 0 || chars.len() <= width {
+		return None;
+	}
+
+	let indent = leading_space(line).chars().count();
+	let is_space = |idx: &usize| chars[*idx].is_whitespace();
+
+	let space = (indent + 1..=width)
+		.rev()
+		.find(is_space)
+		.or_else(|| (width + 1..chars.len()).find(is_space))?;
+
+	let mut start = space;
+	while start > indent + 1 && chars[start - 1].is_whitespace() {
+		start -= 1;
+	}
+	let end = (space..chars.len())
+		.find(|idx| !is_space(idx))
+		.unwrap_or(chars.len());
+
+	// nothing to move to the next line
+	(end < chars.len()).then_some(start..end)
+}
+
+/// the indentation of the lines `line` gets wrapped into, list items
+/// are aligned to their text
+pub fn continuation_indent(line: &str) -> String {
+	let indent = leading_space(line);
+
+	if is_list_item(line) {
+		format!("{indent}  ")
+	} else {
+		indent.to_string()
+	}
+}
+
+/// `line` broken at `width` as often as needed
+pub fn wrap_line(line: &str, width: usize) -> Vec<String> {
+	let indent = continuation_indent(line);
+	let mut res = Vec::new();
+	let mut rest = line.to_string();
+
+	while let Some(range) = wrap_point(&rest, width) {
+		let chars: Vec<char> = rest.chars().collect();
+		res.push(chars[..range.start].iter().collect());
+		rest = format!(
+			"{indent}{}",
+			chars[range.end..].iter().collect::<String>()
+		);
+	}
+	res.push(rest);
+
+	res
+}
+
+fn leading_space(line: &str) -> &str {
+	&line[..line.len() - line.trim_start().len()]
+}
+
+fn is_list_item(line: &str) -> bool {
+	let text = line.trim_start();
+
+	["- ", "* ", "+ "]
+		.iter()
+		.any(|bullet| text.starts_with(bullet))
+}
+
+/// `Token: value` with a token without spaces
+fn is_trailer(line: &str) -> bool {
+	line.split_once(": ").is_some_and(|(token, _)| {
+		!token.is_empty()
+			&& token
+				.chars()
+				.all(|c| c.is_ascii_alphanumeric() || c == '-')
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	const LIMITS: LineLimits = LineLimits {
+		subject: 10,
+		body: 20,
+		hard_wrap: true,
+		comment_char: '#',
+	};
+
+	fn lines(text: &str) -> Vec<String> {
+		text.split('\n').map(String::from).collect()
+	}
+
+	#[test]
+	fn test_line_kinds() {
+		let msg = lines("# comment\n\nsubject\nstill subject\n\nbody\n# c\n\nmore");
+
+		assert_eq!(
+			LIMITS.kinds(&msg),
+			vec![
+				LineKind::Comment,
+				LineKind::Blank,
+				LineKind::Subject,
+				LineKind::Subject,
+				LineKind::Blank,
+				LineKind::Body,
+				LineKind::Comment,
+				LineKind::Blank,
+				LineKind::Body,
+			]
+		);
+		assert_eq!(
+			LIMITS.of_lines(&lines("a\n\nb")),
+			vec![Some(10), None, Some(20)]
+		);
+
+		let no_subject_limit = LineLimits {
+			subject: 0,
+			..LIMITS
+		};
+		assert_eq!(
+			no_subject_limit.of_lines(&lines("a")),
+			vec![None]
+		);
+	}
+
+	#[test]
+	fn test_wrap_point() {
+		assert_eq!(wrap_point("fits", 4), None);
+		assert_eq!(wrap_point("aaa bbb", 4), Some(3..4));
+		assert_eq!(wrap_point("aaa bbb", 3), Some(3..4));
+		assert_eq!(wrap_point("aa  bbb ccc", 9), Some(7..8));
+		assert_eq!(wrap_point("aa   bbb", 3), Some(2..5));
+		// the long word stays whole
+		assert_eq!(wrap_point("aaaaaa bb", 3), Some(6..7));
+		assert_eq!(wrap_point("aaaaaa", 3), None);
+		assert_eq!(wrap_point("aaa    ", 3), None);
+		// not at the indentation
+		assert_eq!(wrap_point("  aaaa bb", 4), Some(6..7));
+	}
+
+	#[test]
+	fn test_wrap_line() {
+		assert_eq!(
+			wrap_line("one two three four", 8),
+			vec!["one two", "three", "four"]
+		);
+		assert_eq!(
+			wrap_line("- one two three", 8),
+			vec!["- one", "  two", "  three"]
+		);
+	}
+
+	#[test]
+	fn test_reflow() {
+		let msg = lines(
+			"a subject that is too long\n\
+			\n\
+			one two\n\
+			three four five six seven\n\
+			\n\
+			- item one two three four\n\
+			\x20 more\n\
+			- item two\n\
+			\n\
+			# a comment that is not wrapped\n\
+			Signed-off-by: someone with a long name <a@b.c>",
+		);
+
+		assert_eq!(
+			LIMITS.reflow(&msg),
+			lines(
+				"a subject that is too long\n\
+				\n\
+				one two three four\n\
+				five six seven\n\
+				\n\
+				- item one two three\n\
+				\x20 four more\n\
+				- item two\n\
+				\n\
+				# a comment that is not wrapped\n\
+				Signed-off-by: someone with a long name <a@b.c>"
+			)
+		);
+	}
+}
@@ -0,0 +1,271 @@
+//! occurrences of a search text in the lines of a diff and how they
+//! are highlighted next to changed words
+
+use super::word_diff::word_segments;
+use std::ops::Range;
+
+/// byte range of an occurrence in a line (by index)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchMatch {
+	pub line: usize,
+	pub range: Range<usize>,
+}
+
+/// how a part of a diff line is highlighted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Highlight {
+	None,
+	Word,
+	Match,
+	CurrentMatch,
+}
+
+/// matches of the search in line order and the one jumped to
+#[derive(Default)]
+pub struct DiffSearch {
+	pub case_sensitive: bool,
+	matches: Vec<SearchMatch>,
+	current: Option<usize>,
+}
+
+impl DiffSearch {
+	///
+	pub fn clear(&mut self) {
+		self.matches.clear();
+		self.current = None;
+	}
+
+	/// searches `lines` again, the current match is the first one
+	/// at or after `origin`
+	pub fn update<'a>(
+		&mut self,
+		lines: impl IntoIterator<Item = &'a str>,
+		query: &str,
+		origin: usize,
+	) {
+		self.matches = lines
+			.into_iter()
+			.enumerate()
+			.flat_map(|(line, content)| {
+				find_in_line(content, query, self.case_sensitive)
+					.into_iter()
+					.map(move |range| SearchMatch { line, range })
+			})
+			.collect();
+
+		self.current = (!self.matches.is_empty()).then(|| {
+			self.matches
+				.iter()
+				.position(|m| m.line >= origin)
+				.unwrap_or_default()
+		});
+	}
+
+	///
+	pub fn count(&self) -> usize {
+		self.matches.len()
+	}
+
+	///
+	pub const fn current_index(&self) -> Option<usize> {
+		self.current
+	}
+
+	///
+	pub fn current(&self) -> Option<&SearchMatch> {
+		self.current.and_then(|index| self.matches.get(index))
+	}
+
+	/// moves to the next (or previous) match, wrapping around
+	pub fn select_next(
+		&mut self,
+		forward: bool,
+	) -> Option<&SearchMatch> {
+		let count = self.matches.len();
+		if count == 0 {
+			return None;
+		}
+
+		self.current = Some(self.current.map_or(0, |current| {
+			if forward {
+				(current + 1) % count
+			} else {
+				(current + count - 1) % count
+			}
+		}));
+
+		self.current()
+	}
+
+	/// matches in `line` and whether it is the current one
+	pub fn line_matches(
+		&self,
+		line: usize,
+	) -> Vec<(Range<usize>, bool)> {
+		let start = self.matches.partition_point(|m| m.line < line);
+
+		self.matches[start..]
+			.iter()
+			.enumerate()
+			.take_while(|(_, m)| m.line == line)
+			.map(|(index, m)| {
+				(m.range.clone(), self.current == Some(start + index))
+			})
+			.collect()
+	}
+}
+
+/// byte ranges of `query` in `line`, not overlapping
+fn find_in_line(
+	line: &str,
+	query: &str,
+	case_sensitive: bool,
+) -> Vec<Range<usize>> {
+	if query.is_empty() {
+		return Vec::new();
+	}
+
+	if case_sensitive {
+		return line
+			.match_indices(query)
+			.map(|(start, found)| start..start + found.len())
+			.collect();
+	}
+
+	let query: Vec<char> =
+		query.chars().flat_map(char::to_lowercase).collect();
+	let mut matches = Vec::new();
+	let mut next_start = 0;
+
+	for (start, _) in line.char_indices() {
+		if start < next_start {
+			continue;
+		}
+
+		let mut expected = query.iter();
+		for (index, c) in line[start..].char_indices() {
+			if !c.to_lowercase().all(|c| expected.next() == Some(&c))
+			{
+				break;
+			}
+
+			if expected.len() == 0 {
+				next_start = start + index + c.len_utf8();
+				matches.push(start..next_start);
+				break;
+			}
+		}
+	}
+
+	matches
+}
+
+/// splits the `visible` part of a line into its highlighted parts,
+/// search matches take precedence over changed words
+pub fn highlight_segments(
+	visible: Range<usize>,
+	word_changes: &[Range<usize>],
+	matches: &[(Range<usize>, bool)],
+) -> Vec<(Range<usize>, Highlight)> {
+	let match_ranges: Vec<Range<usize>> =
+		matches.iter().map(|(range, _)| range.clone()).collect();
+
+	word_segments(visible, word_changes)
+		.into_iter()
+		.flat_map(|(range, changed)| {
+			word_segments(range, &match_ranges).into_iter().map(
+				move |(range, matched)| {
+					let highlight = if matched {
+						let current =
+							matches.iter().any(|(m, current)| {
+								*current && m.contains(&range.start)
+							});
+						if current {
+							Highlight::CurrentMatch
+						} else {
+							Highlight::Match
+						}
+					} else if changed {
+						Highlight::Word
+					} else {
+						Highlight::None
+					};
+
+					(range, highlight)
+				},
+			)
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn test_find_in_line() {
+		assert_eq!(
+			find_in_line("Foo foo FOO", "foo", false),
+			vec![0..3, 4..7, 8..11]
+		);
+		assert_eq!(
+			find_in_line("Foo foo FOO", "foo", true),
+			vec![4..7]
+		);
+		assert_eq!(find_in_line("aaa", "aa", false), vec![0..2]);
+		assert_eq!(
+			find_in_line("Größe ÄPFEL", "äpfel", false),
+			vec![8..14]
+		);
+		assert!(find_in_line("foo", "", false).is_empty());
+	}
+
+	#[test]
+	fn test_navigation() {
+		let mut search = DiffSearch::default();
+		search.update(["a x", "b", "x x", "x"], "x", 1);
+
+		assert_eq!(search.count(), 4);
+		assert_eq!(search.current().map(|m| m.line), Some(2));
+		assert_eq!(
+			search.line_matches(2),
+			vec![(0..1, true), (2..3, false)]
+		);
+
+		assert_eq!(search.select_next(true).map(|m| m.line), Some(2));
+		assert_eq!(search.select_next(true).map(|m| m.line), Some(3));
+		assert_eq!(search.select_next(true).map(|m| m.line), Some(0));
+		assert_eq!(
+			search.select_next(false).map(|m| m.line),
+			Some(3)
+		);
+
+		// nothing after the origin wraps to the first match
+		search.update(["x", "b"], "x", 1);
+		assert_eq!(search.current_index(), Some(0));
+
+		search.update(["x", "b"], "y", 0);
+		assert_eq!(search.current(), None);
+		assert_eq!(search.select_next(true), None);
+	}
+
+	#[test]
+	fn test_highlight_segments() {
+		assert_eq!(
+			highlight_segments(
+				0..10,
+				&[2..6, 12..14],
+				&[(4..8, true), (9..10, false)]
+			),
+			vec![
+				(0..2, Highlight::None),
+				(2..4, Highlight::Word),
+				(4..6, Highlight::CurrentMatch),
+				(6..8, Highlight::CurrentMatch),
+				(8..9, Highlight::None),
+				(9..10, Highlight::Match),
+			]
+		);
+	}
+}
@@ -1,7 +1,7 @@
 //TODO: remove in favour of new `filetreelist` crate
 
-use anyhow::{bail, Result};
-use asyncgit::StatusItem;
+use anyhow::{anyhow, bail, Result};
+use asyncgit::{StatusItem, StatusItemType};
 use std::{
 	collections::BTreeSet,
 	ffi::OsStr,
@@ -20,6 +20,12 @@ pub struct TreeItemInfo {
 	pub path: String,
 	/// the full path
 	pub full_path: String,
+	/// a conflicted file is at or below it
+	pub conflicted: bool,
+	/// which of the roots of [`FileTreeItems::new_roots`] it belongs to
+	pub root: Option<usize>,
+	/// length of the root label in front of the path in `full_path`
+	root_len: usize,
 }
 
 impl TreeItemInfo {
@@ -33,8 +39,27 @@ impl TreeItemInfo {
 			visible: true,
 			path,
 			full_path,
+			conflicted: false,
+			root: None,
+			root_len: 0,
 		}
 	}
+
+	/// `full_path` without the label of its root, empty for a root
+	pub fn path_in_root(&self) -> &str {
+		&self.full_path[self.root_len..]
+	}
+
+	/// the labeled item of a root itself
+	pub const fn is_root(&self) -> bool {
+		self.root.is_some() && self.indent == 0
+	}
+
+	/// not below a root other than the first one, which stands for
+	/// the repo itself
+	pub fn in_repo(&self) -> bool {
+		self.root.map_or(true, |root| root == 0)
+	}
 }
 
 /// attribute used to indicate the collapse/expand state of a path item
@@ -160,12 +185,71 @@ impl FileTreeItems {
 			items.push(FileTreeItem::new_file(e)?);
 		}
 
+		Self::mark_conflicted(&mut items);
+
 		Ok(Self {
 			items,
 			file_count: list.len(),
 		})
 	}
 
+	/// one labeled folder per `(label, list)` holding the items
+	/// [`Self::new`] builds for `list`, their `full_path` starts with
+	/// `"{label}/"` (like the paths in `collapsed` do)
+	pub(crate) fn new_roots(
+		roots: &[(String, Vec<StatusItem>)],
+		collapsed: &BTreeSet<&String>,
+	) -> Result<Self> {
+		let mut items = Vec::new();
+		let mut file_count = 0;
+
+		for (root, (label, list)) in roots.iter().enumerate() {
+			let prefix = format!("{label}/");
+
+			let mut info =
+				TreeItemInfo::new(0, label.clone(), label.clone());
+			info.root = Some(root);
+			info.root_len = label.len();
+			items.push(FileTreeItem {
+				info,
+				kind: FileTreeItemKind::Path(PathCollapsed(
+					collapsed.contains(&label),
+				)),
+			});
+
+			let collapsed_in_root = collapsed
+				.iter()
+				.filter_map(|path| path.strip_prefix(&prefix))
+				.map(String::from)
+				.collect::<BTreeSet<_>>();
+
+			for mut item in
+				Self::new(list, &collapsed_in_root.iter().collect())?
+					.items
+			{
+				item.info.indent =
+					item.info.indent.checked_add(1).ok_or_else(
+						|| {
+							anyhow!(
+								"path too deep: {}",
+								item.info.full_path
+							)
+						},
+					)?;
+				item.info.full_path.insert_str(0, &prefix);
+				item.info.root = Some(root);
+				item.info.root_len = prefix.len();
+				items.push(item);
+			}
+
+			file_count += list.len();
+		}
+
+		Self::mark_conflicted(&mut items);
+
+		Ok(Self { items, file_count })
+	}
+
 	///
 	pub(crate) const fn items(&self) -> &Vec<FileTreeItem> {
 		&self.items
@@ -195,6 +279,55 @@ impl FileTreeItems {
 		parent_index
 	}
 
+	/// all files below the item at `index` (regardless of them being
+	/// collapsed), the file itself if `index` is not a folder
+	pub(crate) fn subtree_files(
+		&self,
+		index: usize,
+	) -> Vec<&StatusItem> {
+		let Some(root) = self.items.get(index) else {
+			return Vec::new();
+		};
+
+		std::iter::once(root)
+			.chain(self.items[index + 1..].iter().take_while(
+				|item| item.info.indent > root.info.indent,
+			))
+			.filter_map(|item| match &item.kind {
+				FileTreeItemKind::File(file) => Some(file),
+				FileTreeItemKind::Path(_) => None,
+			})
+			.collect()
+	}
+
+	/// flags conflicted files and every folder above them
+	fn mark_conflicted(items: &mut [FileTreeItem]) {
+		for index in 0..items.len() {
+			let (parents, rest) = items.split_at_mut(index);
+			let item = &mut rest[0];
+			if !matches!(
+				&item.kind,
+				FileTreeItemKind::File(file)
+					if file.status == StatusItemType::Conflicted
+			) {
+				continue;
+			}
+
+			item.info.conflicted = true;
+
+			let mut indent = item.info.indent;
+			for parent in parents.iter_mut().rev() {
+				if indent == 0 {
+					break;
+				}
+				if parent.info.indent < indent {
+					parent.info.conflicted = true;
+					indent = parent.info.indent;
+				}
+			}
+		}
+	}
+
 	fn push_dirs<'a>(
 		item_path: &'a Path,
 		nodes: &mut Vec<FileTreeItem>,
@@ -260,7 +393,6 @@ impl Index<usize> for FileTreeItems {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use asyncgit::StatusItemType;
 
 	fn string_vec_to_status(items: &[&str]) -> Vec<StatusItem> {
 		items
@@ -268,6 +400,12 @@ mod tests {
 			.map(|a| StatusItem {
 				path: String::from(*a),
 				status: StatusItemType::Modified,
+				conflict: None,
+				auto_resolved: false,
+				submodule: None,
+				lfs: false,
+				mode: None,
+				symlink_target: None,
 			})
 			.collect::<Vec<_>>()
 	}
@@ -289,6 +427,9 @@ mod tests {
 					full_path: items[0].path.clone(),
 					indent: 0,
 					visible: true,
+					conflicted: false,
+					root: None,
+					root_len: 0,
 				},
 				kind: FileTreeItemKind::File(items[0].clone())
 			}]
@@ -362,6 +503,39 @@ mod tests {
 		assert_eq!(res.next(), Some((0, "a.txt")));
 	}
 
+	#[test]
+	fn test_conflicted_folders() {
+		let mut items = string_vec_to_status(&[
+			"a/b/c/file.txt", //
+			"a/b/d.txt",      //
+			"a/e.txt",        //
+			"f/g.txt",        //
+		]);
+		items[0].status = StatusItemType::Conflicted;
+
+		let list =
+			FileTreeItems::new(&items, &BTreeSet::new()).unwrap();
+		let res = list
+			.items
+			.iter()
+			.map(|i| (i.info.full_path.as_str(), i.info.conflicted))
+			.collect::<Vec<_>>();
+
+		assert_eq!(
+			res,
+			vec![
+				("a", true),
+				("a/b", true),
+				("a/b/c", true),
+				("a/b/c/file.txt", true),
+				("a/b/d.txt", false),
+				("a/e.txt", false),
+				("f", false),
+				("f/g.txt", false),
+			]
+		);
+	}
+
 	#[test]
 	fn test_folder_dup() {
 		let items = string_vec_to_status(&[
@@ -409,6 +583,117 @@ mod tests {
 		assert!(res.multiple_items_at_path(2));
 	}
 
+	#[test]
+	fn test_subtree_files() {
+		fn paths(res: &FileTreeItems, index: usize) -> Vec<&str> {
+			res.subtree_files(index)
+				.into_iter()
+				.map(|item| item.path.as_str())
+				.collect()
+		}
+
+		let items = string_vec_to_status(&[
+			"a/b/c", //
+			"a/b/d", //
+			"a/e",   //
+			"f/g",   //
+			"h",     //
+		]);
+
+		//0 a/
+		//1   b/
+		//2     c
+		//3     d
+		//4   e
+		//5 f/
+		//6   g
+		//7 h
+
+		let mut res =
+			FileTreeItems::new(&items, &BTreeSet::new()).unwrap();
+
+		assert_eq!(paths(&res, 0), vec!["a/b/c", "a/b/d", "a/e"]);
+		assert_eq!(paths(&res, 1), vec!["a/b/c", "a/b/d"]);
+		assert_eq!(paths(&res, 5), vec!["f/g"]);
+		assert_eq!(paths(&res, 7), vec!["h"]);
+		assert!(paths(&res, 8).is_empty());
+
+		// visibility does not matter
+		res[1].kind = FileTreeItemKind::Path(PathCollapsed(true));
+		res[2].info.visible = false;
+		res[3].info.visible = false;
+		assert_eq!(paths(&res, 0), vec!["a/b/c", "a/b/d", "a/e"]);
+	}
+
+	#[test]
+	fn test_roots() {
+		let mut sub = string_vec_to_status(&[
+			"a/b", //
+		]);
+		sub[0].status = StatusItemType::Conflicted;
+		let roots = vec![
+			(
+				String::from("<repo>"),
+				string_vec_to_status(&["a/b", "c"]),
+			),
+			(String::from("<submodule s>"), sub),
+		];
+		let collapsed = String::from("<submodule s>/a");
+
+		//0 <repo>/
+		//1   a/
+		//2     b
+		//3   c
+		//4 <submodule s>/
+		//5   a/
+		//6     b
+
+		let res = FileTreeItems::new_roots(
+			&roots,
+			&std::iter::once(&collapsed).collect(),
+		)
+		.unwrap();
+
+		assert_eq!(res.file_count(), 3);
+		assert_eq!(
+			res.items
+				.iter()
+				.map(|i| (
+					i.info.indent,
+					i.info.full_path.as_str(),
+					i.info.path_in_root(),
+					i.info.root,
+				))
+				.collect::<Vec<_>>(),
+			vec![
+				(0, "<repo>", "", Some(0)),
+				(1, "<repo>/a", "a", Some(0)),
+				(2, "<repo>/a/b", "a/b", Some(0)),
+				(1, "<repo>/c", "c", Some(0)),
+				(0, "<submodule s>", "", Some(1)),
+				(1, "<submodule s>/a", "a", Some(1)),
+				(2, "<submodule s>/a/b", "a/b", Some(1)),
+			]
+		);
+
+		assert!(res[0].info.is_root() && res[0].info.in_repo());
+		assert!(!res[1].info.is_root() && res[1].info.in_repo());
+		assert!(res[4].info.is_root() && !res[6].info.in_repo());
+		assert_eq!(
+			res.items
+				.iter()
+				.map(|i| i.info.conflicted)
+				.collect::<Vec<_>>(),
+			vec![false, false, false, false, true, true, true]
+		);
+		assert_eq!(
+			res[5].kind,
+			FileTreeItemKind::Path(PathCollapsed(true))
+		);
+		assert_eq!(res.subtree_files(4)[0].path, "a/b");
+		assert_eq!(res.find_parent_index(5), 4);
+	}
+
 	#[test]
 	fn test_find_parent() {
 		//0 a/
@@ -20,6 +20,8 @@ pub struct LogEntry {
 	pub hash_short: BoxStr,
 	pub id: CommitId,
 	pub highlighted: bool,
+	pub signed: bool,
+	pub has_note: bool,
 }
 
 impl From<CommitInfo> for LogEntry {
@@ -54,6 +56,8 @@ impl From<CommitInfo> for LogEntry {
 			hash_short,
 			id: c.id,
 			highlighted: false,
+			signed: c.signed,
+			has_note: c.has_note,
 		}
 	}
 }
@@ -1,13 +1,28 @@
+use crate::{
+	queue::{InternalEvent, Queue},
+	strings,
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	self, utils::repo_work_dir, CommitId, CommitInfo, RepoPath,
+};
 use chrono::{DateTime, Local, Utc};
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Margin, Position, Rect};
+use std::path::Path;
 use unicode_width::UnicodeWidthStr;
 
+pub mod commit_msg;
+pub mod diff_search;
 #[cfg(feature = "ghemoji")]
 pub mod emoji;
 pub mod filetree;
 pub mod logitems;
 pub mod scroll_horizontal;
 pub mod scroll_vertical;
+pub mod split_diff;
 pub mod statustree;
+pub mod word_diff;
 
 /// macro to simplify running code that might return Err.
 /// It will show a popup in that case
@@ -26,6 +41,164 @@ macro_rules! try_or_popup {
 	};
 }
 
+/// copies repo relative `paths` (or their location in the work dir
+/// if `absolute`) as one path per line and confirms what was copied
+pub fn copy_paths(
+	queue: &Queue,
+	repo: &RepoPath,
+	paths: &[&str],
+	absolute: bool,
+) -> Result<()> {
+	let work_dir = if absolute {
+		Some(repo_work_dir(repo)?)
+	} else {
+		None
+	};
+	let text = paths_text(paths, work_dir.as_deref());
+
+	crate::clipboard::copy_string(&text)?;
+	queue.push(InternalEvent::ShowInfoMsg(strings::copy_success(
+		&text,
+	)));
+
+	Ok(())
+}
+
+/// one path per line, joined onto `work_dir` if given
+fn paths_text(paths: &[&str], work_dir: Option<&str>) -> String {
+	work_dir.map_or_else(
+		|| paths.join("\n"),
+		|work_dir| {
+			paths
+				.iter()
+				.map(|path| {
+					Path::new(work_dir)
+						.join(path)
+						.display()
+						.to_string()
+				})
+				.collect::<Vec<_>>()
+				.join("\n")
+		},
+	)
+}
+
+/// what of a commit gets copied
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitCopy {
+	Hash,
+	ShortHash,
+	/// a line in the format of `Options::copy_commit_format`
+	Summary,
+}
+
+/// copies `what` of each of `ids` one per line and confirms what was
+/// copied
+pub fn copy_commits(
+	queue: &Queue,
+	repo: &RepoPath,
+	ids: &[CommitId],
+	what: CommitCopy,
+	format: &str,
+) -> Result<()> {
+	if ids.is_empty() {
+		return Ok(());
+	}
+
+	let lines: Vec<String> = match what {
+		CommitCopy::Hash => {
+			ids.iter().map(ToString::to_string).collect()
+		}
+		CommitCopy::ShortHash => {
+			ids.iter().map(CommitId::get_short_string).collect()
+		}
+		CommitCopy::Summary => {
+			sync::get_commits_info(repo, ids, usize::MAX)?
+				.iter()
+				.map(|info| format_commit_line(format, info))
+				.collect()
+		}
+	};
+	let text = lines.join("\n");
+
+	crate::clipboard::copy_string(&text)?;
+	queue.push(InternalEvent::ShowInfoMsg(strings::copy_success(
+		&text,
+	)));
+
+	Ok(())
+}
+
+/// fills in `{hash}`, `{short}`, `{summary}` and `{author}`, other
+/// braces are kept as they are
+pub fn format_commit_line(format: &str, info: &CommitInfo) -> String {
+	let values = [
+		("{hash}", info.id.to_string()),
+		("{short}", info.id.get_short_string()),
+		("{summary}", info.message.clone()),
+		("{author}", info.author.clone()),
+	];
+
+	let mut res = String::with_capacity(format.len());
+	let mut rest = format;
+	while let Some(start) = rest.find('{') {
+		res.push_str(&rest[..start]);
+		rest = &rest[start..];
+
+		if let Some((placeholder, value)) = values
+			.iter()
+			.find(|(placeholder, _)| rest.starts_with(placeholder))
+		{
+			res.push_str(value);
+			rest = &rest[placeholder.len()..];
+		} else {
+			res.push('{');
+			rest = &rest[1..];
+		}
+	}
+	res.push_str(rest);
+
+	res
+}
+
+/// what a mouse event over a bordered list asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListMouse {
+	ScrollUp,
+	ScrollDown,
+	/// left click on line `n` inside of the border
+	Click(usize),
+}
+
+/// translates `mouse` for a bordered list drawn into `area`, events
+/// outside of it (or clicks on the border) are `None`
+pub fn list_mouse(
+	area: Rect,
+	mouse: MouseEvent,
+) -> Option<ListMouse> {
+	let position = Position::new(mouse.column, mouse.row);
+
+	if !area.contains(position) {
+		return None;
+	}
+
+	match mouse.kind {
+		MouseEventKind::ScrollUp => Some(ListMouse::ScrollUp),
+		MouseEventKind::ScrollDown => Some(ListMouse::ScrollDown),
+		MouseEventKind::Down(MouseButton::Left) => {
+			let inner = area.inner(Margin {
+				vertical: 1,
+				horizontal: 1,
+			});
+
+			inner.contains(position).then(|| {
+				ListMouse::Click(usize::from(mouse.row - inner.y))
+			})
+		}
+		_ => None,
+	}
+}
+
 /// helper func to convert unix time since epoch to formatted time string in local timezone
 pub fn time_to_string(secs: i64, short: bool) -> String {
 	let time = DateTime::<Local>::from(
@@ -67,3 +240,103 @@ pub fn string_width_align(s: &str, width: usize) -> String {
 fn find_truncate_point(s: &str, chars: usize) -> usize {
 	s.chars().take(chars).map(char::len_utf8).sum()
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crossterm::event::KeyModifiers;
+
+	fn mouse(
+		kind: MouseEventKind,
+		column: u16,
+		row: u16,
+	) -> MouseEvent {
+		MouseEvent {
+			kind,
+			column,
+			row,
+			modifiers: KeyModifiers::empty(),
+		}
+	}
+
+	#[test]
+	fn test_format_commit_line() {
+		let info = CommitInfo {
+			message: String::from("fix {author} in the {docs}"),
+			time: 0,
+			author: String::from("jane"),
+			id: CommitId::from_str_unchecked(&format!("{:040}", 7))
+				.unwrap(),
+			signed: false,
+			has_note: false,
+		};
+
+		assert_eq!(
+			format_commit_line("{short} (\"{summary}\")", &info),
+			"0000000 (\"fix {author} in the {docs}\")"
+		);
+		assert_eq!(
+			format_commit_line("{hash} by {author} {", &info),
+			"0000000000000000000000000000000000000007 by jane {"
+		);
+	}
+
+	#[test]
+	fn test_paths_text() {
+		let paths = ["a.txt", "sub/b.txt"];
+
+		assert_eq!(paths_text(&paths, None), "a.txt\nsub/b.txt");
+		assert_eq!(
+			paths_text(&paths, Some("/repo")),
+			format!(
+				"{}\n{}",
+				Path::new("/repo").join("a.txt").display(),
+				Path::new("/repo").join("sub/b.txt").display()
+			)
+		);
+		assert_eq!(paths_text(&[], Some("/repo")), "");
+	}
+
+	#[test]
+	fn test_list_mouse() {
+		let area = Rect::new(10, 5, 20, 6);
+		let click = MouseEventKind::Down(MouseButton::Left);
+
+		assert_eq!(
+			list_mouse(area, mouse(click, 11, 6)),
+			Some(ListMouse::Click(0))
+		);
+		assert_eq!(
+			list_mouse(area, mouse(click, 28, 9)),
+			Some(ListMouse::Click(3))
+		);
+		// border
+		assert_eq!(list_mouse(area, mouse(click, 11, 5)), None);
+		assert_eq!(list_mouse(area, mouse(click, 11, 10)), None);
+		// outside
+		assert_eq!(list_mouse(area, mouse(click, 30, 6)), None);
+		assert_eq!(
+			list_mouse(area, mouse(MouseEventKind::ScrollUp, 40, 6)),
+			None
+		);
+
+		assert_eq!(
+			list_mouse(
+				area,
+				mouse(MouseEventKind::ScrollDown, 10, 5)
+			),
+			Some(ListMouse::ScrollDown)
+		);
+		assert_eq!(
+			list_mouse(
+				area,
+				mouse(
+					MouseEventKind::Down(MouseButton::Right),
+					11,
+					6
+				)
+			),
+			None
+		);
+	}
+}
@@ -26,6 +26,10 @@ impl HorizontalScroll {
 		self.right.set(0);
 	}
 
+	pub fn set_right(&self, right: usize) {
+		self.right.set(right.min(self.max_right.get()));
+	}
+
 	pub fn move_right(
 		&self,
 		move_type: HorizontalScrollType,
@@ -87,16 +87,26 @@ impl VerticalScroll {
 			selection,
 			selection_max,
 		);
-		self.top.set(new_top);
+		self.set_top(new_top, selection_max, visual_height);
+
+		new_top
+	}
+
+	/// for content that calculates its scroll position itself
+	pub fn set_top(
+		&self,
+		top: usize,
+		line_count: usize,
+		visual_height: usize,
+	) {
+		self.top.set(top);
 
 		if visual_height == 0 {
 			self.max_top.set(0);
 		} else {
-			let new_max = selection_max.saturating_sub(visual_height);
+			let new_max = line_count.saturating_sub(visual_height);
 			self.max_top.set(new_max);
 		}
-
-		new_top
 	}
 
 	pub fn update_no_selection(
@@ -0,0 +1,164 @@
+//! rows of the side-by-side diff view: removed lines on the left,
+//! added lines on the right
+
+use asyncgit::{DiffLineType, FileDiff};
+
+/// lines (indices into the diff) shown next to each other,
+/// context lines and hunk headers use the same line on both sides
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitRow {
+	///
+	pub hunk: usize,
+	///
+	pub old: Option<usize>,
+	///
+	pub new: Option<usize>,
+}
+
+/// the rows of `diff` and the row of each of its lines
+pub fn split_rows(diff: &FileDiff) -> (Vec<SplitRow>, Vec<usize>) {
+	let mut rows = Vec::new();
+	let mut line_rows = Vec::with_capacity(diff.lines);
+	let mut removed = Vec::new();
+	let mut added = Vec::new();
+	let mut index = 0_usize;
+
+	for (hunk, lines) in diff.hunks.iter().enumerate() {
+		for line in &lines.lines {
+			match line.line_type {
+				DiffLineType::Delete => {
+					if !added.is_empty() {
+						pair_rows(
+							hunk,
+							&mut removed,
+							&mut added,
+							&mut rows,
+							&mut line_rows,
+						);
+					}
+					removed.push(index);
+				}
+				DiffLineType::Add => added.push(index),
+				DiffLineType::Header | DiffLineType::None => {
+					pair_rows(
+						hunk,
+						&mut removed,
+						&mut added,
+						&mut rows,
+						&mut line_rows,
+					);
+
+					line_rows.push(rows.len());
+					rows.push(SplitRow {
+						hunk,
+						old: Some(index),
+						new: Some(index),
+					});
+				}
+			}
+
+			index += 1;
+		}
+
+		pair_rows(
+			hunk,
+			&mut removed,
+			&mut added,
+			&mut rows,
+			&mut line_rows,
+		);
+	}
+
+	(rows, line_rows)
+}
+
+/// puts the removed lines next to the added lines replacing them,
+/// `line_rows` stays in line order
+fn pair_rows(
+	hunk: usize,
+	removed: &mut Vec<usize>,
+	added: &mut Vec<usize>,
+	rows: &mut Vec<SplitRow>,
+	line_rows: &mut Vec<usize>,
+) {
+	let first = rows.len();
+	let count = removed.len().max(added.len());
+
+	rows.extend((0..count).map(|i| SplitRow {
+		hunk,
+		old: removed.get(i).copied(),
+		new: added.get(i).copied(),
+	}));
+
+	line_rows.extend((0..removed.len()).map(|i| first + i));
+	line_rows.extend((0..added.len()).map(|i| first + i));
+
+	removed.clear();
+	added.clear();
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use asyncgit::{
+		sync::diff::{DiffLinePosition, Hunk},
+		DiffLine,
+	};
+	use pretty_assertions::assert_eq;
+
+	fn diff(hunks: &[&[DiffLineType]]) -> FileDiff {
+		FileDiff {
+			hunks: hunks
+				.iter()
+				.map(|lines| Hunk {
+					header_hash: 0,
+					lines: lines
+						.iter()
+						.map(|line_type| DiffLine {
+							content: "".into(),
+							line_type: *line_type,
+							position: DiffLinePosition::default(),
+						})
+						.collect(),
+				})
+				.collect(),
+			lines: hunks.iter().map(|lines| lines.len()).sum(),
+			..FileDiff::default()
+		}
+	}
+
+	fn row(
+		hunk: usize,
+		old: Option<usize>,
+		new: Option<usize>,
+	) -> SplitRow {
+		SplitRow { hunk, old, new }
+	}
+
+	#[test]
+	fn test_split_rows() {
+		use DiffLineType::{Add, Delete, Header};
+		const CONTEXT: DiffLineType = DiffLineType::None;
+
+		let (rows, line_rows) = split_rows(&diff(&[
+			&[Header, CONTEXT, Delete, Delete, Add, CONTEXT],
+			&[Header, Add, Delete, Add, Add],
+		]));
+
+		assert_eq!(
+			rows,
+			vec![
+				row(0, Some(0), Some(0)),
+				row(0, Some(1), Some(1)),
+				row(0, Some(2), Some(4)),
+				row(0, Some(3), None),
+				row(0, Some(5), Some(5)),
+				row(1, Some(6), Some(6)),
+				row(1, None, Some(7)),
+				row(1, Some(8), Some(9)),
+				row(1, None, Some(10)),
+			]
+		);
+		assert_eq!(line_rows, vec![0, 1, 2, 3, 2, 4, 5, 6, 7, 7, 8]);
+	}
+}
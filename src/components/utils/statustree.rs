@@ -1,9 +1,10 @@
 use super::filetree::{
 	FileTreeItem, FileTreeItemKind, FileTreeItems, PathCollapsed,
+	TreeItemInfo,
 };
 use anyhow::Result;
-use asyncgit::StatusItem;
-use std::{cell::Cell, cmp, collections::BTreeSet};
+use asyncgit::{StatusItem, StatusItemType};
+use std::{cell::Cell, cmp, collections::BTreeSet, path::Path};
 
 //TODO: use new `filetreelist` crate
 
@@ -49,15 +50,81 @@ impl SelectionChange {
 impl StatusTree {
 	/// update tree with a new list, try to retain selection and collapse states
 	pub fn update(&mut self, list: &[StatusItem]) -> Result<()> {
-		let last_collapsed = self.all_collapsed();
+		self.update_collapsing(list, |_| false)
+	}
+
+	/// like `update`, folders that were not in the tree before start
+	/// collapsed if `collapse_new` says so
+	pub fn update_collapsing(
+		&mut self,
+		list: &[StatusItem],
+		collapse_new: impl Fn(&str) -> bool,
+	) -> Result<()> {
+		let known = self.known_folders();
+		let mut last_collapsed = self.collapsed_paths();
+		last_collapsed.extend(
+			new_folders(list, "", &known).filter(|f| collapse_new(f)),
+		);
+
+		let tree = FileTreeItems::new(
+			list,
+			&last_collapsed.iter().collect(),
+		)?;
+		self.replace_tree(tree);
+
+		Ok(())
+	}
+
+	/// like `update_collapsing` but with a labeled root per
+	/// `(label, list)` (see [`FileTreeItems::new_roots`]), roots start
+	/// expanded and `collapse_new` gets paths within their root
+	pub fn update_roots(
+		&mut self,
+		roots: &[(String, Vec<StatusItem>)],
+		collapse_new: impl Fn(&str) -> bool,
+	) -> Result<()> {
+		let known = self.known_folders();
+		let mut last_collapsed = self.collapsed_paths();
+		for (label, list) in roots {
+			let prefix = format!("{label}/");
+			last_collapsed.extend(
+				new_folders(list, &prefix, &known)
+					.filter(|f| collapse_new(f))
+					.map(|f| format!("{prefix}{f}")),
+			);
+		}
+
+		let tree = FileTreeItems::new_roots(
+			roots,
+			&last_collapsed.iter().collect(),
+		)?;
+		self.replace_tree(tree);
+
+		Ok(())
+	}
+
+	fn known_folders(&self) -> BTreeSet<String> {
+		self.folders()
+			.map(|(folder, _)| folder.full_path.clone())
+			.collect()
+	}
 
+	fn collapsed_paths(&self) -> BTreeSet<String> {
+		self.all_collapsed().into_iter().cloned().collect()
+	}
+
+	/// swaps in `tree` trying to retain the selection
+	fn replace_tree(&mut self, tree: FileTreeItems) {
 		let last_selection =
 			self.selected_item().map(|e| e.info.full_path);
 		let last_selection_index = self.selection.unwrap_or(0);
 
-		self.tree = FileTreeItems::new(list, &last_collapsed)?;
+		self.tree = tree;
 		self.selection = last_selection.as_ref().map_or_else(
-			|| self.tree.items().first().map(|_| 0),
+			|| {
+				self.initial_selection()
+					.or_else(|| self.tree.items().first().map(|_| 0))
+			},
 			|last_selection| {
 				self.find_last_selection(
 					last_selection,
@@ -74,8 +141,22 @@ impl StatusTree {
 		if let Some(idx) = self.selection {
 			self.selection = Some(self.find_visible_idx(idx));
 		}
+	}
 
-		Ok(())
+	/// without a previous selection start on the first conflicted
+	/// file, otherwise on the first modified one
+	fn initial_selection(&self) -> Option<usize> {
+		let first_file = |kind: StatusItemType| {
+			self.tree.items().iter().position(|item| {
+				matches!(
+					&item.kind,
+					FileTreeItemKind::File(file) if file.status == kind
+				)
+			})
+		};
+
+		first_file(StatusItemType::Conflicted)
+			.or_else(|| first_file(StatusItemType::Modified))
 	}
 
 	/// Return which indices can be selected, taking into account that
@@ -95,7 +176,9 @@ impl StatusTree {
 			let mut idx_temp = index;
 			vec_available_selections.push(index);
 
+			// roots keep their label to themselves
 			while idx_temp < tree_items.len().saturating_sub(2)
+				&& !tree_items[idx_temp].info.is_root()
 				&& tree_items[idx_temp].info.indent
 					< tree_items[idx_temp + 1].info.indent
 			{
@@ -179,16 +262,247 @@ impl StatusTree {
 		})
 	}
 
+	/// index of the item drawn in line `visual_index`, folders folded
+	/// up into their parent share one line
+	pub fn visual_index_to_absolute(
+		&self,
+		visual_index: usize,
+	) -> Option<usize> {
+		self.available_selections
+			.iter()
+			.copied()
+			.filter(|idx| self.is_visible_index(*idx))
+			.nth(visual_index)
+	}
+
+	/// selects the item at the absolute `index`, see
+	/// [`Self::visual_index_to_absolute`]
+	pub fn select(&mut self, index: usize) -> bool {
+		if self.selection == Some(index)
+			|| !self.available_selections.contains(&index)
+			|| !self.is_visible_index(index)
+		{
+			return false;
+		}
+
+		self.selection = Some(index);
+		true
+	}
+
+	/// collapses or expands the selected folder just like
+	/// [`MoveSelection::Left`]/[`MoveSelection::Right`] would
+	pub fn toggle_selected_folder(&mut self) -> bool {
+		let Some(FileTreeItemKind::Path(PathCollapsed(collapsed))) =
+			self.selected_item().map(|item| item.kind)
+		else {
+			return false;
+		};
+
+		self.move_selection(if collapsed {
+			MoveSelection::Right
+		} else {
+			MoveSelection::Left
+		})
+	}
+
 	///
 	pub fn selected_item(&self) -> Option<FileTreeItem> {
 		self.selection.map(|i| self.tree[i].clone())
 	}
 
+	///
+	pub fn all_files(&self) -> Vec<StatusItem> {
+		self.tree
+			.items()
+			.iter()
+			.filter_map(|item| match &item.kind {
+				FileTreeItemKind::File(file) => Some(file.clone()),
+				FileTreeItemKind::Path(_) => None,
+			})
+			.collect()
+	}
+
+	/// all files of the root (see [`Self::update_roots`]) the
+	/// selection is in, `None` without roots
+	pub fn selected_root_files(&self) -> Option<Vec<StatusItem>> {
+		let root = self.selected_item()?.info.root?;
+
+		Some(
+			self.tree
+				.items()
+				.iter()
+				.filter(|item| item.info.root == Some(root))
+				.filter_map(|item| match &item.kind {
+					FileTreeItemKind::File(file) => {
+						Some(file.clone())
+					}
+					FileTreeItemKind::Path(_) => None,
+				})
+				.collect(),
+		)
+	}
+
+	/// all files below the selected item
+	pub fn selected_files(&self) -> Vec<StatusItem> {
+		self.selection.map_or_else(Vec::new, |index| {
+			self.tree
+				.subtree_files(index)
+				.into_iter()
+				.cloned()
+				.collect()
+		})
+	}
+
+	/// selects the next file after the selection (wrapping around)
+	/// that `skip` does not reject, expanding its folders if needed
+	pub fn select_next_file(
+		&mut self,
+		skip: impl Fn(&StatusItem) -> bool,
+	) -> bool {
+		let len = self.tree.len();
+		let start =
+			self.selection.map_or(0, |selection| selection + 1);
+
+		let next = (start..len).chain(0..start).find(|idx| {
+			matches!(
+				&self.tree[*idx].kind,
+				FileTreeItemKind::File(file) if !skip(file)
+			)
+		});
+
+		let Some(next) = next else {
+			return false;
+		};
+
+		let path = self.tree[next].info.full_path.clone();
+		for idx in 0..next {
+			if self.tree[idx].kind
+				== FileTreeItemKind::Path(PathCollapsed(true))
+			{
+				let folder = self.tree[idx].info.full_path.clone();
+				if path.starts_with(&format!("{folder}/")) {
+					self.expand(&folder, idx);
+				}
+			}
+		}
+
+		let changed = self.selection != Some(next);
+		self.selection = Some(next);
+		changed
+	}
+
+	/// selects the first file `matches` accepts, expanding its folders
+	/// if needed
+	pub fn select_first_file(
+		&mut self,
+		matches: impl Fn(&StatusItem) -> bool,
+	) -> bool {
+		let selection = self.selection.take();
+
+		if self.select_next_file(|item| !matches(item)) {
+			self.selection != selection
+		} else {
+			self.selection = selection;
+			false
+		}
+	}
+
+	/// collapses every outermost folder whose files all match `done`,
+	/// unless the selection is inside of it
+	pub fn collapse_folders(
+		&mut self,
+		done: impl Fn(&StatusItem) -> bool,
+	) {
+		let mut idx = 0;
+
+		while idx < self.tree.len() {
+			if matches!(
+				self.tree[idx].kind,
+				FileTreeItemKind::Path(_)
+			) && self
+				.tree
+				.subtree_files(idx)
+				.into_iter()
+				.all(&done)
+			{
+				let folder = self.tree[idx].info.full_path.clone();
+				let selected_inside =
+					self.selection.is_some_and(|s| {
+						self.tree[s]
+							.info
+							.full_path
+							.starts_with(&format!("{folder}/"))
+					});
+
+				if !selected_inside {
+					self.collapse(&folder, idx);
+					idx = self.last_in_subtree(idx);
+				}
+			}
+
+			idx += 1;
+		}
+	}
+
+	fn last_in_subtree(&self, index: usize) -> usize {
+		let indent = self.tree[index].info.indent;
+
+		(index + 1..self.tree.len())
+			.take_while(|idx| self.tree[*idx].info.indent > indent)
+			.last()
+			.unwrap_or(index)
+	}
+
 	///
 	pub fn is_empty(&self) -> bool {
 		self.tree.items().is_empty()
 	}
 
+	/// every folder and whether it is collapsed
+	pub fn folders(
+		&self,
+	) -> impl Iterator<Item = (&TreeItemInfo, bool)> {
+		self.tree.items().iter().filter_map(|item| match item.kind {
+			FileTreeItemKind::Path(PathCollapsed(collapsed)) => {
+				Some((&item.info, collapsed))
+			}
+			FileTreeItemKind::File(_) => None,
+		})
+	}
+
+	/// collapses the folders `collapsed` returns true for (given
+	/// their path within their root) and expands all others including
+	/// roots, a hidden selection moves up to its visible folder
+	pub fn set_collapsed(
+		&mut self,
+		collapsed: impl Fn(&str) -> bool,
+	) {
+		for idx in 0..self.tree.len() {
+			let info = &self.tree[idx].info;
+			let is_collapsed =
+				!info.is_root() && collapsed(info.path_in_root());
+			if let FileTreeItemKind::Path(PathCollapsed(
+				ref mut current,
+			)) = self.tree[idx].kind
+			{
+				*current = is_collapsed;
+			}
+		}
+
+		self.update_visibility(None, 0, true);
+		self.available_selections = self.setup_available_selections();
+
+		if let Some(idx) = self.selection {
+			self.selection = Some(self.find_visible_idx(idx));
+		}
+	}
+
+	/// folders up to `depth` levels deep are expanded, all others
+	/// collapsed
+	pub fn expand_to_depth(&mut self, depth: usize) {
+		self.set_collapsed(|folder| folder_depth(folder) >= depth);
+	}
+
 	fn all_collapsed(&self) -> BTreeSet<&String> {
 		let mut res = BTreeSet::new();
 
@@ -492,9 +806,36 @@ impl StatusTree {
 	}
 }
 
+/// how many folders `folder` is nested in, 0 for a top level one
+pub fn folder_depth(folder: &str) -> usize {
+	folder.matches('/').count()
+}
+
+/// folders of the files in `list` that are not `known` with `prefix`
+/// in front of them
+fn new_folders<'a>(
+	list: &'a [StatusItem],
+	prefix: &'a str,
+	known: &'a BTreeSet<String>,
+) -> impl Iterator<Item = String> + 'a {
+	list.iter()
+		.flat_map(|item| {
+			Path::new(&item.path)
+				.ancestors()
+				.skip(1)
+				.filter(|folder| folder.parent().is_some())
+				.filter_map(Path::to_str)
+		})
+		.filter(move |folder| {
+			!known.contains(&format!("{prefix}{folder}"))
+		})
+		.map(String::from)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::options::InitialCollapse;
 	use asyncgit::StatusItemType;
 
 	fn string_vec_to_status(items: &[&str]) -> Vec<StatusItem> {
@@ -503,6 +844,12 @@ mod tests {
 			.map(|a| StatusItem {
 				path: String::from(*a),
 				status: StatusItemType::Modified,
+				conflict: None,
+				auto_resolved: false,
+				submodule: None,
+				lfs: false,
+				mode: None,
+				symlink_target: None,
 			})
 			.collect::<Vec<_>>()
 	}
@@ -515,6 +862,272 @@ mod tests {
 			.collect::<Vec<_>>()
 	}
 
+	#[test]
+	fn test_select_visual() {
+		let items = string_vec_to_status(&[
+			"a/b/c", //
+			"e",     //
+		]);
+
+		//0 a/b/ (b/ is folded up into a/)
+		//1
+		//2     c
+		//3 e
+
+		let mut res = StatusTree::default();
+		res.update(&items).unwrap();
+		res.selection = Some(0);
+
+		assert_eq!(res.visual_index_to_absolute(0), Some(0));
+		assert_eq!(res.visual_index_to_absolute(1), Some(2));
+		assert_eq!(res.visual_index_to_absolute(2), Some(3));
+		assert_eq!(res.visual_index_to_absolute(3), None);
+
+		assert!(res.select(2));
+		assert!(!res.select(2));
+		assert!(!res.select(1));
+		assert!(!res.toggle_selected_folder());
+
+		assert!(res.select(0));
+		assert!(res.toggle_selected_folder());
+		assert_eq!(get_visible(&res), vec![true, false, false, true]);
+		assert_eq!(res.visual_index_to_absolute(1), Some(3));
+
+		assert!(res.toggle_selected_folder());
+		assert_eq!(res.visual_index_to_absolute(1), Some(2));
+	}
+
+	#[test]
+	fn test_select_next_file() {
+		let items = string_vec_to_status(&[
+			"a/b/c", //
+			"a/d",   //
+			"e",     //
+		]);
+
+		//0 a/
+		//1   b/
+		//2     c
+		//3   d
+		//4 e
+
+		let mut res = StatusTree::default();
+		res.update(&items).unwrap();
+		res.collapse("a/b", 1);
+		res.selection = Some(3);
+
+		let viewed = |item: &StatusItem| item.path != "a/b/c";
+
+		assert!(res.select_next_file(|item| item.path == "a/d"));
+		assert_eq!(res.selection, Some(4));
+
+		// wraps around and expands the collapsed folder
+		assert!(res.select_next_file(viewed));
+		assert_eq!(res.selection, Some(2));
+		assert!(get_visible(&res)[2]);
+
+		assert!(!res.select_next_file(|_| true));
+		assert_eq!(res.selection, Some(2));
+	}
+
+	#[test]
+	fn test_select_first_file() {
+		let mut items = string_vec_to_status(&[
+			"a/b/c", //
+			"a/d",   //
+			"e",     //
+		]);
+		items[1].status = StatusItemType::Conflicted;
+		items[2].status = StatusItemType::Conflicted;
+
+		//0 a/
+		//1   b/
+		//2     c
+		//3   d
+		//4 e
+
+		let mut res = StatusTree::default();
+		res.update(&items).unwrap();
+		res.collapse("a", 0);
+		res.selection = Some(4);
+
+		let conflicted = |item: &StatusItem| {
+			item.status == StatusItemType::Conflicted
+		};
+
+		assert!(res.select_first_file(conflicted));
+		assert_eq!(res.selection, Some(3));
+		assert!(get_visible(&res)[3]);
+
+		assert!(!res.select_first_file(conflicted));
+		assert!(!res.select_first_file(|item| item.path == "x"));
+		assert_eq!(res.selection, Some(3));
+	}
+
+	#[test]
+	fn test_initial_collapse_of_new_folders() {
+		let before = string_vec_to_status(&["a/b", "c"]);
+		let after = string_vec_to_status(&["a/b", "a/d/e/f", "c"]);
+
+		//0 a/
+		//1   b
+		//2   d/
+		//3     e/
+		//4       f
+		//5 c
+
+		let remembered = BTreeSet::from([String::from("a/d/e")]);
+
+		let visible_after = |policy: InitialCollapse| {
+			let mut res = StatusTree::default();
+			let collapse =
+				|folder: &str| policy.collapses(folder, &remembered);
+			res.update_collapsing(&before, collapse).unwrap();
+			res.update_collapsing(&after, collapse).unwrap();
+			get_visible(&res)
+		};
+
+		assert_eq!(
+			visible_after(InitialCollapse::Expanded),
+			vec![true, true, true, true, true, true]
+		);
+		// `a` was collapsed when first built
+		assert_eq!(
+			visible_after(InitialCollapse::Collapsed),
+			vec![true, false, false, false, false, true]
+		);
+		assert_eq!(
+			visible_after(InitialCollapse::TopLevelOnly),
+			vec![true, true, true, false, false, true]
+		);
+		assert_eq!(
+			visible_after(InitialCollapse::Remember),
+			vec![true, true, true, true, false, true]
+		);
+	}
+
+	#[test]
+	fn test_new_folders_keep_known_state() {
+		let mut res = StatusTree::default();
+		res.update_collapsing(
+			&string_vec_to_status(&["a/b"]),
+			|_| false,
+		)
+		.unwrap();
+
+		// only the folder showing up is collapsed, `a` stays expanded
+		res.update_collapsing(
+			&string_vec_to_status(&["a/b", "a/c/d"]),
+			|_| true,
+		)
+		.unwrap();
+
+		assert_eq!(get_visible(&res), vec![true, true, true, false]);
+
+		res.expand_to_depth(usize::MAX);
+		assert_eq!(get_visible(&res), vec![true, true, true, true]);
+
+		res.expand_to_depth(0);
+		assert_eq!(
+			get_visible(&res),
+			vec![true, false, false, false]
+		);
+	}
+
+	#[test]
+	fn test_collapse_folders() {
+		let items = string_vec_to_status(&[
+			"a/b/c", //
+			"a/d",   //
+			"e/f",   //
+			"g",     //
+		]);
+
+		//0 a/
+		//1   b/
+		//2     c
+		//3   d
+		//4 e/
+		//5   f
+		//6 g
+
+		let mut res = StatusTree::default();
+		res.update(&items).unwrap();
+		res.selection = Some(6);
+
+		res.collapse_folders(|item| item.path != "a/d");
+
+		assert_eq!(
+			get_visible(&res),
+			vec![true, true, false, true, true, false, true]
+		);
+
+		// never hides the selection
+		res.selection = Some(3);
+		res.collapse_folders(|_| true);
+
+		assert_eq!(
+			get_visible(&res),
+			vec![true, true, false, true, true, false, true]
+		);
+	}
+
+	fn status(path: &str, status: StatusItemType) -> StatusItem {
+		StatusItem {
+			path: String::from(path),
+			status,
+			conflict: None,
+			auto_resolved: false,
+			submodule: None,
+			lfs: false,
+			mode: None,
+			symlink_target: None,
+		}
+	}
+
+	#[test]
+	fn test_initial_selection() {
+		let items = vec![
+			status("a", StatusItemType::New),
+			status("b", StatusItemType::Modified),
+			status("c/d", StatusItemType::Conflicted),
+		];
+
+		//0 a
+		//1 b
+		//2 c/
+		//3   d
+
+		let mut res = StatusTree::default();
+		res.update(&items).unwrap();
+		assert_eq!(res.selection, Some(3));
+
+		let mut res = StatusTree::default();
+		res.update(&items[..2]).unwrap();
+		assert_eq!(res.selection, Some(1));
+
+		let mut res = StatusTree::default();
+		res.update(&items[..1]).unwrap();
+		assert_eq!(res.selection, Some(0));
+
+		// an established selection is kept
+		res.update(&items).unwrap();
+		assert_eq!(res.selection, Some(0));
+
+		//0 a/
+		//1   b
+		//2 c
+
+		// the file is selected rather than its folder on top
+		let mut res = StatusTree::default();
+		res.update(&[
+			status("a/b", StatusItemType::Modified),
+			status("c", StatusItemType::New),
+		])
+		.unwrap();
+		assert_eq!(res.selection, Some(1));
+	}
+
 	#[test]
 	fn test_selection() {
 		let items = string_vec_to_status(&[
@@ -523,6 +1136,7 @@ mod tests {
 
 		let mut res = StatusTree::default();
 		res.update(&items).unwrap();
+		res.selection = Some(0);
 
 		assert!(res.move_selection(MoveSelection::Down));
 
@@ -822,6 +1436,61 @@ mod tests {
 		assert_eq!(res.selection, Some(3));
 	}
 
+	#[test]
+	fn test_roots() {
+		let roots = vec![
+			(
+				String::from("<repo>"),
+				string_vec_to_status(&["a/b/c"]),
+			),
+			(String::from("<sub s>"), string_vec_to_status(&["d"])),
+		];
+
+		//0 <repo>/
+		//1   a/
+		//2     b/
+		//3       c
+		//4 <sub s>/
+		//5   d
+
+		//0 <repo>/
+		//1   a/b/
+		//3       c
+		//4 <sub s>/
+		//5   d
+
+		let mut res = StatusTree::default();
+		res.update_roots(&roots, |folder| folder == "a").unwrap();
+
+		assert_eq!(res.available_selections, vec![0, 1, 3, 4, 5]);
+		assert_eq!(
+			get_visible(&res),
+			vec![true, true, false, false, true, true]
+		);
+		// the file to start on is hidden in `a`
+		assert_eq!(res.selection, Some(1));
+
+		assert!(res.move_selection(MoveSelection::Down));
+		assert_eq!(res.selection, Some(4));
+
+		// collapsing a root hides all of it
+		assert!(res.move_selection(MoveSelection::Left));
+		assert_eq!(
+			get_visible(&res),
+			vec![true, true, false, false, true, false]
+		);
+
+		res.expand_to_depth(usize::MAX);
+		assert_eq!(get_visible(&res), vec![true; 6]);
+
+		// roots stay expanded
+		res.expand_to_depth(0);
+		assert_eq!(
+			get_visible(&res),
+			vec![true, true, false, false, true, true]
+		);
+	}
+
 	#[test]
 	fn test_folders_fold_up_if_alone_in_directory() {
 		let items = string_vec_to_status(&[
@@ -0,0 +1,294 @@
+//! what changed between a removed and an added line on the level of
+//! words, used to highlight it inside of the lines
+
+use std::ops::Range;
+
+/// longer lines are not compared at all
+const MAX_LINE_LEN: usize = 2_000;
+/// upper bound of the lcs table, the differing middle part of the
+/// lines is marked as changed as a whole beyond that
+const MAX_TABLE_SIZE: usize = 100_000;
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum CharClass {
+	Word,
+	Space,
+	Other,
+}
+
+impl CharClass {
+	fn of(c: char) -> Self {
+		if c.is_alphanumeric() || c == '_' {
+			Self::Word
+		} else if c.is_whitespace() {
+			Self::Space
+		} else {
+			Self::Other
+		}
+	}
+}
+
+/// byte ranges of the changed parts of both lines
+#[derive(Debug, PartialEq, Eq)]
+pub struct WordChanges {
+	pub old: Vec<Range<usize>>,
+	pub new: Vec<Range<usize>>,
+}
+
+/// `None` if the lines are too long or have nothing in common
+pub fn word_diff(old: &str, new: &str) -> Option<WordChanges> {
+	if old.len() > MAX_LINE_LEN || new.len() > MAX_LINE_LEN {
+		return None;
+	}
+
+	let old_tokens = tokenize(old);
+	let new_tokens = tokenize(new);
+	let same = |a: &Range<usize>, b: &Range<usize>| {
+		old[a.clone()] == new[b.clone()]
+	};
+
+	let prefix = old_tokens
+		.iter()
+		.zip(&new_tokens)
+		.take_while(|(a, b)| same(a, b))
+		.count();
+	let suffix = old_tokens[prefix..]
+		.iter()
+		.rev()
+		.zip(new_tokens[prefix..].iter().rev())
+		.take_while(|(a, b)| same(a, b))
+		.count();
+
+	let old_middle = &old_tokens[prefix..old_tokens.len() - suffix];
+	let new_middle = &new_tokens[prefix..new_tokens.len() - suffix];
+
+	let (old_changed, new_changed) = if old_middle.len()
+		* new_middle.len()
+		> MAX_TABLE_SIZE
+	{
+		(vec![true; old_middle.len()], vec![true; new_middle.len()])
+	} else {
+		lcs_changed(old_middle, new_middle, same)
+	};
+
+	let is_word =
+		|token: &Range<usize>| !old[token.clone()].trim().is_empty();
+	let common = old_tokens[..prefix]
+		.iter()
+		.chain(&old_tokens[old_tokens.len() - suffix..])
+		.chain(
+			old_middle
+				.iter()
+				.zip(&old_changed)
+				.filter(|(_, changed)| !**changed)
+				.map(|(token, _)| token),
+		)
+		.filter(|token| is_word(token))
+		.count();
+
+	// highlighting everything does not add anything
+	if common == 0 {
+		return None;
+	}
+
+	Some(WordChanges {
+		old: changed_ranges(old_middle, &old_changed),
+		new: changed_ranges(new_middle, &new_changed),
+	})
+}
+
+/// splits the `visible` part of a line into parts that changed
+/// (`true`) or not
+pub fn word_segments(
+	visible: Range<usize>,
+	changes: &[Range<usize>],
+) -> Vec<(Range<usize>, bool)> {
+	let clamp =
+		|index: usize| index.clamp(visible.start, visible.end);
+
+	let mut segments = Vec::new();
+	let mut start = visible.start;
+
+	for range in changes {
+		let range = clamp(range.start)..clamp(range.end);
+		if range.is_empty() {
+			continue;
+		}
+
+		if start < range.start {
+			segments.push((start..range.start, false));
+		}
+		start = range.end;
+		segments.push((range, true));
+	}
+
+	if start < visible.end {
+		segments.push((start..visible.end, false));
+	}
+
+	segments
+}
+
+/// runs of word or whitespace characters, other characters are a
+/// token on their own
+fn tokenize(line: &str) -> Vec<Range<usize>> {
+	let mut tokens = Vec::new();
+	let mut chars = line.char_indices().peekable();
+
+	while let Some((start, c)) = chars.next() {
+		let class = CharClass::of(c);
+		let mut end = start + c.len_utf8();
+
+		if class != CharClass::Other {
+			while let Some((index, c)) =
+				chars.next_if(|(_, c)| CharClass::of(*c) == class)
+			{
+				end = index + c.len_utf8();
+			}
+		}
+
+		tokens.push(start..end);
+	}
+
+	tokens
+}
+
+/// marks the tokens that are not part of the longest common
+/// subsequence
+fn lcs_changed(
+	old: &[Range<usize>],
+	new: &[Range<usize>],
+	same: impl Fn(&Range<usize>, &Range<usize>) -> bool,
+) -> (Vec<bool>, Vec<bool>) {
+	let width = new.len() + 1;
+	// length of the lcs of `old[i..]` and `new[j..]`
+	let mut table = vec![0_u32; (old.len() + 1) * width];
+
+	for i in (0..old.len()).rev() {
+		for j in (0..new.len()).rev() {
+			table[i * width + j] = if same(&old[i], &new[j]) {
+				table[(i + 1) * width + j + 1] + 1
+			} else {
+				table[(i + 1) * width + j]
+					.max(table[i * width + j + 1])
+			};
+		}
+	}
+
+	let mut old_changed = vec![true; old.len()];
+	let mut new_changed = vec![true; new.len()];
+	let (mut i, mut j) = (0, 0);
+
+	while i < old.len() && j < new.len() {
+		if same(&old[i], &new[j]) {
+			old_changed[i] = false;
+			new_changed[j] = false;
+			i += 1;
+			j += 1;
+		} else if table[(i + 1) * width + j]
+			>= table[i * width + j + 1]
+		{
+			i += 1;
+		} else {
+			j += 1;
+		}
+	}
+
+	(old_changed, new_changed)
+}
+
+/// joins adjacent changed tokens
+fn changed_ranges(
+	tokens: &[Range<usize>],
+	changed: &[bool],
+) -> Vec<Range<usize>> {
+	let mut ranges: Vec<Range<usize>> = Vec::new();
+
+	for (token, _) in
+		tokens.iter().zip(changed).filter(|(_, changed)| **changed)
+	{
+		match ranges.last_mut() {
+			Some(last) if last.end == token.start => {
+				last.end = token.end;
+			}
+			_ => ranges.push(token.clone()),
+		}
+	}
+
+	ranges
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	fn changed<'a>(
+		line: &'a str,
+		ranges: &[Range<usize>],
+	) -> Vec<&'a str> {
+		ranges.iter().map(|range| &line[range.clone()]).collect()
+	}
+
+	#[test]
+	fn test_changed_word() {
+		let old = "let foo = bar(1, 2);";
+		let new = "let foo = baz(1, 3, 2);";
+
+		let changes = word_diff(old, new).unwrap();
+
+		assert_eq!(changed(old, &changes.old), vec!["bar"]);
+		assert_eq!(changed(new, &changes.new), vec!["baz", ", 3"]);
+	}
+
+	#[test]
+	fn test_unicode() {
+		let old = "größe: äpfel";
+		let new = "größe: birnen";
+
+		let changes = word_diff(old, new).unwrap();
+
+		assert_eq!(changed(old, &changes.old), vec!["äpfel"]);
+		assert_eq!(changed(new, &changes.new), vec!["birnen"]);
+	}
+
+	#[test]
+	fn test_nothing_in_common() {
+		assert_eq!(word_diff("foo bar", "baz qux"), None);
+		assert_eq!(word_diff("", "new"), None);
+	}
+
+	#[test]
+	fn test_word_segments() {
+		assert_eq!(
+			word_segments(2..10, &[0..3, 5..6, 9..12]),
+			vec![
+				(2..3, true),
+				(3..5, false),
+				(5..6, true),
+				(6..9, false),
+				(9..10, true)
+			]
+		);
+		assert_eq!(
+			word_segments(4..8, &[0..2, 9..10]),
+			vec![(4..8, false)]
+		);
+		assert_eq!(word_segments(4..4, &[]), vec![]);
+	}
+
+	#[test]
+	fn test_long_lines() {
+		let old = "a ".repeat(MAX_LINE_LEN);
+		assert_eq!(word_diff(&old, "a"), None);
+
+		// too many tokens for the table, still finds prefix and suffix
+		let old = format!("start {} end", "x ".repeat(400));
+		let new = format!("start {} end", "y ".repeat(400));
+
+		let changes = word_diff(&old, &new).unwrap();
+
+		assert_eq!(changes.old, vec![6..805]);
+		assert_eq!(changes.new, vec![6..805]);
+	}
+}
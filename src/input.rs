@@ -1,7 +1,10 @@
 use crate::notify_mutex::NotifyableMutex;
 use anyhow::Result;
 use crossbeam_channel::{unbounded, Receiver, Sender};
-use crossterm::event::{self, Event, Event::Key, KeyEventKind};
+use crossterm::event::{
+	self, Event, Event::Key, Event::Mouse, KeyEventKind,
+	MouseEventKind,
+};
 use std::{
 	sync::{
 		atomic::{AtomicBool, Ordering},
@@ -121,6 +124,18 @@ impl Input {
 							continue;
 						}
 					}
+					// only clicks and the wheel are used, do not
+					// redraw on every move
+					if let Mouse(mouse) = e {
+						if matches!(
+							mouse.kind,
+							MouseEventKind::Moved
+								| MouseEventKind::Drag(_)
+								| MouseEventKind::Up(_)
+						) {
+							continue;
+						}
+					}
 
 					tx.send(InputEvent::Input(e))?;
 					//Note: right after an input event we might have a reason to stop
@@ -1,11 +1,15 @@
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyModifiers};
-use std::{fs::canonicalize, path::PathBuf, rc::Rc};
+use std::{
+	fs::{self, canonicalize},
+	path::PathBuf,
+	rc::Rc,
+};
 
-use crate::{args::get_app_config_path, strings::symbol};
+use crate::{args::get_app_config_path, ui::GlyphSet};
 
 use super::{
-	key_list::{GituiKeyEvent, KeysList},
+	key_list::{GituiKeyEvent, KeyConfigWarning, KeysList},
 	symbols::KeySymbols,
 };
 
@@ -16,6 +20,8 @@ const KEY_SYMBOLS_FILENAME: &str = "key_symbols.ron";
 #[derive(Default, Clone)]
 pub struct KeyConfig {
 	pub keys: KeysList,
+	/// problems of the key bindings file
+	pub warnings: Vec<KeyConfigWarning>,
 	symbols: KeySymbols,
 }
 
@@ -34,10 +40,32 @@ impl KeyConfig {
 			.map_or_else(|_| Ok(symbols_file), Ok)
 	}
 
-	pub fn init() -> Result<Self> {
-		let keys = KeysList::init(Self::get_config_file()?);
-		let symbols = KeySymbols::init(Self::get_symbols_file()?);
-		Ok(Self { keys, symbols })
+	pub fn init(glyphs: GlyphSet) -> Result<Self> {
+		let (keys, warnings) =
+			KeysList::init(Self::get_config_file()?);
+		let symbols =
+			KeySymbols::init(Self::get_symbols_file()?, glyphs);
+		Ok(Self {
+			keys,
+			warnings,
+			symbols,
+		})
+	}
+
+	/// path and content of the key bindings file, if there is one
+	pub fn read_config_file() -> Option<(PathBuf, String)> {
+		let file = Self::get_config_file().ok()?;
+		let content = fs::read_to_string(&file).ok()?;
+		Some((file, content))
+	}
+
+	/// default key bindings hinted with the symbols of `glyphs`
+	pub fn with_glyphs(glyphs: GlyphSet) -> Self {
+		Self {
+			keys: KeysList::default(),
+			warnings: Vec::new(),
+			symbols: KeySymbols::for_glyphs(glyphs),
+		}
 	}
 
 	#[expect(
@@ -88,7 +116,7 @@ impl KeyConfig {
 					self.get_key_symbol(ev.code)
 				)
 			}
-			KeyCode::Char(' ') => String::from(symbol::SPACE),
+			KeyCode::Char(' ') => self.symbols.space.clone(),
 			KeyCode::Char(c) => {
 				format!(
 					"{}{}",
@@ -141,6 +169,44 @@ mod tests {
 		assert_eq!(h, "^c");
 	}
 
+	#[test]
+	fn test_get_hint_ascii() {
+		let config = KeyConfig::with_glyphs(GlyphSet::Ascii);
+		for code in [
+			KeyCode::Enter,
+			KeyCode::Up,
+			KeyCode::Down,
+			KeyCode::Left,
+			KeyCode::Right,
+			KeyCode::Char(' '),
+			KeyCode::Tab,
+			KeyCode::BackTab,
+			KeyCode::Esc,
+			KeyCode::Delete,
+		] {
+			for modifiers in [
+				KeyModifiers::NONE,
+				KeyModifiers::SHIFT,
+				KeyModifiers::ALT,
+			] {
+				let h = config
+					.get_hint(GituiKeyEvent::new(code, modifiers));
+				assert!(h.is_ascii(), "{h}");
+			}
+		}
+		assert_eq!(
+			config.get_hint(GituiKeyEvent::new(
+				KeyCode::Down,
+				KeyModifiers::SHIFT
+			)),
+			"S-Down"
+		);
+	}
+
+	fn init_unicode() -> KeyConfig {
+		KeyConfig::init(GlyphSet::Unicode).unwrap()
+	}
+
 	#[test]
 	fn test_symbolic_links() {
 		let app_home = get_app_config_path().unwrap();
@@ -193,7 +259,7 @@ mod tests {
 
 		// testing
 		let result = std::panic::catch_unwind(|| {
-			let loaded_config = KeyConfig::init().unwrap();
+			let loaded_config = init_unicode();
 			assert_eq!(
 				loaded_config.keys.move_down,
 				KeysList::default().move_down
@@ -208,7 +274,7 @@ mod tests {
 				&original_key_symbols_path,
 			)
 			.unwrap();
-			let loaded_config = KeyConfig::init().unwrap();
+			let loaded_config = init_unicode();
 			assert_eq!(
 				loaded_config.keys.move_down,
 				KeysList::default().move_down
@@ -220,7 +286,7 @@ mod tests {
 				&original_key_list_path,
 			)
 			.unwrap();
-			let loaded_config = KeyConfig::init().unwrap();
+			let loaded_config = init_unicode();
 			assert_eq!(
 				loaded_config.keys.move_down,
 				GituiKeyEvent::new(
@@ -231,7 +297,7 @@ mod tests {
 			assert_eq!(loaded_config.symbols.esc, "Esc");
 
 			fs::remove_file(&original_key_symbols_path).unwrap();
-			let loaded_config = KeyConfig::init().unwrap();
+			let loaded_config = init_unicode();
 			assert_eq!(
 				loaded_config.keys.move_down,
 				GituiKeyEvent::new(
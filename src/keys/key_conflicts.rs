@@ -0,0 +1,749 @@
+//! finds key bindings that never see their key because another
+//! binding takes it first and actions that can not be reached at all
+
+use super::key_list::{GituiKeyEvent, KeysList};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::cmp::Ordering;
+
+/// part of the ui that checks keys on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum KeyArea {
+	Status,
+	Diff,
+	/// search input of the diff
+	DiffSearch,
+	Log,
+	Files,
+	Stashing,
+	Stashes,
+	Help,
+	Commit,
+	/// single line text popups (branch, tag or remote names, ...)
+	Input,
+	/// find and grep popups
+	Search,
+	Branches,
+	Tags,
+	Remotes,
+	Submodules,
+	Bookmarks,
+	ReplaceRefs,
+	Oplog,
+	Reflog,
+	Worktrees,
+	Options,
+	/// file history and blame
+	History,
+	/// local history of a changed file
+	Snapshots,
+	/// inspect and compare commits
+	Inspect,
+	/// fixer run on the staged files
+	FixStaged,
+	/// conflict editor
+	Merge,
+	/// confirm, message, push, reset and other small popups
+	Dialog,
+}
+
+use KeyArea::{
+	Bookmarks, Branches, Commit, Dialog, Diff, DiffSearch, Files,
+	FixStaged, Help, History, Input, Inspect, Log, Merge, Oplog,
+	Options, Reflog, Remotes, ReplaceRefs, Search, Snapshots,
+	Stashes, Stashing, Status, Submodules, Tags, Worktrees,
+};
+
+impl KeyArea {
+	///
+	pub const fn name(self) -> &'static str {
+		match self {
+			Status => "status",
+			Diff => "diff",
+			DiffSearch => "diff search",
+			Log => "log",
+			Files => "files",
+			Stashing => "stashing",
+			Stashes => "stashes",
+			Help => "help",
+			Commit => "commit",
+			Input => "text input",
+			Search => "search",
+			Branches => "branches",
+			Tags => "tags",
+			Remotes => "remotes",
+			Submodules => "submodules",
+			Bookmarks => "bookmarks",
+			ReplaceRefs => "replace refs",
+			Oplog => "oplog",
+			Reflog => "reflog",
+			Worktrees => "worktrees",
+			Options => "options",
+			History => "file history",
+			Snapshots => "local history",
+			Inspect => "inspect commit",
+			FixStaged => "fix staged",
+			Merge => "conflict editor",
+			Dialog => "dialogs",
+		}
+	}
+
+	/// plain characters are typed as text here
+	const fn is_text_input(self) -> bool {
+		matches!(self, Commit | Input | Search | DiffSearch)
+	}
+}
+
+const ALL: &[KeyArea] = &[
+	Status,
+	Diff,
+	DiffSearch,
+	Log,
+	Files,
+	Stashing,
+	Stashes,
+	Help,
+	Commit,
+	Input,
+	Search,
+	Branches,
+	Tags,
+	Remotes,
+	Submodules,
+	Bookmarks,
+	ReplaceRefs,
+	Oplog,
+	Reflog,
+	Worktrees,
+	Options,
+	History,
+	Snapshots,
+	Inspect,
+	FixStaged,
+	Merge,
+	Dialog,
+];
+const TABS: &[KeyArea] =
+	&[Status, Diff, Log, Files, Stashing, Stashes];
+const POPUPS: &[KeyArea] = &[
+	Help,
+	Commit,
+	Input,
+	Search,
+	Branches,
+	Tags,
+	Remotes,
+	Submodules,
+	Bookmarks,
+	ReplaceRefs,
+	Oplog,
+	Reflog,
+	Worktrees,
+	Options,
+	History,
+	Snapshots,
+	Inspect,
+	FixStaged,
+	Merge,
+	Dialog,
+];
+const LISTS: &[KeyArea] = &[
+	Status,
+	Diff,
+	Log,
+	Files,
+	Stashing,
+	Stashes,
+	Help,
+	Branches,
+	Tags,
+	Remotes,
+	Submodules,
+	Bookmarks,
+	ReplaceRefs,
+	Oplog,
+	Reflog,
+	Worktrees,
+	Options,
+	History,
+	Snapshots,
+	Inspect,
+	FixStaged,
+	Merge,
+	Dialog,
+];
+const SCROLLING: &[KeyArea] = &[
+	Status,
+	Diff,
+	Log,
+	Files,
+	Stashing,
+	Stashes,
+	Branches,
+	Tags,
+	Remotes,
+	Submodules,
+	Bookmarks,
+	ReplaceRefs,
+	Oplog,
+	Reflog,
+	Worktrees,
+	History,
+	Snapshots,
+	FixStaged,
+	Merge,
+	Dialog,
+];
+const FILE_LISTS: &[KeyArea] =
+	&[Status, Stashing, Files, Log, Inspect, History, FixStaged];
+
+/// when a binding gets to see a key, earlier ones see it first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Level {
+	/// before anything else, even in popups
+	Always,
+	/// before the tabs, unless a popup is open
+	BeforeTabs,
+	/// by the focused part of a tab or by the open popup
+	Area,
+	/// once the tab did not use the key, unless a popup is open
+	AfterTabs,
+}
+
+use Level::{AfterTabs, Always, Area, BeforeTabs};
+
+struct Binding {
+	name: &'static str,
+	key: GituiKeyEvent,
+	default: GituiKeyEvent,
+	contexts: Vec<(Level, &'static [KeyArea])>,
+}
+
+impl Binding {
+	/// the earliest point the binding sees keys in `area`
+	fn level_in(&self, area: KeyArea) -> Option<Level> {
+		self.contexts
+			.iter()
+			.filter(|(_, areas)| areas.contains(&area))
+			.map(|(level, _)| *level)
+			.min()
+	}
+
+	fn is_changed(&self) -> bool {
+		self.key != self.default
+	}
+
+	/// bindings sharing their default key are meant to share it
+	fn collides_with(&self, other: &Self) -> bool {
+		self.key == other.key && self.default != other.default
+	}
+}
+
+macro_rules! bindings {
+	(
+		$keys:expr, $defaults:expr,
+		{ $($name:ident: [$($level:ident => $areas:expr),+],)* }
+	) => {{
+		// no `..`: a new binding does not build until it is listed
+		let KeysList { $($name),* } = $keys;
+		let defaults = $defaults;
+		vec![$(Binding {
+			name: stringify!($name),
+			key: *$name,
+			default: defaults.$name,
+			contexts: vec![$(($level, $areas)),+],
+		}),*]
+	}};
+}
+
+#[allow(clippy::too_many_lines)]
+fn bindings(keys: &KeysList) -> Vec<Binding> {
+	bindings!(keys, KeysList::default(), {
+		tab_status: [AfterTabs => TABS],
+		tab_log: [AfterTabs => TABS],
+		tab_files: [AfterTabs => TABS],
+		tab_stashing: [AfterTabs => TABS],
+		tab_stashes: [AfterTabs => TABS],
+		tab_toggle: [AfterTabs => TABS, Area => &[Branches]],
+		tab_toggle_reverse: [AfterTabs => TABS],
+		toggle_workarea: [Area => &[Status]],
+		exit: [Always => ALL],
+		quit: [BeforeTabs => TABS],
+		quit_print: [BeforeTabs => TABS],
+		exit_popup: [
+			Area => &[Status, Diff, DiffSearch, Log],
+			Area => POPUPS
+		],
+		open_commit: [Area => &[Status]],
+		open_commit_editor: [Area => &[Commit]],
+		open_help: [BeforeTabs => TABS],
+		open_key_conflicts: [Area => &[Help]],
+		open_options: [AfterTabs => TABS],
+		open_oplog: [AfterTabs => TABS],
+		open_reflog: [AfterTabs => TABS],
+		refresh: [AfterTabs => TABS, Area => &[Branches]],
+		refresh_all: [AfterTabs => TABS],
+		oplog_filter: [Area => &[Oplog]],
+		move_left: [Area => &[
+			Status, Diff, Files, Options, Inspect, FixStaged,
+		]],
+		move_right: [Area => &[
+			Status, Diff, Log, Files, Options, Inspect, History,
+			Snapshots, Tags, Branches, FixStaged,
+		]],
+		move_up: [Area => LISTS],
+		move_down: [Area => LISTS],
+		tree_collapse_recursive: [Area => &[Files]],
+		tree_expand_recursive: [Area => &[Files]],
+		home: [Area => SCROLLING],
+		end: [Area => SCROLLING],
+		popup_up: [Area => &[Search, Dialog]],
+		popup_down: [Area => &[Search, Dialog]],
+		page_down: [Area => SCROLLING],
+		page_up: [Area => SCROLLING],
+		shift_up: [Area => SCROLLING],
+		shift_down: [Area => SCROLLING],
+		enter: [Area => &[Log, Stashes, DiffSearch], Area => POPUPS],
+		blame: [Area => FILE_LISTS],
+		file_history: [Area => FILE_LISTS],
+		file_history_goto_log: [Area => &[History]],
+		files_goto_log: [Area => &[Files]],
+		open_local_history: [Area => &[Status, Stashing]],
+		local_history_restore: [Area => &[Snapshots]],
+		edit_file: [Area => &[Status, Files, Merge]],
+		status_stage_all: [Area => &[Status, FixStaged]],
+		status_reset_item: [Area => &[Status, Diff, Log]],
+		status_ignore_file: [Area => &[Status]],
+		status_fixup: [Area => &[Status]],
+		status_fix_staged: [Area => &[Status]],
+		status_resolve_conflict: [Area => &[Status]],
+		status_stash_selected: [Area => &[Status]],
+		conflict_take_ours: [Area => &[Merge]],
+		conflict_take_theirs: [Area => &[Merge]],
+		conflict_take_both: [Area => &[Merge]],
+		conflict_take_both_reverse: [Area => &[Merge]],
+		conflict_save: [Area => &[Merge]],
+		status_scope_folder: [Area => &[Status]],
+		status_clear_scope: [Area => &[Status]],
+		review_toggle_viewed: [Area => &[Inspect]],
+		diff_stage_lines: [Area => &[Diff]],
+		diff_reset_lines: [Area => &[Diff]],
+		stashing_save: [Area => &[Stashing]],
+		stashing_toggle_untracked: [Area => &[Stashing]],
+		stashing_toggle_index: [Area => &[Stashing]],
+		stash_apply: [Area => &[Stashes, Inspect]],
+		stash_open: [Area => &[Stashes]],
+		stash_drop: [Area => &[Stashes]],
+		stash_branch: [Area => &[Stashes]],
+		cmd_bar_toggle: [
+			AfterTabs => TABS,
+			Area => &[Branches, Submodules]
+		],
+		log_branch_commit: [Area => &[Log, Reflog]],
+		log_tag_commit: [Area => &[Log]],
+		log_bookmark_commit: [Area => &[Log]],
+		log_bookmarks: [Area => &[Log]],
+		bookmark_delete: [Area => &[Bookmarks]],
+		bookmark_repoint: [Area => &[Bookmarks]],
+		log_replace_refs: [Area => &[Log]],
+		log_toggle_replace_refs: [Area => &[Log]],
+		replace_ref_delete: [Area => &[ReplaceRefs]],
+		process_kill: [Area => &[Dialog, FixStaged]],
+		pull_toggle_rebase: [Area => &[Dialog]],
+		log_mark_commit: [Area => &[Log, Status]],
+		log_checkout_commit: [Area => &[Log, Reflog]],
+		log_reset_commit: [Area => &[Log, Reflog]],
+		log_reword_commit: [Area => &[Log]],
+		log_fixup_commit: [Area => &[Log]],
+		log_squash_commit: [Area => &[Log]],
+		log_autosquash: [Area => &[Log]],
+		log_amend_commit: [Area => &[Log]],
+		log_cherry_pick: [Area => &[Log]],
+		log_bisect_bad: [Area => &[Log]],
+		log_bisect_good: [Area => &[Log]],
+		log_bisect_skip: [Area => &[Log]],
+		log_bisect_reset: [Area => &[Log]],
+		log_find: [Area => &[Log]],
+		log_filter: [Area => &[Log]],
+		log_deepen_history: [Area => &[Log]],
+		find_commit_sha: [Area => &[Search]],
+		commit_amend: [Area => &[Commit]],
+		toggle_signoff: [Area => &[Commit]],
+		toggle_verify: [Area => &[Commit]],
+		copy: [Area => &[Status, Diff, Files, Log, Inspect, Dialog]],
+		copy_absolute_path: [Area => &[Status, Files]],
+		copy_short_hash: [Area => &[Log, Inspect]],
+		copy_commit_summary: [Area => &[Log, Inspect]],
+		create_branch: [Area => &[Branches]],
+		toggle_push_after_create: [Area => &[Input]],
+		toggle_checkout_after_create: [Area => &[Input]],
+		rename_branch: [Area => &[Branches]],
+		select_branch: [Area => &[Status, Log]],
+		delete_branch: [Area => &[Branches]],
+		merge_branch: [Area => &[Branches]],
+		rebase_branch: [Area => &[Branches, Status]],
+		reset_branch: [Area => &[Branches]],
+		compare_commits: [Area => &[Branches, Log, Stashes, Inspect]],
+		compare_branch: [Area => &[Branches]],
+		branch_park: [Area => &[Branches]],
+		branch_unpark: [Area => &[Branches]],
+		branch_push_remote: [Area => &[Branches]],
+		compare_base_next: [Area => &[Inspect]],
+		tags: [Area => &[Log]],
+		delete_tag: [Area => &[Tags]],
+		select_tag: [Area => &[Tags]],
+		push_all_tags: [Area => &[Tags]],
+		delete_remote_tag: [Area => &[Tags]],
+		push: [Area => &[Status, Log, Tags]],
+		open_pull_request: [Area => &[Status]],
+		open_file_tree: [Area => &[Log, Inspect]],
+		file_find: [Area => &[Files]],
+		file_grep: [Area => &[Files]],
+		file_grep_regex: [Area => &[Search]],
+		branch_find: [Area => &[Branches]],
+		force_push: [Area => &[Status]],
+		fetch: [Area => &[Branches, Status]],
+		pull: [Area => &[Status]],
+		abort_merge: [Area => &[Status]],
+		undo_commit: [Area => &[Status]],
+		diff_hunk_next: [Area => &[Diff, Merge]],
+		diff_hunk_prev: [Area => &[Diff, Merge]],
+		diff_toggle_split: [Area => &[Diff]],
+		diff_search: [Area => &[Diff]],
+		diff_search_next: [Area => &[Diff]],
+		diff_search_prev: [Area => &[Diff]],
+		diff_search_case: [Area => &[DiffSearch]],
+		diff_lfs_content: [Area => &[Diff]],
+		toggle_hex_view: [Area => &[Files]],
+		stage_unstage_item: [Area => &[Status, Diff]],
+		tag_annotate: [Area => &[Input]],
+		view_submodules: [Area => &[Status]],
+		view_remotes: [Area => &[Branches]],
+		update_remote_name: [Area => &[Remotes]],
+		update_remote_url: [Area => &[Remotes]],
+		add_remote: [Area => &[Remotes]],
+		delete_remote: [Area => &[Remotes]],
+		remote_set_default: [Area => &[Remotes]],
+		remote_set_push_default: [Area => &[Remotes]],
+		view_submodule_parent: [Area => &[Submodules]],
+		update_submodule: [Area => &[Submodules]],
+		init_submodule: [Area => &[Submodules]],
+		view_worktrees: [Area => &[Status]],
+		branch_create_worktree: [Area => &[Branches]],
+		prune_worktrees: [Area => &[Worktrees]],
+		toggle_worktree_force: [Area => &[Input]],
+		commit_history_next: [Area => &[Commit]],
+		commit_template_next: [Area => &[Commit]],
+		commit_pull_first: [Area => &[Commit]],
+		commit_pull_rebase_first: [Area => &[Commit]],
+		commit_diff_scroll_up: [Area => &[Commit]],
+		commit_diff_scroll_down: [Area => &[Commit]],
+		commit_reflow: [Area => &[Commit]],
+		commit: [Area => &[Commit, Input]],
+		newline: [Area => &[Commit, Input]],
+		scroll_up_half_page: [Area => SCROLLING],
+		scroll_down_half_page: [Area => SCROLLING],
+	})
+}
+
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyIssueKind {
+	/// `by` sees the key before `hidden`
+	Hidden {
+		by: &'static str,
+		hidden: &'static str,
+	},
+	/// both see the key at the same time, which one gets it depends
+	/// on the focus or the order they are checked in
+	Ambiguous {
+		first: &'static str,
+		second: &'static str,
+	},
+	/// the key of the binding can not be typed as text anymore
+	Typing(&'static str),
+	/// the binding is hidden everywhere
+	Unreachable(&'static str),
+	/// the binding has no key
+	Unbound(&'static str),
+}
+
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyIssue {
+	///
+	pub kind: KeyIssueKind,
+	///
+	pub key: GituiKeyEvent,
+	/// where it happens
+	pub areas: Vec<KeyArea>,
+	/// binding to change in the config
+	pub fix: &'static str,
+}
+
+/// conflicts of the bindings in `keys` with each other and actions
+/// left without a key that reaches them
+pub fn key_issues(keys: &KeysList) -> Vec<KeyIssue> {
+	let bindings = bindings(keys);
+	let mut issues = Vec::new();
+
+	for (index, a) in bindings.iter().enumerate() {
+		for b in &bindings[index + 1..] {
+			if a.collides_with(b) {
+				pair_issues(a, b, &mut issues);
+			}
+		}
+	}
+
+	for binding in &bindings {
+		let mut areas = ALL
+			.iter()
+			.copied()
+			.filter(|area| binding.level_in(*area).is_some());
+
+		let typed_in: Vec<KeyArea> = areas
+			.clone()
+			.filter(|area| area.is_text_input())
+			.collect();
+		if is_typed(binding.key) && !typed_in.is_empty() {
+			issues.push(KeyIssue {
+				kind: KeyIssueKind::Typing(binding.name),
+				key: binding.key,
+				areas: typed_in,
+				fix: binding.name,
+			});
+		}
+
+		let kind = if binding.key.code == KeyCode::Null {
+			KeyIssueKind::Unbound(binding.name)
+		} else if areas.any(|area| {
+			!bindings.iter().any(|other| {
+				other.collides_with(binding)
+					&& other.level_in(area).is_some_and(|level| {
+						binding.level_in(area) > Some(level)
+					})
+			})
+		}) {
+			continue;
+		} else {
+			KeyIssueKind::Unreachable(binding.name)
+		};
+
+		issues.push(KeyIssue {
+			kind,
+			key: binding.key,
+			areas: Vec::new(),
+			fix: binding.name,
+		});
+	}
+
+	issues
+}
+
+fn pair_issues(a: &Binding, b: &Binding, issues: &mut Vec<KeyIssue>) {
+	let mut a_first = Vec::new();
+	let mut b_first = Vec::new();
+	let mut ambiguous = Vec::new();
+
+	for area in ALL {
+		if let (Some(level_a), Some(level_b)) =
+			(a.level_in(*area), b.level_in(*area))
+		{
+			match level_a.cmp(&level_b) {
+				Ordering::Less => a_first.push(*area),
+				Ordering::Greater => b_first.push(*area),
+				Ordering::Equal => ambiguous.push(*area),
+			}
+		}
+	}
+
+	for (by, hidden, areas) in [(a, b, a_first), (b, a, b_first)] {
+		if !areas.is_empty() {
+			issues.push(KeyIssue {
+				kind: KeyIssueKind::Hidden {
+					by: by.name,
+					hidden: hidden.name,
+				},
+				key: a.key,
+				areas,
+				fix: if by.is_changed() && !hidden.is_changed() {
+					by.name
+				} else {
+					hidden.name
+				},
+			});
+		}
+	}
+
+	if !ambiguous.is_empty() {
+		issues.push(KeyIssue {
+			kind: KeyIssueKind::Ambiguous {
+				first: a.name,
+				second: b.name,
+			},
+			key: a.key,
+			areas: ambiguous,
+			fix: if b.is_changed() && !a.is_changed() {
+				b.name
+			} else {
+				a.name
+			},
+		});
+	}
+}
+
+/// ends up as text in a text input
+const fn is_typed(key: GituiKeyEvent) -> bool {
+	matches!(key.code, KeyCode::Char(_))
+		&& key.modifiers.difference(KeyModifiers::SHIFT).is_empty()
+}
+
+/// whether `name` is a binding of `KeysList`
+pub fn is_action(name: &str) -> bool {
+	bindings(&KeysList::default())
+		.iter()
+		.any(|binding| binding.name == name)
+}
+
+/// line (1-based) that sets `binding` in the content of the key config
+pub fn config_line(config: &str, binding: &str) -> Option<usize> {
+	config
+		.lines()
+		.position(|line| {
+			line.trim_start().strip_prefix(binding).is_some_and(
+				|rest| rest.trim_start().starts_with(':'),
+			)
+		})
+		.map(|index| index + 1)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	use std::fs::File;
+	use struct_patch::traits::Patch;
+
+	fn key(c: char, modifiers: KeyModifiers) -> GituiKeyEvent {
+		GituiKeyEvent::new(KeyCode::Char(c), modifiers)
+	}
+
+	#[test]
+	fn test_defaults_have_no_issues() {
+		assert_eq!(key_issues(&KeysList::default()), vec![]);
+	}
+
+	#[test]
+	fn test_vim_style_example() {
+		let mut keys = KeysList::default();
+		let config =
+			std::fs::read_to_string("vim_style_key_config.ron")
+				.unwrap();
+		keys.apply(
+			ron::de::from_reader(
+				File::open("vim_style_key_config.ron").unwrap(),
+			)
+			.unwrap(),
+		);
+
+		let issues = key_issues(&keys);
+
+		assert_eq!(
+			issues,
+			vec![KeyIssue {
+				kind: KeyIssueKind::Ambiguous {
+					first: "status_reset_item",
+					second: "undo_commit",
+				},
+				key: key('U', KeyModifiers::SHIFT),
+				areas: vec![Status],
+				fix: "status_reset_item",
+			}]
+		);
+		assert_eq!(
+			config.lines().nth(
+				config_line(&config, issues[0].fix).unwrap() - 1
+			),
+			Some(
+				"    status_reset_item: Some(( code: Char('U'), modifiers: \"SHIFT\")),"
+			)
+		);
+	}
+
+	#[test]
+	fn test_hidden_and_unreachable() {
+		let keys = KeysList {
+			// global keys see it before the commit popup
+			exit: key('s', KeyModifiers::CONTROL),
+			// the status tab sees it before switching tabs
+			tab_log: key('s', KeyModifiers::empty()),
+			..KeysList::default()
+		};
+
+		let issues = key_issues(&keys);
+
+		assert_eq!(
+			issues,
+			vec![
+				KeyIssue {
+					kind: KeyIssueKind::Hidden {
+						by: "diff_stage_lines",
+						hidden: "tab_log",
+					},
+					key: key('s', KeyModifiers::empty()),
+					areas: vec![Diff],
+					fix: "tab_log",
+				},
+				KeyIssue {
+					kind: KeyIssueKind::Hidden {
+						by: "stashing_save",
+						hidden: "tab_log",
+					},
+					key: key('s', KeyModifiers::empty()),
+					areas: vec![Stashing],
+					fix: "tab_log",
+				},
+				KeyIssue {
+					kind: KeyIssueKind::Hidden {
+						by: "exit",
+						hidden: "toggle_signoff",
+					},
+					key: key('s', KeyModifiers::CONTROL),
+					areas: vec![Commit],
+					fix: "exit",
+				},
+				KeyIssue {
+					kind: KeyIssueKind::Unreachable("toggle_signoff"),
+					key: key('s', KeyModifiers::CONTROL),
+					areas: vec![],
+					fix: "toggle_signoff",
+				},
+			]
+		);
+	}
+
+	#[test]
+	fn test_typing() {
+		let keys = KeysList {
+			toggle_signoff: key('S', KeyModifiers::SHIFT),
+			..KeysList::default()
+		};
+
+		assert_eq!(
+			key_issues(&keys),
+			vec![KeyIssue {
+				kind: KeyIssueKind::Typing("toggle_signoff"),
+				key: key('S', KeyModifiers::SHIFT),
+				areas: vec![Commit],
+				fix: "toggle_signoff",
+			}]
+		);
+	}
+
+	#[test]
+	fn test_config_line() {
+		let config = "(\n  // move_up: comment\n  move_up : Some(()),\n  move_up_more: Some(()),\n)";
+
+		assert_eq!(config_line(config, "move_up"), Some(3));
+		assert_eq!(config_line(config, "move_down"), None);
+	}
+}
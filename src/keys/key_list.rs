@@ -1,6 +1,10 @@
+use super::key_conflicts::is_action;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use serde::{Deserialize, Serialize};
-use std::{fs::File, path::PathBuf};
+use serde::{
+	de::{IgnoredAny, MapAccess, Visitor},
+	Deserialize, Deserializer, Serialize,
+};
+use std::{fmt, fs, path::PathBuf};
 use struct_patch::traits::Patch as PatchTrait;
 use struct_patch::Patch;
 
@@ -28,6 +32,8 @@ impl PartialEq for GituiKeyEvent {
 	}
 }
 
+impl Eq for GituiKeyEvent {}
+
 impl From<&GituiKeyEvent> for KeyEvent {
 	fn from(other: &GituiKeyEvent) -> Self {
 		Self::new(other.code, other.modifiers)
@@ -47,11 +53,18 @@ pub struct KeysList {
 	pub toggle_workarea: GituiKeyEvent,
 	pub exit: GituiKeyEvent,
 	pub quit: GituiKeyEvent,
+	pub quit_print: GituiKeyEvent,
 	pub exit_popup: GituiKeyEvent,
 	pub open_commit: GituiKeyEvent,
 	pub open_commit_editor: GituiKeyEvent,
 	pub open_help: GituiKeyEvent,
+	pub open_key_conflicts: GituiKeyEvent,
 	pub open_options: GituiKeyEvent,
+	pub open_oplog: GituiKeyEvent,
+	pub open_reflog: GituiKeyEvent,
+	pub refresh: GituiKeyEvent,
+	pub refresh_all: GituiKeyEvent,
+	pub oplog_filter: GituiKeyEvent,
 	pub move_left: GituiKeyEvent,
 	pub move_right: GituiKeyEvent,
 	pub move_up: GituiKeyEvent,
@@ -69,10 +82,26 @@ pub struct KeysList {
 	pub enter: GituiKeyEvent,
 	pub blame: GituiKeyEvent,
 	pub file_history: GituiKeyEvent,
+	pub file_history_goto_log: GituiKeyEvent,
+	pub files_goto_log: GituiKeyEvent,
+	pub open_local_history: GituiKeyEvent,
+	pub local_history_restore: GituiKeyEvent,
 	pub edit_file: GituiKeyEvent,
 	pub status_stage_all: GituiKeyEvent,
 	pub status_reset_item: GituiKeyEvent,
 	pub status_ignore_file: GituiKeyEvent,
+	pub status_fixup: GituiKeyEvent,
+	pub status_fix_staged: GituiKeyEvent,
+	pub status_resolve_conflict: GituiKeyEvent,
+	pub status_stash_selected: GituiKeyEvent,
+	pub conflict_take_ours: GituiKeyEvent,
+	pub conflict_take_theirs: GituiKeyEvent,
+	pub conflict_take_both: GituiKeyEvent,
+	pub conflict_take_both_reverse: GituiKeyEvent,
+	pub conflict_save: GituiKeyEvent,
+	pub status_scope_folder: GituiKeyEvent,
+	pub status_clear_scope: GituiKeyEvent,
+	pub review_toggle_viewed: GituiKeyEvent,
 	pub diff_stage_lines: GituiKeyEvent,
 	pub diff_reset_lines: GituiKeyEvent,
 	pub stashing_save: GituiKeyEvent,
@@ -81,19 +110,46 @@ pub struct KeysList {
 	pub stash_apply: GituiKeyEvent,
 	pub stash_open: GituiKeyEvent,
 	pub stash_drop: GituiKeyEvent,
+	pub stash_branch: GituiKeyEvent,
 	pub cmd_bar_toggle: GituiKeyEvent,
+	pub log_branch_commit: GituiKeyEvent,
 	pub log_tag_commit: GituiKeyEvent,
+	pub log_bookmark_commit: GituiKeyEvent,
+	pub log_bookmarks: GituiKeyEvent,
+	pub bookmark_delete: GituiKeyEvent,
+	pub bookmark_repoint: GituiKeyEvent,
+	pub log_replace_refs: GituiKeyEvent,
+	pub log_toggle_replace_refs: GituiKeyEvent,
+	pub replace_ref_delete: GituiKeyEvent,
+	pub process_kill: GituiKeyEvent,
+	pub pull_toggle_rebase: GituiKeyEvent,
 	pub log_mark_commit: GituiKeyEvent,
 	pub log_checkout_commit: GituiKeyEvent,
 	pub log_reset_commit: GituiKeyEvent,
 	pub log_reword_commit: GituiKeyEvent,
+	pub log_fixup_commit: GituiKeyEvent,
+	pub log_squash_commit: GituiKeyEvent,
+	pub log_autosquash: GituiKeyEvent,
+	pub log_amend_commit: GituiKeyEvent,
+	pub log_cherry_pick: GituiKeyEvent,
+	pub log_bisect_bad: GituiKeyEvent,
+	pub log_bisect_good: GituiKeyEvent,
+	pub log_bisect_skip: GituiKeyEvent,
+	pub log_bisect_reset: GituiKeyEvent,
 	pub log_find: GituiKeyEvent,
+	pub log_filter: GituiKeyEvent,
+	pub log_deepen_history: GituiKeyEvent,
 	pub find_commit_sha: GituiKeyEvent,
 	pub commit_amend: GituiKeyEvent,
 	pub toggle_signoff: GituiKeyEvent,
 	pub toggle_verify: GituiKeyEvent,
 	pub copy: GituiKeyEvent,
+	pub copy_absolute_path: GituiKeyEvent,
+	pub copy_short_hash: GituiKeyEvent,
+	pub copy_commit_summary: GituiKeyEvent,
 	pub create_branch: GituiKeyEvent,
+	pub toggle_push_after_create: GituiKeyEvent,
+	pub toggle_checkout_after_create: GituiKeyEvent,
 	pub rename_branch: GituiKeyEvent,
 	pub select_branch: GituiKeyEvent,
 	pub delete_branch: GituiKeyEvent,
@@ -101,20 +157,37 @@ pub struct KeysList {
 	pub rebase_branch: GituiKeyEvent,
 	pub reset_branch: GituiKeyEvent,
 	pub compare_commits: GituiKeyEvent,
+	pub compare_branch: GituiKeyEvent,
+	pub branch_park: GituiKeyEvent,
+	pub branch_unpark: GituiKeyEvent,
+	pub branch_push_remote: GituiKeyEvent,
+	pub push_all_tags: GituiKeyEvent,
+	pub delete_remote_tag: GituiKeyEvent,
+	pub compare_base_next: GituiKeyEvent,
 	pub tags: GituiKeyEvent,
 	pub delete_tag: GituiKeyEvent,
 	pub select_tag: GituiKeyEvent,
 	pub push: GituiKeyEvent,
 	pub open_file_tree: GituiKeyEvent,
 	pub file_find: GituiKeyEvent,
+	pub file_grep: GituiKeyEvent,
+	pub file_grep_regex: GituiKeyEvent,
 	pub branch_find: GituiKeyEvent,
 	pub force_push: GituiKeyEvent,
+	pub open_pull_request: GituiKeyEvent,
 	pub fetch: GituiKeyEvent,
 	pub pull: GituiKeyEvent,
 	pub abort_merge: GituiKeyEvent,
 	pub undo_commit: GituiKeyEvent,
 	pub diff_hunk_next: GituiKeyEvent,
 	pub diff_hunk_prev: GituiKeyEvent,
+	pub diff_toggle_split: GituiKeyEvent,
+	pub diff_search: GituiKeyEvent,
+	pub diff_search_next: GituiKeyEvent,
+	pub diff_search_prev: GituiKeyEvent,
+	pub diff_search_case: GituiKeyEvent,
+	pub diff_lfs_content: GituiKeyEvent,
+	pub toggle_hex_view: GituiKeyEvent,
 	pub stage_unstage_item: GituiKeyEvent,
 	pub tag_annotate: GituiKeyEvent,
 	pub view_submodules: GituiKeyEvent,
@@ -123,9 +196,22 @@ pub struct KeysList {
 	pub update_remote_url: GituiKeyEvent,
 	pub add_remote: GituiKeyEvent,
 	pub delete_remote: GituiKeyEvent,
+	pub remote_set_default: GituiKeyEvent,
+	pub remote_set_push_default: GituiKeyEvent,
 	pub view_submodule_parent: GituiKeyEvent,
 	pub update_submodule: GituiKeyEvent,
+	pub init_submodule: GituiKeyEvent,
+	pub view_worktrees: GituiKeyEvent,
+	pub branch_create_worktree: GituiKeyEvent,
+	pub prune_worktrees: GituiKeyEvent,
+	pub toggle_worktree_force: GituiKeyEvent,
 	pub commit_history_next: GituiKeyEvent,
+	pub commit_template_next: GituiKeyEvent,
+	pub commit_pull_first: GituiKeyEvent,
+	pub commit_pull_rebase_first: GituiKeyEvent,
+	pub commit_diff_scroll_up: GituiKeyEvent,
+	pub commit_diff_scroll_down: GituiKeyEvent,
+	pub commit_reflow: GituiKeyEvent,
 	pub commit: GituiKeyEvent,
 	pub newline: GituiKeyEvent,
 	pub scroll_up_half_page: GituiKeyEvent,
@@ -134,6 +220,7 @@ pub struct KeysList {
 
 #[rustfmt::skip]
 impl Default for KeysList {
+	#[allow(clippy::too_many_lines)]
 	fn default() -> Self {
 		Self {
 			tab_status: GituiKeyEvent::new(KeyCode::Char('1'), KeyModifiers::empty()),
@@ -146,11 +233,18 @@ impl Default for KeysList {
 			toggle_workarea: GituiKeyEvent::new(KeyCode::Char('w'),  KeyModifiers::empty()),
 			exit: GituiKeyEvent::new(KeyCode::Char('c'),  KeyModifiers::CONTROL),
 			quit: GituiKeyEvent::new(KeyCode::Char('q'),  KeyModifiers::empty()),
+			quit_print: GituiKeyEvent::new(KeyCode::Char('Q'),  KeyModifiers::SHIFT),
 			exit_popup: GituiKeyEvent::new(KeyCode::Esc,  KeyModifiers::empty()),
 			open_commit: GituiKeyEvent::new(KeyCode::Char('c'),  KeyModifiers::empty()),
 			open_commit_editor: GituiKeyEvent::new(KeyCode::Char('e'), KeyModifiers::CONTROL),
 			open_help: GituiKeyEvent::new(KeyCode::Char('h'),  KeyModifiers::empty()),
+			open_key_conflicts: GituiKeyEvent::new(KeyCode::Char('c'),  KeyModifiers::empty()),
 			open_options: GituiKeyEvent::new(KeyCode::Char('o'),  KeyModifiers::empty()),
+			open_oplog: GituiKeyEvent::new(KeyCode::Char('O'),  KeyModifiers::SHIFT),
+			open_reflog: GituiKeyEvent::new(KeyCode::Char('g'),  KeyModifiers::CONTROL),
+			refresh: GituiKeyEvent::new(KeyCode::F(5),  KeyModifiers::empty()),
+			refresh_all: GituiKeyEvent::new(KeyCode::F(5),  KeyModifiers::SHIFT),
+			oplog_filter: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::empty()),
 			move_left: GituiKeyEvent::new(KeyCode::Left,  KeyModifiers::empty()),
 			move_right: GituiKeyEvent::new(KeyCode::Right,  KeyModifiers::empty()),
 			tree_collapse_recursive: GituiKeyEvent::new(KeyCode::Left,  KeyModifiers::SHIFT),
@@ -168,11 +262,27 @@ impl Default for KeysList {
 			enter: GituiKeyEvent::new(KeyCode::Enter,  KeyModifiers::empty()),
 			blame: GituiKeyEvent::new(KeyCode::Char('B'),  KeyModifiers::SHIFT),
 			file_history: GituiKeyEvent::new(KeyCode::Char('H'),  KeyModifiers::SHIFT),
+			file_history_goto_log: GituiKeyEvent::new(KeyCode::Char('L'),  KeyModifiers::SHIFT),
+			files_goto_log: GituiKeyEvent::new(KeyCode::Char('L'),  KeyModifiers::SHIFT),
+			open_local_history: GituiKeyEvent::new(KeyCode::Char('h'),  KeyModifiers::ALT),
+			local_history_restore: GituiKeyEvent::new(KeyCode::Char('r'),  KeyModifiers::empty()),
 			edit_file: GituiKeyEvent::new(KeyCode::Char('e'),  KeyModifiers::empty()),
 			status_stage_all: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::empty()),
 			status_reset_item: GituiKeyEvent::new(KeyCode::Char('D'),  KeyModifiers::SHIFT),
 			diff_reset_lines: GituiKeyEvent::new(KeyCode::Char('d'),  KeyModifiers::empty()),
 			status_ignore_file: GituiKeyEvent::new(KeyCode::Char('i'),  KeyModifiers::empty()),
+			status_fixup: GituiKeyEvent::new(KeyCode::Char('X'),  KeyModifiers::SHIFT),
+			status_fix_staged: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::ALT),
+			status_resolve_conflict: GituiKeyEvent::new(KeyCode::Char('x'),  KeyModifiers::empty()),
+			status_stash_selected: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::ALT),
+			conflict_take_ours: GituiKeyEvent::new(KeyCode::Char('o'),  KeyModifiers::empty()),
+			conflict_take_theirs: GituiKeyEvent::new(KeyCode::Char('t'),  KeyModifiers::empty()),
+			conflict_take_both: GituiKeyEvent::new(KeyCode::Char('b'),  KeyModifiers::empty()),
+			conflict_take_both_reverse: GituiKeyEvent::new(KeyCode::Char('B'),  KeyModifiers::SHIFT),
+			conflict_save: GituiKeyEvent::new(KeyCode::Char('S'),  KeyModifiers::SHIFT),
+			status_scope_folder: GituiKeyEvent::new(KeyCode::Char('z'),  KeyModifiers::empty()),
+			status_clear_scope: GituiKeyEvent::new(KeyCode::Char('Z'),  KeyModifiers::SHIFT),
+			review_toggle_viewed: GituiKeyEvent::new(KeyCode::Char('v'),  KeyModifiers::empty()),
 			diff_stage_lines: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::empty()),
 			stashing_save: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::empty()),
 			stashing_toggle_untracked: GituiKeyEvent::new(KeyCode::Char('u'),  KeyModifiers::empty()),
@@ -180,19 +290,46 @@ impl Default for KeysList {
 			stash_apply: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::empty()),
 			stash_open: GituiKeyEvent::new(KeyCode::Right,  KeyModifiers::empty()),
 			stash_drop: GituiKeyEvent::new(KeyCode::Char('D'),  KeyModifiers::SHIFT),
+			stash_branch: GituiKeyEvent::new(KeyCode::Char('b'),  KeyModifiers::empty()),
 			cmd_bar_toggle: GituiKeyEvent::new(KeyCode::Char('.'),  KeyModifiers::empty()),
+			log_branch_commit: GituiKeyEvent::new(KeyCode::Char('c'),  KeyModifiers::empty()),
 			log_tag_commit: GituiKeyEvent::new(KeyCode::Char('t'),  KeyModifiers::empty()),
+			log_bookmark_commit: GituiKeyEvent::new(KeyCode::Char('m'),  KeyModifiers::empty()),
+			log_bookmarks: GituiKeyEvent::new(KeyCode::Char('\''),  KeyModifiers::empty()),
+			bookmark_delete: GituiKeyEvent::new(KeyCode::Char('D'),  KeyModifiers::SHIFT),
+			bookmark_repoint: GituiKeyEvent::new(KeyCode::Char('r'),  KeyModifiers::empty()),
+			log_replace_refs: GituiKeyEvent::new(KeyCode::Char('g'),  KeyModifiers::ALT),
+			log_toggle_replace_refs: GituiKeyEvent::new(KeyCode::Char('r'),  KeyModifiers::ALT),
+			replace_ref_delete: GituiKeyEvent::new(KeyCode::Char('D'),  KeyModifiers::SHIFT),
+			process_kill: GituiKeyEvent::new(KeyCode::Char('k'),  KeyModifiers::CONTROL),
+			pull_toggle_rebase: GituiKeyEvent::new(KeyCode::Char('r'),  KeyModifiers::NONE),
 			log_mark_commit: GituiKeyEvent::new(KeyCode::Char(' '),  KeyModifiers::empty()),
 			log_checkout_commit: GituiKeyEvent { code: KeyCode::Char('S'), modifiers: KeyModifiers::SHIFT },
 			log_reset_commit: GituiKeyEvent { code: KeyCode::Char('R'), modifiers: KeyModifiers::SHIFT },
 			log_reword_commit: GituiKeyEvent { code: KeyCode::Char('r'), modifiers: KeyModifiers::empty() },
+			log_fixup_commit: GituiKeyEvent { code: KeyCode::Char('X'), modifiers: KeyModifiers::SHIFT },
+			log_squash_commit: GituiKeyEvent { code: KeyCode::Char('x'), modifiers: KeyModifiers::ALT },
+			log_autosquash: GituiKeyEvent { code: KeyCode::Char('A'), modifiers: KeyModifiers::SHIFT },
+			log_amend_commit: GituiKeyEvent { code: KeyCode::Char('E'), modifiers: KeyModifiers::SHIFT },
+			log_cherry_pick: GituiKeyEvent { code: KeyCode::Char('c'), modifiers: KeyModifiers::ALT },
+			log_bisect_bad: GituiKeyEvent { code: KeyCode::Char('b'), modifiers: KeyModifiers::ALT },
+			log_bisect_good: GituiKeyEvent { code: KeyCode::Char('o'), modifiers: KeyModifiers::ALT },
+			log_bisect_skip: GituiKeyEvent { code: KeyCode::Char('k'), modifiers: KeyModifiers::ALT },
+			log_bisect_reset: GituiKeyEvent { code: KeyCode::Char('q'), modifiers: KeyModifiers::ALT },
 			log_find: GituiKeyEvent { code: KeyCode::Char('f'), modifiers: KeyModifiers::empty() },
+			log_filter: GituiKeyEvent { code: KeyCode::Char('\\'), modifiers: KeyModifiers::empty() },
+			log_deepen_history: GituiKeyEvent { code: KeyCode::Char('u'), modifiers: KeyModifiers::ALT },
 			find_commit_sha: GituiKeyEvent::new(KeyCode::Char('j'), KeyModifiers::CONTROL),
 			commit_amend: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::CONTROL),
 			toggle_signoff: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::CONTROL),
 			toggle_verify: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::CONTROL),
 			copy: GituiKeyEvent::new(KeyCode::Char('y'),  KeyModifiers::empty()),
+			copy_absolute_path: GituiKeyEvent::new(KeyCode::Char('Y'),  KeyModifiers::SHIFT),
+			copy_short_hash: GituiKeyEvent::new(KeyCode::Char('y'),  KeyModifiers::ALT),
+			copy_commit_summary: GituiKeyEvent::new(KeyCode::Char('y'),  KeyModifiers::CONTROL),
 			create_branch: GituiKeyEvent::new(KeyCode::Char('c'),  KeyModifiers::empty()),
+			toggle_push_after_create: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::CONTROL),
+			toggle_checkout_after_create: GituiKeyEvent::new(KeyCode::Char('o'),  KeyModifiers::CONTROL),
 			rename_branch: GituiKeyEvent::new(KeyCode::Char('r'),  KeyModifiers::empty()),
 			select_branch: GituiKeyEvent::new(KeyCode::Char('b'),  KeyModifiers::empty()),
 			delete_branch: GituiKeyEvent::new(KeyCode::Char('D'),  KeyModifiers::SHIFT),
@@ -200,20 +337,37 @@ impl Default for KeysList {
 			rebase_branch: GituiKeyEvent::new(KeyCode::Char('R'),  KeyModifiers::SHIFT),
 			reset_branch: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::empty()),
 			compare_commits: GituiKeyEvent::new(KeyCode::Char('C'),  KeyModifiers::SHIFT),
+			compare_branch: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::empty()),
+			branch_park: GituiKeyEvent::new(KeyCode::Char('P'),  KeyModifiers::SHIFT),
+			branch_unpark: GituiKeyEvent::new(KeyCode::Char('U'),  KeyModifiers::SHIFT),
+			branch_push_remote: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::empty()),
+			push_all_tags: GituiKeyEvent::new(KeyCode::Char('P'),  KeyModifiers::SHIFT),
+			delete_remote_tag: GituiKeyEvent::new(KeyCode::Char('d'),  KeyModifiers::empty()),
+			compare_base_next: GituiKeyEvent::new(KeyCode::Char('b'),  KeyModifiers::empty()),
 			tags: GituiKeyEvent::new(KeyCode::Char('T'),  KeyModifiers::SHIFT),
 			delete_tag: GituiKeyEvent::new(KeyCode::Char('D'),  KeyModifiers::SHIFT),
 			select_tag: GituiKeyEvent::new(KeyCode::Enter,  KeyModifiers::empty()),
 			push: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::empty()),
 			force_push: GituiKeyEvent::new(KeyCode::Char('P'),  KeyModifiers::SHIFT),
+			open_pull_request: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::ALT),
 			undo_commit: GituiKeyEvent::new(KeyCode::Char('U'),  KeyModifiers::SHIFT),
 			fetch: GituiKeyEvent::new(KeyCode::Char('F'),  KeyModifiers::SHIFT),
 			pull: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::empty()),
 			abort_merge: GituiKeyEvent::new(KeyCode::Char('A'),  KeyModifiers::SHIFT),
 			open_file_tree: GituiKeyEvent::new(KeyCode::Char('F'),  KeyModifiers::SHIFT),
 			file_find: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::empty()),
+			file_grep: GituiKeyEvent::new(KeyCode::Char('/'),  KeyModifiers::empty()),
+			file_grep_regex: GituiKeyEvent::new(KeyCode::Char('r'),  KeyModifiers::CONTROL),
 			branch_find: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::empty()),
 			diff_hunk_next: GituiKeyEvent::new(KeyCode::Char('n'),  KeyModifiers::empty()),
 			diff_hunk_prev: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::empty()),
+			diff_toggle_split: GituiKeyEvent::new(KeyCode::Char('V'),  KeyModifiers::SHIFT),
+			diff_search: GituiKeyEvent::new(KeyCode::Char('/'),  KeyModifiers::empty()),
+			diff_search_next: GituiKeyEvent::new(KeyCode::Char('n'),  KeyModifiers::empty()),
+			diff_search_prev: GituiKeyEvent::new(KeyCode::Char('N'),  KeyModifiers::SHIFT),
+			diff_search_case: GituiKeyEvent::new(KeyCode::Char('t'),  KeyModifiers::CONTROL),
+			diff_lfs_content: GituiKeyEvent::new(KeyCode::Char('L'),  KeyModifiers::SHIFT),
+			toggle_hex_view: GituiKeyEvent::new(KeyCode::Char('x'),  KeyModifiers::empty()),
 			stage_unstage_item: GituiKeyEvent::new(KeyCode::Enter,  KeyModifiers::empty()),
 			tag_annotate: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::CONTROL),
 			view_submodules: GituiKeyEvent::new(KeyCode::Char('S'),  KeyModifiers::SHIFT),
@@ -222,9 +376,22 @@ impl Default for KeysList {
 			update_remote_url: GituiKeyEvent::new(KeyCode::Char('u'),KeyModifiers::NONE),
 			add_remote: GituiKeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE),
 			delete_remote: GituiKeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE),
+			remote_set_default: GituiKeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE),
+			remote_set_push_default: GituiKeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE),
 			view_submodule_parent: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::empty()),
 			update_submodule: GituiKeyEvent::new(KeyCode::Char('u'),  KeyModifiers::empty()),
+			init_submodule: GituiKeyEvent::new(KeyCode::Char('i'),  KeyModifiers::empty()),
+			view_worktrees: GituiKeyEvent::new(KeyCode::Char('W'),  KeyModifiers::SHIFT),
+			branch_create_worktree: GituiKeyEvent::new(KeyCode::Char('w'),  KeyModifiers::empty()),
+			prune_worktrees: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::empty()),
+			toggle_worktree_force: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::CONTROL),
 			commit_history_next: GituiKeyEvent::new(KeyCode::Char('n'),  KeyModifiers::CONTROL),
+			commit_template_next: GituiKeyEvent::new(KeyCode::Char('t'),  KeyModifiers::CONTROL),
+			commit_pull_first: GituiKeyEvent::new(KeyCode::Char('l'),  KeyModifiers::CONTROL),
+			commit_pull_rebase_first: GituiKeyEvent::new(KeyCode::Char('l'),  KeyModifiers::ALT),
+			commit_diff_scroll_up: GituiKeyEvent::new(KeyCode::Up,  KeyModifiers::ALT),
+			commit_diff_scroll_down: GituiKeyEvent::new(KeyCode::Down,  KeyModifiers::ALT),
+			commit_reflow: GituiKeyEvent::new(KeyCode::Char('q'),  KeyModifiers::ALT),
 			commit: GituiKeyEvent::new(KeyCode::Char('d'),  KeyModifiers::ALT),
 			newline: GituiKeyEvent::new(KeyCode::Enter,  KeyModifiers::empty()),
 			scroll_up_half_page: GituiKeyEvent::new(KeyCode::Char('u'),  KeyModifiers::CONTROL),
@@ -233,18 +400,128 @@ impl Default for KeysList {
 	}
 }
 
+/// built-in key bindings a config picks with `preset: Some("vim")`,
+/// its own bindings are applied on top
+const PRESETS: [(&str, &str); 1] =
+	[("vim", include_str!("../../vim_style_key_config.ron"))];
+
+/// names of the built-in key bindings
+pub fn presets() -> impl Iterator<Item = &'static str> {
+	PRESETS.iter().map(|(name, _)| *name)
+}
+
+/// what is wrong with the key bindings file, whatever could not be
+/// read keeps its default
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyConfigWarning {
+	/// the file is no valid config, none of it is used
+	Parse(String),
+	/// there is no action of that name
+	UnknownAction(String),
+	/// there is no preset of that name
+	UnknownPreset(String),
+}
+
+/// names of the entries in a key bindings file
+struct EntryNames(Vec<String>);
+
+impl<'de> Deserialize<'de> for EntryNames {
+	fn deserialize<D: Deserializer<'de>>(
+		deserializer: D,
+	) -> Result<Self, D::Error> {
+		struct NamesVisitor;
+
+		impl<'de> Visitor<'de> for NamesVisitor {
+			type Value = EntryNames;
+
+			fn expecting(
+				&self,
+				f: &mut fmt::Formatter,
+			) -> fmt::Result {
+				f.write_str("key bindings")
+			}
+
+			fn visit_map<A: MapAccess<'de>>(
+				self,
+				mut map: A,
+			) -> Result<EntryNames, A::Error> {
+				let mut names = Vec::new();
+				while let Some(name) = map.next_key::<String>()? {
+					map.next_value::<IgnoredAny>()?;
+					names.push(name);
+				}
+				Ok(EntryNames(names))
+			}
+		}
+
+		deserializer.deserialize_struct("", &[], NamesVisitor)
+	}
+}
+
+#[derive(Deserialize)]
+struct PresetEntry {
+	#[serde(default)]
+	preset: Option<String>,
+}
+
 impl KeysList {
-	pub fn init(file: PathBuf) -> Self {
+	pub fn init(file: PathBuf) -> (Self, Vec<KeyConfigWarning>) {
 		let mut keys_list = Self::default();
-		if let Ok(f) = File::open(file) {
-			match ron::de::from_reader(f) {
-				Ok(patch) => keys_list.apply(patch),
-				Err(e) => {
-					log::error!("KeysList parse error: {e}");
+		let mut warnings = Vec::new();
+		if let Ok(content) = fs::read_to_string(file) {
+			keys_list.apply_config(&content, &mut warnings);
+		}
+		(keys_list, warnings)
+	}
+
+	/// applies the preset `content` names, then its own bindings
+	fn apply_config(
+		&mut self,
+		content: &str,
+		warnings: &mut Vec<KeyConfigWarning>,
+	) {
+		let parsed =
+			ron::from_str::<EntryNames>(content).and_then(|names| {
+				Ok((
+					names,
+					ron::from_str::<PresetEntry>(content)?,
+					ron::from_str::<KeysListPatch>(content)?,
+				))
+			});
+
+		let (names, preset, patch) = match parsed {
+			Ok(parsed) => parsed,
+			Err(e) => {
+				log::error!("KeysList parse error: {e}");
+				warnings.push(KeyConfigWarning::Parse(e.to_string()));
+				return;
+			}
+		};
+
+		if let Some(name) = preset.preset {
+			match PRESETS.iter().find(|(preset, _)| *preset == name) {
+				Some((_, preset)) => match ron::from_str(preset) {
+					Ok(patch) => self.apply(patch),
+					Err(e) => {
+						log::error!("KeysList preset error: {e}");
+					}
+				},
+				None => {
+					warnings
+						.push(KeyConfigWarning::UnknownPreset(name));
 				}
 			}
 		}
-		keys_list
+
+		warnings.extend(
+			names
+				.0
+				.into_iter()
+				.filter(|name| name != "preset" && !is_action(name))
+				.map(KeyConfigWarning::UnknownAction),
+		);
+
+		self.apply(patch);
 	}
 }
 
@@ -258,7 +535,7 @@ mod tests {
 	#[test]
 	fn test_apply_vim_style_example() {
 		let mut keys_list = KeysList::default();
-		let f = File::open("vim_style_key_config.ron")
+		let f = fs::File::open("vim_style_key_config.ron")
 			.expect("vim style config should exist");
 		let patch = ron::de::from_reader(f)
 			.expect("vim style config format incorrect");
@@ -280,8 +557,10 @@ mod tests {
 		)
 		.unwrap();
 
-		let keys = KeysList::init(file.path().to_path_buf());
+		let (keys, warnings) =
+			KeysList::init(file.path().to_path_buf());
 
+		assert_eq!(warnings, vec![]);
 		assert_eq!(keys.move_right, KeysList::default().move_right);
 		assert_eq!(
 			keys.move_down,
@@ -298,4 +577,83 @@ mod tests {
 			)
 		);
 	}
+
+	#[test]
+	fn test_preset() {
+		let mut keys = KeysList::default();
+		let mut warnings = Vec::new();
+
+		keys.apply_config(
+			r#"
+(
+	preset: Some("vim"),
+	move_up: Some(( code: Char('w'), modifiers: "")),
+)
+"#,
+			&mut warnings,
+		);
+
+		assert_eq!(warnings, vec![]);
+		assert_eq!(
+			keys.move_down,
+			GituiKeyEvent::new(
+				KeyCode::Char('j'),
+				KeyModifiers::NONE
+			)
+		);
+		assert_eq!(
+			keys.scroll_down_half_page,
+			GituiKeyEvent::new(
+				KeyCode::Char('d'),
+				KeyModifiers::CONTROL
+			)
+		);
+		assert_eq!(
+			keys.move_up,
+			GituiKeyEvent::new(
+				KeyCode::Char('w'),
+				KeyModifiers::NONE
+			)
+		);
+	}
+
+	#[test]
+	fn test_warnings() {
+		let mut keys = KeysList::default();
+		let mut warnings = Vec::new();
+
+		keys.apply_config(
+			r#"
+(
+	preset: Some("emacs"),
+	move_dowm: Some(( code: Char('j'), modifiers: "")),
+	move_up: Some(( code: Char('k'), modifiers: "")),
+)
+"#,
+			&mut warnings,
+		);
+
+		assert_eq!(
+			warnings,
+			vec![
+				KeyConfigWarning::UnknownPreset("emacs".into()),
+				KeyConfigWarning::UnknownAction("move_dowm".into()),
+			]
+		);
+		assert_eq!(keys.move_down, KeysList::default().move_down);
+		assert_eq!(
+			keys.move_up,
+			GituiKeyEvent::new(
+				KeyCode::Char('k'),
+				KeyModifiers::NONE
+			)
+		);
+
+		let mut warnings = Vec::new();
+		keys.apply_config("(move_up: Some(", &mut warnings);
+		assert!(matches!(
+			warnings.as_slice(),
+			[KeyConfigWarning::Parse(_)]
+		));
+	}
 }
@@ -1,6 +1,12 @@
 mod key_config;
+mod key_conflicts;
 mod key_list;
 mod symbols;
+mod vim;
 
 pub use key_config::{KeyConfig, SharedKeyConfig};
-pub use key_list::key_match;
+pub use key_conflicts::{
+	config_line, key_issues, KeyIssue, KeyIssueKind,
+};
+pub use key_list::{key_match, presets, KeyConfigWarning};
+pub use vim::{VimContext, VimLayer, VimMode};
@@ -3,6 +3,8 @@ use std::{fs::File, io::Read, path::PathBuf};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
+use crate::ui::GlyphSet;
+
 #[derive(Debug, Clone)]
 pub struct KeySymbols {
 	pub enter: String,
@@ -20,6 +22,7 @@ pub struct KeySymbols {
 	pub delete: String,
 	pub insert: String,
 	pub esc: String,
+	pub space: String,
 	pub control: String,
 	pub shift: String,
 	pub alt: String,
@@ -44,6 +47,7 @@ impl Default for KeySymbols {
 			delete: "\u{2326}".into(),    //⌦
 			insert: "\u{2380}".into(),    //⎀
 			esc: "\u{238b}".into(),       //⎋
+			space: "\u{02fd}".into(),     //˽
 			control: "^".into(),
 			shift: "\u{21e7}".into(),     //⇧
 			alt: "\u{2325}".into(),       //⌥
@@ -52,13 +56,46 @@ impl Default for KeySymbols {
 }
 
 impl KeySymbols {
-	pub fn init(file: PathBuf) -> Self {
+	pub fn init(file: PathBuf, glyphs: GlyphSet) -> Self {
+		let default = Self::for_glyphs(glyphs);
+
 		if file.exists() {
 			let file =
 				KeySymbolsFile::read_file(file).unwrap_or_default();
-			file.get_symbols()
+			file.get_symbols(default)
 		} else {
-			Self::default()
+			default
+		}
+	}
+
+	pub fn for_glyphs(glyphs: GlyphSet) -> Self {
+		match glyphs {
+			GlyphSet::Unicode => Self::default(),
+			GlyphSet::Ascii => Self::ascii(),
+		}
+	}
+
+	fn ascii() -> Self {
+		Self {
+			enter: "Enter".into(),
+			left: "Left".into(),
+			right: "Right".into(),
+			up: "Up".into(),
+			down: "Down".into(),
+			backspace: "Bksp".into(),
+			home: "Home".into(),
+			end: "End".into(),
+			page_up: "PgUp".into(),
+			page_down: "PgDn".into(),
+			tab: "Tab".into(),
+			back_tab: "BTab".into(),
+			delete: "Del".into(),
+			insert: "Ins".into(),
+			esc: "Esc".into(),
+			space: "Space".into(),
+			control: "^".into(),
+			shift: "S-".into(),
+			alt: "A-".into(),
 		}
 	}
 }
@@ -81,6 +118,7 @@ pub struct KeySymbolsFile {
 	pub delete: Option<String>,
 	pub insert: Option<String>,
 	pub esc: Option<String>,
+	pub space: Option<String>,
 	pub control: Option<String>,
 	pub shift: Option<String>,
 	pub alt: Option<String>,
@@ -94,9 +132,7 @@ impl KeySymbolsFile {
 		Ok(ron::de::from_bytes(&buffer)?)
 	}
 
-	pub fn get_symbols(self) -> KeySymbols {
-		let default = KeySymbols::default();
-
+	pub fn get_symbols(self, default: KeySymbols) -> KeySymbols {
 		KeySymbols {
 			enter: self.enter.unwrap_or(default.enter),
 			left: self.left.unwrap_or(default.left),
@@ -113,6 +149,7 @@ impl KeySymbolsFile {
 			delete: self.delete.unwrap_or(default.delete),
 			insert: self.insert.unwrap_or(default.insert),
 			esc: self.esc.unwrap_or(default.esc),
+			space: self.space.unwrap_or(default.space),
 			control: self.control.unwrap_or(default.control),
 			shift: self.shift.unwrap_or(default.shift),
 			alt: self.alt.unwrap_or(default.alt),
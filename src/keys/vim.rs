@@ -0,0 +1,475 @@
+//! opt-in vim style normal mode: keys typed outside of popups and
+//! inputs are translated into the configured keys of the commands
+//! they stand for, everything the layer does not know passes through
+//! untouched so a view never ends up unreachable
+
+use super::key_list::{GituiKeyEvent, KeysList};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// counts beyond are cut to not flood the views with keys
+const MAX_COUNT: usize = 999;
+
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VimMode {
+	Normal,
+	/// moving marks the items passed like a vim visual selection
+	Visual,
+}
+
+/// what the focused view offers the layer
+#[derive(Clone, Copy, Debug)]
+pub struct VimContext {
+	/// the key `/` stands for
+	pub filter: Option<GituiKeyEvent>,
+	/// the key marking the selected item of the view, `v` only
+	/// works where there is one
+	pub marking: Option<GituiKeyEvent>,
+}
+
+///
+#[derive(Debug)]
+pub struct VimLayer {
+	mode: VimMode,
+	count: Option<usize>,
+	/// the digits the count was typed with, handed on as they are
+	/// if no motion follows (they are the tab keys by default)
+	count_keys: Vec<KeyEvent>,
+	/// a `g` waits for the second key of `gg`, `gt` or `gT`
+	pending_g: bool,
+	/// distance of the selection from where visual mode started
+	visual_offset: isize,
+	/// the marking key of the view visual mode started in
+	visual_mark: Option<GituiKeyEvent>,
+}
+
+impl Default for VimLayer {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl VimLayer {
+	///
+	pub const fn new() -> Self {
+		Self {
+			mode: VimMode::Normal,
+			count: None,
+			count_keys: Vec::new(),
+			pending_g: false,
+			visual_offset: 0,
+			visual_mark: None,
+		}
+	}
+
+	///
+	pub const fn mode(&self) -> VimMode {
+		self.mode
+	}
+
+	/// the count and `g` typed so far
+	pub fn pending(&self) -> String {
+		let count = self
+			.count
+			.map(|count| count.to_string())
+			.unwrap_or_default();
+
+		if self.pending_g {
+			format!("{count}g")
+		} else {
+			count
+		}
+	}
+
+	/// back to normal mode with nothing pending, the marks of a
+	/// visual selection stay
+	pub fn reset(&mut self) {
+		*self = Self::new();
+	}
+
+	/// the keys `key` stands for, they are handed on in order until
+	/// one of them is not taken (like a move at the end of a list)
+	pub fn translate(
+		&mut self,
+		key: &KeyEvent,
+		keys: &KeysList,
+		context: VimContext,
+	) -> Vec<KeyEvent> {
+		let plain = key.modifiers.is_empty()
+			|| (key.modifiers == KeyModifiers::SHIFT
+				&& matches!(key.code, KeyCode::Char(_)));
+		if !plain {
+			self.clear_pending();
+			return vec![*key];
+		}
+
+		if let KeyCode::Char(c @ '0'..='9') = key.code {
+			if c != '0' || self.count.is_some() {
+				let digit =
+					c.to_digit(10).unwrap_or_default() as usize;
+				self.count = Some(
+					(self.count.unwrap_or_default() * 10 + digit)
+						.min(MAX_COUNT),
+				);
+				self.count_keys.push(*key);
+				self.pending_g = false;
+				return Vec::new();
+			}
+		}
+
+		let had_pending = self.count.is_some() || self.pending_g;
+		let typed_count = self.count.take();
+		let count = typed_count.unwrap_or(1);
+		let count_keys = std::mem::take(&mut self.count_keys);
+		let had_g = self.pending_g;
+
+		if std::mem::take(&mut self.pending_g) {
+			match key.code {
+				KeyCode::Char('g') => {
+					return vec![(&keys.home).into()]
+				}
+				KeyCode::Char('t') => {
+					return self.switch_tab(keys, typed_count, false);
+				}
+				KeyCode::Char('T') => {
+					return self.switch_tab(keys, typed_count, true);
+				}
+				_ => (),
+			}
+		}
+
+		let moved = match key.code {
+			KeyCode::Char('g') => {
+				// keep the count for `{n}gt`
+				self.count = typed_count;
+				self.count_keys = count_keys;
+				self.pending_g = true;
+				return Vec::new();
+			}
+			KeyCode::Char('G') => Some(vec![(&keys.end).into()]),
+			KeyCode::Char('h') => Some(repeat(keys.move_left, count)),
+			KeyCode::Char('l') => {
+				Some(repeat(keys.move_right, count))
+			}
+			KeyCode::Char('j') => {
+				Some(self.vertical(keys, count, true))
+			}
+			KeyCode::Char('k') => {
+				Some(self.vertical(keys, count, false))
+			}
+			KeyCode::Esc
+				if had_pending || self.mode == VimMode::Visual =>
+			{
+				self.reset();
+				Some(Vec::new())
+			}
+			_ => None,
+		};
+		if let Some(moved) = moved {
+			return moved;
+		}
+
+		let res = match key.code {
+			KeyCode::Char('v') if context.marking.is_some() => {
+				self.toggle_visual(context.marking)
+			}
+			KeyCode::Char('/') => {
+				vec![context.filter.map_or(*key, |f| (&f).into())]
+			}
+			// there is no command prompt, the help lists all commands
+			KeyCode::Char(':') => vec![(&keys.open_help).into()],
+			_ => vec![*key],
+		};
+
+		// a count no move took were the keys typed (like the tab
+		// keys `1`-`5`)
+		if had_g {
+			res
+		} else {
+			count_keys.into_iter().chain(res).collect()
+		}
+	}
+
+	fn clear_pending(&mut self) {
+		self.count = None;
+		self.count_keys.clear();
+		self.pending_g = false;
+	}
+
+	/// `{n}gt` goes to tab `n` (1 based), `gt` to the next one and
+	/// `{n}gT` `n` tabs back
+	fn switch_tab(
+		&mut self,
+		keys: &KeysList,
+		count: Option<usize>,
+		reverse: bool,
+	) -> Vec<KeyEvent> {
+		let tabs = [
+			keys.tab_status,
+			keys.tab_log,
+			keys.tab_files,
+			keys.tab_stashing,
+			keys.tab_stashes,
+		];
+
+		// the marks of another view are not extended
+		self.reset();
+
+		match (count, reverse) {
+			(Some(tab), false) => tabs
+				.get(tab.saturating_sub(1))
+				.map(|key| vec![key.into()])
+				.unwrap_or_default(),
+			(count, true) => {
+				repeat(keys.tab_toggle_reverse, count.unwrap_or(1))
+			}
+			(None, false) => vec![(&keys.tab_toggle).into()],
+		}
+	}
+
+	/// in visual mode the item moved to gets marked, moving back
+	/// towards the start unmarks the one left
+	fn vertical(
+		&mut self,
+		keys: &KeysList,
+		count: usize,
+		down: bool,
+	) -> Vec<KeyEvent> {
+		let step = if down { keys.move_down } else { keys.move_up };
+
+		let Some(mark) = self.visual_mark else {
+			return repeat(step, count);
+		};
+
+		let mut res = Vec::with_capacity(count * 2);
+		for _ in 0..count {
+			let growing = if down {
+				self.visual_offset >= 0
+			} else {
+				self.visual_offset <= 0
+			};
+
+			if growing {
+				res.push((&step).into());
+				res.push((&mark).into());
+			} else {
+				res.push((&mark).into());
+				res.push((&step).into());
+			}
+
+			self.visual_offset += if down { 1 } else { -1 };
+		}
+
+		res
+	}
+
+	fn toggle_visual(
+		&mut self,
+		mark: Option<GituiKeyEvent>,
+	) -> Vec<KeyEvent> {
+		if self.mode == VimMode::Visual {
+			self.reset();
+			return Vec::new();
+		}
+
+		self.mode = VimMode::Visual;
+		self.visual_offset = 0;
+		self.visual_mark = mark;
+
+		mark.iter().map(Into::into).collect()
+	}
+}
+
+fn repeat(key: GituiKeyEvent, count: usize) -> Vec<KeyEvent> {
+	vec![(&key).into(); count]
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	const CONTEXT: VimContext = VimContext {
+		filter: None,
+		marking: Some(GituiKeyEvent::new(
+			KeyCode::Char(' '),
+			KeyModifiers::empty(),
+		)),
+	};
+
+	fn typed(layer: &mut VimLayer, text: &str) -> Vec<KeyEvent> {
+		typed_in(layer, text, CONTEXT)
+	}
+
+	fn typed_in(
+		layer: &mut VimLayer,
+		text: &str,
+		context: VimContext,
+	) -> Vec<KeyEvent> {
+		let keys = KeysList::default();
+
+		text.chars()
+			.flat_map(|c| {
+				let modifiers = if c.is_uppercase() {
+					KeyModifiers::SHIFT
+				} else {
+					KeyModifiers::empty()
+				};
+				layer.translate(
+					&KeyEvent::new(KeyCode::Char(c), modifiers),
+					&keys,
+					context,
+				)
+			})
+			.collect()
+	}
+
+	fn char_key(c: char) -> KeyEvent {
+		KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty())
+	}
+
+	fn key(key: GituiKeyEvent) -> KeyEvent {
+		(&key).into()
+	}
+
+	#[test]
+	fn test_motions_and_counts() {
+		let keys = KeysList::default();
+		let mut layer = VimLayer::new();
+
+		assert_eq!(typed(&mut layer, "j"), vec![key(keys.move_down)]);
+		assert_eq!(
+			typed(&mut layer, "3k"),
+			vec![key(keys.move_up); 3]
+		);
+		assert_eq!(typed(&mut layer, "12"), Vec::new());
+		assert_eq!(layer.pending(), "12");
+		assert_eq!(typed(&mut layer, "l").len(), 12);
+		assert_eq!(
+			typed(&mut layer, "ggG"),
+			vec![key(keys.home), key(keys.end)]
+		);
+		assert_eq!(typed(&mut layer, "2gt"), vec![key(keys.tab_log)]);
+		assert_eq!(
+			typed(&mut layer, "gT"),
+			vec![key(keys.tab_toggle_reverse)]
+		);
+		// unknown keys pass through after the digits of the count
+		assert_eq!(
+			typed(&mut layer, "5x"),
+			vec![char_key('5'), char_key('x')]
+		);
+		assert_eq!(layer.pending(), "");
+		// a dropped `g` takes the count along
+		assert_eq!(typed(&mut layer, "2gx"), vec![char_key('x')]);
+		assert_eq!(layer.pending(), "");
+	}
+
+	#[test]
+	fn test_count_falls_back_to_tab_keys() {
+		let keys = KeysList::default();
+		let mut layer = VimLayer::new();
+
+		assert_eq!(typed(&mut layer, "2"), Vec::new());
+		assert_eq!(
+			layer.translate(
+				&KeyEvent::new(KeyCode::Tab, KeyModifiers::empty()),
+				&keys,
+				CONTEXT,
+			),
+			vec![
+				key(keys.tab_log),
+				KeyEvent::new(KeyCode::Tab, KeyModifiers::empty())
+			]
+		);
+		assert_eq!(
+			typed(&mut layer, "4/"),
+			vec![key(keys.tab_stashing), char_key('/')]
+		);
+		// a move still takes it as a count
+		assert_eq!(
+			typed(&mut layer, "3j"),
+			vec![key(keys.move_down); 3]
+		);
+	}
+
+	#[test]
+	fn test_escape() {
+		let keys = KeysList::default();
+		let esc = KeyEvent::new(KeyCode::Esc, KeyModifiers::empty());
+		let mut layer = VimLayer::new();
+
+		typed(&mut layer, "4");
+		assert!(layer.translate(&esc, &keys, CONTEXT).is_empty());
+		assert_eq!(layer.pending(), "");
+
+		typed(&mut layer, "v");
+		assert_eq!(layer.mode(), VimMode::Visual);
+		assert!(layer.translate(&esc, &keys, CONTEXT).is_empty());
+		assert_eq!(layer.mode(), VimMode::Normal);
+
+		// nothing pending, it is the regular escape again
+		assert_eq!(layer.translate(&esc, &keys, CONTEXT), vec![esc]);
+	}
+
+	#[test]
+	fn test_visual_marks() {
+		let keys = KeysList::default();
+		let mark = key(keys.log_mark_commit);
+		let mut layer = VimLayer::new();
+
+		assert_eq!(
+			typed_in(
+				&mut layer,
+				"vj",
+				VimContext {
+					filter: None,
+					marking: Some(keys.stage_unstage_item),
+				}
+			),
+			vec![
+				key(keys.stage_unstage_item),
+				key(keys.move_down),
+				key(keys.stage_unstage_item)
+			]
+		);
+		layer.reset();
+
+		assert_eq!(typed(&mut layer, "v"), vec![mark]);
+		assert_eq!(
+			typed(&mut layer, "2j"),
+			vec![
+				key(keys.move_down),
+				mark,
+				key(keys.move_down),
+				mark
+			]
+		);
+		// shrinking unmarks before moving
+		assert_eq!(
+			typed(&mut layer, "k"),
+			vec![mark, key(keys.move_up)]
+		);
+		assert_eq!(typed(&mut layer, "v"), Vec::new());
+		assert_eq!(typed(&mut layer, "j"), vec![key(keys.move_down)]);
+
+		let mut layer = VimLayer::new();
+		assert_eq!(
+			layer.translate(
+				&KeyEvent::new(
+					KeyCode::Char('v'),
+					KeyModifiers::empty()
+				),
+				&keys,
+				VimContext {
+					filter: None,
+					marking: None,
+				},
+			),
+			vec![KeyEvent::new(
+				KeyCode::Char('v'),
+				KeyModifiers::empty()
+			)]
+		);
+	}
+}
@@ -59,8 +59,15 @@
 //TODO:
 // #![deny(clippy::expect_used)]
 
+#[cfg(all(
+	feature = "syntax-highlighting",
+	not(any(feature = "regex-fancy", feature = "regex-onig"))
+))]
+compile_error!("syntax-highlighting needs regex-fancy or regex-onig");
+
 mod app;
 mod args;
+mod browser;
 mod bug_report;
 mod clipboard;
 mod cmdbar;
@@ -76,19 +83,24 @@ mod spinner;
 mod string_utils;
 mod strings;
 mod tabs;
+mod tty;
 mod ui;
 mod watcher;
 
-use crate::{app::App, args::process_cmdline};
+use crate::{
+	app::App,
+	args::{process_cmdline, PrintOnExit},
+};
 use anyhow::{anyhow, bail, Result};
 use app::QuitState;
 use asyncgit::{
-	sync::{utils::repo_work_dir, RepoPath},
+	sync::{repo_watch_paths, RepoPath},
 	AsyncGitNotification,
 };
 use backtrace::Backtrace;
 use crossbeam_channel::{never, tick, unbounded, Receiver, Select};
 use crossterm::{
+	event::{DisableMouseCapture, EnableMouseCapture},
 	terminal::{
 		disable_raw_mode, enable_raw_mode, EnterAlternateScreen,
 		LeaveAlternateScreen,
@@ -103,15 +115,16 @@ use scopetime::scope_time;
 use spinner::Spinner;
 use std::{
 	cell::RefCell,
-	io::{self, Stdout},
+	io::{self, Write},
+	ops::ControlFlow,
 	panic,
 	path::Path,
 	time::{Duration, Instant},
 };
 use ui::style::Theme;
-use watcher::RepoWatcher;
+use watcher::{RepoChange, RepoWatcher};
 
-type Terminal = ratatui::Terminal<CrosstermBackend<io::Stdout>>;
+type Terminal = ratatui::Terminal<CrosstermBackend<Box<dyn Write>>>;
 
 static TICK_INTERVAL: Duration = Duration::from_secs(5);
 static SPINNER_INTERVAL: Duration = Duration::from_millis(80);
@@ -121,6 +134,10 @@ static SPINNER_INTERVAL: Duration = Duration::from_millis(80);
 pub enum QueueEvent {
 	Tick,
 	Notify,
+	/// only files inside the git dir changed
+	NotifyGitDir,
+	/// a merge, rebase, .. started, stopped or moved on
+	NotifyRepoState,
 	SpinnerUpdate,
 	AsyncEvent(AsyncNotification),
 	InputEvent(InputEvent),
@@ -136,6 +153,8 @@ pub enum SyntaxHighlightProgress {
 pub enum AsyncAppNotification {
 	///
 	SyntaxHighlighting(SyntaxHighlightProgress),
+	/// the tree of the files of a revision is built
+	FileTree,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -168,57 +187,104 @@ fn main() -> Result<()> {
 	asyncgit::register_tracing_logging();
 	ensure_valid_path(&cliargs.repo_path)?;
 
-	let key_config = KeyConfig::init()
+	let key_config = KeyConfig::init(cliargs.glyphs)
 		.map_err(|e| log_eprintln!("KeyConfig loading error: {e}"))
-		.unwrap_or_default();
-	let theme = Theme::init(&cliargs.theme);
+		.unwrap_or_else(|()| KeyConfig::with_glyphs(cliargs.glyphs));
+	let theme =
+		Theme::init(&cliargs.theme).with_glyphs(cliargs.glyphs);
 
-	setup_terminal()?;
-	defer! {
-		shutdown_terminal();
+	if cliargs.print_on_exit.is_some() {
+		tty::draw_to_stderr();
 	}
 
-	set_panic_handler()?;
+	run_in_terminal(
+		|| setup_terminal(cliargs.mouse),
+		shutdown_terminal,
+		|| {
+			set_panic_handler()?;
+
+			let mut repo_path = cliargs.repo_path;
+			let mut terminal =
+				start_terminal(tty::output(), &repo_path)?;
+			let input = Input::new();
+
+			let updater = if cliargs.notify_watcher {
+				Updater::NotifyWatcher
+			} else {
+				Updater::Ticker
+			};
+
+			loop {
+				let quit_state = run_app(
+					app_start,
+					repo_path.clone(),
+					theme.clone(),
+					key_config.clone(),
+					cliargs.print_on_exit,
+					&input,
+					updater,
+					cliargs.mouse,
+					&mut terminal,
+				)?;
+
+				match printed_on_quit(quit_state) {
+					ControlFlow::Continue(p) => repo_path = p,
+					ControlFlow::Break(printed) => break Ok(printed),
+				}
+			}
+		},
+		&mut io::stdout(),
+	)
+}
 
-	let mut repo_path = cliargs.repo_path;
-	let mut terminal = start_terminal(io::stdout(), &repo_path)?;
-	let input = Input::new();
+/// runs `ui` between setting up the terminal and restoring it (also
+/// if `ui` fails), the `--print-on-exit` value it returns is written
+/// to `out` after the restore in case stdout is the terminal too
+fn run_in_terminal(
+	setup: impl FnOnce() -> Result<()>,
+	restore: impl FnOnce(),
+	ui: impl FnOnce() -> Result<Option<String>>,
+	out: &mut impl Write,
+) -> Result<()> {
+	let printed = {
+		setup()?;
+		defer! {
+			restore();
+		}
 
-	let updater = if cliargs.notify_watcher {
-		Updater::NotifyWatcher
-	} else {
-		Updater::Ticker
+		ui()?
 	};
 
-	loop {
-		let quit_state = run_app(
-			app_start,
-			repo_path.clone(),
-			theme.clone(),
-			key_config.clone(),
-			&input,
-			updater,
-			&mut terminal,
-		)?;
-
-		match quit_state {
-			QuitState::OpenSubmodule(p) => {
-				repo_path = p;
-			}
-			_ => break,
-		}
+	if let Some(value) = printed {
+		writeln!(out, "{value}")?;
 	}
 
 	Ok(())
 }
 
+/// the repo to open next or, once gitui is done, what to print
+fn printed_on_quit(
+	quit_state: QuitState,
+) -> ControlFlow<Option<String>, RepoPath> {
+	match quit_state {
+		QuitState::OpenSubmodule(p) => ControlFlow::Continue(p),
+		QuitState::Print(value) => ControlFlow::Break(Some(value)),
+		QuitState::None | QuitState::Close => {
+			ControlFlow::Break(None)
+		}
+	}
+}
+
+#[allow(clippy::too_many_arguments)]
 fn run_app(
 	app_start: Instant,
 	repo: RepoPath,
 	theme: Theme,
 	key_config: KeyConfig,
+	print_on_exit: Option<PrintOnExit>,
 	input: &Input,
 	updater: Updater,
+	mouse: bool,
 	terminal: &mut Terminal,
 ) -> Result<QuitState, anyhow::Error> {
 	let (tx_git, rx_git) = unbounded();
@@ -229,7 +295,7 @@ fn run_app(
 	let (rx_ticker, rx_watcher) = match updater {
 		Updater::NotifyWatcher => {
 			let repo_watcher =
-				RepoWatcher::new(repo_work_dir(&repo)?.as_str());
+				RepoWatcher::new(&repo_watch_paths(&repo)?);
 
 			(never(), repo_watcher.receiver())
 		}
@@ -237,6 +303,7 @@ fn run_app(
 	};
 
 	let spinner_ticker = tick(SPINNER_INTERVAL);
+	let mut spinner = Spinner::new(theme.glyphs().spinner);
 
 	let mut app = App::new(
 		RefCell::new(repo),
@@ -245,9 +312,9 @@ fn run_app(
 		input.clone(),
 		theme,
 		key_config,
+		print_on_exit,
 	)?;
 
-	let mut spinner = Spinner::default();
 	let mut first_update = true;
 
 	log::trace!("app start: {} ms", app_start.elapsed().as_millis());
@@ -269,6 +336,9 @@ fn run_app(
 
 		{
 			if matches!(event, QueueEvent::SpinnerUpdate) {
+				if app.expire_toast() {
+					draw(terminal, &app)?;
+				}
 				spinner.update();
 				spinner.draw(terminal)?;
 				continue;
@@ -284,12 +354,23 @@ fn run_app(
 					) {
 						//Note: external ed closed, we need to re-hide cursor
 						terminal.hide_cursor()?;
+						// and to capture the mouse again
+						if mouse {
+							tty::output()
+								.execute(EnableMouseCapture)?;
+						}
 					}
 					app.event(ev)?;
 				}
 				QueueEvent::Tick | QueueEvent::Notify => {
 					app.update()?;
 				}
+				QueueEvent::NotifyGitDir => {
+					app.update_git_dir()?;
+				}
+				QueueEvent::NotifyRepoState => {
+					app.update_repo_state()?;
+				}
 				QueueEvent::AsyncEvent(ev) => {
 					if !matches!(
 						ev,
@@ -317,15 +398,22 @@ fn run_app(
 	Ok(app.quit_state())
 }
 
-fn setup_terminal() -> Result<()> {
+fn setup_terminal(mouse: bool) -> Result<()> {
 	enable_raw_mode()?;
-	io::stdout().execute(EnterAlternateScreen)?;
+	tty::output().execute(EnterAlternateScreen)?;
+	if mouse {
+		tty::output().execute(EnableMouseCapture)?;
+	}
 	Ok(())
 }
 
 fn shutdown_terminal() {
+	if let Err(e) = tty::output().execute(DisableMouseCapture) {
+		log::error!("disable mouse capture failed:\n{e}");
+	}
+
 	let leave_screen =
-		io::stdout().execute(LeaveAlternateScreen).map(|_f| ());
+		tty::output().execute(LeaveAlternateScreen).map(|_f| ());
 
 	if let Err(e) = leave_screen {
 		log::error!("leave_screen failed:\n{e}");
@@ -367,7 +455,7 @@ fn select_event(
 	rx_git: &Receiver<AsyncGitNotification>,
 	rx_app: &Receiver<AsyncAppNotification>,
 	rx_ticker: &Receiver<Instant>,
-	rx_notify: &Receiver<()>,
+	rx_notify: &Receiver<RepoChange>,
 	rx_spinner: &Receiver<Instant>,
 ) -> Result<QueueEvent> {
 	let mut sel = Select::new();
@@ -391,7 +479,11 @@ fn select_event(
 			QueueEvent::AsyncEvent(AsyncNotification::App(e))
 		}),
 		3 => oper.recv(rx_ticker).map(|_| QueueEvent::Notify),
-		4 => oper.recv(rx_notify).map(|()| QueueEvent::Notify),
+		4 => oper.recv(rx_notify).map(|change| match change {
+			RepoChange::GitDir => QueueEvent::NotifyGitDir,
+			RepoChange::WorkDir => QueueEvent::Notify,
+			RepoChange::RepoState => QueueEvent::NotifyRepoState,
+		}),
 		5 => oper.recv(rx_spinner).map(|_| QueueEvent::SpinnerUpdate),
 		_ => bail!("unknown select source"),
 	}?;
@@ -400,7 +492,7 @@ fn select_event(
 }
 
 fn start_terminal(
-	buf: Stdout,
+	buf: Box<dyn Write>,
 	repo_path: &RepoPath,
 ) -> Result<Terminal> {
 	let mut path = repo_path.gitpath().canonicalize()?;
@@ -441,3 +533,77 @@ fn set_panic_handler() -> Result<()> {
 
 	Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// appends what is written to the shared log of calls
+	struct LogWriter<'a>(&'a RefCell<String>);
+
+	impl Write for LogWriter<'_> {
+		fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+			self.0
+				.borrow_mut()
+				.push_str(&String::from_utf8_lossy(buf));
+			Ok(buf.len())
+		}
+
+		fn flush(&mut self) -> io::Result<()> {
+			Ok(())
+		}
+	}
+
+	fn run(ui: Result<Option<String>>) -> (Result<()>, String) {
+		let log = RefCell::new(String::new());
+
+		let res = run_in_terminal(
+			|| {
+				log.borrow_mut().push_str("setup;");
+				Ok(())
+			},
+			|| log.borrow_mut().push_str("restore;"),
+			|| {
+				log.borrow_mut().push_str("ui;");
+				ui
+			},
+			&mut LogWriter(&log),
+		);
+
+		(res, log.into_inner())
+	}
+
+	#[test]
+	fn test_print_after_restore() {
+		let (res, log) = run(Ok(Some(String::from("/repo"))));
+		assert!(res.is_ok());
+		assert_eq!(log, "setup;ui;restore;/repo\n");
+
+		let (res, log) = run(Ok(None));
+		assert!(res.is_ok());
+		assert_eq!(log, "setup;ui;restore;");
+
+		let (res, log) = run(Err(anyhow!("failed")));
+		assert!(res.is_err());
+		assert_eq!(log, "setup;ui;restore;");
+	}
+
+	#[test]
+	fn test_printed_on_quit() {
+		assert!(matches!(
+			printed_on_quit(QuitState::Print(String::from("abc"))),
+			ControlFlow::Break(Some(value)) if value == "abc"
+		));
+		assert!(matches!(
+			printed_on_quit(QuitState::Close),
+			ControlFlow::Break(None)
+		));
+		assert!(matches!(
+			printed_on_quit(QuitState::OpenSubmodule(RepoPath::Path(
+				"sub".into()
+			))),
+			ControlFlow::Continue(RepoPath::Path(path))
+				if path == Path::new("sub")
+		));
+	}
+}
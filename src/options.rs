@@ -1,6 +1,10 @@
+use crate::{
+	args::get_app_config_path,
+	components::{folder_depth, LineLimits},
+};
 use anyhow::Result;
 use asyncgit::sync::{
-	diff::DiffOptions, repo_dir, RepoPathRef,
+	diff::DiffOptions, get_config_string, repo_dir, RepoPathRef,
 	ShowUntrackedFilesConfig,
 };
 use ron::{
@@ -10,26 +14,194 @@ use ron::{
 use serde::{Deserialize, Serialize};
 use std::{
 	cell::RefCell,
+	collections::{BTreeMap, BTreeSet},
 	fs::File,
 	io::{Read, Write},
 	path::PathBuf,
 	rc::Rc,
 };
 
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Default, Clone, Serialize, Deserialize)]
 struct OptionsData {
 	pub tab: usize,
 	pub diff: DiffOptions,
 	pub status_show_untracked: Option<ShowUntrackedFilesConfig>,
 	pub commit_msgs: Vec<String>,
+	#[serde(default)]
+	pub commit_verbose: Option<bool>,
+	/// per reviewed commit range: files marked as viewed and the
+	/// hash of their patch at that time, most recently used last
+	#[serde(default)]
+	pub review_marks: Vec<(String, BTreeMap<String, u64>)>,
+	#[serde(default)]
+	pub commit_hide_behind_notice: bool,
+	#[serde(default)]
+	pub diff_hide_word_changes: bool,
+	/// snapshot changed files into the local history
+	#[serde(default)]
+	pub local_history: bool,
+	#[serde(default)]
+	pub tree_initial_collapse: InitialCollapse,
+	/// leave the status tab as it is when a merge, rebase, .. stops
+	#[serde(default)]
+	pub status_keep_focus_on_conflicts: bool,
+	/// collapsed status tree folders for `InitialCollapse::Remember`
+	#[serde(default)]
+	pub tree_collapsed: BTreeSet<String>,
+	/// columns of the log, `LogColumn::defaults` if not set
+	#[serde(default)]
+	pub log_columns: Option<Vec<LogColumn>>,
+	/// vim style normal mode outside of popups and inputs
+	#[serde(default)]
+	pub vim_mode: bool,
+	/// line length of the commit subject, `DEFAULT_COMMIT_SUBJECT_WIDTH`
+	/// if not set and no limit if 0
+	#[serde(default)]
+	pub commit_subject_width: Option<usize>,
+	/// line length of the commit body, `DEFAULT_COMMIT_BODY_WIDTH` if
+	/// not set and no limit if 0
+	#[serde(default)]
+	pub commit_body_width: Option<usize>,
+	/// break body lines at their line length while typing
+	#[serde(default)]
+	pub commit_hard_wrap: bool,
+	/// changes inside of submodules get status tree sections
+	#[serde(default)]
+	pub status_submodule_sections: bool,
+}
+
+const DEFAULT_COPY_COMMIT_FORMAT: &str = "{short} (\"{summary}\")";
+const COPY_COMMIT_FILENAME: &str = "copy_commit.ron";
+const DEFAULT_COMMIT_SUBJECT_WIDTH: usize = 50;
+const DEFAULT_COMMIT_BODY_WIDTH: usize = 72;
+
+/// what a column of the log shows
+#[derive(
+	Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum LogColumnKind {
+	Hash,
+	Age,
+	Author,
+	/// tags, bookmarks and branches
+	Refs,
+	/// whether the commit is signed, it is not verified
+	Signature,
+	/// whether the commit has a note
+	Note,
+	/// gets the width the other columns leave
+	Message,
+}
+
+/// a column of the log, they are shown in the order configured
+#[derive(
+	Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize,
+)]
+pub struct LogColumn {
+	pub kind: LogColumnKind,
+	/// fixed width, `None` to size it to its content
+	#[serde(default)]
+	pub width: Option<usize>,
+	/// hidden while the list is narrower than this, so the lowest
+	/// thresholds are the last to go. the message is never hidden
+	#[serde(default)]
+	pub hide_below: usize,
+}
+
+impl LogColumn {
+	const fn new(kind: LogColumnKind, hide_below: usize) -> Self {
+		Self {
+			kind,
+			width: None,
+			hide_below,
+		}
+	}
+
+	pub fn defaults() -> Vec<Self> {
+		vec![
+			Self::new(LogColumnKind::Hash, 0),
+			Self::new(LogColumnKind::Age, 50),
+			Self::new(LogColumnKind::Author, 60),
+			Self::new(LogColumnKind::Refs, 30),
+			Self::new(LogColumnKind::Signature, 100),
+			Self::new(LogColumnKind::Note, 100),
+			Self::new(LogColumnKind::Message, 0),
+		]
+	}
+
+	/// whether it is shown in a list `width` wide
+	pub fn visible(&self, width: usize) -> bool {
+		self.kind == LogColumnKind::Message
+			|| width >= self.hide_below
+	}
+}
+
+/// whether a folder starts collapsed when the status tree is first
+/// built or the folder newly shows up, afterwards it keeps its state
+#[derive(
+	Default, Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum InitialCollapse {
+	#[default]
+	Expanded,
+	Collapsed,
+	/// top level folders are expanded, all below are collapsed
+	TopLevelOnly,
+	/// as it was left the last time, new folders are expanded
+	Remember,
+}
+
+impl InitialCollapse {
+	///
+	pub fn collapses(
+		self,
+		folder: &str,
+		remembered: &BTreeSet<String>,
+	) -> bool {
+		match self {
+			Self::Expanded => false,
+			Self::Collapsed => true,
+			Self::TopLevelOnly => folder_depth(folder) >= 1,
+			Self::Remember => remembered.contains(folder),
+		}
+	}
+}
+
+/// `copy_commit.ron` in the config dir, it is the same for every repo
+#[derive(Default, Deserialize)]
+struct CopyCommitFile {
+	/// the line copied per commit, `{hash}`, `{short}`, `{summary}`
+	/// and `{author}` get filled in, `DEFAULT_COPY_COMMIT_FORMAT` if
+	/// not set
+	#[serde(default)]
+	format: Option<String>,
+}
+
+impl CopyCommitFile {
+	fn read() -> Result<Self> {
+		let file = get_app_config_path()?.join(COPY_COMMIT_FILENAME);
+		if !file.exists() {
+			return Ok(Self::default());
+		}
+
+		let mut f = File::open(file)?;
+		let mut buffer = Vec::new();
+		f.read_to_end(&mut buffer)?;
+		Ok(from_bytes(&buffer)?)
+	}
 }
 
 const COMMIT_MSG_HISTORY_LENGTH: usize = 20;
+const REVIEW_MARKS_HISTORY_LENGTH: usize = 20;
 
 #[derive(Clone)]
 pub struct Options {
 	repo: RepoPathRef,
 	data: OptionsData,
+	copy_commit_format: String,
 }
 
 #[cfg(test)]
@@ -39,6 +211,8 @@ impl Options {
 		Self {
 			repo: RefCell::new(RepoPath::Path(Default::default())),
 			data: Default::default(),
+			copy_commit_format: DEFAULT_COPY_COMMIT_FORMAT
+				.to_string(),
 		}
 	}
 }
@@ -47,9 +221,22 @@ pub type SharedOptions = Rc<RefCell<Options>>;
 
 impl Options {
 	pub fn new(repo: RepoPathRef) -> SharedOptions {
+		let copy_commit_format = CopyCommitFile::read()
+			.map_err(|e| {
+				log::error!(
+					"read {COPY_COMMIT_FILENAME} failed: {e}"
+				);
+			})
+			.unwrap_or_default()
+			.format
+			.unwrap_or_else(|| {
+				DEFAULT_COPY_COMMIT_FORMAT.to_string()
+			});
+
 		Rc::new(RefCell::new(Self {
 			data: Self::read(&repo).unwrap_or_default(),
 			repo,
+			copy_commit_format,
 		}))
 	}
 
@@ -80,6 +267,186 @@ impl Options {
 		self.save();
 	}
 
+	pub const fn commit_verbose(&self) -> Option<bool> {
+		self.data.commit_verbose
+	}
+
+	pub fn set_commit_verbose(&mut self, value: Option<bool>) {
+		self.data.commit_verbose = value;
+		self.save();
+	}
+
+	/// `commit_verbose` falling back to the `commit.verbose` git config
+	pub fn is_commit_verbose(&self) -> bool {
+		self.data.commit_verbose.unwrap_or_else(|| {
+			get_config_string(&self.repo.borrow(), "commit.verbose")
+				.ok()
+				.flatten()
+				.is_some_and(|v| {
+					matches!(
+						v.to_lowercase().as_str(),
+						"true" | "yes" | "on" | "1"
+					)
+				})
+		})
+	}
+
+	pub const fn tree_initial_collapse(&self) -> InitialCollapse {
+		self.data.tree_initial_collapse
+	}
+
+	pub fn set_tree_initial_collapse(
+		&mut self,
+		value: InitialCollapse,
+	) {
+		self.data.tree_initial_collapse = value;
+		self.save();
+	}
+
+	pub const fn tree_collapsed(&self) -> &BTreeSet<String> {
+		&self.data.tree_collapsed
+	}
+
+	/// replaces what is remembered about `folders` by which of them
+	/// are `collapsed`, other folders are kept
+	pub fn remember_tree_collapsed(
+		&mut self,
+		folders: &BTreeSet<String>,
+		collapsed: &BTreeSet<String>,
+	) {
+		let remembered: BTreeSet<String> = self
+			.data
+			.tree_collapsed
+			.iter()
+			.filter(|folder| !folders.contains(*folder))
+			.chain(collapsed)
+			.cloned()
+			.collect();
+
+		if remembered != self.data.tree_collapsed {
+			self.data.tree_collapsed = remembered;
+			self.save();
+		}
+	}
+
+	pub fn log_columns(&self) -> Vec<LogColumn> {
+		self.data
+			.log_columns
+			.clone()
+			.unwrap_or_else(LogColumn::defaults)
+	}
+
+	pub fn copy_commit_format(&self) -> String {
+		self.copy_commit_format.clone()
+	}
+
+	pub fn commit_line_limits(
+		&self,
+		comment_char: char,
+	) -> LineLimits {
+		LineLimits {
+			subject: self
+				.data
+				.commit_subject_width
+				.unwrap_or(DEFAULT_COMMIT_SUBJECT_WIDTH),
+			body: self
+				.data
+				.commit_body_width
+				.unwrap_or(DEFAULT_COMMIT_BODY_WIDTH),
+			hard_wrap: self.data.commit_hard_wrap,
+			comment_char,
+		}
+	}
+
+	pub const fn commit_hard_wrap(&self) -> bool {
+		self.data.commit_hard_wrap
+	}
+
+	pub fn set_commit_hard_wrap(&mut self, value: bool) {
+		self.data.commit_hard_wrap = value;
+		self.save();
+	}
+
+	pub const fn focus_conflicts(&self) -> bool {
+		!self.data.status_keep_focus_on_conflicts
+	}
+
+	pub fn set_focus_conflicts(&mut self, value: bool) {
+		self.data.status_keep_focus_on_conflicts = !value;
+		self.save();
+	}
+
+	pub const fn status_submodule_sections(&self) -> bool {
+		self.data.status_submodule_sections
+	}
+
+	pub fn set_status_submodule_sections(&mut self, value: bool) {
+		self.data.status_submodule_sections = value;
+		self.save();
+	}
+
+	pub const fn commit_behind_notice(&self) -> bool {
+		!self.data.commit_hide_behind_notice
+	}
+
+	pub fn set_commit_behind_notice(&mut self, value: bool) {
+		self.data.commit_hide_behind_notice = !value;
+		self.save();
+	}
+
+	pub const fn diff_word_changes(&self) -> bool {
+		!self.data.diff_hide_word_changes
+	}
+
+	pub fn set_diff_word_changes(&mut self, value: bool) {
+		self.data.diff_hide_word_changes = !value;
+		self.save();
+	}
+
+	pub const fn local_history(&self) -> bool {
+		self.data.local_history
+	}
+
+	pub fn set_local_history(&mut self, value: bool) {
+		self.data.local_history = value;
+		self.save();
+	}
+
+	pub const fn vim_mode(&self) -> bool {
+		self.data.vim_mode
+	}
+
+	pub fn set_vim_mode(&mut self, value: bool) {
+		self.data.vim_mode = value;
+		self.save();
+	}
+
+	pub fn review_marks(&self, range: &str) -> BTreeMap<String, u64> {
+		self.data
+			.review_marks
+			.iter()
+			.find(|(key, _)| key == range)
+			.map(|(_, marks)| marks.clone())
+			.unwrap_or_default()
+	}
+
+	pub fn set_review_marks(
+		&mut self,
+		range: &str,
+		marks: BTreeMap<String, u64>,
+	) {
+		self.data.review_marks.retain(|(key, _)| key != range);
+		if !marks.is_empty() {
+			self.data.review_marks.push((range.to_string(), marks));
+		}
+		while self.data.review_marks.len()
+			> REVIEW_MARKS_HISTORY_LENGTH
+		{
+			self.data.review_marks.remove(0);
+		}
+		self.save();
+	}
+
 	pub fn diff_context_change(&mut self, increase: bool) {
 		self.data.diff.context = if increase {
 			self.data.diff.context.saturating_add(1)
@@ -16,7 +16,9 @@ use crate::{
 use anyhow::Result;
 use asyncgit::{
 	asyncjob::AsyncSingleJob,
-	sync::{BlameHunk, CommitId, FileBlame, RepoPathRef},
+	sync::{
+		blame_file_check, BlameHunk, CommitId, FileBlame, RepoPathRef,
+	},
 	AsyncBlame, AsyncGitNotification, BlameParams,
 };
 use crossbeam_channel::Sender;
@@ -370,6 +372,18 @@ impl BlameFilePopup {
 
 	///
 	pub fn open(&mut self, open: BlameFileOpen) -> Result<()> {
+		if let Err(err) = blame_file_check(
+			&self.repo.borrow(),
+			&open.file_path,
+			open.commit_id,
+		) {
+			self.queue.push(InternalEvent::ShowInfoMsg(
+				strings::blame_unavailable(&open.file_path, &err),
+			));
+			self.queue.push(InternalEvent::PopupStackPop);
+			return Ok(());
+		}
+
 		self.open_request = Some(open.clone());
 		self.params = Some(BlameParams {
 			file_path: open.file_path,
@@ -511,12 +525,17 @@ impl BlameFilePopup {
 				)
 			}
 			(false, Some(params), Some(file_blame)) => {
-				format!(
+				let title = format!(
 					"{} -- {} -- {}",
 					self.title,
 					params.file_path,
 					file_blame.commit_id().get_short_string()
-				)
+				);
+				if file_blame.file_blame.truncated {
+					strings::title_history_truncated(&title)
+				} else {
+					title
+				}
 			}
 			(false, Some(params), None) => {
 				format!(
@@ -557,6 +576,18 @@ impl BlameFilePopup {
 	}
 
 	fn highlight_blame_lines(&mut self) {
+		let Some(syntax) = self.theme.get_syntax() else {
+			if let Some(BlameProcess::SyntaxHighlighting {
+				unstyled_file_blame,
+				..
+			}) = self.blame.take()
+			{
+				self.blame =
+					Some(BlameProcess::Result(unstyled_file_blame));
+			}
+			return;
+		};
+
 		let Some(BlameProcess::SyntaxHighlighting {
 			ref unstyled_file_blame,
 			ref mut job,
@@ -580,7 +611,7 @@ impl BlameFilePopup {
 		job.spawn(AsyncSyntaxJob::new(
 			text,
 			params.file_path.clone(),
-			self.theme.get_syntax(),
+			syntax,
 		));
 	}
 
@@ -616,17 +647,19 @@ impl BlameFilePopup {
 
 		let line_number_width = self.get_line_number_width();
 
-		let text_cell = styled_text.as_ref().map_or_else(
-			|| {
-				Cell::from(tabs_to_spaces(String::from(line)))
-					.style(self.theme.text(true, false))
-			},
-			|styled_text| {
-				let styled_text =
-					styled_text.lines[line_number].clone();
-				Cell::from(styled_text)
-			},
-		);
+		// every row stays next to its own blame, lines missing in
+		// the highlighted text are shown plain
+		let text_cell = styled_text
+			.and_then(|styled_text| {
+				styled_text.lines.get(line_number)
+			})
+			.map_or_else(
+				|| {
+					Cell::from(tabs_to_spaces(String::from(line)))
+						.style(self.theme.text(true, false))
+				},
+				|styled_line| Cell::from(styled_line.clone()),
+			);
 
 		cells.push(
 			Cell::from(format!(
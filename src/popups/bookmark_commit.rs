@@ -0,0 +1,141 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, InputType, TextInputComponent,
+};
+use crate::{
+	app::Environment,
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings,
+};
+use anyhow::Result;
+use asyncgit::sync::{self, CommitId, RepoPathRef};
+use crossterm::event::Event;
+use ratatui::{layout::Rect, Frame};
+
+pub struct BookmarkCommitPopup {
+	repo: RepoPathRef,
+	input: TextInputComponent,
+	commit_id: Option<CommitId>,
+	queue: Queue,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for BookmarkCommitPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		self.input.draw(f, rect)?;
+
+		Ok(())
+	}
+}
+
+impl Component for BookmarkCommitPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			self.input.commands(out, force_all);
+
+			out.push(CommandInfo::new(
+				strings::commands::bookmark_commit_confirm_msg(
+					&self.key_config,
+				),
+				self.is_valid_name(),
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.enter)
+					&& self.is_valid_name()
+				{
+					self.bookmark();
+					return Ok(EventState::Consumed);
+				}
+			}
+
+			self.input.event(ev)?;
+			return Ok(EventState::Consumed);
+		}
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.input.is_visible()
+	}
+
+	fn hide(&mut self) {
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.input.show()?;
+
+		Ok(())
+	}
+}
+
+impl BookmarkCommitPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			queue: env.queue.clone(),
+			input: TextInputComponent::new(
+				env,
+				&strings::bookmark_popup_name_title(),
+				&strings::bookmark_popup_name_msg(),
+				true,
+			)
+			.with_input_type(InputType::Singleline),
+			commit_id: None,
+			key_config: env.key_config.clone(),
+			repo: env.repo.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self, id: CommitId) -> Result<()> {
+		self.commit_id = Some(id);
+		self.input.clear();
+		self.show()?;
+
+		Ok(())
+	}
+
+	fn is_valid_name(&self) -> bool {
+		!self.input.get_text().trim().is_empty()
+	}
+
+	fn bookmark(&mut self) {
+		if let Some(commit_id) = self.commit_id {
+			let result = sync::bookmark_commit(
+				&self.repo.borrow(),
+				self.input.get_text(),
+				commit_id,
+			);
+
+			self.hide();
+
+			match result {
+				Ok(()) => {
+					self.queue.push(InternalEvent::Update(
+						NeedsUpdate::ALL,
+					));
+				}
+				Err(e) => {
+					log::error!("bookmark error: {e}");
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!("bookmark error:\n{e}"),
+					));
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,443 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState,
+};
+use crate::{
+	app::Environment,
+	components::ScrollType,
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings, try_or_popup,
+	ui::{self, Size},
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	bookmark_commit, bookmark_reachable, delete_bookmark,
+	find_commit_by_patch_id, get_bookmarks, get_commit_info,
+	Bookmark, RepoPathRef,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Margin, Rect},
+	text::Span,
+	widgets::{
+		Block, BorderType, Borders, Cell, Clear, Row, Table,
+		TableState,
+	},
+	Frame,
+};
+use ui::style::SharedTheme;
+
+struct BookmarkEntry {
+	bookmark: Bookmark,
+	reachable: bool,
+	/// `None` once the commit is gone (gc)
+	message: Option<String>,
+}
+
+///
+pub struct BookmarksPopup {
+	repo: RepoPathRef,
+	theme: SharedTheme,
+	queue: Queue,
+	entries: Vec<BookmarkEntry>,
+	visible: bool,
+	table_state: std::cell::Cell<TableState>,
+	current_height: std::cell::Cell<usize>,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for BookmarksPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.visible {
+			const PERCENT_SIZE: Size = Size::new(80, 50);
+			const MIN_SIZE: Size = Size::new(60, 20);
+
+			let area = ui::centered_rect(
+				PERCENT_SIZE.width,
+				PERCENT_SIZE.height,
+				f.area(),
+			);
+			let area =
+				ui::rect_inside(MIN_SIZE, f.area().into(), area);
+			let area = area.intersection(rect);
+
+			let name_width =
+				self.entries.iter().fold(0, |acc, entry| {
+					acc.max(entry.bookmark.name.len())
+				});
+
+			let constraints = [
+				// glyph
+				Constraint::Length(1),
+				// name
+				Constraint::Length(name_width.try_into()?),
+				// hash
+				Constraint::Length(7),
+				// message
+				Constraint::Percentage(100),
+			];
+
+			let rows: Vec<Row> = self
+				.entries
+				.iter()
+				.map(|e| self.get_row(e))
+				.collect();
+			let number_of_rows = rows.len();
+
+			let table = Table::new(rows, constraints)
+				.column_spacing(1)
+				.row_highlight_style(self.theme.text(true, true))
+				.block(
+					Block::default()
+						.borders(Borders::ALL)
+						.title(Span::styled(
+							strings::title_bookmarks(),
+							self.theme.title(true),
+						))
+						.border_style(self.theme.block(true))
+						.border_type(BorderType::Thick),
+				);
+
+			let mut table_state = self.table_state.take();
+
+			f.render_widget(Clear, area);
+			f.render_stateful_widget(table, area, &mut table_state);
+
+			let area = area.inner(Margin {
+				vertical: 1,
+				horizontal: 0,
+			});
+
+			ui::draw_scrollbar(
+				f,
+				area,
+				&self.theme,
+				number_of_rows,
+				table_state.selected().unwrap_or(0),
+				ui::Orientation::Vertical,
+			);
+
+			self.table_state.set(table_state);
+			self.current_height.set(area.height.into());
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for BookmarksPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			if !force_all {
+				out.clear();
+			}
+
+			let selected = self.selected_entry();
+
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::select_bookmark(&self.key_config),
+				selected.is_some_and(|entry| entry.reachable),
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::delete_bookmark(&self.key_config),
+				selected.is_some(),
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::repoint_bookmark(&self.key_config),
+				selected.is_some_and(|entry| !entry.reachable),
+				true,
+			));
+		}
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, event: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(key) = event {
+				if key_match(key, self.key_config.keys.exit_popup) {
+					self.hide();
+				} else if key_match(key, self.key_config.keys.move_up)
+				{
+					self.move_selection(ScrollType::Up);
+				} else if key_match(
+					key,
+					self.key_config.keys.move_down,
+				) {
+					self.move_selection(ScrollType::Down);
+				} else if key_match(
+					key,
+					self.key_config.keys.shift_up,
+				) || key_match(
+					key,
+					self.key_config.keys.home,
+				) {
+					self.move_selection(ScrollType::Home);
+				} else if key_match(
+					key,
+					self.key_config.keys.shift_down,
+				) || key_match(
+					key,
+					self.key_config.keys.end,
+				) {
+					self.move_selection(ScrollType::End);
+				} else if key_match(
+					key,
+					self.key_config.keys.page_down,
+				) {
+					self.move_selection(ScrollType::PageDown);
+				} else if key_match(key, self.key_config.keys.page_up)
+				{
+					self.move_selection(ScrollType::PageUp);
+				} else if key_match(key, self.key_config.keys.enter) {
+					self.select_selected();
+				} else if key_match(
+					key,
+					self.key_config.keys.bookmark_delete,
+				) {
+					try_or_popup!(
+						self,
+						"delete bookmark error:",
+						self.delete_selected()
+					);
+				} else if key_match(
+					key,
+					self.key_config.keys.bookmark_repoint,
+				) {
+					try_or_popup!(
+						self,
+						"re-point bookmark error:",
+						self.repoint_selected()
+					);
+				}
+			}
+
+			Ok(EventState::Consumed)
+		} else {
+			Ok(EventState::NotConsumed)
+		}
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
+
+impl BookmarksPopup {
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			theme: env.theme.clone(),
+			queue: env.queue.clone(),
+			entries: Vec::new(),
+			visible: false,
+			table_state: std::cell::Cell::new(TableState::default()),
+			current_height: std::cell::Cell::new(0),
+			key_config: env.key_config.clone(),
+			repo: env.repo.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self) -> Result<()> {
+		self.update_entries()?;
+		self.table_state.get_mut().select(Some(0));
+		self.show()?;
+
+		Ok(())
+	}
+
+	fn update_entries(&mut self) -> Result<()> {
+		let repo = self.repo.borrow();
+
+		self.entries = get_bookmarks(&repo)?
+			.into_iter()
+			.map(|bookmark| {
+				let reachable =
+					bookmark_reachable(&repo, bookmark.id)?;
+				let message = get_commit_info(&repo, &bookmark.id)
+					.ok()
+					.map(|info| info.message);
+
+				Ok(BookmarkEntry {
+					bookmark,
+					reachable,
+					message,
+				})
+			})
+			.collect::<Result<_>>()?;
+
+		let max_selection = self.entries.len().saturating_sub(1);
+		let table_state = self.table_state.get_mut();
+		table_state.select(Some(
+			table_state.selected().unwrap_or(0).min(max_selection),
+		));
+
+		Ok(())
+	}
+
+	///
+	fn move_selection(&self, scroll_type: ScrollType) -> bool {
+		let mut table_state = self.table_state.take();
+
+		let old_selection = table_state.selected().unwrap_or(0);
+		let max_selection = self.entries.len().saturating_sub(1);
+
+		let new_selection = match scroll_type {
+			ScrollType::Up => old_selection.saturating_sub(1),
+			ScrollType::Down => {
+				old_selection.saturating_add(1).min(max_selection)
+			}
+			ScrollType::Home => 0,
+			ScrollType::End => max_selection,
+			ScrollType::PageUp => old_selection.saturating_sub(
+				self.current_height.get().saturating_sub(1),
+			),
+			ScrollType::PageDown => old_selection
+				.saturating_add(
+					self.current_height.get().saturating_sub(1),
+				)
+				.min(max_selection),
+			ScrollType::HalfPageUp => old_selection.saturating_sub(
+				(self.current_height.get().saturating_sub(1)) / 2,
+			),
+			ScrollType::HalfPageDown => old_selection
+				.saturating_add(
+					(self.current_height.get().saturating_sub(1)) / 2,
+				)
+				.min(max_selection),
+		};
+
+		let needs_update = new_selection != old_selection;
+
+		table_state.select(Some(new_selection));
+		self.table_state.set(table_state);
+
+		needs_update
+	}
+
+	fn get_row(&self, entry: &BookmarkEntry) -> Row<'_> {
+		let style = |style| {
+			if entry.reachable {
+				style
+			} else {
+				self.theme.commit_unhighlighted()
+			}
+		};
+
+		let cells: Vec<Cell> = vec![
+			Cell::from(self.theme.glyphs().bookmark)
+				.style(style(self.theme.tags(false))),
+			Cell::from(entry.bookmark.name.clone())
+				.style(style(self.theme.text(true, false))),
+			Cell::from(entry.bookmark.id.get_short_string())
+				.style(style(self.theme.commit_hash(false))),
+			Cell::from(entry.message.clone().unwrap_or_default())
+				.style(style(self.theme.text(true, false))),
+		];
+
+		Row::new(cells)
+	}
+
+	fn selected_entry(&self) -> Option<&BookmarkEntry> {
+		let table_state = self.table_state.take();
+
+		let entry = table_state
+			.selected()
+			.and_then(|selected| self.entries.get(selected));
+
+		self.table_state.set(table_state);
+
+		entry
+	}
+
+	fn select_selected(&self) {
+		let Some(entry) = self.selected_entry() else {
+			return;
+		};
+
+		if entry.reachable {
+			self.queue.push(InternalEvent::SelectCommitInRevlog(
+				entry.bookmark.id,
+			));
+		} else {
+			self.queue.push(InternalEvent::ShowInfoMsg(
+				strings::bookmark_unreachable(),
+			));
+		}
+	}
+
+	fn delete_selected(&mut self) -> Result<()> {
+		if let Some(entry) = self.selected_entry() {
+			delete_bookmark(
+				&self.repo.borrow(),
+				&entry.bookmark.name,
+			)?;
+
+			self.update_entries()?;
+			self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+		}
+
+		Ok(())
+	}
+
+	/// moves an unreachable bookmark to the commit that has the same
+	/// changes in the history of `HEAD` (e.g. after a rebase)
+	fn repoint_selected(&mut self) -> Result<()> {
+		let Some(entry) = self.selected_entry() else {
+			return Ok(());
+		};
+
+		if entry.reachable {
+			return Ok(());
+		}
+
+		let repo = self.repo.borrow().clone();
+
+		match find_commit_by_patch_id(&repo, entry.bookmark.id)? {
+			Some(id) => {
+				bookmark_commit(&repo, &entry.bookmark.name, id)?;
+
+				self.update_entries()?;
+				self.queue
+					.push(InternalEvent::Update(NeedsUpdate::ALL));
+			}
+			None => {
+				self.queue.push(InternalEvent::ShowInfoMsg(
+					strings::bookmark_repoint_not_found(),
+				));
+			}
+		}
+
+		Ok(())
+	}
+}
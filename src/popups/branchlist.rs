@@ -21,7 +21,7 @@ use asyncgit::{
 			RemoteBranch,
 		},
 		checkout_branch, get_branches_info, BranchInfo, BranchType,
-		CommitId, RepoPathRef, RepoState,
+		CommitId, ParkReturn, ParkedChanges, RepoPathRef, RepoState,
 	},
 	AsyncGitNotification,
 };
@@ -38,16 +38,21 @@ use std::cell::Cell;
 use ui::style::SharedTheme;
 use unicode_truncate::UnicodeTruncateStr;
 
-use super::InspectCommitOpen;
+use super::{CompareBranchesOpen, InspectCommitOpen};
 
 ///
+#[allow(clippy::struct_excessive_bools)]
 pub struct BranchListPopup {
 	repo: RepoPathRef,
 	branches: Vec<BranchInfo>,
 	local: bool,
 	has_remotes: bool,
+	/// the latest changes parked to switch branches
+	parked: Option<ParkedChanges>,
 	visible: bool,
 	selection: u16,
+	/// set once the user moved the selection themselves
+	user_selection: bool,
 	scroll: VerticalScroll,
 	current_height: Cell<u16>,
 	queue: Queue,
@@ -118,7 +123,7 @@ impl Component for BranchListPopup {
 	}
 
 	//TODO: cleanup
-	#[allow(clippy::cognitive_complexity)]
+	#[allow(clippy::cognitive_complexity, clippy::too_many_lines)]
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if !self.visible {
 			return Ok(EventState::NotConsumed);
@@ -143,9 +148,21 @@ impl Component for BranchListPopup {
 			{
 				self.queue.push(InternalEvent::CreateBranch);
 			} else if key_match(e, self.key_config.keys.rename_branch)
+				&& self.local
 				&& self.valid_selection()
 			{
 				self.rename_branch();
+			} else if key_match(
+				e,
+				self.key_config.keys.branch_create_worktree,
+			) && self.local
+				&& self.valid_selection()
+			{
+				self.queue.push(InternalEvent::CreateWorktree(
+					self.branches[self.selection as usize]
+						.name
+						.clone(),
+				));
 			} else if key_match(e, self.key_config.keys.delete_branch)
 				&& !selection_is_cur_branch
 				&& self.valid_selection()
@@ -155,11 +172,7 @@ impl Component for BranchListPopup {
 				&& !selection_is_cur_branch
 				&& self.valid_selection()
 			{
-				try_or_popup!(
-					self,
-					"merge branch error:",
-					self.merge_branch()
-				);
+				self.merge_branch();
 			} else if key_match(e, self.key_config.keys.rebase_branch)
 				&& !selection_is_cur_branch
 				&& self.valid_selection()
@@ -186,6 +199,41 @@ impl Component for BranchListPopup {
 						),
 					));
 				}
+			} else if key_match(
+				e,
+				self.key_config.keys.compare_branch,
+			) && self.valid_selection()
+			{
+				self.compare_branch();
+			} else if key_match(e, self.key_config.keys.branch_park)
+				&& self.local
+				&& !selection_is_cur_branch
+				&& self.valid_selection()
+			{
+				try_or_popup!(
+					self,
+					"park and switch error:",
+					self.park_and_switch()
+				);
+			} else if key_match(e, self.key_config.keys.branch_unpark)
+				&& self.parked.is_some()
+			{
+				try_or_popup!(
+					self,
+					"return to parked branch error:",
+					self.return_to_parked()
+				);
+			} else if key_match(
+				e,
+				self.key_config.keys.branch_push_remote,
+			) && self.local
+				&& self.valid_selection()
+			{
+				try_or_popup!(
+					self,
+					"set push remote error:",
+					self.cycle_push_remote()
+				);
 			} else if key_match(e, self.key_config.keys.fetch)
 				&& self.has_remotes
 			{
@@ -243,8 +291,10 @@ impl BranchListPopup {
 			branches: Vec::new(),
 			local: true,
 			has_remotes: false,
+			parked: None,
 			visible: false,
 			selection: 0,
+			user_selection: false,
 			scroll: VerticalScroll::new(),
 			queue: env.queue.clone(),
 			theme: env.theme.clone(),
@@ -285,6 +335,12 @@ impl BranchListPopup {
 			self.local = !self.local;
 			self.check_remotes();
 			self.update_branches()?;
+		} else if key_match(e, self.key_config.keys.refresh) {
+			self.update_branches()?;
+			self.queue.push(InternalEvent::ShowToast(
+				strings::refreshed("branches"),
+			));
+			return Ok(EventState::Consumed);
 		}
 		Ok(EventState::NotConsumed)
 	}
@@ -298,6 +354,7 @@ impl BranchListPopup {
 	}
 
 	pub fn branch_finder_update(&mut self, idx: usize) -> Result<()> {
+		self.user_selection = true;
 		self.set_selection(idx.try_into()?)?;
 		Ok(())
 	}
@@ -315,6 +372,8 @@ impl BranchListPopup {
 	pub fn update_branches(&mut self) -> Result<()> {
 		if self.is_visible() {
 			self.check_remotes();
+			self.parked =
+				sync::get_parked(&self.repo.borrow())?.pop();
 			self.branches =
 				get_branches_info(&self.repo.borrow(), self.local)?;
 			//remove remote branch called `HEAD`
@@ -324,7 +383,14 @@ impl BranchListPopup {
 					.position(|b| b.name.ends_with("/HEAD"))
 					.map(|idx| self.branches.remove(idx));
 			}
-			self.set_selection(self.selection)?;
+			let selection = if self.user_selection {
+				self.selection
+			} else {
+				head_branch_index(&self.branches)
+					.unwrap_or_default()
+					.try_into()?
+			};
+			self.set_selection(selection)?;
 		}
 		Ok(())
 	}
@@ -345,11 +411,23 @@ impl BranchListPopup {
 		!self.branches.is_empty()
 	}
 
-	fn merge_branch(&mut self) -> Result<()> {
+	fn merge_branch(&mut self) {
 		if let Some(branch) =
 			self.branches.get(usize::from(self.selection))
 		{
-			sync::merge_branch(
+			self.queue.push(InternalEvent::OpenMergeBranchPopup(
+				branch.name.clone(),
+				self.get_branch_type(),
+			));
+			self.hide();
+		}
+	}
+
+	fn rebase_branch(&mut self) -> Result<()> {
+		if let Some(branch) =
+			self.branches.get(usize::from(self.selection))
+		{
+			sync::rebase_branch(
 				&self.repo.borrow(),
 				&branch.name,
 				self.get_branch_type(),
@@ -361,22 +439,83 @@ impl BranchListPopup {
 		Ok(())
 	}
 
-	fn rebase_branch(&mut self) -> Result<()> {
+	fn park_and_switch(&mut self) -> Result<()> {
 		if let Some(branch) =
 			self.branches.get(usize::from(self.selection))
 		{
-			sync::rebase_branch(
+			let parked = sync::park_and_switch(
 				&self.repo.borrow(),
 				&branch.name,
-				self.get_branch_type(),
 			)?;
 
-			self.hide_and_switch_tab()?;
+			self.hide();
+			self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+			self.queue.push(InternalEvent::ShowInfoMsg(
+				strings::branch_parked(parked.as_ref()),
+			));
 		}
 
 		Ok(())
 	}
 
+	fn return_to_parked(&mut self) -> Result<()> {
+		let result = sync::return_to_parked(&self.repo.borrow());
+
+		// a branch that is gone is forgotten as well
+		self.update_branches()?;
+		let (parked, result) = result?;
+
+		self.hide();
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+		self.queue.push(InternalEvent::ShowInfoMsg(
+			strings::branch_unparked(&parked, result),
+		));
+		if result == ParkReturn::Conflicted {
+			self.queue.push(InternalEvent::TabSwitchStatus);
+		}
+
+		Ok(())
+	}
+
+	/// cycles `branch.<name>.pushRemote` through: unset, all remotes
+	fn cycle_push_remote(&self) -> Result<()> {
+		let Some(branch) =
+			self.branches.get(usize::from(self.selection))
+		else {
+			return Ok(());
+		};
+		let repo = self.repo.borrow();
+
+		let remotes = sync::get_remotes(&repo)?;
+		let next =
+			sync::get_branch_push_remote_config(&repo, &branch.name)?
+				.map_or_else(
+					|| remotes.first(),
+					|current| {
+						remotes
+							.iter()
+							.position(|r| *r == current)
+							.and_then(|idx| remotes.get(idx + 1))
+					},
+				);
+
+		sync::set_branch_push_remote(
+			&repo,
+			&branch.name,
+			next.map(String::as_str),
+		)?;
+
+		self.queue.push(InternalEvent::ShowToast(
+			strings::branch_push_remote(
+				&branch.name,
+				next.is_some(),
+				&sync::get_branch_push_remote(&repo, &branch.name)?,
+			),
+		));
+
+		Ok(())
+	}
+
 	fn inspect_head_of_branch(&mut self) {
 		if let Some(commit_id) = self.get_selected_commit() {
 			self.hide();
@@ -388,6 +527,21 @@ impl BranchListPopup {
 		}
 	}
 
+	fn compare_branch(&mut self) {
+		if let Some(branch) =
+			self.branches.get(usize::from(self.selection))
+		{
+			let open = CompareBranchesOpen {
+				branch_ref: branch.reference.clone(),
+				name: branch.name.clone(),
+			};
+			self.hide();
+			self.queue.push(InternalEvent::OpenPopup(
+				StackablePopupOpen::CompareBranches(open),
+			));
+		}
+	}
+
 	const fn get_branch_type(&self) -> BranchType {
 		if self.local {
 			BranchType::Local
@@ -453,6 +607,7 @@ impl BranchListPopup {
 			}
 		};
 
+		self.user_selection = true;
 		self.set_selection(new_selection)?;
 
 		Ok(true)
@@ -480,10 +635,8 @@ impl BranchListPopup {
 		width_available: u16,
 		height: usize,
 	) -> Text {
-		const UPSTREAM_SYMBOL: char = '\u{2191}';
-		const TRACKING_SYMBOL: char = '\u{2193}';
-		const HEAD_SYMBOL: char = '*';
-		const EMPTY_SYMBOL: char = ' ';
+		const HEAD_SYMBOL: &str = "*";
+		const EMPTY_SYMBOL: &str = " ";
 		const THREE_DOTS: &str = "...";
 		const THREE_DOTS_LENGTH: usize = THREE_DOTS.len(); // "..."
 		const COMMIT_HASH_LENGTH: usize = 8;
@@ -540,14 +693,18 @@ impl BranchListPopup {
 			let is_head_str =
 				if is_head { HEAD_SYMBOL } else { EMPTY_SYMBOL };
 			let upstream_tracking_str = match displaybranch.details {
+				BranchDetails::Local(LocalBranch {
+					upstream_gone,
+					..
+				}) if upstream_gone => theme.glyphs().gone,
 				BranchDetails::Local(LocalBranch {
 					has_upstream,
 					..
-				}) if has_upstream => UPSTREAM_SYMBOL,
+				}) if has_upstream => theme.glyphs().ahead,
 				BranchDetails::Remote(RemoteBranch {
 					has_tracking,
 					..
-				}) if has_tracking => TRACKING_SYMBOL,
+				}) if has_tracking => theme.glyphs().behind,
 				_ => EMPTY_SYMBOL,
 			};
 
@@ -682,6 +839,7 @@ impl BranchListPopup {
 		));
 	}
 
+	#[allow(clippy::too_many_lines)]
 	fn add_commands_internal(&self, out: &mut Vec<CommandInfo>) {
 		let selection_is_cur_branch = self.selection_is_cur_branch();
 
@@ -752,10 +910,30 @@ impl BranchListPopup {
 
 		out.push(CommandInfo::new(
 			strings::commands::rename_branch_popup(&self.key_config),
-			true,
+			self.valid_selection(),
+			self.local,
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::branch_create_worktree(
+				&self.key_config,
+			),
+			self.valid_selection(),
 			self.local,
 		));
 
+		out.push(CommandInfo::new(
+			strings::commands::compare_branch(&self.key_config),
+			self.valid_selection(),
+			true,
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::refresh(&self.key_config),
+			true,
+			true,
+		));
+
 		out.push(CommandInfo::new(
 			strings::commands::fetch_remotes(&self.key_config),
 			self.has_remotes,
@@ -779,5 +957,117 @@ impl BranchListPopup {
 			true,
 			self.has_remotes,
 		));
+
+		self.add_park_commands(out);
+
+		out.push(CommandInfo::new(
+			strings::commands::branch_push_remote(&self.key_config),
+			self.valid_selection(),
+			self.local,
+		));
+	}
+
+	fn add_park_commands(&self, out: &mut Vec<CommandInfo>) {
+		out.push(CommandInfo::new(
+			strings::commands::branch_park(&self.key_config),
+			!self.selection_is_cur_branch() && self.valid_selection(),
+			self.local,
+		));
+
+		if let Some(parked) = &self.parked {
+			out.push(CommandInfo::new(
+				strings::commands::branch_unpark(
+					&self.key_config,
+					&parked.branch,
+				),
+				true,
+				true,
+			));
+		}
+	}
+}
+
+/// index of the checked out branch
+fn head_branch_index(branches: &[BranchInfo]) -> Option<usize> {
+	branches.iter().position(|branch| {
+		branch
+			.local_details()
+			.is_some_and(|details| details.is_head)
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use asyncgit::sync::RepoPath;
+	use crossterm::event::{KeyCode, KeyModifiers};
+	use std::{path::Path, process::Command};
+	use tempfile::TempDir;
+
+	fn git(path: &Path, args: &[&str]) {
+		assert!(Command::new("git")
+			.args(["-c", "user.name=name", "-c", "user.email=email"])
+			.args(args)
+			.current_dir(path)
+			.status()
+			.unwrap()
+			.success());
+	}
+
+	fn branch(name: &str, is_head: bool) -> BranchInfo {
+		BranchInfo {
+			name: String::from(name),
+			reference: format!("refs/heads/{name}"),
+			top_commit_message: String::new(),
+			top_commit: CommitId::default(),
+			details: BranchDetails::Local(LocalBranch {
+				is_head,
+				has_upstream: false,
+				upstream_gone: false,
+				upstream: None,
+				remote: None,
+			}),
+		}
+	}
+
+	#[test]
+	fn test_head_branch_index() {
+		assert_eq!(head_branch_index(&[]), None);
+		assert_eq!(
+			head_branch_index(&[
+				branch("a", false),
+				branch("b", true),
+				branch("c", false),
+			]),
+			Some(1)
+		);
+		assert_eq!(head_branch_index(&[branch("a", false)]), None);
+	}
+
+	#[test]
+	fn test_refresh_reads_branches_again() {
+		let td = TempDir::new().unwrap();
+		git(td.path(), &["init", "-q"]);
+		git(td.path(), &["commit", "-q", "--allow-empty", "-m", "a"]);
+
+		let env = Environment::test_env();
+		*env.repo.borrow_mut() = RepoPath::Path(td.path().into());
+		let mut popup = BranchListPopup::new(&env);
+		popup.open().unwrap();
+		assert_eq!(popup.branches.len(), 1);
+
+		git(td.path(), &["branch", "other"]);
+		assert_eq!(popup.branches.len(), 1);
+
+		let refresh = Event::Key(KeyEvent::new(
+			KeyCode::F(5),
+			KeyModifiers::empty(),
+		));
+		assert!(popup.event(&refresh).unwrap().is_consumed());
+		assert_eq!(popup.branches.len(), 2);
+		assert!(matches!(
+			env.queue.pop(),
+			Some(InternalEvent::ShowToast(msg)) if msg == "refreshed branches"
+		));
 	}
 }
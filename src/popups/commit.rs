@@ -1,39 +1,45 @@
 use crate::components::{
 	visibility_blocking, CommandBlocking, CommandInfo, Component,
-	DrawableComponent, EventState, TextInputComponent,
+	DiffComponent, DrawableComponent, EventState, LineLimits,
+	ScrollType, TextInputComponent,
 };
 use crate::{
 	app::Environment,
+	args::get_app_config_path,
 	keys::{key_match, SharedKeyConfig},
 	options::SharedOptions,
-	queue::{InternalEvent, NeedsUpdate, Queue},
+	queue::{InternalEvent, NeedsUpdate, ProcessItem, Queue},
 	strings, try_or_popup,
 	ui::style::SharedTheme,
 };
 use anyhow::{bail, Ok, Result};
-use asyncgit::sync::commit::commit_message_prettify;
+use asyncgit::sync::commit::{
+	commit_comment_char, commit_message_prettify,
+};
 use asyncgit::{
 	cached,
 	sync::{
-		self, get_config_string, CommitId, HookResult,
-		PrepareCommitMsgSource, RepoPathRef, RepoState,
+		self, get_config_string, CommitId, PrepareCommitMsgSource,
+		RepoPathRef, RepoState,
 	},
-	StatusItem, StatusItemType,
+	FileDiff, StatusItem, StatusItemType,
 };
 use crossterm::event::Event;
 use easy_cast::Cast;
 use ratatui::{
 	layout::{Alignment, Rect},
-	widgets::Paragraph,
+	widgets::{Clear, Paragraph},
 	Frame,
 };
 
 use std::{
 	fmt::Write as _,
-	fs::{read_to_string, File},
+	fs::{read_dir, read_to_string, File},
 	io::{Read, Write},
-	path::PathBuf,
+	path::{Path, PathBuf},
+	process::Child,
 	str::FromStr,
+	time::{Duration, SystemTime},
 };
 
 use super::ExternalEditorPopup;
@@ -43,6 +49,13 @@ enum CommitResult {
 	Aborted,
 }
 
+/// the branch is behind its upstream as of the last fetch
+struct BehindUpstream {
+	branch: String,
+	behind: usize,
+	fetched: Option<SystemTime>,
+}
+
 enum Mode {
 	Normal,
 	Amend(CommitId),
@@ -59,13 +72,26 @@ pub struct CommitPopup {
 	key_config: SharedKeyConfig,
 	git_branch_name: cached::BranchName,
 	commit_template: Option<String>,
+	/// saved message templates by name, see [`read_commit_templates`]
+	templates: Vec<(String, String)>,
+	template_idx: usize,
+	comment_char: char,
 	theme: SharedTheme,
 	commit_msg_history_idx: usize,
 	options: SharedOptions,
 	verify: bool,
+	/// skip the pre-commit hook, it already ran for this commit
+	pre_commit_passed: bool,
+	/// the msg the commit-msg hook passed (and maybe altered),
+	/// committed right away
+	commit_msg_passed: Option<String>,
+	verbose: bool,
+	diff: DiffComponent,
+	behind_upstream: Option<BehindUpstream>,
 }
 
-const FIRST_LINE_LIMIT: usize = 50;
+/// below this height the verbose diff pane is collapsed
+const VERBOSE_DIFF_MIN_HEIGHT: u16 = 6;
 
 impl CommitPopup {
 	///
@@ -84,11 +110,19 @@ impl CommitPopup {
 				env.repo.clone(),
 			),
 			commit_template: None,
+			templates: Vec::new(),
+			template_idx: 0,
+			comment_char: '#',
 			theme: env.theme.clone(),
 			repo: env.repo.clone(),
 			commit_msg_history_idx: 0,
 			options: env.options.clone(),
 			verify: true,
+			pre_commit_passed: false,
+			commit_msg_passed: None,
+			verbose: false,
+			diff: DiffComponent::new(env, true),
+			behind_upstream: None,
 		}
 	}
 
@@ -97,6 +131,48 @@ impl CommitPopup {
 		self.git_branch_name.lookup().ok();
 	}
 
+	/// the staged diff is shown below the message
+	pub fn wants_staged_diff(&self) -> bool {
+		self.is_visible() && self.verbose
+	}
+
+	/// `None` while the staged diff is still being computed
+	pub fn set_staged_diff(&mut self, diff: Option<FileDiff>) {
+		if let Some(diff) = diff {
+			self.diff.update(
+				strings::commit_verbose_diff_title(),
+				true,
+				diff,
+			);
+		} else {
+			self.diff.clear(true);
+		}
+	}
+
+	fn draw_verbose_diff(&self, f: &mut Frame) -> Result<()> {
+		if !self.verbose {
+			return Ok(());
+		}
+
+		let input = self.input.get_area();
+		let screen = f.area();
+
+		let rect = {
+			let mut rect = input;
+			rect.y = input.bottom();
+			rect.height = (screen.height / 3)
+				.min(screen.bottom().saturating_sub(input.bottom()));
+			rect
+		};
+
+		if rect.height >= VERBOSE_DIFF_MIN_HEIGHT {
+			f.render_widget(Clear, rect);
+			self.diff.draw(f, rect)?;
+		}
+
+		Ok(())
+	}
+
 	fn draw_branch_name(&self, f: &mut Frame) {
 		if let Some(name) = self.git_branch_name.last() {
 			let w = Paragraph::new(format!("{{{name}}}"))
@@ -113,16 +189,87 @@ impl CommitPopup {
 		}
 	}
 
+	fn update_behind_upstream(&mut self) {
+		self.behind_upstream = if matches!(self.mode, Mode::Normal)
+			&& self.options.borrow().commit_behind_notice()
+		{
+			self.lookup_behind_upstream()
+		} else {
+			None
+		};
+	}
+
+	/// only uses what was fetched before, never touches the network
+	fn lookup_behind_upstream(&mut self) -> Option<BehindUpstream> {
+		let branch = self.git_branch_name.lookup().ok()?;
+		let behind = sync::branch_compare_upstream(
+			&self.repo.borrow(),
+			&branch,
+		)
+		.ok()?
+		.behind;
+
+		(behind > 0).then(|| BehindUpstream {
+			branch,
+			behind,
+			fetched: sync::last_fetch_time(&self.repo.borrow())
+				.ok()
+				.flatten(),
+		})
+	}
+
+	fn pull_first(&mut self, rebase: bool) {
+		if let Some(behind) = self.behind_upstream.take() {
+			self.queue.push(if rebase {
+				InternalEvent::PullRebase(behind.branch)
+			} else {
+				InternalEvent::Pull(behind.branch)
+			});
+			self.hide();
+		}
+	}
+
+	fn draw_behind_upstream(&self, f: &mut Frame) {
+		if let Some(behind) = &self.behind_upstream {
+			let fetched_ago = behind.fetched.map(|fetched| {
+				fetch_age(
+					SystemTime::now()
+						.duration_since(fetched)
+						.unwrap_or_default(),
+				)
+			});
+
+			let w = Paragraph::new(strings::commit_behind_upstream(
+				&self.key_config,
+				behind.behind,
+				fetched_ago.as_deref(),
+			))
+			.style(self.theme.text_danger());
+
+			let rect = {
+				let mut rect = self.input.get_area();
+				rect.y += rect.height.saturating_sub(1);
+				rect.height = 1;
+				rect.x += 1;
+				rect.width = rect.width.saturating_sub(2);
+				rect
+			};
+
+			f.render_widget(w, rect);
+		}
+	}
+
 	fn draw_warnings(&self, f: &mut Frame) {
 		let first_line = self
 			.input
 			.get_text()
 			.lines()
 			.next()
-			.map(str::len)
+			.map(|line| line.chars().count())
 			.unwrap_or_default();
+		let limit = self.line_limits().subject;
 
-		if first_line > FIRST_LINE_LIMIT {
+		if limit > 0 && first_line > limit {
 			let msg = strings::commit_first_line_warning(first_line);
 			let msg_length: u16 = msg.len().cast();
 			let w =
@@ -227,56 +374,117 @@ impl CommitPopup {
 		Ok(())
 	}
 
+	/// continues the commit that waited for the pre-commit hook
+	pub fn pre_commit_hook_passed(&mut self) -> Result<()> {
+		if self.is_visible() {
+			self.pre_commit_passed = true;
+			self.commit()?;
+		}
+
+		Ok(())
+	}
+
+	/// commits the msg the commit-msg hook passed
+	pub fn commit_msg_hook_passed(&mut self) -> Result<()> {
+		if self.is_visible() {
+			self.commit_msg_passed =
+				Some(sync::hook_commit_msg(&self.repo.borrow())?);
+			self.commit()?;
+		}
+
+		Ok(())
+	}
+
+	/// takes over the msg the prepare-commit-msg hook wrote
+	pub fn prepare_commit_msg_hook_passed(&mut self) -> Result<()> {
+		if self.is_visible() {
+			self.input.set_text(sync::hook_commit_msg(
+				&self.repo.borrow(),
+			)?);
+		}
+
+		Ok(())
+	}
+
 	fn commit_with_msg(
 		&mut self,
 		msg: String,
 	) -> Result<CommitResult> {
-		// on exit verify should always be on
-		let verify = self.verify;
-		self.verify = true;
-
-		if verify {
-			// run pre commit hook - can reject commit
-			if let HookResult::NotOk(e) =
-				sync::hooks_pre_commit(&self.repo.borrow())?
+		let msg = if let Some(msg) = self.commit_msg_passed.take() {
+			msg
+		} else {
+			// on exit verify should always be on
+			let verify = self.verify;
+			self.verify = true;
+
+			if verify && !std::mem::take(&mut self.pre_commit_passed)
 			{
-				log::error!("pre-commit hook error: {}", e);
-				self.queue.push(InternalEvent::ShowErrorMsg(
-					format!("pre-commit hook error:\n{e}"),
-				));
-				return Ok(CommitResult::Aborted);
+				// run pre commit hook showing its output - can reject
+				// commit, otherwise the commit continues once it passed
+				if let Some(child) =
+					sync::spawn_pre_commit_hook(&self.repo.borrow())?
+				{
+					self.run_hook(
+						strings::title_pre_commit_hook(),
+						child,
+						Some(InternalEvent::PreCommitHookPassed),
+					);
+					return Ok(CommitResult::Aborted);
+				}
 			}
-		}
 
-		let mut msg =
-			commit_message_prettify(&self.repo.borrow(), msg)?;
+			let msg =
+				commit_message_prettify(&self.repo.borrow(), msg)?;
 
-		if verify {
-			// run commit message check hook - can reject commit
-			if let HookResult::NotOk(e) =
-				sync::hooks_commit_msg(&self.repo.borrow(), &mut msg)?
-			{
-				log::error!("commit-msg hook error: {}", e);
-				self.queue.push(InternalEvent::ShowErrorMsg(
-					format!("commit-msg hook error:\n{e}"),
-				));
-				return Ok(CommitResult::Aborted);
+			if verify {
+				// run commit message check hook the same way - it may
+				// also alter the msg that gets committed once it passed
+				if let Some(child) = sync::spawn_commit_msg_hook(
+					&self.repo.borrow(),
+					&msg,
+				)? {
+					self.run_hook(
+						strings::title_commit_msg_hook(),
+						child,
+						Some(InternalEvent::CommitMsgHookPassed),
+					);
+					return Ok(CommitResult::Aborted);
+				}
 			}
-		}
+
+			msg
+		};
+
 		self.do_commit(&msg)?;
 
-		if let HookResult::NotOk(e) =
-			sync::hooks_post_commit(&self.repo.borrow())?
+		if let Some(child) =
+			sync::spawn_post_commit_hook(&self.repo.borrow())?
 		{
-			log::error!("post-commit hook error: {}", e);
-			self.queue.push(InternalEvent::ShowErrorMsg(format!(
-				"post-commit hook error:\n{e}"
-			)));
+			self.run_hook(
+				strings::title_post_commit_hook(),
+				child,
+				None,
+			);
 		}
 
 		Ok(CommitResult::CommitDone)
 	}
 
+	fn run_hook(
+		&self,
+		title: String,
+		child: Child,
+		on_success: Option<InternalEvent>,
+	) {
+		self.queue.push(InternalEvent::RunProcess(Box::new(
+			ProcessItem {
+				title,
+				child,
+				on_success,
+			},
+		)));
+	}
+
 	fn do_commit(&self, msg: &str) -> Result<()> {
 		match &self.mode {
 			Mode::Normal => sync::commit(&self.repo.borrow(), msg)?,
@@ -300,20 +508,64 @@ impl CommitPopup {
 		Ok(())
 	}
 
+	/// a template counts as entered once it was edited or once it
+	/// has a line that is no comment
 	fn can_commit(&self) -> bool {
-		!self.is_empty() && self.is_changed()
+		self.has_message()
+			|| (self.is_changed()
+				&& !self.input.get_text().trim().is_empty())
 	}
 
 	fn can_amend(&self) -> bool {
 		matches!(self.mode, Mode::Normal)
 			&& sync::get_head(&self.repo.borrow()).is_ok()
-			&& (self.is_empty() || !self.is_changed())
+			&& !self.can_commit()
 	}
 
 	fn is_empty(&self) -> bool {
 		self.input.get_text().is_empty()
 	}
 
+	/// anything is left once comment lines are stripped
+	fn has_message(&self) -> bool {
+		self.input.get_text().lines().any(|line| {
+			!line.starts_with(self.comment_char)
+				&& !line.trim().is_empty()
+		})
+	}
+
+	fn can_use_template(&self) -> bool {
+		matches!(self.mode, Mode::Normal)
+			&& !self.templates.is_empty()
+	}
+
+	/// replaces the message with the next saved template, it counts
+	/// as entered once it was edited
+	fn next_template(&mut self) {
+		if self.templates.is_empty() {
+			return;
+		}
+
+		let (name, template) =
+			&self.templates[self.template_idx % self.templates.len()];
+
+		self.input.set_text(template.clone());
+		self.input.set_title(strings::commit_title_template(name));
+		self.commit_template = Some(template.clone());
+		self.template_idx += 1;
+	}
+
+	fn load_templates(&mut self) {
+		self.comment_char =
+			commit_comment_char(&self.repo.borrow()).unwrap_or('#');
+		self.templates = if matches!(self.mode, Mode::Normal) {
+			read_commit_templates()
+		} else {
+			Vec::new()
+		};
+		self.template_idx = 0;
+	}
+
 	fn is_changed(&self) -> bool {
 		Some(self.input.get_text().trim())
 			!= self.commit_template.as_ref().map(|s| s.trim())
@@ -347,6 +599,20 @@ impl CommitPopup {
 		self.verify = !self.verify;
 	}
 
+	fn line_limits(&self) -> LineLimits {
+		self.options.borrow().commit_line_limits(self.comment_char)
+	}
+
+	/// offers `msg` when the popup gets opened next for a normal
+	/// commit, unless a message was typed already
+	pub fn prefill_message(&mut self, msg: String) {
+		if !matches!(self.mode, Mode::Normal) || !self.has_message() {
+			self.mode = Mode::Normal;
+			self.input.set_text(msg);
+		}
+	}
+
+	#[allow(clippy::too_many_lines)]
 	pub fn open(&mut self, reword: Option<CommitId>) -> Result<()> {
 		//only clear text if it was not a normal commit dlg before, so to preserve old commit msg that was edited
 		if !matches!(self.mode, Mode::Normal) {
@@ -421,8 +687,15 @@ impl CommitPopup {
 							.ok()
 					});
 
+					let squash_msg =
+						sync::squash_msg(&self.repo.borrow())?;
+
 					let msg_source = if self.is_empty() {
-						if let Some(s) = &self.commit_template {
+						if let Some(msg) = squash_msg {
+							self.input.set_text(msg);
+							PrepareCommitMsgSource::Squash
+						} else if let Some(s) = &self.commit_template
+						{
 							self.input.set_text(s.clone());
 							PrepareCommitMsgSource::Template
 						} else {
@@ -439,20 +712,28 @@ impl CommitPopup {
 		};
 
 		self.mode = mode;
+		self.update_behind_upstream();
+
+		self.load_templates();
 
-		let mut msg = self.input.get_text().to_string();
-		if let HookResult::NotOk(e) = sync::hooks_prepare_commit_msg(
+		if let Some(child) = sync::spawn_prepare_commit_msg_hook(
 			&self.repo.borrow(),
 			msg_source,
-			&mut msg,
+			self.input.get_text(),
 		)? {
-			log::error!("prepare-commit-msg hook rejection: {e}",);
+			self.run_hook(
+				strings::title_prepare_commit_msg_hook(),
+				child,
+				Some(InternalEvent::PrepareCommitMsgHookPassed),
+			);
 		}
-		self.input.set_text(msg);
 
 		self.commit_msg_history_idx = 0;
+		self.input.set_line_limits(Some(self.line_limits()));
 		self.input.show()?;
 
+		self.verbose = self.options.borrow().is_commit_verbose();
+
 		Ok(())
 	}
 
@@ -484,7 +765,9 @@ impl DrawableComponent for CommitPopup {
 		if self.is_visible() {
 			self.input.draw(f, rect)?;
 			self.draw_branch_name(f);
+			self.draw_behind_upstream(f);
 			self.draw_warnings(f);
+			self.draw_verbose_diff(f)?;
 		}
 
 		Ok(())
@@ -535,6 +818,14 @@ impl Component for CommitPopup {
 				true,
 			));
 
+			out.push(CommandInfo::new(
+				strings::commands::commit_next_template(
+					&self.key_config,
+				),
+				true,
+				self.can_use_template(),
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::commit_next_msg_from_history(
 					&self.key_config,
@@ -543,16 +834,47 @@ impl Component for CommitPopup {
 				true,
 			));
 
+			out.push(CommandInfo::new(
+				strings::commands::commit_pull_first(
+					&self.key_config,
+				),
+				true,
+				self.behind_upstream.is_some(),
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::commit_pull_rebase_first(
+					&self.key_config,
+				),
+				true,
+				self.behind_upstream.is_some(),
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::newline(&self.key_config),
 				true,
 				true,
 			));
+
+			out.push(CommandInfo::new(
+				strings::commands::commit_diff_scroll(
+					&self.key_config,
+				),
+				true,
+				self.verbose,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::commit_reflow(&self.key_config),
+				self.line_limits().body > 0,
+				true,
+			));
 		}
 
 		visibility_blocking(self)
 	}
 
+	#[allow(clippy::too_many_lines)]
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.is_visible() {
 			if let Event::Key(e) = ev {
@@ -602,12 +924,55 @@ impl Component for CommitPopup {
 							self.commit_msg_history_idx += 1;
 						}
 						true
+					} else if key_match(
+						e,
+						self.key_config.keys.commit_template_next,
+					) && self.can_use_template()
+					{
+						self.next_template();
+						true
 					} else if key_match(
 						e,
 						self.key_config.keys.toggle_signoff,
 					) {
 						self.signoff_commit();
 						true
+					} else if key_match(
+						e,
+						self.key_config.keys.commit_pull_first,
+					) && self.behind_upstream.is_some()
+					{
+						self.pull_first(false);
+						true
+					} else if key_match(
+						e,
+						self.key_config.keys.commit_pull_rebase_first,
+					) && self.behind_upstream.is_some()
+					{
+						self.pull_first(true);
+						true
+					} else if key_match(
+						e,
+						self.key_config.keys.commit_diff_scroll_up,
+					) && self.verbose
+					{
+						self.diff
+							.move_selection(ScrollType::HalfPageUp);
+						true
+					} else if key_match(
+						e,
+						self.key_config.keys.commit_diff_scroll_down,
+					) && self.verbose
+					{
+						self.diff
+							.move_selection(ScrollType::HalfPageDown);
+						true
+					} else if key_match(
+						e,
+						self.key_config.keys.commit_reflow,
+					) {
+						self.input.reflow();
+						true
 					} else {
 						false
 					};
@@ -637,3 +1002,189 @@ impl Component for CommitPopup {
 		Ok(())
 	}
 }
+
+/// the files in the `commit_templates` folder of the config dir
+/// (name and content), sorted by name
+fn read_commit_templates() -> Vec<(String, String)> {
+	get_app_config_path().map_or_else(
+		|_| Vec::new(),
+		|path| {
+			read_commit_templates_in(&path.join("commit_templates"))
+		},
+	)
+}
+
+fn read_commit_templates_in(dir: &Path) -> Vec<(String, String)> {
+	let Some(entries) = read_dir(dir).ok() else {
+		return Vec::new();
+	};
+
+	let mut templates: Vec<(String, String)> = entries
+		.filter_map(std::result::Result::ok)
+		.filter(|entry| entry.path().is_file())
+		.filter_map(|entry| {
+			let path = entry.path();
+			read_to_string(&path)
+				.map_err(|e| {
+					log::error!(
+						"read commit template failed: {e} (path: '{:?}')",
+						path
+					);
+				})
+				.ok()
+				.map(|content| {
+					(
+						entry
+							.file_name()
+							.to_string_lossy()
+							.into_owned(),
+						content,
+					)
+				})
+		})
+		.collect();
+	templates.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+
+	templates
+}
+
+/// coarse age like `5m`, `3h` or `2d`
+fn fetch_age(age: Duration) -> String {
+	const MINUTE: u64 = 60;
+	const HOUR: u64 = 60 * MINUTE;
+	const DAY: u64 = 24 * HOUR;
+
+	match age.as_secs() {
+		s if s < MINUTE => String::from("<1m"),
+		s if s < HOUR => format!("{}m", s / MINUTE),
+		s if s < DAY => format!("{}h", s / HOUR),
+		s => format!("{}d", s / DAY),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use asyncgit::sync::RepoPath;
+	use std::{fs, process::Command};
+	use tempfile::TempDir;
+
+	fn templates(names: &[&str]) -> Vec<(String, String)> {
+		names
+			.iter()
+			.map(|name| {
+				(
+					(*name).to_string(),
+					format!("{name}\n# describe the change"),
+				)
+			})
+			.collect()
+	}
+
+	#[test]
+	fn test_comment_char() {
+		let td = TempDir::new().unwrap();
+		for args in [
+			&["init", "-q"][..],
+			&["config", "core.commentChar", ";"],
+		] {
+			assert!(Command::new("git")
+				.args(args)
+				.current_dir(td.path())
+				.status()
+				.unwrap()
+				.success());
+		}
+
+		let env = Environment::test_env();
+		*env.repo.borrow_mut() = RepoPath::Path(td.path().into());
+		let mut popup = CommitPopup::new(&env);
+		popup.load_templates();
+		assert_eq!(popup.comment_char, ';');
+
+		popup.input.set_text("; only a comment\n\n".into());
+		assert!(!popup.has_message());
+
+		popup.input.set_text("; comment\n# no comment".into());
+		assert!(popup.has_message());
+		assert!(popup.can_commit());
+	}
+
+	#[test]
+	fn test_template_not_entered_until_edited() {
+		let env = Environment::test_env();
+		let mut popup = CommitPopup::new(&env);
+		popup.templates =
+			vec![("empty".into(), "\n# describe the change".into())];
+
+		assert!(!popup.can_commit());
+
+		popup.next_template();
+		assert_eq!(popup.input.get_text(), "\n# describe the change");
+		assert!(!popup.can_commit());
+
+		popup.input.set_text("\n# describe the change\n".into());
+		assert!(!popup.can_commit());
+
+		popup.input.set_text("fix\n# describe the change".into());
+		assert!(popup.can_commit());
+
+		// a line that is no comment counts right away
+		popup.templates = templates(&["feat: "]);
+		popup.template_idx = 0;
+		popup.next_template();
+		assert!(popup.can_commit());
+	}
+
+	#[test]
+	fn test_cycle_templates() {
+		let env = Environment::test_env();
+		let mut popup = CommitPopup::new(&env);
+
+		popup.next_template();
+		assert_eq!(popup.input.get_text(), "");
+
+		popup.templates = templates(&["a", "b"]);
+		for name in ["a", "b", "a"] {
+			popup.next_template();
+			assert_eq!(
+				popup.input.get_text(),
+				format!("{name}\n# describe the change")
+			);
+			assert!(!popup.is_changed());
+		}
+	}
+
+	#[test]
+	fn test_read_commit_templates() {
+		let td = TempDir::new().unwrap();
+		let dir = td.path().join("commit_templates");
+
+		assert!(read_commit_templates_in(&dir).is_empty());
+
+		fs::create_dir(&dir).unwrap();
+		assert!(read_commit_templates_in(&dir).is_empty());
+
+		fs::write(dir.join("fix"), "fix: ").unwrap();
+		fs::write(dir.join("feat"), "feat: ").unwrap();
+		fs::create_dir(dir.join("folder")).unwrap();
+		assert_eq!(
+			read_commit_templates_in(&dir),
+			vec![
+				("feat".to_string(), "feat: ".to_string()),
+				("fix".to_string(), "fix: ".to_string())
+			]
+		);
+	}
+
+	#[test]
+	fn test_fetch_age() {
+		assert_eq!(fetch_age(Duration::from_secs(59)), "<1m");
+		assert_eq!(fetch_age(Duration::from_secs(60 * 5)), "5m");
+		assert_eq!(fetch_age(Duration::from_secs(60 * 60 * 3)), "3h");
+		assert_eq!(
+			fetch_age(Duration::from_secs(60 * 60 * 24 * 2 + 1)),
+			"2d"
+		);
+	}
+}
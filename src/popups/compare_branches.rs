@@ -0,0 +1,314 @@
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo,
+		CommitList, Component, DrawableComponent, EventState,
+	},
+	keys::{key_match, SharedKeyConfig},
+	popups::InspectCommitOpen,
+	queue::{InternalEvent, Queue, StackablePopupOpen},
+	strings,
+};
+use anyhow::Result;
+use asyncgit::{
+	asyncjob::AsyncSingleJob,
+	sync::{BranchComparison, CommitId, RepoPathRef},
+	AsyncBranchCompareJob, AsyncGitNotification,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Direction, Layout, Rect},
+	widgets::Clear,
+	Frame,
+};
+
+///
+#[derive(Clone, Debug)]
+pub struct CompareBranchesOpen {
+	pub branch_ref: String,
+	/// shown in the titles
+	pub name: String,
+}
+
+/// commits only `HEAD` has next to the ones only another branch has
+pub struct CompareBranchesPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	key_config: SharedKeyConfig,
+	ahead: CommitList,
+	behind: CommitList,
+	behind_focused: bool,
+	async_compare: AsyncSingleJob<AsyncBranchCompareJob>,
+	open_request: Option<CompareBranchesOpen>,
+	comparison: Option<BranchComparison>,
+	visible: bool,
+}
+
+impl CompareBranchesPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			key_config: env.key_config.clone(),
+			ahead: CommitList::new(env, ""),
+			behind: CommitList::new(env, ""),
+			behind_focused: false,
+			async_compare: AsyncSingleJob::new(
+				env.sender_git.clone(),
+			),
+			open_request: None,
+			comparison: None,
+			visible: false,
+		}
+	}
+
+	/// walks both sides again, the selections stay if the commits
+	/// did not change
+	pub fn open(&mut self, open: CompareBranchesOpen) -> Result<()> {
+		if self
+			.open_request
+			.as_ref()
+			.map_or(true, |last| last.branch_ref != open.branch_ref)
+		{
+			self.comparison = None;
+			self.ahead.clear();
+			self.behind.clear();
+			self.focus_behind(false);
+		}
+
+		self.ahead.set_title(&strings::title_branch_ahead(
+			&open.name, true,
+		));
+		self.behind
+			.set_title(&strings::title_branch_behind(&open.name));
+
+		self.async_compare.spawn(AsyncBranchCompareJob::new(
+			self.repo.borrow().clone(),
+			open.branch_ref.clone(),
+		));
+		self.open_request = Some(open);
+
+		self.show()
+	}
+
+	///
+	pub fn any_work_pending(&self) -> bool {
+		self.async_compare.is_pending()
+	}
+
+	///
+	pub fn update_git(&mut self, ev: AsyncGitNotification) {
+		if self.is_visible()
+			&& ev == AsyncGitNotification::BranchCompare
+		{
+			self.update();
+		}
+	}
+
+	fn update(&mut self) {
+		let Some(result) = self
+			.async_compare
+			.take_last()
+			.and_then(|job| job.result())
+		else {
+			return;
+		};
+		let Some(open) = &self.open_request else {
+			return;
+		};
+
+		match result {
+			Ok(comparison)
+				if comparison.branch_ref == open.branch_ref =>
+			{
+				self.ahead.set_title(&strings::title_branch_ahead(
+					&open.name,
+					comparison.merge_base.is_some(),
+				));
+				self.ahead.set_commits(
+					comparison.ahead.iter().copied().collect(),
+				);
+				self.behind.set_commits(
+					comparison.behind.iter().copied().collect(),
+				);
+				self.comparison = Some(comparison);
+			}
+			Ok(_) => (),
+			Err(e) => {
+				self.hide();
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("compare branches error:\n{e}"),
+				));
+			}
+		}
+	}
+
+	fn focus_behind(&mut self, behind: bool) {
+		self.behind_focused = behind;
+		self.ahead.focus(!behind);
+		self.behind.focus(behind);
+	}
+
+	const fn focused_list(&self) -> &CommitList {
+		if self.behind_focused {
+			&self.behind
+		} else {
+			&self.ahead
+		}
+	}
+
+	fn selected_commit(&self) -> Option<CommitId> {
+		self.focused_list().selected_entry().map(|entry| entry.id)
+	}
+
+	/// `branch...HEAD`, nothing to diff against without a merge base
+	fn diff_ids(&self) -> Option<(CommitId, CommitId)> {
+		let comparison = self.comparison.as_ref()?;
+
+		Some((comparison.merge_base?, comparison.head))
+	}
+
+	fn hide_stacked(&mut self, stack: bool) {
+		self.hide();
+
+		if stack {
+			if let Some(open) = self.open_request.clone() {
+				self.queue.push(InternalEvent::PopupStackPush(
+					StackablePopupOpen::CompareBranches(open),
+				));
+			}
+		} else {
+			self.queue.push(InternalEvent::PopupStackPop);
+		}
+	}
+}
+
+impl DrawableComponent for CompareBranchesPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.is_visible() {
+			let chunks = Layout::default()
+				.direction(Direction::Horizontal)
+				.constraints([
+					Constraint::Percentage(50),
+					Constraint::Percentage(50),
+				])
+				.split(rect);
+
+			f.render_widget(Clear, rect);
+
+			self.ahead.draw(f, chunks[0])?;
+			self.behind.draw(f, chunks[1])?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for CompareBranchesPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			self.focused_list().commands(out, force_all);
+
+			out.push(
+				CommandInfo::new(
+					strings::commands::close_popup(&self.key_config),
+					true,
+					true,
+				)
+				.order(1),
+			);
+
+			out.push(CommandInfo::new(
+				strings::commands::compare_branches_inspect(
+					&self.key_config,
+				),
+				self.selected_commit().is_some(),
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::compare_branches_side(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::compare_branches_diff(
+					&self.key_config,
+				),
+				self.diff_ids().is_some(),
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if !self.is_visible() {
+			return Ok(EventState::NotConsumed);
+		}
+
+		if let Event::Key(e) = ev {
+			if key_match(e, self.key_config.keys.exit_popup) {
+				self.hide_stacked(false);
+			} else if key_match(e, self.key_config.keys.move_left) {
+				self.focus_behind(false);
+			} else if key_match(e, self.key_config.keys.move_right) {
+				self.focus_behind(true);
+			} else if key_match(e, self.key_config.keys.enter) {
+				if let Some(id) = self.selected_commit() {
+					self.hide_stacked(true);
+					self.queue.push(InternalEvent::OpenPopup(
+						StackablePopupOpen::InspectCommit(
+							InspectCommitOpen::new(id),
+						),
+					));
+				}
+			} else if key_match(
+				e,
+				self.key_config.keys.compare_commits,
+			) {
+				if let Some((merge_base, head)) = self.diff_ids() {
+					self.hide_stacked(true);
+					self.queue.push(InternalEvent::OpenPopup(
+						StackablePopupOpen::CompareCommits(
+							InspectCommitOpen {
+								commit_id: head,
+								compare_id: Some(merge_base),
+								tags: None,
+							},
+						),
+					));
+				}
+			} else if self.behind_focused {
+				self.behind.event(ev)?;
+			} else {
+				self.ahead.event(ev)?;
+			}
+		}
+
+		Ok(EventState::Consumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
@@ -14,9 +14,10 @@ use crate::{
 };
 use anyhow::Result;
 use asyncgit::{
+	asyncjob::AsyncSingleJob,
 	sync::{self, commit_files::OldNew, CommitId, RepoPathRef},
-	AsyncDiff, AsyncGitNotification, CommitFilesParams, DiffParams,
-	DiffType,
+	AsyncDiff, AsyncGitNotification, AsyncPatchHashesJob,
+	CommitFilesParams, DiffParams, DiffType,
 };
 use crossterm::event::Event;
 use ratatui::{
@@ -24,10 +25,25 @@ use ratatui::{
 	widgets::Clear,
 	Frame,
 };
+use std::collections::{BTreeMap, HashMap};
+
+/// files marked as viewed while reviewing the compared range
+struct Review {
+	/// key the marks are persisted under
+	range: String,
+	/// current hash of every file's patch
+	patches: HashMap<String, u64>,
+	/// viewed files with their patch hash when they were marked
+	marks: BTreeMap<String, u64>,
+}
 
 pub struct CompareCommitsPopup {
 	repo: RepoPathRef,
 	open_request: Option<InspectCommitOpen>,
+	review: Option<Review>,
+	/// range waiting for its patch hashes to start the review
+	pending_review: Option<String>,
+	async_patches: AsyncSingleJob<AsyncPatchHashesJob>,
 	diff: DiffComponent,
 	details: CommitDetailsComponent,
 	git_diff: AsyncDiff,
@@ -100,6 +116,12 @@ impl Component for CompareCommitsPopup {
 				true,
 				self.diff.focused() || force_all,
 			));
+
+			out.push(CommandInfo::new(
+				strings::commands::toggle_viewed(&self.key_config),
+				self.can_focus_diff(),
+				self.review.is_some() || force_all,
+			));
 		}
 
 		visibility_blocking(self)
@@ -134,6 +156,11 @@ impl Component for CompareCommitsPopup {
 				} else if key_match(e, self.key_config.keys.move_left)
 				{
 					self.hide_stacked(false);
+				} else if key_match(
+					e,
+					self.key_config.keys.review_toggle_viewed,
+				) {
+					self.toggle_viewed()?;
 				}
 
 				return Ok(EventState::Consumed);
@@ -169,6 +196,11 @@ impl CompareCommitsPopup {
 			details: CommitDetailsComponent::new(env),
 			diff: DiffComponent::new(env, true),
 			open_request: None,
+			review: None,
+			pending_review: None,
+			async_patches: AsyncSingleJob::new(
+				env.sender_git.clone(),
+			),
 			git_diff: AsyncDiff::new(
 				env.repo.borrow().clone(),
 				&env.sender_git,
@@ -187,19 +219,114 @@ impl CompareCommitsPopup {
 		} else {
 			sync::get_head_tuple(&self.repo.borrow())?.id
 		};
+
+		// comparing against `HEAD` keeps the review across new commits
+		let range = format!(
+			"{}..{}",
+			open.compare_id.map_or_else(
+				|| "HEAD".to_string(),
+				|id| id.to_string()
+			),
+			open.commit_id
+		);
+
 		self.open_request = Some(InspectCommitOpen {
 			commit_id: open.commit_id,
 			compare_id: Some(compare_id),
 			tags: open.tags,
 		});
+		self.start_review(range);
 		self.show()?;
 
 		Ok(())
 	}
 
+	fn start_review(&mut self, range: String) {
+		self.review = None;
+		self.details.set_viewed(None);
+
+		if let Some(ids) = self.get_ids() {
+			self.pending_review = Some(range);
+			self.async_patches.spawn(AsyncPatchHashesJob::new(
+				self.repo.borrow().clone(),
+				ids,
+			));
+		}
+	}
+
+	fn finish_review(&mut self) -> Result<()> {
+		let Some(last) = self.async_patches.take_last() else {
+			return Ok(());
+		};
+		let Some(result) = last.result() else {
+			return Ok(());
+		};
+		if Some(result.ids) != self.get_ids() {
+			return Ok(());
+		}
+		let Some(range) = self.pending_review.take() else {
+			return Ok(());
+		};
+
+		let patches = result.result?;
+		let mut marks = self.options.borrow().review_marks(&range);
+		let marked = marks.len();
+
+		// files that changed since they were marked need another look
+		marks.retain(|path, hash| patches.get(path) == Some(hash));
+
+		if marks.len() != marked {
+			self.options
+				.borrow_mut()
+				.set_review_marks(&range, marks.clone());
+		}
+
+		self.details
+			.set_viewed(Some(marks.keys().cloned().collect()));
+		self.review = Some(Review {
+			range,
+			patches,
+			marks,
+		});
+
+		Ok(())
+	}
+
+	/// toggles the selected file, marking it moves on to the next
+	/// file not viewed yet
+	fn toggle_viewed(&mut self) -> Result<()> {
+		let (Some(review), Some(file)) = (
+			self.review.as_mut(),
+			self.details.files().selection_file(),
+		) else {
+			return Ok(());
+		};
+
+		let marked = review.marks.remove(&file.path).is_none();
+		if marked {
+			if let Some(hash) = review.patches.get(&file.path) {
+				review.marks.insert(file.path, *hash);
+			}
+		}
+
+		self.options
+			.borrow_mut()
+			.set_review_marks(&review.range, review.marks.clone());
+		self.details
+			.set_viewed(Some(review.marks.keys().cloned().collect()));
+
+		if marked {
+			self.details.select_next_unviewed();
+		}
+
+		self.update_diff()
+	}
+
 	///
 	pub fn any_work_pending(&self) -> bool {
-		self.git_diff.is_pending() || self.details.any_work_pending()
+		self.git_diff.is_pending()
+			|| self.async_patches.is_pending()
+			|| self.details.any_work_pending()
 	}
 
 	///
@@ -212,6 +339,8 @@ impl CompareCommitsPopup {
 				self.update()?;
 			} else if ev == AsyncGitNotification::Diff {
 				self.update_diff()?;
+			} else if ev == AsyncGitNotification::PatchHashes {
+				self.finish_review()?;
 			}
 		}
 
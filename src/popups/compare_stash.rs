@@ -0,0 +1,414 @@
+use crate::{
+	accessors,
+	app::Environment,
+	components::{
+		command_pump, event_pump, visibility_blocking,
+		CommandBlocking, CommandInfo, Component, DiffComponent,
+		DrawableComponent, EventState, StatusTreeComponent,
+	},
+	keys::{key_match, SharedKeyConfig},
+	options::SharedOptions,
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings,
+};
+use anyhow::Result;
+use asyncgit::{
+	sync::{
+		self,
+		commit_files::{OldNew, TreeSource},
+		CommitId, RepoPath, RepoPathRef,
+	},
+	AsyncDiff, AsyncGitNotification, DiffParams, DiffType,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Direction, Layout, Rect},
+	widgets::Clear,
+	Frame,
+};
+
+/// what a stash can be compared with
+struct Base {
+	name: String,
+	source: TreeSource,
+}
+
+/// files of a stash that differ from `HEAD`, the working tree or
+/// another stash and the diff of the selected one
+pub struct CompareStashPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	options: SharedOptions,
+	key_config: SharedKeyConfig,
+	files: StatusTreeComponent,
+	diff: DiffComponent,
+	git_diff: AsyncDiff,
+	/// name and commit of the compared stash
+	stash: Option<(String, CommitId)>,
+	bases: Vec<Base>,
+	base_idx: usize,
+	visible: bool,
+}
+
+impl CompareStashPopup {
+	accessors!(self, [files, diff]);
+
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			options: env.options.clone(),
+			key_config: env.key_config.clone(),
+			files: StatusTreeComponent::new(env, "", true),
+			diff: DiffComponent::new(env, true),
+			git_diff: AsyncDiff::new(
+				env.repo.borrow().clone(),
+				&env.sender_git,
+			),
+			stash: None,
+			bases: Vec::new(),
+			base_idx: 0,
+			visible: false,
+		}
+	}
+
+	/// compares `stash` with `base` or `HEAD` if there is no base
+	pub fn open(
+		&mut self,
+		stash: CommitId,
+		base: Option<CommitId>,
+	) -> Result<()> {
+		let repo = self.repo.borrow().clone();
+		let stashes = sync::get_stashes(&repo)?;
+		let stash_name = |idx: usize| format!("stash@{{{idx}}}");
+
+		self.bases.clear();
+		if let Ok(head) = sync::get_head(&repo) {
+			self.bases.push(Base {
+				name: String::from("HEAD"),
+				source: TreeSource::Commit(head),
+			});
+		}
+		self.bases.push(Base {
+			name: String::from("working tree"),
+			source: TreeSource::WorkDir,
+		});
+		self.bases.extend(
+			stashes
+				.iter()
+				.enumerate()
+				.filter(|(_, id)| **id != stash)
+				.map(|(idx, id)| Base {
+					name: stash_name(idx),
+					source: TreeSource::Commit(*id),
+				}),
+		);
+
+		self.base_idx = base
+			.and_then(|base| {
+				self.bases.iter().position(|b| {
+					b.source == TreeSource::Commit(base)
+				})
+			})
+			.unwrap_or_default();
+
+		let name = stashes
+			.iter()
+			.position(|id| *id == stash)
+			.map_or_else(|| stash.get_short_string(), stash_name);
+		self.stash = Some((name, stash));
+
+		self.files.set_commit(Some(stash));
+		self.show()?;
+
+		self.update_files()
+	}
+
+	/// the working tree changed
+	pub fn update(&mut self) -> Result<()> {
+		if self.is_visible()
+			&& self.base().map(|base| base.source)
+				== Some(TreeSource::WorkDir)
+		{
+			self.git_diff.refresh()?;
+			self.update_files()?;
+		}
+
+		Ok(())
+	}
+
+	///
+	pub fn any_work_pending(&self) -> bool {
+		self.git_diff.is_pending()
+	}
+
+	///
+	pub fn update_git(
+		&mut self,
+		ev: AsyncGitNotification,
+	) -> Result<()> {
+		if self.is_visible() && ev == AsyncGitNotification::Diff {
+			self.update_diff()?;
+		}
+
+		Ok(())
+	}
+
+	fn base(&self) -> Option<&Base> {
+		self.bases.get(self.base_idx)
+	}
+
+	fn trees(&self) -> Option<OldNew<TreeSource>> {
+		Some(OldNew {
+			old: self.base()?.source,
+			new: TreeSource::Commit(self.stash.as_ref()?.1),
+		})
+	}
+
+	fn next_base(&mut self) -> Result<()> {
+		if self.bases.len() > 1 {
+			self.base_idx = (self.base_idx + 1) % self.bases.len();
+			self.update_files()?;
+		}
+
+		Ok(())
+	}
+
+	fn update_files(&mut self) -> Result<()> {
+		let (Some(trees), Some(base), Some((stash, _))) =
+			(self.trees(), self.base(), self.stash.as_ref())
+		else {
+			return Ok(());
+		};
+
+		self.files.set_title(strings::title_compare_stash(
+			stash, &base.name,
+		));
+
+		match sync::get_trees_files(&self.repo.borrow(), trees) {
+			Ok(files) => self.files.update(&files)?,
+			Err(e) => {
+				self.files.clear()?;
+				self.queue
+					.push(InternalEvent::ShowErrorMsg(e.to_string()));
+			}
+		}
+
+		self.update_diff()
+	}
+
+	/// called when the file selection changed
+	fn update_diff(&mut self) -> Result<()> {
+		if let (Some(trees), Some(f)) =
+			(self.trees(), self.files.selection_file())
+		{
+			let diff_params = DiffParams {
+				path: f.path.clone(),
+				diff_type: DiffType::TreeToTree(trees),
+				options: self.options.borrow().diff_options(),
+			};
+
+			if let Some((params, last)) = self.git_diff.last()? {
+				if params == diff_params {
+					self.diff.update(f.path, false, last);
+					return Ok(());
+				}
+			}
+
+			self.git_diff.request(diff_params)?;
+			self.diff.clear(true);
+		} else {
+			self.diff.clear(false);
+		}
+
+		Ok(())
+	}
+
+	fn can_focus_diff(&self) -> bool {
+		self.files.selection_file().is_some()
+	}
+
+	fn apply_file(&self) {
+		if let (Some((_, stash)), Some(file)) =
+			(&self.stash, self.files.selection_file())
+		{
+			apply_stash_file(
+				&self.repo.borrow(),
+				&self.queue,
+				*stash,
+				&file.path,
+			);
+		}
+	}
+}
+
+/// restores the stashed version of `path` and reports how it went
+pub fn apply_stash_file(
+	repo: &RepoPath,
+	queue: &Queue,
+	stash: CommitId,
+	path: &str,
+) {
+	match sync::stash_apply_file(repo, stash, path) {
+		Ok(result) => {
+			queue.push(InternalEvent::ShowInfoMsg(
+				strings::stash_file_applied(path, result),
+			));
+			queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+		}
+		Err(e) => queue.push(InternalEvent::ShowErrorMsg(format!(
+			"apply stashed file error:\n{e}"
+		))),
+	}
+}
+
+impl DrawableComponent for CompareStashPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.is_visible() {
+			let percentages = if self.diff.focused() {
+				(0, 100)
+			} else {
+				(30, 70)
+			};
+
+			let chunks = Layout::default()
+				.direction(Direction::Horizontal)
+				.constraints([
+					Constraint::Percentage(percentages.0),
+					Constraint::Percentage(percentages.1),
+				])
+				.split(rect);
+
+			f.render_widget(Clear, rect);
+
+			self.files.draw(f, chunks[0])?;
+			self.diff.draw(f, chunks[1])?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for CompareStashPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			command_pump(
+				out,
+				force_all,
+				self.components().as_slice(),
+			);
+
+			out.push(
+				CommandInfo::new(
+					strings::commands::close_popup(&self.key_config),
+					true,
+					true,
+				)
+				.order(1),
+			);
+
+			out.push(CommandInfo::new(
+				strings::commands::compare_base_next(
+					&self.key_config,
+				),
+				self.bases.len() > 1,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::stash_apply_file(&self.key_config),
+				self.can_focus_diff(),
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::diff_focus_right(&self.key_config),
+				self.can_focus_diff(),
+				!self.diff.focused() || force_all,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::diff_focus_left(&self.key_config),
+				true,
+				self.diff.focused() || force_all,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if event_pump(ev, self.components_mut().as_mut_slice())?
+				.is_consumed()
+			{
+				if !self.diff.focused() {
+					self.update_diff()?;
+				}
+				return Ok(EventState::Consumed);
+			}
+
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.exit_popup) {
+					if self.diff.focused() {
+						self.files.focus(true);
+						self.diff.focus(false);
+					} else {
+						self.hide();
+					}
+				} else if key_match(
+					e,
+					self.key_config.keys.move_right,
+				) && self.can_focus_diff()
+				{
+					self.files.focus(false);
+					self.diff.focus(true);
+				} else if key_match(e, self.key_config.keys.move_left)
+				{
+					if self.diff.focused() {
+						self.files.focus(true);
+						self.diff.focus(false);
+					} else {
+						self.hide();
+					}
+				} else if key_match(
+					e,
+					self.key_config.keys.compare_base_next,
+				) {
+					self.next_base()?;
+				} else if key_match(
+					e,
+					self.key_config.keys.stash_apply,
+				) {
+					self.apply_file();
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+		self.files.show()?;
+		self.files.focus(true);
+		self.diff.focus(false);
+
+		Ok(())
+	}
+}
@@ -5,7 +5,7 @@ use crate::{
 		DrawableComponent, EventState,
 	},
 	keys::{key_match, SharedKeyConfig},
-	queue::{Action, InternalEvent, Queue},
+	queue::{Action, InternalEvent, Queue, ResetFolderItem},
 	strings, ui,
 };
 use anyhow::Result;
@@ -125,6 +125,18 @@ impl ConfirmPopup {
 		self.hide();
 	}
 
+	fn reset_folder_text(item: &ResetFolderItem) -> (String, String) {
+		(
+			strings::confirm_title_reset(),
+			strings::confirm_msg_reset_folder(
+				&item.path,
+				item.modified.len(),
+				item.untracked.len(),
+			),
+		)
+	}
+
+	#[allow(clippy::too_many_lines)]
 	fn get_text(&self) -> (String, String) {
 		if let Some(ref a) = self.target {
 			return match a {
@@ -132,6 +144,7 @@ impl ConfirmPopup {
                     strings::confirm_title_reset(),
                     strings::confirm_msg_reset(),
                 ),
+                Action::ResetFolder(item) => Self::reset_folder_text(item),
                 Action::StashDrop(ids) => (
                     strings::confirm_title_stashdrop(
                         &self.key_config,ids.len()>1
@@ -142,11 +155,11 @@ impl ConfirmPopup {
                     strings::confirm_title_stashpop(&self.key_config),
                     strings::confirm_msg_stashpop(&self.key_config),
                 ),
-                Action::ResetHunk(_, _) => (
+                Action::ResetHunk(..) => (
                     strings::confirm_title_reset(),
                     strings::confirm_msg_resethunk(&self.key_config),
                 ),
-                Action::ResetLines(_, lines) => (
+                Action::ResetLines(_, lines, _) => (
                     strings::confirm_title_reset(),
                     strings::confirm_msg_reset_lines(lines.len()),
                 ),
@@ -185,13 +198,27 @@ impl ConfirmPopup {
                     strings::confirm_title_delete_tag_remote(),
                     strings::confirm_msg_delete_tag_remote(remote),
                 ),
-                Action::ForcePush(branch, _force) => (
+				Action::PushTag(tag_name,remote) => (
+                    strings::confirm_title_push_tag(),
+                    strings::confirm_msg_push_tag(tag_name,remote),
+                ),
+				Action::PushAllTags => (
+                    strings::confirm_title_push_all_tags(),
+                    strings::confirm_msg_push_all_tags(),
+                ),
+				Action::ForcePushTag(tag_name,remote) => (
+                    strings::confirm_title_force_push_tag(),
+                    strings::confirm_msg_force_push_tag(tag_name,remote),
+                ),
+                Action::ForcePush(branch, force) => (
                     strings::confirm_title_force_push(
                         &self.key_config,
+                        *force,
                     ),
                     strings::confirm_msg_force_push(
                         &self.key_config,
                         branch.rsplit('/').next().expect("There was no / in the head reference which is impossible in git"),
+                        *force,
                     ),
                 ),
                 Action::PullMerge{incoming,rebase} => (
@@ -210,9 +237,53 @@ impl ConfirmPopup {
                     strings::confirm_title_abortrevert(),
                     strings::confirm_msg_revertchanges(),
                 ),
-                Action::UndoCommit => (
+				Action::AbortCherryPick => (
+                    strings::confirm_title_abort_cherry_pick(),
+                    strings::confirm_msg_revertchanges(),
+                ),
+                Action::UndoCommit { pushed } => (
                     strings::confirm_title_undo_commit(),
-                    strings::confirm_msg_undo_commit(),
+                    strings::confirm_msg_undo_commit(*pushed),
+                ),
+                Action::ResetReflog { id, selector, kind } => (
+                    strings::confirm_title_reset_reflog(),
+                    strings::confirm_msg_reset_reflog(
+                        &id.get_short_string(),
+                        selector,
+                        *kind,
+                    ),
+                ),
+                Action::RewordPushedCommit(_) => (
+                    strings::confirm_title_reword_pushed(),
+                    strings::confirm_msg_reword_pushed(),
+                ),
+                Action::DeleteReplaceRef(original) => (
+                    strings::confirm_title_delete_replace_ref(),
+                    strings::confirm_msg_delete_replace_ref(
+                        &original.get_short_string(),
+                    ),
+                ),
+                Action::RevertMerge(_) => (
+                    strings::confirm_title_revert_merge(),
+                    strings::confirm_msg_revert_merge(),
+                ),
+                Action::Autosquash { pushed, .. } => (
+                    strings::confirm_title_autosquash(),
+                    strings::confirm_msg_autosquash(*pushed),
+                ),
+                Action::AmendCommit(plan) => (
+                    strings::confirm_title_amend_commit(plan.pushed),
+                    strings::confirm_msg_amend_commit(plan),
+                ),
+                Action::CommitOutsideScope { scope, count } => (
+                    strings::confirm_title_commit_outside_scope(),
+                    strings::confirm_msg_commit_outside_scope(
+                        scope, *count,
+                    ),
+                ),
+                Action::MarkResolved { path, blocks } => (
+                    strings::confirm_title_mark_resolved(),
+                    strings::confirm_msg_mark_resolved(path, *blocks),
                 ),
             };
 		}
@@ -0,0 +1,437 @@
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState, TextInputComponent,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{Action, InternalEvent, Queue},
+	strings, try_or_popup,
+	ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	self, BlockResolution, ConflictSides, ConflictText, RepoPathRef,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Direction, Layout, Rect},
+	text::{Line, Span},
+	widgets::{Block, Borders, Clear, Paragraph},
+	Frame,
+};
+use std::ops::Range;
+
+/// lines shown above a conflict block once it is selected
+const CONTEXT_LINES: usize = 3;
+
+/// both sides of the selected conflict block of a file next to each
+/// other and the merge result below
+pub struct ConflictEditorPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	key_config: SharedKeyConfig,
+	theme: SharedTheme,
+	path: String,
+	text: ConflictText,
+	/// index into the blocks of `text`
+	block: usize,
+	/// first line of the result on screen
+	scroll: usize,
+	/// the result while it is edited by hand
+	editor: TextInputComponent,
+	/// lines ended in `\r\n`, the editor only knows `\n`
+	crlf: bool,
+	/// branch names shown instead of the labels of the markers
+	sides: Option<ConflictSides>,
+	visible: bool,
+}
+
+impl ConflictEditorPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		let mut editor = TextInputComponent::new(env, "", "", false);
+		editor.embed();
+
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			key_config: env.key_config.clone(),
+			theme: env.theme.clone(),
+			path: String::new(),
+			text: ConflictText::default(),
+			block: 0,
+			scroll: 0,
+			editor,
+			crlf: false,
+			sides: None,
+			visible: false,
+		}
+	}
+
+	/// reads the conflicted file `path` from the working tree
+	pub fn open(&mut self, path: &str) -> Result<()> {
+		let repo = self.repo.borrow().clone();
+		self.text = sync::read_conflicted_file(&repo, path)?;
+		self.sides = sync::conflict_sides(&repo).ok();
+		self.path = path.to_string();
+		self.editor.hide();
+		self.select_block(0);
+
+		self.show()
+	}
+
+	fn select_block(&mut self, block: usize) {
+		let count = self.text.blocks().len();
+		self.block = block.min(count.saturating_sub(1));
+
+		if let Some(block) = self.text.blocks().get(self.block) {
+			self.scroll =
+				block.range.start.saturating_sub(CONTEXT_LINES);
+		}
+	}
+
+	fn move_block(&mut self, next: bool) {
+		let count = self.text.blocks().len();
+
+		if count > 0 {
+			self.select_block(if next {
+				(self.block + 1) % count
+			} else {
+				(self.block + count - 1) % count
+			});
+		}
+	}
+
+	fn resolve(&mut self, resolution: BlockResolution) {
+		if self.block < self.text.blocks().len() {
+			self.text.resolve(self.block, resolution);
+			// the next block moved up to the same index
+			self.select_block(self.block);
+		}
+	}
+
+	fn start_editing(&mut self) -> Result<()> {
+		let text = self.text.text();
+		self.crlf = text.contains("\r\n");
+
+		self.editor.show()?;
+		self.editor.set_text(text.replace("\r\n", "\n"));
+
+		Ok(())
+	}
+
+	fn stop_editing(&mut self) {
+		let text = self.editor.get_text();
+		let text = if self.crlf {
+			text.replace('\n', "\r\n")
+		} else {
+			text.to_string()
+		};
+
+		self.editor.hide();
+		self.text = ConflictText::parse(&text);
+		self.select_block(self.block);
+	}
+
+	fn save(&mut self) -> Result<()> {
+		sync::write_merged_file(
+			&self.repo.borrow(),
+			&self.path,
+			&self.text.text(),
+		)?;
+
+		self.hide();
+		self.queue.push(InternalEvent::ConfirmAction(
+			Action::MarkResolved {
+				path: self.path.clone(),
+				blocks: self.text.blocks().len(),
+			},
+		));
+
+		Ok(())
+	}
+
+	fn side_lines(&self, range: Range<usize>) -> Vec<Line<'_>> {
+		self.text.lines()[range]
+			.iter()
+			.map(|line| {
+				Line::from(Span::styled(
+					line.trim_end_matches(['\n', '\r']),
+					self.theme.text(true, false),
+				))
+			})
+			.collect()
+	}
+
+	fn draw_side(&self, f: &mut Frame, area: Rect, ours: bool) {
+		let block = self.text.blocks().get(self.block);
+
+		let (side, label, lines) = match (block, ours) {
+			(Some(block), true) => (
+				"Ours",
+				block.ours_label.as_str(),
+				self.side_lines(block.ours.clone()),
+			),
+			(Some(block), false) => (
+				"Theirs",
+				block.theirs_label.as_str(),
+				self.side_lines(block.theirs.clone()),
+			),
+			(None, true) => ("Ours", "", Vec::new()),
+			(None, false) => ("Theirs", "", Vec::new()),
+		};
+		let label = self.sides.as_ref().map_or(label, |sides| {
+			if ours {
+				sides.ours.as_str()
+			} else {
+				sides.theirs.as_str()
+			}
+		});
+
+		f.render_widget(
+			Paragraph::new(lines).block(
+				Block::default()
+					.borders(Borders::ALL)
+					.title(Span::styled(
+						strings::title_conflict_side(side, label),
+						self.theme.title(false),
+					))
+					.border_style(self.theme.block(false)),
+			),
+			area,
+		);
+	}
+
+	fn draw_result(&self, f: &mut Frame, area: Rect) -> Result<()> {
+		let editing = self.editor.is_visible();
+		let title = if editing {
+			strings::title_conflict_result_edit(&self.path)
+		} else {
+			strings::title_conflict_result(
+				&self.path,
+				self.block,
+				self.text.blocks().len(),
+			)
+		};
+
+		let block = Block::default()
+			.borders(Borders::ALL)
+			.title(Span::styled(title, self.theme.title(true)))
+			.border_style(self.theme.block(true));
+
+		if editing {
+			let inner = block.inner(area);
+			f.render_widget(block, area);
+			return self.editor.draw(f, inner);
+		}
+
+		let selected = self
+			.text
+			.blocks()
+			.get(self.block)
+			.map(|block| block.range.clone())
+			.unwrap_or_default();
+
+		let scroll = self
+			.scroll
+			.min(self.text.lines().len().saturating_sub(1));
+		let lines: Vec<Line> = self
+			.text
+			.lines()
+			.iter()
+			.enumerate()
+			.skip(scroll)
+			.take(usize::from(area.height))
+			.map(|(idx, line)| {
+				Line::from(Span::styled(
+					line.trim_end_matches(['\n', '\r']),
+					self.theme.text(true, selected.contains(&idx)),
+				))
+			})
+			.collect();
+
+		f.render_widget(Paragraph::new(lines).block(block), area);
+
+		Ok(())
+	}
+}
+
+impl DrawableComponent for ConflictEditorPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.is_visible() {
+			let chunks = Layout::default()
+				.direction(Direction::Vertical)
+				.constraints([
+					Constraint::Percentage(40),
+					Constraint::Percentage(60),
+				])
+				.split(rect);
+			let sides = Layout::default()
+				.direction(Direction::Horizontal)
+				.constraints([
+					Constraint::Percentage(50),
+					Constraint::Percentage(50),
+				])
+				.split(chunks[0]);
+
+			f.render_widget(Clear, rect);
+
+			self.draw_side(f, sides[0], true);
+			self.draw_side(f, sides[1], false);
+			self.draw_result(f, chunks[1])?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for ConflictEditorPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			let editing = self.editor.is_visible();
+			let has_block =
+				!editing && !self.text.blocks().is_empty();
+
+			if !editing {
+				out.push(
+					CommandInfo::new(
+						strings::commands::close_popup(
+							&self.key_config,
+						),
+						true,
+						true,
+					)
+					.order(1),
+				);
+			}
+
+			out.push(CommandInfo::new(
+				strings::commands::conflict_block_nav(
+					&self.key_config,
+				),
+				has_block,
+				!editing || force_all,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::conflict_take_ours(
+					&self.key_config,
+				),
+				has_block,
+				!editing || force_all,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::conflict_take_theirs(
+					&self.key_config,
+				),
+				has_block,
+				!editing || force_all,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::conflict_take_both(
+					&self.key_config,
+				),
+				has_block,
+				!editing || force_all,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::conflict_take_both_reverse(
+					&self.key_config,
+				),
+				has_block,
+				!editing || force_all,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::conflict_edit(
+					&self.key_config,
+					editing,
+				),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::conflict_save(&self.key_config),
+				!editing,
+				!editing || force_all,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if !self.is_visible() {
+			return Ok(EventState::NotConsumed);
+		}
+
+		if self.editor.is_visible() {
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.exit_popup) {
+					self.stop_editing();
+					return Ok(EventState::Consumed);
+				}
+			}
+
+			self.editor.event(ev)?;
+			return Ok(EventState::Consumed);
+		}
+
+		if let Event::Key(e) = ev {
+			let keys = &self.key_config.keys;
+
+			if key_match(e, keys.exit_popup) {
+				self.hide();
+			} else if key_match(e, keys.diff_hunk_next) {
+				self.move_block(true);
+			} else if key_match(e, keys.diff_hunk_prev) {
+				self.move_block(false);
+			} else if key_match(e, keys.conflict_take_ours) {
+				self.resolve(BlockResolution::Ours);
+			} else if key_match(e, keys.conflict_take_theirs) {
+				self.resolve(BlockResolution::Theirs);
+			} else if key_match(e, keys.conflict_take_both) {
+				self.resolve(BlockResolution::OursFirst);
+			} else if key_match(e, keys.conflict_take_both_reverse) {
+				self.resolve(BlockResolution::TheirsFirst);
+			} else if key_match(e, keys.edit_file) {
+				self.start_editing()?;
+			} else if key_match(e, keys.conflict_save) {
+				try_or_popup!(
+					self,
+					"save merge result error:",
+					self.save()
+				);
+			} else if key_match(e, keys.move_down) {
+				self.scroll = (self.scroll + 1)
+					.min(self.text.lines().len().saturating_sub(1));
+			} else if key_match(e, keys.move_up) {
+				self.scroll = self.scroll.saturating_sub(1);
+			} else if key_match(e, keys.home) {
+				self.scroll = 0;
+			} else if key_match(e, keys.end) {
+				self.scroll =
+					self.text.lines().len().saturating_sub(1);
+			}
+		}
+
+		Ok(EventState::Consumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
@@ -5,12 +5,16 @@ use crate::components::{
 use crate::{
 	app::Environment,
 	keys::{key_match, SharedKeyConfig},
+	popups::{PushAfterCreate, PushTarget},
 	queue::{InternalEvent, NeedsUpdate, Queue},
-	strings,
+	strings, try_or_popup,
 	ui::style::SharedTheme,
 };
-use anyhow::Result;
-use asyncgit::sync::{self, RepoPathRef};
+use anyhow::{anyhow, Result};
+use asyncgit::{
+	sync::{self, CommitId, RepoPathRef},
+	PushType,
+};
 use crossterm::event::Event;
 use easy_cast::Cast;
 use ratatui::{layout::Rect, widgets::Paragraph, Frame};
@@ -21,6 +25,14 @@ pub struct CreateBranchPopup {
 	queue: Queue,
 	key_config: SharedKeyConfig,
 	theme: SharedTheme,
+	push_target: PushTarget,
+	/// branch off this commit instead of `HEAD`
+	commit: Option<CommitId>,
+	/// branch off the base of this stash and pop it there
+	stash: Option<CommitId>,
+	/// check the branch out once it is created, `HEAD` branches
+	/// always are
+	checkout: bool,
 }
 
 impl DrawableComponent for CreateBranchPopup {
@@ -28,6 +40,7 @@ impl DrawableComponent for CreateBranchPopup {
 		if self.is_visible() {
 			self.input.draw(f, rect)?;
 			self.draw_warnings(f);
+			self.push_target.draw(f, self.input.get_area());
 		}
 
 		Ok(())
@@ -50,6 +63,22 @@ impl Component for CreateBranchPopup {
 				true,
 				true,
 			));
+
+			out.push(CommandInfo::new(
+				strings::commands::toggle_push_after_create(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::toggle_checkout_after_create(
+					&self.key_config,
+				),
+				true,
+				self.commit.is_some() || force_all,
+			));
 		}
 
 		visibility_blocking(self)
@@ -57,6 +86,32 @@ impl Component for CreateBranchPopup {
 
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.is_visible() {
+			if let Event::Key(e) = ev {
+				if key_match(
+					e,
+					self.key_config.keys.toggle_push_after_create,
+				) {
+					try_or_popup!(
+						self,
+						"push target error:",
+						self.push_target.cycle(&self.repo.borrow())
+					);
+					return Ok(EventState::Consumed);
+				}
+
+				if self.commit.is_some()
+					&& key_match(
+						e,
+						self.key_config
+							.keys
+							.toggle_checkout_after_create,
+					) {
+					self.checkout = !self.checkout;
+					self.update_title();
+					return Ok(EventState::Consumed);
+				}
+			}
+
 			if self.input.event(ev)?.is_consumed() {
 				return Ok(EventState::Consumed);
 			}
@@ -102,37 +157,124 @@ impl CreateBranchPopup {
 			theme: env.theme.clone(),
 			key_config: env.key_config.clone(),
 			repo: env.repo.clone(),
+			push_target: PushTarget::default(),
+			commit: None,
+			stash: None,
+			checkout: true,
 		}
 	}
 
-	///
+	/// branches off `HEAD` and checks the branch out
 	pub fn open(&mut self) -> Result<()> {
+		self.open_at(None)
+	}
+
+	/// branches off `commit` (`HEAD` if `None`)
+	pub fn open_at(
+		&mut self,
+		commit: Option<CommitId>,
+	) -> Result<()> {
+		self.commit = commit;
+		self.stash = None;
+		self.checkout = commit.is_none();
+		self.update_title();
+		self.push_target.reset();
 		self.show()?;
 
 		Ok(())
 	}
 
+	/// like `git stash branch`, the branch is always checked out
+	pub fn open_stash(&mut self, stash: CommitId) -> Result<()> {
+		self.commit = None;
+		self.stash = Some(stash);
+		self.checkout = true;
+		self.update_title();
+		self.push_target.reset();
+		self.show()?;
+
+		Ok(())
+	}
+
+	fn update_title(&mut self) {
+		if let Some(stash) = self.stash {
+			self.input.set_title(
+				strings::create_branch_from_stash_popup_title(
+					&stash.get_short_string(),
+				),
+			);
+			return;
+		}
+
+		self.input.set_title(self.commit.map_or_else(
+			|| strings::create_branch_popup_title(&self.key_config),
+			|commit| {
+				strings::create_branch_at_popup_title(
+					&commit.get_short_string(),
+					self.checkout,
+				)
+			},
+		));
+	}
+
+	fn create(&self, name: &str) -> Result<()> {
+		let repo = self.repo.borrow();
+
+		if let Some(stash) = self.stash {
+			sync::stash_branch(&repo, name, stash)?;
+		} else if let Some(commit) = self.commit {
+			sync::create_branch_at(&repo, name, commit)?;
+
+			if self.checkout {
+				sync::checkout_branch(&repo, name).map_err(|e| {
+					anyhow!(
+						"branch '{name}' created, but not checked out:\n{e}"
+					)
+				})?;
+			}
+		} else {
+			sync::create_branch(&repo, name)?;
+		}
+
+		Ok(())
+	}
+
 	///
 	pub fn create_branch(&mut self) {
-		let res = sync::create_branch(
-			&self.repo.borrow(),
-			self.input.get_text(),
-		);
+		let name = self.input.get_text().to_string();
+		let res = self.create(&name);
 
 		self.input.clear();
 		self.hide();
 
 		match res {
-			Ok(_) => {
+			Ok(()) => {
 				self.queue.push(InternalEvent::Update(
 					NeedsUpdate::ALL | NeedsUpdate::BRANCHES,
 				));
+				if self.stash.is_some() {
+					self.queue.push(InternalEvent::TabSwitchStatus);
+				}
+
+				if let Some(remote) = self.push_target.remote() {
+					self.queue.push(InternalEvent::PushAfterCreate(
+						PushAfterCreate {
+							name,
+							push_type: PushType::Branch,
+							remote: remote.to_string(),
+						},
+					));
+				}
 			}
 			Err(e) => {
 				log::error!("create branch: {}", e,);
 				self.queue.push(InternalEvent::ShowErrorMsg(
 					format!("create branch error:\n{e}",),
 				));
+				// a branch that was not checked out exists anyway
+				self.queue.push(InternalEvent::Update(
+					NeedsUpdate::ALL | NeedsUpdate::BRANCHES,
+				));
 			}
 		}
 	}
@@ -0,0 +1,203 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, InputType, TextInputComponent,
+};
+use crate::{
+	app::Environment,
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, ProcessItem, Queue},
+	strings, try_or_popup,
+};
+use anyhow::Result;
+use asyncgit::sync::{self, PullRequestTarget, RepoPathRef};
+use crossterm::event::Event;
+use ratatui::{layout::Rect, Frame};
+
+enum Mode {
+	Title,
+	Body { title: String },
+}
+
+/// title and then body of a pull request the forge's CLI creates
+pub struct CreatePullRequestPopup {
+	repo: RepoPathRef,
+	mode: Mode,
+	input: TextInputComponent,
+	target: Option<PullRequestTarget>,
+	/// prefilled once the title is confirmed
+	body: String,
+	queue: Queue,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for CreatePullRequestPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		self.input.draw(f, rect)?;
+
+		Ok(())
+	}
+}
+
+impl Component for CreatePullRequestPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			self.input.commands(out, force_all);
+
+			out.push(CommandInfo::new(
+				strings::commands::create_pull_request_confirm(
+					&self.key_config,
+					matches!(self.mode, Mode::Body { .. }),
+				),
+				self.is_valid(),
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if let Event::Key(e) = ev {
+				match self.mode {
+					Mode::Title
+						if key_match(
+							e,
+							self.key_config.keys.enter,
+						) && self.is_valid() =>
+					{
+						self.start_body_mode();
+						return Ok(EventState::Consumed);
+					}
+					Mode::Body { .. }
+						if key_match(
+							e,
+							self.key_config.keys.commit,
+						) =>
+					{
+						try_or_popup!(
+							self,
+							"pull request error:",
+							self.create()
+						);
+						return Ok(EventState::Consumed);
+					}
+					_ => (),
+				}
+			}
+
+			self.input.event(ev)?;
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.input.is_visible()
+	}
+
+	fn hide(&mut self) {
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.input.show()?;
+
+		Ok(())
+	}
+}
+
+impl CreatePullRequestPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			queue: env.queue.clone(),
+			input: TextInputComponent::new(
+				env,
+				"",
+				strings::PULL_REQUEST_TITLE_MSG,
+				true,
+			)
+			.with_input_type(InputType::Singleline),
+			target: None,
+			body: String::new(),
+			key_config: env.key_config.clone(),
+			repo: env.repo.clone(),
+			mode: Mode::Title,
+		}
+	}
+
+	/// `title` and `body` are prefilled from the commits
+	pub fn open(
+		&mut self,
+		target: PullRequestTarget,
+		title: String,
+		body: String,
+	) -> Result<()> {
+		self.mode = Mode::Title;
+		self.input.set_input_type(InputType::Singleline);
+		self.input.set_title(strings::pull_request_title_title(
+			&target.branch,
+			&target.base,
+		));
+		self.input
+			.set_default_msg(strings::PULL_REQUEST_TITLE_MSG.into());
+		self.input.set_text(title);
+
+		self.target = Some(target);
+		self.body = body;
+
+		self.show()
+	}
+
+	fn is_valid(&self) -> bool {
+		!self.input.get_text().trim().is_empty()
+	}
+
+	fn start_body_mode(&mut self) {
+		let title = self.input.get_text().trim().to_string();
+
+		self.input.set_input_type(InputType::Multiline);
+		self.input
+			.set_title(strings::pull_request_body_title(&title));
+		self.input
+			.set_default_msg(strings::PULL_REQUEST_BODY_MSG.into());
+		self.input.set_text(std::mem::take(&mut self.body));
+		self.mode = Mode::Body { title };
+	}
+
+	fn create(&mut self) -> Result<()> {
+		let (Mode::Body { title }, Some(target)) =
+			(&self.mode, &self.target)
+		else {
+			return Ok(());
+		};
+
+		let child = sync::spawn_create_pull_request(
+			&self.repo.borrow(),
+			target,
+			title,
+			self.input.get_text(),
+		)?;
+
+		self.queue.push(InternalEvent::RunProcess(Box::new(
+			ProcessItem {
+				title: strings::title_create_pull_request(
+					target.forge.forge.cli(),
+				),
+				child,
+				on_success: None,
+			},
+		)));
+
+		self.input.clear();
+		self.hide();
+
+		Ok(())
+	}
+}
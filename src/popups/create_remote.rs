@@ -1,5 +1,7 @@
 use anyhow::Result;
-use asyncgit::sync::{self, validate_remote_name, RepoPathRef};
+use asyncgit::sync::{
+	self, validate_remote_name, validate_remote_url, RepoPathRef,
+};
 use crossterm::event::Event;
 use easy_cast::Cast;
 use ratatui::{widgets::Paragraph, Frame};
@@ -33,6 +35,8 @@ pub struct CreateRemotePopup {
 	queue: Queue,
 	key_config: SharedKeyConfig,
 	state: State,
+	/// names taken already
+	remotes: Vec<String>,
 	theme: SharedTheme,
 }
 
@@ -122,52 +126,65 @@ impl CreateRemotePopup {
 				.with_input_type(InputType::Singleline),
 			key_config: env.key_config.clone(),
 			state: State::Name,
+			remotes: Vec::new(),
 			theme: env.theme.clone(),
 		}
 	}
 
 	pub fn open(&mut self) -> Result<()> {
 		self.state = State::Name;
+		self.remotes = sync::get_remotes(&self.repo.borrow())?;
 		self.input.clear();
 		self.show()?;
 
 		Ok(())
 	}
 
-	fn draw_warnings(&self, f: &mut Frame) {
-		let remote_name = match self.state {
-			State::Name => self.input.get_text(),
-			State::Url { .. } => return,
-		};
-
-		if !remote_name.is_empty() {
-			let valid = validate_remote_name(remote_name);
-
-			if !valid {
-				let msg = strings::remote_name_invalid();
-				let msg_length: u16 = msg.len().cast();
-				let w = Paragraph::new(msg)
-					.style(self.theme.text_danger());
-
-				let rect = {
-					let mut rect = self.input.get_area();
-					rect.y += rect.height.saturating_sub(1);
-					rect.height = 1;
-					let offset =
-						rect.width.saturating_sub(msg_length + 1);
-					rect.width =
-						rect.width.saturating_sub(offset + 1);
-					rect.x += offset;
-
-					rect
-				};
+	/// what is wrong with the input, `None` while it is empty
+	fn input_error(&self) -> Option<&'static str> {
+		let text = self.input.get_text();
+
+		if text.is_empty() {
+			None
+		} else if matches!(self.state, State::Url { .. }) {
+			(!validate_remote_url(text))
+				.then(strings::remote_url_invalid)
+		} else if !validate_remote_name(text) {
+			Some(strings::remote_name_invalid())
+		} else if self.remotes.iter().any(|remote| remote == text) {
+			Some(strings::remote_name_exists())
+		} else {
+			None
+		}
+	}
 
-				f.render_widget(w, rect);
-			}
+	fn draw_warnings(&self, f: &mut Frame) {
+		if let Some(msg) = self.input_error() {
+			let msg_length: u16 = msg.len().cast();
+			let w =
+				Paragraph::new(msg).style(self.theme.text_danger());
+
+			let rect = {
+				let mut rect = self.input.get_area();
+				rect.y += rect.height.saturating_sub(1);
+				rect.height = 1;
+				let offset =
+					rect.width.saturating_sub(msg_length + 1);
+				rect.width = rect.width.saturating_sub(offset + 1);
+				rect.x += offset;
+
+				rect
+			};
+
+			f.render_widget(w, rect);
 		}
 	}
 
 	fn handle_submit(&mut self) {
+		if self.input_error().is_some() {
+			return;
+		}
+
 		match &self.state {
 			State::Name => {
 				self.state = State::Url {
@@ -0,0 +1,247 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, InputType, TextInputComponent,
+};
+use crate::{
+	app::Environment,
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings,
+	ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::sync::{self, RepoPathRef};
+use crossterm::event::Event;
+use easy_cast::Cast;
+use ratatui::{layout::Rect, widgets::Paragraph, Frame};
+use std::path::PathBuf;
+
+/// asks for the path of a new worktree with a local branch checked
+/// out
+pub struct CreateWorktreePopup {
+	repo: RepoPathRef,
+	input: TextInputComponent,
+	queue: Queue,
+	key_config: SharedKeyConfig,
+	theme: SharedTheme,
+	branch: String,
+	/// typed paths are relative to this working tree
+	work_dir: PathBuf,
+	/// the worktree the branch is checked out in already
+	checked_out: Option<PathBuf>,
+	/// check it out anyway, `git worktree add --force`
+	force: bool,
+}
+
+impl DrawableComponent for CreateWorktreePopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.is_visible() {
+			self.input.draw(f, rect)?;
+			self.draw_warnings(f);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for CreateWorktreePopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			self.input.commands(out, force_all);
+
+			out.push(CommandInfo::new(
+				strings::commands::create_worktree_confirm_msg(
+					&self.key_config,
+				),
+				self.can_create(),
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::toggle_worktree_force(
+					&self.key_config,
+				),
+				true,
+				self.checked_out.is_some() || force_all,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if let Event::Key(e) = ev {
+				if self.checked_out.is_some()
+					&& key_match(
+						e,
+						self.key_config.keys.toggle_worktree_force,
+					) {
+					self.force = !self.force;
+					self.update_title();
+					return Ok(EventState::Consumed);
+				}
+			}
+
+			if self.input.event(ev)?.is_consumed() {
+				return Ok(EventState::Consumed);
+			}
+
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.enter)
+					&& self.can_create()
+				{
+					self.create_worktree();
+				}
+
+				return Ok(EventState::Consumed);
+			}
+		}
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.input.is_visible()
+	}
+
+	fn hide(&mut self) {
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.input.show()?;
+
+		Ok(())
+	}
+}
+
+impl CreateWorktreePopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			queue: env.queue.clone(),
+			input: TextInputComponent::new(
+				env,
+				"",
+				&strings::create_worktree_popup_msg(),
+				true,
+			)
+			.with_input_type(InputType::Singleline),
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+			repo: env.repo.clone(),
+			branch: String::new(),
+			work_dir: PathBuf::new(),
+			checked_out: None,
+			force: false,
+		}
+	}
+
+	/// suggests a directory named after `branch` next to this
+	/// working tree
+	pub fn open(&mut self, branch: String) -> Result<()> {
+		let repo = self.repo.borrow().clone();
+		self.work_dir = sync::utils::repo_work_dir(&repo)?.into();
+		self.checked_out = sync::worktree_of_branch(&repo, &branch)?;
+		self.force = false;
+		self.input
+			.set_text(format!("../{}", branch.replace('/', "-")));
+		self.branch = branch;
+		self.update_title();
+		self.show()?;
+
+		Ok(())
+	}
+
+	fn update_title(&mut self) {
+		let checked_out = self
+			.checked_out
+			.as_ref()
+			.map(|path| path.to_string_lossy().to_string());
+
+		self.input.set_title(strings::create_worktree_popup_title(
+			&self.branch,
+			checked_out.as_deref(),
+			self.force,
+		));
+	}
+
+	fn path(&self) -> Option<PathBuf> {
+		let text = self.input.get_text().trim();
+
+		(!text.is_empty()).then(|| self.work_dir.join(text))
+	}
+
+	fn is_valid_path(&self) -> bool {
+		self.path()
+			.is_some_and(|path| sync::is_valid_worktree_path(&path))
+	}
+
+	fn can_create(&self) -> bool {
+		self.is_valid_path()
+			&& (self.checked_out.is_none() || self.force)
+	}
+
+	fn create_worktree(&mut self) {
+		let Some(path) = self.path() else {
+			return;
+		};
+
+		let res = sync::create_worktree(
+			&self.repo.borrow(),
+			&path,
+			&self.branch,
+			self.force,
+		);
+
+		self.input.clear();
+		self.hide();
+
+		match res {
+			Ok(()) => {
+				self.queue.push(InternalEvent::Update(
+					NeedsUpdate::ALL | NeedsUpdate::BRANCHES,
+				));
+				self.queue.push(InternalEvent::ViewWorktrees);
+			}
+			Err(e) => {
+				log::error!("create worktree: {}", e);
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("create worktree error:\n{e}"),
+				));
+			}
+		}
+	}
+
+	fn draw_warnings(&self, f: &mut Frame) {
+		let msg = if !self.input.get_text().trim().is_empty()
+			&& !self.is_valid_path()
+		{
+			strings::worktree_path_invalid()
+		} else if self.checked_out.is_some() && !self.force {
+			strings::worktree_branch_checked_out()
+		} else {
+			return;
+		};
+
+		let msg_length: u16 = msg.len().cast();
+		let w = Paragraph::new(msg).style(self.theme.text_danger());
+
+		let rect = {
+			let mut rect = self.input.get_area();
+			rect.y += rect.height.saturating_sub(1);
+			rect.height = 1;
+			let offset = rect.width.saturating_sub(msg_length + 1);
+			rect.width = rect.width.saturating_sub(offset + 1);
+			rect.x += offset;
+
+			rect
+		};
+
+		f.render_widget(w, rect);
+	}
+}
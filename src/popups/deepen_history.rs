@@ -0,0 +1,156 @@
+use anyhow::Result;
+use asyncgit::sync::Deepen;
+use crossterm::event::Event;
+use easy_cast::Cast;
+use ratatui::{layout::Rect, widgets::Paragraph, Frame};
+
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState, InputType, TextInputComponent,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
+	strings,
+	ui::style::SharedTheme,
+};
+
+/// asks how many commits to fetch beyond where a shallow clone ends
+pub struct DeepenHistoryPopup {
+	input: TextInputComponent,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+	queue: Queue,
+}
+
+impl DrawableComponent for DeepenHistoryPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.is_visible() {
+			self.input.draw(f, rect)?;
+			self.draw_warnings(f);
+		}
+		Ok(())
+	}
+}
+
+impl Component for DeepenHistoryPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			self.input.commands(out, force_all);
+
+			out.push(CommandInfo::new(
+				strings::commands::deepen_history_confirm(
+					&self.key_config,
+				),
+				self.deepen().is_some(),
+				true,
+			));
+		}
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if self.input.event(ev)?.is_consumed() {
+				return Ok(EventState::Consumed);
+			}
+
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.enter) {
+					self.confirm();
+				}
+
+				return Ok(EventState::Consumed);
+			}
+		}
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.input.is_visible()
+	}
+
+	fn hide(&mut self) {
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.input.show()?;
+
+		Ok(())
+	}
+}
+
+impl DeepenHistoryPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			input: TextInputComponent::new(
+				env,
+				&strings::deepen_history_popup_title(),
+				&strings::deepen_history_popup_msg(),
+				true,
+			)
+			.with_input_type(InputType::Singleline),
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+			queue: env.queue.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self) -> Result<()> {
+		self.input.clear();
+		self.show()
+	}
+
+	/// nothing entered fetches all of the history
+	fn deepen(&self) -> Option<Deepen> {
+		let text = self.input.get_text().trim();
+
+		if text.is_empty() {
+			Some(Deepen::Unshallow)
+		} else {
+			text.parse::<u32>()
+				.ok()
+				.filter(|count| *count > 0)
+				.map(Deepen::By)
+		}
+	}
+
+	fn draw_warnings(&self, f: &mut Frame) {
+		if self.deepen().is_none() {
+			let msg = strings::deepen_history_invalid();
+			let msg_length: u16 = msg.len().cast();
+			let w =
+				Paragraph::new(msg).style(self.theme.text_danger());
+
+			let rect = {
+				let mut rect = self.input.get_area();
+				rect.y += rect.height.saturating_sub(1);
+				rect.height = 1;
+				let offset =
+					rect.width.saturating_sub(msg_length + 1);
+				rect.width = rect.width.saturating_sub(offset + 1);
+				rect.x += offset;
+
+				rect
+			};
+
+			f.render_widget(w, rect);
+		}
+	}
+
+	fn confirm(&mut self) {
+		if let Some(deepen) = self.deepen() {
+			self.queue.push(InternalEvent::DeepenHistory(deepen));
+			self.input.clear();
+			self.hide();
+		}
+	}
+}
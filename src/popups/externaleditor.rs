@@ -5,7 +5,7 @@ use crate::{
 		DrawableComponent, EventState,
 	},
 	keys::SharedKeyConfig,
-	strings,
+	strings, tty,
 	ui::{self, style::SharedTheme},
 };
 use anyhow::{anyhow, bail, Result};
@@ -13,7 +13,7 @@ use asyncgit::sync::{
 	get_config_string, utils::repo_work_dir, RepoPath,
 };
 use crossterm::{
-	event::Event,
+	event::{DisableMouseCapture, Event},
 	terminal::{EnterAlternateScreen, LeaveAlternateScreen},
 	ExecutableCommand,
 };
@@ -25,7 +25,7 @@ use ratatui::{
 };
 use scopeguard::defer;
 use std::ffi::OsStr;
-use std::{env, io, path::Path, process::Command};
+use std::{env, path::Path, process::Command};
 
 ///
 pub struct ExternalEditorPopup {
@@ -61,9 +61,11 @@ impl ExternalEditorPopup {
 			bail!("file not found: {:?}", path);
 		}
 
-		io::stdout().execute(LeaveAlternateScreen)?;
+		// captured again (if enabled) once input polling resumes
+		tty::output().execute(DisableMouseCapture)?;
+		tty::output().execute(LeaveAlternateScreen)?;
 		defer! {
-			io::stdout().execute(EnterAlternateScreen).expect("reset terminal");
+			tty::output().execute(EnterAlternateScreen).expect("reset terminal");
 		}
 
 		let environment_options = ["GIT_EDITOR", "VISUAL", "EDITOR"];
@@ -112,6 +114,7 @@ impl ExternalEditorPopup {
 		Command::new(command.clone())
 			.current_dir(work_dir)
 			.args(args)
+			.stdout(tty::child_stdout())
 			.status()
 			.map_err(|e| anyhow!("\"{}\": {}", command, e))?;
 
@@ -2,9 +2,11 @@ use crate::{
 	app::Environment,
 	components::{
 		visibility_blocking, CommandBlocking, CommandInfo, Component,
-		CredComponent, DrawableComponent, EventState,
+		CredComponent, CredPromptComponent, DrawableComponent,
+		EventState,
 	},
 	keys::SharedKeyConfig,
+	popups::PushPopup,
 	queue::{InternalEvent, NeedsUpdate, Queue},
 	strings,
 	ui::{self, style::SharedTheme},
@@ -17,9 +19,10 @@ use asyncgit::{
 			extract_username_password, need_username_password,
 			BasicAuthCredential,
 		},
+		remotes::CredPrompt,
 		RepoPathRef,
 	},
-	AsyncFetchJob, AsyncGitNotification, ProgressPercent,
+	AsyncFetchJob, AsyncGitNotification, RemoteProgress,
 };
 use crossterm::event::Event;
 use ratatui::{
@@ -34,12 +37,13 @@ pub struct FetchPopup {
 	repo: RepoPathRef,
 	visible: bool,
 	async_fetch: AsyncSingleJob<AsyncFetchJob>,
-	progress: Option<ProgressPercent>,
+	progress: Option<RemoteProgress>,
 	pending: bool,
 	queue: Queue,
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
 	input_cred: CredComponent,
+	cred_prompt: CredPromptComponent,
 }
 
 impl FetchPopup {
@@ -52,6 +56,14 @@ impl FetchPopup {
 			async_fetch: AsyncSingleJob::new(env.sender_git.clone()),
 			progress: None,
 			input_cred: CredComponent::new(env),
+			cred_prompt: CredPromptComponent::new(
+				env,
+				CredPrompt::new(Some((
+					env.sender_git.clone(),
+					AsyncGitNotification::Fetch,
+				))),
+				"fetch",
+			),
 			theme: env.theme.clone(),
 			key_config: env.key_config.clone(),
 			repo: env.repo.clone(),
@@ -82,10 +94,10 @@ impl FetchPopup {
 	fn fetch_all(&mut self, cred: Option<BasicAuthCredential>) {
 		self.pending = true;
 		self.progress = None;
-		self.progress = Some(ProgressPercent::empty());
 		self.async_fetch.spawn(AsyncFetchJob::new(
 			self.repo.borrow().clone(),
 			cred,
+			Some(self.cred_prompt.prompt().clone()),
 		));
 	}
 
@@ -104,12 +116,35 @@ impl FetchPopup {
 	///
 	fn update(&mut self) {
 		self.pending = self.async_fetch.is_pending();
-		self.progress = self.async_fetch.progress();
+		self.progress = self.async_fetch.progress().flatten();
 
-		if !self.pending {
+		if self.pending {
+			if let Err(e) = self.cred_prompt.update() {
+				log::error!("credentials prompt error: {e}");
+			}
+		} else {
+			self.cred_prompt.hide();
 			self.hide();
 			self.queue
 				.push(InternalEvent::Update(NeedsUpdate::BRANCHES));
+
+			match self
+				.async_fetch
+				.take_last()
+				.and_then(|job| job.result())
+			{
+				Some(Ok(pruned)) if !pruned.is_empty() => {
+					self.queue.push(InternalEvent::ShowInfoMsg(
+						strings::fetch_pruned(&pruned),
+					));
+				}
+				Some(Err(e)) => {
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!("fetch failed:\n{e}"),
+					));
+				}
+				_ => (),
+			}
 		}
 	}
 }
@@ -117,13 +152,15 @@ impl FetchPopup {
 impl DrawableComponent for FetchPopup {
 	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
 		if self.visible {
-			let progress = self.progress.unwrap_or_default().progress;
+			let (state, progress) =
+				PushPopup::get_progress(self.progress.as_ref());
 
 			let area = ui::centered_rect_absolute(30, 3, f.area());
 
 			f.render_widget(Clear, area);
 			f.render_widget(
 				Gauge::default()
+					.label(state.as_str())
 					.block(
 						Block::default()
 							.title(Span::styled(
@@ -139,6 +176,7 @@ impl DrawableComponent for FetchPopup {
 				area,
 			);
 			self.input_cred.draw(f, rect)?;
+			self.cred_prompt.draw(f, rect)?;
 		}
 
 		Ok(())
@@ -159,6 +197,9 @@ impl Component for FetchPopup {
 			if self.input_cred.is_visible() {
 				return self.input_cred.commands(out, force_all);
 			}
+			if self.cred_prompt.is_visible() {
+				return self.cred_prompt.commands(out, force_all);
+			}
 			out.push(CommandInfo::new(
 				strings::commands::close_msg(&self.key_config),
 				!self.pending,
@@ -171,7 +212,9 @@ impl Component for FetchPopup {
 
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.visible {
-			if let Event::Key(_) = ev {
+			if self.pending && self.cred_prompt.is_visible() {
+				self.cred_prompt.event(ev)?;
+			} else if let Event::Key(_) = ev {
 				if self.input_cred.is_visible() {
 					self.input_cred.event(ev)?;
 
@@ -0,0 +1,485 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, InputType, ScrollType,
+	TextInputComponent,
+};
+use crate::{
+	app::Environment,
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{
+	asyncjob::AsyncSingleJob,
+	sync::{CommitId, GrepHit, GrepQuery, RepoPathRef},
+	AsyncGitNotification, AsyncGrepJob, ProgressPercent,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Direction, Layout, Margin, Rect},
+	text::Span,
+	widgets::{Block, Borders, Clear},
+	Frame,
+};
+use std::borrow::Cow;
+
+/// stop searching after this many hits so pathological patterns
+/// (like `.`) do not flood the list
+const MAX_HITS: usize = 1000;
+
+enum SearchState {
+	Idle,
+	Searching(ProgressPercent),
+	Done { truncated: bool },
+	Failed(String),
+}
+
+pub struct FileGrepPopup {
+	queue: Queue,
+	repo: RepoPathRef,
+	visible: bool,
+	find_text: TextInputComponent,
+	commit: Option<CommitId>,
+	regex: bool,
+	query: Option<GrepQuery>,
+	async_grep: AsyncSingleJob<AsyncGrepJob>,
+	job: Option<AsyncGrepJob>,
+	state: SearchState,
+	hits: Vec<GrepHit>,
+	selection: usize,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl FileGrepPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		let mut find_text =
+			TextInputComponent::new(env, "", "start typing..", false)
+				.with_input_type(InputType::Singleline);
+		find_text.embed();
+
+		Self {
+			queue: env.queue.clone(),
+			repo: env.repo.clone(),
+			visible: false,
+			find_text,
+			commit: None,
+			regex: false,
+			query: None,
+			async_grep: AsyncSingleJob::new(env.sender_git.clone()),
+			job: None,
+			state: SearchState::Idle,
+			hits: Vec::new(),
+			selection: 0,
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self, commit: CommitId) -> Result<()> {
+		self.show()?;
+		self.find_text.show()?;
+
+		if self.commit != Some(commit) {
+			self.commit = Some(commit);
+			self.query = None;
+		}
+
+		self.update_query();
+
+		Ok(())
+	}
+
+	///
+	pub fn update_git(&mut self, ev: AsyncGitNotification) {
+		if ev != AsyncGitNotification::Grep {
+			return;
+		}
+
+		if let Some(job) = &self.job {
+			let was_empty = self.hits.is_empty();
+			self.hits.extend(job.hits(self.hits.len()));
+
+			if let Some(result) = job.result() {
+				self.state = match result {
+					Ok(result) => SearchState::Done {
+						truncated: result.truncated,
+					},
+					Err(e) => SearchState::Failed(e.to_string()),
+				};
+				self.job = None;
+			} else if let Some(progress) = self.async_grep.progress()
+			{
+				self.state = SearchState::Searching(progress);
+			}
+
+			if was_empty && !self.hits.is_empty() {
+				self.selection_changed();
+			}
+		}
+	}
+
+	///
+	pub fn any_work_pending(&self) -> bool {
+		self.async_grep.is_pending()
+	}
+
+	fn update_query(&mut self) {
+		let query =
+			(!self.find_text.get_text().is_empty()).then(|| {
+				GrepQuery {
+					pattern: self.find_text.get_text().to_string(),
+					regex: self.regex,
+				}
+			});
+
+		if query != self.query {
+			self.query = query;
+			self.search();
+		}
+	}
+
+	fn search(&mut self) {
+		if let Some(job) = self.job.take() {
+			job.cancel();
+		}
+
+		self.hits.clear();
+		self.selection = 0;
+		self.state = SearchState::Idle;
+
+		if let (Some(query), Some(commit)) =
+			(self.query.clone(), self.commit)
+		{
+			let job = AsyncGrepJob::new(
+				self.repo.borrow().clone(),
+				commit,
+				query,
+				MAX_HITS,
+			);
+			self.async_grep.spawn(job.clone());
+			self.job = Some(job);
+			self.state =
+				SearchState::Searching(ProgressPercent::empty());
+		}
+	}
+
+	fn move_selection(&mut self, move_type: ScrollType) -> bool {
+		let new_selection = match move_type {
+			ScrollType::Up => self.selection.saturating_sub(1),
+			ScrollType::Down => self.selection.saturating_add(1),
+			_ => self.selection,
+		}
+		.min(self.hits.len().saturating_sub(1));
+
+		if new_selection != self.selection {
+			self.selection = new_selection;
+			self.selection_changed();
+			return true;
+		}
+
+		false
+	}
+
+	fn selection_changed(&self) {
+		if let Some(hit) = self.hits.get(self.selection) {
+			self.queue.push(InternalEvent::FileGrepChanged(
+				hit.path.clone(),
+				hit.line,
+			));
+		}
+	}
+
+	fn title(&self) -> String {
+		let hits = self.hits.len();
+		match &self.state {
+			SearchState::Idle => format!("Hits: {hits}"),
+			SearchState::Searching(progress) => {
+				format!("Hits: {hits} ({}%)", progress.progress)
+			}
+			SearchState::Done { truncated: true } => {
+				format!("Hits: {hits} (limit reached)")
+			}
+			SearchState::Done { truncated: false } => {
+				format!("Hits: {hits}")
+			}
+			SearchState::Failed(e) => format!("Error: {e}"),
+		}
+	}
+
+	fn hit_text(hit: &GrepHit) -> String {
+		let path = hit.path.to_string_lossy();
+		format!(
+			"{}:{}: {}",
+			path.strip_prefix("./").unwrap_or(&path),
+			hit.line,
+			hit.snippet
+		)
+	}
+
+	fn draw_hits(&self, f: &mut Frame, area: Rect) {
+		// Block has two lines up and down which need to be considered
+		const HEIGHT_BLOCK_MARGIN: usize = 2;
+
+		let list_height = usize::from(area.height)
+			.saturating_sub(HEIGHT_BLOCK_MARGIN);
+
+		// keeps the selection on the last visible row
+		let scroll_skip =
+			(self.selection + 1).saturating_sub(list_height);
+
+		let items = self
+			.hits
+			.iter()
+			.enumerate()
+			.skip(scroll_skip)
+			.take(list_height)
+			.map(|(idx, hit)| {
+				Span::styled(
+					Cow::from(Self::hit_text(hit)),
+					self.theme.text(true, idx == self.selection),
+				)
+			});
+
+		ui::draw_list_block(
+			f,
+			area,
+			Block::default()
+				.title(Span::styled(
+					self.title(),
+					self.theme.title(true),
+				))
+				.borders(Borders::TOP),
+			items,
+		);
+	}
+}
+
+impl DrawableComponent for FileGrepPopup {
+	fn draw(&self, f: &mut Frame, area: Rect) -> Result<()> {
+		if self.is_visible() {
+			const MAX_SIZE: (u16, u16) = (80, 20);
+
+			let area = ui::centered_rect_absolute(
+				MAX_SIZE.0, MAX_SIZE.1, area,
+			);
+
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Block::default()
+					.borders(Borders::all())
+					.style(self.theme.title(true))
+					.title(Span::styled(
+						format!(
+							"{}{}",
+							strings::POPUP_TITLE_FILE_GREP,
+							if self.regex { " (regex)" } else { "" }
+						),
+						self.theme.title(true),
+					)),
+				area,
+			);
+
+			let chunks = Layout::default()
+				.direction(Direction::Vertical)
+				.constraints(
+					[
+						Constraint::Length(1),
+						Constraint::Percentage(100),
+					]
+					.as_ref(),
+				)
+				.split(area.inner(Margin {
+					horizontal: 1,
+					vertical: 1,
+				}));
+
+			self.find_text.draw(f, chunks[0])?;
+			self.draw_hits(f, chunks[1]);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for FileGrepPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			out.push(CommandInfo::new(
+				strings::commands::scroll_popup(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::grep_toggle_regex(
+					&self.key_config,
+					self.regex,
+				),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::close_fuzzy_finder(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, event: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if let Event::Key(key) = event {
+				if key_match(key, self.key_config.keys.exit_popup)
+					|| key_match(key, self.key_config.keys.enter)
+				{
+					self.hide();
+				} else if key_match(
+					key,
+					self.key_config.keys.popup_down,
+				) {
+					self.move_selection(ScrollType::Down);
+				} else if key_match(
+					key,
+					self.key_config.keys.popup_up,
+				) {
+					self.move_selection(ScrollType::Up);
+				} else if key_match(
+					key,
+					self.key_config.keys.file_grep_regex,
+				) {
+					self.regex = !self.regex;
+					self.update_query();
+					return Ok(EventState::Consumed);
+				}
+			}
+
+			if self.find_text.event(event)?.is_consumed() {
+				self.update_query();
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		if let Some(job) = self.job.take() {
+			job.cancel();
+			self.state = SearchState::Idle;
+		}
+		self.query = None;
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ratatui::{backend::TestBackend, Terminal};
+	use std::path::PathBuf;
+
+	fn popup(hits: usize) -> FileGrepPopup {
+		let mut popup = FileGrepPopup::new(&Environment::test_env());
+		popup.hits = (1..=hits)
+			.map(|line| GrepHit {
+				path: PathBuf::from("./a.rs"),
+				line,
+				snippet: format!("hit {line}"),
+			})
+			.collect();
+		popup
+	}
+
+	fn rendered_hits(
+		popup: &FileGrepPopup,
+		height: u16,
+	) -> Vec<String> {
+		let area = Rect::new(0, 0, 30, height);
+		let mut terminal =
+			Terminal::new(TestBackend::new(area.width, area.height))
+				.expect("Unable to set up terminal");
+		terminal
+			.draw(|f| popup.draw_hits(f, area))
+			.expect("Draw failed");
+
+		let buffer = terminal.backend().buffer();
+		area.rows()
+			.skip(1)
+			.map(|row| {
+				row.columns()
+					.map(|pos| buffer[pos].symbol())
+					.collect::<String>()
+					.trim_end()
+					.to_string()
+			})
+			.filter(|row| !row.is_empty())
+			.collect()
+	}
+
+	#[test]
+	fn test_move_selection_bounds() {
+		let mut popup = popup(3);
+
+		assert!(!popup.move_selection(ScrollType::Up));
+		assert!(popup.move_selection(ScrollType::Down));
+		assert!(popup.move_selection(ScrollType::Down));
+		assert!(!popup.move_selection(ScrollType::Down));
+		assert_eq!(popup.selection, 2);
+
+		let mut empty = self::popup(0);
+		assert!(!empty.move_selection(ScrollType::Down));
+		assert_eq!(empty.selection, 0);
+	}
+
+	#[test]
+	fn test_selection_stays_visible() {
+		// one title row and three hits
+		let height = 5;
+		let mut popup = popup(10);
+
+		assert_eq!(
+			rendered_hits(&popup, height),
+			vec!["a.rs:1: hit 1", "a.rs:2: hit 2", "a.rs:3: hit 3"]
+		);
+
+		for _ in 0..3 {
+			popup.move_selection(ScrollType::Down);
+		}
+		assert_eq!(
+			rendered_hits(&popup, height),
+			vec!["a.rs:2: hit 2", "a.rs:3: hit 3", "a.rs:4: hit 4"]
+		);
+
+		for _ in 0..10 {
+			popup.move_selection(ScrollType::Down);
+		}
+		assert_eq!(
+			rendered_hits(&popup, height),
+			vec!["a.rs:8: hit 8", "a.rs:9: hit 9", "a.rs:10: hit 10"]
+		);
+	}
+}
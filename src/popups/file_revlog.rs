@@ -7,14 +7,18 @@ use crate::{
 	},
 	keys::{key_match, SharedKeyConfig},
 	options::SharedOptions,
-	queue::{InternalEvent, NeedsUpdate, Queue, StackablePopupOpen},
+	queue::{
+		AppTabs, InternalEvent, NeedsUpdate, Queue,
+		StackablePopupOpen,
+	},
 	strings,
 	ui::{draw_scrollbar, style::SharedTheme, Orientation},
 };
 use anyhow::Result;
 use asyncgit::{
 	sync::{
-		diff_contains_file, get_commits_info, CommitId, RepoPathRef,
+		get_commits_info, shallow_commits, CommitId, FileFollow,
+		RepoPathRef,
 	},
 	AsyncDiff, AsyncGitNotification, AsyncLog, DiffParams, DiffType,
 };
@@ -50,6 +54,7 @@ impl FileRevOpen {
 ///
 pub struct FileRevlogPopup {
 	git_log: Option<AsyncLog>,
+	follow: Option<FileFollow>,
 	git_diff: AsyncDiff,
 	theme: SharedTheme,
 	queue: Queue,
@@ -61,6 +66,8 @@ pub struct FileRevlogPopup {
 	table_state: std::cell::Cell<TableState>,
 	items: ItemBatch,
 	count_total: usize,
+	/// the file goes back to where a shallow clone ends
+	truncated: bool,
 	key_config: SharedKeyConfig,
 	options: SharedOptions,
 	current_width: std::cell::Cell<usize>,
@@ -76,6 +83,7 @@ impl FileRevlogPopup {
 			sender: env.sender_git.clone(),
 			diff: DiffComponent::new(env, true),
 			git_log: None,
+			follow: None,
 			git_diff: AsyncDiff::new(
 				env.repo.borrow().clone(),
 				&env.sender_git,
@@ -86,6 +94,7 @@ impl FileRevlogPopup {
 			table_state: std::cell::Cell::new(TableState::default()),
 			items: ItemBatch::default(),
 			count_total: 0,
+			truncated: false,
 			key_config: env.key_config.clone(),
 			current_width: std::cell::Cell::new(0),
 			current_height: std::cell::Cell::new(0),
@@ -101,12 +110,13 @@ impl FileRevlogPopup {
 	pub fn open(&mut self, open_request: FileRevOpen) -> Result<()> {
 		self.open_request = Some(open_request.clone());
 
-		let filter = diff_contains_file(open_request.file_path);
+		let follow = FileFollow::new(open_request.file_path);
 		self.git_log = Some(AsyncLog::new(
 			self.repo_path.borrow().clone(),
 			&self.sender,
-			Some(filter),
+			Some(follow.filter()),
 		));
+		self.follow = Some(follow);
 
 		self.items.clear();
 		self.set_selection(open_request.selection.unwrap_or(0));
@@ -133,12 +143,35 @@ impl FileRevlogPopup {
 			git_log.fetch()?;
 
 			self.fetch_commits_if_needed()?;
+			self.update_truncated()?;
 			self.update_diff()?;
 		}
 
 		Ok(())
 	}
 
+	/// the oldest revision is where the history ends, older ones of
+	/// the file were not fetched
+	fn update_truncated(&mut self) -> Result<()> {
+		self.truncated = match &self.git_log {
+			Some(git_log) if !git_log.is_pending() => {
+				let shallow =
+					shallow_commits(&self.repo_path.borrow())?;
+				let oldest = git_log
+					.count()?
+					.checked_sub(1)
+					.map(|last| git_log.get_slice(last, 1))
+					.transpose()?
+					.and_then(|ids| ids.first().copied());
+
+				oldest.is_some_and(|id| shallow.contains(&id))
+			}
+			_ => false,
+		};
+
+		Ok(())
+	}
+
 	///
 	pub fn update_git(
 		&mut self,
@@ -159,9 +192,9 @@ impl FileRevlogPopup {
 	pub fn update_diff(&mut self) -> Result<()> {
 		if self.is_visible() {
 			if let Some(commit_id) = self.selected_commit() {
-				if let Some(open_request) = &self.open_request {
+				if let Some(path) = self.path_at(commit_id)? {
 					let diff_params = DiffParams {
-						path: open_request.file_path.clone(),
+						path: path.clone(),
 						diff_type: DiffType::Commit(commit_id),
 						options: self.options.borrow().diff_options(),
 					};
@@ -170,11 +203,7 @@ impl FileRevlogPopup {
 						self.git_diff.last()?
 					{
 						if params == diff_params {
-							self.diff.update(
-								open_request.file_path.to_string(),
-								false,
-								last,
-							);
+							self.diff.update(path, false, last);
 
 							return Ok(());
 						}
@@ -219,6 +248,15 @@ impl FileRevlogPopup {
 		Ok(())
 	}
 
+	/// path of the file in `commit_id`, differs from the requested one
+	/// in commits from before it got renamed
+	fn path_at(&self, commit_id: CommitId) -> Result<Option<String>> {
+		Ok(match &self.follow {
+			Some(follow) => Some(follow.path_at(commit_id)?),
+			None => None,
+		})
+	}
+
 	fn selected_commit(&self) -> Option<CommitId> {
 		let table_state = self.table_state.take();
 
@@ -254,11 +292,16 @@ impl FileRevlogPopup {
 		self.open_request.as_ref().map_or(
 			"<no history available>".into(),
 			|open_request| {
-				strings::file_log_title(
+				let title = strings::file_log_title(
 					&open_request.file_path,
 					selected,
 					revisions,
-				)
+				);
+				if self.truncated {
+					strings::title_history_truncated(&title)
+				} else {
+					title
+				}
 			},
 		)
 	}
@@ -321,10 +364,13 @@ impl FileRevlogPopup {
 			ScrollType::PageDown => old_selection
 				.saturating_add(height_in_items.saturating_sub(2))
 				.min(max_selection),
-			ScrollType::HalfPageUp => old_selection
-				.saturating_sub((height_in_items.saturating_sub(2)) / 2),
+			ScrollType::HalfPageUp => old_selection.saturating_sub(
+				(height_in_items.saturating_sub(2)) / 2,
+			),
 			ScrollType::HalfPageDown => old_selection
-				.saturating_add((height_in_items.saturating_sub(2)) / 2)
+				.saturating_add(
+					(height_in_items.saturating_sub(2)) / 2,
+				)
 				.min(max_selection),
 		};
 
@@ -445,6 +491,29 @@ impl FileRevlogPopup {
 		self.current_height.set(area.height.into());
 	}
 
+	fn blame_selected(&mut self) -> Result<()> {
+		if let Some(open_request) = self.open_request.clone() {
+			let commit_id = self.selected_commit();
+			let file_path = match commit_id {
+				Some(commit_id) => self
+					.path_at(commit_id)?
+					.unwrap_or(open_request.file_path),
+				None => open_request.file_path,
+			};
+
+			self.hide_stacked(true);
+			self.queue.push(InternalEvent::OpenPopup(
+				StackablePopupOpen::BlameFile(BlameFileOpen {
+					file_path,
+					commit_id,
+					selection: None,
+				}),
+			));
+		}
+
+		Ok(())
+	}
+
 	fn hide_stacked(&mut self, stack: bool) {
 		self.hide();
 
@@ -528,19 +597,21 @@ impl Component for FileRevlogPopup {
 						));
 					}
 				} else if key_match(key, self.key_config.keys.blame) {
-					if let Some(open_request) =
-						self.open_request.clone()
-					{
-						self.hide_stacked(true);
-						self.queue.push(InternalEvent::OpenPopup(
-							StackablePopupOpen::BlameFile(
-								BlameFileOpen {
-									file_path: open_request.file_path,
-									commit_id: self.selected_commit(),
-									selection: None,
-								},
-							),
+					self.blame_selected()?;
+				} else if key_match(
+					key,
+					self.key_config.keys.file_history_goto_log,
+				) {
+					if let Some(commit_id) = self.selected_commit() {
+						self.hide_stacked(false);
+						self.queue.push(InternalEvent::TabSwitch(
+							AppTabs::Log,
 						));
+						self.queue.push(
+							InternalEvent::SelectCommitInRevlog(
+								commit_id,
+							),
+						);
 					}
 				} else if key_match(key, self.key_config.keys.move_up)
 				{
@@ -615,6 +686,16 @@ impl Component for FileRevlogPopup {
 				)
 				.order(1),
 			);
+			out.push(
+				CommandInfo::new(
+					strings::commands::file_history_goto_log(
+						&self.key_config,
+					),
+					true,
+					self.selected_commit().is_some(),
+				)
+				.order(1),
+			);
 
 			out.push(CommandInfo::new(
 				strings::commands::diff_focus_right(&self.key_config),
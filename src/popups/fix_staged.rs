@@ -0,0 +1,568 @@
+use crate::{
+	accessors,
+	app::Environment,
+	components::{
+		command_pump, event_pump, visibility_blocking,
+		CommandBlocking, CommandInfo, Component, DiffComponent,
+		DrawableComponent, EventState, ProcessOutputComponent,
+		StatusTreeComponent,
+	},
+	keys::{key_match, SharedKeyConfig},
+	options::SharedOptions,
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings, try_or_popup,
+	ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::{
+	asyncjob::AsyncSingleJob,
+	sync::{self, RepoPathRef, FIX_STAGED_BATCH_SIZE},
+	AsyncDiff, AsyncGitNotification, AsyncProcessJob, DiffParams,
+	DiffType, ProcessResult, StatusItem, StatusItemType,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Direction, Layout, Rect},
+	text::{Line, Span},
+	widgets::{Block, Borders, Clear, Paragraph, Wrap},
+	Frame,
+};
+use std::path::Path;
+
+/// files the fixer is run on at once
+struct Batch {
+	files: Vec<String>,
+	/// `None` until the fixer ran
+	result: Option<ProcessResult>,
+}
+
+/// runs the configured fixer over the fully staged files, shows what
+/// it changed and restages that
+pub struct FixStagedPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	options: SharedOptions,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+	command: String,
+	async_process: AsyncSingleJob<AsyncProcessJob>,
+	job: Option<AsyncProcessJob>,
+	batches: Vec<Batch>,
+	/// staged files with unstaged changes on top
+	skipped: Vec<String>,
+	/// changed by the fixer, `None` while it runs
+	fixed: Option<Vec<StatusItem>>,
+	output: ProcessOutputComponent,
+	files: StatusTreeComponent,
+	diff: DiffComponent,
+	git_diff: AsyncDiff,
+	visible: bool,
+}
+
+impl FixStagedPopup {
+	accessors!(self, [files, diff]);
+
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			options: env.options.clone(),
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+			command: String::new(),
+			async_process: AsyncSingleJob::new(
+				env.sender_git.clone(),
+			),
+			job: None,
+			batches: Vec::new(),
+			skipped: Vec::new(),
+			fixed: None,
+			output: ProcessOutputComponent::new(
+				env.theme.clone(),
+				env.key_config.clone(),
+			),
+			files: StatusTreeComponent::new(env, "", true),
+			diff: DiffComponent::new(env, true),
+			git_diff: AsyncDiff::new(
+				env.repo.borrow().clone(),
+				&env.sender_git,
+			),
+			visible: false,
+		}
+	}
+
+	///
+	pub fn open(&mut self) -> Result<()> {
+		let repo = self.repo.borrow().clone();
+
+		let Some(command) = sync::fix_staged_command(&repo)? else {
+			self.queue.push(InternalEvent::ShowErrorMsg(
+				strings::fix_staged_not_configured(),
+			));
+			return Ok(());
+		};
+
+		let staged = sync::fix_staged_files(&repo)?;
+		if staged.fixable.is_empty() {
+			self.queue.push(InternalEvent::ShowInfoMsg(
+				strings::fix_staged_nothing_to_fix(
+					&staged.partially_staged,
+				),
+			));
+			return Ok(());
+		}
+
+		self.kill();
+		self.command = command;
+		self.skipped = staged.partially_staged;
+		self.batches = staged
+			.fixable
+			.chunks(FIX_STAGED_BATCH_SIZE)
+			.map(|files| Batch {
+				files: files.to_vec(),
+				result: None,
+			})
+			.collect();
+		self.fixed = None;
+		self.output.clear();
+		self.files.clear()?;
+		self.diff.clear(false);
+		self.show()?;
+
+		self.run_next()
+	}
+
+	///
+	pub fn update_git(
+		&mut self,
+		ev: AsyncGitNotification,
+	) -> Result<()> {
+		if !self.is_visible() {
+			return Ok(());
+		}
+
+		if ev == AsyncGitNotification::Diff {
+			return self.update_diff();
+		}
+
+		if ev != AsyncGitNotification::Process {
+			return Ok(());
+		}
+
+		let Some(job) = &self.job else {
+			return Ok(());
+		};
+
+		self.output.update(job);
+
+		let Some(result) = job.result() else {
+			return Ok(());
+		};
+		self.job = None;
+
+		match result {
+			Ok(result) => {
+				if let Some(batch) = self
+					.batches
+					.iter_mut()
+					.find(|b| b.result.is_none())
+				{
+					batch.result = Some(result);
+				}
+
+				if result.killed {
+					self.finish()
+				} else {
+					self.run_next()
+				}
+			}
+			Err(e) => {
+				self.hide();
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("fixer error:\n{e}"),
+				));
+				Ok(())
+			}
+		}
+	}
+
+	///
+	pub fn any_work_pending(&self) -> bool {
+		self.async_process.is_pending() || self.git_diff.is_pending()
+	}
+
+	const fn is_running(&self) -> bool {
+		self.job.is_some()
+	}
+
+	fn kill(&self) {
+		if let Some(job) = &self.job {
+			job.kill();
+		}
+	}
+
+	fn run_next(&mut self) -> Result<()> {
+		let Some(batch) =
+			self.batches.iter().find(|b| b.result.is_none())
+		else {
+			return self.finish();
+		};
+
+		let child = sync::spawn_fixer(
+			&self.repo.borrow(),
+			&self.command,
+			&batch.files,
+		)?;
+
+		let job = AsyncProcessJob::new(child);
+		self.async_process.spawn(job.clone());
+		self.output.start_job();
+		self.job = Some(job);
+
+		Ok(())
+	}
+
+	/// the fixer ran on all batches (or got killed)
+	fn finish(&mut self) -> Result<()> {
+		let ran: Vec<String> = self
+			.batches
+			.iter()
+			.filter(|b| b.result.is_some())
+			.flat_map(|b| b.files.iter().cloned())
+			.collect();
+
+		let fixed = sync::fixed_files(&self.repo.borrow(), &ran)?;
+
+		// the working tree changed under cached diffs
+		self.git_diff.refresh()?;
+
+		self.files.set_title(strings::title_fix_staged_files(
+			fixed.len(),
+			ran.len(),
+		));
+		self.files.update(&fixed)?;
+		self.files.show()?;
+		self.fixed = Some(fixed);
+
+		self.update_diff()
+	}
+
+	/// stages what the fixer changed
+	fn restage(&mut self) -> Result<()> {
+		let Some(fixed) = self.fixed.take() else {
+			return Ok(());
+		};
+
+		let repo = self.repo.borrow().clone();
+		for item in &fixed {
+			let path = Path::new(&item.path);
+			if item.status == StatusItemType::Deleted {
+				sync::stage_addremoved(&repo, path)?;
+			} else {
+				sync::stage_add_file(&repo, path)?;
+			}
+		}
+
+		self.hide();
+		self.queue.push(InternalEvent::ShowInfoMsg(
+			strings::fix_staged_restaged(fixed.len()),
+		));
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+
+		Ok(())
+	}
+
+	fn can_restage(&self) -> bool {
+		self.fixed.as_ref().is_some_and(|fixed| !fixed.is_empty())
+	}
+
+	fn can_focus_diff(&self) -> bool {
+		self.files.selection_file().is_some()
+	}
+
+	/// called when the file selection changed
+	fn update_diff(&mut self) -> Result<()> {
+		if let Some(f) = self.files.selection_file() {
+			let diff_params = DiffParams {
+				path: f.path.clone(),
+				diff_type: DiffType::WorkDir,
+				options: self.options.borrow().diff_options(),
+			};
+
+			if let Some((params, last)) = self.git_diff.last()? {
+				if params == diff_params {
+					self.diff.update(f.path, false, last);
+					return Ok(());
+				}
+			}
+
+			self.git_diff.request(diff_params)?;
+			self.diff.clear(true);
+		} else {
+			self.diff.clear(false);
+		}
+
+		Ok(())
+	}
+
+	/// the failed batches and the skipped files
+	fn summary(&self) -> Vec<Line<'static>> {
+		let mut lines: Vec<Line<'static>> = self
+			.batches
+			.iter()
+			.filter_map(|batch| {
+				let result = batch.result?;
+				(!result.success()).then(|| {
+					Line::from(Span::styled(
+						strings::fix_staged_failed(
+							&strings::process_status(Some(&result)),
+							&batch.files,
+						),
+						self.theme.text_danger(),
+					))
+				})
+			})
+			.collect();
+
+		if !self.skipped.is_empty() {
+			lines.push(Line::from(Span::styled(
+				strings::fix_staged_skipped(&self.skipped),
+				self.theme.text(false, false),
+			)));
+		}
+
+		lines
+	}
+
+	fn draw_summary(&self, f: &mut Frame, area: Rect) {
+		f.render_widget(
+			Paragraph::new(self.summary())
+				.block(
+					Block::default()
+						.borders(Borders::ALL)
+						.title(Span::styled(
+							strings::title_fix_staged_summary(),
+							self.theme.title(false),
+						))
+						.border_style(self.theme.block(false)),
+				)
+				.wrap(Wrap { trim: false }),
+			area,
+		);
+	}
+
+	fn draw_output(&self, f: &mut Frame, area: Rect) {
+		let status = if self.is_running() {
+			strings::process_status(None)
+		} else {
+			self.batches
+				.iter()
+				.rev()
+				.find_map(|b| b.result.filter(|r| !r.success()))
+				.or_else(|| {
+					self.batches.iter().rev().find_map(|b| b.result)
+				})
+				.map_or_else(String::new, |result| {
+					strings::process_status(Some(&result))
+				})
+		};
+
+		let block = Block::default()
+			.borders(Borders::ALL)
+			.title(Span::styled(
+				strings::title_fix_staged(&self.command, &status),
+				self.theme.title(self.is_running()),
+			))
+			.border_style(self.theme.block(self.is_running()));
+
+		let inner = block.inner(area);
+		f.render_widget(block, area);
+		self.output.draw(f, inner);
+	}
+}
+
+impl DrawableComponent for FixStagedPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.is_visible() {
+			let percentages = if self.diff.focused() {
+				(0, 100)
+			} else {
+				(30, 70)
+			};
+
+			let chunks = Layout::default()
+				.direction(Direction::Horizontal)
+				.constraints([
+					Constraint::Percentage(percentages.0),
+					Constraint::Percentage(percentages.1),
+				])
+				.split(rect);
+
+			let left = Layout::default()
+				.direction(Direction::Vertical)
+				.constraints([
+					Constraint::Percentage(60),
+					Constraint::Percentage(40),
+				])
+				.split(chunks[0]);
+
+			let right = Layout::default()
+				.direction(Direction::Vertical)
+				.constraints([
+					Constraint::Percentage(65),
+					Constraint::Percentage(35),
+				])
+				.split(chunks[1]);
+
+			f.render_widget(Clear, rect);
+
+			self.files.draw(f, left[0])?;
+			self.draw_summary(f, left[1]);
+			self.diff.draw(f, right[0])?;
+			self.draw_output(f, right[1]);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for FixStagedPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			command_pump(
+				out,
+				force_all,
+				self.components().as_slice(),
+			);
+
+			out.push(
+				CommandInfo::new(
+					strings::commands::close_popup(&self.key_config),
+					true,
+					true,
+				)
+				.order(1),
+			);
+
+			out.push(CommandInfo::new(
+				strings::commands::kill_process(&self.key_config),
+				self.is_running(),
+				self.is_running() || force_all,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::fix_staged_restage(
+					&self.key_config,
+				),
+				self.can_restage(),
+				!self.is_running() || force_all,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::diff_focus_right(&self.key_config),
+				self.can_focus_diff(),
+				!self.diff.focused() || force_all,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::diff_focus_left(&self.key_config),
+				true,
+				self.diff.focused() || force_all,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if self.is_running() {
+				if let Event::Key(e) = ev {
+					if key_match(e, self.key_config.keys.exit_popup) {
+						// the remaining batches are dropped with it
+						self.kill();
+						self.hide();
+					} else if key_match(
+						e,
+						self.key_config.keys.process_kill,
+					) {
+						self.kill();
+					} else {
+						self.output.event(ev);
+					}
+				}
+
+				return Ok(EventState::Consumed);
+			}
+
+			if event_pump(ev, self.components_mut().as_mut_slice())?
+				.is_consumed()
+			{
+				if !self.diff.focused() {
+					self.update_diff()?;
+				}
+				return Ok(EventState::Consumed);
+			}
+
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.exit_popup) {
+					if self.diff.focused() {
+						self.files.focus(true);
+						self.diff.focus(false);
+					} else {
+						self.hide();
+					}
+				} else if key_match(
+					e,
+					self.key_config.keys.move_right,
+				) && self.can_focus_diff()
+				{
+					self.files.focus(false);
+					self.diff.focus(true);
+				} else if key_match(e, self.key_config.keys.move_left)
+					&& self.diff.focused()
+				{
+					self.files.focus(true);
+					self.diff.focus(false);
+				} else if key_match(
+					e,
+					self.key_config.keys.status_stage_all,
+				) && self.can_restage()
+				{
+					try_or_popup!(
+						self,
+						"restaging error:",
+						self.restage()
+					);
+				} else {
+					self.output.event(ev);
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+		self.files.focus(true);
+		self.diff.focus(false);
+
+		Ok(())
+	}
+}
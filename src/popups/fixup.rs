@@ -0,0 +1,325 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState,
+};
+use crate::{
+	app::Environment,
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings, try_or_popup,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{
+	asyncjob::AsyncSingleJob,
+	sync::{self, FixupKind, FixupTargets, RepoPathRef},
+	AsyncFixupJob, AsyncGitNotification,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Alignment, Rect},
+	text::{Line, Span},
+	widgets::{Block, Borders, Clear, Paragraph},
+	Frame,
+};
+
+/// proposes the commit the staged changes are a fixup of and
+/// creates the `fixup!` commit
+pub struct FixupPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	async_targets: AsyncSingleJob<AsyncFixupJob>,
+	job: Option<AsyncFixupJob>,
+	targets: Option<FixupTargets>,
+	selection: usize,
+	visible: bool,
+	key_config: SharedKeyConfig,
+	theme: SharedTheme,
+}
+
+impl FixupPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			async_targets: AsyncSingleJob::new(
+				env.sender_git.clone(),
+			),
+			job: None,
+			targets: None,
+			selection: 0,
+			visible: false,
+			key_config: env.key_config.clone(),
+			theme: env.theme.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self) -> Result<()> {
+		let job = AsyncFixupJob::new(self.repo.borrow().clone());
+		self.async_targets.spawn(job.clone());
+
+		self.job = Some(job);
+		self.targets = None;
+		self.selection = 0;
+
+		self.show()
+	}
+
+	///
+	pub fn update_git(&mut self, ev: AsyncGitNotification) {
+		if ev != AsyncGitNotification::Fixup {
+			return;
+		}
+
+		if let Some(result) =
+			self.job.as_ref().and_then(AsyncFixupJob::result)
+		{
+			self.job = None;
+
+			match result {
+				Ok(targets) => self.targets = Some(targets),
+				Err(e) => {
+					self.hide();
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!("fixup target error:\n{e}"),
+					));
+				}
+			}
+		}
+	}
+
+	///
+	pub fn any_work_pending(&self) -> bool {
+		self.async_targets.is_pending()
+	}
+
+	fn get_text(&self) -> Vec<Line<'_>> {
+		let Some(targets) = &self.targets else {
+			return vec![Line::from(Span::styled(
+				strings::fixup_analyzing(),
+				self.theme.text(false, false),
+			))];
+		};
+
+		if targets.candidates.is_empty() {
+			return vec![Line::from(Span::styled(
+				strings::fixup_no_candidates(),
+				self.theme.text(true, false),
+			))];
+		}
+
+		let mut txt = vec![
+			Line::from(Span::styled(
+				strings::fixup_info(targets),
+				if targets.unambiguous().is_some() {
+					self.theme.text(true, false)
+				} else {
+					self.theme.text_danger()
+				},
+			)),
+			Line::default(),
+		];
+
+		txt.extend(targets.candidates.iter().enumerate().map(
+			|(index, candidate)| {
+				let selected = index == self.selection;
+
+				let mut spans = vec![
+					Span::styled(
+						format!(
+							"{} ",
+							candidate.id.get_short_string()
+						),
+						self.theme.commit_hash(selected),
+					),
+					Span::styled(
+						candidate.summary.clone(),
+						self.theme.text(true, selected),
+					),
+					Span::styled(
+						format!(
+							" ({}/{})",
+							candidate.hunks, targets.hunks
+						),
+						self.theme.text(false, selected),
+					),
+				];
+
+				if candidate.pushed {
+					spans.push(Span::styled(
+						strings::fixup_pushed(),
+						self.theme.text_danger(),
+					));
+				}
+
+				Line::from(spans)
+			},
+		));
+
+		txt
+	}
+
+	fn move_selection(&mut self, down: bool) {
+		let count = self
+			.targets
+			.as_ref()
+			.map_or(0, |targets| targets.candidates.len());
+
+		self.selection = if down {
+			(self.selection + 1).min(count.saturating_sub(1))
+		} else {
+			self.selection.saturating_sub(1)
+		};
+	}
+
+	fn commit_fixup(&mut self) -> Result<()> {
+		let Some(candidate) =
+			self.targets.as_ref().and_then(|targets| {
+				targets.candidates.get(self.selection)
+			})
+		else {
+			return Ok(());
+		};
+
+		sync::commit_fixup(
+			&self.repo.borrow(),
+			candidate.id,
+			FixupKind::Fixup,
+		)?;
+
+		self.hide();
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+
+		Ok(())
+	}
+
+	fn can_commit(&self) -> bool {
+		self.targets
+			.as_ref()
+			.is_some_and(|targets| !targets.candidates.is_empty())
+	}
+}
+
+impl DrawableComponent for FixupPopup {
+	fn draw(&self, f: &mut Frame, area: Rect) -> Result<()> {
+		if self.is_visible() {
+			let txt = self.get_text();
+
+			let width = txt
+				.iter()
+				.map(Line::width)
+				.max()
+				.unwrap_or_default()
+				.saturating_add(2)
+				.max(40);
+			let area = ui::centered_rect_absolute(
+				u16::try_from(width).unwrap_or(u16::MAX),
+				u16::try_from(txt.len() + 2).unwrap_or(u16::MAX),
+				area,
+			);
+
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Paragraph::new(txt)
+					.block(
+						Block::default()
+							.borders(Borders::ALL)
+							.title(Span::styled(
+								strings::title_fixup(),
+								self.theme.title(true),
+							))
+							.border_style(self.theme.block(true)),
+					)
+					.alignment(Alignment::Left),
+				area,
+			);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for FixupPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			out.push(
+				CommandInfo::new(
+					strings::commands::close_popup(&self.key_config),
+					true,
+					true,
+				)
+				.order(1),
+			);
+
+			out.push(
+				CommandInfo::new(
+					strings::commands::fixup_confirm(
+						&self.key_config,
+					),
+					self.can_commit(),
+					true,
+				)
+				.order(1),
+			);
+
+			out.push(
+				CommandInfo::new(
+					strings::commands::fixup_target(&self.key_config),
+					self.can_commit(),
+					true,
+				)
+				.order(1),
+			);
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, event: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if let Event::Key(key) = &event {
+				if key_match(key, self.key_config.keys.exit_popup) {
+					self.hide();
+				} else if key_match(
+					key,
+					self.key_config.keys.move_down,
+				) {
+					self.move_selection(true);
+				} else if key_match(key, self.key_config.keys.move_up)
+				{
+					self.move_selection(false);
+				} else if key_match(key, self.key_config.keys.enter) {
+					try_or_popup!(
+						self,
+						"fixup commit error:",
+						self.commit_fixup()
+					);
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
@@ -13,7 +13,7 @@ use crate::{
 };
 use anyhow::Result;
 use crossterm::event::Event;
-use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
 use ratatui::{
 	layout::{Constraint, Direction, Layout, Margin, Rect},
 	text::{Line, Span},
@@ -80,19 +80,34 @@ impl FuzzyFindPopup {
 		self.filtered.clear();
 
 		if let Some(q) = &self.query {
-			let matcher =
-				fuzzy_matcher::skim::SkimMatcherV2::default();
-
-			let mut contents = self
-				.contents
-				.iter()
-				.enumerate()
-				.filter_map(|a| {
-					matcher
-						.fuzzy_indices(a.1, q)
-						.map(|(score, indices)| (score, a.0, indices))
-				})
-				.collect::<Vec<(_, _, _)>>();
+			let matcher = SkimMatcherV2::default();
+			let is_files =
+				matches!(self.target, Some(FuzzyFinderTarget::Files));
+
+			let mut contents = if is_files {
+				let matcher = matcher.ignore_case();
+				self.contents
+					.iter()
+					.enumerate()
+					.filter_map(|a| {
+						match_path(&matcher, a.1, q).map(
+							|(score, indices)| (score, a.0, indices),
+						)
+					})
+					.collect::<Vec<(_, _, _)>>()
+			} else {
+				self.contents
+					.iter()
+					.enumerate()
+					.filter_map(|a| {
+						matcher.fuzzy_indices(a.1, q).map(
+							|(score, indices)| {
+								((false, score), a.0, indices)
+							},
+						)
+					})
+					.collect::<Vec<(_, _, _)>>()
+			};
 
 			contents.sort_by(|(score1, _, _), (score2, _, _)| {
 				score2.cmp(score1)
@@ -246,6 +261,31 @@ impl FuzzyFindPopup {
 	}
 }
 
+/// matches `query` against a path, ranking hits within the file name
+/// above hits that need the directory part
+fn match_path(
+	matcher: &SkimMatcherV2,
+	path: &str,
+	query: &str,
+) -> Option<((bool, i64), Vec<usize>)> {
+	let name_start = path.rfind('/').map_or(0, |idx| idx + 1);
+	let offset = path[..name_start].chars().count();
+
+	matcher
+		.fuzzy_indices(&path[name_start..], query)
+		.map(|(score, indices)| {
+			(
+				(true, score),
+				indices.into_iter().map(|idx| idx + offset).collect(),
+			)
+		})
+		.or_else(|| {
+			matcher
+				.fuzzy_indices(path, query)
+				.map(|(score, indices)| ((false, score), indices))
+		})
+}
+
 impl DrawableComponent for FuzzyFindPopup {
 	fn draw(&self, f: &mut Frame, area: Rect) -> Result<()> {
 		if self.is_visible() {
@@ -339,9 +379,16 @@ impl Component for FuzzyFindPopup {
 	) -> Result<EventState> {
 		if self.is_visible() {
 			if let Event::Key(key) = event {
-				if key_match(key, self.key_config.keys.exit_popup)
-					|| key_match(key, self.key_config.keys.enter)
-				{
+				if key_match(key, self.key_config.keys.exit_popup) {
+					self.hide();
+					if let Some(target) = self.target {
+						self.queue.push(
+							InternalEvent::FuzzyFinderCancelled(
+								target,
+							),
+						);
+					}
+				} else if key_match(key, self.key_config.keys.enter) {
 					self.hide();
 				} else if key_match(
 					key,
@@ -379,3 +426,49 @@ impl Component for FuzzyFindPopup {
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn rank(paths: &[&str], query: &str) -> Vec<String> {
+		let matcher = SkimMatcherV2::default().ignore_case();
+		let mut hits = paths
+			.iter()
+			.filter_map(|p| {
+				match_path(&matcher, p, query).map(|(s, _)| (s, *p))
+			})
+			.collect::<Vec<_>>();
+		hits.sort_by_key(|hit| std::cmp::Reverse(hit.0));
+		hits.into_iter().map(|(_, p)| p.to_string()).collect()
+	}
+
+	#[test]
+	fn test_basename_first() {
+		assert_eq!(
+			rank(
+				&["app/lib/other.rs", "src/main.rs", "lib/app.rs"],
+				"app"
+			),
+			vec!["lib/app.rs", "app/lib/other.rs"]
+		);
+	}
+
+	#[test]
+	fn test_case_insensitive() {
+		assert_eq!(
+			rank(&["src/Readme.md", "docs/x.txt"], "README"),
+			vec!["src/Readme.md"]
+		);
+	}
+
+	#[test]
+	fn test_indices_offset() {
+		let matcher = SkimMatcherV2::default().ignore_case();
+		let (score, indices) =
+			match_path(&matcher, "ab/cd.rs", "cd").unwrap();
+
+		assert!(score.0);
+		assert_eq!(indices, vec![3, 4]);
+	}
+}
@@ -5,6 +5,7 @@ use crate::components::{
 use crate::{
 	app::Environment,
 	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
 	strings, ui,
 };
 use anyhow::Result;
@@ -26,6 +27,7 @@ pub struct HelpPopup {
 	cmds: Vec<CommandInfo>,
 	visible: bool,
 	selection: u16,
+	queue: Queue,
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
 }
@@ -116,6 +118,14 @@ impl Component for HelpPopup {
 				true,
 				true,
 			));
+
+			out.push(CommandInfo::new(
+				strings::commands::key_conflicts_open(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
 		}
 
 		if !self.visible || force_all {
@@ -142,6 +152,13 @@ impl Component for HelpPopup {
 					self.move_selection(true);
 				} else if key_match(e, self.key_config.keys.move_up) {
 					self.move_selection(false);
+				} else if key_match(
+					e,
+					self.key_config.keys.open_key_conflicts,
+				) {
+					self.hide();
+					self.queue
+						.push(InternalEvent::OpenKeyConflictsPopup);
 				}
 			}
 
@@ -179,6 +196,7 @@ impl HelpPopup {
 			cmds: vec![],
 			visible: false,
 			selection: 0,
+			queue: env.queue.clone(),
 			theme: env.theme.clone(),
 			key_config: env.key_config.clone(),
 		}
@@ -0,0 +1,245 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState,
+};
+use crate::{
+	app::Environment,
+	keys::{key_match, SharedKeyConfig},
+	queue::{IgnoreItem, InternalEvent, NeedsUpdate, Queue},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::{self, ignore_pattern, IgnoreKind, RepoPathRef};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Alignment, Rect},
+	text::{Line, Span},
+	widgets::{Block, Borders, Clear, Paragraph},
+	Frame,
+};
+
+/// lets the user pick the pattern that ignores a status item
+pub struct IgnorePopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	item: Option<IgnoreItem>,
+	patterns: Vec<(IgnoreKind, String)>,
+	selection: usize,
+	visible: bool,
+	key_config: SharedKeyConfig,
+	theme: SharedTheme,
+}
+
+impl IgnorePopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			item: None,
+			patterns: Vec::new(),
+			selection: 0,
+			visible: false,
+			key_config: env.key_config.clone(),
+			theme: env.theme.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self, item: IgnoreItem) -> Result<()> {
+		self.patterns = IgnoreKind::ALL
+			.into_iter()
+			.filter_map(|kind| {
+				ignore_pattern(&item.path, item.is_dir, kind)
+					.map(|pattern| (kind, pattern))
+			})
+			.collect();
+		self.selection = 0;
+		self.item = Some(item);
+
+		self.show()
+	}
+
+	fn get_text(&self) -> Vec<Line<'_>> {
+		let mut txt: Vec<Line> = self
+			.patterns
+			.iter()
+			.enumerate()
+			.map(|(index, (kind, pattern))| {
+				let selected = index == self.selection;
+
+				Line::from(vec![
+					Span::styled(
+						format!(
+							"{:<10}",
+							strings::ignore_kind(*kind)
+						),
+						self.theme.text(true, selected),
+					),
+					Span::styled(
+						pattern.clone(),
+						self.theme.text(true, selected),
+					),
+				])
+			})
+			.collect();
+
+		if self.item.as_ref().is_some_and(|item| item.tracked) {
+			txt.push(Line::default());
+			txt.push(Line::from(Span::styled(
+				strings::ignore_tracked_warning(),
+				self.theme.text_danger(),
+			)));
+		}
+
+		txt
+	}
+
+	fn move_selection(&mut self, down: bool) {
+		self.selection = if down {
+			(self.selection + 1)
+				.min(self.patterns.len().saturating_sub(1))
+		} else {
+			self.selection.saturating_sub(1)
+		};
+	}
+
+	fn ignore(&mut self) {
+		if let Some((_, pattern)) = self.patterns.get(self.selection)
+		{
+			if let Err(e) =
+				sync::add_to_ignore(&self.repo.borrow(), pattern)
+			{
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!(
+						"ignore error:\n{e}\npattern:\n{pattern:?}"
+					),
+				));
+			} else {
+				self.queue
+					.push(InternalEvent::Update(NeedsUpdate::ALL));
+			}
+		}
+
+		self.hide();
+	}
+}
+
+impl DrawableComponent for IgnorePopup {
+	fn draw(&self, f: &mut Frame, area: Rect) -> Result<()> {
+		if self.is_visible() {
+			let txt = self.get_text();
+
+			let width = txt
+				.iter()
+				.map(Line::width)
+				.max()
+				.unwrap_or_default()
+				.saturating_add(2)
+				.max(30);
+			let area = ui::centered_rect_absolute(
+				u16::try_from(width).unwrap_or(u16::MAX),
+				u16::try_from(txt.len() + 2).unwrap_or(u16::MAX),
+				area,
+			);
+
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Paragraph::new(txt)
+					.block(
+						Block::default()
+							.borders(Borders::ALL)
+							.title(Span::styled(
+								strings::title_ignore(),
+								self.theme.title(true),
+							))
+							.border_style(self.theme.block(true)),
+					)
+					.alignment(Alignment::Left),
+				area,
+			);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for IgnorePopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			out.push(
+				CommandInfo::new(
+					strings::commands::close_popup(&self.key_config),
+					true,
+					true,
+				)
+				.order(1),
+			);
+
+			out.push(
+				CommandInfo::new(
+					strings::commands::ignore_confirm(
+						&self.key_config,
+					),
+					!self.patterns.is_empty(),
+					true,
+				)
+				.order(1),
+			);
+
+			out.push(
+				CommandInfo::new(
+					strings::commands::ignore_kind(&self.key_config),
+					true,
+					true,
+				)
+				.order(1),
+			);
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, event: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if let Event::Key(key) = &event {
+				if key_match(key, self.key_config.keys.exit_popup) {
+					self.hide();
+				} else if key_match(
+					key,
+					self.key_config.keys.move_down,
+				) {
+					self.move_selection(true);
+				} else if key_match(key, self.key_config.keys.move_up)
+				{
+					self.move_selection(false);
+				} else if key_match(key, self.key_config.keys.enter) {
+					self.ignore();
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
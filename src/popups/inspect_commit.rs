@@ -13,7 +13,7 @@ use crate::{
 };
 use anyhow::Result;
 use asyncgit::{
-	sync::{CommitId, CommitTags},
+	sync::{self, CommitId, CommitTags, RepoPathRef},
 	AsyncDiff, AsyncGitNotification, DiffParams, DiffType,
 };
 use crossterm::event::Event;
@@ -23,7 +23,7 @@ use ratatui::{
 	Frame,
 };
 
-use super::FileTreeOpen;
+use super::{compare_stash::apply_stash_file, FileTreeOpen};
 
 #[derive(Clone, Debug)]
 pub struct InspectCommitOpen {
@@ -55,11 +55,14 @@ impl InspectCommitOpen {
 }
 
 pub struct InspectCommitPopup {
+	repo: RepoPathRef,
 	queue: Queue,
 	open_request: Option<InspectCommitOpen>,
 	diff: DiffComponent,
 	details: CommitDetailsComponent,
 	git_diff: AsyncDiff,
+	/// the inspected commit is a stash, its files can be applied
+	stash: bool,
 	visible: bool,
 	key_config: SharedKeyConfig,
 	options: SharedOptions,
@@ -136,6 +139,12 @@ impl Component for InspectCommitPopup {
 				true,
 				true,
 			));
+
+			out.push(CommandInfo::new(
+				strings::commands::stash_apply_file(&self.key_config),
+				self.can_focus_diff(),
+				self.stash || force_all,
+			));
 		}
 
 		visibility_blocking(self)
@@ -189,6 +198,12 @@ impl Component for InspectCommitPopup {
 						return Ok(EventState::Consumed);
 					}
 					return Ok(EventState::NotConsumed);
+				} else if key_match(
+					e,
+					self.key_config.keys.stash_apply,
+				) && self.stash
+				{
+					self.apply_file();
 				}
 
 				return Ok(EventState::Consumed);
@@ -220,10 +235,12 @@ impl InspectCommitPopup {
 	///
 	pub fn new(env: &Environment) -> Self {
 		Self {
+			repo: env.repo.clone(),
 			queue: env.queue.clone(),
 			details: CommitDetailsComponent::new(env),
 			diff: DiffComponent::new(env, true),
 			open_request: None,
+			stash: false,
 			git_diff: AsyncDiff::new(
 				env.repo.borrow().clone(),
 				&env.sender_git,
@@ -236,6 +253,9 @@ impl InspectCommitPopup {
 
 	///
 	pub fn open(&mut self, open: InspectCommitOpen) -> Result<()> {
+		self.stash = open.compare_id.is_none()
+			&& sync::get_stashes(&self.repo.borrow())?
+				.contains(&open.commit_id);
 		self.open_request = Some(open);
 		self.show()?;
 
@@ -314,6 +334,20 @@ impl InspectCommitPopup {
 		self.details.files().selection_file().is_some()
 	}
 
+	fn apply_file(&self) {
+		if let (Some(request), Some(file)) = (
+			&self.open_request,
+			self.details.files().selection_file(),
+		) {
+			apply_stash_file(
+				&self.repo.borrow(),
+				&self.queue,
+				request.commit_id,
+				&file.path,
+			);
+		}
+	}
+
 	fn hide_stacked(&mut self, stack: bool) {
 		self.hide();
 
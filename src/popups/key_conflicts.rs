@@ -0,0 +1,237 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState,
+};
+use crate::{
+	app::Environment,
+	keys::{
+		config_line, key_issues, key_match, KeyConfig,
+		KeyConfigWarning, KeyIssue, KeyIssueKind, SharedKeyConfig,
+	},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Alignment, Rect},
+	text::{Line, Span},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+use std::path::PathBuf;
+
+/// lists key bindings hidden by other bindings, actions that can
+/// not be reached with their key and what gitui could not make sense
+/// of in the key bindings file
+pub struct KeyConflictsPopup {
+	warnings: Vec<KeyConfigWarning>,
+	issues: Vec<KeyIssue>,
+	config: Option<(PathBuf, String)>,
+	scroll: u16,
+	visible: bool,
+	key_config: SharedKeyConfig,
+	theme: SharedTheme,
+}
+
+impl KeyConflictsPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			warnings: env.key_config.warnings.clone(),
+			issues: key_issues(&env.key_config.keys),
+			config: KeyConfig::read_config_file(),
+			scroll: 0,
+			visible: false,
+			key_config: env.key_config.clone(),
+			theme: env.theme.clone(),
+		}
+	}
+
+	///
+	pub fn has_issues(&self) -> bool {
+		!self.warnings.is_empty() || !self.issues.is_empty()
+	}
+
+	fn get_text(&self) -> Vec<Line<'_>> {
+		if !self.has_issues() {
+			return vec![Line::from(Span::styled(
+				strings::key_conflicts_none(),
+				self.theme.text(true, false),
+			))];
+		}
+
+		let mut txt = Vec::new();
+
+		for warning in &self.warnings {
+			txt.push(Line::from(Span::styled(
+				strings::key_config_warning(warning),
+				self.theme.text_danger(),
+			)));
+			if let Some(fix) = strings::key_config_warning_fix(
+				warning,
+				self.config.as_ref().and_then(|(_, content)| {
+					config_line(content, warning_entry(warning)?)
+				}),
+			) {
+				txt.push(Line::from(Span::styled(
+					fix,
+					self.theme.text(false, false),
+				)));
+			}
+		}
+
+		for issue in &self.issues {
+			txt.push(Line::from(Span::styled(
+				strings::key_issue(issue, &self.key_config),
+				if matches!(
+					issue.kind,
+					KeyIssueKind::Ambiguous { .. }
+				) {
+					self.theme.text(true, false)
+				} else {
+					self.theme.text_danger()
+				},
+			)));
+			txt.push(Line::from(Span::styled(
+				strings::key_issue_fix(
+					issue.fix,
+					self.config.as_ref().and_then(|(_, content)| {
+						config_line(content, issue.fix)
+					}),
+				),
+				self.theme.text(false, false),
+			)));
+		}
+
+		if let Some((path, _)) = &self.config {
+			txt.push(Line::default());
+			txt.push(Line::from(Span::styled(
+				strings::key_conflicts_file(path),
+				self.theme.text(false, false),
+			)));
+		}
+
+		txt
+	}
+
+	fn move_scroll(&mut self, down: bool, lines: usize) {
+		let max = u16::try_from(lines.saturating_sub(1))
+			.unwrap_or(u16::MAX);
+
+		self.scroll = if down {
+			self.scroll.saturating_add(1).min(max)
+		} else {
+			self.scroll.saturating_sub(1)
+		};
+	}
+}
+
+/// the entry of the key bindings file the warning is about
+fn warning_entry(warning: &KeyConfigWarning) -> Option<&str> {
+	match warning {
+		KeyConfigWarning::Parse(_) => None,
+		KeyConfigWarning::UnknownAction(name) => Some(name.as_str()),
+		KeyConfigWarning::UnknownPreset(_) => Some("preset"),
+	}
+}
+
+impl DrawableComponent for KeyConflictsPopup {
+	fn draw(&self, f: &mut Frame, _rect: Rect) -> Result<()> {
+		if self.is_visible() {
+			const SIZE: (u16, u16) = (80, 20);
+
+			let txt = self.get_text();
+			let lines = txt.len();
+
+			let area =
+				ui::centered_rect_absolute(SIZE.0, SIZE.1, f.area());
+
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Paragraph::new(txt)
+					.block(
+						Block::default()
+							.borders(Borders::ALL)
+							.border_type(BorderType::Thick)
+							.title(Span::styled(
+								strings::title_key_conflicts(),
+								self.theme.title(true),
+							))
+							.border_style(self.theme.block(true)),
+					)
+					.scroll((self.scroll, 0))
+					.alignment(Alignment::Left),
+				area,
+			);
+
+			ui::draw_scrollbar(
+				f,
+				area,
+				&self.theme,
+				lines,
+				self.scroll as usize,
+				ui::Orientation::Vertical,
+			);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for KeyConflictsPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.exit_popup) {
+					self.hide();
+				} else if key_match(e, self.key_config.keys.move_down)
+				{
+					self.move_scroll(true, self.get_text().len());
+				} else if key_match(e, self.key_config.keys.move_up) {
+					self.move_scroll(false, self.get_text().len());
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
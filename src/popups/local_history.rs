@@ -0,0 +1,415 @@
+use crate::{
+	app::Environment,
+	components::{
+		event_pump, time_to_string, visibility_blocking,
+		CommandBlocking, CommandInfo, Component, DiffComponent,
+		DrawableComponent, EventState, ScrollType,
+	},
+	keys::{key_match, SharedKeyConfig},
+	options::SharedOptions,
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings,
+	ui::{draw_scrollbar, style::SharedTheme, Orientation},
+};
+use anyhow::Result;
+use asyncgit::{
+	asyncjob::AsyncSingleJob,
+	sync::{
+		get_diff_snapshot, get_local_history, restore_snapshot,
+		LocalHistoryLimits, RepoPathRef, Snapshot,
+	},
+	AsyncGitNotification, AsyncLocalHistoryJob,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Direction, Layout, Rect},
+	text::Span,
+	widgets::{Block, Borders, Cell, Clear, Row, Table, TableState},
+	Frame,
+};
+use std::cell::Cell as StdCell;
+
+/// snapshots of one file taken while it was changed in the workdir,
+/// the diff shows what changed since the selected one
+pub struct LocalHistoryPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	options: SharedOptions,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+	job: AsyncSingleJob<AsyncLocalHistoryJob>,
+	diff: DiffComponent,
+	path: String,
+	snapshots: Vec<Snapshot>,
+	table_state: StdCell<TableState>,
+	current_height: StdCell<usize>,
+	visible: bool,
+}
+
+impl LocalHistoryPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			options: env.options.clone(),
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+			job: AsyncSingleJob::new(env.sender_git.clone()),
+			diff: DiffComponent::new(env, true),
+			path: String::new(),
+			snapshots: Vec::new(),
+			table_state: StdCell::new(TableState::default()),
+			current_height: StdCell::new(0),
+			visible: false,
+		}
+	}
+
+	/// drops expired snapshots, even with recording turned off
+	pub fn prune(&self) {
+		self.job.spawn(AsyncLocalHistoryJob::new(
+			self.repo.borrow().clone(),
+			LocalHistoryLimits::default(),
+			false,
+		));
+	}
+
+	///
+	pub fn open(&mut self, path: String) -> Result<()> {
+		self.path = path;
+		self.table_state.get_mut().select(Some(0));
+		self.diff.focus(false);
+		self.show()?;
+
+		self.refresh()
+	}
+
+	/// the workdir changed: snapshots it if recording and updates the
+	/// diff to it
+	pub fn update(&mut self) {
+		if self.options.borrow().local_history() {
+			self.job.spawn(AsyncLocalHistoryJob::new(
+				self.repo.borrow().clone(),
+				LocalHistoryLimits::default(),
+				true,
+			));
+		}
+
+		if self.is_visible() {
+			self.update_diff();
+		}
+	}
+
+	///
+	pub fn update_git(
+		&mut self,
+		ev: AsyncGitNotification,
+	) -> Result<()> {
+		if ev == AsyncGitNotification::LocalHistory {
+			if let Some(Err(e)) =
+				self.job.take_last().and_then(|job| job.result())
+			{
+				log::error!("local history error: {}", e);
+			}
+
+			if self.is_visible() {
+				self.refresh()?;
+			}
+		}
+
+		Ok(())
+	}
+
+	fn refresh(&mut self) -> Result<()> {
+		self.snapshots =
+			get_local_history(&self.repo.borrow(), &self.path)?;
+
+		let max = self.snapshots.len().saturating_sub(1);
+		let table_state = self.table_state.get_mut();
+		table_state.select(Some(
+			table_state.selected().unwrap_or(0).min(max),
+		));
+
+		self.update_diff();
+
+		Ok(())
+	}
+
+	fn update_diff(&mut self) {
+		let diff = self.selected_snapshot().map(|snapshot| {
+			get_diff_snapshot(
+				&self.repo.borrow(),
+				snapshot,
+				Some(self.options.borrow().diff_options()),
+			)
+		});
+
+		match diff {
+			Some(Ok(diff)) => {
+				self.diff.update(self.path.clone(), false, diff);
+			}
+			Some(Err(e)) => {
+				self.diff.clear(false);
+				self.queue
+					.push(InternalEvent::ShowErrorMsg(e.to_string()));
+			}
+			None => self.diff.clear(false),
+		}
+	}
+
+	fn selected_snapshot(&self) -> Option<&Snapshot> {
+		let table_state = self.table_state.take();
+		let selected = table_state.selected();
+		self.table_state.set(table_state);
+
+		selected.and_then(|selected| self.snapshots.get(selected))
+	}
+
+	fn move_selection(&mut self, scroll_type: ScrollType) {
+		let old_selection =
+			self.table_state.get_mut().selected().unwrap_or(0);
+		let max_selection = self.snapshots.len().saturating_sub(1);
+		let page = self.current_height.get().saturating_sub(1);
+
+		let new_selection = match scroll_type {
+			ScrollType::Up => old_selection.saturating_sub(1),
+			ScrollType::Down => old_selection.saturating_add(1),
+			ScrollType::Home => 0,
+			ScrollType::End => max_selection,
+			ScrollType::PageUp => old_selection.saturating_sub(page),
+			ScrollType::PageDown => {
+				old_selection.saturating_add(page)
+			}
+			ScrollType::HalfPageUp => {
+				old_selection.saturating_sub(page / 2)
+			}
+			ScrollType::HalfPageDown => {
+				old_selection.saturating_add(page / 2)
+			}
+		}
+		.min(max_selection);
+
+		if new_selection != old_selection {
+			self.table_state.get_mut().select(Some(new_selection));
+			self.update_diff();
+		}
+	}
+
+	fn restore_selected(&mut self) -> Result<()> {
+		let Some(snapshot) = self.selected_snapshot().cloned() else {
+			return Ok(());
+		};
+
+		match restore_snapshot(&self.repo.borrow(), &snapshot) {
+			Ok(()) => {
+				self.queue.push(InternalEvent::ShowInfoMsg(
+					strings::local_history_restored(
+						&snapshot.path,
+						&time_to_string(snapshot.time, false),
+					),
+				));
+				self.queue
+					.push(InternalEvent::Update(NeedsUpdate::ALL));
+			}
+			Err(e) => {
+				self.queue
+					.push(InternalEvent::ShowErrorMsg(e.to_string()));
+			}
+		}
+
+		// the replaced content is the newest snapshot now
+		self.table_state.get_mut().select(Some(0));
+		self.refresh()
+	}
+
+	fn draw_list(&self, f: &mut Frame, area: Rect) {
+		let rows: Vec<Row> = self
+			.snapshots
+			.iter()
+			.map(|snapshot| {
+				Row::new(vec![Cell::from(Span::styled(
+					time_to_string(snapshot.time, false),
+					self.theme.commit_time(false),
+				))])
+			})
+			.collect();
+
+		let table = Table::new(rows, [Constraint::Percentage(100)])
+			.row_highlight_style(self.theme.text(true, true))
+			.block(
+				Block::default()
+					.borders(Borders::ALL)
+					.title(Span::styled(
+						strings::title_local_history(
+							&self.path,
+							self.snapshots.len(),
+							self.options.borrow().local_history(),
+						),
+						self.theme.title(!self.diff.focused()),
+					))
+					.border_style(
+						self.theme.block(!self.diff.focused()),
+					),
+			);
+
+		let mut table_state = self.table_state.take();
+
+		f.render_widget(Clear, area);
+		f.render_stateful_widget(table, area, &mut table_state);
+
+		draw_scrollbar(
+			f,
+			area,
+			&self.theme,
+			self.snapshots.len(),
+			table_state.selected().unwrap_or(0),
+			Orientation::Vertical,
+		);
+
+		self.table_state.set(table_state);
+		self.current_height
+			.set(area.height.saturating_sub(2).into());
+	}
+}
+
+impl DrawableComponent for LocalHistoryPopup {
+	fn draw(&self, f: &mut Frame, area: Rect) -> Result<()> {
+		if self.visible {
+			let percentages = if self.diff.focused() {
+				(0, 100)
+			} else {
+				(30, 70)
+			};
+
+			let chunks = Layout::default()
+				.direction(Direction::Horizontal)
+				.constraints([
+					Constraint::Percentage(percentages.0),
+					Constraint::Percentage(percentages.1),
+				])
+				.split(area);
+
+			f.render_widget(Clear, area);
+
+			self.draw_list(f, chunks[0]);
+			self.diff.draw(f, chunks[1])?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for LocalHistoryPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			out.push(
+				CommandInfo::new(
+					strings::commands::close_popup(&self.key_config),
+					true,
+					true,
+				)
+				.order(1),
+			);
+			out.push(CommandInfo::new(
+				strings::commands::local_history_restore(
+					&self.key_config,
+				),
+				self.selected_snapshot().is_some(),
+				!self.diff.focused(),
+			));
+			out.push(CommandInfo::new(
+				strings::commands::diff_focus_right(&self.key_config),
+				self.selected_snapshot().is_some(),
+				!self.diff.focused(),
+			));
+
+			self.diff.commands(out, force_all);
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, event: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if event_pump(event, &mut [&mut self.diff])?.is_consumed()
+			{
+				return Ok(EventState::Consumed);
+			}
+
+			if let Event::Key(key) = event {
+				if key_match(key, self.key_config.keys.exit_popup) {
+					if self.diff.focused() {
+						self.diff.focus(false);
+					} else {
+						self.hide();
+					}
+				} else if key_match(
+					key,
+					self.key_config.keys.move_right,
+				) && self.selected_snapshot().is_some()
+				{
+					self.diff.focus(true);
+				} else if key_match(
+					key,
+					self.key_config.keys.local_history_restore,
+				) {
+					self.restore_selected()?;
+				} else if key_match(key, self.key_config.keys.move_up)
+				{
+					self.move_selection(ScrollType::Up);
+				} else if key_match(
+					key,
+					self.key_config.keys.move_down,
+				) {
+					self.move_selection(ScrollType::Down);
+				} else if key_match(
+					key,
+					self.key_config.keys.shift_up,
+				) || key_match(
+					key,
+					self.key_config.keys.home,
+				) {
+					self.move_selection(ScrollType::Home);
+				} else if key_match(
+					key,
+					self.key_config.keys.shift_down,
+				) || key_match(
+					key,
+					self.key_config.keys.end,
+				) {
+					self.move_selection(ScrollType::End);
+				} else if key_match(key, self.key_config.keys.page_up)
+				{
+					self.move_selection(ScrollType::PageUp);
+				} else if key_match(
+					key,
+					self.key_config.keys.page_down,
+				) {
+					self.move_selection(ScrollType::PageDown);
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
@@ -0,0 +1,232 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, InputType, TextInputComponent,
+};
+use crate::{
+	app::Environment,
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::LogFilterCriteria;
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Direction, Layout, Margin, Rect},
+	text::Span,
+	widgets::{Block, Borders, Clear, Paragraph},
+	Frame,
+};
+
+const LABEL_WIDTH: u16 = 9;
+
+/// message, author and path to filter the log by, all of the ones
+/// entered have to match
+pub struct LogFilterPopup {
+	queue: Queue,
+	visible: bool,
+	inputs: [TextInputComponent; 3],
+	selection: usize,
+	key_config: SharedKeyConfig,
+	theme: SharedTheme,
+}
+
+impl LogFilterPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		let input = |msg: &str| {
+			let mut input =
+				TextInputComponent::new(env, "", msg, false)
+					.with_input_type(InputType::Singleline);
+			input.embed();
+			input
+		};
+
+		Self {
+			queue: env.queue.clone(),
+			visible: false,
+			inputs: [
+				input(strings::LOG_FILTER_MESSAGE_MSG),
+				input(strings::LOG_FILTER_AUTHOR_MSG),
+				input(strings::LOG_FILTER_PATH_MSG),
+			],
+			selection: 0,
+			key_config: env.key_config.clone(),
+			theme: env.theme.clone(),
+		}
+	}
+
+	/// starts out with the filter shown now to refine it
+	pub fn open(
+		&mut self,
+		criteria: LogFilterCriteria,
+	) -> Result<()> {
+		let LogFilterCriteria {
+			message,
+			author,
+			path,
+		} = criteria;
+
+		for (input, text) in
+			self.inputs.iter_mut().zip([message, author, path])
+		{
+			input.set_text(text);
+			input.show()?;
+		}
+		self.select(0);
+
+		self.show()
+	}
+
+	fn select(&mut self, selection: usize) {
+		self.selection = selection % self.inputs.len();
+
+		for (index, input) in self.inputs.iter_mut().enumerate() {
+			input.enabled(index == self.selection);
+		}
+	}
+
+	fn criteria(&self) -> LogFilterCriteria {
+		let [message, author, path] = &self.inputs;
+		let text = |input: &TextInputComponent| {
+			input.get_text().trim().to_string()
+		};
+
+		LogFilterCriteria {
+			message: text(message),
+			author: text(author),
+			path: text(path),
+		}
+	}
+
+	fn confirm(&mut self) {
+		self.hide();
+		self.queue.push(InternalEvent::LogFilter(self.criteria()));
+	}
+}
+
+impl DrawableComponent for LogFilterPopup {
+	fn draw(&self, f: &mut Frame, area: Rect) -> Result<()> {
+		const SIZE: (u16, u16) = (60, 5);
+
+		if !self.is_visible() {
+			return Ok(());
+		}
+
+		let area = ui::centered_rect_absolute(SIZE.0, SIZE.1, area);
+
+		f.render_widget(Clear, area);
+		f.render_widget(
+			Block::default()
+				.borders(Borders::all())
+				.style(self.theme.title(true))
+				.title(Span::styled(
+					strings::POPUP_TITLE_LOG_FILTER,
+					self.theme.title(true),
+				)),
+			area,
+		);
+
+		let rows = Layout::default()
+			.direction(Direction::Vertical)
+			.constraints([Constraint::Length(1); 3])
+			.split(area.inner(Margin {
+				horizontal: 1,
+				vertical: 1,
+			}));
+
+		for (index, (row, label)) in
+			rows.iter().zip(strings::LOG_FILTER_LABELS).enumerate()
+		{
+			let columns = Layout::default()
+				.direction(Direction::Horizontal)
+				.constraints([
+					Constraint::Length(LABEL_WIDTH),
+					Constraint::Min(1),
+				])
+				.split(*row);
+
+			f.render_widget(
+				Paragraph::new(label).style(
+					self.theme.text(index == self.selection, false),
+				),
+				columns[0],
+			);
+			self.inputs[index].draw(f, columns[1])?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for LogFilterPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			out.push(
+				CommandInfo::new(
+					strings::commands::close_popup(&self.key_config),
+					true,
+					true,
+				)
+				.order(1),
+			);
+			out.push(
+				CommandInfo::new(
+					strings::commands::scroll_popup(&self.key_config),
+					true,
+					true,
+				)
+				.order(1),
+			);
+			out.push(CommandInfo::new(
+				strings::commands::confirm_action(&self.key_config),
+				true,
+				self.visible,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if !self.is_visible() {
+			return Ok(EventState::NotConsumed);
+		}
+
+		if let Event::Key(key) = ev {
+			if key_match(key, self.key_config.keys.exit_popup) {
+				self.hide();
+			} else if key_match(key, self.key_config.keys.enter) {
+				self.confirm();
+			} else if key_match(key, self.key_config.keys.popup_up) {
+				self.select(self.selection + self.inputs.len() - 1);
+			} else if key_match(key, self.key_config.keys.popup_down)
+			{
+				self.select(self.selection + 1);
+			} else {
+				self.inputs[self.selection].event(ev)?;
+			}
+		}
+
+		Ok(EventState::Consumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
@@ -0,0 +1,283 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState,
+};
+use crate::{
+	app::Environment,
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{
+	cached,
+	sync::{self, BranchType, MergeMode, MergeOutcome, RepoPathRef},
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Alignment, Rect},
+	text::{Line, Span},
+	widgets::{Block, Borders, Clear, Paragraph},
+	Frame,
+};
+
+const fn mode_to_string(
+	mode: MergeMode,
+) -> (&'static str, &'static str) {
+	const MERGE_MODE_DESC_FF: &str =
+		" Move the branch if possible, merge commit otherwise";
+	const MERGE_MODE_DESC_NO_FF: &str =
+		" Always create a merge commit (--no-ff)";
+	const MERGE_MODE_DESC_SQUASH: &str =
+		" Stage the changes for a single commit (--squash)";
+
+	match mode {
+		MergeMode::FastForward => {
+			("Fast-forward", MERGE_MODE_DESC_FF)
+		}
+		MergeMode::NoFastForward => {
+			("No fast-forward", MERGE_MODE_DESC_NO_FF)
+		}
+		MergeMode::Squash => ("Squash", MERGE_MODE_DESC_SQUASH),
+	}
+}
+
+/// merges a branch into the checked out one, choosing how
+pub struct MergeBranchPopup {
+	queue: Queue,
+	repo: RepoPathRef,
+	branch: String,
+	branch_type: BranchType,
+	mode: MergeMode,
+	git_branch_name: cached::BranchName,
+	visible: bool,
+	key_config: SharedKeyConfig,
+	theme: SharedTheme,
+}
+
+impl MergeBranchPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			queue: env.queue.clone(),
+			repo: env.repo.clone(),
+			branch: String::new(),
+			branch_type: BranchType::Local,
+			mode: MergeMode::FastForward,
+			git_branch_name: cached::BranchName::new(
+				env.repo.clone(),
+			),
+			visible: false,
+			key_config: env.key_config.clone(),
+			theme: env.theme.clone(),
+		}
+	}
+
+	fn get_text(&self) -> Vec<Line<'_>> {
+		let label = |text: &'static str| {
+			Span::styled(text, self.theme.text(true, false))
+		};
+
+		let (mode_name, mode_desc) = mode_to_string(self.mode);
+
+		vec![
+			Line::from(vec![
+				label("Into: "),
+				Span::styled(
+					self.git_branch_name.last().unwrap_or_default(),
+					self.theme.branch(false, true),
+				),
+			]),
+			Line::from(vec![
+				label("Merge: "),
+				Span::styled(
+					self.branch.as_str(),
+					self.theme.branch(false, false),
+				),
+			]),
+			Line::from(vec![
+				label("How: "),
+				Span::styled(mode_name, self.theme.text(true, true)),
+				Span::styled(mode_desc, self.theme.text(true, false)),
+			]),
+		]
+	}
+
+	///
+	pub fn open(
+		&mut self,
+		branch: String,
+		branch_type: BranchType,
+	) -> Result<()> {
+		self.branch = branch;
+		self.branch_type = branch_type;
+		self.mode = MergeMode::FastForward;
+		self.git_branch_name.lookup().ok();
+
+		self.show()
+	}
+
+	fn merge(&mut self) {
+		self.hide();
+
+		let res = sync::merge_branch_with_mode(
+			&self.repo.borrow(),
+			&self.branch,
+			self.branch_type,
+			self.mode,
+		);
+
+		match res {
+			Ok(MergeOutcome::UpToDate) => {
+				self.queue.push(InternalEvent::ShowInfoMsg(
+					strings::merge_up_to_date(&self.branch),
+				));
+			}
+			Ok(MergeOutcome::FastForwarded) => {
+				self.queue.push(InternalEvent::ShowToast(
+					strings::merge_fast_forwarded(&self.branch),
+				));
+			}
+			Ok(MergeOutcome::Merged | MergeOutcome::Squashed) => {
+				self.queue.push(InternalEvent::OpenCommit);
+			}
+			Ok(MergeOutcome::Conflicted) => {
+				self.queue.push(InternalEvent::TabSwitchStatus);
+			}
+			Err(e) => {
+				log::error!("merge branch: {}", e);
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("merge branch error:\n{e}"),
+				));
+			}
+		}
+
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+	}
+
+	fn change_mode(&mut self, incr: bool) {
+		self.mode = if incr {
+			match self.mode {
+				MergeMode::FastForward => MergeMode::NoFastForward,
+				MergeMode::NoFastForward => MergeMode::Squash,
+				MergeMode::Squash => MergeMode::FastForward,
+			}
+		} else {
+			match self.mode {
+				MergeMode::FastForward => MergeMode::Squash,
+				MergeMode::NoFastForward => MergeMode::FastForward,
+				MergeMode::Squash => MergeMode::NoFastForward,
+			}
+		};
+	}
+}
+
+impl DrawableComponent for MergeBranchPopup {
+	fn draw(&self, f: &mut Frame, area: Rect) -> Result<()> {
+		if self.is_visible() {
+			const SIZE: (u16, u16) = (70, 5);
+			let area =
+				ui::centered_rect_absolute(SIZE.0, SIZE.1, area);
+
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Paragraph::new(self.get_text())
+					.block(
+						Block::default()
+							.borders(Borders::ALL)
+							.title(Span::styled(
+								strings::POPUP_TITLE_MERGE_BRANCH,
+								self.theme.title(true),
+							))
+							.border_style(self.theme.block(true)),
+					)
+					.alignment(Alignment::Left),
+				area,
+			);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for MergeBranchPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			out.push(
+				CommandInfo::new(
+					strings::commands::close_popup(&self.key_config),
+					true,
+					true,
+				)
+				.order(1),
+			);
+
+			out.push(
+				CommandInfo::new(
+					strings::commands::merge_branch_confirm(
+						&self.key_config,
+					),
+					true,
+					true,
+				)
+				.order(1),
+			);
+
+			out.push(
+				CommandInfo::new(
+					strings::commands::merge_branch_mode(
+						&self.key_config,
+					),
+					true,
+					true,
+				)
+				.order(1),
+			);
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, event: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if let Event::Key(key) = &event {
+				if key_match(key, self.key_config.keys.exit_popup) {
+					self.hide();
+				} else if key_match(
+					key,
+					self.key_config.keys.move_down,
+				) {
+					self.change_mode(true);
+				} else if key_match(key, self.key_config.keys.move_up)
+				{
+					self.change_mode(false);
+				} else if key_match(key, self.key_config.keys.enter) {
+					self.merge();
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
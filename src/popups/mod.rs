@@ -1,62 +1,108 @@
 mod blame_file;
+mod bookmark_commit;
+mod bookmarks;
 mod branchlist;
 mod commit;
+mod compare_branches;
 mod compare_commits;
+mod compare_stash;
 mod confirm;
+mod conflict_editor;
 mod create_branch;
+mod create_pull_request;
 mod create_remote;
+mod create_worktree;
+mod deepen_history;
 mod externaleditor;
 mod fetch;
+mod file_grep;
 mod file_revlog;
+mod fix_staged;
+mod fixup;
 mod fuzzy_find;
 mod help;
+mod ignore;
 mod inspect_commit;
+mod key_conflicts;
+mod local_history;
+mod log_filter;
 mod log_search;
+mod merge_branch;
 mod msg;
+mod oplog;
 mod options;
+mod process;
 mod pull;
 mod push;
 mod push_tags;
+mod reflog;
 mod remotelist;
 mod rename_branch;
 mod rename_remote;
+mod replace_refs;
 mod reset;
+mod resolve_conflict;
 mod revision_files;
 mod stashmsg;
 mod submodules;
 mod tag_commit;
 mod taglist;
 mod update_remote_url;
+mod worktrees;
 
 pub use blame_file::{BlameFileOpen, BlameFilePopup};
+pub use bookmark_commit::BookmarkCommitPopup;
+pub use bookmarks::BookmarksPopup;
 pub use branchlist::BranchListPopup;
 pub use commit::CommitPopup;
+pub use compare_branches::{
+	CompareBranchesOpen, CompareBranchesPopup,
+};
 pub use compare_commits::CompareCommitsPopup;
+pub use compare_stash::CompareStashPopup;
 pub use confirm::ConfirmPopup;
+pub use conflict_editor::ConflictEditorPopup;
 pub use create_branch::CreateBranchPopup;
+pub use create_pull_request::CreatePullRequestPopup;
 pub use create_remote::CreateRemotePopup;
+pub use create_worktree::CreateWorktreePopup;
+pub use deepen_history::DeepenHistoryPopup;
 pub use externaleditor::ExternalEditorPopup;
 pub use fetch::FetchPopup;
+pub use file_grep::FileGrepPopup;
 pub use file_revlog::{FileRevOpen, FileRevlogPopup};
+pub use fix_staged::FixStagedPopup;
+pub use fixup::FixupPopup;
 pub use fuzzy_find::FuzzyFindPopup;
 pub use help::HelpPopup;
+pub use ignore::IgnorePopup;
 pub use inspect_commit::{InspectCommitOpen, InspectCommitPopup};
+pub use key_conflicts::KeyConflictsPopup;
+pub use local_history::LocalHistoryPopup;
+pub use log_filter::LogFilterPopup;
 pub use log_search::LogSearchPopupPopup;
+pub use merge_branch::MergeBranchPopup;
 pub use msg::MsgPopup;
+pub use oplog::OpLogPopup;
 pub use options::{AppOption, OptionsPopup};
+pub use process::ProcessPopup;
 pub use pull::PullPopup;
-pub use push::PushPopup;
+pub use push::{PushAfterCreate, PushPopup, PushTarget};
 pub use push_tags::PushTagsPopup;
+pub use reflog::ReflogPopup;
 pub use remotelist::RemoteListPopup;
 pub use rename_branch::RenameBranchPopup;
 pub use rename_remote::RenameRemotePopup;
+pub use replace_refs::ReplaceRefsPopup;
 pub use reset::ResetPopup;
+pub use resolve_conflict::ResolveConflictPopup;
 pub use revision_files::{FileTreeOpen, RevisionFilesPopup};
 pub use stashmsg::StashMsgPopup;
 pub use submodules::SubmodulesListPopup;
 pub use tag_commit::TagCommitPopup;
 pub use taglist::TagListPopup;
 pub use update_remote_url::UpdateRemoteUrlPopup;
+pub use worktrees::WorktreesPopup;
 
 use crate::ui::style::Theme;
 use ratatui::{
@@ -6,7 +6,9 @@ use crate::strings::order;
 use crate::{
 	app::Environment,
 	keys::{key_match, SharedKeyConfig},
-	strings, ui,
+	queue::{InternalEvent, Queue},
+	string_utils::last_url,
+	strings, try_or_popup, ui,
 };
 use anyhow::Result;
 use crossterm::event::Event;
@@ -20,6 +22,7 @@ use ratatui::{
 use ui::style::SharedTheme;
 
 pub struct MsgPopup {
+	queue: Queue,
 	title: String,
 	msg: String,
 	visible: bool,
@@ -128,6 +131,11 @@ impl Component for MsgPopup {
 			)
 			.order(order::NAV),
 		);
+		out.push(CommandInfo::new(
+			strings::commands::copy_url(&self.key_config),
+			true,
+			self.visible && last_url(&self.msg).is_some(),
+		));
 
 		visibility_blocking(self)
 	}
@@ -145,6 +153,12 @@ impl Component for MsgPopup {
 				} else if key_match(e, self.key_config.keys.popup_up)
 				{
 					self.scroll.move_top(ScrollType::Up);
+				} else if key_match(e, self.key_config.keys.copy) {
+					try_or_popup!(
+						self,
+						strings::POPUP_FAIL_COPY,
+						self.copy_url()
+					);
 				}
 			}
 			Ok(EventState::Consumed)
@@ -171,6 +185,7 @@ impl Component for MsgPopup {
 impl MsgPopup {
 	pub fn new(env: &Environment) -> Self {
 		Self {
+			queue: env.queue.clone(),
 			title: String::new(),
 			msg: String::new(),
 			visible: false,
@@ -191,6 +206,18 @@ impl MsgPopup {
 		self.show()
 	}
 
+	/// a link in the message, like the page of a pull request
+	fn copy_url(&self) -> Result<()> {
+		if let Some(url) = last_url(&self.msg) {
+			crate::clipboard::copy_string(url)?;
+			self.queue.push(InternalEvent::ShowInfoMsg(
+				strings::copy_success(url),
+			));
+		}
+
+		Ok(())
+	}
+
 	///
 	pub fn show_error(&mut self, msg: &str) -> Result<()> {
 		self.set_new_msg(
@@ -0,0 +1,404 @@
+use crate::components::{
+	time_to_string, visibility_blocking, CommandBlocking,
+	CommandInfo, Component, DrawableComponent, EventState,
+};
+use crate::{
+	app::Environment,
+	components::ScrollType,
+	keys::{key_match, SharedKeyConfig},
+	popups::InspectCommitOpen,
+	queue::{InternalEvent, Queue, StackablePopupOpen},
+	strings,
+	ui::{self, Size},
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	get_commit_info, get_oplog, CommitId, OpKind, OpLogEntry,
+	RepoPathRef,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Margin, Rect},
+	text::Span,
+	widgets::{
+		Block, BorderType, Borders, Cell, Clear, Row, Table,
+		TableState,
+	},
+	Frame,
+};
+use ui::style::SharedTheme;
+
+///
+pub struct OpLogPopup {
+	repo: RepoPathRef,
+	theme: SharedTheme,
+	queue: Queue,
+	entries: Vec<OpLogEntry>,
+	filter: Option<OpKind>,
+	visible: bool,
+	table_state: std::cell::Cell<TableState>,
+	current_height: std::cell::Cell<usize>,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for OpLogPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.visible {
+			const PERCENT_SIZE: Size = Size::new(80, 50);
+			const MIN_SIZE: Size = Size::new(60, 20);
+
+			let area = ui::centered_rect(
+				PERCENT_SIZE.width,
+				PERCENT_SIZE.height,
+				f.area(),
+			);
+			let area =
+				ui::rect_inside(MIN_SIZE, f.area().into(), area);
+			let area = area.intersection(rect);
+
+			let target_width = self
+				.filtered()
+				.fold(0, |acc, entry| acc.max(entry.target.len()));
+
+			let constraints = [
+				// time
+				Constraint::Length(19),
+				// kind
+				Constraint::Length(12),
+				// target
+				Constraint::Length(target_width.try_into()?),
+				// ids
+				Constraint::Length(16),
+				// outcome
+				Constraint::Percentage(100),
+			];
+
+			let rows: Vec<Row> =
+				self.filtered().map(|e| self.get_row(e)).collect();
+			let number_of_rows = rows.len();
+
+			let table = Table::new(rows, constraints)
+				.column_spacing(1)
+				.row_highlight_style(self.theme.text(true, true))
+				.block(
+					Block::default()
+						.borders(Borders::ALL)
+						.title(Span::styled(
+							strings::title_oplog(
+								self.filter.map(OpKind::name),
+							),
+							self.theme.title(true),
+						))
+						.border_style(self.theme.block(true))
+						.border_type(BorderType::Thick),
+				);
+
+			let mut table_state = self.table_state.take();
+
+			f.render_widget(Clear, area);
+			f.render_stateful_widget(table, area, &mut table_state);
+
+			let area = area.inner(Margin {
+				vertical: 1,
+				horizontal: 0,
+			});
+
+			ui::draw_scrollbar(
+				f,
+				area,
+				&self.theme,
+				number_of_rows,
+				table_state.selected().unwrap_or(0),
+				ui::Orientation::Vertical,
+			);
+
+			self.table_state.set(table_state);
+			self.current_height.set(area.height.into());
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for OpLogPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			if !force_all {
+				out.clear();
+			}
+
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::oplog_filter(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::oplog_inspect(&self.key_config),
+				self.selected_entry().is_some_and(|entry| {
+					entry.old_id.is_some() || entry.new_id.is_some()
+				}),
+				true,
+			));
+		}
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, event: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(key) = event {
+				if key_match(key, self.key_config.keys.exit_popup) {
+					self.hide();
+				} else if key_match(key, self.key_config.keys.move_up)
+				{
+					self.move_selection(ScrollType::Up);
+				} else if key_match(
+					key,
+					self.key_config.keys.move_down,
+				) {
+					self.move_selection(ScrollType::Down);
+				} else if key_match(
+					key,
+					self.key_config.keys.shift_up,
+				) || key_match(
+					key,
+					self.key_config.keys.home,
+				) {
+					self.move_selection(ScrollType::Home);
+				} else if key_match(
+					key,
+					self.key_config.keys.shift_down,
+				) || key_match(
+					key,
+					self.key_config.keys.end,
+				) {
+					self.move_selection(ScrollType::End);
+				} else if key_match(
+					key,
+					self.key_config.keys.page_down,
+				) {
+					self.move_selection(ScrollType::PageDown);
+				} else if key_match(key, self.key_config.keys.page_up)
+				{
+					self.move_selection(ScrollType::PageUp);
+				} else if key_match(
+					key,
+					self.key_config.keys.oplog_filter,
+				) {
+					self.cycle_filter();
+				} else if key_match(key, self.key_config.keys.enter) {
+					self.inspect_selected();
+				}
+			}
+
+			Ok(EventState::Consumed)
+		} else {
+			Ok(EventState::NotConsumed)
+		}
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
+
+impl OpLogPopup {
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			theme: env.theme.clone(),
+			queue: env.queue.clone(),
+			entries: Vec::new(),
+			filter: None,
+			visible: false,
+			table_state: std::cell::Cell::new(TableState::default()),
+			current_height: std::cell::Cell::new(0),
+			key_config: env.key_config.clone(),
+			repo: env.repo.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self) -> Result<()> {
+		self.entries = get_oplog(&self.repo.borrow())?;
+		self.table_state.get_mut().select(Some(0));
+		self.show()?;
+
+		Ok(())
+	}
+
+	fn filtered(&self) -> impl Iterator<Item = &OpLogEntry> {
+		self.entries.iter().filter(|entry| {
+			self.filter.map_or(true, |kind| entry.kind == kind)
+		})
+	}
+
+	/// `None` -> every kind in turn -> `None`
+	fn cycle_filter(&mut self) {
+		self.filter = self.filter.map_or_else(
+			|| OpKind::ALL.first().copied(),
+			|kind| {
+				OpKind::ALL
+					.iter()
+					.position(|k| *k == kind)
+					.and_then(|index| OpKind::ALL.get(index + 1))
+					.copied()
+			},
+		);
+
+		self.table_state.get_mut().select(Some(0));
+	}
+
+	///
+	fn move_selection(&self, scroll_type: ScrollType) -> bool {
+		let mut table_state = self.table_state.take();
+
+		let old_selection = table_state.selected().unwrap_or(0);
+		let max_selection = self.filtered().count().saturating_sub(1);
+
+		let new_selection = match scroll_type {
+			ScrollType::Up => old_selection.saturating_sub(1),
+			ScrollType::Down => {
+				old_selection.saturating_add(1).min(max_selection)
+			}
+			ScrollType::Home => 0,
+			ScrollType::End => max_selection,
+			ScrollType::PageUp => old_selection.saturating_sub(
+				self.current_height.get().saturating_sub(1),
+			),
+			ScrollType::PageDown => old_selection
+				.saturating_add(
+					self.current_height.get().saturating_sub(1),
+				)
+				.min(max_selection),
+			ScrollType::HalfPageUp => old_selection.saturating_sub(
+				(self.current_height.get().saturating_sub(1)) / 2,
+			),
+			ScrollType::HalfPageDown => old_selection
+				.saturating_add(
+					(self.current_height.get().saturating_sub(1)) / 2,
+				)
+				.min(max_selection),
+		};
+
+		let needs_update = new_selection != old_selection;
+
+		table_state.select(Some(new_selection));
+		self.table_state.set(table_state);
+
+		needs_update
+	}
+
+	fn get_row(&self, entry: &OpLogEntry) -> Row<'_> {
+		let id = |id: Option<CommitId>| {
+			id.map_or_else(
+				|| String::from("-"),
+				|id| id.get_short_string(),
+			)
+		};
+
+		let (outcome, outcome_style) =
+			entry.error.as_ref().map_or_else(
+				|| (String::from("ok"), self.theme.text(true, false)),
+				|error| (error.clone(), self.theme.text_danger()),
+			);
+
+		let cells: Vec<Cell> = vec![
+			Cell::from(time_to_string(entry.time, false))
+				.style(self.theme.commit_time(false)),
+			Cell::from(entry.kind.name())
+				.style(self.theme.commit_author(false)),
+			Cell::from(entry.target.clone())
+				.style(self.theme.text(true, false)),
+			Cell::from(format!(
+				"{}..{}",
+				id(entry.old_id),
+				id(entry.new_id)
+			))
+			.style(self.theme.commit_hash(false)),
+			Cell::from(outcome).style(outcome_style),
+		];
+
+		Row::new(cells)
+	}
+
+	fn selected_entry(&self) -> Option<&OpLogEntry> {
+		let table_state = self.table_state.take();
+
+		let entry = table_state
+			.selected()
+			.and_then(|selected| self.filtered().nth(selected));
+
+		self.table_state.set(table_state);
+
+		entry
+	}
+
+	fn inspect_selected(&mut self) {
+		let Some(entry) = self.selected_entry() else {
+			return;
+		};
+
+		if entry.old_id.is_none() && entry.new_id.is_none() {
+			return;
+		}
+
+		match self.selected_commit() {
+			Some(commit_id) => {
+				self.hide();
+				self.queue.push(InternalEvent::OpenPopup(
+					StackablePopupOpen::InspectCommit(
+						InspectCommitOpen::new(commit_id),
+					),
+				));
+			}
+			None => {
+				self.queue.push(InternalEvent::ShowInfoMsg(
+					strings::oplog_commit_gone(),
+				));
+			}
+		}
+	}
+
+	/// the commit to recover for the selected entry: the created one
+	/// for commits, the previous target for everything else, falling
+	/// back to the other one if it no longer exists
+	fn selected_commit(&self) -> Option<CommitId> {
+		let entry = self.selected_entry()?;
+
+		let ids = if entry.kind == OpKind::Commit {
+			[entry.new_id, entry.old_id]
+		} else {
+			[entry.old_id, entry.new_id]
+		};
+
+		ids.into_iter().flatten().find(|id| {
+			get_commit_info(&self.repo.borrow(), id).is_ok()
+		})
+	}
+}
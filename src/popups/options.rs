@@ -5,7 +5,7 @@ use crate::{
 		CommandInfo, Component, DrawableComponent, EventState,
 	},
 	keys::{key_match, SharedKeyConfig},
-	options::SharedOptions,
+	options::{InitialCollapse, SharedOptions},
 	queue::{InternalEvent, Queue},
 	strings,
 	ui::{self, style::SharedTheme},
@@ -24,9 +24,18 @@ use ratatui::{
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum AppOption {
 	StatusShowUntracked,
+	TreeInitialCollapse,
+	StatusFocusConflicts,
+	StatusSubmoduleSections,
 	DiffIgnoreWhitespaces,
 	DiffContextLines,
 	DiffInterhunkLines,
+	DiffWordChanges,
+	CommitVerbose,
+	CommitBehindNotice,
+	CommitHardWrap,
+	LocalHistory,
+	VimMode,
 }
 
 pub struct OptionsPopup {
@@ -55,6 +64,10 @@ impl OptionsPopup {
 		let mut txt: Vec<Line> = Vec::with_capacity(10);
 
 		self.add_status(&mut txt, width);
+		self.add_diff(&mut txt, width);
+		self.add_commit(&mut txt, width);
+		self.add_local_history(&mut txt, width);
+		self.add_keys(&mut txt, width);
 
 		txt
 	}
@@ -74,6 +87,39 @@ impl OptionsPopup {
 			},
 			self.is_select(AppOption::StatusShowUntracked),
 		);
+		self.add_entry(
+			txt,
+			width,
+			"Initially collapsed",
+			match self.options.borrow().tree_initial_collapse() {
+				InitialCollapse::Expanded => "None",
+				InitialCollapse::Collapsed => "All",
+				InitialCollapse::TopLevelOnly => "Below top level",
+				InitialCollapse::Remember => "Remember",
+			},
+			self.is_select(AppOption::TreeInitialCollapse),
+		);
+		self.add_entry(
+			txt,
+			width,
+			"Focus conflicts",
+			&self.options.borrow().focus_conflicts().to_string(),
+			self.is_select(AppOption::StatusFocusConflicts),
+		);
+		self.add_entry(
+			txt,
+			width,
+			"Submodule sections",
+			&self
+				.options
+				.borrow()
+				.status_submodule_sections()
+				.to_string(),
+			self.is_select(AppOption::StatusSubmoduleSections),
+		);
+	}
+
+	fn add_diff(&self, txt: &mut Vec<Line>, width: u16) {
 		Self::add_header(txt, "");
 
 		let diff = self.options.borrow().diff_options();
@@ -99,6 +145,70 @@ impl OptionsPopup {
 			&diff.interhunk_lines.to_string(),
 			self.is_select(AppOption::DiffInterhunkLines),
 		);
+		self.add_entry(
+			txt,
+			width,
+			"Highlight changed words",
+			&self.options.borrow().diff_word_changes().to_string(),
+			self.is_select(AppOption::DiffWordChanges),
+		);
+	}
+
+	fn add_commit(&self, txt: &mut Vec<Line>, width: u16) {
+		Self::add_header(txt, "");
+
+		Self::add_header(txt, "Commit");
+		self.add_entry(
+			txt,
+			width,
+			"Verbose",
+			match self.options.borrow().commit_verbose() {
+				None => "Gitconfig",
+				Some(true) => "true",
+				Some(false) => "false",
+			},
+			self.is_select(AppOption::CommitVerbose),
+		);
+		self.add_entry(
+			txt,
+			width,
+			"Behind upstream notice",
+			&self.options.borrow().commit_behind_notice().to_string(),
+			self.is_select(AppOption::CommitBehindNotice),
+		);
+		self.add_entry(
+			txt,
+			width,
+			"Hard wrap body",
+			&self.options.borrow().commit_hard_wrap().to_string(),
+			self.is_select(AppOption::CommitHardWrap),
+		);
+	}
+
+	fn add_local_history(&self, txt: &mut Vec<Line>, width: u16) {
+		Self::add_header(txt, "");
+
+		Self::add_header(txt, "Local history");
+		self.add_entry(
+			txt,
+			width,
+			"Record snapshots",
+			&self.options.borrow().local_history().to_string(),
+			self.is_select(AppOption::LocalHistory),
+		);
+	}
+
+	fn add_keys(&self, txt: &mut Vec<Line>, width: u16) {
+		Self::add_header(txt, "");
+
+		Self::add_header(txt, "Keys");
+		self.add_entry(
+			txt,
+			width,
+			"Vim mode",
+			&self.options.borrow().vim_mode().to_string(),
+			self.is_select(AppOption::VimMode),
+		);
 	}
 
 	fn is_select(&self, kind: AppOption) -> bool {
@@ -137,11 +247,18 @@ impl OptionsPopup {
 	fn move_selection(&mut self, up: bool) {
 		if up {
 			self.selection = match self.selection {
-				AppOption::StatusShowUntracked => {
-					AppOption::DiffInterhunkLines
+				AppOption::StatusShowUntracked => AppOption::VimMode,
+				AppOption::TreeInitialCollapse => {
+					AppOption::StatusShowUntracked
+				}
+				AppOption::StatusFocusConflicts => {
+					AppOption::TreeInitialCollapse
+				}
+				AppOption::StatusSubmoduleSections => {
+					AppOption::StatusFocusConflicts
 				}
 				AppOption::DiffIgnoreWhitespaces => {
-					AppOption::StatusShowUntracked
+					AppOption::StatusSubmoduleSections
 				}
 				AppOption::DiffContextLines => {
 					AppOption::DiffIgnoreWhitespaces
@@ -149,10 +266,33 @@ impl OptionsPopup {
 				AppOption::DiffInterhunkLines => {
 					AppOption::DiffContextLines
 				}
+				AppOption::DiffWordChanges => {
+					AppOption::DiffInterhunkLines
+				}
+				AppOption::CommitVerbose => {
+					AppOption::DiffWordChanges
+				}
+				AppOption::CommitBehindNotice => {
+					AppOption::CommitVerbose
+				}
+				AppOption::CommitHardWrap => {
+					AppOption::CommitBehindNotice
+				}
+				AppOption::LocalHistory => AppOption::CommitHardWrap,
+				AppOption::VimMode => AppOption::LocalHistory,
 			};
 		} else {
 			self.selection = match self.selection {
 				AppOption::StatusShowUntracked => {
+					AppOption::TreeInitialCollapse
+				}
+				AppOption::TreeInitialCollapse => {
+					AppOption::StatusFocusConflicts
+				}
+				AppOption::StatusFocusConflicts => {
+					AppOption::StatusSubmoduleSections
+				}
+				AppOption::StatusSubmoduleSections => {
 					AppOption::DiffIgnoreWhitespaces
 				}
 				AppOption::DiffIgnoreWhitespaces => {
@@ -162,12 +302,95 @@ impl OptionsPopup {
 					AppOption::DiffInterhunkLines
 				}
 				AppOption::DiffInterhunkLines => {
-					AppOption::StatusShowUntracked
+					AppOption::DiffWordChanges
+				}
+				AppOption::DiffWordChanges => {
+					AppOption::CommitVerbose
 				}
+				AppOption::CommitVerbose => {
+					AppOption::CommitBehindNotice
+				}
+				AppOption::CommitBehindNotice => {
+					AppOption::CommitHardWrap
+				}
+				AppOption::CommitHardWrap => AppOption::LocalHistory,
+				AppOption::LocalHistory => AppOption::VimMode,
+				AppOption::VimMode => AppOption::StatusShowUntracked,
 			};
 		}
 	}
 
+	fn switch_commit_verbose(&self, right: bool) {
+		let verbose = self.options.borrow().commit_verbose();
+
+		let verbose = match (verbose, right) {
+			(None, true) | (Some(false), false) => Some(true),
+			(Some(true), true) | (None, false) => Some(false),
+			(Some(false), true) | (Some(true), false) => None,
+		};
+
+		self.options.borrow_mut().set_commit_verbose(verbose);
+	}
+
+	fn toggle_focus_conflicts(&self) {
+		let focus = self.options.borrow().focus_conflicts();
+		self.options.borrow_mut().set_focus_conflicts(!focus);
+	}
+
+	fn toggle_submodule_sections(&self) {
+		let sections =
+			self.options.borrow().status_submodule_sections();
+		self.options
+			.borrow_mut()
+			.set_status_submodule_sections(!sections);
+	}
+
+	fn toggle_commit_behind_notice(&self) {
+		let notice = self.options.borrow().commit_behind_notice();
+		self.options.borrow_mut().set_commit_behind_notice(!notice);
+	}
+
+	fn switch_tree_initial_collapse(&self, right: bool) {
+		let all = [
+			InitialCollapse::Expanded,
+			InitialCollapse::Collapsed,
+			InitialCollapse::TopLevelOnly,
+			InitialCollapse::Remember,
+		];
+		let current = self.options.borrow().tree_initial_collapse();
+		let idx = all.iter().position(|c| *c == current).unwrap_or(0);
+		let next = if right {
+			(idx + 1) % all.len()
+		} else {
+			(idx + all.len() - 1) % all.len()
+		};
+
+		self.options
+			.borrow_mut()
+			.set_tree_initial_collapse(all[next]);
+	}
+
+	fn toggle_commit_hard_wrap(&self) {
+		let wrap = self.options.borrow().commit_hard_wrap();
+		self.options.borrow_mut().set_commit_hard_wrap(!wrap);
+	}
+
+	fn toggle_local_history(&self) {
+		let enabled = self.options.borrow().local_history();
+		self.options.borrow_mut().set_local_history(!enabled);
+	}
+
+	fn toggle_vim_mode(&self) {
+		let enabled = self.options.borrow().vim_mode();
+		self.options.borrow_mut().set_vim_mode(!enabled);
+	}
+
+	fn toggle_diff_word_changes(&self) {
+		let changes = self.options.borrow().diff_word_changes();
+		self.options.borrow_mut().set_diff_word_changes(!changes);
+	}
+
+	#[allow(clippy::too_many_lines)]
 	fn switch_option(&self, right: bool) {
 		if right {
 			match self.selection {
@@ -192,6 +415,15 @@ impl OptionsPopup {
 						.borrow_mut()
 						.set_status_show_untracked(untracked);
 				}
+				AppOption::TreeInitialCollapse => {
+					self.switch_tree_initial_collapse(right);
+				}
+				AppOption::StatusFocusConflicts => {
+					self.toggle_focus_conflicts();
+				}
+				AppOption::StatusSubmoduleSections => {
+					self.toggle_submodule_sections();
+				}
 				AppOption::DiffIgnoreWhitespaces => {
 					self.options
 						.borrow_mut()
@@ -207,6 +439,22 @@ impl OptionsPopup {
 						.borrow_mut()
 						.diff_hunk_lines_change(true);
 				}
+				AppOption::DiffWordChanges => {
+					self.toggle_diff_word_changes();
+				}
+				AppOption::CommitVerbose => {
+					self.switch_commit_verbose(true);
+				}
+				AppOption::CommitBehindNotice => {
+					self.toggle_commit_behind_notice();
+				}
+				AppOption::CommitHardWrap => {
+					self.toggle_commit_hard_wrap();
+				}
+				AppOption::LocalHistory => {
+					self.toggle_local_history();
+				}
+				AppOption::VimMode => self.toggle_vim_mode(),
 			}
 		} else {
 			match self.selection {
@@ -231,6 +479,15 @@ impl OptionsPopup {
 						.borrow_mut()
 						.set_status_show_untracked(untracked);
 				}
+				AppOption::TreeInitialCollapse => {
+					self.switch_tree_initial_collapse(right);
+				}
+				AppOption::StatusFocusConflicts => {
+					self.toggle_focus_conflicts();
+				}
+				AppOption::StatusSubmoduleSections => {
+					self.toggle_submodule_sections();
+				}
 				AppOption::DiffIgnoreWhitespaces => {
 					self.options
 						.borrow_mut()
@@ -246,6 +503,22 @@ impl OptionsPopup {
 						.borrow_mut()
 						.diff_hunk_lines_change(false);
 				}
+				AppOption::DiffWordChanges => {
+					self.toggle_diff_word_changes();
+				}
+				AppOption::CommitVerbose => {
+					self.switch_commit_verbose(false);
+				}
+				AppOption::CommitBehindNotice => {
+					self.toggle_commit_behind_notice();
+				}
+				AppOption::CommitHardWrap => {
+					self.toggle_commit_hard_wrap();
+				}
+				AppOption::LocalHistory => {
+					self.toggle_local_history();
+				}
+				AppOption::VimMode => self.toggle_vim_mode(),
 			}
 		}
 
@@ -257,7 +530,7 @@ impl OptionsPopup {
 impl DrawableComponent for OptionsPopup {
 	fn draw(&self, f: &mut Frame, area: Rect) -> Result<()> {
 		if self.is_visible() {
-			const SIZE: (u16, u16) = (50, 10);
+			const SIZE: (u16, u16) = (50, 24);
 			let area =
 				ui::centered_rect_absolute(SIZE.0, SIZE.1, area);
 
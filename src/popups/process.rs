@@ -0,0 +1,271 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, ProcessOutputComponent,
+};
+use crate::{
+	app::Environment,
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, ProcessItem, Queue},
+	string_utils::last_url,
+	strings, try_or_popup,
+	ui::{self, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::{
+	asyncjob::AsyncSingleJob, AsyncGitNotification, AsyncProcessJob,
+	ProcessResult,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Margin, Rect},
+	text::Span,
+	widgets::{Block, BorderType, Borders, Clear},
+	Frame,
+};
+
+/// shows the output of a process (like a hook) while it runs
+pub struct ProcessPopup {
+	queue: Queue,
+	visible: bool,
+	title: String,
+	async_process: AsyncSingleJob<AsyncProcessJob>,
+	job: Option<AsyncProcessJob>,
+	result: Option<ProcessResult>,
+	on_success: Option<InternalEvent>,
+	output: ProcessOutputComponent,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl ProcessPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			queue: env.queue.clone(),
+			visible: false,
+			title: String::new(),
+			async_process: AsyncSingleJob::new(
+				env.sender_git.clone(),
+			),
+			job: None,
+			result: None,
+			on_success: None,
+			output: ProcessOutputComponent::new(
+				env.theme.clone(),
+				env.key_config.clone(),
+			),
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self, item: ProcessItem) -> Result<()> {
+		self.kill();
+
+		let job = AsyncProcessJob::new(item.child);
+		self.async_process.spawn(job.clone());
+
+		self.job = Some(job);
+		self.title = item.title;
+		self.result = None;
+		self.on_success = item.on_success;
+		self.output.clear();
+
+		self.show()
+	}
+
+	///
+	pub fn update_git(&mut self, ev: AsyncGitNotification) {
+		if ev != AsyncGitNotification::Process {
+			return;
+		}
+
+		let Some(job) = &self.job else {
+			return;
+		};
+
+		self.output.update(job);
+
+		if let Some(result) = job.result() {
+			self.job = None;
+
+			match result {
+				Ok(result) => {
+					self.result = Some(result);
+
+					if let Some(event) = self.on_success.take() {
+						if result.success() {
+							self.hide();
+							self.queue.push(event);
+						}
+					}
+				}
+				Err(e) => {
+					self.hide();
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!("{} error:\n{e}", self.title),
+					));
+				}
+			}
+		}
+	}
+
+	///
+	pub fn any_work_pending(&self) -> bool {
+		self.async_process.is_pending()
+	}
+
+	const fn is_running(&self) -> bool {
+		self.job.is_some()
+	}
+
+	/// e.g. the pull request a CLI created
+	fn url(&self) -> Option<String> {
+		last_url(&self.output.text()).map(String::from)
+	}
+
+	fn copy_url(&self) -> Result<()> {
+		if let Some(url) = self.url() {
+			crate::clipboard::copy_string(&url)?;
+			self.queue.push(InternalEvent::ShowInfoMsg(
+				strings::copy_success(&url),
+			));
+		}
+
+		Ok(())
+	}
+
+	fn kill(&mut self) {
+		if let Some(job) = &self.job {
+			job.kill();
+		}
+		self.on_success = None;
+	}
+}
+
+impl DrawableComponent for ProcessPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.is_visible() {
+			const PERCENT_SIZE: Size = Size::new(80, 60);
+			const MIN_SIZE: Size = Size::new(60, 20);
+
+			let area = ui::centered_rect(
+				PERCENT_SIZE.width,
+				PERCENT_SIZE.height,
+				f.area(),
+			);
+			let area =
+				ui::rect_inside(MIN_SIZE, f.area().into(), area);
+			let area = area.intersection(rect);
+
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Block::default()
+					.borders(Borders::ALL)
+					.title(Span::styled(
+						format!(
+							"{} [{}]",
+							self.title,
+							strings::process_status(
+								self.result.as_ref()
+							)
+						),
+						self.theme.title(true),
+					))
+					.border_style(self.theme.block(true))
+					.border_type(BorderType::Thick),
+				area,
+			);
+
+			self.output.draw(
+				f,
+				area.inner(Margin {
+					horizontal: 1,
+					vertical: 1,
+				}),
+			);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for ProcessPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::kill_process(&self.key_config),
+				self.is_running(),
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::copy_url(&self.key_config),
+				self.url().is_some(),
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, event: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if let Event::Key(key) = event {
+				if key_match(key, self.key_config.keys.exit_popup) {
+					// nothing is supposed to continue after closing
+					self.kill();
+					self.hide();
+				} else if key_match(
+					key,
+					self.key_config.keys.process_kill,
+				) {
+					self.kill();
+				} else if key_match(key, self.key_config.keys.copy) {
+					try_or_popup!(
+						self,
+						strings::POPUP_FAIL_COPY,
+						self.copy_url()
+					);
+				} else {
+					self.output.event(event);
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
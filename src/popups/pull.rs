@@ -2,11 +2,12 @@ use crate::{
 	app::Environment,
 	components::{
 		visibility_blocking, CommandBlocking, CommandInfo, Component,
-		CredComponent, DrawableComponent, EventState,
+		CredComponent, CredPromptComponent, DrawableComponent,
+		EventState,
 	},
-	keys::SharedKeyConfig,
+	keys::{key_match, SharedKeyConfig},
 	popups::PushPopup,
-	queue::{Action, InternalEvent, Queue},
+	queue::{Action, InternalEvent, NeedsUpdate, Queue},
 	strings, try_or_popup,
 	ui::{self, style::SharedTheme},
 };
@@ -19,7 +20,7 @@ use asyncgit::{
 			need_username_password_for_fetch, BasicAuthCredential,
 		},
 		remotes::get_default_remote_for_fetch,
-		RepoPathRef,
+		RebaseState, RepoPathRef,
 	},
 	AsyncGitNotification, AsyncPull, FetchRequest, RemoteProgress,
 };
@@ -40,25 +41,36 @@ pub struct PullPopup {
 	progress: Option<RemoteProgress>,
 	pending: bool,
 	branch: String,
+	/// rebase instead of merging if the branches diverged
+	rebase: bool,
 	queue: Queue,
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
 	input_cred: CredComponent,
+	cred_prompt: CredPromptComponent,
 }
 
 impl PullPopup {
 	///
 	pub fn new(env: &Environment) -> Self {
+		let git_fetch = AsyncPull::new(
+			env.repo.borrow().clone(),
+			&env.sender_git,
+		);
+
 		Self {
 			repo: env.repo.clone(),
 			queue: env.queue.clone(),
 			pending: false,
 			visible: false,
 			branch: String::new(),
-			git_fetch: AsyncPull::new(
-				env.repo.borrow().clone(),
-				&env.sender_git,
+			rebase: false,
+			cred_prompt: CredPromptComponent::new(
+				env,
+				git_fetch.cred_prompt().clone(),
+				"pull",
 			),
+			git_fetch,
 			progress: None,
 			input_cred: CredComponent::new(env),
 			theme: env.theme.clone(),
@@ -66,8 +78,23 @@ impl PullPopup {
 		}
 	}
 
-	///
+	/// merges or rebases like `pull.rebase` says unless it is
+	/// toggled while fetching
 	pub fn fetch(&mut self, branch: String) -> Result<()> {
+		self.rebase =
+			sync::config_is_pull_rebase(&self.repo.borrow())
+				.unwrap_or_default();
+		self.start(branch)
+	}
+
+	/// like `fetch` but rebases instead of merging if the branches
+	/// diverged, no matter what `pull.rebase` says
+	pub fn fetch_rebase(&mut self, branch: String) -> Result<()> {
+		self.rebase = true;
+		self.start(branch)
+	}
+
+	fn start(&mut self, branch: String) -> Result<()> {
 		self.branch = branch;
 		self.show()?;
 		if need_username_password_for_fetch(&self.repo.borrow())? {
@@ -113,6 +140,7 @@ impl PullPopup {
 		if self.is_visible() && ev == AsyncGitNotification::Pull {
 			if let Err(error) = self.update() {
 				self.pending = false;
+				self.cred_prompt.hide();
 				self.hide();
 				self.queue.push(InternalEvent::ShowErrorMsg(
 					format!("fetch failed:\n{error}"),
@@ -126,7 +154,10 @@ impl PullPopup {
 		self.pending = self.git_fetch.is_pending()?;
 		self.progress = self.git_fetch.progress()?;
 
-		if !self.pending {
+		if self.pending {
+			self.cred_prompt.update()?;
+		} else {
+			self.cred_prompt.hide();
 			if let Some((_bytes, err)) =
 				self.git_fetch.last_result()?
 			{
@@ -158,21 +189,32 @@ impl PullPopup {
 			}
 		}
 
+		// the fetch might have pruned remote-tracking branches
+		self.queue
+			.push(InternalEvent::Update(NeedsUpdate::BRANCHES));
 		self.hide();
 
 		Ok(())
 	}
 
+	/// a conflict of the rebase leaves it to finish in the status tab
 	pub fn try_conflict_free_merge(&self, rebase: bool) {
 		if rebase {
-			try_or_popup!(
-				self,
-				"rebase failed:",
-				sync::merge_upstream_rebase(
-					&self.repo.borrow(),
-					&self.branch
-				)
-			);
+			match sync::pull_rebase(&self.repo.borrow(), &self.branch)
+			{
+				Ok(RebaseState::Conflicted) => {
+					self.queue.push(InternalEvent::ShowInfoMsg(
+						strings::pull_rebase_conflicted(),
+					));
+					self.queue.push(InternalEvent::TabSwitchStatus);
+				}
+				Ok(RebaseState::Finished) => (),
+				Err(e) => {
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!("rebase failed:\n{e}"),
+					));
+				}
+			}
 		} else {
 			try_or_popup!(
 				self,
@@ -189,10 +231,7 @@ impl PullPopup {
 		self.queue.push(InternalEvent::ConfirmAction(
 			Action::PullMerge {
 				incoming,
-				rebase: sync::config_is_pull_rebase(
-					&self.repo.borrow(),
-				)
-				.unwrap_or_default(),
+				rebase: self.rebase,
 			},
 		));
 		self.hide();
@@ -214,7 +253,11 @@ impl DrawableComponent for PullPopup {
 					.block(
 						Block::default()
 							.title(Span::styled(
-								strings::PULL_POPUP_MSG,
+								if self.rebase {
+									strings::PULL_REBASE_POPUP_MSG
+								} else {
+									strings::PULL_POPUP_MSG
+								},
 								self.theme.title(true),
 							))
 							.borders(Borders::ALL)
@@ -226,6 +269,7 @@ impl DrawableComponent for PullPopup {
 				area,
 			);
 			self.input_cred.draw(f, rect)?;
+			self.cred_prompt.draw(f, rect)?;
 		}
 
 		Ok(())
@@ -246,11 +290,22 @@ impl Component for PullPopup {
 			if self.input_cred.is_visible() {
 				return self.input_cred.commands(out, force_all);
 			}
+			if self.cred_prompt.is_visible() {
+				return self.cred_prompt.commands(out, force_all);
+			}
 			out.push(CommandInfo::new(
 				strings::commands::close_msg(&self.key_config),
 				!self.pending,
 				self.visible,
 			));
+			out.push(CommandInfo::new(
+				strings::commands::pull_toggle_rebase(
+					&self.key_config,
+					self.rebase,
+				),
+				true,
+				self.visible,
+			));
 		}
 
 		visibility_blocking(self)
@@ -258,7 +313,9 @@ impl Component for PullPopup {
 
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.visible {
-			if let Event::Key(_) = ev {
+			if self.pending && self.cred_prompt.is_visible() {
+				self.cred_prompt.event(ev)?;
+			} else if let Event::Key(e) = ev {
 				if self.input_cred.is_visible() {
 					self.input_cred.event(ev)?;
 
@@ -270,6 +327,11 @@ impl Component for PullPopup {
 						))?;
 						self.input_cred.hide();
 					}
+				} else if key_match(
+					e,
+					self.key_config.keys.pull_toggle_rebase,
+				) {
+					self.rebase = !self.rebase;
 				}
 			}
 			return Ok(EventState::Consumed);
@@ -2,10 +2,11 @@ use crate::{
 	app::Environment,
 	components::{
 		visibility_blocking, CommandBlocking, CommandInfo, Component,
-		CredComponent, DrawableComponent, EventState,
+		CredComponent, CredPromptComponent, DrawableComponent,
+		EventState,
 	},
 	keys::{key_match, SharedKeyConfig},
-	queue::{InternalEvent, Queue},
+	queue::{Action, InternalEvent, Queue},
 	strings,
 	ui::{self, style::SharedTheme},
 };
@@ -13,73 +14,150 @@ use anyhow::Result;
 use asyncgit::{
 	sync::{
 		cred::{
-			extract_username_password_for_push,
-			need_username_password_for_push, BasicAuthCredential,
+			extract_username_password_for_remote,
+			https_url_for_remote, need_username_password_for_remote,
+			BasicAuthCredential,
 		},
-		get_branch_remote,
+		get_branch_push_remote, get_branch_remotes, get_remotes,
+		push_lease,
 		remotes::get_default_remote_for_push,
-		RepoPathRef,
+		BranchRemotes, RepoPath, RepoPathRef,
 	},
-	AsyncGitNotification, AsyncPush, PushRequest, PushType,
-	RemoteProgress, RemoteProgressState,
+	AsyncGitNotification, AsyncPush, PushForce, PushRequest,
+	PushType, RemoteProgress, RemoteProgressState,
 };
+use bytesize::ByteSize;
 use crossterm::event::Event;
+use easy_cast::Conv;
 use ratatui::{
-	layout::Rect,
+	layout::{Alignment, Rect},
 	text::Span,
-	widgets::{Block, BorderType, Borders, Clear, Gauge},
+	widgets::{Block, BorderType, Borders, Clear, Gauge, Paragraph},
 	Frame,
 };
+use unicode_width::UnicodeWidthStr;
 
-///
-#[derive(PartialEq, Eq)]
-enum PushComponentModifier {
-	None,
-	Force,
-	Delete,
-	ForceDelete,
+/// a freshly created branch or tag that gets pushed right away
+#[derive(Clone, Debug)]
+pub struct PushAfterCreate {
+	pub name: String,
+	pub push_type: PushType,
+	pub remote: String,
+}
+
+/// remote the creation popups push a new ref to, `None` means
+/// the ref is only created locally
+#[derive(Default)]
+pub struct PushTarget {
+	remote: Option<String>,
 }
 
-impl PushComponentModifier {
-	pub(crate) fn force(&self) -> bool {
-		self == &Self::Force || self == &Self::ForceDelete
+impl PushTarget {
+	///
+	pub fn remote(&self) -> Option<&str> {
+		self.remote.as_deref()
 	}
-	pub(crate) fn delete(&self) -> bool {
-		self == &Self::Delete || self == &Self::ForceDelete
+
+	///
+	pub fn reset(&mut self) {
+		self.remote = None;
+	}
+
+	/// cycles through: off, default push remote, all other remotes
+	pub fn cycle(&mut self, repo: &RepoPath) -> Result<()> {
+		let mut remotes = get_remotes(repo)?;
+
+		if let Ok(default) = get_default_remote_for_push(repo) {
+			if let Some(idx) =
+				remotes.iter().position(|r| *r == default)
+			{
+				let default = remotes.remove(idx);
+				remotes.insert(0, default);
+			}
+		}
+
+		self.remote = self.remote.as_ref().map_or_else(
+			|| remotes.first().cloned(),
+			|current| {
+				remotes
+					.iter()
+					.position(|r| r == current)
+					.and_then(|idx| remotes.get(idx + 1))
+					.cloned()
+			},
+		);
+
+		Ok(())
+	}
+
+	/// shows the remote in the top right corner of the name input
+	pub fn draw(&self, f: &mut Frame, input_area: Rect) {
+		if let Some(remote) = self.remote() {
+			let w = Paragraph::new(
+				strings::push_after_create_target(remote),
+			)
+			.alignment(Alignment::Right);
+
+			let rect = {
+				let mut rect = input_area;
+				rect.height = 1;
+				rect.width = rect.width.saturating_sub(1);
+				rect
+			};
+
+			f.render_widget(w, rect);
+		}
 	}
 }
 
 ///
 pub struct PushPopup {
 	repo: RepoPathRef,
-	modifier: PushComponentModifier,
+	force: PushForce,
+	delete: bool,
 	visible: bool,
 	git_push: AsyncPush,
 	progress: Option<RemoteProgress>,
 	pending: bool,
 	branch: String,
+	remote: String,
+	/// fetch source and push destination of a pushed branch
+	branch_remotes: Option<BranchRemotes>,
 	push_type: PushType,
+	after_create: Option<PushAfterCreate>,
 	queue: Queue,
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
 	input_cred: CredComponent,
+	cred_prompt: CredPromptComponent,
 }
 
 impl PushPopup {
 	///
 	pub fn new(env: &Environment) -> Self {
+		let git_push = AsyncPush::new(
+			env.repo.borrow().clone(),
+			&env.sender_git,
+		);
+
 		Self {
 			repo: env.repo.clone(),
 			queue: env.queue.clone(),
-			modifier: PushComponentModifier::None,
+			force: PushForce::FastForward,
+			delete: false,
 			pending: false,
 			visible: false,
 			branch: String::new(),
+			remote: String::new(),
+			branch_remotes: None,
 			push_type: PushType::Branch,
-			git_push: AsyncPush::new(
-				env.repo.borrow().clone(),
-				&env.sender_git,
+			after_create: None,
+			cred_prompt: CredPromptComponent::new(
+				env,
+				git_push.cred_prompt().clone(),
+				"push",
 			),
+			git_push,
 			progress: None,
 			input_cred: CredComponent::new(env),
 			theme: env.theme.clone(),
@@ -87,71 +165,151 @@ impl PushPopup {
 		}
 	}
 
-	///
+	/// without a remote-tracking ref to lease against a force push
+	/// asks once more to force push without lease
 	pub fn push(
 		&mut self,
 		branch: String,
 		push_type: PushType,
-		force: bool,
+		force: PushForce,
 		delete: bool,
 	) -> Result<()> {
+		self.after_create = None;
 		self.branch = branch;
 		self.push_type = push_type;
-		self.modifier = match (force, delete) {
-			(true, true) => PushComponentModifier::ForceDelete,
-			(false, true) => PushComponentModifier::Delete,
-			(true, false) => PushComponentModifier::Force,
-			(false, false) => PushComponentModifier::None,
-		};
+		self.remote = self.remote_for_push()?;
+		self.force = force;
+		self.delete = delete;
+		self.branch_remotes = (push_type == PushType::Branch
+			&& !delete)
+			.then(|| {
+				get_branch_remotes(&self.repo.borrow(), &self.branch)
+					.ok()
+			})
+			.flatten();
+
+		if force == PushForce::Lease
+			&& push_lease(
+				&self.repo.borrow(),
+				&self.remote,
+				&self.branch,
+				push_type,
+			)?
+			.is_none()
+		{
+			self.queue.push(InternalEvent::ConfirmAction(
+				Action::ForcePush(
+					self.branch.clone(),
+					PushForce::Force,
+				),
+			));
+			return Ok(());
+		}
+
+		self.start()
+	}
+
+	/// pushes or deletes `name` on `remote` rather than on the
+	/// remote the local ref of that name pushes to
+	pub fn push_on_remote(
+		&mut self,
+		remote: String,
+		name: String,
+		push_type: PushType,
+		force: PushForce,
+		delete: bool,
+	) -> Result<()> {
+		self.after_create = None;
+		self.branch = name;
+		self.remote = remote;
+		self.branch_remotes = None;
+		self.push_type = push_type;
+		self.force = force;
+		self.delete = delete;
 
+		self.start()
+	}
+
+	/// push a ref that was just created, the outcome of both steps
+	/// is reported in a single message once the push finished
+	pub fn push_after_create(&mut self, created: PushAfterCreate) {
+		self.branch.clone_from(&created.name);
+		self.remote.clone_from(&created.remote);
+		self.branch_remotes = None;
+		self.push_type = created.push_type;
+		self.force = PushForce::FastForward;
+		self.delete = false;
+		self.after_create = Some(created);
+
+		if let Err(e) = self.start() {
+			self.hide();
+			self.pending = false;
+			if let Some(created) = self.after_create.take() {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					strings::push_after_create_failed(
+						created.push_type,
+						&created.name,
+						&created.remote,
+						&e.to_string(),
+					),
+				));
+			}
+		}
+	}
+
+	fn start(&mut self) -> Result<()> {
 		self.show()?;
 
-		if need_username_password_for_push(&self.repo.borrow())? {
-			let cred = extract_username_password_for_push(
+		if need_username_password_for_remote(
+			&self.repo.borrow(),
+			&self.remote,
+		)? {
+			let cred = extract_username_password_for_remote(
 				&self.repo.borrow(),
+				&self.remote,
 			)
 			.unwrap_or_else(|_| BasicAuthCredential::new(None, None));
 			if cred.is_complete() {
-				self.push_to_remote(Some(cred), force)
+				self.push_to_remote(Some(cred))
 			} else {
 				self.input_cred.set_cred(cred);
 				self.input_cred.show()
 			}
 		} else {
-			self.push_to_remote(None, force)
+			self.push_to_remote(None)
 		}
 	}
 
+	/// `branch.<name>.pushRemote`, `remote.pushDefault` and then the
+	/// upstream like git does
+	fn remote_for_push(&self) -> Result<String> {
+		let remote = if self.push_type == PushType::Branch {
+			get_branch_push_remote(&self.repo.borrow(), &self.branch)?
+		} else {
+			get_default_remote_for_push(&self.repo.borrow())?
+		};
+		log::info!("push: '{}' to remote '{}'", self.branch, remote);
+
+		Ok(remote)
+	}
+
 	fn push_to_remote(
 		&mut self,
 		cred: Option<BasicAuthCredential>,
-		force: bool,
 	) -> Result<()> {
-		let remote = if let Ok(Some(remote)) =
-			get_branch_remote(&self.repo.borrow(), &self.branch)
-		{
-			log::info!("push: branch '{}' has upstream for remote '{}' - using that",self.branch,remote);
-			remote
-		} else {
-			log::info!("push: branch '{}' has no upstream - looking up default remote",self.branch);
-			let remote =
-				get_default_remote_for_push(&self.repo.borrow())?;
-			log::info!(
-				"push: branch '{}' to remote '{}'",
-				self.branch,
-				remote
-			);
-			remote
-		};
-
 		self.pending = true;
 		self.progress = None;
+		self.cred_prompt.set_switch_url(
+			https_url_for_remote(&self.repo.borrow(), &self.remote)
+				.ok()
+				.flatten(),
+		);
 		self.git_push.request(PushRequest {
-			remote,
+			remote: self.remote.clone(),
 			branch: self.branch.clone(),
 			push_type: self.push_type,
-			force,
-			delete: self.modifier.delete(),
+			force: self.force,
+			delete: self.delete,
 			basic_credential: cred,
 		})?;
 		Ok(())
@@ -174,8 +332,41 @@ impl PushPopup {
 		self.pending = self.git_push.is_pending()?;
 		self.progress = self.git_push.progress()?;
 
-		if !self.pending {
-			if let Some(err) = self.git_push.last_result()? {
+		if self.pending {
+			self.cred_prompt.update()?;
+		} else {
+			self.cred_prompt.hide();
+			let err = self.git_push.last_result()?;
+
+			if let Some(created) = self.after_create.take() {
+				self.queue.push(err.map_or_else(
+					|| {
+						InternalEvent::ShowInfoMsg(
+							strings::push_after_create_done(
+								created.push_type,
+								&created.name,
+								&created.remote,
+							),
+						)
+					},
+					|err| {
+						InternalEvent::ShowErrorMsg(
+							strings::push_after_create_failed(
+								created.push_type,
+								&created.name,
+								&created.remote,
+								&err,
+							),
+						)
+					},
+				));
+			} else if let Some(tag) =
+				self.git_push.last_rejected_tag()?
+			{
+				self.queue.push(InternalEvent::ConfirmAction(
+					Action::ForcePushTag(tag, self.remote.clone()),
+				));
+			} else if let Some(err) = err {
 				self.queue.push(InternalEvent::ShowErrorMsg(
 					format!("push failed:\n{err}"),
 				));
@@ -198,10 +389,20 @@ impl PushPopup {
 		progress.as_ref().map_or(
 			(strings::PUSH_POPUP_PROGRESS_NONE.into(), 0),
 			|progress| {
-				(
-					Self::progress_state_name(&progress.state),
-					progress.get_progress_percent(),
-				)
+				let name = Self::progress_state_name(&progress.state);
+
+				let name = if progress.bytes_per_sec > 0 {
+					format!(
+						"{name} ({}/s)",
+						ByteSize::b(u64::conv(
+							progress.bytes_per_sec
+						))
+					)
+				} else {
+					name
+				};
+
+				(name, progress.get_progress_percent())
 			},
 		)
 	}
@@ -234,7 +435,22 @@ impl DrawableComponent for PushPopup {
 			let (state, progress) =
 				Self::get_progress(self.progress.as_ref());
 
-			let area = ui::centered_rect_absolute(30, 3, f.area());
+			let title = strings::push_popup_title(
+				if self.force == PushForce::Lease {
+					strings::FORCE_PUSH_LEASE_POPUP_MSG
+				} else if self.force.is_forced() {
+					strings::FORCE_PUSH_POPUP_MSG
+				} else if self.delete {
+					strings::DELETE_PUSH_POPUP_MSG
+				} else {
+					strings::PUSH_POPUP_MSG
+				},
+				self.branch_remotes.as_ref(),
+			);
+			let width = u16::try_from(title.width() + 4)
+				.unwrap_or(u16::MAX)
+				.max(30);
+			let area = ui::centered_rect_absolute(width, 3, f.area());
 
 			f.render_widget(Clear, area);
 			f.render_widget(
@@ -243,11 +459,7 @@ impl DrawableComponent for PushPopup {
 					.block(
 						Block::default()
 							.title(Span::styled(
-								if self.modifier.force() {
-									strings::FORCE_PUSH_POPUP_MSG
-								} else {
-									strings::PUSH_POPUP_MSG
-								},
+								title,
 								self.theme.title(true),
 							))
 							.borders(Borders::ALL)
@@ -259,6 +471,7 @@ impl DrawableComponent for PushPopup {
 				area,
 			);
 			self.input_cred.draw(f, rect)?;
+			self.cred_prompt.draw(f, rect)?;
 		}
 
 		Ok(())
@@ -279,6 +492,9 @@ impl Component for PushPopup {
 			if self.input_cred.is_visible() {
 				return self.input_cred.commands(out, force_all);
 			}
+			if self.cred_prompt.is_visible() {
+				return self.cred_prompt.commands(out, force_all);
+			}
 			out.push(CommandInfo::new(
 				strings::commands::close_msg(&self.key_config),
 				!self.pending,
@@ -291,17 +507,18 @@ impl Component for PushPopup {
 
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.visible {
-			if let Event::Key(e) = ev {
+			if self.pending && self.cred_prompt.is_visible() {
+				self.cred_prompt.event(ev)?;
+			} else if let Event::Key(e) = ev {
 				if self.input_cred.is_visible() {
 					self.input_cred.event(ev)?;
 
 					if self.input_cred.get_cred().is_complete()
 						|| !self.input_cred.is_visible()
 					{
-						self.push_to_remote(
-							Some(self.input_cred.get_cred().clone()),
-							self.modifier.force(),
-						)?;
+						self.push_to_remote(Some(
+							self.input_cred.get_cred().clone(),
+						))?;
 						self.input_cred.hide();
 					}
 				} else if key_match(
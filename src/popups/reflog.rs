@@ -0,0 +1,416 @@
+use crate::components::{
+	time_to_string, visibility_blocking, CommandBlocking,
+	CommandInfo, Component, DrawableComponent, EventState,
+};
+use crate::{
+	app::Environment,
+	components::ScrollType,
+	keys::{key_match, SharedKeyConfig},
+	popups::InspectCommitOpen,
+	queue::{InternalEvent, NeedsUpdate, Queue, StackablePopupOpen},
+	strings, try_or_popup,
+	ui::{self, Size},
+};
+use anyhow::Result;
+use asyncgit::{
+	asyncjob::AsyncSingleJob,
+	sync::{checkout_commit, ReflogEntry, RepoPathRef},
+	AsyncGitNotification, AsyncReflogJob,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Margin, Rect},
+	text::Span,
+	widgets::{
+		Block, BorderType, Borders, Cell, Clear, Row, Table,
+		TableState,
+	},
+	Frame,
+};
+use ui::style::SharedTheme;
+
+/// moves of `HEAD` with actions to get back to any of them
+pub struct ReflogPopup {
+	repo: RepoPathRef,
+	theme: SharedTheme,
+	queue: Queue,
+	entries: Vec<ReflogEntry>,
+	async_reflog: AsyncSingleJob<AsyncReflogJob>,
+	visible: bool,
+	table_state: std::cell::Cell<TableState>,
+	current_height: std::cell::Cell<usize>,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for ReflogPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.visible {
+			const PERCENT_SIZE: Size = Size::new(80, 60);
+			const MIN_SIZE: Size = Size::new(60, 20);
+
+			let area = ui::centered_rect(
+				PERCENT_SIZE.width,
+				PERCENT_SIZE.height,
+				f.area(),
+			);
+			let area =
+				ui::rect_inside(MIN_SIZE, f.area().into(), area);
+			let area = area.intersection(rect);
+
+			let selector_width = self
+				.entries
+				.iter()
+				.fold(0, |acc, entry| acc.max(entry.selector.len()));
+
+			let constraints = [
+				// selector
+				Constraint::Length(selector_width.try_into()?),
+				// id
+				Constraint::Length(7),
+				// time
+				Constraint::Length(19),
+				// message
+				Constraint::Percentage(100),
+			];
+
+			let rows: Vec<Row> = self
+				.entries
+				.iter()
+				.map(|e| self.get_row(e))
+				.collect();
+			let number_of_rows = rows.len();
+
+			let table = Table::new(rows, constraints)
+				.column_spacing(1)
+				.row_highlight_style(self.theme.text(true, true))
+				.block(
+					Block::default()
+						.borders(Borders::ALL)
+						.title(Span::styled(
+							strings::title_reflog(
+								self.async_reflog.is_pending(),
+							),
+							self.theme.title(true),
+						))
+						.border_style(self.theme.block(true))
+						.border_type(BorderType::Thick),
+				);
+
+			let mut table_state = self.table_state.take();
+
+			f.render_widget(Clear, area);
+			f.render_stateful_widget(table, area, &mut table_state);
+
+			let area = area.inner(Margin {
+				vertical: 1,
+				horizontal: 0,
+			});
+
+			ui::draw_scrollbar(
+				f,
+				area,
+				&self.theme,
+				number_of_rows,
+				table_state.selected().unwrap_or(0),
+				ui::Orientation::Vertical,
+			);
+
+			self.table_state.set(table_state);
+			self.current_height.set(area.height.into());
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for ReflogPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			if !force_all {
+				out.clear();
+			}
+
+			let selected = self.selected_entry().is_some();
+
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::reflog_inspect(&self.key_config),
+				selected,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::log_checkout_commit(
+					&self.key_config,
+				),
+				selected,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::log_branch_commit(
+					&self.key_config,
+				),
+				selected,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::log_reset_commit(&self.key_config),
+				selected,
+				true,
+			));
+		}
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, event: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(key) = event {
+				let keys = &self.key_config.keys;
+
+				if key_match(key, keys.exit_popup) {
+					self.hide_stacked(false);
+				} else if key_match(key, keys.move_up) {
+					self.move_selection(ScrollType::Up);
+				} else if key_match(key, keys.move_down) {
+					self.move_selection(ScrollType::Down);
+				} else if key_match(key, keys.shift_up)
+					|| key_match(key, keys.home)
+				{
+					self.move_selection(ScrollType::Home);
+				} else if key_match(key, keys.shift_down)
+					|| key_match(key, keys.end)
+				{
+					self.move_selection(ScrollType::End);
+				} else if key_match(key, keys.page_down) {
+					self.move_selection(ScrollType::PageDown);
+				} else if key_match(key, keys.page_up) {
+					self.move_selection(ScrollType::PageUp);
+				} else if key_match(key, keys.enter) {
+					self.inspect_selected();
+				} else if key_match(key, keys.log_checkout_commit) {
+					self.checkout_selected();
+				} else if key_match(key, keys.log_branch_commit) {
+					if let Some(entry) = self.selected_entry() {
+						self.queue.push(InternalEvent::BranchCommit(
+							entry.id,
+						));
+					}
+				} else if key_match(key, keys.log_reset_commit) {
+					if let Some(entry) = self.selected_entry() {
+						self.queue.push(
+							InternalEvent::OpenResetPopupReflog(
+								entry.id,
+								entry.selector.clone(),
+							),
+						);
+					}
+				}
+			}
+
+			Ok(EventState::Consumed)
+		} else {
+			Ok(EventState::NotConsumed)
+		}
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
+
+impl ReflogPopup {
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			theme: env.theme.clone(),
+			queue: env.queue.clone(),
+			entries: Vec::new(),
+			async_reflog: AsyncSingleJob::new(env.sender_git.clone()),
+			visible: false,
+			table_state: std::cell::Cell::new(TableState::default()),
+			current_height: std::cell::Cell::new(0),
+			key_config: env.key_config.clone(),
+			repo: env.repo.clone(),
+		}
+	}
+
+	/// reads the reflog again, the selection stays where it was
+	pub fn open(&mut self) -> Result<()> {
+		self.show()?;
+		self.update();
+
+		Ok(())
+	}
+
+	/// `HEAD` moved
+	pub fn update(&self) {
+		if self.visible {
+			self.async_reflog.spawn(AsyncReflogJob::new(
+				self.repo.borrow().clone(),
+			));
+		}
+	}
+
+	///
+	pub fn any_work_pending(&self) -> bool {
+		self.async_reflog.is_pending()
+	}
+
+	///
+	pub fn update_git(&mut self, ev: AsyncGitNotification) {
+		if ev != AsyncGitNotification::Reflog {
+			return;
+		}
+
+		let Some(result) = self
+			.async_reflog
+			.take_last()
+			.and_then(|job| job.result())
+		else {
+			return;
+		};
+
+		match result {
+			Ok(entries) => {
+				self.entries = entries;
+
+				let selected = self
+					.table_state
+					.get_mut()
+					.selected()
+					.unwrap_or_default()
+					.min(self.entries.len().saturating_sub(1));
+				self.table_state.get_mut().select(Some(selected));
+			}
+			Err(e) => {
+				self.hide();
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("reflog error:\n{e}"),
+				));
+			}
+		}
+	}
+
+	fn move_selection(&self, scroll_type: ScrollType) -> bool {
+		let mut table_state = self.table_state.take();
+
+		let old_selection = table_state.selected().unwrap_or(0);
+		let max_selection = self.entries.len().saturating_sub(1);
+		let page = self.current_height.get().saturating_sub(1);
+
+		let new_selection = match scroll_type {
+			ScrollType::Up => old_selection.saturating_sub(1),
+			ScrollType::Down => {
+				old_selection.saturating_add(1).min(max_selection)
+			}
+			ScrollType::Home => 0,
+			ScrollType::End => max_selection,
+			ScrollType::PageUp => old_selection.saturating_sub(page),
+			ScrollType::PageDown => {
+				old_selection.saturating_add(page).min(max_selection)
+			}
+			ScrollType::HalfPageUp => {
+				old_selection.saturating_sub(page / 2)
+			}
+			ScrollType::HalfPageDown => old_selection
+				.saturating_add(page / 2)
+				.min(max_selection),
+		};
+
+		let needs_update = new_selection != old_selection;
+
+		table_state.select(Some(new_selection));
+		self.table_state.set(table_state);
+
+		needs_update
+	}
+
+	fn get_row(&self, entry: &ReflogEntry) -> Row<'_> {
+		let cells: Vec<Cell> = vec![
+			Cell::from(entry.selector.clone())
+				.style(self.theme.text(true, false)),
+			Cell::from(entry.id.get_short_string())
+				.style(self.theme.commit_hash(false)),
+			Cell::from(time_to_string(entry.time, false))
+				.style(self.theme.commit_time(false)),
+			Cell::from(entry.message.clone())
+				.style(self.theme.text(true, false)),
+		];
+
+		Row::new(cells)
+	}
+
+	fn selected_entry(&self) -> Option<&ReflogEntry> {
+		let table_state = self.table_state.take();
+
+		let entry = table_state
+			.selected()
+			.and_then(|selected| self.entries.get(selected));
+
+		self.table_state.set(table_state);
+
+		entry
+	}
+
+	fn inspect_selected(&mut self) {
+		if let Some(id) = self.selected_entry().map(|entry| entry.id)
+		{
+			self.hide_stacked(true);
+			self.queue.push(InternalEvent::OpenPopup(
+				StackablePopupOpen::InspectCommit(
+					InspectCommitOpen::new(id),
+				),
+			));
+		}
+	}
+
+	fn checkout_selected(&self) {
+		if let Some(id) = self.selected_entry().map(|entry| entry.id)
+		{
+			try_or_popup!(
+				self,
+				"failed to checkout commit:",
+				checkout_commit(&self.repo.borrow(), id)
+			);
+			self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+		}
+	}
+
+	fn hide_stacked(&mut self, stack: bool) {
+		self.hide();
+
+		if stack {
+			self.queue.push(InternalEvent::PopupStackPush(
+				StackablePopupOpen::Reflog,
+			));
+		} else {
+			self.queue.push(InternalEvent::PopupStackPop);
+		}
+	}
+}
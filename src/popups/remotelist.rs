@@ -1,6 +1,8 @@
 use std::cell::Cell;
 
-use asyncgit::sync::{get_remote_url, get_remotes, RepoPathRef};
+use asyncgit::sync::{
+	self, get_remote_url, get_remotes, RemoteDefaults, RepoPathRef,
+};
 use ratatui::{
 	layout::{
 		Alignment, Constraint, Direction, Layout, Margin, Rect,
@@ -18,7 +20,7 @@ use crate::{
 		DrawableComponent, EventState, ScrollType, VerticalScroll,
 	},
 	keys::{key_match, SharedKeyConfig},
-	queue::{Action, InternalEvent, Queue},
+	queue::{Action, InternalEvent, NeedsUpdate, Queue},
 	strings,
 	ui::{self, style::SharedTheme, Size},
 };
@@ -27,6 +29,7 @@ use crossterm::event::{Event, KeyEvent};
 
 pub struct RemoteListPopup {
 	remote_names: Vec<String>,
+	defaults: RemoteDefaults,
 	repo: RepoPathRef,
 	visible: bool,
 	current_height: Cell<u16>,
@@ -66,7 +69,7 @@ impl DrawableComponent for RemoteListPopup {
 				.constraints(vec![
 					Constraint::Min(1),
 					Constraint::Length(1),
-					Constraint::Length(2),
+					Constraint::Length(3),
 				])
 				.split(area);
 			self.draw_remotes_list(f, chunks[0])?;
@@ -125,6 +128,24 @@ impl Component for RemoteListPopup {
 				true,
 				self.valid_selection(),
 			));
+
+			out.push(CommandInfo::new(
+				strings::commands::set_default_remote(
+					&self.key_config,
+					self.selected_is_default(false),
+				),
+				true,
+				self.valid_selection(),
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::set_push_default_remote(
+					&self.key_config,
+					self.selected_is_default(true),
+				),
+				true,
+				self.valid_selection(),
+			));
 		}
 		visibility_blocking(self)
 	}
@@ -153,6 +174,18 @@ impl Component for RemoteListPopup {
 				self.key_config.keys.update_remote_url,
 			) {
 				self.update_remote_url();
+			} else if key_match(
+				e,
+				self.key_config.keys.remote_set_default,
+			) && self.valid_selection()
+			{
+				self.toggle_default(false);
+			} else if key_match(
+				e,
+				self.key_config.keys.remote_set_push_default,
+			) && self.valid_selection()
+			{
+				self.toggle_default(true);
 			}
 		}
 		Ok(EventState::Consumed)
@@ -176,6 +209,7 @@ impl RemoteListPopup {
 	pub fn new(env: &Environment) -> Self {
 		Self {
 			remote_names: Vec::new(),
+			defaults: RemoteDefaults::default(),
 			repo: env.repo.clone(),
 			visible: false,
 			scroll: VerticalScroll::new(),
@@ -247,7 +281,15 @@ impl RemoteListPopup {
 					let selected = (self.selection as usize
 						- self.scroll.get_top())
 						== i;
-					let mut remote_name = remote.clone();
+					let mut remote_name = format!(
+						"{remote}{}",
+						strings::remote_default_marks(
+							self.defaults.fetch.as_ref()
+								== Some(remote),
+							self.defaults.push.as_ref()
+								== Some(remote),
+						)
+					);
 					if remote_name.len()
 						> name_length
 							.saturating_sub(THREE_DOTS_LENGTH)
@@ -326,6 +368,9 @@ impl RemoteListPopup {
 			REMOTE_NAME_LABEL.len();
 		const REMOTE_URL_LABEL: &str = "url: ";
 		const REMOTE_URL_LABEL_LENGTH: usize = REMOTE_URL_LABEL.len();
+		const REMOTE_PUSH_URL_LABEL: &str = "push url: ";
+		const REMOTE_PUSH_URL_LABEL_LENGTH: usize =
+			REMOTE_PUSH_URL_LABEL.len();
 
 		let name_length: usize = (r.width.saturating_sub(1) as usize)
 			.saturating_sub(REMOTE_NAME_LABEL_LENGTH);
@@ -356,21 +401,8 @@ impl RemoteListPopup {
 			)));
 			let remote_url =
 				get_remote_url(&self.repo.borrow(), remote);
-			if let Ok(Some(mut remote_url)) = remote_url {
-				if remote_url.len()
-					> url_length.saturating_sub(THREE_DOTS_LENGTH)
-				{
-					remote_url = remote_url
-						.chars()
-						.skip(
-							remote_url.len()
-								- url_length.saturating_sub(
-									THREE_DOTS_LENGTH,
-								),
-						)
-						.collect::<String>();
-					remote_url = format!("{THREE_DOTS}{remote_url}");
-				}
+			if let Ok(Some(remote_url)) = remote_url {
+				let remote_url = trim_url(remote_url, url_length);
 				lines.push(Line::from(Span::styled(
 					format!(
 						"{REMOTE_URL_LABEL}{remote_url:url_length$}"
@@ -378,6 +410,22 @@ impl RemoteListPopup {
 					self.theme.text(true, false),
 				)));
 			}
+			let push_url = sync::get_remote_push_url(
+				&self.repo.borrow(),
+				remote,
+			);
+			if let Ok(Some(push_url)) = push_url {
+				let push_url_length = url_length
+					.saturating_sub(REMOTE_PUSH_URL_LABEL_LENGTH)
+					+ REMOTE_URL_LABEL_LENGTH;
+				let push_url = trim_url(push_url, push_url_length);
+				lines.push(Line::from(Span::styled(
+					format!(
+						"{REMOTE_PUSH_URL_LABEL}{push_url:push_url_length$}"
+					),
+					self.theme.text(true, false),
+				)));
+			}
 			f.render_widget(
 				Paragraph::new(Text::from(lines))
 					.alignment(Alignment::Left)
@@ -445,11 +493,50 @@ impl RemoteListPopup {
 	pub fn update_remotes(&mut self) -> Result<()> {
 		if self.is_visible() {
 			self.remote_names = get_remotes(&self.repo.borrow())?;
+			self.defaults =
+				sync::get_remote_defaults(&self.repo.borrow())?;
 			self.set_selection(self.selection)?;
 		}
 		Ok(())
 	}
 
+	fn selected_is_default(&self, push: bool) -> bool {
+		let default = if push {
+			&self.defaults.push
+		} else {
+			&self.defaults.fetch
+		};
+
+		default.is_some()
+			&& default.as_ref()
+				== self.remote_names.get(usize::from(self.selection))
+	}
+
+	/// the selected remote becomes the default or stops being it
+	fn toggle_default(&self, push: bool) {
+		let remote =
+			self.remote_names[self.selection as usize].clone();
+		let remote =
+			(!self.selected_is_default(push)).then_some(remote);
+
+		match sync::set_default_remote(
+			&self.repo.borrow(),
+			remote.as_deref(),
+			push,
+		) {
+			Ok(()) => {
+				self.queue.push(InternalEvent::Update(
+					NeedsUpdate::ALL | NeedsUpdate::REMOTES,
+				));
+			}
+			Err(e) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("set default remote error:\n{e}"),
+				));
+			}
+		}
+	}
+
 	fn delete_remote(&self) {
 		let remote_name =
 			self.remote_names[self.selection as usize].clone();
@@ -479,3 +566,21 @@ impl RemoteListPopup {
 		}
 	}
 }
+
+/// keeps the end of `url` if it is longer than `length`
+fn trim_url(url: String, length: usize) -> String {
+	const THREE_DOTS: &str = "...";
+	const THREE_DOTS_LENGTH: usize = THREE_DOTS.len();
+
+	if url.len() > length.saturating_sub(THREE_DOTS_LENGTH) {
+		let url = url
+			.chars()
+			.skip(
+				url.len() - length.saturating_sub(THREE_DOTS_LENGTH),
+			)
+			.collect::<String>();
+		format!("{THREE_DOTS}{url}")
+	} else {
+		url
+	}
+}
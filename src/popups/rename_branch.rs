@@ -19,6 +19,7 @@ pub struct RenameBranchPopup {
 	repo: RepoPathRef,
 	input: TextInputComponent,
 	branch_ref: Option<String>,
+	cur_name: String,
 	queue: Queue,
 	key_config: SharedKeyConfig,
 	theme: SharedTheme,
@@ -47,7 +48,7 @@ impl Component for RenameBranchPopup {
 				strings::commands::rename_branch_confirm_msg(
 					&self.key_config,
 				),
-				true,
+				self.is_valid(),
 				true,
 			));
 		}
@@ -62,7 +63,9 @@ impl Component for RenameBranchPopup {
 			}
 
 			if let Event::Key(e) = ev {
-				if key_match(e, self.key_config.keys.enter) {
+				if key_match(e, self.key_config.keys.enter)
+					&& self.is_valid()
+				{
 					self.rename_branch();
 				}
 
@@ -101,6 +104,7 @@ impl RenameBranchPopup {
 			)
 			.with_input_type(InputType::Singleline),
 			branch_ref: None,
+			cur_name: String::new(),
 			key_config: env.key_config.clone(),
 			theme: env.theme.clone(),
 		}
@@ -112,17 +116,24 @@ impl RenameBranchPopup {
 		branch_ref: String,
 		cur_name: String,
 	) -> Result<()> {
-		self.branch_ref = None;
 		self.branch_ref = Some(branch_ref);
-		self.input.set_text(cur_name);
+		self.input.set_text(cur_name.clone());
+		self.cur_name = cur_name;
 		self.show()?;
 
 		Ok(())
 	}
 
+	fn is_valid(&self) -> bool {
+		sync::validate_branch_name(self.input.get_text())
+			.unwrap_or_default()
+	}
+
 	///
 	pub fn rename_branch(&mut self) {
-		if let Some(br) = &self.branch_ref {
+		if self.input.get_text() == self.cur_name {
+			self.hide();
+		} else if let Some(br) = &self.branch_ref {
 			let res = sync::rename_branch(
 				&self.repo.borrow(),
 				br,
@@ -23,6 +23,8 @@ pub struct RenameRemotePopup {
 	key_config: SharedKeyConfig,
 	queue: Queue,
 	initial_name: Option<String>,
+	/// names taken already
+	remotes: Vec<String>,
 }
 
 impl DrawableComponent for RenameRemotePopup {
@@ -103,11 +105,13 @@ impl RenameRemotePopup {
 			key_config: env.key_config.clone(),
 			queue: env.queue.clone(),
 			initial_name: None,
+			remotes: Vec::new(),
 		}
 	}
 
 	///
 	pub fn open(&mut self, cur_name: String) -> Result<()> {
+		self.remotes = sync::get_remotes(&self.repo.borrow())?;
 		self.input.set_text(cur_name.clone());
 		self.initial_name = Some(cur_name);
 		self.show()?;
@@ -115,38 +119,51 @@ impl RenameRemotePopup {
 		Ok(())
 	}
 
+	/// what is wrong with the new name, `None` while it is empty
+	fn input_error(&self) -> Option<&'static str> {
+		let text = self.input.get_text();
+
+		if text.is_empty() {
+			None
+		} else if !sync::validate_remote_name(text) {
+			Some(strings::branch_name_invalid())
+		} else if self.initial_name.as_deref() != Some(text)
+			&& self.remotes.iter().any(|remote| remote == text)
+		{
+			Some(strings::remote_name_exists())
+		} else {
+			None
+		}
+	}
+
 	fn draw_warnings(&self, f: &mut Frame) {
-		let current_text = self.input.get_text();
-
-		if !current_text.is_empty() {
-			let valid = sync::validate_remote_name(current_text);
-
-			if !valid {
-				let msg = strings::branch_name_invalid();
-				let msg_length: u16 = msg.len().cast();
-				let w = Paragraph::new(msg)
-					.style(self.theme.text_danger());
-
-				let rect = {
-					let mut rect = self.input.get_area();
-					rect.y += rect.height.saturating_sub(1);
-					rect.height = 1;
-					let offset =
-						rect.width.saturating_sub(msg_length + 1);
-					rect.width =
-						rect.width.saturating_sub(offset + 1);
-					rect.x += offset;
-
-					rect
-				};
-
-				f.render_widget(w, rect);
-			}
+		if let Some(msg) = self.input_error() {
+			let msg_length: u16 = msg.len().cast();
+			let w =
+				Paragraph::new(msg).style(self.theme.text_danger());
+
+			let rect = {
+				let mut rect = self.input.get_area();
+				rect.y += rect.height.saturating_sub(1);
+				rect.height = 1;
+				let offset =
+					rect.width.saturating_sub(msg_length + 1);
+				rect.width = rect.width.saturating_sub(offset + 1);
+				rect.x += offset;
+
+				rect
+			};
+
+			f.render_widget(w, rect);
 		}
 	}
 
 	///
 	pub fn rename_remote(&mut self) {
+		if self.input_error().is_some() {
+			return;
+		}
+
 		if let Some(init_name) = &self.initial_name {
 			if init_name != self.input.get_text() {
 				let res = sync::rename_remote(
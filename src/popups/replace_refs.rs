@@ -0,0 +1,349 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState,
+};
+use crate::{
+	app::Environment,
+	components::ScrollType,
+	keys::{key_match, SharedKeyConfig},
+	queue::{Action, InternalEvent, Queue},
+	strings,
+	ui::{self, Size},
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	get_commit_info, get_replace_refs, ReplaceRef, RepoPathRef,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Margin, Rect},
+	text::Span,
+	widgets::{
+		Block, BorderType, Borders, Cell, Clear, Row, Table,
+		TableState,
+	},
+	Frame,
+};
+use ui::style::SharedTheme;
+
+struct ReplaceRefEntry {
+	replace: ReplaceRef,
+	/// `None` if the replaced object is no commit
+	message: Option<String>,
+}
+
+/// lists the objects `git replace` replaces
+pub struct ReplaceRefsPopup {
+	repo: RepoPathRef,
+	theme: SharedTheme,
+	queue: Queue,
+	entries: Vec<ReplaceRefEntry>,
+	visible: bool,
+	table_state: std::cell::Cell<TableState>,
+	current_height: std::cell::Cell<usize>,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for ReplaceRefsPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.visible {
+			const PERCENT_SIZE: Size = Size::new(80, 50);
+			const MIN_SIZE: Size = Size::new(60, 20);
+
+			let area = ui::centered_rect(
+				PERCENT_SIZE.width,
+				PERCENT_SIZE.height,
+				f.area(),
+			);
+			let area =
+				ui::rect_inside(MIN_SIZE, f.area().into(), area);
+			let area = area.intersection(rect);
+
+			let constraints = [
+				// kind
+				Constraint::Length(7),
+				// original
+				Constraint::Length(7),
+				// replacement
+				Constraint::Length(9),
+				// message
+				Constraint::Percentage(100),
+			];
+
+			let rows: Vec<Row> = self
+				.entries
+				.iter()
+				.map(|e| self.get_row(e))
+				.collect();
+			let number_of_rows = rows.len();
+
+			let table = Table::new(rows, constraints)
+				.column_spacing(1)
+				.row_highlight_style(self.theme.text(true, true))
+				.block(
+					Block::default()
+						.borders(Borders::ALL)
+						.title(Span::styled(
+							strings::title_replace_refs(),
+							self.theme.title(true),
+						))
+						.border_style(self.theme.block(true))
+						.border_type(BorderType::Thick),
+				);
+
+			let mut table_state = self.table_state.take();
+
+			f.render_widget(Clear, area);
+			f.render_stateful_widget(table, area, &mut table_state);
+
+			let area = area.inner(Margin {
+				vertical: 1,
+				horizontal: 0,
+			});
+
+			ui::draw_scrollbar(
+				f,
+				area,
+				&self.theme,
+				number_of_rows,
+				table_state.selected().unwrap_or(0),
+				ui::Orientation::Vertical,
+			);
+
+			self.table_state.set(table_state);
+			self.current_height.set(area.height.into());
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for ReplaceRefsPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			if !force_all {
+				out.clear();
+			}
+
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::delete_replace_ref(
+					&self.key_config,
+				),
+				self.selected_entry().is_some(),
+				true,
+			));
+		}
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, event: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(key) = event {
+				if key_match(key, self.key_config.keys.exit_popup) {
+					self.hide();
+				} else if key_match(key, self.key_config.keys.move_up)
+				{
+					self.move_selection(ScrollType::Up);
+				} else if key_match(
+					key,
+					self.key_config.keys.move_down,
+				) {
+					self.move_selection(ScrollType::Down);
+				} else if key_match(
+					key,
+					self.key_config.keys.shift_up,
+				) || key_match(
+					key,
+					self.key_config.keys.home,
+				) {
+					self.move_selection(ScrollType::Home);
+				} else if key_match(
+					key,
+					self.key_config.keys.shift_down,
+				) || key_match(
+					key,
+					self.key_config.keys.end,
+				) {
+					self.move_selection(ScrollType::End);
+				} else if key_match(
+					key,
+					self.key_config.keys.page_down,
+				) {
+					self.move_selection(ScrollType::PageDown);
+				} else if key_match(key, self.key_config.keys.page_up)
+				{
+					self.move_selection(ScrollType::PageUp);
+				} else if key_match(
+					key,
+					self.key_config.keys.replace_ref_delete,
+				) {
+					if let Some(entry) = self.selected_entry() {
+						self.queue.push(
+							InternalEvent::ConfirmAction(
+								Action::DeleteReplaceRef(
+									entry.replace.original,
+								),
+							),
+						);
+					}
+				}
+			}
+
+			Ok(EventState::Consumed)
+		} else {
+			Ok(EventState::NotConsumed)
+		}
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
+
+impl ReplaceRefsPopup {
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			theme: env.theme.clone(),
+			queue: env.queue.clone(),
+			entries: Vec::new(),
+			visible: false,
+			table_state: std::cell::Cell::new(TableState::default()),
+			current_height: std::cell::Cell::new(0),
+			key_config: env.key_config.clone(),
+			repo: env.repo.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self) -> Result<()> {
+		self.update_entries()?;
+		self.table_state.get_mut().select(Some(0));
+		self.show()?;
+
+		Ok(())
+	}
+
+	/// a replace ref was deleted
+	pub fn update_entries(&mut self) -> Result<()> {
+		let repo = self.repo.borrow();
+
+		self.entries = get_replace_refs(&repo)?
+			.into_iter()
+			.map(|replace| ReplaceRefEntry {
+				message: get_commit_info(&repo, &replace.original)
+					.ok()
+					.map(|info| info.message),
+				replace,
+			})
+			.collect();
+
+		let max_selection = self.entries.len().saturating_sub(1);
+		let table_state = self.table_state.get_mut();
+		table_state.select(Some(
+			table_state.selected().unwrap_or(0).min(max_selection),
+		));
+
+		Ok(())
+	}
+
+	///
+	fn move_selection(&self, scroll_type: ScrollType) -> bool {
+		let mut table_state = self.table_state.take();
+
+		let old_selection = table_state.selected().unwrap_or(0);
+		let max_selection = self.entries.len().saturating_sub(1);
+
+		let new_selection = match scroll_type {
+			ScrollType::Up => old_selection.saturating_sub(1),
+			ScrollType::Down => {
+				old_selection.saturating_add(1).min(max_selection)
+			}
+			ScrollType::Home => 0,
+			ScrollType::End => max_selection,
+			ScrollType::PageUp => old_selection.saturating_sub(
+				self.current_height.get().saturating_sub(1),
+			),
+			ScrollType::PageDown => old_selection
+				.saturating_add(
+					self.current_height.get().saturating_sub(1),
+				)
+				.min(max_selection),
+			ScrollType::HalfPageUp => old_selection.saturating_sub(
+				(self.current_height.get().saturating_sub(1)) / 2,
+			),
+			ScrollType::HalfPageDown => old_selection
+				.saturating_add(
+					(self.current_height.get().saturating_sub(1)) / 2,
+				)
+				.min(max_selection),
+		};
+
+		let needs_update = new_selection != old_selection;
+
+		table_state.select(Some(new_selection));
+		self.table_state.set(table_state);
+
+		needs_update
+	}
+
+	fn get_row(&self, entry: &ReplaceRefEntry) -> Row<'_> {
+		let kind = if entry.replace.graft {
+			"graft"
+		} else {
+			"replace"
+		};
+
+		let cells: Vec<Cell> = vec![
+			Cell::from(kind).style(self.theme.tags(false)),
+			Cell::from(entry.replace.original.get_short_string())
+				.style(self.theme.commit_hash(false)),
+			Cell::from(format!(
+				"\u{2192} {}",
+				entry.replace.replacement.get_short_string()
+			))
+			.style(self.theme.commit_hash(false)),
+			Cell::from(entry.message.clone().unwrap_or_default())
+				.style(self.theme.text(true, false)),
+		];
+
+		Row::new(cells)
+	}
+
+	fn selected_entry(&self) -> Option<&ReplaceRefEntry> {
+		let table_state = self.table_state.take();
+
+		let entry = table_state
+			.selected()
+			.and_then(|selected| self.entries.get(selected));
+
+		self.table_state.set(table_state);
+
+		entry
+	}
+}
@@ -5,7 +5,7 @@ use crate::components::{
 use crate::{
 	app::Environment,
 	keys::{key_match, SharedKeyConfig},
-	queue::Queue,
+	queue::{Action, InternalEvent, Queue},
 	strings, try_or_popup,
 	ui::{self, style::SharedTheme},
 };
@@ -43,6 +43,9 @@ pub struct ResetPopup {
 	queue: Queue,
 	repo: RepoPath,
 	commit: Option<CommitId>,
+	/// selector of the reflog entry `commit` is from, resetting to
+	/// it is confirmed once more
+	reflog: Option<String>,
 	kind: ResetType,
 	git_branch_name: cached::BranchName,
 	visible: bool,
@@ -57,6 +60,7 @@ impl ResetPopup {
 			queue: env.queue.clone(),
 			repo: env.repo.borrow().clone(),
 			commit: None,
+			reflog: None,
 			kind: ResetType::Soft,
 			git_branch_name: cached::BranchName::new(
 				env.repo.clone(),
@@ -113,6 +117,20 @@ impl ResetPopup {
 		self.show()?;
 
 		self.commit = Some(id);
+		self.reflog = None;
+
+		Ok(())
+	}
+
+	/// resets to the commit of the reflog entry `selector` after
+	/// another confirmation
+	pub fn open_reflog(
+		&mut self,
+		id: CommitId,
+		selector: String,
+	) -> Result<()> {
+		self.open(id)?;
+		self.reflog = Some(selector);
 
 		Ok(())
 	}
@@ -126,7 +144,17 @@ impl ResetPopup {
 	}
 
 	fn reset(&mut self) {
-		if let Some(id) = self.commit {
+		if let (Some(id), Some(selector)) =
+			(self.commit, self.reflog.clone())
+		{
+			self.queue.push(InternalEvent::ConfirmAction(
+				Action::ResetReflog {
+					id,
+					selector,
+					kind: self.kind,
+				},
+			));
+		} else if let Some(id) = self.commit {
 			try_or_popup!(
 				self,
 				"reset:",
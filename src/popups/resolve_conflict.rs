@@ -0,0 +1,499 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState,
+};
+use crate::{
+	app::Environment,
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings, try_or_popup,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	self, Conflict, ConflictResolution, ConflictSide, ConflictSides,
+	ConflictType, RepoPathRef,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Alignment, Rect},
+	text::{Line, Span},
+	widgets::{Block, Borders, Clear, Paragraph},
+	Frame,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Choice {
+	Resolve(ConflictResolution),
+	/// take one side for all conflicts in a folder
+	Side(ConflictSide),
+	/// pick a side for each conflict block in the conflict editor
+	EditBlocks,
+	/// record the resolution in the working dir file for rerere
+	RerereRecord,
+	/// drop the recorded resolution of rerere
+	RerereForget,
+}
+
+pub struct ResolveConflictPopup {
+	queue: Queue,
+	repo: RepoPathRef,
+	conflict: Option<Conflict>,
+	/// resolving all conflicts beneath it and how many there are
+	folder: Option<(String, usize)>,
+	/// branch names of ours and theirs
+	sides: Option<ConflictSides>,
+	choices: Vec<Choice>,
+	selection: usize,
+	visible: bool,
+	key_config: SharedKeyConfig,
+	theme: SharedTheme,
+}
+
+impl ResolveConflictPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			queue: env.queue.clone(),
+			repo: env.repo.clone(),
+			conflict: None,
+			folder: None,
+			sides: None,
+			choices: Vec::new(),
+			selection: 0,
+			visible: false,
+			key_config: env.key_config.clone(),
+			theme: env.theme.clone(),
+		}
+	}
+
+	/// `path` is a conflicted file or a folder with conflicts in it
+	pub fn open(&mut self, path: &str) -> Result<()> {
+		self.sides = sync::conflict_sides(&self.repo.borrow()).ok();
+
+		let Some(conflict) =
+			sync::get_conflict(&self.repo.borrow(), path)?
+		else {
+			return self.open_folder(path);
+		};
+
+		self.choices = conflict
+			.kind
+			.resolutions()
+			.iter()
+			.copied()
+			.map(Choice::Resolve)
+			.collect();
+
+		let text_conflict = matches!(
+			conflict.kind,
+			ConflictType::BothModified | ConflictType::BothAdded
+		);
+		if text_conflict && conflict.rename.is_none() {
+			self.choices.insert(0, Choice::EditBlocks);
+		}
+
+		let repo = self.repo.borrow();
+		if text_conflict && sync::rerere_enabled(&repo)? {
+			self.choices.push(Choice::RerereRecord);
+
+			if sync::rerere_has_resolution(&repo, path)? {
+				self.choices.push(Choice::RerereForget);
+			}
+		}
+		drop(repo);
+
+		self.conflict = Some(conflict);
+		self.folder = None;
+		self.selection = 0;
+
+		self.show()
+	}
+
+	fn open_folder(&mut self, path: &str) -> Result<()> {
+		let folder = path.trim_end_matches('/');
+		let count = sync::get_conflicts(&self.repo.borrow())?
+			.iter()
+			.filter(|conflict| {
+				conflict
+					.path
+					.strip_prefix(folder)
+					.is_some_and(|rest| rest.starts_with('/'))
+			})
+			.count();
+
+		if count == 0 {
+			self.queue.push(InternalEvent::ShowErrorMsg(format!(
+				"'{path}' is not conflicted"
+			)));
+			return Ok(());
+		}
+
+		self.choices = vec![
+			Choice::Side(ConflictSide::Ours),
+			Choice::Side(ConflictSide::Theirs),
+		];
+		self.conflict = None;
+		self.folder = Some((folder.to_string(), count));
+		self.selection = 0;
+
+		self.show()
+	}
+
+	fn side_name(&self, side: ConflictSide) -> &str {
+		match (&self.sides, side) {
+			(Some(sides), ConflictSide::Ours) => &sides.ours,
+			(Some(sides), ConflictSide::Theirs) => &sides.theirs,
+			(None, ConflictSide::Ours) => "ours",
+			(None, ConflictSide::Theirs) => "theirs",
+		}
+	}
+
+	fn choice_text(&self, choice: Choice) -> String {
+		match choice {
+			Choice::Resolve(
+				resolution @ (ConflictResolution::TakeOurs
+				| ConflictResolution::TakeTheirs),
+			) => format!(
+				"{} ({})",
+				strings::conflict_resolution(resolution),
+				self.side_name(
+					if resolution == ConflictResolution::TakeOurs {
+						ConflictSide::Ours
+					} else {
+						ConflictSide::Theirs
+					}
+				)
+			),
+			Choice::Resolve(resolution) => {
+				strings::conflict_resolution(resolution).to_string()
+			}
+			Choice::Side(side) => strings::resolve_folder_with_side(
+				side,
+				self.side_name(side),
+				self.folder.as_ref().map_or(0, |(_, count)| *count),
+			),
+			Choice::EditBlocks => {
+				strings::EDIT_CONFLICT_BLOCKS.to_string()
+			}
+			Choice::RerereRecord => {
+				strings::RERERE_RECORD.to_string()
+			}
+			Choice::RerereForget => {
+				strings::RERERE_FORGET.to_string()
+			}
+		}
+	}
+
+	fn get_text(&self) -> Vec<Line<'_>> {
+		let label = |text: &'static str| {
+			Span::styled(text, self.theme.text(true, false))
+		};
+
+		let mut txt = if let Some((folder, _)) = &self.folder {
+			vec![Line::from(vec![
+				label("Folder: "),
+				Span::styled(
+					folder.as_str(),
+					self.theme.text(true, true),
+				),
+			])]
+		} else if let Some(conflict) = &self.conflict {
+			self.get_conflict_text(conflict)
+		} else {
+			return Vec::new();
+		};
+
+		if !self.conflict.as_ref().is_some_and(|c| c.rename.is_some())
+		{
+			for (text, side) in [
+				("Ours: ", ConflictSide::Ours),
+				("Theirs: ", ConflictSide::Theirs),
+			] {
+				txt.push(Line::from(vec![
+					label(text),
+					Span::styled(
+						self.side_name(side).to_string(),
+						self.theme.text(true, true),
+					),
+				]));
+			}
+		}
+
+		txt.push(Line::default());
+
+		for (idx, choice) in self.choices.iter().enumerate() {
+			let selected = idx == self.selection;
+
+			txt.push(Line::from(Span::styled(
+				format!(
+					"{} {}",
+					if selected { ">" } else { " " },
+					self.choice_text(*choice)
+				),
+				self.theme.text(true, selected),
+			)));
+		}
+
+		txt
+	}
+
+	fn get_conflict_text<'a>(
+		&'a self,
+		conflict: &'a Conflict,
+	) -> Vec<Line<'a>> {
+		let label = |text: &'static str| {
+			Span::styled(text, self.theme.text(true, false))
+		};
+
+		let mut txt = vec![
+			Line::from(vec![
+				label("File: "),
+				Span::styled(
+					conflict.path.as_str(),
+					self.theme.text(true, true),
+				),
+			]),
+			Line::from(vec![
+				label("Conflict: "),
+				Span::styled(
+					strings::conflict_type(conflict.kind),
+					self.theme.text(true, true),
+				),
+			]),
+		];
+
+		if let Some(rename) = &conflict.rename {
+			txt.push(Line::from(vec![
+				label("Renamed from: "),
+				Span::styled(
+					rename.ancestor.as_str(),
+					self.theme.text(true, false),
+				),
+			]));
+			for (text, side, path) in [
+				("Ours: ", ConflictSide::Ours, &rename.ours),
+				("Theirs: ", ConflictSide::Theirs, &rename.theirs),
+			] {
+				txt.push(Line::from(vec![
+					label(text),
+					Span::styled(
+						format!("{path} ({})", self.side_name(side)),
+						self.theme.text(true, true),
+					),
+				]));
+			}
+		}
+
+		txt
+	}
+
+	fn resolve(&mut self) {
+		let choice = self.choices.get(self.selection).copied();
+
+		if let (Some((folder, _)), Some(Choice::Side(side))) =
+			(self.folder.take(), choice)
+		{
+			self.resolve_folder(&folder, side);
+			self.hide();
+			return;
+		}
+
+		if let (Some(conflict), Some(choice)) =
+			(self.conflict.take(), choice)
+		{
+			let repo = self.repo.borrow().clone();
+			let res = match choice {
+				Choice::Resolve(resolution) => {
+					sync::resolve_conflict(
+						&repo, &conflict, resolution,
+					)
+				}
+				Choice::Side(side) => conflict
+					.kind
+					.side_resolution(side)
+					.map_or(Ok(()), |resolution| {
+						sync::resolve_conflict(
+							&repo, &conflict, resolution,
+						)
+					}),
+				Choice::RerereRecord => {
+					sync::rerere_record(&repo, &conflict.path)
+				}
+				Choice::RerereForget => {
+					sync::rerere_forget(&repo, &conflict.path)
+				}
+				Choice::EditBlocks => {
+					self.queue.push(
+						InternalEvent::OpenConflictEditor(
+							conflict.path.clone(),
+						),
+					);
+					Ok(())
+				}
+			};
+
+			// the merge still needs to be edited by the user
+			if res.is_ok()
+				&& choice
+					== Choice::Resolve(ConflictResolution::MergeBoth)
+			{
+				self.queue.push(InternalEvent::OpenExternalEditor(
+					Some(conflict.path),
+				));
+			}
+
+			try_or_popup!(self, "resolve conflict error:", res);
+
+			self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+		}
+
+		self.hide();
+	}
+
+	fn resolve_folder(&self, folder: &str, side: ConflictSide) {
+		match sync::resolve_conflicts_with_side(
+			&self.repo.borrow(),
+			folder,
+			side,
+		) {
+			Ok(left) if !left.is_empty() => {
+				let paths: Vec<String> =
+					left.into_iter().map(|c| c.path).collect();
+				self.queue.push(InternalEvent::ShowInfoMsg(
+					strings::conflicts_left(&paths),
+				));
+			}
+			Ok(_) => (),
+			Err(e) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("resolve conflicts error:\n{e}"),
+				));
+			}
+		}
+
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+	}
+
+	fn move_selection(&mut self, down: bool) {
+		let count = self.choices.len();
+
+		if count > 0 {
+			self.selection = if down {
+				(self.selection + 1) % count
+			} else {
+				(self.selection + count - 1) % count
+			};
+		}
+	}
+}
+
+impl DrawableComponent for ResolveConflictPopup {
+	fn draw(&self, f: &mut Frame, area: Rect) -> Result<()> {
+		if self.is_visible() {
+			let txt = self.get_text();
+
+			let height = u16::try_from(txt.len())?.saturating_add(2);
+			let area = ui::centered_rect_absolute(70, height, area);
+
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Paragraph::new(txt)
+					.block(
+						Block::default()
+							.borders(Borders::ALL)
+							.title(Span::styled(
+								strings::POPUP_TITLE_RESOLVE_CONFLICT,
+								self.theme.title(true),
+							))
+							.border_style(self.theme.block(true)),
+					)
+					.alignment(Alignment::Left),
+				area,
+			);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for ResolveConflictPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			out.push(
+				CommandInfo::new(
+					strings::commands::close_popup(&self.key_config),
+					true,
+					true,
+				)
+				.order(1),
+			);
+
+			out.push(
+				CommandInfo::new(
+					strings::commands::resolve_conflict_confirm(
+						&self.key_config,
+					),
+					true,
+					true,
+				)
+				.order(1),
+			);
+
+			out.push(
+				CommandInfo::new(
+					strings::commands::resolve_conflict_choice(
+						&self.key_config,
+					),
+					true,
+					true,
+				)
+				.order(1),
+			);
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, event: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if let Event::Key(key) = &event {
+				if key_match(key, self.key_config.keys.exit_popup) {
+					self.hide();
+				} else if key_match(
+					key,
+					self.key_config.keys.move_down,
+				) {
+					self.move_selection(true);
+				} else if key_match(key, self.key_config.keys.move_up)
+				{
+					self.move_selection(false);
+				} else if key_match(key, self.key_config.keys.enter) {
+					self.resolve();
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
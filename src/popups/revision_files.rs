@@ -69,6 +69,14 @@ impl RevisionFilesPopup {
 		self.files.find_file(file);
 	}
 
+	pub fn file_finder_cancel(&mut self) {
+		self.files.find_file_cancel();
+	}
+
+	pub fn file_grep_update(&mut self, file: &Path, line: usize) {
+		self.files.find_line(file, line);
+	}
+
 	fn hide_stacked(&mut self, stack: bool) {
 		self.hide();
 
@@ -5,7 +5,7 @@ use crate::components::{
 use crate::{
 	app::Environment,
 	keys::{key_match, SharedKeyConfig},
-	queue::{AppTabs, InternalEvent, Queue},
+	queue::{AppTabs, InternalEvent, NeedsUpdate, Queue},
 	strings,
 	tabs::StashingOptions,
 };
@@ -17,6 +17,8 @@ use ratatui::{layout::Rect, Frame};
 pub struct StashMsgPopup {
 	repo: RepoPathRef,
 	options: StashingOptions,
+	/// only these are stashed if not empty
+	paths: Vec<String>,
 	input: TextInputComponent,
 	queue: Queue,
 	key_config: SharedKeyConfig,
@@ -59,16 +61,7 @@ impl Component for StashMsgPopup {
 
 			if let Event::Key(e) = ev {
 				if key_match(e, self.key_config.keys.enter) {
-					let result = sync::stash_save(
-						&self.repo.borrow(),
-						if self.input.get_text().is_empty() {
-							None
-						} else {
-							Some(self.input.get_text())
-						},
-						self.options.stash_untracked,
-						self.options.keep_index,
-					);
+					let result = self.stash();
 					match result {
 						Ok(_) => {
 							self.input.clear();
@@ -79,6 +72,9 @@ impl Component for StashMsgPopup {
 									AppTabs::Stashlist,
 								),
 							);
+							self.queue.push(InternalEvent::Update(
+								NeedsUpdate::ALL,
+							));
 						}
 						Err(e) => {
 							self.hide();
@@ -124,6 +120,7 @@ impl StashMsgPopup {
 	pub fn new(env: &Environment) -> Self {
 		Self {
 			options: StashingOptions::default(),
+			paths: Vec::new(),
 			queue: env.queue.clone(),
 			input: TextInputComponent::new(
 				env,
@@ -140,5 +137,37 @@ impl StashMsgPopup {
 	///
 	pub fn options(&mut self, options: StashingOptions) {
 		self.options = options;
+		self.paths.clear();
+		self.input
+			.set_title(strings::stash_popup_title(&self.key_config));
+	}
+
+	/// stash only the changes of `paths`, untracked files included
+	pub fn open_paths(&mut self, paths: Vec<String>) -> Result<()> {
+		self.input
+			.set_title(strings::stash_paths_popup_title(paths.len()));
+		self.paths = paths;
+
+		self.show()
+	}
+
+	fn stash(&self) -> Result<sync::CommitId> {
+		let text = self.input.get_text();
+		let message = if text.is_empty() { None } else { Some(text) };
+
+		Ok(if self.paths.is_empty() {
+			sync::stash_save(
+				&self.repo.borrow(),
+				message,
+				self.options.stash_untracked,
+				self.options.keep_index,
+			)?
+		} else {
+			sync::stash_save_paths(
+				&self.repo.borrow(),
+				message,
+				&self.paths,
+			)?
+		})
 	}
 }
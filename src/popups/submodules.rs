@@ -5,15 +5,20 @@ use crate::{
 		DrawableComponent, EventState, ScrollType, VerticalScroll,
 	},
 	keys::{key_match, SharedKeyConfig},
+	popups::PushPopup,
 	queue::{InternalEvent, NeedsUpdate, Queue},
 	strings, try_or_popup,
 	ui::{self, Size},
 };
 use anyhow::Result;
-use asyncgit::sync::{
-	get_submodules, repo_dir, submodule_parent_info,
-	update_submodule, RepoPathRef, SubmoduleInfo,
-	SubmoduleParentInfo,
+use asyncgit::{
+	asyncjob::AsyncSingleJob,
+	sync::{
+		get_submodules, init_submodule, repo_dir,
+		submodule_parent_info, RepoPathRef, SubmoduleInfo,
+		SubmoduleParentInfo,
+	},
+	AsyncGitNotification, AsyncSubmoduleUpdateJob, RemoteProgress,
 };
 use crossterm::event::Event;
 use ratatui::{
@@ -21,7 +26,7 @@ use ratatui::{
 		Alignment, Constraint, Direction, Layout, Margin, Rect,
 	},
 	text::{Line, Span, Text},
-	widgets::{Block, Borders, Clear, Paragraph},
+	widgets::{Block, Borders, Clear, Gauge, Paragraph},
 	Frame,
 };
 use std::cell::Cell;
@@ -41,6 +46,10 @@ pub struct SubmodulesListPopup {
 	scroll: VerticalScroll,
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
+	async_update: AsyncSingleJob<AsyncSubmoduleUpdateJob>,
+	/// name of the submodule being updated
+	updating: Option<String>,
+	progress: Option<RemoteProgress>,
 }
 
 impl DrawableComponent for SubmodulesListPopup {
@@ -75,8 +84,14 @@ impl DrawableComponent for SubmodulesListPopup {
 			let chunks_vertical = Layout::default()
 				.direction(Direction::Vertical)
 				.constraints(
-					[Constraint::Min(1), Constraint::Length(5)]
-						.as_ref(),
+					[
+						Constraint::Min(1),
+						Constraint::Length(5),
+						Constraint::Length(u16::from(
+							self.updating.is_some(),
+						)),
+					]
+					.as_ref(),
 				)
 				.split(area);
 
@@ -91,6 +106,7 @@ impl DrawableComponent for SubmodulesListPopup {
 			self.draw_list(f, chunks[0])?;
 			self.draw_info(f, chunks[1]);
 			self.draw_local_info(f, chunks_vertical[1]);
+			self.draw_progress(f, chunks_vertical[2]);
 		}
 
 		Ok(())
@@ -126,9 +142,15 @@ impl Component for SubmodulesListPopup {
 				true,
 			));
 
+			out.push(CommandInfo::new(
+				strings::commands::init_submodule(&self.key_config),
+				self.can_init_submodule(),
+				true,
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::update_submodule(&self.key_config),
-				self.is_valid_selection(),
+				self.is_valid_selection() && self.updating.is_none(),
 				true,
 			));
 
@@ -187,21 +209,24 @@ impl Component for SubmodulesListPopup {
 				e,
 				self.key_config.keys.update_submodule,
 			) {
-				if let Some(submodule) = self.selected_entry() {
-					try_or_popup!(
-						self,
-						"update submodule:",
-						update_submodule(
-							&self.repo.borrow(),
-							&submodule.name,
-						)
-					);
+				self.update_selected();
+			} else if key_match(
+				e,
+				self.key_config.keys.init_submodule,
+			) {
+				if self.can_init_submodule() {
+					if let Some(submodule) = self.selected_entry() {
+						try_or_popup!(
+							self,
+							"init submodule:",
+							init_submodule(
+								&self.repo.borrow(),
+								&submodule.name,
+							)
+						);
+					}
 
 					self.update_submodules()?;
-
-					self.queue.push(InternalEvent::Update(
-						NeedsUpdate::ALL,
-					));
 				}
 			} else if key_match(
 				e,
@@ -253,6 +278,64 @@ impl SubmodulesListPopup {
 			current_height: Cell::new(0),
 			repo: env.repo.clone(),
 			repo_path: String::new(),
+			async_update: AsyncSingleJob::new(env.sender_git.clone()),
+			updating: None,
+			progress: None,
+		}
+	}
+
+	///
+	pub fn any_work_pending(&self) -> bool {
+		self.async_update.is_pending()
+	}
+
+	///
+	pub fn update_git(
+		&mut self,
+		ev: AsyncGitNotification,
+	) -> Result<()> {
+		if ev != AsyncGitNotification::SubmoduleUpdate {
+			return Ok(());
+		}
+
+		self.progress = self.async_update.progress().flatten();
+
+		if self.async_update.is_pending() {
+			return Ok(());
+		}
+
+		let name = self.updating.take().unwrap_or_default();
+		self.progress = None;
+
+		if let Some(Err(e)) =
+			self.async_update.take_last().and_then(|job| job.result())
+		{
+			self.queue.push(InternalEvent::ShowErrorMsg(format!(
+				"update submodule '{name}' failed:\n{e}"
+			)));
+		}
+
+		self.update_submodules()?;
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+
+		Ok(())
+	}
+
+	/// clones or fetches the selected submodule in the background
+	fn update_selected(&mut self) {
+		if self.updating.is_some() {
+			return;
+		}
+
+		if let Some(submodule) = self.selected_entry() {
+			let name = submodule.name.clone();
+
+			self.async_update.spawn(AsyncSubmoduleUpdateJob::new(
+				self.repo.borrow().clone(),
+				name.clone(),
+			));
+			self.updating = Some(name);
+			self.progress = None;
 		}
 	}
 
@@ -293,6 +376,13 @@ impl SubmodulesListPopup {
 		self.selected_entry().is_some_and(|s| s.status.is_in_wd())
 	}
 
+	fn can_init_submodule(&self) -> bool {
+		self.updating.is_none()
+			&& self
+				.selected_entry()
+				.is_some_and(|s| s.status.is_wd_uninitialized())
+	}
+
 	//TODO: dedup this almost identical with BranchListComponent
 	fn move_selection(&mut self, scroll: ScrollType) -> Result<bool> {
 		let new_selection = match scroll {
@@ -512,6 +602,23 @@ impl SubmodulesListPopup {
 		);
 	}
 
+	fn draw_progress(&self, f: &mut Frame, r: Rect) {
+		if let Some(name) = &self.updating {
+			let (state, progress) =
+				PushPopup::get_progress(self.progress.as_ref());
+
+			f.render_widget(
+				Gauge::default()
+					.label(strings::submodule_update_progress(
+						name, &state,
+					))
+					.gauge_style(self.theme.push_gauge())
+					.percent(u16::from(progress)),
+				r,
+			);
+		}
+	}
+
 	fn draw_local_info(&self, f: &mut Frame, r: Rect) {
 		f.render_widget(
 			Paragraph::new(self.get_local_info_text(&self.theme))
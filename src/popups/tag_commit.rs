@@ -5,12 +5,14 @@ use crate::components::{
 use crate::{
 	app::Environment,
 	keys::{key_match, SharedKeyConfig},
+	popups::{PushAfterCreate, PushTarget},
 	queue::{InternalEvent, NeedsUpdate, Queue},
 	strings, try_or_popup,
 };
 use anyhow::Result;
-use asyncgit::sync::{
-	self, get_config_string, CommitId, RepoPathRef,
+use asyncgit::{
+	sync::{self, get_config_string, CommitId, RepoPathRef},
+	PushType,
 };
 use crossterm::event::Event;
 use ratatui::{layout::Rect, Frame};
@@ -27,12 +29,17 @@ pub struct TagCommitPopup {
 	commit_id: Option<CommitId>,
 	queue: Queue,
 	key_config: SharedKeyConfig,
+	push_target: PushTarget,
 }
 
 impl DrawableComponent for TagCommitPopup {
 	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
 		self.input.draw(f, rect)?;
 
+		if self.is_visible() {
+			self.push_target.draw(f, self.input.get_area());
+		}
+
 		Ok(())
 	}
 }
@@ -63,6 +70,14 @@ impl Component for TagCommitPopup {
 				self.is_valid_tag(),
 				matches!(self.mode, Mode::Name),
 			));
+
+			out.push(CommandInfo::new(
+				strings::commands::toggle_push_after_create(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
 		}
 
 		visibility_blocking(self)
@@ -78,13 +93,19 @@ impl Component for TagCommitPopup {
 					&& key_match(e, self.key_config.keys.enter)
 					&& self.is_valid_tag()
 				{
-					try_or_popup!(self, "tag error:", self.tag());
+					// signed tags need a tag object to carry the
+					// signature
+					if self.gpgsign() {
+						self.start_annotate_mode();
+					} else {
+						self.tag();
+					}
 					return Ok(EventState::Consumed);
 				}
 				if is_annotation_mode
 					&& key_match(e, self.key_config.keys.commit)
 				{
-					try_or_popup!(self, "tag error:", self.tag());
+					self.tag();
 					return Ok(EventState::Consumed);
 				} else if key_match(
 					e,
@@ -93,6 +114,16 @@ impl Component for TagCommitPopup {
 				{
 					self.start_annotate_mode();
 					return Ok(EventState::Consumed);
+				} else if key_match(
+					e,
+					self.key_config.keys.toggle_push_after_create,
+				) {
+					try_or_popup!(
+						self,
+						"push target error:",
+						self.push_target.cycle(&self.repo.borrow())
+					);
+					return Ok(EventState::Consumed);
 				}
 			}
 
@@ -137,12 +168,14 @@ impl TagCommitPopup {
 			key_config: env.key_config.clone(),
 			repo: env.repo.clone(),
 			mode: Mode::Name,
+			push_target: PushTarget::default(),
 		}
 	}
 
 	///
 	pub fn open(&mut self, id: CommitId) -> Result<()> {
 		self.commit_id = Some(id);
+		self.push_target.reset();
 		self.show()?;
 
 		Ok(())
@@ -161,16 +194,16 @@ impl TagCommitPopup {
 		}
 	}
 
-	pub fn tag(&mut self) -> Result<()> {
-		let gpgsign =
-			get_config_string(&self.repo.borrow(), "tag.gpgsign")
-				.ok()
-				.flatten()
-				.and_then(|val| val.parse::<bool>().ok())
-				.unwrap_or_default();
-
-		anyhow::ensure!(!gpgsign, "config tag.gpgsign=true detected.\ngpg signing not supported.\ndeactivate in your repo/gitconfig to be able to tag without signing.");
+	/// `tag.gpgSign`, annotated tags get signed then
+	fn gpgsign(&self) -> bool {
+		get_config_string(&self.repo.borrow(), "tag.gpgsign")
+			.ok()
+			.flatten()
+			.and_then(|val| val.parse::<bool>().ok())
+			.unwrap_or_default()
+	}
 
+	pub fn tag(&mut self) {
 		let (tag_name, tag_annotation) = self.tag_info();
 
 		if let Some(commit_id) = self.commit_id {
@@ -186,8 +219,20 @@ impl TagCommitPopup {
 					self.hide();
 
 					self.queue.push(InternalEvent::Update(
-						NeedsUpdate::ALL,
+						NeedsUpdate::ALL | NeedsUpdate::TAGS,
 					));
+
+					if let Some(remote) = self.push_target.remote() {
+						self.queue.push(
+							InternalEvent::PushAfterCreate(
+								PushAfterCreate {
+									name: tag_name,
+									push_type: PushType::Tag,
+									remote: remote.to_string(),
+								},
+							),
+						);
+					}
 				}
 				Err(e) => {
 					// go back to tag name if something goes wrong
@@ -201,8 +246,6 @@ impl TagCommitPopup {
 				}
 			}
 		}
-
-		Ok(())
 	}
 
 	fn start_annotate_mode(&mut self) {
@@ -212,6 +255,7 @@ impl TagCommitPopup {
 		self.input.set_input_type(InputType::Multiline);
 		self.input.set_title(strings::tag_popup_annotation_title(
 			&tag_name,
+			self.gpgsign(),
 		));
 		self.input
 			.set_default_msg(strings::tag_popup_annotation_msg());
@@ -27,16 +27,19 @@ use asyncgit::{
 
 use crossterm::event::Event;
 use ratatui::{
-	layout::{Constraint, Margin, Rect},
-	text::Span,
+	layout::{Constraint, Direction, Layout, Margin, Rect},
+	text::{Line, Span},
 	widgets::{
-		Block, BorderType, Borders, Cell, Clear, Row, Table,
-		TableState,
+		Block, BorderType, Borders, Cell, Clear, Paragraph, Row,
+		Table, TableState, Wrap,
 	},
 	Frame,
 };
 use ui::style::SharedTheme;
 
+/// height of the pane with the annotation of the selected tag
+const DETAILS_HEIGHT: u16 = 8;
+
 ///
 pub struct TagListPopup {
 	repo: RepoPathRef,
@@ -107,8 +110,26 @@ impl DrawableComponent for TagListPopup {
 				);
 
 			let mut table_state = self.table_state.take();
+			let details = table_state
+				.selected()
+				.and_then(|idx| self.tags.as_ref()?.get(idx))
+				.filter(|tag| tag.details.is_some());
 
 			f.render_widget(Clear, area);
+
+			let area = details.map_or(area, |tag| {
+				let chunks = Layout::default()
+					.direction(Direction::Vertical)
+					.constraints([
+						Constraint::Min(0),
+						Constraint::Length(DETAILS_HEIGHT),
+					])
+					.split(area);
+
+				self.draw_details(f, chunks[1], tag);
+				chunks[0]
+			});
+
 			f.render_stateful_widget(table, area, &mut table_state);
 
 			let area = area.inner(Margin {
@@ -166,6 +187,18 @@ impl Component for TagListPopup {
 				self.valid_selection(),
 				true,
 			));
+			out.push(CommandInfo::new(
+				strings::commands::push_tag(&self.key_config),
+				self.has_remotes && self.valid_selection(),
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::delete_tag_remote(
+					&self.key_config,
+				),
+				self.has_remotes && self.valid_selection(),
+				true,
+			));
 			out.push(CommandInfo::new(
 				strings::commands::push_tags(&self.key_config),
 				self.has_remotes,
@@ -260,7 +293,23 @@ impl Component for TagListPopup {
 				} else if key_match(key, self.key_config.keys.push)
 					&& self.has_remotes
 				{
-					self.queue.push(InternalEvent::PushTags);
+					self.confirm_remote_action(Action::PushTag);
+				} else if key_match(
+					key,
+					self.key_config.keys.delete_remote_tag,
+				) && self.has_remotes
+				{
+					self.confirm_remote_action(
+						Action::DeleteRemoteTag,
+					);
+				} else if key_match(
+					key,
+					self.key_config.keys.push_all_tags,
+				) && self.has_remotes
+				{
+					self.queue.push(InternalEvent::ConfirmAction(
+						Action::PushAllTags,
+					));
 				}
 			}
 
@@ -424,6 +473,91 @@ impl TagListPopup {
 		needs_update
 	}
 
+	/// asks to push or delete the selected tag on the remote tags are
+	/// pushed to
+	fn confirm_remote_action(
+		&self,
+		action: impl FnOnce(String, String) -> Action,
+	) {
+		let Some(tag) = self.selected_tag() else {
+			return;
+		};
+
+		match sync::get_default_remote_for_push(&self.repo.borrow()) {
+			Ok(remote) => {
+				self.queue.push(InternalEvent::ConfirmAction(
+					action(tag.name.clone(), remote),
+				));
+			}
+			Err(e) => {
+				self.queue
+					.push(InternalEvent::ShowErrorMsg(e.to_string()));
+			}
+		}
+	}
+
+	fn draw_details(
+		&self,
+		f: &mut Frame,
+		area: Rect,
+		tag: &TagWithMetadata,
+	) {
+		let Some(details) = &tag.details else {
+			return;
+		};
+
+		let mut lines = vec![
+			Line::from(vec![
+				Span::styled(
+					strings::tag_details_tagger(),
+					self.theme.text(false, false),
+				),
+				Span::styled(
+					details.tagger.clone(),
+					self.theme.text(true, false),
+				),
+			]),
+			Line::from(vec![
+				Span::styled(
+					strings::commit::details_date(),
+					self.theme.text(false, false),
+				),
+				Span::styled(
+					time_to_string(details.time, false),
+					self.theme.text(true, false),
+				),
+			]),
+			Line::default(),
+		];
+		lines.extend(
+			tag.annotation.iter().flat_map(|msg| msg.lines()).map(
+				|line| {
+					Line::from(Span::styled(
+						line.to_string(),
+						self.theme.text(true, false),
+					))
+				},
+			),
+		);
+
+		f.render_widget(
+			Paragraph::new(lines)
+				.block(
+					Block::default()
+						.borders(Borders::ALL)
+						.title(Span::styled(
+							strings::title_tag_details(
+								details.signed,
+							),
+							self.theme.title(false),
+						))
+						.border_style(self.theme.block(false)),
+				)
+				.wrap(Wrap { trim: false }),
+			area,
+		);
+	}
+
 	fn show_annotation(&self) {
 		if let Some(tag) = self.selected_tag() {
 			if let Some(annotation) = &tag.annotation {
@@ -449,7 +583,6 @@ impl TagListPopup {
 
 	///
 	fn get_row(&self, tag: &TagWithMetadata) -> Row {
-		const UPSTREAM_SYMBOL: &str = "\u{2191}";
 		const ATTACHMENT_SYMBOL: &str = "@";
 		const EMPTY_SYMBOL: &str = " ";
 
@@ -463,12 +596,12 @@ impl TagListPopup {
 			});
 
 		let has_remote_str = if is_tag_missing_on_remote {
-			UPSTREAM_SYMBOL
+			self.theme.glyphs().ahead
 		} else {
 			EMPTY_SYMBOL
 		};
 
-		let has_attachement_str = if tag.annotation.is_some() {
+		let has_attachement_str = if tag.details.is_some() {
 			ATTACHMENT_SYMBOL
 		} else {
 			EMPTY_SYMBOL
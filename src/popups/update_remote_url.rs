@@ -1,6 +1,8 @@
 use anyhow::Result;
 use asyncgit::sync::{self, RepoPathRef};
 use crossterm::event::Event;
+use easy_cast::Cast;
+use ratatui::{widgets::Paragraph, Frame};
 
 use crate::{
 	app::Environment,
@@ -11,11 +13,13 @@ use crate::{
 	keys::{key_match, SharedKeyConfig},
 	queue::{InternalEvent, NeedsUpdate, Queue},
 	strings,
+	ui::style::SharedTheme,
 };
 
 pub struct UpdateRemoteUrlPopup {
 	repo: RepoPathRef,
 	input: TextInputComponent,
+	theme: SharedTheme,
 	key_config: SharedKeyConfig,
 	queue: Queue,
 	remote_name: Option<String>,
@@ -30,6 +34,7 @@ impl DrawableComponent for UpdateRemoteUrlPopup {
 	) -> anyhow::Result<()> {
 		if self.is_visible() {
 			self.input.draw(f, rect)?;
+			self.draw_warnings(f);
 		}
 		Ok(())
 	}
@@ -102,6 +107,7 @@ impl UpdateRemoteUrlPopup {
 				true,
 			)
 			.with_input_type(InputType::Singleline),
+			theme: env.theme.clone(),
 			key_config: env.key_config.clone(),
 			queue: env.queue.clone(),
 			initial_url: None,
@@ -123,8 +129,37 @@ impl UpdateRemoteUrlPopup {
 		Ok(())
 	}
 
+	fn draw_warnings(&self, f: &mut Frame) {
+		let url = self.input.get_text();
+
+		if !url.is_empty() && !sync::validate_remote_url(url) {
+			let msg = strings::remote_url_invalid();
+			let msg_length: u16 = msg.len().cast();
+			let w =
+				Paragraph::new(msg).style(self.theme.text_danger());
+
+			let rect = {
+				let mut rect = self.input.get_area();
+				rect.y += rect.height.saturating_sub(1);
+				rect.height = 1;
+				let offset =
+					rect.width.saturating_sub(msg_length + 1);
+				rect.width = rect.width.saturating_sub(offset + 1);
+				rect.x += offset;
+
+				rect
+			};
+
+			f.render_widget(w, rect);
+		}
+	}
+
 	///
 	pub fn update_remote_url(&mut self) {
+		if !sync::validate_remote_url(self.input.get_text()) {
+			return;
+		}
+
 		if let Some(remote_name) = &self.remote_name {
 			let res = sync::update_remote_url(
 				&self.repo.borrow(),
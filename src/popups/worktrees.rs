@@ -0,0 +1,371 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState,
+};
+use crate::{
+	app::Environment,
+	components::ScrollType,
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
+	strings, try_or_popup,
+	ui::{self, Size},
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	get_worktrees, prune_worktrees, RepoPathRef, WorktreeInfo,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Margin, Rect},
+	text::Span,
+	widgets::{
+		Block, BorderType, Borders, Cell, Clear, Row, Table,
+		TableState,
+	},
+	Frame,
+};
+use ui::style::SharedTheme;
+
+/// the working trees of the repository, opening one switches gitui
+/// over to it
+pub struct WorktreesPopup {
+	repo: RepoPathRef,
+	theme: SharedTheme,
+	queue: Queue,
+	worktrees: Vec<WorktreeInfo>,
+	visible: bool,
+	table_state: std::cell::Cell<TableState>,
+	current_height: std::cell::Cell<usize>,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for WorktreesPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.visible {
+			const PERCENT_SIZE: Size = Size::new(80, 50);
+			const MIN_SIZE: Size = Size::new(60, 12);
+
+			let area = ui::centered_rect(
+				PERCENT_SIZE.width,
+				PERCENT_SIZE.height,
+				f.area(),
+			);
+			let area =
+				ui::rect_inside(MIN_SIZE, f.area().into(), area);
+			let area = area.intersection(rect);
+
+			let branch_width = self
+				.worktrees
+				.iter()
+				.fold(0, |acc, w| acc.max(Self::head_text(w).len()));
+
+			let constraints = [
+				// current
+				Constraint::Length(1),
+				// path
+				Constraint::Percentage(100),
+				// branch
+				Constraint::Length(branch_width.try_into()?),
+				// state
+				Constraint::Length(16),
+			];
+
+			let rows: Vec<Row> = self
+				.worktrees
+				.iter()
+				.map(|w| self.get_row(w))
+				.collect();
+			let number_of_rows = rows.len();
+
+			let table = Table::new(rows, constraints)
+				.column_spacing(1)
+				.row_highlight_style(self.theme.text(true, true))
+				.block(
+					Block::default()
+						.borders(Borders::ALL)
+						.title(Span::styled(
+							strings::POPUP_TITLE_WORKTREES,
+							self.theme.title(true),
+						))
+						.border_style(self.theme.block(true))
+						.border_type(BorderType::Thick),
+				);
+
+			let mut table_state = self.table_state.take();
+
+			f.render_widget(Clear, area);
+			f.render_stateful_widget(table, area, &mut table_state);
+
+			let area = area.inner(Margin {
+				vertical: 1,
+				horizontal: 0,
+			});
+
+			ui::draw_scrollbar(
+				f,
+				area,
+				&self.theme,
+				number_of_rows,
+				table_state.selected().unwrap_or(0),
+				ui::Orientation::Vertical,
+			);
+
+			self.table_state.set(table_state);
+			self.current_height.set(area.height.into());
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for WorktreesPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			if !force_all {
+				out.clear();
+			}
+
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::open_worktree(&self.key_config),
+				self.can_open_selected(),
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::prune_worktrees(&self.key_config),
+				self.worktrees.iter().any(|w| w.prunable),
+				true,
+			));
+		}
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, event: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(key) = event {
+				let keys = &self.key_config.keys;
+
+				if key_match(key, keys.exit_popup) {
+					self.hide();
+				} else if key_match(key, keys.move_up) {
+					self.move_selection(ScrollType::Up);
+				} else if key_match(key, keys.move_down) {
+					self.move_selection(ScrollType::Down);
+				} else if key_match(key, keys.shift_up)
+					|| key_match(key, keys.home)
+				{
+					self.move_selection(ScrollType::Home);
+				} else if key_match(key, keys.shift_down)
+					|| key_match(key, keys.end)
+				{
+					self.move_selection(ScrollType::End);
+				} else if key_match(key, keys.page_down) {
+					self.move_selection(ScrollType::PageDown);
+				} else if key_match(key, keys.page_up) {
+					self.move_selection(ScrollType::PageUp);
+				} else if key_match(key, keys.enter) {
+					self.open_selected();
+				} else if key_match(key, keys.prune_worktrees) {
+					self.prune();
+				}
+			}
+
+			Ok(EventState::Consumed)
+		} else {
+			Ok(EventState::NotConsumed)
+		}
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
+
+impl WorktreesPopup {
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			theme: env.theme.clone(),
+			queue: env.queue.clone(),
+			worktrees: Vec::new(),
+			visible: false,
+			table_state: std::cell::Cell::new(TableState::default()),
+			current_height: std::cell::Cell::new(0),
+			key_config: env.key_config.clone(),
+			repo: env.repo.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self) -> Result<()> {
+		self.update_worktrees()?;
+		self.show()?;
+
+		let current = self
+			.worktrees
+			.iter()
+			.position(|w| w.current)
+			.unwrap_or_default();
+		self.table_state.get_mut().select(Some(current));
+
+		Ok(())
+	}
+
+	fn update_worktrees(&mut self) -> Result<()> {
+		self.worktrees = get_worktrees(&self.repo.borrow())?;
+
+		let selected = self
+			.table_state
+			.get_mut()
+			.selected()
+			.unwrap_or_default()
+			.min(self.worktrees.len().saturating_sub(1));
+		self.table_state.get_mut().select(Some(selected));
+
+		Ok(())
+	}
+
+	fn move_selection(&self, scroll_type: ScrollType) -> bool {
+		let mut table_state = self.table_state.take();
+
+		let old_selection = table_state.selected().unwrap_or(0);
+		let max_selection = self.worktrees.len().saturating_sub(1);
+		let page = self.current_height.get().saturating_sub(1);
+
+		let new_selection = match scroll_type {
+			ScrollType::Up => old_selection.saturating_sub(1),
+			ScrollType::Down => {
+				old_selection.saturating_add(1).min(max_selection)
+			}
+			ScrollType::Home => 0,
+			ScrollType::End => max_selection,
+			ScrollType::PageUp => old_selection.saturating_sub(page),
+			ScrollType::PageDown => {
+				old_selection.saturating_add(page).min(max_selection)
+			}
+			ScrollType::HalfPageUp => {
+				old_selection.saturating_sub(page / 2)
+			}
+			ScrollType::HalfPageDown => old_selection
+				.saturating_add(page / 2)
+				.min(max_selection),
+		};
+
+		let needs_update = new_selection != old_selection;
+
+		table_state.select(Some(new_selection));
+		self.table_state.set(table_state);
+
+		needs_update
+	}
+
+	/// the branch, the commit if `HEAD` is detached
+	fn head_text(worktree: &WorktreeInfo) -> String {
+		worktree.branch.clone().unwrap_or_else(|| {
+			worktree
+				.head
+				.map(|id| id.get_short_string())
+				.unwrap_or_default()
+		})
+	}
+
+	fn get_row(&self, worktree: &WorktreeInfo) -> Row<'_> {
+		let cells: Vec<Cell> = vec![
+			Cell::from(if worktree.current { "*" } else { " " })
+				.style(self.theme.text(true, false)),
+			Cell::from(worktree.path.to_string_lossy().to_string())
+				.style(self.theme.text(!worktree.prunable, false)),
+			Cell::from(Self::head_text(worktree)).style(
+				if worktree.branch.is_some() {
+					self.theme.branch(false, worktree.current)
+				} else {
+					self.theme.commit_hash(false)
+				},
+			),
+			Cell::from(strings::worktree_state(
+				worktree.locked.as_deref(),
+				worktree.prunable,
+			))
+			.style(self.theme.text(false, false)),
+		];
+
+		Row::new(cells)
+	}
+
+	fn selected_worktree(&self) -> Option<&WorktreeInfo> {
+		let table_state = self.table_state.take();
+
+		let worktree = table_state
+			.selected()
+			.and_then(|selected| self.worktrees.get(selected));
+
+		self.table_state.set(table_state);
+
+		worktree
+	}
+
+	fn can_open_selected(&self) -> bool {
+		self.selected_worktree()
+			.is_some_and(|w| !w.current && !w.prunable)
+	}
+
+	fn open_selected(&mut self) {
+		if !self.can_open_selected() {
+			return;
+		}
+
+		if let Some(path) =
+			self.selected_worktree().map(|w| w.path.clone())
+		{
+			self.hide();
+			self.queue.push(InternalEvent::OpenRepo { path });
+		}
+	}
+
+	fn prune(&mut self) {
+		let pruned = match prune_worktrees(&self.repo.borrow()) {
+			Ok(pruned) => pruned,
+			Err(e) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("prune worktrees error:\n{e}"),
+				));
+				return;
+			}
+		};
+
+		self.queue.push(InternalEvent::ShowToast(
+			strings::worktrees_pruned(pruned),
+		));
+
+		try_or_popup!(
+			self,
+			"worktrees error:",
+			self.update_worktrees()
+		);
+	}
+}
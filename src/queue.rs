@@ -1,20 +1,23 @@
 use crate::{
 	components::FuzzyFinderTarget,
 	popups::{
-		AppOption, BlameFileOpen, FileRevOpen, FileTreeOpen,
-		InspectCommitOpen,
+		AppOption, BlameFileOpen, CompareBranchesOpen, FileRevOpen,
+		FileTreeOpen, InspectCommitOpen, PushAfterCreate,
 	},
 	tabs::StashingOptions,
 };
 use asyncgit::{
 	sync::{
-		diff::DiffLinePosition, CommitId, LogFilterSearchOptions,
+		diff::DiffLinePosition, AmendPlan, BranchType, CommitId,
+		Deepen, LogFilterCriteria, LogFilterSearchOptions, RepoPath,
+		ResetType,
 	},
-	PushType,
+	PushForce, PushType,
 };
 use bitflags::bitflags;
 use std::{
-	cell::RefCell, collections::VecDeque, path::PathBuf, rc::Rc,
+	cell::RefCell, collections::VecDeque, path::PathBuf,
+	process::Child, rc::Rc,
 };
 
 bitflags! {
@@ -30,6 +33,10 @@ bitflags! {
 		const BRANCHES = 0b1000;
 		/// Remotes have changed
 		const REMOTES = 0b1001;
+		/// only the index changed (status::update_index)
+		const INDEX = 0b10000;
+		/// tags have changed
+		const TAGS = 0b10_0000;
 	}
 }
 
@@ -37,26 +44,102 @@ bitflags! {
 pub struct ResetItem {
 	/// path to the item (folder/file)
 	pub path: String,
+	/// the submodule the path is in, `None` for the repo itself
+	pub submodule: Option<RepoPath>,
+}
+
+/// status item that is supposed to be ignored
+pub struct IgnoreItem {
+	/// repo relative path of the item (folder/file)
+	pub path: String,
+	///
+	pub is_dir: bool,
+	/// the item (or something inside the folder) is tracked
+	pub tracked: bool,
+}
+
+/// a spawned process whose output is shown while it runs
+pub struct ProcessItem {
+	///
+	pub title: String,
+	/// needs piped stdout and stderr
+	pub child: Child,
+	/// pushed once the process exited successfully
+	pub on_success: Option<InternalEvent>,
+}
+
+/// files inside of a folder that are supposed to be reset
+pub struct ResetFolderItem {
+	/// path of the folder
+	pub path: String,
+	/// tracked files getting their changes reverted
+	pub modified: Vec<String>,
+	/// untracked files getting deleted
+	pub untracked: Vec<String>,
+	/// the submodule the files are in, `None` for the repo itself
+	pub submodule: Option<RepoPath>,
 }
 
 ///
 pub enum Action {
 	Reset(ResetItem),
-	ResetHunk(String, u64),
-	ResetLines(String, Vec<DiffLinePosition>),
+	ResetFolder(ResetFolderItem),
+	/// path, hunk hash and hash of the diff it is part of
+	ResetHunk(String, u64, u64),
+	/// path, lines and hash of the diff they are part of
+	ResetLines(String, Vec<DiffLinePosition>, u64),
 	StashDrop(Vec<CommitId>),
 	StashPop(CommitId),
 	DeleteLocalBranch(String),
 	DeleteRemoteBranch(String),
 	DeleteTag(String),
 	DeleteRemoteTag(String, String),
+	/// tag and the remote it is pushed to
+	PushTag(String, String),
+	PushAllTags,
+	/// tag the remote has pointing somewhere else and that remote
+	ForcePushTag(String, String),
 	DeleteRemote(String),
-	ForcePush(String, bool),
-	PullMerge { incoming: usize, rebase: bool },
+	ForcePush(String, PushForce),
+	PullMerge {
+		incoming: usize,
+		rebase: bool,
+	},
 	AbortMerge,
 	AbortRebase,
 	AbortRevert,
-	UndoCommit,
+	AbortCherryPick,
+	UndoCommit {
+		/// reachable from a remote branch already
+		pushed: bool,
+	},
+	/// reachable from a remote branch already
+	RewordPushedCommit(CommitId),
+	/// reset the branch to the commit of a reflog entry
+	ResetReflog {
+		id: CommitId,
+		selector: String,
+		kind: ResetType,
+	},
+	/// the replace ref of the replaced object
+	DeleteReplaceRef(CommitId),
+	/// against its first parent
+	RevertMerge(CommitId),
+	Autosquash {
+		target: CommitId,
+		/// reachable from a remote branch already
+		pushed: bool,
+	},
+	AmendCommit(AmendPlan),
+	CommitOutsideScope {
+		scope: String,
+		count: usize,
+	},
+	/// stages the merge result of a path, conflict blocks left in it
+	MarkResolved {
+		path: String,
+		blocks: usize,
+	},
 }
 
 #[derive(Debug)]
@@ -71,6 +154,10 @@ pub enum StackablePopupOpen {
 	InspectCommit(InspectCommitOpen),
 	///
 	CompareCommits(InspectCommitOpen),
+	///
+	CompareBranches(CompareBranchesOpen),
+	///
+	Reflog,
 }
 
 pub enum AppTabs {
@@ -91,6 +178,8 @@ pub enum InternalEvent {
 	ShowErrorMsg(String),
 	///
 	ShowInfoMsg(String),
+	/// short note that goes away on its own
+	ShowToast(String),
 	///
 	Update(NeedsUpdate),
 	///
@@ -99,6 +188,8 @@ pub enum InternalEvent {
 	OpenCommit,
 	///
 	PopupStashing(StashingOptions),
+	/// stash only the changes of these paths
+	PopupStashPaths(Vec<String>),
 	///
 	TabSwitchStatus,
 	///
@@ -106,8 +197,18 @@ pub enum InternalEvent {
 	///
 	SelectCommitInRevlog(CommitId),
 	///
+	BranchCommit(CommitId),
+	/// branch off the commit a stash was made on and pop it there
+	BranchFromStash(CommitId),
+	///
 	TagCommit(CommitId),
 	///
+	BookmarkCommit(CommitId),
+	///
+	ViewBookmarks,
+	///
+	ViewReplaceRefs,
+	///
 	Tags,
 	///
 	CreateBranch,
@@ -122,9 +223,17 @@ pub enum InternalEvent {
 	///
 	OpenExternalEditor(Option<String>),
 	///
-	Push(String, PushType, bool, bool),
+	OpenLocalHistory(String),
+	/// stash and what to compare it with (`HEAD` if `None`)
+	OpenCompareStash(CommitId, Option<CommitId>),
+	///
+	Push(String, PushType, PushForce, bool),
+	///
+	PushAfterCreate(PushAfterCreate),
 	///
 	Pull(String),
+	/// pull rebasing local commits regardless of `pull.rebase`
+	PullRebase(String),
 	///
 	PushTags,
 	///
@@ -133,8 +242,18 @@ pub enum InternalEvent {
 	OpenFuzzyFinder(Vec<String>, FuzzyFinderTarget),
 	///
 	OpenLogSearchPopup,
+	/// with the criteria of the filter shown now
+	OpenLogFilterPopup(LogFilterCriteria),
+	///
+	LogFilter(LogFilterCriteria),
 	///
 	FuzzyFinderChanged(usize, String, FuzzyFinderTarget),
+	/// finder closed without accepting the current hit
+	FuzzyFinderCancelled(FuzzyFinderTarget),
+	/// search file contents of this revision
+	OpenFileGrep(CommitId),
+	/// path and line of the selected content search hit
+	FileGrepChanged(PathBuf, usize),
 	///
 	FetchRemotes,
 	///
@@ -146,6 +265,10 @@ pub enum InternalEvent {
 	///
 	ViewSubmodules,
 	///
+	ViewWorktrees,
+	/// add a worktree for this local branch
+	CreateWorktree(String),
+	///
 	ViewRemotes,
 	///
 	CreateRemote,
@@ -153,8 +276,42 @@ pub enum InternalEvent {
 	OpenRepo { path: PathBuf },
 	///
 	OpenResetPopup(CommitId),
+	/// reset to the commit of a reflog entry, named by its selector
+	OpenResetPopupReflog(CommitId, String),
+	/// choose how to merge the branch into the checked out one
+	OpenMergeBranchPopup(String, BranchType),
+	/// path of the conflicted file or of a folder with conflicts
+	OpenResolveConflictPopup(String),
+	/// path of the conflicted file
+	OpenConflictEditor(String),
+	///
+	OpenIgnorePopup(IgnoreItem),
+	///
+	OpenFixupPopup,
+	///
+	OpenFixStaged,
+	///
+	OpenKeyConflictsPopup,
 	///
 	RewordCommit(CommitId),
+	/// with the mainline parent for merges
+	RevertCommit(CommitId, Option<u32>),
+	///
+	RunProcess(Box<ProcessItem>),
+	/// for the current branch
+	OpenPullRequest,
+	/// blobs a partial clone has to fetch to show a diff
+	FetchMissingObjects(Vec<String>),
+	/// asks how far to deepen the history of a shallow clone
+	OpenDeepenHistoryPopup,
+	/// fetches commits beyond where a shallow clone ends
+	DeepenHistory(Deepen),
+	/// continue the commit the pre-commit hook was run for
+	PreCommitHookPassed,
+	/// commit the msg the commit-msg hook passed
+	CommitMsgHookPassed,
+	/// take over the msg the prepare-commit-msg hook wrote
+	PrepareCommitMsgHookPassed,
 	///
 	CommitSearch(LogFilterSearchOptions),
 }
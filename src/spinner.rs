@@ -4,33 +4,29 @@ use ratatui::{
 };
 use std::{cell::Cell, char, io};
 
-// static SPINNER_CHARS: &[char] = &['◢', '◣', '◤', '◥'];
-// static SPINNER_CHARS: &[char] = &['⢹', '⢺', '⢼', '⣸', '⣇', '⡧', '⡗', '⡏'];
-static SPINNER_CHARS: &[char] =
-	&['⣷', '⣯', '⣟', '⡿', '⢿', '⣻', '⣽', '⣾'];
-
 ///
 pub struct Spinner {
 	idx: usize,
 	active: bool,
+	chars: &'static [char],
 	last_char: Cell<char>,
 }
 
-impl Default for Spinner {
-	fn default() -> Self {
+impl Spinner {
+	///
+	pub const fn new(chars: &'static [char]) -> Self {
 		Self {
 			idx: 0,
 			active: false,
+			chars,
 			last_char: Cell::new(' '),
 		}
 	}
-}
 
-impl Spinner {
 	/// increment spinner graphic by one
 	pub fn update(&mut self) {
 		self.idx += 1;
-		self.idx %= SPINNER_CHARS.len();
+		self.idx %= self.chars.len();
 	}
 
 	///
@@ -39,14 +35,14 @@ impl Spinner {
 	}
 
 	/// draws or removes spinner char depending on `pending` state
-	pub fn draw(
+	pub fn draw<W: io::Write>(
 		&self,
-		terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+		terminal: &mut Terminal<CrosstermBackend<W>>,
 	) -> io::Result<()> {
 		let idx = self.idx;
 
 		let char_to_draw =
-			if self.active { SPINNER_CHARS[idx] } else { ' ' };
+			if self.active { self.chars[idx] } else { ' ' };
 
 		if self.last_char.get() != char_to_draw {
 			self.last_char.set(char_to_draw);
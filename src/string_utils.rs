@@ -40,15 +40,79 @@ pub fn trim_offset(src: &str, mut offset: usize) -> &str {
 	&src[start..]
 }
 
+/// the last http(s) url in `text`, e.g. the one a CLI printed
+pub fn last_url(text: &str) -> Option<&str> {
+	text.split_whitespace().rev().find(|word| {
+		word.starts_with("https://") || word.starts_with("http://")
+	})
+}
+
+/// bytes in a row of a hex dump
+pub const HEX_ROW_BYTES: usize = 16;
+
+/// a row of a hex dump like `hexdump -C` prints it: the offset, the
+/// bytes in hex and the printable ones as text
+pub fn hex_dump_line(offset: usize, bytes: &[u8]) -> String {
+	let hex: Vec<String> = (0..HEX_ROW_BYTES)
+		.map(|idx| {
+			bytes.get(idx).map_or_else(
+				|| "  ".to_string(),
+				|byte| format!("{byte:02x}"),
+			)
+		})
+		.collect();
+	let (left, right) = hex.split_at(HEX_ROW_BYTES / 2);
+
+	let text: String = bytes
+		.iter()
+		.map(|byte| {
+			if byte.is_ascii_graphic() || *byte == b' ' {
+				char::from(*byte)
+			} else {
+				'.'
+			}
+		})
+		.collect();
+
+	format!(
+		"{offset:08x}  {}  {}  |{text}|",
+		left.join(" "),
+		right.join(" ")
+	)
+}
+
 #[cfg(test)]
 mod test {
 	use pretty_assertions::assert_eq;
 
-	use crate::string_utils::trim_length_left;
+	use crate::string_utils::{
+		hex_dump_line, last_url, trim_length_left,
+	};
 
 	#[test]
 	fn test_trim() {
 		assert_eq!(trim_length_left("👍foo", 3), "foo");
 		assert_eq!(trim_length_left("👍foo", 4), "foo");
 	}
+
+	#[test]
+	fn test_last_url() {
+		assert_eq!(
+			last_url("Creating pull request\nhttps://github.com/o/r/pull/1\n"),
+			Some("https://github.com/o/r/pull/1")
+		);
+		assert_eq!(last_url("no url"), None);
+	}
+
+	#[test]
+	fn test_hex_dump_line() {
+		assert_eq!(
+			hex_dump_line(0x20, b"Hello,\0world!\xff\n"),
+			"00000020  48 65 6c 6c 6f 2c 00 77  6f 72 6c 64 21 ff 0a     |Hello,.world!..|"
+		);
+		assert_eq!(
+			hex_dump_line(0, b"ab"),
+			"00000000  61 62                                             |ab|"
+		);
+	}
 }
@@ -1,10 +1,29 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, path::Path};
 
-use asyncgit::sync::CommitId;
+use asyncgit::{
+	sync::{
+		diff::ForcedDiffType, remotes::CredRejection, AmendPlan,
+		BisectState, BranchRemotes, CommitId, ConflictResolution,
+		ConflictSide, ConflictType, Deepen, FileMode, FixupTargets,
+		IgnoreKind, ImageSize, LfsDiff, LfsPointer, LfsSide,
+		ModeChange, ParkReturn, ParkedChanges, ResetType,
+		StageCleanupPreview, StashFileApply, SubmoduleChanges,
+		SubmoduleDiff,
+	},
+	Error, ProcessResult, PushForce, PushType, StagePathsProgress,
+};
+use bytesize::ByteSize;
+use easy_cast::Cast;
 use unicode_truncate::UnicodeTruncateStr;
 use unicode_width::UnicodeWidthStr;
 
-use crate::keys::SharedKeyConfig;
+use crate::{
+	args::PrintOnExit,
+	keys::{
+		presets, KeyConfigWarning, KeyIssue, KeyIssueKind,
+		SharedKeyConfig, VimMode,
+	},
+};
 
 pub mod order {
 	pub const RARE_ACTION: i8 = 30;
@@ -15,7 +34,11 @@ pub mod order {
 
 pub static PUSH_POPUP_MSG: &str = "Push";
 pub static FORCE_PUSH_POPUP_MSG: &str = "Force Push";
-pub static PULL_POPUP_MSG: &str = "Pull";
+pub static FORCE_PUSH_LEASE_POPUP_MSG: &str =
+	"Force Push (with lease)";
+pub static DELETE_PUSH_POPUP_MSG: &str = "Delete on Remote";
+pub static PULL_POPUP_MSG: &str = "Pull (merge)";
+pub static PULL_REBASE_POPUP_MSG: &str = "Pull (rebase)";
 pub static FETCH_POPUP_MSG: &str = "Fetch";
 pub static PUSH_POPUP_PROGRESS_NONE: &str = "preparing...";
 pub static PUSH_POPUP_STATES_ADDING: &str = "adding objects (1/3)";
@@ -30,37 +53,615 @@ pub static PUSH_TAGS_STATES_PUSHING: &str = "pushing";
 pub static PUSH_TAGS_STATES_DONE: &str = "done";
 
 pub static POPUP_TITLE_SUBMODULES: &str = "Submodules";
+pub static POPUP_TITLE_WORKTREES: &str = "Worktrees";
+pub static POPUP_TITLE_MERGE_BRANCH: &str = "Merge Branch";
 pub static POPUP_TITLE_REMOTES: &str = "Remotes";
 pub static POPUP_SUBTITLE_REMOTES: &str = "Details";
 pub static POPUP_TITLE_FUZZY_FIND: &str = "Fuzzy Finder";
+pub static POPUP_TITLE_FILE_GREP: &str = "Search File Contents";
 pub static POPUP_TITLE_LOG_SEARCH: &str = "Search";
+pub static POPUP_TITLE_LOG_FILTER: &str = "Filter Log";
+pub static LOG_FILTER_LABELS: [&str; 3] =
+	["message", "author", "path"];
+pub static LOG_FILTER_MESSAGE_MSG: &str = "in summary or body";
+pub static LOG_FILTER_AUTHOR_MSG: &str = "name or email";
+pub static LOG_FILTER_PATH_MSG: &str = "file or folder touched";
+pub static POPUP_TITLE_RESOLVE_CONFLICT: &str = "Resolve Conflict";
 
 pub static POPUP_FAIL_COPY: &str = "Failed to copy text";
+pub static POPUP_FAIL_EDIT: &str = "Failed to open file in editor";
 pub static POPUP_SUCCESS_COPY: &str = "Copied Text";
 pub static POPUP_COMMIT_SHA_INVALID: &str = "Invalid commit sha";
 
 pub mod symbol {
-	pub const CHECKMARK: &str = "\u{2713}"; //✓
-	pub const SPACE: &str = "\u{02FD}"; //˽
 	pub const EMPTY_SPACE: &str = " ";
-	pub const FOLDER_ICON_COLLAPSED: &str = "\u{25b8}"; //▸
-	pub const FOLDER_ICON_EXPANDED: &str = "\u{25be}"; //▾
 	pub const EMPTY_STR: &str = "";
-	pub const ELLIPSIS: char = '\u{2026}'; // …
 }
 
+pub const fn conflict_type(kind: ConflictType) -> &'static str {
+	match kind {
+		ConflictType::BothModified => "both modified",
+		ConflictType::BothAdded => "both added",
+		ConflictType::DeletedByUs => "deleted by us",
+		ConflictType::DeletedByThem => "deleted by them",
+		ConflictType::BothDeleted => "both deleted",
+		ConflictType::AddedByUs => "added by us",
+		ConflictType::AddedByThem => "added by them",
+		ConflictType::BothRenamed => "both renamed",
+	}
+}
+
+pub const fn conflict_resolution(
+	resolution: ConflictResolution,
+) -> &'static str {
+	match resolution {
+		ConflictResolution::TakeOurs => "take ours",
+		ConflictResolution::TakeTheirs => "take theirs",
+		ConflictResolution::MergeBoth => "open merge of both",
+		ConflictResolution::KeepDeleted => "keep deleted",
+		ConflictResolution::KeepModified => "keep modified file",
+	}
+}
+
+pub fn resolve_folder_with_side(
+	side: ConflictSide,
+	name: &str,
+	count: usize,
+) -> String {
+	format!(
+		"take {} ({name}) for all {count} conflicted files",
+		match side {
+			ConflictSide::Ours => "ours",
+			ConflictSide::Theirs => "theirs",
+		}
+	)
+}
+pub fn conflicts_left(paths: &[String]) -> String {
+	format!(
+		"these files do not exist on that side, resolve them one by one:\n{}",
+		paths.join("\n")
+	)
+}
+pub fn conflicts_resolved() -> String {
+	"All conflicts resolved: continue when ready".to_string()
+}
+
+pub const RERERE_AUTO_RESOLVED: &str = "auto-resolved (rerere)";
+pub const RERERE_RECORD: &str =
+	"record resolution for reuse (rerere)";
+pub const RERERE_FORGET: &str =
+	"forget recorded resolution and restore conflict (rerere)";
+pub const EDIT_CONFLICT_BLOCKS: &str =
+	"edit the conflict blocks one by one";
+
+pub fn rerere_resolved(paths: &[String]) -> String {
+	format!(
+		"resolved using previous resolution, review before staging:\n{}",
+		paths.join("\n")
+	)
+}
+
+const fn print_on_exit_name(what: PrintOnExit) -> &'static str {
+	match what {
+		PrintOnExit::File => "file",
+		PrintOnExit::RepoRoot => "repo root",
+		PrintOnExit::Commit => "commit",
+	}
+}
+pub fn print_on_exit_nothing_selected(what: PrintOnExit) -> String {
+	format!(
+		"no {} selected to print, select one in the {} tab",
+		print_on_exit_name(what),
+		if what == PrintOnExit::File {
+			"status or files"
+		} else {
+			"log"
+		}
+	)
+}
 pub fn title_branches() -> String {
 	"Branches".to_string()
 }
 pub fn title_tags() -> String {
 	"Tags".to_string()
 }
+pub fn title_oplog(filter: Option<&str>) -> String {
+	filter.map_or_else(
+		|| "Operation log".to_string(),
+		|filter| format!("Operation log [{filter}]"),
+	)
+}
+/// like `git status` words them
+pub fn submodule_changes(changes: SubmoduleChanges) -> String {
+	let parts: Vec<&str> = [
+		(changes.uninitialized, "not initialized"),
+		(changes.new_commits, "new commits"),
+		(changes.modified_content, "modified content"),
+		(changes.untracked_content, "untracked content"),
+	]
+	.into_iter()
+	.filter_map(|(set, part)| set.then_some(part))
+	.collect();
+
+	if parts.is_empty() {
+		"submodule".to_string()
+	} else {
+		format!("submodule: {}", parts.join(", "))
+	}
+}
+/// root of the repo's own changes when submodules get sections
+pub const STATUS_SECTION_REPO: &str = "<repo>";
+pub fn status_section_submodule(path: &str) -> String {
+	format!("<submodule {path}>")
+}
+pub fn submodule_diff(diff: &SubmoduleDiff) -> Vec<String> {
+	let id = |id: Option<CommitId>| {
+		id.map_or_else(|| "none".to_string(), |id| id.to_string())
+	};
+
+	let mut lines = vec![
+		format!("{}:", submodule_changes(diff.changes)),
+		format!("  old: {}", id(diff.old)),
+		format!("  new: {}", id(diff.new)),
+	];
+	if diff.old.is_some() && diff.old == diff.new {
+		lines.push(
+			"the recorded commit is checked out, open the submodule to see its changes"
+				.to_string(),
+		);
+	}
+
+	lines
+}
+pub const LFS_FILE: &str = "LFS";
+fn lfs_side(side: &LfsSide) -> String {
+	match side {
+		LfsSide::Absent => "none".to_string(),
+		LfsSide::Pointer(pointer) => lfs_object(pointer),
+		LfsSide::Content(bytes) => {
+			format!("plain content (size: {})", ByteSize::b(*bytes))
+		}
+	}
+}
+fn lfs_object(pointer: &LfsPointer) -> String {
+	format!("LFS object (size: {})", ByteSize::b(pointer.size))
+}
+/// the pointers say nothing but the hash and size of the content
+pub fn lfs_diff(diff: &LfsDiff) -> Vec<String> {
+	let oid = |side: &LfsSide| {
+		side.pointer().map_or_else(String::new, |pointer| {
+			format!(" sha256:{}", pointer.oid)
+		})
+	};
+
+	vec![
+		"Git LFS:".to_string(),
+		format!("  old: {}{}", lfs_side(&diff.old), oid(&diff.old)),
+		format!("  new: {}{}", lfs_side(&diff.new), oid(&diff.new)),
+	]
+}
+pub fn diff_lfs_title(diff: &LfsDiff) -> String {
+	diff.pointer()
+		.map(|pointer| format!(" - {}", lfs_object(pointer)))
+		.unwrap_or_default()
+}
+pub fn title_lfs_content(path: &str) -> String {
+	format!("LFS content of '{path}'")
+}
+/// the permissions of files like `git diff` shows them
+const fn file_mode(mode: FileMode) -> &'static str {
+	match mode {
+		FileMode::Regular => "644",
+		FileMode::Executable => "755",
+		FileMode::Symlink => "symlink",
+		FileMode::Submodule => "submodule",
+	}
+}
+/// marks executables like `ls -F` and shows where symlinks point to
+pub fn file_mode_suffix(
+	mode: Option<FileMode>,
+	symlink_target: Option<&str>,
+) -> String {
+	match mode {
+		Some(FileMode::Executable) => "*".to_string(),
+		Some(FileMode::Symlink) => {
+			format!(" -> {}", symlink_target.unwrap_or_default())
+		}
+		_ => String::new(),
+	}
+}
+pub fn mode_change(change: &ModeChange) -> String {
+	match change {
+		ModeChange::Mode(modes) => format!(
+			"mode changed {} → {}",
+			file_mode(modes.old),
+			file_mode(modes.new)
+		),
+		ModeChange::SymlinkTarget(_) => {
+			"symlink target changed".to_string()
+		}
+	}
+}
+pub fn diff_mode_change_title(change: &ModeChange) -> String {
+	format!(" - {}", mode_change(change))
+}
+pub fn submodule_update_progress(name: &str, state: &str) -> String {
+	format!("updating '{name}': {state}")
+}
+pub fn title_reflog(loading: bool) -> String {
+	if loading {
+		"Reflog of HEAD (loading)".to_string()
+	} else {
+		"Reflog of HEAD".to_string()
+	}
+}
+/// `locked` and `prunable` of a [`asyncgit::sync::WorktreeInfo`]
+pub fn worktree_state(
+	locked: Option<&str>,
+	prunable: bool,
+) -> String {
+	match (locked, prunable) {
+		(_, true) => "prunable".to_string(),
+		(Some(""), false) => "locked".to_string(),
+		(Some(reason), false) => format!("locked: {reason}"),
+		(None, false) => String::new(),
+	}
+}
+pub fn merge_up_to_date(branch: &str) -> String {
+	format!("Already up to date: '{branch}' is merged already.")
+}
+pub fn merge_fast_forwarded(branch: &str) -> String {
+	format!("fast-forwarded to '{branch}'")
+}
+pub fn worktrees_pruned(count: usize) -> String {
+	match count {
+		0 => "no stale worktrees".to_string(),
+		1 => "pruned 1 stale worktree".to_string(),
+		_ => format!("pruned {count} stale worktrees"),
+	}
+}
+pub fn oplog_commit_gone() -> String {
+	"the commits of this operation no longer exist".to_string()
+}
+pub fn title_local_history(
+	path: &str,
+	snapshots: usize,
+	recording: bool,
+) -> String {
+	if recording {
+		format!("Local history: {path} ({snapshots})")
+	} else {
+		format!("Local history: {path} ({snapshots}, not recording)")
+	}
+}
+pub fn title_fix_staged(command: &str, status: &str) -> String {
+	format!("{command} [{status}]")
+}
+pub fn title_fix_staged_files(fixed: usize, total: usize) -> String {
+	format!("Fixed {fixed} of {total} staged files")
+}
+pub fn title_fix_staged_summary() -> String {
+	"Summary".to_string()
+}
+pub fn fix_staged_not_configured() -> String {
+	"no fixer configured, set one like:\ngit config gitui.fixStaged 'cargo fmt --'".to_string()
+}
+pub fn fix_staged_nothing_to_fix(skipped: &[String]) -> String {
+	if skipped.is_empty() {
+		"nothing staged to fix".to_string()
+	} else {
+		format!(
+			"nothing to fix, skipped partially staged files:\n{}",
+			skipped.join("\n")
+		)
+	}
+}
+pub fn fix_staged_failed(status: &str, files: &[String]) -> String {
+	format!("failed ({status}): {}", files.join(", "))
+}
+pub fn fix_staged_skipped(files: &[String]) -> String {
+	format!("skipped, partially staged: {}", files.join(", "))
+}
+pub fn fix_staged_restaged(count: usize) -> String {
+	format!("restaged {count} fixed files")
+}
+pub fn title_compare_stash(stash: &str, base: &str) -> String {
+	format!("Compare {stash} with {base}")
+}
+pub fn title_branch_ahead(branch: &str, related: bool) -> String {
+	if related {
+		format!("Only in HEAD, not in '{branch}'")
+	} else {
+		format!("Only in HEAD, no history shared with '{branch}'")
+	}
+}
+pub fn title_branch_behind(branch: &str) -> String {
+	format!("Only in '{branch}', not in HEAD")
+}
+pub fn title_conflict_side(side: &str, label: &str) -> String {
+	if label.is_empty() {
+		side.to_string()
+	} else {
+		format!("{side} ({label})")
+	}
+}
+pub fn title_conflict_result(
+	path: &str,
+	block: usize,
+	blocks: usize,
+) -> String {
+	if blocks == 0 {
+		format!("Result of '{path}' (no conflicts left)")
+	} else {
+		format!(
+			"Result of '{path}' (conflict {}/{blocks})",
+			block + 1
+		)
+	}
+}
+pub fn title_conflict_result_edit(path: &str) -> String {
+	format!("Editing result of '{path}'")
+}
+pub fn local_history_restored(path: &str, time: &str) -> String {
+	format!("restored '{path}' as of {time}")
+}
+pub fn title_ignore() -> String {
+	"Add to .gitignore".to_string()
+}
+pub const fn ignore_kind(kind: IgnoreKind) -> &'static str {
+	match kind {
+		IgnoreKind::Path => "path",
+		IgnoreKind::Extension => "extension",
+		IgnoreKind::Directory => "directory",
+	}
+}
+pub fn title_fixup() -> String {
+	"Fixup Target".to_string()
+}
+pub fn fixup_analyzing() -> String {
+	"analyzing staged hunks...".to_string()
+}
+pub fn fixup_no_candidates() -> String {
+	"no commit touched the lines of the staged hunks".to_string()
+}
+pub fn fixup_info(targets: &FixupTargets) -> String {
+	if targets.unambiguous().is_some() {
+		return format!(
+			"all {} staged hunks belong to:",
+			targets.hunks
+		);
+	}
+
+	if targets.unattributed > 0 {
+		format!(
+			"ambiguous, pick the commit to fix up ({} hunks in new files)",
+			targets.unattributed
+		)
+	} else {
+		"ambiguous, pick the commit to fix up".to_string()
+	}
+}
+pub fn fixup_pushed() -> String {
+	" [pushed]".to_string()
+}
+pub fn title_key_conflicts() -> String {
+	"Key Binding Conflicts".to_string()
+}
+pub fn key_conflicts_none() -> String {
+	"every action can be reached with its key".to_string()
+}
+pub fn key_conflicts_file(path: &Path) -> String {
+	format!("set in {}", path.display())
+}
+pub fn key_issue(
+	issue: &KeyIssue,
+	key_config: &SharedKeyConfig,
+) -> String {
+	let key = key_config.get_hint(issue.key);
+	let areas = issue
+		.areas
+		.iter()
+		.map(|area| area.name())
+		.collect::<Vec<_>>()
+		.join(", ");
+
+	match issue.kind {
+		KeyIssueKind::Hidden { by, hidden } => {
+			format!("{hidden} [{key}] is hidden by {by} in {areas}")
+		}
+		KeyIssueKind::Ambiguous { first, second } => {
+			format!(
+				"{first} and {second} both use [{key}] in {areas}"
+			)
+		}
+		KeyIssueKind::Typing(binding) => {
+			format!("{binding} takes [{key}], it can not be typed in {areas}")
+		}
+		KeyIssueKind::Unreachable(binding) => {
+			format!("{binding} [{key}] can not be reached anywhere")
+		}
+		KeyIssueKind::Unbound(binding) => {
+			format!("{binding} has no key")
+		}
+	}
+}
+pub fn key_config_warning(warning: &KeyConfigWarning) -> String {
+	match warning {
+		KeyConfigWarning::Parse(e) => {
+			format!("the key bindings could not be read: {e}")
+		}
+		KeyConfigWarning::UnknownAction(name) => {
+			format!("{name} is no action, it is ignored")
+		}
+		KeyConfigWarning::UnknownPreset(name) => {
+			format!("there is no preset {name}, it is ignored")
+		}
+	}
+}
+pub fn key_config_warning_fix(
+	warning: &KeyConfigWarning,
+	line: Option<usize>,
+) -> Option<String> {
+	let line = line?;
+	match warning {
+		KeyConfigWarning::Parse(_) => None,
+		KeyConfigWarning::UnknownAction(name) => {
+			Some(format!("  line {line}: rename or remove `{name}`"))
+		}
+		KeyConfigWarning::UnknownPreset(_) => {
+			let presets = presets()
+				.map(|preset| format!("`{preset}`"))
+				.collect::<Vec<_>>()
+				.join(", ");
+			Some(format!("  line {line}: the presets are {presets}"))
+		}
+	}
+}
+pub fn key_issue_fix(binding: &str, line: Option<usize>) -> String {
+	line.map_or_else(
+		|| format!("  add `{binding}` to the key bindings"),
+		|line| format!("  line {line}: change `{binding}`"),
+	)
+}
+pub fn ignore_tracked_warning() -> String {
+	"already tracked: ignoring won't hide its changes".to_string()
+}
 pub fn title_status(_key_config: &SharedKeyConfig) -> String {
 	"Unstaged Changes".to_string()
 }
+pub fn title_stage_paths_progress(
+	title: &str,
+	stage: bool,
+	progress: StagePathsProgress,
+	key_config: &SharedKeyConfig,
+) -> String {
+	format!(
+		"{title} - {} {}/{} [{}] cancel",
+		if stage { "staging" } else { "unstaging" },
+		progress.done,
+		progress.total,
+		key_config.get_hint(key_config.keys.exit_popup),
+	)
+}
+pub fn title_scoped(title: &str, scope: Option<&str>) -> String {
+	scope.map_or_else(
+		|| title.to_string(),
+		|scope| format!("{title} (in {scope}/)"),
+	)
+}
+/// marks the staged changes of a repo with a sparse index, gitui
+/// refuses to stage there
+pub fn title_sparse_index(title: &str) -> String {
+	format!("{title} [sparse index]")
+}
+/// marks the working dir of a partial clone, diffs may need to
+/// fetch objects first
+pub fn title_partial_clone(title: &str) -> String {
+	format!("{title} [partial clone]")
+}
 pub fn title_diff(_key_config: &SharedKeyConfig) -> String {
 	"Diff: ".to_string()
 }
+/// warns about whitespace issues staging adds, or says how the
+/// configured cleanup fixes them
+pub fn diff_stage_cleanup(preview: &StageCleanupPreview) -> String {
+	let mut parts = Vec::new();
+
+	if preview.trailing_whitespace > 0 {
+		parts.push(format!(
+			"{}{} trailing whitespace chars",
+			if preview.cleanup.strip_trailing_whitespace {
+				"will strip "
+			} else {
+				""
+			},
+			preview.trailing_whitespace
+		));
+	}
+
+	if preview.missing_final_newline {
+		parts.push(if preview.cleanup.final_newline {
+			"will add the final newline".to_string()
+		} else {
+			"no final newline".to_string()
+		});
+	}
+
+	format!(" ({})", parts.join(", "))
+}
+
+pub const fn diff_forced_type(
+	forced: ForcedDiffType,
+) -> &'static str {
+	match forced {
+		ForcedDiffType::Text => " (forced text via .gitattributes)",
+		ForcedDiffType::Binary => {
+			" (forced binary via .gitattributes)"
+		}
+		ForcedDiffType::BigFile => {
+			" (binary: larger than core.bigFileThreshold)"
+		}
+	}
+}
+fn image_size(size: ImageSize) -> String {
+	format!("{} {}x{}", size.format.name(), size.width, size.height)
+}
+/// `None` unless either side is an image
+pub fn diff_images(
+	images: (Option<ImageSize>, Option<ImageSize>),
+) -> Option<String> {
+	let side = |size: Option<ImageSize>| {
+		size.map_or_else(|| "none".to_string(), image_size)
+	};
+
+	(images.0.is_some() || images.1.is_some()).then(|| {
+		format!("image: {} -> {}", side(images.0), side(images.1))
+	})
+}
+/// shown by the file viewer instead of binary content
+pub fn binary_file_summary(
+	size: usize,
+	image: Option<ImageSize>,
+	key_config: &SharedKeyConfig,
+) -> String {
+	let mut lines = vec![
+		"binary content, not shown as text".to_string(),
+		format!("size: {}", ByteSize::b(size.cast())),
+	];
+	if let Some(image) = image {
+		lines.push(format!("image: {}", image_size(image)));
+	}
+	lines.push(String::new());
+	lines.push(format!(
+		"{} shows it as a hex dump",
+		key_config.get_hint(key_config.keys.toggle_hex_view)
+	));
+
+	lines.join("\n")
+}
+pub fn diff_search_placeholder(
+	key_config: &SharedKeyConfig,
+) -> String {
+	format!(
+		"search diff.. ({} to match case)",
+		key_config.get_hint(key_config.keys.diff_search_case)
+	)
+}
+pub fn diff_search_matches(
+	current: Option<usize>,
+	count: usize,
+	case_sensitive: bool,
+) -> String {
+	format!(
+		" [{}/{count} matches{}]",
+		current.map_or(0, |current| current + 1),
+		if case_sensitive {
+			", case sensitive"
+		} else {
+			""
+		}
+	)
+}
 pub fn title_index(_key_config: &SharedKeyConfig) -> String {
 	"Staged Changes".to_string()
 }
@@ -94,6 +695,73 @@ pub fn tab_stashes(key_config: &SharedKeyConfig) -> String {
 pub fn tab_divider(_key_config: &SharedKeyConfig) -> String {
 	" | ".to_string()
 }
+/// `None` is insert mode
+pub fn vim_mode_indicator(
+	mode: Option<VimMode>,
+	pending: &str,
+) -> String {
+	let mode = match mode {
+		None => "INSERT",
+		Some(VimMode::Normal) => "NORMAL",
+		Some(VimMode::Visual) => "VISUAL",
+	};
+
+	if pending.is_empty() {
+		format!("[{mode}] ")
+	} else {
+		format!("[{mode} {pending}] ")
+	}
+}
+/// shown in the top bar for as long as the bisect session lasts
+pub fn bisect_banner(state: &BisectState) -> String {
+	state.culprit.map_or_else(
+		|| {
+			if state.bad.is_none() {
+				"[bisect: mark a bad commit] ".to_string()
+			} else if state.good.is_empty() {
+				"[bisect: mark a good commit] ".to_string()
+			} else if state.next.is_none() {
+				"[bisect: only skipped commits left] ".to_string()
+			} else {
+				format!(
+					"[bisect: {} left, roughly {} steps] ",
+					state.left, state.steps
+				)
+			}
+		},
+		|culprit| {
+			format!(
+				"[bisect: {} is the first bad commit] ",
+				culprit.get_short_string()
+			)
+		},
+	)
+}
+pub fn bisect_found(
+	key_config: &SharedKeyConfig,
+	culprit: &CommitId,
+) -> String {
+	format!(
+		"{} is the first bad commit.\n\nIt is selected in the log, copy its hash with [{}]. End the bisect with [{}] to get back to where it started.",
+		culprit.get_short_string(),
+		key_config.get_hint(key_config.keys.copy),
+		key_config.get_hint(key_config.keys.log_bisect_reset),
+	)
+}
+pub fn bisect_only_skipped(
+	bad: &CommitId,
+	skipped: &[CommitId],
+) -> String {
+	format!(
+		"There are only skipped commits left to test, the first bad commit could be {} or any of: {}",
+		bad.get_short_string(),
+		skipped
+			.iter()
+			.map(CommitId::get_short_string)
+			.collect::<Vec<_>>()
+			.join(", ")
+	)
+}
 pub fn cmd_splitter(_key_config: &SharedKeyConfig) -> String {
 	" ".to_string()
 }
@@ -109,6 +777,9 @@ pub fn msg_title_info(_key_config: &SharedKeyConfig) -> String {
 pub fn commit_title() -> String {
 	"Commit".to_string()
 }
+pub fn commit_title_template(name: &str) -> String {
+	format!("Commit (template: {name})")
+}
 pub fn commit_reword_title() -> String {
 	"Reword Commit".to_string()
 }
@@ -122,12 +793,63 @@ pub fn commit_title_revert() -> String {
 pub fn commit_title_amend() -> String {
 	"Commit (Amend)".to_string()
 }
+pub fn push_after_create_target(remote: &str) -> String {
+	format!("[push to: {remote}]")
+}
+pub fn push_after_create_done(
+	push_type: PushType,
+	name: &str,
+	remote: &str,
+) -> String {
+	match push_type {
+		PushType::Branch => format!(
+			"created branch '{name}' and pushed it to '{remote}' (upstream set)"
+		),
+		PushType::Tag => {
+			format!("created tag '{name}' and pushed it to '{remote}'")
+		}
+	}
+}
+pub fn push_after_create_failed(
+	push_type: PushType,
+	name: &str,
+	remote: &str,
+	err: &str,
+) -> String {
+	let kind = match push_type {
+		PushType::Branch => "branch",
+		PushType::Tag => "tag",
+	};
+	format!(
+		"{kind} '{name}' was created locally, but pushing it to '{remote}' failed:\n{err}"
+	)
+}
+pub fn commit_verbose_diff_title() -> String {
+	"staged changes".to_string()
+}
 pub fn commit_msg(_key_config: &SharedKeyConfig) -> String {
 	"type commit message..".to_string()
 }
 pub fn commit_first_line_warning(count: usize) -> String {
 	format!("[subject length: {count}]")
 }
+pub fn commit_line_length(len: usize, limit: usize) -> String {
+	format!(" [{len}/{limit}]")
+}
+pub fn commit_behind_upstream(
+	key_config: &SharedKeyConfig,
+	behind: usize,
+	fetched_ago: Option<&str>,
+) -> String {
+	let fetched = fetched_ago.map_or_else(
+		|| String::from("never fetched"),
+		|ago| format!("fetched {ago} ago"),
+	);
+	format!(
+		"[{behind} behind upstream ({fetched}), {} to pull first]",
+		key_config.get_hint(key_config.keys.commit_pull_first),
+	)
+}
 pub const fn branch_name_invalid() -> &'static str {
 	"[invalid name]"
 }
@@ -143,12 +865,44 @@ pub fn stash_popup_title(_key_config: &SharedKeyConfig) -> String {
 pub fn stash_popup_msg(_key_config: &SharedKeyConfig) -> String {
 	"type name (optional)".to_string()
 }
+pub fn stash_paths_popup_title(files: usize) -> String {
+	if files == 1 {
+		"Stash 1 file".to_string()
+	} else {
+		format!("Stash {files} files")
+	}
+}
 pub fn confirm_title_reset() -> String {
 	"Reset".to_string()
 }
 pub fn confirm_title_undo_commit() -> String {
 	"Undo commit".to_string()
 }
+pub fn confirm_title_reset_reflog() -> String {
+	"Reset to reflog entry".to_string()
+}
+pub fn confirm_title_reword_pushed() -> String {
+	"Reword pushed commit".to_string()
+}
+pub fn confirm_title_delete_replace_ref() -> String {
+	"Delete replace ref".to_string()
+}
+pub fn confirm_title_revert_merge() -> String {
+	"Revert merge".to_string()
+}
+pub fn confirm_title_autosquash() -> String {
+	"Autosquash".to_string()
+}
+pub fn confirm_title_amend_commit(pushed: bool) -> String {
+	if pushed {
+		"Amend pushed commit".to_string()
+	} else {
+		"Amend commit".to_string()
+	}
+}
+pub fn confirm_title_mark_resolved() -> String {
+	"Mark resolved".to_string()
+}
 pub fn confirm_title_stashdrop(
 	_key_config: &SharedKeyConfig,
 	multiple: bool,
@@ -192,6 +946,13 @@ pub fn confirm_msg_revertchanges() -> String {
 	"This will revert all uncommitted changes. Are you sure?"
 		.to_string()
 }
+pub fn confirm_title_abort_cherry_pick() -> String {
+	"Abort cherry-pick?".to_string()
+}
+pub fn continue_conflicted() -> String {
+	"Stopped on conflicts: resolve and stage them, then continue."
+		.to_string()
+}
 pub fn confirm_title_abortrebase() -> String {
 	"Abort rebase?".to_string()
 }
@@ -202,13 +963,244 @@ pub fn confirm_msg_abortrebase() -> String {
 pub fn confirm_msg_reset() -> String {
 	"confirm file reset?".to_string()
 }
+pub fn confirm_msg_reset_folder(
+	path: &str,
+	modified: usize,
+	untracked: usize,
+) -> String {
+	let msg = format!(
+		"discard all changes in '{path}/'?\n\n{modified} modified file(s) reverted"
+	);
+
+	if untracked > 0 {
+		format!("{msg}\n{untracked} untracked file(s) DELETED (cannot be undone)")
+	} else {
+		msg
+	}
+}
 pub fn confirm_msg_reset_lines(lines: usize) -> String {
 	format!(
 		"are you sure you want to discard {lines} selected lines?"
 	)
 }
-pub fn confirm_msg_undo_commit() -> String {
-	"confirm undo last commit?".to_string()
+pub fn confirm_msg_undo_commit(pushed: bool) -> String {
+	if pushed {
+		"This commit is on a remote branch already, undoing it rewrites published history and needs a force push.\nUndo anyway?".to_string()
+	} else {
+		"confirm undo last commit?".to_string()
+	}
+}
+pub fn confirm_msg_reset_reflog(
+	id: &str,
+	selector: &str,
+	kind: ResetType,
+) -> String {
+	let (kind, effect) = match kind {
+		ResetType::Soft => ("soft", "keeps all changes staged"),
+		ResetType::Mixed => ("mixed", "keeps all changes unstaged"),
+		ResetType::Hard => {
+			("hard", "DISCARDS all uncommitted changes for good")
+		}
+	};
+
+	format!(
+		"Reset the current branch ({kind}) to {selector} ({id})?\nCommits only the branch had are left reachable through the reflog alone, a {kind} reset {effect}."
+	)
+}
+pub fn confirm_msg_reword_pushed() -> String {
+	"This commit is on a remote branch already, rewording it rewrites published history and needs a force push.\nReword anyway?".to_string()
+}
+pub fn confirm_msg_delete_replace_ref(original: &str) -> String {
+	format!("Stop replacing '{original}'? Its replacement is no longer referenced and may be garbage collected.")
+}
+pub fn confirm_msg_revert_merge() -> String {
+	"This is a merge commit: reverting it undoes the changes it brought in relative to its first parent (mainline 1), the branch that was merged into.\nRevert anyway?".to_string()
+}
+pub fn confirm_msg_autosquash(pushed: bool) -> String {
+	if pushed {
+		"This commit is on a remote branch already, squashing its fixups rewrites published history and needs a force push.\nAutosquash anyway?".to_string()
+	} else {
+		"Rebase onto the parent of this commit and fold in the fixup! and squash! commits?".to_string()
+	}
+}
+pub fn confirm_msg_amend_commit(plan: &AmendPlan) -> String {
+	let steps = if plan.staged > 0 {
+		format!(
+			"1. commit the {} staged file(s) as 'fixup! {}'\n2. rebase the commit and the {} on top of it, folding the fixup in",
+			plan.staged, plan.subject, plan.rebased
+		)
+	} else {
+		format!(
+			"Nothing is staged.\n1. rebase, stopping at '{}' to amend it or to split it up (undo it and commit its changes in parts)\n2. continue the rebase in the Status tab to pick the {} commit(s) on top of it again",
+			plan.subject, plan.rebased
+		)
+	};
+
+	if plan.pushed {
+		format!("This commit is on a remote branch already, amending it rewrites published history and needs a force push.\n\n{steps}\n\nAmend anyway?")
+	} else {
+		format!("{steps}\n\nAmend?")
+	}
+}
+pub fn confirm_msg_mark_resolved(
+	path: &str,
+	blocks: usize,
+) -> String {
+	if blocks == 0 {
+		format!(
+			"Saved '{path}'. Stage it to mark the conflict resolved?"
+		)
+	} else {
+		format!("Saved '{path}', but it still contains {blocks} conflict block(s). Stage it anyway to mark the conflict resolved?")
+	}
+}
+pub fn cherry_pick_conflicted() -> String {
+	"Cherry-pick stopped at a conflict: resolve it in the Status tab, then continue or abort the cherry-pick there.".to_string()
+}
+pub fn revert_conflicted() -> String {
+	"Revert stopped at a conflict: resolve it in the Status tab, then continue or abort the revert there.".to_string()
+}
+pub fn cherry_pick_skipped_empty(commits: &[String]) -> String {
+	format!(
+		"Skipped cherry-picking {} as the changes are already on the current branch.",
+		commits.join(", ")
+	)
+}
+pub fn autosquash_conflicted() -> String {
+	"Autosquash stopped at a conflict: resolve it in the Status tab, then continue or abort the rebase there.".to_string()
+}
+/// names where `msg` pushes to and, if that is another remote
+/// (triangular workflow), where the branch fetches from
+pub fn push_popup_title(
+	msg: &str,
+	remotes: Option<&BranchRemotes>,
+) -> String {
+	match remotes {
+		Some(remotes) if remotes.is_triangular() => format!(
+			"{msg} to {} (fetch from {})",
+			remotes.push.as_deref().unwrap_or_default(),
+			remotes.upstream.as_deref().unwrap_or_default()
+		),
+		Some(BranchRemotes {
+			push: Some(push), ..
+		}) => format!("{msg} to {push}"),
+		_ => msg.to_string(),
+	}
+}
+pub fn fetch_pruned(pruned: &[String]) -> String {
+	format!(
+		"Pruned {} remote-tracking branches deleted on their remote:\n{}",
+		pruned.len(),
+		pruned.join("\n")
+	)
+}
+pub fn pull_rebase_conflicted() -> String {
+	"Pull stopped at a conflict while rebasing: resolve it in the Status tab, then continue or abort the rebase there.".to_string()
+}
+pub fn branch_push_remote(
+	branch: &str,
+	configured: bool,
+	remote: &str,
+) -> String {
+	if configured {
+		format!("'{branch}' pushes to '{remote}'")
+	} else {
+		format!("'{branch}' pushes to the default '{remote}'")
+	}
+}
+pub fn stash_parked(parked: &ParkedChanges) -> String {
+	format!("parked from {}", parked.branch)
+}
+pub fn stash_file_applied(
+	path: &str,
+	result: StashFileApply,
+) -> String {
+	match result {
+		StashFileApply::Applied => {
+			format!("'{path}' restored from the stash.")
+		}
+		StashFileApply::Merged => format!(
+			"'{path}' changed since it was stashed, the stashed changes were merged into it."
+		),
+		StashFileApply::Conflicted => format!(
+			"'{path}' changed since it was stashed and the stashed changes conflict with it, the conflict markers are in the file."
+		),
+	}
+}
+pub fn branch_parked(parked: Option<&ParkedChanges>) -> String {
+	parked.map_or_else(
+		|| "Nothing to park, switched branch.".to_string(),
+		|parked| {
+			format!(
+				"Changes of '{}' parked in the stash list, return to pick them up again.",
+				parked.branch
+			)
+		},
+	)
+}
+pub fn branch_unparked(
+	parked: &ParkedChanges,
+	result: ParkReturn,
+) -> String {
+	match result {
+		ParkReturn::Restored => format!(
+			"Back on '{}' with its parked changes.",
+			parked.branch
+		),
+		ParkReturn::Conflicted => format!(
+			"Back on '{}', its parked changes conflict: resolve them in the Status tab, the stash is kept until you drop it.",
+			parked.branch
+		),
+		ParkReturn::StashGone => format!(
+			"Back on '{}', its parked stash was dropped meanwhile.",
+			parked.branch
+		),
+	}
+}
+pub fn edit_commit_stopped(subject: &str) -> String {
+	format!("The rebase stopped at '{subject}': amend it or undo it to commit its changes in parts, then continue the rebase in the Status tab.")
+}
+pub fn edit_revision_copy(path: &str, revision: &str) -> String {
+	format!(
+		"'{path}' was opened as a read-only copy from {revision}, changes to it are not saved to the repository"
+	)
+}
+pub fn blame_unavailable(path: &str, err: &Error) -> String {
+	format!("{err}:\n{path}")
+}
+pub fn folder_conflicts_skipped(
+	action: &str,
+	skipped: &[String],
+) -> String {
+	format!(
+		"conflicted files were not {action}d, resolve them first:\n{}",
+		skipped.join("\n")
+	)
+}
+pub fn folder_action_failed(
+	action: &str,
+	failed: &[String],
+	skipped: &[String],
+) -> String {
+	let msg = format!("{action} failed for:\n{}", failed.join("\n"));
+
+	if skipped.is_empty() {
+		msg
+	} else {
+		format!(
+			"{msg}\n\n{}",
+			folder_conflicts_skipped(action, skipped)
+		)
+	}
+}
+pub fn confirm_title_commit_outside_scope() -> String {
+	"Commit outside scope".to_string()
+}
+pub fn confirm_msg_commit_outside_scope(
+	scope: &str,
+	count: usize,
+) -> String {
+	format!("{count} staged change(s) outside of '{scope}/' will be committed as well. Continue?")
 }
 pub fn confirm_msg_stashdrop(
 	_key_config: &SharedKeyConfig,
@@ -287,22 +1279,89 @@ pub fn confirm_title_delete_tag_remote() -> String {
 pub fn confirm_msg_delete_tag_remote(remote_name: &str) -> String {
 	format!("Confirm deleting tag on remote '{remote_name}'?")
 }
+pub fn confirm_title_push_tag() -> String {
+	"Push Tag".to_string()
+}
+pub fn confirm_msg_push_tag(tag_name: &str, remote: &str) -> String {
+	format!("Confirm pushing tag '{tag_name}' to remote '{remote}'?")
+}
+pub fn confirm_title_push_all_tags() -> String {
+	"Push All Tags".to_string()
+}
+pub fn confirm_msg_push_all_tags() -> String {
+	"Confirm pushing every tag the remote does not have yet?"
+		.to_string()
+}
+pub fn confirm_title_force_push_tag() -> String {
+	"Force Push Tag".to_string()
+}
+pub fn confirm_msg_force_push_tag(
+	tag_name: &str,
+	remote: &str,
+) -> String {
+	format!(
+		"Remote '{remote}' has a tag '{tag_name}' pointing somewhere else, pushing refused to replace it.\nForce push to replace the tag others may have fetched already?"
+	)
+}
 pub fn confirm_title_force_push(
 	_key_config: &SharedKeyConfig,
+	force: PushForce,
 ) -> String {
-	"Force Push".to_string()
+	if force == PushForce::Lease {
+		"Force Push".to_string()
+	} else {
+		"Force Push without Lease".to_string()
+	}
 }
 pub fn confirm_msg_force_push(
 	_key_config: &SharedKeyConfig,
 	branch_ref: &str,
+	force: PushForce,
 ) -> String {
-	format!(
-        "Confirm force push to branch '{branch_ref}' ?  This may rewrite history."
-    )
+	if force == PushForce::Lease {
+		format!(
+			"Confirm force push to branch '{branch_ref}' ?  This may rewrite history.\nIt is refused if the remote branch moved since it was last fetched."
+		)
+	} else {
+		format!(
+			"There is no remote-tracking ref of '{branch_ref}' to check the remote branch against: this force push replaces whatever the remote has, commits of others included.\nForce push anyway?"
+		)
+	}
 }
 pub fn log_title(_key_config: &SharedKeyConfig) -> String {
 	"Commit".to_string()
 }
+/// `replaced`: the history is walked through the replace refs
+pub fn log_title_replace_refs(
+	key_config: &SharedKeyConfig,
+	count: usize,
+	replaced: bool,
+) -> String {
+	let refs = if count == 1 { "ref" } else { "refs" };
+
+	if replaced {
+		format!(
+			"{} (through {count} replace {refs})",
+			log_title(key_config)
+		)
+	} else {
+		format!(
+			"{} (ignoring {count} replace {refs})",
+			log_title(key_config)
+		)
+	}
+}
+pub fn log_title_filtered(
+	title: &str,
+	path: &str,
+	matches: usize,
+) -> String {
+	if path.is_empty() {
+		format!("{title} (filtered: {matches} matches)")
+	} else {
+		format!("{title} (filtered to '{path}': {matches} matches)")
+	}
+}
 pub fn file_log_title(
 	file_path: &str,
 	selected: usize,
@@ -319,8 +1378,126 @@ pub fn tag_popup_name_title() -> String {
 pub fn tag_popup_name_msg() -> String {
 	"type tag name".to_string()
 }
-pub fn tag_popup_annotation_title(name: &str) -> String {
-	format!("Tag Annotation ({name})")
+pub fn bookmark_popup_name_title() -> String {
+	"Bookmark".to_string()
+}
+pub fn bookmark_popup_name_msg() -> String {
+	"type bookmark name".to_string()
+}
+pub fn title_bookmarks() -> String {
+	"Bookmarks".to_string()
+}
+pub fn title_replace_refs() -> String {
+	"Replace refs".to_string()
+}
+pub fn replace_refs_disabled() -> String {
+	"git ignores replace refs here (core.useReplaceRefs or GIT_NO_REPLACE_OBJECTS)".to_string()
+}
+pub fn bookmark_unreachable() -> String {
+	"this commit is no longer on any branch".to_string()
+}
+pub fn bookmark_repoint_not_found() -> String {
+	"no commit with the same changes found in the history of HEAD"
+		.to_string()
+}
+pub static PULL_REQUEST_TITLE_MSG: &str = "title";
+pub static PULL_REQUEST_BODY_MSG: &str = "description";
+pub fn pull_request_title_title(branch: &str, base: &str) -> String {
+	format!("Pull Request: {branch} into {base}")
+}
+pub fn pull_request_body_title(title: &str) -> String {
+	format!("Pull Request: {title}")
+}
+pub fn title_create_pull_request(cli: &str) -> String {
+	format!("{cli}: creating pull request")
+}
+pub fn pull_request_page_opened(url: &str) -> String {
+	format!("opened the page to create the pull request:\n{url}")
+}
+pub fn title_fetch_objects(count: usize) -> String {
+	format!(
+		"fetching {count} missing object{}",
+		if count == 1 { "" } else { "s" }
+	)
+}
+/// explains an empty diff of a partial clone, the objects are
+/// fetched from `remote`
+pub fn diff_objects_not_fetched(remote: &str) -> String {
+	format!(
+		"not fetched yet by this partial clone, fetching from `{remote}`:"
+	)
+}
+pub const DIFF_OBJECTS_MISSING: &str =
+	"missing from the object database (and objects/info/alternates):";
+/// the row after the last commit of a shallow clone
+pub const LOG_TRUNCATED_SHALLOW: &str =
+	"⋯ history truncated (shallow clone)";
+/// marks blames and file histories reaching the end of a shallow
+/// clone, older changes are put on the commit it ends at
+pub fn title_history_truncated(title: &str) -> String {
+	format!("{title} [history truncated (shallow clone)]")
+}
+pub fn deepen_history_popup_title() -> String {
+	"Deepen history".to_string()
+}
+pub fn deepen_history_popup_msg() -> String {
+	"commits to fetch, empty for all of the history".to_string()
+}
+pub const fn deepen_history_invalid() -> &'static str {
+	"not a number of commits"
+}
+pub fn title_deepen_history(deepen: Deepen) -> String {
+	match deepen {
+		Deepen::By(count) => format!(
+			"fetching {count} more commit{} of the history",
+			if count == 1 { "" } else { "s" }
+		),
+		Deepen::Unshallow => {
+			"fetching all of the history".to_string()
+		}
+	}
+}
+pub fn title_pre_commit_hook() -> String {
+	"pre-commit hook".to_string()
+}
+pub fn title_commit_msg_hook() -> String {
+	"commit-msg hook".to_string()
+}
+pub fn title_post_commit_hook() -> String {
+	"post-commit hook".to_string()
+}
+pub fn title_prepare_commit_msg_hook() -> String {
+	"prepare-commit-msg hook".to_string()
+}
+pub fn process_status(result: Option<&ProcessResult>) -> String {
+	match result {
+		None => "running".to_string(),
+		Some(result) if result.killed => "killed".to_string(),
+		Some(ProcessResult {
+			code: Some(code), ..
+		}) => format!("exit code {code}"),
+		Some(_) => "terminated".to_string(),
+	}
+}
+pub fn tag_popup_annotation_title(
+	name: &str,
+	signed: bool,
+) -> String {
+	if signed {
+		format!("Signed Tag Annotation ({name})")
+	} else {
+		format!("Tag Annotation ({name})")
+	}
+}
+pub fn title_tag_details(signed: bool) -> String {
+	if signed {
+		"Annotation (signed)".to_string()
+	} else {
+		"Annotation".to_string()
+	}
+}
+pub fn tag_details_tagger() -> String {
+	"Tagger: ".to_string()
 }
 pub fn tag_popup_annotation_msg() -> String {
 	"type tag annotation".to_string()
@@ -342,16 +1519,54 @@ pub fn stashing_options_title(
 pub fn loading_text(_key_config: &SharedKeyConfig) -> String {
 	"Loading ...".to_string()
 }
+pub fn loading_files_text(read: usize) -> String {
+	format!("Loading ... {read} files")
+}
 pub fn create_branch_popup_title(
 	_key_config: &SharedKeyConfig,
 ) -> String {
 	"Branch".to_string()
 }
+pub fn create_branch_at_popup_title(
+	commit: &str,
+	checkout: bool,
+) -> String {
+	if checkout {
+		format!("Branch at {commit} (checkout)")
+	} else {
+		format!("Branch at {commit}")
+	}
+}
+pub fn create_branch_from_stash_popup_title(stash: &str) -> String {
+	format!("Branch from stash {stash} (checkout)")
+}
 pub fn create_branch_popup_msg(
 	_key_config: &SharedKeyConfig,
 ) -> String {
 	"type branch name".to_string()
 }
+pub fn create_worktree_popup_title(
+	branch: &str,
+	checked_out: Option<&str>,
+	force: bool,
+) -> String {
+	match (checked_out, force) {
+		(Some(_), true) => format!("Worktree for {branch} (force)"),
+		(Some(path), false) => {
+			format!("Worktree for {branch} (checked out at {path})")
+		}
+		(None, _) => format!("Worktree for {branch}"),
+	}
+}
+pub fn create_worktree_popup_msg() -> String {
+	"path of the new worktree, relative to this one".to_string()
+}
+pub const fn worktree_path_invalid() -> &'static str {
+	"[exists already]"
+}
+pub const fn worktree_branch_checked_out() -> &'static str {
+	"[checked out elsewhere]"
+}
 pub fn rename_remote_popup_title(
 	_key_config: &SharedKeyConfig,
 ) -> String {
@@ -395,6 +1610,23 @@ pub fn create_remote_popup_msg_url(
 pub const fn remote_name_invalid() -> &'static str {
 	"[invalid name]"
 }
+pub const fn remote_default_marks(
+	fetch: bool,
+	push: bool,
+) -> &'static str {
+	match (fetch, push) {
+		(true, true) => " [default, push]",
+		(true, false) => " [default]",
+		(false, true) => " [push]",
+		(false, false) => "",
+	}
+}
+pub const fn remote_name_exists() -> &'static str {
+	"[remote exists already]"
+}
+pub const fn remote_url_invalid() -> &'static str {
+	"[invalid url]"
+}
 pub fn username_popup_title(_key_config: &SharedKeyConfig) -> String {
 	"Username".to_string()
 }
@@ -407,6 +1639,38 @@ pub fn password_popup_title(_key_config: &SharedKeyConfig) -> String {
 pub fn password_popup_msg(_key_config: &SharedKeyConfig) -> String {
 	"type password".to_string()
 }
+pub fn passphrase_popup_title(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"Passphrase".to_string()
+}
+pub fn passphrase_popup_msg(key: &Path) -> String {
+	format!("passphrase for '{}'", key.display())
+}
+pub fn cred_prompt_cancelled_title() -> String {
+	"Credentials cancelled".to_string()
+}
+pub fn cred_prompt_retry() -> String {
+	"retry with different credentials".to_string()
+}
+pub fn cred_prompt_switch_url(url: &str) -> String {
+	format!("switch to {url}")
+}
+pub fn cred_prompt_abort(job: &str) -> String {
+	format!("abort {job}")
+}
+pub fn cred_prompt_rejected(rejection: &CredRejection) -> String {
+	format!(
+		"credentials rejected, attempt {} of {}{}",
+		rejection.attempt,
+		rejection.max_attempts,
+		rejection
+			.message
+			.as_ref()
+			.map(|msg| format!(": {msg}"))
+			.unwrap_or_default()
+	)
+}
 
 pub fn rename_branch_popup_title(
 	_key_config: &SharedKeyConfig,
@@ -419,17 +1683,24 @@ pub fn rename_branch_popup_msg(
 	"new branch name".to_string()
 }
 
+pub fn refreshed(what: &str) -> String {
+	format!("refreshed {what}")
+}
+
 pub fn copy_success(s: &str) -> String {
 	format!("{POPUP_SUCCESS_COPY} \"{s}\"")
 }
 
-pub fn ellipsis_trim_start(s: &str, width: usize) -> Cow<str> {
+pub fn ellipsis_trim_start(
+	s: &str,
+	width: usize,
+	ellipsis: char,
+) -> Cow<'_, str> {
 	if s.width() <= width {
 		Cow::Borrowed(s)
 	} else {
 		Cow::Owned(format!(
-			"[{}]{}",
-			symbol::ELLIPSIS,
+			"[{ellipsis}]{}",
 			s.unicode_truncate_start(
 				width.saturating_sub(3 /* front indicator */)
 			)
@@ -480,9 +1751,16 @@ pub mod commit {
 	) -> String {
 		"Files:".to_string()
 	}
+	pub fn details_files_viewed(
+		viewed: usize,
+		total: usize,
+	) -> String {
+		format!("{viewed}/{total} files viewed")
+	}
 }
 
 pub mod commands {
+	use crate::args::PrintOnExit;
 	use crate::components::CommandText;
 	use crate::keys::SharedKeyConfig;
 
@@ -494,24 +1772,106 @@ pub mod commands {
 	static CMD_GROUP_STASHES: &str = "-- Stashes --";
 	static CMD_GROUP_LOG: &str = "-- Log --";
 	static CMD_GROUP_BRANCHES: &str = "-- Branches --";
+	static CMD_GROUP_VIM: &str = "-- Vim mode --";
+
+	pub fn vim_move() -> CommandText {
+		CommandText::new(
+			"Move [hjkl]".to_string(),
+			"move left, down, up and right",
+			CMD_GROUP_VIM,
+		)
+	}
+	pub fn vim_top_bottom() -> CommandText {
+		CommandText::new(
+			"Top/Bottom [gg/G]".to_string(),
+			"jump to the first or the last item",
+			CMD_GROUP_VIM,
+		)
+	}
+	pub fn vim_count() -> CommandText {
+		CommandText::new(
+			"Count [1-9]".to_string(),
+			"repeat the next move (like `5j`), without a move the digits are the usual keys",
+			CMD_GROUP_VIM,
+		)
+	}
+	pub fn vim_tabs() -> CommandText {
+		CommandText::new(
+			"Tabs [gt/gT]".to_string(),
+			"next or previous tab, `{n}gt` goes to tab n",
+			CMD_GROUP_VIM,
+		)
+	}
+	pub fn vim_filter() -> CommandText {
+		CommandText::new(
+			"Filter [/]".to_string(),
+			"search or filter the focused tab",
+			CMD_GROUP_VIM,
+		)
+	}
+	pub fn vim_commands() -> CommandText {
+		CommandText::new(
+			"Commands [:]".to_string(),
+			"there is no command prompt, `:` lists all commands",
+			CMD_GROUP_VIM,
+		)
+	}
+	pub fn vim_visual() -> CommandText {
+		CommandText::new(
+			"Visual [v]".to_string(),
+			"mark the items moved over (in the log, the stash list and the working dir)",
+			CMD_GROUP_VIM,
+		)
+	}
+	pub fn vim_escape() -> CommandText {
+		CommandText::new(
+			"Normal [esc]".to_string(),
+			"drop the count or leave visual mode, else the usual esc",
+			CMD_GROUP_VIM,
+		)
+	}
 
 	pub fn toggle_tabs(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
-				"Next [{}]",
-				key_config.get_hint(key_config.keys.tab_toggle)
+				"Next [{}]",
+				key_config.get_hint(key_config.keys.tab_toggle)
+			),
+			"switch to next tab",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn find_file(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Find [{}]",
+				key_config.get_hint(key_config.keys.file_find)
+			),
+			"find file in tree",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn grep_files(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Search [{}]",
+				key_config.get_hint(key_config.keys.file_grep)
 			),
-			"switch to next tab",
+			"search file contents of this revision",
 			CMD_GROUP_GENERAL,
 		)
 	}
-	pub fn find_file(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn grep_toggle_regex(
+		key_config: &SharedKeyConfig,
+		regex: bool,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Find [{}]",
-				key_config.get_hint(key_config.keys.file_find)
+				"{} [{}]",
+				if regex { "Literal" } else { "Regex" },
+				key_config.get_hint(key_config.keys.file_grep_regex)
 			),
-			"find file in tree",
+			"toggle between literal and regex search",
 			CMD_GROUP_GENERAL,
 		)
 	}
@@ -553,6 +1913,80 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn oplog_popup(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Op log [{}]",
+				key_config.get_hint(key_config.keys.open_oplog),
+			),
+			"show the operations gitui ran on this repo",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn reflog_popup(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Reflog [{}]",
+				key_config.get_hint(key_config.keys.open_reflog),
+			),
+			"show where HEAD pointed to before, to recover from a rebase or reset",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn reflog_inspect(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Inspect [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"inspect the commit of the reflog entry",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn refresh(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Refresh [{}]",
+				key_config.get_hint(key_config.keys.refresh),
+			),
+			"re-read what the focused view shows",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn refresh_all(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Refresh all [{}]",
+				key_config.get_hint(key_config.keys.refresh_all),
+			),
+			"re-read status, log, files, stashes and branches",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn oplog_filter(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Filter [{}]",
+				key_config.get_hint(key_config.keys.oplog_filter),
+			),
+			"cycle the operation type shown",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn oplog_inspect(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Inspect [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"inspect the commit this operation replaced or created",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn help_open(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -563,6 +1997,19 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn key_conflicts_open(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Key Conflicts [{}]",
+				key_config
+					.get_hint(key_config.keys.open_key_conflicts)
+			),
+			"list key bindings hidden by others",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn navigate_commit_message(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -602,6 +2049,20 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn toggle_hex_view(
+		key_config: &SharedKeyConfig,
+		hex: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} [{}]",
+				if hex { "Summary" } else { "Hex" },
+				key_config.get_hint(key_config.keys.toggle_hex_view),
+			),
+			"switch between the summary and a hex dump of binary content",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn commit_list_mark(
 		key_config: &SharedKeyConfig,
 		marked: bool,
@@ -626,13 +2087,47 @@ pub mod commands {
 			CMD_GROUP_DIFF,
 		)
 	}
+	pub fn copy_url(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Copy url [{}]",
+				key_config.get_hint(key_config.keys.copy),
+			),
+			"copy the url shown to clipboard",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn copy_hash(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
 				"Copy Hash [{}]",
 				key_config.get_hint(key_config.keys.copy),
 			),
-			"copy selected commit hash to clipboard",
+			"copy the hash of the selected commit, or of the marked ones one per line",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn copy_short_hash(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Copy Short Hash [{}]",
+				key_config.get_hint(key_config.keys.copy_short_hash),
+			),
+			"copy the abbreviated hash of the selected commit, or of the marked ones one per line",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn copy_commit_summary(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Copy Summary [{}]",
+				key_config.get_hint(key_config.keys.copy_commit_summary),
+			),
+			"copy a line like 'abc1234 (\"summary\")' for the selected commit, or for the marked ones, formatted by 'format' in copy_commit.ron of the config dir",
 			CMD_GROUP_LOG,
 		)
 	}
@@ -642,17 +2137,30 @@ pub mod commands {
 				"Copy Path [{}]",
 				key_config.get_hint(key_config.keys.copy),
 			),
-			"copy selected file path to clipboard",
+			"copy selected path relative to the repository to clipboard",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn copy_absolute_path(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Copy Absolute Path [{}]",
+				key_config
+					.get_hint(key_config.keys.copy_absolute_path),
+			),
+			"copy absolute path of the selection to clipboard",
 			CMD_GROUP_LOG,
 		)
 	}
 	pub fn push_tags(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
-				"Push Tags [{}]",
-				key_config.get_hint(key_config.keys.push),
+				"Push All Tags [{}]",
+				key_config.get_hint(key_config.keys.push_all_tags),
 			),
-			"push tags to remote",
+			"push every tag the remote does not have yet",
 			CMD_GROUP_LOG,
 		)
 	}
@@ -704,6 +2212,74 @@ pub mod commands {
 			CMD_GROUP_DIFF,
 		)
 	}
+	pub fn diff_toggle_split(
+		key_config: &SharedKeyConfig,
+		split: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} [{}]",
+				if split { "Unified" } else { "Split" },
+				key_config
+					.get_hint(key_config.keys.diff_toggle_split),
+			),
+			"switch between unified and side-by-side diff",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_lfs_content(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"LFS content [{}]",
+				key_config.get_hint(key_config.keys.diff_lfs_content),
+			),
+			"show the content of the LFS object (git lfs smudge)",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_search(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Search [{}]",
+				key_config.get_hint(key_config.keys.diff_search),
+			),
+			"search within the diff",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_search_next_prev(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Next/Prev match [{}/{}]",
+				key_config.get_hint(key_config.keys.diff_search_next),
+				key_config.get_hint(key_config.keys.diff_search_prev),
+			),
+			"jump to the next or previous search match",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_search_case(
+		key_config: &SharedKeyConfig,
+		case_sensitive: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} [{}]",
+				if case_sensitive {
+					"Ignore case"
+				} else {
+					"Match case"
+				},
+				key_config.get_hint(key_config.keys.diff_search_case),
+			),
+			"toggle whether the diff search matches case",
+			CMD_GROUP_DIFF,
+		)
+	}
 	pub fn diff_home_end(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -839,6 +2415,20 @@ pub mod commands {
 		)
 		.hide_help()
 	}
+	pub fn pull_toggle_rebase(
+		key_config: &SharedKeyConfig,
+		rebase: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} [{}]",
+				if rebase { "Merge" } else { "Rebase" },
+				key_config.get_hint(key_config.keys.pull_toggle_rebase),
+			),
+			"merge or rebase onto the incoming commits of this pull, the default is pull.rebase",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn validate_msg(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -862,6 +2452,58 @@ pub mod commands {
 		)
 	}
 
+	pub fn continue_merge(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Continue merge [{}]",
+				key_config.get_hint(key_config.keys.rebase_branch),
+			),
+			"commit the resolved merge with its prepared message",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn continue_revert(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Continue revert [{}]",
+				key_config.get_hint(key_config.keys.rebase_branch),
+			),
+			"commit the resolved revert with its prepared message",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn continue_cherry_pick(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Continue cherry-pick [{}]",
+				key_config.get_hint(key_config.keys.rebase_branch),
+			),
+			"commit the resolved cherry-pick and pick the rest of the sequence",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn abort_cherry_pick(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Abort cherry-pick [{}]",
+				key_config.get_hint(key_config.keys.abort_merge),
+			),
+			"abort ongoing cherry-pick",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
 	pub fn abort_revert(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -886,6 +2528,45 @@ pub mod commands {
 		)
 	}
 
+	pub fn view_worktrees(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Worktrees [{}]",
+				key_config.get_hint(key_config.keys.view_worktrees),
+			),
+			"list the working trees of the repository",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn open_worktree(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Open [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"switch to the selected worktree",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn prune_worktrees(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Prune [{}]",
+				key_config.get_hint(key_config.keys.prune_worktrees),
+			),
+			"forget worktrees whose directory is gone",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
 	pub fn view_remotes(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -933,20 +2614,52 @@ pub mod commands {
 				"Add [{}]",
 				key_config.get_hint(key_config.keys.add_remote)
 			),
-			"creates a new remote",
-			CMD_GROUP_GENERAL,
+			"creates a new remote",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn delete_remote_popup(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Remove [{}]",
+				key_config.get_hint(key_config.keys.delete_remote),
+			),
+			"remove a remote",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+
+	pub fn set_default_remote(
+		key_config: &SharedKeyConfig,
+		is_default: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} default [{}]",
+				if is_default { "Unset" } else { "Set" },
+				key_config
+					.get_hint(key_config.keys.remote_set_default),
+			),
+			"remote fetching and pushing go to unless the branch names one (`gitui.defaultRemote`)",
+			CMD_GROUP_BRANCHES,
 		)
 	}
 
-	pub fn delete_remote_popup(
+	pub fn set_push_default_remote(
 		key_config: &SharedKeyConfig,
+		is_default: bool,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Remove [{}]",
-				key_config.get_hint(key_config.keys.delete_remote),
+				"{} push default [{}]",
+				if is_default { "Unset" } else { "Set" },
+				key_config
+					.get_hint(key_config.keys.remote_set_push_default),
 			),
-			"remove a remote",
+			"remote pushing goes to unless the branch names one (`remote.pushDefault`)",
 			CMD_GROUP_BRANCHES,
 		)
 	}
@@ -1006,6 +2719,19 @@ pub mod commands {
 		)
 	}
 
+	pub fn init_submodule(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Init [{}]",
+				key_config.get_hint(key_config.keys.init_submodule),
+			),
+			"register the url of the submodule in the config without cloning it",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
 	pub fn update_submodule(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1014,7 +2740,7 @@ pub mod commands {
 				"Update [{}]",
 				key_config.get_hint(key_config.keys.update_submodule),
 			),
-			"update submodule",
+			"clone or fetch the submodule and check out the commit recorded for it",
 			CMD_GROUP_GENERAL,
 		)
 	}
@@ -1100,6 +2826,19 @@ pub mod commands {
 			CMD_GROUP_COMMIT_POPUP,
 		)
 	}
+	pub fn commit_next_template(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Template [{}]",
+				key_config
+					.get_hint(key_config.keys.commit_template_next),
+			),
+			"use the next saved commit message template",
+			CMD_GROUP_COMMIT_POPUP,
+		)
+	}
 	pub fn commit_next_msg_from_history(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1113,6 +2852,60 @@ pub mod commands {
 			CMD_GROUP_COMMIT_POPUP,
 		)
 	}
+	pub fn commit_pull_first(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Pull first [{}]",
+				key_config
+					.get_hint(key_config.keys.commit_pull_first),
+			),
+			"pull the branch that is behind its upstream",
+			CMD_GROUP_COMMIT_POPUP,
+		)
+	}
+	pub fn commit_pull_rebase_first(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Pull rebase first [{}]",
+				key_config
+					.get_hint(key_config.keys.commit_pull_rebase_first),
+			),
+			"pull the branch rebasing local commits onto its upstream",
+			CMD_GROUP_COMMIT_POPUP,
+		)
+	}
+	pub fn commit_diff_scroll(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Scroll Diff [{}{}]",
+				key_config
+					.get_hint(key_config.keys.commit_diff_scroll_up),
+				key_config.get_hint(
+					key_config.keys.commit_diff_scroll_down
+				),
+			),
+			"scroll the staged diff (commit.verbose)",
+			CMD_GROUP_COMMIT_POPUP,
+		)
+	}
+	pub fn commit_reflow(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Reflow body [{}]",
+				key_config.get_hint(key_config.keys.commit_reflow),
+			),
+			"join and wrap the paragraphs of the body at its line length",
+			CMD_GROUP_COMMIT_POPUP,
+		)
+	}
 	pub fn commit_submit(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1199,53 +2992,327 @@ pub mod commands {
 	pub fn stage_all(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
-				"Stage All [{}]",
-				key_config.get_hint(key_config.keys.status_stage_all),
+				"Stage All [{}]",
+				key_config.get_hint(key_config.keys.status_stage_all),
+			),
+			"stage all changes (in unstaged files)",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn unstage_item(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Unstage [{}]",
+				key_config
+					.get_hint(key_config.keys.stage_unstage_item),
+			),
+			"unstage currently selected file or entire path",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn cancel_stage_paths(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Cancel [{}]",
+				key_config.get_hint(key_config.keys.exit_popup),
+			),
+			"stop (un)staging, the index is left as it was",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn unstage_all(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Unstage all [{}]",
+				key_config.get_hint(key_config.keys.status_stage_all),
+			),
+			"unstage all files (in staged files)",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn reset_item(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Reset [{}]",
+				key_config
+					.get_hint(key_config.keys.status_reset_item),
+			),
+			"revert changes in selected file or entire path",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn ignore_item(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Ignore [{}]",
+				key_config
+					.get_hint(key_config.keys.status_ignore_file),
+			),
+			"Add file or path to .gitignore",
+			CMD_GROUP_CHANGES,
+		)
+	}
+
+	pub fn fixup_commit(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Fixup [{}]",
+				key_config.get_hint(key_config.keys.status_fixup),
+			),
+			"create a fixup commit for the commit the staged changes belong to",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn fix_staged(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Fix [{}]",
+				key_config.get_hint(key_config.keys.status_fix_staged),
+			),
+			"run the configured fixer (`gitui.fixStaged`) on the staged files",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn fix_staged_restage(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Restage [{}]",
+				key_config.get_hint(key_config.keys.status_stage_all),
+			),
+			"stage what the fixer changed",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn fixup_confirm(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Confirm [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"commit the staged changes as fixup of the selected commit",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn fixup_target(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Change Target [{}{}]",
+				key_config.get_hint(key_config.keys.move_up),
+				key_config.get_hint(key_config.keys.move_down)
+			),
+			"choose the commit to fix up",
+			CMD_GROUP_CHANGES,
+		)
+	}
+
+	pub fn resolve_conflict(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Resolve [{}]",
+				key_config.get_hint(
+					key_config.keys.status_resolve_conflict
+				),
+			),
+			"Choose how to resolve the conflict of a file or all conflicts of a folder",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn stash_selected(
+		key_config: &SharedKeyConfig,
+		marked: usize,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Stash {} [{}]",
+				if marked == 0 {
+					"selected".to_string()
+				} else {
+					format!("marked ({marked})")
+				},
+				key_config
+					.get_hint(key_config.keys.status_stash_selected),
+			),
+			"stash the changes of the marked files only, of the selected file or folder if none are marked",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn mark_file(
+		key_config: &SharedKeyConfig,
+		marked: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} [{}]",
+				if marked { "Unmark" } else { "Mark" },
+				key_config.get_hint(key_config.keys.log_mark_commit),
+			),
+			"mark the selected file or folder to stash several at once",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn toggle_viewed(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Viewed [{}]",
+				key_config
+					.get_hint(key_config.keys.review_toggle_viewed),
+			),
+			"Toggle file as viewed and go to the next unviewed one",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn scope_folder(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Scope [{}]",
+				key_config
+					.get_hint(key_config.keys.status_scope_folder),
+			),
+			"Only show changes inside the selected folder",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn clear_scope(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Unscope [{}]",
+				key_config
+					.get_hint(key_config.keys.status_clear_scope),
+			),
+			"Show changes of the whole repository again",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn resolve_conflict_confirm(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Confirm [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"resolve conflict",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn conflict_block_nav(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Next/Prev conflict [{}{}]",
+				key_config.get_hint(key_config.keys.diff_hunk_next),
+				key_config.get_hint(key_config.keys.diff_hunk_prev),
+			),
+			"go to the next or previous conflict block",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn conflict_take_ours(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Ours [{}]",
+				key_config
+					.get_hint(key_config.keys.conflict_take_ours),
+			),
+			"replace the conflict block by our side",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn conflict_take_theirs(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Theirs [{}]",
+				key_config
+					.get_hint(key_config.keys.conflict_take_theirs),
 			),
-			"stage all changes (in unstaged files)",
+			"replace the conflict block by their side",
 			CMD_GROUP_CHANGES,
 		)
 	}
-	pub fn unstage_item(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn conflict_take_both(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Unstage [{}]",
+				"Both [{}]",
 				key_config
-					.get_hint(key_config.keys.stage_unstage_item),
+					.get_hint(key_config.keys.conflict_take_both),
 			),
-			"unstage currently selected file or entire path",
+			"replace the conflict block by both sides, ours first",
 			CMD_GROUP_CHANGES,
 		)
 	}
-	pub fn unstage_all(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn conflict_take_both_reverse(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Unstage all [{}]",
-				key_config.get_hint(key_config.keys.status_stage_all),
+				"Both reversed [{}]",
+				key_config.get_hint(
+					key_config.keys.conflict_take_both_reverse
+				),
 			),
-			"unstage all files (in staged files)",
+			"replace the conflict block by both sides, theirs first",
 			CMD_GROUP_CHANGES,
 		)
 	}
-	pub fn reset_item(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn conflict_edit(
+		key_config: &SharedKeyConfig,
+		editing: bool,
+	) -> CommandText {
+		CommandText::new(
+			if editing {
+				format!(
+					"Done [{}]",
+					key_config.get_hint(key_config.keys.exit_popup),
+				)
+			} else {
+				format!(
+					"Edit [{}]",
+					key_config.get_hint(key_config.keys.edit_file),
+				)
+			},
+			"edit the result by hand",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn conflict_save(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Reset [{}]",
-				key_config
-					.get_hint(key_config.keys.status_reset_item),
+				"Save [{}]",
+				key_config.get_hint(key_config.keys.conflict_save),
 			),
-			"revert changes in selected file or entire path",
+			"write the result into the file and offer to stage it",
 			CMD_GROUP_CHANGES,
 		)
 	}
-	pub fn ignore_item(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn resolve_conflict_choice(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Ignore [{}]",
-				key_config
-					.get_hint(key_config.keys.status_ignore_file),
+				"Choose [{}{}]",
+				key_config.get_hint(key_config.keys.move_up),
+				key_config.get_hint(key_config.keys.move_down)
 			),
-			"Add file or path to .gitignore",
+			"change conflict resolution",
 			CMD_GROUP_CHANGES,
 		)
 	}
@@ -1284,6 +3351,20 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn quit_print(
+		key_config: &SharedKeyConfig,
+		what: PrintOnExit,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Quit & Print {} [{}]",
+				super::print_on_exit_name(what),
+				key_config.get_hint(key_config.keys.quit_print),
+			),
+			"quit and print the selection to stdout (--print-on-exit)",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn confirm_action(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1359,6 +3440,18 @@ pub mod commands {
 			CMD_GROUP_STASHES,
 		)
 	}
+	pub fn stash_apply_file(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Apply File [{}]",
+				key_config.get_hint(key_config.keys.stash_apply),
+			),
+			"apply the stashed version of the selected file only",
+			CMD_GROUP_STASHES,
+		)
+	}
 	pub fn stashlist_drop(
 		key_config: &SharedKeyConfig,
 		marked: usize,
@@ -1389,6 +3482,47 @@ pub mod commands {
 			CMD_GROUP_STASHES,
 		)
 	}
+	pub fn stashlist_compare(
+		key_config: &SharedKeyConfig,
+		marked: usize,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Compare [{}]",
+				key_config.get_hint(key_config.keys.compare_commits),
+			),
+			if marked == 1 {
+				"compare stash with the marked one"
+			} else {
+				"compare stash with HEAD, the working tree or another stash"
+			},
+			CMD_GROUP_STASHES,
+		)
+	}
+	pub fn compare_base_next(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Compare With [{}]",
+				key_config.get_hint(key_config.keys.compare_base_next),
+			),
+			"compare with the next of HEAD, the working tree and the other stashes",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn stashlist_branch(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Branch [{}]",
+				key_config.get_hint(key_config.keys.stash_branch),
+			),
+			"check out a new branch at the commit the stash was made on and pop it there",
+			CMD_GROUP_STASHES,
+		)
+	}
 	pub fn stashlist_inspect(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1449,6 +3583,45 @@ pub mod commands {
 			CMD_GROUP_LOG,
 		)
 	}
+	pub fn open_local_history(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Local history [{}]",
+				key_config
+					.get_hint(key_config.keys.open_local_history),
+			),
+			"open snapshots of the selected file",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn local_history_restore(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Restore [{}]",
+				key_config
+					.get_hint(key_config.keys.local_history_restore),
+			),
+			"replace the file with the selected snapshot",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn log_branch_commit(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Branch [{}]",
+				key_config
+					.get_hint(key_config.keys.log_branch_commit),
+			),
+			"create a branch at the commit",
+			CMD_GROUP_LOG,
+		)
+	}
 	pub fn log_tag_commit(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1461,6 +3634,31 @@ pub mod commands {
 			CMD_GROUP_LOG,
 		)
 	}
+	pub fn log_bookmark_commit(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Bookmark [{}]",
+				key_config
+					.get_hint(key_config.keys.log_bookmark_commit),
+			),
+			"bookmark commit",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn open_bookmarks_popup(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Bookmarks [{}]",
+				key_config.get_hint(key_config.keys.log_bookmarks),
+			),
+			"open bookmarks popup",
+			CMD_GROUP_LOG,
+		)
+	}
 	pub fn log_checkout_commit(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1524,6 +3722,117 @@ pub mod commands {
 			CMD_GROUP_LOG,
 		)
 	}
+	pub fn log_fixup_commit(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Fixup [{}]",
+				key_config.get_hint(key_config.keys.log_fixup_commit),
+			),
+			"commit the staged changes as fixup! of the commit",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn log_squash_commit(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Squash [{}]",
+				key_config
+					.get_hint(key_config.keys.log_squash_commit),
+			),
+			"commit the staged changes as squash! of the commit",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn log_autosquash(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Autosquash [{}]",
+				key_config.get_hint(key_config.keys.log_autosquash),
+			),
+			"fold the fixup! and squash! commits into their targets",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn log_amend_commit(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Amend [{}]",
+				key_config.get_hint(key_config.keys.log_amend_commit),
+			),
+			"amend the commit with the staged changes (fixup and autosquash), or stop at it to edit it if nothing is staged",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn log_cherry_pick(
+		key_config: &SharedKeyConfig,
+		marked: usize,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Cherry-pick{} [{}]",
+				if marked > 0 { " marked" } else { "" },
+				key_config.get_hint(key_config.keys.log_cherry_pick),
+			),
+			"apply the selected commit, or the marked ones oldest first, on top of HEAD",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn log_bisect_bad(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Bisect bad [{}]",
+				key_config.get_hint(key_config.keys.log_bisect_bad),
+			),
+			"mark the selected commit bad, starting a bisect if there is none",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn log_bisect_good(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Bisect good [{}]",
+				key_config.get_hint(key_config.keys.log_bisect_good),
+			),
+			"mark the selected commit good, starting a bisect if there is none",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn log_bisect_skip(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Bisect skip [{}]",
+				key_config.get_hint(key_config.keys.log_bisect_skip),
+			),
+			"leave the selected commit out of the bisect as it cannot be tested",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn log_bisect_reset(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Bisect reset [{}]",
+				key_config.get_hint(key_config.keys.log_bisect_reset),
+			),
+			"end the bisect and check out what was checked out before it",
+			CMD_GROUP_LOG,
+		)
+	}
 	pub fn log_find_commit(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1536,6 +3845,54 @@ pub mod commands {
 			CMD_GROUP_LOG,
 		)
 	}
+	pub fn log_filter(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Filter [{}]",
+				key_config.get_hint(key_config.keys.log_filter),
+			),
+			"show only the commits matching a message, author and path",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn log_deepen_history(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Deepen [{}]",
+				key_config
+					.get_hint(key_config.keys.log_deepen_history),
+			),
+			"fetch more of the history a shallow clone ends early",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn deepen_history_confirm(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Fetch [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"fetch the commits",
+			CMD_GROUP_LOG,
+		)
+		.hide_help()
+	}
+	pub fn log_clear_filter(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Clear filter [{}]",
+				key_config.get_hint(key_config.keys.exit_popup),
+			),
+			"show the full log again",
+			CMD_GROUP_LOG,
+		)
+	}
 	pub fn log_close_search(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1566,11 +3923,34 @@ pub mod commands {
 				"Reset [{}]",
 				key_config.get_hint(key_config.keys.reset_branch),
 			),
-			"confirm reset",
-			CMD_GROUP_BRANCHES,
+			"confirm reset",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+
+	pub fn ignore_confirm(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Confirm [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"append the selected pattern to .gitignore",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn ignore_kind(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Change Pattern [{}{}]",
+				key_config.get_hint(key_config.keys.move_up),
+				key_config.get_hint(key_config.keys.move_down)
+			),
+			"choose how to ignore the item",
+			CMD_GROUP_CHANGES,
 		)
 	}
-
 	pub fn reset_type(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -1626,6 +4006,34 @@ pub mod commands {
 		)
 		.hide_help()
 	}
+	pub fn toggle_push_after_create(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Push After Create [{}]",
+				key_config.get_hint(
+					key_config.keys.toggle_push_after_create
+				),
+			),
+			"push the new ref right away (cycles through remotes)",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn toggle_checkout_after_create(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Checkout After Create [{}]",
+				key_config.get_hint(
+					key_config.keys.toggle_checkout_after_create
+				),
+			),
+			"check the new branch out right away",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn open_branch_create_popup(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1638,6 +4046,45 @@ pub mod commands {
 			CMD_GROUP_BRANCHES,
 		)
 	}
+	pub fn branch_create_worktree(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Worktree [{}]",
+				key_config
+					.get_hint(key_config.keys.branch_create_worktree),
+			),
+			"add a worktree with the selected branch checked out",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+	pub fn create_worktree_confirm_msg(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Create Worktree [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"create worktree",
+			CMD_GROUP_BRANCHES,
+		)
+		.hide_help()
+	}
+	pub fn toggle_worktree_force(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Force [{}]",
+				key_config
+					.get_hint(key_config.keys.toggle_worktree_force),
+			),
+			"check out a branch checked out in another worktree, too",
+			CMD_GROUP_BRANCHES,
+		)
+	}
 	pub fn rename_branch_confirm_msg(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1683,7 +4130,32 @@ pub mod commands {
 				"Merge [{}]",
 				key_config.get_hint(key_config.keys.merge_branch),
 			),
-			"merge a branch",
+			"merge a branch: fast-forward, no fast-forward or squash",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+	pub fn merge_branch_confirm(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Merge [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"merge the branch the chosen way",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+	pub fn merge_branch_mode(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Change Mode [{}{}]",
+				key_config.get_hint(key_config.keys.move_up),
+				key_config.get_hint(key_config.keys.move_down)
+			),
+			"fast-forward if possible, always a merge commit or squash",
 			CMD_GROUP_BRANCHES,
 		)
 	}
@@ -1714,6 +4186,90 @@ pub mod commands {
 		)
 	}
 
+	pub fn compare_branch(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Ahead/Behind [{}]",
+				key_config.get_hint(key_config.keys.compare_branch),
+			),
+			"list the commits only the branch or only HEAD has",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+	pub fn branch_park(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Park & Switch [{}]",
+				key_config.get_hint(key_config.keys.branch_park),
+			),
+			"stash the changes and switch to the branch",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+	pub fn branch_push_remote(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Push Remote [{}]",
+				key_config.get_hint(key_config.keys.branch_push_remote),
+			),
+			"cycle the remote the branch pushes to apart from its upstream (`branch.<name>.pushRemote`)",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+	pub fn branch_unpark(
+		key_config: &SharedKeyConfig,
+		branch: &str,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Return to {branch} [{}]",
+				key_config.get_hint(key_config.keys.branch_unpark),
+			),
+			"switch back and pop the changes parked there",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+	pub fn compare_branches_diff(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Diff [{}]",
+				key_config.get_hint(key_config.keys.compare_commits),
+			),
+			"what HEAD changed since it forked from the branch",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+	pub fn compare_branches_side(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Other Side [{}{}]",
+				key_config.get_hint(key_config.keys.move_left),
+				key_config.get_hint(key_config.keys.move_right),
+			),
+			"switch between the commits of HEAD and of the branch",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+	pub fn compare_branches_inspect(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Inspect [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"inspect selected commit in detail",
+			CMD_GROUP_BRANCHES,
+		)
+	}
 	pub fn compare_commits(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1778,6 +4334,29 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn push_tag(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Push [{}]",
+				key_config.get_hint(key_config.keys.push),
+			),
+			"push the selected tag to the remote",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn delete_tag_remote(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Delete on Remote [{}]",
+				key_config
+					.get_hint(key_config.keys.delete_remote_tag),
+			),
+			"delete the selected tag on the remote only",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn delete_tag_popup(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1790,6 +4369,105 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn bookmark_commit_confirm_msg(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Bookmark [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"bookmark commit",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn select_bookmark(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Select commit [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"select bookmarked commit in revlog",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn delete_replace_ref(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Delete [{}]",
+				key_config
+					.get_hint(key_config.keys.replace_ref_delete),
+			),
+			"delete a replace ref",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn log_replace_refs(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Replace refs [{}]",
+				key_config.get_hint(key_config.keys.log_replace_refs),
+			),
+			"list replace refs",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn log_toggle_replace_refs(
+		key_config: &SharedKeyConfig,
+		replaced: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} replacements [{}]",
+				if replaced { "Hide" } else { "Show" },
+				key_config.get_hint(
+					key_config.keys.log_toggle_replace_refs
+				),
+			),
+			"toggle viewing the history through replace refs",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn delete_bookmark(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Delete [{}]",
+				key_config.get_hint(key_config.keys.bookmark_delete),
+			),
+			"delete a bookmark",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn repoint_bookmark(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Re-point [{}]",
+				key_config.get_hint(key_config.keys.bookmark_repoint),
+			),
+			"move an unreachable bookmark to the commit with the same changes",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn kill_process(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Kill [{}]",
+				key_config.get_hint(key_config.keys.process_kill),
+			),
+			"kill the running process",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn select_tag(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -1801,6 +4479,33 @@ pub mod commands {
 		)
 	}
 
+	pub fn files_goto_log(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Log [{}]",
+				key_config.get_hint(key_config.keys.files_goto_log),
+			),
+			"show the commits touching the selected file or folder in the log tab",
+			CMD_GROUP_LOG,
+		)
+	}
+
+	pub fn file_history_goto_log(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Show in log [{}]",
+				key_config
+					.get_hint(key_config.keys.file_history_goto_log),
+			),
+			"select commit in the log tab",
+			CMD_GROUP_LOG,
+		)
+	}
+
 	pub fn status_push(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -1811,6 +4516,38 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn open_pull_request(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Pull request [{}]",
+				key_config.get_hint(key_config.keys.open_pull_request),
+			),
+			"create a pull request for the branch with gh or glab, or open its page in the browser",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn create_pull_request_confirm(
+		key_config: &SharedKeyConfig,
+		is_body_mode: bool,
+	) -> CommandText {
+		CommandText::new(
+			if is_body_mode {
+				format!(
+					"Create [{}]",
+					key_config.get_hint(key_config.keys.commit),
+				)
+			} else {
+				format!(
+					"Description [{}]",
+					key_config.get_hint(key_config.keys.enter),
+				)
+			},
+			"create the pull request",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn status_force_push(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1819,7 +4556,7 @@ pub mod commands {
 				"Force Push [{}]",
 				key_config.get_hint(key_config.keys.force_push),
 			),
-			"force push to origin",
+			"force push to origin, refused if the remote branch moved since the last fetch",
 			CMD_GROUP_GENERAL,
 		)
 	}
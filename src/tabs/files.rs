@@ -38,6 +38,20 @@ impl FilesTab {
 		Ok(())
 	}
 
+	/// reads the tree of `HEAD` again even if it did not move
+	pub fn refresh(&mut self) -> Result<()> {
+		if self.is_visible() {
+			let head = sync::get_head(&self.repo.borrow())?;
+			if self.files.revision().is_some_and(|r| r.id == head) {
+				self.files.refresh();
+			} else {
+				self.files.set_commit(head)?;
+			}
+		}
+
+		Ok(())
+	}
+
 	///
 	pub fn anything_pending(&self) -> bool {
 		self.files.any_work_pending()
@@ -55,9 +69,22 @@ impl FilesTab {
 		Ok(())
 	}
 
+	/// repo relative
+	pub fn selected_file_path(&self) -> Option<String> {
+		self.files.selected_file_path()
+	}
+
 	pub fn file_finder_update(&mut self, file: &Path) {
 		self.files.find_file(file);
 	}
+
+	pub fn file_finder_cancel(&mut self) {
+		self.files.find_file_cancel();
+	}
+
+	pub fn file_grep_update(&mut self, file: &Path, line: usize) {
+		self.files.find_line(file, line);
+	}
 }
 
 impl DrawableComponent for FilesTab {
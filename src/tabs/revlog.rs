@@ -2,12 +2,14 @@ use crate::{
 	app::Environment,
 	components::{
 		visibility_blocking, CommandBlocking, CommandInfo,
-		CommitDetailsComponent, CommitList, Component,
+		CommitCopy, CommitDetailsComponent, CommitList, Component,
 		DrawableComponent, EventState,
 	},
 	keys::{key_match, SharedKeyConfig},
 	popups::{FileTreeOpen, InspectCommitOpen},
-	queue::{InternalEvent, Queue, StackablePopupOpen},
+	queue::{
+		Action, InternalEvent, NeedsUpdate, Queue, StackablePopupOpen,
+	},
 	strings::{self, order},
 	try_or_popup,
 	ui::style::{SharedTheme, Theme},
@@ -16,15 +18,17 @@ use anyhow::Result;
 use asyncgit::{
 	asyncjob::AsyncSingleJob,
 	sync::{
-		self, filter_commit_by_search, CommitId, LogFilterSearch,
-		LogFilterSearchOptions, RepoPathRef,
+		self, filter_commit_by_criteria, filter_commit_by_search,
+		BisectMark, BisectState, CommitId, FixupKind,
+		LogFilterCriteria, LogFilterSearch, LogFilterSearchOptions,
+		RebaseState, ReplaceRef, RepoPathRef,
 	},
 	AsyncBranchesJob, AsyncCommitFilterJob, AsyncGitNotification,
 	AsyncLog, AsyncTags, CommitFilesParams, FetchStatus,
 	ProgressPercent,
 };
 use crossbeam_channel::Sender;
-use crossterm::event::Event;
+use crossterm::event::{Event, KeyEvent};
 use indexmap::IndexSet;
 use ratatui::{
 	layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -47,6 +51,14 @@ struct LogSearchResult {
 	duration: Duration,
 }
 
+/// the log narrowed down to the commits matching `criteria`
+struct LogFilter {
+	criteria: LogFilterCriteria,
+	git_log: AsyncLog,
+	/// the full log walked meanwhile, shown again once cleared
+	unfiltered: IndexSet<CommitId>,
+}
+
 //TODO: deserves its own component
 enum LogSearch {
 	Off,
@@ -65,7 +77,16 @@ pub struct Revlog {
 	commit_details: CommitDetailsComponent,
 	list: CommitList,
 	git_log: AsyncLog,
+	replace_refs: Vec<ReplaceRef>,
+	/// git would walk the history through the replace refs
+	replace_refs_enabled: bool,
+	/// where the history of a shallow clone ends
+	shallow: Vec<CommitId>,
 	search: LogSearch,
+	filter: Option<LogFilter>,
+	bisect: Option<BisectState>,
+	/// selected once the log got to it
+	pending_select: Option<CommitId>,
 	git_tags: AsyncTags,
 	git_local_branches: AsyncSingleJob<AsyncBranchesJob>,
 	git_remote_branches: AsyncSingleJob<AsyncBranchesJob>,
@@ -92,7 +113,13 @@ impl Revlog {
 				&env.sender_git,
 				None,
 			),
+			replace_refs: Vec::new(),
+			replace_refs_enabled: true,
+			shallow: Vec::new(),
 			search: LogSearch::Off,
+			filter: None,
+			bisect: None,
+			pending_select: None,
 			git_tags: AsyncTags::new(
 				env.repo.borrow().clone(),
 				&env.sender_git,
@@ -113,6 +140,10 @@ impl Revlog {
 	///
 	pub fn any_work_pending(&self) -> bool {
 		self.git_log.is_pending()
+			|| self
+				.filter
+				.as_ref()
+				.is_some_and(|filter| filter.git_log.is_pending())
 			|| self.is_search_pending()
 			|| self.git_tags.is_pending()
 			|| self.git_local_branches.is_pending()
@@ -127,15 +158,54 @@ impl Revlog {
 	///
 	pub fn update(&mut self) -> Result<()> {
 		if self.is_visible() {
-			if self.git_log.fetch()? == FetchStatus::Started {
-				self.list.clear();
+			self.update_replace_refs()?;
+			self.update_shallow()?;
+
+			let started =
+				self.git_log.fetch()? == FetchStatus::Started;
+			// checked before extracting so nothing is left behind
+			let mut done = !self.git_log.is_pending();
+			let items = self.git_log.extract_items()?;
+
+			if let Some(filter) = &mut self.filter {
+				if started {
+					filter.unfiltered.clear();
+				}
+				filter.unfiltered.extend(items);
+
+				if filter.git_log.fetch()? == FetchStatus::Started {
+					self.list.reload();
+				}
+				done = !filter.git_log.is_pending();
+				self.list.refresh_extend_data(
+					filter.git_log.extract_items()?,
+				);
+			} else {
+				if started {
+					self.list.reload();
+				}
+				self.list.refresh_extend_data(items);
+			}
+
+			if done {
+				self.list.finish_reload();
+			}
+			self.list.set_truncated(done && !self.shallow.is_empty());
+
+			if let Some(id) = self.pending_select {
+				if self.list.select_commit(id).is_ok() || done {
+					self.pending_select = None;
+				}
 			}
 
-			self.list
-				.refresh_extend_data(self.git_log.extract_items()?);
+			self.update_title();
 
 			self.git_tags.request(Duration::from_secs(3), false)?;
 
+			self.list.set_bookmarks(sync::get_bookmarks(
+				&self.repo.borrow(),
+			)?);
+
 			if self.commit_details.is_visible() {
 				let commit = self.selected_commit();
 				let tags = self.selected_commit_tags(commit.as_ref());
@@ -150,6 +220,55 @@ impl Revlog {
 		Ok(())
 	}
 
+	/// reads the bisect session again, also if it was moved on from
+	/// the command line
+	pub fn update_bisect(&mut self) -> Result<()> {
+		self.bisect = sync::bisect_state(&self.repo.borrow())?;
+
+		Ok(())
+	}
+
+	///
+	pub const fn bisect(&self) -> Option<&BisectState> {
+		self.bisect.as_ref()
+	}
+
+	/// the next update walks the log again even if no ref moved
+	pub fn invalidate(&self) -> Result<()> {
+		self.git_log.invalidate()?;
+		if let Some(filter) = &self.filter {
+			filter.git_log.invalidate()?;
+		}
+
+		Ok(())
+	}
+
+	///
+	pub fn refresh(&mut self) -> Result<()> {
+		self.invalidate()?;
+		self.update()
+	}
+
+	/// reloads the branches and tags shown next to the commits, they
+	/// are loaded again once the tab is shown otherwise
+	pub fn update_refs(&mut self) -> Result<()> {
+		if self.is_visible() {
+			self.git_local_branches.spawn(AsyncBranchesJob::new(
+				self.repo.borrow().clone(),
+				true,
+			));
+
+			self.git_remote_branches.spawn(AsyncBranchesJob::new(
+				self.repo.borrow().clone(),
+				false,
+			));
+
+			self.git_tags.request(Duration::ZERO, false)?;
+		}
+
+		Ok(())
+	}
+
 	///
 	pub fn update_git(
 		&mut self,
@@ -200,7 +319,7 @@ impl Revlog {
 		Ok(())
 	}
 
-	fn selected_commit(&self) -> Option<CommitId> {
+	pub fn selected_commit(&self) -> Option<CommitId> {
 		self.list.selected_entry().map(|e| e.id)
 	}
 
@@ -222,10 +341,270 @@ impl Revlog {
 
 	fn revert_commit(&self) -> Result<()> {
 		if let Some(c) = self.selected_commit() {
-			sync::revert_commit(&self.repo.borrow(), c)?;
+			if sync::is_merge_commit(&self.repo.borrow(), c)? {
+				self.queue.push(InternalEvent::ConfirmAction(
+					Action::RevertMerge(c),
+				));
+			} else {
+				self.queue.push(InternalEvent::RevertCommit(c, None));
+			}
+		}
+
+		Ok(())
+	}
+
+	/// walks the history again when the replace refs changed
+	fn update_replace_refs(&mut self) -> Result<()> {
+		let repo = self.repo.borrow().clone();
+
+		let replace_refs = sync::get_replace_refs(&repo)?;
+		if replace_refs != self.replace_refs {
+			self.replace_refs = replace_refs;
+			self.git_log.invalidate()?;
+			if let Some(filter) = &self.filter {
+				filter.git_log.invalidate()?;
+			}
+		}
+		self.replace_refs_enabled =
+			sync::replace_refs_enabled(&repo)?;
+
+		Ok(())
+	}
+
+	/// walks the history again once a shallow clone got deepened,
+	/// also from outside of gitui
+	fn update_shallow(&mut self) -> Result<()> {
+		let shallow = sync::shallow_commits(&self.repo.borrow())?;
+		if shallow != self.shallow {
+			self.shallow = shallow;
+			self.invalidate()?;
+		}
+
+		Ok(())
+	}
+
+	fn update_title(&mut self) {
+		let title = if self.replace_refs.is_empty() {
+			strings::log_title(&self.key_config)
+		} else {
+			strings::log_title_replace_refs(
+				&self.key_config,
+				self.replace_refs.len(),
+				self.is_replaced(),
+			)
+		};
+
+		let title = if let Some(filter) = &self.filter {
+			strings::log_title_filtered(
+				&title,
+				&filter.criteria.path,
+				self.list.count(),
+			)
+		} else {
+			title
+		};
+
+		self.list.set_title(&title);
+	}
+
+	/// walks the history again for the commits matching `criteria`,
+	/// no criteria at all show the full log again
+	pub fn filter(
+		&mut self,
+		criteria: LogFilterCriteria,
+	) -> Result<()> {
+		if criteria.is_empty() {
+			return self.clear_filter();
+		}
+
+		let unfiltered = match self.filter.take() {
+			Some(filter) => filter.unfiltered,
+			None => self.list.copy_items().into_iter().collect(),
+		};
+
+		let git_log = AsyncLog::new(
+			self.repo.borrow().clone(),
+			&self.sender,
+			Some(filter_commit_by_criteria(criteria.clone())),
+		);
+		git_log.set_replace_refs(self.git_log.replace_refs())?;
+
+		self.filter = Some(LogFilter {
+			criteria,
+			git_log,
+			unfiltered,
+		});
+
+		// the results are about the list being replaced
+		self.cancel_search();
+		self.search = LogSearch::Off;
+		self.list.set_highlighting(None);
+
+		self.list.clear();
+		self.update()
+	}
+
+	/// shows the full log again, keeping the selected commit
+	fn clear_filter(&mut self) -> Result<()> {
+		if let Some(filter) = self.filter.take() {
+			let selected = self.selected_commit();
+
+			self.list.clear();
+			self.list.set_commits(filter.unfiltered);
+			self.update()?;
+
+			if let Some(id) = selected {
+				if let Err(e) = self.list.select_commit(id) {
+					log::warn!("keep selection: {e}");
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	fn is_replaced(&self) -> bool {
+		self.replace_refs_enabled && self.git_log.replace_refs()
+	}
+
+	fn toggle_replace_refs(&mut self) -> Result<()> {
+		if !self.replace_refs_enabled {
+			self.queue.push(InternalEvent::ShowInfoMsg(
+				strings::replace_refs_disabled(),
+			));
+			return Ok(());
+		}
+
+		self.git_log
+			.set_replace_refs(!self.git_log.replace_refs())?;
+		if let Some(filter) = &self.filter {
+			filter
+				.git_log
+				.set_replace_refs(self.git_log.replace_refs())?;
+		}
+
+		self.update()
+	}
+
+	fn fixup_commit(&self, kind: FixupKind) -> Result<()> {
+		if let Some(c) = self.selected_commit() {
+			sync::commit_fixup(&self.repo.borrow(), c, kind)?;
+			self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+		}
+
+		Ok(())
+	}
+
+	fn autosquash(&self) -> Result<()> {
+		if let Some(target) = self.selected_commit() {
+			let pushed =
+				sync::is_commit_pushed(&self.repo.borrow(), target)?;
+
+			self.queue.push(InternalEvent::ConfirmAction(
+				Action::Autosquash { target, pushed },
+			));
+		}
+
+		Ok(())
+	}
+
+	fn amend_commit(&self) -> Result<()> {
+		if let Some(target) = self.selected_commit() {
+			let plan = sync::amend_plan(&self.repo.borrow(), target)?;
+
+			self.queue.push(InternalEvent::ConfirmAction(
+				Action::AmendCommit(plan),
+			));
+		}
+
+		Ok(())
+	}
+
+	/// the marked commits oldest first, or the selected one
+	fn cherry_pick(&mut self) -> Result<()> {
+		let mut commits = self.list.marked_commits();
+		if commits.is_empty() {
+			commits.extend(self.selected_commit());
+		} else {
+			// marked commits are in log order, newest first
+			commits.reverse();
+		}
+
+		if commits.is_empty() {
+			return Ok(());
+		}
+
+		let outcome =
+			sync::cherry_pick(&self.repo.borrow(), &commits)?;
+		self.list.clear_marked();
+
+		let mut notices = Vec::new();
+		if !outcome.empty.is_empty() {
+			let skipped: Vec<String> = outcome
+				.empty
+				.iter()
+				.map(CommitId::get_short_string)
+				.collect();
+			notices
+				.push(strings::cherry_pick_skipped_empty(&skipped));
+		}
+
+		if outcome.state == RebaseState::Conflicted {
+			notices.push(strings::cherry_pick_conflicted());
 			self.queue.push(InternalEvent::TabSwitchStatus);
 		}
 
+		if !notices.is_empty() {
+			self.queue
+				.push(InternalEvent::ShowInfoMsg(notices.join("\n")));
+		}
+
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+
+		Ok(())
+	}
+
+	/// marks the selected commit and selects the one checked out
+	/// next, the first bad commit gets inspected once it is found
+	fn bisect_mark(&mut self, mark: BisectMark) -> Result<()> {
+		let Some(id) = self.selected_commit() else {
+			return Ok(());
+		};
+
+		let state = sync::bisect_mark(&self.repo.borrow(), id, mark)?;
+
+		if let Some(culprit) = state.culprit {
+			self.pending_select = Some(culprit);
+			self.queue.push(InternalEvent::OpenPopup(
+				StackablePopupOpen::InspectCommit(
+					InspectCommitOpen::new(culprit),
+				),
+			));
+			self.queue.push(InternalEvent::ShowInfoMsg(
+				strings::bisect_found(&self.key_config, &culprit),
+			));
+		} else if let Some(next) = state.next {
+			self.pending_select = Some(next);
+		} else if let (Some(bad), false) =
+			(state.bad, state.good.is_empty())
+		{
+			self.queue.push(InternalEvent::ShowInfoMsg(
+				strings::bisect_only_skipped(&bad, &state.skipped),
+			));
+		}
+
+		self.bisect = Some(state);
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+
+		Ok(())
+	}
+
+	fn bisect_reset(&mut self) -> Result<()> {
+		sync::bisect_reset(&self.repo.borrow())?;
+
+		self.bisect = None;
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+
 		Ok(())
 	}
 
@@ -401,6 +780,42 @@ impl Revlog {
 		self.is_in_search_mode() && !self.is_search_pending()
 	}
 
+	fn copy_key(&self, key: &KeyEvent) -> Option<CommitCopy> {
+		if key_match(key, self.key_config.keys.copy) {
+			Some(CommitCopy::Hash)
+		} else if key_match(key, self.key_config.keys.copy_short_hash)
+		{
+			Some(CommitCopy::ShortHash)
+		} else if key_match(
+			key,
+			self.key_config.keys.copy_commit_summary,
+		) {
+			Some(CommitCopy::Summary)
+		} else {
+			None
+		}
+	}
+
+	fn bisect_mark_key(&self, key: &KeyEvent) -> Option<BisectMark> {
+		if self.is_search_pending()
+			|| self.selected_commit().is_none()
+		{
+			None
+		} else if key_match(key, self.key_config.keys.log_bisect_bad)
+		{
+			Some(BisectMark::Bad)
+		} else if key_match(key, self.key_config.keys.log_bisect_good)
+		{
+			Some(BisectMark::Good)
+		} else if key_match(key, self.key_config.keys.log_bisect_skip)
+			&& self.bisect.is_some()
+		{
+			Some(BisectMark::Skip)
+		} else {
+			None
+		}
+	}
+
 	fn can_start_search(&self) -> bool {
 		!self.git_log.is_pending() && !self.is_search_pending()
 	}
@@ -470,18 +885,37 @@ impl Component for Revlog {
 					} else if self.can_close_search() {
 						self.list.set_highlighting(None);
 						self.search = LogSearch::Off;
+					} else if self.filter.is_some() {
+						try_or_popup!(
+							self,
+							"filter error:",
+							self.clear_filter()
+						);
 					}
 					return Ok(EventState::Consumed);
-				} else if key_match(k, self.key_config.keys.copy) {
+				} else if let Some(what) = self.copy_key(k) {
 					try_or_popup!(
 						self,
 						strings::POPUP_FAIL_COPY,
-						self.list.copy_commit_hash()
+						self.list.copy_commits(what)
 					);
 					return Ok(EventState::Consumed);
 				} else if key_match(k, self.key_config.keys.push) {
 					self.queue.push(InternalEvent::PushTags);
 					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_branch_commit,
+				) {
+					return self.selected_commit().map_or(
+						Ok(EventState::NotConsumed),
+						|id| {
+							self.queue.push(
+								InternalEvent::BranchCommit(id),
+							);
+							Ok(EventState::Consumed)
+						},
+					);
 				} else if key_match(
 					k,
 					self.key_config.keys.log_tag_commit,
@@ -494,6 +928,52 @@ impl Component for Revlog {
 							Ok(EventState::Consumed)
 						},
 					);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_bookmark_commit,
+				) {
+					return self.selected_commit().map_or(
+						Ok(EventState::NotConsumed),
+						|id| {
+							self.queue.push(
+								InternalEvent::BookmarkCommit(id),
+							);
+							Ok(EventState::Consumed)
+						},
+					);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_bookmarks,
+				) {
+					self.queue.push(InternalEvent::ViewBookmarks);
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_replace_refs,
+				) && !self.replace_refs.is_empty()
+				{
+					self.queue.push(InternalEvent::ViewReplaceRefs);
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_toggle_replace_refs,
+				) && !self.replace_refs.is_empty()
+					&& !self.is_search_pending()
+				{
+					try_or_popup!(
+						self,
+						"replace refs error:",
+						self.toggle_replace_refs()
+					);
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_deepen_history,
+				) && !self.shallow.is_empty()
+				{
+					self.queue
+						.push(InternalEvent::OpenDeepenHistoryPopup);
+					return Ok(EventState::Consumed);
 				} else if key_match(
 					k,
 					self.key_config.keys.move_right,
@@ -569,12 +1049,99 @@ impl Component for Revlog {
 							Ok(EventState::Consumed)
 						},
 					);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_fixup_commit,
+				) && !self.is_search_pending()
+				{
+					try_or_popup!(
+						self,
+						"fixup error:",
+						self.fixup_commit(FixupKind::Fixup)
+					);
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_squash_commit,
+				) && !self.is_search_pending()
+				{
+					try_or_popup!(
+						self,
+						"squash error:",
+						self.fixup_commit(FixupKind::Squash)
+					);
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_autosquash,
+				) && !self.is_search_pending()
+				{
+					try_or_popup!(
+						self,
+						"autosquash error:",
+						self.autosquash()
+					);
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_amend_commit,
+				) && !self.is_search_pending()
+				{
+					try_or_popup!(
+						self,
+						"amend error:",
+						self.amend_commit()
+					);
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_cherry_pick,
+				) && !self.is_search_pending()
+				{
+					try_or_popup!(
+						self,
+						"cherry-pick error:",
+						self.cherry_pick()
+					);
+					return Ok(EventState::Consumed);
+				} else if let Some(mark) = self.bisect_mark_key(k) {
+					try_or_popup!(
+						self,
+						"bisect error:",
+						self.bisect_mark(mark)
+					);
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_bisect_reset,
+				) && self.bisect.is_some()
+				{
+					try_or_popup!(
+						self,
+						"bisect reset error:",
+						self.bisect_reset()
+					);
+					return Ok(EventState::Consumed);
 				} else if key_match(k, self.key_config.keys.log_find)
 					&& self.can_start_search()
 				{
 					self.queue
 						.push(InternalEvent::OpenLogSearchPopup);
 					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_filter,
+				) && !self.is_search_pending()
+				{
+					self.queue.push(
+						InternalEvent::OpenLogFilterPopup(
+							self.filter
+								.as_ref()
+								.map(|filter| filter.criteria.clone())
+								.unwrap_or_default(),
+						),
+					);
+					return Ok(EventState::Consumed);
 				} else if key_match(
 					k,
 					self.key_config.keys.compare_commits,
@@ -612,6 +1179,7 @@ impl Component for Revlog {
 		Ok(EventState::NotConsumed)
 	}
 
+	#[allow(clippy::too_many_lines)]
 	fn commands(
 		&self,
 		out: &mut Vec<CommandInfo>,
@@ -678,12 +1246,65 @@ impl Component for Revlog {
 			self.visible || force_all,
 		));
 
+		out.push(CommandInfo::new(
+			strings::commands::copy_short_hash(&self.key_config),
+			self.selected_commit().is_some(),
+			self.visible || force_all,
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::copy_commit_summary(&self.key_config),
+			self.selected_commit().is_some(),
+			self.visible || force_all,
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::log_branch_commit(&self.key_config),
+			self.selected_commit().is_some(),
+			self.visible || force_all,
+		));
+
 		out.push(CommandInfo::new(
 			strings::commands::log_tag_commit(&self.key_config),
 			self.selected_commit().is_some(),
 			self.visible || force_all,
 		));
 
+		out.push(CommandInfo::new(
+			strings::commands::log_bookmark_commit(&self.key_config),
+			self.selected_commit().is_some(),
+			self.visible || force_all,
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::open_bookmarks_popup(&self.key_config),
+			true,
+			self.visible || force_all,
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::log_replace_refs(&self.key_config),
+			true,
+			(self.visible && !self.replace_refs.is_empty())
+				|| force_all,
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::log_toggle_replace_refs(
+				&self.key_config,
+				self.is_replaced(),
+			),
+			!self.is_search_pending(),
+			(self.visible && !self.replace_refs.is_empty())
+				|| force_all,
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::log_deepen_history(&self.key_config),
+			true,
+			(self.visible && !self.shallow.is_empty()) || force_all,
+		));
+
 		out.push(CommandInfo::new(
 			strings::commands::log_checkout_commit(&self.key_config),
 			self.selected_commit().is_some(),
@@ -724,11 +1345,76 @@ impl Component for Revlog {
 			self.selected_commit().is_some(),
 			(self.visible && !self.is_search_pending()) || force_all,
 		));
+		out.push(CommandInfo::new(
+			strings::commands::log_fixup_commit(&self.key_config),
+			self.selected_commit().is_some(),
+			(self.visible && !self.is_search_pending()) || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_squash_commit(&self.key_config),
+			self.selected_commit().is_some(),
+			(self.visible && !self.is_search_pending()) || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_autosquash(&self.key_config),
+			self.selected_commit().is_some(),
+			(self.visible && !self.is_search_pending()) || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_amend_commit(&self.key_config),
+			self.selected_commit().is_some(),
+			(self.visible && !self.is_search_pending()) || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_cherry_pick(
+				&self.key_config,
+				self.list.marked_count(),
+			),
+			self.selected_commit().is_some()
+				|| self.list.marked_count() > 0,
+			(self.visible && !self.is_search_pending()) || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_bisect_bad(&self.key_config),
+			self.selected_commit().is_some(),
+			(self.visible && !self.is_search_pending()) || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_bisect_good(&self.key_config),
+			self.selected_commit().is_some(),
+			(self.visible && !self.is_search_pending()) || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_bisect_skip(&self.key_config),
+			self.selected_commit().is_some(),
+			(self.visible
+				&& self.bisect.is_some()
+				&& !self.is_search_pending())
+				|| force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_bisect_reset(&self.key_config),
+			true,
+			(self.visible && self.bisect.is_some()) || force_all,
+		));
 		out.push(CommandInfo::new(
 			strings::commands::log_find_commit(&self.key_config),
 			self.can_start_search(),
 			self.visible || force_all,
 		));
+		out.push(CommandInfo::new(
+			strings::commands::log_filter(&self.key_config),
+			!self.is_search_pending(),
+			self.visible || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_clear_filter(&self.key_config),
+			true,
+			(self.visible
+				&& self.filter.is_some()
+				&& !self.is_in_search_mode())
+				|| force_all,
+		));
 
 		visibility_blocking(self)
 	}
@@ -740,23 +1426,72 @@ impl Component for Revlog {
 	fn hide(&mut self) {
 		self.visible = false;
 		self.git_log.set_background();
+		if let Some(filter) = &self.filter {
+			filter.git_log.set_background();
+		}
 	}
 
 	fn show(&mut self) -> Result<()> {
 		self.visible = true;
 
-		self.git_local_branches.spawn(AsyncBranchesJob::new(
-			self.repo.borrow().clone(),
-			true,
-		));
-
-		self.git_remote_branches.spawn(AsyncBranchesJob::new(
-			self.repo.borrow().clone(),
-			false,
-		));
-
+		self.update_refs()?;
 		self.update()?;
 
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use asyncgit::sync::RepoPath;
+	use crossbeam_channel::unbounded;
+	use std::{path::Path, process::Command, thread};
+	use tempfile::TempDir;
+
+	fn git(path: &Path, args: &[&str]) -> String {
+		let output = Command::new("git")
+			.args(["-c", "user.name=name", "-c", "user.email=email"])
+			.args(args)
+			.current_dir(path)
+			.output()
+			.unwrap();
+		assert!(output.status.success());
+
+		String::from_utf8(output.stdout).unwrap().trim().to_string()
+	}
+
+	#[test]
+	fn test_starts_on_head() {
+		let td = TempDir::new().unwrap();
+		git(td.path(), &["init", "-q"]);
+		for msg in ["a", "b", "c"] {
+			git(
+				td.path(),
+				&["commit", "-q", "--allow-empty", "-m", msg],
+			);
+		}
+		let head = git(td.path(), &["rev-parse", "HEAD"]);
+
+		let (sender, _receiver) = unbounded();
+		let mut env = Environment::test_env();
+		env.sender_git = sender;
+		*env.repo.borrow_mut() = RepoPath::Path(td.path().into());
+
+		let mut revlog = Revlog::new(&env);
+		revlog.show().unwrap();
+		for _ in 0..500 {
+			if !revlog.git_log.is_pending() {
+				break;
+			}
+			thread::sleep(Duration::from_millis(10));
+			revlog.update().unwrap();
+		}
+		revlog.update().unwrap();
+
+		assert_eq!(
+			revlog.selected_commit(),
+			Some(CommitId::from_str_unchecked(&head).unwrap())
+		);
+	}
+}
@@ -41,6 +41,14 @@ impl StashList {
 		if self.is_visible() {
 			let stashes = sync::get_stashes(&self.repo.borrow())?;
 			self.list.set_commits(stashes.into_iter().collect());
+			self.list.set_labels(
+				sync::get_parked(&self.repo.borrow())?
+					.into_iter()
+					.map(|parked| {
+						(parked.stash, strings::stash_parked(&parked))
+					})
+					.collect(),
+			);
 		}
 
 		Ok(())
@@ -82,6 +90,29 @@ impl StashList {
 		}
 	}
 
+	/// compares with the marked stash if there is exactly one
+	fn compare(&self) {
+		if let Some(e) = self.list.selected_entry() {
+			let base = if self.list.marked_count() == 1 {
+				self.list
+					.marked_commits()
+					.into_iter()
+					.find(|id| *id != e.id)
+			} else {
+				None
+			};
+
+			self.queue
+				.push(InternalEvent::OpenCompareStash(e.id, base));
+		}
+	}
+
+	fn branch(&self) {
+		if let Some(e) = self.list.selected_entry() {
+			self.queue.push(InternalEvent::BranchFromStash(e.id));
+		}
+	}
+
 	fn inspect(&self) {
 		if let Some(e) = self.list.selected_entry() {
 			self.queue.push(InternalEvent::OpenPopup(
@@ -175,6 +206,19 @@ impl Component for StashList {
 				selection_valid,
 				true,
 			));
+			out.push(CommandInfo::new(
+				strings::commands::stashlist_compare(
+					&self.key_config,
+					self.list.marked_count(),
+				),
+				selection_valid,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::stashlist_branch(&self.key_config),
+				selection_valid,
+				true,
+			));
 			out.push(CommandInfo::new(
 				strings::commands::stashlist_inspect(
 					&self.key_config,
@@ -209,11 +253,21 @@ impl Component for StashList {
 					self.key_config.keys.stash_drop,
 				) {
 					self.drop_stash();
+				} else if key_match(
+					k,
+					self.key_config.keys.stash_branch,
+				) {
+					self.branch();
 				} else if key_match(
 					k,
 					self.key_config.keys.stash_open,
 				) {
 					self.inspect();
+				} else if key_match(
+					k,
+					self.key_config.keys.compare_commits,
+				) {
+					self.compare();
 				}
 			}
 		}
@@ -235,3 +289,47 @@ impl Component for StashList {
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::{fs, path::Path, process::Command};
+	use tempfile::TempDir;
+
+	fn git(path: &Path, args: &[&str]) -> String {
+		let output = Command::new("git")
+			.args(["-c", "user.name=name", "-c", "user.email=email"])
+			.args(args)
+			.current_dir(path)
+			.output()
+			.unwrap();
+		assert!(output.status.success());
+
+		String::from_utf8(output.stdout).unwrap().trim().to_string()
+	}
+
+	#[test]
+	fn test_starts_on_newest_stash() {
+		let td = TempDir::new().unwrap();
+		git(td.path(), &["init", "-q"]);
+		fs::write(td.path().join("a"), "a").unwrap();
+		git(td.path(), &["add", "a"]);
+		git(td.path(), &["commit", "-q", "-m", "a"]);
+		for content in ["b", "c"] {
+			fs::write(td.path().join("a"), content).unwrap();
+			git(td.path(), &["stash", "-q"]);
+		}
+		let newest = git(td.path(), &["rev-parse", "stash@{0}"]);
+
+		let env = Environment::test_env();
+		*env.repo.borrow_mut() = RepoPath::Path(td.path().into());
+
+		let mut stashes = StashList::new(&env);
+		stashes.show().unwrap();
+
+		assert_eq!(
+			stashes.list.selected_entry().map(|e| e.id),
+			Some(CommitId::from_str_unchecked(&newest).unwrap())
+		);
+	}
+}
@@ -5,23 +5,28 @@ use crate::{
 		command_pump, event_pump, visibility_blocking,
 		ChangesComponent, CommandBlocking, CommandInfo, Component,
 		DiffComponent, DrawableComponent, EventState,
-		FileTreeItemKind,
+		FileTreeItemKind, SubmoduleSection,
 	},
 	keys::{key_match, SharedKeyConfig},
 	options::SharedOptions,
-	queue::{Action, InternalEvent, NeedsUpdate, Queue, ResetItem},
+	queue::{
+		Action, InternalEvent, NeedsUpdate, Queue, ResetFolderItem,
+		ResetItem,
+	},
 	strings, try_or_popup,
-	ui::style::Theme,
+	ui::style::{SharedTheme, Theme},
 };
 use anyhow::Result;
 use asyncgit::{
 	cached,
 	sync::{
-		self, status::StatusType, RepoPath, RepoPathRef, RepoState,
+		self, status::StatusType, RebaseState, RepoPath, RepoPathRef,
+		RepoState,
 	},
-	sync::{BranchCompare, CommitId},
+	sync::{BranchCompare, BranchRemotes, CommitId},
 	AsyncDiff, AsyncGitNotification, AsyncStatus, DiffParams,
-	DiffType, PushType, StatusItem, StatusParams,
+	DiffType, FileDiff, PushForce, PushType, StatusItem,
+	StatusItemType, StatusParams,
 };
 use crossterm::event::Event;
 use itertools::Itertools;
@@ -30,6 +35,7 @@ use ratatui::{
 	style::{Color, Style},
 	widgets::{Block, BorderType, Borders, Paragraph},
 };
+use std::{collections::BTreeSet, path::Path};
 
 /// what part of the screen is focused
 #[derive(PartialEq)]
@@ -57,13 +63,76 @@ enum DiffTarget {
 	WorkingDir,
 }
 
+/// more stale paths than this are not rechecked one by one, a new
+/// working dir pass covers them at once
+const MAX_STALE_WORKDIR_PATHS: usize = 64;
+
+/// paths whose working dir status might have changed since the last
+/// working dir pass because their index entry changed
+///
+/// only files listed as staged before or after an index change can
+/// have a different index entry, everything else matches `HEAD` both
+/// times.
+#[derive(Default)]
+struct StaleWorkdirPaths {
+	paths: BTreeSet<String>,
+	/// index fingerprint and stage items the paths were last updated
+	/// with
+	stage: (u64, Vec<StatusItem>),
+	/// stale paths the running working dir pass is going to cover
+	covered_by_pass: Option<BTreeSet<String>>,
+}
+
+impl StaleWorkdirPaths {
+	fn stage_changed(
+		&mut self,
+		index_fingerprint: u64,
+		stage_items: &[StatusItem],
+	) {
+		if self.stage.0 == index_fingerprint {
+			return;
+		}
+
+		let changed = self
+			.stage
+			.1
+			.iter()
+			.chain(stage_items)
+			.map(|item| item.path.clone())
+			.collect::<BTreeSet<_>>();
+
+		if let Some(covered) = &mut self.covered_by_pass {
+			covered.retain(|path| !changed.contains(path));
+		}
+
+		self.paths.extend(changed);
+		self.stage = (index_fingerprint, stage_items.to_vec());
+	}
+
+	fn pass_requested(&mut self) {
+		self.covered_by_pass = Some(self.paths.clone());
+	}
+
+	fn pass_done(&mut self) {
+		if let Some(covered) = self.covered_by_pass.take() {
+			self.paths.retain(|path| !covered.contains(path));
+		}
+	}
+
+	fn overflows(&self) -> bool {
+		self.paths.len() > MAX_STALE_WORKDIR_PATHS
+	}
+}
+
 struct RemoteStatus {
 	has_remote_for_fetch: bool,
 	has_remote_for_push: bool,
 }
 
+#[allow(clippy::struct_excessive_bools)]
 pub struct Status {
 	repo: RepoPathRef,
+	theme: SharedTheme,
 	visible: bool,
 	focus: Focus,
 	diff_target: DiffTarget,
@@ -72,13 +141,38 @@ pub struct Status {
 	diff: DiffComponent,
 	remotes: RemoteStatus,
 	git_diff: AsyncDiff,
+	/// everything staged, shown by the commit popup with `commit.verbose`
+	git_diff_staged: AsyncDiff,
+	staged_diff_fingerprint: Option<u64>,
 	git_state: RepoState,
+	sparse_index: bool,
+	/// diffs may have to fetch objects first
+	partial_clone: bool,
 	git_status_workdir: AsyncStatus,
 	git_status_stage: AsyncStatus,
+	/// index fingerprint the last stage pass was requested for
+	index_fingerprint: u64,
+	/// bumped whenever the working dir might have changed
+	workdir_generation: u64,
+	/// generation the last working dir pass was requested for
+	workdir_requested: Option<u64>,
+	stale_workdir: StaleWorkdirPaths,
+	/// folder the status is limited to
+	scope: Option<String>,
 	git_branch_state: Option<BranchCompare>,
+	/// set if the branch pushes to another remote than its upstream
+	git_push_remotes: Option<BranchRemotes>,
+	/// compared to the remote-tracking branch of the pushes
+	git_push_state: Option<BranchCompare>,
 	git_branch_name: cached::BranchName,
 	queue: Queue,
 	git_action_executed: bool,
+	/// conflicts were seen, rerere runs until they are gone
+	rerere_active: bool,
+	/// the index has conflicts, continuing needs them resolved
+	conflicted: bool,
+	/// select the first conflicted file once the status has them
+	focus_conflict: bool,
 	options: SharedOptions,
 	key_config: SharedKeyConfig,
 }
@@ -159,12 +253,15 @@ impl Status {
 		let repo_clone = env.repo.borrow().clone();
 		Self {
 			queue: env.queue.clone(),
+			theme: env.theme.clone(),
 			visible: true,
 			remotes: RemoteStatus {
 				has_remote_for_fetch: false,
 				has_remote_for_push: false,
 			},
 			git_state: RepoState::Clean,
+			sparse_index: false,
+			partial_clone: false,
 			focus: Focus::WorkDir,
 			diff_target: DiffTarget::WorkingDir,
 			index_wd: ChangesComponent::new(
@@ -184,6 +281,11 @@ impl Status {
 				repo_clone.clone(),
 				&env.sender_git,
 			),
+			git_diff_staged: AsyncDiff::new(
+				repo_clone.clone(),
+				&env.sender_git,
+			),
+			staged_diff_fingerprint: None,
 			git_status_workdir: AsyncStatus::new(
 				repo_clone.clone(),
 				env.sender_git.clone(),
@@ -192,8 +294,18 @@ impl Status {
 				repo_clone,
 				env.sender_git.clone(),
 			),
+			index_fingerprint: 0,
+			workdir_generation: 0,
+			workdir_requested: None,
+			stale_workdir: StaleWorkdirPaths::default(),
+			scope: None,
 			git_action_executed: false,
+			rerere_active: false,
+			focus_conflict: false,
+			conflicted: false,
 			git_branch_state: None,
+			git_push_remotes: None,
+			git_push_state: None,
 			git_branch_name: cached::BranchName::new(
 				env.repo.clone(),
 			),
@@ -209,15 +321,30 @@ impl Status {
 		chunks: &[ratatui::layout::Rect],
 	) {
 		if let Some(branch_name) = self.git_branch_name.last() {
-			let ahead_behind = self
-				.git_branch_state
-				.as_ref()
-				.map_or_else(String::new, |state| {
+			let compare = |state: Option<&BranchCompare>| {
+				state.map_or_else(String::new, |state| {
+					let glyphs = self.theme.glyphs();
 					format!(
-						"\u{2191}{} \u{2193}{} ",
-						state.ahead, state.behind,
+						"{}{} {}{} ",
+						glyphs.ahead,
+						state.ahead,
+						glyphs.behind,
+						state.behind,
 					)
-				});
+				})
+			};
+
+			let mut ahead_behind =
+				compare(self.git_branch_state.as_ref());
+			// `↑1 ↓0 origin/feature ↑3 ↓10 upstream/main`
+			if let Some(remotes) = &self.git_push_remotes {
+				ahead_behind = format!(
+					"{}{} {ahead_behind}{} ",
+					compare(self.git_push_state.as_ref()),
+					remotes.push.as_deref().unwrap_or_default(),
+					remotes.upstream.as_deref().unwrap_or_default(),
+				);
+			}
 
 			let w = Paragraph::new(format!(
 				"{ahead_behind}{{{branch_name}}}"
@@ -242,7 +369,7 @@ impl Status {
 		}
 	}
 
-	fn repo_state_text(repo: &RepoPath, state: &RepoState) -> String {
+	fn repo_state_text(repo: &RepoPath, state: RepoState) -> String {
 		match state {
 			RepoState::Merge => {
 				let ids =
@@ -280,6 +407,16 @@ impl Status {
 						.unwrap_or_default(),
 				)
 			}
+			RepoState::CherryPick => {
+				format!(
+					"Cherry-pick {}",
+					sync::cherry_pick_head(repo)
+						.ok()
+						.as_ref()
+						.map(CommitId::get_short_string)
+						.unwrap_or_default(),
+				)
+			}
 			_ => format!("{state:?}"),
 		}
 	}
@@ -292,7 +429,7 @@ impl Status {
 		if self.git_state != RepoState::Clean {
 			let txt = Self::repo_state_text(
 				&self.repo.borrow(),
-				&self.git_state,
+				self.git_state,
 			);
 
 			let w = Paragraph::new(txt)
@@ -329,6 +466,17 @@ impl Status {
 		self.focus == Focus::Diff
 	}
 
+	/// the working dir tree, the one files are marked in, has the
+	/// focus
+	pub fn workdir_focused(&self) -> bool {
+		self.visible && self.focus == Focus::WorkDir
+	}
+
+	/// typing into the diff search, keys must not trigger anything else
+	pub fn diff_search_input_active(&self) -> bool {
+		self.visible && self.diff.search_input_active()
+	}
+
 	fn switch_focus(&mut self, f: Focus) -> Result<bool> {
 		if self.focus != f {
 			self.focus = f;
@@ -366,52 +514,309 @@ impl Status {
 		self.index.focus_select(is_stage);
 	}
 
+	/// paths of files in submodule sections start with the path of
+	/// their submodule
 	pub fn selected_path(&self) -> Option<(String, bool)> {
+		self.selected_file().map(|(path, is_stage, submodule)| {
+			(
+				submodule.map_or_else(
+					|| path.clone(),
+					|section| format!("{}/{path}", section.path),
+				),
+				is_stage,
+			)
+		})
+	}
+
+	/// the selected file with its path in its repo and the section of
+	/// the submodule it is in
+	fn selected_file(
+		&self,
+	) -> Option<(String, bool, Option<&SubmoduleSection>)> {
 		let (idx, is_stage) = match self.diff_target {
 			DiffTarget::Stage => (&self.index, true),
 			DiffTarget::WorkingDir => (&self.index_wd, false),
 		};
 
-		if let Some(item) = idx.selection() {
-			if let FileTreeItemKind::File(i) = item.kind {
-				return Some((i.path, is_stage));
+		match idx.selection()?.kind {
+			FileTreeItemKind::File(i) => {
+				Some((i.path, is_stage, idx.selection_submodule()))
 			}
+			FileTreeItemKind::Path(_) => None,
 		}
-		None
 	}
 
 	///
 	pub fn update(&mut self) -> Result<()> {
+		self.workdir_generation =
+			self.workdir_generation.wrapping_add(1);
+
+		self.update_git_dir()
+	}
+
+	/// like `update` but keeps the cached working dir pass, for when
+	/// only files inside the git dir changed (index, refs, ..)
+	pub fn update_git_dir(&mut self) -> Result<()> {
 		self.git_branch_name.lookup().map(Some).unwrap_or(None);
 
 		if self.is_visible() {
-			let config =
-				self.options.borrow().status_show_untracked();
-
 			self.git_diff.refresh()?;
-			self.git_status_workdir.fetch(&StatusParams::new(
-				StatusType::WorkingDir,
-				config,
-			))?;
-			self.git_status_stage.fetch(&StatusParams::new(
-				StatusType::Stage,
-				config,
-			))?;
+			self.fetch_status()?;
 
 			self.git_state = sync::repo_state(&self.repo.borrow())
 				.unwrap_or(RepoState::Clean);
 
+			let sparse_index = sync::index_info(&self.repo.borrow())
+				.ok()
+				.flatten()
+				.is_some_and(|info| info.is_sparse());
+			let partial_clone =
+				sync::promisor_remote(&self.repo.borrow())
+					.ok()
+					.flatten()
+					.is_some();
+			if sparse_index != self.sparse_index
+				|| partial_clone != self.partial_clone
+			{
+				self.sparse_index = sparse_index;
+				self.partial_clone = partial_clone;
+				self.update_titles();
+			}
+
 			self.branch_compare();
 		}
 
 		Ok(())
 	}
 
+	/// re-reads the diff if it has the focus, otherwise the status
+	/// (and the diff of the selected file with it), returns which
+	pub fn refresh(&mut self) -> Result<&'static str> {
+		if self.is_focus_on_diff() {
+			self.git_diff.refresh()?;
+			Ok("diff")
+		} else {
+			self.update()?;
+			Ok("status")
+		}
+	}
+
+	/// lighter version of `update` for when only the index changed
+	/// (e.g. after staging): the working dir pass is not invalidated
+	/// but reconciled once the new index pass arrived
+	pub fn update_index(&mut self) -> Result<()> {
+		if self.is_visible() {
+			self.git_diff.refresh()?;
+			self.fetch_status()?;
+		}
+
+		Ok(())
+	}
+
+	fn fetch_status(&mut self) -> Result<()> {
+		let config = self.options.borrow().status_show_untracked();
+
+		// if we cannot tell, assume the index changed
+		self.index_fingerprint =
+			sync::status::index_fingerprint(&self.repo.borrow())
+				.unwrap_or_else(|_| {
+					self.index_fingerprint.wrapping_add(1)
+				});
+
+		self.git_status_stage.fetch(
+			&StatusParams::new_keyed(
+				StatusType::Stage,
+				config,
+				self.index_fingerprint,
+			)
+			.scoped(self.scope.clone()),
+		)?;
+
+		self.fetch_workdir_status()
+	}
+
+	/// the working dir pass is only redone if the working dir might
+	/// have changed. index changes are covered by `reconcile_workdir`.
+	fn fetch_workdir_status(&mut self) -> Result<()> {
+		if self.git_status_workdir.is_pending()
+			|| self.workdir_requested == Some(self.workdir_generation)
+		{
+			return Ok(());
+		}
+
+		let config = self.options.borrow().status_show_untracked();
+
+		self.git_status_workdir.fetch(
+			&StatusParams::new_keyed(
+				StatusType::WorkingDir,
+				config,
+				self.workdir_generation,
+			)
+			.scoped(self.scope.clone()),
+		)?;
+
+		self.workdir_requested = Some(self.workdir_generation);
+		self.stale_workdir.pass_requested();
+
+		Ok(())
+	}
+
+	/// the working dir pass compares against the index, so when both
+	/// passes are from different moments they can disagree about files
+	/// whose index entry changed in between. those get rechecked
+	/// individually until a working dir pass started after the change
+	/// arrived. too many of them ask for that pass right away instead.
+	fn reconcile_workdir(
+		&mut self,
+		stage_items: &[StatusItem],
+		workdir_items: Vec<StatusItem>,
+	) -> Result<Vec<StatusItem>> {
+		if !self.git_status_stage.is_pending() {
+			self.stale_workdir
+				.stage_changed(self.index_fingerprint, stage_items);
+		}
+
+		if !self.git_status_workdir.is_pending() {
+			self.stale_workdir.pass_done();
+		}
+
+		if self.stale_workdir.paths.is_empty() {
+			return Ok(workdir_items);
+		}
+
+		if self.stale_workdir.overflows() {
+			if !self.git_status_workdir.is_pending() {
+				self.workdir_generation =
+					self.workdir_generation.wrapping_add(1);
+				self.fetch_workdir_status()?;
+			}
+
+			return Ok(workdir_items);
+		}
+
+		Ok(sync::status::refresh_workdir_status(
+			&self.repo.borrow(),
+			self.options.borrow().status_show_untracked(),
+			workdir_items.clone(),
+			&self.stale_workdir.paths,
+		)
+		.unwrap_or_else(|e| {
+			log::error!("refresh workdir status: {e}");
+			workdir_items
+		}))
+	}
+
+	/// folder selected in the focused list
+	fn selected_folder(&self) -> Option<String> {
+		let list = match self.focus {
+			Focus::WorkDir => &self.index_wd,
+			Focus::Stage => &self.index,
+			Focus::Diff => return None,
+		};
+
+		if list.selection_submodule().is_some() {
+			return None;
+		}
+
+		list.selection().and_then(|item| {
+			(matches!(item.kind, FileTreeItemKind::Path(_))
+				&& !item.info.is_root())
+			.then(|| item.info.path_in_root().into())
+		})
+	}
+
+	fn set_scope(&mut self, scope: Option<String>) -> Result<()> {
+		self.scope = scope;
+		self.update_titles();
+
+		self.update()
+	}
+
+	/// see [`ChangesComponent::apply_initial_collapse`]
+	pub fn apply_initial_collapse(&mut self) {
+		self.index_wd.apply_initial_collapse();
+		self.index.apply_initial_collapse();
+	}
+
+	fn update_titles(&mut self) {
+		let status_title = strings::title_scoped(
+			&strings::title_status(&self.key_config),
+			self.scope.as_deref(),
+		);
+		self.index_wd.set_title(if self.partial_clone {
+			strings::title_partial_clone(&status_title)
+		} else {
+			status_title
+		});
+
+		let index_title = strings::title_scoped(
+			&strings::title_index(&self.key_config),
+			self.scope.as_deref(),
+		);
+		self.index.set_title(if self.sparse_index {
+			strings::title_sparse_index(&index_title)
+		} else {
+			index_title
+		});
+	}
+
+	/// opens the commit popup, warns first if a scope hides
+	/// staged changes that would be committed as well
+	fn open_commit(&self) -> Result<()> {
+		if let Some(scope) = &self.scope {
+			let count = sync::status::get_status(
+				&self.repo.borrow(),
+				StatusType::Stage,
+				None,
+			)?
+			.iter()
+			.filter(|item| !Path::new(&item.path).starts_with(scope))
+			.count();
+
+			if count > 0 {
+				self.queue.push(InternalEvent::ConfirmAction(
+					Action::CommitOutsideScope {
+						scope: scope.clone(),
+						count,
+					},
+				));
+				return Ok(());
+			}
+		}
+
+		self.queue.push(InternalEvent::OpenCommit);
+
+		Ok(())
+	}
+
 	///
 	pub fn anything_pending(&self) -> bool {
 		self.git_diff.is_pending()
+			|| self.git_diff_staged.is_pending()
 			|| self.git_status_stage.is_pending()
 			|| self.git_status_workdir.is_pending()
+			|| self.index_wd.any_work_pending()
+			|| self.index.any_work_pending()
+	}
+
+	/// diff of everything staged, only recomputed once the index
+	/// changed. `None` while it is being computed.
+	pub fn staged_diff(&mut self) -> Result<Option<FileDiff>> {
+		let fingerprint =
+			sync::status::index_fingerprint(&self.repo.borrow()).ok();
+
+		if fingerprint.is_none()
+			|| fingerprint != self.staged_diff_fingerprint
+		{
+			self.git_diff_staged.refresh()?;
+			self.staged_diff_fingerprint = fingerprint;
+		}
+
+		Ok(self.git_diff_staged.request(DiffParams {
+			path: String::new(),
+			diff_type: DiffType::StageAll,
+			options: self.options.borrow().diff_options(),
+		})?)
 	}
 
 	fn check_remotes(&mut self) {
@@ -432,13 +837,22 @@ impl Status {
 		&mut self,
 		ev: AsyncGitNotification,
 	) -> Result<()> {
+		// finished staging is reported even if the tab was left
+		self.index_wd.update_git(ev);
+		self.index.update_git(ev);
+
 		if !self.is_visible() {
 			return Ok(());
 		}
 
 		match ev {
 			AsyncGitNotification::Diff => self.update_diff()?,
-			AsyncGitNotification::Status => self.update_status()?,
+			AsyncGitNotification::Status => {
+				self.update_status()?;
+				// requests dropped while a pass was running
+				// are retried now
+				self.fetch_status()?;
+			}
 			AsyncGitNotification::Branches => self.check_remotes(),
 			AsyncGitNotification::Push
 			| AsyncGitNotification::Pull
@@ -457,10 +871,58 @@ impl Status {
 
 	fn update_status(&mut self) -> Result<()> {
 		let stage_status = self.git_status_stage.last()?;
-		self.index.set_items(&stage_status.items)?;
+		let workdir_items = self.reconcile_workdir(
+			&stage_status.items,
+			self.git_status_workdir.last()?.items,
+		)?;
+
+		self.index.set_sections(
+			&stage_status.items,
+			self.submodule_sections(
+				&workdir_items,
+				StatusType::Stage,
+			),
+		)?;
+		self.index_wd.set_sections(
+			&workdir_items,
+			self.submodule_sections(
+				&workdir_items,
+				StatusType::WorkingDir,
+			),
+		)?;
 
-		let workdir_status = self.git_status_workdir.last()?;
-		self.index_wd.set_items(&workdir_status.items)?;
+		let conflicts =
+			stage_status.items.iter().chain(&workdir_items).any(
+				|item| item.status == StatusItemType::Conflicted,
+			);
+		// one more run once they are gone records the staged
+		// resolutions
+		if conflicts || self.rerere_active {
+			self.rerere_active = conflicts;
+			self.rerere();
+		}
+
+		if self.conflicted
+			&& !conflicts
+			&& self.git_state != RepoState::Clean
+		{
+			self.queue.push(InternalEvent::ShowToast(
+				strings::conflicts_resolved(),
+			));
+		}
+		self.conflicted = conflicts;
+
+		if conflicts && self.focus_conflict {
+			self.focus_conflict = false;
+			self.select_first_conflict()?;
+		} else if self.git_state == RepoState::Clean {
+			self.focus_conflict = false;
+		}
+
+		// have it ready once the commit popup opens
+		if self.options.borrow().is_commit_verbose() {
+			self.staged_diff()?;
+		}
 
 		self.update_diff()?;
 
@@ -468,7 +930,7 @@ impl Status {
 			self.git_action_executed = false;
 
 			if self.focus == Focus::WorkDir
-				&& workdir_status.items.is_empty()
+				&& workdir_items.is_empty()
 				&& !stage_status.items.is_empty()
 			{
 				self.switch_focus(Focus::Stage)?;
@@ -482,8 +944,98 @@ impl Status {
 		Ok(())
 	}
 
+	/// a section per submodule with changes of `status_type` inside of
+	/// it, none unless enabled
+	fn submodule_sections(
+		&self,
+		workdir_items: &[StatusItem],
+		status_type: StatusType,
+	) -> Vec<SubmoduleSection> {
+		if !self.options.borrow().status_submodule_sections() {
+			return Vec::new();
+		}
+
+		let Ok(work_dir) =
+			sync::utils::repo_work_dir(&self.repo.borrow())
+		else {
+			return Vec::new();
+		};
+		let config = self.options.borrow().status_show_untracked();
+
+		workdir_items
+			.iter()
+			.filter(|item| {
+				item.submodule.is_some_and(|changes| {
+					changes.modified_content
+						|| changes.untracked_content
+				})
+			})
+			.filter_map(|item| {
+				let repo = RepoPath::Path(
+					Path::new(&work_dir).join(&item.path),
+				);
+
+				match sync::status::get_status(
+					&repo,
+					status_type,
+					config,
+				) {
+					Ok(items) if !items.is_empty() => {
+						Some(SubmoduleSection {
+							path: item.path.clone(),
+							repo,
+							items,
+						})
+					}
+					Ok(_) => None,
+					Err(e) => {
+						log::error!("submodule status: {e}");
+						None
+					}
+				}
+			})
+			.collect()
+	}
+
+	/// the next status with conflicts selects the first of them
+	pub fn focus_first_conflict(&mut self) {
+		self.focus_conflict = true;
+	}
+
+	fn select_first_conflict(&mut self) -> Result<()> {
+		if self.index_wd.select_first_conflict() {
+			self.switch_focus(Focus::WorkDir)?;
+		} else if self.index.select_first_conflict() {
+			self.switch_focus(Focus::Stage)?;
+		}
+
+		Ok(())
+	}
+
+	fn rerere(&self) {
+		match sync::rerere(&self.repo.borrow()) {
+			Ok(res) if !res.resolved.is_empty() => {
+				self.queue.push(InternalEvent::ShowInfoMsg(
+					strings::rerere_resolved(&res.resolved),
+				));
+				self.queue
+					.push(InternalEvent::Update(NeedsUpdate::ALL));
+			}
+			Ok(_) => (),
+			Err(e) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("rerere error:\n{e}"),
+				));
+			}
+		}
+	}
+
 	///
 	pub fn update_diff(&mut self) -> Result<()> {
+		if self.update_submodule_diff()? {
+			return Ok(());
+		}
+
 		if let Some((path, is_stage)) = self.selected_path() {
 			let diff_type = if is_stage {
 				DiffType::Stage
@@ -524,6 +1076,31 @@ impl Status {
 		Ok(())
 	}
 
+	/// files of submodule sections get their diff right away from the
+	/// submodule, it can not be changed from there, `false` for other
+	/// selections
+	fn update_submodule_diff(&mut self) -> Result<bool> {
+		let Some((path, is_stage, Some(submodule))) =
+			self.selected_file()
+		else {
+			self.diff.set_immutable(false);
+			return Ok(false);
+		};
+
+		let diff = sync::diff::get_diff(
+			&submodule.repo,
+			&path,
+			is_stage,
+			Some(self.options.borrow().diff_options()),
+		)?;
+		let path = format!("{}/{path}", submodule.path);
+
+		self.diff.set_immutable(true);
+		self.diff.update(path, is_stage, diff);
+
+		Ok(true)
+	}
+
 	fn request_diff(
 		&mut self,
 		diff_params: DiffParams,
@@ -541,8 +1118,9 @@ impl Status {
 
 	/// called after confirmation
 	pub fn reset(&self, item: &ResetItem) -> bool {
+		let repo = self.repo.borrow();
 		if let Err(e) = sync::reset_workdir(
-			&self.repo.borrow(),
+			item.submodule.as_ref().unwrap_or(&repo),
 			item.path.as_str(),
 		) {
 			self.queue.push(InternalEvent::ShowErrorMsg(format!(
@@ -555,6 +1133,29 @@ impl Status {
 		}
 	}
 
+	/// called after confirmation, failing files do not stop the
+	/// others from being reset
+	pub fn reset_folder(&self, item: &ResetFolderItem) {
+		let repo = self.repo.borrow();
+		let repo = item.submodule.as_ref().unwrap_or(&repo);
+		let failed = item
+			.modified
+			.iter()
+			.chain(&item.untracked)
+			.filter_map(|path| {
+				sync::reset_workdir(repo, path)
+					.err()
+					.map(|e| format!("{path}: {e}"))
+			})
+			.collect::<Vec<_>>();
+
+		if !failed.is_empty() {
+			self.queue.push(InternalEvent::ShowErrorMsg(
+				strings::folder_action_failed("reset", &failed, &[]),
+			));
+		}
+	}
+
 	pub fn last_file_moved(&mut self) -> Result<()> {
 		if !self.is_focus_on_diff() && self.is_visible() {
 			self.switch_focus(self.focus.toggled_focus())?;
@@ -567,13 +1168,13 @@ impl Status {
 			if let Some(branch) = self.git_branch_name.last() {
 				if force {
 					self.queue.push(InternalEvent::ConfirmAction(
-						Action::ForcePush(branch, force),
+						Action::ForcePush(branch, PushForce::Lease),
 					));
 				} else {
 					self.queue.push(InternalEvent::Push(
 						branch,
 						PushType::Branch,
-						force,
+						PushForce::FastForward,
 						false,
 					));
 				}
@@ -593,9 +1194,16 @@ impl Status {
 		}
 	}
 
-	fn undo_last_commit(&self) {
-		self.queue
-			.push(InternalEvent::ConfirmAction(Action::UndoCommit));
+	fn undo_last_commit(&self) -> Result<()> {
+		let repo = self.repo.borrow();
+		let head = sync::undo_last_commit_check(&repo)?;
+		let pushed = sync::is_commit_pushed(&repo, head)?;
+
+		self.queue.push(InternalEvent::ConfirmAction(
+			Action::UndoCommit { pushed },
+		));
+
+		Ok(())
 	}
 
 	fn branch_compare(&mut self) {
@@ -607,13 +1215,35 @@ impl Status {
 				)
 				.ok()
 			});
+
+		self.git_push_remotes =
+			self.git_branch_name.last().and_then(|branch| {
+				sync::get_branch_remotes(&self.repo.borrow(), &branch)
+					.ok()
+					.filter(BranchRemotes::is_triangular)
+			});
+		self.git_push_state = self
+			.git_branch_name
+			.last()
+			.filter(|_| self.git_push_remotes.is_some())
+			.and_then(|branch| {
+				sync::branch_compare_push(
+					&self.repo.borrow(),
+					&branch,
+				)
+				.ok()
+				.flatten()
+			});
 	}
 
 	fn can_push(&self) -> bool {
-		let is_ahead = self
-			.git_branch_state
-			.as_ref()
-			.map_or(true, |state| state.ahead > 0);
+		// a triangular branch is ahead of where it pushes to
+		let state = if self.git_push_remotes.is_some() {
+			self.git_push_state.as_ref()
+		} else {
+			self.git_branch_state.as_ref()
+		};
+		let is_ahead = state.map_or(true, |state| state.ahead > 0);
 
 		is_ahead && self.remotes.has_remote_for_push
 	}
@@ -635,6 +1265,10 @@ impl Status {
 		self.git_state == RepoState::Revert
 	}
 
+	fn pending_cherry_pick(&self) -> bool {
+		self.git_state == RepoState::CherryPick
+	}
+
 	pub fn revert_pending_state(&self) {
 		try_or_popup!(
 			self,
@@ -643,22 +1277,36 @@ impl Status {
 		);
 	}
 
-	pub fn abort_rebase(&self) {
+	pub fn abort_cherry_pick(&self) {
 		try_or_popup!(
 			self,
-			"abort rebase",
-			sync::abort_pending_rebase(&self.repo.borrow())
+			"abort cherry-pick",
+			sync::abort_pending_cherry_pick(&self.repo.borrow())
 		);
 	}
 
-	fn continue_rebase(&self) {
+	pub fn abort_rebase(&self) {
 		try_or_popup!(
 			self,
-			"continue rebase",
-			sync::continue_pending_rebase(&self.repo.borrow())
+			"abort rebase",
+			sync::abort_pending_rebase(&self.repo.borrow())
 		);
 	}
 
+	/// commits the resolved conflicts of a merge, revert, cherry-pick
+	/// or rebase step and applies the steps left
+	fn continue_pending(&self) -> Result<()> {
+		if sync::continue_pending_state(&self.repo.borrow())?
+			== RebaseState::Conflicted
+		{
+			self.queue.push(InternalEvent::ShowInfoMsg(
+				strings::continue_conflicted(),
+			));
+		}
+
+		Ok(())
+	}
+
 	fn commands_nav(
 		&self,
 		out: &mut Vec<CommandInfo>,
@@ -713,6 +1361,7 @@ impl Status {
 }
 
 impl Component for Status {
+	#[allow(clippy::too_many_lines)]
 	fn commands(
 		&self,
 		out: &mut Vec<CommandInfo>,
@@ -736,6 +1385,17 @@ impl Component for Status {
 				.order(-1),
 			);
 
+			out.push(CommandInfo::new(
+				strings::commands::scope_folder(&self.key_config),
+				self.selected_folder().is_some(),
+				!focus_on_diff || force_all,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::clear_scope(&self.key_config),
+				true,
+				self.scope.is_some() || force_all,
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::open_branch_select_popup(
 					&self.key_config,
@@ -756,6 +1416,13 @@ impl Component for Status {
 				true,
 				self.can_push() && !focus_on_diff,
 			));
+			out.push(CommandInfo::new(
+				strings::commands::open_pull_request(
+					&self.key_config,
+				),
+				self.remotes.has_remote_for_push,
+				!focus_on_diff,
+			));
 
 			out.push(CommandInfo::new(
 				strings::commands::status_fetch(&self.key_config),
@@ -781,9 +1448,15 @@ impl Component for Status {
 				self.can_abort_merge() || force_all,
 			));
 
+			out.push(CommandInfo::new(
+				strings::commands::continue_merge(&self.key_config),
+				!self.conflicted,
+				self.can_abort_merge() || force_all,
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::continue_rebase(&self.key_config),
-				true,
+				!self.conflicted,
 				self.pending_rebase() || force_all,
 			));
 
@@ -793,17 +1466,45 @@ impl Component for Status {
 				self.pending_rebase() || force_all,
 			));
 
+			out.push(CommandInfo::new(
+				strings::commands::continue_revert(&self.key_config),
+				!self.conflicted,
+				self.pending_revert() || force_all,
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::abort_revert(&self.key_config),
 				true,
 				self.pending_revert() || force_all,
 			));
 
+			out.push(CommandInfo::new(
+				strings::commands::continue_cherry_pick(
+					&self.key_config,
+				),
+				!self.conflicted,
+				self.pending_cherry_pick() || force_all,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::abort_cherry_pick(
+					&self.key_config,
+				),
+				true,
+				self.pending_cherry_pick() || force_all,
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::view_submodules(&self.key_config),
 				true,
 				true,
 			));
+
+			out.push(CommandInfo::new(
+				strings::commands::view_worktrees(&self.key_config),
+				true,
+				true,
+			));
 		}
 
 		self.commands_nav(out, force_all);
@@ -830,7 +1531,23 @@ impl Component for Status {
 					self.key_config.keys.open_commit,
 				) && self.can_commit()
 				{
-					self.queue.push(InternalEvent::OpenCommit);
+					self.open_commit()?;
+					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.status_scope_folder,
+				) && !self.is_focus_on_diff()
+				{
+					if let Some(folder) = self.selected_folder() {
+						self.set_scope(Some(folder))?;
+					}
+					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.status_clear_scope,
+				) && self.scope.is_some()
+				{
+					self.set_scope(None)?;
 					Ok(EventState::Consumed)
 				} else if key_match(
 					k,
@@ -884,6 +1601,14 @@ impl Component for Status {
 				{
 					self.push(false);
 					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.open_pull_request,
+				) && !self.is_focus_on_diff()
+					&& self.remotes.has_remote_for_push
+				{
+					self.queue.push(InternalEvent::OpenPullRequest);
+					Ok(EventState::Consumed)
 				} else if key_match(k, self.key_config.keys.fetch)
 					&& !self.is_focus_on_diff()
 					&& self.can_fetch()
@@ -901,10 +1626,11 @@ impl Component for Status {
 					self.key_config.keys.undo_commit,
 				) && !self.is_focus_on_diff()
 				{
-					self.undo_last_commit();
-					self.queue.push(InternalEvent::Update(
-						NeedsUpdate::ALL,
-					));
+					try_or_popup!(
+						self,
+						"undo commit failed:",
+						self.undo_last_commit()
+					);
 					Ok(EventState::Consumed)
 				} else if key_match(
 					k,
@@ -928,15 +1654,27 @@ impl Component for Status {
 								Action::AbortRevert,
 							),
 						);
+					} else if self.pending_cherry_pick() {
+						self.queue.push(
+							InternalEvent::ConfirmAction(
+								Action::AbortCherryPick,
+							),
+						);
 					}
 
 					Ok(EventState::Consumed)
 				} else if key_match(
 					k,
 					self.key_config.keys.rebase_branch,
-				) && self.pending_rebase()
-				{
-					self.continue_rebase();
+				) && !matches!(
+					self.git_state,
+					RepoState::Clean | RepoState::Other
+				) {
+					try_or_popup!(
+						self,
+						"continue failed:",
+						self.continue_pending()
+					);
 					self.queue.push(InternalEvent::Update(
 						NeedsUpdate::ALL,
 					));
@@ -947,6 +1685,12 @@ impl Component for Status {
 				) {
 					self.queue.push(InternalEvent::ViewSubmodules);
 					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.view_worktrees,
+				) {
+					self.queue.push(InternalEvent::ViewWorktrees);
+					Ok(EventState::Consumed)
 				} else {
 					Ok(EventState::NotConsumed)
 				};
@@ -978,3 +1722,98 @@ impl Component for Status {
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use asyncgit::StatusItemType;
+
+	fn items(paths: &[&str]) -> Vec<StatusItem> {
+		paths
+			.iter()
+			.map(|path| StatusItem {
+				path: (*path).to_string(),
+				status: StatusItemType::Modified,
+				conflict: None,
+				auto_resolved: false,
+				submodule: None,
+				lfs: false,
+				mode: None,
+				symlink_target: None,
+			})
+			.collect()
+	}
+
+	fn stale(stale: &StaleWorkdirPaths) -> Vec<&str> {
+		stale.paths.iter().map(String::as_str).collect()
+	}
+
+	#[test]
+	fn test_stale_workdir_paths() {
+		let mut stale = StaleWorkdirPaths::default();
+
+		stale.pass_requested();
+		stale.stage_changed(1, &items(&["a"]));
+		stale.pass_done();
+		// staged while the pass was running, so it might be outdated
+		assert_eq!(self::stale(&stale), vec!["a"]);
+
+		stale.pass_requested();
+		stale.pass_done();
+		assert!(stale.paths.is_empty());
+
+		// same index, nothing new
+		stale.stage_changed(1, &items(&["a"]));
+		assert!(stale.paths.is_empty());
+
+		// staging more of `a` keeps the list but changes the index
+		stale.stage_changed(2, &items(&["a"]));
+		assert_eq!(self::stale(&stale), vec!["a"]);
+
+		// unstaging `a` and staging `b` touches both
+		stale.stage_changed(3, &items(&["b"]));
+		assert_eq!(self::stale(&stale), vec!["a", "b"]);
+
+		stale.pass_requested();
+		stale.stage_changed(4, &items(&["b", "c"]));
+		stale.pass_done();
+		// what changed after the pass started stays
+		assert_eq!(self::stale(&stale), vec!["b", "c"]);
+
+		stale.pass_requested();
+		stale.pass_done();
+		assert!(stale.paths.is_empty());
+	}
+
+	#[test]
+	fn test_stale_workdir_paths_drain() {
+		let mut stale = StaleWorkdirPaths::default();
+
+		let many = (0..1000)
+			.map(|idx| format!("file{idx}"))
+			.collect::<Vec<_>>();
+		let many = items(
+			&many.iter().map(String::as_str).collect::<Vec<_>>(),
+		);
+
+		stale.stage_changed(1, &many);
+		assert!(stale.overflows());
+
+		// the pass requested for the overflow covers all of them
+		stale.pass_requested();
+		stale.stage_changed(2, &many[..10]);
+		stale.pass_done();
+		// unstaging the rest touched those again
+		assert!(stale.overflows());
+
+		stale.pass_requested();
+		stale.pass_done();
+		assert!(stale.paths.is_empty());
+		assert!(!stale.overflows());
+
+		// the ten staged before and now are rechecked one by one
+		stale.stage_changed(3, &many[..2]);
+		assert_eq!(stale.paths.len(), 10);
+		assert!(!stale.overflows());
+	}
+}
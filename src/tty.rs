@@ -0,0 +1,39 @@
+//! where gitui draws: stdout, unless `--print-on-exit` keeps stdout
+//! free for the printed value and the ui goes to stderr instead
+
+use std::{
+	io::{self, BufWriter, Write},
+	process::Stdio,
+	sync::atomic::{AtomicBool, Ordering},
+};
+
+static DRAW_TO_STDERR: AtomicBool = AtomicBool::new(false);
+
+/// has to be called before the terminal is set up
+pub fn draw_to_stderr() {
+	DRAW_TO_STDERR.store(true, Ordering::Relaxed);
+}
+
+///
+pub fn is_stderr() -> bool {
+	DRAW_TO_STDERR.load(Ordering::Relaxed)
+}
+
+/// the terminal the ui, escape sequences included, is written to
+pub fn output() -> Box<dyn Write> {
+	if is_stderr() {
+		// stderr is unbuffered, the terminal flushes after each draw
+		Box::new(BufWriter::new(io::stderr()))
+	} else {
+		Box::new(io::stdout())
+	}
+}
+
+/// stdout for programs taking over the terminal, like the editor
+pub fn child_stdout() -> Stdio {
+	if is_stderr() {
+		io::stderr().into()
+	} else {
+		Stdio::inherit()
+	}
+}
@@ -0,0 +1,270 @@
+use crate::string_utils::tabs_to_spaces;
+use ratatui::{
+	style::{Color, Modifier, Style},
+	text::{Line, Span},
+};
+use std::iter::Peekable;
+
+/// the 16 basic ansi colors (30-37 and 90-97)
+const COLORS: [Color; 16] = [
+	Color::Black,
+	Color::Red,
+	Color::Green,
+	Color::Yellow,
+	Color::Blue,
+	Color::Magenta,
+	Color::Cyan,
+	Color::Gray,
+	Color::DarkGray,
+	Color::LightRed,
+	Color::LightGreen,
+	Color::LightYellow,
+	Color::LightBlue,
+	Color::LightMagenta,
+	Color::LightCyan,
+	Color::White,
+];
+
+/// turns the ansi colors and attributes (sgr escapes) of process
+/// output into styled spans, other escapes are dropped.
+/// like in a terminal the style carries over to the next line
+#[derive(Default, Clone)]
+pub struct AnsiParser {
+	style: Style,
+}
+
+impl AnsiParser {
+	/// `base` styles text that has no escapes applied
+	pub fn parse_line(
+		&mut self,
+		line: &str,
+		base: Style,
+	) -> Line<'static> {
+		let line = tabs_to_spaces(line.to_string());
+
+		let mut spans = Vec::new();
+		let mut text = String::new();
+		let mut chars = line.chars().peekable();
+
+		while let Some(c) = chars.next() {
+			match c {
+				'\x1b' => {
+					if let Some(params) = skip_escape(&mut chars) {
+						if !text.is_empty() {
+							spans.push(Span::styled(
+								std::mem::take(&mut text),
+								base.patch(self.style),
+							));
+						}
+
+						self.apply_sgr(&params);
+					}
+				}
+				c if c.is_control() => (),
+				c => text.push(c),
+			}
+		}
+
+		if !text.is_empty() {
+			spans.push(Span::styled(text, base.patch(self.style)));
+		}
+
+		Line::from(spans)
+	}
+
+	fn apply_sgr(&mut self, params: &str) {
+		let mut codes = params
+			.split(';')
+			.map(|code| code.parse::<u8>().unwrap_or_default());
+
+		while let Some(code) = codes.next() {
+			let modifiers = &mut self.style.add_modifier;
+
+			match code {
+				0 => self.style = Style::default(),
+				1 => modifiers.insert(Modifier::BOLD),
+				2 => modifiers.insert(Modifier::DIM),
+				3 => modifiers.insert(Modifier::ITALIC),
+				4 => modifiers.insert(Modifier::UNDERLINED),
+				5 => modifiers.insert(Modifier::SLOW_BLINK),
+				7 => modifiers.insert(Modifier::REVERSED),
+				9 => modifiers.insert(Modifier::CROSSED_OUT),
+				21 | 22 => {
+					modifiers.remove(Modifier::BOLD | Modifier::DIM);
+				}
+				23 => modifiers.remove(Modifier::ITALIC),
+				24 => modifiers.remove(Modifier::UNDERLINED),
+				25 => modifiers.remove(Modifier::SLOW_BLINK),
+				27 => modifiers.remove(Modifier::REVERSED),
+				29 => modifiers.remove(Modifier::CROSSED_OUT),
+				30..=37 => {
+					self.style.fg =
+						Some(COLORS[usize::from(code - 30)]);
+				}
+				90..=97 => {
+					self.style.fg =
+						Some(COLORS[usize::from(code - 90 + 8)]);
+				}
+				40..=47 => {
+					self.style.bg =
+						Some(COLORS[usize::from(code - 40)]);
+				}
+				100..=107 => {
+					self.style.bg =
+						Some(COLORS[usize::from(code - 100 + 8)]);
+				}
+				38 => self.style.fg = extended_color(&mut codes),
+				48 => self.style.bg = extended_color(&mut codes),
+				39 => self.style.fg = None,
+				49 => self.style.bg = None,
+				_ => (),
+			}
+		}
+	}
+}
+
+/// skips the escape sequence after `\x1b`, returns the parameters if
+/// it is a sgr sequence (`\x1b[...m`)
+fn skip_escape(
+	chars: &mut Peekable<impl Iterator<Item = char>>,
+) -> Option<String> {
+	match chars.next()? {
+		// csi: parameters followed by a final byte
+		'[' => {
+			let mut params = String::new();
+
+			for c in chars.by_ref() {
+				if ('\x40'..='\x7e').contains(&c) {
+					return (c == 'm').then_some(params);
+				}
+				params.push(c);
+			}
+
+			None
+		}
+		// osc (e.g. hyperlinks or titles): ends with bel or `\x1b\`
+		']' => {
+			while let Some(c) = chars.next() {
+				if c == '\x07' {
+					break;
+				}
+				if c == '\x1b' {
+					chars.next_if_eq(&'\\');
+					break;
+				}
+			}
+
+			None
+		}
+		_ => None,
+	}
+}
+
+/// `5;n` (256 colors) or `2;r;g;b` after a 38/48
+fn extended_color(
+	codes: &mut impl Iterator<Item = u8>,
+) -> Option<Color> {
+	match codes.next()? {
+		5 => codes.next().map(Color::Indexed),
+		2 => Some(Color::Rgb(
+			codes.next()?,
+			codes.next()?,
+			codes.next()?,
+		)),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	fn spans(line: &Line) -> Vec<(String, Style)> {
+		line.spans
+			.iter()
+			.map(|span| (span.content.to_string(), span.style))
+			.collect()
+	}
+
+	#[test]
+	fn test_colors() {
+		let mut parser = AnsiParser::default();
+		let base = Style::default().fg(Color::White);
+
+		let line = parser.parse_line(
+			"a \x1b[1;31merror\x1b[0m: \x1b[38;5;208mx\x1b[48;2;1;2;3my\x1b[39mz",
+			base,
+		);
+
+		assert_eq!(
+			spans(&line),
+			vec![
+				(String::from("a "), base),
+				(
+					String::from("error"),
+					Style::default()
+						.fg(Color::Red)
+						.add_modifier(Modifier::BOLD)
+				),
+				(String::from(": "), base),
+				(String::from("x"), base.fg(Color::Indexed(208))),
+				(
+					String::from("y"),
+					base.fg(Color::Indexed(208))
+						.bg(Color::Rgb(1, 2, 3))
+				),
+				(String::from("z"), base.bg(Color::Rgb(1, 2, 3))),
+			]
+		);
+	}
+
+	#[test]
+	fn test_style_carries_over() {
+		let mut parser = AnsiParser::default();
+		let base = Style::default();
+
+		parser.parse_line("\x1b[32mstart", base);
+		let line = parser.parse_line("next\x1b[m end", base);
+
+		assert_eq!(
+			spans(&line),
+			vec![
+				(String::from("next"), base.fg(Color::Green)),
+				(String::from(" end"), base),
+			]
+		);
+	}
+
+	#[test]
+	fn test_other_escapes_dropped() {
+		let mut parser = AnsiParser::default();
+		let base = Style::default();
+
+		let line = parser.parse_line(
+			"\x1b[2K\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x07\tdone\x1b[",
+			base,
+		);
+
+		assert_eq!(line.to_string(), "link  done");
+		assert_eq!(line.spans.len(), 1);
+	}
+
+	#[test]
+	fn test_long_line() {
+		let mut parser = AnsiParser::default();
+		let base = Style::default();
+
+		let line = parser.parse_line(
+			&"\x1b[31mred\x1b[34mblue".repeat(50_000),
+			base,
+		);
+
+		assert_eq!(line.spans.len(), 100_000);
+		assert_eq!(line.width(), 350_000);
+		assert_eq!(
+			line.spans.last().map(|span| span.style),
+			Some(base.fg(Color::Blue))
+		);
+	}
+}
@@ -0,0 +1,166 @@
+use std::env;
+
+/// which characters are used to draw trees, markers, scrollbars,
+/// spinners and key hints
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GlyphSet {
+	#[default]
+	Unicode,
+	/// fallback for terminals/fonts lacking the unicode glyphs
+	Ascii,
+}
+
+impl GlyphSet {
+	/// picks `Ascii` if the locale is set to something that is not utf-8
+	pub fn detect() -> Self {
+		let locale =
+			["LC_ALL", "LC_CTYPE", "LANG"].iter().find_map(|var| {
+				env::var(var).ok().filter(|v| !v.is_empty())
+			});
+
+		Self::from_locale(locale.as_deref())
+	}
+
+	fn from_locale(locale: Option<&str>) -> Self {
+		locale.map_or(Self::Unicode, |locale| {
+			let locale = locale.to_lowercase();
+			if locale.contains("utf-8") || locale.contains("utf8") {
+				Self::Unicode
+			} else {
+				Self::Ascii
+			}
+		})
+	}
+
+	pub const fn glyphs(self) -> Glyphs {
+		match self {
+			Self::Unicode => Glyphs {
+				folder_collapsed: "\u{25b8}",     //▸
+				folder_expanded: "\u{25be}",      //▾
+				checkmark: "\u{2713}",            //✓
+				ellipsis: '\u{2026}',             //…
+				ahead: "\u{2191}",                //↑
+				behind: "\u{2193}",               //↓
+				scrollbar_vertical: "\u{2551}",   //║
+				scrollbar_horizontal: "\u{2550}", //═
+				scrollbar_pos: "\u{2588}",        //█
+				hunk_start: "\u{250c}",           //┌
+				hunk_line: "\u{2502}",            //│
+				hunk_end: "\u{2514}",             //└
+				line_break: "\u{b6}",             //¶
+				bookmark: "\u{2691}",             //⚑
+				signed: "\u{26bf}",               //⚿
+				note: "\u{270e}",                 //✎
+				gone: "\u{2717}",                 //✗
+				spinner: &['⣷', '⣯', '⣟', '⡿', '⢿', '⣻', '⣽', '⣾'],
+			},
+			Self::Ascii => Glyphs {
+				folder_collapsed: "+",
+				folder_expanded: "-",
+				checkmark: "*",
+				ellipsis: '~',
+				ahead: "^",
+				behind: "v",
+				scrollbar_vertical: "|",
+				scrollbar_horizontal: "-",
+				scrollbar_pos: "#",
+				hunk_start: "+",
+				hunk_line: "|",
+				hunk_end: "+",
+				line_break: "$",
+				bookmark: "@",
+				signed: "S",
+				note: "N",
+				gone: "x",
+				spinner: &['|', '/', '-', '\\'],
+			},
+		}
+	}
+}
+
+/// every glyph is exactly one column wide
+#[derive(Debug, Clone, Copy)]
+pub struct Glyphs {
+	pub folder_collapsed: &'static str,
+	pub folder_expanded: &'static str,
+	pub checkmark: &'static str,
+	pub ellipsis: char,
+	/// commits/tags present locally but not on the remote
+	pub ahead: &'static str,
+	/// commits present on the remote but not locally
+	pub behind: &'static str,
+	pub scrollbar_vertical: &'static str,
+	pub scrollbar_horizontal: &'static str,
+	pub scrollbar_pos: &'static str,
+	pub hunk_start: &'static str,
+	pub hunk_line: &'static str,
+	pub hunk_end: &'static str,
+	/// default for the theme's `line_break`
+	pub line_break: &'static str,
+	/// marks bookmarked commits
+	pub bookmark: &'static str,
+	/// signed commits in the log
+	pub signed: &'static str,
+	/// commits with a note in the log
+	pub note: &'static str,
+	/// local branches whose upstream is gone
+	pub gone: &'static str,
+	pub spinner: &'static [char],
+}
+
+impl Default for Glyphs {
+	fn default() -> Self {
+		GlyphSet::default().glyphs()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use unicode_width::UnicodeWidthStr;
+
+	#[test]
+	fn test_from_locale() {
+		assert_eq!(GlyphSet::from_locale(None), GlyphSet::Unicode);
+		assert_eq!(
+			GlyphSet::from_locale(Some("en_US.UTF-8")),
+			GlyphSet::Unicode
+		);
+		assert_eq!(
+			GlyphSet::from_locale(Some("de_DE.utf8")),
+			GlyphSet::Unicode
+		);
+		assert_eq!(GlyphSet::from_locale(Some("C")), GlyphSet::Ascii);
+		assert_eq!(
+			GlyphSet::from_locale(Some("en_US.ISO-8859-1")),
+			GlyphSet::Ascii
+		);
+	}
+
+	#[test]
+	fn test_single_column() {
+		for set in [GlyphSet::Unicode, GlyphSet::Ascii] {
+			let g = set.glyphs();
+			for s in [
+				g.folder_collapsed,
+				g.folder_expanded,
+				g.checkmark,
+				g.ahead,
+				g.behind,
+				g.scrollbar_vertical,
+				g.scrollbar_horizontal,
+				g.scrollbar_pos,
+				g.hunk_start,
+				g.hunk_line,
+				g.hunk_end,
+				g.line_break,
+				g.bookmark,
+				g.signed,
+				g.note,
+				g.gone,
+			] {
+				assert_eq!(s.width(), 1, "{s}");
+			}
+		}
+	}
+}
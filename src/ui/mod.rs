@@ -1,3 +1,5 @@
+mod ansi;
+mod glyphs;
 mod reflow;
 mod scrollbar;
 mod scrolllist;
@@ -5,7 +7,9 @@ mod stateful_paragraph;
 pub mod style;
 mod syntax_text;
 
+pub use ansi::AnsiParser;
 use filetreelist::MoveSelection;
+pub use glyphs::{GlyphSet, Glyphs};
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 pub use scrollbar::{draw_scrollbar, Orientation};
 pub use scrolllist::{draw_list, draw_list_block};
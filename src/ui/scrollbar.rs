@@ -1,13 +1,9 @@
-use super::style::SharedTheme;
+use super::{style::SharedTheme, Glyphs};
 use easy_cast::CastFloat;
 use ratatui::{
 	buffer::Buffer,
 	layout::{Margin, Rect},
 	style::Style,
-	symbols::{
-		block::FULL,
-		line::{DOUBLE_HORIZONTAL, DOUBLE_VERTICAL},
-	},
 	widgets::Widget,
 	Frame,
 };
@@ -24,16 +20,23 @@ struct Scrollbar {
 	style_bar: Style,
 	style_pos: Style,
 	orientation: Orientation,
+	glyphs: Glyphs,
 }
 
 impl Scrollbar {
-	fn new(max: usize, pos: usize, orientation: Orientation) -> Self {
+	fn new(
+		max: usize,
+		pos: usize,
+		orientation: Orientation,
+		glyphs: &Glyphs,
+	) -> Self {
 		Self {
 			max: u16::try_from(max).unwrap_or_default(),
 			pos: u16::try_from(pos).unwrap_or_default(),
 			style_pos: Style::default(),
 			style_bar: Style::default(),
 			orientation,
+			glyphs: *glyphs,
 		}
 	}
 
@@ -61,7 +64,12 @@ impl Scrollbar {
 		};
 
 		for y in bar_top..(bar_top + bar_height) {
-			buf.set_string(right, y, DOUBLE_VERTICAL, self.style_bar);
+			buf.set_string(
+				right,
+				y,
+				self.glyphs.scrollbar_vertical,
+				self.style_bar,
+			);
 		}
 
 		let progress = f32::from(self.pos) / f32::from(self.max);
@@ -71,7 +79,12 @@ impl Scrollbar {
 		let pos: u16 = pos.cast_nearest();
 		let pos = pos.saturating_sub(1);
 
-		buf.set_string(right, bar_top + pos, FULL, self.style_pos);
+		buf.set_string(
+			right,
+			bar_top + pos,
+			self.glyphs.scrollbar_pos,
+			self.style_pos,
+		);
 	}
 
 	fn render_horizontal(self, area: Rect, buf: &mut Buffer) {
@@ -101,7 +114,7 @@ impl Scrollbar {
 			buf.set_string(
 				x,
 				bottom,
-				DOUBLE_HORIZONTAL,
+				self.glyphs.scrollbar_horizontal,
 				self.style_bar,
 			);
 		}
@@ -113,7 +126,12 @@ impl Scrollbar {
 		let pos: u16 = pos.cast_nearest();
 		let pos = pos.saturating_sub(1);
 
-		buf.set_string(bar_left + pos, bottom, FULL, self.style_pos);
+		buf.set_string(
+			bar_left + pos,
+			bottom,
+			self.glyphs.scrollbar_pos,
+			self.style_pos,
+		);
 	}
 }
 
@@ -136,7 +154,8 @@ pub fn draw_scrollbar(
 	pos: usize,
 	orientation: Orientation,
 ) {
-	let mut widget = Scrollbar::new(max, pos, orientation);
+	let mut widget =
+		Scrollbar::new(max, pos, orientation, theme.glyphs());
 	widget.style_pos = theme.scroll_bar_pos();
 	f.render_widget(widget, r);
 }
@@ -1,4 +1,6 @@
-use crate::ui::syntax_text::DEFAULT_SYNTAX_THEME;
+use crate::ui::{
+	syntax_text::DEFAULT_SYNTAX_THEME, GlyphSet, Glyphs,
+};
 use anyhow::Result;
 use asyncgit::{DiffLineType, StatusItemType};
 use ratatui::style::{Color, Modifier, Style};
@@ -22,10 +24,14 @@ pub struct Theme {
 	disabled_fg: Color,
 	diff_line_add: Color,
 	diff_line_delete: Color,
+	diff_word_add: Color,
+	diff_word_delete: Color,
+	diff_search_match: Color,
 	diff_file_added: Color,
 	diff_file_removed: Color,
 	diff_file_moved: Color,
 	diff_file_modified: Color,
+	diff_file_conflicted: Color,
 	commit_hash: Color,
 	commit_time: Color,
 	commit_author: Color,
@@ -37,6 +43,10 @@ pub struct Theme {
 	line_break: String,
 	block_title_focused: Color,
 	syntax: String,
+	syntax_highlighting: bool,
+	#[serde(skip)]
+	#[patch(skip)]
+	glyphs: Glyphs,
 }
 
 impl Theme {
@@ -124,7 +134,7 @@ impl Theme {
 				Style::default().fg(self.diff_file_moved)
 			}
 			StatusItemType::Conflicted => Style::default()
-				.fg(self.diff_file_modified)
+				.fg(self.diff_file_conflicted)
 				.add_modifier(Modifier::BOLD),
 			StatusItemType::Typechange => Style::default(),
 		};
@@ -203,6 +213,34 @@ impl Theme {
 		self.apply_select(style, selected)
 	}
 
+	/// changed words inside of an added or removed line
+	pub fn diff_word(
+		&self,
+		typ: DiffLineType,
+		selected: bool,
+	) -> Style {
+		let style = self.diff_line(typ, selected);
+
+		match typ {
+			DiffLineType::Add => style.bg(self.diff_word_add),
+			DiffLineType::Delete => style.bg(self.diff_word_delete),
+			DiffLineType::Header | DiffLineType::None => style,
+		}
+	}
+
+	/// occurrence of the diff search, the one jumped to stands out
+	pub fn diff_search_match(&self, current: bool) -> Style {
+		let style = Style::default()
+			.fg(Color::Black)
+			.bg(self.diff_search_match);
+
+		if current {
+			style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+		} else {
+			style
+		}
+	}
+
 	pub fn text_danger(&self) -> Style {
 		Style::default().fg(self.danger_fg)
 	}
@@ -305,8 +343,25 @@ impl Theme {
 		Ok(())
 	}
 
-	pub fn get_syntax(&self) -> String {
-		self.syntax.clone()
+	pub const fn glyphs(&self) -> &Glyphs {
+		&self.glyphs
+	}
+
+	#[must_use]
+	pub fn with_glyphs(mut self, glyphs: GlyphSet) -> Self {
+		self.glyphs = glyphs.glyphs();
+		// a marker set in the theme file is kept
+		if self.line_break == Self::default().line_break {
+			self.line_break = self.glyphs.line_break.to_string();
+		}
+		self
+	}
+
+	/// `None` if files are shown without syntax highlighting
+	pub fn get_syntax(&self) -> Option<String> {
+		(cfg!(feature = "syntax-highlighting")
+			&& self.syntax_highlighting)
+			.then(|| self.syntax.clone())
 	}
 
 	pub fn init(theme_path: &PathBuf) -> Self {
@@ -351,10 +406,14 @@ impl Default for Theme {
 			disabled_fg: Color::DarkGray,
 			diff_line_add: Color::Green,
 			diff_line_delete: Color::Red,
+			diff_word_add: Color::Indexed(22),
+			diff_word_delete: Color::Indexed(52),
+			diff_search_match: Color::Yellow,
 			diff_file_added: Color::LightGreen,
 			diff_file_removed: Color::LightRed,
 			diff_file_moved: Color::LightMagenta,
 			diff_file_modified: Color::Yellow,
+			diff_file_conflicted: Color::Red,
 			commit_hash: Color::Magenta,
 			commit_time: Color::LightCyan,
 			commit_author: Color::Green,
@@ -363,11 +422,16 @@ impl Default for Theme {
 			push_gauge_fg: Color::Reset,
 			tag_fg: Color::LightMagenta,
 			branch_fg: Color::LightYellow,
-			line_break: "¶".to_string(),
+			line_break: GlyphSet::default()
+				.glyphs()
+				.line_break
+				.to_string(),
 			block_title_focused: Color::Reset,
 			// Available themes can be found in:
 			// [ThemeSet::load_defaults function](https://github.com/trishume/syntect/blob/7fe13c0fd53cdfa0f9fea1aa14c5ba37f81d8b71/src/dumps.rs#L215).
 			syntax: DEFAULT_SYNTAX_THEME.to_string(),
+			syntax_highlighting: true,
+			glyphs: Glyphs::default(),
 		}
 	}
 }
@@ -2,19 +2,25 @@ use asyncgit::{
 	asyncjob::{AsyncJob, RunParams},
 	ProgressPercent,
 };
+#[cfg(feature = "syntax-highlighting")]
 use once_cell::sync::{Lazy, OnceCell};
-use ratatui::text::{Line, Span};
+use ratatui::{
+	style::Style,
+	text::{Line, Span},
+};
 use scopetime::scope_time;
+#[cfg(feature = "syntax-highlighting")]
+use std::time::{Duration, Instant};
 use std::{
 	ops::Range,
 	path::{Path, PathBuf},
 	sync::{Arc, Mutex},
-	time::{Duration, Instant},
 };
+#[cfg(feature = "syntax-highlighting")]
 use syntect::{
 	highlighting::{
 		FontStyle, HighlightState, Highlighter,
-		RangedHighlightIterator, Style, Theme, ThemeSet,
+		RangedHighlightIterator, Theme, ThemeSet,
 	},
 	parsing::{ParseState, ScopeStack, SyntaxSet},
 };
@@ -23,8 +29,21 @@ use crate::{AsyncAppNotification, SyntaxHighlightProgress};
 
 pub const DEFAULT_SYNTAX_THEME: &str = "base16-eighties.dark";
 
+/// longer lines are shown as plain text, highlighting them (minified
+/// code mostly) takes long and does not help reading them anyway
+#[cfg(feature = "syntax-highlighting")]
+const MAX_HIGHLIGHT_LINE_LENGTH: usize = 2000;
+
 struct SyntaxLine {
-	items: Vec<(Style, usize, Range<usize>)>,
+	items: Vec<(Style, Range<usize>)>,
+}
+
+impl SyntaxLine {
+	fn plain(line: &str) -> Self {
+		Self {
+			items: vec![(Style::default(), 0..line.len())],
+		}
+	}
 }
 
 pub struct SyntaxText {
@@ -33,10 +52,13 @@ pub struct SyntaxText {
 	path: PathBuf,
 }
 
+#[cfg(feature = "syntax-highlighting")]
 static SYNTAX_SET: Lazy<SyntaxSet> =
 	Lazy::new(two_face::syntax::extra_no_newlines);
+#[cfg(feature = "syntax-highlighting")]
 static THEME: OnceCell<Theme> = OnceCell::new();
 
+#[cfg(feature = "syntax-highlighting")]
 pub struct AsyncProgressBuffer {
 	current: usize,
 	total: usize,
@@ -44,6 +66,7 @@ pub struct AsyncProgressBuffer {
 	min_interval: Duration,
 }
 
+#[cfg(feature = "syntax-highlighting")]
 impl AsyncProgressBuffer {
 	pub const fn new(total: usize, min_interval: Duration) -> Self {
 		Self {
@@ -68,6 +91,8 @@ impl AsyncProgressBuffer {
 }
 
 impl SyntaxText {
+	/// binary content is not highlighted, neither are very long
+	/// lines
 	pub fn new(
 		text: String,
 		file_path: &Path,
@@ -75,94 +100,16 @@ impl SyntaxText {
 		syntax: &str,
 	) -> asyncgit::Result<Self> {
 		scope_time!("syntax_highlighting");
-		let mut state = {
-			scope_time!("syntax_highlighting.0");
-			let plain_text = || SYNTAX_SET.find_syntax_plain_text();
-			let syntax = SYNTAX_SET
-				.find_syntax_for_file(file_path)
-				.unwrap_or_else(|e| {
-					log::error!("Could not read the file to detect its syntax: {e}");
-					Some(plain_text())
-				})
-				.unwrap_or_else(plain_text);
-
-			ParseState::new(syntax)
-		};
-
-		let theme = THEME.get_or_try_init(|| -> Result<Theme, asyncgit::Error> {
-			let theme_path = crate::args::get_app_config_path()
-				.map_err(|e| asyncgit::Error::Generic(e.to_string()))?.join(format!("{syntax}.tmTheme"));
-
-			match ThemeSet::get_theme(&theme_path) {
-				Ok(t) => return Ok(t),
-			    Err(e) => log::info!("could not load '{}': {e}, trying from the set of default themes", theme_path.display()),
-			}
-
-			let mut theme_set = ThemeSet::load_defaults();
-			if let Some(t) = theme_set.themes.remove(syntax) {
-			    return Ok(t);
-			}
-
-			log::error!("the syntax theme '{syntax}' cannot be found. Using default theme ('{DEFAULT_SYNTAX_THEME}') instead");
-			Ok(theme_set.themes.remove(DEFAULT_SYNTAX_THEME).expect("the default theme should be there"))
-		})?;
-
-		let highlighter = Highlighter::new(theme);
-		let mut syntax_lines: Vec<SyntaxLine> = Vec::new();
 
-		let mut highlight_state =
-			HighlightState::new(&highlighter, ScopeStack::new());
-
-		{
-			let total_count = text.lines().count();
-
-			let mut buffer = AsyncProgressBuffer::new(
-				total_count,
-				Duration::from_millis(200),
-			);
-			params.set_progress(buffer.send_progress())?;
-			params.send(AsyncAppNotification::SyntaxHighlighting(
-				SyntaxHighlightProgress::Progress,
-			))?;
-
-			for (number, line) in text.lines().enumerate() {
-				let ops = state
-					.parse_line(line, &SYNTAX_SET)
-					.map_err(|e| {
-						log::error!("syntax error: {:?}", e);
-						asyncgit::Error::Generic(
-							"syntax error".to_string(),
-						)
-					})?;
-				let iter = RangedHighlightIterator::new(
-					&mut highlight_state,
-					&ops[..],
-					line,
-					&highlighter,
-				);
-
-				syntax_lines.push(SyntaxLine {
-					items: iter
-						.map(|(style, _, range)| {
-							(style, number, range)
-						})
-						.collect(),
-				});
-
-				if buffer.update(number) {
-					params.set_progress(buffer.send_progress())?;
-					params.send(
-						AsyncAppNotification::SyntaxHighlighting(
-							SyntaxHighlightProgress::Progress,
-						),
-					)?;
-				}
-			}
-		}
+		let lines = if is_binary(&text) {
+			text.lines().map(SyntaxLine::plain).collect()
+		} else {
+			highlight(&text, file_path, params, syntax)?
+		};
 
 		Ok(Self {
 			text,
-			lines: syntax_lines,
+			lines,
 			path: file_path.into(),
 		})
 	}
@@ -177,6 +124,121 @@ impl SyntaxText {
 	}
 }
 
+/// a nul byte is what git looks for as well
+fn is_binary(text: &str) -> bool {
+	text.contains('\0')
+}
+
+#[cfg(feature = "syntax-highlighting")]
+fn highlight(
+	text: &str,
+	file_path: &Path,
+	params: &RunParams<AsyncAppNotification, ProgressPercent>,
+	syntax: &str,
+) -> asyncgit::Result<Vec<SyntaxLine>> {
+	let mut state = {
+		scope_time!("syntax_highlighting.0");
+		let plain_text = || SYNTAX_SET.find_syntax_plain_text();
+		let syntax = SYNTAX_SET
+			.find_syntax_for_file(file_path)
+			.unwrap_or_else(|e| {
+				log::error!("Could not read the file to detect its syntax: {e}");
+				Some(plain_text())
+			})
+			.unwrap_or_else(plain_text);
+
+		ParseState::new(syntax)
+	};
+
+	let theme = THEME.get_or_try_init(|| -> Result<Theme, asyncgit::Error> {
+		let theme_path = crate::args::get_app_config_path()
+			.map_err(|e| asyncgit::Error::Generic(e.to_string()))?.join(format!("{syntax}.tmTheme"));
+
+		match ThemeSet::get_theme(&theme_path) {
+			Ok(t) => return Ok(t),
+		    Err(e) => log::info!("could not load '{}': {e}, trying from the set of default themes", theme_path.display()),
+		}
+
+		let mut theme_set = ThemeSet::load_defaults();
+		if let Some(t) = theme_set.themes.remove(syntax) {
+		    return Ok(t);
+		}
+
+		log::error!("the syntax theme '{syntax}' cannot be found. Using default theme ('{DEFAULT_SYNTAX_THEME}') instead");
+		Ok(theme_set.themes.remove(DEFAULT_SYNTAX_THEME).expect("the default theme should be there"))
+	})?;
+
+	let highlighter = Highlighter::new(theme);
+	let mut syntax_lines: Vec<SyntaxLine> = Vec::new();
+
+	let mut highlight_state =
+		HighlightState::new(&highlighter, ScopeStack::new());
+
+	{
+		let total_count = text.lines().count();
+
+		let mut buffer = AsyncProgressBuffer::new(
+			total_count,
+			Duration::from_millis(200),
+		);
+		params.set_progress(buffer.send_progress())?;
+		params.send(AsyncAppNotification::SyntaxHighlighting(
+			SyntaxHighlightProgress::Progress,
+		))?;
+
+		for (number, line) in text.lines().enumerate() {
+			if line.len() > MAX_HIGHLIGHT_LINE_LENGTH {
+				syntax_lines.push(SyntaxLine::plain(line));
+				continue;
+			}
+
+			let ops =
+				state.parse_line(line, &SYNTAX_SET).map_err(|e| {
+					log::error!("syntax error: {:?}", e);
+					asyncgit::Error::Generic(
+						"syntax error".to_string(),
+					)
+				})?;
+			let iter = RangedHighlightIterator::new(
+				&mut highlight_state,
+				&ops[..],
+				line,
+				&highlighter,
+			);
+
+			syntax_lines.push(SyntaxLine {
+				items: iter
+					.map(|(style, _, range)| {
+						(syntact_style_to_tui(&style), range)
+					})
+					.collect(),
+			});
+
+			if buffer.update(number) {
+				params.set_progress(buffer.send_progress())?;
+				params.send(
+					AsyncAppNotification::SyntaxHighlighting(
+						SyntaxHighlightProgress::Progress,
+					),
+				)?;
+			}
+		}
+	}
+
+	Ok(syntax_lines)
+}
+
+#[cfg(not(feature = "syntax-highlighting"))]
+#[allow(clippy::unnecessary_wraps)]
+fn highlight(
+	text: &str,
+	_file_path: &Path,
+	_params: &RunParams<AsyncAppNotification, ProgressPercent>,
+	_syntax: &str,
+) -> asyncgit::Result<Vec<SyntaxLine>> {
+	Ok(text.lines().map(SyntaxLine::plain).collect())
+}
+
 impl<'a> From<&'a SyntaxText> for ratatui::text::Text<'a> {
 	fn from(v: &'a SyntaxText) -> Self {
 		let mut result_lines: Vec<Line> =
@@ -188,13 +250,12 @@ impl<'a> From<&'a SyntaxText> for ratatui::text::Text<'a> {
 			let mut line_span: Line =
 				Vec::with_capacity(syntax_line.items.len()).into();
 
-			for (style, _, range) in &syntax_line.items {
+			for (style, range) in &syntax_line.items {
 				let item_content = &line_content[range.clone()];
-				let item_style = syntact_style_to_tui(style);
 
 				line_span
 					.spans
-					.push(Span::styled(item_content, item_style));
+					.push(Span::styled(item_content, *style));
 			}
 
 			result_lines.push(line_span);
@@ -204,14 +265,15 @@ impl<'a> From<&'a SyntaxText> for ratatui::text::Text<'a> {
 	}
 }
 
-fn syntact_style_to_tui(style: &Style) -> ratatui::style::Style {
-	let mut res = ratatui::style::Style::default().fg(
-		ratatui::style::Color::Rgb(
-			style.foreground.r,
-			style.foreground.g,
-			style.foreground.b,
-		),
-	);
+#[cfg(feature = "syntax-highlighting")]
+fn syntact_style_to_tui(
+	style: &syntect::highlighting::Style,
+) -> Style {
+	let mut res = Style::default().fg(ratatui::style::Color::Rgb(
+		style.foreground.r,
+		style.foreground.g,
+		style.foreground.b,
+	));
 
 	if style.font_style.contains(FontStyle::BOLD) {
 		res = res.add_modifier(ratatui::style::Modifier::BOLD);
@@ -296,3 +358,59 @@ impl AsyncJob for AsyncSyntaxJob {
 		))
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use asyncgit::asyncjob::AsyncSingleJob;
+	use crossbeam_channel::unbounded;
+
+	fn syntax_text(text: &str, path: &str) -> SyntaxText {
+		let (tx, rx) = unbounded();
+		let job = AsyncSingleJob::new(tx);
+
+		job.spawn(AsyncSyntaxJob::new(
+			text.to_string(),
+			path.to_string(),
+			DEFAULT_SYNTAX_THEME.to_string(),
+		));
+
+		while !matches!(
+			rx.recv().unwrap(),
+			AsyncAppNotification::SyntaxHighlighting(
+				SyntaxHighlightProgress::Done
+			)
+		) {}
+
+		job.take_last().unwrap().result().unwrap()
+	}
+
+	fn is_plain(line: &SyntaxLine, content: &str) -> bool {
+		line.items == vec![(Style::default(), 0..content.len())]
+	}
+
+	#[test]
+	fn test_binary_is_plain() {
+		let text = "fn main() {\0}\nlet a = 1;";
+		let syntax = syntax_text(text, "main.rs");
+
+		assert_eq!(syntax.lines.len(), 2);
+		for (line, content) in syntax.lines.iter().zip(text.lines()) {
+			assert!(is_plain(line, content));
+		}
+	}
+
+	#[cfg(feature = "syntax-highlighting")]
+	#[test]
+	fn test_long_lines_are_plain() {
+		let long = "let a = 1;".repeat(MAX_HIGHLIGHT_LINE_LENGTH);
+		let text = format!("fn main() {{}}\n{long}\nlet b = 2;");
+		let syntax = syntax_text(&text, "main.rs");
+
+		let lines: Vec<_> = text.lines().collect();
+		assert_eq!(syntax.lines.len(), 3);
+		assert!(!is_plain(&syntax.lines[0], lines[0]));
+		assert!(is_plain(&syntax.lines[1], lines[1]));
+		assert!(!is_plain(&syntax.lines[2], lines[2]));
+	}
+}
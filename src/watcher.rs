@@ -1,16 +1,73 @@
 use anyhow::Result;
+use asyncgit::sync::RepoWatchPaths;
 use crossbeam_channel::{unbounded, Sender};
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
-use notify_debouncer_mini::{new_debouncer, DebounceEventResult};
+use notify_debouncer_mini::{
+	new_debouncer, DebounceEventResult, DebouncedEvent,
+};
 use scopetime::scope_time;
-use std::{path::Path, thread, time::Duration};
+use std::{
+	path::{Path, PathBuf},
+	thread,
+	time::Duration,
+};
+
+/// files and folders in the git dir of a worktree that exist while
+/// a merge, rebase, cherry-pick or revert is in progress, the bisect
+/// log grows with each bisect step
+const SEQUENCER_PATHS: [&str; 7] = [
+	"MERGE_HEAD",
+	"CHERRY_PICK_HEAD",
+	"REVERT_HEAD",
+	"rebase-merge",
+	"rebase-apply",
+	"sequencer",
+	"BISECT_LOG",
+];
+
+/// what a batch of file system events touched
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RepoChange {
+	/// only files inside the git dirs (index, refs, ..)
+	GitDir,
+	/// something in the working dir
+	WorkDir,
+	/// a merge, rebase, .. started, stopped or moved on, no matter
+	/// if gitui or git itself runs it
+	RepoState,
+}
+
+impl RepoChange {
+	fn of_events(
+		events: &[DebouncedEvent],
+		paths: &RepoWatchPaths,
+	) -> Self {
+		let sequencer = |path: &Path| {
+			SEQUENCER_PATHS.iter().any(|name| {
+				path.starts_with(paths.git_dir.join(name))
+			})
+		};
+		let git_dir = |path: &Path| {
+			path.starts_with(&paths.git_dir)
+				|| path.starts_with(&paths.common_dir)
+		};
+
+		if events.iter().any(|ev| sequencer(&ev.path)) {
+			Self::RepoState
+		} else if events.iter().all(|ev| git_dir(&ev.path)) {
+			Self::GitDir
+		} else {
+			Self::WorkDir
+		}
+	}
+}
 
 pub struct RepoWatcher {
-	receiver: crossbeam_channel::Receiver<()>,
+	receiver: crossbeam_channel::Receiver<RepoChange>,
 }
 
 impl RepoWatcher {
-	pub fn new(workdir: &str) -> Self {
+	pub fn new(paths: &RepoWatchPaths) -> Self {
 		log::trace!(
 			"recommended watcher: {:?}",
 			RecommendedWatcher::kind()
@@ -18,17 +75,18 @@ impl RepoWatcher {
 
 		let (tx, rx) = std::sync::mpsc::channel();
 
-		let workdir = workdir.to_string();
+		let targets = watch_targets(paths);
+		let paths = paths.clone();
 
 		thread::spawn(move || {
 			let timeout = Duration::from_secs(2);
-			create_watcher(timeout, tx, &workdir);
+			create_watcher(timeout, tx, &targets);
 		});
 
 		let (out_tx, out_rx) = unbounded();
 
 		thread::spawn(move || {
-			if let Err(e) = Self::forwarder(&rx, &out_tx) {
+			if let Err(e) = Self::forwarder(&rx, &out_tx, &paths) {
 				//maybe we need to restart the forwarder now?
 				log::error!("notify receive error: {}", e);
 			}
@@ -38,13 +96,16 @@ impl RepoWatcher {
 	}
 
 	///
-	pub fn receiver(&self) -> crossbeam_channel::Receiver<()> {
+	pub fn receiver(
+		&self,
+	) -> crossbeam_channel::Receiver<RepoChange> {
 		self.receiver.clone()
 	}
 
 	fn forwarder(
 		receiver: &std::sync::mpsc::Receiver<DebounceEventResult>,
-		sender: &Sender<()>,
+		sender: &Sender<RepoChange>,
+		paths: &RepoWatchPaths,
 	) -> Result<()> {
 		loop {
 			let ev = receiver.recv()?;
@@ -57,26 +118,213 @@ impl RepoWatcher {
 				}
 
 				if !ev.is_empty() {
-					sender.send(())?;
+					sender.send(RepoChange::of_events(&ev, paths))?;
 				}
 			}
 		}
 	}
 }
 
+/// the working tree and the parts of the git dirs outside of it,
+/// which is where the git dir of a linked worktree (`HEAD`, `index`,
+/// sequencer state) and the common dir of all worktrees (refs) are
+fn watch_targets(
+	paths: &RepoWatchPaths,
+) -> Vec<(PathBuf, RecursiveMode)> {
+	let mut targets =
+		vec![(paths.work_dir.clone(), RecursiveMode::Recursive)];
+
+	let outside = |dir: &PathBuf| !dir.starts_with(&paths.work_dir);
+
+	if paths.common_dir == paths.git_dir {
+		if outside(&paths.git_dir) {
+			// objects are of no interest, the sequencer folders are
+			// seen being created and removed
+			targets.push((
+				paths.git_dir.clone(),
+				RecursiveMode::NonRecursive,
+			));
+			targets.push((
+				paths.git_dir.join("refs"),
+				RecursiveMode::Recursive,
+			));
+		}
+	} else {
+		// the git dir of a linked worktree has no objects
+		if outside(&paths.git_dir) {
+			targets.push((
+				paths.git_dir.clone(),
+				RecursiveMode::Recursive,
+			));
+		}
+		if outside(&paths.common_dir) {
+			targets.push((
+				paths.common_dir.clone(),
+				RecursiveMode::NonRecursive,
+			));
+			targets.push((
+				paths.common_dir.join("refs"),
+				RecursiveMode::Recursive,
+			));
+		}
+	}
+
+	targets
+}
+
 fn create_watcher(
 	timeout: Duration,
 	tx: std::sync::mpsc::Sender<DebounceEventResult>,
-	workdir: &str,
+	targets: &[(PathBuf, RecursiveMode)],
 ) {
 	scope_time!("create_watcher");
 
 	let mut bouncer =
 		new_debouncer(timeout, tx).expect("Watch create error");
-	bouncer
-		.watcher()
-		.watch(Path::new(&workdir), RecursiveMode::Recursive)
-		.expect("Watch error");
+
+	for (idx, (path, mode)) in targets.iter().enumerate() {
+		let watched = bouncer.watcher().watch(path, *mode);
+
+		if idx == 0 {
+			watched.expect("Watch error");
+		} else if let Err(e) = watched {
+			log::warn!("not watching {}: {}", path.display(), e);
+		}
+	}
 
 	std::mem::forget(bouncer);
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use notify_debouncer_mini::DebouncedEventKind;
+
+	fn events(paths: &[&str]) -> Vec<DebouncedEvent> {
+		paths
+			.iter()
+			.map(|path| {
+				DebouncedEvent::new(
+					PathBuf::from(path),
+					DebouncedEventKind::Any,
+				)
+			})
+			.collect()
+	}
+
+	#[test]
+	fn test_repo_change() {
+		let git_dir = &RepoWatchPaths {
+			work_dir: PathBuf::from("/repo"),
+			git_dir: PathBuf::from("/repo/.git"),
+			common_dir: PathBuf::from("/repo/.git"),
+		};
+
+		assert_eq!(
+			RepoChange::of_events(
+				&events(&["/repo/.git/index", "/repo/.git/HEAD"]),
+				git_dir
+			),
+			RepoChange::GitDir
+		);
+		assert_eq!(
+			RepoChange::of_events(
+				&events(&[
+					"/repo/.git/index",
+					"/repo/.git/MERGE_HEAD"
+				]),
+				git_dir
+			),
+			RepoChange::RepoState
+		);
+		assert_eq!(
+			RepoChange::of_events(
+				&events(&["/repo/.git/index", "/repo/src/main.rs"]),
+				git_dir
+			),
+			RepoChange::WorkDir
+		);
+		assert_eq!(
+			RepoChange::of_events(
+				&events(&["/repo/.gitignore"]),
+				git_dir
+			),
+			RepoChange::WorkDir
+		);
+	}
+
+	#[test]
+	fn test_linked_worktree() {
+		let paths = RepoWatchPaths {
+			work_dir: PathBuf::from("/wt"),
+			git_dir: PathBuf::from("/repo/.git/worktrees/wt"),
+			common_dir: PathBuf::from("/repo/.git"),
+		};
+
+		assert_eq!(
+			watch_targets(&paths),
+			vec![
+				(PathBuf::from("/wt"), RecursiveMode::Recursive),
+				(
+					PathBuf::from("/repo/.git/worktrees/wt"),
+					RecursiveMode::Recursive
+				),
+				(
+					PathBuf::from("/repo/.git"),
+					RecursiveMode::NonRecursive
+				),
+				(
+					PathBuf::from("/repo/.git/refs"),
+					RecursiveMode::Recursive
+				),
+			]
+		);
+
+		assert_eq!(
+			RepoChange::of_events(
+				&events(&[
+					"/repo/.git/worktrees/wt/index",
+					"/repo/.git/refs/heads/main",
+				]),
+				&paths
+			),
+			RepoChange::GitDir
+		);
+		assert_eq!(
+			RepoChange::of_events(&events(&["/wt/a"]), &paths),
+			RepoChange::WorkDir
+		);
+		assert_eq!(
+			RepoChange::of_events(
+				&events(&[
+					"/wt/a",
+					"/repo/.git/worktrees/wt/rebase-merge/done"
+				]),
+				&paths
+			),
+			RepoChange::RepoState
+		);
+		// the main worktree's merge is none of its business
+		assert_eq!(
+			RepoChange::of_events(
+				&events(&["/repo/.git/MERGE_HEAD"]),
+				&paths
+			),
+			RepoChange::GitDir
+		);
+	}
+
+	#[test]
+	fn test_main_worktree_targets() {
+		let paths = RepoWatchPaths {
+			work_dir: PathBuf::from("/repo"),
+			git_dir: PathBuf::from("/repo/.git"),
+			common_dir: PathBuf::from("/repo/.git"),
+		};
+
+		assert_eq!(
+			watch_targets(&paths),
+			vec![(PathBuf::from("/repo"), RecursiveMode::Recursive)]
+		);
+	}
+}